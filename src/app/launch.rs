@@ -20,7 +20,8 @@ pub fn launch_editor_with_default_layout(
         metadata: &layout.metadata,
     };
 
-    let geo_result = services::geometry::build_geometry_for_layout(geo_context, layout_variant)?;
+    let geo_result =
+        services::geometry_cache::build_geometry_for_layout_cached(geo_context, layout_variant)?;
     let geometry = geo_result.geometry;
     let mapping = geo_result.mapping;
 
@@ -51,6 +52,7 @@ pub fn launch_editor_with_default_layout(
     let layout_path = layouts_dir.join(format!("{}.md", sanitized_name));
 
     // Save the layout immediately so it can be found on restart
+    layout.record_geometry(&geometry);
     services::LayoutService::save(&layout, &layout_path)?;
 
     println!("Layout saved to: {}", layout_path.display());
@@ -67,6 +69,9 @@ pub fn launch_editor_with_default_layout(
     // Run main TUI loop
     let result = tui::run_tui(&mut app_state, &mut terminal);
 
+    // Stop builds still running in the background
+    services::process_tree::kill_all();
+
     // Restore terminal
     tui::restore_terminal(terminal)?;
 
@@ -53,16 +53,21 @@ pub fn run_layout_picker_terminal(config: &config::Config) -> Result<()> {
                                     )
                                 })?;
 
-                            // Build geometry using the centralized geometry service
+                            // Build geometry using the centralized geometry service,
+                            // falling back to the geometry stored with the layout
                             let geo_context = services::geometry::GeometryContext {
                                 config,
                                 metadata: &layout.metadata,
                             };
 
-                            let geo_result = services::geometry::build_geometry_for_layout(
-                                geo_context,
-                                layout_variant,
-                            )?;
+                            let geo_result =
+                                services::geometry_cache::build_geometry_for_layout_cached(
+                                    geo_context,
+                                    layout_variant,
+                                )
+                                .or_else(|e| {
+                                    services::geometry::stored_geometry(&layout, &path).ok_or(e)
+                                })?;
                             let geometry = geo_result.geometry;
                             let mapping = geo_result.mapping;
 
@@ -82,6 +87,9 @@ pub fn run_layout_picker_terminal(config: &config::Config) -> Result<()> {
                             // Run main TUI loop
                             let result = tui::run_tui(&mut app_state, &mut terminal);
 
+                            // Stop builds still running in the background
+                            services::process_tree::kill_all();
+
                             // Restore terminal
                             tui::restore_terminal(terminal)?;
 
@@ -19,6 +19,7 @@ pub fn run_onboarding_wizard_terminal() -> Result<()> {
         // Re-detect OS theme on each loop iteration to respond to system theme changes
         let theme = tui::Theme::detect();
 
+        wizard_state.poll_setup();
         terminal.draw(|f| {
             tui::onboarding_wizard::render(f, &wizard_state, &theme);
         })?;
@@ -109,6 +110,7 @@ pub fn run_new_layout_wizard_terminal(config: &config::Config) -> Result<()> {
         // Re-detect OS theme on each loop iteration to respond to system theme changes
         let theme = tui::Theme::detect();
 
+        wizard_state.poll_setup();
         terminal.draw(|f| {
             tui::onboarding_wizard::render(f, &wizard_state, &theme);
         })?;
@@ -0,0 +1,238 @@
+//! Analyze command: score the base layer of layouts against a text corpus.
+
+use crate::cli::common::{print_json, CliError, CliResult};
+use crate::config::Config;
+use crate::models::{Finger, Hand};
+use crate::services::analysis::{self, EffortScore};
+use crate::services::geometry;
+use crate::services::thumb_optimizer::CorpusStats;
+use crate::services::LayoutService;
+use clap::Args;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Score finger travel and effort of a layout for a text corpus
+#[derive(Debug, Clone, Args)]
+pub struct AnalyzeArgs {
+    /// Path to layout file
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Corpus to score: plain text, or JSON statistics
+    /// (`{"chars": {"a": 10}, "bigrams": {"th": 5}}`) when the file ends in `.json`
+    #[arg(short, long, value_name = "FILE")]
+    pub corpus: PathBuf,
+
+    /// Further layout files to score side by side (repeatable)
+    #[arg(long, value_name = "FILE")]
+    pub compare: Vec<PathBuf>,
+
+    /// Path to QMK firmware repository (defaults to the configured path)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Score of one layout for JSON output
+#[derive(Debug, Serialize)]
+struct LayoutScoreData {
+    layout: String,
+    name: String,
+    effort_per_char: f64,
+    distance_per_char: f64,
+    #[serde(flatten)]
+    score: EffortScore,
+}
+
+/// JSON response for the analyze command
+#[derive(Debug, Serialize)]
+struct AnalyzeResponse {
+    corpus: String,
+    layouts: Vec<LayoutScoreData>,
+}
+
+impl AnalyzeArgs {
+    /// Execute the analyze command
+    pub fn execute(&self) -> CliResult<()> {
+        let corpus = load_corpus(&self.corpus)?;
+
+        let mut config = Config::load().unwrap_or_default();
+        if let Some(qmk_path) = &self.qmk_path {
+            config.pin_qmk_path(qmk_path.clone());
+        }
+
+        let mut scores = Vec::new();
+        for path in std::iter::once(&self.layout).chain(&self.compare) {
+            let (name, score) = score_layout(&config, path, &corpus)?;
+            scores.push((path, name, score));
+        }
+
+        if self.json {
+            let response = AnalyzeResponse {
+                corpus: self.corpus.display().to_string(),
+                layouts: scores
+                    .into_iter()
+                    .map(|(path, name, score)| LayoutScoreData {
+                        layout: path.display().to_string(),
+                        name,
+                        effort_per_char: score.effort_per_char(),
+                        distance_per_char: score.distance_per_char(),
+                        score,
+                    })
+                    .collect(),
+            };
+            print_json(&response)?;
+            return Ok(());
+        }
+
+        let total = scores.first().map_or(0, |(_, _, score)| score.total);
+        say!(
+            "Corpus: {} ({total} characters), base layer, lower effort is better",
+            self.corpus.display()
+        );
+        say!();
+
+        let mut rows: Vec<(String, Vec<String>)> = vec![
+            (
+                "Layout".to_string(),
+                scores.iter().map(|(_, name, _)| name.clone()).collect(),
+            ),
+            row(&scores, "Effort per char", |score| {
+                format!("{:.2}", score.effort_per_char())
+            }),
+            row(&scores, "Travel per char", |score| {
+                format!("{:.2}", score.distance_per_char())
+            }),
+            row(&scores, "Typed", |score| percent(score.typed, score.total)),
+            row(&scores, "Home keys", |score| {
+                percent(score.home_keys, score.typed)
+            }),
+            row(&scores, "Same-finger bigrams", |score| {
+                percent(score.same_finger_bigrams, score.bigrams)
+            }),
+            row(&scores, "Hand alternation", |score| {
+                percent(score.hand_alternations, score.bigrams)
+            }),
+        ];
+        for hand in [Hand::Left, Hand::Right] {
+            for finger in [
+                Finger::Thumb,
+                Finger::Index,
+                Finger::Middle,
+                Finger::Ring,
+                Finger::Pinky,
+            ] {
+                rows.push(row(&scores, &finger_label(hand, finger), |score| {
+                    let presses = score
+                        .fingers
+                        .iter()
+                        .find(|load| load.hand == hand && load.finger == finger)
+                        .map_or(0, |load| load.presses);
+                    percent(presses, score.typed)
+                }));
+            }
+        }
+
+        let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        let column_widths: Vec<usize> = (0..scores.len())
+            .map(|idx| {
+                rows.iter()
+                    .map(|(_, cells)| cells[idx].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        for (label, cells) in &rows {
+            let cells: Vec<String> = cells
+                .iter()
+                .zip(&column_widths)
+                .map(|(cell, width)| format!("{cell:>width$}"))
+                .collect();
+            say!("{label:<label_width$}  {}", cells.join("  "));
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads a corpus as text, or as JSON statistics for `.json` files.
+fn load_corpus(path: &Path) -> CliResult<CorpusStats> {
+    let corpus = fs::read_to_string(path)
+        .map_err(|e| CliError::io(format!("Failed to read corpus {}: {e}", path.display())))?;
+    if path.extension().is_some_and(|ext| ext == "json") {
+        CorpusStats::from_json(&corpus).map_err(|e| CliError::validation(format!("{e:#}")))
+    } else {
+        Ok(CorpusStats::from_text(&corpus))
+    }
+}
+
+/// Loads a layout with its keyboard geometry and scores its base layer.
+fn score_layout(
+    config: &Config,
+    path: &Path,
+    corpus: &CorpusStats,
+) -> CliResult<(String, EffortScore)> {
+    let layout = LayoutService::load(path)
+        .map_err(|e| CliError::io(format!("Failed to load layout {}: {e}", path.display())))?;
+    let layout_variant = layout.metadata.layout_variant.clone().ok_or_else(|| {
+        CliError::validation(format!(
+            "Layout variant not specified in metadata of {}",
+            path.display()
+        ))
+    })?;
+    let geo_context = geometry::GeometryContext {
+        config,
+        metadata: &layout.metadata,
+    };
+    let geometry = geometry::build_geometry_for_layout(geo_context, &layout_variant)
+        .map_err(|e| CliError::io(format!("Failed to build geometry: {e}")))?
+        .geometry;
+
+    let name = if layout.metadata.name.trim().is_empty() {
+        path.file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned())
+    } else {
+        layout.metadata.name.clone()
+    };
+    Ok((name, analysis::score_effort(&layout, &geometry, corpus)))
+}
+
+/// Builds one table row with a cell per scored layout.
+fn row(
+    scores: &[(&PathBuf, String, EffortScore)],
+    label: &str,
+    cell: impl Fn(&EffortScore) -> String,
+) -> (String, Vec<String>) {
+    (
+        label.to_string(),
+        scores.iter().map(|(_, _, score)| cell(score)).collect(),
+    )
+}
+
+/// Row label of a finger, e.g. "Left index".
+fn finger_label(hand: Hand, finger: Finger) -> String {
+    let hand = match hand {
+        Hand::Left => "Left",
+        Hand::Right => "Right",
+    };
+    let finger = match finger {
+        Finger::Thumb => "thumb",
+        Finger::Index => "index",
+        Finger::Middle => "middle",
+        Finger::Ring => "ring",
+        Finger::Pinky => "pinky",
+    };
+    format!("{hand} {finger}")
+}
+
+/// Formats `part` as a percentage of `total` with one decimal.
+fn percent(part: u64, total: u64) -> String {
+    if total == 0 {
+        return "0.0%".to_string();
+    }
+    format!("{:.1}%", part as f64 * 100.0 / total as f64)
+}
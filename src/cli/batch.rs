@@ -0,0 +1,410 @@
+//! Batch command: generate firmware and export documentation for every
+//! layout in a directory.
+
+use crate::cli::common::{find_layout_files, print_json, resolve_workspace, CliError, CliResult};
+use crate::cli::generate::write_firmware_files;
+use crate::config::Config;
+use crate::export;
+use crate::firmware::generator::FirmwareGenerator;
+use crate::firmware::validator::FirmwareValidator;
+use crate::keycode_db::KeycodeDb;
+use crate::models::{KeyboardGeometry, Layout};
+use crate::parser::keyboard_json::resolve_info_json;
+use crate::services::geometry::{self, GeometryContext, GeometryResult};
+use crate::services::LayoutService;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Documentation formats produced by `--export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// SVG diagram of every layer
+    Svg,
+    /// Markdown documentation (same as `lazyqmk export`)
+    Md,
+    /// keymap-drawer YAML, rendered with `keymap draw`
+    KeymapDrawer,
+    /// VIA saved layout (JSON)
+    Via,
+    /// QMK keymap JSON, built with `qmk compile keymap.json`
+    QmkJson,
+    /// ZMK devicetree keymap
+    Zmk,
+}
+
+impl ExportFormat {
+    /// Short name used in reports.
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Md => "md",
+            Self::KeymapDrawer => "keymap-drawer",
+            Self::Via => "via",
+            Self::QmkJson => "qmk-json",
+            Self::Zmk => "zmk",
+        }
+    }
+
+    /// Extension of the exported file.
+    pub(crate) const fn extension(self) -> &'static str {
+        match self {
+            Self::KeymapDrawer => "yaml",
+            Self::Via => "json",
+            Self::QmkJson => "keymap.json",
+            Self::Zmk => "keymap",
+            other => other.name(),
+        }
+    }
+
+    /// Exports `layout` in this format.
+    ///
+    /// `config` locates the QMK keyboard definition, which VIA needs for the
+    /// keyboard's USB IDs.
+    pub(crate) fn render(
+        self,
+        layout: &Layout,
+        geometry: &KeyboardGeometry,
+        config: &Config,
+        keycode_db: &KeycodeDb,
+    ) -> anyhow::Result<String> {
+        match self {
+            Self::Svg => export::export_to_svg(layout, geometry),
+            Self::Md => export::export_to_markdown(layout, geometry, keycode_db),
+            Self::KeymapDrawer => export::export_to_keymap_drawer(layout, geometry, keycode_db),
+            Self::Via => {
+                let vendor_product_id = config
+                    .qmk_tree_path(layout.metadata.qmk_tree.as_deref())
+                    .zip(layout.metadata.keyboard.as_deref())
+                    .and_then(|(qmk_path, keyboard)| {
+                        resolve_info_json(qmk_path, &config.paths.keyboard_roots, keyboard).ok()
+                    })
+                    .and_then(|resolved| export::via::vendor_product_id(&resolved.info));
+                export::export_to_via(layout, geometry, keycode_db, vendor_product_id)
+            }
+            Self::QmkJson => export::export_to_qmk_json(layout, geometry, keycode_db),
+            Self::Zmk => export::export_to_zmk(layout, geometry, keycode_db),
+        }
+    }
+}
+
+/// Generate firmware and/or export every layout in a directory
+#[derive(Debug, Clone, Args)]
+pub struct BatchArgs {
+    /// Directory containing layout files (defaults to the layouts directory)
+    #[arg(short, long, value_name = "DIR")]
+    pub workspace: Option<PathBuf>,
+
+    /// Output directory; each layout gets a subdirectory named after its file
+    #[arg(short, long, value_name = "DIR")]
+    pub out_dir: PathBuf,
+
+    /// Path to QMK firmware repository (defaults to the configured path)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+
+    /// Generate firmware files (keymap.c, config.h, ...) for each layout
+    #[arg(long)]
+    pub generate: bool,
+
+    /// Export documentation formats, comma-separated (e.g. `svg,md,keymap-drawer`)
+    #[arg(long, value_name = "FORMATS", value_delimiter = ',')]
+    pub export: Vec<ExportFormat>,
+
+    /// Output results as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Outcome of one task for one layout.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchTask {
+    /// "generate" or an export format name (e.g. "svg")
+    pub task: String,
+    /// Whether the task succeeded
+    pub ok: bool,
+    /// Files written, relative to the layout's output directory
+    pub files: Vec<String>,
+    /// Why the task failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Results for one layout file.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchLayout {
+    /// Layout file name within the workspace
+    pub file: String,
+    /// Directory the layout's outputs were written to
+    pub output_dir: String,
+    /// Per-task results, in the order they ran
+    pub tasks: Vec<BatchTask>,
+}
+
+impl BatchLayout {
+    fn ok(&self) -> bool {
+        self.tasks.iter().all(|task| task.ok)
+    }
+}
+
+/// JSON response for the batch command.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchResponse {
+    /// Per-layout results, sorted by file name
+    pub layouts: Vec<BatchLayout>,
+    /// Number of layouts where every task succeeded
+    pub succeeded: usize,
+    /// Number of layouts with at least one failed task
+    pub failed: usize,
+}
+
+impl BatchArgs {
+    /// Execute the batch command
+    pub fn execute(&self) -> CliResult<()> {
+        if !self.generate && self.export.is_empty() {
+            return Err(CliError::validation(
+                "Nothing to do. Pass --generate and/or --export svg,md",
+            ));
+        }
+
+        let mut config = Config::load().unwrap_or_default();
+        if let Some(qmk_path) = &self.qmk_path {
+            config.pin_qmk_path(qmk_path.clone());
+        }
+
+        let workspace = resolve_workspace(self.workspace.as_deref())?;
+        let files = find_layout_files(&workspace)?;
+
+        let keycode_db = KeycodeDb::load()
+            .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
+
+        let layouts: Vec<BatchLayout> = files
+            .iter()
+            .map(|path| self.process_layout(path, &config, &keycode_db))
+            .collect();
+        let succeeded = layouts.iter().filter(|layout| layout.ok()).count();
+        let failed = layouts.len() - succeeded;
+
+        let response = BatchResponse {
+            layouts,
+            succeeded,
+            failed,
+        };
+
+        if self.json {
+            print_json(&response)?;
+        } else {
+            self.print_summary(&response, &workspace);
+        }
+
+        if failed > 0 {
+            return Err(CliError::validation(format!(
+                "{failed} of {} layout(s) failed",
+                response.layouts.len()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Names of the tasks requested, in the order they run.
+    fn task_names(&self) -> Vec<&'static str> {
+        let mut names = Vec::new();
+        if self.generate {
+            names.push("generate");
+        }
+        names.extend(self.export.iter().map(|format| format.name()));
+        names
+    }
+
+    /// Runs every requested task for one layout.
+    ///
+    /// A layout that cannot be loaded, or has no geometry, fails all tasks.
+    fn process_layout(&self, path: &Path, config: &Config, keycode_db: &KeycodeDb) -> BatchLayout {
+        let file = path
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+        let stem = path
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+        let out_dir = self.out_dir.join(&stem);
+
+        let prepared = LayoutService::load(path)
+            .map_err(|e| format!("Failed to load layout: {e}"))
+            .and_then(|layout| {
+                let geo = build_geometry(&layout, config)?;
+                Ok((layout, geo))
+            })
+            .and_then(|prepared| {
+                fs::create_dir_all(&out_dir)
+                    .map_err(|e| format!("Failed to create output directory: {e}"))?;
+                Ok(prepared)
+            });
+
+        let tasks = match prepared {
+            Ok((layout, geo)) => {
+                let mut tasks = Vec::new();
+                if self.generate {
+                    tasks.push(task_result(
+                        "generate",
+                        generate(&layout, &geo, config, keycode_db, &out_dir),
+                    ));
+                }
+                for format in &self.export {
+                    tasks.push(task_result(
+                        format.name(),
+                        export_layout(&layout, &geo, config, keycode_db, *format, &out_dir, &stem),
+                    ));
+                }
+                tasks
+            }
+            Err(error) => self
+                .task_names()
+                .into_iter()
+                .map(|task| task_result(task, Err(error.clone())))
+                .collect(),
+        };
+
+        BatchLayout {
+            file,
+            output_dir: out_dir.display().to_string(),
+            tasks,
+        }
+    }
+
+    /// Prints the summary table and any failure reasons.
+    fn print_summary(&self, response: &BatchResponse, workspace: &Path) {
+        if response.layouts.is_empty() {
+            say!("No layouts found in {}", workspace.display());
+            return;
+        }
+
+        let tasks = self.task_names();
+        let file_width = response
+            .layouts
+            .iter()
+            .map(|layout| layout.file.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Layout".len());
+
+        let mut header = format!("{:<file_width$}", "Layout");
+        for task in &tasks {
+            let _ = write!(header, "  {task:<8}");
+        }
+        say!("{}", header.trim_end());
+        say!("{}", "-".repeat(header.trim_end().chars().count()));
+
+        for layout in &response.layouts {
+            let mut row = format!("{:<file_width$}", layout.file);
+            for task in &layout.tasks {
+                let mark = if task.ok { "✓" } else { "✗" };
+                let _ = write!(row, "  {mark:<8}");
+            }
+            say!("{}", row.trim_end());
+        }
+
+        let failures: Vec<_> = response
+            .layouts
+            .iter()
+            .flat_map(|layout| {
+                layout.tasks.iter().filter_map(move |task| {
+                    task.error
+                        .as_ref()
+                        .map(|error| (&layout.file, &task.task, error))
+                })
+            })
+            .collect();
+        if !failures.is_empty() {
+            say!("\nFailures:");
+            for (file, task, error) in failures {
+                say!("  ✗ {file} [{task}] {error}");
+            }
+        }
+
+        say!(
+            "\n{} succeeded, {} failed. Output: {}",
+            response.succeeded,
+            response.failed,
+            self.out_dir.display()
+        );
+    }
+}
+
+/// Wraps a task outcome in a report entry.
+fn task_result(task: &str, outcome: Result<Vec<String>, String>) -> BatchTask {
+    match outcome {
+        Ok(files) => BatchTask {
+            task: task.to_string(),
+            ok: true,
+            files,
+            error: None,
+        },
+        Err(error) => BatchTask {
+            task: task.to_string(),
+            ok: false,
+            files: Vec::new(),
+            error: Some(error),
+        },
+    }
+}
+
+/// Builds geometry for `layout` from its keyboard definition.
+fn build_geometry(layout: &Layout, config: &Config) -> Result<GeometryResult, String> {
+    let variant = layout
+        .metadata
+        .layout_variant
+        .as_deref()
+        .ok_or("Layout variant not specified in metadata")?;
+    let context = GeometryContext {
+        config,
+        metadata: &layout.metadata,
+    };
+    geometry::build_geometry_for_layout(context, variant)
+        .map_err(|e| format!("Failed to build geometry: {e:#}"))
+}
+
+/// Validates and generates firmware files into `out_dir`.
+fn generate(
+    layout: &Layout,
+    geo: &GeometryResult,
+    config: &Config,
+    keycode_db: &KeycodeDb,
+    out_dir: &Path,
+) -> Result<Vec<String>, String> {
+    let report = FirmwareValidator::new(layout, &geo.geometry, &geo.mapping, keycode_db)
+        .validate()
+        .map_err(|e| format!("Validation failed: {e}"))?;
+    if !report.is_valid() {
+        return Err(format!(
+            "Layout validation failed: {}",
+            report.format_message().replace('\n', " ")
+        ));
+    }
+
+    let generator = FirmwareGenerator::new(layout, &geo.geometry, &geo.mapping, config, keycode_db);
+    write_firmware_files(&generator, out_dir)
+        .map(|files| files.into_iter().map(str::to_string).collect())
+        .map_err(|e| e.message)
+}
+
+/// Exports `layout` in `format` to `<out_dir>/<stem>.<ext>`.
+fn export_layout(
+    layout: &Layout,
+    geo: &GeometryResult,
+    config: &Config,
+    keycode_db: &KeycodeDb,
+    format: ExportFormat,
+    out_dir: &Path,
+    stem: &str,
+) -> Result<Vec<String>, String> {
+    let content = format
+        .render(layout, &geo.geometry, config, keycode_db)
+        .map_err(|e| format!("Failed to export {}: {e}", format.name()))?;
+
+    let name = format!("{stem}.{}", format.extension());
+    fs::write(out_dir.join(&name), content).map_err(|e| format!("Failed to write {name}: {e}"))?;
+    Ok(vec![name])
+}
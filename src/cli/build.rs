@@ -0,0 +1,211 @@
+//! Build command: generate firmware files and compile them with QMK.
+
+use crate::cli::common::{print_json, CliError, CliResult, LayoutFileArg};
+use crate::config::Config;
+use crate::firmware::builder::{compile_args, enhance_qmk_error, find_firmware_file};
+use crate::firmware::generator::FirmwareGenerator;
+use crate::firmware::validator::FirmwareValidator;
+use crate::keycode_db::KeycodeDb;
+use crate::parser::keyboard_json::keyboards_dir_for;
+use crate::services::geometry::{self, extract_base_keyboard};
+use crate::services::hooks::{self, Hook};
+use crate::services::process_tree::{ChildOutcome, OutputLine, TrackedChild};
+use crate::services::LayoutService;
+use clap::Args;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Generate firmware files and compile them with `qmk compile`
+#[derive(Debug, Clone, Args)]
+pub struct BuildArgs {
+    /// Layout file to build
+    #[command(flatten)]
+    pub layout: LayoutFileArg,
+
+    /// Path to QMK firmware repository (defaults to the configured path)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+
+    /// Copy the compiled firmware into this directory
+    #[arg(short, long, value_name = "DIR")]
+    pub out_dir: Option<PathBuf>,
+
+    /// QMK layout variant (auto-detected from metadata if omitted)
+    #[arg(long, value_name = "NAME")]
+    pub layout_name: Option<String>,
+
+    /// Output the built firmware as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// JSON response for the build command
+#[derive(Debug, Serialize)]
+struct BuildResponse {
+    keyboard: String,
+    keymap: String,
+    firmware: String,
+}
+
+impl BuildArgs {
+    /// Execute the build command
+    pub fn execute(&self) -> CliResult<()> {
+        let layout = LayoutService::load(self.layout.path()?)
+            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+
+        let mut config = Config::load().unwrap_or_default();
+        if let Some(qmk_path) = &self.qmk_path {
+            config.pin_qmk_path(qmk_path.clone());
+        }
+        let qmk_path = config
+            .qmk_tree_path(layout.metadata.qmk_tree.as_deref())
+            .cloned()
+            .ok_or_else(|| CliError::config("QMK firmware path not configured. Use --qmk-path"))?;
+        let keyboard = layout
+            .metadata
+            .keyboard
+            .clone()
+            .ok_or_else(|| CliError::validation("Keyboard not specified in layout metadata"))?;
+
+        // Determine layout variant
+        let layout_variant = self
+            .layout_name
+            .clone()
+            .or_else(|| layout.metadata.layout_variant.clone())
+            .ok_or_else(|| {
+                CliError::validation(
+                    "Layout variant not specified. Use --layout-name or set in metadata",
+                )
+            })?;
+
+        // Build geometry
+        let geo_context = geometry::GeometryContext {
+            config: &config,
+            metadata: &layout.metadata,
+        };
+        let geo_result = geometry::build_geometry_for_layout(geo_context, &layout_variant)
+            .map_err(|e| CliError::io(format!("Failed to build geometry: {e}")))?;
+
+        let keycode_db = KeycodeDb::load()
+            .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
+
+        // Validate before generating
+        let report = FirmwareValidator::new(
+            &layout,
+            &geo_result.geometry,
+            &geo_result.mapping,
+            &keycode_db,
+        )
+        .validate()
+        .map_err(|e| CliError::io(format!("Validation failed: {e}")))?;
+        if !report.is_valid() {
+            return Err(CliError::validation(format!(
+                "Layout validation failed:\n{}",
+                report.format_message()
+            )));
+        }
+
+        // Write keymap.c etc. into the QMK keymap directory
+        FirmwareGenerator::new(
+            &layout,
+            &geo_result.geometry,
+            &geo_result.mapping,
+            &config,
+            &keycode_db,
+        )
+        .generate()
+        .map_err(|e| CliError::io(format!("Failed to generate firmware files: {e}")))?;
+
+        // Build the keyboard variant matching the layout's key count
+        let base_keyboard = extract_base_keyboard(&keyboard);
+        let build_keyboard = config
+            .build
+            .determine_keyboard_variant(
+                &keyboards_dir_for(&qmk_path, &config.paths.keyboard_roots, &base_keyboard),
+                &base_keyboard,
+                geo_result.geometry.keys.len(),
+            )
+            .unwrap_or_else(|_| keyboard.clone());
+        let keymap = layout
+            .metadata
+            .keymap_name
+            .clone()
+            .unwrap_or_else(|| "default".to_string());
+        let converter = layout.metadata.converter.as_deref();
+
+        compile(&qmk_path, &build_keyboard, &keymap, converter)?;
+
+        let firmware = find_firmware_file(&qmk_path, &build_keyboard, &keymap, converter)
+            .map_err(|e| CliError::build(e.to_string()))?;
+
+        let firmware = match &self.out_dir {
+            Some(out_dir) => copy_firmware(&firmware, out_dir)?,
+            None => firmware,
+        };
+        say!("✓ Built firmware: {}", firmware.display());
+
+        hooks::run_hook(
+            &config.hooks,
+            Hook::PostBuild,
+            self.layout.path()?,
+            std::slice::from_ref(&firmware),
+        )
+        .map_err(|e| CliError::build(format!("{e:#}")))?;
+
+        if self.json {
+            print_json(&BuildResponse {
+                keyboard: build_keyboard,
+                keymap,
+                firmware: firmware.display().to_string(),
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs `qmk compile`, passing its output through (stdout only without
+/// `--quiet` or `--json`).
+fn compile(
+    qmk_path: &Path,
+    keyboard: &str,
+    keymap: &str,
+    converter: Option<&str>,
+) -> CliResult<()> {
+    let args = compile_args(keyboard, keymap, converter);
+    say!("Running: qmk {}", args.join(" "));
+
+    let mut cmd = Command::new("qmk");
+    cmd.args(&args).current_dir(qmk_path);
+
+    let outcome = TrackedChild::spawn(&mut cmd).and_then(|mut child| {
+        child.wait_with_output(&|| false, &mut |line| match line {
+            OutputLine::Stdout(line) => say!("{line}"),
+            OutputLine::Stderr(line) => eprintln!("{line}"),
+        })
+    });
+
+    match outcome {
+        Ok(ChildOutcome::Exited(status)) if status.success() => Ok(()),
+        Ok(ChildOutcome::Exited(status)) => {
+            Err(CliError::build(format!("qmk compile failed ({status})")))
+        }
+        Ok(ChildOutcome::Stopped) => Err(CliError::build(
+            "qmk compile was stopped before it finished",
+        )),
+        Err(e) => Err(CliError::build(enhance_qmk_error(&format!(
+            "Failed to execute qmk compile command: {e}"
+        )))),
+    }
+}
+
+/// Copies the firmware file into `out_dir`, returning the new path.
+fn copy_firmware(firmware: &Path, out_dir: &Path) -> CliResult<PathBuf> {
+    std::fs::create_dir_all(out_dir)
+        .map_err(|e| CliError::io(format!("Failed to create output directory: {e}")))?;
+    let target = out_dir.join(firmware.file_name().unwrap_or_default());
+    std::fs::copy(firmware, &target)
+        .map_err(|e| CliError::io(format!("Failed to copy firmware: {e}")))?;
+    Ok(target)
+}
@@ -0,0 +1,240 @@
+//! Bundle commands: export and import `.lazyqmk` share bundles.
+
+use crate::cli::common::{print_json, CliError, CliResult};
+use crate::config::Config;
+use crate::keycode_db::KeycodeDb;
+use crate::services::geometry;
+use crate::services::share_bundle::{ShareBundle, BUNDLE_EXTENSION};
+use crate::services::LayoutService;
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Share layouts as single-file bundles
+#[derive(Debug, Clone, Args)]
+pub struct BundleArgs {
+    /// Bundle subcommand
+    #[command(subcommand)]
+    pub command: BundleCommand,
+}
+
+/// Bundle subcommands
+#[derive(Debug, Clone, Subcommand)]
+pub enum BundleCommand {
+    /// Package a layout, its preview, geometry and keymap.c into a .lazyqmk bundle
+    Export(BundleExportArgs),
+    /// Extract the layout from a .lazyqmk bundle
+    Import(BundleImportArgs),
+}
+
+/// Package a layout into a share bundle
+#[derive(Debug, Clone, Args)]
+pub struct BundleExportArgs {
+    /// Path to layout file
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Output bundle (defaults to the layout path with a .lazyqmk extension)
+    #[arg(short, long, value_name = "FILE")]
+    pub out: Option<PathBuf>,
+
+    /// Path to QMK firmware repository, used to render the preview and
+    /// embed the keyboard geometry (defaults to the configured path)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+
+    /// Skip the SVG preview, geometry snapshot and keymap.c
+    #[arg(long)]
+    pub no_preview: bool,
+
+    /// Output the written bundle as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Extract the layout from a share bundle
+#[derive(Debug, Clone, Args)]
+pub struct BundleImportArgs {
+    /// Bundle to import
+    #[arg(short, long, value_name = "FILE")]
+    pub bundle: PathBuf,
+
+    /// Output layout file (defaults to the bundle path with a .json extension)
+    #[arg(short, long, value_name = "FILE")]
+    pub out: Option<PathBuf>,
+
+    /// Also write the bundled SVG preview to this file
+    #[arg(long, value_name = "FILE")]
+    pub preview: Option<PathBuf>,
+
+    /// Also write the bundled keymap.c to this file
+    #[arg(long, value_name = "FILE")]
+    pub keymap: Option<PathBuf>,
+
+    /// Output the imported layout as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// JSON response for the bundle export and import commands
+#[derive(Debug, Serialize)]
+struct BundleResponse {
+    output: String,
+    name: String,
+    keyboard: Option<String>,
+    layers: usize,
+    preview: bool,
+    geometry: bool,
+    keymap: bool,
+    app_version: String,
+}
+
+impl BundleResponse {
+    fn new(out: &Path, bundle: &ShareBundle) -> Self {
+        let metadata = &bundle.layout.metadata;
+        Self {
+            output: out.display().to_string(),
+            name: metadata.name.clone(),
+            keyboard: metadata.keyboard.clone(),
+            layers: bundle.layout.layers.len(),
+            preview: bundle.preview_svg.is_some(),
+            geometry: bundle.geometry.is_some(),
+            keymap: bundle.keymap_c.is_some(),
+            app_version: bundle.manifest.app_version.clone(),
+        }
+    }
+}
+
+impl BundleArgs {
+    /// Execute the bundle command
+    pub fn execute(&self) -> CliResult<()> {
+        match &self.command {
+            BundleCommand::Export(args) => args.execute(),
+            BundleCommand::Import(args) => args.execute(),
+        }
+    }
+}
+
+impl BundleExportArgs {
+    /// Execute the bundle export command
+    pub fn execute(&self) -> CliResult<()> {
+        let layout = LayoutService::load(&self.layout)
+            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+
+        let mut config = Config::load().unwrap_or_default();
+        if let Some(qmk_path) = &self.qmk_path {
+            config.pin_qmk_path(qmk_path.clone());
+        }
+        let geometry = if self.no_preview {
+            None
+        } else {
+            let geo_context = geometry::GeometryContext {
+                config: &config,
+                metadata: &layout.metadata,
+            };
+            let variant = layout.metadata.layout_variant.as_deref().unwrap_or("");
+            match geometry::build_geometry_for_layout(geo_context, variant) {
+                Ok(result) => Some(result.geometry),
+                Err(e) => {
+                    eprintln!("Warning: no preview, keyboard geometry unavailable: {e}");
+                    None
+                }
+            }
+        };
+
+        let mut bundle = ShareBundle::new(&layout, geometry.as_ref())
+            .map_err(|e| CliError::io(format!("{e:#}")))?;
+        if geometry.is_some() {
+            let keycode_db = KeycodeDb::load()
+                .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
+            if let Err(e) = bundle.generate_keymap(&config, &keycode_db) {
+                eprintln!("Warning: no keymap.c in bundle: {e:#}");
+            }
+        }
+        let out = self
+            .out
+            .clone()
+            .unwrap_or_else(|| self.layout.with_extension(BUNDLE_EXTENSION));
+        bundle
+            .write(&out)
+            .map_err(|e| CliError::io(format!("{e:#}")))?;
+
+        if self.json {
+            return print_json(&BundleResponse::new(&out, &bundle));
+        }
+        say!(
+            "✓ Bundled {} ({} layers{}) into {}",
+            layout.metadata.name,
+            layout.layers.len(),
+            if bundle.keymap_c.is_some() {
+                ", with preview, geometry and keymap.c"
+            } else if bundle.preview_svg.is_some() {
+                ", with preview and geometry"
+            } else {
+                ""
+            },
+            out.display()
+        );
+
+        Ok(())
+    }
+}
+
+impl BundleImportArgs {
+    /// Execute the bundle import command
+    pub fn execute(&self) -> CliResult<()> {
+        let out = self
+            .out
+            .clone()
+            .unwrap_or_else(|| self.bundle.with_extension("json"));
+        if out.exists() {
+            return Err(CliError::validation(format!(
+                "Output file already exists: {}",
+                out.display()
+            )));
+        }
+
+        let bundle =
+            ShareBundle::read(&self.bundle).map_err(|e| CliError::validation(format!("{e:#}")))?;
+        LayoutService::save(&bundle.layout, &out)
+            .map_err(|e| CliError::io(format!("Failed to save layout: {e}")))?;
+
+        if let Some(preview_path) = &self.preview {
+            let svg = bundle.preview_svg.as_deref().ok_or_else(|| {
+                CliError::validation("Bundle has no preview; the layout was still imported")
+            })?;
+            std::fs::write(preview_path, svg).map_err(|e| {
+                CliError::io(format!("Failed to write {}: {e}", preview_path.display()))
+            })?;
+        }
+        if let Some(keymap_path) = &self.keymap {
+            let keymap_c = bundle.keymap_c.as_deref().ok_or_else(|| {
+                CliError::validation("Bundle has no keymap.c; the layout was still imported")
+            })?;
+            std::fs::write(keymap_path, keymap_c).map_err(|e| {
+                CliError::io(format!("Failed to write {}: {e}", keymap_path.display()))
+            })?;
+        }
+
+        let layout = &bundle.layout;
+        if self.json {
+            return print_json(&BundleResponse::new(&out, &bundle));
+        }
+        say!(
+            "✓ Imported {} ({} layers) for {}",
+            layout.metadata.name,
+            layout.layers.len(),
+            layout
+                .metadata
+                .keyboard
+                .as_deref()
+                .unwrap_or("no keyboard set")
+        );
+        say!("  Output: {}", out.display());
+        if bundle.manifest.app_version != env!("CARGO_PKG_VERSION") {
+            say!("  Bundled with LazyQMK {}", bundle.manifest.app_version);
+        }
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! Provides commands to list, add, and delete categories in a layout file.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult};
 use crate::models::{Category, RgbColor};
 use crate::services::LayoutService;
 use clap::{Args, Subcommand};
@@ -126,18 +126,14 @@ impl ListCategoriesArgs {
         };
 
         if self.json {
-            println!(
-                "{}",
-                serde_json::to_string(&response)
-                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-            );
+            print_json(&response)?;
         } else if response.count == 0 {
-            println!("No categories defined.");
+            say!("No categories defined.");
         } else {
-            println!("Categories ({}):", response.count);
-            println!();
+            say!("Categories ({}):", response.count);
+            say!();
             for cat in response.categories {
-                println!("  {:<20} {:<30} {}", cat.id, cat.name, cat.color);
+                say!("  {:<20} {:<30} {}", cat.id, cat.name, cat.color);
             }
         }
 
@@ -176,7 +172,7 @@ impl AddCategoryArgs {
         LayoutService::save(&layout, &self.layout)
             .map_err(|e| CliError::io(format!("Failed to save layout: {e}")))?;
 
-        println!("Category '{}' added successfully.", self.id);
+        say!("Category '{}' added successfully.", self.id);
         Ok(())
     }
 }
@@ -216,7 +212,7 @@ impl DeleteCategoryArgs {
         LayoutService::save(&layout, &self.layout)
             .map_err(|e| CliError::io(format!("Failed to save layout: {e}")))?;
 
-        println!("Category '{}' deleted successfully.", self.id);
+        say!("Category '{}' deleted successfully.", self.id);
         Ok(())
     }
 }
@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_validate_hex_color_valid_long() {
     let result = validate_and_parse_hex("#FF0000");
@@ -0,0 +1,163 @@
+//! Collect-usage command: record key presses reported by the keyboard.
+
+use crate::cli::common::{print_json, CliError, CliResult};
+use crate::config::Config;
+use crate::services::geometry;
+use crate::services::hid_listen::{HidEvent, HidListener};
+use crate::services::usage::UsageLog;
+use crate::services::LayoutService;
+use clap::Args;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::{Duration, Instant};
+
+/// How long to wait for more reports before saving what arrived.
+const SAVE_AFTER: Duration = Duration::from_secs(1);
+
+/// Record key usage reported by firmware built with usage counting
+#[derive(Debug, Clone, Args)]
+pub struct CollectUsageArgs {
+    /// Path to layout file (the firmware must be built from it)
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Path to QMK firmware repository (defaults to the configured path)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+
+    /// Stop after this many seconds (default: run until interrupted)
+    #[arg(long, value_name = "SECS")]
+    pub duration: Option<u64>,
+
+    /// Output the recorded totals as JSON when collection stops
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// JSON response for the collect-usage command
+#[derive(Debug, Serialize)]
+struct CollectUsageResponse {
+    log: String,
+    recorded: u64,
+    total: u64,
+    unmapped: u64,
+}
+
+impl CollectUsageArgs {
+    /// Execute the collect-usage command
+    pub fn execute(&self) -> CliResult<()> {
+        let layout = LayoutService::load(&self.layout)
+            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+        if !layout.usage_counting {
+            return Err(CliError::validation(
+                "Usage counting is off for this layout. Turn on Settings → Usage Counting, \
+                 then build and flash the firmware",
+            ));
+        }
+
+        let mut config = Config::load().unwrap_or_default();
+        if let Some(qmk_path) = &self.qmk_path {
+            config.pin_qmk_path(qmk_path.clone());
+        }
+        let layout_variant = layout
+            .metadata
+            .layout_variant
+            .clone()
+            .ok_or_else(|| CliError::validation("Layout variant not specified in metadata"))?;
+        let geo_context = geometry::GeometryContext {
+            config: &config,
+            metadata: &layout.metadata,
+        };
+        let mapping = geometry::build_geometry_for_layout(geo_context, &layout_variant)
+            .map_err(|e| CliError::io(format!("Failed to build geometry: {e}")))?
+            .mapping;
+
+        let path = UsageLog::path_for(&self.layout);
+        let mut log = UsageLog::load(&path).map_err(|e| CliError::io(format!("{e:#}")))?;
+        let save = |log: &UsageLog| log.save(&path).map_err(|e| CliError::io(format!("{e:#}")));
+
+        let deadline = self
+            .duration
+            .map(|secs| Instant::now() + Duration::from_secs(secs));
+        say!(
+            "Collecting key usage into {}{}",
+            path.display(),
+            if deadline.is_some() {
+                ""
+            } else {
+                " (Ctrl+C to stop)"
+            }
+        );
+
+        let listener = HidListener::start();
+        let mut recorded = 0u64;
+        let mut unmapped = 0u64;
+        let mut unsaved = false;
+        loop {
+            let timeout = match deadline {
+                Some(deadline) => {
+                    let left = deadline.saturating_duration_since(Instant::now());
+                    if left.is_zero() {
+                        break;
+                    }
+                    left.min(SAVE_AFTER)
+                }
+                None => SAVE_AFTER,
+            };
+            match listener.recv_timeout(timeout) {
+                Ok(HidEvent::Usage {
+                    layer,
+                    row,
+                    col,
+                    count,
+                }) => match mapping.matrix_to_visual_pos(row, col) {
+                    Some(position) => {
+                        log.record(usize::from(layer), position, u64::from(count));
+                        recorded += u64::from(count);
+                        unsaved = true;
+                    }
+                    None => unmapped += u64::from(count),
+                },
+                Ok(HidEvent::Connected { device }) => say!("Connected to {device}"),
+                Ok(HidEvent::Disconnected) => say!("Keyboard disconnected, waiting..."),
+                Ok(HidEvent::Unavailable { reason }) => eprintln!("Warning: {reason}"),
+                Ok(HidEvent::Layer { .. } | HidEvent::KeyPressed { .. }) => {}
+                // A batch of reports is complete once the console goes quiet
+                Err(RecvTimeoutError::Timeout) => {
+                    if unsaved {
+                        save(&log)?;
+                        unsaved = false;
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+        if unsaved {
+            save(&log)?;
+        }
+
+        if self.json {
+            return print_json(&CollectUsageResponse {
+                log: path.display().to_string(),
+                recorded,
+                total: log.total(),
+                unmapped,
+            });
+        }
+        if recorded == 0 {
+            say!("No key presses reported");
+        } else {
+            say!(
+                "✓ Recorded {recorded} presses ({} in total) in {}",
+                log.total(),
+                path.display()
+            );
+        }
+        if unmapped > 0 {
+            say!("  {unmapped} presses on matrix positions not in the layout were skipped");
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 //! Common types and utilities for CLI commands.
 
+use crate::config::Config;
+use clap::Args;
 use serde::Serialize;
 use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// CLI result type with proper exit codes.
 pub type CliResult<T> = Result<T, CliError>;
@@ -15,6 +19,24 @@ pub enum ExitCode {
     ValidationError = 1,
     /// I/O or system error (2)
     IoError = 2,
+    /// Firmware compilation failed (3)
+    BuildError = 3,
+    /// Missing or unreadable configuration (4)
+    ConfigError = 4,
+}
+
+impl ExitCode {
+    /// Short machine-readable name used in JSON error output.
+    #[must_use]
+    pub const fn kind(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::ValidationError => "validation",
+            Self::IoError => "io",
+            Self::BuildError => "build",
+            Self::ConfigError => "config",
+        }
+    }
 }
 
 /// CLI error with exit code.
@@ -44,6 +66,88 @@ impl CliError {
             exit_code: ExitCode::IoError,
         }
     }
+
+    /// Creates a build error (exit code 3).
+    #[must_use]
+    pub fn build(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            exit_code: ExitCode::BuildError,
+        }
+    }
+
+    /// Creates a configuration error (exit code 4).
+    #[must_use]
+    pub fn config(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            exit_code: ExitCode::ConfigError,
+        }
+    }
+}
+
+/// JSON body printed for a failed command run with `--json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorResponse {
+    /// The error
+    pub error: ErrorDetail,
+}
+
+/// Error details in [`ErrorResponse`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorDetail {
+    /// Human-readable message
+    pub message: String,
+    /// Error kind: "validation", "io", "build", or "config"
+    pub kind: &'static str,
+    /// Process exit code
+    pub exit_code: i32,
+}
+
+impl From<&CliError> for ErrorResponse {
+    fn from(err: &CliError) -> Self {
+        Self {
+            error: ErrorDetail {
+                message: err.message.clone(),
+                kind: err.exit_code.kind(),
+                exit_code: err.exit_code as i32,
+            },
+        }
+    }
+}
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+static JSON_PRINTED: AtomicBool = AtomicBool::new(false);
+
+/// Turns human-readable output of CLI commands off or on (`--quiet`).
+///
+/// JSON output and errors are printed either way.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether human-readable output is suppressed.
+#[must_use]
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Prints `value` as pretty JSON on stdout.
+pub fn print_json(value: &impl Serialize) -> CliResult<()> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?;
+    println!("{json}");
+    JSON_PRINTED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Whether a command already printed its JSON response.
+///
+/// Commands such as `validate` print a full report and then fail; the
+/// error is not printed as a second JSON document in that case.
+#[must_use]
+pub fn json_printed() -> bool {
+    JSON_PRINTED.load(Ordering::Relaxed)
 }
 
 impl fmt::Display for CliError {
@@ -67,6 +171,28 @@ impl From<std::io::Error> for CliError {
     }
 }
 
+/// Layout file argument, given either as `<FILE>` or as `-l/--layout <FILE>`.
+#[derive(Debug, Clone, Default, Args)]
+pub struct LayoutFileArg {
+    /// Path to layout file
+    #[arg(value_name = "FILE", required_unless_present = "layout")]
+    pub file: Option<PathBuf>,
+
+    /// Path to layout file (alternative to the positional FILE)
+    #[arg(short, long, value_name = "FILE", conflicts_with = "file")]
+    pub layout: Option<PathBuf>,
+}
+
+impl LayoutFileArg {
+    /// Returns the layout file path.
+    pub fn path(&self) -> CliResult<&Path> {
+        self.file
+            .as_deref()
+            .or(self.layout.as_deref())
+            .ok_or_else(|| CliError::validation("No layout file given"))
+    }
+}
+
 /// JSON response for validation commands.
 #[derive(Debug, Clone, Serialize)]
 pub struct ValidationResponse {
@@ -97,6 +223,9 @@ pub struct ValidationLocation {
     pub layer: usize,
     /// Position in the layout
     pub position: ValidationPosition,
+    /// Human-readable key name (e.g., "left home row pinky"), if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
 }
 
 /// Position coordinates.
@@ -133,3 +262,38 @@ impl ValidationChecks {
         }
     }
 }
+
+/// Returns `workspace`, or the platform layouts directory if `None`.
+pub fn resolve_workspace(workspace: Option<&Path>) -> CliResult<PathBuf> {
+    match workspace {
+        Some(dir) => Ok(dir.to_path_buf()),
+        None => Ok(Config::config_dir()
+            .map_err(|e| CliError::config(format!("Failed to locate layouts directory: {e}")))?
+            .join("layouts")),
+    }
+}
+
+/// Lists the layout files in `workspace`, sorted by name.
+///
+/// Legacy `.md` files are skipped when a `.json` with the same name exists.
+pub fn find_layout_files(workspace: &Path) -> CliResult<Vec<PathBuf>> {
+    let entries = std::fs::read_dir(workspace).map_err(|e| {
+        CliError::io(format!(
+            "Failed to read workspace {}: {e}",
+            workspace.display()
+        ))
+    })?;
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => true,
+            Some("md") => !path.with_extension("json").exists(),
+            _ => false,
+        })
+        .collect();
+    files.sort();
+
+    Ok(files)
+}
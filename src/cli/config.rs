@@ -1,6 +1,6 @@
 //! Configuration management CLI commands.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult};
 use crate::config::{Config, ThemeMode};
 use clap::{Args, Subcommand};
 use serde::Serialize;
@@ -43,6 +43,15 @@ pub struct ConfigSetArgs {
     /// Theme mode (auto, light, or dark)
     #[arg(long, value_name = "MODE")]
     theme: Option<String>,
+
+    /// Extra keyboard directory laid out like QMK's keyboards/ (repeatable,
+    /// replaces the configured list)
+    #[arg(long = "keyboard-root", value_name = "DIR")]
+    keyboard_roots: Vec<PathBuf>,
+
+    /// Remove all extra keyboard directories
+    #[arg(long, conflicts_with = "keyboard_roots")]
+    clear_keyboard_roots: bool,
 }
 
 /// JSON-serializable configuration for output
@@ -57,6 +66,8 @@ struct ConfigOutput {
 struct PathsOutput {
     #[serde(skip_serializing_if = "Option::is_none")]
     qmk_firmware: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    keyboard_roots: Vec<String>,
 }
 
 #[derive(Serialize, Debug)]
@@ -83,7 +94,7 @@ impl ConfigShowArgs {
     /// Execute show command
     pub fn execute(&self) -> CliResult<()> {
         let config = Config::load()
-            .map_err(|e| CliError::validation(format!("Failed to load configuration: {}", e)))?;
+            .map_err(|e| CliError::config(format!("Failed to load configuration: {}", e)))?;
 
         if self.json {
             output_json(&config)?;
@@ -99,9 +110,14 @@ impl ConfigSetArgs {
     /// Execute set command
     pub fn execute(&self) -> CliResult<()> {
         // At least one argument must be provided
-        if self.qmk_path.is_none() && self.output_dir.is_none() && self.theme.is_none() {
+        if self.qmk_path.is_none()
+            && self.output_dir.is_none()
+            && self.theme.is_none()
+            && self.keyboard_roots.is_empty()
+            && !self.clear_keyboard_roots
+        {
             return Err(CliError::validation(
-                "At least one configuration option must be specified: --qmk-path, --output-dir, or --theme"
+                "At least one configuration option must be specified: --qmk-path, --output-dir, --theme, or --keyboard-root"
             ));
         }
 
@@ -163,12 +179,25 @@ impl ConfigSetArgs {
             config.ui.theme_mode = theme;
         }
 
+        // Validate and apply keyboard roots if provided
+        if self.clear_keyboard_roots {
+            config.paths.keyboard_roots.clear();
+        } else if !self.keyboard_roots.is_empty() {
+            if let Some(root) = self.keyboard_roots.iter().find(|root| !root.is_dir()) {
+                return Err(CliError::validation(format!(
+                    "Keyboard root directory does not exist: {}",
+                    root.display()
+                )));
+            }
+            config.paths.keyboard_roots.clone_from(&self.keyboard_roots);
+        }
+
         // Save configuration
         config
             .save()
-            .map_err(|e| CliError::io(format!("Failed to save configuration: {}", e)))?;
+            .map_err(|e| CliError::config(format!("Failed to save configuration: {}", e)))?;
 
-        println!("Configuration updated successfully.");
+        say!("Configuration updated successfully.");
 
         Ok(())
     }
@@ -183,6 +212,12 @@ fn output_json(config: &Config) -> CliResult<()> {
                 .qmk_firmware
                 .as_ref()
                 .map(|p| p.to_string_lossy().to_string()),
+            keyboard_roots: config
+                .paths
+                .keyboard_roots
+                .iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect(),
         },
         build: BuildOutput {
             output_dir: config.build.output_dir.to_string_lossy().to_string(),
@@ -192,37 +227,36 @@ fn output_json(config: &Config) -> CliResult<()> {
         },
     };
 
-    let json = serde_json::to_string_pretty(&output)
-        .map_err(|e| CliError::io(format!("Failed to serialize configuration to JSON: {}", e)))?;
-
-    println!("{}", json);
-    Ok(())
+    print_json(&output)
 }
 
 /// Output configuration in human-readable format
 fn output_human_readable(config: &Config) {
-    println!("LazyQMK Configuration");
-    println!("====================");
-    println!();
+    say!("LazyQMK Configuration");
+    say!("====================");
+    say!();
 
-    println!("Paths:");
+    say!("Paths:");
     if let Some(qmk_path) = &config.paths.qmk_firmware {
-        println!("  QMK Firmware: {}", qmk_path.display());
+        say!("  QMK Firmware: {}", qmk_path.display());
     } else {
-        println!("  QMK Firmware: (not configured)");
+        say!("  QMK Firmware: (not configured)");
+    }
+    for root in &config.paths.keyboard_roots {
+        say!("  Keyboard Root: {}", root.display());
     }
-    println!();
+    say!();
 
-    println!("Build:");
-    println!("  Output Directory: {}", config.build.output_dir.display());
-    println!();
+    say!("Build:");
+    say!("  Output Directory: {}", config.build.output_dir.display());
+    say!();
 
-    println!("UI:");
-    println!(
+    say!("UI:");
+    say!(
         "  Theme Mode: {}",
         format!("{:?}", config.ui.theme_mode).to_lowercase()
     );
-    println!();
+    say!();
 }
 
 #[cfg(test)]
@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_theme_mode_parsing() {
     assert_eq!(
@@ -1,6 +1,6 @@
 //! Doctor command for dependency checking.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult};
 use crate::config::Config;
 use crate::doctor::{DependencyChecker, DoctorFormatter, OutputFormat, ToolStatus};
 use clap::Args;
@@ -26,7 +26,8 @@ impl DoctorArgs {
 
         // Create checker and run all checks
         let checker = DependencyChecker::new();
-        let statuses = checker.check_all(qmk_path);
+        let mut statuses = checker.check_all(qmk_path);
+        statuses.push(checker.check_keycode_db());
 
         // Determine output format
         let format = if self.json {
@@ -38,7 +39,13 @@ impl DoctorArgs {
         // Format and print results
         let formatter = DoctorFormatter::with_format(format);
         let output = formatter.format_results(&statuses);
-        println!("{}", output);
+        if self.json {
+            let value: serde_json::Value = serde_json::from_str(&output)
+                .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?;
+            print_json(&value)?;
+        } else {
+            say!("{output}");
+        }
 
         // Determine exit code
         let has_missing = statuses.iter().any(|s| s.status == ToolStatus::Missing);
@@ -1,46 +1,63 @@
-//! Export command for generating markdown documentation.
+//! Export command for generating markdown documentation, SVG diagrams and
+//! keymap-drawer YAML.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::batch::ExportFormat;
+use crate::cli::common::{print_json, CliError, CliResult, LayoutFileArg};
 use crate::config::Config;
-use crate::export;
 use crate::keycode_db::KeycodeDb;
 use crate::models::Layout;
 use crate::services::geometry;
 use crate::services::LayoutService;
 use clap::Args;
+use serde::Serialize;
 use std::fs;
 use std::path::PathBuf;
 
-/// Export keyboard layout to markdown documentation
+/// Export keyboard layout documentation
 #[derive(Debug, Clone, Args)]
 pub struct ExportArgs {
-    /// Path to layout markdown file
-    #[arg(short, long, value_name = "FILE")]
-    pub layout: PathBuf,
+    /// Layout file to read
+    #[command(flatten)]
+    pub layout: LayoutFileArg,
 
     /// Path to QMK firmware repository
     #[arg(long, value_name = "PATH")]
     pub qmk_path: PathBuf,
 
-    /// Output path for markdown file (defaults to `{layout_name}_export_{date}.md`)
+    /// Output path (defaults to `{layout_name}_export_{date}.{ext}`)
     #[arg(short, long, value_name = "FILE")]
     pub output: Option<PathBuf>,
 
+    /// Output format
+    #[arg(long, value_enum, default_value = "md")]
+    pub format: ExportFormat,
+
     /// QMK layout variant (auto-detected from metadata if omitted)
     #[arg(long, value_name = "NAME")]
     pub layout_name: Option<String>,
+
+    /// Output the written file as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// JSON response for the export command
+#[derive(Debug, Serialize)]
+struct ExportResponse {
+    output: String,
+    format: String,
 }
 
 impl ExportArgs {
     /// Execute the export command
     pub fn execute(&self) -> CliResult<()> {
         // Load layout
-        let layout = LayoutService::load(&self.layout)
+        let layout = LayoutService::load(self.layout.path()?)
             .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
 
         // Build config with QMK path
         let mut config = Config::load().unwrap_or_default();
-        config.paths.qmk_firmware = Some(self.qmk_path.clone());
+        config.pin_qmk_path(self.qmk_path.clone());
 
         // Determine layout variant
         let layout_variant = self
@@ -63,22 +80,29 @@ impl ExportArgs {
             .map_err(|e| CliError::io(format!("Failed to build geometry: {e}")))?;
         let geometry = geo_result.geometry;
 
-        // Load keycode database (needed for tap dance docs)
+        // Load keycode database (needed for tap dance docs and key legends)
         let keycode_db = KeycodeDb::load()
             .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
 
-        // Generate markdown content using export module
-        let markdown = export::export_to_markdown(&layout, &geometry, &keycode_db)
-            .map_err(|e| CliError::io(format!("Failed to generate markdown: {e}")))?;
+        let content = self
+            .format
+            .render(&layout, &geometry, &config, &keycode_db)
+            .map_err(|e| CliError::io(format!("Failed to export layout: {e}")))?;
 
         // Determine output path
         let output_path = self.get_output_path(&layout);
 
         // Write to file
-        fs::write(&output_path, markdown)
+        fs::write(&output_path, content)
             .map_err(|e| CliError::io(format!("Failed to write output file: {e}")))?;
 
-        println!("✓ Exported layout to: {}", output_path.display());
+        if self.json {
+            return print_json(&ExportResponse {
+                output: output_path.display().to_string(),
+                format: self.format.name().to_string(),
+            });
+        }
+        say!("✓ Exported layout to: {}", output_path.display());
 
         Ok(())
     }
@@ -89,11 +113,16 @@ impl ExportArgs {
             return path.clone();
         }
 
-        // Auto-generate filename: [layout_name]_export_[date].md
+        // Auto-generate filename: [layout_name]_export_[date].[ext]
         let date = chrono::Local::now().format("%Y-%m-%d");
         let layout_name = layout.metadata.name.replace(' ', "_").to_lowercase();
 
-        PathBuf::from(format!("{}_export_{}.md", layout_name, date))
+        PathBuf::from(format!(
+            "{}_export_{}.{}",
+            layout_name,
+            date,
+            self.format.extension()
+        ))
     }
 }
 
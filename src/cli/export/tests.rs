@@ -4,15 +4,18 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_get_output_path_default() {
     let args = ExportArgs {
-        layout: PathBuf::from("test.md"),
+        layout: LayoutFileArg {
+            file: Some(PathBuf::from("test.md")),
+            ..Default::default()
+        },
         qmk_path: PathBuf::from("/qmk"),
         output: None,
         layout_name: None,
+        format: ExportFormat::Md,
+        json: false,
     };
 
     let layout = Layout::new("My Test Layout").unwrap();
@@ -27,10 +30,15 @@ fn test_get_output_path_default() {
 fn test_get_output_path_custom() {
     let custom_path = PathBuf::from("/tmp/my_export.md");
     let args = ExportArgs {
-        layout: PathBuf::from("test.md"),
+        layout: LayoutFileArg {
+            file: Some(PathBuf::from("test.md")),
+            ..Default::default()
+        },
         qmk_path: PathBuf::from("/qmk"),
         output: Some(custom_path.clone()),
         layout_name: None,
+        format: ExportFormat::Md,
+        json: false,
     };
 
     let layout = Layout::new("Test").unwrap();
@@ -38,3 +46,23 @@ fn test_get_output_path_custom() {
 
     assert_eq!(path, custom_path);
 }
+
+#[test]
+fn test_get_output_path_uses_format_extension() {
+    let args = ExportArgs {
+        layout: LayoutFileArg {
+            file: Some(PathBuf::from("test.md")),
+            ..Default::default()
+        },
+        qmk_path: PathBuf::from("/qmk"),
+        output: None,
+        layout_name: None,
+        format: ExportFormat::KeymapDrawer,
+        json: false,
+    };
+
+    let layout = Layout::new("Test").unwrap();
+    let path = args.get_output_path(&layout);
+
+    assert!(path.to_string_lossy().ends_with(".yaml"));
+}
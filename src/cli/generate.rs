@@ -1,20 +1,24 @@
 //! Generate command for firmware files.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult, LayoutFileArg};
 use crate::config::Config;
 use crate::firmware::generator::FirmwareGenerator;
+use crate::firmware::user_code;
 use crate::keycode_db::KeycodeDb;
+use crate::models::{KeyboardGeometry, Layout, VisualLayoutMapping};
 use crate::services::geometry;
+use crate::services::hooks::{self, Hook};
 use crate::services::LayoutService;
 use clap::Args;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 
 /// Generate QMK firmware files from a layout
 #[derive(Debug, Clone, Args)]
 pub struct GenerateArgs {
-    /// Path to layout markdown file
-    #[arg(short, long, value_name = "FILE")]
-    pub layout: PathBuf,
+    /// Layout file to read
+    #[command(flatten)]
+    pub layout: LayoutFileArg,
 
     /// Path to QMK firmware repository
     #[arg(long, value_name = "PATH")]
@@ -32,9 +36,28 @@ pub struct GenerateArgs {
     #[arg(long, value_name = "TYPE", default_value = "all")]
     pub format: String,
 
-    /// Use stable timestamps/UUIDs for deterministic output (for testing)
+    /// Accepted for compatibility; generated files are always deterministic
     #[arg(long)]
     pub deterministic: bool,
+
+    /// Generate for this keyboard target of the layout instead of its primary keyboard
+    #[arg(long, value_name = "NAME", conflicts_with = "layout_name")]
+    pub target: Option<String>,
+
+    /// Also generate every keyboard target, each into `<out-dir>/<target name>`
+    #[arg(long, conflicts_with = "target")]
+    pub all_targets: bool,
+
+    /// Output the written files as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// JSON response for the generate command
+#[derive(Debug, Serialize)]
+struct GenerateResponse {
+    out_dir: String,
+    files: Vec<String>,
 }
 
 impl GenerateArgs {
@@ -49,14 +72,24 @@ impl GenerateArgs {
         }
 
         // Load layout
-        let layout = LayoutService::load(&self.layout)
+        let layout = LayoutService::load(self.layout.path()?)
             .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
 
         // Build config with QMK path
         let mut config = Config::load().unwrap_or_default();
-        config.paths.qmk_firmware = Some(self.qmk_path.clone());
+        config.pin_qmk_path(self.qmk_path.clone());
         config.build.output_dir.clone_from(&self.out_dir);
 
+        // Load keycode database
+        let keycode_db = KeycodeDb::load()
+            .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
+
+        if let Some(target) = &self.target {
+            let files =
+                self.generate_target(&layout, target, &config, &keycode_db, &self.out_dir)?;
+            return self.finish(&files);
+        }
+
         // Determine layout variant
         let layout_variant = self
             .layout_name
@@ -76,19 +109,102 @@ impl GenerateArgs {
 
         let geo_result = geometry::build_geometry_for_layout(geo_context, &layout_variant)
             .map_err(|e| CliError::io(format!("Failed to build geometry: {e}")))?;
-        let geometry = geo_result.geometry;
-        let mapping = geo_result.mapping;
 
-        // Load keycode database
-        let keycode_db = KeycodeDb::load()
-            .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
+        let mut files = self.write_output(
+            &layout,
+            &geo_result.geometry,
+            &geo_result.mapping,
+            &config,
+            &keycode_db,
+            &self.out_dir,
+        )?;
+
+        if self.all_targets {
+            for target in &layout.metadata.targets {
+                let out_dir = self.out_dir.join(&target.name);
+                files.extend(self.generate_target(
+                    &layout,
+                    &target.name,
+                    &config,
+                    &keycode_db,
+                    &out_dir,
+                )?);
+            }
+        }
+
+        self.finish(&files)
+    }
+
+    /// Prints the JSON response listing `files`, if requested.
+    fn finish(&self, files: &[PathBuf]) -> CliResult<()> {
+        if self.json {
+            print_json(&GenerateResponse {
+                out_dir: self.out_dir.display().to_string(),
+                files: files.iter().map(|f| f.display().to_string()).collect(),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Generates the files for the keyboard target `target` into `out_dir`.
+    fn generate_target(
+        &self,
+        layout: &Layout,
+        target: &str,
+        config: &Config,
+        keycode_db: &KeycodeDb,
+        out_dir: &Path,
+    ) -> CliResult<Vec<PathBuf>> {
+        let build = geometry::build_target(config, layout, target)
+            .map_err(|e| CliError::validation(format!("{e:#}")))?;
 
+        say!(
+            "Target {target}: {} ({})",
+            build
+                .layout
+                .metadata
+                .keyboard
+                .as_deref()
+                .unwrap_or_default(),
+            build
+                .layout
+                .metadata
+                .layout_variant
+                .as_deref()
+                .unwrap_or_default()
+        );
+        if !build.adjustments.is_empty() {
+            say!(
+                "  {} key(s) left off, {} position(s) filled with KC_NO",
+                build.adjustments.removed_count(),
+                build.adjustments.added_count()
+            );
+        }
+
+        self.write_output(
+            &build.layout,
+            &build.geometry,
+            &build.mapping,
+            config,
+            keycode_db,
+            out_dir,
+        )
+    }
+
+    /// Validates `layout`, writes the files selected by `--format` to `out_dir`
+    /// and runs the `post_generate` hook with their paths, which are returned.
+    fn write_output(
+        &self,
+        layout: &Layout,
+        geometry: &KeyboardGeometry,
+        mapping: &VisualLayoutMapping,
+        config: &Config,
+        keycode_db: &KeycodeDb,
+        out_dir: &Path,
+    ) -> CliResult<Vec<PathBuf>> {
         // Validate before generating
         let validator = crate::firmware::validator::FirmwareValidator::new(
-            &layout,
-            &geometry,
-            &mapping,
-            &keycode_db,
+            layout, geometry, mapping, keycode_db,
         );
         let report = validator
             .validate()
@@ -102,126 +218,116 @@ impl GenerateArgs {
         }
 
         // Create output directory
-        std::fs::create_dir_all(&self.out_dir)
+        std::fs::create_dir_all(out_dir)
             .map_err(|e| CliError::io(format!("Failed to create output directory: {e}")))?;
 
         // Generate files
-        let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+        let generator = FirmwareGenerator::new(layout, geometry, mapping, config, keycode_db);
 
-        match self.format.as_str() {
+        let written = match self.format.as_str() {
             "all" => {
-                // Generate both files
-                let keymap_c = generator
-                    .generate_keymap_c()
-                    .map_err(|e| CliError::io(format!("Failed to generate keymap.c: {e}")))?;
-                let config_h = generator
-                    .generate_merged_config_h()
-                    .map_err(|e| CliError::io(format!("Failed to generate config.h: {e}")))?;
-                let rules_mk = generator.generate_rules_mk();
-                let keymap_json = generator.generate_keymap_json();
-
-                // Apply deterministic transformations if requested
-                let keymap_c = if self.deterministic {
-                    normalize_for_deterministic(&keymap_c)
-                } else {
-                    keymap_c
-                };
-                let config_h = if self.deterministic {
-                    normalize_for_deterministic(&config_h)
-                } else {
-                    config_h
-                };
-
-                // Write files
-                std::fs::write(self.out_dir.join("keymap.c"), keymap_c)
-                    .map_err(|e| CliError::io(format!("Failed to write keymap.c: {e}")))?;
-                std::fs::write(self.out_dir.join("config.h"), config_h)
-                    .map_err(|e| CliError::io(format!("Failed to write config.h: {e}")))?;
-
-                // Write keymap.json (for QMK community modules)
-                if !keymap_json.is_empty() {
-                    std::fs::write(self.out_dir.join("keymap.json"), &keymap_json)
-                        .map_err(|e| CliError::io(format!("Failed to write keymap.json: {e}")))?;
-                } else {
-                    let json_path = self.out_dir.join("keymap.json");
-                    if json_path.exists() {
-                        std::fs::remove_file(&json_path).map_err(|e| {
-                            CliError::io(format!("Failed to remove stale keymap.json: {e}"))
-                        })?;
-                    }
-                }
-
-                if !rules_mk.is_empty() {
-                    std::fs::write(self.out_dir.join("rules.mk"), &rules_mk)
-                        .map_err(|e| CliError::io(format!("Failed to write rules.mk: {e}")))?;
-                    println!("✓ Generated keymap.c, config.h, rules.mk, and keymap.json");
-                } else {
-                    let rules_mk_path = self.out_dir.join("rules.mk");
-                    if rules_mk_path.exists() {
-                        std::fs::remove_file(&rules_mk_path).map_err(|e| {
-                            CliError::io(format!("Failed to remove stale rules.mk: {e}"))
-                        })?;
-                    }
-                    if !keymap_json.is_empty() {
-                        println!("✓ Generated keymap.c, config.h, and keymap.json");
-                    } else {
-                        println!("✓ Generated keymap.c and config.h");
-                    }
-                }
-                println!("  Output: {}", self.out_dir.display());
+                let written = write_firmware_files(&generator, out_dir)?;
+                say!("✓ Generated {}", join_file_names(&written));
+                say!("  Output: {}", out_dir.display());
+                written
             }
             "keymap" => {
                 let keymap_c = generator
                     .generate_keymap_c()
                     .map_err(|e| CliError::io(format!("Failed to generate keymap.c: {e}")))?;
 
-                let keymap_c = if self.deterministic {
-                    normalize_for_deterministic(&keymap_c)
-                } else {
-                    keymap_c
-                };
-
-                std::fs::write(self.out_dir.join("keymap.c"), keymap_c)
-                    .map_err(|e| CliError::io(format!("Failed to write keymap.c: {e}")))?;
+                write_preserving_user_code(&out_dir.join("keymap.c"), "keymap.c", &keymap_c)?;
 
-                println!("✓ Generated keymap.c");
-                println!("  Output: {}", self.out_dir.display());
+                say!("✓ Generated keymap.c");
+                say!("  Output: {}", out_dir.display());
+                vec!["keymap.c"]
             }
             "config" => {
                 let config_h = generator
                     .generate_merged_config_h()
                     .map_err(|e| CliError::io(format!("Failed to generate config.h: {e}")))?;
 
-                let config_h = if self.deterministic {
-                    normalize_for_deterministic(&config_h)
-                } else {
-                    config_h
-                };
+                write_preserving_user_code(&out_dir.join("config.h"), "config.h", &config_h)?;
 
-                std::fs::write(self.out_dir.join("config.h"), config_h)
-                    .map_err(|e| CliError::io(format!("Failed to write config.h: {e}")))?;
-
-                println!("✓ Generated config.h");
-                println!("  Output: {}", self.out_dir.display());
+                say!("✓ Generated config.h");
+                say!("  Output: {}", out_dir.display());
+                vec!["config.h"]
             }
             _ => unreachable!("Format already validated"),
-        }
+        };
 
-        Ok(())
+        let files: Vec<PathBuf> = written.iter().map(|name| out_dir.join(name)).collect();
+        hooks::run_hook(
+            &config.hooks,
+            Hook::PostGenerate,
+            self.layout.path()?,
+            &files,
+        )
+        .map_err(|e| CliError::io(format!("{e:#}")))?;
+
+        Ok(files)
     }
 }
 
-/// Normalize generated code for deterministic output (remove timestamps)
-fn normalize_for_deterministic(content: &str) -> String {
-    content
-        .lines()
-        .map(|line| {
-            if line.contains("Generated:") || line.contains("Generated at:") {
-                "// Generated: <timestamp>"
-            } else {
-                line
+/// Writes keymap.c, config.h, and wrappers.h to `out_dir`, plus rules.mk and
+/// keymap.json when the layout needs them.
+///
+/// Stale optional files from an earlier run are removed. Returns the names of
+/// the files written.
+pub fn write_firmware_files(
+    generator: &FirmwareGenerator<'_>,
+    out_dir: &Path,
+) -> CliResult<Vec<&'static str>> {
+    let keymap_c = generator
+        .generate_keymap_c()
+        .map_err(|e| CliError::io(format!("Failed to generate keymap.c: {e}")))?;
+    let config_h = generator
+        .generate_merged_config_h()
+        .map_err(|e| CliError::io(format!("Failed to generate config.h: {e}")))?;
+    let rules_mk = generator.generate_rules_mk();
+    let keymap_json = generator.generate_keymap_json();
+    let wrappers_h = generator
+        .generate_wrappers_h()
+        .map_err(|e| CliError::io(format!("Failed to generate wrappers.h: {e}")))?;
+
+    let mut written = Vec::new();
+    for (name, content) in [
+        ("keymap.c", keymap_c),
+        ("config.h", config_h),
+        ("rules.mk", rules_mk),
+        // keymap.json is for QMK community modules
+        ("keymap.json", keymap_json),
+        // wrappers.h is for userspaces built on row wrapper macros
+        ("wrappers.h", wrappers_h),
+    ] {
+        let path = out_dir.join(name);
+        if content.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path)
+                    .map_err(|e| CliError::io(format!("Failed to remove stale {name}: {e}")))?;
             }
-        })
-        .collect::<Vec<_>>()
-        .join("\n")
+        } else {
+            write_preserving_user_code(&path, name, &content)?;
+            written.push(name);
+        }
+    }
+
+    Ok(written)
+}
+
+/// Writes a generated file, keeping the user region of the file it replaces.
+fn write_preserving_user_code(path: &Path, name: &str, content: &str) -> CliResult<()> {
+    let content = user_code::preserve_from_file(content, path)
+        .map_err(|e| CliError::io(format!("Failed to read existing {name}: {e}")))?;
+    std::fs::write(path, content).map_err(|e| CliError::io(format!("Failed to write {name}: {e}")))
+}
+
+/// Joins file names for a status line ("a and b", "a, b, and c").
+fn join_file_names(names: &[&str]) -> String {
+    match names {
+        [] => String::new(),
+        [one] => (*one).to_string(),
+        [first, second] => format!("{first} and {second}"),
+        [rest @ .., last] => format!("{}, and {last}", rest.join(", ")),
+    }
 }
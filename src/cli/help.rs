@@ -1,8 +1,8 @@
 //! Display help topics from help.toml
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult};
 use clap::Args;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
 /// Display help topics and keybindings from help.toml
@@ -11,6 +11,10 @@ pub struct HelpArgs {
     /// Help topic name to display (e.g., "main", "`keycode_picker`", "`settings_manager`")
     #[arg(value_name = "TOPIC")]
     topic: Option<String>,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
 }
 
 #[derive(Deserialize, Debug)]
@@ -19,7 +23,7 @@ struct HelpData {
     contexts: BTreeMap<String, Context>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 struct Context {
     name: String,
     description: String,
@@ -27,7 +31,7 @@ struct Context {
     bindings: Vec<Binding>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 struct Binding {
     keys: Vec<String>,
     #[serde(default)]
@@ -37,6 +41,14 @@ struct Binding {
     priority: u32,
 }
 
+/// JSON entry for one topic in the topic list
+#[derive(Serialize, Debug)]
+struct TopicSummary<'a> {
+    topic: &'a str,
+    name: &'a str,
+    description: &'a str,
+}
+
 impl HelpArgs {
     /// Execute the help command: display topics or specific topic details
     pub fn execute(&self) -> CliResult<()> {
@@ -46,7 +58,18 @@ impl HelpArgs {
 
         if let Some(topic) = &self.topic {
             // Display specific topic
-            display_topic(topic, &help_data)
+            display_topic(topic, &help_data, self.json)
+        } else if self.json {
+            let topics: Vec<TopicSummary> = help_data
+                .contexts
+                .iter()
+                .map(|(topic, context)| TopicSummary {
+                    topic,
+                    name: &context.name,
+                    description: &context.description,
+                })
+                .collect();
+            print_json(&topics)
         } else {
             // List all topics
             list_all_topics(&help_data);
@@ -55,7 +78,7 @@ impl HelpArgs {
     }
 }
 
-fn display_topic(topic: &str, help_data: &HelpData) -> CliResult<()> {
+fn display_topic(topic: &str, help_data: &HelpData, json: bool) -> CliResult<()> {
     // Try to find the context with matching name (using underscore normalization)
     let normalized_topic = topic.replace('-', "_");
 
@@ -66,15 +89,19 @@ fn display_topic(topic: &str, help_data: &HelpData) -> CliResult<()> {
         ))
     })?;
 
+    if json {
+        return print_json(context);
+    }
+
     // Display context header
-    println!("{}", context.name);
-    println!("{}", "=".repeat(context.name.len()));
-    println!();
-    println!("{}", context.description);
-    println!();
+    say!("{}", context.name);
+    say!("{}", "=".repeat(context.name.len()));
+    say!();
+    say!("{}", context.description);
+    say!();
 
     if context.bindings.is_empty() {
-        println!("(No keybindings defined for this context)");
+        say!("(No keybindings defined for this context)");
         return Ok(());
     }
 
@@ -83,8 +110,8 @@ fn display_topic(topic: &str, help_data: &HelpData) -> CliResult<()> {
     sorted_bindings.sort_by_key(|b| b.priority);
 
     // Display bindings
-    println!("Keybindings:");
-    println!();
+    say!("Keybindings:");
+    say!();
 
     for binding in sorted_bindings {
         let keys_str = if binding.alt_keys.is_empty() {
@@ -97,16 +124,16 @@ fn display_topic(topic: &str, help_data: &HelpData) -> CliResult<()> {
             )
         };
 
-        println!("  {}  →  {}", keys_str, binding.action);
+        say!("  {}  →  {}", keys_str, binding.action);
     }
 
     Ok(())
 }
 
 fn list_all_topics(help_data: &HelpData) {
-    println!("Available Help Topics");
-    println!("====================");
-    println!();
+    say!("Available Help Topics");
+    say!("====================");
+    say!();
 
     // Group contexts by category (main vs other)
     let mut main_contexts = Vec::new();
@@ -125,32 +152,32 @@ fn list_all_topics(help_data: &HelpData) {
 
     // Display main context first
     if !main_contexts.is_empty() {
-        println!("Core Navigation:");
+        say!("Core Navigation:");
         for (key, context) in main_contexts {
-            println!("  {}  -  {}", key, context.description);
+            say!("  {}  -  {}", key, context.description);
         }
-        println!();
+        say!();
     }
 
     // Display UI contexts
     if !ui_contexts.is_empty() {
-        println!("UI Dialogs & Editors:");
+        say!("UI Dialogs & Editors:");
         for (key, context) in ui_contexts {
-            println!("  {}  -  {}", key, context.description);
+            say!("  {}  -  {}", key, context.description);
         }
-        println!();
+        say!();
     }
 
     // Display informational contexts
     if !info_contexts.is_empty() {
-        println!("Reference Information:");
+        say!("Reference Information:");
         for (key, context) in info_contexts {
-            println!("  {}  -  {}", key, context.description);
+            say!("  {}  -  {}", key, context.description);
         }
-        println!();
+        say!();
     }
 
-    println!("Usage: lazyqmk help <topic>");
-    println!("       lazyqmk help main");
-    println!("       lazyqmk help keycode_picker");
+    say!("Usage: lazyqmk help <topic>");
+    say!("       lazyqmk help main");
+    say!("       lazyqmk help keycode_picker");
 }
@@ -0,0 +1,152 @@
+//! Import command: convert an existing QMK keymap.c or keymap.json into a
+//! layout file.
+
+use crate::cli::common::{print_json, CliError, CliResult};
+use crate::config::Config;
+use crate::models::LayoutMetadata;
+use crate::parser::{keymap_c, qmk_json};
+use crate::services::geometry;
+use crate::services::LayoutService;
+use clap::Args;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+/// Import an existing QMK keymap.c or keymap.json as a layout
+#[derive(Debug, Clone, Args)]
+pub struct ImportArgs {
+    /// Path to the keymap.c or QMK keymap.json (`.json`) to import
+    #[arg(short, long, value_name = "FILE")]
+    pub keymap: PathBuf,
+
+    /// Keyboard the keymap belongs to (e.g., "crkbd/rev1"; defaults to the
+    /// keyboard named in a keymap.json)
+    #[arg(long, value_name = "KEYBOARD")]
+    pub keyboard: Option<String>,
+
+    /// Layout variant (defaults to the LAYOUT macro used in the keymap)
+    #[arg(long, value_name = "VARIANT")]
+    pub layout_variant: Option<String>,
+
+    /// Output layout file
+    #[arg(short, long, value_name = "FILE")]
+    pub out: PathBuf,
+
+    /// Layout name (defaults to the output file name)
+    #[arg(short, long, value_name = "NAME")]
+    pub name: Option<String>,
+
+    /// Path to QMK firmware repository (defaults to the configured path)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// JSON response for the import command
+#[derive(Debug, Serialize)]
+struct ImportResponse {
+    output: String,
+    layout_variant: String,
+    layers: Vec<String>,
+    tap_dances: usize,
+    warnings: Vec<String>,
+}
+
+impl ImportArgs {
+    /// Execute the import command
+    pub fn execute(&self) -> CliResult<()> {
+        if self.out.exists() {
+            return Err(CliError::validation(format!(
+                "Output file already exists: {}",
+                self.out.display()
+            )));
+        }
+
+        let source = fs::read_to_string(&self.keymap)
+            .map_err(|e| CliError::io(format!("Failed to read {}: {e}", self.keymap.display())))?;
+        let is_json = self
+            .keymap
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let (parsed, json) = if is_json {
+            let json = qmk_json::parse_qmk_json(&source)
+                .map_err(|e| CliError::validation(format!("Failed to parse keymap: {e:#}")))?;
+            (json.keymap.clone(), Some(json))
+        } else {
+            let parsed = keymap_c::parse_keymap_c(&source)
+                .map_err(|e| CliError::validation(format!("Failed to parse keymap: {e:#}")))?;
+            (parsed, None)
+        };
+        let keyboard = self
+            .keyboard
+            .clone()
+            .or_else(|| json.as_ref().and_then(|json| json.keyboard.clone()))
+            .ok_or_else(|| {
+                CliError::validation("Keyboard not specified. Use --keyboard <KEYBOARD>")
+            })?;
+
+        let name = self.name.clone().unwrap_or_else(|| {
+            self.out.file_stem().map_or_else(
+                || "Imported".to_string(),
+                |s| s.to_string_lossy().to_string(),
+            )
+        });
+        let variant = self
+            .layout_variant
+            .clone()
+            .unwrap_or_else(|| parsed.layout_macro.clone());
+
+        let mut config = Config::load().unwrap_or_default();
+        if let Some(qmk_path) = &self.qmk_path {
+            config.pin_qmk_path(qmk_path.clone());
+        }
+        let mut metadata =
+            LayoutMetadata::new(&name).map_err(|e| CliError::validation(e.to_string()))?;
+        metadata.keyboard = Some(keyboard.clone());
+        let geo_context = geometry::GeometryContext {
+            config: &config,
+            metadata: &metadata,
+        };
+        let geometry = geometry::build_geometry_for_layout(geo_context, &variant)
+            .map_err(|e| CliError::io(format!("Failed to build geometry: {e}")))?
+            .geometry;
+
+        let layout = json
+            .as_ref()
+            .map_or_else(
+                || parsed.to_layout(&name, &geometry),
+                |json| json.to_layout(&name, &geometry),
+            )
+            .map_err(|e| CliError::validation(format!("Failed to import keymap: {e:#}")))?;
+        LayoutService::save(&layout, &self.out)
+            .map_err(|e| CliError::io(format!("Failed to save layout: {e}")))?;
+
+        if self.json {
+            let response = ImportResponse {
+                output: self.out.display().to_string(),
+                layout_variant: variant,
+                layers: layout.layers.iter().map(|l| l.name.clone()).collect(),
+                tap_dances: layout.tap_dances.len(),
+                warnings: parsed.warnings,
+            };
+            print_json(&response)?;
+            return Ok(());
+        }
+
+        for warning in &parsed.warnings {
+            eprintln!("Warning: {warning}");
+        }
+        say!(
+            "✓ Imported {} layer(s) and {} tap dance(s) for {} ({variant})",
+            layout.layers.len(),
+            layout.tap_dances.len(),
+            keyboard
+        );
+        say!("  Output: {}", self.out.display());
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 //! Inspect command for reading layout sections.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult};
 use crate::services::LayoutService;
 use clap::Args;
 use serde::Serialize;
@@ -120,30 +120,26 @@ impl InspectArgs {
                 };
 
                 if self.json {
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&section)
-                            .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-                    );
+                    print_json(&section)?;
                 } else {
-                    println!("Metadata:");
-                    println!("  Name:           {}", section.name);
+                    say!("Metadata:");
+                    say!("  Name:           {}", section.name);
                     if !section.author.is_empty() {
-                        println!("  Author:         {}", section.author);
+                        say!("  Author:         {}", section.author);
                     }
                     if let Some(keyboard) = section.keyboard {
-                        println!("  Keyboard:       {}", keyboard);
+                        say!("  Keyboard:       {}", keyboard);
                     }
                     if let Some(layout_variant) = section.layout_variant {
-                        println!("  Layout Variant: {}", layout_variant);
+                        say!("  Layout Variant: {}", layout_variant);
                     }
                     if let Some(keymap_name) = section.keymap_name {
-                        println!("  Keymap Name:    {}", keymap_name);
+                        say!("  Keymap Name:    {}", keymap_name);
                     }
-                    println!("  Created:        {}", section.created);
-                    println!("  Modified:       {}", section.modified);
+                    say!("  Created:        {}", section.created);
+                    say!("  Modified:       {}", section.modified);
                     if !section.tags.is_empty() {
-                        println!("  Tags:           {}", section.tags.join(", "));
+                        say!("  Tags:           {}", section.tags.join(", "));
                     }
                 }
             }
@@ -165,17 +161,15 @@ impl InspectArgs {
                 };
 
                 if self.json {
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&section)
-                            .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-                    );
+                    print_json(&section)?;
                 } else {
-                    println!("Layers ({} total):", section.count);
+                    say!("Layers ({} total):", section.count);
                     for layer in &section.layers {
-                        println!(
+                        say!(
                             "  [{}] {} ({} keys)",
-                            layer.number, layer.name, layer.key_count
+                            layer.number,
+                            layer.name,
+                            layer.key_count
                         );
                     }
                 }
@@ -200,15 +194,11 @@ impl InspectArgs {
                 };
 
                 if self.json {
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&section)
-                            .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-                    );
+                    print_json(&section)?;
                 } else {
-                    println!("Categories ({} total):", section.count);
+                    say!("Categories ({} total):", section.count);
                     for cat in &section.categories {
-                        println!("  {} - {} ({})", cat.id, cat.name, cat.color);
+                        say!("  {} - {} ({})", cat.id, cat.name, cat.color);
                     }
                 }
             }
@@ -237,21 +227,17 @@ impl InspectArgs {
                 };
 
                 if self.json {
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&section)
-                            .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-                    );
+                    print_json(&section)?;
                 } else {
-                    println!("Tap Dances ({} total):", section.count);
+                    say!("Tap Dances ({} total):", section.count);
                     for td in &section.tap_dances {
-                        println!("  {} ({}):", td.name, td.td_type);
-                        println!("    Single: {}", td.single_tap);
+                        say!("  {} ({}):", td.name, td.td_type);
+                        say!("    Single: {}", td.single_tap);
                         if let Some(double) = &td.double_tap {
-                            println!("    Double: {}", double);
+                            say!("    Double: {}", double);
                         }
                         if let Some(hold) = &td.hold {
-                            println!("    Hold:   {}", hold);
+                            say!("    Hold:   {}", hold);
                         }
                     }
                 }
@@ -268,21 +254,17 @@ impl InspectArgs {
                 };
 
                 if self.json {
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&section)
-                            .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-                    );
+                    print_json(&section)?;
                 } else {
-                    println!("RGB Settings:");
-                    println!("  Enabled:    {}", section.rgb_enabled);
-                    println!("  Brightness: {}%", section.rgb_brightness);
-                    println!("  Timeout:    {}ms", section.rgb_timeout_ms);
-                    println!("\nIdle Effect Settings:");
-                    println!("  Enabled:  {}", section.idle_effect_enabled);
-                    println!("  Timeout:  {}ms", section.idle_effect_timeout_ms);
-                    println!("  Duration: {}ms", section.idle_effect_duration_ms);
-                    println!("  Mode:     {}", section.idle_effect_mode);
+                    say!("RGB Settings:");
+                    say!("  Enabled:    {}", section.rgb_enabled);
+                    say!("  Brightness: {}%", section.rgb_brightness);
+                    say!("  Timeout:    {}ms", section.rgb_timeout_ms);
+                    say!("\nIdle Effect Settings:");
+                    say!("  Enabled:  {}", section.idle_effect_enabled);
+                    say!("  Timeout:  {}ms", section.idle_effect_timeout_ms);
+                    say!("  Duration: {}ms", section.idle_effect_duration_ms);
+                    say!("  Mode:     {}", section.idle_effect_mode);
                 }
             }
             _ => unreachable!("Section already validated"),
@@ -1,6 +1,6 @@
 //! Keycode resolution command.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult};
 use crate::keycode_db::KeycodeDb;
 use crate::services::LayoutService;
 use clap::Args;
@@ -68,21 +68,17 @@ impl KeycodeArgs {
         };
 
         if self.json {
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&result)
-                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-            );
+            print_json(&result)?;
         } else {
-            println!("Input:    {}", result.input);
-            println!("Resolved: {}", result.resolved);
+            say!("Input:    {}", result.input);
+            say!("Resolved: {}", result.resolved);
             if let Some(name) = result.layer_name {
-                println!("Layer:    {}", name);
+                say!("Layer:    {}", name);
             }
             if result.valid {
-                println!("Status:   ✓ Valid");
+                say!("Status:   ✓ Valid");
             } else {
-                println!("Status:   ✗ Could not resolve");
+                say!("Status:   ✗ Could not resolve");
             }
         }
 
@@ -1,6 +1,6 @@
 //! Keycodes utility command for listing available keycodes.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult};
 use crate::keycode_db::KeycodeDb;
 use clap::Args;
 use serde::Serialize;
@@ -83,11 +83,7 @@ impl KeycodesArgs {
             keycodes: output_keycodes,
         };
 
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&response)
-                .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-        );
+        print_json(&response)?;
 
         Ok(())
     }
@@ -95,7 +91,7 @@ impl KeycodesArgs {
     /// Output keycodes as a formatted table
     fn output_table(&self, keycodes: &[&crate::keycode_db::KeycodeDefinition]) {
         if keycodes.is_empty() {
-            println!("No keycodes found.");
+            say!("No keycodes found.");
             return;
         }
 
@@ -122,7 +118,7 @@ impl KeycodesArgs {
             .max(8); // At least "CATEGORY"
 
         // Print header
-        println!(
+        say!(
             "{:<width_code$}  {:<width_label$}  {:<width_category$}  DESCRIPTION",
             "CODE",
             "LABEL",
@@ -133,7 +129,7 @@ impl KeycodesArgs {
         );
 
         // Print separator
-        println!(
+        say!(
             "{}  {}  {}  {}",
             "─".repeat(max_code_len),
             "─".repeat(max_label_len),
@@ -149,7 +145,7 @@ impl KeycodesArgs {
                 .map(|d| d.chars().take(50).collect::<String>())
                 .unwrap_or_default();
 
-            println!(
+            say!(
                 "{:<width_code$}  {:<width_label$}  {:<width_category$}  {}",
                 keycode.code,
                 keycode.name,
@@ -161,7 +157,7 @@ impl KeycodesArgs {
             );
         }
 
-        println!();
-        println!("Total: {} keycodes", keycodes.len());
+        say!();
+        say!("Total: {} keycodes", keycodes.len());
     }
 }
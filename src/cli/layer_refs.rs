@@ -1,6 +1,6 @@
 //! Layer references command for displaying inbound layer references and transparency warnings.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliResult};
 use crate::services::layer_refs::{build_layer_ref_index, is_transparent};
 use crate::services::LayoutService;
 use clap::Args;
@@ -129,24 +129,20 @@ impl LayerRefsArgs {
                 layers: layers_data,
             };
 
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&response)
-                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-            );
+            print_json(&response)?;
         } else {
             // Human-readable text output
             for (layer_idx, layer) in layout.layers.iter().enumerate() {
-                println!("Layer {}: {}", layer_idx, layer.name);
+                say!("Layer {}: {}", layer_idx, layer.name);
 
                 // Get inbound references
                 if let Some(refs) = layer_ref_index.get(&layer_idx) {
                     if refs.is_empty() {
-                        println!("  No inbound references");
+                        say!("  No inbound references");
                     } else {
-                        println!("  Inbound References:");
+                        say!("  Inbound References:");
                         for r in refs {
-                            println!(
+                            say!(
                                 "    - Layer {} [{},{}] {} ({}): {}",
                                 r.from_layer,
                                 r.position.row,
@@ -158,7 +154,7 @@ impl LayerRefsArgs {
                         }
                     }
                 } else {
-                    println!("  No inbound references");
+                    say!("  No inbound references");
                 }
 
                 // Check for warnings
@@ -174,10 +170,10 @@ impl LayerRefsArgs {
                         // Check if the key is non-transparent
                         if !is_transparent(&target_key.keycode) {
                             if !has_warnings {
-                                println!("  Warnings:");
+                                say!("  Warnings:");
                                 has_warnings = true;
                             }
-                            println!(
+                            say!(
                                 "    - Position [{},{}]: Non-transparent key ({}) conflicts with hold-like reference from Layer {} {}",
                                 r.position.row,
                                 r.position.col,
@@ -189,7 +185,7 @@ impl LayerRefsArgs {
                     }
                 }
 
-                println!();
+                say!();
             }
         }
 
@@ -2,36 +2,65 @@
 //!
 //! This module provides headless, scriptable access to `LazyQMK`'s core functionality
 //! for automation, testing, and CI/CD integration.
+//!
+//! Every command exits with a [`ExitCode`] that tells scripts what went
+//! wrong. Human-readable output goes through [`say!`] so `--quiet` can
+//! silence it, while `--json` responses and errors are always printed.
+
+/// Prints a line of human-readable output unless `--quiet` was given.
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if !$crate::cli::common::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
 
+pub mod analyze;
+pub mod batch;
+pub mod build;
+pub mod bundle;
 pub mod category;
+pub mod collect_usage;
 pub mod common;
 pub mod config;
 pub mod doctor;
 pub mod export;
 pub mod generate;
 pub mod help;
+pub mod import;
 pub mod inspect;
 pub mod keycode;
 pub mod keycodes;
 pub mod layer_refs;
+pub mod optimize_thumbs;
 pub mod qmk;
+pub mod refresh;
 pub mod tap_dance;
 pub mod template;
 pub mod validate;
 
 // Re-export types used by main.rs and tests
+pub use analyze::AnalyzeArgs;
+pub use batch::BatchArgs;
+pub use build::BuildArgs;
+pub use bundle::BundleArgs;
 pub use category::CategoryArgs;
+pub use collect_usage::CollectUsageArgs;
 pub use common::ExitCode;
 pub use config::ConfigArgs;
 pub use doctor::DoctorArgs;
 pub use export::ExportArgs;
 pub use generate::GenerateArgs;
 pub use help::HelpArgs;
+pub use import::ImportArgs;
 pub use inspect::InspectArgs;
 pub use keycode::KeycodeArgs;
 pub use keycodes::KeycodesArgs;
 pub use layer_refs::LayerRefsArgs;
+pub use optimize_thumbs::OptimizeThumbsArgs;
 pub use qmk::{GeometryArgs, ListKeyboardsArgs, ListLayoutsArgs};
+pub use refresh::RefreshArgs;
 pub use tap_dance::TapDanceArgs;
 pub use template::TemplateArgs;
 pub use validate::ValidateArgs;
@@ -0,0 +1,223 @@
+//! Optimize-thumbs command: suggest hold key placements for a text corpus.
+
+use crate::cli::common::{print_json, CliError, CliResult};
+use crate::config::Config;
+use crate::models::{Position, PositionNames};
+use crate::services::geometry;
+use crate::services::thumb_optimizer::{self, CorpusStats, SwapSuggestion, ThumbScore};
+use crate::services::LayoutService;
+use clap::Args;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+/// Suggest thumb and hold key swaps that reduce same-hand holds
+#[derive(Debug, Clone, Args)]
+pub struct OptimizeThumbsArgs {
+    /// Path to layout file
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Corpus to optimize for: plain text, or JSON statistics
+    /// (`{"chars": {"a": 10}, "bigrams": {"th": 5}}`) when the file ends in `.json`
+    #[arg(short, long, value_name = "FILE")]
+    pub corpus: PathBuf,
+
+    /// Path to QMK firmware repository (defaults to the configured path)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+
+    /// Maximum number of suggestions to show
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    pub limit: usize,
+
+    /// Apply suggestions by number (e.g. `1,3`) and save the layout
+    #[arg(long, value_name = "N", value_delimiter = ',')]
+    pub apply: Vec<usize>,
+
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Score data for JSON output
+#[derive(Debug, Serialize)]
+struct ScoreData {
+    same_hand_holds: u64,
+    roll_conflicts: u64,
+    total: u64,
+}
+
+impl From<ThumbScore> for ScoreData {
+    fn from(score: ThumbScore) -> Self {
+        Self {
+            same_hand_holds: score.same_hand_holds,
+            roll_conflicts: score.roll_conflicts,
+            total: score.total(),
+        }
+    }
+}
+
+/// Swapped key for JSON output
+#[derive(Debug, Serialize)]
+struct SwapKeyData {
+    row: u8,
+    col: u8,
+    name: String,
+    keycode: String,
+}
+
+/// Suggestion data for JSON output
+#[derive(Debug, Serialize)]
+struct SuggestionData {
+    number: usize,
+    first: SwapKeyData,
+    second: SwapKeyData,
+    improvement: u64,
+    score: ScoreData,
+}
+
+/// JSON response for the optimize-thumbs command
+#[derive(Debug, Serialize)]
+struct OptimizeThumbsResponse {
+    score: ScoreData,
+    suggestions: Vec<SuggestionData>,
+    applied: Vec<usize>,
+}
+
+impl OptimizeThumbsArgs {
+    /// Execute the optimize-thumbs command
+    pub fn execute(&self) -> CliResult<()> {
+        let mut layout = LayoutService::load(&self.layout)
+            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+
+        let corpus = fs::read_to_string(&self.corpus).map_err(|e| {
+            CliError::io(format!(
+                "Failed to read corpus {}: {e}",
+                self.corpus.display()
+            ))
+        })?;
+        let stats = if self.corpus.extension().is_some_and(|ext| ext == "json") {
+            CorpusStats::from_json(&corpus).map_err(|e| CliError::validation(format!("{e:#}")))?
+        } else {
+            CorpusStats::from_text(&corpus)
+        };
+
+        let mut config = Config::load().unwrap_or_default();
+        if let Some(qmk_path) = &self.qmk_path {
+            config.pin_qmk_path(qmk_path.clone());
+        }
+        let layout_variant = layout
+            .metadata
+            .layout_variant
+            .clone()
+            .ok_or_else(|| CliError::validation("Layout variant not specified in metadata"))?;
+        let geo_context = geometry::GeometryContext {
+            config: &config,
+            metadata: &layout.metadata,
+        };
+        let geometry = geometry::build_geometry_for_layout(geo_context, &layout_variant)
+            .map_err(|e| CliError::io(format!("Failed to build geometry: {e}")))?
+            .geometry;
+
+        let report = thumb_optimizer::analyze(&layout, &geometry, &stats);
+        let suggestions: Vec<&SwapSuggestion> =
+            report.suggestions.iter().take(self.limit).collect();
+
+        // Validate the selection before touching the layout
+        let mut used: Vec<Position> = Vec::new();
+        for &number in &self.apply {
+            let swap = number
+                .checked_sub(1)
+                .and_then(|idx| suggestions.get(idx))
+                .ok_or_else(|| {
+                    CliError::validation(format!(
+                        "No suggestion #{number} (there are {})",
+                        suggestions.len()
+                    ))
+                })?;
+            if used.contains(&swap.first) || used.contains(&swap.second) {
+                return Err(CliError::validation(format!(
+                    "Suggestion #{number} moves a key already moved by another selected suggestion"
+                )));
+            }
+            used.extend([swap.first, swap.second]);
+        }
+        for &number in &self.apply {
+            thumb_optimizer::apply_swap(&mut layout, suggestions[number - 1])
+                .map_err(|e| CliError::validation(format!("Suggestion #{number}: {e}")))?;
+        }
+        if !self.apply.is_empty() {
+            LayoutService::save(&layout, &self.layout)
+                .map_err(|e| CliError::io(format!("Failed to save layout: {e}")))?;
+        }
+
+        let names = PositionNames::build(&geometry);
+        if self.json {
+            let response = OptimizeThumbsResponse {
+                score: report.score.into(),
+                suggestions: suggestions
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, swap)| SuggestionData {
+                        number: idx + 1,
+                        first: swap_key(&names, swap.first, &swap.first_keycode),
+                        second: swap_key(&names, swap.second, &swap.second_keycode),
+                        improvement: swap.improvement,
+                        score: swap.score.into(),
+                    })
+                    .collect(),
+                applied: self.apply.clone(),
+            };
+            print_json(&response)?;
+            return Ok(());
+        }
+
+        say!(
+            "Current score: {} (same-hand holds: {}, roll conflicts: {})",
+            report.score.total(),
+            report.score.same_hand_holds,
+            report.score.roll_conflicts
+        );
+        if suggestions.is_empty() {
+            say!("\nNo swap improves this layout for the given corpus.");
+            return Ok(());
+        }
+
+        say!("\nSuggested swaps:");
+        for (idx, swap) in suggestions.iter().enumerate() {
+            say!(
+                "  {}. {} {} <-> {} {}  (-{}, new score {})",
+                idx + 1,
+                names.describe(swap.first),
+                swap.first_keycode,
+                names.describe(swap.second),
+                swap.second_keycode,
+                swap.improvement,
+                swap.score.total()
+            );
+        }
+
+        if self.apply.is_empty() {
+            say!("\nApply with --apply <numbers>, e.g. --apply 1");
+        } else {
+            say!(
+                "\n✓ Applied {} swap(s) to {}",
+                self.apply.len(),
+                self.layout.display()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Builds JSON data for one side of a swap.
+fn swap_key(names: &PositionNames, position: Position, keycode: &str) -> SwapKeyData {
+    SwapKeyData {
+        row: position.row,
+        col: position.col,
+        name: names.describe(position),
+        keycode: keycode.to_string(),
+    }
+}
@@ -1,10 +1,12 @@
 //! `qmk geometry` — display matrix/LED/visual coordinate mappings.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult};
+use crate::config::Config;
 use crate::parser::keyboard_json::{
-    build_keyboard_geometry_with_rgb, build_matrix_to_led_map, parse_keyboard_info_json,
+    build_keyboard_geometry_with_rgb, build_led_geometry, parse_keyboard_info_json,
     parse_variant_keyboard_json,
 };
+use crate::services::split_leds;
 use clap::Args;
 use serde::Serialize;
 use std::path::PathBuf;
@@ -42,6 +44,20 @@ struct GeometryResponse {
     key_count: usize,
     /// Coordinate mappings
     mappings: Vec<KeyMapping>,
+    /// LEDs without a key (underglow, indicators)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    non_key_leds: Vec<NonKeyLed>,
+    /// Split LEDs moved to the matching key on their own half
+    #[serde(skip_serializing_if = "is_zero")]
+    corrected_split_leds: usize,
+    /// Split LED mapping problems that could not be corrected
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    split_led_warnings: Vec<String>,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // serde's skip_serializing_if passes a reference
+const fn is_zero(value: &usize) -> bool {
+    *value == 0
 }
 
 /// Matrix dimension information
@@ -66,6 +82,17 @@ struct KeyMapping {
     visual_position: [f32; 2],
 }
 
+/// LED with no key above it
+#[derive(Debug, Clone, Serialize)]
+struct NonKeyLed {
+    /// LED index
+    led_index: u8,
+    /// QMK LED coordinates (x, y)
+    led_position: [u8; 2],
+    /// QMK LED flags
+    flags: u8,
+}
+
 impl GeometryArgs {
     /// Execute the geometry command
     pub fn execute(&self) -> CliResult<()> {
@@ -84,8 +111,11 @@ impl GeometryArgs {
             )));
         }
 
+        // Keyboards may also live in the configured extra roots
+        let keyboard_roots = Config::load().unwrap_or_default().paths.keyboard_roots;
+
         // Parse keyboard info.json
-        let info = parse_keyboard_info_json(&qmk_path, &self.keyboard)
+        let info = parse_keyboard_info_json(&qmk_path, &keyboard_roots, &self.keyboard)
             .map_err(|e| CliError::validation(format!("Invalid keyboard: {e}")))?;
 
         // Check if layout exists
@@ -98,15 +128,27 @@ impl GeometryArgs {
         }
 
         // Try to load RGB matrix mapping for accurate LED indices
-        let matrix_to_led = parse_variant_keyboard_json(&qmk_path, &self.keyboard)
-            .and_then(|variant| variant.rgb_matrix.map(|rgb| build_matrix_to_led_map(&rgb)));
+        let variant = parse_variant_keyboard_json(&qmk_path, &keyboard_roots, &self.keyboard);
+        let split = variant
+            .as_ref()
+            .and_then(|variant| variant.split.clone())
+            .or_else(|| info.split.clone());
+        let rgb_matrix = variant.and_then(|variant| variant.rgb_matrix);
+        let split_map = rgb_matrix.as_ref().map(|rgb| {
+            split_leds::split_matrix_to_led(
+                rgb,
+                split.as_ref(),
+                split_leds::matrix_rows(&info.layouts[&self.layout_name]),
+            )
+        });
+        let matrix_to_led = split_map.as_ref().map(|map| &map.matrix_to_led);
 
         // Build keyboard geometry
         let geometry = build_keyboard_geometry_with_rgb(
             &info,
             &self.keyboard,
             &self.layout_name,
-            matrix_to_led.as_ref(),
+            matrix_to_led,
         )
         .map_err(|e| CliError::validation(format!("Invalid layout: {e}")))?;
 
@@ -122,6 +164,19 @@ impl GeometryArgs {
             })
             .collect();
 
+        let non_key_leds: Vec<NonKeyLed> = rgb_matrix
+            .as_ref()
+            .map(build_led_geometry)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|led| !led.is_key_led())
+            .map(|led| NonKeyLed {
+                led_index: led.led_index,
+                led_position: [led.x, led.y],
+                flags: led.flags,
+            })
+            .collect();
+
         let response = GeometryResponse {
             keyboard: self.keyboard.clone(),
             layout: self.layout_name.clone(),
@@ -131,28 +186,32 @@ impl GeometryArgs {
             },
             key_count: geometry.keys.len(),
             mappings,
+            non_key_leds,
+            corrected_split_leds: split_map.as_ref().map_or(0, |map| map.corrected),
+            split_led_warnings: split_map
+                .iter()
+                .flat_map(|map| &map.issues)
+                .map(split_leds::SplitLedIssue::message)
+                .collect(),
         };
 
         // Output results
         if self.json {
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&response)
-                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-            );
+            print_json(&response)?;
         } else {
-            println!("Keyboard: {}", response.keyboard);
-            println!("Layout: {}", response.layout);
-            println!(
+            say!("Keyboard: {}", response.keyboard);
+            say!("Layout: {}", response.layout);
+            say!(
                 "\nMatrix: {}x{}",
-                response.matrix.rows, response.matrix.cols
+                response.matrix.rows,
+                response.matrix.cols
             );
-            println!("Keys: {}", response.key_count);
-            println!("\nCoordinate Mappings:");
-            println!("  Visual | Matrix    | LED | Position");
-            println!("  -------|-----------|-----|----------");
+            say!("Keys: {}", response.key_count);
+            say!("\nCoordinate Mappings:");
+            say!("  Visual | Matrix    | LED | Position");
+            say!("  -------|-----------|-----|----------");
             for mapping in &response.mappings {
-                println!(
+                say!(
                     "  {:6} | ({:2}, {:2}) | {:3} | ({:5.1}, {:5.1})",
                     mapping.visual_index,
                     mapping.matrix[0],
@@ -162,6 +221,29 @@ impl GeometryArgs {
                     mapping.visual_position[1]
                 );
             }
+            if response.corrected_split_leds > 0 {
+                say!(
+                    "\nCorrected {} right-half LEDs that used left-half matrix rows (or vice versa)",
+                    response.corrected_split_leds
+                );
+            }
+            for warning in &response.split_led_warnings {
+                say!("⚠ {warning}");
+            }
+            if !response.non_key_leds.is_empty() {
+                say!("\nLEDs without keys:");
+                say!("  LED | Position   | Flags");
+                say!("  ----|------------|------");
+                for led in &response.non_key_leds {
+                    say!(
+                        "  {:3} | ({:3}, {:3}) | {}",
+                        led.led_index,
+                        led.led_position[0],
+                        led.led_position[1],
+                        led.flags
+                    );
+                }
+            }
         }
 
         Ok(())
@@ -1,10 +1,11 @@
 //! `qmk list-keyboards` — list compilable keyboards.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult};
+use crate::config::Config;
+use crate::parser::keyboard_json::scan_keyboard_root;
 use clap::Args;
 use regex::Regex;
 use serde::Serialize;
-use std::fs;
 use std::path::PathBuf;
 
 /// List all compilable keyboards in QMK firmware directory
@@ -58,9 +59,18 @@ impl ListKeyboardsArgs {
             )));
         }
 
-        // Scan keyboards directory recursively
-        let mut keyboards = scan_keyboards_directory(&keyboards_dir)
+        // Scan keyboards directory recursively, plus the configured extra roots
+        let keyboard_roots = Config::load().unwrap_or_default().paths.keyboard_roots;
+        let mut keyboards = scan_keyboard_root(&keyboards_dir)
             .map_err(|e| CliError::io(format!("Failed to scan keyboards directory: {e}")))?;
+        for root in &keyboard_roots {
+            keyboards.extend(scan_keyboard_root(root).map_err(|e| {
+                CliError::io(format!(
+                    "Failed to scan keyboard root {}: {e}",
+                    root.display()
+                ))
+            })?);
+        }
 
         if keyboards.is_empty() {
             return Err(CliError::validation("No keyboards found in QMK directory"));
@@ -82,6 +92,7 @@ impl ListKeyboardsArgs {
 
         // Sort alphabetically for consistent ordering
         keyboards.sort();
+        keyboards.dedup();
 
         // Output results
         if self.json {
@@ -89,68 +100,14 @@ impl ListKeyboardsArgs {
                 keyboards: keyboards.clone(),
                 count: keyboards.len(),
             };
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&response)
-                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-            );
+            print_json(&response)?;
         } else {
-            println!("Found {} keyboards:", keyboards.len());
+            say!("Found {} keyboards:", keyboards.len());
             for kb in &keyboards {
-                println!("  {kb}");
-            }
-        }
-
-        Ok(())
-    }
-}
-
-/// Scans the keyboards directory recursively for keyboards with info.json or keyboard.json
-fn scan_keyboards_directory(keyboards_dir: &PathBuf) -> anyhow::Result<Vec<String>> {
-    use anyhow::Context;
-    use std::collections::HashSet;
-
-    let mut keyboards = HashSet::new();
-
-    fn visit_directory(
-        dir: &std::path::Path,
-        keyboards_root: &std::path::Path,
-        keyboards: &mut HashSet<String>,
-    ) -> anyhow::Result<()> {
-        let entries =
-            fs::read_dir(dir).context(format!("Failed to read directory: {}", dir.display()))?;
-
-        for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.is_dir() {
-                // Skip hidden directories and common non-keyboard directories
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                    if name.starts_with('.') || name == "lib" || name == "template" {
-                        continue;
-                    }
-                }
-                // Recursively visit subdirectories
-                visit_directory(&path, keyboards_root, keyboards)?;
-            } else if path.is_file() {
-                let file_name = path.file_name().and_then(|n| n.to_str());
-                if file_name == Some("info.json") || file_name == Some("keyboard.json") {
-                    // Found a keyboard config file - compute relative path
-                    if let Ok(rel_path) = path.parent().unwrap().strip_prefix(keyboards_root) {
-                        let keyboard_name = rel_path.to_str().unwrap_or("").replace('\\', "/");
-                        if !keyboard_name.is_empty() {
-                            keyboards.insert(keyboard_name);
-                        }
-                    }
-                }
+                say!("  {kb}");
             }
         }
 
         Ok(())
     }
-
-    visit_directory(keyboards_dir, keyboards_dir, &mut keyboards)?;
-
-    Ok(keyboards.into_iter().collect())
 }
@@ -1,6 +1,7 @@
 //! `qmk list-layouts` — list layout variants for a keyboard.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult};
+use crate::config::Config;
 use crate::parser::keyboard_json::{
     discover_keyboard_config, extract_layout_variants, parse_keyboard_info_json,
 };
@@ -62,8 +63,11 @@ impl ListLayoutsArgs {
             )));
         }
 
+        // Keyboards may also live in the configured extra roots
+        let keyboard_roots = Config::load().unwrap_or_default().paths.keyboard_roots;
+
         // Discover keyboard config files
-        let config = discover_keyboard_config(&qmk_path, &self.keyboard)
+        let config = discover_keyboard_config(&qmk_path, &keyboard_roots, &self.keyboard)
             .map_err(|e| CliError::validation(format!("Keyboard not found: {e}")))?;
 
         if !config.has_layouts {
@@ -74,7 +78,7 @@ impl ListLayoutsArgs {
         }
 
         // Parse keyboard info.json
-        let info = parse_keyboard_info_json(&qmk_path, &self.keyboard)
+        let info = parse_keyboard_info_json(&qmk_path, &keyboard_roots, &self.keyboard)
             .map_err(|e| CliError::io(format!("Failed to parse keyboard info: {e}")))?;
 
         // Extract layout variants with key counts
@@ -103,16 +107,12 @@ impl ListLayoutsArgs {
                 count: variants.len(),
             };
 
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&response)
-                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-            );
+            print_json(&response)?;
         } else {
-            println!("Keyboard: {}", self.keyboard);
-            println!("\nAvailable layouts ({}):", variants.len());
+            say!("Keyboard: {}", self.keyboard);
+            say!("\nAvailable layouts ({}):", variants.len());
             for variant in &variants {
-                println!("  {} ({} keys)", variant.name, variant.key_count);
+                say!("  {} ({} keys)", variant.name, variant.key_count);
             }
         }
 
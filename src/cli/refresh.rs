@@ -0,0 +1,343 @@
+//! Refresh command: update QMK, re-validate every workspace layout, and
+//! optionally regenerate firmware.
+
+use crate::cli::common::{find_layout_files, print_json, resolve_workspace, CliError, CliResult};
+use crate::config::Config;
+use crate::firmware::generator::FirmwareGenerator;
+use crate::firmware::validator::FirmwareValidator;
+use crate::keycode_db::KeycodeDb;
+use crate::models::Layout;
+use crate::services::geometry::{self, GeometryContext, GeometryResult};
+use crate::services::LayoutService;
+use clap::Args;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Update the QMK checkout and re-check all layouts against it
+#[derive(Debug, Clone, Args)]
+pub struct RefreshArgs {
+    /// Directory containing layout files (defaults to the layouts directory)
+    #[arg(short, long, value_name = "DIR")]
+    pub workspace: Option<PathBuf>,
+
+    /// Path to QMK firmware repository (defaults to the configured path)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+
+    /// Skip `git pull` and only re-validate against the current checkout
+    #[arg(long)]
+    pub no_update: bool,
+
+    /// Regenerate firmware files for every layout that still validates
+    #[arg(long)]
+    pub generate: bool,
+
+    /// Archive directory for regenerated files (defaults to the configured output directory)
+    #[arg(short, long, value_name = "DIR")]
+    pub out_dir: Option<PathBuf>,
+
+    /// Output results as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Outcome of updating the QMK checkout.
+#[derive(Debug, Clone, Serialize)]
+pub struct QmkUpdate {
+    /// "updated", "up_to_date", or "skipped"
+    pub status: String,
+    /// Commit before the update
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Commit after the update
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Why the update was skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Re-check result for a single layout file.
+#[derive(Debug, Clone, Serialize)]
+pub struct LayoutRefresh {
+    /// Layout file name within the workspace
+    pub file: String,
+    /// "ok" or "broken"
+    pub status: String,
+    /// Why the layout no longer works with the keyboard definition
+    pub errors: Vec<String>,
+    /// Number of validation warnings
+    pub warnings: usize,
+    /// Archive directory of regenerated firmware files, if generated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generated: Option<String>,
+}
+
+/// JSON response for the refresh command.
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshResponse {
+    /// QMK checkout update
+    pub qmk: QmkUpdate,
+    /// Per-layout results, sorted by file name
+    pub layouts: Vec<LayoutRefresh>,
+    /// Number of broken layouts
+    pub broken: usize,
+}
+
+impl RefreshArgs {
+    /// Execute the refresh command
+    pub fn execute(&self) -> CliResult<()> {
+        let mut config = Config::load().unwrap_or_default();
+        if let Some(qmk_path) = &self.qmk_path {
+            config.pin_qmk_path(qmk_path.clone());
+        }
+        if let Some(out_dir) = &self.out_dir {
+            config.build.output_dir.clone_from(out_dir);
+        }
+        let qmk_path =
+            config.paths.qmk_firmware.clone().ok_or_else(|| {
+                CliError::config("QMK firmware path not configured. Use --qmk-path")
+            })?;
+        if !qmk_path.is_dir() {
+            return Err(CliError::io(format!(
+                "QMK firmware directory not found: {}",
+                qmk_path.display()
+            )));
+        }
+
+        let workspace = resolve_workspace(self.workspace.as_deref())?;
+
+        let qmk = if self.no_update {
+            QmkUpdate::skipped("--no-update")
+        } else {
+            update_qmk_checkout(&qmk_path)?
+        };
+
+        let keycode_db = KeycodeDb::load()
+            .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
+
+        let layouts = find_layout_files(&workspace)?
+            .iter()
+            .filter_map(|path| self.refresh_layout(path, &config, &keycode_db))
+            .collect::<Vec<_>>();
+        let broken = layouts.iter().filter(|l| l.status == "broken").count();
+
+        let response = RefreshResponse {
+            qmk,
+            layouts,
+            broken,
+        };
+
+        if self.json {
+            print_json(&response)?;
+        } else {
+            print_report(&response, &workspace);
+        }
+
+        if broken > 0 {
+            return Err(CliError::validation(format!(
+                "{broken} layout(s) broken after refresh"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Loads, validates, and optionally regenerates one layout.
+    ///
+    /// Returns `None` for templates, which are not built on their own.
+    fn refresh_layout(
+        &self,
+        path: &Path,
+        config: &Config,
+        keycode_db: &KeycodeDb,
+    ) -> Option<LayoutRefresh> {
+        let file = path
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+
+        let loaded = LayoutService::load(path);
+        if loaded
+            .as_ref()
+            .is_ok_and(|layout| layout.metadata.is_template)
+        {
+            return None;
+        }
+
+        let checked = loaded
+            .map_err(|e| vec![format!("Failed to load layout: {e}")])
+            .and_then(|layout| {
+                let geo = build_geometry(&layout, config)?;
+                Ok((layout, geo))
+            })
+            .and_then(|(layout, geo)| {
+                let report =
+                    FirmwareValidator::new(&layout, &geo.geometry, &geo.mapping, keycode_db)
+                        .validate()
+                        .map_err(|e| vec![format!("Validation failed: {e}")])?;
+                if report.is_valid() {
+                    Ok((layout, geo, report.warnings.len()))
+                } else {
+                    Err(report.errors.iter().map(|e| e.message.clone()).collect())
+                }
+            });
+
+        match checked {
+            Ok((layout, geo, warnings)) => {
+                let mut result = LayoutRefresh {
+                    file,
+                    status: "ok".to_string(),
+                    errors: Vec::new(),
+                    warnings,
+                    generated: None,
+                };
+                if self.generate {
+                    let generator = FirmwareGenerator::new(
+                        &layout,
+                        &geo.geometry,
+                        &geo.mapping,
+                        config,
+                        keycode_db,
+                    );
+                    match generator.generate() {
+                        Ok((keymap_path, _)) => {
+                            result.generated = Path::new(&keymap_path)
+                                .parent()
+                                .map(|dir| dir.display().to_string());
+                        }
+                        Err(e) => {
+                            result.status = "broken".to_string();
+                            result
+                                .errors
+                                .push(format!("Failed to generate firmware: {e}"));
+                        }
+                    }
+                }
+                Some(result)
+            }
+            Err(errors) => Some(LayoutRefresh {
+                file,
+                status: "broken".to_string(),
+                errors,
+                warnings: 0,
+                generated: None,
+            }),
+        }
+    }
+}
+
+impl QmkUpdate {
+    fn skipped(reason: impl Into<String>) -> Self {
+        Self {
+            status: "skipped".to_string(),
+            from: None,
+            to: None,
+            reason: Some(reason.into()),
+        }
+    }
+}
+
+/// Fast-forwards the QMK checkout and its submodules.
+///
+/// Directories that are not git checkouts are left alone.
+fn update_qmk_checkout(qmk_path: &Path) -> CliResult<QmkUpdate> {
+    if !qmk_path.join(".git").exists() {
+        return Ok(QmkUpdate::skipped("not a git checkout"));
+    }
+
+    let from = run_git(qmk_path, &["rev-parse", "--short", "HEAD"])?;
+    run_git(qmk_path, &["pull", "--ff-only"])?;
+    run_git(qmk_path, &["submodule", "update", "--init", "--recursive"])?;
+    let to = run_git(qmk_path, &["rev-parse", "--short", "HEAD"])?;
+
+    Ok(QmkUpdate {
+        status: if from == to { "up_to_date" } else { "updated" }.to_string(),
+        from: Some(from),
+        to: Some(to),
+        reason: None,
+    })
+}
+
+/// Runs a git command in `dir` and returns its trimmed stdout.
+fn run_git(dir: &Path, args: &[&str]) -> CliResult<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| CliError::io(format!("Failed to run git: {e}")))?;
+
+    if !output.status.success() {
+        return Err(CliError::io(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Builds geometry for `layout` from the (updated) keyboard definition.
+fn build_geometry(layout: &Layout, config: &Config) -> Result<GeometryResult, Vec<String>> {
+    let variant = layout
+        .metadata
+        .layout_variant
+        .as_deref()
+        .ok_or_else(|| vec!["Layout variant not specified in metadata".to_string()])?;
+    let context = GeometryContext {
+        config,
+        metadata: &layout.metadata,
+    };
+    geometry::build_geometry_for_layout(context, variant).map_err(|e| vec![format!("{e:#}")])
+}
+
+/// Prints the human-readable refresh report.
+fn print_report(response: &RefreshResponse, workspace: &Path) {
+    match response.qmk.status.as_str() {
+        "updated" => say!(
+            "✓ QMK updated {} → {}",
+            response.qmk.from.as_deref().unwrap_or("?"),
+            response.qmk.to.as_deref().unwrap_or("?")
+        ),
+        "up_to_date" => say!(
+            "✓ QMK already up to date ({})",
+            response.qmk.to.as_deref().unwrap_or("?")
+        ),
+        _ => say!(
+            "- QMK update skipped ({})",
+            response.qmk.reason.as_deref().unwrap_or("")
+        ),
+    }
+
+    if response.layouts.is_empty() {
+        say!("\nNo layouts found in {}", workspace.display());
+        return;
+    }
+
+    say!("\nLayouts in {}:", workspace.display());
+    for layout in &response.layouts {
+        if layout.status == "ok" {
+            if layout.warnings > 0 {
+                say!("  ✓ {} ({} warning(s))", layout.file, layout.warnings);
+            } else {
+                say!("  ✓ {}", layout.file);
+            }
+            if let Some(dir) = &layout.generated {
+                say!("      generated: {dir}");
+            }
+        } else {
+            say!("  ✗ {}", layout.file);
+            for error in &layout.errors {
+                say!("      {error}");
+            }
+        }
+    }
+
+    say!(
+        "\n{} of {} layout(s) OK",
+        response.layouts.len() - response.broken,
+        response.layouts.len()
+    );
+}
@@ -1,6 +1,6 @@
 //! Tap dance management commands for CLI.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult};
 use crate::models::{Layout, TapDanceAction};
 use crate::services::LayoutService;
 use clap::{Args, Subcommand};
@@ -160,22 +160,21 @@ fn execute_list(args: &ListArgs) -> CliResult<()> {
             count: tap_dances.len(),
             tap_dances,
         };
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&response)
-                .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-        );
+        print_json(&response)?;
     } else {
         // Text output: one per line with name and actions
         for td in &tap_dances {
-            print!("{}: single={}", td.name, td.single_tap);
-            if let Some(ref double) = td.double_tap {
-                print!(", double={double}");
-            }
-            if let Some(ref hold) = td.hold {
-                print!(", hold={hold}");
-            }
-            println!();
+            let double = td
+                .double_tap
+                .as_ref()
+                .map(|double| format!(", double={double}"))
+                .unwrap_or_default();
+            let hold = td
+                .hold
+                .as_ref()
+                .map(|hold| format!(", hold={hold}"))
+                .unwrap_or_default();
+            say!("{}: single={}{double}{hold}", td.name, td.single_tap);
         }
     }
 
@@ -221,7 +220,7 @@ fn execute_add(args: &AddArgs) -> CliResult<()> {
     LayoutService::save(&layout, &args.layout)
         .map_err(|e| CliError::io(format!("Failed to save layout: {e}")))?;
 
-    println!(
+    say!(
         "Successfully added tap dance '{}' to {}",
         args.name,
         args.layout.display()
@@ -270,13 +269,13 @@ fn execute_delete(args: &DeleteArgs) -> CliResult<()> {
         .map_err(|e| CliError::io(format!("Failed to save layout: {e}")))?;
 
     if args.force && !references.is_empty() {
-        println!(
+        say!(
             "Successfully deleted tap dance '{}' and replaced {} reference(s) with KC_TRNS",
             args.name,
             references.len()
         );
     } else {
-        println!("Successfully deleted tap dance '{}'", args.name);
+        say!("Successfully deleted tap dance '{}'", args.name);
     }
 
     Ok(())
@@ -302,11 +301,7 @@ fn execute_validate(args: &ValidateArgs) -> CliResult<()> {
             orphaned,
             unused,
         };
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&response)
-                .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-        );
+        print_json(&response)?;
 
         return if !valid {
             Err(CliError::validation("Tap dance validation failed"))
@@ -317,21 +312,21 @@ fn execute_validate(args: &ValidateArgs) -> CliResult<()> {
 
     // Text output
     if !orphaned.is_empty() {
-        println!("Errors:");
+        say!("Errors:");
         for name in &orphaned {
-            println!("  ✗ Orphaned reference: TD({name}) used but not defined");
+            say!("  ✗ Orphaned reference: TD({name}) used but not defined");
         }
     }
 
     if !unused.is_empty() {
-        println!("{}Warnings:", if orphaned.is_empty() { "" } else { "\n" });
+        say!("{}Warnings:", if orphaned.is_empty() { "" } else { "\n" });
         for name in &unused {
-            println!("  ⚠ Unused definition: '{name}' defined but never used");
+            say!("  ⚠ Unused definition: '{name}' defined but never used");
         }
     }
 
     if valid && unused.is_empty() {
-        println!("✓ All tap dance references are valid");
+        say!("✓ All tap dance references are valid");
     }
 
     if !valid {
@@ -1,11 +1,13 @@
 //! Template management commands for layout files.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{print_json, CliError, CliResult};
 use crate::config::Config;
+use crate::models::TemplatePlaceholder;
 use crate::services::LayoutService;
 use chrono::Utc;
 use clap::{Args, Subcommand};
 use serde::Serialize;
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -62,6 +64,10 @@ pub struct ApplyArgs {
     /// Output file path
     #[arg(short, long, value_name = "FILE")]
     pub out: PathBuf,
+
+    /// Value for a template placeholder (repeatable, e.g. "OS_MOD=LGUI")
+    #[arg(long = "set", value_name = "NAME=VALUE")]
+    pub set: Vec<String>,
 }
 
 /// Template metadata for JSON output
@@ -77,6 +83,8 @@ pub struct TemplateInfo {
     pub author: String,
     /// Creation timestamp (RFC 3339)
     pub created: String,
+    /// Placeholders asked for when the template is applied
+    pub placeholders: Vec<TemplatePlaceholder>,
 }
 
 /// Template list response
@@ -135,6 +143,7 @@ impl ListArgs {
                             tags: layout.metadata.tags.clone(),
                             author: layout.metadata.author.clone(),
                             created: layout.metadata.created.to_rfc3339(),
+                            placeholders: layout.required_placeholders(),
                         });
                     }
                     Err(_) => {
@@ -152,29 +161,33 @@ impl ListArgs {
 
         // Output
         if self.json {
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&response)
-                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-            );
+            print_json(&response)?;
         } else {
             // Human-readable output
             if count == 0 {
-                println!("No templates found.");
-                println!("Template directory: {}", template_dir.display());
+                say!("No templates found.");
+                say!("Template directory: {}", template_dir.display());
             } else {
-                println!("Available templates ({}):\n", count);
+                say!("Available templates ({}):\n", count);
                 for template in &response.templates {
-                    println!("  {} ({})", template.name, template.file);
+                    say!("  {} ({})", template.name, template.file);
                     if !template.tags.is_empty() {
-                        println!("    Tags: {}", template.tags.join(", "));
+                        say!("    Tags: {}", template.tags.join(", "));
                     }
                     if !template.author.is_empty() {
-                        println!("    Author: {}", template.author);
+                        say!("    Author: {}", template.author);
+                    }
+                    if !template.placeholders.is_empty() {
+                        let names: Vec<&str> = template
+                            .placeholders
+                            .iter()
+                            .map(|p| p.name.as_str())
+                            .collect();
+                        say!("    Placeholders: {}", names.join(", "));
                     }
-                    println!();
+                    say!();
                 }
-                println!("Template directory: {}", template_dir.display());
+                say!("Template directory: {}", template_dir.display());
             }
         }
 
@@ -240,8 +253,8 @@ impl SaveArgs {
         LayoutService::save(&layout, &template_path)
             .map_err(|e| CliError::io(format!("Failed to save template: {e}")))?;
 
-        println!("✓ Template saved: {}", self.name);
-        println!("  File: {}", template_path.display());
+        say!("✓ Template saved: {}", self.name);
+        say!("  File: {}", template_path.display());
 
         Ok(())
     }
@@ -285,9 +298,15 @@ impl ApplyArgs {
             .ok_or_else(|| CliError::validation(format!("Template '{}' not found", self.name)))?;
 
         // Load the template
-        let mut layout = LayoutService::load(&template_path)
+        let template = LayoutService::load(&template_path)
             .map_err(|e| CliError::io(format!("Failed to load template: {e}")))?;
 
+        // Fill in placeholders from --set values and defaults
+        let values = parse_placeholder_values(&self.set)?;
+        let mut layout = template
+            .fill_placeholders(&values)
+            .map_err(|e| CliError::validation(format!("{e:#}")))?;
+
         // Update metadata for new layout
         layout.metadata.is_template = false;
         layout.metadata.created = Utc::now();
@@ -297,17 +316,33 @@ impl ApplyArgs {
         LayoutService::save(&layout, &self.out)
             .map_err(|e| CliError::io(format!("Failed to save layout: {e}")))?;
 
-        println!("✓ Template applied: {}", self.name);
-        println!("  Output: {}", self.out.display());
+        say!("✓ Template applied: {}", self.name);
+        say!("  Output: {}", self.out.display());
 
         Ok(())
     }
 }
 
+/// Parses `NAME=VALUE` pairs given with `--set`.
+fn parse_placeholder_values(pairs: &[String]) -> CliResult<HashMap<String, String>> {
+    pairs
+        .iter()
+        .map(|pair| {
+            pair.split_once('=')
+                .map(|(name, value)| (name.trim().to_string(), value.to_string()))
+                .ok_or_else(|| {
+                    CliError::validation(format!(
+                        "Invalid placeholder value '{pair}', expected NAME=VALUE"
+                    ))
+                })
+        })
+        .collect()
+}
+
 /// Get the platform-specific template directory
 fn get_template_dir() -> CliResult<PathBuf> {
     let config_dir = Config::config_dir()
-        .map_err(|e| CliError::io(format!("Failed to get config directory: {e}")))?;
+        .map_err(|e| CliError::config(format!("Failed to get config directory: {e}")))?;
     Ok(config_dir.join("templates"))
 }
 
@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_sanitize_filename() {
     assert_eq!(sanitize_filename("My Layout"), "my_layout");
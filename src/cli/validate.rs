@@ -1,23 +1,24 @@
 //! Validation command for layout files.
 
 use crate::cli::common::{
-    CliError, CliResult, ValidationChecks, ValidationLocation, ValidationMessage,
-    ValidationPosition, ValidationResponse,
+    print_json, CliError, CliResult, LayoutFileArg, ValidationChecks, ValidationLocation,
+    ValidationMessage, ValidationPosition, ValidationResponse,
 };
+use crate::config::Config;
 use crate::firmware::validator::FirmwareValidator;
 use crate::keycode_db::KeycodeDb;
 use crate::models::keyboard_geometry::KeyboardGeometry;
 use crate::models::visual_layout_mapping::VisualLayoutMapping;
+use crate::services::lints::{lint_layout, Lint, LintRule};
 use crate::services::LayoutService;
 use clap::Args;
-use std::path::PathBuf;
 
 /// Validate a layout file for errors and warnings
 #[derive(Debug, Clone, Args)]
 pub struct ValidateArgs {
-    /// Path to layout markdown file
-    #[arg(short, long, value_name = "FILE")]
-    pub layout: PathBuf,
+    /// Layout file to read
+    #[command(flatten)]
+    pub layout: LayoutFileArg,
 
     /// Output results as JSON
     #[arg(long)]
@@ -32,7 +33,7 @@ impl ValidateArgs {
     /// Execute the validate command
     pub fn execute(&self) -> CliResult<()> {
         // Load layout
-        let layout = LayoutService::load(&self.layout)
+        let layout = LayoutService::load(self.layout.path()?)
             .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
 
         // Build minimal geometry for validation
@@ -76,6 +77,7 @@ impl ValidateArgs {
                     Some(ValidationLocation {
                         layer,
                         position: ValidationPosition { row, col },
+                        name: error.position_name.clone(),
                     })
                 } else {
                     None
@@ -90,49 +92,78 @@ impl ValidateArgs {
 
         // Convert warnings
         for warning in &report.warnings {
-            let msg = warning.message.clone();
+            messages.push(ValidationMessage {
+                severity: "warning".to_string(),
+                message: warning.message.clone(),
+                location: None,
+            });
+        }
 
-            // Update check status for tap dance warnings
-            if msg.contains("Tap dance") || msg.contains("tap dance") {
-                checks.tap_dances = "warning".to_string();
+        // Layout lints, with the severities configured under [lints]
+        let lint_config = Config::load().unwrap_or_default().lints;
+        let lints = lint_layout(&layout, &keycode_db, None, &lint_config);
+        for lint in &lints {
+            let status = if lint.is_error() { "failed" } else { "warning" };
+            let check = match lint.rule {
+                LintRule::UnreachableLayer | LintRule::LayerWithoutWayBack => {
+                    Some(&mut checks.layer_refs)
+                }
+                LintRule::UnknownTapDanceKeycode | LintRule::UnusedTapDance => {
+                    Some(&mut checks.tap_dances)
+                }
+                LintRule::MissingBootloaderKey
+                | LintRule::TransparentOnBase
+                | LintRule::LedIndexCollision => None,
+            };
+            if let Some(check) = check {
+                if check.as_str() != "failed" {
+                    *check = status.to_string();
+                }
             }
 
+            let location =
+                lint.layer
+                    .zip(lint.position)
+                    .map(|(layer, position)| ValidationLocation {
+                        layer,
+                        position: ValidationPosition {
+                            row: position.row,
+                            col: position.col,
+                        },
+                        name: None,
+                    });
             messages.push(ValidationMessage {
-                severity: "warning".to_string(),
-                message: msg,
-                location: None,
+                severity: if lint.is_error() { "error" } else { "warning" }.to_string(),
+                message: lint.message.clone(),
+                location,
             });
         }
 
         let response = ValidationResponse {
-            valid: report.is_valid(),
+            valid: report.is_valid() && !lints.iter().any(Lint::is_error),
             errors: messages,
             checks,
         };
 
         // Output results
         if self.json {
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&response)
-                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
-            );
+            print_json(&response)?;
         } else {
             // Human-readable output
             if response.valid {
-                println!("✓ Validation passed");
+                say!("✓ Validation passed");
             } else {
-                println!("✗ Validation failed");
+                say!("✗ Validation failed");
             }
 
-            println!("\nChecks:");
-            println!("  Keycodes:   {}", response.checks.keycodes);
-            println!("  Positions:  {}", response.checks.positions);
-            println!("  Layer refs: {}", response.checks.layer_refs);
-            println!("  Tap dances: {}", response.checks.tap_dances);
+            say!("\nChecks:");
+            say!("  Keycodes:   {}", response.checks.keycodes);
+            say!("  Positions:  {}", response.checks.positions);
+            say!("  Layer refs: {}", response.checks.layer_refs);
+            say!("  Tap dances: {}", response.checks.tap_dances);
 
             if !response.errors.is_empty() {
-                println!("\nIssues:");
+                say!("\nIssues:");
                 for msg in &response.errors {
                     let prefix = if msg.severity == "error" {
                         "  ✗"
@@ -140,12 +171,21 @@ impl ValidateArgs {
                         "  ⚠"
                     };
                     if let Some(loc) = &msg.location {
-                        println!(
-                            "{} [Layer {} ({}, {})] {}",
-                            prefix, loc.layer, loc.position.row, loc.position.col, msg.message
+                        let name = loc
+                            .name
+                            .as_ref()
+                            .map_or_else(String::new, |name| format!(", {name}"));
+                        say!(
+                            "{} [Layer {} ({}, {}){}] {}",
+                            prefix,
+                            loc.layer,
+                            loc.position.row,
+                            loc.position.col,
+                            name,
+                            msg.message
                         );
                     } else {
-                        println!("{} {}", prefix, msg.message);
+                        say!("{} {}", prefix, msg.message);
                     }
                 }
             }
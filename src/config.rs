@@ -5,8 +5,9 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Theme display mode preference.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -20,13 +21,81 @@ pub enum ThemeMode {
     Light,
 }
 
+/// A segment of the status bar summary line.
+///
+/// The order of segments in [`UiConfig::status_bar_segments`] is the order they
+/// are rendered in. Segments with nothing to show (e.g. an empty clipboard) are
+/// skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusBarSegment {
+    /// Current editing mode (Edit, Selection, Popup, ...)
+    Mode,
+    /// Selected key info (layer, position, keycode)
+    SelectedKey,
+    /// Saved/unsaved state of the layout
+    Draft,
+    /// Clipboard contents ready for paste
+    Clipboard,
+    /// Firmware build status
+    BuildStatus,
+    /// Chain of layers leading from the base layer to the current layer
+    LayerChain,
+    /// Git branch of the directory containing the layout file, and whether
+    /// the file is committed
+    GitBranch,
+    /// Active layer and recent key presses reported by the keyboard (while
+    /// live preview is running)
+    LivePreview,
+}
+
+impl StatusBarSegment {
+    /// All available segments, in their canonical order.
+    pub const ALL: [Self; 8] = [
+        Self::Mode,
+        Self::SelectedKey,
+        Self::Draft,
+        Self::Clipboard,
+        Self::BuildStatus,
+        Self::LayerChain,
+        Self::GitBranch,
+        Self::LivePreview,
+    ];
+}
+
 /// Path configuration for file system locations.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct PathConfig {
     /// QMK firmware directory path (e.g., "/`path/to/qmk_firmware`")
     pub qmk_firmware: Option<PathBuf>,
+    /// Where layout backups are kept (defaults to `backups/` in the config
+    /// directory)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup_dir: Option<PathBuf>,
+    /// Extra directories laid out like QMK's `keyboards/` (e.g. the
+    /// `keyboards/` folder of a personal fork, or an out-of-tree vendor
+    /// board). Searched before QMK's own keyboards, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keyboard_roots: Vec<PathBuf>,
+    /// Other QMK checkouts layouts can build against instead of
+    /// `qmk_firmware` (e.g. a personal fork or vial-qmk)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub qmk_trees: Vec<QmkTree>,
 }
 
+/// A named QMK firmware checkout, selected per layout by
+/// [`LayoutMetadata::qmk_tree`](crate::models::LayoutMetadata::qmk_tree).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QmkTree {
+    /// Name layouts refer to the checkout by (e.g. "fork")
+    pub name: String,
+    /// QMK firmware directory path
+    pub path: PathBuf,
+}
+
+/// Name of the `paths.qmk_firmware` checkout among the QMK trees.
+pub const DEFAULT_QMK_TREE: &str = "default";
+
 /// Firmware build configuration.
 ///
 /// Note: keyboard, `layout_variant`, `keymap_name`, and `output_format` have been moved
@@ -35,6 +104,12 @@ pub struct PathConfig {
 pub struct BuildConfig {
     /// Build output directory (where all firmware files go)
     pub output_dir: PathBuf,
+    /// QMK userspace name to generate keymaps for. When set, keymaps pull in
+    /// `users/<name>/` through `USER_NAME` and its shared `rules.mk` is
+    /// created if missing; otherwise keymaps stand alone under
+    /// `keyboards/<kb>/keymaps/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub userspace: Option<String>,
 }
 
 impl Default for BuildConfig {
@@ -42,7 +117,10 @@ impl Default for BuildConfig {
         // Use config directory for build output by default
         let output_dir = Self::default_output_dir().unwrap_or_else(|_| PathBuf::from(".build"));
 
-        Self { output_dir }
+        Self {
+            output_dir,
+            userspace: None,
+        }
     }
 }
 
@@ -64,7 +142,7 @@ impl BuildConfig {
     ///
     /// # Arguments
     ///
-    /// * `qmk_path` - Path to QMK firmware directory
+    /// * `keyboards_dir` - Directory containing the keyboard (QMK's `keyboards/` or an extra keyboard root)
     /// * `base_keyboard` - Base keyboard path without variant (e.g., "`keebart/corne_choc_pro`")
     /// * `layout_key_count` - Number of keys in the selected layout
     ///
@@ -74,11 +152,11 @@ impl BuildConfig {
     /// or the base keyboard path if no variant is needed.
     pub fn determine_keyboard_variant(
         &self,
-        qmk_path: &std::path::Path,
+        keyboards_dir: &std::path::Path,
         base_keyboard: &str,
         layout_key_count: usize,
     ) -> Result<String> {
-        let keyboard_dir = qmk_path.join("keyboards").join(base_keyboard);
+        let keyboard_dir = keyboards_dir.join(base_keyboard);
 
         // Discover all variant subdirectories dynamically by scanning the filesystem
         let discovered_variants = Self::discover_keyboard_variants(&keyboard_dir)?;
@@ -114,7 +192,7 @@ impl BuildConfig {
         };
 
         let variant_path = format!("{base_keyboard}/{variant}");
-        let variant_dir = qmk_path.join("keyboards").join(&variant_path);
+        let variant_dir = keyboards_dir.join(&variant_path);
 
         // Validate the variant directory exists
         if !variant_dir.exists() {
@@ -199,6 +277,21 @@ pub struct UiConfig {
     /// Last selected language in the keycode picker (for convenience)
     #[serde(default)]
     pub last_language: Option<String>,
+    /// Keycodes pinned to the keycode picker's Favorites list, in order
+    #[serde(default)]
+    pub favorite_keycodes: Vec<String>,
+    /// Keycodes last picked in the keycode picker, most recent first
+    #[serde(default)]
+    pub recent_keycodes: Vec<String>,
+    /// Segments shown in the status bar summary line, in display order
+    #[serde(default = "default_status_bar_segments")]
+    pub status_bar_segments: Vec<StatusBarSegment>,
+    /// Show the per-layer minimap strip under the keyboard
+    #[serde(default = "default_show_layer_minimap")]
+    pub show_layer_minimap: bool,
+    /// Label keys with the characters the host keyboard layout types
+    #[serde(default)]
+    pub host_layout_labels: bool,
 }
 
 /// Default keyboard scale (1.0 = 100%)
@@ -206,6 +299,20 @@ fn default_keyboard_scale() -> f32 {
     1.0
 }
 
+/// Layer minimap is shown by default
+const fn default_show_layer_minimap() -> bool {
+    true
+}
+
+/// Default status bar segments (mode, selected key, draft state)
+fn default_status_bar_segments() -> Vec<StatusBarSegment> {
+    vec![
+        StatusBarSegment::Mode,
+        StatusBarSegment::SelectedKey,
+        StatusBarSegment::Draft,
+    ]
+}
+
 impl Default for UiConfig {
     fn default() -> Self {
         Self {
@@ -213,10 +320,211 @@ impl Default for UiConfig {
             theme_mode: ThemeMode::default(),
             keyboard_scale: default_keyboard_scale(),
             last_language: None,
+            favorite_keycodes: Vec::new(),
+            recent_keycodes: Vec::new(),
+            status_bar_segments: default_status_bar_segments(),
+            show_layer_minimap: default_show_layer_minimap(),
+            host_layout_labels: false,
         }
     }
 }
 
+/// Number of keycodes kept in [`UiConfig::recent_keycodes`].
+pub const MAX_RECENT_KEYCODES: usize = 10;
+
+impl UiConfig {
+    /// Moves `keycode` to the front of the recent keycodes, dropping the
+    /// oldest beyond [`MAX_RECENT_KEYCODES`].
+    pub fn record_recent_keycode(&mut self, keycode: &str) {
+        self.recent_keycodes.retain(|recent| recent != keycode);
+        self.recent_keycodes.insert(0, keycode.to_string());
+        self.recent_keycodes.truncate(MAX_RECENT_KEYCODES);
+    }
+
+    /// Appends `keycode` to the favorites.
+    ///
+    /// Returns false if it already is one.
+    pub fn add_favorite_keycode(&mut self, keycode: &str) -> bool {
+        if self.favorite_keycodes.iter().any(|fav| fav == keycode) {
+            return false;
+        }
+        self.favorite_keycodes.push(keycode.to_string());
+        true
+    }
+
+    /// Removes `keycode` from the favorites.
+    ///
+    /// Returns false if it wasn't one.
+    pub fn remove_favorite_keycode(&mut self, keycode: &str) -> bool {
+        let len = self.favorite_keycodes.len();
+        self.favorite_keycodes.retain(|fav| fav != keycode);
+        self.favorite_keycodes.len() != len
+    }
+}
+
+/// Web server configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebConfig {
+    /// Reject all mutating API requests (for publicly shared layout browsers)
+    #[serde(default)]
+    pub read_only: bool,
+    /// Accept `POST /api/shutdown` (set by `lazyqmk web --allow-shutdown` for
+    /// desktop-app wrappers; never persisted)
+    #[serde(skip)]
+    pub allow_shutdown: bool,
+    /// Keep layouts in memory and all other files in a temporary directory
+    /// (set by `lazyqmk web --sandbox`; never persisted)
+    #[serde(skip)]
+    pub sandbox: bool,
+    /// Remote layout storage (WebDAV or plain HTTP collection URL); layouts
+    /// are read from the workspace directory when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_url: Option<String>,
+    /// Seconds a build or generate job may run before it is stopped and
+    /// marked failed (0 = no limit)
+    #[serde(default = "default_job_timeout_secs")]
+    pub job_timeout_secs: u64,
+    /// Number of firmware builds run at the same time; more are queued
+    #[serde(default = "default_build_workers")]
+    pub build_workers: usize,
+    /// CPUs each build may use, passed to `qmk compile -j` (0 = QMK's default)
+    #[serde(default)]
+    pub build_cpus_per_job: usize,
+}
+
+impl Default for WebConfig {
+    fn default() -> Self {
+        Self {
+            read_only: false,
+            allow_shutdown: false,
+            sandbox: false,
+            storage_url: None,
+            job_timeout_secs: default_job_timeout_secs(),
+            build_workers: default_build_workers(),
+            build_cpus_per_job: 0,
+        }
+    }
+}
+
+/// Jobs are stopped after 30 minutes by default; a full QMK compile takes a
+/// few minutes at most
+const fn default_job_timeout_secs() -> u64 {
+    30 * 60
+}
+
+/// Builds run one at a time by default; QMK already compiles in parallel
+const fn default_build_workers() -> usize {
+    1
+}
+
+/// Most builds that may run at the same time (`web.build_workers`).
+pub const MAX_BUILD_WORKERS: usize = 16;
+
+/// Git integration settings (for layouts kept in a git repository).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct GitConfig {
+    /// Commit the layout file after every TUI save, with a message listing
+    /// the changes
+    #[serde(default)]
+    pub auto_commit: bool,
+}
+
+/// Commands run when the TUI saves a layout and when firmware is generated
+/// or built.
+///
+/// Each hook is a program followed by its arguments, e.g.
+/// `post_save = ["git", "-C", "/home/me/dotfiles", "add", "-A"]`. LazyQMK
+/// appends the layout path and, for `post_generate` and `post_build`, the
+/// paths of the files produced. An empty list disables the hook.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HooksConfig {
+    /// Run before a save; a failing command cancels the save
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_save: Vec<String>,
+    /// Run after a save
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_save: Vec<String>,
+    /// Run after keymap.c and config.h are generated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_generate: Vec<String>,
+    /// Run after a successful firmware build
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_build: Vec<String>,
+    /// Seconds a hook may run before it is stopped and counted as failed
+    /// (0 = no limit)
+    #[serde(default = "default_hook_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            pre_save: Vec::new(),
+            post_save: Vec::new(),
+            post_generate: Vec::new(),
+            post_build: Vec::new(),
+            timeout_secs: default_hook_timeout_secs(),
+        }
+    }
+}
+
+/// Hooks block the TUI while they run, so they are stopped after a minute
+const fn default_hook_timeout_secs() -> u64 {
+    60
+}
+
+/// How a layout lint is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    /// Not checked
+    Off,
+    /// Reported, but the layout still counts as valid
+    Warning,
+    /// Reported and fails validation
+    Error,
+}
+
+/// Severity overrides for the layout lints in [`crate::services::lints`].
+///
+/// Keys are rule IDs, e.g. `transparent_on_base = "error"` or
+/// `missing_bootloader_key = "off"` under `[lints]`. Rules that aren't
+/// listed keep their default severity.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LintConfig {
+    /// Severity by rule ID
+    pub severities: BTreeMap<String, LintSeverity>,
+}
+
+/// File name of the copy of the last config that loaded cleanly.
+const LAST_GOOD_CONFIG_FILE: &str = "config.last-good.toml";
+
+/// File name a config that fails to load is copied to in safe mode.
+const BROKEN_CONFIG_FILE: &str = "config.broken.toml";
+
+/// Where the configuration for a safe-mode launch came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeModeSource {
+    /// `config.toml` loaded cleanly
+    Current,
+    /// `config.toml` was missing or broken; the last known good copy was used
+    LastKnownGood,
+    /// Nothing usable was found; built-in defaults were used
+    Defaults,
+}
+
+/// Configuration prepared by [`Config::load_safe_mode`].
+#[derive(Debug, Clone)]
+pub struct SafeModeConfig {
+    /// Configuration with safe-mode restrictions applied
+    pub config: Config,
+    /// Where the configuration came from
+    pub source: SafeModeSource,
+    /// Copy of the config file made before launching, if any
+    pub backup: Option<PathBuf>,
+}
+
 /// Application configuration.
 ///
 /// # File Location
@@ -240,6 +548,22 @@ pub struct Config {
     pub build: BuildConfig,
     /// UI preferences
     pub ui: UiConfig,
+    /// Web server settings
+    #[serde(default)]
+    pub web: WebConfig,
+    /// Git integration settings
+    #[serde(default)]
+    pub git: GitConfig,
+    /// Commands run around saving, generating and building
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    /// Layout lint severities
+    #[serde(default)]
+    pub lints: LintConfig,
+    /// Set for a safe-mode launch; [`Config::save`] then leaves the config
+    /// file alone so the restricted settings never replace the user's.
+    #[serde(skip)]
+    pub safe_mode: bool,
 }
 
 impl Config {
@@ -250,6 +574,11 @@ impl Config {
             paths: PathConfig::default(),
             build: BuildConfig::default(),
             ui: UiConfig::default(),
+            web: WebConfig::default(),
+            git: GitConfig::default(),
+            hooks: HooksConfig::default(),
+            lints: LintConfig::default(),
+            safe_mode: false,
         }
     }
 
@@ -293,11 +622,110 @@ impl Config {
         Ok(config_dir)
     }
 
+    /// Gets the directory layout backups are written to.
+    ///
+    /// Uses `paths.backup_dir` if set, otherwise `backups/` in the config
+    /// directory.
+    pub fn backup_dir(&self) -> Result<PathBuf> {
+        match &self.paths.backup_dir {
+            Some(dir) => Ok(dir.clone()),
+            None => Ok(Self::config_dir()?.join("backups")),
+        }
+    }
+
     /// Gets the full path to the config file.
     pub fn config_file_path() -> Result<PathBuf> {
         Ok(Self::config_dir()?.join("config.toml"))
     }
 
+    /// Copies the config file to `config.last-good.toml`.
+    ///
+    /// Called after the config loaded cleanly, so that safe mode can fall
+    /// back to it if the file is later corrupted.
+    pub fn remember_last_known_good() -> Result<()> {
+        Self::remember_last_known_good_in(&Self::config_dir()?)
+    }
+
+    fn remember_last_known_good_in(dir: &Path) -> Result<()> {
+        let config_path = dir.join("config.toml");
+        if config_path.exists() {
+            fs::copy(&config_path, dir.join(LAST_GOOD_CONFIG_FILE)).context(format!(
+                "Failed to back up config file: {}",
+                config_path.display()
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// Loads configuration for a safe-mode launch.
+    ///
+    /// Uses `config.toml` if it loads cleanly (refreshing the last known good
+    /// copy), otherwise copies it to `config.broken.toml` and falls back to
+    /// the last known good copy, then to defaults. Either way the UI settings
+    /// are reset to their defaults and hooks and git auto-commit are turned
+    /// off; see [`Config::with_safe_mode_restrictions`].
+    pub fn load_safe_mode() -> Result<SafeModeConfig> {
+        Self::load_safe_mode_in(&Self::config_dir()?)
+    }
+
+    fn load_safe_mode_in(dir: &Path) -> Result<SafeModeConfig> {
+        let config_path = dir.join("config.toml");
+        let last_good_path = dir.join(LAST_GOOD_CONFIG_FILE);
+
+        let current = config_path.exists().then(|| Self::read_valid(&config_path));
+        let (config, source, backup) = match current {
+            Some(Ok(config)) => {
+                Self::remember_last_known_good_in(dir)?;
+                (config, SafeModeSource::Current, Some(last_good_path))
+            }
+            current => {
+                let backup = if current.is_some() {
+                    let broken_path = dir.join(BROKEN_CONFIG_FILE);
+                    fs::copy(&config_path, &broken_path).context(format!(
+                        "Failed to back up config file: {}",
+                        config_path.display()
+                    ))?;
+                    Some(broken_path)
+                } else {
+                    None
+                };
+                match Self::read_valid(&last_good_path) {
+                    Ok(config) => (config, SafeModeSource::LastKnownGood, backup),
+                    Err(_) => (Self::new(), SafeModeSource::Defaults, backup),
+                }
+            }
+        };
+
+        Ok(SafeModeConfig {
+            config: config.with_safe_mode_restrictions(),
+            source,
+            backup,
+        })
+    }
+
+    /// Reads and validates a config file, without any auto-fixing.
+    fn read_valid(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .context(format!("Failed to read config file: {}", path.display()))?;
+        let config: Self = toml::from_str(&content)
+            .context(format!("Failed to parse config file: {}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Resets UI settings to their defaults and turns off everything that
+    /// runs external commands (hooks and git auto-commit).
+    #[must_use]
+    pub fn with_safe_mode_restrictions(self) -> Self {
+        Self {
+            ui: UiConfig::default(),
+            git: GitConfig::default(),
+            hooks: HooksConfig::default(),
+            safe_mode: true,
+            ..self
+        }
+    }
+
     /// Loads configuration from the config file.
     ///
     /// If the file doesn't exist, returns default configuration.
@@ -382,13 +810,22 @@ impl Config {
 
     /// Saves configuration to the config file using atomic write.
     ///
-    /// Uses temp file + rename pattern for atomic writes.
+    /// Uses temp file + rename pattern for atomic writes. Does nothing for a
+    /// safe-mode config, so settings changed in safe mode last only for the
+    /// session.
     pub fn save(&self) -> Result<()> {
+        self.save_in(&Self::config_dir()?)
+    }
+
+    fn save_in(&self, config_dir: &Path) -> Result<()> {
+        if self.safe_mode {
+            return Ok(());
+        }
+
         self.validate()?;
 
         // Ensure config directory exists
-        let config_dir = Self::config_dir()?;
-        fs::create_dir_all(&config_dir).context(format!(
+        fs::create_dir_all(config_dir).context(format!(
             "Failed to create config directory: {}",
             config_dir.display()
         ))?;
@@ -396,7 +833,7 @@ impl Config {
         // Serialize to TOML
         let content = toml::to_string_pretty(self).context("Failed to serialize configuration")?;
 
-        let config_path = Self::config_file_path()?;
+        let config_path = config_dir.join("config.toml");
         let temp_path = config_path.with_extension("toml.tmp");
 
         // Write to temp file
@@ -421,30 +858,49 @@ impl Config {
     /// - `output_format` is valid ("uf2", "hex", or "bin")
     /// - `theme` is valid ("dark" or "light")
     /// - `output_dir` parent exists
+    /// - `build.userspace` is a valid QMK user name
+    /// - `web.build_workers` is between 1 and [`MAX_BUILD_WORKERS`]
     pub fn validate(&self) -> Result<()> {
         // Validate QMK firmware path if set
         if let Some(qmk_path) = &self.paths.qmk_firmware {
-            if !qmk_path.exists() {
-                anyhow::bail!("QMK firmware path does not exist: {}", qmk_path.display());
-            }
+            validate_qmk_dir(qmk_path, "QMK firmware path")?;
+        }
 
-            let makefile_path = qmk_path.join("Makefile");
-            if !makefile_path.exists() {
+        let mut tree_names = std::collections::HashSet::new();
+        for tree in &self.paths.qmk_trees {
+            if tree.name.trim().is_empty() || tree.name == DEFAULT_QMK_TREE {
                 anyhow::bail!(
-                    "QMK firmware path is invalid: Makefile not found at {}",
-                    makefile_path.display()
+                    "QMK tree name must be set and not '{DEFAULT_QMK_TREE}': {}",
+                    tree.path.display()
                 );
             }
+            if !tree_names.insert(tree.name.as_str()) {
+                anyhow::bail!("Duplicate QMK tree name: {}", tree.name);
+            }
+            validate_qmk_dir(&tree.path, &format!("QMK tree '{}'", tree.name))?;
+        }
 
-            let keyboards_dir = qmk_path.join("keyboards");
-            if !keyboards_dir.exists() || !keyboards_dir.is_dir() {
+        if let Some(name) = &self.build.userspace {
+            if !is_valid_userspace_name(name) {
                 anyhow::bail!(
-                    "QMK firmware path is invalid: keyboards/ directory not found at {}",
-                    keyboards_dir.display()
+                    "build.userspace must be a QMK user name (letters, digits and underscores), got '{name}'"
                 );
             }
         }
 
+        if !(1..=MAX_BUILD_WORKERS).contains(&self.web.build_workers) {
+            anyhow::bail!(
+                "web.build_workers must be between 1 and {MAX_BUILD_WORKERS}, got {}",
+                self.web.build_workers
+            );
+        }
+
+        for id in self.lints.severities.keys() {
+            if crate::services::lints::LintRule::from_id(id).is_none() {
+                anyhow::bail!("Unknown lint rule in [lints]: {id}");
+            }
+        }
+
         // Keyboard-specific settings (keyboard, layout, keymap, output_format)
         // are now stored in layout metadata, not in config.toml
 
@@ -457,6 +913,64 @@ impl Config {
         self.validate()?;
         Ok(())
     }
+
+    /// Names of the configured QMK trees, [`DEFAULT_QMK_TREE`] first.
+    #[must_use]
+    pub fn qmk_tree_names(&self) -> Vec<&str> {
+        std::iter::once(DEFAULT_QMK_TREE)
+            .chain(self.paths.qmk_trees.iter().map(|tree| tree.name.as_str()))
+            .collect()
+    }
+
+    /// Gets the checkout of the QMK tree `name`.
+    ///
+    /// `None`, [`DEFAULT_QMK_TREE`] and names not in `paths.qmk_trees` give
+    /// `paths.qmk_firmware`, so a layout shared from a machine with other
+    /// trees still builds.
+    #[must_use]
+    pub fn qmk_tree_path(&self, name: Option<&str>) -> Option<&PathBuf> {
+        name.and_then(|name| self.paths.qmk_trees.iter().find(|tree| tree.name == name))
+            .map(|tree| &tree.path)
+            .or(self.paths.qmk_firmware.as_ref())
+    }
+
+    /// Builds against `path` whatever QMK tree a layout selects (for an
+    /// explicit `--qmk-path` or a job that already resolved the tree).
+    pub fn pin_qmk_path(&mut self, path: PathBuf) {
+        self.paths.qmk_firmware = Some(path);
+        self.paths.qmk_trees.clear();
+    }
+}
+
+/// Checks that `name` can name a `users/` directory and `USER_NAME`.
+fn is_valid_userspace_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Checks that `qmk_path` looks like a QMK firmware checkout, naming it
+/// `what` in errors.
+fn validate_qmk_dir(qmk_path: &Path, what: &str) -> Result<()> {
+    if !qmk_path.exists() {
+        anyhow::bail!("{what} does not exist: {}", qmk_path.display());
+    }
+
+    let makefile_path = qmk_path.join("Makefile");
+    if !makefile_path.exists() {
+        anyhow::bail!(
+            "{what} is invalid: Makefile not found at {}",
+            makefile_path.display()
+        );
+    }
+
+    let keyboards_dir = qmk_path.join("keyboards");
+    if !keyboards_dir.exists() || !keyboards_dir.is_dir() {
+        anyhow::bail!(
+            "{what} is invalid: keyboards/ directory not found at {}",
+            keyboards_dir.display()
+        );
+    }
+
+    Ok(())
 }
 
 impl Default for Config {
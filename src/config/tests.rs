@@ -4,7 +4,6 @@
 
 use super::*;
 
-use super::*;
 use std::fs;
 use tempfile::TempDir;
 
@@ -60,6 +59,120 @@ fn test_config_validate_qmk_path() {
     assert!(config.validate().is_ok());
 }
 
+/// Creates a minimal QMK checkout at `dir/name`.
+fn fake_qmk_tree(dir: &Path, name: &str) -> PathBuf {
+    let path = dir.join(name);
+    fs::create_dir_all(path.join("keyboards")).unwrap();
+    fs::write(path.join("Makefile"), "").unwrap();
+    path
+}
+
+#[test]
+fn test_config_validate_qmk_trees() {
+    let temp_dir = TempDir::new().unwrap();
+    let fork = fake_qmk_tree(temp_dir.path(), "fork");
+
+    let mut config = Config::new();
+    config.paths.qmk_trees = vec![QmkTree {
+        name: "fork".to_string(),
+        path: fork.clone(),
+    }];
+    assert!(config.validate().is_ok());
+
+    // Names must be unique and not the default tree's
+    config.paths.qmk_trees.push(QmkTree {
+        name: "fork".to_string(),
+        path: fork,
+    });
+    assert!(config.validate().is_err());
+    config.paths.qmk_trees[1].name = DEFAULT_QMK_TREE.to_string();
+    assert!(config.validate().is_err());
+
+    // Paths must be QMK checkouts
+    config.paths.qmk_trees[1] = QmkTree {
+        name: "vial".to_string(),
+        path: temp_dir.path().join("missing"),
+    };
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("QMK tree 'vial' does not exist"), "{err}");
+}
+
+#[test]
+fn test_qmk_tree_path_falls_back_to_default_tree() {
+    let mut config = Config::new();
+    config.paths.qmk_firmware = Some(PathBuf::from("/qmk/upstream"));
+    config.paths.qmk_trees = vec![QmkTree {
+        name: "fork".to_string(),
+        path: PathBuf::from("/qmk/fork"),
+    }];
+
+    assert_eq!(config.qmk_tree_names(), vec![DEFAULT_QMK_TREE, "fork"]);
+    assert_eq!(
+        config.qmk_tree_path(Some("fork")),
+        Some(&PathBuf::from("/qmk/fork"))
+    );
+    assert_eq!(
+        config.qmk_tree_path(None),
+        Some(&PathBuf::from("/qmk/upstream"))
+    );
+    assert_eq!(
+        config.qmk_tree_path(Some("unknown")),
+        Some(&PathBuf::from("/qmk/upstream"))
+    );
+
+    // A pinned path wins over the layout's tree
+    config.pin_qmk_path(PathBuf::from("/qmk/ci"));
+    assert_eq!(
+        config.qmk_tree_path(Some("fork")),
+        Some(&PathBuf::from("/qmk/ci"))
+    );
+}
+
+#[test]
+fn test_config_validate_userspace() {
+    let mut config = Config::new();
+    config.build.userspace = Some("jane_doe".to_string());
+    assert!(config.validate().is_ok());
+
+    for name in ["", "jane/doe", "jane doe", "../x"] {
+        config.build.userspace = Some(name.to_string());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("build.userspace"), "{err}");
+    }
+}
+
+#[test]
+fn test_qmk_trees_toml_roundtrip() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::new();
+    config.paths.qmk_trees = vec![QmkTree {
+        name: "fork".to_string(),
+        path: fake_qmk_tree(temp_dir.path(), "fork"),
+    }];
+
+    let toml = toml::to_string_pretty(&config).unwrap();
+    assert!(toml.contains("[[paths.qmk_trees]]"), "{toml}");
+    let parsed: Config = toml::from_str(&toml).unwrap();
+    assert_eq!(parsed.paths.qmk_trees, config.paths.qmk_trees);
+
+    // Configs without trees don't mention them
+    let toml = toml::to_string_pretty(&Config::new()).unwrap();
+    assert!(!toml.contains("qmk_trees"));
+}
+
+#[test]
+fn test_config_validate_build_workers() {
+    let mut config = Config::new();
+    assert_eq!(config.web.build_workers, 1);
+
+    config.web.build_workers = 0;
+    assert!(config.validate().is_err());
+    config.web.build_workers = MAX_BUILD_WORKERS;
+    assert!(config.validate().is_ok());
+    config.web.build_workers = MAX_BUILD_WORKERS + 1;
+    assert!(config.validate().is_err());
+}
+
 #[test]
 fn test_config_save_and_load() {
     let temp_dir = TempDir::new().unwrap();
@@ -83,6 +196,79 @@ fn test_config_save_and_load() {
     // Note: keyboard and layout are now per-layout in metadata
 }
 
+#[test]
+fn test_status_bar_segments_default_when_missing() {
+    let content = r#"
+[paths]
+
+[build]
+output_dir = "/tmp/builds"
+
+[ui]
+show_help_on_startup = false
+"#;
+    let config: Config = toml::from_str(content).unwrap();
+    assert_eq!(
+        config.ui.status_bar_segments,
+        vec![
+            StatusBarSegment::Mode,
+            StatusBarSegment::SelectedKey,
+            StatusBarSegment::Draft
+        ]
+    );
+}
+
+#[test]
+fn test_status_bar_segments_custom_order_roundtrip() {
+    let mut config = Config::new();
+    config.ui.status_bar_segments = vec![
+        StatusBarSegment::GitBranch,
+        StatusBarSegment::LayerChain,
+        StatusBarSegment::BuildStatus,
+        StatusBarSegment::Clipboard,
+    ];
+
+    let content = toml::to_string_pretty(&config).unwrap();
+    assert!(content.contains(r#""git_branch""#));
+    assert!(content.contains(r#""layer_chain""#));
+
+    let loaded: Config = toml::from_str(&content).unwrap();
+    assert_eq!(loaded.ui.status_bar_segments, config.ui.status_bar_segments);
+}
+
+#[test]
+fn test_recent_keycodes_move_to_front_and_are_capped() {
+    let mut ui = UiConfig::default();
+    for i in 0..=MAX_RECENT_KEYCODES {
+        ui.record_recent_keycode(&format!("KC_F{i}"));
+    }
+    assert_eq!(ui.recent_keycodes.len(), MAX_RECENT_KEYCODES);
+    assert_eq!(ui.recent_keycodes[0], format!("KC_F{MAX_RECENT_KEYCODES}"));
+    assert!(!ui.recent_keycodes.contains(&"KC_F0".to_string()));
+
+    ui.record_recent_keycode("KC_F5");
+    assert_eq!(ui.recent_keycodes[0], "KC_F5");
+    assert_eq!(ui.recent_keycodes.len(), MAX_RECENT_KEYCODES);
+}
+
+#[test]
+fn test_favorite_keycodes_roundtrip() {
+    let mut config = Config::new();
+    assert!(config.ui.add_favorite_keycode("LT()"));
+    assert!(config.ui.add_favorite_keycode("MT()"));
+    assert!(!config.ui.add_favorite_keycode("LT()"));
+    config.ui.record_recent_keycode("KC_ESC");
+
+    let content = toml::to_string_pretty(&config).unwrap();
+    let mut loaded: Config = toml::from_str(&content).unwrap();
+    assert_eq!(loaded.ui.favorite_keycodes, vec!["LT()", "MT()"]);
+    assert_eq!(loaded.ui.recent_keycodes, vec!["KC_ESC"]);
+
+    assert!(loaded.ui.remove_favorite_keycode("LT()"));
+    assert!(!loaded.ui.remove_favorite_keycode("LT()"));
+    assert_eq!(loaded.ui.favorite_keycodes, vec!["MT()"]);
+}
+
 // Note: set_keyboard, set_layout, and set_output_format methods removed
 // These settings are now per-layout in metadata, not global config
 
@@ -99,7 +285,7 @@ fn test_determine_keyboard_variant_no_variants() {
 
     let build_config = BuildConfig::default();
     let result = build_config
-        .determine_keyboard_variant(&qmk_path, "crkbd", 42)
+        .determine_keyboard_variant(&qmk_path.join("keyboards"), "crkbd", 42)
         .unwrap();
 
     assert_eq!(result, "crkbd");
@@ -121,7 +307,7 @@ fn test_determine_keyboard_variant_with_standard_variant() {
 
     // Use 44 keys which maps to "standard" variant
     let result = build_config
-        .determine_keyboard_variant(&qmk_path, "keebart/corne_choc_pro", 44)
+        .determine_keyboard_variant(&qmk_path.join("keyboards"), "keebart/corne_choc_pro", 44)
         .unwrap();
 
     assert_eq!(result, "keebart/corne_choc_pro/standard");
@@ -142,7 +328,7 @@ fn test_determine_keyboard_variant_with_mini_variant() {
     let build_config = BuildConfig::default();
 
     let result = build_config
-        .determine_keyboard_variant(&qmk_path, "keebart/corne_choc_pro", 36)
+        .determine_keyboard_variant(&qmk_path.join("keyboards"), "keebart/corne_choc_pro", 36)
         .unwrap();
 
     assert_eq!(result, "keebart/corne_choc_pro/mini");
@@ -163,7 +349,7 @@ fn test_determine_keyboard_variant_ex2_layout_standard() {
     let build_config = BuildConfig::default();
 
     let result = build_config
-        .determine_keyboard_variant(&qmk_path, "keebart/corne_choc_pro", 44)
+        .determine_keyboard_variant(&qmk_path.join("keyboards"), "keebart/corne_choc_pro", 44)
         .unwrap();
 
     assert_eq!(result, "keebart/corne_choc_pro/standard");
@@ -184,7 +370,7 @@ fn test_determine_keyboard_variant_ex2_layout_mini() {
     let build_config = BuildConfig::default();
 
     let result = build_config
-        .determine_keyboard_variant(&qmk_path, "keebart/corne_choc_pro", 38)
+        .determine_keyboard_variant(&qmk_path.join("keyboards"), "keebart/corne_choc_pro", 38)
         .unwrap();
 
     assert_eq!(result, "keebart/corne_choc_pro/mini");
@@ -202,7 +388,11 @@ fn test_determine_keyboard_variant_missing_variant_directory() {
 
     let build_config = BuildConfig::default();
 
-    let result = build_config.determine_keyboard_variant(&qmk_path, "keebart/corne_choc_pro", 42);
+    let result = build_config.determine_keyboard_variant(
+        &qmk_path.join("keyboards"),
+        "keebart/corne_choc_pro",
+        42,
+    );
 
     // Should return the base keyboard path when no variants are detected
     assert_eq!(result.unwrap(), "keebart/corne_choc_pro");
@@ -224,7 +414,7 @@ fn test_determine_keyboard_variant_with_info_json() {
 
     // Use 44 keys which maps to "standard" variant
     let result = build_config
-        .determine_keyboard_variant(&qmk_path, "test_keyboard", 44)
+        .determine_keyboard_variant(&qmk_path.join("keyboards"), "test_keyboard", 44)
         .unwrap();
 
     assert_eq!(result, "test_keyboard/standard");
@@ -372,7 +562,7 @@ fn test_determine_keyboard_variant_with_rev1() {
 
     // With 44 keys, it prefers "standard" but should fallback to "rev1" since standard doesn't exist
     let result = build_config
-        .determine_keyboard_variant(&qmk_path, "test_keyboard", 44)
+        .determine_keyboard_variant(&qmk_path.join("keyboards"), "test_keyboard", 44)
         .unwrap();
 
     assert_eq!(result, "test_keyboard/rev1");
@@ -397,8 +587,101 @@ fn test_determine_keyboard_variant_multiple_variants_prefers_standard() {
 
     // With 44 keys, should prefer "standard" when it exists
     let result = build_config
-        .determine_keyboard_variant(&qmk_path, "test_keyboard", 44)
+        .determine_keyboard_variant(&qmk_path.join("keyboards"), "test_keyboard", 44)
         .unwrap();
 
     assert_eq!(result, "test_keyboard/standard");
 }
+
+#[test]
+fn test_safe_mode_uses_current_config_and_remembers_it() {
+    let temp = TempDir::new().unwrap();
+    let mut config = Config::new();
+    config.ui.theme_mode = ThemeMode::Dark;
+    config.hooks.post_save = vec!["echo".to_string()];
+    config.git.auto_commit = true;
+    config.build.output_dir = PathBuf::from("/tmp/out");
+    fs::write(
+        temp.path().join("config.toml"),
+        toml::to_string_pretty(&config).unwrap(),
+    )
+    .unwrap();
+
+    let safe = Config::load_safe_mode_in(temp.path()).unwrap();
+
+    assert_eq!(safe.source, SafeModeSource::Current);
+    assert_eq!(safe.config.ui, UiConfig::default());
+    assert_eq!(safe.config.hooks, HooksConfig::default());
+    assert!(!safe.config.git.auto_commit);
+    // Everything else is kept
+    assert_eq!(safe.config.build.output_dir, PathBuf::from("/tmp/out"));
+    let backup = safe.backup.unwrap();
+    assert_eq!(backup, temp.path().join("config.last-good.toml"));
+    assert_eq!(
+        fs::read_to_string(backup).unwrap(),
+        fs::read_to_string(temp.path().join("config.toml")).unwrap()
+    );
+}
+
+#[test]
+fn test_safe_mode_falls_back_to_last_known_good() {
+    let temp = TempDir::new().unwrap();
+    let mut config = Config::new();
+    config.build.output_dir = PathBuf::from("/tmp/good");
+    fs::write(
+        temp.path().join("config.toml"),
+        toml::to_string_pretty(&config).unwrap(),
+    )
+    .unwrap();
+    Config::remember_last_known_good_in(temp.path()).unwrap();
+    fs::write(temp.path().join("config.toml"), "[ui\ntheme_mode = ").unwrap();
+
+    let safe = Config::load_safe_mode_in(temp.path()).unwrap();
+
+    assert_eq!(safe.source, SafeModeSource::LastKnownGood);
+    assert_eq!(safe.config.build.output_dir, PathBuf::from("/tmp/good"));
+    // The broken file is kept for inspection
+    let backup = safe.backup.unwrap();
+    assert_eq!(backup, temp.path().join("config.broken.toml"));
+    assert_eq!(fs::read_to_string(backup).unwrap(), "[ui\ntheme_mode = ");
+}
+
+#[test]
+fn test_safe_mode_uses_defaults_without_usable_config() {
+    let temp = TempDir::new().unwrap();
+
+    let safe = Config::load_safe_mode_in(temp.path()).unwrap();
+    assert_eq!(safe.source, SafeModeSource::Defaults);
+    assert_eq!(safe.config, Config::new().with_safe_mode_restrictions());
+    assert!(safe.backup.is_none());
+
+    fs::write(temp.path().join("config.toml"), "not toml [").unwrap();
+    let safe = Config::load_safe_mode_in(temp.path()).unwrap();
+    assert_eq!(safe.source, SafeModeSource::Defaults);
+    assert!(safe.backup.is_some());
+    assert!(!temp.path().join("config.last-good.toml").exists());
+}
+
+#[test]
+fn test_safe_mode_config_is_never_saved() {
+    let temp = TempDir::new().unwrap();
+    let mut config = Config::new();
+    config.ui.theme_mode = ThemeMode::Dark;
+    config.hooks.post_save = vec!["echo".to_string()];
+    let content = toml::to_string_pretty(&config).unwrap();
+    fs::write(temp.path().join("config.toml"), &content).unwrap();
+
+    let mut safe = Config::load_safe_mode_in(temp.path()).unwrap().config;
+    assert!(safe.safe_mode);
+    safe.ui.theme_mode = ThemeMode::Light;
+    safe.save_in(temp.path()).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(temp.path().join("config.toml")).unwrap(),
+        content
+    );
+    assert_eq!(
+        fs::read_to_string(temp.path().join("config.last-good.toml")).unwrap(),
+        content
+    );
+}
@@ -58,6 +58,11 @@ use anyhow::{Context, Result};
 use std::path::Path;
 use std::process::Command;
 
+use crate::keycode_db::KeycodeDb;
+
+/// Keycode database issues listed individually before summarizing the rest.
+const MAX_REPORTED_DB_ISSUES: usize = 10;
+
 /// Status of a single dependency check.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ToolStatus {
@@ -321,6 +326,56 @@ impl DependencyChecker {
         }
     }
 
+    /// Runs the embedded keycode database self-test.
+    ///
+    /// Reports duplicate codes, keycodes referencing unknown categories and
+    /// invalid validation patterns.
+    ///
+    /// # Returns
+    ///
+    /// Status listing the first problems found, if any.
+    pub fn check_keycode_db(&self) -> DependencyStatus {
+        let db = match KeycodeDb::load() {
+            Ok(db) => db,
+            Err(e) => {
+                return DependencyStatus::missing(
+                    "Keycode Database",
+                    format!("Failed to load: {e}"),
+                )
+            }
+        };
+
+        let issues = db.check_integrity();
+        if issues.is_empty() {
+            return DependencyStatus::new(
+                "Keycode Database",
+                ToolStatus::Available,
+                None,
+                format!(
+                    "{} keycodes in {} categories",
+                    db.keycode_count(),
+                    db.category_count()
+                ),
+            );
+        }
+
+        let mut lines = vec![format!("{} integrity issue(s):", issues.len())];
+        lines.extend(
+            issues
+                .iter()
+                .take(MAX_REPORTED_DB_ISSUES)
+                .map(ToString::to_string),
+        );
+        if issues.len() > MAX_REPORTED_DB_ISSUES {
+            lines.push(format!(
+                "... and {} more",
+                issues.len() - MAX_REPORTED_DB_ISSUES
+            ));
+        }
+        let message = lines.join("\n");
+        DependencyStatus::missing("Keycode Database", message)
+    }
+
     /// Runs a command to get version information.
     ///
     /// # Arguments
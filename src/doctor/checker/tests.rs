@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_parse_version_simple() {
     assert_eq!(
@@ -77,7 +75,7 @@ fn test_check_qmk_firmware_nonexistent() {
 }
 
 #[test]
-#[ignore] // Requires actual QMK submodule
+#[ignore = "requires actual QMK submodule"]
 fn test_check_qmk_firmware_valid() {
     let checker = DependencyChecker::new();
     let qmk_path = Path::new("qmk_firmware");
@@ -103,3 +101,13 @@ fn test_check_all_returns_all_statuses() {
     assert_eq!(statuses[2].name, "AVR GCC");
     assert_eq!(statuses[3].name, "QMK Firmware");
 }
+
+#[test]
+fn test_check_keycode_db_passes_for_embedded_database() {
+    let checker = DependencyChecker::new();
+    let status = checker.check_keycode_db();
+
+    assert_eq!(status.name, "Keycode Database");
+    assert_eq!(status.status, ToolStatus::Available);
+    assert!(status.message.contains("keycodes"));
+}
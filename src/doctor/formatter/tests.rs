@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 fn sample_statuses() -> Vec<DependencyStatus> {
     vec![
         DependencyStatus::available("QMK CLI", "1.1.5"),
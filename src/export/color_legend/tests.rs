@@ -4,7 +4,6 @@
 
 use super::*;
 
-use super::*;
 use crate::models::{Category, KeyDefinition, Layer, Position};
 
 #[test]
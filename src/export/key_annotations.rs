@@ -0,0 +1,67 @@
+//! Annotated keys section for layout exports.
+//!
+//! Lists every key that has a user description, so obscure macros and
+//! special-purpose keys are documented alongside the diagrams.
+
+use crate::models::Layout;
+use std::fmt::Write;
+
+/// Generates the annotated keys section for layout exports.
+///
+/// Produces a markdown table of every key with a non-empty description,
+/// grouped by layer in key order. Returns an empty string when no key has
+/// a description, so the section is omitted from the export.
+///
+/// # Example Output
+///
+/// ```markdown
+/// ## Annotated Keys
+///
+/// | Layer | Position | Keycode | Description |
+/// |-------|----------|---------|-------------|
+/// | 0: Base | (3,5) | LT(@nav, KC_SPC) | Primary thumb key |
+/// | 2: Macros | (1,0) | M_EMAIL | Types my work email |
+/// ```
+pub fn generate_key_annotations(layout: &Layout) -> String {
+    let mut rows = String::new();
+
+    for (idx, layer) in layout.layers.iter().enumerate() {
+        for key in &layer.keys {
+            let Some(description) = key.description.as_deref().map(str::trim) else {
+                continue;
+            };
+            if description.is_empty() {
+                continue;
+            }
+            let _ = writeln!(
+                rows,
+                "| {idx}: {} | ({},{}) | {} | {} |",
+                escape_cell(&layer.name),
+                key.position.row,
+                key.position.col,
+                escape_cell(&key.keycode),
+                escape_cell(description)
+            );
+        }
+    }
+
+    if rows.is_empty() {
+        return String::new();
+    }
+
+    let mut output = String::new();
+    output.push_str("## Annotated Keys\n\n");
+    output.push_str("| Layer | Position | Keycode | Description |\n");
+    output.push_str("|-------|----------|---------|-------------|\n");
+    output.push_str(&rows);
+    output.push('\n');
+    output
+}
+
+/// Escapes pipes and flattens newlines so text fits in a table cell.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests;
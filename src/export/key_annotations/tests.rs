@@ -0,0 +1,44 @@
+//! Tests for key_annotations.
+
+use super::*;
+
+use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+
+    let mut base = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    base.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    base.add_key(KeyDefinition::new(Position::new(3, 5), "KC_SPC").with_description("Thumb"));
+    layout.add_layer(base).unwrap();
+
+    let mut macros = Layer::new(1, "Macros", RgbColor::new(0, 0, 255)).unwrap();
+    macros.add_key(KeyDefinition::new(Position::new(1, 0), "KC_NO").with_description("   "));
+    macros.add_key(
+        KeyDefinition::new(Position::new(1, 1), "M_EMAIL").with_description("Email | work\nonly"),
+    );
+    layout.add_layer(macros).unwrap();
+
+    layout
+}
+
+#[test]
+fn test_generate_key_annotations_empty_without_descriptions() {
+    let mut layout = Layout::new("Test").unwrap();
+    let mut base = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    base.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    layout.add_layer(base).unwrap();
+
+    assert!(generate_key_annotations(&layout).is_empty());
+}
+
+#[test]
+fn test_generate_key_annotations_lists_described_keys() {
+    let output = generate_key_annotations(&create_test_layout());
+
+    assert!(output.starts_with("## Annotated Keys\n\n| Layer | Position | Keycode | Description |"));
+    assert!(output.contains("| 0: Base | (3,5) | KC_SPC | Thumb |"));
+    assert!(output.contains("| 1: Macros | (1,1) | M_EMAIL | Email \\| work only |"));
+    assert!(!output.contains("KC_A"));
+    assert!(!output.contains("(1,0)"));
+}
@@ -6,7 +6,7 @@
 //! - `format_modifier` — `MOD_LCTL` → `Ctrl`, etc.
 
 /// Handles tap-hold keys with split display (e.g., "LT(1, `KC_A`)" -> "L1 / A")
-pub(super) fn format_keycode(keycode: &str) -> String {
+pub(in crate::export) fn format_keycode(keycode: &str) -> String {
     // Handle Layer Tap: LT(layer, keycode)
     if let Some(inner) = keycode.strip_prefix("LT(") {
         if let Some(args) = inner.strip_suffix(')') {
@@ -15,6 +15,7 @@ mod color_ref;
 mod formatting;
 mod rendering;
 
+pub(super) use formatting::format_keycode;
 use rendering::{build_key_grid, render_grid};
 
 /// A single key positioned in the rendering grid.
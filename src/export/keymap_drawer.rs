@@ -0,0 +1,262 @@
+//! keymap-drawer YAML export.
+//!
+//! Produces a keymap file for [keymap-drawer](https://github.com/caksoylar/keymap-drawer),
+//! which renders publishable SVG diagrams (`keymap draw layout.yaml`).
+//! Keys are listed per layer in the keyboard's QMK layout order, labelled
+//! with the same short legends as the web editor. Combos are exported when
+//! enabled; hold combos are drawn on the base layer only.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{ensure, Context, Result};
+use serde::Serialize;
+
+use crate::keycode_db::{ActionKind, KeycodeDb, TapDanceDisplayInfo};
+use crate::models::{KeyboardGeometry, Layout, Position, TapDanceAction, VisualLayoutMapping};
+
+/// Top-level keymap-drawer document.
+#[derive(Debug, Serialize)]
+struct KeymapDrawerDoc {
+    layout: PhysicalLayout,
+    layers: serde_yml::Mapping,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    combos: Vec<ComboSpec>,
+}
+
+/// Tells keymap-drawer which physical layout to draw.
+#[derive(Debug, Serialize)]
+struct PhysicalLayout {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    qmk_keyboard: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    layout_name: Option<String>,
+}
+
+/// A single key: a plain legend or tap/hold/shifted legends.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum KeySpec {
+    Plain(String),
+    Legends {
+        t: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        h: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        s: Option<String>,
+        #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+        kind: Option<&'static str>,
+    },
+}
+
+/// A combo drawn between its trigger keys.
+#[derive(Debug, Serialize)]
+struct ComboSpec {
+    /// Key indices in layout order
+    p: Vec<usize>,
+    /// Legend
+    k: String,
+    /// Layers the combo is drawn on (all if empty)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    l: Vec<String>,
+}
+
+/// Exports a layout as a keymap-drawer YAML keymap.
+///
+/// # Errors
+///
+/// Returns an error if the geometry has no keys or serialization fails.
+pub fn export_to_keymap_drawer(
+    layout: &Layout,
+    geometry: &KeyboardGeometry,
+    keycode_db: &KeycodeDb,
+) -> Result<String> {
+    ensure!(!geometry.keys.is_empty(), "Keyboard geometry has no keys");
+
+    let mapping = VisualLayoutMapping::build(geometry);
+    // keymap-drawer expects keys in the order of the QMK layout macro
+    let positions: Vec<Option<Position>> = geometry
+        .keys
+        .iter()
+        .map(|key| {
+            let (row, col) = key.matrix_position;
+            mapping.matrix_to_visual_pos(row, col)
+        })
+        .collect();
+
+    let labels = Labels::new(keycode_db, layout);
+    let layer_names = unique_layer_names(layout);
+
+    let mut layers = serde_yml::Mapping::new();
+    for (layer, name) in layout.layers.iter().zip(&layer_names) {
+        let keys: Vec<KeySpec> = positions
+            .iter()
+            .map(|pos| {
+                pos.and_then(|pos| layer.keys.iter().find(|k| k.position == pos))
+                    .map_or_else(
+                        || KeySpec::Plain(String::new()),
+                        |key| labels.key(&key.keycode),
+                    )
+            })
+            .collect();
+        layers.insert(
+            name.clone().into(),
+            serde_yml::to_value(keys).context("Failed to serialize layer")?,
+        );
+    }
+
+    let doc = KeymapDrawerDoc {
+        layout: PhysicalLayout {
+            qmk_keyboard: layout.metadata.keyboard.clone(),
+            layout_name: layout.metadata.layout_variant.clone(),
+        },
+        layers,
+        combos: combos(layout, &positions, &labels, &layer_names),
+    };
+
+    serde_yml::to_string(&doc).context("Failed to serialize keymap-drawer YAML")
+}
+
+/// Names layers for keymap-drawer, which requires them to be unique.
+fn unique_layer_names(layout: &Layout) -> Vec<String> {
+    let mut seen = HashSet::new();
+    layout
+        .layers
+        .iter()
+        .enumerate()
+        .map(|(idx, layer)| {
+            let name = if layer.name.trim().is_empty() {
+                format!("Layer {idx}")
+            } else {
+                layer.name.clone()
+            };
+            if seen.insert(name.clone()) {
+                name
+            } else {
+                let name = format!("{name} ({idx})");
+                seen.insert(name.clone());
+                name
+            }
+        })
+        .collect()
+}
+
+/// Builds the combos drawn on the diagram, skipping any whose keys are not
+/// on the keyboard.
+fn combos(
+    layout: &Layout,
+    positions: &[Option<Position>],
+    labels: &Labels,
+    layer_names: &[String],
+) -> Vec<ComboSpec> {
+    let settings = &layout.combo_settings;
+    if !settings.enabled {
+        return Vec::new();
+    }
+    let index_of = |pos: &Position| positions.iter().position(|p| p.as_ref() == Some(pos));
+    let base_layer: Vec<String> = layer_names.first().cloned().into_iter().collect();
+
+    let hold = settings.combos.iter().filter_map(|combo| {
+        Some(ComboSpec {
+            p: vec![index_of(&combo.key1)?, index_of(&combo.key2)?],
+            k: combo.action.display_name().to_string(),
+            l: base_layer.clone(),
+        })
+    });
+    let keycode = settings.keycode_combos.iter().filter_map(|combo| {
+        Some(ComboSpec {
+            p: combo.keys.iter().map(index_of).collect::<Option<_>>()?,
+            k: labels.legend(&combo.keycode),
+            l: Vec::new(),
+        })
+    });
+    hold.chain(keycode).collect()
+}
+
+/// Resolves key legends, including tap dances and layer references.
+struct Labels<'a> {
+    keycode_db: &'a KeycodeDb,
+    tap_dances: HashMap<&'a str, &'a TapDanceAction>,
+    layer_id_to_number: HashMap<String, u8>,
+}
+
+impl<'a> Labels<'a> {
+    fn new(keycode_db: &'a KeycodeDb, layout: &'a Layout) -> Self {
+        Self {
+            keycode_db,
+            tap_dances: layout
+                .tap_dances
+                .iter()
+                .map(|td| (td.name.as_str(), td))
+                .collect(),
+            layer_id_to_number: layout
+                .layers
+                .iter()
+                .map(|layer| (layer.id.clone(), layer.number))
+                .collect(),
+        }
+    }
+
+    /// Builds the key spec for `keycode`.
+    ///
+    /// The secondary label is the hold legend, unless the key has a double
+    /// tap action: then it is drawn as the shifted legend and the hold
+    /// action (the tertiary label) as the hold legend.
+    fn key(&self, keycode: &str) -> KeySpec {
+        if keycode == "KC_TRNS" || keycode == "_______" {
+            return KeySpec::Legends {
+                t: "▽".to_string(),
+                h: None,
+                s: None,
+                kind: Some("trans"),
+            };
+        }
+
+        let meta = self.metadata(keycode);
+        let display = meta.display;
+        let (h, s) = if meta
+            .details
+            .iter()
+            .any(|action| action.kind == ActionKind::DoubleTap)
+        {
+            (display.tertiary, display.secondary)
+        } else {
+            (display.secondary, display.tertiary)
+        };
+
+        if h.is_none() && s.is_none() {
+            KeySpec::Plain(display.primary)
+        } else {
+            KeySpec::Legends {
+                t: display.primary,
+                h,
+                s,
+                kind: None,
+            }
+        }
+    }
+
+    /// Returns the primary legend for `keycode`.
+    fn legend(&self, keycode: &str) -> String {
+        self.metadata(keycode).display.primary
+    }
+
+    fn metadata(&self, keycode: &str) -> crate::keycode_db::KeyDisplayMetadata {
+        let td_info = self
+            .keycode_db
+            .parse_tap_dance_keycode(keycode)
+            .and_then(|name| self.tap_dances.get(name.as_str()))
+            .map(|td| TapDanceDisplayInfo {
+                single_tap: td.single_tap.clone(),
+                double_tap: td.double_tap.clone(),
+                hold: td.hold.clone(),
+            });
+        self.keycode_db.get_display_metadata(
+            keycode,
+            td_info.as_ref(),
+            Some(&self.layer_id_to_number),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,132 @@
+//! Tests for export::keymap_drawer.
+
+use super::*;
+use crate::models::{
+    ComboAction, ComboDefinition, KeyDefinition, KeyGeometry, KeycodeCombo, Layer, RgbColor,
+};
+
+fn create_test_geometry() -> KeyboardGeometry {
+    let mut geom = KeyboardGeometry::new("test", "LAYOUT", 1, 3);
+    for col in 0u8..3 {
+        geom.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+    }
+    geom
+}
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    layout.metadata.keyboard = Some("crkbd/rev1".to_string());
+    layout.metadata.layout_variant = Some("LAYOUT_split_3x6_3".to_string());
+    for (idx, name) in ["Base", "Nav"].into_iter().enumerate() {
+        let mut layer = Layer::new(idx as u8, name, RgbColor::new(0, 0, 80)).unwrap();
+        for col in 0u8..3 {
+            layer.add_key(KeyDefinition::new(Position::new(0, col), "KC_TRNS"));
+        }
+        layout.add_layer(layer).unwrap();
+    }
+    layout.layers[0].keys[0].keycode = "KC_A".to_string();
+    layout.layers[0].keys[1].keycode = "LT(1, KC_SPC)".to_string();
+    layout.layers[0].keys[2].keycode = "TD(esc_caps)".to_string();
+    layout.tap_dances.push(
+        TapDanceAction::new("esc_caps", "KC_ESC")
+            .with_double_tap("KC_CAPS")
+            .with_hold("KC_LCTL"),
+    );
+    layout
+}
+
+fn export(layout: &Layout) -> serde_yml::Value {
+    let db = KeycodeDb::load().unwrap();
+    let yaml = export_to_keymap_drawer(layout, &create_test_geometry(), &db).unwrap();
+    serde_yml::from_str(&yaml).unwrap()
+}
+
+#[test]
+fn test_export_to_keymap_drawer_physical_layout() {
+    let doc = export(&create_test_layout());
+
+    assert_eq!(doc["layout"]["qmk_keyboard"], "crkbd/rev1");
+    assert_eq!(doc["layout"]["layout_name"], "LAYOUT_split_3x6_3");
+}
+
+#[test]
+fn test_export_to_keymap_drawer_tap_hold_legends() {
+    let doc = export(&create_test_layout());
+    let base = &doc["layers"]["Base"];
+
+    assert_eq!(base[0], "A");
+    assert_eq!(base[1]["t"], "SPC");
+    assert_eq!(base[1]["h"], "L1");
+    // Tap dance: double tap as shifted legend, hold as hold legend
+    assert_eq!(base[2]["t"], "ESC");
+    assert_eq!(base[2]["s"], "CAPS");
+    assert_eq!(base[2]["h"], "LCTL");
+}
+
+#[test]
+fn test_export_to_keymap_drawer_transparent_keys() {
+    let doc = export(&create_test_layout());
+    let nav = doc["layers"]["Nav"].as_sequence().unwrap();
+
+    assert_eq!(nav.len(), 3);
+    assert!(nav.iter().all(|key| key["type"] == "trans"));
+}
+
+#[test]
+fn test_export_to_keymap_drawer_keeps_layer_names_unique() {
+    let mut layout = create_test_layout();
+    layout.layers[1].name = "Base".to_string();
+    let doc = export(&layout);
+
+    assert!(doc["layers"]["Base"].is_sequence());
+    assert!(doc["layers"]["Base (1)"].is_sequence());
+}
+
+#[test]
+fn test_export_to_keymap_drawer_combos() {
+    let mut layout = create_test_layout();
+    layout.combo_settings.enabled = true;
+    layout.combo_settings.combos.push(ComboDefinition::new(
+        Position::new(0, 0),
+        Position::new(0, 1),
+        ComboAction::Bootloader,
+    ));
+    layout.combo_settings.keycode_combos.push(KeycodeCombo::new(
+        vec![Position::new(0, 1), Position::new(0, 2)],
+        "KC_TAB",
+    ));
+    // Combos on keys the keyboard doesn't have are left out
+    layout.combo_settings.keycode_combos.push(KeycodeCombo::new(
+        vec![Position::new(5, 5), Position::new(0, 2)],
+        "KC_ENT",
+    ));
+    let doc = export(&layout);
+    let combos = doc["combos"].as_sequence().unwrap();
+
+    assert_eq!(combos.len(), 2);
+    assert_eq!(combos[0]["p"][0], 0);
+    assert_eq!(combos[0]["p"][1], 1);
+    assert_eq!(combos[0]["k"], "Bootloader");
+    assert_eq!(combos[0]["l"][0], "Base");
+    assert_eq!(combos[1]["k"], "TAB");
+    assert!(combos[1].get("l").is_none());
+}
+
+#[test]
+fn test_export_to_keymap_drawer_omits_disabled_combos() {
+    let mut layout = create_test_layout();
+    layout.combo_settings.keycode_combos.push(KeycodeCombo::new(
+        vec![Position::new(0, 1), Position::new(0, 2)],
+        "KC_TAB",
+    ));
+
+    assert!(export(&layout).get("combos").is_none());
+}
+
+#[test]
+fn test_export_to_keymap_drawer_rejects_empty_geometry() {
+    let geometry = KeyboardGeometry::new("test", "LAYOUT", 0, 0);
+    let db = KeycodeDb::load().unwrap();
+
+    assert!(export_to_keymap_drawer(&create_test_layout(), &geometry, &db).is_err());
+}
@@ -4,7 +4,6 @@
 
 use super::*;
 
-use super::*;
 use crate::models::{KeyDefinition, Layer, Position, RgbColor};
 
 #[test]
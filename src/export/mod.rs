@@ -1,8 +1,9 @@
 //! Export functionality for keyboard layouts.
 //!
-//! This module provides tools to export keyboard layout configurations in various formats,
-//! currently focused on generating markdown documentation with visual representations
-//! and configuration summaries.
+//! This module provides tools to export keyboard layout configurations in various formats:
+//! markdown documentation with visual representations and configuration summaries,
+//! SVG diagrams of every layer, keymap-drawer YAML for publishable diagrams,
+//! QMK keymap JSON, and keymaps for VIA and ZMK.
 
 use crate::keycode_db::KeycodeDb;
 use crate::models::{KeyboardGeometry, Layout};
@@ -10,16 +11,28 @@ use anyhow::Result;
 use std::fmt::Write as _;
 
 pub mod color_legend;
+pub mod key_annotations;
 pub mod keyboard_renderer;
+pub mod keymap_drawer;
 pub mod layer_navigation;
+pub mod qmk_json;
 pub mod settings_summary;
+pub mod svg;
 pub mod tap_dance_docs;
+pub mod via;
+pub mod zmk;
 
 pub use color_legend::generate_color_legend;
+pub use key_annotations::generate_key_annotations;
 pub use keyboard_renderer::render_layer_diagram;
+pub use keymap_drawer::export_to_keymap_drawer;
 pub use layer_navigation::generate_layer_navigation;
+pub use qmk_json::export_to_qmk_json;
 pub use settings_summary::generate_settings_summary;
+pub use svg::export_to_svg;
 pub use tap_dance_docs::generate_tap_dance_docs;
+pub use via::export_to_via;
+pub use zmk::export_to_zmk;
 
 /// Export a complete keyboard layout to markdown format.
 ///
@@ -31,6 +44,7 @@ pub use tap_dance_docs::generate_tap_dance_docs;
 /// - Color legend
 /// - Layer navigation map
 /// - Tap dance documentation
+/// - Annotated keys (keys with descriptions)
 /// - Settings summary
 pub fn export_to_markdown(
     layout: &Layout,
@@ -84,7 +98,10 @@ pub fn export_to_markdown(
         output.push_str(&generate_tap_dance_docs(layout, keycode_db));
     }
 
-    // 8. Settings Summary
+    // 8. Annotated Keys
+    output.push_str(&generate_key_annotations(layout));
+
+    // 9. Settings Summary
     output.push_str(&generate_settings_summary(layout));
 
     Ok(output)
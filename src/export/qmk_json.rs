@@ -0,0 +1,110 @@
+//! QMK keymap JSON export.
+//!
+//! Produces a `keymap.json` in the format of QMK Configurator, which builds
+//! with `qmk compile keymap.json` and opens in Configurator. Each layer lists
+//! its keycodes in LAYOUT macro order, with `KC_NO` for keys the layer
+//! doesn't define. Layer references are resolved to layer numbers. Tap
+//! dances and macros are C code that keymap JSON can't hold, so they are
+//! exported as `KC_NO`.
+
+use anyhow::{ensure, Context, Result};
+use serde::Serialize;
+
+use crate::keycode_db::KeycodeDb;
+use crate::models::{KeyboardGeometry, Layout, Position, VisualLayoutMapping};
+
+/// Keymap JSON format version written by QMK.
+const KEYMAP_JSON_VERSION: u32 = 1;
+
+/// QMK keymap JSON document.
+#[derive(Debug, Serialize)]
+struct KeymapJson {
+    version: u32,
+    notes: String,
+    keyboard: String,
+    keymap: String,
+    layout: String,
+    layers: Vec<Vec<String>>,
+    author: String,
+}
+
+/// Exports a layout as a QMK `keymap.json`.
+///
+/// # Errors
+///
+/// Returns an error if the geometry has no keys or serialization fails.
+pub fn export_to_qmk_json(
+    layout: &Layout,
+    geometry: &KeyboardGeometry,
+    keycode_db: &KeycodeDb,
+) -> Result<String> {
+    ensure!(!geometry.keys.is_empty(), "Keyboard geometry has no keys");
+
+    let mapping = VisualLayoutMapping::build(geometry);
+    // Geometry keys are in LAYOUT macro order
+    let positions: Vec<Option<Position>> = geometry
+        .keys
+        .iter()
+        .map(|key| {
+            let (row, col) = key.matrix_position;
+            mapping.matrix_to_visual_pos(row, col)
+        })
+        .collect();
+
+    let layers = layout
+        .layers
+        .iter()
+        .map(|layer| {
+            positions
+                .iter()
+                .map(|pos| {
+                    pos.and_then(|pos| layer.keys.iter().find(|k| k.position == pos))
+                        .map_or_else(
+                            || "KC_NO".to_string(),
+                            |key| qmk_json_keycode(layout, keycode_db, &key.keycode),
+                        )
+                })
+                .collect()
+        })
+        .collect();
+
+    let doc = KeymapJson {
+        version: KEYMAP_JSON_VERSION,
+        notes: layout.metadata.description.clone(),
+        keyboard: layout
+            .metadata
+            .keyboard
+            .clone()
+            .unwrap_or_else(|| geometry.keyboard_name.clone()),
+        keymap: layout
+            .metadata
+            .keymap_name
+            .clone()
+            .unwrap_or_else(|| "default".to_string()),
+        layout: geometry.layout_name.clone(),
+        layers,
+        author: layout.metadata.author.clone(),
+    };
+    serde_json::to_string_pretty(&doc).context("Failed to serialize keymap.json")
+}
+
+/// Converts a layout keycode to the form QMK Configurator writes.
+fn qmk_json_keycode(layout: &Layout, keycode_db: &KeycodeDb, keycode: &str) -> String {
+    match keycode {
+        "" | "XXXXXXX" => return "KC_NO".to_string(),
+        "_______" => return "KC_TRNS".to_string(),
+        _ => {}
+    }
+    if keycode_db.parse_tap_dance_keycode(keycode).is_some() || keycode.starts_with("MACRO(") {
+        return "KC_NO".to_string();
+    }
+
+    let resolved = layout
+        .resolve_layer_keycode(keycode, keycode_db)
+        .unwrap_or_else(|| keycode.to_string());
+    // Configurator writes "LT(1,KC_SPC)" without spaces
+    resolved.split_whitespace().collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,68 @@
+//! Tests for export::qmk_json.
+
+use super::*;
+use crate::models::{KeyDefinition, KeyGeometry, Layer, RgbColor, TapDanceAction};
+use crate::parser::qmk_json::parse_qmk_json;
+
+/// Three keys in LAYOUT order, wired to the matrix right to left.
+fn create_test_geometry() -> KeyboardGeometry {
+    let mut geom = KeyboardGeometry::new("test", "LAYOUT", 1, 3);
+    geom.add_key(KeyGeometry::new((0, 2), 0, 0.0, 0.0));
+    geom.add_key(KeyGeometry::new((0, 1), 1, 1.0, 0.0));
+    geom.add_key(KeyGeometry::new((0, 0), 2, 2.0, 0.0));
+    geom
+}
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    layout.metadata.keyboard = Some("crkbd/rev1".to_string());
+    layout.metadata.author = "me".to_string();
+    for (idx, name) in ["Base", "Nav"].into_iter().enumerate() {
+        let mut layer = Layer::new(idx as u8, name, RgbColor::new(0, 0, 80)).unwrap();
+        for col in 0..2 {
+            layer.add_key(KeyDefinition::new(Position::new(0, col), "KC_TRNS"));
+        }
+        layout.add_layer(layer).unwrap();
+    }
+    let nav_id = layout.layers[1].id.clone();
+    layout.layers[0].keys[0].keycode = format!("LT(@{nav_id}, KC_SPC)");
+    layout.layers[0].keys[1].keycode = "TD(esc_caps)".to_string();
+    layout
+        .tap_dances
+        .push(TapDanceAction::new("esc_caps", "KC_ESC").with_double_tap("KC_CAPS"));
+    layout
+}
+
+fn export(layout: &Layout) -> String {
+    let db = KeycodeDb::load().unwrap();
+    export_to_qmk_json(layout, &create_test_geometry(), &db).unwrap()
+}
+
+#[test]
+fn test_export_to_qmk_json_layers_in_layout_order() {
+    let doc: serde_json::Value = serde_json::from_str(&export(&create_test_layout())).unwrap();
+
+    assert_eq!(doc["version"], 1);
+    assert_eq!(doc["keyboard"], "crkbd/rev1");
+    assert_eq!(doc["keymap"], "default");
+    assert_eq!(doc["layout"], "LAYOUT");
+    assert_eq!(doc["author"], "me");
+    assert_eq!(
+        doc["layers"],
+        serde_json::json!([
+            ["LT(1,KC_SPC)", "KC_NO", "KC_NO"],
+            ["KC_TRNS", "KC_TRNS", "KC_NO"]
+        ])
+    );
+}
+
+#[test]
+fn test_export_to_qmk_json_round_trips() {
+    let mut layout = create_test_layout();
+    layout.metadata.keymap_name = Some("mine".to_string());
+    let parsed = parse_qmk_json(&export(&layout)).unwrap();
+
+    assert_eq!(parsed.keyboard.as_deref(), Some("crkbd/rev1"));
+    assert_eq!(parsed.keymap_name.as_deref(), Some("mine"));
+    assert_eq!(parsed.keymap.layers[0].keycodes[0], "LT(1, KC_SPC)");
+}
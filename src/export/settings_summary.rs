@@ -111,6 +111,10 @@ pub fn generate_settings_summary(layout: &Layout) -> String {
         output.push_str("- **Output Format:** Not specified\n");
     }
 
+    if let Some(converter) = &layout.metadata.converter {
+        let _ = writeln!(output, "- **Converter:** {converter}");
+    }
+
     output
 }
 
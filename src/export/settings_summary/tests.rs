@@ -4,7 +4,6 @@
 
 use super::*;
 
-use super::*;
 use crate::models::{Layout, RgbBrightness, RgbMatrixEffect, RgbSaturation, UncoloredKeyBehavior};
 
 #[test]
@@ -0,0 +1,145 @@
+//! SVG keyboard diagrams for layout export.
+//!
+//! Renders every layer as a row of colored key caps at their physical
+//! positions, stacked vertically in a single SVG document. Key fills use the
+//! same color priority as the editor (individual > category > layer).
+
+use std::fmt::Write as _;
+
+use anyhow::{ensure, Result};
+
+use crate::models::{KeyDefinition, KeyboardGeometry, Layout, RgbColor, VisualLayoutMapping};
+
+use super::keyboard_renderer::format_keycode;
+
+/// Size of a 1u key in pixels, including the gap to its neighbours.
+const KEY_UNIT: f32 = 60.0;
+/// Gap between neighbouring key caps in pixels.
+const KEY_GAP: f32 = 4.0;
+/// Outer margin and spacing between layers in pixels.
+const MARGIN: f32 = 20.0;
+/// Height reserved for each layer's title in pixels.
+const TITLE_HEIGHT: f32 = 28.0;
+
+/// Exports all layers of a layout as an SVG document.
+///
+/// # Errors
+///
+/// Returns an error if the geometry has no keys.
+pub fn export_to_svg(layout: &Layout, geometry: &KeyboardGeometry) -> Result<String> {
+    ensure!(!geometry.keys.is_empty(), "Keyboard geometry has no keys");
+
+    let mapping = VisualLayoutMapping::build(geometry);
+    let (min_x, min_y, max_x, max_y) = geometry.keys.iter().fold(
+        (f32::MAX, f32::MAX, f32::MIN, f32::MIN),
+        |(min_x, min_y, max_x, max_y), key| {
+            (
+                min_x.min(key.visual_x),
+                min_y.min(key.visual_y),
+                max_x.max(key.visual_x + key.width),
+                max_y.max(key.visual_y + key.height),
+            )
+        },
+    );
+
+    let board_height = (max_y - min_y) * KEY_UNIT;
+    let layer_height = TITLE_HEIGHT + board_height + MARGIN;
+    let width = (max_x - min_x).mul_add(KEY_UNIT, MARGIN * 2.0);
+    #[allow(clippy::cast_precision_loss)]
+    let height = (layout.layers.len() as f32).mul_add(layer_height, MARGIN);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}" viewBox="0 0 {width:.0} {height:.0}" font-family="sans-serif">"#
+    );
+    let _ = writeln!(svg, "  <title>{}</title>", escape(&layout.metadata.name));
+    let _ = writeln!(
+        svg,
+        r##"  <rect width="100%" height="100%" fill="#1E1E1E"/>"##
+    );
+
+    for (layer_idx, layer) in layout.layers.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let top = (layer_idx as f32).mul_add(layer_height, MARGIN);
+        let _ = writeln!(
+            svg,
+            r##"  <g id="layer-{layer_idx}">
+    <text x="{MARGIN:.0}" y="{:.1}" font-size="16" fill="#FFFFFF">Layer {layer_idx}: {}</text>"##,
+            top + 18.0,
+            escape(&layer.name)
+        );
+
+        for key_geom in &geometry.keys {
+            let (row, col) = key_geom.matrix_position;
+            let Some(key) = mapping
+                .matrix_to_visual_pos(row, col)
+                .and_then(|pos| layer.keys.iter().find(|k| k.position == pos))
+            else {
+                continue;
+            };
+
+            let x = (key_geom.visual_x - min_x).mul_add(KEY_UNIT, MARGIN) + KEY_GAP / 2.0;
+            let y =
+                (key_geom.visual_y - min_y).mul_add(KEY_UNIT, top + TITLE_HEIGHT) + KEY_GAP / 2.0;
+            let w = key_geom.width.mul_add(KEY_UNIT, -KEY_GAP);
+            let h = key_geom.height.mul_add(KEY_UNIT, -KEY_GAP);
+            write_key(&mut svg, layout, layer_idx, key, (x, y, w, h));
+        }
+
+        svg.push_str("  </g>\n");
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+/// Writes one key cap with its label.
+fn write_key(
+    svg: &mut String,
+    layout: &Layout,
+    layer_idx: usize,
+    key: &KeyDefinition,
+    (x, y, w, h): (f32, f32, f32, f32),
+) {
+    let (color, _) = layout.resolve_display_color(layer_idx, key);
+    let label = format_keycode(&key.keycode);
+
+    let _ = writeln!(
+        svg,
+        r#"    <rect x="{x:.1}" y="{y:.1}" width="{w:.1}" height="{h:.1}" rx="6" fill="{}"/>"#,
+        color.to_hex()
+    );
+    let _ = writeln!(
+        svg,
+        r#"    <text x="{:.1}" y="{:.1}" font-size="11" text-anchor="middle" dominant-baseline="middle" fill="{}">{}</text>"#,
+        x + w / 2.0,
+        y + h / 2.0,
+        text_color(color),
+        escape(&label)
+    );
+}
+
+/// Picks black or white text for legibility on `background`.
+fn text_color(background: RgbColor) -> &'static str {
+    let luma = 0.114f32.mul_add(
+        f32::from(background.b),
+        0.299f32.mul_add(f32::from(background.r), 0.587 * f32::from(background.g)),
+    );
+    if luma > 140.0 {
+        "#000000"
+    } else {
+        "#FFFFFF"
+    }
+}
+
+/// Escapes text for use in SVG content and attributes.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests;
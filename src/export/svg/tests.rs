@@ -0,0 +1,62 @@
+//! Tests for export::svg.
+
+use super::*;
+use crate::models::{KeyGeometry, Layer, Position};
+
+fn create_test_geometry() -> KeyboardGeometry {
+    let mut geom = KeyboardGeometry::new("test", "LAYOUT", 1, 3);
+    for col in 0u8..3 {
+        geom.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+    }
+    geom
+}
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test & Co").unwrap();
+    for (idx, name) in ["Base", "Nav <1>"].into_iter().enumerate() {
+        let mut layer = Layer::new(idx as u8, name, RgbColor::new(0, 0, 80)).unwrap();
+        for col in 0u8..3 {
+            layer.add_key(KeyDefinition::new(Position::new(0, col), "KC_A"));
+        }
+        layout.add_layer(layer).unwrap();
+    }
+    layout.layers[0].keys[1].keycode = "LT(1, KC_SPC)".to_string();
+    layout.layers[0].keys[2].color_override = Some(RgbColor::new(255, 255, 0));
+    layout
+}
+
+#[test]
+fn test_export_to_svg_renders_every_layer_and_key() {
+    let svg = export_to_svg(&create_test_layout(), &create_test_geometry()).unwrap();
+
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(svg.contains(r#"<g id="layer-0">"#));
+    assert!(svg.contains(r#"<g id="layer-1">"#));
+    assert_eq!(svg.matches("<rect x=").count(), 6);
+    assert!(svg.contains(">L1 / SPC</text>"));
+}
+
+#[test]
+fn test_export_to_svg_uses_resolved_colors() {
+    let svg = export_to_svg(&create_test_layout(), &create_test_geometry()).unwrap();
+
+    assert!(svg.contains(r##"fill="#FFFF00"/>"##));
+    // Dark key caps get white text, bright ones black
+    assert!(svg.contains(r##"fill="#FFFFFF">A</text>"##));
+    assert!(svg.contains(r##"fill="#000000">A</text>"##));
+}
+
+#[test]
+fn test_export_to_svg_escapes_text() {
+    let svg = export_to_svg(&create_test_layout(), &create_test_geometry()).unwrap();
+
+    assert!(svg.contains("<title>Test &amp; Co</title>"));
+    assert!(svg.contains("Layer 1: Nav &lt;1&gt;</text>"));
+}
+
+#[test]
+fn test_export_to_svg_rejects_empty_geometry() {
+    let geometry = KeyboardGeometry::new("test", "LAYOUT", 0, 0);
+    assert!(export_to_svg(&create_test_layout(), &geometry).is_err());
+}
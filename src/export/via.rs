@@ -0,0 +1,108 @@
+//! VIA keymap export.
+//!
+//! Produces a saved-layout `.json` file that VIA loads with "Load Saved
+//! Layout". VIA stores keymaps by matrix position, so every layer lists
+//! `matrix_rows * matrix_cols` keycodes, with `KC_NO` for matrix positions
+//! without a key. Layer references are resolved to layer numbers. Tap
+//! dances and macros only exist in firmware generated by `LazyQMK`; VIA
+//! can't assign them, so they are exported as `KC_NO`.
+
+use anyhow::{ensure, Context, Result};
+use serde::Serialize;
+
+use crate::keycode_db::KeycodeDb;
+use crate::models::{KeyboardGeometry, Layout, Position, VisualLayoutMapping};
+
+/// VIA saved-layout document.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ViaLayout {
+    name: String,
+    /// USB vendor ID in the high 16 bits, product ID in the low 16 bits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vendor_product_id: Option<u32>,
+    layers: Vec<Vec<String>>,
+}
+
+/// Exports a layout as a VIA saved layout.
+///
+/// `vendor_product_id` identifies the keyboard to VIA (see
+/// [`vendor_product_id`]); VIA refuses to load a file without it.
+///
+/// # Errors
+///
+/// Returns an error if the geometry has no keys or serialization fails.
+pub fn export_to_via(
+    layout: &Layout,
+    geometry: &KeyboardGeometry,
+    keycode_db: &KeycodeDb,
+    vendor_product_id: Option<u32>,
+) -> Result<String> {
+    ensure!(!geometry.keys.is_empty(), "Keyboard geometry has no keys");
+
+    let mapping = VisualLayoutMapping::build(geometry);
+    let positions: Vec<Option<Position>> = (0..geometry.matrix_rows)
+        .flat_map(|row| (0..geometry.matrix_cols).map(move |col| (row, col)))
+        .map(|(row, col)| mapping.matrix_to_visual_pos(row, col))
+        .collect();
+
+    let layers = layout
+        .layers
+        .iter()
+        .map(|layer| {
+            positions
+                .iter()
+                .map(|pos| {
+                    pos.and_then(|pos| layer.keys.iter().find(|k| k.position == pos))
+                        .map_or_else(
+                            || "KC_NO".to_string(),
+                            |key| via_keycode(layout, keycode_db, &key.keycode),
+                        )
+                })
+                .collect()
+        })
+        .collect();
+
+    let doc = ViaLayout {
+        name: layout.metadata.name.clone(),
+        vendor_product_id,
+        layers,
+    };
+    serde_json::to_string_pretty(&doc).context("Failed to serialize VIA layout")
+}
+
+/// Reads the USB vendor and product ID from a keyboard's resolved info.json
+/// (`usb.vid`, `usb.pid`) as VIA's `vendorProductId`.
+#[must_use]
+pub fn vendor_product_id(info: &serde_json::Value) -> Option<u32> {
+    let id = |field: &str| {
+        let hex = info.get("usb")?.get(field)?.as_str()?;
+        let hex = hex
+            .strip_prefix("0x")
+            .or_else(|| hex.strip_prefix("0X"))
+            .unwrap_or(hex);
+        u16::from_str_radix(hex, 16).ok()
+    };
+    Some((u32::from(id("vid")?) << 16) | u32::from(id("pid")?))
+}
+
+/// Converts a layout keycode to the form VIA accepts.
+fn via_keycode(layout: &Layout, keycode_db: &KeycodeDb, keycode: &str) -> String {
+    match keycode {
+        "" | "XXXXXXX" => return "KC_NO".to_string(),
+        "_______" => return "KC_TRNS".to_string(),
+        _ => {}
+    }
+    if keycode_db.parse_tap_dance_keycode(keycode).is_some() || keycode.starts_with("MACRO(") {
+        return "KC_NO".to_string();
+    }
+
+    let resolved = layout
+        .resolve_layer_keycode(keycode, keycode_db)
+        .unwrap_or_else(|| keycode.to_string());
+    // VIA doesn't parse "LT(1, KC_SPC)", only "LT(1,KC_SPC)"
+    resolved.split_whitespace().collect()
+}
+
+#[cfg(test)]
+mod tests;
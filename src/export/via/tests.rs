@@ -0,0 +1,74 @@
+//! Tests for export::via.
+
+use super::*;
+use crate::models::{KeyDefinition, KeyGeometry, Layer, RgbColor, TapDanceAction};
+
+/// 2x2 matrix with no key at (1, 1).
+fn create_test_geometry() -> KeyboardGeometry {
+    let mut geom = KeyboardGeometry::new("test", "LAYOUT", 2, 2);
+    geom.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+    geom.add_key(KeyGeometry::new((0, 1), 1, 1.0, 0.0));
+    geom.add_key(KeyGeometry::new((1, 0), 2, 0.0, 1.0));
+    geom
+}
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    for (idx, name) in ["Base", "Nav"].into_iter().enumerate() {
+        let mut layer = Layer::new(idx as u8, name, RgbColor::new(0, 0, 80)).unwrap();
+        for (row, col) in [(0, 0), (0, 1), (1, 0)] {
+            layer.add_key(KeyDefinition::new(Position::new(row, col), "KC_TRNS"));
+        }
+        layout.add_layer(layer).unwrap();
+    }
+    let nav_id = layout.layers[1].id.clone();
+    layout.layers[0].keys[0].keycode = "KC_A".to_string();
+    layout.layers[0].keys[1].keycode = format!("LT(@{nav_id}, KC_SPC)");
+    layout.layers[0].keys[2].keycode = "TD(esc_caps)".to_string();
+    layout
+        .tap_dances
+        .push(TapDanceAction::new("esc_caps", "KC_ESC").with_double_tap("KC_CAPS"));
+    layout
+}
+
+fn export(layout: &Layout, vendor_product_id: Option<u32>) -> serde_json::Value {
+    let db = KeycodeDb::load().unwrap();
+    let json = export_to_via(layout, &create_test_geometry(), &db, vendor_product_id).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn test_export_to_via_layers_in_matrix_order() {
+    let doc = export(&create_test_layout(), None);
+    let layers = doc["layers"].as_array().unwrap();
+
+    assert_eq!(layers.len(), 2);
+    assert_eq!(
+        layers[0],
+        serde_json::json!(["KC_A", "LT(1,KC_SPC)", "KC_NO", "KC_NO"])
+    );
+    assert_eq!(
+        layers[1],
+        serde_json::json!(["KC_TRNS", "KC_TRNS", "KC_TRNS", "KC_NO"])
+    );
+}
+
+#[test]
+fn test_export_to_via_vendor_product_id() {
+    let doc = export(&create_test_layout(), Some(0xFEED_0001));
+    assert_eq!(doc["name"], "Test");
+    assert_eq!(doc["vendorProductId"], 0xFEED_0001_u32);
+
+    let doc = export(&create_test_layout(), None);
+    assert!(doc.get("vendorProductId").is_none());
+}
+
+#[test]
+fn test_vendor_product_id_from_info_json() {
+    let info = serde_json::json!({"usb": {"vid": "0xFEED", "pid": "0x0001"}});
+    assert_eq!(vendor_product_id(&info), Some(0xFEED_0001));
+
+    let info = serde_json::json!({"usb": {"vid": "0xFEED"}});
+    assert_eq!(vendor_product_id(&info), None);
+    assert_eq!(vendor_product_id(&serde_json::json!({})), None);
+}
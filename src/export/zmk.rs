@@ -0,0 +1,372 @@
+//! ZMK keymap export.
+//!
+//! Produces a devicetree `.keymap` file for [ZMK](https://zmk.dev) firmware.
+//! Keys are listed per layer in the keyboard's QMK layout order, which ZMK
+//! shields for the same board usually share. QMK keycodes are translated to
+//! ZMK behaviors: basic keys and modifier combinations to `&kp`, layer keys
+//! to `&mo`, `&lt`, `&tog`, `&to` and `&sl`, mod-taps to `&mt` and one-shot
+//! mods to `&sk`. Keys without a ZMK equivalent (tap dances, macros, RGB and
+//! most other QMK features) become `&none` and are listed in a comment at
+//! the top of the file.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write as _;
+
+use anyhow::{ensure, Result};
+
+use crate::constants::APP_BINARY_NAME;
+use crate::keycode_db::KeycodeDb;
+use crate::models::{KeyboardGeometry, Layout, Position, VisualLayoutMapping};
+
+/// Exports a layout as a ZMK keymap.
+///
+/// # Errors
+///
+/// Returns an error if the geometry has no keys.
+pub fn export_to_zmk(
+    layout: &Layout,
+    geometry: &KeyboardGeometry,
+    keycode_db: &KeycodeDb,
+) -> Result<String> {
+    ensure!(!geometry.keys.is_empty(), "Keyboard geometry has no keys");
+
+    let mapping = VisualLayoutMapping::build(geometry);
+    let positions: Vec<Option<Position>> = geometry
+        .keys
+        .iter()
+        .map(|key| {
+            let (row, col) = key.matrix_position;
+            mapping.matrix_to_visual_pos(row, col)
+        })
+        .collect();
+
+    let mut translator = Translator {
+        layout,
+        keycode_db,
+        unsupported: BTreeSet::new(),
+    };
+    let mut layers = String::new();
+    for (layer, node) in layout.layers.iter().zip(layer_node_names(layout)) {
+        let _ = writeln!(layers, "        {node} {{");
+        let _ = writeln!(layers, "            display-name = \"{}\";", layer.name);
+        layers.push_str("            bindings = <\n");
+        let mut row = None;
+        for pos in &positions {
+            let key = pos.and_then(|pos| layer.keys.iter().find(|k| k.position == pos));
+            let binding = key.map_or_else(
+                || "&none".to_string(),
+                |key| translator.binding(&key.keycode),
+            );
+            // Start a new line for each visual row
+            let key_row = pos.map(|pos| pos.row);
+            if row.is_none() || key_row != row {
+                if row.is_some() {
+                    layers.push('\n');
+                }
+                layers.push_str("                ");
+                row = key_row;
+            } else {
+                layers.push_str("  ");
+            }
+            layers.push_str(&binding);
+        }
+        layers.push_str("\n            >;\n");
+        layers.push_str("        };\n\n");
+    }
+
+    let mut output = String::new();
+    let _ = writeln!(output, "/*");
+    let _ = writeln!(output, " * {}", layout.metadata.name);
+    let _ = writeln!(output, " * Generated by {APP_BINARY_NAME}");
+    if !translator.unsupported.is_empty() {
+        output.push_str(" *\n * No ZMK equivalent (exported as &none):\n");
+        for keycode in &translator.unsupported {
+            let _ = writeln!(output, " *   {keycode}");
+        }
+    }
+    output.push_str(" */\n\n");
+    output.push_str("#include <behaviors.dtsi>\n");
+    output.push_str("#include <dt-bindings/zmk/keys.h>\n\n");
+    output.push_str("/ {\n");
+    output.push_str("    keymap {\n");
+    output.push_str("        compatible = \"zmk,keymap\";\n\n");
+    output.push_str(layers.trim_end());
+    output.push('\n');
+    output.push_str("    };\n");
+    output.push_str("};\n");
+
+    Ok(output)
+}
+
+/// Names the layer nodes (e.g. `base_layer`), which must be unique
+/// devicetree identifiers.
+fn layer_node_names(layout: &Layout) -> Vec<String> {
+    let mut seen = HashSet::new();
+    layout
+        .layers
+        .iter()
+        .enumerate()
+        .map(|(idx, layer)| {
+            let name: String = layer
+                .name
+                .trim()
+                .to_lowercase()
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            let name = if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+                format!("layer{idx}_{name}")
+            } else {
+                name
+            };
+            let name = format!("{}_layer", name.trim_end_matches('_'));
+            if seen.insert(name.clone()) {
+                name
+            } else {
+                let name = format!("{name}_{idx}");
+                seen.insert(name.clone());
+                name
+            }
+        })
+        .collect()
+}
+
+/// Translates QMK keycodes to ZMK bindings, remembering the ones it can't.
+struct Translator<'a> {
+    layout: &'a Layout,
+    keycode_db: &'a KeycodeDb,
+    unsupported: BTreeSet<String>,
+}
+
+impl Translator<'_> {
+    /// Returns the ZMK binding for `keycode`, or `&none` if there is none.
+    fn binding(&mut self, keycode: &str) -> String {
+        if let Some(binding) = self.translate(keycode) {
+            binding
+        } else {
+            self.unsupported.insert(keycode.to_string());
+            "&none".to_string()
+        }
+    }
+
+    fn translate(&self, keycode: &str) -> Option<String> {
+        match keycode {
+            "" | "KC_NO" | "XXXXXXX" => return Some("&none".to_string()),
+            "KC_TRNS" | "KC_TRANSPARENT" | "_______" => return Some("&trans".to_string()),
+            "QK_BOOT" | "QK_BOOTLOADER" | "RESET" => return Some("&bootloader".to_string()),
+            "QK_RBT" | "QK_REBOOT" => return Some("&sys_reset".to_string()),
+            _ => {}
+        }
+
+        let resolved = self
+            .layout
+            .resolve_layer_keycode(keycode, self.keycode_db)
+            .unwrap_or_else(|| keycode.to_string());
+        let Some((function, args)) = split_call(&resolved) else {
+            return zmk_key(&resolved).map(|key| format!("&kp {key}"));
+        };
+
+        match (function, args.as_slice()) {
+            ("MO", [layer]) => Some(format!("&mo {layer}")),
+            ("TG", [layer]) => Some(format!("&tog {layer}")),
+            // ZMK has no default layer; switching to it is the closest match
+            ("TO" | "DF", [layer]) => Some(format!("&to {layer}")),
+            ("OSL", [layer]) => Some(format!("&sl {layer}")),
+            ("LT", [layer, key]) => Some(format!("&lt {layer} {}", zmk_key(key)?)),
+            ("MT", [mods, key]) => Some(format!("&mt {} {}", mod_mask(mods)?, zmk_key(key)?)),
+            ("OSM", [mods]) => Some(format!("&sk {}", mod_mask(mods)?)),
+            (function, [key]) if function.ends_with("_T") => {
+                let modifier = mod_tap(function)?;
+                Some(format!("&mt {modifier} {}", zmk_key(key)?))
+            }
+            _ => zmk_key(&resolved).map(|key| format!("&kp {key}")),
+        }
+    }
+}
+
+/// Splits `FN(a, b)` into `("FN", ["a", "b"])`, keeping nested calls intact.
+fn split_call(keycode: &str) -> Option<(&str, Vec<&str>)> {
+    let open = keycode.find('(')?;
+    let inner = keycode.get(open + 1..)?.strip_suffix(')')?;
+
+    let mut args = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (idx, c) in inner.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                args.push(inner[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    args.push(inner[start..].trim());
+
+    Some((keycode[..open].trim(), args))
+}
+
+/// Translates a key, optionally wrapped in modifiers (`LCTL(KC_C)`), to a
+/// ZMK keycode (`LC(C)`).
+fn zmk_key(keycode: &str) -> Option<String> {
+    if let Some((function, args)) = split_call(keycode) {
+        let [inner] = args.as_slice() else {
+            return None;
+        };
+        let wrapper = match function {
+            "LCTL" | "C" => "LC",
+            "LSFT" | "S" => "LS",
+            "LALT" | "A" | "LOPT" => "LA",
+            "LGUI" | "G" | "LCMD" | "LWIN" => "LG",
+            "RCTL" => "RC",
+            "RSFT" => "RS",
+            "RALT" | "ALGR" | "ROPT" => "RA",
+            "RGUI" | "RCMD" | "RWIN" => "RG",
+            _ => return None,
+        };
+        return Some(format!("{wrapper}({})", zmk_key(inner)?));
+    }
+
+    let name = keycode.strip_prefix("KC_")?;
+    if name.len() == 1 && name.chars().all(|c| c.is_ascii_uppercase()) {
+        return Some(name.to_string());
+    }
+    if name.len() == 1 && name.chars().all(|c| c.is_ascii_digit()) {
+        return Some(format!("N{name}"));
+    }
+    if let Some(number) = name.strip_prefix('F') {
+        if number.parse::<u8>().is_ok_and(|n| (1..=24).contains(&n)) {
+            return Some(name.to_string());
+        }
+    }
+
+    let key = match name {
+        "ENT" | "ENTER" => "RET",
+        "ESC" | "ESCAPE" => "ESC",
+        "BSPC" | "BACKSPACE" => "BSPC",
+        "TAB" => "TAB",
+        "SPC" | "SPACE" => "SPACE",
+        "MINS" | "MINUS" => "MINUS",
+        "EQL" | "EQUAL" => "EQUAL",
+        "LBRC" | "LEFT_BRACKET" => "LBKT",
+        "RBRC" | "RIGHT_BRACKET" => "RBKT",
+        "BSLS" | "BACKSLASH" => "BSLH",
+        "NUHS" => "NON_US_HASH",
+        "NUBS" => "NON_US_BSLH",
+        "SCLN" | "SEMICOLON" => "SEMI",
+        "QUOT" | "QUOTE" => "SQT",
+        "GRV" | "GRAVE" => "GRAVE",
+        "COMM" | "COMMA" => "COMMA",
+        "DOT" => "DOT",
+        "SLSH" | "SLASH" => "FSLH",
+        "CAPS" | "CAPS_LOCK" => "CAPS",
+        "PSCR" | "PRINT_SCREEN" => "PSCRN",
+        "SCRL" | "SCROLL_LOCK" => "SLCK",
+        "PAUS" | "PAUSE" => "PAUSE_BREAK",
+        "INS" | "INSERT" => "INS",
+        "HOME" => "HOME",
+        "PGUP" | "PAGE_UP" => "PG_UP",
+        "DEL" | "DELETE" => "DEL",
+        "END" => "END",
+        "PGDN" | "PAGE_DOWN" => "PG_DN",
+        "RGHT" | "RIGHT" => "RIGHT",
+        "LEFT" => "LEFT",
+        "DOWN" => "DOWN",
+        "UP" => "UP",
+        "APP" | "APPLICATION" => "K_APP",
+        "LCTL" | "LEFT_CTRL" => "LCTRL",
+        "LSFT" | "LEFT_SHIFT" => "LSHFT",
+        "LALT" | "LEFT_ALT" | "LOPT" => "LALT",
+        "LGUI" | "LEFT_GUI" | "LCMD" | "LWIN" => "LGUI",
+        "RCTL" | "RIGHT_CTRL" => "RCTRL",
+        "RSFT" | "RIGHT_SHIFT" => "RSHFT",
+        "RALT" | "RIGHT_ALT" | "ROPT" | "ALGR" => "RALT",
+        "RGUI" | "RIGHT_GUI" | "RCMD" | "RWIN" => "RGUI",
+        "EXLM" => "EXCL",
+        "AT" => "AT",
+        "HASH" => "HASH",
+        "DLR" => "DOLLAR",
+        "PERC" => "PERCENT",
+        "CIRC" => "CARET",
+        "AMPR" => "AMPS",
+        "ASTR" => "STAR",
+        "LPRN" => "LPAR",
+        "RPRN" => "RPAR",
+        "UNDS" => "UNDER",
+        "PLUS" => "PLUS",
+        "LCBR" => "LBRC",
+        "RCBR" => "RBRC",
+        "PIPE" => "PIPE",
+        "COLN" => "COLON",
+        "DQUO" | "DQT" => "DQT",
+        "TILD" => "TILDE",
+        "LT" => "LT",
+        "GT" => "GT",
+        "QUES" => "QMARK",
+        "NUM" | "NUM_LOCK" => "KP_NUM",
+        "PSLS" => "KP_SLASH",
+        "PAST" => "KP_MULTIPLY",
+        "PMNS" => "KP_MINUS",
+        "PPLS" => "KP_PLUS",
+        "PENT" => "KP_ENTER",
+        "PDOT" => "KP_DOT",
+        "P0" => "KP_N0",
+        "P1" => "KP_N1",
+        "P2" => "KP_N2",
+        "P3" => "KP_N3",
+        "P4" => "KP_N4",
+        "P5" => "KP_N5",
+        "P6" => "KP_N6",
+        "P7" => "KP_N7",
+        "P8" => "KP_N8",
+        "P9" => "KP_N9",
+        "MUTE" | "AUDIO_MUTE" => "C_MUTE",
+        "VOLU" | "AUDIO_VOL_UP" => "C_VOL_UP",
+        "VOLD" | "AUDIO_VOL_DOWN" => "C_VOL_DN",
+        "MPLY" | "MEDIA_PLAY_PAUSE" => "C_PP",
+        "MSTP" | "MEDIA_STOP" => "C_STOP",
+        "MNXT" | "MEDIA_NEXT_TRACK" => "C_NEXT",
+        "MPRV" | "MEDIA_PREV_TRACK" => "C_PREV",
+        "BRIU" | "BRIGHTNESS_UP" => "C_BRI_UP",
+        "BRID" | "BRIGHTNESS_DOWN" => "C_BRI_DN",
+        _ => return None,
+    };
+    Some(key.to_string())
+}
+
+/// Translates a single-modifier `MOD_*` mask to the ZMK modifier key.
+fn mod_mask(mask: &str) -> Option<&'static str> {
+    let modifier = match mask {
+        "MOD_LCTL" => "LCTRL",
+        "MOD_LSFT" => "LSHFT",
+        "MOD_LALT" => "LALT",
+        "MOD_LGUI" => "LGUI",
+        "MOD_RCTL" => "RCTRL",
+        "MOD_RSFT" => "RSHFT",
+        "MOD_RALT" => "RALT",
+        "MOD_RGUI" => "RGUI",
+        _ => return None,
+    };
+    Some(modifier)
+}
+
+/// Translates a QMK mod-tap function (`LCTL_T`) to the ZMK hold modifier.
+fn mod_tap(function: &str) -> Option<&'static str> {
+    let modifier = match function {
+        "LCTL_T" | "CTL_T" => "LCTRL",
+        "LSFT_T" | "SFT_T" => "LSHFT",
+        "LALT_T" | "ALT_T" | "LOPT_T" | "OPT_T" => "LALT",
+        "LGUI_T" | "GUI_T" | "LCMD_T" | "CMD_T" | "LWIN_T" | "WIN_T" => "LGUI",
+        "RCTL_T" => "RCTRL",
+        "RSFT_T" => "RSHFT",
+        "RALT_T" | "ROPT_T" | "ALGR_T" => "RALT",
+        "RGUI_T" | "RCMD_T" | "RWIN_T" => "RGUI",
+        _ => return None,
+    };
+    Some(modifier)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,109 @@
+//! Tests for export::zmk.
+
+use super::*;
+use crate::models::{KeyDefinition, KeyGeometry, Layer, RgbColor, TapDanceAction};
+
+/// Two rows of two keys.
+fn create_test_geometry() -> KeyboardGeometry {
+    let mut geom = KeyboardGeometry::new("test", "LAYOUT", 2, 2);
+    for (idx, (row, col)) in [(0u8, 0u8), (0, 1), (1, 0), (1, 1)].into_iter().enumerate() {
+        geom.add_key(KeyGeometry::new(
+            (row, col),
+            idx as u8,
+            f32::from(col),
+            f32::from(row),
+        ));
+    }
+    geom
+}
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    for (idx, name) in ["Base", "Nav Keys"].into_iter().enumerate() {
+        let mut layer = Layer::new(idx as u8, name, RgbColor::new(0, 0, 80)).unwrap();
+        for (row, col) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            layer.add_key(KeyDefinition::new(Position::new(row, col), "KC_TRNS"));
+        }
+        layout.add_layer(layer).unwrap();
+    }
+    let nav_id = layout.layers[1].id.clone();
+    layout.layers[0].keys[0].keycode = "KC_Q".to_string();
+    layout.layers[0].keys[1].keycode = "LGUI_T(KC_A)".to_string();
+    layout.layers[0].keys[2].keycode = format!("LT(@{nav_id}, KC_SPC)");
+    layout.layers[0].keys[3].keycode = "TD(esc_caps)".to_string();
+    layout
+        .tap_dances
+        .push(TapDanceAction::new("esc_caps", "KC_ESC").with_double_tap("KC_CAPS"));
+    layout
+}
+
+fn export(layout: &Layout) -> String {
+    let db = KeycodeDb::load().unwrap();
+    export_to_zmk(layout, &create_test_geometry(), &db).unwrap()
+}
+
+#[test]
+fn test_export_to_zmk_bindings_per_row() {
+    let keymap = export(&create_test_layout());
+
+    assert!(keymap.contains("compatible = \"zmk,keymap\";"));
+    assert!(keymap.contains("                &kp Q  &mt LGUI A\n"));
+    assert!(keymap.contains("                &lt 1 SPACE  &none\n"));
+    assert!(keymap.contains("                &trans  &trans\n"));
+}
+
+#[test]
+fn test_export_to_zmk_lists_unsupported_keycodes() {
+    let keymap = export(&create_test_layout());
+
+    assert!(keymap.contains("No ZMK equivalent (exported as &none):\n *   TD(esc_caps)\n"));
+}
+
+#[test]
+fn test_export_to_zmk_layer_node_names() {
+    let mut layout = create_test_layout();
+    let keymap = export(&layout);
+    assert!(keymap.contains("        base_layer {\n"));
+    assert!(keymap.contains("        nav_keys_layer {\n"));
+    assert!(keymap.contains("display-name = \"Nav Keys\";"));
+
+    layout.layers[1].name = "Base".to_string();
+    let keymap = export(&layout);
+    assert!(keymap.contains("        base_layer_1 {\n"));
+}
+
+#[test]
+fn test_zmk_key_translation() {
+    assert_eq!(zmk_key("KC_1").as_deref(), Some("N1"));
+    assert_eq!(zmk_key("KC_F12").as_deref(), Some("F12"));
+    assert_eq!(zmk_key("KC_SCLN").as_deref(), Some("SEMI"));
+    assert_eq!(zmk_key("LCTL(LSFT(KC_T))").as_deref(), Some("LC(LS(T))"));
+    assert_eq!(zmk_key("RGB_TOG"), None);
+}
+
+#[test]
+fn test_translate_layer_and_modifier_keys() {
+    let layout = create_test_layout();
+    let db = KeycodeDb::load().unwrap();
+    let translator = Translator {
+        layout: &layout,
+        keycode_db: &db,
+        unsupported: BTreeSet::new(),
+    };
+
+    assert_eq!(translator.translate("MO(2)").as_deref(), Some("&mo 2"));
+    assert_eq!(translator.translate("TG(1)").as_deref(), Some("&tog 1"));
+    assert_eq!(
+        translator.translate("MT(MOD_LCTL, KC_ESC)").as_deref(),
+        Some("&mt LCTRL ESC")
+    );
+    assert_eq!(
+        translator.translate("OSM(MOD_LSFT)").as_deref(),
+        Some("&sk LSHFT")
+    );
+    assert_eq!(
+        translator.translate("QK_BOOT").as_deref(),
+        Some("&bootloader")
+    );
+    assert_eq!(translator.translate("MACRO(hello)"), None);
+}
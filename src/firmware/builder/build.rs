@@ -2,13 +2,16 @@
 //! `enhance_qmk_error`.
 
 use anyhow::{Context, Result};
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::mpsc::Sender;
 
 use super::state::{BuildMessage, BuildStatus, LogLevel};
+use crate::services::process_tree::{ChildOutcome, OutputLine, TrackedChild};
 
-pub(super) fn enhance_qmk_error(error_str: &str) -> String {
+/// Adds a `lazyqmk doctor` hint to errors caused by a missing `qmk` CLI.
+#[must_use]
+pub fn enhance_qmk_error(error_str: &str) -> String {
     let error_lower = error_str.to_lowercase();
 
     // Check for command not found patterns across platforms
@@ -35,11 +38,13 @@ pub(super) fn enhance_qmk_error(error_str: &str) -> String {
 /// QMK's build system will use the variant-specific keyboard.json for configuration.
 ///
 /// Uses `qmk compile` CLI command which is the standard way to build QMK firmware.
+/// A `converter` is passed as `-e CONVERT_TO=<converter>`.
 pub(super) fn run_build(
     sender: Sender<BuildMessage>,
     qmk_path: PathBuf,
     keyboard: String,
     keymap: String,
+    converter: Option<String>,
 ) -> Result<()> {
     // Send progress: Compiling
     sender
@@ -49,29 +54,58 @@ pub(super) fn run_build(
         })
         .context("Failed to send progress message")?;
 
+    let args = compile_args(&keyboard, &keymap, converter.as_deref());
     sender
         .send(BuildMessage::Log {
             level: LogLevel::Info,
-            message: format!("Running: qmk compile -kb {keyboard} -km {keymap}"),
+            message: format!("Running: qmk {}", args.join(" ")),
         })
         .ok();
 
-    // Build using qmk compile command (standard QMK CLI)
+    // Build using qmk compile command (standard QMK CLI). The tracked child
+    // runs in its own process group, so quitting also stops make and the
+    // compilers.
     let mut cmd = Command::new("qmk");
-    cmd.arg("compile")
-        .arg("-kb")
-        .arg(&keyboard)
-        .arg("-km")
-        .arg(&keymap)
-        .current_dir(&qmk_path)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    // Execute command
-    let output = match cmd.output() {
-        Ok(o) => o,
-        Err(e) => {
-            let error_msg = format!("Failed to execute qmk compile command: {e}");
+    cmd.args(&args).current_dir(&qmk_path);
+
+    let mut stderr = String::new();
+    let outcome = TrackedChild::spawn(&mut cmd).and_then(|mut child| {
+        child.wait_with_output(&|| false, &mut |line| match line {
+            OutputLine::Stdout(line) => {
+                let level = if line.contains("error") || line.contains("Error") {
+                    LogLevel::Error
+                } else {
+                    LogLevel::Info
+                };
+                sender
+                    .send(BuildMessage::Log {
+                        level,
+                        message: line,
+                    })
+                    .ok();
+            }
+            // Stderr is usually errors
+            OutputLine::Stderr(line) => {
+                if !line.trim().is_empty() {
+                    stderr.push_str(&line);
+                    stderr.push('\n');
+                    sender
+                        .send(BuildMessage::Log {
+                            level: LogLevel::Error,
+                            message: line,
+                        })
+                        .ok();
+                }
+            }
+        })
+    });
+    let status = match outcome {
+        Ok(ChildOutcome::Exited(status)) => status,
+        failed => {
+            let error_msg = match failed {
+                Err(e) => format!("Failed to execute qmk compile command: {e}"),
+                _ => "qmk compile was stopped before it finished".to_string(),
+            };
             let enhanced_msg = enhance_qmk_error(&error_msg);
             sender
                 .send(BuildMessage::Complete {
@@ -84,44 +118,11 @@ pub(super) fn run_build(
         }
     };
 
-    // Parse output
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
-
-    // Send stdout logs
-    for line in stdout.lines() {
-        let level = if line.contains("error") || line.contains("Error") {
-            LogLevel::Error
-        } else if line.contains("warning") || line.contains("Warning") {
-            LogLevel::Info
-        } else {
-            LogLevel::Info
-        };
-
-        sender
-            .send(BuildMessage::Log {
-                level,
-                message: line.to_string(),
-            })
-            .ok();
-    }
-
-    // Send stderr logs (usually errors)
-    for line in stderr.lines() {
-        if !line.trim().is_empty() {
-            sender
-                .send(BuildMessage::Log {
-                    level: LogLevel::Error,
-                    message: line.to_string(),
-                })
-                .ok();
-        }
-    }
-
     // Check success
-    if output.status.success() {
+    if status.success() {
         // Find firmware file
-        let firmware_path = find_firmware_file(&qmk_path, &keyboard, &keymap)?;
+        let firmware_path =
+            find_firmware_file(&qmk_path, &keyboard, &keymap, converter.as_deref())?;
 
         sender
             .send(BuildMessage::Complete {
@@ -146,22 +147,45 @@ pub(super) fn run_build(
     Ok(())
 }
 
+/// Builds the `qmk` arguments for compiling `keyboard`/`keymap`.
+pub fn compile_args(keyboard: &str, keymap: &str, converter: Option<&str>) -> Vec<String> {
+    let mut args = vec![
+        "compile".to_string(),
+        "-kb".to_string(),
+        keyboard.to_string(),
+        "-km".to_string(),
+        keymap.to_string(),
+    ];
+    if let Some(converter) = converter {
+        args.push("-e".to_string());
+        args.push(format!("CONVERT_TO={converter}"));
+    }
+    args
+}
+
 /// Finds the compiled firmware file.
 ///
-/// QMK typically outputs to .build/{keyboard}_{keymap}.{ext}
-pub(super) fn find_firmware_file(
-    qmk_path: &PathBuf,
+/// QMK typically outputs to .build/{keyboard}_{keymap}.{ext}, with a
+/// `_{converter}` suffix when building with `CONVERT_TO`.
+///
+/// # Errors
+///
+/// Returns an error if no `.uf2`, `.hex` or `.bin` file exists.
+pub fn find_firmware_file(
+    qmk_path: &Path,
     keyboard: &str,
     keymap: &str,
+    converter: Option<&str>,
 ) -> Result<PathBuf> {
     // Clean keyboard path (replace / with _)
     let keyboard_clean = keyboard.replace('/', "_");
+    let suffix = converter.map(|c| format!("_{c}")).unwrap_or_default();
 
     // Try common firmware extensions in order
     let extensions = ["uf2", "hex", "bin"];
 
     for ext in &extensions {
-        let firmware_name = format!("{keyboard_clean}_{keymap}.{ext}");
+        let firmware_name = format!("{keyboard_clean}_{keymap}{suffix}.{ext}");
         let firmware_path = qmk_path.join(".build").join(&firmware_name);
 
         if firmware_path.exists() {
@@ -2,7 +2,7 @@
 //!
 //! Auto-extracted from src/firmware/builder/build.rs.
 
-use super::super::state::{BuildMessage, BuildState, BuildStatus, LogLevel};
+use super::super::state::{BuildMessage, BuildState, BuildStatus};
 use super::*;
 
 #[test]
@@ -30,6 +30,9 @@ fn test_build_state_is_building() {
     state.status = BuildStatus::Compiling;
     assert!(state.is_building());
 
+    state.status = BuildStatus::Checking;
+    assert!(state.is_building());
+
     state.status = BuildStatus::Success;
     assert!(!state.is_building());
 }
@@ -60,6 +63,25 @@ fn test_build_message_complete_success() {
     state.handle_message(message);
     assert_eq!(state.status, BuildStatus::Success);
     assert!(state.last_message.contains("firmware.uf2"));
+    assert_eq!(
+        state.firmware_path,
+        Some(PathBuf::from("/test/firmware.uf2"))
+    );
+}
+
+#[cfg(feature = "usb")]
+#[test]
+fn test_start_flash_requires_built_firmware() {
+    let mut state = BuildState::new();
+    let err = state.start_flash(std::time::Duration::ZERO).unwrap_err();
+    assert!(err.to_string().contains("No firmware built yet"));
+
+    state.firmware_path = Some(PathBuf::from("/test/firmware.uf2"));
+    state.status = BuildStatus::Compiling;
+    assert!(state.start_flash(std::time::Duration::ZERO).is_err());
+
+    state.status = BuildStatus::Flashing;
+    assert!(state.is_building());
 }
 
 #[test]
@@ -76,8 +98,11 @@ fn test_build_message_complete_failure() {
     assert_eq!(state.last_message, "Build failed");
 }
 
+#[cfg(feature = "tui")]
 #[test]
 fn test_log_level_color() {
+    use super::super::state::LogLevel;
+
     assert_eq!(LogLevel::Info.color(), ratatui::style::Color::Gray);
     assert_eq!(LogLevel::Ok.color(), ratatui::style::Color::Green);
     assert_eq!(LogLevel::Error.color(), ratatui::style::Color::Red);
@@ -126,3 +151,30 @@ fn test_enhance_qmk_error_preserves_original_message() {
     let enhanced = enhance_qmk_error(error);
     assert!(enhanced.starts_with(error));
 }
+
+#[test]
+fn test_compile_args_without_converter() {
+    assert_eq!(
+        compile_args("crkbd/rev1", "default", None),
+        vec!["compile", "-kb", "crkbd/rev1", "-km", "default"]
+    );
+}
+
+#[test]
+fn test_compile_args_with_converter() {
+    let args = compile_args("crkbd/rev1", "default", Some("kb2040"));
+    assert_eq!(&args[5..], ["-e", "CONVERT_TO=kb2040"]);
+}
+
+#[test]
+fn test_find_firmware_file_with_converter_suffix() {
+    let dir = tempfile::tempdir().unwrap();
+    let build_dir = dir.path().join(".build");
+    std::fs::create_dir_all(&build_dir).unwrap();
+    std::fs::write(build_dir.join("crkbd_rev1_default_kb2040.uf2"), b"").unwrap();
+
+    let qmk_path = dir.path().to_path_buf();
+    let found = find_firmware_file(&qmk_path, "crkbd/rev1", "default", Some("kb2040")).unwrap();
+    assert!(found.ends_with("crkbd_rev1_default_kb2040.uf2"));
+    assert!(find_firmware_file(&qmk_path, "crkbd/rev1", "default", None).is_err());
+}
@@ -0,0 +1,229 @@
+//! Syntax-only preflight of the generated keymap: `check_keymap`.
+//!
+//! A full `qmk compile` takes minutes. The check asks QMK for its build
+//! commands with a dry run (`qmk compile -n`), runs the header generators
+//! it lists, then runs the `keymap.c` compile command with `-fsyntax-only`,
+//! so C errors in the keymap show up in seconds.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use super::build::compile_args;
+
+/// Flags dropped from the `keymap.c` compile command, with whether they
+/// take a value. They write object and dependency files.
+const DROPPED_FLAGS: [(&str, bool); 6] = [
+    ("-c", false),
+    ("-o", true),
+    ("-MMD", false),
+    ("-MP", false),
+    ("-MF", true),
+    ("-MT", true),
+];
+
+/// A compiler error or warning.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompilerDiagnostic {
+    /// Source file, as printed by the compiler
+    pub file: String,
+    /// 1-based line
+    pub line: u32,
+    /// 1-based column, if the compiler printed one
+    pub column: Option<u32>,
+    /// `error` or `warning` (`fatal error` counts as `error`)
+    pub severity: String,
+    /// Compiler message
+    pub message: String,
+}
+
+/// Result of [`check_keymap`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct KeymapCheck {
+    /// True if QMK and the compiler found no errors
+    pub passed: bool,
+    /// True if the compiler ran; false if only the QMK dry run did
+    pub syntax_checked: bool,
+    /// Errors and warnings from the compiler
+    pub diagnostics: Vec<CompilerDiagnostic>,
+    /// Commands run and their output
+    pub log: Vec<String>,
+}
+
+impl KeymapCheck {
+    /// Number of errors in [`Self::diagnostics`].
+    #[must_use]
+    pub fn error_count(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == "error")
+            .count()
+    }
+
+    /// Adds a command's output to the log and its diagnostics to the list.
+    fn record_output(&mut self, output: &Output) {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        for line in stdout.lines().chain(stderr.lines()) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(diagnostic) = parse_diagnostic(line) {
+                self.diagnostics.push(diagnostic);
+            }
+            self.log.push(line.to_string());
+        }
+    }
+}
+
+/// Checks the `keymap.c` already written to the QMK tree for C errors
+/// without building the firmware.
+///
+/// # Errors
+///
+/// Returns an error if `qmk` or the shell can't be started. Failing QMK or
+/// compiler runs are reported in the [`KeymapCheck`].
+pub fn check_keymap(
+    qmk_path: &Path,
+    keyboard: &str,
+    keymap: &str,
+    converter: Option<&str>,
+) -> Result<KeymapCheck> {
+    let mut check = KeymapCheck::default();
+
+    let mut args = compile_args(keyboard, keymap, converter);
+    args.push("-n".to_string());
+    check.log.push(format!("Running: qmk {}", args.join(" ")));
+    let dry_run = Command::new("qmk")
+        .args(&args)
+        .current_dir(qmk_path)
+        .output()
+        .context("Failed to execute qmk compile -n")?;
+    let dry_run_stdout = String::from_utf8_lossy(&dry_run.stdout).into_owned();
+    if !dry_run.status.success() {
+        check.record_output(&dry_run);
+        check
+            .log
+            .push("qmk compile -n failed; the keymap wasn't compiled".to_string());
+        return Ok(check);
+    }
+
+    let Some(compile) = keymap_compile_command(&dry_run_stdout, keymap) else {
+        check.passed = true;
+        check.log.push(
+            "QMK dry run passed, but it listed no keymap.c compile command to check".to_string(),
+        );
+        return Ok(check);
+    };
+
+    // Headers QMK generates from info.json are only written by a real build
+    for generate in generator_commands(&dry_run_stdout) {
+        check.log.push(format!("Running: {generate}"));
+        let output = run_shell(qmk_path, generate)?;
+        if !output.status.success() {
+            check.record_output(&output);
+        }
+    }
+
+    let syntax_only = syntax_only_command(compile);
+    check.log.push(format!("Running: {syntax_only}"));
+    let output = run_shell(qmk_path, &syntax_only)?;
+    check.record_output(&output);
+    check.syntax_checked = true;
+    check.passed = output.status.success();
+    Ok(check)
+}
+
+/// Runs `command` with `sh -c` in `dir`. Dry-run commands are printed for a
+/// shell, with quoted defines like `-DQMK_KEYMAP=\"default\"`.
+fn run_shell(dir: &Path, command: &str) -> Result<Output> {
+    Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(dir)
+        .output()
+        .with_context(|| format!("Failed to run {command}"))
+}
+
+/// Finds the command compiling `keymaps/<keymap>/keymap.c` in the output of
+/// `qmk compile -n`.
+fn keymap_compile_command<'a>(dry_run: &'a str, keymap: &str) -> Option<&'a str> {
+    let source = format!("keymaps/{keymap}/keymap.c");
+    dry_run.lines().map(str::trim).find(|line| {
+        let mut words = line.split_whitespace();
+        words.next().is_some_and(|cc| cc.ends_with("gcc"))
+            && line.split_whitespace().any(|word| word == "-c")
+            && words.any(|word| word.ends_with(&source))
+    })
+}
+
+/// Returns the `qmk generate-*` commands in the output of `qmk compile -n`.
+fn generator_commands(dry_run: &str) -> impl Iterator<Item = &str> {
+    dry_run.lines().map(str::trim).filter(|line| {
+        let mut words = line.split_whitespace();
+        words.next().is_some_and(|bin| bin.ends_with("qmk"))
+            && words.next().is_some_and(|cmd| cmd.starts_with("generate-"))
+    })
+}
+
+/// Turns a compile command into one that only checks syntax.
+fn syntax_only_command(compile: &str) -> String {
+    let mut words = compile.split_whitespace();
+    let mut kept = Vec::new();
+    while let Some(word) = words.next() {
+        match DROPPED_FLAGS.iter().find(|(flag, _)| *flag == word) {
+            Some((_, true)) => {
+                words.next();
+            }
+            Some((_, false)) => {}
+            None => kept.push(word),
+        }
+    }
+    // The compiler comes first, the flag right after it
+    kept.insert(1.min(kept.len()), "-fsyntax-only");
+    kept.join(" ")
+}
+
+/// Parses a `file:line:col: severity: message` compiler line.
+fn parse_diagnostic(line: &str) -> Option<CompilerDiagnostic> {
+    let (location, severity, message) = [
+        ("fatal error", "error"),
+        ("error", "error"),
+        ("warning", "warning"),
+    ]
+    .into_iter()
+    .find_map(|(marker, severity)| {
+        line.split_once(&format!(": {marker}: "))
+            .map(|(location, message)| (location, severity, message))
+    })?;
+
+    // Line and column are the numbers at the end of the location
+    let mut file = location;
+    let mut numbers = Vec::new();
+    while numbers.len() < 2 {
+        match file.rsplit_once(':') {
+            Some((rest, number)) if number.parse::<u32>().is_ok() => {
+                numbers.push(number.parse().ok()?);
+                file = rest;
+            }
+            _ => break,
+        }
+    }
+    let (line, column) = match numbers[..] {
+        [column, line] => (line, Some(column)),
+        [line] => (line, None),
+        _ => return None,
+    };
+
+    Some(CompilerDiagnostic {
+        file: file.to_string(),
+        line,
+        column,
+        severity: severity.to_string(),
+        message: message.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,63 @@
+//! Tests for firmware::builder::check.
+
+use super::*;
+
+const DRY_RUN: &str = "\
+printf \"Compiling: %s\" keyboards/crkbd/keymaps/mine/keymap.c
+qmk generate-config-h --quiet --keyboard crkbd/rev1 --output .build/obj_crkbd_rev1_mine/src/info_config.h
+arm-none-eabi-gcc -c -mcpu=cortex-m0plus -DQMK_KEYMAP=\\\"mine\\\" -MMD -MP -MF .build/obj_crkbd_rev1_mine/keyboards/crkbd/keymaps/mine/keymap.d keyboards/crkbd/keymaps/mine/keymap.c -o .build/obj_crkbd_rev1_mine/keyboards/crkbd/keymaps/mine/keymap.o
+arm-none-eabi-gcc -c -mcpu=cortex-m0plus quantum/quantum.c -o .build/obj_crkbd_rev1_mine/quantum/quantum.o
+";
+
+#[test]
+fn test_finds_keymap_compile_command() {
+    let compile = keymap_compile_command(DRY_RUN, "mine").unwrap();
+    assert!(compile.starts_with("arm-none-eabi-gcc -c"));
+    assert!(compile.contains("keymaps/mine/keymap.c -o"));
+
+    assert_eq!(keymap_compile_command(DRY_RUN, "other"), None);
+}
+
+#[test]
+fn test_finds_generator_commands() {
+    let commands: Vec<&str> = generator_commands(DRY_RUN).collect();
+    assert_eq!(commands.len(), 1);
+    assert!(commands[0].starts_with("qmk generate-config-h"));
+}
+
+#[test]
+fn test_syntax_only_command_drops_outputs() {
+    let compile = keymap_compile_command(DRY_RUN, "mine").unwrap();
+
+    assert_eq!(
+        syntax_only_command(compile),
+        "arm-none-eabi-gcc -fsyntax-only -mcpu=cortex-m0plus -DQMK_KEYMAP=\\\"mine\\\" keyboards/crkbd/keymaps/mine/keymap.c"
+    );
+}
+
+#[test]
+fn test_parse_diagnostic() {
+    let error = parse_diagnostic(
+        "keyboards/crkbd/keymaps/mine/keymap.c:42:17: error: 'KC_NOPE' undeclared here (not in a function)",
+    )
+    .unwrap();
+    assert_eq!(error.file, "keyboards/crkbd/keymaps/mine/keymap.c");
+    assert_eq!((error.line, error.column), (42, Some(17)));
+    assert_eq!(error.severity, "error");
+    assert_eq!(
+        error.message,
+        "'KC_NOPE' undeclared here (not in a function)"
+    );
+
+    let fatal =
+        parse_diagnostic("keymap.c:3:10: fatal error: missing.h: No such file or directory")
+            .unwrap();
+    assert_eq!(fatal.severity, "error");
+    assert_eq!(fatal.message, "missing.h: No such file or directory");
+
+    let warning = parse_diagnostic("config.h:7: warning: \"TAPPING_TERM\" redefined").unwrap();
+    assert_eq!((warning.line, warning.column), (7, None));
+    assert_eq!(warning.severity, "warning");
+
+    assert_eq!(parse_diagnostic("In file included from keymap.c:1:"), None);
+}
@@ -7,9 +7,15 @@
 //! - [`state`] — `BuildStatus`, `LogLevel`, `BuildState` types and the
 //!   `BuildState` impl that drives the build lifecycle.
 //! - [`build`] — low-level helpers (`run_build`, `find_firmware_file`,
-//!   `enhance_qmk_error`) used by `BuildState`.
+//!   `enhance_qmk_error`) used by `BuildState` and `lazyqmk build`.
+//! - [`check`] — `check_keymap`, the syntax-only preflight of the generated
+//!   keymap used by `BuildState` and the web check endpoint.
 
 mod build;
+mod check;
 mod state;
 
+pub use build::{compile_args, enhance_qmk_error, find_firmware_file};
+#[allow(unused_imports)] // bin/lib split: only the web check endpoint uses these
+pub use check::{check_keymap, CompilerDiagnostic, KeymapCheck};
 pub use state::{BuildState, BuildStatus, LogLevel};
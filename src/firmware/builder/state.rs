@@ -6,7 +6,8 @@ use std::path::PathBuf;
 use std::sync::mpsc::{channel, Receiver};
 use std::thread;
 
-use super::build::run_build;
+use super::build::{enhance_qmk_error, run_build};
+use super::check::check_keymap;
 
 /// Build status tracking.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -20,6 +21,10 @@ pub enum BuildStatus {
     Generating,
     /// Compiling QMK firmware
     Compiling,
+    /// Checking the generated keymap for C errors
+    Checking,
+    /// Flashing built firmware onto a keyboard
+    Flashing,
     /// Build completed successfully
     Success,
     /// Build failed with error
@@ -33,6 +38,8 @@ impl std::fmt::Display for BuildStatus {
             Self::Validating => write!(f, "Validating..."),
             Self::Generating => write!(f, "Generating..."),
             Self::Compiling => write!(f, "Compiling..."),
+            Self::Checking => write!(f, "Checking..."),
+            Self::Flashing => write!(f, "Flashing..."),
             Self::Success => write!(f, "✓ Success"),
             Self::Failed => write!(f, "✗ Failed"),
         }
@@ -80,6 +87,7 @@ pub enum LogLevel {
 
 impl LogLevel {
     /// Returns the terminal color for this log level.
+    #[cfg(feature = "tui")]
     #[must_use]
     #[allow(dead_code)] // Display helper for tests; bin target doesn't link
     pub const fn color(self) -> ratatui::style::Color {
@@ -101,6 +109,8 @@ pub struct BuildState {
     pub log_lines: Vec<(LogLevel, String)>,
     /// Last status message
     pub last_message: String,
+    /// Firmware produced by the last successful build
+    pub firmware_path: Option<PathBuf>,
 }
 
 impl BuildState {
@@ -112,6 +122,7 @@ impl BuildState {
             receiver: None,
             log_lines: Vec::new(),
             last_message: String::new(),
+            firmware_path: None,
         }
     }
 
@@ -120,7 +131,11 @@ impl BuildState {
     pub const fn is_building(&self) -> bool {
         matches!(
             self.status,
-            BuildStatus::Validating | BuildStatus::Generating | BuildStatus::Compiling
+            BuildStatus::Validating
+                | BuildStatus::Generating
+                | BuildStatus::Compiling
+                | BuildStatus::Checking
+                | BuildStatus::Flashing
         )
     }
 
@@ -173,6 +188,7 @@ impl BuildState {
                     self.last_message = format!("Firmware written to {}", path.display());
                     self.log_lines
                         .push((LogLevel::Ok, self.last_message.clone()));
+                    self.firmware_path = Some(path);
                 }
 
                 if let Some(err) = error {
@@ -187,12 +203,16 @@ impl BuildState {
 
     /// Starts a build in the background.
     ///
+    /// `converter` is passed to QMK as `CONVERT_TO` for boards whose
+    /// controller has been swapped (e.g., "kb2040").
+    ///
     /// Returns a receiver for build messages.
     pub fn start_build(
         &mut self,
         qmk_path: PathBuf,
         keyboard: String,
         keymap: String,
+        converter: Option<String>,
     ) -> Result<()> {
         if self.is_building() {
             anyhow::bail!("Build already in progress");
@@ -203,10 +223,11 @@ impl BuildState {
         self.status = BuildStatus::Compiling;
         self.log_lines.clear();
         self.last_message = "Starting build...".to_string();
+        self.firmware_path = None;
 
         // Spawn background thread
         thread::spawn(move || {
-            if let Err(e) = run_build(sender.clone(), qmk_path, keyboard, keymap) {
+            if let Err(e) = run_build(sender.clone(), qmk_path, keyboard, keymap, converter) {
                 let _ = sender.send(BuildMessage::Complete {
                     success: false,
                     firmware_path: None,
@@ -217,6 +238,151 @@ impl BuildState {
 
         Ok(())
     }
+
+    /// Checks the generated keymap for C errors in the background, without
+    /// building the firmware.
+    ///
+    /// Compiler output goes to the build log; the last firmware built is
+    /// kept for flashing.
+    pub fn start_check(
+        &mut self,
+        qmk_path: PathBuf,
+        keyboard: String,
+        keymap: String,
+        converter: Option<String>,
+    ) -> Result<()> {
+        if self.is_building() {
+            anyhow::bail!("Build already in progress");
+        }
+
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+        self.status = BuildStatus::Checking;
+        self.log_lines.clear();
+        self.last_message = "Checking keymap.c...".to_string();
+
+        thread::spawn(move || {
+            let error = match check_keymap(&qmk_path, &keyboard, &keymap, converter.as_deref()) {
+                Ok(check) => {
+                    for line in &check.log {
+                        let level = if line.contains("error") {
+                            LogLevel::Error
+                        } else {
+                            LogLevel::Info
+                        };
+                        let _ = sender.send(BuildMessage::Log {
+                            level,
+                            message: line.clone(),
+                        });
+                    }
+                    if check.passed {
+                        let message = if check.syntax_checked {
+                            "No errors in keymap.c"
+                        } else {
+                            "QMK dry run passed (keymap.c not compiled)"
+                        };
+                        let _ = sender.send(BuildMessage::Progress {
+                            status: BuildStatus::Checking,
+                            message: message.to_string(),
+                        });
+                        None
+                    } else {
+                        Some(format!(
+                            "keymap.c check failed with {} error(s), see the build log",
+                            check.error_count()
+                        ))
+                    }
+                }
+                Err(e) => Some(enhance_qmk_error(&format!("Check failed: {e}"))),
+            };
+            let _ = sender.send(BuildMessage::Complete {
+                success: error.is_none(),
+                firmware_path: None,
+                error,
+            });
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "usb")]
+impl BuildState {
+    /// Flashes the last built firmware in the background.
+    ///
+    /// Waits up to `timeout` for a keyboard in bootloader mode, then flashes
+    /// the firmware file matching its bootloader (UF2, DFU, or avrdude).
+    pub fn start_flash(&mut self, timeout: std::time::Duration) -> Result<()> {
+        if self.is_building() {
+            anyhow::bail!("Build already in progress");
+        }
+        let Some(firmware_path) = self.firmware_path.clone() else {
+            anyhow::bail!("No firmware built yet - build first (Ctrl+B)");
+        };
+
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+        self.status = BuildStatus::Flashing;
+        self.log_lines.clear();
+        self.last_message = "Waiting for a keyboard in bootloader mode...".to_string();
+
+        thread::spawn(move || {
+            let error = run_flash(&sender, &firmware_path, timeout)
+                .err()
+                .map(|e| format!("Flash failed: {e}"));
+            let _ = sender.send(BuildMessage::Complete {
+                success: error.is_none(),
+                firmware_path: None,
+                error,
+            });
+        });
+
+        Ok(())
+    }
+}
+
+/// Flashes `firmware_path` (or a sibling artifact in another format) onto
+/// the first keyboard found in bootloader mode.
+#[cfg(feature = "usb")]
+fn run_flash(
+    sender: &std::sync::mpsc::Sender<BuildMessage>,
+    firmware_path: &std::path::Path,
+    timeout: std::time::Duration,
+) -> Result<()> {
+    use crate::services::flasher::Flasher;
+
+    sender
+        .send(BuildMessage::Progress {
+            status: BuildStatus::Flashing,
+            message: "Waiting for a keyboard in bootloader mode...".to_string(),
+        })
+        .ok();
+
+    // QMK may build several formats; offer all of them to the bootloader
+    let artifacts: Vec<PathBuf> = ["uf2", "bin", "hex"]
+        .iter()
+        .map(|ext| firmware_path.with_extension(ext))
+        .filter(|path| path == firmware_path || path.exists())
+        .collect();
+
+    let flasher = Flasher::system();
+    let (artifact, target) = flasher.wait_for_target(&artifacts, timeout)?;
+    let mut log = Vec::new();
+    let result = flasher.flash(&artifact, &target, &mut log);
+    for line in String::from_utf8_lossy(&log).lines() {
+        let level = if line == "Flash complete" {
+            LogLevel::Ok
+        } else {
+            LogLevel::Info
+        };
+        sender
+            .send(BuildMessage::Log {
+                level,
+                message: line.to_string(),
+            })
+            .ok();
+    }
+    result
 }
 
 impl Default for BuildState {
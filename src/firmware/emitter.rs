@@ -0,0 +1,278 @@
+//! Pluggable firmware emitters.
+//!
+//! A [`FirmwareEmitter`] turns a validated layout into one or more output
+//! files. The generate-job pipeline runs the emitters a job asks for and
+//! packs their files into the firmware zip, so new targets only need an
+//! emitter registered in an [`EmitterRegistry`] — the pipeline and the zip
+//! packaging stay unchanged.
+//!
+//! Built-in emitters:
+//! - `qmk` — `keymap.c` and `config.h` (always part of a generate job)
+//! - `zmk` — `zmk/<keyboard>.keymap`
+//! - `via` — `via/<keyboard>.json`
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+
+use crate::config::Config;
+use crate::export;
+use crate::keycode_db::KeycodeDb;
+use crate::models::{KeyboardGeometry, Layout, VisualLayoutMapping};
+use crate::parser::keyboard_json::resolve_info_json;
+
+use super::FirmwareGenerator;
+
+/// Id of the QMK emitter every generate job runs.
+pub const QMK_EMITTER_ID: &str = "qmk";
+
+/// Everything an emitter may read while producing its files.
+#[derive(Clone, Copy)]
+pub struct EmitContext<'a> {
+    /// Layout being generated (already adjusted for the keyboard target)
+    pub layout: &'a Layout,
+    /// Keyboard geometry
+    pub geometry: &'a KeyboardGeometry,
+    /// Visual to matrix mapping for `geometry`
+    pub mapping: &'a VisualLayoutMapping,
+    /// Application config (QMK path, keyboard roots)
+    pub config: &'a Config,
+    /// Keycode database
+    pub keycode_db: &'a KeycodeDb,
+}
+
+impl EmitContext<'_> {
+    /// Returns the keyboard name with `/` replaced, for use in file names.
+    #[must_use]
+    pub fn keyboard_file_stem(&self) -> String {
+        self.layout
+            .metadata
+            .keyboard
+            .as_deref()
+            .unwrap_or("keyboard")
+            .replace('/', "_")
+    }
+}
+
+/// A file produced by an emitter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmittedFile {
+    /// Relative path inside the firmware archive, e.g. `zmk/crkbd.keymap`
+    pub name: String,
+    /// File contents
+    pub contents: String,
+}
+
+impl EmittedFile {
+    /// Creates an emitted file.
+    #[must_use]
+    pub fn new(name: impl Into<String>, contents: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            contents: contents.into(),
+        }
+    }
+}
+
+/// Produces output files for one firmware target.
+pub trait FirmwareEmitter: Send + Sync {
+    /// Unique id used to select the emitter, e.g. `zmk`.
+    fn id(&self) -> &str;
+
+    /// Short human-readable description.
+    fn description(&self) -> &str;
+
+    /// Produces the emitter's files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layout can't be expressed for this target.
+    fn emit(&self, ctx: &EmitContext<'_>) -> Result<Vec<EmittedFile>>;
+}
+
+/// QMK `keymap.c` and `config.h`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QmkEmitter;
+
+impl FirmwareEmitter for QmkEmitter {
+    fn id(&self) -> &'static str {
+        QMK_EMITTER_ID
+    }
+
+    fn description(&self) -> &'static str {
+        "QMK keymap.c and config.h"
+    }
+
+    fn emit(&self, ctx: &EmitContext<'_>) -> Result<Vec<EmittedFile>> {
+        let generator = FirmwareGenerator::new(
+            ctx.layout,
+            ctx.geometry,
+            ctx.mapping,
+            ctx.config,
+            ctx.keycode_db,
+        );
+        let keymap_c = generator
+            .generate_keymap_c()
+            .context("Failed to generate keymap.c")?;
+        let config_h = generator
+            .generate_merged_config_h()
+            .context("Failed to generate config.h")?;
+        Ok(vec![
+            EmittedFile::new("keymap.c", keymap_c),
+            EmittedFile::new("config.h", config_h),
+        ])
+    }
+}
+
+/// ZMK devicetree keymap (see [`export::zmk`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZmkEmitter;
+
+impl FirmwareEmitter for ZmkEmitter {
+    fn id(&self) -> &'static str {
+        "zmk"
+    }
+
+    fn description(&self) -> &'static str {
+        "ZMK .keymap file"
+    }
+
+    fn emit(&self, ctx: &EmitContext<'_>) -> Result<Vec<EmittedFile>> {
+        let keymap = export::export_to_zmk(ctx.layout, ctx.geometry, ctx.keycode_db)?;
+        Ok(vec![EmittedFile::new(
+            format!("zmk/{}.keymap", ctx.keyboard_file_stem()),
+            keymap,
+        )])
+    }
+}
+
+/// VIA saved layout (see [`export::via`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ViaEmitter;
+
+impl FirmwareEmitter for ViaEmitter {
+    fn id(&self) -> &'static str {
+        "via"
+    }
+
+    fn description(&self) -> &'static str {
+        "VIA saved layout .json"
+    }
+
+    fn emit(&self, ctx: &EmitContext<'_>) -> Result<Vec<EmittedFile>> {
+        let vendor_product_id = ctx
+            .config
+            .qmk_tree_path(ctx.layout.metadata.qmk_tree.as_deref())
+            .zip(ctx.layout.metadata.keyboard.as_deref())
+            .and_then(|(qmk_path, keyboard)| {
+                resolve_info_json(qmk_path, &ctx.config.paths.keyboard_roots, keyboard).ok()
+            })
+            .and_then(|resolved| export::via::vendor_product_id(&resolved.info));
+        let json =
+            export::export_to_via(ctx.layout, ctx.geometry, ctx.keycode_db, vendor_product_id)?;
+        Ok(vec![EmittedFile::new(
+            format!("via/{}.json", ctx.keyboard_file_stem()),
+            json,
+        )])
+    }
+}
+
+/// Set of emitters a generate pipeline can run, looked up by id.
+#[derive(Clone)]
+pub struct EmitterRegistry {
+    emitters: Vec<Arc<dyn FirmwareEmitter>>,
+}
+
+impl EmitterRegistry {
+    /// Creates a registry with the built-in emitters.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            emitters: vec![
+                Arc::new(QmkEmitter),
+                Arc::new(ZmkEmitter),
+                Arc::new(ViaEmitter),
+            ],
+        }
+    }
+
+    /// Adds an emitter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an emitter with the same id is already registered.
+    pub fn register(&mut self, emitter: impl FirmwareEmitter + 'static) -> Result<()> {
+        if self.get(emitter.id()).is_some() {
+            bail!("Emitter '{}' is already registered", emitter.id());
+        }
+        self.emitters.push(Arc::new(emitter));
+        Ok(())
+    }
+
+    /// Returns the emitter with the given id.
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&dyn FirmwareEmitter> {
+        self.emitters
+            .iter()
+            .find(|emitter| emitter.id() == id)
+            .map(AsRef::as_ref)
+    }
+
+    /// Returns the registered emitters, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn FirmwareEmitter> {
+        self.emitters.iter().map(AsRef::as_ref)
+    }
+
+    /// Runs the QMK emitter followed by `ids` (duplicates and `qmk` itself
+    /// are skipped) and returns their files in that order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an id is unknown, an emitter fails, or two
+    /// emitters produce the same file name.
+    pub fn emit(&self, ids: &[&str], ctx: &EmitContext<'_>) -> Result<Vec<EmittedFile>> {
+        let mut selected = vec![QMK_EMITTER_ID];
+        for id in ids {
+            if !selected.contains(id) {
+                selected.push(id);
+            }
+        }
+
+        let mut files = Vec::new();
+        let mut names = HashSet::new();
+        for id in selected {
+            let Some(emitter) = self.get(id) else {
+                let known: Vec<&str> = self.iter().map(FirmwareEmitter::id).collect();
+                bail!("Unknown emitter '{id}' (available: {})", known.join(", "));
+            };
+            for file in emitter
+                .emit(ctx)
+                .with_context(|| format!("Emitter '{id}' failed"))?
+            {
+                if !names.insert(file.name.clone()) {
+                    bail!("Emitter '{id}' produced duplicate file '{}'", file.name);
+                }
+                files.push(file);
+            }
+        }
+        Ok(files)
+    }
+}
+
+impl Default for EmitterRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for EmitterRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(FirmwareEmitter::id))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,119 @@
+//! Tests for emitter.
+
+use super::*;
+use crate::models::keyboard_geometry::KeyGeometry;
+use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+
+/// Two-key layout on a `test/rev1` board.
+fn create_test_setup() -> (Layout, KeyboardGeometry, Config, KeycodeDb) {
+    let mut layout = Layout::new("Test").unwrap();
+    layout.metadata.keyboard = Some("test/rev1".to_string());
+    layout.metadata.layout_variant = Some("LAYOUT".to_string());
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"));
+    layout.add_layer(layer).unwrap();
+
+    let mut geometry = KeyboardGeometry::new("test/rev1", "LAYOUT", 1, 2);
+    geometry.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+    geometry.add_key(KeyGeometry::new((0, 1), 1, 1.0, 0.0));
+
+    let keycode_db = KeycodeDb::load().expect("Failed to load keycode database");
+    (layout, geometry, Config::default(), keycode_db)
+}
+
+/// Emitter that writes a fixed file, for registry tests.
+struct TextEmitter {
+    id: &'static str,
+    file: &'static str,
+}
+
+impl FirmwareEmitter for TextEmitter {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn description(&self) -> &'static str {
+        "Test emitter"
+    }
+
+    fn emit(&self, ctx: &EmitContext<'_>) -> Result<Vec<EmittedFile>> {
+        Ok(vec![EmittedFile::new(self.file, &ctx.layout.metadata.name)])
+    }
+}
+
+#[test]
+fn test_builtin_emitters() {
+    let (layout, geometry, config, keycode_db) = create_test_setup();
+    let mapping = VisualLayoutMapping::build(&geometry);
+    let ctx = EmitContext {
+        layout: &layout,
+        geometry: &geometry,
+        mapping: &mapping,
+        config: &config,
+        keycode_db: &keycode_db,
+    };
+    let registry = EmitterRegistry::new();
+
+    let ids: Vec<&str> = registry.iter().map(FirmwareEmitter::id).collect();
+    assert_eq!(ids, ["qmk", "zmk", "via"]);
+
+    // QMK always runs first; duplicates are skipped
+    let files = registry.emit(&["via", "zmk", "qmk", "via"], &ctx).unwrap();
+    let names: Vec<&str> = files.iter().map(|file| file.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "keymap.c",
+            "config.h",
+            "via/test_rev1.json",
+            "zmk/test_rev1.keymap"
+        ]
+    );
+    assert!(files[0].contents.contains("KC_A"));
+    assert!(files[3].contents.contains("&kp A"));
+
+    let err = registry.emit(&["kanata"], &ctx).unwrap_err();
+    assert!(err.to_string().contains("Unknown emitter 'kanata'"));
+    assert!(err.to_string().contains("qmk, zmk, via"));
+}
+
+#[test]
+fn test_register_custom_emitter() {
+    let (layout, geometry, config, keycode_db) = create_test_setup();
+    let mapping = VisualLayoutMapping::build(&geometry);
+    let ctx = EmitContext {
+        layout: &layout,
+        geometry: &geometry,
+        mapping: &mapping,
+        config: &config,
+        keycode_db: &keycode_db,
+    };
+    let mut registry = EmitterRegistry::new();
+
+    registry
+        .register(TextEmitter {
+            id: "name",
+            file: "name.txt",
+        })
+        .unwrap();
+    assert!(registry
+        .register(TextEmitter {
+            id: "zmk",
+            file: "other.txt",
+        })
+        .is_err());
+
+    let files = registry.emit(&["name"], &ctx).unwrap();
+    assert_eq!(files[2], EmittedFile::new("name.txt", "Test"));
+
+    // Two emitters writing the same file is an error, not a silent overwrite
+    registry
+        .register(TextEmitter {
+            id: "clash",
+            file: "keymap.c",
+        })
+        .unwrap();
+    let err = registry.emit(&["clash"], &ctx).unwrap_err();
+    assert!(err.to_string().contains("duplicate file 'keymap.c'"));
+}
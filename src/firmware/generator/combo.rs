@@ -1,17 +1,35 @@
 //! Combo code generation for QMK keymap.c.
 //!
 //! Emits QMK combo arrays and `process_combo_event` handler for two-key hold
-//! actions. Combos are base-layer only and require holding both keys for the
-//! configured duration.
+//! actions. Hold combos are base-layer only and require holding both keys for
+//! the configured duration. Keycode combos are emitted after them as plain
+//! `COMBO(keys, keycode)` entries.
 
 use anyhow::{anyhow, Result};
 
+use super::keymap_helpers::{resolve_keycode, tap_dance};
 use super::FirmwareGenerator;
 
+/// Returns the number of combos emitted to `key_combos` (placeholders excluded).
+pub fn combo_count(gen: &FirmwareGenerator) -> usize {
+    let settings = &gen.layout.combo_settings;
+    settings.combos.iter().filter(|c| !c.placeholder).count() + settings.keycode_combos.len()
+}
+
+/// Converts a keycode to its C form (layer, tap dance, macro, and Unicode
+/// references).
+pub(super) fn process_keycode(gen: &FirmwareGenerator, keycode: &str) -> String {
+    let resolved = resolve_keycode(gen, keycode);
+    let processed = tap_dance::process_keycode(gen, &resolved);
+    let processed = super::macros::process_keycode(gen, &processed);
+    super::unicode::process_keycode(gen, &processed)
+}
+
 /// Generates combo code if enabled.
 ///
 /// Emits QMK combo arrays and `process_combo_event` handler for two-key hold actions.
 /// Combos are base-layer only and require holding both keys for the configured duration.
+/// Keycode combos use the base-layer keycodes at their trigger positions.
 #[allow(clippy::unnecessary_wraps)]
 pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
     // Collect only real (non-placeholder) combos so that gaps created when
@@ -25,11 +43,23 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
         .filter(|c| !c.placeholder)
         .collect();
 
+    let keycode_combos = &gen.layout.combo_settings.keycode_combos;
+
     // Only generate if combos are enabled and at least one real combo is defined
-    if !gen.layout.combo_settings.enabled || real_combos.is_empty() {
+    if !gen.layout.combo_settings.enabled || combo_count(gen) == 0 {
         return Ok(String::new());
     }
 
+    // A keyboard layout must always have a base layer; if it doesn't,
+    // we cannot generate combo code that references it.
+    let base_layer = gen.layout.get_layer(0).ok_or_else(|| {
+        anyhow!(
+            "Cannot generate firmware for layout '{}': missing base layer (layer 0). \
+             A keyboard layout must always contain a base layer.",
+            gen.layout.metadata.name
+        )
+    })?;
+
     let mut code = String::new();
 
     code.push_str("#ifdef COMBO_ENABLE\n");
@@ -39,14 +69,11 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
 
     // Generate combo enum
     code.push_str("enum combo_events {\n");
-    for (idx, _combo) in real_combos.iter().enumerate() {
-        code.push_str(&format!("    COMBO_{}", idx));
-        if idx < real_combos.len() - 1 {
-            code.push_str(",\n");
-        } else {
-            code.push('\n');
-        }
-    }
+    let names: Vec<String> = (0..real_combos.len())
+        .map(|idx| format!("COMBO_{idx}"))
+        .chain((0..keycode_combos.len()).map(|idx| format!("KEYCODE_COMBO_{idx}")))
+        .collect();
+    code.push_str(&format!("    {}\n", names.join(",\n    ")));
     code.push_str("};\n");
     code.push('\n');
 
@@ -57,16 +84,6 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
     // `get_key()` which also operates on visual positions.
     for (idx, combo) in real_combos.iter().enumerate() {
         // Get keycodes for the two positions from base layer (layer 0).
-        // A keyboard layout must always have a base layer; if it doesn't,
-        // we cannot generate combo code that references it.
-        let base_layer = gen.layout.get_layer(0).ok_or_else(|| {
-            anyhow!(
-                "Cannot generate firmware for layout '{}': missing base layer (layer 0). \
-                 A keyboard layout must always contain a base layer.",
-                gen.layout.metadata.name
-            )
-        })?;
-
         let key1 = base_layer
             .get_key(combo.key1)
             .map(|k| k.keycode.clone())
@@ -81,6 +98,21 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
             idx, key1, key2
         ));
     }
+    for (idx, combo) in keycode_combos.iter().enumerate() {
+        let keys: Vec<String> = combo
+            .keys
+            .iter()
+            .map(|position| {
+                base_layer
+                    .get_key(*position)
+                    .map_or_else(|| "KC_NO".to_string(), |k| process_keycode(gen, &k.keycode))
+            })
+            .collect();
+        code.push_str(&format!(
+            "const uint16_t PROGMEM keycode_combo_{idx}_keys[] = {{{}, COMBO_END}};\n",
+            keys.join(", ")
+        ));
+    }
     code.push('\n');
 
     // Generate combo array
@@ -91,9 +123,20 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
             idx, idx
         ));
     }
+    for (idx, combo) in keycode_combos.iter().enumerate() {
+        code.push_str(&format!(
+            "    [KEYCODE_COMBO_{idx}] = COMBO(keycode_combo_{idx}_keys, {}),\n",
+            process_keycode(gen, &combo.keycode)
+        ));
+    }
     code.push_str("};\n");
     code.push('\n');
 
+    if real_combos.is_empty() {
+        code.push_str("#endif // COMBO_ENABLE\n");
+        return Ok(code);
+    }
+
     // Generate combo state tracking for hold durations
     code.push_str("// Combo hold state tracking\n");
     code.push_str("static struct {\n");
@@ -107,6 +150,7 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
     code.push_str("    // Only activate combos on base layer (layer 0)\n");
     code.push_str("    if (get_highest_layer(layer_state) != 0) {\n");
     code.push_str("        return;\n");
+    code.push_str("    }\n");
     code.push('\n');
     code.push_str("    if (pressed) {\n");
     code.push_str("        // Start hold timer\n");
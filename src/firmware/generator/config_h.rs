@@ -1,8 +1,8 @@
 //! `config.h` generation.
 //!
 //! Merges keymap-specific `#define` flags (tap-hold, RGB matrix, idle effect,
-//! PaletteFX, ripple overlay, combo count) with the keyboard's variant-specific
-//! `keyboard.json` config. Note: `RGB_MATRIX_LED_COUNT` belongs in `keyboard.json`,
+//! PaletteFX, ripple overlay, conditional RGB, combo count, Unicode input
+//! modes) with the keyboard's variant-specific `keyboard.json` config. Note: `RGB_MATRIX_LED_COUNT` belongs in `keyboard.json`,
 //! not in the keymap `config.h`.
 
 use anyhow::Result;
@@ -24,14 +24,10 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
     // Add our generated configuration
     content.push_str(&format!("// Generated by {}\n", APP_BINARY_NAME));
     content.push_str(&format!("// Layout: {}\n", gen.layout.metadata.name));
-    content.push_str(&format!(
-        "// Generated: {}\n",
-        chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
-    ));
     content.push('\n');
     content.push_str("#pragma once\n");
     content.push('\n');
-    content.push_str("// Add keymap-specific configuration here\n");
+    content.push_str("// Add keymap-specific configuration in the user region at the end\n");
 
     // === Tap-Hold Settings ===
     let ths = &gen.layout.tap_hold_settings;
@@ -48,6 +44,11 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
             content.push_str(&format!("#define TAPPING_TERM {}\n", ths.tapping_term));
         }
 
+        // Per-key tapping terms come from get_tapping_term() in keymap.c
+        if !ths.per_key_tapping_terms.is_empty() {
+            content.push_str("#define TAPPING_TERM_PER_KEY\n");
+        }
+
         // Quick Tap Term
         if ths.has_custom_quick_tap_term() {
             if let Some(term) = ths.quick_tap_term {
@@ -284,21 +285,69 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
         ));
     }
 
+    // === Conditional RGB Behaviors ===
+    let rules = &gen.layout.rgb_conditions;
+    if rules.is_active() && gen.geometry.has_rgb_matrix() {
+        rules.validate(gen.layout.layers.len())?;
+
+        content.push_str("\n// Conditional RGB Behaviors\n");
+        content.push_str("#define LQMK_RGB_CONDITIONS_ENABLED\n");
+        if rules.dim_after_minutes > 0 {
+            content.push_str(&format!(
+                "#define LQMK_RGB_DIM_AFTER_MS {}UL\n",
+                u32::from(rules.dim_after_minutes) * 60_000
+            ));
+            content.push_str(&format!(
+                "#define LQMK_RGB_DIM_LEVEL {}\n",
+                u16::from(rules.dim_brightness_pct) * 255 / 100
+            ));
+        }
+        if rules.wpm_threshold > 0 {
+            content.push_str(&format!(
+                "#define LQMK_RGB_WPM_THRESHOLD {}\n",
+                rules.wpm_threshold
+            ));
+            content.push_str(&format!(
+                "#define LQMK_RGB_WPM_MODE {}\n",
+                rules.wpm_effect.qmk_mode_name()
+            ));
+        }
+        if let Some(range) = rules.off_hours {
+            // Raw HID report ID 'L' carries the host clock for off hours
+            content.push_str(&format!("// Off hours: {range}\n"));
+            content.push_str(&format!(
+                "#define LQMK_RGB_OFF_START_MIN {}\n",
+                range.start_minute
+            ));
+            content.push_str(&format!(
+                "#define LQMK_RGB_OFF_END_MIN {}\n",
+                range.end_minute
+            ));
+            content.push_str("#define LQMK_RAW_HID_CLOCK_ID 0x4C\n");
+        }
+    }
+
     // === Combo Settings ===
-    let real_combo_count = gen
-        .layout
-        .combo_settings
-        .combos
-        .iter()
-        .filter(|c| !c.placeholder)
-        .count();
+    let real_combo_count = super::combo::combo_count(gen);
     if gen.layout.combo_settings.enabled && real_combo_count > 0 {
         content.push_str("\n// Combo Configuration\n");
         // COMBO_ENABLE is set via rules.mk (COMBO_ENABLE = yes) which causes QMK's
         // build system to pass -DCOMBO_ENABLE and include process_combo.c.
         // Defining it again here would cause a "redefined" compiler error.
         content.push_str(&format!("#define COMBO_COUNT {}\n", real_combo_count));
+        // Keycode combos are defined by position, so match them against the
+        // base-layer keycodes whichever layer is active.
+        if !gen.layout.combo_settings.keycode_combos.is_empty() {
+            content.push_str("#define COMBO_ONLY_FROM_LAYER 0\n");
+        }
     }
 
+    // === Unicode Input Modes ===
+    content.push_str(&super::unicode::generate_config(gen));
+
+    // === User Code ===
+    content.push('\n');
+    content.push_str(&crate::firmware::user_code::empty_region());
+
     Ok(content)
 }
@@ -0,0 +1,50 @@
+//! Custom keycode code generation for QMK keymap.c.
+//!
+//! Custom keycodes share `enum custom_keycodes` and `process_record_user`
+//! with macros (see [`super::macros`]). Their snippets run on press and
+//! release, so their `switch` comes before the press-only macro playback.
+
+use crate::models::CustomKeycode;
+
+use super::FirmwareGenerator;
+
+/// Returns custom keycodes sorted by name for stable enum ordering.
+pub fn sorted<'a>(gen: &'a FirmwareGenerator) -> Vec<&'a CustomKeycode> {
+    let mut sorted: Vec<_> = gen.layout.custom_keycodes.iter().collect();
+    sorted.sort_by_key(|c| &c.name);
+    sorted
+}
+
+/// Generates the `switch` that runs each custom keycode's snippet.
+///
+/// The snippet gets its own block so it can declare variables. Unless it
+/// returns itself, the key event is consumed.
+pub fn generate_cases(gen: &FirmwareGenerator) -> String {
+    if gen.layout.custom_keycodes.is_empty() {
+        return String::new();
+    }
+
+    let mut code = String::new();
+    code.push_str("    // Custom keycodes\n");
+    code.push_str("    switch (keycode) {\n");
+
+    for custom in sorted(gen) {
+        code.push_str(&format!("        case {}: {{\n", custom.name));
+        if !custom.description.is_empty() {
+            code.push_str(&format!("            // {}\n", custom.description));
+        }
+        for line in custom.behavior.trim().lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                code.push('\n');
+            } else {
+                code.push_str(&format!("            {line}\n"));
+            }
+        }
+        code.push_str("            return false;\n");
+        code.push_str("        }\n");
+    }
+
+    code.push_str("    }\n");
+    code
+}
@@ -0,0 +1,240 @@
+//! Editing helper code generation for QMK keymap.c.
+//!
+//! Each enabled helper adds its keycode to `enum custom_keycodes` (see
+//! [`super::macros`]), a `process_<helper>` function emitted here, and a
+//! call at the top of `process_record_user`. The helpers are small
+//! self-contained versions of the well-known userspace features:
+//!
+//! - Select Word: selects the word under the cursor with Ctrl+Shift+Right,
+//!   each further press extends the selection. macOS users define
+//!   `SELECT_WORD_MOD MOD_BIT(KC_LALT)` in config.h.
+//! - Sentence Case: shifts the first letter after `.`, `!` or `?` and a
+//!   space. `SENTCASE` toggles it.
+//! - Num Word: turns on the numbers layer until a key other than a digit,
+//!   number punctuation or Backspace is typed.
+
+use crate::models::EditingHelper;
+
+use super::FirmwareGenerator;
+
+/// Returns the keycodes of the enabled helpers in display order.
+pub fn keycodes(gen: &FirmwareGenerator) -> Vec<&'static str> {
+    gen.layout
+        .editing_helpers
+        .enabled()
+        .into_iter()
+        .map(EditingHelper::keycode)
+        .collect()
+}
+
+/// Generates the helper functions for the enabled helpers.
+pub fn generate(gen: &FirmwareGenerator) -> String {
+    let helpers = &gen.layout.editing_helpers;
+    if !helpers.any_enabled() {
+        return String::new();
+    }
+
+    let mut code = String::new();
+    code.push_str("// Editing Helpers\n");
+
+    if helpers.sentence_case || helpers.num_word {
+        code.push_str(&generate_tap_keycode_helper());
+    }
+    if helpers.select_word {
+        code.push('\n');
+        code.push_str(&generate_select_word());
+    }
+    if helpers.sentence_case {
+        code.push('\n');
+        code.push_str(&generate_sentence_case());
+    }
+    if helpers.num_word {
+        code.push('\n');
+        code.push_str(&format!(
+            "#define NUM_WORD_LAYER {}\n",
+            helpers.num_word_layer
+        ));
+        code.push_str(&generate_num_word());
+    }
+
+    code
+}
+
+/// Generates the calls to the enabled helpers for `process_record_user`.
+pub fn generate_calls(gen: &FirmwareGenerator) -> String {
+    let enabled = gen.layout.editing_helpers.enabled();
+    if enabled.is_empty() {
+        return String::new();
+    }
+
+    let mut code = String::new();
+    code.push_str("    // Editing helpers\n");
+    for helper in enabled {
+        let function = match helper {
+            EditingHelper::SelectWord => "process_select_word",
+            EditingHelper::SentenceCase => "process_sentence_case",
+            EditingHelper::NumWord => "process_num_word",
+        };
+        code.push_str(&format!("    if (!{function}(keycode, record)) {{\n"));
+        code.push_str("        return false;\n");
+        code.push_str("    }\n");
+    }
+    code
+}
+
+/// Generates `helper_tap_keycode`, which resolves mod-tap and layer-tap keys
+/// to the key they type.
+fn generate_tap_keycode_helper() -> String {
+    let mut code = String::new();
+    code.push_str(
+        "// Returns the basic keycode a key types, or KC_NO while a tap-hold key is held\n",
+    );
+    code.push_str("static uint16_t helper_tap_keycode(uint16_t keycode, keyrecord_t *record) {\n");
+    code.push_str("    switch (keycode) {\n");
+    code.push_str("        case QK_MOD_TAP ... QK_MOD_TAP_MAX:\n");
+    code.push_str("        case QK_LAYER_TAP ... QK_LAYER_TAP_MAX:\n");
+    code.push_str("            return record->tap.count == 0 ? KC_NO : (keycode & 0xFF);\n");
+    code.push_str("    }\n");
+    code.push_str("    return keycode;\n");
+    code.push_str("}\n");
+    code
+}
+
+/// Generates `process_select_word`.
+fn generate_select_word() -> String {
+    let mut code = String::new();
+    code.push_str("#ifndef SELECT_WORD_MOD\n");
+    code.push_str("#    define SELECT_WORD_MOD MOD_BIT(KC_LCTL)\n");
+    code.push_str("#endif\n");
+    code.push('\n');
+    code.push_str("static bool select_word_active = false;\n");
+    code.push('\n');
+    code.push_str("static bool process_select_word(uint16_t keycode, keyrecord_t *record) {\n");
+    code.push_str("    if (keycode != SELWORD) {\n");
+    code.push_str("        if (record->event.pressed) {\n");
+    code.push_str("            select_word_active = false;\n");
+    code.push_str("        }\n");
+    code.push_str("        return true;\n");
+    code.push_str("    }\n");
+    code.push('\n');
+    code.push_str("    if (record->event.pressed) {\n");
+    code.push_str("        if (!select_word_active) {\n");
+    code.push_str("            // Jump to the start of the current word first\n");
+    code.push_str("            register_mods(SELECT_WORD_MOD);\n");
+    code.push_str("            tap_code(KC_RGHT);\n");
+    code.push_str("            tap_code(KC_LEFT);\n");
+    code.push_str("            unregister_mods(SELECT_WORD_MOD);\n");
+    code.push_str("            select_word_active = true;\n");
+    code.push_str("        }\n");
+    code.push_str("        register_mods(SELECT_WORD_MOD | MOD_BIT(KC_LSFT));\n");
+    code.push_str("        register_code(KC_RGHT);\n");
+    code.push_str("    } else {\n");
+    code.push_str("        unregister_code(KC_RGHT);\n");
+    code.push_str("        unregister_mods(SELECT_WORD_MOD | MOD_BIT(KC_LSFT));\n");
+    code.push_str("    }\n");
+    code.push_str("    return false;\n");
+    code.push_str("}\n");
+    code
+}
+
+/// Generates `process_sentence_case`.
+fn generate_sentence_case() -> String {
+    let mut code = String::new();
+    code.push_str("static bool sentence_case_on = true;\n");
+    code.push_str("// 0: in a sentence, 1: after end punctuation, 2: capitalize the next letter\n");
+    code.push_str("static uint8_t sentence_case_state = 0;\n");
+    code.push('\n');
+    code.push_str("static bool process_sentence_case(uint16_t keycode, keyrecord_t *record) {\n");
+    code.push_str("    if (keycode == SENTCASE) {\n");
+    code.push_str("        if (record->event.pressed) {\n");
+    code.push_str("            sentence_case_on = !sentence_case_on;\n");
+    code.push_str("            sentence_case_state = 0;\n");
+    code.push_str("        }\n");
+    code.push_str("        return false;\n");
+    code.push_str("    }\n");
+    code.push_str("    if (!sentence_case_on || !record->event.pressed) {\n");
+    code.push_str("        return true;\n");
+    code.push_str("    }\n");
+    code.push('\n');
+    code.push_str("    keycode = helper_tap_keycode(keycode, record);\n");
+    code.push_str("    const bool shifted = (get_mods() | get_oneshot_mods()) & MOD_MASK_SHIFT;\n");
+    code.push_str("    switch (keycode) {\n");
+    code.push_str("        case KC_NO:\n");
+    code.push_str("        case KC_LCTL ... KC_RGUI:\n");
+    code.push_str("            break;\n");
+    code.push_str("        case KC_DOT:\n");
+    code.push_str("            sentence_case_state = shifted ? 0 : 1;\n");
+    code.push_str("            break;\n");
+    code.push_str("        case KC_1:\n");
+    code.push_str("        case KC_SLSH:\n");
+    code.push_str("            sentence_case_state = shifted ? 1 : 0;\n");
+    code.push_str("            break;\n");
+    code.push_str("        case KC_SPC:\n");
+    code.push_str("        case KC_ENT:\n");
+    code.push_str("            if (sentence_case_state == 1) {\n");
+    code.push_str("                sentence_case_state = 2;\n");
+    code.push_str("            }\n");
+    code.push_str("            break;\n");
+    code.push_str("        case KC_A ... KC_Z:\n");
+    code.push_str("            if (sentence_case_state == 2 && !shifted) {\n");
+    code.push_str("                sentence_case_state = 0;\n");
+    code.push_str("                tap_code16(S(keycode));\n");
+    code.push_str("                return false;\n");
+    code.push_str("            }\n");
+    code.push_str("            sentence_case_state = 0;\n");
+    code.push_str("            break;\n");
+    code.push_str("        default:\n");
+    code.push_str("            sentence_case_state = 0;\n");
+    code.push_str("            break;\n");
+    code.push_str("    }\n");
+    code.push_str("    return true;\n");
+    code.push_str("}\n");
+    code
+}
+
+/// Generates `process_num_word`.
+fn generate_num_word() -> String {
+    let mut code = String::new();
+    code.push_str("static bool num_word_active = false;\n");
+    code.push('\n');
+    code.push_str("static void num_word_off(void) {\n");
+    code.push_str("    num_word_active = false;\n");
+    code.push_str("    layer_off(NUM_WORD_LAYER);\n");
+    code.push_str("}\n");
+    code.push('\n');
+    code.push_str("static bool process_num_word(uint16_t keycode, keyrecord_t *record) {\n");
+    code.push_str("    if (keycode == NUMWORD) {\n");
+    code.push_str("        if (record->event.pressed) {\n");
+    code.push_str("            if (num_word_active) {\n");
+    code.push_str("                num_word_off();\n");
+    code.push_str("            } else {\n");
+    code.push_str("                num_word_active = true;\n");
+    code.push_str("                layer_on(NUM_WORD_LAYER);\n");
+    code.push_str("            }\n");
+    code.push_str("        }\n");
+    code.push_str("        return false;\n");
+    code.push_str("    }\n");
+    code.push_str("    if (!num_word_active || !record->event.pressed) {\n");
+    code.push_str("        return true;\n");
+    code.push_str("    }\n");
+    code.push('\n');
+    code.push_str("    switch (helper_tap_keycode(keycode, record)) {\n");
+    code.push_str("        case KC_NO:\n");
+    code.push_str("        case KC_1 ... KC_0:\n");
+    code.push_str("        case KC_KP_1 ... KC_KP_0:\n");
+    code.push_str("        case KC_DOT:\n");
+    code.push_str("        case KC_COMM:\n");
+    code.push_str("        case KC_MINS:\n");
+    code.push_str("        case KC_PDOT:\n");
+    code.push_str("        case KC_PMNS:\n");
+    code.push_str("        case KC_BSPC:\n");
+    code.push_str("        case KC_LCTL ... KC_RGUI:\n");
+    code.push_str("            break;\n");
+    code.push_str("        default:\n");
+    code.push_str("            num_word_off();\n");
+    code.push_str("            break;\n");
+    code.push_str("    }\n");
+    code.push_str("    return true;\n");
+    code.push_str("}\n");
+    code
+}
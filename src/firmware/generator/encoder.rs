@@ -1,13 +1,34 @@
 //! Conditional encoder map generation for QMK keymap.c.
 //!
 //! Emits the `#ifdef ENCODER_MAP_ENABLE ... #endif` block listing per-layer
-//! encoder bindings. When ENCODER_MAP_ENABLE is not defined (e.g. keyboards
+//! encoder bindings. Layers use their own encoder map where set and fall
+//! back to default RGB/volume bindings otherwise. When ENCODER_MAP_ENABLE is not defined (e.g. keyboards
 //! without encoders), the entire block is dropped from the firmware.
 
 use anyhow::Result;
 
+use super::combo::process_keycode;
 use super::FirmwareGenerator;
 
+/// Bindings used for encoders a layer leaves unset, as (ccw, cw).
+///
+/// Default encoder actions cycle through: RGB effect, hue, brightness,
+/// saturation; encoders beyond them reuse the last (volume) binding.
+const DEFAULT_ENCODER_BINDINGS: [(&str, &str); 5] = [
+    ("RM_NEXT", "RM_PREV"), // Encoder 0: RGB effect
+    ("RM_HUEU", "RM_HUED"), // Encoder 1: RGB hue
+    ("RM_VALU", "RM_VALD"), // Encoder 2: RGB brightness
+    ("RM_SATU", "RM_SATD"), // Encoder 3: RGB saturation
+    ("KC_VOLU", "KC_VOLD"), // Encoder 4+: Volume (fallback for extra encoders)
+];
+
+/// Returns the (ccw, cw) keycodes generated for `encoder` on a layer without
+/// its own binding.
+#[must_use]
+pub fn default_encoder_binding(encoder: usize) -> (&'static str, &'static str) {
+    DEFAULT_ENCODER_BINDINGS[encoder.min(DEFAULT_ENCODER_BINDINGS.len() - 1)]
+}
+
 /// Generates a conditional `encoder_map` wrapped in #ifdef `ENCODER_MAP_ENABLE`.
 ///
 /// This allows the keymap to work both with and without encoders enabled.
@@ -23,25 +44,22 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
     code.push_str("const uint16_t PROGMEM encoder_map[][NUM_ENCODERS][NUM_DIRECTIONS] = {\n");
 
     // Generate encoder bindings for each layer
-    // Default encoder actions cycle through: RGB effect, hue, brightness, saturation
-    let default_encoder_bindings = [
-        ("RM_NEXT", "RM_PREV"), // Encoder 0: RGB effect
-        ("RM_HUEU", "RM_HUED"), // Encoder 1: RGB hue
-        ("RM_VALU", "RM_VALD"), // Encoder 2: RGB brightness
-        ("RM_SATU", "RM_SATD"), // Encoder 3: RGB saturation
-        ("KC_VOLU", "KC_VOLD"), // Encoder 4+: Volume (fallback for extra encoders)
-    ];
-
-    for (layer_idx, _layer) in gen.layout.layers.iter().enumerate() {
+    for (layer_idx, layer) in gen.layout.layers.iter().enumerate() {
         code.push_str(&format!("    [{layer_idx}] = {{\n"));
 
         // Generate encoder bindings based on actual encoder count
         for enc_idx in 0..encoder_count {
-            let (ccw, cw) = if enc_idx < default_encoder_bindings.len() {
-                default_encoder_bindings[enc_idx]
+            let binding = u8::try_from(enc_idx)
+                .ok()
+                .and_then(|encoder| layer.encoder_map.get(encoder));
+            let (ccw, cw) = if let Some(binding) = binding {
+                (
+                    process_keycode(gen, &binding.ccw),
+                    process_keycode(gen, &binding.cw),
+                )
             } else {
-                // Fallback for any additional encoders beyond our defaults
-                default_encoder_bindings[default_encoder_bindings.len() - 1]
+                let (ccw, cw) = default_encoder_binding(enc_idx);
+                (ccw.to_string(), cw.to_string())
             };
             code.push_str(&format!("        ENCODER_CCW_CW({ccw}, {cw}),\n"));
         }
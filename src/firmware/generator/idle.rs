@@ -8,7 +8,7 @@
 
 use anyhow::Result;
 
-use super::FirmwareGenerator;
+use super::{macros, FirmwareGenerator};
 
 /// Generates idle effect state machine code if enabled.
 ///
@@ -85,8 +85,12 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
         code.push('\n');
     }
 
-    // Process record hook to reset on activity
-    code.push_str("bool process_record_user(uint16_t keycode, keyrecord_t *record) {\n");
+    // Process record hook to reset on activity (renamed when macros own process_record_user)
+    code.push_str(macros::record_hook_define(gen));
+    code.push_str(&format!(
+        "bool {}(uint16_t keycode, keyrecord_t *record) {{\n",
+        macros::record_hook_name(gen)
+    ));
 
     // Add ripple trigger integration if both features are enabled
     if has_ripple {
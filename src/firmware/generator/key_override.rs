@@ -0,0 +1,36 @@
+//! Key override code generation.
+//!
+//! Each override becomes a `ko_make_basic` definition listed in the
+//! `key_overrides[]` array QMK reads when `KEY_OVERRIDE_ENABLE = yes`.
+
+use super::combo::process_keycode;
+use super::FirmwareGenerator;
+
+/// Generates the key override definitions and `key_overrides[]` array.
+///
+/// Returns an empty string if the layout has no key overrides.
+pub fn generate(gen: &FirmwareGenerator) -> String {
+    let overrides = &gen.layout.key_overrides;
+    if overrides.is_empty() {
+        return String::new();
+    }
+
+    let mut code = String::new();
+    code.push_str("// Key Overrides\n");
+    for (idx, key_override) in overrides.iter().enumerate() {
+        code.push_str(&format!("// {key_override}\n"));
+        code.push_str(&format!(
+            "const key_override_t key_override_{idx} = ko_make_basic({}, {}, {});\n",
+            key_override.mod_mask(),
+            process_keycode(gen, &key_override.trigger),
+            process_keycode(gen, &key_override.replacement)
+        ));
+    }
+    code.push('\n');
+    code.push_str("const key_override_t *key_overrides[] = {\n");
+    for idx in 0..overrides.len() {
+        code.push_str(&format!("    &key_override_{idx},\n"));
+    }
+    code.push_str("};\n");
+    code
+}
@@ -5,12 +5,13 @@
 //! helper is exposed as a free function taking `&FirmwareGenerator`.
 
 use anyhow::{Context, Result};
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 
 use super::FirmwareGenerator;
 
 /// Scans all keycodes in the layout and returns the set of additional
-/// header files that need to be included for language-specific keycodes.
+/// header files that need to be included for language-specific keycodes,
+/// sorted so the `#include` lines are stable.
 ///
 /// This detects keycodes like `DE_Y`, `DE_UDIA` that require language-specific
 /// headers from QMK's `keymap_extras` directory.
@@ -18,7 +19,7 @@ use super::FirmwareGenerator;
 /// Language prefix-to-header mappings are loaded dynamically from the `KeycodeDb`,
 /// supporting all languages defined in the keycode database.
 pub fn detect_required_headers(gen: &FirmwareGenerator) -> Vec<String> {
-    let mut headers = HashSet::new();
+    let mut headers = BTreeSet::new();
 
     // Build prefix-to-header mapping from language database
     let languages = gen.keycode_db.languages();
@@ -80,6 +81,12 @@ pub fn generate_layer_keys_by_layout(
         // Process tap dance keycodes (e.g., TD(name) -> TD(TD_NAME))
         let processed_keycode = tap_dance::process_keycode(gen, &resolved_keycode);
 
+        // Process macro keycodes (e.g., MACRO(name) -> MACRO_NAME)
+        let processed_keycode = super::macros::process_keycode(gen, &processed_keycode);
+
+        // Process Unicode keycodes (e.g., UM(snek) -> UM(UM_SNEK))
+        let processed_keycode = super::unicode::process_keycode(gen, &processed_keycode);
+
         // Store keycode at layout position
         keys_by_layout[layout_idx as usize] = processed_keycode;
     }
@@ -108,12 +115,15 @@ pub fn resolve_keycode(gen: &FirmwareGenerator, keycode: &str) -> String {
 /// and honor the layout's four-level color priority system and the
 /// `inactive_key_behavior` setting.
 ///
+/// The result covers every physical LED. LEDs without a key (underglow and
+/// indicators) take the layer's underglow color.
+///
 /// If the layer has `colors_enabled = false`, returns all black (off) colors.
 pub fn generate_layer_colors_by_led(
     gen: &FirmwareGenerator,
     layer_idx: usize,
 ) -> Result<Vec<crate::models::RgbColor>> {
-    let led_count = gen.mapping.key_count();
+    let led_count = gen.geometry.led_count();
     let mut colors_by_led = vec![crate::models::RgbColor::default(); led_count];
 
     let layer = gen
@@ -126,8 +136,14 @@ pub fn generate_layer_colors_by_led(
         return Ok(vec![crate::models::RgbColor::new(0, 0, 0); led_count]);
     }
 
+    let underglow = gen
+        .layout
+        .apply_rgb_settings(gen.layout.resolve_underglow_color(layer_idx));
+    for led in gen.geometry.non_key_leds() {
+        colors_by_led[usize::from(led.led_index)] = underglow;
+    }
+
     // Map each key's resolved color to its LED position
-    // Uses resolve_display_color which considers inactive_key_behavior
     for key in &layer.keys {
         let visual_pos = key.position;
 
@@ -141,11 +157,8 @@ pub fn generate_layer_colors_by_led(
                 )
             })?;
 
-        // Use resolve_display_color to respect inactive_key_behavior
-        let (color, _is_key_specific) = gen.layout.resolve_display_color(layer_idx, key);
-        // Apply RGB settings (brightness and master switch)
-        let final_color = gen.layout.apply_rgb_settings(color);
-        colors_by_led[led_idx as usize] = final_color;
+        // Display color (respects inactive_key_behavior) with RGB settings applied
+        colors_by_led[led_idx as usize] = gen.layout.resolve_firmware_color(layer_idx, key);
     }
 
     Ok(colors_by_led)
@@ -197,7 +210,7 @@ pub fn generate_rgb_matrix_color_table(gen: &FirmwareGenerator) -> Result<String
 
     let mut code = String::new();
     let layer_count = gen.layout.layers.len();
-    let led_count = gen.mapping.key_count();
+    let led_count = gen.geometry.led_count();
 
     code.push_str("#ifdef RGB_MATRIX_ENABLE\n");
     code.push_str(&format!(
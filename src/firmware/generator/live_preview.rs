@@ -0,0 +1,131 @@
+//! Live preview and usage reporting for QMK keymap.c.
+//!
+//! When the layout explicitly enables the HID console, the keymap prints the
+//! active layer and every key press as `lqmk:` lines that
+//! `services::hid_listen` reads back. Keyboards that only enable the console
+//! by default get no extra code.
+//!
+//! With usage counting on (and the console not switched off), the keymap
+//! also counts presses per layer and matrix position in RAM, one byte per
+//! key and layer, and prints them as `lqmk:usage:<layer>:<row>:<col>:<n>`
+//! lines after a minute of typing or as soon as a counter fills up. Nothing
+//! is written to EEPROM; `lazyqmk collect-usage` adds the reports up on the
+//! host.
+
+use crate::models::FirmwareFeature;
+
+use super::FirmwareGenerator;
+
+/// Milliseconds between usage reports while typing.
+const USAGE_INTERVAL_MS: u32 = 60_000;
+
+/// Returns true if the generated keymap reports layers and key presses.
+fn enabled(gen: &FirmwareGenerator) -> bool {
+    gen.layout.firmware_features.get(FirmwareFeature::Console) == Some(true)
+}
+
+/// Returns true if the generated keymap counts and reports key usage.
+fn usage_enabled(gen: &FirmwareGenerator) -> bool {
+    gen.layout.usage_counting
+        && gen.layout.firmware_features.get(FirmwareFeature::Console) != Some(false)
+}
+
+/// Generates the `layer_state_set_user` and `post_process_record_user`
+/// hooks that print live preview and usage reports on the console.
+///
+/// Returns an empty string unless the console or usage counting is enabled
+/// for the layout.
+#[must_use]
+pub fn generate(gen: &FirmwareGenerator) -> String {
+    let live = enabled(gen);
+    let usage = usage_enabled(gen);
+    if !live && !usage {
+        return String::new();
+    }
+
+    let mut code = String::new();
+    if usage {
+        code.push_str(&generate_usage_counter(gen));
+        code.push('\n');
+    }
+    if live {
+        code.push_str(
+            "// Live preview: report the active layer and key presses on the HID console\n",
+        );
+    }
+    code.push_str("#ifdef CONSOLE_ENABLE\n");
+    if live {
+        code.push_str("layer_state_t layer_state_set_user(layer_state_t state) {\n");
+        code.push_str(
+            "    uprintf(\"lqmk:layer:%u\\n\", get_highest_layer(state | default_layer_state));\n",
+        );
+        code.push_str("    return state;\n");
+        code.push_str("}\n");
+        code.push('\n');
+    }
+    code.push_str("void post_process_record_user(uint16_t keycode, keyrecord_t *record) {\n");
+    code.push_str("    if (record->event.pressed) {\n");
+    if live {
+        code.push_str(
+            "        uprintf(\"lqmk:key:%u:%u\\n\", record->event.key.row, record->event.key.col);\n",
+        );
+    }
+    if usage {
+        code.push_str("        lqmk_usage_count(record);\n");
+    }
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code.push_str("#endif\n");
+    code
+}
+
+/// Generates the usage counters and the functions that count and report
+/// presses.
+fn generate_usage_counter(gen: &FirmwareGenerator) -> String {
+    let mut code = String::new();
+    code.push_str("// Usage counting: presses per layer and key, reported on the HID console\n");
+    code.push_str("#ifdef CONSOLE_ENABLE\n");
+    code.push_str(&format!(
+        "#define LQMK_USAGE_LAYERS {}\n",
+        gen.layout.layers.len().max(1)
+    ));
+    code.push_str(&format!(
+        "#define LQMK_USAGE_INTERVAL {USAGE_INTERVAL_MS}\n"
+    ));
+    code.push_str("static uint8_t lqmk_usage[LQMK_USAGE_LAYERS][MATRIX_ROWS][MATRIX_COLS];\n");
+    code.push_str("static uint32_t lqmk_usage_timer = 0;\n");
+    code.push('\n');
+    code.push_str("static void lqmk_usage_flush(void) {\n");
+    code.push_str("    for (uint8_t layer = 0; layer < LQMK_USAGE_LAYERS; layer++) {\n");
+    code.push_str("        for (uint8_t row = 0; row < MATRIX_ROWS; row++) {\n");
+    code.push_str("            for (uint8_t col = 0; col < MATRIX_COLS; col++) {\n");
+    code.push_str("                if (lqmk_usage[layer][row][col]) {\n");
+    code.push_str(
+        "                    uprintf(\"lqmk:usage:%u:%u:%u:%u\\n\", layer, row, col, lqmk_usage[layer][row][col]);\n",
+    );
+    code.push_str("                    lqmk_usage[layer][row][col] = 0;\n");
+    code.push_str("                }\n");
+    code.push_str("            }\n");
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push_str("    lqmk_usage_timer = timer_read32();\n");
+    code.push_str("}\n");
+    code.push('\n');
+    code.push_str("static void lqmk_usage_count(keyrecord_t *record) {\n");
+    code.push_str("    uint8_t row = record->event.key.row;\n");
+    code.push_str("    uint8_t col = record->event.key.col;\n");
+    code.push_str("    if (row >= MATRIX_ROWS || col >= MATRIX_COLS) {\n");
+    code.push_str("        return;\n");
+    code.push_str("    }\n");
+    code.push_str("    uint8_t layer = layer_switch_get_layer(record->event.key);\n");
+    code.push_str("    if (layer >= LQMK_USAGE_LAYERS) {\n");
+    code.push_str("        return;\n");
+    code.push_str("    }\n");
+    code.push_str("    if (++lqmk_usage[layer][row][col] == UINT8_MAX\n");
+    code.push_str("        || timer_elapsed32(lqmk_usage_timer) >= LQMK_USAGE_INTERVAL) {\n");
+    code.push_str("        lqmk_usage_flush();\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code.push_str("#endif\n");
+    code
+}
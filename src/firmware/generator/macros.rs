@@ -0,0 +1,196 @@
+//! Macro code generation for QMK keymap.c.
+//!
+//! Generates the `custom_keycodes` enum, a keycode post-processor that
+//! converts `MACRO(name)` references to `MACRO_NAME`, and the
+//! `process_record_user` hook that types each macro's steps. The enum and
+//! the hook also carry the layout's custom keycodes (see
+//! [`super::custom_keycodes`]) and editing helpers (see
+//! [`super::editing_helpers`]). When the idle effect or ripple overlay also
+//! need key events, they emit their hook as `lazyqmk_process_record_rgb`
+//! (see [`record_hook_name`]) and the macro hook calls it first.
+
+use crate::models::{MacroDefinition, MacroStep};
+
+use super::{custom_keycodes, editing_helpers, FirmwareGenerator};
+
+/// Name of the RGB record hook when macros own `process_record_user`.
+const RGB_RECORD_HOOK: &str = "lazyqmk_process_record_rgb";
+
+/// Returns true when macros, custom keycodes or editing helpers need
+/// `process_record_user`.
+fn owns_record_hook(gen: &FirmwareGenerator) -> bool {
+    !gen.layout.macros.is_empty()
+        || !gen.layout.custom_keycodes.is_empty()
+        || gen.layout.editing_helpers.any_enabled()
+}
+
+/// Returns the function name the idle/ripple code should use for its
+/// key event hook.
+///
+/// With macros, custom keycodes or editing helpers defined,
+/// `process_record_user` is generated here and the RGB hook is renamed so
+/// both can coexist.
+pub fn record_hook_name(gen: &FirmwareGenerator) -> &'static str {
+    if owns_record_hook(gen) {
+        RGB_RECORD_HOOK
+    } else {
+        "process_record_user"
+    }
+}
+
+/// Returns the line that marks the RGB record hook as present, or an empty
+/// string when it keeps the `process_record_user` name.
+pub fn record_hook_define(gen: &FirmwareGenerator) -> &'static str {
+    if owns_record_hook(gen) {
+        "#define LQMK_RGB_RECORD_HOOK\n"
+    } else {
+        ""
+    }
+}
+
+/// Returns macros sorted by name for stable enum ordering.
+fn sorted_macros<'a>(gen: &'a FirmwareGenerator) -> Vec<&'a MacroDefinition> {
+    let mut sorted: Vec<_> = gen.layout.macros.iter().collect();
+    sorted.sort_by_key(|m| &m.name);
+    sorted
+}
+
+/// Generates the custom keycode enum.
+///
+/// Creates `enum custom_keycodes { MACRO_A = SAFE_RANGE, MACRO_B, ... };`
+/// followed by the layout's custom keycodes and enabled editing helper
+/// keycodes. Macro and custom keycode names are sorted alphabetically for
+/// stable ordering.
+pub fn generate_enum(gen: &FirmwareGenerator) -> String {
+    if !owns_record_hook(gen) {
+        return String::new();
+    }
+
+    let names = sorted_macros(gen)
+        .into_iter()
+        .map(MacroDefinition::enum_name)
+        .chain(
+            custom_keycodes::sorted(gen)
+                .into_iter()
+                .map(|custom| custom.name.clone()),
+        )
+        .chain(
+            editing_helpers::keycodes(gen)
+                .into_iter()
+                .map(str::to_string),
+        );
+
+    let mut code = String::new();
+    code.push_str("enum custom_keycodes {\n");
+
+    for (idx, name) in names.enumerate() {
+        code.push_str(&format!("    {name}"));
+        if idx == 0 {
+            code.push_str(" = SAFE_RANGE");
+        }
+        code.push_str(",\n");
+    }
+
+    code.push_str("};\n");
+    code
+}
+
+/// Processes a keycode, converting `MACRO(name)` references to `MACRO_NAME`.
+///
+/// References to undefined macros are returned unchanged (caught by the
+/// layout validator).
+pub fn process_keycode(gen: &FirmwareGenerator, keycode: &str) -> String {
+    keycode
+        .strip_prefix("MACRO(")
+        .and_then(|rest| rest.strip_suffix(')'))
+        .and_then(|name| gen.layout.get_macro(name))
+        .map_or_else(|| keycode.to_string(), MacroDefinition::enum_name)
+}
+
+/// Generates the `process_record_user` hook that runs editing helpers and
+/// custom keycodes and plays macros.
+pub fn generate(gen: &FirmwareGenerator) -> String {
+    if !owns_record_hook(gen) {
+        return String::new();
+    }
+
+    let mut code = String::new();
+    if gen.layout.macros.is_empty() {
+        code.push_str("// Custom Keycodes\n");
+    } else {
+        code.push_str("// Macros\n");
+    }
+    code.push_str("bool process_record_user(uint16_t keycode, keyrecord_t *record) {\n");
+    code.push_str("#ifdef LQMK_RGB_RECORD_HOOK\n");
+    code.push_str(&format!(
+        "    if (!{RGB_RECORD_HOOK}(keycode, record)) {{\n"
+    ));
+    code.push_str("        return false;\n");
+    code.push_str("    }\n");
+    code.push_str("#endif\n");
+    code.push('\n');
+
+    let helper_calls = editing_helpers::generate_calls(gen);
+    if !helper_calls.is_empty() {
+        code.push_str(&helper_calls);
+        code.push('\n');
+    }
+    let custom_cases = custom_keycodes::generate_cases(gen);
+    if !custom_cases.is_empty() {
+        code.push_str(&custom_cases);
+        code.push('\n');
+    }
+    if gen.layout.macros.is_empty() {
+        code.push_str("    return true;\n");
+        code.push_str("}\n");
+        return code;
+    }
+
+    code.push_str("    if (!record->event.pressed) {\n");
+    code.push_str("        return true;\n");
+    code.push_str("    }\n");
+    code.push('\n');
+    code.push_str("    switch (keycode) {\n");
+
+    for macro_def in sorted_macros(gen) {
+        code.push_str(&format!("        case {}:\n", macro_def.enum_name()));
+        for step in &macro_def.steps {
+            match step {
+                MacroStep::Text(text) => {
+                    code.push_str(&format!(
+                        "            SEND_STRING(\"{}\");\n",
+                        escape_c_string(text)
+                    ));
+                }
+                MacroStep::Tap(keycode) => {
+                    code.push_str(&format!("            tap_code16({keycode});\n"));
+                }
+                MacroStep::Delay(ms) => {
+                    code.push_str(&format!("            wait_ms({ms});\n"));
+                }
+            }
+        }
+        code.push_str("            return false;\n");
+    }
+
+    code.push_str("    }\n");
+    code.push('\n');
+    code.push_str("    return true;\n");
+    code.push_str("}\n");
+    code
+}
+
+/// Escapes text for use inside a C string literal.
+fn escape_c_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -8,10 +8,20 @@
 //! - `encoder`        — conditional encoder_map
 //! - `idle`           — idle effect state machine
 //! - `ripple`         — RGB overlay ripple (key-action effect)
+//! - `rgb_conditions` — conditional RGB behaviors (dim, WPM/layer effects, off hours)
 //! - `combo`          — two-key combo code
 //! - `tap_dance`      — tap dance enum, helpers, actions
+//! - `key_override`   — `key_overrides[]` for modifier + key overrides
+//! - `repeat_key`     — `get_alt_repeat_key_keycode_user()` for alt repeat overrides
+//! - `tapping_term`   — `get_tapping_term()` for per-key tapping terms
+//! - `macros`         — macro keycode enum and `process_record_user` playback
+//! - `custom_keycodes` — `process_record_user` cases for custom keycode snippets
+//! - `editing_helpers` — Select Word, Sentence Case and Num Word helper code
+//! - `unicode`        — `unicode_map[]` and `UC()`/`UM()` keycode conversion
+//! - `live_preview`   — console reports of the active layer, key presses and usage counts
 //! - `config_h`       — merged config.h emission
 //! - `rules_mk`       — rules.mk + keymap.json
+//! - `wrappers`       — wrappers.h with userspace layer/row macros
 //! - `tests`          — 47 inline tests for the generator (sub: `bootloader_combo`)
 
 // Allow format! appended to String - more readable than write! in code generation
@@ -19,12 +29,25 @@
 
 mod combo;
 mod config_h;
+mod custom_keycodes;
+mod editing_helpers;
 mod encoder;
 mod idle;
+mod key_override;
 mod keymap_helpers;
+mod live_preview;
+mod macros;
+mod repeat_key;
+mod rgb_conditions;
 mod ripple;
 mod rules_mk;
 mod tap_dance;
+mod tapping_term;
+mod unicode;
+mod wrappers;
+
+#[allow(unused_imports)] // bin/lib split: used by the web render metadata
+pub use encoder::default_encoder_binding;
 
 #[cfg(test)]
 mod tests;
@@ -35,10 +58,24 @@ use crate::keycode_db::KeycodeDb;
 use crate::models::keyboard_geometry::KeyboardGeometry;
 use crate::models::layout::Layout;
 use crate::models::visual_layout_mapping::VisualLayoutMapping;
+use crate::services::keyboard_roots;
 use anyhow::{Context, Result};
 use std::fs;
 
 /// Firmware generator for keymap.c and config.h.
+///
+/// # Output stability
+///
+/// Generated files depend only on the layout, geometry, and keycode database,
+/// so generating the same layout twice produces byte-identical output and
+/// diffs of committed firmware files only change when the layout does:
+///
+/// - No timestamps or other per-run values are emitted.
+/// - Keys are emitted in LAYOUT macro order, independent of the order they
+///   are stored in a layer.
+/// - Tap dances and macros are numbered by name (`TD_<NAME>`, `MACRO_<NAME>`),
+///   combos by their order in the layout, and layers by their index.
+/// - Language headers are included in sorted order.
 pub struct FirmwareGenerator<'a> {
     pub(crate) layout: &'a Layout,
     pub(crate) geometry: &'a KeyboardGeometry,
@@ -66,7 +103,7 @@ impl<'a> FirmwareGenerator<'a> {
         }
     }
 
-    /// Generates keymap.c, config.h, and rules.mk files.
+    /// Generates keymap.c, config.h, rules.mk, and wrappers.h files.
     ///
     /// Files are written to both:
     /// 1. Timestamped output directory (for archival)
@@ -98,6 +135,12 @@ impl<'a> FirmwareGenerator<'a> {
             self.write_file_to_both(&timestamp_dir, "keymap.json", &keymap_json)?;
         }
 
+        // Generate wrappers.h (for userspaces built on row wrapper macros)
+        let wrappers_h = self.generate_wrappers_h()?;
+        self.write_file_to_both(&timestamp_dir, "wrappers.h", &wrappers_h)?;
+
+        self.create_userspace()?;
+
         Ok((keymap_path, config_h_path))
     }
 
@@ -143,11 +186,17 @@ impl<'a> FirmwareGenerator<'a> {
             code.push_str("#include \"process_keycode/process_tap_dance.h\"\n");
         }
 
+        // Off hours receive the host clock over Raw HID
+        if self.layout.rgb_conditions.off_hours.is_some() && self.geometry.has_rgb_matrix() {
+            code.push_str("#include \"raw_hid.h\"\n");
+        }
+
         // lib8tion provides fast integer math (scale8, sin8, cos8, sqrt16, etc.)
         // Needed by reactive key-action overlay and PaletteFX community module.
         let needs_lib8tion = self.layout.rgb_overlay_ripple.enabled
             && self.geometry.has_rgb_matrix()
-            || self.layout.palette_fx.enabled;
+            || self.layout.palette_fx.enabled
+            || self.layout.rgb_conditions.dim_after_minutes > 0 && self.geometry.has_rgb_matrix();
         if needs_lib8tion {
             code.push_str("#include <lib/lib8tion/lib8tion.h>\n");
         }
@@ -167,6 +216,21 @@ impl<'a> FirmwareGenerator<'a> {
             code.push('\n');
         }
 
+        // Add keycodes for macros and custom keycodes if any are defined
+        let custom_keycode_enum = self.generate_macro_enum();
+        if !custom_keycode_enum.is_empty() {
+            code.push_str("// Custom Keycodes\n");
+            code.push_str(&custom_keycode_enum);
+            code.push('\n');
+        }
+
+        // Add the Unicode map before the keymap that references it
+        let unicode_map = self.generate_unicode_map();
+        if !unicode_map.is_empty() {
+            code.push_str(&unicode_map);
+            code.push('\n');
+        }
+
         // Keymap definition
         code.push_str("const uint16_t PROGMEM keymaps[][MATRIX_ROWS][MATRIX_COLS] = {\n");
 
@@ -233,10 +297,66 @@ impl<'a> FirmwareGenerator<'a> {
         code.push('\n');
         code.push_str(&self.generate_ripple_overlay_code()?);
 
+        // Add conditional RGB behaviors if any rule is configured
+        let rgb_conditions = self.generate_rgb_conditions_code()?;
+        if !rgb_conditions.is_empty() {
+            code.push('\n');
+            code.push_str(&rgb_conditions);
+        }
+
         // Add combo code if enabled
         code.push('\n');
         code.push_str(&self.generate_combo_code()?);
 
+        // Add key overrides if any are defined
+        let key_overrides = self.generate_key_override_code();
+        if !key_overrides.is_empty() {
+            code.push('\n');
+            code.push_str(&key_overrides);
+        }
+
+        // Add alt repeat overrides if any are defined
+        let alt_repeat = self.generate_alt_repeat_code();
+        if !alt_repeat.is_empty() {
+            code.push('\n');
+            code.push_str(&alt_repeat);
+        }
+
+        // Add per-key tapping terms if any are set
+        let tapping_terms = self.generate_tapping_term_code();
+        if !tapping_terms.is_empty() {
+            code.push('\n');
+            code.push_str(&tapping_terms);
+        }
+
+        // Add editing helpers before the record hook that calls them
+        let editing_helpers = self.generate_editing_helpers_code();
+        if !editing_helpers.is_empty() {
+            code.push('\n');
+            code.push_str(&editing_helpers);
+        }
+
+        // Add macro and custom keycode handling last so it follows any
+        // renamed RGB record hook
+        let macro_code = self.generate_macro_code();
+        if !macro_code.is_empty() {
+            code.push('\n');
+            code.push_str(&macro_code);
+        }
+
+        // Add live preview and usage reports when enabled
+        let live_preview = self.generate_live_preview_code();
+        if !live_preview.is_empty() {
+            code.push('\n');
+            code.push_str(&live_preview);
+        }
+
+        // Hand-written code kept across regeneration
+        if !code.ends_with("\n\n") {
+            code.push('\n');
+        }
+        code.push_str(&crate::firmware::user_code::empty_region());
+
         Ok(code)
     }
 
@@ -291,11 +411,32 @@ impl<'a> FirmwareGenerator<'a> {
         ripple::generate(self)
     }
 
+    /// Generates conditional RGB behavior code if any rule is configured.
+    pub fn generate_rgb_conditions_code(&self) -> Result<String> {
+        rgb_conditions::generate(self)
+    }
+
     /// Generates combo code if enabled.
     pub fn generate_combo_code(&self) -> Result<String> {
         combo::generate(self)
     }
 
+    /// Generates the `key_overrides[]` array if any overrides are defined.
+    pub fn generate_key_override_code(&self) -> String {
+        key_override::generate(self)
+    }
+
+    /// Generates `get_alt_repeat_key_keycode_user()` if any alt repeat
+    /// overrides are defined.
+    pub fn generate_alt_repeat_code(&self) -> String {
+        repeat_key::generate(self)
+    }
+
+    /// Generates `get_tapping_term()` if any per-key tapping terms are set.
+    pub fn generate_tapping_term_code(&self) -> String {
+        tapping_term::generate(self)
+    }
+
     /// Generates tap dance enum definition.
     pub fn generate_tap_dance_enum(&self) -> String {
         tap_dance::generate_enum(self)
@@ -311,6 +452,33 @@ impl<'a> FirmwareGenerator<'a> {
         tap_dance::generate_actions(self)
     }
 
+    /// Generates the `custom_keycodes` enum for macros and custom keycodes.
+    pub fn generate_macro_enum(&self) -> String {
+        macros::generate_enum(self)
+    }
+
+    /// Generates the `unicode_map[]` array if the keymap uses `UM()` keys.
+    pub fn generate_unicode_map(&self) -> String {
+        unicode::generate_map(self)
+    }
+
+    /// Generates the Select Word, Sentence Case and Num Word helpers if any
+    /// are enabled.
+    pub fn generate_editing_helpers_code(&self) -> String {
+        editing_helpers::generate(self)
+    }
+
+    /// Generates the `process_record_user` hook that runs custom keycodes
+    /// and plays macros.
+    pub fn generate_macro_code(&self) -> String {
+        macros::generate(self)
+    }
+
+    /// Generates the console hooks that report layers, key presses and usage.
+    pub fn generate_live_preview_code(&self) -> String {
+        live_preview::generate(self)
+    }
+
     /// Generates rules.mk for the keymap.
     #[must_use]
     pub fn generate_rules_mk(&self) -> String {
@@ -322,6 +490,37 @@ impl<'a> FirmwareGenerator<'a> {
         rules_mk::generate_keymap_json(self)
     }
 
+    /// Creates `users/<name>/rules.mk` in the QMK tree for the configured
+    /// userspace, unless it exists.
+    ///
+    /// Returns the path of the created file, or `None` without a userspace
+    /// or when the file was already there.
+    pub fn create_userspace(&self) -> Result<Option<std::path::PathBuf>> {
+        let Some(name) = &self.config.build.userspace else {
+            return Ok(None);
+        };
+        let qmk_path = self
+            .config
+            .qmk_tree_path(self.layout.metadata.qmk_tree.as_deref())
+            .context("QMK firmware path not configured")?;
+
+        let userspace_dir = qmk_path.join("users").join(name);
+        let rules_mk_path = userspace_dir.join("rules.mk");
+        if rules_mk_path.exists() {
+            return Ok(None);
+        }
+        fs::create_dir_all(&userspace_dir)
+            .with_context(|| format!("Failed to create directory {}", userspace_dir.display()))?;
+        fs::write(&rules_mk_path, rules_mk::generate_userspace_rules_mk(name))
+            .with_context(|| format!("Failed to write {}", rules_mk_path.display()))?;
+        Ok(Some(rules_mk_path))
+    }
+
+    /// Generates wrappers.h with the layer order and row wrapper macros.
+    pub fn generate_wrappers_h(&self) -> Result<String> {
+        wrappers::generate(self)
+    }
+
     /// Generates config.h for the keymap.
     pub fn generate_merged_config_h(&self) -> Result<String> {
         config_h::generate(self)
@@ -333,9 +532,7 @@ impl<'a> FirmwareGenerator<'a> {
     fn get_keymap_directory(&self) -> Result<std::path::PathBuf> {
         let qmk_path = self
             .config
-            .paths
-            .qmk_firmware
-            .as_ref()
+            .qmk_tree_path(self.layout.metadata.qmk_tree.as_deref())
             .context("QMK firmware path not configured")?;
 
         // Use the keyboard path from layout metadata (which may include a variant)
@@ -353,6 +550,9 @@ impl<'a> FirmwareGenerator<'a> {
             .as_deref()
             .context("Keymap name not set in layout metadata")?;
 
+        // Keyboards from an extra keyboard root must be in the QMK tree to compile
+        keyboard_roots::install_keyboard(qmk_path, &self.config.paths.keyboard_roots, keyboard)?;
+
         let keymap_dir = qmk_path
             .join("keyboards")
             .join(keyboard)
@@ -401,15 +601,18 @@ impl<'a> FirmwareGenerator<'a> {
         filename: &str,
         content: &str,
     ) -> Result<String> {
+        // Keep the user region of the file being replaced
+        let keymap_dir = self.get_keymap_directory()?;
+        let qmk_path = keymap_dir.join(filename);
+        let content = crate::firmware::user_code::preserve_from_file(content, &qmk_path)?;
+
         // Write to timestamped archive directory
         let archive_path = timestamp_dir.join(filename);
-        fs::write(&archive_path, content)
+        fs::write(&archive_path, &content)
             .with_context(|| format!("Failed to write {}", archive_path.display()))?;
 
         // Write to QMK keymap directory
-        let keymap_dir = self.get_keymap_directory()?;
-        let qmk_path = keymap_dir.join(filename);
-        fs::write(&qmk_path, content)
+        fs::write(&qmk_path, &content)
             .with_context(|| format!("Failed to write {}", qmk_path.display()))?;
 
         Ok(archive_path.display().to_string())
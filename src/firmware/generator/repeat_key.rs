@@ -0,0 +1,47 @@
+//! Repeat Key code generation.
+//!
+//! Keys using `QK_REP`/`QK_AREP` need `REPEAT_KEY_ENABLE`. Alt repeat
+//! overrides become a `get_alt_repeat_key_keycode_user` callback; returning
+//! `KC_TRNS` keeps QMK's built-in alternates for every other key.
+
+use crate::models::layout::repeat_key::is_repeat_keycode;
+
+use super::combo::process_keycode;
+use super::FirmwareGenerator;
+
+/// Returns true if a key uses Repeat Key or alt repeat overrides are set.
+pub fn enabled(gen: &FirmwareGenerator) -> bool {
+    !gen.layout.alt_repeat_keys.is_empty()
+        || gen
+            .layout
+            .layers
+            .iter()
+            .flat_map(|layer| &layer.keys)
+            .any(|key| is_repeat_keycode(&key.keycode))
+}
+
+/// Generates `get_alt_repeat_key_keycode_user`.
+///
+/// Returns an empty string if the layout has no alt repeat overrides.
+pub fn generate(gen: &FirmwareGenerator) -> String {
+    let overrides = &gen.layout.alt_repeat_keys;
+    if overrides.is_empty() {
+        return String::new();
+    }
+
+    let mut code = String::new();
+    code.push_str("// Alt Repeat Key\n");
+    code.push_str("uint16_t get_alt_repeat_key_keycode_user(uint16_t keycode, uint8_t mods) {\n");
+    code.push_str("    switch (keycode) {\n");
+    for alt_repeat in overrides {
+        code.push_str(&format!(
+            "        case {}: return {};\n",
+            process_keycode(gen, &alt_repeat.keycode),
+            process_keycode(gen, &alt_repeat.alt_keycode)
+        ));
+    }
+    code.push_str("    }\n");
+    code.push_str("    return KC_TRNS;\n");
+    code.push_str("}\n");
+    code
+}
@@ -0,0 +1,210 @@
+//! Conditional RGB behavior code generation.
+//!
+//! Emits a `housekeeping_task_user` that evaluates the layout's conditional
+//! RGB rules every tick: off hours (host-synced clock over Raw HID), the WPM
+//! effect, per-layer effects and dimming after inactivity. Uses only hooks
+//! not claimed by the idle effect or ripple overlay, and yields to the idle
+//! effect state machine while it is running.
+
+use anyhow::Result;
+
+use super::FirmwareGenerator;
+
+/// Generates conditional RGB behavior code if any rule is configured.
+#[allow(clippy::unnecessary_wraps)]
+pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
+    let rules = &gen.layout.rgb_conditions;
+    if !rules.is_active() || !gen.geometry.has_rgb_matrix() {
+        return Ok(String::new());
+    }
+
+    let has_off_hours = rules.off_hours.is_some();
+    let has_wpm = rules.wpm_threshold > 0;
+    let has_layer_effects = !rules.layer_effects.is_empty();
+    let has_mode_rules = has_wpm || has_layer_effects;
+    let has_dim = rules.dim_after_minutes > 0;
+
+    let mut code = String::new();
+
+    code.push_str("#ifdef RGB_MATRIX_ENABLE\n");
+    code.push_str("#ifdef LQMK_RGB_CONDITIONS_ENABLED\n");
+    code.push('\n');
+    code.push_str("// Conditional RGB Behaviors\n");
+    if has_mode_rules {
+        code.push_str("static bool lqmk_cond_overriding = false;\n");
+        code.push_str("static uint8_t lqmk_cond_base_mode = 0;\n");
+    }
+    if has_dim {
+        code.push_str("static bool lqmk_cond_dimmed = false;\n");
+        code.push_str("static uint8_t lqmk_cond_saved_val = 0;\n");
+    }
+    if has_off_hours {
+        code.push_str("static bool lqmk_cond_off = false;\n");
+    }
+    code.push('\n');
+
+    if has_off_hours {
+        push_clock(&mut code);
+    }
+
+    if has_layer_effects {
+        // Per-layer effect table as a switch (0 = RGB_MATRIX_NONE = no override)
+        code.push_str("static uint8_t lqmk_cond_layer_mode(uint8_t layer) {\n");
+        code.push_str("    switch (layer) {\n");
+        for rule in &rules.layer_effects {
+            code.push_str(&format!(
+                "        case {}: return {};\n",
+                rule.layer,
+                rule.effect.qmk_mode_name()
+            ));
+        }
+        code.push_str("        default: return 0;\n");
+        code.push_str("    }\n");
+        code.push_str("}\n");
+        code.push('\n');
+    }
+
+    if has_mode_rules {
+        code.push_str("static uint8_t lqmk_cond_desired_mode(void) {\n");
+        if has_wpm {
+            code.push_str("    if (get_current_wpm() >= LQMK_RGB_WPM_THRESHOLD) {\n");
+            code.push_str("        return LQMK_RGB_WPM_MODE;\n");
+            code.push_str("    }\n");
+        }
+        if has_layer_effects {
+            code.push_str(
+                "    return lqmk_cond_layer_mode(get_highest_layer(layer_state | default_layer_state));\n",
+            );
+        } else {
+            code.push_str("    return 0;\n");
+        }
+        code.push_str("}\n");
+        code.push('\n');
+    }
+
+    code.push_str("void housekeeping_task_user(void) {\n");
+
+    if has_off_hours {
+        code.push_str("    // Off hours take precedence over every other rule\n");
+        code.push_str("    if (lqmk_cond_in_off_hours()) {\n");
+        code.push_str("        if (rgb_matrix_is_enabled()) {\n");
+        code.push_str("            rgb_matrix_disable_noeeprom();\n");
+        code.push_str("        }\n");
+        code.push_str("        lqmk_cond_off = true;\n");
+        code.push_str("        return;\n");
+        code.push_str("    }\n");
+        code.push_str("    if (lqmk_cond_off) {\n");
+        code.push_str("        lqmk_cond_off = false;\n");
+        code.push_str("#ifdef LQMK_IDLE_TIMEOUT_MS\n");
+        code.push_str("        // Stay dark if the idle effect switched RGB off meanwhile\n");
+        code.push_str("        if (idle_state != IDLE_STATE_OFF) rgb_matrix_enable_noeeprom();\n");
+        code.push_str("#else\n");
+        code.push_str("        rgb_matrix_enable_noeeprom();\n");
+        code.push_str("#endif\n");
+        code.push_str("    }\n");
+        code.push('\n');
+    }
+
+    code.push_str("#ifdef LQMK_IDLE_TIMEOUT_MS\n");
+    code.push_str("    // The idle effect owns the LEDs while it is running\n");
+    code.push_str("    if (idle_state != IDLE_STATE_ACTIVE) return;\n");
+    code.push_str("#endif\n");
+
+    if has_mode_rules {
+        code.push('\n');
+        code.push_str("    uint8_t want = lqmk_cond_desired_mode();\n");
+        code.push_str("    if (want != 0) {\n");
+        code.push_str("        if (!lqmk_cond_overriding) {\n");
+        code.push_str("            // Remember the effect to restore once no rule applies\n");
+        code.push_str("            lqmk_cond_base_mode = rgb_matrix_get_mode();\n");
+        code.push_str("            lqmk_cond_overriding = true;\n");
+        code.push_str("        }\n");
+        code.push_str("        if (rgb_matrix_get_mode() != want) {\n");
+        code.push_str("            rgb_matrix_mode_noeeprom(want);\n");
+        code.push_str("        }\n");
+        code.push_str("    } else if (lqmk_cond_overriding) {\n");
+        code.push_str("        rgb_matrix_mode_noeeprom(lqmk_cond_base_mode);\n");
+        code.push_str("        lqmk_cond_overriding = false;\n");
+        code.push_str("    }\n");
+    }
+
+    if has_dim {
+        code.push('\n');
+        code.push_str(
+            "    bool want_dim = last_input_activity_elapsed() >= LQMK_RGB_DIM_AFTER_MS;\n",
+        );
+        code.push_str("    if (want_dim && !lqmk_cond_dimmed) {\n");
+        code.push_str("        lqmk_cond_saved_val = rgb_matrix_get_val();\n");
+        code.push_str(
+            "        rgb_matrix_sethsv_noeeprom(rgb_matrix_get_hue(), rgb_matrix_get_sat(),\n",
+        );
+        code.push_str(
+            "                                   scale8(lqmk_cond_saved_val, LQMK_RGB_DIM_LEVEL));\n",
+        );
+        code.push_str("        lqmk_cond_dimmed = true;\n");
+        code.push_str("    } else if (!want_dim && lqmk_cond_dimmed) {\n");
+        code.push_str(
+            "        rgb_matrix_sethsv_noeeprom(rgb_matrix_get_hue(), rgb_matrix_get_sat(),\n",
+        );
+        code.push_str("                                   lqmk_cond_saved_val);\n");
+        code.push_str("        lqmk_cond_dimmed = false;\n");
+        code.push_str("    }\n");
+    }
+
+    code.push_str("}\n");
+    code.push('\n');
+
+    code.push_str("#endif // LQMK_RGB_CONDITIONS_ENABLED\n");
+    code.push_str("#endif // RGB_MATRIX_ENABLE\n");
+
+    Ok(code)
+}
+
+/// Emits the host-synced clock and its Raw HID receiver.
+///
+/// Keyboards have no real-time clock. The host sends the local time as a
+/// Raw HID report `[LQMK_RAW_HID_CLOCK_ID, 0x01, hour, minute]`; until then
+/// off hours stay inactive. The elapsed time is folded into the base minute
+/// every hour so the 32-bit timer never wraps between syncs.
+fn push_clock(code: &mut String) {
+    code.push_str("// Host-synced clock (no RTC): off hours are inactive until the time is set\n");
+    code.push_str("static bool lqmk_clock_valid = false;\n");
+    code.push_str("static uint16_t lqmk_clock_base_minute = 0;\n");
+    code.push_str("static uint32_t lqmk_clock_base_timer = 0;\n");
+    code.push('\n');
+    code.push_str("static uint16_t lqmk_clock_minute_of_day(void) {\n");
+    code.push_str("    uint32_t elapsed_min = timer_elapsed32(lqmk_clock_base_timer) / 60000;\n");
+    code.push_str("    if (elapsed_min >= 60) {\n");
+    code.push_str(
+        "        lqmk_clock_base_minute = (uint16_t)((lqmk_clock_base_minute + elapsed_min) % 1440);\n",
+    );
+    code.push_str("        lqmk_clock_base_timer += elapsed_min * 60000;\n");
+    code.push_str("        elapsed_min = 0;\n");
+    code.push_str("    }\n");
+    code.push_str("    return (uint16_t)((lqmk_clock_base_minute + elapsed_min) % 1440);\n");
+    code.push_str("}\n");
+    code.push('\n');
+    code.push_str("static bool lqmk_cond_in_off_hours(void) {\n");
+    code.push_str("    if (!lqmk_clock_valid) return false;\n");
+    code.push_str("    uint16_t now = lqmk_clock_minute_of_day();\n");
+    code.push_str("#if LQMK_RGB_OFF_START_MIN <= LQMK_RGB_OFF_END_MIN\n");
+    code.push_str("    return now >= LQMK_RGB_OFF_START_MIN && now < LQMK_RGB_OFF_END_MIN;\n");
+    code.push_str("#else\n");
+    code.push_str("    return now >= LQMK_RGB_OFF_START_MIN || now < LQMK_RGB_OFF_END_MIN;\n");
+    code.push_str("#endif\n");
+    code.push_str("}\n");
+    code.push('\n');
+    code.push_str("void raw_hid_receive(uint8_t *data, uint8_t length) {\n");
+    code.push_str("    if (length >= 4 && data[0] == LQMK_RAW_HID_CLOCK_ID && data[1] == 0x01\n");
+    code.push_str("        && data[2] < 24 && data[3] < 60) {\n");
+    code.push_str("        lqmk_clock_base_minute = (uint16_t)data[2] * 60 + data[3];\n");
+    code.push_str("        lqmk_clock_base_timer = timer_read32();\n");
+    code.push_str("        lqmk_clock_valid = true;\n");
+    code.push_str("        data[1] = 0x00; // acknowledged\n");
+    code.push_str("    } else {\n");
+    code.push_str("        data[1] = 0xFF; // unknown command\n");
+    code.push_str("    }\n");
+    code.push_str("    raw_hid_send(data, length);\n");
+    code.push_str("}\n");
+    code.push('\n');
+}
@@ -8,7 +8,7 @@
 
 use anyhow::Result;
 
-use super::FirmwareGenerator;
+use super::{macros, FirmwareGenerator};
 
 /// Generates RGB overlay ripple code if enabled.
 ///
@@ -135,8 +135,8 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
     code.push('\n');
 
     // LED->matrix position lookup for per-key layer resolution
-    let led_to_matrix = &gen.mapping.led_to_matrix;
-    let led_count = gen.mapping.key_count();
+    let led_to_matrix = led_matrix_positions(gen);
+    let led_count = led_to_matrix.len();
     let mut led_row_vals = String::new();
     let mut led_col_vals = String::new();
     for (i, &(row, col)) in led_to_matrix.iter().enumerate() {
@@ -274,13 +274,13 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
             ));
         }
         crate::models::layout::RippleColorMode::KeyBased => {
-            code.push_str(
-                "            RGB c = lazyqmk_ripple_base_color(ripples[i].led_index);\n",
-            );
+            code.push_str("            RGB c = lazyqmk_ripple_base_color(ripples[i].led_index);\n");
         }
         crate::models::layout::RippleColorMode::HueShift => {
             let hue_shift_steps = (i32::from(settings.hue_shift_deg) * 256) / 360;
-            code.push_str("            RGB __base = lazyqmk_ripple_base_color(ripples[i].led_index);\n");
+            code.push_str(
+                "            RGB __base = lazyqmk_ripple_base_color(ripples[i].led_index);\n",
+            );
             code.push_str("            hsv_t __hsv = rgb_to_hsv(__base);\n");
             code.push_str(&format!(
                 "            int16_t shifted_hue = (int16_t)__hsv.h + {hue_shift_steps};\n"
@@ -465,7 +465,12 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
 
     if !has_idle_effect {
         // No idle effect, generate standalone process_record_user for ripple
-        code.push_str("bool process_record_user(uint16_t keycode, keyrecord_t *record) {\n");
+        // (renamed when macros own process_record_user)
+        code.push_str(macros::record_hook_define(gen));
+        code.push_str(&format!(
+            "bool {}(uint16_t keycode, keyrecord_t *record) {{\n",
+            macros::record_hook_name(gen)
+        ));
         code.push_str("    lazyqmk_ripple_trigger(keycode, record);\n");
         code.push_str("    return true;\n");
         code.push_str("}\n");
@@ -478,3 +483,19 @@ pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
 
     Ok(code)
 }
+
+/// Returns the matrix position each physical LED stands for, indexed by LED.
+///
+/// Key LEDs use their key's position. LEDs without a key (underglow) borrow
+/// the position of the physically nearest key LED, so ripples reach them and
+/// they follow that key's layer.
+fn led_matrix_positions(gen: &FirmwareGenerator) -> Vec<(u8, u8)> {
+    let mut positions = gen.mapping.led_to_matrix.clone();
+    positions.resize(gen.geometry.led_count(), (0, 0));
+    for led in gen.geometry.non_key_leds() {
+        if let Some(position) = gen.geometry.nearest_key_position(led) {
+            positions[usize::from(led.led_index)] = position;
+        }
+    }
+    positions
+}
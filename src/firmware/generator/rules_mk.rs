@@ -1,10 +1,14 @@
 //! `rules.mk` and `keymap.json` generation.
 //!
-//! `rules.mk` enables QMK feature flags (`COMBO_ENABLE`, `TAP_DANCE_ENABLE`)
-//! for the keymap build. `keymap.json` declares QMK community module
+//! `rules.mk` enables QMK feature flags (`COMBO_ENABLE`, `TAP_DANCE_ENABLE`,
+//! `KEY_OVERRIDE_ENABLE`, `REPEAT_KEY_ENABLE`, `UNICODE_ENABLE`, `WPM_ENABLE`, ...) for the keymap build and applies the layout's
+//! firmware feature overrides (`RGB_MATRIX_ENABLE = no`, ...). With a
+//! userspace configured it also sets `USER_NAME`, and the userspace gets a
+//! shared `rules.mk` of its own. `keymap.json` declares QMK community module
 //! references (e.g. PaletteFX) when those features are enabled.
 
 use crate::constants::APP_BINARY_NAME;
+use crate::models::FirmwareFeature;
 
 use super::FirmwareGenerator;
 
@@ -17,23 +21,62 @@ use super::FirmwareGenerator;
 /// Returns empty string if no features need enabling.
 #[must_use]
 pub fn generate_rules_mk(gen: &FirmwareGenerator) -> String {
-    let mut features: Vec<&str> = Vec::new();
-
-    // Check for combos: enabled AND at least one non-placeholder combo
-    let real_combo_count = gen
-        .layout
-        .combo_settings
-        .combos
-        .iter()
-        .filter(|c| !c.placeholder)
-        .count();
+    let mut features: Vec<String> = Vec::new();
+
+    // Check for combos: enabled AND at least one non-placeholder or keycode combo
+    let real_combo_count = super::combo::combo_count(gen);
     if gen.layout.combo_settings.enabled && real_combo_count > 0 {
-        features.push("COMBO_ENABLE = yes");
+        features.push("COMBO_ENABLE = yes".to_string());
     }
 
     // Check for tap dances
     if !gen.layout.tap_dances.is_empty() {
-        features.push("TAP_DANCE_ENABLE = yes");
+        features.push("TAP_DANCE_ENABLE = yes".to_string());
+    }
+
+    // Check for key overrides
+    if !gen.layout.key_overrides.is_empty() {
+        features.push("KEY_OVERRIDE_ENABLE = yes".to_string());
+    }
+
+    // Check for Repeat Key keys or alt repeat overrides
+    if super::repeat_key::enabled(gen) {
+        features.push("REPEAT_KEY_ENABLE = yes".to_string());
+    }
+
+    // Check for Unicode keys (UC() alone, or the Unicode map for UM())
+    if let Some(unicode) = super::unicode::feature(gen) {
+        features.push(unicode.rules_mk_flag().to_string());
+    }
+
+    // Conditional RGB: the WPM effect needs WPM tracking, off hours need Raw HID
+    let rules = &gen.layout.rgb_conditions;
+    if gen.geometry.has_rgb_matrix() {
+        if rules.wpm_threshold > 0 {
+            features.push("WPM_ENABLE = yes".to_string());
+        }
+        if rules.off_hours.is_some() {
+            features.push("RAW_ENABLE = yes".to_string());
+        }
+    }
+
+    // Explicit feature overrides; features left at the keyboard default are omitted
+    for feature in FirmwareFeature::ALL {
+        if let Some(enabled) = gen.layout.firmware_features.get(feature) {
+            let value = if enabled { "yes" } else { "no" };
+            features.push(format!("{} = {value}", feature.rules_mk_flag()));
+        }
+    }
+
+    // Usage counting reports on the console; enable it unless overridden
+    let console = FirmwareFeature::Console;
+    if gen.layout.usage_counting && gen.layout.firmware_features.get(console).is_none() {
+        features.push(format!("{} = yes", console.rules_mk_flag()));
+    }
+
+    // Pull in users/<name>/ whatever the keymap is called
+    if let Some(name) = &gen.config.build.userspace {
+        features.insert(0, format!("USER_NAME := {name}"));
     }
 
     if features.is_empty() {
@@ -51,6 +94,20 @@ pub fn generate_rules_mk(gen: &FirmwareGenerator) -> String {
     content
 }
 
+/// Generates the shared `rules.mk` of the userspace `name`.
+///
+/// Only a starting point: it is written when `users/<name>/rules.mk` does
+/// not exist yet and is the user's to edit afterwards.
+#[must_use]
+pub fn generate_userspace_rules_mk(name: &str) -> String {
+    format!(
+        "# Shared rules for every keymap with USER_NAME := {name}\n\
+         # Created by {APP_BINARY_NAME}, which never overwrites it\n\
+         #\n\
+         # SRC += {name}.c\n"
+    )
+}
+
 /// Generates keymap.json for QMK community module support.
 ///
 /// Currently only generates module references when `PaletteFX` is enabled.
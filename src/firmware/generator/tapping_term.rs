@@ -0,0 +1,40 @@
+//! Per-key tapping term code generation.
+//!
+//! Overrides become a `get_tapping_term()` that matches the pressed key's
+//! matrix position; `config.h` enables it with `TAPPING_TERM_PER_KEY`.
+
+use super::FirmwareGenerator;
+
+/// Generates `get_tapping_term()` for the layout's per-key tapping terms.
+///
+/// Returns an empty string if the layout has no overrides. Positions the
+/// keyboard has no matrix key for are skipped.
+pub fn generate(gen: &FirmwareGenerator) -> String {
+    let terms = &gen.layout.tap_hold_settings.per_key_tapping_terms;
+    if terms.is_empty() {
+        return String::new();
+    }
+
+    let mut code = String::new();
+    code.push_str("// Per-Key Tapping Terms\n");
+    code.push_str("uint16_t get_tapping_term(uint16_t keycode, keyrecord_t *record) {\n");
+    code.push_str("    keypos_t key = record->event.key;\n");
+    for entry in terms {
+        let position = entry.position;
+        let Some((row, col)) = gen.mapping.visual_to_matrix_pos(position.row, position.col) else {
+            continue;
+        };
+        code.push_str(&format!(
+            "    // Key ({}, {})\n",
+            position.row, position.col
+        ));
+        code.push_str(&format!(
+            "    if (key.row == {row} && key.col == {col}) {{\n"
+        ));
+        code.push_str(&format!("        return {};\n", entry.tapping_term));
+        code.push_str("    }\n");
+    }
+    code.push_str("    return TAPPING_TERM;\n");
+    code.push_str("}\n");
+    code
+}
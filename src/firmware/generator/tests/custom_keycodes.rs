@@ -0,0 +1,78 @@
+//! Tests for custom keycode generation.
+
+use super::*;
+use crate::models::{CustomKeycode, MacroDefinition};
+
+fn add_custom_keycodes(layout: &mut Layout) {
+    layout
+        .add_custom_keycode(CustomKeycode::new(
+            "CK_EMAIL",
+            "if (record->event.pressed) {\n    SEND_STRING(\"me@example.com\");\n}",
+            "Type email",
+        ))
+        .unwrap();
+    layout
+        .add_custom_keycode(CustomKeycode::new("CK_BOOT", "reset_keyboard();", ""))
+        .unwrap();
+    layout.layers[0].keys[1].keycode = "CK_EMAIL".to_string();
+}
+
+#[test]
+fn test_custom_keycodes_generate_enum_and_cases() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.idle_effect_settings.enabled = false;
+    add_custom_keycodes(&mut layout);
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(
+        keymap_c.contains("enum custom_keycodes {\n    CK_BOOT = SAFE_RANGE,\n    CK_EMAIL,\n};")
+    );
+    assert!(keymap_c.contains("KC_A, CK_EMAIL"));
+    assert!(keymap_c.contains(
+        "        case CK_EMAIL: {\n            // Type email\n            if (record->event.pressed) {\n                SEND_STRING(\"me@example.com\");\n            }\n            return false;\n        }\n"
+    ));
+    assert!(keymap_c
+        .contains("        case CK_BOOT: {\n            reset_keyboard();\n            return false;\n        }\n"));
+    // Without macros the hook passes other keys straight through
+    assert!(!keymap_c.contains("if (!record->event.pressed)"));
+    assert_eq!(keymap_c.matches("bool process_record_user").count(), 1);
+}
+
+#[test]
+fn test_custom_keycodes_follow_macros() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.idle_effect_settings.enabled = false;
+    add_custom_keycodes(&mut layout);
+    layout
+        .add_macro(MacroDefinition::from_sequence("sig", "Best").unwrap())
+        .unwrap();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains(
+        "enum custom_keycodes {\n    MACRO_SIG = SAFE_RANGE,\n    CK_BOOT,\n    CK_EMAIL,\n};"
+    ));
+    // Snippets run on release too, so they come before the press-only macros
+    let custom = keymap_c.find("case CK_EMAIL:").unwrap();
+    let pressed = keymap_c.find("if (!record->event.pressed)").unwrap();
+    let sig = keymap_c.find("case MACRO_SIG:").unwrap();
+    assert!(custom < pressed && pressed < sig);
+    assert_eq!(keymap_c.matches("bool process_record_user").count(), 1);
+}
+
+#[test]
+fn test_custom_keycodes_chain_rgb_record_hook() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.idle_effect_settings.enabled = true;
+    add_custom_keycodes(&mut layout);
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert_eq!(keymap_c.matches("bool process_record_user").count(), 1);
+    assert!(keymap_c.contains("#define LQMK_RGB_RECORD_HOOK\nbool lazyqmk_process_record_rgb("));
+    assert!(keymap_c.contains("    if (!lazyqmk_process_record_rgb(keycode, record)) {"));
+}
@@ -0,0 +1,74 @@
+//! Tests for editing helper generation.
+
+use super::*;
+use crate::models::{CustomKeycode, EditingHelper};
+
+#[test]
+fn test_no_editing_helpers_by_default() {
+    let (layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(!keymap_c.contains("// Editing Helpers"));
+    assert!(!keymap_c.contains("SELWORD"));
+}
+
+#[test]
+fn test_select_word_generates_keycode_and_hook() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.idle_effect_settings.enabled = false;
+    layout
+        .editing_helpers
+        .set_enabled(EditingHelper::SelectWord, true);
+    layout.layers[0].keys[1].keycode = "SELWORD".to_string();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains("enum custom_keycodes {\n    SELWORD = SAFE_RANGE,\n};"));
+    assert!(keymap_c.contains("KC_A, SELWORD"));
+    assert!(keymap_c.contains("#    define SELECT_WORD_MOD MOD_BIT(KC_LCTL)\n"));
+    assert!(keymap_c.contains(
+        "    // Editing helpers\n    if (!process_select_word(keycode, record)) {\n        return false;\n    }\n"
+    ));
+    // Only Sentence Case and Num Word need the tap-hold resolver
+    assert!(!keymap_c.contains("helper_tap_keycode"));
+    // Helpers are defined before the hook that calls them
+    assert!(
+        keymap_c.find("static bool process_select_word").unwrap()
+            < keymap_c.find("bool process_record_user").unwrap()
+    );
+}
+
+#[test]
+fn test_all_editing_helpers_follow_custom_keycodes() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.idle_effect_settings.enabled = false;
+    for helper in EditingHelper::ALL {
+        layout.editing_helpers.set_enabled(helper, true);
+    }
+    layout.editing_helpers.num_word_layer = 2;
+    layout
+        .add_custom_keycode(CustomKeycode::new("CK_BOOT", "reset_keyboard();", ""))
+        .unwrap();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains(
+        "enum custom_keycodes {\n    CK_BOOT = SAFE_RANGE,\n    SELWORD,\n    SENTCASE,\n    NUMWORD,\n};"
+    ));
+    assert!(keymap_c.contains("static uint16_t helper_tap_keycode("));
+    assert!(keymap_c.contains("static bool process_sentence_case("));
+    assert!(keymap_c.contains("#define NUM_WORD_LAYER 2\n"));
+    assert!(keymap_c.contains("    layer_on(NUM_WORD_LAYER);\n"));
+    // Helpers run before custom keycode snippets
+    assert!(
+        keymap_c
+            .find("if (!process_num_word(keycode, record))")
+            .unwrap()
+            < keymap_c.find("case CK_BOOT:").unwrap()
+    );
+    assert_eq!(keymap_c.matches("bool process_record_user").count(), 1);
+}
@@ -0,0 +1,49 @@
+//! Tests for per-layer encoder map generation.
+
+use super::*;
+use crate::models::EncoderBinding;
+
+#[test]
+fn test_encoder_map_uses_layer_bindings() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.layers[0]
+        .encoder_map
+        .set(1, EncoderBinding::new("KC_PGDN", "KC_PGUP"));
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains(
+        "    [0] = {\n        ENCODER_CCW_CW(RM_NEXT, RM_PREV),\n        ENCODER_CCW_CW(KC_PGDN, KC_PGUP),\n        ENCODER_CCW_CW(RM_VALU, RM_VALD),"
+    ));
+}
+
+#[test]
+fn test_encoder_map_resolves_layer_references() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    let layer = Layer::new(1, "Nav", RgbColor::new(0, 0, 255)).unwrap();
+    let layer_id = layer.id.clone();
+    layout.add_layer(layer).unwrap();
+    layout.layers[0].encoder_map.set(
+        0,
+        EncoderBinding::new(format!("TO(@{layer_id})"), "KC_TRNS"),
+    );
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains("ENCODER_CCW_CW(TO(1), KC_TRNS),"));
+}
+
+#[test]
+fn test_encoder_map_ignores_encoders_beyond_count() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.layers[0]
+        .encoder_map
+        .set(7, EncoderBinding::new("KC_LEFT", "KC_RGHT"));
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(!keymap_c.contains("KC_LEFT"));
+}
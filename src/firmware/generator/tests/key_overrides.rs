@@ -0,0 +1,48 @@
+//! Tests for key override generation.
+
+use super::*;
+use crate::models::{KeyOverride, OverrideModifier};
+
+#[test]
+fn test_no_key_overrides_by_default() {
+    let (layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    assert!(!generator
+        .generate_keymap_c()
+        .unwrap()
+        .contains("key_overrides"));
+    assert!(!generator
+        .generate_rules_mk()
+        .contains("KEY_OVERRIDE_ENABLE"));
+}
+
+#[test]
+fn test_key_overrides_array() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.key_overrides = vec![
+        KeyOverride::new(vec![OverrideModifier::Shift], "KC_BSPC", "KC_DEL"),
+        KeyOverride::new(
+            vec![OverrideModifier::Shift, OverrideModifier::Ctrl],
+            "KC_ESC",
+            "KC_GRV",
+        ),
+    ];
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains("// Shift+KC_BSPC = KC_DEL\n"));
+    assert!(keymap_c.contains(
+        "const key_override_t key_override_0 = ko_make_basic(MOD_MASK_SHIFT, KC_BSPC, KC_DEL);"
+    ));
+    assert!(keymap_c.contains(
+        "const key_override_t key_override_1 = ko_make_basic(MOD_MASK_CTRL | MOD_MASK_SHIFT, KC_ESC, KC_GRV);"
+    ));
+    assert!(keymap_c.contains(
+        "const key_override_t *key_overrides[] = {\n    &key_override_0,\n    &key_override_1,\n};"
+    ));
+    assert!(generator
+        .generate_rules_mk()
+        .contains("KEY_OVERRIDE_ENABLE = yes"));
+}
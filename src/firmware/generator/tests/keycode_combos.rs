@@ -0,0 +1,75 @@
+//! Tests for keycode combo generation.
+
+use super::*;
+use crate::models::KeycodeCombo;
+
+fn add_keycode_combo(layout: &mut Layout) {
+    layout.combo_settings.enabled = true;
+    layout
+        .combo_settings
+        .add_keycode_combo(KeycodeCombo::new(
+            vec![Position::new(0, 0), Position::new(0, 1)],
+            "KC_ESC",
+        ))
+        .unwrap();
+}
+
+#[test]
+fn test_keycode_combo_generates_combo_entries() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    add_keycode_combo(&mut layout);
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains("enum combo_events {\n    KEYCODE_COMBO_0\n};"));
+    assert!(keymap_c
+        .contains("const uint16_t PROGMEM keycode_combo_0_keys[] = {KC_A, KC_B, COMBO_END};"));
+    assert!(keymap_c.contains("[KEYCODE_COMBO_0] = COMBO(keycode_combo_0_keys, KC_ESC),"));
+    // Keycode combos need no hold handling
+    assert!(!keymap_c.contains("process_combo_event"));
+}
+
+#[test]
+fn test_keycode_combo_enables_combo_feature() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    add_keycode_combo(&mut layout);
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let config_h = generator.generate_merged_config_h().unwrap();
+    assert!(config_h.contains("#define COMBO_COUNT 1"));
+    assert!(config_h.contains("#define COMBO_ONLY_FROM_LAYER 0"));
+
+    let rules_mk = generator.generate_rules_mk();
+    assert!(rules_mk.contains("COMBO_ENABLE = yes"));
+}
+
+#[test]
+fn test_keycode_combos_follow_hold_combos() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout
+        .combo_settings
+        .add_combo(crate::models::ComboDefinition::new(
+            Position::new(0, 0),
+            Position::new(0, 1),
+            crate::models::ComboAction::DisableEffects,
+        ))
+        .unwrap();
+    layout
+        .combo_settings
+        .add_keycode_combo(KeycodeCombo::new(
+            vec![Position::new(0, 1), Position::new(0, 0)],
+            "KC_TAB",
+        ))
+        .unwrap();
+    layout.combo_settings.enabled = true;
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+    assert!(keymap_c.contains("enum combo_events {\n    COMBO_0,\n    KEYCODE_COMBO_0\n};"));
+    assert!(keymap_c.contains("[KEYCODE_COMBO_0] = COMBO(keycode_combo_0_keys, KC_TAB),"));
+    assert!(keymap_c.contains("process_combo_event"));
+
+    let config_h = generator.generate_merged_config_h().unwrap();
+    assert!(config_h.contains("#define COMBO_COUNT 2"));
+}
@@ -0,0 +1,87 @@
+//! Tests for live preview console reports.
+
+use super::*;
+use crate::models::FirmwareFeature;
+
+#[test]
+fn test_live_preview_off_by_default() {
+    let (layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(!keymap_c.contains("lqmk:layer"));
+    assert!(!keymap_c.contains("post_process_record_user"));
+}
+
+#[test]
+fn test_live_preview_reports_layers_and_keys_with_console() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout
+        .firmware_features
+        .set(FirmwareFeature::Console, Some(true));
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains("#ifdef CONSOLE_ENABLE"));
+    assert!(keymap_c.contains("layer_state_t layer_state_set_user(layer_state_t state)"));
+    assert!(keymap_c.contains("uprintf(\"lqmk:layer:%u\\n\""));
+    assert!(
+        keymap_c.contains("void post_process_record_user(uint16_t keycode, keyrecord_t *record)")
+    );
+    assert!(generator
+        .generate_rules_mk()
+        .contains("CONSOLE_ENABLE = yes"));
+}
+
+#[test]
+fn test_live_preview_skipped_when_console_disabled() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout
+        .firmware_features
+        .set(FirmwareFeature::Console, Some(false));
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    assert!(generator.generate_live_preview_code().is_empty());
+}
+
+#[test]
+fn test_usage_counting_reports_counts() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.usage_counting = true;
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains(&format!(
+        "#define LQMK_USAGE_LAYERS {}",
+        layout.layers.len()
+    )));
+    assert!(keymap_c
+        .contains("static uint8_t lqmk_usage[LQMK_USAGE_LAYERS][MATRIX_ROWS][MATRIX_COLS];"));
+    assert!(keymap_c.contains("uprintf(\"lqmk:usage:%u:%u:%u:%u\\n\""));
+    assert!(keymap_c.contains("        lqmk_usage_count(record);"));
+    // Live preview stays off without an explicit console override
+    assert!(!keymap_c.contains("lqmk:layer"));
+    assert!(generator
+        .generate_rules_mk()
+        .contains("CONSOLE_ENABLE = yes"));
+}
+
+#[test]
+fn test_usage_counting_skipped_when_console_disabled() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.usage_counting = true;
+    layout
+        .firmware_features
+        .set(FirmwareFeature::Console, Some(false));
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    assert!(generator.generate_live_preview_code().is_empty());
+    assert!(generator
+        .generate_rules_mk()
+        .contains("CONSOLE_ENABLE = no"));
+}
@@ -0,0 +1,65 @@
+//! Tests for macro generation.
+
+use super::*;
+use crate::models::MacroDefinition;
+
+fn add_macros(layout: &mut Layout) {
+    layout
+        .add_macro(MacroDefinition::from_sequence("sig", "Best,{KC_ENT}{50ms}\"Me\"").unwrap())
+        .unwrap();
+    layout
+        .add_macro(MacroDefinition::from_sequence("email", "me@example.com").unwrap())
+        .unwrap();
+    layout.layers[0].keys[1].keycode = "MACRO(sig)".to_string();
+}
+
+#[test]
+fn test_macros_absent_by_default() {
+    let (layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(!keymap_c.contains("custom_keycodes"));
+    assert!(!keymap_c.contains("LQMK_RGB_RECORD_HOOK"));
+}
+
+#[test]
+fn test_macros_generate_enum_keycodes_and_playback() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.idle_effect_settings.enabled = false;
+    add_macros(&mut layout);
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    // Enum is sorted by name and starts at SAFE_RANGE
+    assert!(keymap_c
+        .contains("enum custom_keycodes {\n    MACRO_EMAIL = SAFE_RANGE,\n    MACRO_SIG,\n};"));
+    assert!(keymap_c.find("enum custom_keycodes") < keymap_c.find("PROGMEM keymaps"));
+    assert!(keymap_c.contains("KC_A, MACRO_SIG"));
+
+    assert!(keymap_c.contains("        case MACRO_SIG:\n            SEND_STRING(\"Best,\");\n            tap_code16(KC_ENT);\n            wait_ms(50);\n            SEND_STRING(\"\\\"Me\\\"\");\n            return false;\n"));
+    assert!(keymap_c.contains("SEND_STRING(\"me@example.com\");"));
+    assert_eq!(keymap_c.matches("bool process_record_user").count(), 1);
+}
+
+#[test]
+fn test_macros_chain_rgb_record_hook() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.idle_effect_settings.enabled = true;
+    layout.rgb_overlay_ripple.enabled = true;
+    add_macros(&mut layout);
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert_eq!(keymap_c.matches("bool process_record_user").count(), 1);
+    assert!(keymap_c.contains(
+        "#define LQMK_RGB_RECORD_HOOK\nbool lazyqmk_process_record_rgb(uint16_t keycode, keyrecord_t *record) {"
+    ));
+    let hook = keymap_c.find("bool lazyqmk_process_record_rgb").unwrap();
+    let user = keymap_c.find("bool process_record_user").unwrap();
+    assert!(hook < user, "RGB hook must be defined before it is called");
+    assert!(keymap_c.contains("    if (!lazyqmk_process_record_rgb(keycode, record)) {"));
+}
@@ -7,11 +7,25 @@
 //! Sub-modules:
 //! - `bootloader_combo` — regression tests for the bootloader-combo
 //!   decoupling (LazyQMK-epl0.5).
+//! - `rgb_conditions` — conditional RGB behaviors (dim, WPM/layer effects,
+//!   off hours).
+//! - `macros` — macro keycodes and `process_record_user` playback.
+//! - `custom_keycodes` — custom keycodes with C snippets in
+//!   `process_record_user`.
+//! - `editing_helpers` — Select Word, Sentence Case and Num Word helpers.
+//! - `keycode_combos` — combos that send a keycode.
+//! - `key_overrides` — modifier + key overrides in `key_overrides[]`.
+//! - `repeat_key` — `REPEAT_KEY_ENABLE` and alt repeat overrides.
+//! - `unicode` — `UC()`/`UM()` keys, `unicode_map[]` and input modes.
+//! - `tapping_terms` — per-key tapping terms in `get_tapping_term()`.
+//! - `encoders` — per-layer encoder maps.
+//! - `wrappers` — wrappers.h layer order and row wrapper macros.
+//! - `userspace` — `USER_NAME` and the shared userspace `rules.mk`.
 
 use super::*;
 use crate::models::keyboard_geometry::KeyGeometry;
 use crate::models::layer::{KeyDefinition, Layer, Position};
-use crate::models::RgbColor;
+use crate::models::{RgbColor, TapDanceAction};
 use std::path::PathBuf;
 
 pub(super) fn create_test_setup() -> (
@@ -493,6 +507,71 @@ fn test_generate_keymap_c_no_extra_headers_without_language_keycodes() {
     assert!(!keymap_c.contains("keymap_extras"));
 }
 
+#[test]
+fn test_detect_required_headers_sorted() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    let layer = layout.get_layer_mut(0).unwrap();
+    layer.get_key_mut(Position::new(0, 0)).unwrap().keycode = "SE_A".to_string();
+    layer.get_key_mut(Position::new(0, 1)).unwrap().keycode = "DE_Y".to_string();
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    assert_eq!(
+        generator.detect_required_headers(),
+        [
+            "keymap_extras/keymap_german.h",
+            "keymap_extras/keymap_swedish.h"
+        ]
+    );
+}
+
+// === Output Stability Tests ===
+
+/// Generates keymap.c, config.h, and rules.mk for `layout`.
+fn generate_all(layout: &Layout) -> (String, String, String) {
+    let (_, geometry, mapping, config, keycode_db) = create_test_setup();
+    let generator = FirmwareGenerator::new(layout, &geometry, &mapping, &config, &keycode_db);
+    (
+        generator.generate_keymap_c().unwrap(),
+        generator.generate_merged_config_h().unwrap(),
+        generator.generate_rules_mk(),
+    )
+}
+
+#[test]
+fn test_generated_output_is_identical_across_runs() {
+    let (mut layout, ..) = create_test_setup();
+    layout
+        .add_tap_dance(TapDanceAction::new("esc", "KC_ESC").with_double_tap("KC_CAPS"))
+        .unwrap();
+
+    let first = generate_all(&layout);
+    let second = generate_all(&layout);
+
+    assert_eq!(first, second);
+    assert!(!first.1.contains("Generated:"));
+}
+
+#[test]
+fn test_generated_output_ignores_storage_order() {
+    let (mut layout, ..) = create_test_setup();
+    layout
+        .add_tap_dance(TapDanceAction::new("alpha", "KC_A").with_double_tap("KC_B"))
+        .unwrap();
+    layout
+        .add_tap_dance(TapDanceAction::new("zulu", "KC_Z").with_double_tap("KC_X"))
+        .unwrap();
+    let layer = layout.get_layer_mut(0).unwrap();
+    layer.get_key_mut(Position::new(0, 0)).unwrap().keycode = "TD(zulu)".to_string();
+    layer.get_key_mut(Position::new(0, 1)).unwrap().keycode = "TD(alpha)".to_string();
+
+    let mut reordered = layout.clone();
+    reordered.tap_dances.reverse();
+    reordered.layers[0].keys.reverse();
+
+    assert_eq!(generate_all(&layout), generate_all(&reordered));
+}
+
 // === Idle Effect Tests ===
 
 #[test]
@@ -946,6 +1025,35 @@ fn test_rules_mk_both_features() {
     );
 }
 
+#[test]
+fn test_rules_mk_firmware_feature_overrides() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+
+    layout
+        .firmware_features
+        .set(crate::models::FirmwareFeature::Console, Some(false));
+    layout
+        .firmware_features
+        .set(crate::models::FirmwareFeature::MouseKeys, Some(true));
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let rules_mk = generator.generate_rules_mk();
+
+    assert!(
+        rules_mk.contains("CONSOLE_ENABLE = no"),
+        "rules.mk should disable the console, got: {rules_mk}"
+    );
+    assert!(
+        rules_mk.contains("MOUSEKEY_ENABLE = yes"),
+        "rules.mk should enable mouse keys, got: {rules_mk}"
+    );
+    // Features left at the keyboard default are not written
+    assert!(
+        !rules_mk.contains("AUDIO_ENABLE"),
+        "rules.mk should not mention audio, got: {rules_mk}"
+    );
+}
+
 #[test]
 fn test_rules_mk_combos_disabled_no_enable() {
     let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
@@ -991,3 +1099,16 @@ fn test_rules_mk_only_placeholder_combos_no_enable() {
 }
 
 mod bootloader_combo;
+mod custom_keycodes;
+mod editing_helpers;
+mod encoders;
+mod key_overrides;
+mod keycode_combos;
+mod live_preview;
+mod macros;
+mod repeat_key;
+mod rgb_conditions;
+mod tapping_terms;
+mod unicode;
+mod userspace;
+mod wrappers;
@@ -0,0 +1,52 @@
+//! Tests for Repeat Key generation.
+
+use super::*;
+use crate::models::AltRepeatKey;
+
+#[test]
+fn test_no_repeat_key_by_default() {
+    let (layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    assert!(!generator
+        .generate_keymap_c()
+        .unwrap()
+        .contains("get_alt_repeat_key_keycode_user"));
+    assert!(!generator.generate_rules_mk().contains("REPEAT_KEY_ENABLE"));
+}
+
+#[test]
+fn test_repeat_keycode_enables_feature() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.layers[0].keys[1].keycode = "QK_REP".to_string();
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    assert!(generator
+        .generate_rules_mk()
+        .contains("REPEAT_KEY_ENABLE = yes"));
+    // Without overrides QMK's built-in alternates are used
+    assert!(!generator
+        .generate_keymap_c()
+        .unwrap()
+        .contains("get_alt_repeat_key_keycode_user"));
+}
+
+#[test]
+fn test_alt_repeat_overrides() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.alt_repeat_keys = vec![
+        AltRepeatKey::new("KC_LEFT", "KC_RGHT"),
+        AltRepeatKey::new("C(KC_Z)", "C(KC_Y)"),
+    ];
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains(
+        "// Alt Repeat Key\nuint16_t get_alt_repeat_key_keycode_user(uint16_t keycode, uint8_t mods) {\n    switch (keycode) {\n        case KC_LEFT: return KC_RGHT;\n        case C(KC_Z): return C(KC_Y);\n    }\n    return KC_TRNS;\n}\n"
+    ));
+    assert!(generator
+        .generate_rules_mk()
+        .contains("REPEAT_KEY_ENABLE = yes"));
+}
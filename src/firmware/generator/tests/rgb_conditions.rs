@@ -0,0 +1,75 @@
+//! Tests for conditional RGB behavior generation.
+
+use super::*;
+use crate::models::{RgbMatrixEffect, RgbOffHours};
+
+#[test]
+fn test_rgb_conditions_inactive_by_default() {
+    let (layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let keymap_c = generator.generate_keymap_c().unwrap();
+    let config_h = generator.generate_merged_config_h().unwrap();
+
+    assert!(!keymap_c.contains("housekeeping_task_user"));
+    assert!(!config_h.contains("LQMK_RGB_CONDITIONS_ENABLED"));
+    assert!(generator.generate_rules_mk().is_empty());
+}
+
+#[test]
+fn test_rgb_conditions_generate_rules() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    let rules = &mut layout.rgb_conditions;
+    rules.dim_after_minutes = 10;
+    rules.dim_brightness_pct = 20;
+    rules.wpm_threshold = 70;
+    rules.wpm_effect = RgbMatrixEffect::CycleAll;
+    rules.set_layer_effect(0, Some(RgbMatrixEffect::Breathing));
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let keymap_c = generator.generate_keymap_c().unwrap();
+    let config_h = generator.generate_merged_config_h().unwrap();
+
+    assert!(keymap_c.contains("void housekeeping_task_user(void)"));
+    assert!(keymap_c.contains("case 0: return RGB_MATRIX_BREATHING;"));
+    assert!(keymap_c.contains("get_current_wpm() >= LQMK_RGB_WPM_THRESHOLD"));
+    assert!(keymap_c.contains("last_input_activity_elapsed() >= LQMK_RGB_DIM_AFTER_MS"));
+    assert!(keymap_c.contains("#include <lib/lib8tion/lib8tion.h>"));
+    assert!(
+        !keymap_c.contains("raw_hid_receive"),
+        "the clock is only needed for off hours"
+    );
+
+    assert!(config_h.contains("#define LQMK_RGB_DIM_AFTER_MS 600000UL"));
+    assert!(config_h.contains("#define LQMK_RGB_DIM_LEVEL 51"));
+    assert!(config_h.contains("#define LQMK_RGB_WPM_MODE RGB_MATRIX_CYCLE_ALL"));
+    assert!(generator.generate_rules_mk().contains("WPM_ENABLE = yes"));
+}
+
+#[test]
+fn test_rgb_off_hours_use_raw_hid_clock() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.rgb_conditions.off_hours = RgbOffHours::parse("22:00-07:30");
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let keymap_c = generator.generate_keymap_c().unwrap();
+    let config_h = generator.generate_merged_config_h().unwrap();
+
+    assert!(keymap_c.contains("#include \"raw_hid.h\""));
+    assert!(keymap_c.contains("void raw_hid_receive(uint8_t *data, uint8_t length)"));
+    assert!(keymap_c.contains("lqmk_cond_in_off_hours()"));
+    assert!(config_h.contains("#define LQMK_RGB_OFF_START_MIN 1320"));
+    assert!(config_h.contains("#define LQMK_RGB_OFF_END_MIN 450"));
+    assert!(generator.generate_rules_mk().contains("RAW_ENABLE = yes"));
+}
+
+#[test]
+fn test_rgb_layer_effect_for_missing_layer_is_rejected() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout
+        .rgb_conditions
+        .set_layer_effect(3, Some(RgbMatrixEffect::Breathing));
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    assert!(generator.generate_merged_config_h().is_err());
+}
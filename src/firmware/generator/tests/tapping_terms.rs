@@ -0,0 +1,58 @@
+//! Tests for per-key tapping term generation.
+
+use super::*;
+
+#[test]
+fn test_no_per_key_tapping_terms_by_default() {
+    let (layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    assert!(!generator
+        .generate_keymap_c()
+        .unwrap()
+        .contains("get_tapping_term"));
+    assert!(!generator
+        .generate_merged_config_h()
+        .unwrap()
+        .contains("TAPPING_TERM_PER_KEY"));
+}
+
+#[test]
+fn test_get_tapping_term_matches_matrix_positions() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout
+        .tap_hold_settings
+        .set_key_tapping_term(Position::new(0, 1), Some(260));
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains(
+        "// Per-Key Tapping Terms\n\
+         uint16_t get_tapping_term(uint16_t keycode, keyrecord_t *record) {\n    \
+         keypos_t key = record->event.key;\n    \
+         // Key (0, 1)\n    \
+         if (key.row == 0 && key.col == 1) {\n        \
+         return 260;\n    \
+         }\n    \
+         return TAPPING_TERM;\n\
+         }\n"
+    ));
+    let config_h = generator.generate_merged_config_h().unwrap();
+    assert!(config_h.contains("#define TAPPING_TERM_PER_KEY\n"));
+    assert!(!config_h.contains("#define TAPPING_TERM 200"));
+}
+
+#[test]
+fn test_get_tapping_term_skips_positions_without_matrix_key() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout
+        .tap_hold_settings
+        .set_key_tapping_term(Position::new(3, 7), Some(260));
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains("get_tapping_term"));
+    assert!(!keymap_c.contains("return 260;"));
+}
@@ -0,0 +1,74 @@
+//! Tests for Unicode map and input mode generation.
+
+use super::*;
+use crate::models::{UnicodeGlyph, UnicodeInputMode};
+
+#[test]
+fn test_no_unicode_by_default() {
+    let (layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    assert!(!generator.generate_keymap_c().unwrap().contains("unicode"));
+    assert!(!generator.generate_rules_mk().contains("UNICODE"));
+    assert!(!generator
+        .generate_merged_config_h()
+        .unwrap()
+        .contains("UNICODE"));
+}
+
+#[test]
+fn test_uc_keys_use_basic_unicode() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.layers[0].keys[0].keycode = "UC(0x00E9)".to_string();
+    layout.unicode_input_modes = vec![UnicodeInputMode::Linux, UnicodeInputMode::Macos];
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains("UC(0x00E9)"));
+    assert!(!keymap_c.contains("unicode_map"));
+    let rules_mk = generator.generate_rules_mk();
+    assert!(rules_mk.contains("UNICODE_ENABLE = yes"));
+    assert!(!rules_mk.contains("UNICODEMAP_ENABLE"));
+    assert!(generator
+        .generate_merged_config_h()
+        .unwrap()
+        .contains("#define UNICODE_SELECTED_MODES UNICODE_MODE_LINUX, UNICODE_MODE_MACOS\n"));
+}
+
+#[test]
+fn test_um_keys_generate_unicode_map_and_promote_uc_keys() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.unicode_map = vec![UnicodeGlyph::new("snek", 0x1F40D)];
+    layout.layers[0].keys[0].keycode = "UM(snek)".to_string();
+    layout.layers[0].keys[1].keycode = "UC(0xE9)".to_string();
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains("enum unicode_names {\n    UM_SNEK,\n    UC_00E9,\n};"));
+    assert!(keymap_c.contains("    [UM_SNEK] = 0x1F40D, // 🐍 U+1F40D\n"));
+    assert!(keymap_c.contains("    [UC_00E9] = 0x00E9, // é U+00E9\n"));
+    assert!(keymap_c.contains("UM(UM_SNEK)"));
+    assert!(keymap_c.contains("UM(UC_00E9)"));
+    assert!(!keymap_c.contains("UC(0xE9)"));
+
+    let rules_mk = generator.generate_rules_mk();
+    assert!(rules_mk.contains("UNICODEMAP_ENABLE = yes"));
+    assert!(!rules_mk.contains("UNICODE_ENABLE = yes\n"));
+}
+
+#[test]
+fn test_large_uc_code_point_switches_to_unicode_map() {
+    let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    layout.layers[0].keys[0].keycode = "UC(0x1F600)".to_string();
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let keymap_c = generator.generate_keymap_c().unwrap();
+
+    assert!(keymap_c.contains("[UC_1F600] = 0x1F600,"));
+    assert!(keymap_c.contains("UM(UC_1F600)"));
+    assert!(generator
+        .generate_rules_mk()
+        .contains("UNICODEMAP_ENABLE = yes"));
+}
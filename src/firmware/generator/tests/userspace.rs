@@ -0,0 +1,51 @@
+//! Tests for QMK userspace generation.
+
+use super::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_no_user_name_without_userspace() {
+    let (layout, geometry, mapping, config, keycode_db) = create_test_setup();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    assert!(generator.generate_rules_mk().is_empty());
+    assert_eq!(generator.create_userspace().unwrap(), None);
+}
+
+#[test]
+fn test_userspace_sets_user_name() {
+    let (mut layout, geometry, mapping, mut config, keycode_db) = create_test_setup();
+    config.build.userspace = Some("jane".to_string());
+    layout.layers[0].keys[1].keycode = "QK_REP".to_string();
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let rules_mk = generator.generate_rules_mk();
+
+    assert!(
+        rules_mk.contains("USER_NAME := jane\nREPEAT_KEY_ENABLE = yes\n"),
+        "{rules_mk}"
+    );
+}
+
+#[test]
+fn test_create_userspace_keeps_existing_rules() {
+    let temp_dir = TempDir::new().unwrap();
+    let (layout, geometry, mapping, mut config, keycode_db) = create_test_setup();
+    config.paths.qmk_firmware = Some(temp_dir.path().to_path_buf());
+    config.build.userspace = Some("jane".to_string());
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let rules_mk = temp_dir.path().join("users/jane/rules.mk");
+
+    assert_eq!(
+        generator.create_userspace().unwrap(),
+        Some(rules_mk.clone())
+    );
+    let scaffold = fs::read_to_string(&rules_mk).unwrap();
+    assert!(scaffold.contains("USER_NAME := jane"), "{scaffold}");
+
+    fs::write(&rules_mk, "SRC += jane.c\n").unwrap();
+    assert_eq!(generator.create_userspace().unwrap(), None);
+    assert_eq!(fs::read_to_string(&rules_mk).unwrap(), "SRC += jane.c\n");
+}
@@ -0,0 +1,117 @@
+//! Tests for userspace layer wrappers (wrappers.h).
+
+use super::*;
+
+/// Split board with two rows of two keys per half, listed row by row.
+fn split_setup() -> (
+    Layout,
+    KeyboardGeometry,
+    VisualLayoutMapping,
+    Config,
+    KeycodeDb,
+) {
+    let (mut layout, _, _, config, keycode_db) = create_test_setup();
+
+    let mut geometry = KeyboardGeometry::new("test", "LAYOUT_split", 4, 2);
+    let positions = [
+        ((0, 0), 0.0, 0.0),
+        ((0, 1), 1.0, 0.0),
+        ((2, 0), 4.0, 0.0),
+        ((2, 1), 5.0, 0.0),
+        ((1, 0), 0.0, 1.0),
+        ((1, 1), 1.0, 1.0),
+        ((3, 0), 4.0, 1.0),
+        ((3, 1), 5.0, 1.0),
+    ];
+    for (idx, (matrix, x, y)) in positions.into_iter().enumerate() {
+        geometry.add_key(KeyGeometry::new(matrix, idx as u8, x, y));
+    }
+    let mapping = VisualLayoutMapping::build(&geometry);
+
+    layout.metadata.layout_variant = Some("LAYOUT_split".to_string());
+    layout.layers.clear();
+    let mut qwerty = Layer::new(0, "Qwerty", RgbColor::new(255, 255, 255)).unwrap();
+    let mut lower = Layer::new(1, "Lower / Nav", RgbColor::new(0, 0, 255)).unwrap();
+    let keycodes = [
+        "KC_Q", "KC_W", "KC_O", "KC_P", "KC_A", "KC_S", "KC_L", "KC_SCLN",
+    ];
+    for (key, keycode) in geometry.keys.iter().zip(keycodes) {
+        let position = Position::new(key.visual_y as u8, key.visual_x as u8);
+        qwerty.add_key(KeyDefinition::new(position, keycode));
+        lower.add_key(KeyDefinition::new(position, "KC_TRNS"));
+    }
+    layout.add_layer(qwerty).unwrap();
+    layout.add_layer(lower).unwrap();
+
+    (layout, geometry, mapping, config, keycode_db)
+}
+
+#[test]
+fn test_wrappers_export_layer_order() {
+    let (layout, geometry, mapping, config, keycode_db) = split_setup();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let wrappers = generator.generate_wrappers_h().unwrap();
+
+    assert!(wrappers.contains("#pragma once"));
+    assert!(wrappers.contains("#define LQMK_LAYER_QWERTY 0\n"));
+    assert!(wrappers.contains("#define LQMK_LAYER_LOWER_NAV 1\n"));
+    assert!(wrappers.contains("#define LQMK_LAYER_COUNT 2\n"));
+    assert!(wrappers.contains("#    define LAYOUT_split_wrapper(...) LAYOUT_split(__VA_ARGS__)"));
+}
+
+#[test]
+fn test_wrappers_split_rows_into_halves() {
+    let (layout, geometry, mapping, config, keycode_db) = split_setup();
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let wrappers = generator.generate_wrappers_h().unwrap();
+
+    assert!(wrappers.contains("#define _________________QWERTY_L1_________________ KC_Q, KC_W\n"));
+    assert!(wrappers.contains("#define _________________QWERTY_R1_________________ KC_O, KC_P\n"));
+    assert!(wrappers.contains("#define _________________QWERTY_L2_________________ KC_A, KC_S\n"));
+    assert!(
+        wrappers.contains("#define _________________QWERTY_R2_________________ KC_L, KC_SCLN\n")
+    );
+    assert!(wrappers.contains(
+        "#define LQMK_KEYS_QWERTY \\\n    _________________QWERTY_L1_________________, \\\n    \
+         _________________QWERTY_R1_________________, \\\n"
+    ));
+}
+
+#[test]
+fn test_wrappers_number_rows_on_single_piece_boards() {
+    let (mut layout, _, _, config, keycode_db) = create_test_setup();
+    let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 2, 2);
+    geometry.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+    geometry.add_key(KeyGeometry::new((0, 1), 1, 1.0, 0.0));
+    let mut space = KeyGeometry::new((1, 0), 2, 0.0, 1.0);
+    space.width = 2.0;
+    geometry.add_key(space);
+    let mapping = VisualLayoutMapping::build(&geometry);
+    layout.layers[0].add_key(KeyDefinition::new(Position::new(1, 0), "KC_SPC"));
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let wrappers = generator.generate_wrappers_h().unwrap();
+
+    assert!(wrappers.contains("__BASE_1__"));
+    assert!(wrappers.contains("__BASE_2__"));
+    assert!(wrappers.contains("_BASE_2___________________ KC_SPC\n"));
+    assert!(!wrappers.contains("BASE_L1"));
+}
+
+#[test]
+fn test_wrappers_rename_duplicate_and_numeric_layers() {
+    let (mut layout, geometry, mapping, config, keycode_db) = split_setup();
+    layout.layers[1].name = "Qwerty".to_string();
+    let mut extra = Layer::new(2, "2", RgbColor::new(0, 0, 0)).unwrap();
+    extra.add_key(KeyDefinition::new(Position::new(0, 0), "KC_1"));
+    layout.add_layer(extra).unwrap();
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let wrappers = generator.generate_wrappers_h().unwrap();
+
+    assert!(wrappers.contains("#define LQMK_LAYER_QWERTY 0\n"));
+    assert!(wrappers.contains("#define LQMK_LAYER_QWERTY_1 1\n"));
+    assert!(wrappers.contains("#define LQMK_LAYER_LAYER2 2\n"));
+}
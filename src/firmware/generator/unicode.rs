@@ -0,0 +1,136 @@
+//! Unicode code generation.
+//!
+//! QMK builds one Unicode feature at a time. Layouts that only use small
+//! `UC(0x...)` code points get `UNICODE_ENABLE`; as soon as a key uses
+//! `UM(name)` or a `UC()` code point above `0x7FFF`, the keymap switches to
+//! `UNICODEMAP_ENABLE` and every `UC()` key is moved into `unicode_map[]` as
+//! a `UC_<hex>` entry, so both kinds of key work together.
+
+use std::collections::BTreeSet;
+
+use crate::models::layout::unicode::{format_codepoint, uc_codepoint, um_name, MAX_UC_CODEPOINT};
+
+use super::FirmwareGenerator;
+
+/// QMK Unicode feature a keymap needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnicodeFeature {
+    /// `UC()` keycodes only (`UNICODE_ENABLE`)
+    Basic,
+    /// `unicode_map[]` with `UM()` keycodes (`UNICODEMAP_ENABLE`)
+    Map,
+}
+
+impl UnicodeFeature {
+    /// Returns the `rules.mk` line that enables this feature.
+    pub const fn rules_mk_flag(self) -> &'static str {
+        match self {
+            Self::Basic => "UNICODE_ENABLE = yes",
+            Self::Map => "UNICODEMAP_ENABLE = yes",
+        }
+    }
+}
+
+/// Returns the Unicode feature the layout's keys need, if any.
+pub fn feature(gen: &FirmwareGenerator) -> Option<UnicodeFeature> {
+    let mut uses_uc = false;
+    for keycode in layer_keycodes(gen) {
+        if um_name(keycode).is_some() {
+            return Some(UnicodeFeature::Map);
+        }
+        if let Some(codepoint) = uc_codepoint(keycode) {
+            if codepoint > MAX_UC_CODEPOINT {
+                return Some(UnicodeFeature::Map);
+            }
+            uses_uc = true;
+        }
+    }
+    uses_uc.then_some(UnicodeFeature::Basic)
+}
+
+fn layer_keycodes<'a>(gen: &'a FirmwareGenerator) -> impl Iterator<Item = &'a str> {
+    gen.layout
+        .layers
+        .iter()
+        .flat_map(|layer| &layer.keys)
+        .map(|key| key.keycode.as_str())
+}
+
+/// Enum name of the map entry a `UC()` key is moved to, e.g. `UC_1F40D`.
+fn uc_enum_name(codepoint: u32) -> String {
+    format!("UC_{codepoint:04X}")
+}
+
+/// Generates the `unicode_names` enum and `unicode_map[]` array.
+///
+/// Returns an empty string unless the keymap uses the Unicode map.
+pub fn generate_map(gen: &FirmwareGenerator) -> String {
+    if feature(gen) != Some(UnicodeFeature::Map) {
+        return String::new();
+    }
+
+    let mut entries: Vec<(String, u32)> = gen
+        .layout
+        .unicode_map
+        .iter()
+        .map(|glyph| (glyph.enum_name(), glyph.codepoint))
+        .collect();
+    let uc_codepoints: BTreeSet<u32> = layer_keycodes(gen).filter_map(uc_codepoint).collect();
+    entries.extend(
+        uc_codepoints
+            .into_iter()
+            .map(|codepoint| (uc_enum_name(codepoint), codepoint)),
+    );
+
+    let mut code = String::new();
+    code.push_str("// Unicode Map\n");
+    code.push_str("enum unicode_names {\n");
+    for (name, _) in &entries {
+        code.push_str(&format!("    {name},\n"));
+    }
+    code.push_str("};\n\n");
+    code.push_str("const uint32_t PROGMEM unicode_map[] = {\n");
+    for (name, codepoint) in &entries {
+        code.push_str(&format!("    [{name}] = 0x{codepoint:04X},"));
+        match char::from_u32(*codepoint).filter(|c| !c.is_control()) {
+            Some(c) => code.push_str(&format!(" // {c} {}\n", format_codepoint(*codepoint))),
+            None => code.push_str(&format!(" // {}\n", format_codepoint(*codepoint))),
+        }
+    }
+    code.push_str("};\n");
+    code
+}
+
+/// Processes a keycode, converting `UM(name)` to `UM(UM_NAME)` and, when the
+/// Unicode map is in use, `UC(0x...)` to its `UM(UC_<hex>)` map entry.
+///
+/// References to undefined glyphs are returned unchanged (caught by the
+/// layout validator).
+pub fn process_keycode(gen: &FirmwareGenerator, keycode: &str) -> String {
+    if let Some(glyph) = um_name(keycode).and_then(|name| gen.layout.get_unicode_glyph(name)) {
+        return format!("UM({})", glyph.enum_name());
+    }
+    if let Some(codepoint) = uc_codepoint(keycode) {
+        if feature(gen) == Some(UnicodeFeature::Map) {
+            return format!("UM({})", uc_enum_name(codepoint));
+        }
+    }
+    keycode.to_string()
+}
+
+/// Returns the `config.h` lines selecting the host input modes.
+///
+/// Empty unless the keymap uses Unicode and the layout lists input modes.
+pub fn generate_config(gen: &FirmwareGenerator) -> String {
+    let modes = &gen.layout.unicode_input_modes;
+    if modes.is_empty() || feature(gen).is_none() {
+        return String::new();
+    }
+
+    let modes = modes
+        .iter()
+        .map(|mode| mode.qmk_mode())
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("\n// Unicode Input\n#define UNICODE_SELECTED_MODES {modes}\n")
+}
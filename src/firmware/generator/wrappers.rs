@@ -0,0 +1,219 @@
+//! Userspace layer wrappers (wrappers.h).
+//!
+//! Multi-keyboard QMK userspaces usually define each layer once as
+//! `#define`-based row macros and expand them through a `LAYOUT_*_wrapper`
+//! macro, e.g.
+//! `LAYOUT_wrapper(_________________QWERTY_L1_________________, ...)`.
+//! wrappers.h exports the layout in that form so such keymaps can consume
+//! LazyQMK output directly:
+//! - `LQMK_LAYER_<NAME>`: layer index, in layer order
+//! - one row macro per visual row and split half (`<NAME>_L1`, `<NAME>_R1`,
+//!   or `<NAME>_1` on keyboards without halves)
+//! - `LQMK_KEYS_<NAME>`: all row macros of a layer in layout order
+//! - `<variant>_wrapper`: expands macro arguments before calling the layout
+//!
+//! Keycodes are resolved the same way as in keymap.c; tap dance and macro
+//! keycodes refer to enums defined there.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use crate::constants::APP_BINARY_NAME;
+
+use super::FirmwareGenerator;
+
+/// Width of a row macro name, including the underscore padding.
+const WRAPPER_WIDTH: usize = 43;
+
+/// A run of consecutive layout positions on one visual row and half.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Chunk {
+    /// Row macro suffix (e.g. `L1`)
+    suffix: String,
+    /// Layout indices in the chunk
+    indices: Vec<usize>,
+}
+
+/// Generates wrappers.h for the layout.
+///
+/// # Errors
+///
+/// Returns an error if a key cannot be mapped to its layout position.
+pub fn generate(gen: &FirmwareGenerator) -> Result<String> {
+    let variant = gen
+        .layout
+        .metadata
+        .layout_variant
+        .as_deref()
+        .unwrap_or("LAYOUT");
+    let names = layer_names(gen);
+    let chunks = chunks(gen);
+
+    let mut code = String::new();
+    code.push_str(&format!("// Generated by {APP_BINARY_NAME}\n"));
+    code.push_str(&format!("// Layout: {}\n", gen.layout.metadata.name));
+    code.push_str(&format!(
+        "// Keyboard: {}\n",
+        gen.layout.metadata.keyboard.as_deref().unwrap_or("unknown")
+    ));
+    code.push_str(&format!("// Layout Variant: {variant}\n"));
+    code.push_str("//\n");
+    code.push_str("// Userspace layer wrappers. Include this file from a keymap and use e.g.\n");
+    if let Some(name) = names.first() {
+        code.push_str(&format!("// [0] = {variant}_wrapper(LQMK_KEYS_{name})\n"));
+    }
+    code.push_str("#pragma once\n");
+    code.push('\n');
+
+    code.push_str("// Layer order\n");
+    for (idx, name) in names.iter().enumerate() {
+        code.push_str(&format!("#define LQMK_LAYER_{name} {idx}\n"));
+    }
+    code.push_str(&format!("#define LQMK_LAYER_COUNT {}\n", names.len()));
+    code.push('\n');
+
+    code.push_str("// Expand wrapper macros before they reach the layout macro\n");
+    code.push_str(&format!("#ifndef {variant}_wrapper\n"));
+    code.push_str(&format!(
+        "#    define {variant}_wrapper(...) {variant}(__VA_ARGS__)\n"
+    ));
+    code.push_str("#endif\n");
+
+    for ((idx, layer), name) in gen.layout.layers.iter().enumerate().zip(&names) {
+        let keys = gen.generate_layer_keys_by_layout(layer)?;
+        code.push('\n');
+        code.push_str(&format!("// Layer {idx}: {}\n", layer.name));
+
+        let mut macros = Vec::new();
+        for chunk in &chunks {
+            let macro_name = row_macro_name(name, &chunk.suffix);
+            let row: Vec<&str> = chunk
+                .indices
+                .iter()
+                .map(|&i| keys.get(i).map_or("KC_NO", String::as_str))
+                .collect();
+            code.push_str(&format!("#define {macro_name} {}\n", row.join(", ")));
+            macros.push(macro_name);
+        }
+        code.push_str(&format!("#define LQMK_KEYS_{name} \\\n"));
+        code.push_str(&format!("    {}\n", macros.join(", \\\n    ")));
+    }
+
+    Ok(code)
+}
+
+/// Returns a C identifier for each layer, unique within the layout.
+///
+/// Names are uppercased with anything other than letters and digits turned
+/// into underscores. Names that are empty or start with a digit become
+/// `LAYER<n>`; duplicates get `_<n>` appended.
+fn layer_names(gen: &FirmwareGenerator) -> Vec<String> {
+    let mut seen = HashSet::new();
+    gen.layout
+        .layers
+        .iter()
+        .enumerate()
+        .map(|(idx, layer)| {
+            let name = sanitize(&layer.name);
+            let name = if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+                format!("LAYER{idx}")
+            } else {
+                name
+            };
+            if seen.insert(name.clone()) {
+                name
+            } else {
+                let name = format!("{name}_{idx}");
+                seen.insert(name.clone());
+                name
+            }
+        })
+        .collect()
+}
+
+/// Uppercases `name` and joins its alphanumeric runs with underscores.
+fn sanitize(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_ascii_uppercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Builds a row macro name centered in underscores, e.g.
+/// `_________________QWERTY_L1_________________`.
+fn row_macro_name(layer: &str, suffix: &str) -> String {
+    let label = format!("{layer}_{suffix}");
+    let padding = WRAPPER_WIDTH.saturating_sub(label.len()).max(6);
+    let left = padding / 2;
+    format!("{}{label}{}", "_".repeat(left), "_".repeat(padding - left))
+}
+
+/// Splits the layout positions into row macros.
+///
+/// Positions are walked in layout order; a new chunk starts whenever the
+/// visual row or the split half changes. Halves are numbered separately, so
+/// a layout listing rows as left then right yields `L1, R1, L2, R2, ...`.
+fn chunks(gen: &FirmwareGenerator) -> Vec<Chunk> {
+    let mut keys: Vec<_> = gen.geometry.keys.iter().collect();
+    keys.sort_by_key(|key| key.layout_index);
+
+    let split = split_axis(gen);
+    let mut chunks: Vec<Chunk> = Vec::new();
+    let mut current: Option<(i32, Option<bool>)> = None;
+    let (mut left_rows, mut right_rows, mut rows) = (0, 0, 0);
+
+    for key in keys {
+        let row = key.visual_y.round() as i32;
+        let is_left = split.map(|axis| key.width.mul_add(0.5, key.visual_x) < axis);
+        let group = (row, is_left);
+        if current != Some(group) {
+            let suffix = match is_left {
+                Some(true) => {
+                    left_rows += 1;
+                    format!("L{left_rows}")
+                }
+                Some(false) => {
+                    right_rows += 1;
+                    format!("R{right_rows}")
+                }
+                None => {
+                    rows += 1;
+                    rows.to_string()
+                }
+            };
+            chunks.push(Chunk {
+                suffix,
+                indices: Vec::new(),
+            });
+            current = Some(group);
+        }
+        if let Some(chunk) = chunks.last_mut() {
+            chunk.indices.push(usize::from(key.layout_index));
+        }
+    }
+    chunks
+}
+
+/// Returns the center line between the halves of a split keyboard, or None
+/// if a key straddles the center (a single-piece board).
+fn split_axis(gen: &FirmwareGenerator) -> Option<f32> {
+    let keys = &gen.geometry.keys;
+    let left = keys
+        .iter()
+        .map(|key| key.visual_x)
+        .fold(f32::INFINITY, f32::min);
+    let right = keys
+        .iter()
+        .map(|key| key.visual_x + key.width)
+        .fold(f32::NEG_INFINITY, f32::max);
+    if keys.len() < 2 || !left.is_finite() || !right.is_finite() {
+        return None;
+    }
+    let axis = f32::midpoint(left, right);
+    let straddles = keys
+        .iter()
+        .any(|key| key.visual_x < axis && key.visual_x + key.width > axis);
+    (!straddles).then_some(axis)
+}
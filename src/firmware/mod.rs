@@ -2,12 +2,20 @@
 //!
 //! This module handles generating keymap.c and config.h files,
 //! as well as background compilation of QMK firmware using `qmk compile`.
+//! Additional targets plug into firmware generation through [`emitter`].
+//! Hand-written code in [`user_code`] regions survives regeneration.
 
 pub mod builder;
+#[allow(dead_code)] // bin/lib split: the emitters are only used by the web generate jobs
+pub mod emitter;
 pub mod generator;
+pub mod size_estimate;
+pub mod user_code;
 pub mod validator;
 
 // Re-export firmware types
 pub use builder::{BuildState, BuildStatus};
+#[allow(unused_imports)] // bin/lib split: the emitters are only used by the web generate jobs
+pub use emitter::{EmitContext, EmittedFile, EmitterRegistry, FirmwareEmitter};
 pub use generator::FirmwareGenerator;
 pub use validator::FirmwareValidator;
@@ -0,0 +1,198 @@
+//! Approximate flash usage of optional firmware features.
+//!
+//! The numbers are rough averages of what each feature adds to a typical
+//! QMK build, split by MCU family. They are meant to warn before a build
+//! overflows a small MCU (ATmega32U4 has 28 KB for the application), not
+//! to replace the real size report printed by `qmk compile`.
+
+use crate::models::{FirmwareFeature, KeyboardGeometry, Layout};
+
+/// Bytes per keycode in the compiled keymap.
+const BYTES_PER_KEYCODE: u32 = 2;
+
+/// MCU family, which determines code density.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McuFamily {
+    /// 8-bit AVR (ATmega32U4, AT90USB...)
+    Avr,
+    /// 32-bit ARM (STM32, RP2040, ...)
+    Arm,
+}
+
+impl McuFamily {
+    /// Determines the family from a QMK `processor` value.
+    ///
+    /// Unknown processors are treated as ARM, which gives the larger
+    /// (more conservative) estimates.
+    #[must_use]
+    pub fn from_processor(processor: Option<&str>) -> Self {
+        let processor = processor.unwrap_or_default().to_ascii_lowercase();
+        if processor.starts_with("atmega")
+            || processor.starts_with("at90usb")
+            || processor.starts_with("attiny")
+        {
+            Self::Avr
+        } else {
+            Self::Arm
+        }
+    }
+
+    /// Approximate size of a keymap with no optional features.
+    const fn base_bytes(self) -> u32 {
+        match self {
+            Self::Avr => 16_000,
+            Self::Arm => 30_000,
+        }
+    }
+
+    /// Approximate flash cost of `feature`.
+    #[must_use]
+    pub const fn feature_bytes(self, feature: FirmwareFeature) -> u32 {
+        match (self, feature) {
+            (Self::Avr, FirmwareFeature::RgbMatrix) => 9_000,
+            (Self::Arm, FirmwareFeature::RgbMatrix) => 12_000,
+            (Self::Avr, FirmwareFeature::Audio) => 3_500,
+            (Self::Arm, FirmwareFeature::Audio) => 5_000,
+            (Self::Avr, FirmwareFeature::Console) => 1_600,
+            (Self::Arm, FirmwareFeature::Console) => 2_000,
+            (Self::Avr, FirmwareFeature::MouseKeys) => 2_000,
+            (Self::Arm, FirmwareFeature::MouseKeys) => 2_500,
+            (Self::Avr, FirmwareFeature::Unicode) => 1_100,
+            (Self::Arm, FirmwareFeature::Unicode) => 1_500,
+        }
+    }
+}
+
+/// Flash available to the firmware (excluding the bootloader), if known.
+#[must_use]
+pub fn flash_capacity(processor: &str) -> Option<u32> {
+    let capacity = match processor.to_ascii_lowercase().as_str() {
+        "atmega32u4" | "atmega32u2" => 28_672,
+        "atmega16u4" | "atmega16u2" => 12_288,
+        "at90usb646" | "at90usb647" => 61_440,
+        "at90usb1286" | "at90usb1287" => 126_976,
+        "stm32f072" => 131_072,
+        "stm32f303" | "stm32f401" => 262_144,
+        "stm32f411" => 524_288,
+        "rp2040" => 2_097_152,
+        _ => return None,
+    };
+    Some(capacity)
+}
+
+/// The processor a QMK converter replaces the board's controller with.
+///
+/// Most converters (kb2040, elite_pi, liatris, rp2040_ce, ...) target
+/// RP2040 Pro Micro replacements.
+#[must_use]
+pub fn converter_processor(converter: &str) -> &'static str {
+    match converter {
+        "proton_c" => "STM32F303",
+        "bonsai_c4" | "stemcell" => "STM32F411",
+        _ => "RP2040",
+    }
+}
+
+/// Estimated cost of one optional feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeatureEstimate {
+    /// The feature
+    pub feature: FirmwareFeature,
+    /// Whether the feature ends up in the build
+    pub enabled: bool,
+    /// Approximate flash cost when enabled
+    pub bytes: u32,
+}
+
+/// Approximate firmware size for a layout on its keyboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeEstimate {
+    /// Keyboard processor, if known
+    pub processor: Option<String>,
+    /// Core firmware plus the compiled keymap
+    pub base_bytes: u32,
+    /// Per-feature costs, in `FirmwareFeature::ALL` order
+    pub features: Vec<FeatureEstimate>,
+    /// Flash available to the firmware, if the processor is known
+    pub capacity_bytes: Option<u32>,
+}
+
+impl SizeEstimate {
+    /// Base size plus every enabled feature.
+    #[must_use]
+    pub fn total_bytes(&self) -> u32 {
+        self.base_bytes
+            + self
+                .features
+                .iter()
+                .filter(|estimate| estimate.enabled)
+                .map(|estimate| estimate.bytes)
+                .sum::<u32>()
+    }
+
+    /// Returns the estimate for `feature`.
+    #[must_use]
+    pub fn feature(&self, feature: FirmwareFeature) -> Option<&FeatureEstimate> {
+        self.features
+            .iter()
+            .find(|estimate| estimate.feature == feature)
+    }
+
+    /// Returns true if the estimate exceeds the known flash capacity.
+    #[must_use]
+    pub fn exceeds_capacity(&self) -> bool {
+        self.capacity_bytes
+            .is_some_and(|capacity| self.total_bytes() > capacity)
+    }
+}
+
+/// Returns whether `feature` ends up in the build: the layout override if
+/// set, otherwise the keyboard default.
+#[must_use]
+pub fn is_enabled(layout: &Layout, geometry: &KeyboardGeometry, feature: FirmwareFeature) -> bool {
+    layout.firmware_features.get(feature).unwrap_or_else(|| {
+        geometry
+            .features
+            .get(feature.info_json_key())
+            .copied()
+            .unwrap_or(false)
+    })
+}
+
+/// Estimates the firmware size of `layout` built for `geometry`'s keyboard.
+///
+/// A layout converter replaces the keyboard's own processor.
+#[must_use]
+pub fn estimate(layout: &Layout, geometry: &KeyboardGeometry) -> SizeEstimate {
+    let processor = layout.metadata.converter.as_deref().map_or_else(
+        || geometry.processor.clone(),
+        |converter| Some(converter_processor(converter).to_string()),
+    );
+    let family = McuFamily::from_processor(processor.as_deref());
+    let keycodes: usize = layout.layers.iter().map(|layer| layer.keys.len()).sum();
+    let keymap_bytes =
+        u32::try_from(keycodes).unwrap_or(u32::MAX / BYTES_PER_KEYCODE) * BYTES_PER_KEYCODE;
+
+    SizeEstimate {
+        processor: processor.clone(),
+        base_bytes: family.base_bytes() + keymap_bytes,
+        features: FirmwareFeature::ALL
+            .into_iter()
+            .map(|feature| FeatureEstimate {
+                feature,
+                enabled: is_enabled(layout, geometry, feature),
+                bytes: family.feature_bytes(feature),
+            })
+            .collect(),
+        capacity_bytes: processor.as_deref().and_then(flash_capacity),
+    }
+}
+
+/// Formats a byte count as kilobytes with one decimal (e.g. "9.0 KB").
+#[must_use]
+pub fn format_kb(bytes: u32) -> String {
+    format!("{:.1} KB", f64::from(bytes) / 1024.0)
+}
+
+#[cfg(test)]
+mod tests;
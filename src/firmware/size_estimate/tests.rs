@@ -0,0 +1,121 @@
+//! Tests for size_estimate.
+
+use super::*;
+use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+
+/// One layer of 10 keys on a board with the given processor.
+fn create_test_setup(processor: Option<&str>) -> (Layout, KeyboardGeometry) {
+    let mut layout = Layout::new("Test").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+    for col in 0..10 {
+        layer.add_key(KeyDefinition::new(Position::new(0, col), "KC_A"));
+    }
+    layout.add_layer(layer).unwrap();
+
+    let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 1, 10);
+    geometry.processor = processor.map(str::to_string);
+    (layout, geometry)
+}
+
+#[test]
+fn test_mcu_family_from_processor() {
+    assert_eq!(
+        McuFamily::from_processor(Some("atmega32u4")),
+        McuFamily::Avr
+    );
+    assert_eq!(
+        McuFamily::from_processor(Some("AT90USB1286")),
+        McuFamily::Avr
+    );
+    assert_eq!(McuFamily::from_processor(Some("STM32F411")), McuFamily::Arm);
+    assert_eq!(McuFamily::from_processor(None), McuFamily::Arm);
+}
+
+#[test]
+fn test_flash_capacity_known_and_unknown() {
+    assert_eq!(flash_capacity("atmega32u4"), Some(28_672));
+    assert_eq!(flash_capacity("STM32F411"), Some(524_288));
+    assert_eq!(flash_capacity("mystery_mcu"), None);
+}
+
+#[test]
+fn test_estimate_uses_keyboard_defaults() {
+    let (layout, mut geometry) = create_test_setup(Some("atmega32u4"));
+    geometry.features.insert("rgb_matrix".to_string(), true);
+    geometry.features.insert("console".to_string(), false);
+
+    let estimate = estimate(&layout, &geometry);
+
+    assert_eq!(estimate.base_bytes, 16_000 + 10 * 2);
+    assert!(
+        estimate
+            .feature(FirmwareFeature::RgbMatrix)
+            .unwrap()
+            .enabled
+    );
+    assert!(!estimate.feature(FirmwareFeature::Console).unwrap().enabled);
+    assert!(!estimate.feature(FirmwareFeature::Audio).unwrap().enabled);
+    assert_eq!(estimate.total_bytes(), 16_020 + 9_000);
+    assert_eq!(estimate.capacity_bytes, Some(28_672));
+    assert!(!estimate.exceeds_capacity());
+}
+
+#[test]
+fn test_layout_overrides_win_over_keyboard_defaults() {
+    let (mut layout, mut geometry) = create_test_setup(Some("atmega32u4"));
+    geometry.features.insert("rgb_matrix".to_string(), true);
+    layout
+        .firmware_features
+        .set(FirmwareFeature::RgbMatrix, Some(false));
+    layout
+        .firmware_features
+        .set(FirmwareFeature::MouseKeys, Some(true));
+
+    assert!(!is_enabled(&layout, &geometry, FirmwareFeature::RgbMatrix));
+    assert!(is_enabled(&layout, &geometry, FirmwareFeature::MouseKeys));
+    assert_eq!(estimate(&layout, &geometry).total_bytes(), 16_020 + 2_000);
+}
+
+#[test]
+fn test_estimate_flags_overflow_on_small_mcu() {
+    let (mut layout, geometry) = create_test_setup(Some("atmega32u4"));
+    for feature in FirmwareFeature::ALL {
+        layout.firmware_features.set(feature, Some(true));
+    }
+
+    let estimate = estimate(&layout, &geometry);
+
+    assert!(estimate.total_bytes() > 28_672);
+    assert!(estimate.exceeds_capacity());
+}
+
+#[test]
+fn test_unknown_processor_has_no_capacity() {
+    let (mut layout, geometry) = create_test_setup(None);
+    for feature in FirmwareFeature::ALL {
+        layout.firmware_features.set(feature, Some(true));
+    }
+
+    let estimate = estimate(&layout, &geometry);
+
+    assert_eq!(estimate.capacity_bytes, None);
+    assert!(!estimate.exceeds_capacity());
+}
+
+#[test]
+fn test_format_kb() {
+    assert_eq!(format_kb(9_216), "9.0 KB");
+    assert_eq!(format_kb(28_672), "28.0 KB");
+}
+
+#[test]
+fn test_converter_replaces_keyboard_processor() {
+    let (mut layout, geometry) = create_test_setup(Some("atmega32u4"));
+    layout.metadata.converter = Some("kb2040".to_string());
+
+    let estimate = estimate(&layout, &geometry);
+
+    assert_eq!(estimate.processor.as_deref(), Some("RP2040"));
+    assert_eq!(estimate.capacity_bytes, Some(2_097_152));
+    assert_eq!(converter_processor("proton_c"), "STM32F303");
+}
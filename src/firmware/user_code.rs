@@ -0,0 +1,87 @@
+//! Protected user code regions in generated firmware files.
+//!
+//! Generated `keymap.c` and `config.h` end with an empty region:
+//!
+//! ```c
+//! // LAZYQMK:BEGIN user
+//! // LAZYQMK:END user
+//! ```
+//!
+//! Anything written between the markers is hand-maintained. Before a
+//! generated file overwrites an existing one, the old region content is
+//! copied into the new file so the additions survive regeneration.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Line that opens the user region.
+pub const BEGIN_MARKER: &str = "// LAZYQMK:BEGIN user";
+
+/// Line that closes the user region.
+pub const END_MARKER: &str = "// LAZYQMK:END user";
+
+/// Returns an empty user region, ready to append to a generated file.
+#[must_use]
+pub fn empty_region() -> String {
+    format!("{BEGIN_MARKER}\n{END_MARKER}\n")
+}
+
+/// Finds the byte range between the marker lines.
+///
+/// The range starts after the begin marker's line break and ends at the
+/// start of the end marker line. Returns `None` unless both markers are
+/// present in order.
+fn region_bounds(content: &str) -> Option<(usize, usize)> {
+    let mut offset = 0;
+    let mut start = None;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        match start {
+            None if trimmed == BEGIN_MARKER => start = Some(offset + line.len()),
+            Some(start) if trimmed == END_MARKER => return Some((start, offset)),
+            _ => {}
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Returns the text between the markers, or `None` if there is no region.
+#[must_use]
+pub fn extract(content: &str) -> Option<&str> {
+    region_bounds(content).map(|(start, end)| &content[start..end])
+}
+
+/// Copies the user region of `previous` into `generated`.
+///
+/// Returns `generated` unchanged if either file lacks a complete region,
+/// so a file whose markers were deleted by hand is simply regenerated.
+#[must_use]
+pub fn preserve(generated: &str, previous: &str) -> String {
+    let (Some((start, end)), Some(user_code)) = (region_bounds(generated), extract(previous))
+    else {
+        return generated.to_string();
+    };
+
+    let mut merged = String::with_capacity(generated.len() + user_code.len());
+    merged.push_str(&generated[..start]);
+    merged.push_str(user_code);
+    merged.push_str(&generated[end..]);
+    merged
+}
+
+/// Copies the user region of the file at `path`, if it exists, into
+/// `generated`.
+pub fn preserve_from_file(generated: &str, path: &Path) -> Result<String> {
+    if !path.exists() {
+        return Ok(generated.to_string());
+    }
+    let previous =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(preserve(generated, &previous))
+}
+
+#[cfg(test)]
+mod tests;
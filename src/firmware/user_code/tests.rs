@@ -0,0 +1,77 @@
+//! Tests for user_code.
+
+use super::*;
+
+fn generated() -> String {
+    format!("#pragma once\n\n#define A 1\n\n{}", empty_region())
+}
+
+#[test]
+fn test_extract_empty_region() {
+    assert_eq!(extract(&generated()), Some(""));
+}
+
+#[test]
+fn test_extract_requires_both_markers() {
+    assert_eq!(extract("// LAZYQMK:BEGIN user\n#define X 1\n"), None);
+    assert_eq!(
+        extract("// LAZYQMK:END user\n// LAZYQMK:BEGIN user\n"),
+        None
+    );
+    assert_eq!(extract("#define X 1\n"), None);
+}
+
+#[test]
+fn test_preserve_copies_user_code() {
+    let previous = "#pragma once\n\n#define A 0\n\n// LAZYQMK:BEGIN user\n#define MY_FLAG 1\n\nvoid my_fn(void) {}\n// LAZYQMK:END user\n";
+    let merged = preserve(&generated(), previous);
+
+    assert!(merged.contains("#define A 1\n"));
+    assert!(!merged.contains("#define A 0"));
+    assert_eq!(
+        extract(&merged),
+        Some("#define MY_FLAG 1\n\nvoid my_fn(void) {}\n")
+    );
+    assert!(merged.ends_with("// LAZYQMK:END user\n"));
+}
+
+#[test]
+fn test_preserve_accepts_indented_markers() {
+    let previous = "  // LAZYQMK:BEGIN user\n#define X 1\n  // LAZYQMK:END user";
+    assert_eq!(
+        extract(&preserve(&generated(), previous)),
+        Some("#define X 1\n")
+    );
+}
+
+#[test]
+fn test_preserve_without_previous_region() {
+    let merged = preserve(&generated(), "#define OLD 1\n");
+    assert_eq!(merged, generated());
+}
+
+#[test]
+fn test_preserve_without_generated_region() {
+    let previous = "// LAZYQMK:BEGIN user\n#define X 1\n// LAZYQMK:END user\n";
+    assert_eq!(preserve("#define A 1\n", previous), "#define A 1\n");
+}
+
+#[test]
+fn test_preserve_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.h");
+
+    // No previous file
+    assert_eq!(
+        preserve_from_file(&generated(), &path).unwrap(),
+        generated()
+    );
+
+    fs::write(
+        &path,
+        "// LAZYQMK:BEGIN user\n#define X 1\n// LAZYQMK:END user\n",
+    )
+    .unwrap();
+    let merged = preserve_from_file(&generated(), &path).unwrap();
+    assert_eq!(extract(&merged), Some("#define X 1\n"));
+}
@@ -3,7 +3,9 @@
 use crate::keycode_db::KeycodeDb;
 use crate::models::keyboard_geometry::KeyboardGeometry;
 use crate::models::layout::Layout;
+use crate::models::position_names::PositionNames;
 use crate::models::visual_layout_mapping::VisualLayoutMapping;
+use crate::models::MacroStep;
 use anyhow::Result;
 use std::collections::HashSet;
 
@@ -47,29 +49,8 @@ impl<'a> FirmwareValidator<'a> {
         let mut report = ValidationReport::new();
 
         // Validate layout structure first
-        if let Err(e) = self.layout.validate() {
-            let error_msg = e.to_string();
-            // Determine error kind based on the error message
-            let kind = if error_msg.contains("Duplicate position") {
-                ValidationErrorKind::DuplicatePosition
-            } else if error_msg.contains("at least one layer") {
-                ValidationErrorKind::EmptyLayer
-            } else if error_msg.contains("must have the same number of keys")
-                && error_msg.contains("has 0,")
-            {
-                // Empty layer will show as "has 0, expected N"
-                ValidationErrorKind::EmptyLayer
-            } else {
-                // Default to MismatchedKeyCount for other structural issues
-                ValidationErrorKind::MismatchedKeyCount
-            };
-
-            report.add_error(
-                ValidationError::new(kind, format!("Layout validation failed: {e}"))
-                    .with_suggestion(
-                        "Check that all layers have keys and no gaps in layer numbers",
-                    ),
-            );
+        if let Some(error) = structural_error(self.layout) {
+            report.add_error(error);
             return Ok(report);
         }
 
@@ -81,12 +62,56 @@ impl<'a> FirmwareValidator<'a> {
         // Check matrix coverage
         self.validate_matrix_coverage(&mut report);
 
-        // Check for orphaned tap dances
-        self.validate_tap_dances(&mut report);
+        // Check keycodes tapped by macros
+        add_macro_keycode_errors(&mut report, self.keycode_db, self.layout);
+
+        // Check keycode combo triggers and outputs
+        add_keycode_combo_errors(&mut report, self.keycode_db, self.layout);
+
+        // Check keycodes bound to encoder rotation
+        add_encoder_keycode_errors(&mut report, self.keycode_db, self.layout);
+
+        report.annotate_positions(&PositionNames::build(self.geometry));
 
         Ok(report)
     }
 
+    /// Validates the parts of a layout that do not need keyboard geometry.
+    ///
+    /// Checks layout structure and keycodes. Used to fail fast before the
+    /// keyboard geometry is available (e.g. before starting a job). Layout
+    /// lints such as unused tap dances are in [`crate::services::lints`].
+    #[allow(dead_code)] // Used by the web job validation gate (lib target only)
+    #[must_use]
+    pub fn validate_without_geometry(layout: &Layout, keycode_db: &KeycodeDb) -> ValidationReport {
+        let mut report = ValidationReport::new();
+
+        if let Some(error) = structural_error(layout) {
+            report.add_error(error);
+            return report;
+        }
+
+        for (layer_idx, layer) in layout.layers.iter().enumerate() {
+            for key in &layer.keys {
+                add_keycode_error(
+                    &mut report,
+                    keycode_db,
+                    layout,
+                    layer_idx,
+                    key.position.row,
+                    key.position.col,
+                    &key.keycode,
+                );
+            }
+        }
+
+        add_macro_keycode_errors(&mut report, keycode_db, layout);
+        add_keycode_combo_errors(&mut report, keycode_db, layout);
+        add_encoder_keycode_errors(&mut report, keycode_db, layout);
+
+        report
+    }
+
     /// Validates a single layer.
     fn validate_layer(
         &self,
@@ -156,9 +181,6 @@ impl<'a> FirmwareValidator<'a> {
     }
 
     /// Validates a single keycode at a **visual** position.
-    ///
-    /// `row` and `col` are visual-grid coordinates used only for error reporting;
-    /// the validation itself is purely keycode-based.
     fn validate_keycode(
         &self,
         report: &mut ValidationReport,
@@ -167,30 +189,15 @@ impl<'a> FirmwareValidator<'a> {
         col: u8,
         keycode: &str,
     ) {
-        if !self.keycode_db.is_valid(keycode) {
-            // Try to find similar keycodes for suggestion
-            let suggestions = self.keycode_db.search(keycode);
-            let suggestion_text = if suggestions.is_empty() {
-                "Check the keycode database for valid codes".to_string()
-            } else {
-                let similar: Vec<&str> = suggestions
-                    .iter()
-                    .take(3)
-                    .map(|k| k.code.as_str())
-                    .collect();
-                format!("Did you mean one of: {}", similar.join(", "))
-            };
-
-            report.add_error(
-                ValidationError::new(
-                    ValidationErrorKind::InvalidKeycode,
-                    format!("Invalid keycode '{keycode}'"),
-                )
-                .with_layer(layer)
-                .with_position(row, col)
-                .with_suggestion(suggestion_text),
-            );
-        }
+        add_keycode_error(
+            report,
+            self.keycode_db,
+            self.layout,
+            layer,
+            row,
+            col,
+            keycode,
+        );
     }
 
     /// Validates that a **visual** position `(row, col)` maps to a valid matrix coordinate.
@@ -256,16 +263,164 @@ impl<'a> FirmwareValidator<'a> {
             )));
         }
     }
+}
 
-    /// Validates tap dance definitions.
-    fn validate_tap_dances(&self, report: &mut ValidationReport) {
-        // Check for orphaned tap dances (defined but never used)
-        let orphaned = self.layout.get_orphaned_tap_dances();
-        for td_name in orphaned {
-            report.add_warning(ValidationWarning::new(format!(
-                "Tap dance '{}' is defined but never used in any layer",
-                td_name
-            )));
+/// Maps a `Layout::validate` failure to a structured validation error.
+fn structural_error(layout: &Layout) -> Option<ValidationError> {
+    let e = layout.validate().err()?;
+    let error_msg = e.to_string();
+    // Determine error kind based on the error message
+    let kind = if error_msg.contains("Duplicate position") {
+        ValidationErrorKind::DuplicatePosition
+    } else if error_msg.contains("at least one layer") {
+        ValidationErrorKind::EmptyLayer
+    } else if error_msg.contains("must have the same number of keys")
+        && error_msg.contains("has 0,")
+    {
+        // Empty layer will show as "has 0, expected N"
+        ValidationErrorKind::EmptyLayer
+    } else {
+        // Default to MismatchedKeyCount for other structural issues
+        ValidationErrorKind::MismatchedKeyCount
+    };
+
+    Some(
+        ValidationError::new(kind, format!("Layout validation failed: {e}"))
+            .with_suggestion("Check that all layers have keys and no gaps in layer numbers"),
+    )
+}
+
+/// Adds an `InvalidKeycode` error for `keycode` at a **visual** position if it is unknown.
+///
+/// `row` and `col` are visual-grid coordinates used only for error reporting;
+/// the validation itself is purely keycode-based.
+fn add_keycode_error(
+    report: &mut ValidationReport,
+    keycode_db: &KeycodeDb,
+    layout: &Layout,
+    layer: usize,
+    row: u8,
+    col: u8,
+    keycode: &str,
+) {
+    if is_known_keycode(keycode_db, layout, keycode) {
+        return;
+    }
+
+    // Try to find similar keycodes for suggestion
+    let suggestions = keycode_db.search(keycode);
+    let suggestion_text = if suggestions.is_empty() {
+        "Check the keycode database for valid codes".to_string()
+    } else {
+        let similar: Vec<&str> = suggestions
+            .iter()
+            .take(3)
+            .map(|k| k.code.as_str())
+            .collect();
+        format!("Did you mean one of: {}", similar.join(", "))
+    };
+
+    report.add_error(
+        ValidationError::new(
+            ValidationErrorKind::InvalidKeycode,
+            format!("Invalid keycode '{keycode}'"),
+        )
+        .with_layer(layer)
+        .with_position(row, col)
+        .with_suggestion(suggestion_text),
+    );
+}
+
+/// Returns true if `keycode` is in the database, one of the layout's
+/// custom keycodes or an enabled editing helper keycode.
+fn is_known_keycode(keycode_db: &KeycodeDb, layout: &Layout, keycode: &str) -> bool {
+    keycode_db.is_valid(keycode)
+        || layout.get_custom_keycode(keycode).is_some()
+        || layout.editing_helpers.is_enabled_keycode(keycode)
+}
+
+/// Adds an `InvalidKeycode` error for every unknown keycode tapped by a macro.
+fn add_macro_keycode_errors(
+    report: &mut ValidationReport,
+    keycode_db: &KeycodeDb,
+    layout: &Layout,
+) {
+    for macro_def in &layout.macros {
+        for step in &macro_def.steps {
+            if let MacroStep::Tap(keycode) = step {
+                if !keycode_db.is_valid(keycode) {
+                    report.add_error(
+                        ValidationError::new(
+                            ValidationErrorKind::InvalidKeycode,
+                            format!("Invalid keycode '{keycode}' in macro '{}'", macro_def.name),
+                        )
+                        .with_suggestion("Edit the macro in the macro editor (Shift+M)"),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Adds errors for keycode combos with an unknown output keycode or a trigger
+/// position that has no key on the base layer.
+fn add_keycode_combo_errors(
+    report: &mut ValidationReport,
+    keycode_db: &KeycodeDb,
+    layout: &Layout,
+) {
+    for (idx, combo) in layout.combo_settings.keycode_combos.iter().enumerate() {
+        let combo_num = idx + 1;
+        if !is_known_keycode(keycode_db, layout, &combo.keycode) {
+            report.add_error(
+                ValidationError::new(
+                    ValidationErrorKind::InvalidKeycode,
+                    format!("Invalid keycode '{}' in combo {combo_num}", combo.keycode),
+                )
+                .with_suggestion("Edit the combo in the combo editor (Shift+O)"),
+            );
+        }
+
+        let base_layer = layout.layers.first();
+        for position in &combo.keys {
+            if base_layer
+                .and_then(|layer| layer.get_key(*position))
+                .is_none()
+            {
+                report.add_error(
+                    ValidationError::new(
+                        ValidationErrorKind::MissingPosition,
+                        format!("Combo {combo_num} uses a key that is not on the base layer"),
+                    )
+                    .with_layer(0)
+                    .with_position(position.row, position.col),
+                );
+            }
+        }
+    }
+}
+
+/// Adds an `InvalidKeycode` error for every unknown keycode in a layer's
+/// encoder map.
+fn add_encoder_keycode_errors(
+    report: &mut ValidationReport,
+    keycode_db: &KeycodeDb,
+    layout: &Layout,
+) {
+    for (layer_idx, layer) in layout.layers.iter().enumerate() {
+        for (encoder, binding) in layer.encoder_map.iter() {
+            for keycode in [&binding.ccw, &binding.cw] {
+                if !is_known_keycode(keycode_db, layout, keycode) {
+                    report.add_error(
+                        ValidationError::new(
+                            ValidationErrorKind::InvalidKeycode,
+                            format!("Invalid keycode '{keycode}' on encoder {encoder}"),
+                        )
+                        .with_layer(layer_idx)
+                        .with_suggestion("Edit the encoder in the encoder editor (Shift+N)"),
+                    );
+                }
+            }
         }
     }
 }
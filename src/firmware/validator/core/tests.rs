@@ -4,7 +4,6 @@
 
 use super::*;
 
-use super::*;
 use crate::models::keyboard_geometry::KeyGeometry;
 use crate::models::layer::{KeyDefinition, Layer, Position};
 use crate::models::RgbColor;
@@ -52,6 +51,20 @@ fn test_invalid_keycode() {
     assert_eq!(report.errors[0].kind, ValidationErrorKind::InvalidKeycode);
 }
 
+#[test]
+fn test_errors_annotated_with_position_name() {
+    let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
+    layout.layers[0].keys[0].keycode = "INVALID_KEY".to_string();
+
+    let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+    let report = validator.validate().unwrap();
+
+    assert_eq!(report.errors.len(), 1);
+    let name = report.errors[0].position_name.as_deref();
+    assert!(name.is_some(), "error should carry a position name");
+    assert!(report.errors[0].to_string().contains(name.unwrap()));
+}
+
 #[test]
 fn test_duplicate_position() {
     let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
@@ -104,29 +117,6 @@ fn test_validation_report_format() {
     assert!(message.contains("Test warning"));
 }
 
-#[test]
-fn test_orphaned_tap_dance_warning() {
-    use crate::models::layout::TapDanceAction;
-
-    let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
-
-    // Add a tap dance but don't use it anywhere
-    let mut tap_dance = TapDanceAction::new("unused_td", "KC_A");
-    tap_dance = tap_dance.with_double_tap("KC_B");
-    layout.add_tap_dance(tap_dance).unwrap();
-
-    let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
-    let report = validator.validate().unwrap();
-
-    // Should be valid (warnings don't block validation)
-    assert!(report.is_valid());
-    // Should have exactly one warning about the orphaned tap dance
-    assert_eq!(report.warnings.len(), 1);
-    assert!(report.warnings[0]
-        .message
-        .contains("Tap dance 'unused_td' is defined but never used"));
-}
-
 #[test]
 fn test_used_tap_dance_no_warning() {
     use crate::models::layout::TapDanceAction;
@@ -152,3 +142,79 @@ fn test_used_tap_dance_no_warning() {
         .iter()
         .any(|w| w.message.contains("Tap dance")));
 }
+
+#[test]
+fn test_validate_without_geometry_reports_invalid_keycode() {
+    let (mut layout, _geometry, _mapping, keycode_db) = create_test_setup();
+    layout.layers[0].keys[1].keycode = "KC_NOT_A_KEY".to_string();
+
+    let report = FirmwareValidator::validate_without_geometry(&layout, &keycode_db);
+
+    assert!(!report.is_valid());
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].kind, ValidationErrorKind::InvalidKeycode);
+    assert_eq!(report.errors[0].layer, Some(0));
+    assert_eq!(report.errors[0].col, Some(1));
+}
+
+#[test]
+fn test_validate_without_geometry_accepts_valid_layout() {
+    let (layout, _geometry, _mapping, keycode_db) = create_test_setup();
+
+    let report = FirmwareValidator::validate_without_geometry(&layout, &keycode_db);
+
+    assert!(report.is_valid());
+}
+
+#[test]
+fn test_invalid_encoder_keycode() {
+    let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
+    layout.layers[0]
+        .encoder_map
+        .set(0, crate::models::EncoderBinding::new("KC_VOLD", "KC_NOPE"));
+
+    let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+    let report = validator.validate().unwrap();
+
+    assert!(!report.is_valid());
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].kind, ValidationErrorKind::InvalidKeycode);
+    assert_eq!(report.errors[0].layer, Some(0));
+    assert!(report.errors[0].message.contains("encoder 0"));
+}
+
+#[test]
+fn test_custom_keycode_accepted_when_defined() {
+    let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
+    layout.layers[0].keys[1].keycode = "CK_EMAIL".to_string();
+
+    let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+    let report = validator.validate().unwrap();
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].kind, ValidationErrorKind::InvalidKeycode);
+
+    layout
+        .custom_keycodes
+        .push(crate::models::CustomKeycode::new(
+            "CK_EMAIL",
+            "SEND_STRING(\"me@example.com\");",
+            "",
+        ));
+    let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+    assert!(validator.validate().unwrap().is_valid());
+}
+
+#[test]
+fn test_editing_helper_keycode_accepted_when_enabled() {
+    let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
+    layout.layers[0].keys[1].keycode = "SELWORD".to_string();
+
+    let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+    let report = validator.validate().unwrap();
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].kind, ValidationErrorKind::InvalidKeycode);
+
+    layout.editing_helpers.select_word = true;
+    let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+    assert!(validator.validate().unwrap().is_valid());
+}
@@ -13,3 +13,5 @@ mod report;
 
 pub use core::FirmwareValidator;
 pub use report::ValidationErrorKind;
+#[allow(unused_imports)] // Used by the web job validation gate (lib target only)
+pub use report::{ValidationError, ValidationReport, ValidationWarning};
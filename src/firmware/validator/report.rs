@@ -4,6 +4,8 @@
 // Allow format! appended to String - more readable for building messages
 #![allow(clippy::format_push_string)]
 
+use crate::models::{Position, PositionNames};
+
 /// Validation result with specific errors and warnings.
 #[derive(Debug, Clone)]
 pub struct ValidationReport {
@@ -39,6 +41,15 @@ impl ValidationReport {
         self.warnings.push(warning);
     }
 
+    /// Attaches human-readable key names to all errors tied to a key position.
+    pub fn annotate_positions(&mut self, names: &PositionNames) {
+        for error in &mut self.errors {
+            if let (Some(row), Some(col)) = (error.row, error.col) {
+                error.position_name = names.get(Position::new(row, col)).map(str::to_string);
+            }
+        }
+    }
+
     /// Formats the report as a user-friendly error message.
     #[must_use]
     pub fn format_message(&self) -> String {
@@ -83,6 +94,8 @@ pub struct ValidationError {
     pub message: String,
     /// Optional suggestion for fixing the error
     pub suggestion: Option<String>,
+    /// Human-readable key name (e.g., "left home row pinky"), if known
+    pub position_name: Option<String>,
 }
 
 impl ValidationError {
@@ -95,6 +108,7 @@ impl ValidationError {
             col: None,
             message: message.into(),
             suggestion: None,
+            position_name: None,
         }
     }
 
@@ -125,11 +139,12 @@ impl ValidationError {
 impl std::fmt::Display for ValidationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let (Some(layer), Some(row), Some(col)) = (self.layer, self.row, self.col) {
-            write!(
-                f,
-                "[Layer {} ({}, {})] {}: {}",
-                layer, row, col, self.kind, self.message
-            )?;
+            if let Some(name) = &self.position_name {
+                write!(f, "[Layer {layer} ({row}, {col}), {name}] ")?;
+            } else {
+                write!(f, "[Layer {layer} ({row}, {col})] ")?;
+            }
+            write!(f, "{}: {}", self.kind, self.message)?;
         } else if let Some(layer) = self.layer {
             write!(f, "[Layer {}] {}: {}", layer, self.kind, self.message)?;
         } else {
@@ -145,7 +160,8 @@ impl std::fmt::Display for ValidationError {
 }
 
 /// Types of validation errors.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ValidationErrorKind {
     /// Keycode not recognized by QMK
     InvalidKeycode,
@@ -6,12 +6,19 @@ use std::collections::HashMap;
 
 use super::{
     CategoriesIndex, CategoryFile, KeycodeCategory, KeycodeDefinition, KeycodeParam,
-    LanguageDefinition, LanguageFile, LanguageKeycodes, TapHoldInfo, TapHoldType,
+    LanguageDefinition, LanguageFile, LanguageKeycodes, SearchKey, TapHoldInfo, TapHoldType,
 };
 use crate::keycode_db::KeycodeDb;
+use crate::models::{CustomKeycode, EditingHelper};
 
 #[allow(dead_code)] // bin/lib split: heavily used by tests + CLI subcommands
 impl KeycodeDb {
+    /// Category of the layout's custom keycodes.
+    pub const CUSTOM_CATEGORY: &'static str = "custom";
+
+    /// Category of the layout's enabled editing helper keycodes.
+    pub const HELPERS_CATEGORY: &'static str = "helpers";
+
     /// Loads the keycode database from embedded category files.
     pub fn load() -> Result<Self> {
         // Load categories index
@@ -48,6 +55,8 @@ impl KeycodeDb {
             ("advanced", include_str!("categories/advanced.json")),
             ("magic", include_str!("categories/magic.json")),
             ("tap_dance", include_str!("categories/tap_dance.json")),
+            ("macro", include_str!("categories/macro.json")),
+            ("unicode", include_str!("categories/unicode.json")),
         ];
 
         for (cat_id, json_data) in category_files {
@@ -80,6 +89,7 @@ impl KeycodeDb {
         // validation recognizes DE_/FR_/… codes.
         let languages = Self::load_languages()?;
         let mut categories = index.categories;
+        let mut language_ranges = Vec::with_capacity(languages.len());
 
         for lang in &languages {
             // Add a synthetic category for the language (helps picker grouping)
@@ -94,20 +104,38 @@ impl KeycodeDb {
             });
 
             // Insert language keycodes into lookup and master list
+            let start = all_keycodes.len();
             for kc in &lang.keycodes {
                 let idx = all_keycodes.len();
                 lookup.insert(kc.code.clone(), idx);
                 all_keycodes.push(kc.clone());
             }
+            language_ranges.push(start..all_keycodes.len());
         }
 
-        Ok(Self {
+        let search_keys = all_keycodes.iter().map(SearchKey::new).collect();
+        let custom_start = all_keycodes.len();
+
+        let db = Self {
             keycodes: all_keycodes,
             categories,
             lookup,
             patterns,
             languages,
-        })
+            language_ranges,
+            search_keys,
+            custom_start,
+        };
+
+        // Catch mistakes in the embedded JSON during development and CI;
+        // release builds report them via `lazyqmk doctor` instead.
+        debug_assert!(
+            db.check_integrity().is_empty(),
+            "keycode database failed its self-test: {:?}",
+            db.check_integrity()
+        );
+
+        Ok(db)
     }
 
     /// Loads language-specific keycode files.
@@ -161,6 +189,57 @@ impl KeycodeDb {
         Ok(languages)
     }
 
+    /// Replaces the layout-defined keycodes known to the database.
+    ///
+    /// Custom keycodes join the `custom` category and enabled editing
+    /// helpers the `helpers` category, so they show up in searches and pass
+    /// validation until the next call. Names the embedded database already
+    /// defines are skipped.
+    pub fn set_layout_keycodes(
+        &mut self,
+        custom_keycodes: &[CustomKeycode],
+        helpers: &[EditingHelper],
+    ) {
+        for keycode in self.keycodes.drain(self.custom_start..) {
+            self.lookup.remove(&keycode.code);
+        }
+        self.search_keys.truncate(self.custom_start);
+
+        let custom = custom_keycodes.iter().map(|custom| {
+            (
+                custom.name.as_str(),
+                Self::CUSTOM_CATEGORY,
+                custom.display_description(),
+            )
+        });
+        let helpers = helpers.iter().map(|helper| {
+            (
+                helper.keycode(),
+                Self::HELPERS_CATEGORY,
+                helper.description(),
+            )
+        });
+
+        for (code, category, description) in custom.chain(helpers) {
+            if self.lookup.contains_key(code) {
+                continue;
+            }
+            let keycode = KeycodeDefinition {
+                code: code.to_string(),
+                name: code.to_string(),
+                category: category.to_string(),
+                description: Some(description.to_string()),
+                pattern: None,
+                aliases: Vec::new(),
+                params: Vec::new(),
+            };
+            self.lookup
+                .insert(keycode.code.clone(), self.keycodes.len());
+            self.search_keys.push(SearchKey::new(&keycode));
+            self.keycodes.push(keycode);
+        }
+    }
+
     /// Validates a keycode against the database.
     ///
     /// Returns true if the keycode exists or matches a pattern (e.g., MO(5)).
@@ -216,51 +295,33 @@ impl KeycodeDb {
     /// ```
     #[must_use]
     pub fn search(&self, query: &str) -> Vec<&KeycodeDefinition> {
+        self.rank(0..self.keycodes.len(), query)
+    }
+
+    /// Ranks the keycodes at `indices` against `query`, most relevant first.
+    ///
+    /// An empty query keeps every keycode in its original order.
+    fn rank(&self, indices: impl Iterator<Item = usize>, query: &str) -> Vec<&KeycodeDefinition> {
         if query.is_empty() {
-            return self.keycodes.iter().collect();
+            return indices.map(|idx| &self.keycodes[idx]).collect();
         }
 
         let query_lower = query.to_lowercase();
-        let mut results: Vec<(&KeycodeDefinition, i32)> = self
-            .keycodes
-            .iter()
-            .filter_map(|keycode| {
-                let code_lower = keycode.code.to_lowercase();
-                let name_lower = keycode.name.to_lowercase();
-                let desc_lower = keycode
-                    .description
-                    .as_ref()
-                    .map(|d| d.to_lowercase())
-                    .unwrap_or_default();
-
-                // Exact match (highest priority)
-                if code_lower == query_lower || name_lower == query_lower {
-                    return Some((keycode, 100));
-                }
-
-                // Starts with query (high priority)
-                if code_lower.starts_with(&query_lower) || name_lower.starts_with(&query_lower) {
-                    return Some((keycode, 50));
-                }
-
-                // Contains query in code or name (medium priority)
-                if code_lower.contains(&query_lower) || name_lower.contains(&query_lower) {
-                    return Some((keycode, 10));
-                }
-
-                // Contains query in description (lower priority)
-                if desc_lower.contains(&query_lower) {
-                    return Some((keycode, 5));
-                }
-
-                None
+        let mut results: Vec<(usize, i32)> = indices
+            .filter_map(|idx| {
+                self.search_keys[idx]
+                    .score(&query_lower)
+                    .map(|score| (idx, score))
             })
             .collect();
 
         // Sort by relevance (descending)
         results.sort_by_key(|b| std::cmp::Reverse(b.1));
 
-        results.into_iter().map(|(keycode, _)| keycode).collect()
+        results
+            .into_iter()
+            .map(|(idx, _)| &self.keycodes[idx])
+            .collect()
     }
 
     /// Searches for keycodes within a specific category.
@@ -276,10 +337,21 @@ impl KeycodeDb {
     /// ```
     #[must_use]
     pub fn search_in_category(&self, query: &str, category_id: &str) -> Vec<&KeycodeDefinition> {
-        self.search(query)
-            .into_iter()
-            .filter(|k| k.category == category_id)
-            .collect()
+        let indices =
+            (0..self.keycodes.len()).filter(|&idx| self.keycodes[idx].category == category_id);
+        self.rank(indices, query)
+    }
+
+    /// Searches the keycodes listed in `codes`, e.g. the picker's favorites.
+    ///
+    /// An empty query keeps the order of `codes`; codes that aren't in the
+    /// database are skipped.
+    #[must_use]
+    pub fn search_in_list(&self, query: &str, codes: &[String]) -> Vec<&KeycodeDefinition> {
+        let indices = codes
+            .iter()
+            .filter_map(|code| self.lookup.get(code).copied());
+        self.rank(indices, query)
     }
 
     /// Gets all keycodes in a category.
@@ -349,51 +421,15 @@ impl KeycodeDb {
     /// Searches for keycodes within a specific language.
     #[must_use]
     pub fn search_in_language(&self, query: &str, language_id: &str) -> Vec<&KeycodeDefinition> {
-        let keycodes = self.get_language_keycodes(language_id);
-        if query.is_empty() {
-            return keycodes;
-        }
-
-        let query_lower = query.to_lowercase();
-        let mut results: Vec<(&KeycodeDefinition, i32)> = keycodes
-            .into_iter()
-            .filter_map(|keycode| {
-                let code_lower = keycode.code.to_lowercase();
-                let name_lower = keycode.name.to_lowercase();
-                let desc_lower = keycode
-                    .description
-                    .as_ref()
-                    .map(|d| d.to_lowercase())
-                    .unwrap_or_default();
-
-                // Exact match (highest priority)
-                if code_lower == query_lower || name_lower == query_lower {
-                    return Some((keycode, 100));
-                }
-
-                // Starts with query (high priority)
-                if code_lower.starts_with(&query_lower) || name_lower.starts_with(&query_lower) {
-                    return Some((keycode, 50));
-                }
-
-                // Contains query in code or name (medium priority)
-                if code_lower.contains(&query_lower) || name_lower.contains(&query_lower) {
-                    return Some((keycode, 10));
-                }
-
-                // Contains query in description (lower priority)
-                if desc_lower.contains(&query_lower) {
-                    return Some((keycode, 5));
-                }
-
-                None
-            })
-            .collect();
-
-        // Sort by relevance (descending)
-        results.sort_by_key(|b| std::cmp::Reverse(b.1));
-
-        results.into_iter().map(|(keycode, _)| keycode).collect()
+        let Some(range) = self
+            .languages
+            .iter()
+            .position(|l| l.language.id == language_id)
+            .and_then(|idx| self.language_ranges.get(idx))
+        else {
+            return Vec::new();
+        };
+        self.rank(range.clone(), query)
     }
 
     /// Check if a keycode is parameterized (requires additional input).
@@ -5,7 +5,6 @@
 use super::*;
 
 use super::super::ParamType;
-use super::*;
 
 fn get_test_db() -> KeycodeDb {
     KeycodeDb::load().expect("Failed to load keycode database")
@@ -114,6 +113,60 @@ fn test_search_in_category() {
     assert!(nav_keys.iter().all(|k| k.category == "navigation"));
 }
 
+#[test]
+fn test_search_in_category_ranks_matches() {
+    let db = get_test_db();
+    let results = db.search_in_category("f1", "function");
+    assert_eq!(results.first().map(|k| k.code.as_str()), Some("KC_F1"));
+    assert!(results.iter().all(|k| k.category == "function"));
+}
+
+#[test]
+fn test_search_in_list_keeps_order_and_skips_unknown() {
+    let db = get_test_db();
+    let codes = vec![
+        "MT()".to_string(),
+        "NOT_A_KEYCODE".to_string(),
+        "LT()".to_string(),
+    ];
+
+    let results: Vec<&str> = db
+        .search_in_list("", &codes)
+        .iter()
+        .map(|k| k.code.as_str())
+        .collect();
+    assert_eq!(results, vec!["MT()", "LT()"]);
+
+    let results = db.search_in_list("layer", &codes);
+    assert_eq!(results.first().map(|k| k.code.as_str()), Some("LT()"));
+}
+
+#[test]
+fn test_search_in_language_matches_language_keycodes() {
+    let db = get_test_db();
+    let language = db
+        .languages()
+        .first()
+        .copied()
+        .expect("at least one language");
+    let language_id = language.id.clone();
+    let keycodes = db.get_language_keycodes(&language_id);
+    let first = keycodes.first().expect("language has keycodes");
+
+    let all = db.search_in_language("", &language_id);
+    assert_eq!(all.len(), keycodes.len());
+
+    let results = db.search_in_language(&first.code.to_lowercase(), &language_id);
+    assert_eq!(
+        results.first().map(|k| k.code.as_str()),
+        Some(first.code.as_str())
+    );
+    assert!(results
+        .iter()
+        .all(|k| keycodes.iter().any(|lk| lk.code == k.code)));
+    assert!(db.search_in_language("", "no-such-language").is_empty());
+}
+
 #[test]
 fn test_get_category_keycodes() {
     let db = get_test_db();
@@ -421,3 +474,46 @@ fn test_get_compound_layer_prefixes() {
     assert!(!prefixes.contains(&"MO"));
     assert!(!prefixes.contains(&"TG"));
 }
+
+#[test]
+fn test_set_layout_keycodes() {
+    let mut db = get_test_db();
+    let builtin_count = db.keycode_count();
+
+    db.set_layout_keycodes(
+        &[
+            CustomKeycode::new("CK_EMAIL", "SEND_STRING(\"me\");", "Type email"),
+            CustomKeycode::new("KC_A", "tap_code(KC_B);", ""),
+        ],
+        &[],
+    );
+    // Names the database already defines are skipped
+    assert_eq!(db.keycode_count(), builtin_count + 1);
+    let email = db.get("CK_EMAIL").unwrap();
+    assert_eq!(email.category, KeycodeDb::CUSTOM_CATEGORY);
+    assert_eq!(email.description.as_deref(), Some("Type email"));
+    assert!(db.is_valid("CK_EMAIL"));
+    assert!(db.search("email").iter().any(|k| k.code == "CK_EMAIL"));
+    assert_eq!(db.get_category_keycodes("custom").len(), 1);
+
+    // A later call replaces the previous set
+    db.set_layout_keycodes(
+        &[CustomKeycode::new("CK_BOOT", "reset_keyboard();", "")],
+        &[EditingHelper::SelectWord],
+    );
+    assert!(!db.is_valid("CK_EMAIL"));
+    assert_eq!(
+        db.get("CK_BOOT").unwrap().description.as_deref(),
+        Some("Custom keycode")
+    );
+    assert_eq!(
+        db.get("SELWORD").unwrap().category,
+        KeycodeDb::HELPERS_CATEGORY
+    );
+    assert_eq!(db.keycode_count(), builtin_count + 2);
+
+    db.set_layout_keycodes(&[], &[]);
+    assert_eq!(db.keycode_count(), builtin_count);
+    assert!(!db.is_valid("SELWORD"));
+    assert_eq!(db.get("KC_A").unwrap().category, "basic");
+}
@@ -4,10 +4,10 @@
 //! (tap-hold, layer-tap, mod-tap, tap-dance, etc.) suitable for rendering
 //! in both TUI and web interfaces.
 //!
-//! Note: This module is only compiled with the `web` feature. The helper
-//! functions are used by `get_display_metadata` which is called from the
-//! web API handler. Clippy may report dead code when checking the main
-//! binary because it doesn't use the web module directly.
+//! Note: The helper functions are used by `get_display_metadata` which is
+//! called from the web API handlers and the keymap-drawer export. Clippy may
+//! report dead code when checking the main binary because it doesn't use
+//! the web module directly.
 
 // Allow dead_code because these functions are used by get_display_metadata
 // which is called from web::mod.rs, but clippy checking the main binary
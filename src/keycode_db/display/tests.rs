@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 fn get_test_db() -> KeycodeDb {
     KeycodeDb::load().expect("Failed to load keycode database")
 }
@@ -0,0 +1,149 @@
+//! Self-test for the embedded keycode database.
+//!
+//! The category and language JSON files are hand-edited, so mistakes such as
+//! a code listed twice, a keycode pointing at a category missing from
+//! `categories.json`, or a pattern that does not compile would otherwise only
+//! show up as silently wrong lookups. [`KeycodeDb::check_integrity`] finds
+//! them; it runs on every load in debug builds and from `lazyqmk doctor`.
+
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::{KeycodeCategory, KeycodeDb, KeycodeDefinition, LanguageDefinition};
+
+/// Kind of problem found by the keycode database self-test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityIssueKind {
+    /// A code or alias is defined by more than one keycode
+    DuplicateCode,
+    /// A keycode references a category that does not exist
+    DanglingCategory,
+    /// A keycode's validation pattern is not a valid regex
+    InvalidPattern,
+}
+
+/// A single problem found by [`KeycodeDb::check_integrity`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IntegrityIssue {
+    /// What is wrong
+    pub kind: IntegrityIssueKind,
+    /// Keycode the problem was found on
+    pub code: String,
+    /// Human-readable explanation
+    pub detail: String,
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.detail)
+    }
+}
+
+/// Snapshot of the merged keycode database (categories, languages and every
+/// keycode after language files are folded in), for inspection.
+#[allow(dead_code)] // Used by the web dump endpoint (lib target only)
+#[derive(Debug, Clone, Serialize)]
+pub struct KeycodeDbDump<'a> {
+    /// All categories, including synthetic `lang_*` categories
+    pub categories: &'a [KeycodeCategory],
+    /// Language layouts merged into the database
+    pub languages: Vec<&'a LanguageDefinition>,
+    /// Every keycode definition in lookup order
+    pub keycodes: &'a [KeycodeDefinition],
+    /// Problems reported by the self-test
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl KeycodeDb {
+    /// Checks the database for duplicate codes, dangling category references
+    /// and invalid regex patterns.
+    ///
+    /// Returns an empty list when the database is consistent.
+    #[must_use]
+    pub fn check_integrity(&self) -> Vec<IntegrityIssue> {
+        let mut issues = Vec::new();
+
+        // Code or alias -> code of the keycode that defined it first. Language
+        // variants (e.g. German and German Mac) intentionally share codes, so
+        // each language is only checked against itself and the base keycodes.
+        let mut base_seen = HashMap::new();
+        for keycode in self
+            .keycodes
+            .iter()
+            .filter(|k| !k.category.starts_with("lang_"))
+        {
+            check_duplicates(keycode, &mut base_seen, &mut issues);
+        }
+        for lang in &self.languages {
+            let mut seen = base_seen.clone();
+            for keycode in &lang.keycodes {
+                check_duplicates(keycode, &mut seen, &mut issues);
+            }
+        }
+
+        let category_ids: HashSet<&str> = self.categories.iter().map(|c| c.id.as_str()).collect();
+        for keycode in &self.keycodes {
+            if !category_ids.contains(keycode.category.as_str()) {
+                issues.push(IntegrityIssue {
+                    kind: IntegrityIssueKind::DanglingCategory,
+                    code: keycode.code.clone(),
+                    detail: format!("unknown category '{}'", keycode.category),
+                });
+            }
+
+            if let Some(pattern) = &keycode.pattern {
+                if let Err(e) = Regex::new(pattern) {
+                    issues.push(IntegrityIssue {
+                        kind: IntegrityIssueKind::InvalidPattern,
+                        code: keycode.code.clone(),
+                        detail: format!("invalid pattern '{pattern}': {e}"),
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Returns the merged database together with its self-test results.
+    #[allow(dead_code)] // Used by the web dump endpoint (lib target only)
+    #[must_use]
+    pub fn dump(&self) -> KeycodeDbDump<'_> {
+        KeycodeDbDump {
+            categories: &self.categories,
+            languages: self.languages(),
+            keycodes: &self.keycodes,
+            issues: self.check_integrity(),
+        }
+    }
+}
+
+/// Records `keycode`'s code and aliases in `seen`, reporting any already present.
+fn check_duplicates<'a>(
+    keycode: &'a KeycodeDefinition,
+    seen: &mut HashMap<&'a str, &'a str>,
+    issues: &mut Vec<IntegrityIssue>,
+) {
+    for name in std::iter::once(&keycode.code).chain(&keycode.aliases) {
+        if let Some(&first) = seen.get(name.as_str()) {
+            let detail = if first == keycode.code {
+                format!("'{name}' is defined more than once")
+            } else {
+                format!("'{name}' is also defined by {first}")
+            };
+            issues.push(IntegrityIssue {
+                kind: IntegrityIssueKind::DuplicateCode,
+                code: keycode.code.clone(),
+                detail,
+            });
+        } else {
+            seen.insert(name, &keycode.code);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
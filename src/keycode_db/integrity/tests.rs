@@ -0,0 +1,56 @@
+//! Tests for the keycode database self-test.
+
+use super::*;
+
+fn keycode(code: &str, category: &str) -> KeycodeDefinition {
+    KeycodeDefinition {
+        code: code.to_string(),
+        name: code.to_string(),
+        category: category.to_string(),
+        description: None,
+        pattern: None,
+        aliases: Vec::new(),
+        params: Vec::new(),
+    }
+}
+
+#[test]
+fn test_embedded_database_is_consistent() {
+    let db = KeycodeDb::load().unwrap();
+    let issues = db.check_integrity();
+    assert!(issues.is_empty(), "keycode database issues: {issues:?}");
+}
+
+#[test]
+fn test_check_integrity_reports_each_problem() {
+    let mut db = KeycodeDb::load().unwrap();
+    let mut aliased = keycode("KC_FAKE", "basic");
+    aliased.aliases.push("KC_A".to_string());
+    db.keycodes.push(aliased);
+    db.keycodes.push(keycode("KC_ORPHAN", "no_such_category"));
+    let mut bad_pattern = keycode("BAD()", "layers");
+    bad_pattern.pattern = Some("BAD\\((".to_string());
+    db.keycodes.push(bad_pattern);
+
+    let issues = db.check_integrity();
+    let kinds: Vec<_> = issues.iter().map(|i| (i.kind, i.code.as_str())).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (IntegrityIssueKind::DuplicateCode, "KC_FAKE"),
+            (IntegrityIssueKind::DanglingCategory, "KC_ORPHAN"),
+            (IntegrityIssueKind::InvalidPattern, "BAD()"),
+        ]
+    );
+    assert_eq!(issues[0].detail, "'KC_A' is also defined by KC_A");
+}
+
+#[test]
+fn test_dump_includes_merged_languages() {
+    let db = KeycodeDb::load().unwrap();
+    let dump = db.dump();
+    assert_eq!(dump.keycodes.len(), db.keycode_count());
+    assert_eq!(dump.languages.len(), db.language_count());
+    assert!(dump.categories.iter().any(|c| c.id.starts_with("lang_")));
+    assert!(dump.issues.is_empty());
+}
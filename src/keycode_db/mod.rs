@@ -8,22 +8,26 @@
 //!   param/prefix/parse helpers, language accessors).
 //! - [`format`] — short-form keycode display helpers (`strip_kc_prefix`,
 //!   `format_modifier`) shared with TUI and export rendering.
-//! - [`display`] — display metadata for the web Key Details panel
-//!   (cfg-gated on `web` feature).
+//! - [`integrity`] — self-test for duplicate codes, dangling categories and
+//!   invalid patterns, plus a dump of the merged database.
+//! - [`display`] — display metadata for the web Key Details panel and the
+//!   keymap-drawer export.
 
 #![allow(clippy::doc_link_with_quotes)]
 
 pub mod format;
 
 mod db;
+mod integrity;
+
+#[allow(unused_imports)] // bin/lib split: the dump types are only used by the web API
+pub use integrity::{IntegrityIssue, IntegrityIssueKind, KeycodeDbDump};
 
-#[cfg(feature = "web")]
 mod display;
 
-// Re-exports for web feature - used by web::mod.rs but may appear unused
-// when compiling the main binary with web feature enabled.
-#[cfg(feature = "web")]
-#[allow(unused_imports)] // cfg-gated; bin doesn't link web feature path
+// Re-exports used by web::mod.rs and the keymap-drawer export; some appear
+// unused when compiling the main binary.
+#[allow(unused_imports)] // bin/lib split: only the web API uses every type
 pub use display::{
     ActionKind, KeyDetailAction, KeyDisplay, KeyDisplayMetadata, TapDanceDisplayInfo,
 };
@@ -56,6 +60,10 @@ pub enum ParamType {
     /// Needs a tap dance action selection (opens tap dance picker)
     #[serde(rename = "tapdance")]
     TapDance,
+    /// Needs a macro selection (opens macro editor)
+    Macro,
+    /// Needs a Unicode character (opens Unicode map editor)
+    Unicode,
 }
 
 /// Parameter definition for parameterized keycodes
@@ -177,6 +185,56 @@ pub struct KeycodeDb {
     patterns: Vec<(String, Regex)>,
     /// Language-specific keycodes (loaded separately from main categories)
     languages: Vec<LanguageKeycodes>,
+    /// Index range in `keycodes` of each language's keycodes (same order as `languages`)
+    language_ranges: Vec<std::ops::Range<usize>>,
+    /// Lowercased search text for each keycode (same order as `keycodes`)
+    search_keys: Vec<SearchKey>,
+    /// Index in `keycodes` where the layout's own keycodes start (they
+    /// are always last, see [`KeycodeDb::set_layout_keycodes`])
+    custom_start: usize,
+}
+
+/// Lowercased keycode text, computed once at load so searching on every
+/// keystroke does not allocate per keycode.
+#[derive(Debug, Clone)]
+struct SearchKey {
+    code: String,
+    name: String,
+    description: String,
+}
+
+impl SearchKey {
+    fn new(keycode: &KeycodeDefinition) -> Self {
+        Self {
+            code: keycode.code.to_lowercase(),
+            name: keycode.name.to_lowercase(),
+            description: keycode
+                .description
+                .as_deref()
+                .map(str::to_lowercase)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Relevance of this keycode for a lowercased query, or `None` if it
+    /// does not match.
+    fn score(&self, query_lower: &str) -> Option<i32> {
+        if self.code == query_lower || self.name == query_lower {
+            // Exact match (highest priority)
+            Some(100)
+        } else if self.code.starts_with(query_lower) || self.name.starts_with(query_lower) {
+            // Starts with query (high priority)
+            Some(50)
+        } else if self.code.contains(query_lower) || self.name.contains(query_lower) {
+            // Contains query in code or name (medium priority)
+            Some(10)
+        } else if self.description.contains(query_lower) {
+            // Contains query in description (lower priority)
+            Some(5)
+        } else {
+            None
+        }
+    }
 }
 
 /// Type of tap-hold keycode
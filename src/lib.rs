@@ -3,6 +3,18 @@
 //! This library provides core functionality for the `LazyQMK` application,
 //! including parsing QMK info.json files, managing keyboard layouts, and
 //! generating firmware code.
+//!
+//! # Feature flags
+//!
+//! - `tui` (default): the terminal UI, CLI and app launcher (`app`, `cli`,
+//!   `shortcuts` and `tui` modules), pulling in ratatui, crossterm and clap.
+//!   Enables `usb`
+//! - `web` (default): the web server (`web` module) on axum and tokio
+//! - `usb` (default): flashing keyboards and reading the QMK HID console
+//!   (`services::flasher`, `services::hid_listen`)
+//!
+//! With `default-features = false` the crate only has the parser, models,
+//! services and firmware generator.
 
 // Crate-wide clippy allows for numeric casts.
 //
@@ -26,8 +38,10 @@
 #![allow(clippy::cast_possible_wrap)]
 
 // Module declarations
+#[cfg(feature = "tui")]
 pub mod app;
 pub mod branding;
+#[cfg(feature = "tui")]
 pub mod cli;
 pub mod config;
 pub mod constants;
@@ -38,7 +52,9 @@ pub mod keycode_db;
 pub mod models;
 pub mod parser;
 pub mod services;
+#[cfg(feature = "tui")]
 pub mod shortcuts;
+#[cfg(feature = "tui")]
 pub mod tui;
 
 #[cfg(feature = "web")]
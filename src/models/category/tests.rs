@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_new_valid() {
     let color = RgbColor::new(0, 255, 0);
@@ -3,19 +3,19 @@
 //! This module provides a curated color palette based on Tailwind CSS colors,
 //! with 12 base colors and 9 shades each.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::RgbColor;
 
 /// A complete color palette with multiple base colors.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ColorPalette {
     /// The list of base colors in the palette.
     pub colors: Vec<PaletteColor>,
 }
 
 /// A single base color with multiple shades.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaletteColor {
     /// Display name of the color (e.g., "Red", "Blue").
     pub name: String,
@@ -24,7 +24,7 @@ pub struct PaletteColor {
 }
 
 /// A single shade of a color.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Shade {
     /// Shade level (50, 100, 200, 300, 400, 500, 600, 700, 800).
     pub level: u16,
@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_load_palette() {
     let palette = ColorPalette::load().expect("Failed to load palette");
@@ -1,5 +1,7 @@
 //! Keyboard geometry definitions from QMK info.json.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Terminal rendering scale factors.
@@ -46,6 +48,9 @@ pub struct KeyGeometry {
     pub height: f32,
     /// Rotation in degrees (default 0.0, future use)
     pub rotation: f32,
+    /// Key legend from info.json `label` (e.g., "Esc")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
 }
 
 impl KeyGeometry {
@@ -66,6 +71,7 @@ impl KeyGeometry {
             width: 1.0,
             height: 1.0,
             rotation: 0.0,
+            label: None,
         }
     }
 
@@ -93,6 +99,14 @@ impl KeyGeometry {
         self
     }
 
+    /// Sets the key legend.
+    #[allow(dead_code)] // bin/lib split: used in tests and builder API
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
     /// Converts visual X position to terminal characters.
     /// Uses the default X scale factor.
     #[allow(dead_code)] // bin/lib split: public geometry API for layout rendering
@@ -152,6 +166,41 @@ impl KeyGeometry {
     }
 }
 
+/// Physical RGB LED from the keyboard's `rgb_matrix.layout`.
+///
+/// Covers every LED on the board, including underglow and indicator LEDs that
+/// have no key above them (`matrix_position` is `None` for those).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedGeometry {
+    /// Physical LED index (position in `rgb_matrix.layout`)
+    pub led_index: u8,
+    /// Matrix position of the key this LED sits under, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matrix_position: Option<(u8, u8)>,
+    /// QMK LED X coordinate (0-224 across the board)
+    pub x: u8,
+    /// QMK LED Y coordinate (0-64 down the board)
+    pub y: u8,
+    /// QMK LED flags (1 = modifier, 2 = underglow, 4 = key light, 8 = indicator)
+    pub flags: u8,
+}
+
+impl LedGeometry {
+    /// Returns true if this LED lights a key (as opposed to underglow/indicators).
+    #[must_use]
+    pub const fn is_key_led(&self) -> bool {
+        self.matrix_position.is_some()
+    }
+
+    /// Squared distance to `other` in QMK LED coordinate space.
+    #[must_use]
+    pub fn distance_squared(&self, other: &Self) -> u32 {
+        let dx = u32::from(self.x.abs_diff(other.x));
+        let dy = u32::from(self.y.abs_diff(other.y));
+        dx * dx + dy * dy
+    }
+}
+
 /// Physical keyboard definition loaded from QMK info.json.
 ///
 /// # Validation
@@ -175,6 +224,16 @@ pub struct KeyboardGeometry {
     /// Number of rotary encoders (0 if no encoders)
     #[serde(default)]
     pub encoder_count: u8,
+    /// Physical RGB LEDs from `rgb_matrix.layout` (empty if unknown)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub leds: Vec<LedGeometry>,
+    /// Microcontroller from the keyboard definition (e.g., "atmega32u4")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processor: Option<String>,
+    /// Firmware features the keyboard enables by default, keyed by
+    /// `info.json` feature name (e.g., "`rgb_matrix`")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub features: BTreeMap<String, bool>,
 }
 
 impl KeyboardGeometry {
@@ -192,6 +251,9 @@ impl KeyboardGeometry {
             matrix_cols,
             keys: Vec::new(),
             encoder_count: 0,
+            leds: Vec::new(),
+            processor: None,
+            features: BTreeMap::new(),
         }
     }
 
@@ -231,10 +293,101 @@ impl KeyboardGeometry {
 
     /// Checks if the keyboard has RGB matrix LEDs.
     ///
-    /// Returns true if there are any keys defined (RGB LED count matches key count).
+    /// Returns true if there are any keys or physical LEDs defined.
     #[must_use]
     pub const fn has_rgb_matrix(&self) -> bool {
-        !self.keys.is_empty()
+        !self.keys.is_empty() || !self.leds.is_empty()
+    }
+
+    /// Gets the number of RGB matrix LEDs (`RGB_MATRIX_LED_COUNT`).
+    ///
+    /// Counts underglow and indicator LEDs when the physical LED layout is
+    /// known; otherwise assumes one LED per key.
+    #[must_use]
+    pub fn led_count(&self) -> usize {
+        let key_leds = self
+            .keys
+            .iter()
+            .map(|k| usize::from(k.led_index) + 1)
+            .max()
+            .unwrap_or(0);
+        key_leds.max(self.leds.len())
+    }
+
+    /// Iterates over LEDs that have no key (underglow and indicators).
+    pub fn non_key_leds(&self) -> impl Iterator<Item = &LedGeometry> {
+        self.leds.iter().filter(|led| !led.is_key_led())
+    }
+
+    /// Returns the matrix position of the key LED physically closest to `led`.
+    ///
+    /// Key LEDs return their own position. Used to give underglow LEDs a
+    /// position in matrix space for per-key layer lookups and ripple distance.
+    #[must_use]
+    pub fn nearest_key_position(&self, led: &LedGeometry) -> Option<(u8, u8)> {
+        if led.matrix_position.is_some() {
+            return led.matrix_position;
+        }
+        self.leds
+            .iter()
+            .filter(|other| other.is_key_led())
+            .min_by_key(|other| led.distance_squared(other))
+            .and_then(|other| other.matrix_position)
+    }
+
+    /// Converts an LED's QMK coordinates into keyboard units (LED center).
+    ///
+    /// QMK scales LED coordinates so the key LEDs span 0-224 by 0-64, so the
+    /// mapping is fitted against the keys that have LEDs. Falls back to the
+    /// key bounds when no key LEDs are known.
+    #[allow(dead_code)] // Used by the web geometry endpoint (lib target only)
+    #[must_use]
+    pub fn led_position_in_units(&self, led: &LedGeometry) -> (f32, f32) {
+        // (min, max) of LED coordinates and of matching key centers, per axis
+        let mut led_x = (f32::MAX, f32::MIN);
+        let mut led_y = (f32::MAX, f32::MIN);
+        let mut key_x = (f32::MAX, f32::MIN);
+        let mut key_y = (f32::MAX, f32::MIN);
+        let extend = |range: &mut (f32, f32), value: f32| {
+            range.0 = range.0.min(value);
+            range.1 = range.1.max(value);
+        };
+
+        for key_led in &self.leds {
+            let Some(key) = key_led
+                .matrix_position
+                .and_then(|pos| self.get_key_by_matrix(pos))
+            else {
+                continue;
+            };
+            extend(&mut led_x, f32::from(key_led.x));
+            extend(&mut led_y, f32::from(key_led.y));
+            extend(&mut key_x, key.visual_x + key.width / 2.0);
+            extend(&mut key_y, key.visual_y + key.height / 2.0);
+        }
+
+        if led_x.0 > led_x.1 {
+            // No key LEDs: assume QMK's full 224x64 range covers the keys.
+            led_x = (0.0, 224.0);
+            led_y = (0.0, 64.0);
+            for key in &self.keys {
+                extend(&mut key_x, key.visual_x + key.width / 2.0);
+                extend(&mut key_y, key.visual_y + key.height / 2.0);
+            }
+            if key_x.0 > key_x.1 {
+                // No keys either: approximate 16 LED units per key unit.
+                return (f32::from(led.x) / 16.0, f32::from(led.y) / 16.0);
+            }
+        }
+
+        let scale = |value: u8, from: (f32, f32), to: (f32, f32)| {
+            if (from.1 - from.0).abs() < f32::EPSILON {
+                to.0
+            } else {
+                to.0 + (f32::from(value) - from.0) * (to.1 - to.0) / (from.1 - from.0)
+            }
+        };
+        (scale(led.x, led_x, key_x), scale(led.y, led_y, key_y))
     }
 }
 
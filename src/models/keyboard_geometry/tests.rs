@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 #[allow(clippy::float_cmp)]
 fn test_key_geometry_new() {
@@ -64,3 +62,71 @@ fn test_keyboard_geometry_add_key() {
     assert!(geom.get_key_by_matrix((0, 0)).is_some());
     assert!(geom.get_key_by_matrix((0, 1)).is_some());
 }
+
+/// Two keys with LEDs 1 and 2 at x = 0 and 224, plus underglow LED 0.
+fn geometry_with_underglow() -> KeyboardGeometry {
+    let mut geom = KeyboardGeometry::new("test", "LAYOUT", 1, 2);
+    geom.add_key(KeyGeometry::new((0, 0), 1, 0.0, 0.0));
+    geom.add_key(KeyGeometry::new((0, 1), 2, 4.0, 0.0));
+    geom.leds = vec![
+        LedGeometry {
+            led_index: 0,
+            matrix_position: None,
+            x: 168,
+            y: 64,
+            flags: 2,
+        },
+        LedGeometry {
+            led_index: 1,
+            matrix_position: Some((0, 0)),
+            x: 0,
+            y: 32,
+            flags: 4,
+        },
+        LedGeometry {
+            led_index: 2,
+            matrix_position: Some((0, 1)),
+            x: 224,
+            y: 32,
+            flags: 4,
+        },
+    ];
+    geom
+}
+
+#[test]
+fn test_led_count_includes_underglow() {
+    let mut geom = geometry_with_underglow();
+    assert_eq!(geom.led_count(), 3);
+    assert!(geom.has_rgb_matrix());
+    assert_eq!(
+        geom.non_key_leds()
+            .map(|led| led.led_index)
+            .collect::<Vec<_>>(),
+        vec![0]
+    );
+
+    // Without a physical LED layout, one LED per key is assumed
+    geom.leds.clear();
+    assert_eq!(geom.led_count(), 3); // key LED indices go up to 2
+}
+
+#[test]
+fn test_nearest_key_position_uses_physical_coordinates() {
+    let geom = geometry_with_underglow();
+
+    assert_eq!(geom.nearest_key_position(&geom.leds[0]), Some((0, 1)));
+    assert_eq!(geom.nearest_key_position(&geom.leds[1]), Some((0, 0)));
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_led_position_in_units_fits_key_centers() {
+    let geom = geometry_with_underglow();
+
+    // Key centers span x 0.5..4.5 for LED x 0..224
+    assert_eq!(geom.led_position_in_units(&geom.leds[1]), (0.5, 0.5));
+    assert_eq!(geom.led_position_in_units(&geom.leds[2]), (4.5, 0.5));
+    let (x, _) = geom.led_position_in_units(&geom.leds[0]);
+    assert_eq!(x, 3.5);
+}
@@ -3,6 +3,7 @@
 use crate::models::RgbColor;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 /// Default QMK layer limit.
@@ -110,6 +111,10 @@ pub struct KeyDefinition {
     /// Optional user description for this key (e.g., "Primary thumb key")
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Locked keys are skipped by bulk edits, pastes, and layer/fragment
+    /// applications until unlocked
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
 }
 
 impl KeyDefinition {
@@ -126,6 +131,7 @@ impl KeyDefinition {
             category_id: None,
             combo_participant: false,
             description: None,
+            locked: false,
         }
     }
 
@@ -171,6 +177,66 @@ impl KeyDefinition {
     }
 }
 
+/// Keycodes sent when a rotary encoder is turned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncoderBinding {
+    /// Keycode for a counter-clockwise turn
+    pub ccw: String,
+    /// Keycode for a clockwise turn
+    pub cw: String,
+}
+
+impl EncoderBinding {
+    /// Creates a new binding from counter-clockwise and clockwise keycodes.
+    pub fn new(ccw: impl Into<String>, cw: impl Into<String>) -> Self {
+        Self {
+            ccw: ccw.into(),
+            cw: cw.into(),
+        }
+    }
+}
+
+/// Encoder rotation bindings for one layer, keyed by encoder index.
+///
+/// Encoders without a binding use the firmware generator's defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EncoderMap(BTreeMap<u8, EncoderBinding>);
+
+impl EncoderMap {
+    /// Returns the binding for `encoder`, if one is set.
+    #[must_use]
+    pub fn get(&self, encoder: u8) -> Option<&EncoderBinding> {
+        self.0.get(&encoder)
+    }
+
+    /// Sets the binding for `encoder`, replacing any existing one.
+    pub fn set(&mut self, encoder: u8, binding: EncoderBinding) {
+        self.0.insert(encoder, binding);
+    }
+
+    /// Removes the binding for `encoder`, returning it if one was set.
+    pub fn remove(&mut self, encoder: u8) -> Option<EncoderBinding> {
+        self.0.remove(&encoder)
+    }
+
+    /// Iterates over bindings in encoder order.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &EncoderBinding)> {
+        self.0.iter().map(|(encoder, binding)| (*encoder, binding))
+    }
+
+    /// Iterates mutably over bindings in encoder order.
+    pub fn bindings_mut(&mut self) -> impl Iterator<Item = &mut EncoderBinding> {
+        self.0.values_mut()
+    }
+
+    /// Returns true if no encoder has a binding.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
 /// A single layer of the keyboard with color and key assignments.
 ///
 /// # Validation
@@ -200,6 +266,13 @@ pub struct Layer {
     /// but individual key colors and key category colors still work.
     #[serde(default = "default_layer_colors_enabled")]
     pub layer_colors_enabled: bool,
+    /// Fixed color for LEDs without a key (underglow, indicators).
+    /// When `None`, they follow the layer color.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub underglow_color: Option<RgbColor>,
+    /// Rotary encoder bindings for this layer
+    #[serde(default, skip_serializing_if = "EncoderMap::is_empty")]
+    pub encoder_map: EncoderMap,
 }
 
 /// Generates a new unique layer ID
@@ -246,6 +319,8 @@ impl Layer {
             category_id: None,
             keys: Vec::new(),
             layer_colors_enabled: true,
+            underglow_color: None,
+            encoder_map: EncoderMap::default(),
         })
     }
 
@@ -288,6 +363,31 @@ impl Layer {
         self.keys.iter_mut().find(|k| k.position == position)
     }
 
+    /// Checks if the key at the given **visual** position is locked.
+    #[must_use]
+    pub fn is_key_locked(&self, position: Position) -> bool {
+        self.get_key(position).is_some_and(|k| k.locked)
+    }
+
+    /// Swaps the keys at two **visual** positions.
+    ///
+    /// Whole key definitions move, so labels, colors, categories and
+    /// descriptions travel with their keycodes. Returns false if either
+    /// position has no key.
+    pub fn swap_keys(&mut self, first: Position, second: Position) -> bool {
+        let first_idx = self.keys.iter().position(|k| k.position == first);
+        let second_idx = self.keys.iter().position(|k| k.position == second);
+        let (Some(first_idx), Some(second_idx)) = (first_idx, second_idx) else {
+            return false;
+        };
+
+        // Positions stay in place, everything else changes sides
+        self.keys.swap(first_idx, second_idx);
+        self.keys[first_idx].position = first;
+        self.keys[second_idx].position = second;
+        true
+    }
+
     /// Sets the category for this layer.
     #[allow(dead_code)] // bin/lib split: setter in public layer API
     #[allow(dead_code)] // bin/lib split: Layer mutator (tests use it)
@@ -312,6 +412,29 @@ impl Layer {
         Ok(())
     }
 
+    /// Returns a copy of this layer with a new ID, number, and name.
+    ///
+    /// Keycodes, key colors and categories, encoder bindings, and the layer's
+    /// color settings are copied; key locks, labels, and descriptions are not.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name or number is invalid.
+    pub fn duplicate(&self, number: u8, name: impl Into<String>) -> Result<Self> {
+        let mut layer = Self::new(number, name, self.default_color)?;
+        for key in &self.keys {
+            let mut new_key = KeyDefinition::new(key.position, &key.keycode);
+            new_key.color_override = key.color_override;
+            new_key.category_id.clone_from(&key.category_id);
+            layer.add_key(new_key);
+        }
+        layer.layer_colors_enabled = self.layer_colors_enabled;
+        layer.underglow_color = self.underglow_color;
+        layer.category_id.clone_from(&self.category_id);
+        layer.encoder_map.clone_from(&self.encoder_map);
+        Ok(layer)
+    }
+
     /// Toggles layer-level RGB colors on/off.
     pub const fn toggle_layer_colors(&mut self) {
         self.layer_colors_enabled = !self.layer_colors_enabled;
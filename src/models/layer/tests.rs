@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_key_definition_new() {
     let pos = Position::new(0, 0);
@@ -102,6 +100,49 @@ fn test_layer_get_key_mut() {
     assert_eq!(layer.get_key(pos).unwrap().keycode, "KC_B");
 }
 
+#[test]
+fn test_key_lock_flag_serialization() {
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
+    let pos = Position::new(0, 0);
+    layer.add_key(KeyDefinition::new(pos, "QK_BOOT"));
+
+    let json = serde_json::to_string(layer.get_key(pos).unwrap()).unwrap();
+    assert!(!json.contains("locked"), "unlocked keys omit the flag");
+    assert!(!layer.is_key_locked(pos));
+
+    layer.get_key_mut(pos).unwrap().locked = true;
+    assert!(layer.is_key_locked(pos));
+    assert!(!layer.is_key_locked(Position::new(5, 5)));
+
+    let json = serde_json::to_string(layer.get_key(pos).unwrap()).unwrap();
+    let restored: KeyDefinition = serde_json::from_str(&json).unwrap();
+    assert!(restored.locked);
+}
+
+#[test]
+fn test_layer_duplicate() {
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
+    layer.add_key(
+        KeyDefinition::new(Position::new(0, 0), "KC_A")
+            .with_color(RgbColor::new(1, 2, 3))
+            .with_category("nav"),
+    );
+    layer.keys[0].locked = true;
+    layer.underglow_color = Some(RgbColor::new(9, 9, 9));
+
+    let copy = layer.duplicate(3, "Base Copy").unwrap();
+
+    assert_ne!(copy.id, layer.id);
+    assert_eq!(copy.number, 3);
+    assert_eq!(copy.name, "Base Copy");
+    assert_eq!(copy.keys[0].keycode, "KC_A");
+    assert_eq!(copy.keys[0].color_override, Some(RgbColor::new(1, 2, 3)));
+    assert_eq!(copy.keys[0].category_id.as_deref(), Some("nav"));
+    assert!(!copy.keys[0].locked);
+    assert_eq!(copy.underglow_color, layer.underglow_color);
+    assert!(layer.duplicate(1, "").is_err());
+}
+
 #[test]
 fn test_layer_set_category() {
     let mut layer = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
@@ -174,3 +215,21 @@ fn test_validate_layer_number_error_message() {
     assert!(error_msg.contains("LAYER_STATE_16BIT"));
     assert!(error_msg.contains("LAYER_STATE_32BIT"));
 }
+
+#[test]
+fn test_swap_keys_keeps_positions() {
+    let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A").with_description("first"));
+    layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"));
+
+    assert!(layer.swap_keys(Position::new(0, 0), Position::new(0, 1)));
+
+    let first = layer.get_key(Position::new(0, 0)).unwrap();
+    assert_eq!(first.keycode, "KC_B");
+    assert_eq!(first.description, None);
+    let second = layer.get_key(Position::new(0, 1)).unwrap();
+    assert_eq!(second.keycode, "KC_A");
+    assert_eq!(second.description.as_deref(), Some("first"));
+
+    assert!(!layer.swap_keys(Position::new(0, 0), Position::new(3, 3)));
+}
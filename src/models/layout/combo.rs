@@ -1,11 +1,15 @@
-//! Combo settings — two-key hold combos on the base layer.
+//! Combo settings — two-key hold combos on the base layer and keycode
+//! combos that send a keycode when several keys are pressed together.
 
 use crate::models::layer::Position;
 use serde::{Deserialize, Serialize};
 
-/// Maximum number of combo definitions.
+/// Maximum number of combo definitions (hold and keycode combos together).
 pub const MAX_COMBOS: usize = 32;
 
+/// Maximum number of trigger keys in a keycode combo.
+pub const MAX_COMBO_KEYS: usize = 8;
+
 /// Action to perform when a combo is activated.
 ///
 /// Combos are two-key combinations that trigger special actions when held together.
@@ -163,6 +167,68 @@ impl ComboDefinition {
     }
 }
 
+/// A combo that sends a keycode when all of its trigger keys are pressed
+/// together.
+///
+/// Trigger keys are **visual** positions; the firmware generator uses the
+/// base-layer keycodes at those positions as the QMK combo keys, so the combo
+/// fires from the same physical keys on every layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeycodeCombo {
+    /// Trigger key positions (2 to `MAX_COMBO_KEYS`)
+    pub keys: Vec<Position>,
+    /// Keycode sent when the combo fires
+    pub keycode: String,
+}
+
+impl KeycodeCombo {
+    /// Creates a keycode combo.
+    #[must_use]
+    pub fn new(keys: Vec<Position>, keycode: impl Into<String>) -> Self {
+        Self {
+            keys,
+            keycode: keycode.into(),
+        }
+    }
+
+    /// Returns whether `other` triggers on the same set of keys.
+    #[must_use]
+    pub fn same_keys(&self, other: &Self) -> bool {
+        self.keys.len() == other.keys.len() && self.keys.iter().all(|k| other.keys.contains(k))
+    }
+
+    /// Validates the combo.
+    ///
+    /// Checks:
+    /// - Between 2 and `MAX_COMBO_KEYS` trigger keys
+    /// - Trigger keys are distinct
+    /// - Output keycode is non-empty
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.keys.len() < 2 || self.keys.len() > MAX_COMBO_KEYS {
+            anyhow::bail!(
+                "Combo must have between 2 and {MAX_COMBO_KEYS} keys (got {})",
+                self.keys.len()
+            );
+        }
+
+        for (idx, key) in self.keys.iter().enumerate() {
+            if self.keys[..idx].contains(key) {
+                anyhow::bail!(
+                    "Combo keys must be different (row {}, col {} is repeated)",
+                    key.row,
+                    key.col
+                );
+            }
+        }
+
+        if self.keycode.trim().is_empty() {
+            anyhow::bail!("Combo keycode cannot be empty");
+        }
+
+        Ok(())
+    }
+}
+
 /// Configuration for two-key hold combos.
 ///
 /// Supports up to 32 custom combos that are active only on the base layer (layer 0).
@@ -176,6 +242,10 @@ pub struct ComboSettings {
     /// List of combo definitions (max 32)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub combos: Vec<ComboDefinition>,
+
+    /// Combos that send a keycode (share the `MAX_COMBOS` limit)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keycode_combos: Vec<KeycodeCombo>,
 }
 
 impl ComboSettings {
@@ -187,13 +257,14 @@ impl ComboSettings {
         Self {
             enabled,
             combos: Vec::new(),
+            keycode_combos: Vec::new(),
         }
     }
 
     /// Adds a combo definition.
     #[allow(dead_code)] // Public API; tests are in lib target
     pub fn add_combo(&mut self, combo: ComboDefinition) -> Result<(), anyhow::Error> {
-        if self.combos.len() >= MAX_COMBOS {
+        if self.combos.len() + self.keycode_combos.len() >= MAX_COMBOS {
             anyhow::bail!("Maximum of {} combos allowed", MAX_COMBOS);
         }
 
@@ -218,9 +289,56 @@ impl ComboSettings {
         Ok(())
     }
 
+    /// Adds a keycode combo.
+    pub fn add_keycode_combo(&mut self, combo: KeycodeCombo) -> Result<(), anyhow::Error> {
+        if self.combos.len() + self.keycode_combos.len() >= MAX_COMBOS {
+            anyhow::bail!("Maximum of {} combos allowed", MAX_COMBOS);
+        }
+
+        self.check_keycode_combo(&combo, None)?;
+        self.keycode_combos.push(combo);
+        Ok(())
+    }
+
+    /// Replaces the keycode combo at `index`.
+    pub fn update_keycode_combo(
+        &mut self,
+        index: usize,
+        combo: KeycodeCombo,
+    ) -> Result<(), anyhow::Error> {
+        if index >= self.keycode_combos.len() {
+            anyhow::bail!("Combo {} not found", index + 1);
+        }
+
+        self.check_keycode_combo(&combo, Some(index))?;
+        self.keycode_combos[index] = combo;
+        Ok(())
+    }
+
+    /// Validates `combo` and rejects trigger keys already used by another
+    /// keycode combo (ignoring the one at `skip`).
+    fn check_keycode_combo(
+        &self,
+        combo: &KeycodeCombo,
+        skip: Option<usize>,
+    ) -> Result<(), anyhow::Error> {
+        combo.validate()?;
+
+        let duplicate = self
+            .keycode_combos
+            .iter()
+            .enumerate()
+            .any(|(idx, existing)| Some(idx) != skip && existing.same_keys(combo));
+        if duplicate {
+            anyhow::bail!("A combo with the same keys already exists");
+        }
+
+        Ok(())
+    }
+
     /// Checks if any settings differ from defaults.
     #[must_use]
     pub fn has_custom_settings(&self) -> bool {
-        self.enabled || !self.combos.is_empty()
+        self.enabled || !self.combos.is_empty() || !self.keycode_combos.is_empty()
     }
 }
@@ -0,0 +1,99 @@
+//! User-defined keycodes backed by C snippets.
+
+use serde::{Deserialize, Serialize};
+
+use super::EditingHelper;
+
+/// Maximum number of custom keycodes in a layout.
+pub const MAX_CUSTOM_KEYCODES: usize = 64;
+
+/// Name prefixes taken by QMK keycodes and by keycodes `LazyQMK` generates.
+const RESERVED_PREFIXES: [&str; 6] = ["KC_", "QK_", "MACRO_", "UM_", "UC_", "TD_"];
+
+/// A keycode whose behavior is a user-written C snippet.
+///
+/// Keys use the name directly as their keycode, e.g. `CK_EMAIL`. The
+/// firmware generator adds the name to `enum custom_keycodes` and pastes
+/// the snippet into a `case` of `process_record_user`, where `keycode` and
+/// `record` are in scope. The snippet runs on press and release (check
+/// `record->event.pressed`); unless it returns itself, the key event is
+/// consumed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomKeycode {
+    /// Keycode name (uppercase C identifier, e.g. `CK_EMAIL`)
+    pub name: String,
+    /// C statements run for the key's events
+    pub behavior: String,
+    /// What the keycode does, shown in the keycode picker
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+}
+
+impl CustomKeycode {
+    /// Creates a custom keycode.
+    #[must_use]
+    pub fn new(
+        name: impl Into<String>,
+        behavior: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            behavior: behavior.into(),
+            description: description.into(),
+        }
+    }
+
+    /// Returns the description, or a generic one when none is set.
+    #[must_use]
+    pub fn display_description(&self) -> &str {
+        if self.description.is_empty() {
+            "Custom keycode"
+        } else {
+            &self.description
+        }
+    }
+
+    /// Validates the custom keycode.
+    ///
+    /// Checks:
+    /// - Name is an uppercase C identifier without a reserved prefix
+    /// - Name is not an editing helper keycode
+    /// - Behavior is not blank
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        let mut chars = self.name.chars();
+        let valid_name = chars.next().is_some_and(|c| c.is_ascii_uppercase())
+            && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+        if !valid_name {
+            anyhow::bail!(
+                "Custom keycode name '{}' must start with a letter and contain only \
+                 uppercase letters, digits and underscores",
+                self.name
+            );
+        }
+
+        if let Some(prefix) = RESERVED_PREFIXES
+            .iter()
+            .find(|prefix| self.name.starts_with(*prefix))
+        {
+            anyhow::bail!(
+                "Custom keycode name '{}' must not start with reserved prefix '{prefix}'",
+                self.name
+            );
+        }
+
+        if let Some(helper) = EditingHelper::from_keycode(&self.name) {
+            anyhow::bail!(
+                "Custom keycode name '{}' is used by the {} helper",
+                self.name,
+                helper.display_name()
+            );
+        }
+
+        if self.behavior.trim().is_empty() {
+            anyhow::bail!("Custom keycode '{}' has no behavior", self.name);
+        }
+
+        Ok(())
+    }
+}
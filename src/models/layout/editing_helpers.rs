@@ -0,0 +1,163 @@
+//! Optional editing helpers: Select Word, Sentence Case and Num Word.
+//!
+//! Each helper is a small C feature generated into keymap.c together with
+//! the custom keycode that drives it. Helpers are off unless a layout
+//! turns them on.
+
+use serde::{Deserialize, Serialize};
+
+/// An editing helper that can be switched on per layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EditingHelper {
+    /// Selects the word under the cursor, each further press extends it
+    SelectWord,
+    /// Capitalizes the first letter of each sentence
+    SentenceCase,
+    /// Turns on a numbers layer until a non-number key is typed
+    NumWord,
+}
+
+impl EditingHelper {
+    /// All helpers, in display order.
+    pub const ALL: [Self; 3] = [Self::SelectWord, Self::SentenceCase, Self::NumWord];
+
+    /// The custom keycode that triggers this helper.
+    #[must_use]
+    pub const fn keycode(self) -> &'static str {
+        match self {
+            Self::SelectWord => "SELWORD",
+            Self::SentenceCase => "SENTCASE",
+            Self::NumWord => "NUMWORD",
+        }
+    }
+
+    /// Human-readable name.
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::SelectWord => "Select Word",
+            Self::SentenceCase => "Sentence Case",
+            Self::NumWord => "Num Word",
+        }
+    }
+
+    /// What the helper's keycode does, shown in the keycode picker.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::SelectWord => "Select the current word, press again to extend",
+            Self::SentenceCase => "Toggle automatic capitalization of sentences",
+            Self::NumWord => "Numbers layer until a non-number key is typed",
+        }
+    }
+
+    /// Looks up a helper by display name (case-insensitive).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|helper| helper.display_name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Looks up a helper by its keycode.
+    #[must_use]
+    pub fn from_keycode(keycode: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|helper| helper.keycode() == keycode)
+    }
+}
+
+/// Default layer Num Word turns on.
+const fn default_num_word_layer() -> u8 {
+    1
+}
+
+/// Per-layout editing helper switches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditingHelpers {
+    /// Select Word (`SELWORD`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub select_word: bool,
+    /// Sentence Case (`SENTCASE`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sentence_case: bool,
+    /// Num Word (`NUMWORD`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub num_word: bool,
+    /// Layer index Num Word turns on
+    #[serde(default = "default_num_word_layer")]
+    pub num_word_layer: u8,
+}
+
+impl Default for EditingHelpers {
+    fn default() -> Self {
+        Self {
+            select_word: false,
+            sentence_case: false,
+            num_word: false,
+            num_word_layer: default_num_word_layer(),
+        }
+    }
+}
+
+impl EditingHelpers {
+    /// Returns true if `helper` is on.
+    #[must_use]
+    pub const fn is_enabled(&self, helper: EditingHelper) -> bool {
+        match helper {
+            EditingHelper::SelectWord => self.select_word,
+            EditingHelper::SentenceCase => self.sentence_case,
+            EditingHelper::NumWord => self.num_word,
+        }
+    }
+
+    /// Switches `helper` on or off.
+    pub fn set_enabled(&mut self, helper: EditingHelper, enabled: bool) {
+        let slot = match helper {
+            EditingHelper::SelectWord => &mut self.select_word,
+            EditingHelper::SentenceCase => &mut self.sentence_case,
+            EditingHelper::NumWord => &mut self.num_word,
+        };
+        *slot = enabled;
+    }
+
+    /// Returns the helpers that are on, in display order.
+    #[must_use]
+    pub fn enabled(&self) -> Vec<EditingHelper> {
+        EditingHelper::ALL
+            .into_iter()
+            .filter(|helper| self.is_enabled(*helper))
+            .collect()
+    }
+
+    /// Returns true if any helper is on.
+    #[must_use]
+    pub const fn any_enabled(&self) -> bool {
+        self.select_word || self.sentence_case || self.num_word
+    }
+
+    /// Returns true if `keycode` belongs to a helper that is on.
+    #[must_use]
+    pub fn is_enabled_keycode(&self, keycode: &str) -> bool {
+        EditingHelper::from_keycode(keycode).is_some_and(|helper| self.is_enabled(helper))
+    }
+
+    /// Validates settings against a layout with `layer_count` layers.
+    ///
+    /// Checks:
+    /// - Num Word targets an existing layer other than the base layer
+    pub fn validate(&self, layer_count: usize) -> anyhow::Result<()> {
+        if self.num_word
+            && (self.num_word_layer == 0 || usize::from(self.num_word_layer) >= layer_count)
+        {
+            anyhow::bail!(
+                "Num Word layer must be between 1 and {}, got {}",
+                layer_count.saturating_sub(1),
+                self.num_word_layer
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+//! Optional QMK firmware features that can be switched on or off per layout.
+//!
+//! Each feature maps to a `rules.mk` flag. A feature without an override
+//! keeps whatever the keyboard's `info.json`/`keyboard.json` enables.
+
+use serde::{Deserialize, Serialize};
+
+/// Optional firmware feature with a significant flash cost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FirmwareFeature {
+    /// RGB Matrix lighting and effects
+    RgbMatrix,
+    /// Audio (speaker/buzzer) support
+    Audio,
+    /// Debug console over HID
+    Console,
+    /// Mouse keys
+    MouseKeys,
+    /// Unicode input
+    Unicode,
+}
+
+impl FirmwareFeature {
+    /// All features, in display order.
+    pub const ALL: [Self; 5] = [
+        Self::RgbMatrix,
+        Self::Audio,
+        Self::Console,
+        Self::MouseKeys,
+        Self::Unicode,
+    ];
+
+    /// The `rules.mk` variable controlling this feature.
+    #[must_use]
+    pub const fn rules_mk_flag(self) -> &'static str {
+        match self {
+            Self::RgbMatrix => "RGB_MATRIX_ENABLE",
+            Self::Audio => "AUDIO_ENABLE",
+            Self::Console => "CONSOLE_ENABLE",
+            Self::MouseKeys => "MOUSEKEY_ENABLE",
+            Self::Unicode => "UNICODE_ENABLE",
+        }
+    }
+
+    /// The key used for this feature in the `features` object of `info.json`.
+    #[must_use]
+    pub const fn info_json_key(self) -> &'static str {
+        match self {
+            Self::RgbMatrix => "rgb_matrix",
+            Self::Audio => "audio",
+            Self::Console => "console",
+            Self::MouseKeys => "mousekey",
+            Self::Unicode => "unicode",
+        }
+    }
+
+    /// Human-readable name.
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::RgbMatrix => "RGB Matrix",
+            Self::Audio => "Audio",
+            Self::Console => "Console",
+            Self::MouseKeys => "Mouse Keys",
+            Self::Unicode => "Unicode",
+        }
+    }
+
+    /// Looks up a feature by display name (case-insensitive).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|feature| feature.display_name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// Per-layout overrides of the keyboard's firmware features.
+///
+/// `None` keeps the keyboard default; `Some` writes `FLAG = yes/no` to
+/// the generated `rules.mk`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FirmwareFeatures {
+    /// RGB Matrix override
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rgb_matrix: Option<bool>,
+    /// Audio override
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<bool>,
+    /// Console override
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub console: Option<bool>,
+    /// Mouse keys override
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mouse_keys: Option<bool>,
+    /// Unicode override
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unicode: Option<bool>,
+}
+
+impl FirmwareFeatures {
+    /// Returns the override for `feature`.
+    #[must_use]
+    pub const fn get(&self, feature: FirmwareFeature) -> Option<bool> {
+        match feature {
+            FirmwareFeature::RgbMatrix => self.rgb_matrix,
+            FirmwareFeature::Audio => self.audio,
+            FirmwareFeature::Console => self.console,
+            FirmwareFeature::MouseKeys => self.mouse_keys,
+            FirmwareFeature::Unicode => self.unicode,
+        }
+    }
+
+    /// Sets (or clears, with `None`) the override for `feature`.
+    pub fn set(&mut self, feature: FirmwareFeature, value: Option<bool>) {
+        let slot = match feature {
+            FirmwareFeature::RgbMatrix => &mut self.rgb_matrix,
+            FirmwareFeature::Audio => &mut self.audio,
+            FirmwareFeature::Console => &mut self.console,
+            FirmwareFeature::MouseKeys => &mut self.mouse_keys,
+            FirmwareFeature::Unicode => &mut self.unicode,
+        };
+        *slot = value;
+    }
+
+    /// Returns true if any feature is overridden.
+    #[must_use]
+    pub fn has_custom_settings(&self) -> bool {
+        FirmwareFeature::ALL
+            .into_iter()
+            .any(|feature| self.get(feature).is_some())
+    }
+}
@@ -0,0 +1,149 @@
+//! Compact keyboard geometry stored with a layout.
+//!
+//! Saving a layout records the key positions, matrix and LED indices of the
+//! keyboard it was edited on in `metadata.geometry`. When the keyboard can't
+//! be read from a QMK tree (none configured, or the keyboard isn't in it),
+//! the layout still renders from this snapshot.
+
+use serde::{Deserialize, Serialize};
+
+use super::layout_core::Layout;
+use crate::models::{KeyGeometry, KeyboardGeometry};
+
+/// Keyboard geometry saved with a layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeometrySnapshot {
+    /// QMK keyboard identifier (e.g., "crkbd/rev1")
+    pub keyboard: String,
+    /// Layout variant (e.g., "`LAYOUT_split_3x6_3`")
+    pub layout_variant: String,
+    /// Electrical matrix row count
+    pub matrix_rows: u8,
+    /// Electrical matrix column count
+    pub matrix_cols: u8,
+    /// Number of rotary encoders
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub encoder_count: u8,
+    /// Keys in LAYOUT macro order
+    pub keys: Vec<SnapshotKey>,
+}
+
+/// One key of a [`GeometrySnapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotKey {
+    /// Matrix position [row, col]
+    pub matrix: [u8; 2],
+    /// RGB LED index
+    pub led: u8,
+    /// X position in keyboard units
+    pub x: f32,
+    /// Y position in keyboard units
+    pub y: f32,
+    /// Width in keyboard units
+    #[serde(default = "one", skip_serializing_if = "is_one")]
+    pub w: f32,
+    /// Height in keyboard units
+    #[serde(default = "one", skip_serializing_if = "is_one")]
+    pub h: f32,
+    /// Rotation in degrees
+    #[serde(default, skip_serializing_if = "is_zero_f32")]
+    pub r: f32,
+}
+
+// Coordinates come from info.json and are never NaN, so equality is total
+impl Eq for SnapshotKey {}
+impl Eq for GeometrySnapshot {}
+
+impl GeometrySnapshot {
+    /// Records the keys of `geometry`, dropping legends and LED layout.
+    #[must_use]
+    pub fn from_geometry(geometry: &KeyboardGeometry) -> Self {
+        let mut keys: Vec<&KeyGeometry> = geometry.keys.iter().collect();
+        keys.sort_by_key(|key| key.layout_index);
+        Self {
+            keyboard: geometry.keyboard_name.clone(),
+            layout_variant: geometry.layout_name.clone(),
+            matrix_rows: geometry.matrix_rows,
+            matrix_cols: geometry.matrix_cols,
+            encoder_count: geometry.encoder_count,
+            keys: keys
+                .into_iter()
+                .map(|key| SnapshotKey {
+                    matrix: [key.matrix_position.0, key.matrix_position.1],
+                    led: key.led_index,
+                    x: key.visual_x,
+                    y: key.visual_y,
+                    w: key.width,
+                    h: key.height,
+                    r: key.rotation,
+                })
+                .collect(),
+        }
+    }
+
+    /// Rebuilds the keyboard geometry.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_geometry(&self) -> KeyboardGeometry {
+        let mut geometry = KeyboardGeometry::new(
+            &self.keyboard,
+            &self.layout_variant,
+            self.matrix_rows,
+            self.matrix_cols,
+        );
+        geometry.encoder_count = self.encoder_count;
+        for (index, key) in self.keys.iter().enumerate() {
+            let mut key_geometry =
+                KeyGeometry::new((key.matrix[0], key.matrix[1]), key.led, key.x, key.y);
+            key_geometry.layout_index = index as u8;
+            key_geometry.width = key.w;
+            key_geometry.height = key.h;
+            key_geometry.rotation = key.r;
+            geometry.add_key(key_geometry);
+        }
+        geometry
+    }
+}
+
+impl Layout {
+    /// Stores a snapshot of `geometry` in the metadata.
+    ///
+    /// Geometries without keys (nothing known about the keyboard) leave the
+    /// current snapshot in place.
+    pub fn record_geometry(&mut self, geometry: &KeyboardGeometry) {
+        if !geometry.keys.is_empty() {
+            self.metadata.geometry = Some(GeometrySnapshot::from_geometry(geometry));
+        }
+    }
+
+    /// Returns the stored geometry if it is for the layout's variant.
+    #[must_use]
+    pub fn snapshot_geometry(&self) -> Option<KeyboardGeometry> {
+        let snapshot = self.metadata.geometry.as_ref()?;
+        let variant_matches = self
+            .metadata
+            .layout_variant
+            .as_deref()
+            .is_none_or(|variant| variant == snapshot.layout_variant);
+        variant_matches.then(|| snapshot.to_geometry())
+    }
+}
+
+const fn one() -> f32 {
+    1.0
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // serde skip_serializing_if signature
+fn is_one(value: &f32) -> bool {
+    (*value - 1.0).abs() < f32::EPSILON
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // serde skip_serializing_if signature
+fn is_zero_f32(value: &f32) -> bool {
+    value.abs() < f32::EPSILON
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // serde skip_serializing_if signature
+const fn is_zero(value: &u8) -> bool {
+    *value == 0
+}
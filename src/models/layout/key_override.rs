@@ -0,0 +1,155 @@
+//! Key overrides — send a different key when a key is pressed with
+//! certain modifiers held, e.g. Shift+Backspace sends Delete.
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of key overrides per layout.
+pub const MAX_KEY_OVERRIDES: usize = 32;
+
+/// Modifier that must be held for a key override to trigger.
+///
+/// Either side of the keyboard counts (`MOD_MASK_SHIFT` covers both Shift keys).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverrideModifier {
+    /// Either Control key
+    Ctrl,
+    /// Either Shift key
+    Shift,
+    /// Either Alt key
+    Alt,
+    /// Either GUI (Super/Command) key
+    Gui,
+}
+
+impl OverrideModifier {
+    /// Returns a human-readable name for this modifier.
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::Ctrl => "Ctrl",
+            Self::Shift => "Shift",
+            Self::Alt => "Alt",
+            Self::Gui => "Gui",
+        }
+    }
+
+    /// Returns the QMK modifier mask, e.g. `MOD_MASK_SHIFT`.
+    #[must_use]
+    pub const fn mod_mask(self) -> &'static str {
+        match self {
+            Self::Ctrl => "MOD_MASK_CTRL",
+            Self::Shift => "MOD_MASK_SHIFT",
+            Self::Alt => "MOD_MASK_ALT",
+            Self::Gui => "MOD_MASK_GUI",
+        }
+    }
+
+    /// Parses a modifier name (case-insensitive).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "ctrl" | "control" | "ctl" => Some(Self::Ctrl),
+            "shift" | "sft" => Some(Self::Shift),
+            "alt" | "opt" | "option" => Some(Self::Alt),
+            "gui" | "cmd" | "super" | "win" => Some(Self::Gui),
+            _ => None,
+        }
+    }
+}
+
+/// Sends `replacement` instead of `trigger` while `mods` are held.
+///
+/// Generated as a QMK `ko_make_basic` entry in `key_overrides[]`. The
+/// compact form used by the settings editor is
+/// `Shift+KC_BSPC = KC_DEL`: modifiers and the trigger key joined by `+`,
+/// then `=` and the replacement keycode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyOverride {
+    /// Modifiers that must be held (sorted, no duplicates)
+    pub mods: Vec<OverrideModifier>,
+    /// Keycode pressed together with the modifiers
+    pub trigger: String,
+    /// Keycode sent instead
+    pub replacement: String,
+}
+
+impl KeyOverride {
+    /// Creates a key override; modifiers are sorted and deduplicated.
+    #[must_use]
+    pub fn new(
+        mut mods: Vec<OverrideModifier>,
+        trigger: impl Into<String>,
+        replacement: impl Into<String>,
+    ) -> Self {
+        mods.sort_unstable();
+        mods.dedup();
+        Self {
+            mods,
+            trigger: trigger.into(),
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Parses the compact form, e.g. `Shift+KC_BSPC = KC_DEL`.
+    ///
+    /// Returns `None` if the form is malformed or no modifier is given.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let (trigger_part, replacement) = value.split_once('=')?;
+        let mut parts: Vec<&str> = trigger_part.split('+').map(str::trim).collect();
+        let trigger = parts.pop()?;
+        let mods = parts
+            .into_iter()
+            .map(OverrideModifier::from_name)
+            .collect::<Option<Vec<_>>>()?;
+        let replacement = replacement.trim();
+        if mods.is_empty() || trigger.is_empty() || replacement.is_empty() {
+            return None;
+        }
+        Some(Self::new(mods, trigger, replacement))
+    }
+
+    /// Returns the modifier mask expression, e.g. `MOD_MASK_CTRL | MOD_MASK_SHIFT`.
+    #[must_use]
+    pub fn mod_mask(&self) -> String {
+        self.mods
+            .iter()
+            .map(|m| m.mod_mask())
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// Validates the override.
+    ///
+    /// Checks:
+    /// - At least one modifier is required
+    /// - Trigger and replacement keycodes are non-empty and differ
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.mods.is_empty() {
+            anyhow::bail!(
+                "Key override for {} needs at least one modifier",
+                self.trigger
+            );
+        }
+        if self.trigger.is_empty() || self.replacement.is_empty() {
+            anyhow::bail!("Key override keycodes cannot be empty");
+        }
+        if self.trigger == self.replacement {
+            anyhow::bail!(
+                "Key override for {} must send a different key",
+                self.trigger
+            );
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for KeyOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for modifier in &self.mods {
+            write!(f, "{}+", modifier.display_name())?;
+        }
+        write!(f, "{} = {}", self.trigger, self.replacement)
+    }
+}
@@ -0,0 +1,238 @@
+//! Additional keyboards a layout can be generated for.
+//!
+//! A layout is edited against its primary keyboard (`metadata.keyboard`).
+//! Each [`KeyboardTarget`] names another board sharing the same logical
+//! layers, e.g. a Lily58 next to a Corne. Key positions are carried over
+//! unchanged unless shifted by the target's row/column offset or listed in
+//! its `remap`; a remap entry without `to` leaves the key off that board.
+//! [`Layout::for_target`] produces the layout as seen by a target, ready for
+//! firmware generation.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::models::layer::Position;
+
+use super::layout_core::{Layout, LayoutMetadata};
+
+/// Moves one key of the primary layout to another position on a target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionRemap {
+    /// Visual position on the primary keyboard
+    pub from: Position,
+    /// Visual position on the target (`None` drops the key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Position>,
+}
+
+/// Another keyboard the layout is generated for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyboardTarget {
+    /// Short unique name (e.g., "lily58"), used for output directories
+    pub name: String,
+    /// QMK keyboard path (e.g., "lily58/rev1")
+    pub keyboard: String,
+    /// QMK layout variant (e.g., "LAYOUT")
+    pub layout_variant: String,
+    /// QMK keymap name; the layout's own is used when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keymap_name: Option<String>,
+    /// Firmware output format; the layout's own is used when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<String>,
+    /// QMK converter for this board (not inherited from the layout)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converter: Option<String>,
+    /// Rows added to every position not listed in `remap`
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub row_offset: i16,
+    /// Columns added to every position not listed in `remap`
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub col_offset: i16,
+    /// Explicit per-key position changes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remap: Vec<PositionRemap>,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // serde's skip_serializing_if passes a reference
+const fn is_zero(value: &i16) -> bool {
+    *value == 0
+}
+
+impl KeyboardTarget {
+    /// Creates a target with unchanged key positions.
+    #[must_use]
+    pub fn new(
+        name: impl Into<String>,
+        keyboard: impl Into<String>,
+        layout_variant: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            keyboard: keyboard.into(),
+            layout_variant: layout_variant.into(),
+            keymap_name: None,
+            output_format: None,
+            converter: None,
+            row_offset: 0,
+            col_offset: 0,
+            remap: Vec::new(),
+        }
+    }
+
+    /// Returns where the key at `position` of the primary layout goes on this
+    /// target, or None if the key is left off.
+    #[must_use]
+    pub fn map_position(&self, position: Position) -> Option<Position> {
+        if let Some(remap) = self.remap.iter().find(|remap| remap.from == position) {
+            return remap.to;
+        }
+        let row = u8::try_from(i16::from(position.row) + self.row_offset).ok()?;
+        let col = u8::try_from(i16::from(position.col) + self.col_offset).ok()?;
+        Some(Position::new(row, col))
+    }
+
+    /// Validates the target's names and remap entries.
+    pub fn validate(&self) -> Result<()> {
+        if self.name.is_empty()
+            || !self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+        {
+            anyhow::bail!(
+                "Keyboard target name '{}' must use lowercase letters, digits, '-' and '_' only",
+                self.name
+            );
+        }
+        if self.keyboard.trim().is_empty() {
+            anyhow::bail!("Keyboard target '{}' has no keyboard", self.name);
+        }
+        if self.layout_variant.trim().is_empty() {
+            anyhow::bail!("Keyboard target '{}' has no layout variant", self.name);
+        }
+        if let Some(converter) = &self.converter {
+            LayoutMetadata::validate_converter(converter)?;
+        }
+
+        let mut sources = HashSet::new();
+        for remap in &self.remap {
+            if !sources.insert(remap.from) {
+                anyhow::bail!(
+                    "Keyboard target '{}' remaps position ({}, {}) more than once",
+                    self.name,
+                    remap.from.row,
+                    remap.from.col
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl LayoutMetadata {
+    /// Validates all keyboard targets and checks that their names are unique.
+    pub fn validate_targets(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        for target in &self.targets {
+            target.validate()?;
+            if !names.insert(target.name.as_str()) {
+                anyhow::bail!("Duplicate keyboard target '{}'", target.name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds a keyboard target by name.
+    #[must_use]
+    pub fn target(&self, name: &str) -> Option<&KeyboardTarget> {
+        self.targets.iter().find(|target| target.name == name)
+    }
+}
+
+impl Layout {
+    /// Returns this layout as seen by the keyboard target `name`.
+    ///
+    /// The metadata describes the target's keyboard (keymap name and output
+    /// format fall back to the layout's own) and lists no targets. Every key
+    /// is moved to its target position; keys left off the target are
+    /// dropped, as are combos using them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such target or two keys of a layer
+    /// land on the same position.
+    pub fn for_target(&self, name: &str) -> Result<Self> {
+        let target = self
+            .metadata
+            .target(name)
+            .ok_or_else(|| anyhow::anyhow!("Keyboard target '{name}' not found"))?
+            .clone();
+
+        let mut layout = self.clone();
+        let metadata = &mut layout.metadata;
+        metadata.keyboard = Some(target.keyboard.clone());
+        metadata.layout_variant = Some(target.layout_variant.clone());
+        if target.keymap_name.is_some() {
+            metadata.keymap_name.clone_from(&target.keymap_name);
+        }
+        if target.output_format.is_some() {
+            metadata.output_format.clone_from(&target.output_format);
+        }
+        metadata.converter.clone_from(&target.converter);
+        metadata.targets.clear();
+
+        for layer in &mut layout.layers {
+            let mut placed: HashMap<Position, Position> = HashMap::new();
+            let keys = std::mem::take(&mut layer.keys);
+            for mut key in keys {
+                let Some(position) = target.map_position(key.position) else {
+                    continue;
+                };
+                if let Some(other) = placed.insert(position, key.position) {
+                    anyhow::bail!(
+                        "Keys ({}, {}) and ({}, {}) of layer '{}' both map to ({}, {}) on '{}'",
+                        other.row,
+                        other.col,
+                        key.position.row,
+                        key.position.col,
+                        layer.name,
+                        position.row,
+                        position.col,
+                        target.name
+                    );
+                }
+                key.position = position;
+                layer.keys.push(key);
+            }
+        }
+
+        let combos = &mut layout.combo_settings;
+        combos.combos.retain_mut(|combo| {
+            match (
+                target.map_position(combo.key1),
+                target.map_position(combo.key2),
+            ) {
+                (Some(key1), Some(key2)) => {
+                    combo.key1 = key1;
+                    combo.key2 = key2;
+                    true
+                }
+                _ => false,
+            }
+        });
+        combos.keycode_combos.retain_mut(|combo| {
+            let keys: Option<Vec<Position>> = combo
+                .keys
+                .iter()
+                .map(|&position| target.map_position(position))
+                .collect();
+            keys.map(|keys| combo.keys = keys).is_some()
+        });
+
+        Ok(layout)
+    }
+}
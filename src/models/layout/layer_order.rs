@@ -0,0 +1,138 @@
+//! Moving and duplicating layers.
+//!
+//! Layer keycodes such as `MO(2)` or `LT(3, KC_SPC)` name their target by
+//! index, so changing the order of layers would silently point them at a
+//! different layer. [`Layout::move_layer`] and [`Layout::duplicate_layer`]
+//! rewrite every numeric reference (keys, encoders, tap dances, keycode
+//! combos and per-layer RGB effects) to follow the layers. References by
+//! `@id` keep working on their own.
+
+use anyhow::Result;
+
+use crate::services::layer_refs::{remap_layer_index, retarget_layer_id};
+
+use super::layout_core::Layout;
+
+impl Layout {
+    /// Moves the layer at `from` to index `to`, shifting the layers between.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either index is out of range.
+    pub fn move_layer(&mut self, from: usize, to: usize) -> Result<()> {
+        let count = self.layers.len();
+        anyhow::ensure!(from < count, "Layer {from} not found");
+        anyhow::ensure!(to < count, "Layer {to} not found");
+        if from == to {
+            return Ok(());
+        }
+
+        let layer = self.layers.remove(from);
+        self.layers.insert(to, layer);
+        self.remap_layer_indices(|n| {
+            if n == from {
+                to
+            } else if from < to && n > from && n <= to {
+                n - 1
+            } else if to < from && n >= to && n < from {
+                n + 1
+            } else {
+                n
+            }
+        });
+        Ok(())
+    }
+
+    /// Inserts a copy of layer `index` right after it and returns the copy's
+    /// index.
+    ///
+    /// The copy is made with [`Layer::duplicate`](crate::models::Layer::duplicate).
+    /// Keys on the copy that referred to the source layer itself (e.g. a
+    /// `TG` to leave it) refer to the copy instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layer doesn't exist, the name is invalid or
+    /// the layout already has 256 layers.
+    pub fn duplicate_layer(&mut self, index: usize, name: &str) -> Result<usize> {
+        let source = self
+            .layers
+            .get(index)
+            .ok_or_else(|| anyhow::anyhow!("Layer {index} not found"))?;
+        let copy_index = index + 1;
+        let number = u8::try_from(copy_index)
+            .ok()
+            .filter(|_| self.layers.len() < 256)
+            .ok_or_else(|| anyhow::anyhow!("Too many layers"))?;
+        let mut copy = source.duplicate(number, name)?;
+
+        // The copy is inserted after the layers are remapped, so shift its
+        // own references here, pointing those to the source at the copy
+        let shift = |n: usize| match n.cmp(&index) {
+            std::cmp::Ordering::Less => n,
+            std::cmp::Ordering::Equal => copy_index,
+            std::cmp::Ordering::Greater => n + 1,
+        };
+        let (source_id, copy_id) = (source.id.clone(), copy.id.clone());
+        let keycodes = copy.keys.iter_mut().map(|key| &mut key.keycode).chain(
+            copy.encoder_map
+                .bindings_mut()
+                .flat_map(|binding| [&mut binding.ccw, &mut binding.cw]),
+        );
+        for keycode in keycodes {
+            if let Some(new) = remap_layer_index(keycode, shift)
+                .or_else(|| retarget_layer_id(keycode, &source_id, &copy_id))
+            {
+                *keycode = new;
+            }
+        }
+
+        self.remap_layer_indices(|n| if n > index { n + 1 } else { n });
+        self.layers.insert(copy_index, copy);
+        for (i, layer) in self.layers.iter_mut().enumerate() {
+            layer.number = i as u8;
+        }
+        Ok(copy_index)
+    }
+
+    /// Rewrites every numeric layer reference through `map` and renumbers
+    /// the layers.
+    fn remap_layer_indices(&mut self, map: impl Fn(usize) -> usize) {
+        let remap = |keycode: &mut String| {
+            if let Some(new) = remap_layer_index(keycode, &map) {
+                *keycode = new;
+            }
+        };
+
+        for (i, layer) in self.layers.iter_mut().enumerate() {
+            layer.number = i as u8;
+            for key in &mut layer.keys {
+                remap(&mut key.keycode);
+            }
+            for binding in layer.encoder_map.bindings_mut() {
+                remap(&mut binding.ccw);
+                remap(&mut binding.cw);
+            }
+        }
+        for tap_dance in &mut self.tap_dances {
+            remap(&mut tap_dance.single_tap);
+            if let Some(keycode) = &mut tap_dance.double_tap {
+                remap(keycode);
+            }
+            if let Some(keycode) = &mut tap_dance.hold {
+                remap(keycode);
+            }
+        }
+        for combo in &mut self.combo_settings.keycode_combos {
+            remap(&mut combo.keycode);
+        }
+        for rule in &mut self.rgb_conditions.layer_effects {
+            rule.layer = map(usize::from(rule.layer)) as u8;
+        }
+        self.rgb_conditions
+            .layer_effects
+            .sort_by_key(|rule| rule.layer);
+        let num_word_layer = usize::from(self.editing_helpers.num_word_layer);
+        self.editing_helpers.num_word_layer = map(num_word_layer) as u8;
+    }
+}
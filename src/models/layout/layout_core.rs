@@ -7,13 +7,19 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::EditingHelpers;
+use super::FirmwareFeatures;
 use super::IdleEffectSettings;
+use super::KeyboardTarget;
 use super::RgbBrightness;
+use super::RgbConditionalSettings;
 use super::RgbOverlayRippleSettings;
 use super::RgbSaturation;
 use super::{
-    ComboSettings, PaletteFxSettings, TapDanceAction, TapHoldSettings, UncoloredKeyBehavior,
+    AltRepeatKey, ComboSettings, CustomKeycode, KeyOverride, MacroDefinition, PaletteFxSettings,
+    TapDanceAction, TapHoldSettings, UncoloredKeyBehavior, UnicodeGlyph, UnicodeInputMode,
 };
+use super::{GeometrySnapshot, TemplatePlaceholder};
 
 /// File metadata embedded in YAML frontmatter.
 ///
@@ -56,6 +62,23 @@ pub struct LayoutMetadata {
     /// Firmware output format: "uf2", "hex", or "bin"
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub output_format: Option<String>,
+    /// QMK converter for a replacement controller (e.g., "kb2040"),
+    /// passed to `qmk compile` as `CONVERT_TO`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converter: Option<String>,
+    /// Other keyboards sharing this layout's layers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<KeyboardTarget>,
+    /// Values a template asks for when applied (`${NAME}` in its text)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub placeholders: Vec<TemplatePlaceholder>,
+    /// Geometry of the keyboard the layout was last saved on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geometry: Option<GeometrySnapshot>,
+    /// Name of the configured QMK tree the layout builds against (the
+    /// default tree when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qmk_tree: Option<String>,
 }
 
 impl LayoutMetadata {
@@ -78,6 +101,11 @@ impl LayoutMetadata {
             keyboard: None,
             keymap_name: None,
             output_format: None,
+            converter: None,
+            targets: Vec::new(),
+            placeholders: Vec::new(),
+            geometry: None,
+            qmk_tree: None,
         })
     }
 
@@ -117,6 +145,21 @@ impl LayoutMetadata {
         Ok(())
     }
 
+    /// Validates a QMK converter name (lowercase, underscores, alphanumeric).
+    pub fn validate_converter(converter: &str) -> Result<()> {
+        if converter.is_empty()
+            || !converter
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            anyhow::bail!(
+                "Converter '{converter}' must be a QMK converter name such as kb2040 or rp2040_ce"
+            );
+        }
+
+        Ok(())
+    }
+
     /// Validates tag format (lowercase, hyphens, alphanumeric).
     #[allow(dead_code)] // Helper for add_tag
     fn validate_tag(tag: &str) -> Result<()> {
@@ -206,6 +249,16 @@ pub struct Layout {
     #[serde(default)]
     pub palette_fx: PaletteFxSettings,
 
+    // === Conditional RGB Behaviors ===
+    /// Dim on inactivity, WPM/per-layer effects and off hours
+    #[serde(default)]
+    pub rgb_conditions: RgbConditionalSettings,
+
+    // === Firmware Features ===
+    /// Overrides of the keyboard's optional firmware features
+    #[serde(default)]
+    pub firmware_features: FirmwareFeatures,
+
     // === Tap-Hold Settings ===
     /// Tap-hold configuration (LT, MT, TT timing and behavior)
     #[serde(default)]
@@ -220,6 +273,44 @@ pub struct Layout {
     /// Tap dance action definitions
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tap_dances: Vec<TapDanceAction>,
+
+    // === Macros ===
+    /// Text and key sequence macro definitions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub macros: Vec<MacroDefinition>,
+
+    // === Custom Keycodes ===
+    /// Keycodes whose behavior is a user-written C snippet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_keycodes: Vec<CustomKeycode>,
+
+    // === Key Overrides ===
+    /// Modifier + key combinations that send a different key
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_overrides: Vec<KeyOverride>,
+
+    // === Repeat Key ===
+    /// Keycodes Alt Repeat Key (`QK_AREP`) sends after specific keys
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alt_repeat_keys: Vec<AltRepeatKey>,
+
+    // === Unicode ===
+    /// Named characters typed by `UM(name)` keycodes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unicode_map: Vec<UnicodeGlyph>,
+    /// Host input modes for Unicode keys, first is the default (empty = QMK default)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unicode_input_modes: Vec<UnicodeInputMode>,
+
+    // === Usage Counting ===
+    /// Count key presses in the firmware and report them on the HID console
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub usage_counting: bool,
+
+    // === Editing Helpers ===
+    /// Select Word, Sentence Case and Num Word switches
+    #[serde(default)]
+    pub editing_helpers: EditingHelpers,
 }
 
 /// Default for `rgb_enabled` is true
@@ -244,9 +335,19 @@ impl Layout {
             idle_effect_settings: IdleEffectSettings::default(),
             rgb_overlay_ripple: RgbOverlayRippleSettings::default(),
             palette_fx: PaletteFxSettings::default(),
+            rgb_conditions: RgbConditionalSettings::default(),
+            firmware_features: FirmwareFeatures::default(),
             tap_hold_settings: TapHoldSettings::default(),
             combo_settings: ComboSettings::default(),
             tap_dances: Vec::new(),
+            macros: Vec::new(),
+            custom_keycodes: Vec::new(),
+            key_overrides: Vec::new(),
+            alt_repeat_keys: Vec::new(),
+            unicode_map: Vec::new(),
+            unicode_input_modes: Vec::new(),
+            usage_counting: false,
+            editing_helpers: EditingHelpers::default(),
         })
     }
 
@@ -472,13 +573,21 @@ impl Layout {
         }
 
         // From here, colors are layer-level (not key-specific)
-        // Apply uncolored_key_behavior
+        (self.resolve_uncolored_display_color(layer_idx), false)
+    }
 
+    /// Resolves the display color of a light with no color of its own.
+    ///
+    /// Used for keys without an override or key category, and for LEDs that
+    /// have no key (underglow). Applies `uncolored_key_behavior` to the layer
+    /// category color or layer default color.
+    #[must_use]
+    pub fn resolve_uncolored_display_color(&self, layer_idx: usize) -> RgbColor {
         // First, check if layer colors are enabled
         if let Some(layer) = self.get_layer(layer_idx) {
             if !layer.layer_colors_enabled {
                 // Layer colors disabled entirely - show gray
-                return (RgbColor::new(64, 64, 64), false);
+                return RgbColor::new(64, 64, 64);
             }
 
             // Get the layer-level color (layer category or default)
@@ -500,11 +609,61 @@ impl Layout {
                 percent => layer_color.dim(percent), // Dim to percentage
             };
 
-            return (display_color, false);
+            return display_color;
         }
 
         // Fallback to white if layer doesn't exist
-        (RgbColor::default(), false)
+        RgbColor::default()
+    }
+
+    /// Resolves the color the generated firmware lights `key` with.
+    ///
+    /// Black when the layer's colors are disabled, otherwise
+    /// [`Layout::resolve_display_color`] with [`Layout::apply_rgb_settings`]
+    /// applied. The firmware generator uses this for every key LED.
+    #[must_use]
+    pub fn resolve_firmware_color(&self, layer_idx: usize, key: &KeyDefinition) -> RgbColor {
+        if self
+            .get_layer(layer_idx)
+            .is_some_and(|layer| !layer.layer_colors_enabled)
+        {
+            return RgbColor::new(0, 0, 0);
+        }
+        let (color, _is_key_specific) = self.resolve_display_color(layer_idx, key);
+        self.apply_rgb_settings(color)
+    }
+
+    /// Resolves the category color of `key`, ignoring color overrides.
+    ///
+    /// The key's own category wins over the layer category. Returns `None`
+    /// when neither is set (or the category no longer exists).
+    #[must_use]
+    pub fn resolve_category_color(
+        &self,
+        layer_idx: usize,
+        key: &KeyDefinition,
+    ) -> Option<RgbColor> {
+        key.category_id
+            .as_deref()
+            .and_then(|id| self.get_category(id))
+            .or_else(|| {
+                self.get_layer(layer_idx)?
+                    .category_id
+                    .as_deref()
+                    .and_then(|id| self.get_category(id))
+            })
+            .map(|category| category.color)
+    }
+
+    /// Resolves the color of LEDs without a key (underglow) on a layer.
+    ///
+    /// Uses the layer's fixed underglow color if set, otherwise the layer
+    /// color as for keys without a color of their own.
+    #[must_use]
+    pub fn resolve_underglow_color(&self, layer_idx: usize) -> RgbColor {
+        self.get_layer(layer_idx)
+            .and_then(|layer| layer.underglow_color)
+            .unwrap_or_else(|| self.resolve_uncolored_display_color(layer_idx))
     }
 
     /// Applies global RGB settings (master switch, saturation, brightness) to a color.
@@ -682,6 +841,232 @@ impl Layout {
             .collect()
     }
 
+    /// Adds a macro to the layout.
+    pub fn add_macro(&mut self, macro_def: MacroDefinition) -> Result<()> {
+        macro_def.validate()?;
+
+        if self.macros.iter().any(|m| m.name == macro_def.name) {
+            anyhow::bail!("Macro with name '{}' already exists", macro_def.name);
+        }
+
+        self.macros.push(macro_def);
+        self.metadata.touch();
+        Ok(())
+    }
+
+    /// Gets a macro by name.
+    #[must_use]
+    pub fn get_macro(&self, name: &str) -> Option<&MacroDefinition> {
+        self.macros.iter().find(|m| m.name == name)
+    }
+
+    /// Removes a macro by name.
+    pub fn remove_macro(&mut self, name: &str) -> Option<MacroDefinition> {
+        if let Some(index) = self.macros.iter().position(|m| m.name == name) {
+            self.metadata.touch();
+            Some(self.macros.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Validates all macro definitions and references in the layout.
+    ///
+    /// Checks:
+    /// - Every MACRO(name) keycode references a defined macro
+    /// - No duplicate macro names
+    /// - Each macro definition is valid
+    pub fn validate_macros(&self) -> Result<()> {
+        let macro_pattern = regex::Regex::new(r"MACRO\(([^)]+)\)").unwrap();
+
+        for layer in &self.layers {
+            for key in &layer.keys {
+                if let Some(captures) = macro_pattern.captures(&key.keycode) {
+                    if self.get_macro(&captures[1]).is_none() {
+                        anyhow::bail!("Macro '{}' is referenced but not defined", &captures[1]);
+                    }
+                }
+            }
+        }
+
+        let mut seen_names = std::collections::HashSet::new();
+        for macro_def in &self.macros {
+            if !seen_names.insert(&macro_def.name) {
+                anyhow::bail!("Duplicate macro name: {}", macro_def.name);
+            }
+            macro_def.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds a custom keycode to the layout.
+    pub fn add_custom_keycode(&mut self, custom: CustomKeycode) -> Result<()> {
+        custom.validate()?;
+
+        if self.get_custom_keycode(&custom.name).is_some() {
+            anyhow::bail!("Custom keycode '{}' already exists", custom.name);
+        }
+
+        self.custom_keycodes.push(custom);
+        self.metadata.touch();
+        Ok(())
+    }
+
+    /// Gets a custom keycode by name.
+    #[must_use]
+    pub fn get_custom_keycode(&self, name: &str) -> Option<&CustomKeycode> {
+        self.custom_keycodes.iter().find(|c| c.name == name)
+    }
+
+    /// Removes a custom keycode by name.
+    pub fn remove_custom_keycode(&mut self, name: &str) -> Option<CustomKeycode> {
+        let index = self.custom_keycodes.iter().position(|c| c.name == name)?;
+        self.metadata.touch();
+        Some(self.custom_keycodes.remove(index))
+    }
+
+    /// Validates the custom keycodes.
+    ///
+    /// Checks:
+    /// - At most `MAX_CUSTOM_KEYCODES` custom keycodes, with unique names
+    /// - Each custom keycode is valid
+    pub fn validate_custom_keycodes(&self) -> Result<()> {
+        use super::custom_keycode::MAX_CUSTOM_KEYCODES;
+
+        if self.custom_keycodes.len() > MAX_CUSTOM_KEYCODES {
+            anyhow::bail!("Maximum of {MAX_CUSTOM_KEYCODES} custom keycodes allowed");
+        }
+
+        let mut seen_names = std::collections::HashSet::new();
+        for custom in &self.custom_keycodes {
+            if !seen_names.insert(&custom.name) {
+                anyhow::bail!("Duplicate custom keycode name: {}", custom.name);
+            }
+            custom.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// Validates key overrides.
+    ///
+    /// Checks:
+    /// - At most `MAX_KEY_OVERRIDES` overrides
+    /// - No two overrides share the same modifiers and trigger key
+    /// - Each override is valid
+    pub fn validate_key_overrides(&self) -> Result<()> {
+        use super::key_override::MAX_KEY_OVERRIDES;
+
+        if self.key_overrides.len() > MAX_KEY_OVERRIDES {
+            anyhow::bail!("Maximum of {MAX_KEY_OVERRIDES} key overrides allowed");
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for key_override in &self.key_overrides {
+            if !seen.insert((&key_override.mods, &key_override.trigger)) {
+                anyhow::bail!("Duplicate key override: {key_override}");
+            }
+            key_override.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// Validates alt repeat overrides.
+    ///
+    /// Checks:
+    /// - At most `MAX_ALT_REPEAT_KEYS` overrides
+    /// - No two overrides share the same keycode
+    /// - Each override is valid
+    pub fn validate_alt_repeat_keys(&self) -> Result<()> {
+        use super::repeat_key::MAX_ALT_REPEAT_KEYS;
+
+        if self.alt_repeat_keys.len() > MAX_ALT_REPEAT_KEYS {
+            anyhow::bail!("Maximum of {MAX_ALT_REPEAT_KEYS} alt repeat overrides allowed");
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for alt_repeat in &self.alt_repeat_keys {
+            if !seen.insert(&alt_repeat.keycode) {
+                anyhow::bail!("Duplicate alt repeat override for {}", alt_repeat.keycode);
+            }
+            alt_repeat.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets a Unicode map glyph by name.
+    #[must_use]
+    pub fn get_unicode_glyph(&self, name: &str) -> Option<&UnicodeGlyph> {
+        self.unicode_map.iter().find(|g| g.name == name)
+    }
+
+    /// Validates the Unicode map, input modes and Unicode keycodes.
+    ///
+    /// Checks:
+    /// - At most `MAX_UNICODE_GLYPHS` glyphs, with unique names
+    /// - Each glyph is valid
+    /// - No input mode is listed twice
+    /// - Every UM(name) keycode references a defined glyph
+    /// - Every UC(0x...) keycode is a valid code point
+    pub fn validate_unicode(&self) -> Result<()> {
+        use super::unicode::{uc_codepoint, um_name, MAX_UNICODE_GLYPHS};
+
+        if self.unicode_map.len() > MAX_UNICODE_GLYPHS {
+            anyhow::bail!("Maximum of {MAX_UNICODE_GLYPHS} Unicode map glyphs allowed");
+        }
+
+        // Names become C enum members, so they must differ ignoring case
+        let mut seen_names = std::collections::HashSet::new();
+        for glyph in &self.unicode_map {
+            if !seen_names.insert(glyph.enum_name()) {
+                anyhow::bail!("Duplicate Unicode glyph name: {}", glyph.name);
+            }
+            glyph.validate()?;
+        }
+
+        let mut seen_modes = std::collections::HashSet::new();
+        for mode in &self.unicode_input_modes {
+            if !seen_modes.insert(mode) {
+                anyhow::bail!("Unicode input mode {} is listed twice", mode.display_name());
+            }
+        }
+
+        for layer in &self.layers {
+            for key in &layer.keys {
+                if let Some(name) = um_name(&key.keycode) {
+                    if self.get_unicode_glyph(name).is_none() {
+                        anyhow::bail!("Unicode glyph '{name}' is referenced but not defined");
+                    }
+                } else if key.keycode.starts_with("UC(")
+                    && uc_codepoint(&key.keycode)
+                        .and_then(char::from_u32)
+                        .is_none()
+                {
+                    anyhow::bail!(
+                        "Invalid Unicode keycode '{}' in layer {}",
+                        key.keycode,
+                        layer.number
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the Unicode input modes as a comma-separated list of names.
+    #[must_use]
+    pub fn unicode_input_modes_display(&self) -> String {
+        self.unicode_input_modes
+            .iter()
+            .map(|mode| mode.display_name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
     /// Resolves layer references in a keycode to layer indices.
     ///
     /// Uses the keycode database to detect layer keycodes dynamically,
@@ -727,6 +1112,10 @@ impl Layout {
     /// - No duplicate positions within each layer
     /// - All category references exist
     /// - All tap dance references are valid
+    /// - All macro references are valid
+    /// - All key overrides are valid
+    /// - All alt repeat overrides are valid
+    /// - The Unicode map and Unicode keycodes are valid
     pub fn validate(&self) -> Result<()> {
         if self.layers.is_empty() {
             anyhow::bail!("Layout must have at least one layer");
@@ -803,9 +1192,33 @@ impl Layout {
         // Validate tap dance actions and references
         self.validate_tap_dances()?;
 
+        // Validate macro definitions and references
+        self.validate_macros()?;
+
+        // Validate custom keycodes
+        self.validate_custom_keycodes()?;
+
+        // Validate key overrides
+        self.validate_key_overrides()?;
+
+        // Validate alt repeat overrides
+        self.validate_alt_repeat_keys()?;
+
+        // Validate Unicode map, input modes and references
+        self.validate_unicode()?;
+
         // Validate ripple settings even when loaded from embedded/frontmatter data
         self.rgb_overlay_ripple.validate()?;
 
+        // Conditional RGB rules may reference layers by index
+        self.rgb_conditions.validate(self.layers.len())?;
+
+        // Num Word references a layer by index
+        self.editing_helpers.validate(self.layers.len())?;
+
+        // Template placeholders
+        self.metadata.validate_placeholders()?;
+
         Ok(())
     }
 }
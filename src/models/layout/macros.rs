@@ -0,0 +1,182 @@
+//! Text and key sequence macro definitions.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Longest delay a single macro step may wait, in milliseconds.
+pub const MAX_MACRO_DELAY_MS: u32 = 10_000;
+
+/// One step of a macro sequence.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum MacroStep {
+    /// Types literal text (emitted as `SEND_STRING`)
+    Text(String),
+    /// Taps a keycode (emitted as `tap_code16`)
+    Tap(String),
+    /// Waits the given number of milliseconds (emitted as `wait_ms`)
+    Delay(u32),
+}
+
+/// A macro that types text and taps keys when its key is pressed.
+///
+/// Keys reference macros as `MACRO(name)`. The firmware generator turns each
+/// macro into a custom keycode handled in `process_record_user`.
+///
+/// Steps have a compact one-line form used by the markdown format and the
+/// macro editor: literal text, `{KEYCODE}` for a key tap, `{Nms}` for a
+/// delay, and `{{` for a literal brace, e.g. `git status{KC_ENT}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MacroDefinition {
+    /// Unique name for this macro (used in `MACRO()` references)
+    /// Must be a valid C identifier (alphanumeric + underscore)
+    pub name: String,
+    /// Steps performed in order when the key is pressed
+    pub steps: Vec<MacroStep>,
+}
+
+impl MacroDefinition {
+    /// Creates a macro from the compact sequence form (see type docs).
+    pub fn from_sequence(name: impl Into<String>, sequence: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            name: name.into(),
+            steps: parse_sequence(sequence)?,
+        })
+    }
+
+    /// Returns the keycode that triggers this macro, e.g. `MACRO(email)`.
+    #[must_use]
+    pub fn keycode(&self) -> String {
+        format!("MACRO({})", self.name)
+    }
+
+    /// Returns the C custom keycode name, e.g. `MACRO_EMAIL`.
+    #[must_use]
+    pub fn enum_name(&self) -> String {
+        format!("MACRO_{}", self.name.to_uppercase())
+    }
+
+    /// Returns the steps in compact sequence form.
+    #[must_use]
+    pub fn sequence(&self) -> String {
+        let mut out = String::new();
+        for step in &self.steps {
+            match step {
+                MacroStep::Text(text) => out.push_str(&text.replace('{', "{{")),
+                MacroStep::Tap(keycode) => {
+                    out.push('{');
+                    out.push_str(keycode);
+                    out.push('}');
+                }
+                MacroStep::Delay(ms) => {
+                    let _ = write!(out, "{{{ms}ms}}");
+                }
+            }
+        }
+        out
+    }
+
+    /// Validates the macro.
+    ///
+    /// Checks:
+    /// - Name is non-empty and valid C identifier
+    /// - At least one step is defined
+    /// - Text steps are non-empty ASCII (`SEND_STRING` only types ASCII)
+    /// - Tap steps are non-empty
+    /// - Delays are between 1 and `MAX_MACRO_DELAY_MS`
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.name.is_empty() {
+            anyhow::bail!("Macro name cannot be empty");
+        }
+
+        if !self
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            anyhow::bail!(
+                "Macro name '{}' must be alphanumeric with underscores only",
+                self.name
+            );
+        }
+
+        if self.steps.is_empty() {
+            anyhow::bail!("Macro '{}' must have at least one step", self.name);
+        }
+
+        for step in &self.steps {
+            match step {
+                MacroStep::Text(text) if text.is_empty() => {
+                    anyhow::bail!("Macro '{}': text cannot be empty", self.name);
+                }
+                MacroStep::Text(text) if !text.is_ascii() => {
+                    anyhow::bail!(
+                        "Macro '{}': text must be ASCII (use key taps for other characters)",
+                        self.name
+                    );
+                }
+                MacroStep::Tap(keycode) if keycode.is_empty() => {
+                    anyhow::bail!("Macro '{}': key tap cannot be empty", self.name);
+                }
+                MacroStep::Delay(ms) if *ms == 0 || *ms > MAX_MACRO_DELAY_MS => {
+                    anyhow::bail!(
+                        "Macro '{}': delay must be between 1 and {MAX_MACRO_DELAY_MS} ms",
+                        self.name
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the compact sequence form into steps.
+///
+/// Adjacent text is merged into one step. A brace token of digits followed
+/// by `ms` is a delay; any other token is a key tap. Returns an error for an
+/// unclosed `{` or an empty `{}`.
+pub fn parse_sequence(sequence: &str) -> anyhow::Result<Vec<MacroStep>> {
+    let mut steps = Vec::new();
+    let mut text = String::new();
+    let mut chars = sequence.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            text.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            text.push('{');
+            continue;
+        }
+
+        let mut token = String::new();
+        loop {
+            match chars.next() {
+                Some('}') => break,
+                Some(c) => token.push(c),
+                None => anyhow::bail!("Unclosed '{{' in macro sequence"),
+            }
+        }
+        let token = token.trim();
+        if token.is_empty() {
+            anyhow::bail!("Empty '{{}}' in macro sequence");
+        }
+
+        if !text.is_empty() {
+            steps.push(MacroStep::Text(std::mem::take(&mut text)));
+        }
+        match token.strip_suffix("ms").map(|ms| ms.trim().parse::<u32>()) {
+            Some(Ok(ms)) => steps.push(MacroStep::Delay(ms)),
+            _ => steps.push(MacroStep::Tap(token.to_string())),
+        }
+    }
+
+    if !text.is_empty() {
+        steps.push(MacroStep::Text(text));
+    }
+    Ok(steps)
+}
@@ -6,28 +6,51 @@
 #![allow(clippy::trivially_copy_pass_by_ref)]
 
 pub mod combo;
+pub mod custom_keycode;
+pub mod editing_helpers;
+pub mod firmware_features;
+pub mod geometry_snapshot;
 pub mod idle_effect_settings;
+pub mod key_override;
+pub mod keyboard_target;
+pub mod layer_order;
 pub mod layout_core;
+pub mod macros;
 pub mod palette_fx;
+pub mod repeat_key;
 pub mod rgb_brightness;
+pub mod rgb_conditions;
 pub mod rgb_matrix_effect;
 pub mod rgb_saturation;
 pub mod ripple;
 pub mod tap_dance;
 pub mod tap_hold;
+pub mod template_placeholder;
 pub mod uncolored_key_behavior;
+pub mod unicode;
 
 #[cfg(test)]
 mod tests;
 
-pub use combo::{ComboAction, ComboDefinition, ComboSettings};
+pub use combo::{ComboAction, ComboDefinition, ComboSettings, KeycodeCombo};
+pub use custom_keycode::CustomKeycode;
+pub use editing_helpers::{EditingHelper, EditingHelpers};
+pub use firmware_features::{FirmwareFeature, FirmwareFeatures};
+pub use geometry_snapshot::GeometrySnapshot;
 pub use idle_effect_settings::IdleEffectSettings;
+pub use key_override::{KeyOverride, OverrideModifier};
+pub use keyboard_target::{KeyboardTarget, PositionRemap};
 pub use layout_core::{Layout, LayoutMetadata};
+pub use macros::{MacroDefinition, MacroStep};
 pub use palette_fx::{PaletteFxEffect, PaletteFxPalette, PaletteFxSettings};
+pub use repeat_key::AltRepeatKey;
 pub use rgb_brightness::RgbBrightness;
+pub use rgb_conditions::{RgbConditionalSettings, RgbOffHours};
 pub use rgb_matrix_effect::RgbMatrixEffect;
 pub use rgb_saturation::RgbSaturation;
 pub use ripple::{RgbOverlayRippleSettings, RippleColorMode};
 pub use tap_dance::TapDanceAction;
-pub use tap_hold::{HoldDecisionMode, TapHoldPreset, TapHoldSettings};
+pub use tap_hold::{HoldDecisionMode, KeyTappingTerm, TapHoldPreset, TapHoldSettings};
+pub use template_placeholder::TemplatePlaceholder;
 pub use uncolored_key_behavior::UncoloredKeyBehavior;
+pub use unicode::{UnicodeGlyph, UnicodeInputMode};
@@ -0,0 +1,74 @@
+//! Repeat Key and Alt Repeat Key — `QK_REP` sends the last key again,
+//! `QK_AREP` sends its "opposite", e.g. Left after Right.
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of alt repeat overrides per layout.
+pub const MAX_ALT_REPEAT_KEYS: usize = 32;
+
+/// Keycodes that need QMK's Repeat Key feature, including their aliases.
+pub const REPEAT_KEYCODES: [&str; 4] = ["QK_REP", "QK_REPEAT_KEY", "QK_AREP", "QK_ALT_REPEAT_KEY"];
+
+/// Returns true if `keycode` is a Repeat Key or Alt Repeat Key keycode.
+#[must_use]
+pub fn is_repeat_keycode(keycode: &str) -> bool {
+    REPEAT_KEYCODES.contains(&keycode)
+}
+
+/// Alt Repeat Key sends `alt_keycode` when the last key was `keycode`.
+///
+/// Generated as a `case` in `get_alt_repeat_key_keycode_user`; keys without
+/// an override keep QMK's built-in alternates. The compact form used by the
+/// settings editor is `KC_LEFT = KC_RGHT`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AltRepeatKey {
+    /// Last pressed keycode
+    pub keycode: String,
+    /// Keycode Alt Repeat sends after it
+    pub alt_keycode: String,
+}
+
+impl AltRepeatKey {
+    /// Creates an alt repeat override.
+    #[must_use]
+    pub fn new(keycode: impl Into<String>, alt_keycode: impl Into<String>) -> Self {
+        Self {
+            keycode: keycode.into(),
+            alt_keycode: alt_keycode.into(),
+        }
+    }
+
+    /// Parses the compact form, e.g. `KC_LEFT = KC_RGHT`.
+    ///
+    /// Returns `None` if either side is missing.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let (keycode, alt_keycode) = value.split_once('=')?;
+        let (keycode, alt_keycode) = (keycode.trim(), alt_keycode.trim());
+        if keycode.is_empty() || alt_keycode.is_empty() {
+            return None;
+        }
+        Some(Self::new(keycode, alt_keycode))
+    }
+
+    /// Validates the override.
+    ///
+    /// Checks:
+    /// - Both keycodes are non-empty
+    /// - Neither keycode is itself a repeat key
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.keycode.is_empty() || self.alt_keycode.is_empty() {
+            anyhow::bail!("Alt repeat keycodes cannot be empty");
+        }
+        if is_repeat_keycode(&self.keycode) || is_repeat_keycode(&self.alt_keycode) {
+            anyhow::bail!("Alt repeat override {self} cannot use a repeat key");
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for AltRepeatKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.keycode, self.alt_keycode)
+    }
+}
@@ -0,0 +1,169 @@
+//! Conditional RGB behaviors (dim on inactivity, WPM and per-layer effects,
+//! off hours).
+//!
+//! These settings are turned into firmware logic by the generator so users
+//! do not have to hand-write the equivalent C in their keymap.
+
+use serde::{Deserialize, Serialize};
+
+use super::RgbMatrixEffect;
+
+/// Minutes in a day, used to validate clock times.
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// Effect override applied while a layer is the highest active layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RgbLayerEffect {
+    /// Layer index (0-based)
+    pub layer: u8,
+    /// Effect shown while the layer is active
+    pub effect: RgbMatrixEffect,
+}
+
+/// Daily time range during which RGB is switched off.
+///
+/// Times are minutes since midnight. A range whose end is before its start
+/// wraps past midnight (e.g. 22:00-07:00).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RgbOffHours {
+    /// Start of the range, in minutes since midnight
+    pub start_minute: u16,
+    /// End of the range (exclusive), in minutes since midnight
+    pub end_minute: u16,
+}
+
+impl RgbOffHours {
+    /// Parses a range written as `HH:MM-HH:MM`.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let (start, end) = value.split_once('-')?;
+        Some(Self {
+            start_minute: parse_clock_time(start.trim())?,
+            end_minute: parse_clock_time(end.trim())?,
+        })
+    }
+}
+
+impl std::fmt::Display for RgbOffHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}-{:02}:{:02}",
+            self.start_minute / 60,
+            self.start_minute % 60,
+            self.end_minute / 60,
+            self.end_minute % 60
+        )
+    }
+}
+
+/// Parses `HH:MM` into minutes since midnight.
+fn parse_clock_time(value: &str) -> Option<u16> {
+    let (hours, minutes) = value.split_once(':')?;
+    let hours: u16 = hours.parse().ok()?;
+    let minutes: u16 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+/// Conditional RGB behaviors generated as firmware logic.
+///
+/// Rules are evaluated in priority order: off hours, then the WPM effect,
+/// then per-layer effects. Dimming is applied on top of whichever effect
+/// is active.
+///
+/// Keyboards have no real-time clock, so off hours only take effect once
+/// the host has sent the current time over Raw HID (see the README).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RgbConditionalSettings {
+    /// Minutes of inactivity before dimming (0 = never dim)
+    #[serde(default)]
+    pub dim_after_minutes: u16,
+
+    /// Brightness while dimmed, as a percentage of the current brightness
+    #[serde(default = "default_dim_brightness_pct")]
+    pub dim_brightness_pct: u8,
+
+    /// Typing speed at which `wpm_effect` takes over (0 = disabled)
+    #[serde(default)]
+    pub wpm_threshold: u8,
+
+    /// Effect shown while typing at or above `wpm_threshold`
+    #[serde(default)]
+    pub wpm_effect: RgbMatrixEffect,
+
+    /// Effects to switch to while specific layers are active
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layer_effects: Vec<RgbLayerEffect>,
+
+    /// Daily range during which RGB is off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub off_hours: Option<RgbOffHours>,
+}
+
+const fn default_dim_brightness_pct() -> u8 {
+    30
+}
+
+impl Default for RgbConditionalSettings {
+    fn default() -> Self {
+        Self {
+            dim_after_minutes: 0,
+            dim_brightness_pct: default_dim_brightness_pct(),
+            wpm_threshold: 0,
+            wpm_effect: RgbMatrixEffect::default(),
+            layer_effects: Vec::new(),
+            off_hours: None,
+        }
+    }
+}
+
+impl RgbConditionalSettings {
+    /// Returns true if any conditional behavior is configured.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.dim_after_minutes > 0
+            || self.wpm_threshold > 0
+            || !self.layer_effects.is_empty()
+            || self.off_hours.is_some()
+    }
+
+    /// Checks if any settings differ from defaults.
+    #[must_use]
+    pub fn has_custom_settings(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Sets (or with `None`, removes) the effect override for `layer`.
+    pub fn set_layer_effect(&mut self, layer: u8, effect: Option<RgbMatrixEffect>) {
+        self.layer_effects.retain(|rule| rule.layer != layer);
+        if let Some(effect) = effect {
+            self.layer_effects.push(RgbLayerEffect { layer, effect });
+            self.layer_effects.sort_by_key(|rule| rule.layer);
+        }
+    }
+
+    /// Validates settings against a layout with `layer_count` layers.
+    pub fn validate(&self, layer_count: usize) -> anyhow::Result<()> {
+        if self.dim_brightness_pct > 100 {
+            anyhow::bail!("dim_brightness_pct must be between 0 and 100");
+        }
+        if let Some(range) = self.off_hours {
+            if range.start_minute >= MINUTES_PER_DAY || range.end_minute >= MINUTES_PER_DAY {
+                anyhow::bail!("off hours must be times between 00:00 and 23:59");
+            }
+            if range.start_minute == range.end_minute {
+                anyhow::bail!("off hours start and end must differ");
+            }
+        }
+        for rule in &self.layer_effects {
+            if usize::from(rule.layer) >= layer_count {
+                anyhow::bail!(
+                    "RGB layer effect references layer {}, but the layout has {} layers",
+                    rule.layer,
+                    layer_count
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::layer::Position;
+
 /// How QMK decides between tap and hold when other keys are involved.
 ///
 /// These modes affect what happens when you press a tap-hold key and then
@@ -139,6 +141,7 @@ impl TapHoldPreset {
                 tapping_toggle: 5,
                 flow_tap_term: Some(150),
                 chordal_hold: true,
+                per_key_tapping_terms: Vec::new(),
                 preset: Self::HomeRowMods,
             },
             Self::Responsive => TapHoldSettings {
@@ -149,6 +152,7 @@ impl TapHoldPreset {
                 tapping_toggle: 5,
                 flow_tap_term: None,
                 chordal_hold: false,
+                per_key_tapping_terms: Vec::new(),
                 preset: Self::Responsive,
             },
             Self::Deliberate => TapHoldSettings {
@@ -159,12 +163,22 @@ impl TapHoldPreset {
                 tapping_toggle: 5,
                 flow_tap_term: None,
                 chordal_hold: false,
+                per_key_tapping_terms: Vec::new(),
                 preset: Self::Deliberate,
             },
         }
     }
 }
 
+/// Tapping term override for one key position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyTappingTerm {
+    /// Visual position of the key (applies on every layer)
+    pub position: Position,
+    /// Tapping term in milliseconds for this key
+    pub tapping_term: u16,
+}
+
 /// Configuration for tap-hold behavior (LT, MT, TT, etc.).
 ///
 /// These settings control how QMK distinguishes between tap and hold actions
@@ -208,6 +222,13 @@ pub struct TapHoldSettings {
     /// Excellent for home-row mods.
     pub chordal_hold: bool,
 
+    // === Per-Key Overrides ===
+    /// Tapping terms for individual key positions, e.g. slower pinky mods.
+    /// Generated as `get_tapping_term()` with `TAPPING_TERM_PER_KEY`.
+    /// Kept when a preset is applied.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub per_key_tapping_terms: Vec<KeyTappingTerm>,
+
     // === Preset Tracking ===
     /// Which preset these settings are based on (Custom if modified).
     #[serde(default)]
@@ -224,6 +245,7 @@ impl Default for TapHoldSettings {
             tapping_toggle: 5,
             flow_tap_term: None,
             chordal_hold: false,
+            per_key_tapping_terms: Vec::new(),
             preset: TapHoldPreset::Default,
         }
     }
@@ -236,9 +258,11 @@ impl TapHoldSettings {
         preset.settings()
     }
 
-    /// Applies a preset, updating all values.
+    /// Applies a preset, updating all values except per-key tapping terms.
     pub fn apply_preset(&mut self, preset: TapHoldPreset) {
+        let per_key_tapping_terms = std::mem::take(&mut self.per_key_tapping_terms);
         *self = Self::from_preset(preset);
+        self.per_key_tapping_terms = per_key_tapping_terms;
     }
 
     /// Marks settings as custom (called when any value is manually changed).
@@ -258,6 +282,37 @@ impl TapHoldSettings {
         self.quick_tap_term.is_some()
     }
 
+    /// Returns the tapping term override for a key position, if any.
+    #[must_use]
+    pub fn key_tapping_term(&self, position: Position) -> Option<u16> {
+        self.per_key_tapping_terms
+            .iter()
+            .find(|entry| entry.position == position)
+            .map(|entry| entry.tapping_term)
+    }
+
+    /// Returns the tapping term that applies to a key position.
+    #[must_use]
+    pub fn tapping_term_for(&self, position: Position) -> u16 {
+        self.key_tapping_term(position).unwrap_or(self.tapping_term)
+    }
+
+    /// Sets or clears (`None`) the tapping term override for a key position.
+    ///
+    /// Overrides are kept sorted by row, then column.
+    pub fn set_key_tapping_term(&mut self, position: Position, tapping_term: Option<u16>) {
+        self.per_key_tapping_terms
+            .retain(|entry| entry.position != position);
+        if let Some(tapping_term) = tapping_term {
+            self.per_key_tapping_terms.push(KeyTappingTerm {
+                position,
+                tapping_term,
+            });
+            self.per_key_tapping_terms
+                .sort_by_key(|entry| (entry.position.row, entry.position.col));
+        }
+    }
+
     /// Checks if any non-default settings are configured.
     #[must_use]
     pub fn has_custom_settings(&self) -> bool {
@@ -268,6 +323,7 @@ impl TapHoldSettings {
             || self.tapping_toggle != 5
             || self.flow_tap_term.is_some()
             || self.chordal_hold
+            || !self.per_key_tapping_terms.is_empty()
     }
 
     /// Validates settings are within acceptable ranges.
@@ -288,6 +344,16 @@ impl TapHoldSettings {
                 anyhow::bail!("Flow tap term must be at most 500ms");
             }
         }
+        let mut seen = std::collections::HashSet::new();
+        for entry in &self.per_key_tapping_terms {
+            let Position { row, col } = entry.position;
+            if !seen.insert(entry.position) {
+                anyhow::bail!("Key ({row}, {col}) has more than one tapping term");
+            }
+            if entry.tapping_term < 50 || entry.tapping_term > 1000 {
+                anyhow::bail!("Tapping term for key ({row}, {col}) must be between 50 and 1000ms");
+            }
+        }
         Ok(())
     }
 }
@@ -0,0 +1,208 @@
+//! Placeholders a template asks for when it is applied.
+//!
+//! A template can write `${NAME}` in any text: keycodes, layer names, tap
+//! dances, macros, its own name and description. Each name is declared in
+//! `metadata.placeholders` with a prompt, an optional default and optional
+//! choices. Applying the template replaces every `${NAME}` with the value
+//! picked for it ([`Layout::fill_placeholders`]), so one template can yield
+//! e.g. a macOS flavor (`${OS_MOD}` = `LGUI`) and a Windows flavor
+//! (`LCTL`).
+
+use std::collections::{BTreeSet, HashMap};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::layout_core::{Layout, LayoutMetadata};
+
+/// A value a template asks for when it is applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplatePlaceholder {
+    /// Name used as `${NAME}` (e.g., "`OS_MOD`")
+    pub name: String,
+    /// Prompt shown when the template is applied
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Value used when none is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Allowed values (any value when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<String>,
+}
+
+impl TemplatePlaceholder {
+    /// Creates a placeholder without prompt, default or choices.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: String::new(),
+            default: None,
+            choices: Vec::new(),
+        }
+    }
+
+    /// Validates the placeholder name, default and choices.
+    pub fn validate(&self) -> Result<()> {
+        if !is_placeholder_name(&self.name) {
+            anyhow::bail!(
+                "Placeholder name '{}' must start with a letter and contain only \
+                 uppercase letters, digits and underscores",
+                self.name
+            );
+        }
+        if let Some(default) = &self.default {
+            self.check_value(default)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that `value` can be used for this placeholder.
+    pub fn check_value(&self, value: &str) -> Result<()> {
+        if value.contains("${") {
+            anyhow::bail!("Value for ${{{}}} cannot contain '${{'", self.name);
+        }
+        if !self.choices.is_empty() && !self.choices.iter().any(|choice| choice == value) {
+            anyhow::bail!(
+                "Value '{value}' for ${{{}}} must be one of: {}",
+                self.name,
+                self.choices.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
+
+impl LayoutMetadata {
+    /// Validates all placeholders and checks that their names are unique.
+    pub fn validate_placeholders(&self) -> Result<()> {
+        let mut names = BTreeSet::new();
+        for placeholder in &self.placeholders {
+            placeholder.validate()?;
+            if !names.insert(placeholder.name.as_str()) {
+                anyhow::bail!("Duplicate placeholder '{}'", placeholder.name);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Layout {
+    /// Returns the placeholders to ask for when applying this template.
+    ///
+    /// These are the declared placeholders followed by any `${NAME}` used in
+    /// the layout without a declaration, sorted by name.
+    #[must_use]
+    pub fn required_placeholders(&self) -> Vec<TemplatePlaceholder> {
+        let mut placeholders = self.metadata.placeholders.clone();
+        let mut used = BTreeSet::new();
+        if let Ok(value) = serde_json::to_value(self) {
+            collect_used(&value, &mut used);
+        }
+        for name in used {
+            if !placeholders.iter().any(|p| p.name == name) {
+                placeholders.push(TemplatePlaceholder::new(name));
+            }
+        }
+        placeholders
+    }
+
+    /// Returns a copy of this template with every `${NAME}` replaced by
+    /// `values[NAME]`, or the placeholder's default when no value is given.
+    ///
+    /// The copy declares no placeholders.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a placeholder has neither a value nor a default,
+    /// a value isn't allowed, or the filled-in layout is invalid.
+    pub fn fill_placeholders(&self, values: &HashMap<String, String>) -> Result<Self> {
+        let mut resolved = HashMap::new();
+        let mut missing = Vec::new();
+        for placeholder in self.required_placeholders() {
+            match values
+                .get(&placeholder.name)
+                .or(placeholder.default.as_ref())
+            {
+                Some(value) => {
+                    placeholder.check_value(value)?;
+                    resolved.insert(placeholder.name, value.clone());
+                }
+                None => missing.push(format!("${{{}}}", placeholder.name)),
+            }
+        }
+        if !missing.is_empty() {
+            anyhow::bail!("No value for {}", missing.join(", "));
+        }
+
+        let mut layout = self.clone();
+        layout.metadata.placeholders.clear();
+        let mut value = serde_json::to_value(&layout).context("Failed to serialize template")?;
+        substitute(&mut value, &resolved);
+        let layout: Self =
+            serde_json::from_value(value).context("Filled-in template is not a valid layout")?;
+        layout
+            .validate()
+            .context("Filled-in template is not a valid layout")?;
+        Ok(layout)
+    }
+}
+
+/// Returns true for names like `OS_MOD` or `LAYER2`.
+fn is_placeholder_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Collects the names of all `${NAME}` in the strings of `value`.
+fn collect_used(value: &serde_json::Value, used: &mut BTreeSet<String>) {
+    match value {
+        serde_json::Value::String(text) => {
+            let mut rest = text.as_str();
+            while let Some(start) = rest.find("${") {
+                rest = &rest[start + 2..];
+                let Some(end) = rest.find('}') else {
+                    break;
+                };
+                if is_placeholder_name(&rest[..end]) {
+                    used.insert(rest[..end].to_string());
+                }
+                rest = &rest[end + 1..];
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_used(item, used);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for field in fields.values() {
+                collect_used(field, used);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replaces every `${NAME}` in the strings of `value` with `values[NAME]`.
+fn substitute(value: &mut serde_json::Value, values: &HashMap<String, String>) {
+    match value {
+        serde_json::Value::String(text) if text.contains("${") => {
+            for (name, replacement) in values {
+                *text = text.replace(&format!("${{{name}}}"), replacement);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                substitute(item, values);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for field in fields.values_mut() {
+                substitute(field, values);
+            }
+        }
+        _ => {}
+    }
+}
@@ -1,9 +1,11 @@
 //! Tests for all layout types.
 
-use super::*;
 use super::combo::MAX_COMBOS;
+use super::macros::parse_sequence;
+use super::*;
 use crate::models::layer::{KeyDefinition, Layer, Position};
-use crate::models::{Category, RgbColor};
+use crate::models::{Category, KeyGeometry, KeyboardGeometry, RgbColor};
+use std::collections::HashMap;
 
 #[test]
 fn test_layout_metadata_new() {
@@ -23,6 +25,15 @@ fn test_layout_metadata_validate_name() {
     assert!(LayoutMetadata::new("a".repeat(101)).is_err());
 }
 
+#[test]
+fn test_layout_metadata_validate_converter() {
+    assert!(LayoutMetadata::validate_converter("kb2040").is_ok());
+    assert!(LayoutMetadata::validate_converter("rp2040_ce").is_ok());
+    assert!(LayoutMetadata::validate_converter("").is_err());
+    assert!(LayoutMetadata::validate_converter("KB2040").is_err());
+    assert!(LayoutMetadata::validate_converter("kb 2040").is_err());
+}
+
 #[test]
 fn test_layout_metadata_add_tag() {
     let mut metadata = LayoutMetadata::new("Test").unwrap();
@@ -86,24 +97,30 @@ fn test_layout_add_category_duplicate() {
 
 #[test]
 fn test_ripple_settings_validate_rejects_zero_duration() {
-    let mut settings = RgbOverlayRippleSettings::default();
-    settings.duration_ms = 0;
+    let settings = RgbOverlayRippleSettings {
+        duration_ms: 0,
+        ..Default::default()
+    };
 
     assert!(settings.validate().is_err());
 }
 
 #[test]
 fn test_ripple_settings_validate_rejects_zero_band_width() {
-    let mut settings = RgbOverlayRippleSettings::default();
-    settings.band_width = 0;
+    let settings = RgbOverlayRippleSettings {
+        band_width: 0,
+        ..Default::default()
+    };
 
     assert!(settings.validate().is_err());
 }
 
 #[test]
 fn test_ripple_settings_validate_rejects_zero_speed() {
-    let mut settings = RgbOverlayRippleSettings::default();
-    settings.speed = 0;
+    let settings = RgbOverlayRippleSettings {
+        speed: 0,
+        ..Default::default()
+    };
 
     assert!(settings.validate().is_err());
 }
@@ -119,6 +136,149 @@ fn test_layout_validate_rejects_invalid_ripple_settings() {
     assert!(layout.validate().is_err());
 }
 
+#[test]
+fn test_rgb_off_hours_parse_and_display() {
+    let range = RgbOffHours::parse("22:00 - 07:30").unwrap();
+    assert_eq!(range.start_minute, 22 * 60);
+    assert_eq!(range.end_minute, 7 * 60 + 30);
+    assert_eq!(range.to_string(), "22:00-07:30");
+
+    assert!(RgbOffHours::parse("24:00-07:00").is_none());
+    assert!(RgbOffHours::parse("22:00").is_none());
+}
+
+#[test]
+fn test_key_override_parse_and_display() {
+    let key_override = KeyOverride::parse("shift + ctrl+KC_ESC = KC_GRV").unwrap();
+    assert_eq!(
+        key_override.mods,
+        vec![OverrideModifier::Ctrl, OverrideModifier::Shift]
+    );
+    assert_eq!(key_override.trigger, "KC_ESC");
+    assert_eq!(key_override.replacement, "KC_GRV");
+    assert_eq!(key_override.to_string(), "Ctrl+Shift+KC_ESC = KC_GRV");
+    assert_eq!(key_override.mod_mask(), "MOD_MASK_CTRL | MOD_MASK_SHIFT");
+
+    assert!(KeyOverride::parse("KC_BSPC = KC_DEL").is_none());
+    assert!(KeyOverride::parse("Hyper+KC_BSPC = KC_DEL").is_none());
+    assert!(KeyOverride::parse("Shift+KC_BSPC").is_none());
+}
+
+#[test]
+fn test_layout_validate_rejects_duplicate_key_overrides() {
+    let mut layout = Layout::new("Test").unwrap();
+    layout
+        .add_layer(Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap())
+        .unwrap();
+    layout.key_overrides = vec![KeyOverride::parse("Shift+KC_BSPC = KC_DEL").unwrap()];
+    assert!(layout.validate().is_ok());
+
+    layout
+        .key_overrides
+        .push(KeyOverride::parse("Shift+KC_BSPC = KC_INS").unwrap());
+    assert!(layout.validate().is_err());
+
+    layout.key_overrides = vec![KeyOverride::parse("Shift+KC_A = KC_A").unwrap()];
+    assert!(layout.validate().is_err());
+}
+
+#[test]
+fn test_alt_repeat_key_parse_and_validate() {
+    let alt_repeat = AltRepeatKey::parse(" KC_LEFT=KC_RGHT ").unwrap();
+    assert_eq!(alt_repeat, AltRepeatKey::new("KC_LEFT", "KC_RGHT"));
+    assert_eq!(alt_repeat.to_string(), "KC_LEFT = KC_RGHT");
+    assert!(AltRepeatKey::parse("KC_LEFT").is_none());
+    assert!(AltRepeatKey::parse("KC_LEFT = ").is_none());
+
+    let mut layout = Layout::new("Test").unwrap();
+    layout
+        .add_layer(Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap())
+        .unwrap();
+    layout.alt_repeat_keys = vec![alt_repeat];
+    assert!(layout.validate().is_ok());
+
+    layout
+        .alt_repeat_keys
+        .push(AltRepeatKey::new("KC_LEFT", "KC_HOME"));
+    assert!(layout.validate().is_err());
+
+    layout.alt_repeat_keys = vec![AltRepeatKey::new("KC_A", "QK_AREP")];
+    assert!(layout.validate().is_err());
+}
+
+#[test]
+fn test_unicode_codepoint_parsing() {
+    use super::unicode::{parse_codepoint, uc_codepoint, um_name};
+
+    assert_eq!(parse_codepoint("é"), Some(0xE9));
+    assert_eq!(parse_codepoint(" U+1F40D "), Some(0x1F40D));
+    assert_eq!(parse_codepoint("0x2764"), Some(0x2764));
+    assert_eq!(parse_codepoint("ab"), None);
+    assert_eq!(uc_codepoint("UC(0x00E9)"), Some(0xE9));
+    assert_eq!(uc_codepoint("UC(233)"), None);
+    assert_eq!(um_name("UM(snek)"), Some("snek"));
+    assert_eq!(um_name("UM()"), None);
+
+    let glyph = UnicodeGlyph::new("snek", 0x1F40D);
+    assert_eq!(glyph.enum_name(), "UM_SNEK");
+    assert_eq!(glyph.keycode(), "UM(snek)");
+    assert_eq!(glyph.to_string(), "snek = U+1F40D");
+    assert_eq!(
+        UnicodeInputMode::from_name("WinCompose"),
+        Some(UnicodeInputMode::WinCompose)
+    );
+}
+
+#[test]
+fn test_layout_validate_unicode() {
+    let mut layout = Layout::new("Test").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "UM(snek)"));
+    layer.add_key(KeyDefinition::new(Position::new(0, 1), "UC(0x00E9)"));
+    layout.add_layer(layer).unwrap();
+
+    // UM() must reference a defined glyph
+    assert!(layout.validate().is_err());
+    layout.unicode_map = vec![UnicodeGlyph::new("snek", 0x1F40D)];
+    assert!(layout.validate().is_ok());
+
+    // Names collide as C enum members when they differ only in case
+    layout.unicode_map.push(UnicodeGlyph::new("SNEK", 0x41));
+    assert!(layout.validate().is_err());
+    layout.unicode_map.pop();
+
+    // Surrogates are not characters
+    layout.unicode_map.push(UnicodeGlyph::new("bad", 0xD800));
+    assert!(layout.validate().is_err());
+    layout.unicode_map.pop();
+
+    layout.unicode_input_modes = vec![UnicodeInputMode::Linux, UnicodeInputMode::Linux];
+    assert!(layout.validate().is_err());
+    layout.unicode_input_modes = vec![UnicodeInputMode::Linux, UnicodeInputMode::Macos];
+    assert!(layout.validate().is_ok());
+    assert_eq!(layout.unicode_input_modes_display(), "Linux, macOS");
+
+    layout.layers[0].keys[1].keycode = "UC(0xD800)".to_string();
+    assert!(layout.validate().is_err());
+}
+
+#[test]
+fn test_rgb_conditions_validate_layer_references() {
+    let mut settings = RgbConditionalSettings::default();
+    assert!(!settings.is_active());
+    assert!(!settings.has_custom_settings());
+
+    settings.set_layer_effect(1, Some(RgbMatrixEffect::Breathing));
+    settings.set_layer_effect(0, Some(RgbMatrixEffect::CycleAll));
+    assert!(settings.is_active());
+    assert_eq!(settings.layer_effects[0].layer, 0, "rules are kept sorted");
+    assert!(settings.validate(2).is_ok());
+    assert!(settings.validate(1).is_err());
+
+    settings.set_layer_effect(1, None);
+    assert_eq!(settings.layer_effects.len(), 1);
+}
+
 #[test]
 fn test_layout_resolve_key_color() {
     let mut layout = Layout::new("Test").unwrap();
@@ -159,6 +319,70 @@ fn test_layout_resolve_key_color() {
     assert_eq!(color, RgbColor::new(255, 255, 255));
 }
 
+#[test]
+fn test_layout_resolve_firmware_and_category_colors() {
+    let mut layout = Layout::new("Test").unwrap();
+    layout
+        .add_category(Category::new("nav", "Nav", RgbColor::new(0, 255, 0)).unwrap())
+        .unwrap();
+    layout
+        .add_category(Category::new("sym", "Symbols", RgbColor::new(0, 0, 255)).unwrap())
+        .unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    layer.set_category(Some("sym".to_string()));
+    layout.add_layer(layer).unwrap();
+
+    let overridden = KeyDefinition::new(Position::new(0, 0), "KC_A")
+        .with_color(RgbColor::new(255, 0, 0))
+        .with_category("nav");
+    let plain = KeyDefinition::new(Position::new(0, 1), "KC_B");
+
+    // Overrides win in the firmware but are ignored by the category view
+    assert_eq!(
+        layout.resolve_firmware_color(0, &overridden),
+        RgbColor::new(255, 0, 0)
+    );
+    assert_eq!(
+        layout.resolve_category_color(0, &overridden),
+        Some(RgbColor::new(0, 255, 0))
+    );
+    assert_eq!(
+        layout.resolve_category_color(0, &plain),
+        Some(RgbColor::new(0, 0, 255))
+    );
+
+    // RGB settings apply to firmware colors, and disabled layers are off
+    layout.rgb_enabled = false;
+    assert_eq!(
+        layout.resolve_firmware_color(0, &overridden),
+        RgbColor::new(0, 0, 0)
+    );
+    layout.rgb_enabled = true;
+    layout.get_layer_mut(0).unwrap().layer_colors_enabled = false;
+    assert_eq!(
+        layout.resolve_firmware_color(0, &overridden),
+        RgbColor::new(0, 0, 0)
+    );
+
+    layout.get_layer_mut(0).unwrap().set_category(None);
+    assert_eq!(layout.resolve_category_color(0, &plain), None);
+}
+
+#[test]
+fn test_layout_resolve_underglow_color() {
+    let mut layout = Layout::new("Test").unwrap();
+    layout
+        .add_layer(Layer::new(0, "Base", RgbColor::new(0, 0, 255)).unwrap())
+        .unwrap();
+
+    // Follows the layer color by default
+    assert_eq!(layout.resolve_underglow_color(0), RgbColor::new(0, 0, 255));
+
+    // A fixed color takes precedence
+    layout.get_layer_mut(0).unwrap().underglow_color = Some(RgbColor::new(255, 0, 0));
+    assert_eq!(layout.resolve_underglow_color(0), RgbColor::new(255, 0, 0));
+}
+
 #[test]
 fn test_layout_validate() {
     let mut layout = Layout::new("Test").unwrap();
@@ -267,6 +491,38 @@ fn test_tap_hold_settings_has_custom_settings() {
     assert!(with_retro.has_custom_settings());
 }
 
+#[test]
+fn test_tap_hold_per_key_tapping_terms() {
+    let mut settings = TapHoldSettings::default();
+    settings.set_key_tapping_term(Position::new(1, 5), Some(240));
+    settings.set_key_tapping_term(Position::new(1, 0), Some(260));
+    settings.set_key_tapping_term(Position::new(1, 5), Some(230));
+
+    assert_eq!(settings.per_key_tapping_terms.len(), 2);
+    assert_eq!(
+        settings.per_key_tapping_terms[0].position,
+        Position::new(1, 0)
+    );
+    assert_eq!(settings.tapping_term_for(Position::new(1, 5)), 230);
+    assert_eq!(settings.tapping_term_for(Position::new(0, 0)), 200);
+    assert!(settings.has_custom_settings());
+    assert!(settings.validate().is_ok());
+
+    // Presets keep per-key overrides
+    settings.apply_preset(TapHoldPreset::HomeRowMods);
+    assert_eq!(settings.key_tapping_term(Position::new(1, 0)), Some(260));
+    assert_eq!(settings.tapping_term_for(Position::new(0, 0)), 175);
+
+    settings.set_key_tapping_term(Position::new(1, 0), None);
+    assert_eq!(settings.key_tapping_term(Position::new(1, 0)), None);
+
+    settings.per_key_tapping_terms.push(KeyTappingTerm {
+        position: Position::new(2, 2),
+        tapping_term: 20,
+    });
+    assert!(settings.validate().is_err());
+}
+
 #[test]
 fn test_tap_hold_settings_validation() {
     let mut settings = TapHoldSettings::default();
@@ -697,3 +953,618 @@ fn test_combo_settings_has_custom_settings() {
     settings.add_combo(combo).unwrap();
     assert!(settings.has_custom_settings());
 }
+
+#[test]
+fn test_keycode_combo_validate() {
+    let keys = |count: u8| (0..count).map(|col| Position::new(0, col)).collect();
+
+    assert!(KeycodeCombo::new(keys(2), "KC_ESC").validate().is_ok());
+    assert!(KeycodeCombo::new(keys(8), "KC_ESC").validate().is_ok());
+    assert!(KeycodeCombo::new(keys(1), "KC_ESC").validate().is_err());
+    assert!(KeycodeCombo::new(keys(9), "KC_ESC").validate().is_err());
+    assert!(KeycodeCombo::new(keys(2), " ").validate().is_err());
+
+    let repeated = vec![Position::new(0, 0), Position::new(0, 0)];
+    assert!(KeycodeCombo::new(repeated, "KC_ESC").validate().is_err());
+}
+
+#[test]
+fn test_keycode_combo_duplicate_keys_ignore_order() {
+    let mut settings = ComboSettings::default();
+    let keys = vec![Position::new(0, 0), Position::new(0, 1)];
+    settings
+        .add_keycode_combo(KeycodeCombo::new(keys.clone(), "KC_ESC"))
+        .unwrap();
+    settings
+        .add_keycode_combo(KeycodeCombo::new(
+            vec![Position::new(1, 0), Position::new(1, 1)],
+            "KC_TAB",
+        ))
+        .unwrap();
+    assert!(settings.has_custom_settings());
+
+    let reversed: Vec<_> = keys.iter().rev().copied().collect();
+    assert!(settings
+        .add_keycode_combo(KeycodeCombo::new(reversed.clone(), "KC_ENT"))
+        .is_err());
+
+    // Updating a combo may keep its own keys but not take another's
+    assert!(settings
+        .update_keycode_combo(0, KeycodeCombo::new(reversed.clone(), "KC_ENT"))
+        .is_ok());
+    assert_eq!(settings.keycode_combos[0].keycode, "KC_ENT");
+    assert!(settings
+        .update_keycode_combo(1, KeycodeCombo::new(reversed, "KC_ENT"))
+        .is_err());
+    assert!(settings
+        .update_keycode_combo(2, KeycodeCombo::new(keys, "KC_ENT"))
+        .is_err());
+}
+
+#[test]
+fn test_keycode_combos_share_combo_limit() {
+    let mut settings = ComboSettings::default();
+    for index in 0..MAX_COMBOS {
+        let combo = ComboDefinition::new(
+            Position::new(index as u8, 0),
+            Position::new(index as u8, 1),
+            ComboAction::DisableEffects,
+        );
+        settings.add_combo(combo).unwrap();
+    }
+
+    let combo = KeycodeCombo::new(vec![Position::new(0, 2), Position::new(0, 3)], "KC_ESC");
+    assert!(settings.add_keycode_combo(combo).is_err());
+}
+
+#[test]
+fn test_parse_macro_sequence() {
+    let steps = parse_sequence("git status{KC_ENT}{250ms}{{done}").unwrap();
+    assert_eq!(
+        steps,
+        [
+            MacroStep::Text("git status".to_string()),
+            MacroStep::Tap("KC_ENT".to_string()),
+            MacroStep::Delay(250),
+            MacroStep::Text("{done}".to_string()),
+        ]
+    );
+
+    // A token that is not a number of milliseconds is a key tap
+    assert_eq!(
+        parse_sequence("{KC_PARAMS}").unwrap(),
+        [MacroStep::Tap("KC_PARAMS".to_string())]
+    );
+
+    assert!(parse_sequence("unclosed {KC_A").is_err());
+    assert!(parse_sequence("empty {}").is_err());
+}
+
+#[test]
+fn test_macro_sequence_round_trip() {
+    let sequence = "a{{b}{LCTL(KC_C)}{10ms}\"q\"";
+    let macro_def = MacroDefinition::from_sequence("copy", sequence).unwrap();
+
+    assert_eq!(macro_def.sequence(), sequence);
+    assert_eq!(macro_def.keycode(), "MACRO(copy)");
+    assert_eq!(macro_def.enum_name(), "MACRO_COPY");
+}
+
+#[test]
+fn test_macro_validate() {
+    assert!(MacroDefinition::from_sequence("ok", "hi{KC_ENT}")
+        .unwrap()
+        .validate()
+        .is_ok());
+
+    for (name, sequence) in [
+        ("", "hi"),
+        ("bad name", "hi"),
+        ("empty", ""),
+        ("unicode", "caf\u{e9}"),
+        ("zero_delay", "{0ms}"),
+        ("long_delay", "{10001ms}"),
+    ] {
+        let macro_def = MacroDefinition::from_sequence(name, sequence).unwrap();
+        assert!(macro_def.validate().is_err(), "{name:?} should be invalid");
+    }
+}
+
+#[test]
+fn test_layout_add_and_remove_macro() {
+    let mut layout = Layout::new("Test").unwrap();
+    let email = MacroDefinition::from_sequence("email", "me@example.com").unwrap();
+
+    layout.add_macro(email.clone()).unwrap();
+    assert!(layout.add_macro(email).is_err());
+    assert!(layout
+        .add_macro(MacroDefinition::from_sequence("bad", "").unwrap())
+        .is_err());
+    assert!(layout.get_macro("email").is_some());
+
+    assert!(layout.remove_macro("email").is_some());
+    assert!(layout.remove_macro("email").is_none());
+}
+
+#[test]
+fn test_layout_validate_macro_references() {
+    let mut layout = Layout::new("Test").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "MACRO(email)"));
+    layout.add_layer(layer).unwrap();
+
+    let err = layout.validate().unwrap_err().to_string();
+    assert!(
+        err.contains("Macro 'email' is referenced but not defined"),
+        "{err}"
+    );
+
+    layout
+        .add_macro(MacroDefinition::from_sequence("email", "me@example.com").unwrap())
+        .unwrap();
+    assert!(layout.validate().is_ok());
+
+    layout.macros.push(layout.macros[0].clone());
+    assert!(layout.validate().is_err());
+}
+
+#[test]
+fn test_custom_keycode_validate() {
+    assert!(CustomKeycode::new("CK_EMAIL", "SEND_STRING(\"me\");", "")
+        .validate()
+        .is_ok());
+
+    for (name, behavior) in [
+        ("", "reset_keyboard();"),
+        ("ck_lower", "reset_keyboard();"),
+        ("1CK", "reset_keyboard();"),
+        ("CK-DASH", "reset_keyboard();"),
+        ("KC_MINE", "reset_keyboard();"),
+        ("MACRO_SIG", "reset_keyboard();"),
+        ("SELWORD", "reset_keyboard();"),
+        ("CK_EMPTY", "  \n"),
+    ] {
+        let custom = CustomKeycode::new(name, behavior, "");
+        assert!(custom.validate().is_err(), "{name:?} should be invalid");
+    }
+}
+
+#[test]
+fn test_layout_add_and_validate_custom_keycodes() {
+    let mut layout = Layout::new("Test").unwrap();
+    let boot = CustomKeycode::new("CK_BOOT", "reset_keyboard();", "");
+
+    layout.add_custom_keycode(boot.clone()).unwrap();
+    assert!(layout.add_custom_keycode(boot).is_err());
+    assert!(layout
+        .add_custom_keycode(CustomKeycode::new("QK_BOOT", "reset_keyboard();", ""))
+        .is_err());
+    assert_eq!(
+        layout
+            .get_custom_keycode("CK_BOOT")
+            .unwrap()
+            .display_description(),
+        "Custom keycode"
+    );
+    assert!(layout.validate_custom_keycodes().is_ok());
+
+    layout
+        .custom_keycodes
+        .push(layout.custom_keycodes[0].clone());
+    assert!(layout.validate_custom_keycodes().is_err());
+    layout.custom_keycodes.pop();
+
+    assert!(layout.remove_custom_keycode("CK_BOOT").is_some());
+    assert!(layout.remove_custom_keycode("CK_BOOT").is_none());
+}
+
+fn layout_with_target(target: KeyboardTarget) -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    layout.metadata.keyboard = Some("crkbd/rev1".to_string());
+    layout.metadata.layout_variant = Some("LAYOUT_split_3x6_3".to_string());
+    layout.metadata.keymap_name = Some("mine".to_string());
+    layout.metadata.converter = Some("rp2040_ce".to_string());
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_ESC"));
+    layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_Q"));
+    layer.add_key(KeyDefinition::new(Position::new(3, 4), "KC_SPC"));
+    layout.add_layer(layer).unwrap();
+    layout.metadata.targets.push(target);
+    layout
+}
+
+#[test]
+fn test_keyboard_target_map_position() {
+    let mut target = KeyboardTarget::new("lily58", "lily58/rev1", "LAYOUT");
+    target.row_offset = 1;
+    target.remap.push(PositionRemap {
+        from: Position::new(3, 4),
+        to: Some(Position::new(4, 5)),
+    });
+    target.remap.push(PositionRemap {
+        from: Position::new(0, 1),
+        to: None,
+    });
+
+    assert_eq!(
+        target.map_position(Position::new(0, 0)),
+        Some(Position::new(1, 0))
+    );
+    assert_eq!(
+        target.map_position(Position::new(3, 4)),
+        Some(Position::new(4, 5))
+    );
+    assert_eq!(target.map_position(Position::new(0, 1)), None);
+
+    target.col_offset = -1;
+    assert_eq!(target.map_position(Position::new(0, 0)), None);
+}
+
+#[test]
+fn test_keyboard_target_validate() {
+    assert!(KeyboardTarget::new("lily58", "lily58/rev1", "LAYOUT")
+        .validate()
+        .is_ok());
+    assert!(KeyboardTarget::new("Lily 58", "lily58/rev1", "LAYOUT")
+        .validate()
+        .is_err());
+    assert!(KeyboardTarget::new("lily58", "", "LAYOUT")
+        .validate()
+        .is_err());
+
+    let mut target = KeyboardTarget::new("lily58", "lily58/rev1", "LAYOUT");
+    target.converter = Some("KB 2040".to_string());
+    assert!(target.validate().is_err());
+
+    let mut target = KeyboardTarget::new("lily58", "lily58/rev1", "LAYOUT");
+    let remap = PositionRemap {
+        from: Position::new(0, 0),
+        to: None,
+    };
+    target.remap = vec![remap, remap];
+    assert!(target.validate().is_err());
+}
+
+#[test]
+fn test_layout_metadata_validate_targets_rejects_duplicate_names() {
+    let mut metadata = LayoutMetadata::new("Test").unwrap();
+    metadata
+        .targets
+        .push(KeyboardTarget::new("lily58", "lily58/rev1", "LAYOUT"));
+    assert!(metadata.validate_targets().is_ok());
+
+    metadata
+        .targets
+        .push(KeyboardTarget::new("lily58", "lily58/rev2", "LAYOUT"));
+    assert!(metadata.validate_targets().is_err());
+}
+
+#[test]
+fn test_layout_for_target_replaces_keyboard_and_moves_keys() {
+    let mut target = KeyboardTarget::new("lily58", "lily58/rev1", "LAYOUT");
+    target.row_offset = 1;
+    target.remap.push(PositionRemap {
+        from: Position::new(0, 1),
+        to: None,
+    });
+    let mut layout = layout_with_target(target);
+    layout.combo_settings.combos.push(ComboDefinition::new(
+        Position::new(0, 0),
+        Position::new(3, 4),
+        ComboAction::Bootloader,
+    ));
+    layout.combo_settings.keycode_combos.push(KeycodeCombo::new(
+        vec![Position::new(0, 0), Position::new(0, 1)],
+        "KC_TAB",
+    ));
+
+    let lily = layout.for_target("lily58").unwrap();
+
+    assert_eq!(lily.metadata.keyboard.as_deref(), Some("lily58/rev1"));
+    assert_eq!(lily.metadata.layout_variant.as_deref(), Some("LAYOUT"));
+    assert_eq!(lily.metadata.keymap_name.as_deref(), Some("mine"));
+    assert_eq!(lily.metadata.converter, None);
+    assert!(lily.metadata.targets.is_empty());
+
+    let keys: Vec<(Position, &str)> = lily.layers[0]
+        .keys
+        .iter()
+        .map(|key| (key.position, key.keycode.as_str()))
+        .collect();
+    assert_eq!(
+        keys,
+        vec![
+            (Position::new(1, 0), "KC_ESC"),
+            (Position::new(4, 4), "KC_SPC")
+        ]
+    );
+
+    assert_eq!(lily.combo_settings.combos.len(), 1);
+    assert_eq!(lily.combo_settings.combos[0].key1, Position::new(1, 0));
+    assert_eq!(lily.combo_settings.combos[0].key2, Position::new(4, 4));
+    assert!(lily.combo_settings.keycode_combos.is_empty());
+}
+
+#[test]
+fn test_layout_for_target_rejects_colliding_keys_and_unknown_target() {
+    let mut target = KeyboardTarget::new("lily58", "lily58/rev1", "LAYOUT");
+    target.remap.push(PositionRemap {
+        from: Position::new(0, 1),
+        to: Some(Position::new(0, 0)),
+    });
+    let layout = layout_with_target(target);
+
+    assert!(layout.for_target("lily58").is_err());
+    assert!(layout.for_target("missing").is_err());
+}
+
+fn layout_with_layer_refs() -> Layout {
+    let mut layout = Layout::new("Refs").unwrap();
+    for (i, keycodes) in [
+        ["MO(1)", "LT(2, KC_SPC)"],
+        ["KC_TRNS", "TG(1)"],
+        ["TO(0)", "KC_TRNS"],
+    ]
+    .iter()
+    .enumerate()
+    {
+        let mut layer = Layer::new(i as u8, format!("L{i}"), RgbColor::new(0, 0, 0)).unwrap();
+        for (col, keycode) in keycodes.iter().enumerate() {
+            layer.add_key(KeyDefinition::new(Position::new(0, col as u8), *keycode));
+        }
+        layout.add_layer(layer).unwrap();
+    }
+    layout
+}
+
+fn keycodes(layer: &Layer) -> Vec<&str> {
+    layer.keys.iter().map(|key| key.keycode.as_str()).collect()
+}
+
+#[test]
+fn test_move_layer_rewrites_numeric_refs() {
+    let mut layout = layout_with_layer_refs();
+    layout
+        .tap_dances
+        .push(TapDanceAction::new("td", "KC_A").with_hold("MO(2)"));
+    layout
+        .rgb_conditions
+        .set_layer_effect(2, Some(RgbMatrixEffect::Breathing));
+    layout
+        .editing_helpers
+        .set_enabled(EditingHelper::NumWord, true);
+    layout.editing_helpers.num_word_layer = 2;
+
+    layout.move_layer(2, 1).unwrap();
+
+    let names: Vec<&str> = layout.layers.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(names, vec!["L0", "L2", "L1"]);
+    assert_eq!(keycodes(&layout.layers[0]), vec!["MO(2)", "LT(1, KC_SPC)"]);
+    assert_eq!(keycodes(&layout.layers[2]), vec!["KC_TRNS", "TG(2)"]);
+    assert_eq!(layout.tap_dances[0].hold.as_deref(), Some("MO(1)"));
+    assert_eq!(layout.rgb_conditions.layer_effects[0].layer, 1);
+    assert_eq!(layout.editing_helpers.num_word_layer, 1);
+    assert!(layout
+        .layers
+        .iter()
+        .enumerate()
+        .all(|(i, l)| l.number as usize == i));
+    layout.validate().unwrap();
+
+    assert!(layout.move_layer(0, 3).is_err());
+}
+
+#[test]
+fn test_duplicate_layer_inserts_after_source_and_shifts_refs() {
+    let mut layout = layout_with_layer_refs();
+    let source_id = layout.layers[1].id.clone();
+    layout.layers[0].keys[0].keycode = format!("MO(@{source_id})");
+
+    let index = layout.duplicate_layer(1, "L1 copy").unwrap();
+
+    assert_eq!(index, 2);
+    assert_eq!(layout.layers.len(), 4);
+    assert_eq!(layout.layers[2].name, "L1 copy");
+    assert_ne!(layout.layers[2].id, source_id);
+    // References to later layers follow them
+    assert_eq!(
+        keycodes(&layout.layers[0]),
+        vec![format!("MO(@{source_id})").as_str(), "LT(3, KC_SPC)"]
+    );
+    // The copy's reference to its source now points at itself
+    assert_eq!(keycodes(&layout.layers[2]), vec!["KC_TRNS", "TG(2)"]);
+    assert_eq!(keycodes(&layout.layers[1]), vec!["KC_TRNS", "TG(1)"]);
+    layout.validate().unwrap();
+
+    // Num Word on a later layer follows it too
+    layout.editing_helpers.num_word_layer = 2;
+    layout.duplicate_layer(1, "L1 copy 2").unwrap();
+    assert_eq!(layout.editing_helpers.num_word_layer, 3);
+}
+
+#[test]
+fn test_duplicate_layer_retargets_id_self_refs() {
+    let mut layout = layout_with_layer_refs();
+    let source_id = layout.layers[1].id.clone();
+    layout.layers[1].keys[1].keycode = format!("TG(@{source_id})");
+
+    let index = layout.duplicate_layer(1, "L1 copy").unwrap();
+
+    let copy_id = layout.layers[index].id.clone();
+    assert_eq!(
+        layout.layers[index].keys[1].keycode,
+        format!("TG(@{copy_id})")
+    );
+    assert_eq!(
+        layout.layers[1].keys[1].keycode,
+        format!("TG(@{source_id})")
+    );
+    assert!(layout.duplicate_layer(9, "missing").is_err());
+}
+
+#[test]
+fn test_editing_helpers() {
+    let mut helpers = EditingHelpers::default();
+    assert!(!helpers.any_enabled());
+    assert!(!helpers.is_enabled_keycode("SELWORD"));
+
+    helpers.set_enabled(EditingHelper::NumWord, true);
+    helpers.set_enabled(EditingHelper::SelectWord, true);
+    assert_eq!(
+        helpers.enabled(),
+        vec![EditingHelper::SelectWord, EditingHelper::NumWord]
+    );
+    assert!(helpers.is_enabled_keycode("NUMWORD"));
+    assert!(!helpers.is_enabled_keycode("SENTCASE"));
+    assert_eq!(
+        EditingHelper::from_name(" sentence case"),
+        Some(EditingHelper::SentenceCase)
+    );
+
+    // Num Word needs a layer other than the base layer
+    assert!(helpers.validate(2).is_ok());
+    assert!(helpers.validate(1).is_err());
+    helpers.num_word_layer = 0;
+    assert!(helpers.validate(2).is_err());
+    helpers.set_enabled(EditingHelper::NumWord, false);
+    assert!(helpers.validate(1).is_ok());
+}
+
+fn template_with_placeholders() -> Layout {
+    let mut layout = Layout::new("${OS} Template").unwrap();
+    let mut base = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    base.add_key(KeyDefinition::new(Position::new(0, 0), "${OS_MOD}(KC_C)"));
+    base.add_key(KeyDefinition::new(Position::new(0, 1), "KC_A"));
+    layout.add_layer(base).unwrap();
+    let mut nav = Layer::new(1, "${NAV_LAYER_NAME}", RgbColor::new(0, 0, 255)).unwrap();
+    nav.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
+    nav.add_key(KeyDefinition::new(Position::new(0, 1), "KC_LEFT"));
+    layout.add_layer(nav).unwrap();
+
+    let mut os_mod = TemplatePlaceholder::new("OS_MOD");
+    os_mod.description = "Shortcut modifier".to_string();
+    os_mod.choices = vec!["LGUI".to_string(), "LCTL".to_string()];
+    let mut nav_name = TemplatePlaceholder::new("NAV_LAYER_NAME");
+    nav_name.default = Some("Nav".to_string());
+    layout.metadata.placeholders = vec![os_mod, nav_name];
+    layout
+}
+
+#[test]
+fn test_required_placeholders_include_undeclared_names() {
+    let layout = template_with_placeholders();
+    let names: Vec<String> = layout
+        .required_placeholders()
+        .into_iter()
+        .map(|p| p.name)
+        .collect();
+    assert_eq!(names, vec!["OS_MOD", "NAV_LAYER_NAME", "OS"]);
+}
+
+#[test]
+fn test_fill_placeholders() {
+    let layout = template_with_placeholders();
+    let values = HashMap::from([
+        ("OS".to_string(), "macOS".to_string()),
+        ("OS_MOD".to_string(), "LGUI".to_string()),
+    ]);
+    let filled = layout.fill_placeholders(&values).unwrap();
+    assert_eq!(filled.metadata.name, "macOS Template");
+    assert_eq!(filled.layers[0].keys[0].keycode, "LGUI(KC_C)");
+    assert_eq!(filled.layers[1].name, "Nav", "default is used");
+    assert!(filled.metadata.placeholders.is_empty());
+    assert!(filled.required_placeholders().is_empty());
+
+    // Missing value without default
+    let missing = HashMap::from([("OS_MOD".to_string(), "LGUI".to_string())]);
+    let err = layout.fill_placeholders(&missing).unwrap_err();
+    assert!(err.to_string().contains("${OS}"));
+
+    // Value outside the choices
+    let mut bad = values;
+    bad.insert("OS_MOD".to_string(), "LALT".to_string());
+    assert!(layout.fill_placeholders(&bad).is_err());
+
+    // Values can't introduce new placeholders
+    bad.insert("OS_MOD".to_string(), "LGUI".to_string());
+    bad.insert("OS".to_string(), "${OS}".to_string());
+    assert!(layout.fill_placeholders(&bad).is_err());
+}
+
+#[test]
+fn test_validate_placeholders() {
+    let mut layout = template_with_placeholders();
+    assert!(layout.validate().is_ok());
+
+    layout.metadata.placeholders[1].default = None;
+    layout.metadata.placeholders[0].default = Some("LSFT".to_string());
+    assert!(layout.validate().is_err(), "default outside the choices");
+    layout.metadata.placeholders[0].default = None;
+
+    layout
+        .metadata
+        .placeholders
+        .push(TemplatePlaceholder::new("OS_MOD"));
+    assert!(layout.validate().is_err(), "duplicate name");
+    layout.metadata.placeholders.pop();
+
+    layout
+        .metadata
+        .placeholders
+        .push(TemplatePlaceholder::new("os_mod"));
+    assert!(layout.validate().is_err(), "lowercase name");
+}
+
+fn snapshot_geometry() -> KeyboardGeometry {
+    let mut geometry = KeyboardGeometry::new("crkbd/rev1", "LAYOUT_split_3x6_3", 4, 6);
+    let mut wide = KeyGeometry::new((3, 5), 7, 1.0, 3.0);
+    wide.layout_index = 1;
+    wide.width = 2.0;
+    wide.label = Some("Space".to_string());
+    let mut first = KeyGeometry::new((0, 0), 3, 0.0, 0.0);
+    first.layout_index = 0;
+    geometry.add_key(wide);
+    geometry.add_key(first);
+    geometry
+}
+
+#[test]
+fn test_geometry_snapshot_round_trip() {
+    let snapshot = GeometrySnapshot::from_geometry(&snapshot_geometry());
+    let json = serde_json::to_string(&snapshot).unwrap();
+    // Default sizes and rotations are left out
+    assert!(!json.contains("\"h\""));
+    assert!(json.contains("\"w\":2.0"));
+
+    let parsed: GeometrySnapshot = serde_json::from_str(&json).unwrap();
+    let geometry = parsed.to_geometry();
+    assert_eq!(geometry.keyboard_name, "crkbd/rev1");
+    assert_eq!(geometry.layout_name, "LAYOUT_split_3x6_3");
+    assert_eq!(geometry.matrix_rows, 4);
+    // Keys come back in LAYOUT order, without legends
+    assert_eq!(geometry.keys[0].matrix_position, (0, 0));
+    assert_eq!(geometry.keys[0].led_index, 3);
+    assert_eq!(geometry.keys[1].layout_index, 1);
+    assert_eq!(geometry.keys[1].matrix_position, (3, 5));
+    assert!((geometry.keys[1].width - 2.0).abs() < f32::EPSILON);
+    assert!((geometry.keys[1].height - 1.0).abs() < f32::EPSILON);
+    assert_eq!(geometry.keys[1].label, None);
+}
+
+#[test]
+fn test_layout_records_and_uses_geometry_snapshot() {
+    let mut layout = Layout::new("Test").unwrap();
+    layout.metadata.layout_variant = Some("LAYOUT_split_3x6_3".to_string());
+    assert!(layout.snapshot_geometry().is_none());
+
+    layout.record_geometry(&snapshot_geometry());
+    assert_eq!(layout.snapshot_geometry().unwrap().keys.len(), 2);
+
+    // Geometry without keys keeps the snapshot
+    layout.record_geometry(&KeyboardGeometry::new("unknown", "LAYOUT", 0, 0));
+    assert!(layout.metadata.geometry.is_some());
+
+    // A snapshot of another variant isn't used
+    layout.metadata.layout_variant = Some("LAYOUT_split_3x5_3".to_string());
+    assert!(layout.snapshot_geometry().is_none());
+}
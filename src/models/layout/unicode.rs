@@ -0,0 +1,195 @@
+//! Unicode input — `UC(0x...)` keycodes, named glyphs for `UM(name)`, and
+//! the host input modes the firmware types them with.
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of glyphs in a layout's Unicode map.
+pub const MAX_UNICODE_GLYPHS: usize = 256;
+
+/// Largest code point QMK's `UC()` keycode can encode; larger code points
+/// go through the Unicode map.
+pub const MAX_UC_CODEPOINT: u32 = 0x7FFF;
+
+/// Host input method the firmware uses to type Unicode characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnicodeInputMode {
+    /// macOS Unicode Hex Input
+    Macos,
+    /// Linux IBus (Ctrl+Shift+U)
+    Linux,
+    /// Windows HexNumpad
+    Windows,
+    /// FreeBSD (not yet implemented by QMK)
+    Bsd,
+    /// Windows with the WinCompose tool
+    WinCompose,
+    /// Emacs `insert-char`
+    Emacs,
+}
+
+impl UnicodeInputMode {
+    /// All input modes, in settings order.
+    pub const ALL: [Self; 6] = [
+        Self::Macos,
+        Self::Linux,
+        Self::Windows,
+        Self::WinCompose,
+        Self::Bsd,
+        Self::Emacs,
+    ];
+
+    /// Returns a human-readable name for this mode.
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::Macos => "macOS",
+            Self::Linux => "Linux",
+            Self::Windows => "Windows",
+            Self::Bsd => "BSD",
+            Self::WinCompose => "WinCompose",
+            Self::Emacs => "Emacs",
+        }
+    }
+
+    /// Returns the QMK mode constant, e.g. `UNICODE_MODE_LINUX`.
+    #[must_use]
+    pub const fn qmk_mode(self) -> &'static str {
+        match self {
+            Self::Macos => "UNICODE_MODE_MACOS",
+            Self::Linux => "UNICODE_MODE_LINUX",
+            Self::Windows => "UNICODE_MODE_WINDOWS",
+            Self::Bsd => "UNICODE_MODE_BSD",
+            Self::WinCompose => "UNICODE_MODE_WINCOMPOSE",
+            Self::Emacs => "UNICODE_MODE_EMACS",
+        }
+    }
+
+    /// Parses a mode name (case-insensitive).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "macos" | "mac" => Some(Self::Macos),
+            "linux" => Some(Self::Linux),
+            "windows" | "win" => Some(Self::Windows),
+            "bsd" => Some(Self::Bsd),
+            "wincompose" => Some(Self::WinCompose),
+            "emacs" => Some(Self::Emacs),
+            _ => None,
+        }
+    }
+}
+
+/// A named character in the layout's Unicode map, typed by `UM(name)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnicodeGlyph {
+    /// Name used in `UM(name)` (letters, digits and underscores)
+    pub name: String,
+    /// Unicode code point
+    pub codepoint: u32,
+}
+
+impl UnicodeGlyph {
+    /// Creates a glyph.
+    #[must_use]
+    pub fn new(name: impl Into<String>, codepoint: u32) -> Self {
+        Self {
+            name: name.into(),
+            codepoint,
+        }
+    }
+
+    /// Returns the character, or `None` for an invalid code point.
+    #[must_use]
+    pub fn character(&self) -> Option<char> {
+        char::from_u32(self.codepoint)
+    }
+
+    /// Returns the C enum name, e.g. `UM_SNEK`.
+    #[must_use]
+    pub fn enum_name(&self) -> String {
+        format!("UM_{}", self.name.to_uppercase())
+    }
+
+    /// Returns the keycode that types this glyph, e.g. `UM(snek)`.
+    #[must_use]
+    pub fn keycode(&self) -> String {
+        format!("UM({})", self.name)
+    }
+
+    /// Validates the glyph.
+    ///
+    /// Checks:
+    /// - Name is a non-empty C identifier fragment
+    /// - Code point is a Unicode scalar value
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.name.is_empty()
+            || !self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            anyhow::bail!(
+                "Unicode glyph name '{}' must contain only letters, digits and underscores",
+                self.name
+            );
+        }
+        if self.character().is_none() {
+            anyhow::bail!(
+                "Unicode glyph '{}' has an invalid code point {}",
+                self.name,
+                format_codepoint(self.codepoint)
+            );
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for UnicodeGlyph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.name, format_codepoint(self.codepoint))
+    }
+}
+
+/// Parses a character given as itself (`é`), `U+00E9` or `0xE9`.
+#[must_use]
+pub fn parse_codepoint(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let hex = value
+        .strip_prefix("U+")
+        .or_else(|| value.strip_prefix("u+"))
+        .or_else(|| value.strip_prefix("0x"))
+        .or_else(|| value.strip_prefix("0X"));
+    if let Some(hex) = hex {
+        return u32::from_str_radix(hex, 16).ok();
+    }
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(u32::from(c)),
+        _ => None,
+    }
+}
+
+/// Formats a code point as `U+00E9`.
+#[must_use]
+pub fn format_codepoint(codepoint: u32) -> String {
+    format!("U+{codepoint:04X}")
+}
+
+/// Returns the code point of a `UC(0x...)` keycode.
+#[must_use]
+pub fn uc_codepoint(keycode: &str) -> Option<u32> {
+    let hex = keycode.strip_prefix("UC(")?.strip_suffix(')')?.trim();
+    let hex = hex.strip_prefix("0x").or_else(|| hex.strip_prefix("0X"))?;
+    u32::from_str_radix(hex, 16).ok()
+}
+
+/// Returns the glyph name of a `UM(name)` keycode.
+#[must_use]
+pub fn um_name(keycode: &str) -> Option<&str> {
+    keycode
+        .strip_prefix("UM(")?
+        .strip_suffix(')')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+}
@@ -8,23 +8,32 @@ pub mod color_palette;
 pub mod keyboard_geometry;
 pub mod layer;
 pub mod layout;
+pub mod position_names;
 pub mod rgb;
 pub mod visual_layout_mapping;
 
 // Re-export all model types
 pub use category::Category;
 pub use color_palette::{ColorPalette, Shade};
-pub use keyboard_geometry::{KeyGeometry, KeyboardGeometry};
+pub use keyboard_geometry::{KeyGeometry, KeyboardGeometry, LedGeometry};
 #[allow(unused_imports)] // bin/lib split: re-exports consumed by lib tests
 pub use layer::{
-    validate_layer_number, KeyDefinition, Layer, Position, DEFAULT_QMK_LAYER_LIMIT,
-    MAX_QMK_LAYER_LIMIT,
+    validate_layer_number, EncoderBinding, EncoderMap, KeyDefinition, Layer, Position,
+    DEFAULT_QMK_LAYER_LIMIT, MAX_QMK_LAYER_LIMIT,
 };
 pub use layout::{
-    ComboAction, ComboDefinition, ComboSettings, HoldDecisionMode, IdleEffectSettings, Layout,
-    LayoutMetadata, PaletteFxEffect, PaletteFxPalette, PaletteFxSettings, RgbBrightness,
-    RgbMatrixEffect, RgbOverlayRippleSettings, RgbSaturation, RippleColorMode, TapDanceAction,
-    TapHoldPreset, TapHoldSettings, UncoloredKeyBehavior,
+    AltRepeatKey, ComboAction, ComboDefinition, ComboSettings, CustomKeycode, EditingHelper,
+    EditingHelpers, FirmwareFeature, FirmwareFeatures, HoldDecisionMode, IdleEffectSettings,
+    KeyOverride, KeycodeCombo, Layout, LayoutMetadata, MacroDefinition, MacroStep, PaletteFxEffect,
+    PaletteFxPalette, PaletteFxSettings, RgbBrightness, RgbConditionalSettings, RgbMatrixEffect,
+    RgbOffHours, RgbOverlayRippleSettings, RgbSaturation, RippleColorMode, TapDanceAction,
+    TapHoldPreset, TapHoldSettings, TemplatePlaceholder, UncoloredKeyBehavior, UnicodeGlyph,
+    UnicodeInputMode,
 };
+#[allow(unused_imports)] // bin/lib split: re-exports consumed by lib tests
+pub use layout::{
+    GeometrySnapshot, KeyTappingTerm, KeyboardTarget, OverrideModifier, PositionRemap,
+};
+pub use position_names::{Finger, Hand, PositionNames};
 pub use rgb::RgbColor;
 pub use visual_layout_mapping::VisualLayoutMapping;
@@ -0,0 +1,255 @@
+//! Human-readable names for key positions.
+//!
+//! Derives names like "left home row pinky" or "right thumb 2" from the
+//! physical key layout, so diagnostics and the status bar can refer to keys
+//! by where they are instead of by raw visual `(row, col)` coordinates.
+//!
+//! # Heuristics
+//!
+//! - **Hand**: keys left of the horizontal center belong to the left hand.
+//! - **Thumb cluster**: bottom rows holding at most half as many keys as the
+//!   widest row (typical of split ergonomic boards), plus any rotated key.
+//! - **Rows**: counted from the bottom — bottom row, home row, top row,
+//!   number row; further rows fall back to "row N".
+//! - **Fingers**: assigned per row and hand from the inner edge — index (plus
+//!   "inner index" when the hand has five or more keys), middle, ring,
+//!   pinky, then "outer pinky" for any remaining outer keys.
+//!
+//! The info.json `label` of a key is appended in parentheses when present.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use super::keyboard_geometry::{KeyGeometry, KeyboardGeometry};
+use super::layer::Position;
+
+/// Row names counted from the bottom of the main (non-thumb) key area.
+const ROW_NAMES: [&str; 4] = ["bottom row", "home row", "top row", "number row"];
+
+/// Hand pressing a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hand {
+    /// Left hand
+    Left,
+    /// Right hand
+    Right,
+}
+
+/// Finger pressing a key. The inner index column counts as index and the
+/// outer pinky column as pinky.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Finger {
+    /// Thumb (thumb cluster keys)
+    Thumb,
+    /// Index finger
+    Index,
+    /// Middle finger
+    Middle,
+    /// Ring finger
+    Ring,
+    /// Pinky
+    Pinky,
+}
+
+/// Lookup table from visual position to a human-readable key name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PositionNames {
+    names: HashMap<Position, String>,
+}
+
+impl PositionNames {
+    /// Builds names for every key in `geometry`.
+    #[must_use]
+    pub fn build(geometry: &KeyboardGeometry) -> Self {
+        let keys = &geometry.keys;
+        if keys.is_empty() {
+            return Self::default();
+        }
+
+        let min_x = keys
+            .iter()
+            .map(|k| k.visual_x)
+            .fold(f32::INFINITY, f32::min);
+        let max_x = keys
+            .iter()
+            .map(|k| k.visual_x + k.width)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let center = f32::midpoint(min_x, max_x);
+        let is_left = |key: &KeyGeometry| key.visual_x + key.width / 2.0 < center;
+
+        // Group keys by visual row (top to bottom)
+        let mut rows: BTreeMap<u8, Vec<&KeyGeometry>> = BTreeMap::new();
+        for key in keys {
+            rows.entry(visual_position(key).row).or_default().push(key);
+        }
+
+        let widest = rows.values().map(Vec::len).max().unwrap_or(0);
+        let row_ids: Vec<u8> = rows.keys().copied().collect();
+        let thumb_row_count = row_ids
+            .iter()
+            .rev()
+            .take(row_ids.len().saturating_sub(2))
+            .take_while(|row| rows[row].len() * 2 <= widest)
+            .count();
+        let (main_rows, thumb_rows) = row_ids.split_at(row_ids.len() - thumb_row_count);
+
+        let mut names = HashMap::new();
+
+        // Main rows: row name + finger
+        for (rank_from_bottom, row) in main_rows.iter().rev().enumerate() {
+            let row_name = ROW_NAMES
+                .get(rank_from_bottom)
+                .map_or_else(|| format!("row {row}"), ToString::to_string);
+
+            let (mut left, mut right): (Vec<_>, Vec<_>) = rows[row]
+                .iter()
+                .copied()
+                .filter(|k| k.rotation == 0.0)
+                .partition(|k| is_left(k));
+            // Order each hand from the inner edge outwards
+            left.sort_by(|a, b| b.visual_x.total_cmp(&a.visual_x));
+            right.sort_by(|a, b| a.visual_x.total_cmp(&b.visual_x));
+
+            for (hand, hand_keys) in [("left", &left), ("right", &right)] {
+                for (from_inner, key) in hand_keys.iter().enumerate() {
+                    let finger = finger_name(from_inner, hand_keys.len());
+                    names.insert(
+                        visual_position(key),
+                        with_label(format!("{hand} {row_name} {finger}"), key),
+                    );
+                }
+            }
+        }
+
+        // Thumb cluster: thumb rows plus rotated keys, numbered from the inner edge
+        let thumbs = keys
+            .iter()
+            .filter(|k| k.rotation != 0.0 || thumb_rows.contains(&visual_position(k).row));
+        let (mut left, mut right): (Vec<_>, Vec<_>) = thumbs.partition(|k| is_left(k));
+        left.sort_by(|a, b| b.visual_x.total_cmp(&a.visual_x));
+        right.sort_by(|a, b| a.visual_x.total_cmp(&b.visual_x));
+        for (hand, hand_keys) in [("left", &left), ("right", &right)] {
+            for (idx, key) in hand_keys.iter().enumerate() {
+                names.insert(
+                    visual_position(key),
+                    with_label(format!("{hand} thumb {}", idx + 1), key),
+                );
+            }
+        }
+
+        Self { names }
+    }
+
+    /// Returns the name of the key at `position`, if the geometry has one.
+    #[must_use]
+    pub fn get(&self, position: Position) -> Option<&str> {
+        self.names.get(&position).map(String::as_str)
+    }
+
+    /// Returns whether the key at `position` belongs to the left hand.
+    #[must_use]
+    pub fn is_left(&self, position: Position) -> Option<bool> {
+        self.get(position).map(|name| name.starts_with("left "))
+    }
+
+    /// Returns true if the key at `position` is part of a thumb cluster.
+    #[must_use]
+    pub fn is_thumb(&self, position: Position) -> bool {
+        self.get(position)
+            .is_some_and(|name| name.starts_with("left thumb ") || name.starts_with("right thumb "))
+    }
+
+    /// Returns true if the key at `position` is on the home row.
+    #[must_use]
+    pub fn is_home_row(&self, position: Position) -> bool {
+        self.get(position)
+            .is_some_and(|name| name.contains(" home row "))
+    }
+
+    /// Returns the hand and finger pressing the key at `position`.
+    #[must_use]
+    pub fn finger(&self, position: Position) -> Option<(Hand, Finger)> {
+        let name = self.unlabeled(position)?;
+        let hand = if name.starts_with("left ") {
+            Hand::Left
+        } else {
+            Hand::Right
+        };
+        let finger = if name.contains(" thumb ") {
+            Finger::Thumb
+        } else {
+            match name.rsplit(' ').next()? {
+                "index" => Finger::Index,
+                "middle" => Finger::Middle,
+                "ring" => Finger::Ring,
+                "pinky" => Finger::Pinky,
+                _ => return None,
+            }
+        };
+        Some((hand, finger))
+    }
+
+    /// Returns true if a finger rests on the key at `position`: a home row
+    /// key outside the inner index and outer pinky columns.
+    #[must_use]
+    pub fn is_home_key(&self, position: Position) -> bool {
+        self.unlabeled(position).is_some_and(|name| {
+            name.contains(" home row ") && !name.contains(" inner ") && !name.contains(" outer ")
+        })
+    }
+
+    /// Returns the key name without its info.json label.
+    fn unlabeled(&self, position: Position) -> Option<&str> {
+        let name = self.get(position)?;
+        Some(name.split_once(" (").map_or(name, |(name, _)| name))
+    }
+
+    /// Returns the key name, falling back to the raw `(row, col)` coordinates.
+    #[must_use]
+    pub fn describe(&self, position: Position) -> String {
+        self.get(position).map_or_else(
+            || format!("({}, {})", position.row, position.col),
+            ToString::to_string,
+        )
+    }
+
+    /// Returns true if no names are available.
+    #[must_use]
+    #[allow(dead_code)] // Used by tests (lib target only)
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// Visual grid position of a key, matching `VisualLayoutMapping`.
+#[must_use]
+pub fn visual_position(key: &KeyGeometry) -> Position {
+    Position::new(key.visual_y.round() as u8, key.visual_x.round() as u8)
+}
+
+/// Returns the finger for the key `from_inner` steps from the inner edge of a
+/// hand row with `count` keys.
+fn finger_name(from_inner: usize, count: usize) -> &'static str {
+    const FINGERS: [&str; 4] = ["index", "middle", "ring", "pinky"];
+    const WITH_INNER: [&str; 5] = ["inner index", "index", "middle", "ring", "pinky"];
+
+    let fingers: &[&str] = if count >= WITH_INNER.len() {
+        &WITH_INNER
+    } else {
+        &FINGERS
+    };
+    fingers.get(from_inner).copied().unwrap_or("outer pinky")
+}
+
+fn with_label(name: String, key: &KeyGeometry) -> String {
+    match key.label.as_deref().map(str::trim) {
+        Some(label) if !label.is_empty() => format!("{name} ({label})"),
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests;
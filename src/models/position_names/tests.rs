@@ -0,0 +1,107 @@
+//! Tests for position names.
+
+use super::*;
+
+/// 3x6 split grid with a 3+3 thumb row, like a Corne.
+fn split_3x6_3() -> KeyboardGeometry {
+    let mut geometry = KeyboardGeometry::new("test", "LAYOUT_split_3x6_3", 8, 6);
+    let mut index = 0;
+    for row in 0..3u8 {
+        for col in (0..6u8).chain(7..13) {
+            geometry.add_key(KeyGeometry::new(
+                (row, col),
+                index,
+                f32::from(col),
+                f32::from(row),
+            ));
+            index += 1;
+        }
+    }
+    for col in [3u8, 4, 5, 7, 8, 9] {
+        geometry.add_key(KeyGeometry::new((3, col), index, f32::from(col), 3.0));
+        index += 1;
+    }
+    geometry
+}
+
+#[test]
+fn test_split_keyboard_names() {
+    let names = PositionNames::build(&split_3x6_3());
+
+    assert_eq!(
+        names.get(Position::new(1, 0)),
+        Some("left home row outer pinky")
+    );
+    assert_eq!(names.get(Position::new(1, 1)), Some("left home row pinky"));
+    assert_eq!(
+        names.get(Position::new(1, 5)),
+        Some("left home row inner index")
+    );
+    assert_eq!(
+        names.get(Position::new(1, 7)),
+        Some("right home row inner index")
+    );
+    assert_eq!(
+        names.get(Position::new(2, 4)),
+        Some("left bottom row index")
+    );
+    assert_eq!(
+        names.get(Position::new(0, 12)),
+        Some("right top row outer pinky")
+    );
+    assert_eq!(names.get(Position::new(3, 5)), Some("left thumb 1"));
+    assert_eq!(names.get(Position::new(3, 3)), Some("left thumb 3"));
+    assert_eq!(names.get(Position::new(3, 9)), Some("right thumb 3"));
+}
+
+#[test]
+fn test_home_row_keys() {
+    let names = PositionNames::build(&split_3x6_3());
+
+    assert!(names.is_home_row(Position::new(1, 0)));
+    assert!(names.is_home_row(Position::new(1, 12)));
+    assert!(!names.is_home_row(Position::new(0, 1)));
+    assert!(!names.is_home_row(Position::new(3, 5)));
+    assert!(!names.is_home_row(Position::new(9, 9)));
+}
+
+#[test]
+fn test_fingers_and_home_keys() {
+    let names = PositionNames::build(&split_3x6_3());
+
+    assert_eq!(
+        names.finger(Position::new(1, 0)),
+        Some((Hand::Left, Finger::Pinky))
+    );
+    assert_eq!(
+        names.finger(Position::new(1, 7)),
+        Some((Hand::Right, Finger::Index))
+    );
+    assert_eq!(
+        names.finger(Position::new(3, 9)),
+        Some((Hand::Right, Finger::Thumb))
+    );
+    assert_eq!(names.finger(Position::new(9, 9)), None);
+
+    assert!(names.is_home_key(Position::new(1, 1)));
+    assert!(names.is_home_key(Position::new(1, 8)));
+    // Inner index and outer pinky columns are reached by stretching
+    assert!(!names.is_home_key(Position::new(1, 0)));
+    assert!(!names.is_home_key(Position::new(1, 5)));
+    assert!(!names.is_home_key(Position::new(0, 2)));
+}
+
+#[test]
+fn test_label_and_fallback() {
+    let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 1, 2);
+    geometry.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0).with_label("Esc"));
+    geometry.add_key(KeyGeometry::new((0, 1), 1, 1.0, 0.0));
+    let names = PositionNames::build(&geometry);
+
+    assert_eq!(
+        names.get(Position::new(0, 0)),
+        Some("left bottom row index (Esc)")
+    );
+    assert_eq!(names.describe(Position::new(5, 5)), "(5, 5)");
+    assert!(PositionNames::build(&KeyboardGeometry::new("empty", "LAYOUT", 0, 0)).is_empty());
+}
@@ -86,7 +86,7 @@ impl RgbColor {
     }
 
     /// Converts the color to a Ratatui Color for terminal rendering.
-    #[cfg(feature = "ratatui")]
+    #[cfg(feature = "tui")]
     #[allow(dead_code)] // bin/lib split: ratatui feature unused by web/bin builds
     #[must_use]
     #[allow(dead_code)] // bin/lib split: ratatui conversion (used only with ratatui feature; tests in lib)
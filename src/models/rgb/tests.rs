@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_from_hex_valid() {
     let color = RgbColor::from_hex("#FF0000").unwrap();
@@ -183,9 +183,13 @@ impl VisualLayoutMapping {
     ///
     /// Each returned [`Position`] is a visual-grid coordinate with a corresponding
     /// physical key. Used when adjusting layouts to match a new keyboard geometry.
+    /// Positions are sorted by row, then column, so layouts built from them
+    /// list their keys in the same order on every run.
     #[must_use]
     pub fn get_all_visual_positions(&self) -> Vec<Position> {
-        self.visual_to_matrix.keys().copied().collect()
+        let mut positions: Vec<Position> = self.visual_to_matrix.keys().copied().collect();
+        positions.sort_by_key(|pos| (pos.row, pos.col));
+        positions
     }
 
     /// Returns `true` if `pos` is a **valid visual position** (i.e. a physical key exists there).
@@ -4,7 +4,6 @@
 
 use super::*;
 
-use super::*;
 use crate::models::keyboard_geometry::KeyGeometry;
 
 #[test]
@@ -7,9 +7,9 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::models::{KeyGeometry, KeyboardGeometry};
+use crate::models::{KeyGeometry, KeyboardGeometry, LedGeometry};
 
 /// QMK info.json structure (simplified for our needs)
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -29,6 +29,33 @@ pub struct QmkInfoJson {
     pub matrix_pins: Option<MatrixPins>,
     /// Encoder configuration
     pub encoder: Option<EncoderConfig>,
+    /// Microcontroller (e.g., "atmega32u4", "STM32F411")
+    pub processor: Option<String>,
+    /// Firmware features enabled by default (e.g., `"rgb_matrix": true`)
+    #[serde(default)]
+    pub features: HashMap<String, bool>,
+    /// Split keyboard configuration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split: Option<SplitConfig>,
+}
+
+/// Split keyboard configuration from info.json or keyboard.json
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SplitConfig {
+    /// Whether the keyboard is split into two halves
+    #[serde(default)]
+    pub enabled: bool,
+    /// How the halves talk to each other
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport: Option<SplitTransport>,
+}
+
+/// Split transport configuration
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SplitTransport {
+    /// Transport protocol (e.g., "serial", "i2c")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
 }
 
 /// Encoder configuration from info.json
@@ -72,6 +99,9 @@ pub struct KeyPosition {
     /// Rotation in degrees (default 0.0)
     #[serde(default)]
     pub r: f32,
+    /// Optional key legend (e.g., "Esc", "Space")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
 }
 
 /// Matrix pins configuration
@@ -95,6 +125,14 @@ pub struct VariantKeyboardJson {
     pub layouts: HashMap<String, LayoutDefinition>,
     /// Encoder configuration (can also be in keyboard.json)
     pub encoder: Option<EncoderConfig>,
+    /// Microcontroller (can also be in keyboard.json)
+    pub processor: Option<String>,
+    /// Firmware features enabled by default (can also be in keyboard.json)
+    #[serde(default)]
+    pub features: HashMap<String, bool>,
+    /// Split keyboard configuration (can also be in keyboard.json)
+    #[serde(default)]
+    pub split: Option<SplitConfig>,
 }
 
 /// RGB matrix configuration from keyboard.json
@@ -131,9 +169,14 @@ const fn default_key_size() -> f32 {
 /// are returned (e.g., `splitkb/halcyon/ferris/rev1` instead of the non-compilable
 /// parent `splitkb/halcyon/ferris`).
 ///
+/// Keyboards in `keyboard_roots` (see [`keyboards_dir_for`]) are added by
+/// looking for `keyboard.json` and `info.json` files, since `qmk` doesn't
+/// know about them.
+///
 /// # Arguments
 ///
 /// * `qmk_path` - Path to QMK firmware root directory
+/// * `keyboard_roots` - Extra keyboard directories from `paths.keyboard_roots`
 ///
 /// # Returns
 ///
@@ -146,7 +189,7 @@ const fn default_key_size() -> f32 {
 /// - The `qmk list-keyboards` command fails
 /// - The command output cannot be parsed
 #[allow(dead_code)] // Public API; tests are in lib target (bin doesn't link)
-pub fn scan_keyboards(qmk_path: &Path) -> Result<Vec<String>> {
+pub fn scan_keyboards(qmk_path: &Path, keyboard_roots: &[PathBuf]) -> Result<Vec<String>> {
     use std::process::Command;
 
     let keyboards_dir = qmk_path.join("keyboards");
@@ -178,12 +221,74 @@ pub fn scan_keyboards(qmk_path: &Path) -> Result<Vec<String>> {
         .filter(|line| !line.is_empty())
         .collect();
 
+    for root in keyboard_roots {
+        keyboards.extend(scan_keyboard_root(root)?);
+    }
+
     // Sort alphabetically for consistent ordering
     keyboards.sort();
+    keyboards.dedup();
 
     Ok(keyboards)
 }
 
+/// Lists the keyboards defined in a keyboards directory: every directory
+/// with an `info.json` or `keyboard.json`, relative to `root`.
+///
+/// # Errors
+///
+/// Returns an error if a directory cannot be read.
+pub fn scan_keyboard_root(root: &Path) -> Result<Vec<String>> {
+    fn visit(dir: &Path, root: &Path, keyboards: &mut Vec<String>) -> Result<()> {
+        let entries =
+            fs::read_dir(dir).context(format!("Failed to read directory: {}", dir.display()))?;
+
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                // Skip hidden directories and common non-keyboard directories
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    if name.starts_with('.') || name == "lib" || name == "template" {
+                        continue;
+                    }
+                }
+                visit(&path, root, keyboards)?;
+            } else if matches!(
+                path.file_name().and_then(|n| n.to_str()),
+                Some("info.json" | "keyboard.json")
+            ) {
+                if let Some(rel_path) = path.parent().and_then(|p| p.strip_prefix(root).ok()) {
+                    let keyboard = rel_path.to_string_lossy().replace('\\', "/");
+                    if !keyboard.is_empty() && !keyboards.contains(&keyboard) {
+                        keyboards.push(keyboard);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    let mut keyboards = Vec::new();
+    visit(root, root, &mut keyboards)?;
+    keyboards.sort();
+    Ok(keyboards)
+}
+
+/// Returns the keyboards directory that defines `keyboard`.
+///
+/// Each of `keyboard_roots` is laid out like QMK's `keyboards/` directory.
+/// They are searched in order before QMK's own, so a board from a personal
+/// fork or an out-of-tree vendor directory takes precedence over QMK's copy.
+#[must_use]
+pub fn keyboards_dir_for(qmk_path: &Path, keyboard_roots: &[PathBuf], keyboard: &str) -> PathBuf {
+    keyboard_roots
+        .iter()
+        .find(|root| !keyboard.is_empty() && root.join(keyboard).is_dir())
+        .cloned()
+        .unwrap_or_else(|| qmk_path.join("keyboards"))
+}
+
 /// Parses a QMK info.json file.
 ///
 /// # Arguments
@@ -231,6 +336,7 @@ pub struct KeyboardConfig {
 /// # Arguments
 ///
 /// * `qmk_path` - Path to QMK firmware root directory
+/// * `keyboard_roots` - Extra keyboard directories (see [`keyboards_dir_for`])
 /// * `keyboard` - Keyboard name (e.g., "crkbd", "1upkeyboards/pi50/grid")
 ///
 /// # Returns
@@ -240,8 +346,12 @@ pub struct KeyboardConfig {
 /// # Errors
 ///
 /// Returns an error if no configuration files are found
-pub fn discover_keyboard_config(qmk_path: &Path, keyboard: &str) -> Result<KeyboardConfig> {
-    let keyboards_dir = qmk_path.join("keyboards");
+pub fn discover_keyboard_config(
+    qmk_path: &Path,
+    keyboard_roots: &[PathBuf],
+    keyboard: &str,
+) -> Result<KeyboardConfig> {
+    let keyboards_dir = keyboards_dir_for(qmk_path, keyboard_roots, keyboard);
     let keyboard_dir = keyboards_dir.join(keyboard);
 
     let mut config = KeyboardConfig {
@@ -368,15 +478,20 @@ fn format_found_files(config: &KeyboardConfig) -> String {
 /// # Arguments
 ///
 /// * `qmk_path` - Path to QMK firmware root directory
+/// * `keyboard_roots` - Extra keyboard directories (see [`keyboards_dir_for`])
 /// * `keyboard` - Keyboard name (e.g., "crkbd", "ferris/sweep",
 ///   "`keebart/corne_choc_pro/standard`")
 ///
 /// # Returns
 ///
 /// Parsed QMK info.json structure (potentially merged from multiple files)
-pub fn parse_keyboard_info_json(qmk_path: &Path, keyboard: &str) -> Result<QmkInfoJson> {
+pub fn parse_keyboard_info_json(
+    qmk_path: &Path,
+    keyboard_roots: &[PathBuf],
+    keyboard: &str,
+) -> Result<QmkInfoJson> {
     // Step 1: Discover configuration files
-    let config = discover_keyboard_config(qmk_path, keyboard)?;
+    let config = discover_keyboard_config(qmk_path, keyboard_roots, keyboard)?;
 
     // Step 2: Load and merge configuration
     let info = load_merged_config(&config)?;
@@ -412,6 +527,9 @@ fn load_merged_config(config: &KeyboardConfig) -> Result<QmkInfoJson> {
             layouts: variant.layouts,
             matrix_pins: None,
             encoder: variant.encoder,
+            processor: variant.processor,
+            features: variant.features,
+            split: variant.split,
         }
     } else {
         anyhow::bail!(
@@ -432,12 +550,91 @@ fn load_merged_config(config: &KeyboardConfig) -> Result<QmkInfoJson> {
             if info.encoder.is_none() {
                 info.encoder = variant.encoder;
             }
+            if info.split.is_none() {
+                info.split = variant.split;
+            }
         }
     }
 
     Ok(info)
 }
 
+/// A keyboard's info.json after QMK-style inheritance.
+#[allow(dead_code)] // Used by the web keyboard info endpoint (lib target only)
+#[derive(Debug, Clone)]
+pub struct ResolvedInfoJson {
+    /// Files that were merged, parent directories first
+    pub sources: Vec<std::path::PathBuf>,
+    /// Merged JSON document
+    pub info: serde_json::Value,
+}
+
+/// Resolves the full info.json for a keyboard the way QMK does.
+///
+/// Every `info.json` and `keyboard.json` from the top-level keyboard
+/// directory down to `keyboard` is deep-merged in order, so values in a
+/// variant directory override those inherited from its parents. Unlike
+/// [`parse_keyboard_info_json`], every field is kept (features, RGB matrix,
+/// encoders, ...), not only the ones LazyQMK models.
+///
+/// # Errors
+///
+/// Returns an error if the keyboard directory does not exist, no
+/// configuration file exists along the path, or a file cannot be parsed.
+pub fn resolve_info_json(
+    qmk_path: &Path,
+    keyboard_roots: &[PathBuf],
+    keyboard: &str,
+) -> Result<ResolvedInfoJson> {
+    let mut dir = keyboards_dir_for(qmk_path, keyboard_roots, keyboard);
+    let mut sources = Vec::new();
+    let mut info = serde_json::Value::Object(serde_json::Map::new());
+
+    for component in keyboard.split('/').filter(|c| !c.is_empty()) {
+        dir.push(component);
+        for name in ["info.json", "keyboard.json"] {
+            let path = dir.join(name);
+            if !path.is_file() {
+                continue;
+            }
+            let content =
+                fs::read_to_string(&path).context(format!("Failed to read {}", path.display()))?;
+            let value: serde_json::Value =
+                json5::from_str(&content).context(format!("Failed to parse {}", path.display()))?;
+            merge_json(&mut info, value);
+            sources.push(path);
+        }
+    }
+
+    if !dir.is_dir() || sources.is_empty() {
+        anyhow::bail!(
+            "No configuration files found for keyboard '{}' at {}",
+            keyboard,
+            dir.display()
+        );
+    }
+
+    Ok(ResolvedInfoJson { sources, info })
+}
+
+/// Deep-merges `overlay` into `base`: objects merge key by key, any other
+/// value replaces the existing one.
+fn merge_json(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
 /// Internal helper to parse a keyboard.json file.
 ///
 /// Uses JSON5 parser to handle QMK's non-standard JSON with comments.
@@ -459,6 +656,7 @@ fn parse_variant_json(path: &Path) -> Result<VariantKeyboardJson> {
 /// # Arguments
 ///
 /// * `qmk_path` - Path to QMK firmware root directory
+/// * `keyboard_roots` - Extra keyboard directories (see [`keyboards_dir_for`])
 /// * `keyboard` - Full keyboard path including variant (e.g., "`keebart/corne_choc_pro/standard`")
 ///
 /// # Returns
@@ -467,8 +665,12 @@ fn parse_variant_json(path: &Path) -> Result<VariantKeyboardJson> {
 ///
 /// Uses JSON5 parser to handle QMK's non-standard JSON with comments.
 #[must_use]
-pub fn parse_variant_keyboard_json(qmk_path: &Path, keyboard: &str) -> Option<VariantKeyboardJson> {
-    let keyboards_dir = qmk_path.join("keyboards");
+pub fn parse_variant_keyboard_json(
+    qmk_path: &Path,
+    keyboard_roots: &[PathBuf],
+    keyboard: &str,
+) -> Option<VariantKeyboardJson> {
+    let keyboards_dir = keyboards_dir_for(qmk_path, keyboard_roots, keyboard);
     let keyboard_json_path = keyboards_dir.join(keyboard).join("keyboard.json");
 
     if !keyboard_json_path.exists() {
@@ -506,6 +708,26 @@ pub fn build_matrix_to_led_map(rgb_config: &RgbMatrixConfig) -> HashMap<(u8, u8)
     map
 }
 
+/// Builds the physical LED list from the RGB matrix configuration.
+///
+/// Unlike [`build_matrix_to_led_map`], this keeps underglow and indicator LEDs
+/// (entries without a matrix position) along with their physical coordinates.
+#[must_use]
+pub fn build_led_geometry(rgb_config: &RgbMatrixConfig) -> Vec<LedGeometry> {
+    rgb_config
+        .layout
+        .iter()
+        .enumerate()
+        .map(|(led_index, entry)| LedGeometry {
+            led_index: led_index as u8,
+            matrix_position: entry.matrix.map(|[row, col]| (row, col)),
+            x: entry.x,
+            y: entry.y,
+            flags: entry.flags,
+        })
+        .collect()
+}
+
 /// Layout variant information including name and key count.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LayoutVariant {
@@ -659,6 +881,7 @@ pub fn build_keyboard_geometry_with_rgb(
             width: key_pos.w,
             height: key_pos.h,
             rotation: key_pos.r,
+            label: key_pos.label.clone(),
         };
 
         keys.push(key_geometry);
@@ -671,6 +894,13 @@ pub fn build_keyboard_geometry_with_rgb(
         matrix_cols,
         keys,
         encoder_count: 0, // Will be set by caller if encoder info is available
+        leds: Vec::new(), // Will be set by caller if rgb_matrix info is available
+        processor: info.processor.clone(),
+        features: info
+            .features
+            .iter()
+            .map(|(name, enabled)| (name.clone(), *enabled))
+            .collect(),
     })
 }
 
@@ -4,7 +4,6 @@
 
 use super::*;
 
-use super::*;
 use tempfile::TempDir;
 
 fn create_test_info_json() -> String {
@@ -145,7 +144,7 @@ fn test_scan_keyboards_invalid_path() {
     let temp_dir = TempDir::new().unwrap();
 
     // Test with a path that has no keyboards directory
-    let result = scan_keyboards(temp_dir.path());
+    let result = scan_keyboards(temp_dir.path(), &[]);
     assert!(result.is_err());
     assert!(result
         .unwrap_err()
@@ -153,6 +152,109 @@ fn test_scan_keyboards_invalid_path() {
         .contains("QMK keyboards directory not found"));
 }
 
+#[test]
+fn test_build_led_geometry_keeps_underglow() {
+    let rgb: RgbMatrixConfig = serde_json::from_str(
+        r#"{
+            "layout": [
+                {"x": 20, "y": 60, "flags": 2},
+                {"matrix": [0, 1], "x": 0, "y": 0, "flags": 4}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let leds = build_led_geometry(&rgb);
+
+    assert_eq!(leds.len(), 2);
+    assert_eq!(leds[0].matrix_position, None);
+    assert_eq!((leds[0].x, leds[0].y, leds[0].flags), (20, 60, 2));
+    assert_eq!(leds[1].led_index, 1);
+    assert_eq!(leds[1].matrix_position, Some((0, 1)));
+    assert_eq!(build_matrix_to_led_map(&rgb).get(&(0, 1)), Some(&1));
+}
+
+#[test]
+fn test_resolve_info_json_merges_parent_and_variant() {
+    let temp_dir = TempDir::new().unwrap();
+    let parent = temp_dir.path().join("keyboards/acme/split");
+    let variant = parent.join("rev2");
+    fs::create_dir_all(&variant).unwrap();
+    fs::write(
+        parent.join("info.json"),
+        r#"{
+            // Comments are allowed
+            "manufacturer": "Acme",
+            "features": {"rgb_matrix": true, "encoder": false},
+            "encoder": {"rotary": [{"pin_a": "B1", "pin_b": "B2"}]}
+        }"#,
+    )
+    .unwrap();
+    fs::write(
+        variant.join("keyboard.json"),
+        r#"{"features": {"encoder": true}, "rgb_matrix": {"driver": "ws2812"}}"#,
+    )
+    .unwrap();
+
+    let resolved = resolve_info_json(temp_dir.path(), &[], "acme/split/rev2").unwrap();
+
+    assert_eq!(
+        resolved.sources,
+        vec![parent.join("info.json"), variant.join("keyboard.json")]
+    );
+    let info = &resolved.info;
+    assert_eq!(info["manufacturer"], "Acme");
+    assert_eq!(info["features"]["rgb_matrix"], true);
+    assert_eq!(info["features"]["encoder"], true);
+    assert_eq!(info["encoder"]["rotary"][0]["pin_a"], "B1");
+    assert_eq!(info["rgb_matrix"]["driver"], "ws2812");
+
+    assert!(resolve_info_json(temp_dir.path(), &[], "acme/missing").is_err());
+    assert!(resolve_info_json(temp_dir.path(), &[], "acme/split/rev3").is_err());
+}
+
+#[test]
+fn test_keyboard_roots_take_precedence_over_qmk() {
+    let qmk = TempDir::new().unwrap();
+    let root = TempDir::new().unwrap();
+    fs::create_dir_all(qmk.path().join("keyboards/acme/split")).unwrap();
+    fs::create_dir_all(root.path().join("acme/split/rev2")).unwrap();
+    fs::create_dir_all(root.path().join("mine/board")).unwrap();
+    fs::write(
+        root.path().join("acme/split/info.json"),
+        r#"{"manufacturer": "Fork"}"#,
+    )
+    .unwrap();
+    fs::write(root.path().join("acme/split/rev2/keyboard.json"), "{}").unwrap();
+    fs::write(root.path().join("mine/board/keyboard.json"), "{}").unwrap();
+    let roots = [root.path().to_path_buf()];
+
+    assert_eq!(
+        keyboards_dir_for(qmk.path(), &roots, "acme/split"),
+        root.path()
+    );
+    assert_eq!(
+        keyboards_dir_for(qmk.path(), &roots, "crkbd"),
+        qmk.path().join("keyboards")
+    );
+    assert_eq!(
+        keyboards_dir_for(qmk.path(), &[], "acme/split"),
+        qmk.path().join("keyboards")
+    );
+
+    let resolved = resolve_info_json(qmk.path(), &roots, "acme/split/rev2").unwrap();
+    assert_eq!(resolved.info["manufacturer"], "Fork");
+
+    assert_eq!(
+        scan_keyboard_root(root.path()).unwrap(),
+        vec![
+            "acme/split".to_string(),
+            "acme/split/rev2".to_string(),
+            "mine/board".to_string()
+        ]
+    );
+}
+
 // Note: Testing scan_keyboards with actual QMK requires the QMK CLI to be installed
 // and a valid QMK repository. See tests/qmk_info_json_tests.rs for integration tests
 // that test against the actual QMK firmware submodule.
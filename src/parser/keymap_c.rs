@@ -0,0 +1,564 @@
+//! Importer for handwritten QMK `keymap.c` files.
+//!
+//! Reads the `keymaps[]` array, the layer enum, and `ACTION_TAP_DANCE_DOUBLE`
+//! tap dances from a keymap, then places the keys on a keyboard geometry to
+//! build a [`Layout`]. Object-like `#define` aliases (e.g.
+//! `#define LOWER MO(_LOWER)`) are expanded. Constructs the editor cannot
+//! represent are reported as warnings instead of failing the import.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+
+use crate::models::{
+    KeyDefinition, KeyboardGeometry, Layer, Layout, RgbColor, TapDanceAction, VisualLayoutMapping,
+};
+
+/// Keycodes whose first argument is a layer.
+const LAYER_FUNCTIONS: [&str; 9] = ["MO", "TG", "TO", "TT", "OSL", "DF", "PDF", "LT", "LM"];
+
+/// Default color for imported layers (Gray-500, as for new layers).
+const IMPORTED_LAYER_COLOR: RgbColor = RgbColor::new(128, 128, 128);
+
+/// Maximum depth when expanding chained `#define` aliases.
+const MAX_ALIAS_DEPTH: usize = 8;
+
+/// One layer from the `keymaps[]` array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedLayer {
+    /// Layer name derived from its enum identifier (e.g. "_LOWER" → "Lower")
+    pub name: String,
+    /// Keycodes in LAYOUT macro order. Layer arguments are numeric
+    /// (e.g. `MO(1)`) and tap dances use their layout name (`TD(esc_caps)`).
+    pub keycodes: Vec<String>,
+}
+
+/// A parsed `keymap.c`, before it is placed on a keyboard.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedKeymap {
+    /// LAYOUT macro used by the layers (e.g. "`LAYOUT_split_3x6_3`")
+    pub layout_macro: String,
+    /// Layers in index order
+    pub layers: Vec<ParsedLayer>,
+    /// Tap dances that could be imported
+    pub tap_dances: Vec<TapDanceAction>,
+    /// Constructs that were skipped or replaced during the import
+    pub warnings: Vec<String>,
+}
+
+impl ParsedKeymap {
+    /// Builds a layout named `name` by placing each layer's keys on `geometry`.
+    ///
+    /// Layer arguments are rewritten to layer ID references (`MO(@id)`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a layer's key count does not match the geometry,
+    /// or if a layer or tap dance fails validation.
+    pub fn to_layout(&self, name: &str, geometry: &KeyboardGeometry) -> Result<Layout> {
+        let mapping = VisualLayoutMapping::build(geometry);
+        let key_count = geometry.keys.len();
+        let positions = (0..key_count)
+            .map(|idx| {
+                mapping
+                    .layout_to_matrix
+                    .get(idx)
+                    .and_then(|matrix| mapping.matrix_to_visual.get(matrix))
+                    .copied()
+                    .with_context(|| format!("Layout index {idx} has no key in the geometry"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut layout = Layout::new(name)?;
+        layout.metadata.keyboard = Some(geometry.keyboard_name.clone());
+        layout.metadata.layout_variant = Some(geometry.layout_name.clone());
+
+        for (idx, parsed) in self.layers.iter().enumerate() {
+            if parsed.keycodes.len() != key_count {
+                anyhow::bail!(
+                    "Layer '{}' has {} keys but {} expects {key_count}",
+                    parsed.name,
+                    parsed.keycodes.len(),
+                    geometry.layout_name
+                );
+            }
+            let number = u8::try_from(idx).context("Too many layers")?;
+            layout.add_layer(Layer::new(number, &parsed.name, IMPORTED_LAYER_COLOR)?)?;
+        }
+
+        let layer_ids: Vec<String> = layout.layers.iter().map(|l| l.id.clone()).collect();
+        for (layer, parsed) in layout.layers.iter_mut().zip(&self.layers) {
+            for (position, keycode) in positions.iter().zip(&parsed.keycodes) {
+                let keycode = reference_layer_ids(keycode, &layer_ids);
+                layer.add_key(KeyDefinition::new(*position, keycode));
+            }
+        }
+
+        for tap_dance in &self.tap_dances {
+            layout.add_tap_dance(tap_dance.clone())?;
+        }
+
+        Ok(layout)
+    }
+}
+
+/// Parses the keymaps, layer names, and tap dances of a `keymap.c` source.
+///
+/// # Errors
+///
+/// Returns an error if there is no `keymaps[]` array, if a layer index
+/// cannot be resolved, or if layers use different LAYOUT macros.
+pub fn parse_keymap_c(source: &str) -> Result<ParsedKeymap> {
+    let source = strip_comments(source);
+    let symbols = Symbols::parse(&source);
+    let mut keymap = ParsedKeymap::default();
+
+    let tap_dance_names = parse_tap_dances(&source, &symbols, &mut keymap)?;
+
+    let body =
+        find_array_body(&source, "keymaps").context("No keymaps[] array found in keymap.c")?;
+    let mut layers: Vec<(usize, String, Vec<String>)> = Vec::new();
+    for (position, entry) in split_top_level(body).into_iter().enumerate() {
+        let (designator, value) = split_designator(entry);
+        let (index, name) = match designator {
+            Some(ident) => {
+                let index = symbols
+                    .resolve_index(ident)
+                    .with_context(|| format!("Cannot resolve layer index '{ident}'"))?;
+                (index, layer_display_name(ident, index))
+            }
+            None => (position, format!("Layer {position}")),
+        };
+
+        let (macro_name, args) = split_call(value)
+            .with_context(|| format!("Layer '{name}' is not a LAYOUT macro call"))?;
+        if keymap.layout_macro.is_empty() {
+            keymap.layout_macro = macro_name.to_string();
+        } else if keymap.layout_macro != macro_name {
+            anyhow::bail!(
+                "Layer '{name}' uses {macro_name} but earlier layers use {}",
+                keymap.layout_macro
+            );
+        }
+
+        let keycodes = split_top_level(args)
+            .into_iter()
+            .map(|token| convert_keycode(token, &symbols, &tap_dance_names, &mut keymap.warnings))
+            .collect();
+        layers.push((index, name, keycodes));
+    }
+
+    layers.sort_by_key(|(index, _, _)| *index);
+    for (expected, (index, name, keycodes)) in layers.into_iter().enumerate() {
+        if index != expected {
+            anyhow::bail!(
+                "Layer indexes must be contiguous from 0: expected layer {expected}, found {index} ('{name}')"
+            );
+        }
+        keymap.layers.push(ParsedLayer { name, keycodes });
+    }
+    if keymap.layers.is_empty() {
+        anyhow::bail!("The keymaps[] array has no layers");
+    }
+
+    Ok(keymap)
+}
+
+/// `#define` aliases and integer constants from enums and defines.
+#[derive(Debug, Default)]
+struct Symbols {
+    defines: HashMap<String, String>,
+    constants: HashMap<String, usize>,
+}
+
+impl Symbols {
+    fn parse(source: &str) -> Self {
+        let mut symbols = Self::default();
+
+        for line in source.replace("\\\n", " ").lines() {
+            let Some(rest) = line.trim_start().strip_prefix("#define") else {
+                continue;
+            };
+            let rest = rest.trim_start();
+            let name_len = rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len());
+            let (name, value) = rest.split_at(name_len);
+            // Skip function-like macros and empty defines
+            if name.is_empty() || value.starts_with('(') || value.trim().is_empty() {
+                continue;
+            }
+            symbols
+                .defines
+                .insert(name.to_string(), value.trim().to_string());
+        }
+
+        let mut search = 0;
+        while let Some(found) = source[search..].find("enum") {
+            let start = search + found;
+            search = start + "enum".len();
+            let before_ok = source[..start]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !is_ident_char(c));
+            if !before_ok {
+                continue;
+            }
+            let Some(open) = source[search..].find('{').map(|i| search + i) else {
+                break;
+            };
+            // Only `enum [name] {`, not uses of enum types
+            if source[search..open]
+                .trim()
+                .contains(|c: char| !is_ident_char(c))
+            {
+                continue;
+            }
+            let Some(close) = matching_close(&source, open) else {
+                break;
+            };
+            symbols.add_enum(&source[open + 1..close]);
+            search = close;
+        }
+
+        symbols
+    }
+
+    /// Records enum members whose values are known integers.
+    fn add_enum(&mut self, body: &str) {
+        let mut next = Some(0);
+        for member in split_top_level(body) {
+            let (name, value) = match member.split_once('=') {
+                Some((name, value)) => (name.trim(), self.resolve_index(value.trim())),
+                None => (member.trim(), next),
+            };
+            if let Some(value) = value {
+                self.constants.insert(name.to_string(), value);
+            }
+            next = value.map(|v| v + 1);
+        }
+    }
+
+    /// Resolves a layer index: a number, enum member, or define.
+    fn resolve_index(&self, token: &str) -> Option<usize> {
+        let mut token = token.trim();
+        for _ in 0..MAX_ALIAS_DEPTH {
+            if let Some(value) = parse_integer(token) {
+                return Some(value);
+            }
+            if let Some(&value) = self.constants.get(token) {
+                return Some(value);
+            }
+            token = self.defines.get(token)?.trim();
+        }
+        None
+    }
+
+    /// Expands a keycode that is itself a `#define` alias.
+    fn expand<'a>(&'a self, mut token: &'a str) -> &'a str {
+        for _ in 0..MAX_ALIAS_DEPTH {
+            match self.defines.get(token) {
+                Some(value) => token = value.trim(),
+                None => break,
+            }
+        }
+        token
+    }
+}
+
+/// Parses `tap_dance_actions[]` into `keymap.tap_dances`.
+///
+/// Returns the mapping from source enum names to layout tap dance names.
+fn parse_tap_dances(
+    source: &str,
+    symbols: &Symbols,
+    keymap: &mut ParsedKeymap,
+) -> Result<HashMap<String, String>> {
+    let mut names = HashMap::new();
+    let Some(body) = find_array_body(source, "tap_dance_actions") else {
+        return Ok(names);
+    };
+
+    for entry in split_top_level(body) {
+        let (designator, value) = split_designator(entry);
+        let Some(ident) = designator else {
+            keymap.warnings.push(format!(
+                "Skipped tap dance without a [NAME] designator: {value}"
+            ));
+            continue;
+        };
+        let args = split_call(value).and_then(|(action, args)| {
+            let args = split_top_level(args);
+            (action == "ACTION_TAP_DANCE_DOUBLE" && args.len() == 2).then_some(args)
+        });
+        let Some(args) = args else {
+            keymap.warnings.push(format!(
+                "Skipped tap dance {ident}: only ACTION_TAP_DANCE_DOUBLE can be imported"
+            ));
+            continue;
+        };
+
+        let name = tap_dance_name(ident);
+        let single_tap = normalize_keycode(symbols.expand(args[0]));
+        let double_tap = normalize_keycode(symbols.expand(args[1]));
+        let tap_dance = TapDanceAction::new(&name, single_tap).with_double_tap(double_tap);
+        tap_dance
+            .validate()
+            .with_context(|| format!("Invalid tap dance {ident}"))?;
+        keymap.tap_dances.push(tap_dance);
+        names.insert(ident.to_string(), name);
+    }
+
+    Ok(names)
+}
+
+/// Converts one LAYOUT argument to the layout's keycode form.
+fn convert_keycode(
+    token: &str,
+    symbols: &Symbols,
+    tap_dance_names: &HashMap<String, String>,
+    warnings: &mut Vec<String>,
+) -> String {
+    let keycode = normalize_keycode(symbols.expand(token.trim()));
+    let Some((function, args)) = split_call(&keycode) else {
+        return keycode;
+    };
+
+    if function == "TD" {
+        return if let Some(name) = tap_dance_names.get(args.trim()) {
+            format!("TD({name})")
+        } else {
+            warnings.push(format!(
+                "Replaced {keycode} with KC_NO: its tap dance could not be imported"
+            ));
+            "KC_NO".to_string()
+        };
+    }
+
+    if LAYER_FUNCTIONS.contains(&function) {
+        let mut args = split_top_level(args);
+        if let Some(index) = args.first().and_then(|layer| symbols.resolve_index(layer)) {
+            let index = index.to_string();
+            args[0] = &index;
+            return format!("{function}({})", args.join(", "));
+        }
+    }
+
+    keycode
+}
+
+/// Rewrites a numeric layer argument (`MO(1)`) to a layer ID reference.
+fn reference_layer_ids(keycode: &str, layer_ids: &[String]) -> String {
+    let Some((function, args)) = split_call(keycode) else {
+        return keycode.to_string();
+    };
+    if !LAYER_FUNCTIONS.contains(&function) {
+        return keycode.to_string();
+    }
+
+    let mut args: Vec<String> = split_top_level(args)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let Some(id) = args
+        .first()
+        .and_then(|layer| layer.parse::<usize>().ok())
+        .and_then(|index| layer_ids.get(index))
+    else {
+        return keycode.to_string();
+    };
+    args[0] = format!("@{id}");
+    format!("{function}({})", args.join(", "))
+}
+
+/// Collapses whitespace and maps QMK's fill aliases to keycodes.
+pub(super) fn normalize_keycode(token: &str) -> String {
+    let compact: String = token.chars().filter(|c| !c.is_whitespace()).collect();
+    match compact.as_str() {
+        "_______" => "KC_TRNS".to_string(),
+        "XXXXXXX" => "KC_NO".to_string(),
+        _ => compact.replace(',', ", "),
+    }
+}
+
+/// Layer name from an enum identifier: "_LOWER" → "Lower", "L_NAV" → "L Nav".
+fn layer_display_name(ident: &str, index: usize) -> String {
+    if parse_integer(ident).is_some() {
+        return format!("Layer {index}");
+    }
+    let name = ident
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let lower = word.to_lowercase();
+            let mut chars = lower.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    if name.is_empty() {
+        format!("Layer {index}")
+    } else {
+        name
+    }
+}
+
+/// Tap dance name from an enum identifier: "`TD_ESC_CAPS`" → "`esc_caps`".
+fn tap_dance_name(ident: &str) -> String {
+    let name = ident.to_lowercase();
+    match name.strip_prefix("td_") {
+        Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+        _ => name,
+    }
+}
+
+/// Removes `//` and `/* */` comments, keeping string and char literals.
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            '"' | '\'' => {
+                out.push(c);
+                while let Some(inner) = chars.next() {
+                    out.push(inner);
+                    if inner == '\\' {
+                        if let Some(escaped) = chars.next() {
+                            out.push(escaped);
+                        }
+                    } else if inner == c || inner == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Returns the contents of the `{ ... }` initializer of array `name`.
+fn find_array_body<'a>(source: &'a str, name: &str) -> Option<&'a str> {
+    let mut search = 0;
+    while let Some(found) = source[search..].find(name) {
+        let start = search + found;
+        search = start + name.len();
+        let before_ok = source[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !is_ident_char(c));
+        if !before_ok || !source[search..].trim_start().starts_with('[') {
+            continue;
+        }
+        let equals = search + source[search..].find('=')?;
+        let open = equals + source[equals..].find('{')?;
+        let close = matching_close(source, open)?;
+        return Some(&source[open + 1..close]);
+    }
+    None
+}
+
+/// Finds the bracket closing the one at byte offset `open`.
+fn matching_close(source: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (offset, c) in source[open..].char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(open + offset);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits on commas outside brackets, dropping empty items
+/// (e.g. after a trailing comma).
+fn split_top_level(source: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (idx, c) in source.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(source[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(source[start..].trim());
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+/// Splits `[NAME] = value` into the designator and value.
+fn split_designator(entry: &str) -> (Option<&str>, &str) {
+    let entry = entry.trim();
+    if let Some(rest) = entry.strip_prefix('[') {
+        if let Some((designator, value)) = rest.split_once(']') {
+            if let Some(value) = value.trim_start().strip_prefix('=') {
+                return (Some(designator.trim()), value.trim());
+            }
+        }
+    }
+    (None, entry)
+}
+
+/// Splits `NAME(args)` into the name and the argument text.
+fn split_call(value: &str) -> Option<(&str, &str)> {
+    let value = value.trim();
+    let open = value.find('(')?;
+    let name = value[..open].trim();
+    if name.is_empty() || !name.chars().all(is_ident_char) {
+        return None;
+    }
+    let close = matching_close(value, open)?;
+    (close == value.len() - 1).then(|| (name, &value[open + 1..close]))
+}
+
+/// Parses a decimal or hex integer literal.
+fn parse_integer(token: &str) -> Option<usize> {
+    let token = token.trim();
+    token
+        .strip_prefix("0x")
+        .or_else(|| token.strip_prefix("0X"))
+        .map_or_else(
+            || token.parse().ok(),
+            |hex| usize::from_str_radix(hex, 16).ok(),
+        )
+}
+
+const fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,174 @@
+//! Tests for keymap_c.
+
+use super::*;
+use crate::models::{KeyGeometry, Position};
+
+const KEYMAP_C: &str = r"
+#include QMK_KEYBOARD_H
+
+enum layers {
+    _BASE,
+    _LOWER, // symbols
+    _RAISE,
+};
+
+enum tap_dances {
+    TD_ESC_CAPS,
+    TD_CUSTOM,
+};
+
+#define LOWER MO(_LOWER)
+#define CTL_A LCTL_T(KC_A)
+
+tap_dance_action_t tap_dance_actions[] = {
+    [TD_ESC_CAPS] = ACTION_TAP_DANCE_DOUBLE(KC_ESC, KC_CAPS),
+    [TD_CUSTOM] = ACTION_TAP_DANCE_FN(dance_custom),
+};
+
+/* Base
+ * ,-----------------.
+ */
+const uint16_t PROGMEM keymaps[][MATRIX_ROWS][MATRIX_COLS] = {
+    [_BASE] = LAYOUT_test(
+        TD(TD_ESC_CAPS), CTL_A,   KC_B,
+        LOWER,           LT(_RAISE,KC_SPC), TD(TD_CUSTOM)
+    ),
+    [_RAISE] = LAYOUT_test(
+        KC_1, KC_2, KC_3,
+        _______, XXXXXXX, TO( _BASE )
+    ),
+    [_LOWER] = LAYOUT_test(
+        KC_EXLM, KC_AT, KC_HASH,
+        _______, _______, _______
+    )
+};
+";
+
+/// 2x3 grid in row-major LAYOUT order.
+fn create_test_geometry() -> KeyboardGeometry {
+    let mut geometry = KeyboardGeometry::new("test_keyboard", "LAYOUT_test", 2, 3);
+    for idx in 0..6u8 {
+        let (row, col) = (idx / 3, idx % 3);
+        geometry.keys.push(KeyGeometry {
+            matrix_position: (row, col),
+            led_index: idx,
+            layout_index: idx,
+            visual_x: f32::from(col),
+            visual_y: f32::from(row),
+            width: 1.0,
+            height: 1.0,
+            rotation: 0.0,
+            label: None,
+        });
+    }
+    geometry
+}
+
+#[test]
+fn test_parse_layers_in_enum_order() {
+    let keymap = parse_keymap_c(KEYMAP_C).unwrap();
+
+    assert_eq!(keymap.layout_macro, "LAYOUT_test");
+    let names: Vec<&str> = keymap.layers.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(names, ["Base", "Lower", "Raise"]);
+    assert_eq!(keymap.layers[1].keycodes[0], "KC_EXLM");
+}
+
+#[test]
+fn test_parse_expands_aliases_and_layer_arguments() {
+    let keymap = parse_keymap_c(KEYMAP_C).unwrap();
+    let base = &keymap.layers[0].keycodes;
+
+    assert_eq!(base[1], "LCTL_T(KC_A)");
+    assert_eq!(base[3], "MO(1)");
+    assert_eq!(base[4], "LT(2, KC_SPC)");
+    assert_eq!(
+        keymap.layers[2].keycodes[3..],
+        ["KC_TRNS", "KC_NO", "TO(0)"]
+    );
+}
+
+#[test]
+fn test_parse_tap_dances() {
+    let keymap = parse_keymap_c(KEYMAP_C).unwrap();
+
+    assert_eq!(keymap.tap_dances.len(), 1);
+    assert_eq!(keymap.tap_dances[0].name, "esc_caps");
+    assert_eq!(keymap.tap_dances[0].single_tap, "KC_ESC");
+    assert_eq!(keymap.tap_dances[0].double_tap.as_deref(), Some("KC_CAPS"));
+    assert_eq!(keymap.layers[0].keycodes[0], "TD(esc_caps)");
+
+    // Unsupported tap dance is dropped and its key cleared
+    assert_eq!(keymap.layers[0].keycodes[5], "KC_NO");
+    assert_eq!(keymap.warnings.len(), 2);
+    assert!(keymap.warnings[0].contains("TD_CUSTOM"));
+}
+
+#[test]
+fn test_parse_without_designators_uses_array_order() {
+    let source = "const uint16_t keymaps[][2][2] = { LAYOUT(KC_A, KC_B), LAYOUT(KC_C, KC_D) };";
+    let keymap = parse_keymap_c(source).unwrap();
+
+    assert_eq!(keymap.layers.len(), 2);
+    assert_eq!(keymap.layers[1].name, "Layer 1");
+    assert_eq!(keymap.layers[1].keycodes, ["KC_C", "KC_D"]);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(parse_keymap_c("void keyboard_post_init_user(void) {}").is_err());
+
+    let gap = "enum layers { A, B, C };\n\
+               const uint16_t keymaps[][1][1] = { [A] = LAYOUT(KC_A), [C] = LAYOUT(KC_B) };";
+    let err = parse_keymap_c(gap).unwrap_err().to_string();
+    assert!(err.contains("contiguous"), "{err}");
+
+    let mixed = "const uint16_t keymaps[][1][1] = { LAYOUT(KC_A), LAYOUT_other(KC_B) };";
+    assert!(parse_keymap_c(mixed).is_err());
+}
+
+#[test]
+fn test_to_layout_places_keys_and_references_layers() {
+    let keymap = parse_keymap_c(KEYMAP_C).unwrap();
+    let layout = keymap
+        .to_layout("Imported", &create_test_geometry())
+        .unwrap();
+
+    assert_eq!(layout.metadata.name, "Imported");
+    assert_eq!(layout.metadata.keyboard.as_deref(), Some("test_keyboard"));
+    assert_eq!(
+        layout.metadata.layout_variant.as_deref(),
+        Some("LAYOUT_test")
+    );
+    assert_eq!(layout.layers.len(), 3);
+    assert_eq!(layout.tap_dances.len(), 1);
+
+    let base = &layout.layers[0];
+    let lower_id = &layout.layers[1].id;
+    let raise_id = &layout.layers[2].id;
+    assert_eq!(base.get_key(Position::new(0, 2)).unwrap().keycode, "KC_B");
+    assert_eq!(
+        base.get_key(Position::new(1, 0)).unwrap().keycode,
+        format!("MO(@{lower_id})")
+    );
+    assert_eq!(
+        base.get_key(Position::new(1, 1)).unwrap().keycode,
+        format!("LT(@{raise_id}, KC_SPC)")
+    );
+    assert!(layout.validate().is_ok());
+}
+
+#[test]
+fn test_to_layout_rejects_key_count_mismatch() {
+    let source = "const uint16_t keymaps[][1][1] = { LAYOUT_test(KC_A, KC_B) };";
+    let keymap = parse_keymap_c(source).unwrap();
+
+    let err = keymap
+        .to_layout("Imported", &create_test_geometry())
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("has 2 keys but LAYOUT_test expects 6"),
+        "{err}"
+    );
+}
@@ -0,0 +1,62 @@
+//! Custom keycodes phase: parse `- **NAME**: "snippet"` lines with an
+//! optional `- Description:` sub-bullet.
+
+use crate::models::CustomKeycode;
+use anyhow::{Context, Result};
+
+/// Parses the custom keycodes section.
+///
+/// Each snippet is a JSON string so newlines and quotes in the C code
+/// survive the round trip.
+///
+/// Format:
+/// ```markdown
+/// ## Custom Keycodes
+///
+/// - **CK_EMAIL**: "if (record->event.pressed) {\n    SEND_STRING(\"me@example.com\");\n}"
+///   - Description: Types my email
+/// ```
+pub(super) fn parse_custom_keycodes(
+    lines: &[&str],
+    start_line: usize,
+    layout: &mut crate::models::Layout,
+) -> Result<usize> {
+    let mut line_num = start_line + 1; // Skip "## Custom Keycodes" header
+
+    while line_num < lines.len() {
+        let line = lines[line_num].trim();
+
+        // Skip empty lines
+        if line.is_empty() {
+            line_num += 1;
+            continue;
+        }
+
+        // Stop at next section
+        if line.starts_with("##") || line.starts_with("---") {
+            break;
+        }
+
+        // Parse keycode line: - **NAME**: "snippet"
+        if let Some((name, behavior)) = line
+            .strip_prefix("- **")
+            .and_then(|rest| rest.split_once("**:"))
+        {
+            let behavior: String = serde_json::from_str(behavior.trim()).with_context(|| {
+                format!("Custom keycode '{name}' behavior must be a quoted string")
+            })?;
+            let custom = CustomKeycode::new(name, behavior, "");
+            custom.validate()?;
+            layout.custom_keycodes.push(custom);
+        } else if let Some(description) = line.strip_prefix("- Description:") {
+            // Sub-bullet belongs to the keycode above it
+            if let Some(custom) = layout.custom_keycodes.last_mut() {
+                custom.description = description.trim().to_string();
+            }
+        }
+
+        line_num += 1;
+    }
+
+    Ok(line_num)
+}
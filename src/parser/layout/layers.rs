@@ -1,8 +1,28 @@
 //! Layer phase: parse layer tables and keycode cells.
 
-use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+use crate::models::{EncoderBinding, EncoderMap, KeyDefinition, Layer, Position, RgbColor};
 use anyhow::{Context, Result};
 
+/// Parses an `**Encoder N**: CCW / CW` property line.
+///
+/// Returns `None` for lines that are not encoder properties.
+fn parse_encoder_binding(line: &str) -> Result<Option<(u8, EncoderBinding)>> {
+    let Some(rest) = line.strip_prefix("**Encoder ") else {
+        return Ok(None);
+    };
+    let (index, bindings) = rest
+        .split_once("**:")
+        .ok_or_else(|| anyhow::anyhow!("Invalid encoder line: {line}"))?;
+    let encoder: u8 = index
+        .trim()
+        .parse()
+        .context(format!("Invalid encoder index in: {line}"))?;
+    let (ccw, cw) = bindings
+        .split_once('/')
+        .ok_or_else(|| anyhow::anyhow!("Encoder line must be 'CCW / CW': {line}"))?;
+    Ok(Some((encoder, EncoderBinding::new(ccw.trim(), cw.trim()))))
+}
+
 /// Parses a single layer section.
 pub(super) fn parse_layer(
     lines: &[&str],
@@ -28,7 +48,9 @@ pub(super) fn parse_layer(
     let mut layer_color = None;
     let mut layer_category = None;
     let mut layer_colors_enabled = true; // Default to true
+    let mut underglow_color = None;
     let mut layer_id = None; // Optional layer ID for persistence
+    let mut encoder_map = EncoderMap::default();
 
     while line_num < lines.len() {
         let line = lines[line_num].trim();
@@ -78,6 +100,22 @@ pub(super) fn parse_layer(
             continue;
         }
 
+        // Parse optional underglow color: **Underglow**: #RRGGBB
+        if line.starts_with("**Underglow**:") {
+            let color_str = line.strip_prefix("**Underglow**:").unwrap().trim();
+            underglow_color =
+                Some(RgbColor::from_hex(color_str).context("Failed to parse underglow color")?);
+            line_num += 1;
+            continue;
+        }
+
+        // Parse optional encoder binding: **Encoder N**: CCW / CW
+        if let Some((encoder, binding)) = parse_encoder_binding(line)? {
+            encoder_map.set(encoder, binding);
+            line_num += 1;
+            continue;
+        }
+
         // Table starts - break out of properties loop
         if line.starts_with('|') {
             break;
@@ -98,6 +136,8 @@ pub(super) fn parse_layer(
     }
     layer.category_id = layer_category;
     layer.layer_colors_enabled = layer_colors_enabled;
+    layer.underglow_color = underglow_color;
+    layer.encoder_map = encoder_map;
 
     // Parse table
     line_num = parse_layer_table(lines, line_num, &mut layer)?;
@@ -0,0 +1,55 @@
+//! Macros phase: parse `- **name**: "sequence"` lines.
+
+use crate::models::MacroDefinition;
+use anyhow::{Context, Result};
+
+/// Parses the macros section.
+///
+/// Each macro is one line with its compact sequence as a JSON string, so
+/// quotes and backslashes in typed text survive the round trip.
+///
+/// Format:
+/// ```markdown
+/// ## Macros
+///
+/// - **email**: "me@example.com"
+/// - **git_status**: "git status{KC_ENT}"
+/// ```
+pub(super) fn parse_macros(
+    lines: &[&str],
+    start_line: usize,
+    layout: &mut crate::models::Layout,
+) -> Result<usize> {
+    let mut line_num = start_line + 1; // Skip "## Macros" header
+
+    while line_num < lines.len() {
+        let line = lines[line_num].trim();
+
+        // Skip empty lines
+        if line.is_empty() {
+            line_num += 1;
+            continue;
+        }
+
+        // Stop at next section
+        if line.starts_with("##") || line.starts_with("---") {
+            break;
+        }
+
+        // Parse macro line: - **name**: "sequence"
+        if let Some((name, sequence)) = line
+            .strip_prefix("- **")
+            .and_then(|rest| rest.split_once("**:"))
+        {
+            let sequence: String = serde_json::from_str(sequence.trim())
+                .with_context(|| format!("Macro '{name}' sequence must be a quoted string"))?;
+            let macro_def = MacroDefinition::from_sequence(name, &sequence)?;
+            macro_def.validate()?;
+            layout.macros.push(macro_def);
+        }
+
+        line_num += 1;
+    }
+
+    Ok(line_num)
+}
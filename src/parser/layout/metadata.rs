@@ -64,6 +64,11 @@ pub(super) fn validate_metadata(metadata: &LayoutMetadata) -> Result<()> {
         );
     }
 
+    if let Some(converter) = &metadata.converter {
+        LayoutMetadata::validate_converter(converter)?;
+    }
+    metadata.validate_targets()?;
+
     // Validate tags
     for tag in &metadata.tags {
         if !super::tag_regex().is_match(tag) {
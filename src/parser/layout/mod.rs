@@ -12,16 +12,22 @@
 //! - [`settings`] — `## Settings` section (split into one file per group)
 //! - [`key_descriptions`] — `## Key Descriptions` section
 //! - [`tap_dances`] — `## Tap Dances` section
+//! - [`macros`] — `## Macros` section
+//! - [`unicode_map`] — `## Unicode Map` section
+//! - [`custom_keycodes`] — `## Custom Keycodes` section
 //!
 //! The dispatch happens in [`parse_content`], which is invoked by the public
 //! entry points [`parse_markdown_layout`] and [`parse_markdown_layout_str`].
 
 mod categories;
+mod custom_keycodes;
 mod key_descriptions;
 mod layers;
+mod macros;
 mod metadata;
 mod settings;
 mod tap_dances;
+mod unicode_map;
 
 use crate::constants::APP_BINARY_NAME;
 use crate::models::Layout;
@@ -166,9 +172,19 @@ pub fn parse_markdown_layout_str(content: &str) -> Result<Layout> {
         idle_effect_settings: crate::models::IdleEffectSettings::default(),
         rgb_overlay_ripple: crate::models::RgbOverlayRippleSettings::default(),
         palette_fx: crate::models::PaletteFxSettings::default(),
+        rgb_conditions: crate::models::RgbConditionalSettings::default(),
+        firmware_features: crate::models::FirmwareFeatures::default(),
         tap_hold_settings: crate::models::TapHoldSettings::default(),
         combo_settings: crate::models::ComboSettings::default(),
         tap_dances: Vec::new(),
+        macros: Vec::new(),
+        custom_keycodes: Vec::new(),
+        key_overrides: Vec::new(),
+        alt_repeat_keys: Vec::new(),
+        unicode_map: Vec::new(),
+        unicode_input_modes: Vec::new(),
+        usage_counting: false,
+        editing_helpers: crate::models::EditingHelpers::default(),
     };
 
     // Parse content (layers and categories)
@@ -233,6 +249,29 @@ fn parse_content(lines: &[&str], layout: &mut Layout) -> Result<()> {
             continue;
         }
 
+        // Check for macros section (## Macros)
+        if line == "## Macros" {
+            line_num = macros::parse_macros(lines, line_num, layout)
+                .with_context(|| format!("Error parsing macros at line {}", line_num + 1))?;
+            continue;
+        }
+
+        // Check for Unicode map section (## Unicode Map)
+        if line == "## Unicode Map" {
+            line_num = unicode_map::parse_unicode_map(lines, line_num, layout)
+                .with_context(|| format!("Error parsing Unicode map at line {}", line_num + 1))?;
+            continue;
+        }
+
+        // Check for custom keycodes section (## Custom Keycodes)
+        if line == "## Custom Keycodes" {
+            line_num = custom_keycodes::parse_custom_keycodes(lines, line_num, layout)
+                .with_context(|| {
+                    format!("Error parsing custom keycodes at line {}", line_num + 1)
+                })?;
+            continue;
+        }
+
         line_num += 1;
     }
 
@@ -478,6 +517,44 @@ version: "1.0"
         assert_eq!(combos[1].action, ComboAction::Bootloader);
     }
 
+    #[test]
+    fn test_keycode_combos_round_trip() {
+        use crate::models::KeycodeCombo;
+
+        let mut layout = Layout::new("Test Layout").expect("Failed to create layout");
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+        layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"));
+        layer.add_key(KeyDefinition::new(Position::new(0, 2), "KC_C"));
+        layout.add_layer(layer).unwrap();
+
+        layout.combo_settings.enabled = true;
+        layout
+            .combo_settings
+            .add_keycode_combo(KeycodeCombo::new(
+                vec![
+                    Position::new(0, 0),
+                    Position::new(0, 1),
+                    Position::new(0, 2),
+                ],
+                "LCTL(KC_C)",
+            ))
+            .unwrap();
+
+        let markdown =
+            template_gen::generate_markdown(&layout).expect("Failed to generate markdown");
+        assert!(
+            markdown.contains("**Keycode Combo 1**: (0,0)+(0,1)+(0,2) → LCTL(KC_C)"),
+            "Keycode combo definition missing"
+        );
+
+        let parsed_layout = parse_markdown_layout_str(&markdown).expect("Parse failed");
+        assert_eq!(
+            parsed_layout.combo_settings.keycode_combos,
+            layout.combo_settings.keycode_combos
+        );
+    }
+
     #[test]
     fn test_combos_not_written_when_default() {
         use crate::models::{KeyDefinition, Layer, RgbColor};
@@ -1,9 +1,10 @@
-//! Combos settings: enabled flag and individual combo definitions.
+//! Combos settings: enabled flag, individual hold combo definitions, and
+//! keycode combos.
 
 use std::collections::BTreeMap;
 
 use crate::models::layout::combo::MAX_COMBOS;
-use crate::models::{ComboDefinition, Layout, Position};
+use crate::models::{ComboDefinition, KeycodeCombo, Layout, Position};
 
 /// Parses the `**Combos**` / `**Combos Enabled**` master switch.
 pub(super) fn try_parse_combos_enabled(line: &str, layout: &mut Layout) -> bool {
@@ -76,3 +77,45 @@ pub(super) fn combo_buffer_into_vec(
 ) -> Vec<ComboDefinition> {
     buffer.into_values().collect()
 }
+
+/// Parses a keycode combo line and appends it to the layout.
+///
+/// Format: `**Keycode Combo N**: (row,col)+(row,col)[+...] → KEYCODE`
+/// Example: `**Keycode Combo 1**: (0,1)+(0,2) → KC_ESC`
+///
+/// Combos are kept in file order; `N` is informational. Lines with malformed
+/// positions or that fail validation are consumed and dropped.
+pub(super) fn try_parse_keycode_combo(line: &str, layout: &mut Layout) -> bool {
+    let Some(rest) = line.strip_prefix("**Keycode Combo ") else {
+        return false;
+    };
+    let Some((_, definition)) = rest.split_once("**:") else {
+        return false;
+    };
+    let Some((keys, keycode)) = definition.split_once('→') else {
+        return true;
+    };
+
+    let keys: Option<Vec<Position>> = keys
+        .split('+')
+        .map(|key| {
+            let (row, col) = key
+                .trim()
+                .strip_prefix('(')?
+                .strip_suffix(')')?
+                .split_once(',')?;
+            Some(Position::new(
+                row.trim().parse().ok()?,
+                col.trim().parse().ok()?,
+            ))
+        })
+        .collect();
+
+    if let Some(keys) = keys {
+        // Invalid, duplicate, or over-limit combos are dropped
+        let _ = layout
+            .combo_settings
+            .add_keycode_combo(KeycodeCombo::new(keys, keycode.trim()));
+    }
+    true
+}
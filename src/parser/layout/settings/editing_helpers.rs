@@ -0,0 +1,27 @@
+//! Editing helper settings: enabled helpers and the Num Word layer.
+
+use crate::models::{EditingHelper, Layout};
+
+/// Parses Editing Helpers, e.g. `**Editing Helpers**: Select Word, Num Word`.
+///
+/// Unknown helper names are skipped.
+pub(super) fn try_parse_editing_helpers(line: &str, layout: &mut Layout) -> bool {
+    let Some(value) = line.strip_prefix("**Editing Helpers**:") else {
+        return false;
+    };
+    for helper in value.split(',').filter_map(EditingHelper::from_name) {
+        layout.editing_helpers.set_enabled(helper, true);
+    }
+    true
+}
+
+/// Parses Num Word Layer (layer index).
+pub(super) fn try_parse_num_word_layer(line: &str, layout: &mut Layout) -> bool {
+    let Some(value) = line.strip_prefix("**Num Word Layer**:") else {
+        return false;
+    };
+    if let Ok(layer) = value.trim().parse::<u8>() {
+        layout.editing_helpers.num_word_layer = layer;
+    }
+    true
+}
@@ -0,0 +1,21 @@
+//! Firmware feature overrides (`rules.mk` flags).
+
+use crate::models::{FirmwareFeature, Layout};
+
+/// Parses a Firmware Feature override ("RGB Matrix = Off").
+pub(super) fn try_parse_firmware_feature(line: &str, layout: &mut Layout) -> bool {
+    let Some(value) = line.strip_prefix("**Firmware Feature**:") else {
+        return false;
+    };
+    if let Some((name, state)) = value.split_once('=') {
+        let enabled = match state.trim().to_lowercase().as_str() {
+            "on" | "true" | "yes" | "enabled" => Some(true),
+            "off" | "false" | "no" | "disabled" => Some(false),
+            _ => None,
+        };
+        if let (Some(feature), Some(enabled)) = (FirmwareFeature::from_name(name), enabled) {
+            layout.firmware_features.set(feature, Some(enabled));
+        }
+    }
+    true
+}
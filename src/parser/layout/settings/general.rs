@@ -1,4 +1,5 @@
-//! General settings: uncolored key behavior, RGB master switch, RGB timeout.
+//! General settings: uncolored key behavior, RGB master switch, RGB timeout,
+//! usage counting.
 
 use crate::models::Layout;
 
@@ -87,3 +88,15 @@ pub(super) fn try_parse_rgb_timeout(line: &str, layout: &mut Layout) -> bool {
     }
     true
 }
+
+/// Parses Usage Counting (on/off).
+pub(super) fn try_parse_usage_counting(line: &str, layout: &mut Layout) -> bool {
+    let Some(value) = line.strip_prefix("**Usage Counting**:") else {
+        return false;
+    };
+    let value = value.trim().to_lowercase();
+
+    layout.usage_counting =
+        value == "on" || value == "true" || value == "yes" || value == "enabled";
+    true
+}
@@ -3,7 +3,8 @@
 //! The `## Settings` section in a layout markdown file contains a series of
 //! `**Setting Name**: value` lines. Each setting is parsed by a dedicated
 //! helper in a sibling file (one per logical group: general, rgb, tap_hold,
-//! idle, ripple, palette_fx, combos).
+//! idle, ripple, palette_fx, rgb_conditions, combos, firmware_features,
+//! overrides, unicode, editing_helpers).
 //!
 //! Each helper returns `true` if it recognized and consumed the line; the
 //! dispatcher advances `line_num` once per iteration regardless of which (if
@@ -13,12 +14,17 @@
 //! terminates on the next `##` or `---` boundary).
 
 mod combos;
+mod editing_helpers;
+mod firmware_features;
 mod general;
 mod idle;
+mod overrides;
 mod palette_fx;
 mod rgb;
+mod rgb_conditions;
 mod ripple;
 mod tap_hold;
+mod unicode;
 
 use std::collections::BTreeMap;
 
@@ -75,6 +81,8 @@ pub(super) fn parse_settings(
             // handled
         } else if tap_hold::try_parse_chordal_hold(line, layout) {
             // handled
+        } else if tap_hold::try_parse_key_tapping_term(line, layout) {
+            // handled
         } else if general::try_parse_uncolored_key_behavior(line, layout) {
             // handled
         } else if general::try_parse_rgb_enabled(line, layout) {
@@ -139,10 +147,38 @@ pub(super) fn parse_settings(
             // handled
         } else if palette_fx::try_parse_palette_fx_all_palettes(line, layout) {
             // handled
+        } else if rgb_conditions::try_parse_rgb_dim_after(line, layout) {
+            // handled
+        } else if rgb_conditions::try_parse_rgb_dim_brightness(line, layout) {
+            // handled
+        } else if rgb_conditions::try_parse_rgb_wpm_threshold(line, layout) {
+            // handled
+        } else if rgb_conditions::try_parse_rgb_wpm_effect(line, layout) {
+            // handled
+        } else if rgb_conditions::try_parse_rgb_layer_effect(line, layout) {
+            // handled
+        } else if rgb_conditions::try_parse_rgb_off_hours(line, layout) {
+            // handled
         } else if combos::try_parse_combos_enabled(line, layout) {
             // handled
         } else if combos::try_parse_combo_definition(line, &mut combo_buffer) {
             // handled
+        } else if combos::try_parse_keycode_combo(line, layout) {
+            // handled
+        } else if firmware_features::try_parse_firmware_feature(line, layout) {
+            // handled
+        } else if overrides::try_parse_key_override(line, layout) {
+            // handled
+        } else if overrides::try_parse_alt_repeat_key(line, layout) {
+            // handled
+        } else if unicode::try_parse_unicode_input_modes(line, layout) {
+            // handled
+        } else if general::try_parse_usage_counting(line, layout) {
+            // handled
+        } else if editing_helpers::try_parse_editing_helpers(line, layout) {
+            // handled
+        } else if editing_helpers::try_parse_num_word_layer(line, layout) {
+            // handled
         }
 
         line_num += 1;
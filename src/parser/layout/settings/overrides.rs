@@ -0,0 +1,33 @@
+//! Key overrides and alt repeat overrides, one line per entry.
+
+use crate::models::{AltRepeatKey, KeyOverride, Layout};
+
+/// Parses a key override: `**Key Override**: Shift+KC_BSPC = KC_DEL`.
+///
+/// Invalid overrides are dropped.
+pub(super) fn try_parse_key_override(line: &str, layout: &mut Layout) -> bool {
+    let Some(value) = line.strip_prefix("**Key Override**:") else {
+        return false;
+    };
+    if let Some(key_override) = KeyOverride::parse(value.trim()) {
+        if key_override.validate().is_ok() {
+            layout.key_overrides.push(key_override);
+        }
+    }
+    true
+}
+
+/// Parses an alt repeat override: `**Alt Repeat**: KC_LEFT = KC_RGHT`.
+///
+/// Invalid overrides are dropped.
+pub(super) fn try_parse_alt_repeat_key(line: &str, layout: &mut Layout) -> bool {
+    let Some(value) = line.strip_prefix("**Alt Repeat**:") else {
+        return false;
+    };
+    if let Some(alt_repeat) = AltRepeatKey::parse(value.trim()) {
+        if alt_repeat.validate().is_ok() {
+            layout.alt_repeat_keys.push(alt_repeat);
+        }
+    }
+    true
+}
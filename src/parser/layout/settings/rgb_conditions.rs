@@ -0,0 +1,101 @@
+//! Conditional RGB settings: dim on inactivity, WPM effect, per-layer
+//! effects, off hours.
+
+use crate::models::{Layout, RgbMatrixEffect, RgbOffHours};
+
+/// Parses RGB Dim After ("10 min", "10", or "off"/"0").
+pub(super) fn try_parse_rgb_dim_after(line: &str, layout: &mut Layout) -> bool {
+    if !line.starts_with("**RGB Dim After**:") {
+        return false;
+    }
+    let value = line
+        .strip_prefix("**RGB Dim After**:")
+        .unwrap()
+        .trim()
+        .to_lowercase();
+
+    if value == "off" || value == "disabled" {
+        layout.rgb_conditions.dim_after_minutes = 0;
+    } else {
+        let minutes = value
+            .strip_suffix("min")
+            .unwrap_or(&value)
+            .trim()
+            .parse::<u16>();
+        if let Ok(minutes) = minutes {
+            layout.rgb_conditions.dim_after_minutes = minutes;
+        }
+    }
+    true
+}
+
+/// Parses RGB Dim Brightness (percent of the current brightness).
+pub(super) fn try_parse_rgb_dim_brightness(line: &str, layout: &mut Layout) -> bool {
+    if !line.starts_with("**RGB Dim Brightness**:") {
+        return false;
+    }
+    let value = line
+        .strip_prefix("**RGB Dim Brightness**:")
+        .unwrap()
+        .trim()
+        .trim_end_matches('%');
+    if let Ok(percent) = value.parse::<u8>() {
+        layout.rgb_conditions.dim_brightness_pct = percent.min(100);
+    }
+    true
+}
+
+/// Parses RGB WPM Threshold (0 = disabled).
+pub(super) fn try_parse_rgb_wpm_threshold(line: &str, layout: &mut Layout) -> bool {
+    if !line.starts_with("**RGB WPM Threshold**:") {
+        return false;
+    }
+    let value = line.strip_prefix("**RGB WPM Threshold**:").unwrap().trim();
+    if let Ok(wpm) = value.parse::<u8>() {
+        layout.rgb_conditions.wpm_threshold = wpm;
+    }
+    true
+}
+
+/// Parses RGB WPM Effect (RgbMatrixEffect name).
+pub(super) fn try_parse_rgb_wpm_effect(line: &str, layout: &mut Layout) -> bool {
+    if !line.starts_with("**RGB WPM Effect**:") {
+        return false;
+    }
+    let value = line.strip_prefix("**RGB WPM Effect**:").unwrap().trim();
+    if let Some(effect) = RgbMatrixEffect::from_name(value) {
+        layout.rgb_conditions.wpm_effect = effect;
+    }
+    true
+}
+
+/// Parses one RGB Layer Effect line ("2 = Breathing"). May appear repeatedly.
+pub(super) fn try_parse_rgb_layer_effect(line: &str, layout: &mut Layout) -> bool {
+    if !line.starts_with("**RGB Layer Effect**:") {
+        return false;
+    }
+    let value = line.strip_prefix("**RGB Layer Effect**:").unwrap().trim();
+    if let Some((layer, effect)) = value.split_once('=') {
+        if let (Ok(layer), Some(effect)) = (
+            layer.trim().parse::<u8>(),
+            RgbMatrixEffect::from_name(effect.trim()),
+        ) {
+            layout.rgb_conditions.set_layer_effect(layer, Some(effect));
+        }
+    }
+    true
+}
+
+/// Parses RGB Off Hours ("22:00-07:00" or "off").
+pub(super) fn try_parse_rgb_off_hours(line: &str, layout: &mut Layout) -> bool {
+    if !line.starts_with("**RGB Off Hours**:") {
+        return false;
+    }
+    let value = line.strip_prefix("**RGB Off Hours**:").unwrap().trim();
+    if value.eq_ignore_ascii_case("off") {
+        layout.rgb_conditions.off_hours = None;
+    } else if let Some(range) = RgbOffHours::parse(value) {
+        layout.rgb_conditions.off_hours = Some(range);
+    }
+    true
+}
@@ -1,7 +1,8 @@
 //! Tap-Hold settings: preset, tapping term, quick tap term, hold mode,
-//! retro tapping, tapping toggle, flow tap term, chordal hold.
+//! retro tapping, tapping toggle, flow tap term, chordal hold, per-key
+//! tapping terms.
 
-use crate::models::{HoldDecisionMode, Layout, TapHoldPreset};
+use crate::models::{HoldDecisionMode, Layout, Position, TapHoldPreset};
 
 /// Parses Tap-Hold Preset (also returns the explicit preset so the dispatcher
 /// can re-apply it after individual settings may have reset it).
@@ -157,3 +158,33 @@ pub(super) fn try_parse_chordal_hold(line: &str, layout: &mut Layout) -> bool {
         value == "on" || value == "true" || value == "yes" || value == "enabled";
     true
 }
+
+/// Parses a per-key tapping term: `**Key Tapping Term**: (row,col) = 250ms`.
+pub(super) fn try_parse_key_tapping_term(line: &str, layout: &mut Layout) -> bool {
+    let Some(value) = line.strip_prefix("**Key Tapping Term**:") else {
+        return false;
+    };
+    let Some((position, term)) = value.split_once('=') else {
+        return true;
+    };
+
+    let position = position
+        .trim()
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .and_then(|rest| rest.split_once(','))
+        .and_then(|(row, col)| {
+            Some(Position::new(
+                row.trim().parse().ok()?,
+                col.trim().parse().ok()?,
+            ))
+        });
+    let term = term.trim().trim_end_matches("ms").trim().parse::<u16>();
+
+    if let (Some(position), Ok(term)) = (position, term) {
+        layout
+            .tap_hold_settings
+            .set_key_tapping_term(position, Some(term));
+    }
+    true
+}
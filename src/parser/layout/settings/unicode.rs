@@ -0,0 +1,19 @@
+//! Unicode settings: input modes.
+
+use crate::models::{Layout, UnicodeInputMode};
+
+/// Parses Unicode Input Modes, e.g. `**Unicode Input Modes**: macOS, Linux`.
+///
+/// Unknown mode names and duplicates are skipped.
+pub(super) fn try_parse_unicode_input_modes(line: &str, layout: &mut Layout) -> bool {
+    let Some(value) = line.strip_prefix("**Unicode Input Modes**:") else {
+        return false;
+    };
+    layout.unicode_input_modes.clear();
+    for mode in value.split(',').filter_map(UnicodeInputMode::from_name) {
+        if !layout.unicode_input_modes.contains(&mode) {
+            layout.unicode_input_modes.push(mode);
+        }
+    }
+    true
+}
@@ -176,8 +176,6 @@ version: "1.0"
     );
 }
 
-use super::*;
-
 #[test]
 fn test_parse_descriptions_from_real_format() {
     let content = r"---
@@ -242,3 +240,101 @@ version: '1.0'
         "Key 0:0:1 description mismatch"
     );
 }
+
+#[test]
+fn test_macros_markdown_round_trip() {
+    use crate::models::{KeyDefinition, Layer, MacroDefinition};
+    use crate::parser::template_gen;
+
+    let mut layout = Layout::new("Macro Layout").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(128, 128, 128)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "MACRO(quote)"));
+    layout.add_layer(layer).unwrap();
+    layout
+        .add_macro(MacroDefinition::from_sequence("quote", "say \"hi\"{KC_ENT}{{x}").unwrap())
+        .unwrap();
+
+    let markdown = template_gen::generate_markdown(&layout).unwrap();
+    assert!(markdown.contains("## Macros\n\n- **quote**: \"say \\\"hi\\\"{KC_ENT}{{x}\"\n"));
+
+    let parsed = parse_markdown_layout_str(&markdown).unwrap();
+    assert_eq!(parsed.macros, layout.macros);
+}
+
+#[test]
+fn test_key_features_markdown_round_trip() {
+    use crate::models::{
+        AltRepeatKey, CustomKeycode, KeyDefinition, KeyOverride, Layer, UnicodeGlyph,
+        UnicodeInputMode,
+    };
+    use crate::parser::template_gen;
+
+    let mut layout = Layout::new("Key Features").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(128, 128, 128)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "CK_EMAIL"));
+    layout.add_layer(layer).unwrap();
+    layout
+        .tap_hold_settings
+        .set_key_tapping_term(Position::new(1, 2), Some(250));
+    layout
+        .key_overrides
+        .push(KeyOverride::parse("Shift+KC_BSPC = KC_DEL").unwrap());
+    layout
+        .alt_repeat_keys
+        .push(AltRepeatKey::new("KC_LEFT", "KC_RGHT"));
+    layout.unicode_map.push(UnicodeGlyph::new("snek", 0x1F40D));
+    layout.unicode_input_modes = vec![UnicodeInputMode::Macos, UnicodeInputMode::Linux];
+    layout
+        .add_custom_keycode(CustomKeycode::new(
+            "CK_EMAIL",
+            "if (record->event.pressed) {\n    SEND_STRING(\"me@example.com\");\n}",
+            "Types my email",
+        ))
+        .unwrap();
+    layout.usage_counting = true;
+
+    let markdown = template_gen::generate_markdown(&layout).unwrap();
+    assert!(markdown.contains("**Key Tapping Term**: (1,2) = 250ms\n"));
+    assert!(markdown.contains("## Unicode Map\n\n- **snek**: U+1F40D\n"));
+    assert!(markdown.contains("  - Description: Types my email\n"));
+
+    let parsed = parse_markdown_layout_str(&markdown).unwrap();
+    assert_eq!(
+        parsed.tap_hold_settings.per_key_tapping_terms,
+        layout.tap_hold_settings.per_key_tapping_terms
+    );
+    assert_eq!(parsed.key_overrides, layout.key_overrides);
+    assert_eq!(parsed.alt_repeat_keys, layout.alt_repeat_keys);
+    assert_eq!(parsed.unicode_map, layout.unicode_map);
+    assert_eq!(parsed.unicode_input_modes, layout.unicode_input_modes);
+    assert_eq!(parsed.custom_keycodes, layout.custom_keycodes);
+    assert!(parsed.usage_counting);
+}
+
+#[test]
+fn test_editing_helpers_markdown_round_trip() {
+    use crate::models::{EditingHelper, KeyDefinition, Layer};
+    use crate::parser::template_gen;
+
+    let mut layout = Layout::new("Helpers").unwrap();
+    for number in 0..3 {
+        let mut layer = Layer::new(number, "Layer", RgbColor::new(128, 128, 128)).unwrap();
+        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+        layout.add_layer(layer).unwrap();
+    }
+    layout
+        .editing_helpers
+        .set_enabled(EditingHelper::SelectWord, true);
+    layout
+        .editing_helpers
+        .set_enabled(EditingHelper::NumWord, true);
+    layout.editing_helpers.num_word_layer = 2;
+
+    let markdown = template_gen::generate_markdown(&layout).unwrap();
+    assert!(
+        markdown.contains("**Editing Helpers**: Select Word, Num Word\n**Num Word Layer**: 2\n")
+    );
+
+    let parsed = parse_markdown_layout_str(&markdown).unwrap();
+    assert_eq!(parsed.editing_helpers, layout.editing_helpers);
+}
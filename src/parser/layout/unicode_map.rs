@@ -0,0 +1,53 @@
+//! Unicode map phase: parse `- **name**: U+XXXX` lines.
+
+use crate::models::layout::unicode::parse_codepoint;
+use crate::models::UnicodeGlyph;
+use anyhow::{Context, Result};
+
+/// Parses the Unicode map section.
+///
+/// Format:
+/// ```markdown
+/// ## Unicode Map
+///
+/// - **snek**: U+1F40D
+/// - **ae_lower**: U+00E6
+/// ```
+pub(super) fn parse_unicode_map(
+    lines: &[&str],
+    start_line: usize,
+    layout: &mut crate::models::Layout,
+) -> Result<usize> {
+    let mut line_num = start_line + 1; // Skip "## Unicode Map" header
+
+    while line_num < lines.len() {
+        let line = lines[line_num].trim();
+
+        // Skip empty lines
+        if line.is_empty() {
+            line_num += 1;
+            continue;
+        }
+
+        // Stop at next section
+        if line.starts_with("##") || line.starts_with("---") {
+            break;
+        }
+
+        // Parse glyph line: - **name**: U+XXXX
+        if let Some((name, codepoint)) = line
+            .strip_prefix("- **")
+            .and_then(|rest| rest.split_once("**:"))
+        {
+            let codepoint = parse_codepoint(codepoint)
+                .with_context(|| format!("Unicode glyph '{name}' has an invalid code point"))?;
+            let glyph = UnicodeGlyph::new(name, codepoint);
+            glyph.validate()?;
+            layout.unicode_map.push(glyph);
+        }
+
+        line_num += 1;
+    }
+
+    Ok(line_num)
+}
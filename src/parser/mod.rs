@@ -2,11 +2,14 @@
 //!
 //! This module handles reading and writing keyboard layouts in JSON format
 //! (primary, since 0.22.0) and legacy Markdown format (for migration).
-//! Also parses QMK info.json files and generates firmware configuration.
+//! Also parses QMK info.json files, imports handwritten `keymap.c` files and
+//! QMK `keymap.json` files, and generates firmware configuration.
 
 pub mod json_serde;
 pub mod keyboard_json;
+pub mod keymap_c;
 pub mod layout;
+pub mod qmk_json;
 pub mod template_gen;
 
 // Re-export commonly used functions
@@ -0,0 +1,121 @@
+//! Importer for QMK keymap JSON files (`keymap.json`).
+//!
+//! Reads the format written by QMK Configurator and `qmk c2json`, and
+//! compiled with `qmk compile keymap.json`. Layers list their keycodes in
+//! LAYOUT macro order with numeric layer arguments, so the result is a
+//! [`ParsedKeymap`] placed on a keyboard geometry like an imported
+//! `keymap.c`. Tap dances live in C code that keymap JSON can't hold, so
+//! `TD()` keycodes are replaced with `KC_NO` and reported as warnings.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::keymap_c::{normalize_keycode, ParsedKeymap, ParsedLayer};
+use crate::models::{KeyboardGeometry, Layout};
+
+/// Raw `keymap.json` document; unknown fields are ignored.
+#[derive(Debug, Deserialize)]
+struct KeymapJson {
+    #[serde(default)]
+    keyboard: Option<String>,
+    #[serde(default)]
+    keymap: Option<String>,
+    layout: String,
+    layers: Vec<Vec<String>>,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    notes: Option<String>,
+}
+
+/// A parsed `keymap.json`, before it is placed on a keyboard.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedQmkJson {
+    /// Keyboard the keymap is for (e.g. "crkbd/rev1"), if given
+    pub keyboard: Option<String>,
+    /// QMK keymap name, if given
+    pub keymap_name: Option<String>,
+    /// Author, if given
+    pub author: Option<String>,
+    /// Free-form notes, if given
+    pub notes: Option<String>,
+    /// Layers, LAYOUT macro and warnings
+    pub keymap: ParsedKeymap,
+}
+
+impl ParsedQmkJson {
+    /// Builds a layout named `name` by placing each layer's keys on
+    /// `geometry`, keeping the keymap name, author and notes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a layer's key count does not match the geometry.
+    pub fn to_layout(&self, name: &str, geometry: &KeyboardGeometry) -> Result<Layout> {
+        let mut layout = self.keymap.to_layout(name, geometry)?;
+        layout.metadata.keymap_name.clone_from(&self.keymap_name);
+        if let Some(author) = &self.author {
+            layout.metadata.author.clone_from(author);
+        }
+        if let Some(notes) = &self.notes {
+            layout.metadata.description.clone_from(notes);
+        }
+        Ok(layout)
+    }
+}
+
+/// Parses a QMK `keymap.json` source.
+///
+/// # Errors
+///
+/// Returns an error if the source isn't a keymap JSON document or has no
+/// layers.
+pub fn parse_qmk_json(source: &str) -> Result<ParsedQmkJson> {
+    let doc: KeymapJson = serde_json::from_str(source).context("Not a QMK keymap.json document")?;
+    if doc.layers.is_empty() {
+        anyhow::bail!("The keymap.json has no layers");
+    }
+
+    let mut keymap = ParsedKeymap {
+        layout_macro: doc.layout,
+        ..ParsedKeymap::default()
+    };
+    for (index, keycodes) in doc.layers.iter().enumerate() {
+        let keycodes = keycodes
+            .iter()
+            .map(|token| convert_keycode(token, &mut keymap.warnings))
+            .collect();
+        keymap.layers.push(ParsedLayer {
+            name: format!("Layer {index}"),
+            keycodes,
+        });
+    }
+
+    Ok(ParsedQmkJson {
+        keyboard: doc.keyboard.filter(|s| !s.is_empty()),
+        keymap_name: doc.keymap.filter(|s| !s.is_empty()),
+        author: doc.author.filter(|s| !s.is_empty()),
+        notes: doc.notes.filter(|s| !s.is_empty()),
+        keymap,
+    })
+}
+
+/// Converts one keymap JSON keycode to the layout's keycode form.
+fn convert_keycode(token: &str, warnings: &mut Vec<String>) -> String {
+    let keycode = normalize_keycode(token);
+    // Configurator wraps keycodes it doesn't know in ANY()
+    let keycode = keycode
+        .strip_prefix("ANY(")
+        .and_then(|rest| rest.strip_suffix(')'))
+        .map_or(keycode.clone(), str::to_string);
+
+    if keycode.starts_with("TD(") {
+        warnings.push(format!(
+            "Replaced {keycode} with KC_NO: keymap.json has no tap dance definitions"
+        ));
+        return "KC_NO".to_string();
+    }
+    keycode
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,79 @@
+//! Tests for qmk_json.
+
+use super::*;
+use crate::models::{KeyGeometry, Position};
+
+const KEYMAP_JSON: &str = r#"{
+    "version": 1,
+    "notes": "Shared on Discord",
+    "keyboard": "test_keyboard",
+    "keymap": "mine",
+    "layout": "LAYOUT_test",
+    "layers": [
+        ["KC_A", "LT(1,KC_SPC)", "ANY(QK_BOOT)", "MO(1)", "TD(0)", "LCTL_T(KC_B)"],
+        ["_______", "KC_TRNS", "XXXXXXX", "KC_1", "KC_2", "TO(0)"]
+    ],
+    "author": "someone",
+    "documentation": "ignored"
+}"#;
+
+/// 2x3 grid in row-major LAYOUT order.
+fn create_test_geometry() -> KeyboardGeometry {
+    let mut geometry = KeyboardGeometry::new("test_keyboard", "LAYOUT_test", 2, 3);
+    for idx in 0..6u8 {
+        let (row, col) = (idx / 3, idx % 3);
+        geometry.keys.push(KeyGeometry::new(
+            (row, col),
+            idx,
+            f32::from(col),
+            f32::from(row),
+        ));
+    }
+    geometry
+}
+
+#[test]
+fn test_parse_qmk_json_fields_and_keycodes() {
+    let parsed = parse_qmk_json(KEYMAP_JSON).unwrap();
+
+    assert_eq!(parsed.keyboard.as_deref(), Some("test_keyboard"));
+    assert_eq!(parsed.keymap_name.as_deref(), Some("mine"));
+    assert_eq!(parsed.keymap.layout_macro, "LAYOUT_test");
+    assert_eq!(parsed.keymap.layers.len(), 2);
+    assert_eq!(parsed.keymap.layers[1].name, "Layer 1");
+
+    let base = &parsed.keymap.layers[0].keycodes;
+    assert_eq!(base[1], "LT(1, KC_SPC)");
+    assert_eq!(base[2], "QK_BOOT");
+    assert_eq!(base[4], "KC_NO");
+    assert_eq!(
+        parsed.keymap.layers[1].keycodes[..3],
+        ["KC_TRNS", "KC_TRNS", "KC_NO"]
+    );
+    assert_eq!(parsed.keymap.warnings.len(), 1);
+    assert!(parsed.keymap.warnings[0].contains("TD(0)"));
+}
+
+#[test]
+fn test_qmk_json_to_layout() {
+    let parsed = parse_qmk_json(KEYMAP_JSON).unwrap();
+    let layout = parsed.to_layout("Shared", &create_test_geometry()).unwrap();
+
+    assert_eq!(layout.metadata.keymap_name.as_deref(), Some("mine"));
+    assert_eq!(layout.metadata.author, "someone");
+    assert_eq!(layout.metadata.description, "Shared on Discord");
+    let layer_one = layout.layers[1].id.clone();
+    let key = layout.layers[0]
+        .keys
+        .iter()
+        .find(|k| k.position == Position::new(1, 0))
+        .unwrap();
+    assert_eq!(key.keycode, format!("MO(@{layer_one})"));
+}
+
+#[test]
+fn test_parse_qmk_json_rejects_other_documents() {
+    assert!(parse_qmk_json("{}").is_err());
+    assert!(parse_qmk_json(r#"{"layout": "LAYOUT", "layers": []}"#).is_err());
+    assert!(parse_qmk_json("const uint16_t keymaps[] = {};").is_err());
+}
@@ -45,46 +45,33 @@ pub fn generate_markdown(layout: &Layout) -> Result<String> {
         output.push('\n');
     }
 
-    // Generate key descriptions section if any exist
+    // Trailing sections; the first is separated from the layers by `---`
+    let mut sections = Vec::new();
     if let Some(descriptions_section) = generate_key_descriptions(layout) {
-        output.push_str("---\n\n");
-        output.push_str(&descriptions_section);
+        sections.push(descriptions_section);
     }
-
-    // Generate categories section if any exist
     if !layout.categories.is_empty() {
-        // Add separator only if descriptions weren't written
-        if has_key_descriptions(layout) {
-            output.push('\n');
-        } else {
-            output.push_str("---\n\n");
-        }
-        output.push_str(&generate_categories(layout));
+        sections.push(generate_categories(layout));
     }
-
-    // Generate settings section if any non-default settings exist
     if let Some(settings_section) = generate_settings(layout) {
-        // Add separator if neither descriptions nor categories were written
-        if !has_key_descriptions(layout) && layout.categories.is_empty() {
-            output.push_str("---\n\n");
-        } else {
-            output.push('\n');
-        }
-        output.push_str(&settings_section);
+        sections.push(settings_section);
     }
-
-    // Generate tap dances section if any exist
     if !layout.tap_dances.is_empty() {
-        // Add separator if nothing else was written after layers
-        if !has_key_descriptions(layout)
-            && layout.categories.is_empty()
-            && generate_settings(layout).is_none()
-        {
-            output.push_str("---\n\n");
-        } else {
-            output.push('\n');
-        }
-        output.push_str(&generate_tap_dances(layout));
+        sections.push(generate_tap_dances(layout));
+    }
+    if !layout.macros.is_empty() {
+        sections.push(generate_macros(layout));
+    }
+    if !layout.unicode_map.is_empty() {
+        sections.push(generate_unicode_map(layout));
+    }
+    if !layout.custom_keycodes.is_empty() {
+        sections.push(generate_custom_keycodes(layout));
+    }
+
+    for (idx, section) in sections.iter().enumerate() {
+        output.push_str(if idx == 0 { "---\n\n" } else { "\n" });
+        output.push_str(section);
     }
 
     Ok(output)
@@ -121,6 +108,19 @@ fn generate_layer(layer: &crate::models::Layer) -> Result<String> {
         output.push_str("**Layer Colors**: false\n");
     }
 
+    // Optional fixed underglow color
+    if let Some(color) = layer.underglow_color {
+        output.push_str(&format!("**Underglow**: {}\n", color.to_hex()));
+    }
+
+    // Encoder bindings: **Encoder N**: CCW / CW
+    for (encoder, binding) in layer.encoder_map.iter() {
+        output.push_str(&format!(
+            "**Encoder {encoder}**: {} / {}\n",
+            binding.ccw, binding.cw
+        ));
+    }
+
     output.push('\n');
 
     // Generate table
@@ -236,14 +236,6 @@ fn generate_categories(layout: &Layout) -> String {
     output
 }
 
-/// Checks if any keys in the layout have descriptions.
-fn has_key_descriptions(layout: &Layout) -> bool {
-    layout
-        .layers
-        .iter()
-        .any(|layer| layer.keys.iter().any(|key| key.description.is_some()))
-}
-
 /// Generates the key descriptions section.
 /// Format: `- layer:row:col: description text`
 fn generate_key_descriptions(layout: &Layout) -> Option<String> {
@@ -296,6 +288,13 @@ fn generate_settings(layout: &Layout) -> Option<String> {
     let has_ripple_settings = layout.rgb_overlay_ripple.has_custom_settings();
     let has_tap_hold_settings = layout.tap_hold_settings != default_tap_hold;
     let has_combo_settings = layout.combo_settings.has_custom_settings();
+    let has_rgb_condition_settings = layout.rgb_conditions.has_custom_settings();
+    let has_firmware_features = layout.firmware_features.has_custom_settings();
+    let has_key_settings = !layout.key_overrides.is_empty()
+        || !layout.alt_repeat_keys.is_empty()
+        || !layout.unicode_input_modes.is_empty()
+        || layout.usage_counting
+        || layout.editing_helpers.any_enabled();
 
     if !has_rgb_settings
         && !has_uncolored_setting
@@ -303,6 +302,9 @@ fn generate_settings(layout: &Layout) -> Option<String> {
         && !has_ripple_settings
         && !has_tap_hold_settings
         && !has_combo_settings
+        && !has_rgb_condition_settings
+        && !has_firmware_features
+        && !has_key_settings
     {
         return None;
     }
@@ -524,6 +526,10 @@ fn generate_settings(layout: &Layout) -> Option<String> {
         }
     }
 
+    if has_rgb_condition_settings {
+        generate_rgb_condition_settings(&layout.rgb_conditions, &mut output);
+    }
+
     // Write tap-hold settings if any are non-default
     if has_tap_hold_settings {
         let ths = &layout.tap_hold_settings;
@@ -584,6 +590,14 @@ fn generate_settings(layout: &Layout) -> Option<String> {
             let value = if ths.chordal_hold { "On" } else { "Off" };
             output.push_str(&format!("**Chordal Hold**: {value}\n"));
         }
+
+        // One line per key: **Key Tapping Term**: (row,col) = 250ms
+        for entry in &ths.per_key_tapping_terms {
+            output.push_str(&format!(
+                "**Key Tapping Term**: ({},{}) = {}ms\n",
+                entry.position.row, entry.position.col, entry.tapping_term
+            ));
+        }
     }
 
     // Write combo settings if any are non-default
@@ -613,11 +627,116 @@ fn generate_settings(layout: &Layout) -> Option<String> {
                 combo.hold_duration_ms
             ));
         }
+
+        // Format: **Keycode Combo N**: (row,col)+(row,col)[+...] → KEYCODE
+        for (idx, combo) in cs.keycode_combos.iter().enumerate() {
+            let keys: Vec<String> = combo
+                .keys
+                .iter()
+                .map(|key| format!("({},{})", key.row, key.col))
+                .collect();
+            output.push_str(&format!(
+                "**Keycode Combo {}**: {} → {}\n",
+                idx + 1,
+                keys.join("+"),
+                combo.keycode
+            ));
+        }
+    }
+
+    // One line per overridden firmware feature: **Firmware Feature**: Name = On
+    if has_firmware_features {
+        for feature in crate::models::FirmwareFeature::ALL {
+            if let Some(enabled) = layout.firmware_features.get(feature) {
+                let value = if enabled { "On" } else { "Off" };
+                output.push_str(&format!(
+                    "**Firmware Feature**: {} = {value}\n",
+                    feature.display_name()
+                ));
+            }
+        }
+    }
+
+    // One line per override: **Key Override**: Shift+KC_BSPC = KC_DEL
+    for key_override in &layout.key_overrides {
+        output.push_str(&format!("**Key Override**: {key_override}\n"));
+    }
+
+    // One line per override: **Alt Repeat**: KC_LEFT = KC_RGHT
+    for alt_repeat in &layout.alt_repeat_keys {
+        output.push_str(&format!("**Alt Repeat**: {alt_repeat}\n"));
+    }
+
+    if !layout.unicode_input_modes.is_empty() {
+        output.push_str(&format!(
+            "**Unicode Input Modes**: {}\n",
+            layout.unicode_input_modes_display()
+        ));
+    }
+
+    if layout.usage_counting {
+        output.push_str("**Usage Counting**: On\n");
+    }
+
+    let helpers = layout.editing_helpers.enabled();
+    if !helpers.is_empty() {
+        let names: Vec<_> = helpers.iter().map(|helper| helper.display_name()).collect();
+        output.push_str(&format!("**Editing Helpers**: {}\n", names.join(", ")));
+        if layout.editing_helpers.num_word {
+            output.push_str(&format!(
+                "**Num Word Layer**: {}\n",
+                layout.editing_helpers.num_word_layer
+            ));
+        }
     }
 
     Some(output)
 }
 
+/// Writes the non-default conditional RGB settings.
+fn generate_rgb_condition_settings(
+    settings: &crate::models::RgbConditionalSettings,
+    output: &mut String,
+) {
+    let defaults = crate::models::RgbConditionalSettings::default();
+
+    if settings.dim_after_minutes != defaults.dim_after_minutes {
+        output.push_str(&format!(
+            "**RGB Dim After**: {} min\n",
+            settings.dim_after_minutes
+        ));
+    }
+    if settings.dim_brightness_pct != defaults.dim_brightness_pct {
+        output.push_str(&format!(
+            "**RGB Dim Brightness**: {}%\n",
+            settings.dim_brightness_pct
+        ));
+    }
+    if settings.wpm_threshold != defaults.wpm_threshold {
+        output.push_str(&format!(
+            "**RGB WPM Threshold**: {}\n",
+            settings.wpm_threshold
+        ));
+    }
+    if settings.wpm_effect != defaults.wpm_effect {
+        output.push_str(&format!(
+            "**RGB WPM Effect**: {}\n",
+            settings.wpm_effect.display_name()
+        ));
+    }
+    // One line per layer rule: **RGB Layer Effect**: N = Effect
+    for rule in &settings.layer_effects {
+        output.push_str(&format!(
+            "**RGB Layer Effect**: {} = {}\n",
+            rule.layer,
+            rule.effect.display_name()
+        ));
+    }
+    if let Some(range) = settings.off_hours {
+        output.push_str(&format!("**RGB Off Hours**: {range}\n"));
+    }
+}
+
 /// Generates the tap dances section.
 fn generate_tap_dances(layout: &Layout) -> String {
     let mut output = String::from("## Tap Dances\n\n");
@@ -636,6 +755,54 @@ fn generate_tap_dances(layout: &Layout) -> String {
     output
 }
 
+/// Generates the macros section.
+///
+/// Sequences are written as JSON strings so quotes and backslashes round-trip.
+fn generate_macros(layout: &Layout) -> String {
+    let mut output = String::from("## Macros\n\n");
+
+    for macro_def in &layout.macros {
+        let sequence = serde_json::to_string(&macro_def.sequence()).unwrap_or_default();
+        output.push_str(&format!("- **{}**: {sequence}\n", macro_def.name));
+    }
+
+    output
+}
+
+/// Generates the Unicode map section.
+fn generate_unicode_map(layout: &Layout) -> String {
+    use crate::models::layout::unicode::format_codepoint;
+
+    let mut output = String::from("## Unicode Map\n\n");
+
+    for glyph in &layout.unicode_map {
+        output.push_str(&format!(
+            "- **{}**: {}\n",
+            glyph.name,
+            format_codepoint(glyph.codepoint)
+        ));
+    }
+
+    output
+}
+
+/// Generates the custom keycodes section.
+///
+/// Snippets are written as JSON strings so newlines and quotes round-trip.
+fn generate_custom_keycodes(layout: &Layout) -> String {
+    let mut output = String::from("## Custom Keycodes\n\n");
+
+    for custom in &layout.custom_keycodes {
+        let behavior = serde_json::to_string(&custom.behavior).unwrap_or_default();
+        output.push_str(&format!("- **{}**: {behavior}\n", custom.name));
+        if !custom.description.is_empty() {
+            output.push_str(&format!("  - Description: {}\n", custom.description));
+        }
+    }
+
+    output
+}
+
 /// Performs an atomic file write using temp file + rename pattern.
 ///
 /// This ensures the target file is never left in a corrupted state:
@@ -4,9 +4,8 @@
 
 use super::*;
 
-use super::*;
 use crate::models::{
-    Category, ColorPalette, KeyDefinition, Layer, LayoutMetadata, Position, RgbColor,
+    Category, ColorPalette, EncoderMap, KeyDefinition, Layer, LayoutMetadata, Position, RgbColor,
 };
 use crate::parser::layout::parse_markdown_layout_str;
 use chrono::Utc;
@@ -25,6 +24,11 @@ fn create_test_layout() -> Layout {
         keyboard: None,
         keymap_name: None,
         output_format: None,
+        converter: None,
+        targets: Vec::new(),
+        placeholders: Vec::new(),
+        geometry: None,
+        qmk_tree: None,
     };
 
     let mut layer = Layer {
@@ -37,6 +41,8 @@ fn create_test_layout() -> Layout {
         category_id: None,
         keys: vec![],
         layer_colors_enabled: true,
+        underglow_color: None,
+        encoder_map: EncoderMap::default(),
     };
 
     // Add some keys
@@ -48,6 +54,7 @@ fn create_test_layout() -> Layout {
         category_id: None,
         combo_participant: false,
         description: None,
+        locked: false,
     });
 
     layer.keys.push(KeyDefinition {
@@ -58,6 +65,7 @@ fn create_test_layout() -> Layout {
         category_id: None,
         combo_participant: false,
         description: None,
+        locked: false,
     });
 
     let category = Category {
@@ -79,9 +87,19 @@ fn create_test_layout() -> Layout {
         idle_effect_settings: crate::models::IdleEffectSettings::default(),
         rgb_overlay_ripple: crate::models::RgbOverlayRippleSettings::default(),
         palette_fx: crate::models::PaletteFxSettings::default(),
+        rgb_conditions: crate::models::RgbConditionalSettings::default(),
+        firmware_features: crate::models::FirmwareFeatures::default(),
         tap_hold_settings: crate::models::TapHoldSettings::default(),
         combo_settings: crate::models::ComboSettings::default(),
         tap_dances: vec![],
+        macros: vec![],
+        custom_keycodes: Vec::new(),
+        key_overrides: Vec::new(),
+        alt_repeat_keys: Vec::new(),
+        unicode_map: Vec::new(),
+        unicode_input_modes: Vec::new(),
+        usage_counting: false,
+        editing_helpers: crate::models::EditingHelpers::default(),
     }
 }
 
@@ -110,6 +128,7 @@ fn test_serialize_keycode_syntax() {
         category_id: None,
         combo_participant: false,
         description: None,
+        locked: false,
     };
     assert_eq!(serialize_keycode_syntax(&key), "KC_A");
 
@@ -122,6 +141,7 @@ fn test_serialize_keycode_syntax() {
         category_id: None,
         combo_participant: false,
         description: None,
+        locked: false,
     };
     assert_eq!(serialize_keycode_syntax(&key_with_color), "KC_A{#FF0000}");
 
@@ -134,6 +154,7 @@ fn test_serialize_keycode_syntax() {
         category_id: Some("navigation".to_string()),
         combo_participant: false,
         description: None,
+        locked: false,
     };
     assert_eq!(
         serialize_keycode_syntax(&key_with_category),
@@ -149,6 +170,7 @@ fn test_serialize_keycode_syntax() {
         category_id: Some("symbols".to_string()),
         combo_participant: false,
         description: None,
+        locked: false,
     };
     assert_eq!(
         serialize_keycode_syntax(&key_with_both),
@@ -189,6 +211,50 @@ fn test_round_trip() {
     );
 }
 
+#[test]
+fn test_underglow_color_round_trip() {
+    let mut layout = create_test_layout();
+    layout.layers[0].underglow_color = Some(RgbColor::new(0, 128, 255));
+
+    let markdown = generate_markdown(&layout).unwrap();
+    assert!(markdown.contains("**Underglow**: #0080FF"));
+
+    let parsed_layout = parse_markdown_layout_str(&markdown).unwrap();
+    assert_eq!(
+        parsed_layout.layers[0].underglow_color,
+        Some(RgbColor::new(0, 128, 255))
+    );
+
+    // Omitted when following the layer color
+    layout.layers[0].underglow_color = None;
+    assert!(!generate_markdown(&layout)
+        .unwrap()
+        .contains("**Underglow**"));
+}
+
+#[test]
+fn test_encoder_map_round_trip() {
+    use crate::models::EncoderBinding;
+
+    let mut layout = create_test_layout();
+    layout.layers[0]
+        .encoder_map
+        .set(0, EncoderBinding::new("KC_VOLD", "KC_VOLU"));
+    layout.layers[0]
+        .encoder_map
+        .set(2, EncoderBinding::new("LCTL(KC_Z)", "LCTL(KC_Y)"));
+
+    let markdown = generate_markdown(&layout).unwrap();
+    assert!(markdown.contains("**Encoder 0**: KC_VOLD / KC_VOLU"));
+    assert!(markdown.contains("**Encoder 2**: LCTL(KC_Z) / LCTL(KC_Y)"));
+
+    let parsed_layout = parse_markdown_layout_str(&markdown).unwrap();
+    assert_eq!(
+        parsed_layout.layers[0].encoder_map,
+        layout.layers[0].encoder_map
+    );
+}
+
 #[test]
 fn test_settings_round_trip() {
     use crate::models::UncoloredKeyBehavior;
@@ -313,6 +379,7 @@ fn test_tap_hold_custom_settings_round_trip() {
         tapping_toggle: 3,
         flow_tap_term: Some(120),
         chordal_hold: true,
+        per_key_tapping_terms: Vec::new(),
         preset: TapHoldPreset::Custom,
     };
 
@@ -773,3 +840,57 @@ fn test_ripple_overlay_complete_settings() {
     assert!(rip.ignore_modifiers);
     assert!(rip.ignore_layer_switch);
 }
+
+#[test]
+fn test_rgb_conditions_default_not_written() {
+    let layout = create_test_layout();
+    let markdown = generate_markdown(&layout).unwrap();
+    assert!(!markdown.contains("**RGB Dim"));
+    assert!(!markdown.contains("**RGB Off Hours**"));
+}
+
+#[test]
+fn test_rgb_conditions_round_trip() {
+    use crate::models::{RgbMatrixEffect, RgbOffHours};
+
+    let mut layout = create_test_layout();
+    let rules = &mut layout.rgb_conditions;
+    rules.dim_after_minutes = 10;
+    rules.dim_brightness_pct = 20;
+    rules.wpm_threshold = 80;
+    rules.wpm_effect = RgbMatrixEffect::CycleAll;
+    rules.set_layer_effect(0, Some(RgbMatrixEffect::Breathing));
+    rules.off_hours = RgbOffHours::parse("22:30-07:00");
+
+    let markdown = generate_markdown(&layout).unwrap();
+    assert!(markdown.contains("**RGB Dim After**: 10 min"));
+    assert!(markdown.contains("**RGB Layer Effect**: 0 = Breathing"));
+    assert!(markdown.contains("**RGB Off Hours**: 22:30-07:00"));
+
+    let parsed = parse_markdown_layout_str(&markdown).unwrap();
+    assert_eq!(parsed.rgb_conditions, layout.rgb_conditions);
+}
+
+#[test]
+fn test_firmware_features_round_trip() {
+    use crate::models::FirmwareFeature;
+
+    let mut layout = create_test_layout();
+    assert!(!generate_markdown(&layout)
+        .unwrap()
+        .contains("**Firmware Feature**"));
+
+    layout
+        .firmware_features
+        .set(FirmwareFeature::RgbMatrix, Some(false));
+    layout
+        .firmware_features
+        .set(FirmwareFeature::MouseKeys, Some(true));
+
+    let markdown = generate_markdown(&layout).unwrap();
+    assert!(markdown.contains("**Firmware Feature**: RGB Matrix = Off"));
+    assert!(markdown.contains("**Firmware Feature**: Mouse Keys = On"));
+
+    let parsed = parse_markdown_layout_str(&markdown).unwrap();
+    assert_eq!(parsed.firmware_features, layout.firmware_features);
+}
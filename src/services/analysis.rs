@@ -0,0 +1,473 @@
+//! Layout statistics and typing heatmap.
+//!
+//! [`analyze_layout`] summarizes each layer: how many keys are assigned,
+//! transparent or empty, which keycodes appear more than once, how the home
+//! row is used and how modifiers are split between the hands. Given corpus
+//! statistics it also builds a [`Heatmap`] of how often each key is pressed
+//! to type the corpus, and [`score_effort`] rates how much work the fingers
+//! do to type it on the base layer. [`usage_heatmap`] builds the same
+//! heatmap from presses recorded on the keyboard itself.
+//!
+//! Hands, fingers and the home row come from [`PositionNames`]; without a
+//! keyboard geometry those figures are left out (home row, effort) or
+//! counted as unplaced (modifiers).
+//!
+//! # Effort model
+//!
+//! Every finger rests on its home key (see [`PositionNames::is_home_key`]).
+//! A press costs `1 + d`, where `d` is the distance in key widths from the
+//! finger's home key to the key pressed, multiplied by the finger's weight:
+//! 1.0 for thumb, index and middle, 1.3 for ring and 1.6 for pinky. Thumbs
+//! have no home key and travel nothing. Shift and layer holds are not
+//! counted. Same-finger bigrams (two different keys in a row with one
+//! finger) and hand alternation are reported alongside, since they matter
+//! as much as travel when comparing alpha layouts.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::models::position_names::visual_position;
+use crate::models::{Finger, Hand, KeyboardGeometry, Layer, Layout, Position, PositionNames};
+use crate::services::thumb_optimizer::{is_modifier, tap_keycode, typed_chars, CorpusStats};
+use crate::services::usage::UsageLog;
+
+/// Keycodes that leave a key without a function.
+const UNASSIGNED_KEYCODES: [&str; 3] = ["KC_NO", "XXXXXXX", ""];
+
+/// A keycode assigned to more than one key of a layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicateKeycode {
+    /// The repeated keycode
+    pub keycode: String,
+    /// Positions holding it, in key order
+    pub positions: Vec<Position>,
+}
+
+/// How a layer uses the home row.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct HomeRowUsage {
+    /// Home row keys
+    pub keys: usize,
+    /// Home row keys that are neither transparent nor empty
+    pub assigned: usize,
+    /// Home row keys that hold a modifier (home row mods)
+    pub mod_taps: usize,
+}
+
+/// Modifier keys per hand.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ModifierBalance {
+    /// Modifiers on the left hand
+    pub left: usize,
+    /// Modifiers on the right hand
+    pub right: usize,
+    /// Modifiers on keys the geometry has no hand for
+    pub unplaced: usize,
+}
+
+/// Statistics of one layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LayerStats {
+    /// Layer index
+    pub layer: usize,
+    /// Layer name
+    pub name: String,
+    /// Keys on the layer
+    pub keys: usize,
+    /// Keys with a keycode of their own
+    pub assigned: usize,
+    /// `KC_TRNS` keys
+    pub transparent: usize,
+    /// `KC_NO` keys
+    pub unassigned: usize,
+    /// Keycodes assigned to several keys, ordered by keycode
+    pub duplicates: Vec<DuplicateKeycode>,
+    /// Home row usage, when the geometry names a home row
+    pub home_row: Option<HomeRowUsage>,
+    /// Modifiers and mod-taps per hand
+    pub modifiers: ModifierBalance,
+}
+
+/// Presses of one key while typing a corpus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KeyHeat {
+    /// Layer index
+    pub layer: usize,
+    /// Key position
+    pub position: Position,
+    /// Keycode of the key
+    pub keycode: String,
+    /// Characters of the corpus typed with the key
+    pub presses: u64,
+}
+
+/// How often each key is pressed to type a corpus.
+///
+/// Each character is counted on the first key that types it, searching the
+/// layers in order. Holding Shift or a layer key is not counted. A heatmap
+/// built by [`usage_heatmap`] counts recorded presses instead of characters.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Heatmap {
+    /// Characters in the corpus
+    pub total: u64,
+    /// Characters no key of the layout types
+    pub untyped: u64,
+    /// Presses on the left hand
+    pub left: u64,
+    /// Presses on the right hand
+    pub right: u64,
+    /// Presses on home row keys
+    pub home_row: u64,
+    /// Pressed keys, most presses first
+    pub keys: Vec<KeyHeat>,
+}
+
+/// Presses of one finger while typing a corpus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FingerLoad {
+    /// Hand of the finger
+    pub hand: Hand,
+    /// The finger
+    pub finger: Finger,
+    /// Characters typed with it
+    pub presses: u64,
+}
+
+/// Finger travel and effort of typing a corpus on the base layer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct EffortScore {
+    /// Characters in the corpus
+    pub total: u64,
+    /// Characters typed with a base layer key (with or without Shift)
+    pub typed: u64,
+    /// Presses on the home keys
+    pub home_keys: u64,
+    /// Finger travel from the home keys, in key widths
+    pub distance: f64,
+    /// Weighted effort (see the module docs); lower is better
+    pub effort: f64,
+    /// Pairs of consecutive characters that are both typed
+    pub bigrams: u64,
+    /// Pairs typed with one finger on two different keys
+    pub same_finger_bigrams: u64,
+    /// Pairs typed with alternating hands
+    pub hand_alternations: u64,
+    /// Presses per finger, left hand first, each hand from thumb to pinky
+    pub fingers: Vec<FingerLoad>,
+}
+
+impl EffortScore {
+    /// Effort per typed character; 0 when nothing was typed.
+    #[must_use]
+    pub fn effort_per_char(&self) -> f64 {
+        if self.typed == 0 {
+            0.0
+        } else {
+            self.effort / self.typed as f64
+        }
+    }
+
+    /// Travel per typed character, in key widths.
+    #[must_use]
+    pub fn distance_per_char(&self) -> f64 {
+        if self.typed == 0 {
+            0.0
+        } else {
+            self.distance / self.typed as f64
+        }
+    }
+}
+
+/// Statistics of a whole layout.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LayoutAnalysis {
+    /// Per-layer statistics, in layer order
+    pub layers: Vec<LayerStats>,
+    /// Typing heatmap, when a corpus was given
+    pub heatmap: Option<Heatmap>,
+    /// Base layer effort, when a corpus and the keyboard geometry were given
+    pub effort: Option<EffortScore>,
+}
+
+/// Computes per-layer statistics and, with a corpus, the typing heatmap and
+/// base layer effort.
+#[must_use]
+pub fn analyze_layout(
+    layout: &Layout,
+    geometry: Option<&KeyboardGeometry>,
+    corpus: Option<&CorpusStats>,
+) -> LayoutAnalysis {
+    let geometry = geometry.filter(|geometry| !geometry.keys.is_empty());
+    let names = geometry.map(PositionNames::build).unwrap_or_default();
+    LayoutAnalysis {
+        layers: layout
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| layer_stats(index, layer, &names))
+            .collect(),
+        heatmap: corpus.map(|corpus| build_heatmap(layout, &names, corpus)),
+        effort: geometry
+            .zip(corpus)
+            .map(|(geometry, corpus)| score_effort(layout, geometry, corpus)),
+    }
+}
+
+/// Weight of a press with `finger`; weaker fingers cost more.
+const fn finger_weight(finger: Finger) -> f64 {
+    match finger {
+        Finger::Thumb | Finger::Index | Finger::Middle => 1.0,
+        Finger::Ring => 1.3,
+        Finger::Pinky => 1.6,
+    }
+}
+
+/// Scores typing `corpus` on the base layer of `layout` with the effort
+/// model described in the module docs.
+#[must_use]
+pub fn score_effort(
+    layout: &Layout,
+    geometry: &KeyboardGeometry,
+    corpus: &CorpusStats,
+) -> EffortScore {
+    let names = PositionNames::build(geometry);
+    let centers: HashMap<Position, (f32, f32)> = geometry
+        .keys
+        .iter()
+        .map(|key| {
+            let center = (
+                key.visual_x + key.width / 2.0,
+                key.visual_y + key.height / 2.0,
+            );
+            (visual_position(key), center)
+        })
+        .collect();
+    let homes: HashMap<(Hand, Finger), (f32, f32)> = centers
+        .iter()
+        .filter(|(position, _)| names.is_home_key(**position))
+        .filter_map(|(position, center)| Some((names.finger(*position)?, *center)))
+        .collect();
+
+    // Key typing each character on the base layer
+    let mut typed_by: HashMap<char, Position> = HashMap::new();
+    if let Some(base) = layout.layers.first() {
+        for key in &base.keys {
+            for (c, _) in typed_chars(tap_keycode(&key.keycode)) {
+                typed_by.entry(c).or_insert(key.position);
+            }
+        }
+    }
+
+    let mut score = EffortScore::default();
+    let mut loads: BTreeMap<(Hand, Finger), u64> = BTreeMap::new();
+    for (c, &count) in &corpus.chars {
+        score.total += count;
+        let Some(&position) = typed_by.get(c) else {
+            continue;
+        };
+        score.typed += count;
+        if names.is_home_key(position) {
+            score.home_keys += count;
+        }
+
+        let finger = names.finger(position);
+        let travel = finger
+            .and_then(|finger| homes.get(&finger))
+            .zip(centers.get(&position))
+            .map_or(0.0, |(home, key)| {
+                f64::from((key.0 - home.0).hypot(key.1 - home.1))
+            });
+        let weight = finger.map_or(1.0, |(_, finger)| finger_weight(finger));
+        score.distance += travel * count as f64;
+        score.effort += weight * (1.0 + travel) * count as f64;
+        if let Some(finger) = finger {
+            *loads.entry(finger).or_default() += count;
+        }
+    }
+
+    for (&(first, second), &count) in &corpus.bigrams {
+        let (Some(&a), Some(&b)) = (typed_by.get(&first), typed_by.get(&second)) else {
+            continue;
+        };
+        score.bigrams += count;
+        let (Some(finger_a), Some(finger_b)) = (names.finger(a), names.finger(b)) else {
+            continue;
+        };
+        if finger_a.0 != finger_b.0 {
+            score.hand_alternations += count;
+        } else if finger_a.1 == finger_b.1 && a != b {
+            score.same_finger_bigrams += count;
+        }
+    }
+
+    score.fingers = loads
+        .into_iter()
+        .map(|((hand, finger), presses)| FingerLoad {
+            hand,
+            finger,
+            presses,
+        })
+        .collect();
+    score
+}
+
+/// Returns true if the keycode leaves the key without a function.
+fn is_unassigned(keycode: &str) -> bool {
+    UNASSIGNED_KEYCODES.contains(&keycode.trim())
+}
+
+fn layer_stats(index: usize, layer: &Layer, names: &PositionNames) -> LayerStats {
+    let mut stats = LayerStats {
+        layer: index,
+        name: layer.name.clone(),
+        keys: layer.keys.len(),
+        assigned: 0,
+        transparent: 0,
+        unassigned: 0,
+        duplicates: Vec::new(),
+        home_row: None,
+        modifiers: ModifierBalance::default(),
+    };
+    let mut home_row = HomeRowUsage::default();
+    let mut positions: BTreeMap<&str, Vec<Position>> = BTreeMap::new();
+
+    for key in &layer.keys {
+        let transparent = key.is_transparent();
+        let unassigned = is_unassigned(&key.keycode);
+        if transparent {
+            stats.transparent += 1;
+        } else if unassigned {
+            stats.unassigned += 1;
+        } else {
+            stats.assigned += 1;
+            positions
+                .entry(&key.keycode)
+                .or_default()
+                .push(key.position);
+        }
+
+        let modifier = is_modifier(&key.keycode) || key.keycode.starts_with("OSM(");
+        if modifier {
+            match names.is_left(key.position) {
+                Some(true) => stats.modifiers.left += 1,
+                Some(false) => stats.modifiers.right += 1,
+                None => stats.modifiers.unplaced += 1,
+            }
+        }
+
+        if names.is_home_row(key.position) {
+            home_row.keys += 1;
+            if !transparent && !unassigned {
+                home_row.assigned += 1;
+            }
+            if modifier && tap_keycode(&key.keycode) != key.keycode {
+                home_row.mod_taps += 1;
+            }
+        }
+    }
+
+    stats.duplicates = positions
+        .into_iter()
+        .filter(|(_, positions)| positions.len() > 1)
+        .map(|(keycode, positions)| DuplicateKeycode {
+            keycode: keycode.to_string(),
+            positions,
+        })
+        .collect();
+    stats.home_row = (home_row.keys > 0).then_some(home_row);
+    stats
+}
+
+fn build_heatmap(layout: &Layout, names: &PositionNames, corpus: &CorpusStats) -> Heatmap {
+    // First key typing each character: (layer, key index)
+    let mut typed_by: HashMap<char, (usize, usize)> = HashMap::new();
+    for (layer_idx, layer) in layout.layers.iter().enumerate() {
+        for (key_idx, key) in layer.keys.iter().enumerate() {
+            for (c, _) in typed_chars(tap_keycode(&key.keycode)) {
+                typed_by.entry(c).or_insert((layer_idx, key_idx));
+            }
+        }
+    }
+
+    let mut heatmap = Heatmap::default();
+    let mut presses: HashMap<(usize, usize), u64> = HashMap::new();
+    for (c, &count) in &corpus.chars {
+        heatmap.total += count;
+        match typed_by.get(c) {
+            Some(&key) => *presses.entry(key).or_default() += count,
+            None => heatmap.untyped += count,
+        }
+    }
+
+    add_presses(&mut heatmap, layout, names, presses);
+    heatmap
+}
+
+/// Builds a heatmap of the key presses recorded by usage counting firmware.
+///
+/// `total` counts every recorded press; presses on keys the layout no
+/// longer has count as `untyped`.
+#[must_use]
+pub fn usage_heatmap(
+    layout: &Layout,
+    geometry: Option<&KeyboardGeometry>,
+    usage: &UsageLog,
+) -> Heatmap {
+    let names = geometry
+        .filter(|geometry| !geometry.keys.is_empty())
+        .map(PositionNames::build)
+        .unwrap_or_default();
+    let mut heatmap = Heatmap::default();
+    let mut presses: HashMap<(usize, usize), u64> = HashMap::new();
+    for key in &usage.keys {
+        heatmap.total += key.presses;
+        let key_idx = layout.layers.get(key.layer).and_then(|layer| {
+            layer
+                .keys
+                .iter()
+                .position(|layer_key| layer_key.position == key.position)
+        });
+        match key_idx {
+            Some(key_idx) => *presses.entry((key.layer, key_idx)).or_default() += key.presses,
+            None => heatmap.untyped += key.presses,
+        }
+    }
+    add_presses(&mut heatmap, layout, &names, presses);
+    heatmap
+}
+
+/// Adds presses per (layer, key index) to the hand, home row and key figures.
+fn add_presses(
+    heatmap: &mut Heatmap,
+    layout: &Layout,
+    names: &PositionNames,
+    presses: HashMap<(usize, usize), u64>,
+) {
+    for ((layer_idx, key_idx), count) in presses {
+        let key = &layout.layers[layer_idx].keys[key_idx];
+        match names.is_left(key.position) {
+            Some(true) => heatmap.left += count,
+            Some(false) => heatmap.right += count,
+            None => {}
+        }
+        if names.is_home_row(key.position) {
+            heatmap.home_row += count;
+        }
+        heatmap.keys.push(KeyHeat {
+            layer: layer_idx,
+            position: key.position,
+            keycode: key.keycode.clone(),
+            presses: count,
+        });
+    }
+    heatmap.keys.sort_by(|a, b| {
+        b.presses
+            .cmp(&a.presses)
+            .then(a.layer.cmp(&b.layer))
+            .then((a.position.row, a.position.col).cmp(&(b.position.row, b.position.col)))
+    });
+}
+
+#[cfg(test)]
+mod tests;
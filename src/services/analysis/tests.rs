@@ -0,0 +1,212 @@
+//! Tests for services::analysis.
+
+use super::*;
+use crate::models::{KeyDefinition, KeyGeometry, KeyboardGeometry, RgbColor};
+
+/// Two rows of four keys, two per hand: row 0 is the home row.
+fn geometry() -> KeyboardGeometry {
+    let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 2, 4);
+    let mut index = 0;
+    for row in 0..2u8 {
+        for col in 0..4u8 {
+            geometry.add_key(KeyGeometry::new(
+                (row, col),
+                index,
+                f32::from(col),
+                f32::from(row),
+            ));
+            index += 1;
+        }
+    }
+    geometry
+}
+
+fn layer(index: u8, name: &str, keycodes: [&str; 8]) -> Layer {
+    let mut layer = Layer::new(index, name, RgbColor::new(255, 255, 255)).unwrap();
+    for (i, keycode) in keycodes.iter().enumerate() {
+        let position = Position::new(i as u8 / 4, i as u8 % 4);
+        layer.add_key(KeyDefinition::new(position, *keycode));
+    }
+    layer
+}
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    layout
+        .add_layer(layer(
+            0,
+            "Base",
+            [
+                "KC_A",
+                "LSFT_T(KC_S)",
+                "KC_D",
+                "KC_A",
+                "KC_NO",
+                "KC_LCTL",
+                "KC_TRNS",
+                "MO(1)",
+            ],
+        ))
+        .unwrap();
+    layout
+        .add_layer(layer(
+            1,
+            "Nav",
+            [
+                "KC_TRNS", "KC_TRNS", "KC_TRNS", "KC_TRNS", "KC_TRNS", "KC_TRNS", "KC_TRNS", "KC_1",
+            ],
+        ))
+        .unwrap();
+    layout
+}
+
+#[test]
+fn test_layer_stats() {
+    let analysis = analyze_layout(&create_test_layout(), Some(&geometry()), None);
+
+    let base = &analysis.layers[0];
+    assert_eq!(base.name, "Base");
+    assert_eq!(
+        (base.keys, base.assigned, base.transparent, base.unassigned),
+        (8, 6, 1, 1)
+    );
+    assert_eq!(
+        base.duplicates,
+        vec![DuplicateKeycode {
+            keycode: "KC_A".to_string(),
+            positions: vec![Position::new(0, 0), Position::new(0, 3)],
+        }]
+    );
+    assert_eq!(
+        base.home_row,
+        Some(HomeRowUsage {
+            keys: 4,
+            assigned: 4,
+            mod_taps: 1,
+        })
+    );
+    assert_eq!(
+        base.modifiers,
+        ModifierBalance {
+            left: 2,
+            right: 0,
+            unplaced: 0,
+        }
+    );
+
+    let nav = &analysis.layers[1];
+    assert_eq!((nav.assigned, nav.transparent), (1, 7));
+    assert!(nav.duplicates.is_empty());
+    assert_eq!(nav.home_row.map(|usage| usage.assigned), Some(0));
+    assert!(analysis.heatmap.is_none());
+}
+
+#[test]
+fn test_without_geometry_hands_are_unknown() {
+    let analysis = analyze_layout(&create_test_layout(), None, None);
+
+    let base = &analysis.layers[0];
+    assert_eq!(base.home_row, None);
+    assert_eq!(base.modifiers.unplaced, 2);
+}
+
+#[test]
+fn test_heatmap_counts_presses() {
+    let corpus = CorpusStats::from_text("aab1!s");
+
+    let heatmap = analyze_layout(&create_test_layout(), Some(&geometry()), Some(&corpus))
+        .heatmap
+        .unwrap();
+
+    assert_eq!(heatmap.total, 6);
+    assert_eq!(heatmap.untyped, 1);
+    assert_eq!((heatmap.left, heatmap.right, heatmap.home_row), (3, 2, 3));
+    let keys: Vec<(usize, &str, u64)> = heatmap
+        .keys
+        .iter()
+        .map(|key| (key.layer, key.keycode.as_str(), key.presses))
+        .collect();
+    assert_eq!(
+        keys,
+        [(0, "KC_A", 2), (1, "KC_1", 2), (0, "LSFT_T(KC_S)", 1)]
+    );
+}
+
+#[test]
+fn test_usage_heatmap_from_recorded_presses() {
+    let mut usage = UsageLog::default();
+    usage.record(0, Position::new(0, 0), 5);
+    usage.record(1, Position::new(1, 3), 2);
+    // Layer removed since recording
+    usage.record(2, Position::new(0, 0), 1);
+
+    let heatmap = usage_heatmap(&create_test_layout(), Some(&geometry()), &usage);
+
+    assert_eq!((heatmap.total, heatmap.untyped), (8, 1));
+    assert_eq!((heatmap.left, heatmap.right, heatmap.home_row), (5, 2, 5));
+    let keys: Vec<(usize, &str, u64)> = heatmap
+        .keys
+        .iter()
+        .map(|key| (key.layer, key.keycode.as_str(), key.presses))
+        .collect();
+    assert_eq!(keys, [(0, "KC_A", 5), (1, "KC_1", 2)]);
+}
+
+#[test]
+fn test_effort_counts_travel_and_bigrams() {
+    let mut layout = Layout::new("Alphas").unwrap();
+    layout
+        .add_layer(layer(
+            0,
+            "Base",
+            [
+                "KC_A", "KC_S", "KC_D", "KC_F", "KC_Z", "KC_X", "KC_C", "KC_V",
+            ],
+        ))
+        .unwrap();
+    // a and f rest on home keys, z is one row below a (same finger)
+    let corpus = CorpusStats::from_text("azf?");
+
+    let score = score_effort(&layout, &geometry(), &corpus);
+
+    assert_eq!((score.total, score.typed, score.home_keys), (4, 3, 2));
+    assert!((score.distance - 1.0).abs() < 1e-6);
+    // Middle fingers weigh 1.0: 1 + (1 + 1) + 1
+    assert!((score.effort - 4.0).abs() < 1e-6);
+    assert_eq!(score.bigrams, 2);
+    assert_eq!(score.same_finger_bigrams, 1);
+    assert_eq!(score.hand_alternations, 1);
+    assert_eq!(
+        score.fingers,
+        vec![
+            FingerLoad {
+                hand: Hand::Left,
+                finger: Finger::Middle,
+                presses: 2,
+            },
+            FingerLoad {
+                hand: Hand::Right,
+                finger: Finger::Middle,
+                presses: 1,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_effort_needs_geometry_and_corpus() {
+    let corpus = CorpusStats::from_text("as");
+
+    assert!(analyze_layout(&create_test_layout(), None, Some(&corpus))
+        .effort
+        .is_none());
+    assert!(
+        analyze_layout(&create_test_layout(), Some(&geometry()), None)
+            .effort
+            .is_none()
+    );
+    let effort = analyze_layout(&create_test_layout(), Some(&geometry()), Some(&corpus))
+        .effort
+        .unwrap();
+    assert_eq!(effort.typed, 2);
+}
@@ -0,0 +1,370 @@
+//! Append-only audit log of layout modifications.
+//!
+//! Every mutation made through the web API or the TUI is recorded as one JSON
+//! line in `.lazyqmk/audit/<layout>.jsonl`, next to the layout file. Each entry
+//! records who made the change, when, through which frontend, and a
+//! human-readable summary of what changed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::models::{KeyDefinition, Layer, Layout};
+
+/// Maximum number of change lines stored per entry.
+const MAX_CHANGES: usize = 200;
+
+/// Frontend through which a change was made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditSource {
+    /// Web API
+    Web,
+    /// Terminal UI
+    Tui,
+}
+
+/// A single audit log entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// When the change was made
+    pub timestamp: DateTime<Utc>,
+    /// Who made the change
+    pub actor: String,
+    /// Frontend that made the change
+    pub source: AuditSource,
+    /// Operation name (e.g. "save", "swap_keys")
+    pub action: String,
+    /// Human-readable list of changes
+    pub changes: Vec<String>,
+}
+
+impl AuditEntry {
+    /// Creates an entry for `action`, describing the difference between
+    /// `before` (if the layout existed) and `after`.
+    #[must_use]
+    pub fn new(
+        actor: impl Into<String>,
+        source: AuditSource,
+        action: impl Into<String>,
+        before: Option<&Layout>,
+        after: &Layout,
+    ) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            actor: actor.into(),
+            source,
+            action: action.into(),
+            changes: diff_layouts(before, after),
+        }
+    }
+}
+
+/// Returns the audit log path for a layout file.
+#[must_use]
+pub fn audit_log_path(layout_path: &Path) -> PathBuf {
+    let dir = layout_path.parent().unwrap_or_else(|| Path::new("."));
+    let stem = layout_path
+        .file_stem()
+        .map_or_else(|| "layout".into(), |s| s.to_string_lossy());
+    dir.join(".lazyqmk")
+        .join("audit")
+        .join(format!("{stem}.jsonl"))
+}
+
+/// Appends `entry` to the audit log of the layout at `layout_path`.
+///
+/// # Errors
+///
+/// Returns an error if the audit directory or file cannot be written.
+pub fn record(layout_path: &Path, entry: &AuditEntry) -> Result<()> {
+    let log_path = audit_log_path(layout_path);
+    if let Some(dir) = log_path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create audit directory: {}", dir.display()))?;
+    }
+
+    let line = serde_json::to_string(entry).context("Failed to serialize audit entry")?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .with_context(|| format!("Failed to open audit log: {}", log_path.display()))?;
+    writeln!(file, "{line}")
+        .with_context(|| format!("Failed to write audit log: {}", log_path.display()))?;
+
+    Ok(())
+}
+
+/// Reads all audit entries of the layout at `layout_path`, oldest first.
+///
+/// A missing log yields an empty list; malformed lines are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the log exists but cannot be read.
+#[allow(dead_code)] // Used by the web audit endpoint (lib target only)
+pub fn read(layout_path: &Path) -> Result<Vec<AuditEntry>> {
+    let log_path = audit_log_path(layout_path);
+    if !log_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&log_path)
+        .with_context(|| format!("Failed to read audit log: {}", log_path.display()))?;
+
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Returns the name of the local user, for TUI and CLI entries.
+#[must_use]
+pub fn local_actor() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Describes the differences between two versions of a layout.
+#[must_use]
+pub fn diff_layouts(before: Option<&Layout>, after: &Layout) -> Vec<String> {
+    let Some(before) = before else {
+        return vec![format!(
+            "Created layout '{}' with {} layer(s)",
+            after.metadata.name,
+            after.layers.len()
+        )];
+    };
+
+    let mut changes = Vec::new();
+
+    diff_metadata(before, after, &mut changes);
+    diff_layers(&before.layers, &after.layers, &mut changes);
+    diff_named(
+        "category",
+        before.categories.iter().map(|c| (c.id.as_str(), c)),
+        after.categories.iter().map(|c| (c.id.as_str(), c)),
+        &mut changes,
+    );
+    diff_named(
+        "tap dance",
+        before.tap_dances.iter().map(|td| (td.name.as_str(), td)),
+        after.tap_dances.iter().map(|td| (td.name.as_str(), td)),
+        &mut changes,
+    );
+    diff_settings(before, after, &mut changes);
+
+    if changes.len() > MAX_CHANGES {
+        let more = changes.len() - MAX_CHANGES;
+        changes.truncate(MAX_CHANGES);
+        changes.push(format!("... and {more} more change(s)"));
+    }
+
+    changes
+}
+
+fn diff_metadata(before: &Layout, after: &Layout, changes: &mut Vec<String>) {
+    let (Ok(Value::Object(old)), Ok(Value::Object(new))) = (
+        serde_json::to_value(&before.metadata),
+        serde_json::to_value(&after.metadata),
+    ) else {
+        return;
+    };
+
+    let fields: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    for field in fields {
+        if field == "modified" {
+            continue;
+        }
+        let (old_value, new_value) = (old.get(field), new.get(field));
+        if old_value != new_value {
+            changes.push(format!(
+                "Metadata {field}: {} → {}",
+                display_value(old_value),
+                display_value(new_value)
+            ));
+        }
+    }
+}
+
+fn diff_layers(before: &[Layer], after: &[Layer], changes: &mut Vec<String>) {
+    for (idx, new_layer) in after.iter().enumerate() {
+        let Some(old_layer) = before.get(idx) else {
+            changes.push(format!("Added layer {idx} '{}'", new_layer.name));
+            continue;
+        };
+
+        if old_layer.name != new_layer.name {
+            changes.push(format!(
+                "Layer {idx}: renamed '{}' → '{}'",
+                old_layer.name, new_layer.name
+            ));
+        }
+        if old_layer.default_color != new_layer.default_color {
+            changes.push(format!(
+                "Layer {idx}: default color {} → {}",
+                old_layer.default_color.to_hex(),
+                new_layer.default_color.to_hex()
+            ));
+        }
+        if old_layer.category_id != new_layer.category_id {
+            changes.push(format!(
+                "Layer {idx}: category {} → {}",
+                display_opt(old_layer.category_id.as_deref()),
+                display_opt(new_layer.category_id.as_deref())
+            ));
+        }
+        if old_layer.layer_colors_enabled != new_layer.layer_colors_enabled {
+            changes.push(format!(
+                "Layer {idx}: layer colors {}",
+                if new_layer.layer_colors_enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            ));
+        }
+        if old_layer.underglow_color != new_layer.underglow_color {
+            changes.push(format!(
+                "Layer {idx}: underglow {} → {}",
+                display_opt(old_layer.underglow_color.map(|c| c.to_hex()).as_deref()),
+                display_opt(new_layer.underglow_color.map(|c| c.to_hex()).as_deref())
+            ));
+        }
+
+        diff_keys(idx, &old_layer.keys, &new_layer.keys, changes);
+    }
+
+    for (idx, old_layer) in before.iter().enumerate().skip(after.len()) {
+        changes.push(format!("Removed layer {idx} '{}'", old_layer.name));
+    }
+}
+
+fn diff_keys(
+    layer: usize,
+    before: &[KeyDefinition],
+    after: &[KeyDefinition],
+    changes: &mut Vec<String>,
+) {
+    let old_keys: BTreeMap<_, _> = before
+        .iter()
+        .map(|k| ((k.position.row, k.position.col), k))
+        .collect();
+    let new_keys: BTreeMap<_, _> = after
+        .iter()
+        .map(|k| ((k.position.row, k.position.col), k))
+        .collect();
+
+    for (&(row, col), new_key) in &new_keys {
+        let Some(old_key) = old_keys.get(&(row, col)) else {
+            changes.push(format!(
+                "Layer {layer} ({row}, {col}): added key {}",
+                new_key.keycode
+            ));
+            continue;
+        };
+
+        if old_key.keycode != new_key.keycode {
+            changes.push(format!(
+                "Layer {layer} ({row}, {col}): {} → {}",
+                old_key.keycode, new_key.keycode
+            ));
+        }
+        if old_key.color_override != new_key.color_override {
+            changes.push(format!(
+                "Layer {layer} ({row}, {col}): color {} → {}",
+                display_opt(old_key.color_override.map(|c| c.to_hex()).as_deref()),
+                display_opt(new_key.color_override.map(|c| c.to_hex()).as_deref())
+            ));
+        }
+        if old_key.category_id != new_key.category_id {
+            changes.push(format!(
+                "Layer {layer} ({row}, {col}): category {} → {}",
+                display_opt(old_key.category_id.as_deref()),
+                display_opt(new_key.category_id.as_deref())
+            ));
+        }
+        if old_key.description != new_key.description {
+            changes.push(format!("Layer {layer} ({row}, {col}): description changed"));
+        }
+    }
+
+    for (&(row, col), old_key) in &old_keys {
+        if !new_keys.contains_key(&(row, col)) {
+            changes.push(format!(
+                "Layer {layer} ({row}, {col}): removed key {}",
+                old_key.keycode
+            ));
+        }
+    }
+}
+
+fn diff_named<'a, T: PartialEq + 'a>(
+    kind: &str,
+    before: impl Iterator<Item = (&'a str, &'a T)>,
+    after: impl Iterator<Item = (&'a str, &'a T)>,
+    changes: &mut Vec<String>,
+) {
+    let old: BTreeMap<_, _> = before.collect();
+    let new: BTreeMap<_, _> = after.collect();
+
+    for (name, item) in &new {
+        match old.get(name) {
+            None => changes.push(format!("Added {kind} '{name}'")),
+            Some(old_item) if old_item != item => {
+                changes.push(format!("Changed {kind} '{name}'"));
+            }
+            Some(_) => {}
+        }
+    }
+    for name in old.keys() {
+        if !new.contains_key(name) {
+            changes.push(format!("Removed {kind} '{name}'"));
+        }
+    }
+}
+
+/// Reports top-level settings (RGB, idle effect, tap-hold, combos, ...) that changed.
+fn diff_settings(before: &Layout, after: &Layout, changes: &mut Vec<String>) {
+    const DIFFED_ELSEWHERE: [&str; 4] = ["metadata", "layers", "categories", "tap_dances"];
+
+    let (Ok(Value::Object(old)), Ok(Value::Object(new))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return;
+    };
+
+    let fields: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    for field in fields {
+        if DIFFED_ELSEWHERE.contains(&field.as_str()) {
+            continue;
+        }
+        if old.get(field) != new.get(field) {
+            changes.push(format!("Changed setting {field}"));
+        }
+    }
+}
+
+fn display_value(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => "(none)".to_string(),
+        Some(Value::String(s)) => format!("'{s}'"),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn display_opt(value: Option<&str>) -> String {
+    value.map_or_else(|| "(none)".to_string(), ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,66 @@
+//! Tests for the audit log.
+
+use super::*;
+
+use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+use tempfile::TempDir;
+
+fn sample_layout() -> Layout {
+    let mut layout = Layout::new("audit_test").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"));
+    layout.add_layer(layer).unwrap();
+    layout
+}
+
+#[test]
+fn test_diff_reports_key_and_layer_changes() {
+    let before = sample_layout();
+    let mut after = before.clone();
+    after.layers[0].keys[1].keycode = "KC_C".to_string();
+    after.layers[0].name = "Main".to_string();
+    after.metadata.touch();
+
+    let changes = diff_layouts(Some(&before), &after);
+    assert_eq!(
+        changes,
+        vec![
+            "Layer 0: renamed 'Base' → 'Main'".to_string(),
+            "Layer 0 (0, 1): KC_B → KC_C".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_diff_new_layout_and_unchanged_layout() {
+    let layout = sample_layout();
+    let created = diff_layouts(None, &layout);
+    assert_eq!(created.len(), 1);
+    assert!(created[0].starts_with("Created layout 'audit_test'"));
+
+    assert!(diff_layouts(Some(&layout), &layout).is_empty());
+}
+
+#[test]
+fn test_record_and_read_roundtrip() {
+    let tmp = TempDir::new().unwrap();
+    let layout_path = tmp.path().join("my_layout.json");
+
+    assert!(read(&layout_path).unwrap().is_empty());
+
+    let layout = sample_layout();
+    let entry = AuditEntry::new("alice", AuditSource::Web, "save", None, &layout);
+    record(&layout_path, &entry).unwrap();
+    record(&layout_path, &entry).unwrap();
+
+    let log_path = audit_log_path(&layout_path);
+    assert_eq!(log_path, tmp.path().join(".lazyqmk/audit/my_layout.jsonl"));
+
+    // Malformed lines are skipped
+    let mut file = OpenOptions::new().append(true).open(&log_path).unwrap();
+    writeln!(file, "not json").unwrap();
+
+    let entries = read(&layout_path).unwrap();
+    assert_eq!(entries, vec![entry.clone(), entry]);
+}
@@ -0,0 +1,182 @@
+//! Automatic timestamped layout backups.
+//!
+//! Before a layout file is overwritten, its current contents are copied to
+//! `<backup dir>/<name>-<hash>/<timestamp>.json`. The backup directory is
+//! `backups/` in the config directory (`~/.config/LazyQMK/backups/` on Linux)
+//! unless `paths.backup_dir` is set. The hash of the layout's absolute path
+//! keeps layouts with the same file name in different directories apart.
+//!
+//! Only the newest [`BACKUP_LIMIT`] backups of each layout are kept, and a
+//! file identical to its newest backup is not copied again.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+
+use crate::models::Layout;
+use crate::services::LayoutService;
+
+/// Number of backups kept per layout.
+pub const BACKUP_LIMIT: usize = 20;
+
+/// Timestamp format of backup file names (sortable, UTC).
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%.6fZ";
+
+/// Extension of backup files.
+const BACKUP_EXTENSION: &str = "json";
+
+/// A stored backup of a layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BackupInfo {
+    /// Identifier used to restore the backup (its file name)
+    pub id: String,
+    /// When the backup was taken
+    pub created_at: DateTime<Utc>,
+    /// Size in bytes
+    pub size: u64,
+}
+
+/// Rotating backups for layout files, kept under one root directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupStore {
+    root: PathBuf,
+}
+
+impl BackupStore {
+    /// Creates a store that keeps its backups under `root`.
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Copies the current contents of `layout_path` into a new backup.
+    ///
+    /// Returns None if there is nothing to back up: the file doesn't exist
+    /// yet or is identical to its newest backup. Older backups beyond
+    /// [`BACKUP_LIMIT`] are removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file or the backup directory can't be
+    /// read or written.
+    pub fn backup(&self, layout_path: &Path) -> Result<Option<BackupInfo>> {
+        if !layout_path.is_file() {
+            return Ok(None);
+        }
+        let contents = fs::read(layout_path)
+            .with_context(|| format!("Failed to read {}", layout_path.display()))?;
+
+        let dir = self.layout_dir(layout_path);
+        let existing = self.list(layout_path)?;
+        if let Some(newest) = existing.first() {
+            if fs::read(dir.join(&newest.id)).is_ok_and(|newest| newest == contents) {
+                return Ok(None);
+            }
+        }
+
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let id = format!("{}.{BACKUP_EXTENSION}", Utc::now().format(TIMESTAMP_FORMAT));
+        // Report the time as stored in the file name
+        let created_at = parse_timestamp(&id).context("Invalid backup timestamp")?;
+        let path = dir.join(&id);
+        fs::write(&path, &contents)
+            .with_context(|| format!("Failed to write backup {}", path.display()))?;
+
+        // The new backup is newest; drop the oldest beyond the limit
+        for stale in existing.iter().skip(BACKUP_LIMIT.saturating_sub(1)) {
+            let _ = fs::remove_file(dir.join(&stale.id));
+        }
+
+        Ok(Some(BackupInfo {
+            id,
+            created_at,
+            size: contents.len() as u64,
+        }))
+    }
+
+    /// Lists the backups of `layout_path`, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backup directory exists but can't be read.
+    pub fn list(&self, layout_path: &Path) -> Result<Vec<BackupInfo>> {
+        let dir = self.layout_dir(layout_path);
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let entries =
+            fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+
+        let mut backups: Vec<BackupInfo> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let id = entry.file_name().to_str()?.to_string();
+                let created_at = parse_timestamp(&id)?;
+                let size = entry.metadata().ok()?.len();
+                Some(BackupInfo {
+                    id,
+                    created_at,
+                    size,
+                })
+            })
+            .collect();
+        backups.sort_by_key(|backup| std::cmp::Reverse(backup.created_at));
+        Ok(backups)
+    }
+
+    /// Loads the backup `id` of `layout_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no such backup exists or it can't be parsed.
+    pub fn load(&self, layout_path: &Path, id: &str) -> Result<Layout> {
+        if !self.list(layout_path)?.iter().any(|backup| backup.id == id) {
+            bail!("Backup '{id}' not found");
+        }
+        LayoutService::load(&self.layout_dir(layout_path).join(id))
+            .with_context(|| format!("Backup '{id}' is not a readable layout"))
+    }
+
+    /// Directory holding the backups of `layout_path`.
+    fn layout_dir(&self, layout_path: &Path) -> PathBuf {
+        let absolute = std::path::absolute(layout_path).unwrap_or_else(|_| layout_path.into());
+        let stem = layout_path
+            .file_stem()
+            .map_or_else(|| "layout".into(), |s| s.to_string_lossy());
+        let stem: String = stem
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.root.join(format!(
+            "{stem}-{:016x}",
+            fnv1a(absolute.to_string_lossy().as_bytes())
+        ))
+    }
+}
+
+/// Parses the creation time from a backup file name.
+fn parse_timestamp(id: &str) -> Option<DateTime<Utc>> {
+    let stamp = id.strip_suffix(BACKUP_EXTENSION)?.strip_suffix('.')?;
+    NaiveDateTime::parse_from_str(stamp, TIMESTAMP_FORMAT)
+        .ok()
+        .map(|time| time.and_utc())
+}
+
+/// 64-bit FNV-1a hash; stable across builds, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,95 @@
+use super::*;
+use tempfile::TempDir;
+
+fn write_layout(path: &Path, name: &str) {
+    let mut layout = Layout::new(name).unwrap();
+    layout
+        .add_layer(
+            crate::models::Layer::new(0, "Base", crate::models::RgbColor::new(0, 0, 0)).unwrap(),
+        )
+        .unwrap();
+    LayoutService::save(&layout, path).unwrap();
+}
+
+#[test]
+fn test_backup_skips_missing_and_unchanged_files() {
+    let dir = TempDir::new().unwrap();
+    let store = BackupStore::new(dir.path().join("backups"));
+    let path = dir.path().join("corne.json");
+
+    assert_eq!(store.backup(&path).unwrap(), None);
+
+    write_layout(&path, "First");
+    let first = store.backup(&path).unwrap().expect("first backup");
+    assert_eq!(store.backup(&path).unwrap(), None);
+    assert_eq!(store.list(&path).unwrap(), vec![first]);
+}
+
+#[test]
+fn test_backups_list_newest_first_and_load() {
+    let dir = TempDir::new().unwrap();
+    let store = BackupStore::new(dir.path().join("backups"));
+    let path = dir.path().join("corne.json");
+
+    write_layout(&path, "First");
+    let first = store.backup(&path).unwrap().unwrap();
+    write_layout(&path, "Second");
+    let second = store.backup(&path).unwrap().unwrap();
+
+    let backups = store.list(&path).unwrap();
+    assert_eq!(backups, vec![second.clone(), first.clone()]);
+    assert_eq!(store.load(&path, &first.id).unwrap().metadata.name, "First");
+    assert_eq!(
+        store.load(&path, &second.id).unwrap().metadata.name,
+        "Second"
+    );
+}
+
+#[test]
+fn test_backups_rotate_beyond_limit() {
+    let dir = TempDir::new().unwrap();
+    let store = BackupStore::new(dir.path().join("backups"));
+    let path = dir.path().join("corne.json");
+
+    for idx in 0..BACKUP_LIMIT + 3 {
+        write_layout(&path, &format!("Version {idx}"));
+        store.backup(&path).unwrap().unwrap();
+    }
+
+    let backups = store.list(&path).unwrap();
+    assert_eq!(backups.len(), BACKUP_LIMIT);
+    let newest = store.load(&path, &backups[0].id).unwrap();
+    assert_eq!(
+        newest.metadata.name,
+        format!("Version {}", BACKUP_LIMIT + 2)
+    );
+}
+
+#[test]
+fn test_same_name_in_different_directories_kept_apart() {
+    let dir = TempDir::new().unwrap();
+    let store = BackupStore::new(dir.path().join("backups"));
+    let a = dir.path().join("a").join("corne.json");
+    let b = dir.path().join("b").join("corne.json");
+    fs::create_dir_all(a.parent().unwrap()).unwrap();
+    fs::create_dir_all(b.parent().unwrap()).unwrap();
+
+    write_layout(&a, "A");
+    write_layout(&b, "B");
+    store.backup(&a).unwrap().unwrap();
+
+    assert_eq!(store.list(&a).unwrap().len(), 1);
+    assert!(store.list(&b).unwrap().is_empty());
+}
+
+#[test]
+fn test_load_rejects_unknown_ids() {
+    let dir = TempDir::new().unwrap();
+    let store = BackupStore::new(dir.path().join("backups"));
+    let path = dir.path().join("corne.json");
+    write_layout(&path, "First");
+    store.backup(&path).unwrap().unwrap();
+
+    assert!(store.load(&path, "../../corne.json").is_err());
+    assert!(store.load(&path, "20200101T000000.000000Z.json").is_err());
+}
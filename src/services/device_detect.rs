@@ -0,0 +1,305 @@
+//! Detection of connected keyboards by USB vendor and product ID.
+//!
+//! QMK keyboards declare their IDs in `info.json` or `keyboard.json`
+//! (`"usb": {"vid": "0x4653", "pid": "0x0001"}`), often in a parent
+//! directory shared by every revision. [`UsbIdResolver`] resolves them the
+//! way QMK merges those files; [`connected_devices`] lists what is plugged
+//! in:
+//! - Linux: `/sys/bus/usb/devices`.
+//! - macOS: `ioreg -p IOUSB`.
+//! - Elsewhere nothing is detected.
+//!
+//! Matching the two lets the onboarding wizard and `GET /api/keyboards`
+//! propose the keyboard that is actually connected. Several keyboards can
+//! share an ID (revisions, or QMK's placeholder `0xFEED`), so a device maps
+//! to a list of candidates.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// A USB vendor and product ID pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct UsbId {
+    /// Vendor ID
+    pub vid: u16,
+    /// Product ID
+    pub pid: u16,
+}
+
+impl std::fmt::Display for UsbId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04x}:{:04x}", self.vid, self.pid)
+    }
+}
+
+/// A device on the USB bus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UsbDevice {
+    /// Vendor and product ID
+    pub id: UsbId,
+    /// Manufacturer string, if the device reports one
+    pub manufacturer: Option<String>,
+    /// Product string, if the device reports one
+    pub product: Option<String>,
+}
+
+/// A connected device and the keyboards it could be.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DetectedKeyboard {
+    /// The device on the bus
+    pub device: UsbDevice,
+    /// Paths of the keyboards declaring the device's ID, sorted
+    pub keyboards: Vec<String>,
+}
+
+/// The `usb` block of an `info.json` or `keyboard.json`.
+#[derive(Debug, Default, Deserialize)]
+struct UsbJson {
+    vid: Option<String>,
+    pid: Option<String>,
+}
+
+/// The part of an `info.json` or `keyboard.json` read here.
+#[derive(Debug, Default, Deserialize)]
+struct UsbInfoJson {
+    #[serde(default)]
+    usb: Option<UsbJson>,
+}
+
+/// Resolves USB IDs of keyboards, reading each directory's JSON once.
+///
+/// QMK merges `info.json` and `keyboard.json` from the keyboards directory
+/// down to the keyboard, so the deepest file declaring `vid` (or `pid`)
+/// wins.
+#[derive(Debug, Default)]
+pub struct UsbIdResolver {
+    /// Vendor and product ID declared directly in each directory
+    declared: HashMap<PathBuf, (Option<u16>, Option<u16>)>,
+}
+
+impl UsbIdResolver {
+    /// Returns the USB ID of the keyboard at `keyboard_dir` below `base_dir`.
+    pub fn resolve(&mut self, base_dir: &Path, keyboard_dir: &Path) -> Option<UsbId> {
+        let (mut vid, mut pid) = (None, None);
+        let mut dir = Some(keyboard_dir);
+        while let Some(current) = dir.filter(|d| d.starts_with(base_dir) && *d != base_dir) {
+            let (dir_vid, dir_pid) = *self
+                .declared
+                .entry(current.to_path_buf())
+                .or_insert_with(|| declared_usb(current));
+            vid = vid.or(dir_vid);
+            pid = pid.or(dir_pid);
+            if vid.is_some() && pid.is_some() {
+                break;
+            }
+            dir = current.parent();
+        }
+        Some(UsbId {
+            vid: vid?,
+            pid: pid?,
+        })
+    }
+}
+
+/// Reads the vendor and product ID declared in one directory.
+fn declared_usb(dir: &Path) -> (Option<u16>, Option<u16>) {
+    let (mut vid, mut pid) = (None, None);
+    // keyboard.json is read last by QMK, so it wins over info.json
+    for file in ["keyboard.json", "info.json"] {
+        let Ok(content) = fs::read_to_string(dir.join(file)) else {
+            continue;
+        };
+        let Some(usb) = json5::from_str::<UsbInfoJson>(&content)
+            .ok()
+            .and_then(|info| info.usb)
+        else {
+            continue;
+        };
+        vid = vid.or_else(|| usb.vid.as_deref().and_then(parse_hex_id));
+        pid = pid.or_else(|| usb.pid.as_deref().and_then(parse_hex_id));
+    }
+    (vid, pid)
+}
+
+/// Parses an ID written as `0xFEED` (or without the prefix).
+#[must_use]
+pub fn parse_hex_id(value: &str) -> Option<u16> {
+    let value = value.trim();
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+    u16::from_str_radix(digits, 16).ok()
+}
+
+/// Lists the devices on the USB bus.
+#[must_use]
+pub fn connected_devices() -> Vec<UsbDevice> {
+    if cfg!(target_os = "linux") {
+        sysfs_devices(Path::new("/sys/bus/usb/devices"))
+    } else if cfg!(target_os = "macos") {
+        std::process::Command::new("ioreg")
+            .args(["-p", "IOUSB", "-l", "-w", "0"])
+            .output()
+            .map(|output| parse_ioreg(&String::from_utf8_lossy(&output.stdout)))
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Reads the devices under a sysfs `usb/devices` directory.
+///
+/// Interfaces (`1-1:1.0`) and hubs without IDs are skipped.
+#[must_use]
+pub fn sysfs_devices(devices_dir: &Path) -> Vec<UsbDevice> {
+    let Ok(entries) = fs::read_dir(devices_dir) else {
+        return Vec::new();
+    };
+    let read = |dir: &Path, name: &str| {
+        fs::read_to_string(dir.join(name))
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let mut devices: Vec<UsbDevice> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter_map(|dir| {
+            let vid = parse_hex_id(&read(&dir, "idVendor")?)?;
+            let pid = parse_hex_id(&read(&dir, "idProduct")?)?;
+            Some(UsbDevice {
+                id: UsbId { vid, pid },
+                manufacturer: read(&dir, "manufacturer"),
+                product: read(&dir, "product"),
+            })
+        })
+        .collect();
+    devices.sort_by_key(|device| device.id);
+    devices
+}
+
+/// Parses the devices from `ioreg -p IOUSB -l` output.
+///
+/// Each device starts with a `+-o` line followed by its properties.
+#[must_use]
+pub fn parse_ioreg(output: &str) -> Vec<UsbDevice> {
+    #[derive(Default)]
+    struct Properties {
+        vid: Option<u16>,
+        pid: Option<u16>,
+        manufacturer: Option<String>,
+        product: Option<String>,
+    }
+
+    impl Properties {
+        fn into_device(self) -> Option<UsbDevice> {
+            Some(UsbDevice {
+                id: UsbId {
+                    vid: self.vid?,
+                    pid: self.pid?,
+                },
+                manufacturer: self.manufacturer,
+                product: self.product,
+            })
+        }
+    }
+
+    let mut devices = Vec::new();
+    let mut current = Properties::default();
+    for line in output.lines() {
+        if line.contains("+-o ") {
+            devices.extend(std::mem::take(&mut current).into_device());
+            continue;
+        }
+        let Some((key, value)) = line.split_once(" = ") else {
+            continue;
+        };
+        let key = key.trim_start_matches(|c: char| c.is_whitespace() || c == '|');
+        let text = || Some(value.trim().trim_matches('"').to_string());
+        match key.trim() {
+            "\"idVendor\"" => current.vid = value.trim().parse().ok(),
+            "\"idProduct\"" => current.pid = value.trim().parse().ok(),
+            "\"USB Vendor Name\"" => current.manufacturer = text(),
+            "\"USB Product Name\"" => current.product = text(),
+            _ => {}
+        }
+    }
+    devices.extend(current.into_device());
+    devices.sort_by_key(|device| device.id);
+    devices
+}
+
+/// Matches `devices` against keyboards and their USB IDs.
+///
+/// Devices no keyboard declares (mice, hubs, ...) are left out.
+#[must_use]
+pub fn match_devices<'a>(
+    devices: &[UsbDevice],
+    keyboards: impl IntoIterator<Item = (&'a str, UsbId)>,
+) -> Vec<DetectedKeyboard> {
+    let mut by_id: HashMap<UsbId, Vec<String>> = HashMap::new();
+    for (path, id) in keyboards {
+        by_id.entry(id).or_default().push(path.to_string());
+    }
+
+    devices
+        .iter()
+        .filter_map(|device| {
+            let mut keyboards = by_id.get(&device.id)?.clone();
+            keyboards.sort();
+            Some(DetectedKeyboard {
+                device: device.clone(),
+                keyboards,
+            })
+        })
+        .collect()
+}
+
+/// Finds which of `keyboards` (paths as returned by
+/// [`crate::parser::keyboard_json::scan_keyboards`]) are plugged in.
+///
+/// Returns matching keyboard paths, without reading any keyboard files when
+/// no USB device is connected.
+#[must_use]
+pub fn detect_connected(
+    qmk_path: &Path,
+    keyboard_roots: &[PathBuf],
+    keyboards: &[String],
+) -> Vec<String> {
+    let devices = connected_devices();
+    if devices.is_empty() {
+        return Vec::new();
+    }
+
+    let keyboards_dir = qmk_path.join("keyboards");
+    let bases: Vec<&Path> = std::iter::once(keyboards_dir.as_path())
+        .chain(keyboard_roots.iter().map(PathBuf::as_path))
+        .collect();
+    let mut resolver = UsbIdResolver::default();
+    let ids: Vec<(&str, UsbId)> = keyboards
+        .iter()
+        .filter_map(|keyboard| {
+            let id = bases.iter().find_map(|base| {
+                let dir = base.join(keyboard);
+                dir.is_dir().then(|| resolver.resolve(base, &dir)).flatten()
+            })?;
+            Some((keyboard.as_str(), id))
+        })
+        .collect();
+
+    let mut connected: Vec<String> = match_devices(&devices, ids)
+        .into_iter()
+        .flat_map(|detected| detected.keyboards)
+        .collect();
+    connected.sort();
+    connected.dedup();
+    connected
+}
+
+#[cfg(test)]
+mod tests;
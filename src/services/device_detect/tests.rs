@@ -0,0 +1,168 @@
+//! Tests for services::device_detect.
+
+use super::*;
+use tempfile::TempDir;
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn device(vid: u16, pid: u16) -> UsbDevice {
+    UsbDevice {
+        id: UsbId { vid, pid },
+        manufacturer: None,
+        product: None,
+    }
+}
+
+#[test]
+fn test_parse_hex_id() {
+    assert_eq!(parse_hex_id("0xFEED"), Some(0xFEED));
+    assert_eq!(parse_hex_id(" 0X4653 "), Some(0x4653));
+    assert_eq!(parse_hex_id("046d"), Some(0x046D));
+    assert_eq!(parse_hex_id("0x10000"), None);
+    assert_eq!(parse_hex_id("keyboard"), None);
+}
+
+#[test]
+fn test_resolver_merges_parent_directories() {
+    let temp_dir = TempDir::new().unwrap();
+    let base = temp_dir.path();
+    write(
+        &base.join("crkbd/info.json"),
+        r#"{"usb": {"vid": "0x4653", "pid": "0x0001"}}"#,
+    );
+    write(
+        &base.join("crkbd/rev4/keyboard.json"),
+        r#"{"usb": {"pid": "0x0004"}, "layouts": {}}"#,
+    );
+    write(&base.join("crkbd/rev1/keyboard.json"), r#"{"layouts": {}}"#);
+    write(&base.join("nousb/keyboard.json"), r#"{"layouts": {}}"#);
+
+    let mut resolver = UsbIdResolver::default();
+    assert_eq!(
+        resolver.resolve(base, &base.join("crkbd/rev4")),
+        Some(UsbId {
+            vid: 0x4653,
+            pid: 0x0004
+        })
+    );
+    assert_eq!(
+        resolver.resolve(base, &base.join("crkbd/rev1")),
+        Some(UsbId {
+            vid: 0x4653,
+            pid: 0x0001
+        })
+    );
+    assert_eq!(resolver.resolve(base, &base.join("nousb")), None);
+}
+
+#[test]
+fn test_keyboard_json_wins_over_info_json() {
+    let temp_dir = TempDir::new().unwrap();
+    let base = temp_dir.path();
+    write(
+        &base.join("board/info.json"),
+        r#"{"usb": {"vid": "0x1111", "pid": "0x2222"}}"#,
+    );
+    write(
+        &base.join("board/keyboard.json"),
+        r#"{"usb": {"vid": "0x3333"}}"#,
+    );
+
+    assert_eq!(
+        UsbIdResolver::default().resolve(base, &base.join("board")),
+        Some(UsbId {
+            vid: 0x3333,
+            pid: 0x2222
+        })
+    );
+}
+
+#[test]
+fn test_sysfs_devices_skips_interfaces() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    write(&dir.join("1-2/idVendor"), "4653\n");
+    write(&dir.join("1-2/idProduct"), "0001\n");
+    write(&dir.join("1-2/manufacturer"), "foostan\n");
+    write(&dir.join("1-2/product"), "Corne\n");
+    write(&dir.join("1-1/idVendor"), "046d\n");
+    write(&dir.join("1-1/idProduct"), "c52b\n");
+    write(&dir.join("1-2:1.0/bInterfaceClass"), "03\n");
+
+    let devices = sysfs_devices(dir);
+
+    assert_eq!(devices.len(), 2);
+    assert_eq!(devices[0].id.to_string(), "046d:c52b");
+    assert_eq!(devices[0].product, None);
+    assert_eq!(devices[1].product.as_deref(), Some("Corne"));
+    assert_eq!(devices[1].manufacturer.as_deref(), Some("foostan"));
+    assert!(sysfs_devices(&dir.join("missing")).is_empty());
+}
+
+#[test]
+fn test_parse_ioreg() {
+    let output = r#"+-o Root  <class IORegistryEntry, id 0x100000100, retain 20>
+  +-o AppleT8103USBXHCI@00000000  <class AppleT8103USBXHCI, id 0x10000032c>
+  | +-o Corne@00100000  <class IOUSBHostDevice, id 0x100000a1b>
+  |     {
+  |       "USB Product Name" = "Corne"
+  |       "idProduct" = 1
+  |       "USB Vendor Name" = "foostan"
+  |       "idVendor" = 18003
+  |     }
+  | +-o USB Receiver@00200000  <class IOUSBHostDevice, id 0x100000a2c>
+  |     {
+  |       "idProduct" = 50475
+  |       "idVendor" = 1133
+  |     }
+"#;
+
+    let devices = parse_ioreg(output);
+
+    assert_eq!(devices.len(), 2);
+    assert_eq!(
+        devices[0].id,
+        UsbId {
+            vid: 1133,
+            pid: 50475
+        }
+    );
+    assert_eq!(
+        devices[1].id,
+        UsbId {
+            vid: 0x4653,
+            pid: 1
+        }
+    );
+    assert_eq!(devices[1].product.as_deref(), Some("Corne"));
+    assert_eq!(devices[1].manufacturer.as_deref(), Some("foostan"));
+}
+
+#[test]
+fn test_match_devices() {
+    let corne = UsbId {
+        vid: 0x4653,
+        pid: 0x0001,
+    };
+    let devices = vec![device(0x046D, 0xC52B), device(0x4653, 0x0001)];
+    let keyboards = vec![
+        ("crkbd/rev1", corne),
+        ("crkbd/r2g", corne),
+        (
+            "planck/rev6",
+            UsbId {
+                vid: 0x03A8,
+                pid: 0xA4F9,
+            },
+        ),
+    ];
+
+    let detected = match_devices(&devices, keyboards);
+
+    assert_eq!(detected.len(), 1);
+    assert_eq!(detected[0].device.id, corne);
+    assert_eq!(detected[0].keyboards, vec!["crkbd/r2g", "crkbd/rev1"]);
+}
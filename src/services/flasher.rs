@@ -0,0 +1,300 @@
+//! Firmware flashing: detect keyboards in bootloader mode and flash a build.
+//!
+//! Three bootloader families are supported, each paired with the artifact
+//! format QMK builds for it:
+//! - UF2 (RP2040 and friends): a mass-storage drive containing
+//!   `INFO_UF2.TXT`. Flashed by copying the `.uf2` onto the drive.
+//! - DFU (STM32 and similar): a device listed by `dfu-util -l`. Flashed from
+//!   the `.bin` with `dfu-util`.
+//! - Caterina (Pro Micro and other AVR109 boards): a USB serial port.
+//!   Flashed from the `.hex` with `avrdude`.
+
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// File that identifies a mounted UF2 bootloader drive.
+const UF2_INFO_FILE: &str = "INFO_UF2.TXT";
+
+/// Serial device name prefixes used by AVR109 bootloaders.
+const SERIAL_PORT_PREFIXES: &[&str] = &["ttyACM", "cu.usbmodem"];
+
+/// Flash address and options QMK uses for STM32 DFU bootloaders.
+const DFU_ARGS: &[&str] = &["-a", "0", "-s", "0x08000000:leave"];
+
+/// How often `wait_for_target` checks for a device.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A keyboard in bootloader mode that can be flashed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlashTarget {
+    /// Mounted UF2 mass-storage drive
+    Uf2Drive(PathBuf),
+    /// Device reachable with `dfu-util`
+    Dfu,
+    /// AVR109 serial bootloader flashed with `avrdude`
+    Avrdude {
+        /// Serial port of the bootloader
+        port: PathBuf,
+    },
+}
+
+impl FlashTarget {
+    /// Firmware file extension this bootloader accepts.
+    #[must_use]
+    pub const fn artifact_extension(&self) -> &'static str {
+        match self {
+            Self::Uf2Drive(_) => "uf2",
+            Self::Dfu => "bin",
+            Self::Avrdude { .. } => "hex",
+        }
+    }
+
+    /// Returns true if `artifact` has the format this bootloader accepts.
+    #[must_use]
+    pub fn accepts(&self, artifact: &Path) -> bool {
+        artifact
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case(self.artifact_extension()))
+    }
+}
+
+impl std::fmt::Display for FlashTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Uf2Drive(drive) => write!(f, "UF2 drive {}", drive.display()),
+            Self::Dfu => write!(f, "DFU device"),
+            Self::Avrdude { port } => write!(f, "serial bootloader {}", port.display()),
+        }
+    }
+}
+
+/// Detects bootloaders and flashes firmware onto them.
+#[derive(Debug, Clone)]
+pub struct Flasher {
+    /// Directories whose subdirectories are mounted drives
+    mount_roots: Vec<PathBuf>,
+    /// Directory holding serial device nodes
+    serial_dir: PathBuf,
+    /// Whether to ask `dfu-util` for DFU devices
+    probe_dfu: bool,
+}
+
+impl Flasher {
+    /// Creates a flasher that looks in the usual places on this system.
+    #[must_use]
+    pub fn system() -> Self {
+        let mut mount_roots = Vec::new();
+        if cfg!(target_os = "macos") {
+            mount_roots.push(PathBuf::from("/Volumes"));
+        } else if cfg!(windows) {
+            // Drive roots: treat every drive letter as a "mount" of itself
+            mount_roots.extend(('D'..='Z').map(|letter| PathBuf::from(format!("{letter}:\\"))));
+        } else {
+            if let Ok(user) = std::env::var("USER") {
+                mount_roots.push(Path::new("/media").join(&user));
+                mount_roots.push(Path::new("/run/media").join(&user));
+            }
+            mount_roots.push(PathBuf::from("/media"));
+            mount_roots.push(PathBuf::from("/mnt"));
+        }
+
+        Self {
+            mount_roots,
+            serial_dir: PathBuf::from("/dev"),
+            probe_dfu: true,
+        }
+    }
+
+    /// Creates a flasher that only looks in the given directories.
+    ///
+    /// DFU devices are not probed. Used by tests.
+    #[allow(dead_code)] // Used by tests
+    #[must_use]
+    pub fn with_paths(mount_roots: Vec<PathBuf>, serial_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            mount_roots,
+            serial_dir: serial_dir.into(),
+            probe_dfu: false,
+        }
+    }
+
+    /// Lists keyboards currently in bootloader mode.
+    #[must_use]
+    pub fn detect(&self) -> Vec<FlashTarget> {
+        let mut targets: Vec<FlashTarget> = self
+            .uf2_drives()
+            .into_iter()
+            .map(FlashTarget::Uf2Drive)
+            .collect();
+        if self.probe_dfu && dfu_device_present() {
+            targets.push(FlashTarget::Dfu);
+        }
+        targets.extend(
+            self.serial_ports()
+                .into_iter()
+                .map(|port| FlashTarget::Avrdude { port }),
+        );
+        targets
+    }
+
+    /// Picks the first detected bootloader that accepts one of `artifacts`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no keyboard in bootloader mode accepts any of the
+    /// artifacts.
+    pub fn find_target(&self, artifacts: &[PathBuf]) -> Result<(PathBuf, FlashTarget)> {
+        let targets = self.detect();
+        for target in &targets {
+            if let Some(artifact) = artifacts.iter().find(|a| target.accepts(a)) {
+                return Ok((artifact.clone(), target.clone()));
+            }
+        }
+        if targets.is_empty() {
+            bail!("No keyboard in bootloader mode found");
+        }
+        let found: Vec<String> = targets.iter().map(ToString::to_string).collect();
+        bail!(
+            "Found {} but no matching firmware file was built",
+            found.join(", ")
+        )
+    }
+
+    /// Waits up to `timeout` for a keyboard in bootloader mode that accepts
+    /// one of `artifacts`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last detection error once `timeout` has passed.
+    pub fn wait_for_target(
+        &self,
+        artifacts: &[PathBuf],
+        timeout: Duration,
+    ) -> Result<(PathBuf, FlashTarget)> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.find_target(artifacts) {
+                Ok(found) => return Ok(found),
+                Err(e) if Instant::now() >= deadline => return Err(e),
+                Err(_) => std::thread::sleep(POLL_INTERVAL),
+            }
+        }
+    }
+
+    /// Flashes `artifact` onto `target`, writing progress to `log`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the artifact does not match the bootloader, the
+    /// copy fails, or the flashing tool exits unsuccessfully.
+    pub fn flash(&self, artifact: &Path, target: &FlashTarget, log: &mut dyn Write) -> Result<()> {
+        if !target.accepts(artifact) {
+            bail!(
+                "{target} needs a .{} file, got {}",
+                target.artifact_extension(),
+                artifact.display()
+            );
+        }
+        writeln!(log, "Flashing {} to {target}", artifact.display())?;
+
+        match target {
+            FlashTarget::Uf2Drive(drive) => {
+                let filename = artifact
+                    .file_name()
+                    .context("Firmware path has no file name")?;
+                std::fs::copy(artifact, drive.join(filename))
+                    .with_context(|| format!("Failed to copy firmware to {}", drive.display()))?;
+            }
+            FlashTarget::Dfu => {
+                let mut cmd = Command::new("dfu-util");
+                cmd.args(DFU_ARGS).arg("-D").arg(artifact);
+                run_tool(&mut cmd, "dfu-util", log)?;
+            }
+            FlashTarget::Avrdude { port } => {
+                let mut cmd = Command::new("avrdude");
+                cmd.args(["-p", "atmega32u4", "-c", "avr109", "-P"])
+                    .arg(port)
+                    .arg("-U")
+                    .arg(format!("flash:w:{}:i", artifact.display()));
+                run_tool(&mut cmd, "avrdude", log)?;
+            }
+        }
+
+        writeln!(log, "Flash complete")?;
+        Ok(())
+    }
+
+    fn uf2_drives(&self) -> Vec<PathBuf> {
+        let mut drives = Vec::new();
+        for root in &self.mount_roots {
+            // A drive letter root is itself the drive
+            if root.join(UF2_INFO_FILE).is_file() {
+                drives.push(root.clone());
+                continue;
+            }
+            let Ok(entries) = std::fs::read_dir(root) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.join(UF2_INFO_FILE).is_file() && !drives.contains(&path) {
+                    drives.push(path);
+                }
+            }
+        }
+        drives.sort();
+        drives
+    }
+
+    fn serial_ports(&self) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(&self.serial_dir) else {
+            return Vec::new();
+        };
+        let mut ports: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                SERIAL_PORT_PREFIXES
+                    .iter()
+                    .any(|prefix| name.starts_with(prefix))
+            })
+            .map(|entry| entry.path())
+            .collect();
+        ports.sort();
+        ports
+    }
+}
+
+/// Returns true if `dfu-util -l` lists a DFU device.
+fn dfu_device_present() -> bool {
+    Command::new("dfu-util")
+        .arg("-l")
+        .output()
+        .is_ok_and(|output| parse_dfu_list(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Returns true if `dfu-util -l` output lists at least one device.
+fn parse_dfu_list(output: &str) -> bool {
+    output.lines().any(|line| line.starts_with("Found DFU:"))
+}
+
+/// Runs a flashing tool, copying its output to `log`.
+fn run_tool(cmd: &mut Command, name: &str, log: &mut dyn Write) -> Result<()> {
+    let output = cmd
+        .output()
+        .with_context(|| format!("Failed to run {name} - is it installed?"))?;
+    log.write_all(&output.stdout)?;
+    log.write_all(&output.stderr)?;
+    if !output.status.success() {
+        bail!("{name} exited with {}", output.status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,103 @@
+//! Tests for firmware flashing.
+
+use super::*;
+
+use tempfile::TempDir;
+
+/// A mount root with one UF2 drive and one ordinary drive, plus a serial
+/// directory with one bootloader port.
+fn fake_system() -> (TempDir, Flasher) {
+    let dir = TempDir::new().unwrap();
+    let mounts = dir.path().join("media");
+    std::fs::create_dir_all(mounts.join("RPI-RP2")).unwrap();
+    std::fs::write(mounts.join("RPI-RP2").join(UF2_INFO_FILE), "UF2 Bootloader").unwrap();
+    std::fs::create_dir_all(mounts.join("USB-STICK")).unwrap();
+
+    let dev = dir.path().join("dev");
+    std::fs::create_dir_all(&dev).unwrap();
+    std::fs::write(dev.join("ttyACM0"), "").unwrap();
+    std::fs::write(dev.join("ttyS0"), "").unwrap();
+
+    let flasher = Flasher::with_paths(vec![mounts], dev);
+    (dir, flasher)
+}
+
+#[test]
+fn test_detect_finds_uf2_drive_and_serial_port() {
+    let (dir, flasher) = fake_system();
+
+    assert_eq!(
+        flasher.detect(),
+        vec![
+            FlashTarget::Uf2Drive(dir.path().join("media").join("RPI-RP2")),
+            FlashTarget::Avrdude {
+                port: dir.path().join("dev").join("ttyACM0")
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_find_target_matches_artifact_format() {
+    let (dir, flasher) = fake_system();
+    let artifacts = vec![PathBuf::from("kb.hex"), PathBuf::from("kb.uf2")];
+
+    let (artifact, target) = flasher.find_target(&artifacts).unwrap();
+
+    assert_eq!(artifact, PathBuf::from("kb.uf2"));
+    assert_eq!(
+        target,
+        FlashTarget::Uf2Drive(dir.path().join("media").join("RPI-RP2"))
+    );
+}
+
+#[test]
+fn test_find_target_reports_missing_device_and_format() {
+    let dir = TempDir::new().unwrap();
+    let empty = Flasher::with_paths(vec![dir.path().to_path_buf()], dir.path());
+    let err = empty.find_target(&[PathBuf::from("kb.uf2")]).unwrap_err();
+    assert!(err.to_string().contains("No keyboard in bootloader mode"));
+
+    let (_dir, flasher) = fake_system();
+    let err = flasher.find_target(&[PathBuf::from("kb.bin")]).unwrap_err();
+    assert!(err.to_string().contains("no matching firmware"));
+}
+
+#[test]
+fn test_flash_copies_uf2_to_drive() {
+    let (dir, flasher) = fake_system();
+    let artifact = dir.path().join("corne.uf2");
+    std::fs::write(&artifact, b"firmware").unwrap();
+    let drive = dir.path().join("media").join("RPI-RP2");
+
+    let mut log = Vec::new();
+    flasher
+        .flash(&artifact, &FlashTarget::Uf2Drive(drive.clone()), &mut log)
+        .unwrap();
+
+    assert_eq!(std::fs::read(drive.join("corne.uf2")).unwrap(), b"firmware");
+    assert!(String::from_utf8(log).unwrap().contains("Flash complete"));
+}
+
+#[test]
+fn test_flash_rejects_wrong_format() {
+    let (dir, flasher) = fake_system();
+    let drive = dir.path().join("media").join("RPI-RP2");
+
+    let err = flasher
+        .flash(
+            Path::new("corne.hex"),
+            &FlashTarget::Uf2Drive(drive),
+            &mut Vec::new(),
+        )
+        .unwrap_err();
+
+    assert!(err.to_string().contains("needs a .uf2 file"));
+}
+
+#[test]
+fn test_parse_dfu_list() {
+    let output = "dfu-util 0.11\n\nFound DFU: [0483:df11] ver=2200, devnum=5, cfg=1, intf=0\n";
+    assert!(parse_dfu_list(output));
+    assert!(!parse_dfu_list("dfu-util 0.11\n"));
+}
@@ -0,0 +1,320 @@
+//! Layer fragments: a single layer exported with everything it references.
+//!
+//! A fragment bundles one layer together with the categories and tap dances
+//! its keys use, so it can be applied into a layer slot of another layout.
+//! Layer references (`MO(@id)`, `LT(@id, ...)`) are stored as layer indices,
+//! since layer IDs are specific to the source layout.
+//!
+//! Saved fragments live in `<config dir>/fragments/*.json`.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::keycode_db::KeycodeDb;
+use crate::models::{Category, Layer, Layout, TapDanceAction};
+
+/// A layer exported from a layout, with the definitions it depends on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerFragment {
+    /// Fragment name (defaults to the layer name)
+    pub name: String,
+    /// Name of the layout the layer was exported from
+    #[serde(default)]
+    pub source_layout: String,
+    /// When the fragment was exported
+    pub created: DateTime<Utc>,
+    /// The exported layer
+    pub layer: Layer,
+    /// Categories referenced by the layer or its keys
+    #[serde(default)]
+    pub categories: Vec<Category>,
+    /// Tap dances referenced by the layer's keys
+    #[serde(default)]
+    pub tap_dances: Vec<TapDanceAction>,
+}
+
+/// Outcome of applying a fragment into a layout.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FragmentApplyReport {
+    /// Number of keys copied into the target layer
+    pub keys_applied: usize,
+    /// Number of fragment keys without a matching position in the target layer
+    pub keys_skipped: usize,
+    /// Number of target keys left unchanged because they are locked
+    #[serde(default)]
+    pub keys_locked: usize,
+    /// IDs of categories added to the layout
+    pub categories_added: Vec<String>,
+    /// Names of tap dances added to the layout
+    pub tap_dances_added: Vec<String>,
+    /// Problems that did not prevent applying the fragment
+    pub warnings: Vec<String>,
+}
+
+impl FragmentApplyReport {
+    /// Returns a one-line summary for status messages.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!("{} key(s) applied", self.keys_applied)];
+        if self.keys_skipped > 0 {
+            parts.push(format!("{} skipped", self.keys_skipped));
+        }
+        if self.keys_locked > 0 {
+            parts.push(format!("{} locked", self.keys_locked));
+        }
+        let added = self.categories_added.len() + self.tap_dances_added.len();
+        if added > 0 {
+            parts.push(format!("{added} definition(s) added"));
+        }
+        if !self.warnings.is_empty() {
+            parts.push(format!("{} warning(s)", self.warnings.len()));
+        }
+        parts.join(", ")
+    }
+}
+
+impl LayerFragment {
+    /// Exports layer `layer_index` of `layout` as a fragment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layer does not exist.
+    pub fn extract(layout: &Layout, layer_index: usize, keycode_db: &KeycodeDb) -> Result<Self> {
+        let source = layout
+            .layers
+            .get(layer_index)
+            .with_context(|| format!("Invalid layer number: {layer_index}"))?;
+
+        let mut layer = source.clone();
+        for key in &mut layer.keys {
+            if keycode_db.parse_layer_keycode(&key.keycode).is_some() {
+                if let Some(resolved) = layout.resolve_layer_keycode(&key.keycode, keycode_db) {
+                    key.keycode = resolved;
+                }
+            }
+        }
+
+        let category_ids: BTreeSet<&str> = source
+            .category_id
+            .iter()
+            .chain(source.keys.iter().filter_map(|k| k.category_id.as_ref()))
+            .map(String::as_str)
+            .collect();
+        let tap_dance_names: BTreeSet<String> = source
+            .keys
+            .iter()
+            .filter_map(|k| keycode_db.parse_tap_dance_keycode(&k.keycode))
+            .collect();
+
+        Ok(Self {
+            name: source.name.clone(),
+            source_layout: layout.metadata.name.clone(),
+            created: Utc::now(),
+            layer,
+            categories: layout
+                .categories
+                .iter()
+                .filter(|c| category_ids.contains(c.id.as_str()))
+                .cloned()
+                .collect(),
+            tap_dances: layout
+                .tap_dances
+                .iter()
+                .filter(|td| tap_dance_names.contains(&td.name))
+                .cloned()
+                .collect(),
+        })
+    }
+
+    /// Applies the fragment into layer `layer_index` of `layout`.
+    ///
+    /// The target layer keeps its ID and number; its name, colors, category,
+    /// and the keys at positions present in the fragment are replaced, except
+    /// for locked keys, which are left untouched.
+    /// Missing categories and tap dances are added. Definitions that already
+    /// exist with different content are kept and reported as warnings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target layer does not exist.
+    pub fn apply(
+        &self,
+        layout: &mut Layout,
+        layer_index: usize,
+        keycode_db: &KeycodeDb,
+    ) -> Result<FragmentApplyReport> {
+        if layer_index >= layout.layers.len() {
+            anyhow::bail!("Invalid layer number: {layer_index}");
+        }
+
+        let mut report = FragmentApplyReport::default();
+
+        for category in &self.categories {
+            match layout.get_category(&category.id) {
+                None => {
+                    layout.categories.push(category.clone());
+                    report.categories_added.push(category.id.clone());
+                }
+                Some(existing) if existing != category => report.warnings.push(format!(
+                    "Category '{}' already exists with different settings; kept existing",
+                    category.id
+                )),
+                Some(_) => {}
+            }
+        }
+
+        for tap_dance in &self.tap_dances {
+            match layout
+                .tap_dances
+                .iter()
+                .find(|td| td.name == tap_dance.name)
+            {
+                None => {
+                    layout.tap_dances.push(tap_dance.clone());
+                    report.tap_dances_added.push(tap_dance.name.clone());
+                }
+                Some(existing) if existing != tap_dance => report.warnings.push(format!(
+                    "Tap dance '{}' already exists with a different definition; kept existing",
+                    tap_dance.name
+                )),
+                Some(_) => {}
+            }
+        }
+
+        let layer_count = layout.layers.len();
+        let target = &mut layout.layers[layer_index];
+        target.name.clone_from(&self.layer.name);
+        target.default_color = self.layer.default_color;
+        target.category_id.clone_from(&self.layer.category_id);
+        target.layer_colors_enabled = self.layer.layer_colors_enabled;
+        target.underglow_color = self.layer.underglow_color;
+
+        for key in &self.layer.keys {
+            let Some(target_key) = target.get_key_mut(key.position) else {
+                report.keys_skipped += 1;
+                continue;
+            };
+            if target_key.locked {
+                report.keys_locked += 1;
+                continue;
+            }
+            target_key.keycode.clone_from(&key.keycode);
+            target_key.label.clone_from(&key.label);
+            target_key.color_override = key.color_override;
+            target_key.category_id.clone_from(&key.category_id);
+            target_key.description.clone_from(&key.description);
+            report.keys_applied += 1;
+
+            let missing_layer = keycode_db
+                .parse_layer_keycode(&key.keycode)
+                .and_then(|(_, layer_ref, _)| layer_ref.parse::<usize>().ok())
+                .filter(|&idx| idx >= layer_count);
+            if let Some(idx) = missing_layer {
+                report.warnings.push(format!(
+                    "Key ({}, {}) references layer {idx}, which does not exist",
+                    key.position.row, key.position.col
+                ));
+            }
+        }
+
+        layout.metadata.touch();
+        Ok(report)
+    }
+
+    /// Loads a fragment from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read fragment: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse fragment: {}", path.display()))
+    }
+
+    /// Writes the fragment as JSON to `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize fragment")?;
+        fs::write(path, json)
+            .with_context(|| format!("Failed to write fragment: {}", path.display()))
+    }
+}
+
+/// Returns the directory where saved fragments are stored.
+///
+/// # Errors
+///
+/// Returns an error if the config directory cannot be determined.
+pub fn fragments_dir() -> Result<PathBuf> {
+    Ok(Config::config_dir()?.join("fragments"))
+}
+
+/// Saves `fragment` into `dir`, named after the fragment, and returns its path.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn save_to_library(fragment: &LayerFragment, dir: &Path) -> Result<PathBuf> {
+    let stem: String = fragment
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stem = if stem.is_empty() {
+        "fragment".into()
+    } else {
+        stem
+    };
+    let path = dir.join(format!("{stem}.json"));
+    fragment.save(&path)?;
+    Ok(path)
+}
+
+/// Lists the fragments saved in `dir`, sorted by name.
+///
+/// A missing directory yields an empty list; unreadable files are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the directory exists but cannot be read.
+pub fn list_library(dir: &Path) -> Result<Vec<(PathBuf, LayerFragment)>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read fragments directory: {}", dir.display()))?;
+    let mut fragments: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| LayerFragment::load(&path).ok().map(|f| (path, f)))
+        .collect();
+    fragments.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+
+    Ok(fragments)
+}
+
+#[cfg(test)]
+mod tests;
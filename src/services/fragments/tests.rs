@@ -0,0 +1,135 @@
+//! Tests for layer fragments.
+
+use super::*;
+
+use crate::models::{KeyDefinition, Position, RgbColor};
+use tempfile::TempDir;
+
+fn source_layout() -> Layout {
+    let mut layout = Layout::new("Source").unwrap();
+    layout
+        .add_category(Category::new("nav", "Navigation", RgbColor::new(0, 255, 0)).unwrap())
+        .unwrap();
+    layout
+        .add_category(Category::new("unused", "Unused", RgbColor::new(9, 9, 9)).unwrap())
+        .unwrap();
+    layout
+        .add_tap_dance(TapDanceAction::new("esc_caps", "KC_ESC"))
+        .unwrap();
+
+    let base = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    let base_id = base.id.clone();
+    let mut nav = Layer::new(1, "Nav", RgbColor::new(0, 0, 255)).unwrap();
+    nav.add_key(KeyDefinition::new(
+        Position::new(0, 0),
+        format!("MO(@{base_id})"),
+    ));
+    nav.add_key(KeyDefinition::new(Position::new(0, 1), "KC_UP").with_category("nav"));
+    nav.add_key(KeyDefinition::new(Position::new(0, 2), "TD(esc_caps)"));
+    layout.add_layer(base).unwrap();
+    layout.add_layer(nav).unwrap();
+    layout
+}
+
+fn target_layout() -> Layout {
+    let mut layout = Layout::new("Target").unwrap();
+    for (number, name) in [(0, "Main"), (1, "Empty")] {
+        let mut layer = Layer::new(number, name, RgbColor::new(10, 10, 10)).unwrap();
+        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
+        layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_TRNS"));
+        layout.add_layer(layer).unwrap();
+    }
+    layout
+}
+
+#[test]
+fn test_extract_collects_references_and_resolves_layer_ids() {
+    let db = KeycodeDb::load().unwrap();
+    let fragment = LayerFragment::extract(&source_layout(), 1, &db).unwrap();
+
+    assert_eq!(fragment.name, "Nav");
+    assert_eq!(fragment.source_layout, "Source");
+    assert_eq!(fragment.layer.keys[0].keycode, "MO(0)");
+    assert_eq!(fragment.categories.len(), 1);
+    assert_eq!(fragment.categories[0].id, "nav");
+    assert_eq!(fragment.tap_dances.len(), 1);
+    assert_eq!(fragment.tap_dances[0].name, "esc_caps");
+
+    assert!(LayerFragment::extract(&source_layout(), 5, &db).is_err());
+}
+
+#[test]
+fn test_apply_into_layer_slot() {
+    let db = KeycodeDb::load().unwrap();
+    let fragment = LayerFragment::extract(&source_layout(), 1, &db).unwrap();
+    let mut target = target_layout();
+    target
+        .add_tap_dance(TapDanceAction::new("esc_caps", "KC_GRV"))
+        .unwrap();
+    let target_id = target.layers[1].id.clone();
+
+    let report = fragment.apply(&mut target, 1, &db).unwrap();
+
+    let layer = &target.layers[1];
+    assert_eq!(layer.id, target_id, "target layer keeps its ID");
+    assert_eq!(layer.number, 1);
+    assert_eq!(layer.name, "Nav");
+    assert_eq!(layer.default_color, RgbColor::new(0, 0, 255));
+    assert_eq!(layer.keys[0].keycode, "MO(0)");
+    assert_eq!(layer.keys[1].keycode, "KC_UP");
+    assert_eq!(layer.keys[1].category_id.as_deref(), Some("nav"));
+
+    assert_eq!(report.keys_applied, 2);
+    assert_eq!(
+        report.keys_skipped, 1,
+        "(0, 2) does not exist in the target"
+    );
+    assert_eq!(report.categories_added, vec!["nav".to_string()]);
+    assert!(report.tap_dances_added.is_empty());
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("esc_caps"));
+    assert_eq!(
+        target.tap_dances[0].single_tap, "KC_GRV",
+        "existing tap dance is kept"
+    );
+
+    assert!(fragment.apply(&mut target, 2, &db).is_err());
+}
+
+#[test]
+fn test_apply_leaves_locked_keys_untouched() {
+    let db = KeycodeDb::load().unwrap();
+    let fragment = LayerFragment::extract(&source_layout(), 1, &db).unwrap();
+    let mut target = target_layout();
+    let locked = target.layers[1].get_key_mut(Position::new(0, 0)).unwrap();
+    locked.keycode = "QK_BOOT".to_string();
+    locked.locked = true;
+
+    let report = fragment.apply(&mut target, 1, &db).unwrap();
+
+    let layer = &target.layers[1];
+    assert_eq!(layer.keys[0].keycode, "QK_BOOT");
+    assert!(layer.keys[0].locked);
+    assert_eq!(layer.keys[1].keycode, "KC_UP");
+    assert_eq!(report.keys_applied, 1);
+    assert_eq!(report.keys_locked, 1);
+    assert!(report.summary().contains("1 locked"));
+}
+
+#[test]
+fn test_library_roundtrip() {
+    let db = KeycodeDb::load().unwrap();
+    let dir = TempDir::new().unwrap();
+    assert!(list_library(&dir.path().join("missing"))
+        .unwrap()
+        .is_empty());
+
+    let fragment = LayerFragment::extract(&source_layout(), 1, &db).unwrap();
+    let path = save_to_library(&fragment, dir.path()).unwrap();
+    assert_eq!(path.file_name().unwrap(), "nav.json");
+
+    let listed = list_library(dir.path()).unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].0, path);
+    assert_eq!(listed[0].1, fragment);
+}
@@ -4,15 +4,22 @@
 //! It handles parsing QMK JSON files, resolving keyboard variants, and creating
 //! visual layout mappings with RGB matrix support.
 
+use std::collections::HashSet;
+use std::path::Path;
+
 use anyhow::{Context, Result};
 
 use crate::{
     config::Config,
-    models::{KeyboardGeometry, LayoutMetadata, VisualLayoutMapping},
+    models::{
+        KeyDefinition, KeyboardGeometry, Layer, Layout, LayoutMetadata, Position,
+        VisualLayoutMapping,
+    },
     parser::keyboard_json::{
-        build_keyboard_geometry_with_rgb, build_matrix_to_led_map, parse_keyboard_info_json,
-        parse_variant_keyboard_json,
+        build_keyboard_geometry_with_rgb, build_led_geometry, keyboards_dir_for,
+        parse_keyboard_info_json, parse_variant_keyboard_json, QmkInfoJson,
     },
+    services::{share_bundle, split_leds},
 };
 
 /// Context required for building keyboard geometry.
@@ -41,6 +48,33 @@ pub struct GeometryResult {
     pub variant_path: String,
 }
 
+impl GeometryResult {
+    /// Wraps an already built geometry, such as a snapshot from a share
+    /// bundle.
+    #[must_use]
+    pub fn from_geometry(geometry: KeyboardGeometry) -> Self {
+        let mapping = VisualLayoutMapping::build(&geometry);
+        Self {
+            variant_path: geometry.keyboard_name.clone(),
+            geometry,
+            mapping,
+        }
+    }
+}
+
+/// Returns the geometry stored with a layout opened from `path`: the
+/// geometry of a share bundle, or the layout's geometry snapshot.
+///
+/// Used when the keyboard can't be built from a QMK tree.
+#[must_use]
+pub fn stored_geometry(layout: &Layout, path: &Path) -> Option<GeometryResult> {
+    share_bundle::bundled_geometry(path).or_else(|| {
+        layout
+            .snapshot_geometry()
+            .map(GeometryResult::from_geometry)
+    })
+}
+
 /// Extracts the base keyboard name from a keyboard path that may include a variant.
 ///
 /// # Examples
@@ -117,9 +151,7 @@ pub fn build_geometry_for_layout(
     // Get QMK path from config
     let qmk_path = context
         .config
-        .paths
-        .qmk_firmware
-        .as_ref()
+        .qmk_tree_path(context.metadata.qmk_tree.as_deref())
         .context("QMK firmware path not configured")?;
 
     // Get keyboard from metadata
@@ -133,9 +165,40 @@ pub fn build_geometry_for_layout(
     let base_keyboard = extract_base_keyboard(keyboard);
 
     // Parse keyboard info.json using the base keyboard path
-    let keyboard_info = parse_keyboard_info_json(qmk_path, &base_keyboard)
+    let keyboard_roots = &context.config.paths.keyboard_roots;
+    let keyboard_info = parse_keyboard_info_json(qmk_path, keyboard_roots, &base_keyboard)
         .context("Failed to parse keyboard info.json")?;
 
+    build_geometry_with_info(context, &keyboard_info, layout_name)
+}
+
+/// Builds keyboard geometry from an already parsed info.json.
+///
+/// Does the work of [`build_geometry_for_layout`] after parsing, so callers
+/// holding the merged info.json of the layout's base keyboard (see
+/// [`extract_base_keyboard`]) can skip reading it again.
+///
+/// # Errors
+///
+/// Returns error if the QMK path or keyboard is not configured, the layout
+/// is not in `keyboard_info`, or the geometry cannot be built.
+pub fn build_geometry_with_info(
+    context: GeometryContext<'_>,
+    keyboard_info: &QmkInfoJson,
+    layout_name: &str,
+) -> Result<GeometryResult> {
+    let qmk_path = context
+        .config
+        .qmk_tree_path(context.metadata.qmk_tree.as_deref())
+        .context("QMK firmware path not configured")?;
+    let keyboard = context
+        .metadata
+        .keyboard
+        .as_ref()
+        .context("Keyboard not specified in layout metadata")?;
+    let base_keyboard = extract_base_keyboard(keyboard);
+    let keyboard_roots = &context.config.paths.keyboard_roots;
+
     // Get the key count for the selected layout to determine the correct variant
     let layout_def = keyboard_info.layouts.get(layout_name).context(format!(
         "Layout '{}' not found in keyboard info.json",
@@ -148,22 +211,48 @@ pub fn build_geometry_for_layout(
     let variant_path = context
         .config
         .build
-        .determine_keyboard_variant(qmk_path, &base_keyboard, key_count)
+        .determine_keyboard_variant(
+            &keyboards_dir_for(qmk_path, keyboard_roots, &base_keyboard),
+            &base_keyboard,
+            key_count,
+        )
         .unwrap_or_else(|_| base_keyboard.clone());
 
     // Try to get RGB matrix mapping from the variant's keyboard.json
-    let matrix_to_led = parse_variant_keyboard_json(qmk_path, &variant_path)
-        .and_then(|variant| variant.rgb_matrix)
-        .map(|rgb_config| build_matrix_to_led_map(&rgb_config));
+    let variant_json = parse_variant_keyboard_json(qmk_path, keyboard_roots, &variant_path);
+    let rgb_matrix = variant_json
+        .as_ref()
+        .and_then(|variant| variant.rgb_matrix.clone());
+    // Split boards may need right-half LEDs moved to right-half rows
+    let split = variant_json
+        .as_ref()
+        .and_then(|variant| variant.split.as_ref())
+        .or(keyboard_info.split.as_ref());
+    let matrix_to_led = rgb_matrix.as_ref().map(|rgb| {
+        split_leds::split_matrix_to_led(rgb, split, split_leds::matrix_rows(layout_def))
+            .matrix_to_led
+    });
 
     // Build geometry from the selected layout with RGB matrix mapping if available
     let mut geometry = build_keyboard_geometry_with_rgb(
-        &keyboard_info,
+        keyboard_info,
         &base_keyboard,
         layout_name,
         matrix_to_led.as_ref(),
     )
     .context("Failed to build keyboard geometry")?;
+    geometry.leds = rgb_matrix
+        .as_ref()
+        .map(build_led_geometry)
+        .unwrap_or_default();
+
+    // The variant's keyboard.json refines the processor and default features
+    if let Some(variant) = variant_json {
+        if variant.processor.is_some() {
+            geometry.processor = variant.processor;
+        }
+        geometry.features.extend(variant.features);
+    }
 
     // Extract encoder count from keyboard info (capped at u8::MAX)
     geometry.encoder_count = keyboard_info
@@ -203,5 +292,158 @@ pub fn build_minimal_geometry() -> GeometryResult {
     }
 }
 
+/// Keys added and removed on one layer when fitting it to a geometry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerAdjustment {
+    /// Index of the adjusted layer
+    pub layer_index: usize,
+    /// Name of the adjusted layer
+    pub layer_name: String,
+    /// Positions that had no key and were filled with `KC_NO`
+    pub added: Vec<Position>,
+    /// Keys dropped because their position is not part of the geometry
+    pub removed: Vec<KeyDefinition>,
+}
+
+/// Report of all changes made when fitting layers to a geometry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeometryAdjustReport {
+    /// Per-layer changes (layers without changes are omitted)
+    pub layers: Vec<LayerAdjustment>,
+}
+
+impl GeometryAdjustReport {
+    /// Returns true if no layer was changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Total number of positions filled with `KC_NO`.
+    #[must_use]
+    pub fn added_count(&self) -> usize {
+        self.layers.iter().map(|l| l.added.len()).sum()
+    }
+
+    /// Total number of keys dropped.
+    #[must_use]
+    pub fn removed_count(&self) -> usize {
+        self.layers.iter().map(|l| l.removed.len()).sum()
+    }
+
+    /// Appends the changes of a later adjustment.
+    pub fn merge(&mut self, other: Self) {
+        self.layers.extend(other.layers);
+    }
+
+    /// One-line summary for status messages.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        format!(
+            "Layout adjusted to keyboard geometry: {} key(s) added, {} removed on {} layer(s)",
+            self.added_count(),
+            self.removed_count(),
+            self.layers.len()
+        )
+    }
+}
+
+/// Fits every layer to `valid_positions`.
+///
+/// Keys outside the geometry are dropped and missing positions are filled with
+/// `KC_NO`. Existing keys at valid positions are preserved. Returns what was
+/// changed so callers can present it for review.
+pub fn adjust_layers_to_positions(
+    layers: &mut [Layer],
+    valid_positions: &HashSet<Position>,
+) -> GeometryAdjustReport {
+    let mut sorted_positions: Vec<Position> = valid_positions.iter().copied().collect();
+    sorted_positions.sort_by_key(|pos| (pos.row, pos.col));
+
+    let mut report = GeometryAdjustReport::default();
+
+    for (layer_index, layer) in layers.iter_mut().enumerate() {
+        let (kept, removed): (Vec<_>, Vec<_>) = std::mem::take(&mut layer.keys)
+            .into_iter()
+            .partition(|key| valid_positions.contains(&key.position));
+        layer.keys = kept;
+
+        let existing: HashSet<Position> = layer.keys.iter().map(|k| k.position).collect();
+        let added: Vec<Position> = sorted_positions
+            .iter()
+            .filter(|pos| !existing.contains(pos))
+            .copied()
+            .collect();
+
+        for pos in &added {
+            layer.add_key(KeyDefinition::new(*pos, "KC_NO"));
+        }
+
+        if !added.is_empty() || !removed.is_empty() {
+            report.layers.push(LayerAdjustment {
+                layer_index,
+                layer_name: layer.name.clone(),
+                added,
+                removed,
+            });
+        }
+    }
+
+    report
+}
+
+/// A layout prepared for firmware generation on one of its keyboard targets.
+#[derive(Debug)]
+pub struct TargetBuild {
+    /// The layout as seen by the target (see [`Layout::for_target`])
+    pub layout: Layout,
+    /// Geometry of the target keyboard
+    pub geometry: KeyboardGeometry,
+    /// Visual mapping of the target keyboard
+    pub mapping: VisualLayoutMapping,
+    /// Keys dropped or filled with `KC_NO` to fit the target's geometry
+    pub adjustments: GeometryAdjustReport,
+}
+
+/// Prepares `layout` for generating firmware for its keyboard target `target`.
+///
+/// Keys are moved to their positions on the target, then every layer is
+/// fitted to the target's geometry.
+///
+/// # Errors
+///
+/// Returns an error if the target doesn't exist, its remapping puts two keys
+/// on one position, or its geometry can't be built.
+pub fn build_target(config: &Config, layout: &Layout, target: &str) -> Result<TargetBuild> {
+    let mut layout = layout.for_target(target)?;
+    let layout_variant = layout
+        .metadata
+        .layout_variant
+        .clone()
+        .context("Keyboard target has no layout variant")?;
+    let result = build_geometry_for_layout(
+        GeometryContext {
+            config,
+            metadata: &layout.metadata,
+        },
+        &layout_variant,
+    )
+    .with_context(|| format!("Failed to build geometry for target '{target}'"))?;
+
+    let valid_positions: HashSet<Position> = result
+        .mapping
+        .get_all_visual_positions()
+        .into_iter()
+        .collect();
+    let adjustments = adjust_layers_to_positions(&mut layout.layers, &valid_positions);
+
+    Ok(TargetBuild {
+        layout,
+        geometry: result.geometry,
+        mapping: result.mapping,
+        adjustments,
+    })
+}
+
 #[cfg(test)]
 mod tests;
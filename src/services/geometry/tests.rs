@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_extract_base_keyboard() {
     // With standard variant names
@@ -96,3 +94,52 @@ fn test_build_minimal_geometry() {
     assert_eq!(result.geometry.matrix_cols, 0);
     assert_eq!(result.variant_path, "");
 }
+
+#[test]
+fn test_adjust_layers_to_positions_reports_changes() {
+    use crate::models::RgbColor;
+
+    let mut base = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    base.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    base.add_key(KeyDefinition::new(Position::new(0, 5), "KC_Z"));
+    let mut nav = Layer::new(1, "Nav", RgbColor::new(0, 0, 255)).unwrap();
+    nav.add_key(KeyDefinition::new(Position::new(0, 0), "KC_LEFT"));
+    nav.add_key(KeyDefinition::new(Position::new(0, 1), "KC_RGHT"));
+    let mut layers = vec![base, nav];
+
+    let valid: HashSet<Position> = [Position::new(0, 0), Position::new(0, 1)]
+        .into_iter()
+        .collect();
+    let report = adjust_layers_to_positions(&mut layers, &valid);
+
+    // Nav already matches the geometry, so only Base is reported
+    assert_eq!(report.layers.len(), 1);
+    let base_report = &report.layers[0];
+    assert_eq!(base_report.layer_index, 0);
+    assert_eq!(base_report.layer_name, "Base");
+    assert_eq!(base_report.added, vec![Position::new(0, 1)]);
+    assert_eq!(base_report.removed.len(), 1);
+    assert_eq!(base_report.removed[0].keycode, "KC_Z");
+    assert_eq!(report.added_count(), 1);
+    assert_eq!(report.removed_count(), 1);
+
+    assert_eq!(layers[0].keys.len(), 2);
+    assert!(layers[0]
+        .keys
+        .iter()
+        .any(|k| k.position == Position::new(0, 1) && k.keycode == "KC_NO"));
+    assert_eq!(layers[1].keys.len(), 2);
+}
+
+#[test]
+fn test_adjust_layers_to_positions_no_changes() {
+    use crate::models::RgbColor;
+
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    let mut layers = vec![layer];
+
+    let valid: HashSet<Position> = std::iter::once(Position::new(0, 0)).collect();
+    let report = adjust_layers_to_positions(&mut layers, &valid);
+    assert!(report.is_empty());
+}
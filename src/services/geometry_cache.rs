@@ -0,0 +1,271 @@
+//! Persistent cache of parsed keyboard files.
+//!
+//! Merging a keyboard's info.json files and building its geometry happens on
+//! every TUI launch and layout variant switch, and takes noticeable time on
+//! large keyboards. [`GeometryCache`] saves the parsed info.json and each
+//! built geometry to `geometry_cache/` in the config directory, one file per
+//! keyboard.
+//!
+//! An entry records the modification time of every JSON file under the
+//! keyboard's top-level directory (e.g. `keyboards/crkbd/` for
+//! `crkbd/rev1`). It is only used while those files, the QMK path and the
+//! keyboard roots are unchanged; otherwise the files are parsed again.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::models::{KeyboardGeometry, VisualLayoutMapping};
+use crate::parser::keyboard_json::{keyboards_dir_for, parse_keyboard_info_json, QmkInfoJson};
+use crate::services::geometry::{
+    build_geometry_with_info, extract_base_keyboard, GeometryContext, GeometryResult,
+};
+
+/// Bumped whenever the cached types change shape.
+const CACHE_VERSION: u32 = 2;
+
+/// Directory inside the config directory that holds the cache.
+const CACHE_DIR: &str = "geometry_cache";
+
+/// Modification time of one keyboard source file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceStamp {
+    path: PathBuf,
+    /// Nanoseconds since the Unix epoch
+    modified_ns: u64,
+}
+
+/// Geometry built for one layout variant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedLayout {
+    variant_path: String,
+    geometry: KeyboardGeometry,
+}
+
+/// Everything cached for one keyboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    version: u32,
+    qmk_path: PathBuf,
+    keyboard_roots: Vec<PathBuf>,
+    sources: Vec<SourceStamp>,
+    info: QmkInfoJson,
+    #[serde(default)]
+    layouts: BTreeMap<String, CachedLayout>,
+}
+
+/// On-disk cache of parsed info.json files and built geometries.
+#[derive(Debug, Clone)]
+pub struct GeometryCache {
+    dir: PathBuf,
+}
+
+impl GeometryCache {
+    /// Creates a cache stored in `dir`.
+    #[must_use]
+    pub const fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Opens the cache in the config directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory cannot be determined.
+    pub fn open() -> Result<Self> {
+        Ok(Self::new(Config::config_dir()?.join(CACHE_DIR)))
+    }
+
+    /// Returns the merged info.json of `keyboard`, parsing it on a miss.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the info.json cannot be parsed.
+    pub fn keyboard_info(
+        &self,
+        qmk_path: &Path,
+        keyboard_roots: &[PathBuf],
+        keyboard: &str,
+    ) -> Result<QmkInfoJson> {
+        Ok(self.entry(qmk_path, keyboard_roots, keyboard)?.info)
+    }
+
+    /// Same as [`crate::services::geometry::build_geometry_for_layout`], but
+    /// reuses the parsed info.json and geometry from the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `build_geometry_for_layout`.
+    pub fn build_for_layout(
+        &self,
+        context: GeometryContext<'_>,
+        layout_name: &str,
+    ) -> Result<GeometryResult> {
+        let qmk_path = context
+            .config
+            .qmk_tree_path(context.metadata.qmk_tree.as_deref())
+            .context("QMK firmware path not configured")?;
+        let keyboard = context
+            .metadata
+            .keyboard
+            .as_ref()
+            .context("Keyboard not specified in layout metadata")?;
+        let base_keyboard = extract_base_keyboard(keyboard);
+        let keyboard_roots = &context.config.paths.keyboard_roots;
+
+        let mut entry = self
+            .entry(qmk_path, keyboard_roots, &base_keyboard)
+            .context("Failed to parse keyboard info.json")?;
+        if let Some(cached) = entry.layouts.get(layout_name) {
+            return Ok(GeometryResult {
+                mapping: VisualLayoutMapping::build(&cached.geometry),
+                geometry: cached.geometry.clone(),
+                variant_path: cached.variant_path.clone(),
+            });
+        }
+
+        let result = build_geometry_with_info(context, &entry.info, layout_name)?;
+        entry.layouts.insert(
+            layout_name.to_string(),
+            CachedLayout {
+                variant_path: result.variant_path.clone(),
+                geometry: result.geometry.clone(),
+            },
+        );
+        self.store(&base_keyboard, &entry);
+        Ok(result)
+    }
+
+    /// Returns the valid cache entry for `keyboard`, or parses its info.json
+    /// into a new one.
+    fn entry(
+        &self,
+        qmk_path: &Path,
+        keyboard_roots: &[PathBuf],
+        keyboard: &str,
+    ) -> Result<CacheEntry> {
+        let sources = source_stamps(qmk_path, keyboard_roots, keyboard);
+        if let Some(entry) = self.load(keyboard) {
+            if entry.version == CACHE_VERSION
+                && entry.qmk_path == qmk_path
+                && entry.keyboard_roots == keyboard_roots
+                && entry.sources == sources
+            {
+                return Ok(entry);
+            }
+        }
+
+        let entry = CacheEntry {
+            version: CACHE_VERSION,
+            qmk_path: qmk_path.to_path_buf(),
+            keyboard_roots: keyboard_roots.to_vec(),
+            sources,
+            info: parse_keyboard_info_json(qmk_path, keyboard_roots, keyboard)?,
+            layouts: BTreeMap::new(),
+        };
+        self.store(keyboard, &entry);
+        Ok(entry)
+    }
+
+    fn entry_path(&self, keyboard: &str) -> PathBuf {
+        self.dir
+            .join(format!("{}.json", keyboard.replace('/', "__")))
+    }
+
+    fn load(&self, keyboard: &str) -> Option<CacheEntry> {
+        let content = fs::read_to_string(self.entry_path(keyboard)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Saves `entry`. The cache is only an optimization, so failures are
+    /// ignored and the files are parsed again next time.
+    fn store(&self, keyboard: &str, entry: &CacheEntry) {
+        let Ok(json) = serde_json::to_string(entry) else {
+            return;
+        };
+        if fs::create_dir_all(&self.dir).is_ok() {
+            let _ = fs::write(self.entry_path(keyboard), json);
+        }
+    }
+}
+
+/// Builds geometry like
+/// [`build_geometry_for_layout`](crate::services::geometry::build_geometry_for_layout),
+/// going through the cache in the config directory when it is available.
+///
+/// # Errors
+///
+/// Returns the errors of `build_geometry_for_layout`.
+pub fn build_geometry_for_layout_cached(
+    context: GeometryContext<'_>,
+    layout_name: &str,
+) -> Result<GeometryResult> {
+    match GeometryCache::open() {
+        Ok(cache) => cache.build_for_layout(context, layout_name),
+        Err(_) => crate::services::geometry::build_geometry_for_layout(context, layout_name),
+    }
+}
+
+/// Returns the merged info.json of `keyboard`, going through the cache in the
+/// config directory when it is available.
+///
+/// # Errors
+///
+/// Returns an error if the info.json cannot be parsed.
+pub fn keyboard_info_cached(
+    qmk_path: &Path,
+    keyboard_roots: &[PathBuf],
+    keyboard: &str,
+) -> Result<QmkInfoJson> {
+    match GeometryCache::open() {
+        Ok(cache) => cache.keyboard_info(qmk_path, keyboard_roots, keyboard),
+        Err(_) => parse_keyboard_info_json(qmk_path, keyboard_roots, keyboard),
+    }
+}
+
+/// Stamps of the JSON files under the keyboard's top-level directory,
+/// sorted by path. Keymaps are skipped.
+pub fn source_stamps(
+    qmk_path: &Path,
+    keyboard_roots: &[PathBuf],
+    keyboard: &str,
+) -> Vec<SourceStamp> {
+    let top_level = keyboard.split('/').next().unwrap_or(keyboard);
+    let dir = keyboards_dir_for(qmk_path, keyboard_roots, keyboard).join(top_level);
+    let mut stamps = Vec::new();
+    collect_stamps(&dir, &mut stamps);
+    stamps.sort_by(|a, b| a.path.cmp(&b.path));
+    stamps
+}
+
+fn collect_stamps(dir: &Path, stamps: &mut Vec<SourceStamp>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            if entry.file_name() != "keymaps" {
+                collect_stamps(&path, stamps);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            let modified_ns = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |age| u64::try_from(age.as_nanos()).unwrap_or(u64::MAX));
+            stamps.push(SourceStamp { path, modified_ns });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
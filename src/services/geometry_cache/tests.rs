@@ -0,0 +1,156 @@
+//! Tests for services::geometry_cache.
+
+use super::*;
+use crate::models::LayoutMetadata;
+use std::time::{Duration, SystemTime};
+use tempfile::TempDir;
+
+const INFO: &str = r#"{
+    "keyboard_name": "Test",
+    "layouts": {"LAYOUT": {"layout": [
+        {"matrix": [0, 0], "x": 0, "y": 0},
+        {"matrix": [0, 1], "x": 1, "y": 0}
+    ]}}
+}"#;
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn setup() -> (TempDir, TempDir, GeometryCache) {
+    let qmk = TempDir::new().unwrap();
+    write(&qmk.path().join("keyboards/testkb/info.json"), INFO);
+    let cache_dir = TempDir::new().unwrap();
+    let cache = GeometryCache::new(cache_dir.path().join(CACHE_DIR));
+    (qmk, cache_dir, cache)
+}
+
+/// Rewrites the cached info so a hit can be told apart from a fresh parse.
+fn tamper_keyboard_name(cache: &GeometryCache, keyboard: &str) {
+    let mut entry = cache.load(keyboard).unwrap();
+    entry.info.keyboard_name = Some("Cached".to_string());
+    cache.store(keyboard, &entry);
+}
+
+#[test]
+fn test_keyboard_info_reuses_entry_while_sources_unchanged() {
+    let (qmk, _dir, cache) = setup();
+
+    let info = cache.keyboard_info(qmk.path(), &[], "testkb").unwrap();
+    assert_eq!(info.keyboard_name.as_deref(), Some("Test"));
+
+    tamper_keyboard_name(&cache, "testkb");
+    let info = cache.keyboard_info(qmk.path(), &[], "testkb").unwrap();
+    assert_eq!(info.keyboard_name.as_deref(), Some("Cached"));
+}
+
+#[test]
+fn test_modified_source_invalidates_entry() {
+    let (qmk, _dir, cache) = setup();
+    cache.keyboard_info(qmk.path(), &[], "testkb").unwrap();
+    tamper_keyboard_name(&cache, "testkb");
+
+    let info_path = qmk.path().join("keyboards/testkb/info.json");
+    let file = fs::File::options().write(true).open(&info_path).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_mins(1))
+        .unwrap();
+
+    let info = cache.keyboard_info(qmk.path(), &[], "testkb").unwrap();
+    assert_eq!(info.keyboard_name.as_deref(), Some("Test"));
+}
+
+#[test]
+fn test_other_qmk_path_invalidates_entry() {
+    let (qmk, _dir, cache) = setup();
+    cache.keyboard_info(qmk.path(), &[], "testkb").unwrap();
+    tamper_keyboard_name(&cache, "testkb");
+
+    let other = TempDir::new().unwrap();
+    write(&other.path().join("keyboards/testkb/info.json"), INFO);
+
+    let info = cache.keyboard_info(other.path(), &[], "testkb").unwrap();
+    assert_eq!(info.keyboard_name.as_deref(), Some("Test"));
+}
+
+#[test]
+fn test_build_for_layout_caches_geometry() {
+    let (qmk, _dir, cache) = setup();
+    let mut config = Config::default();
+    config.paths.qmk_firmware = Some(qmk.path().to_path_buf());
+    let metadata = LayoutMetadata {
+        keyboard: Some("testkb".to_string()),
+        ..LayoutMetadata::default()
+    };
+    let context = || GeometryContext {
+        config: &config,
+        metadata: &metadata,
+    };
+
+    let built = cache.build_for_layout(context(), "LAYOUT").unwrap();
+    assert!(cache.load("testkb").unwrap().layouts.contains_key("LAYOUT"));
+
+    let cached = cache.build_for_layout(context(), "LAYOUT").unwrap();
+    assert_eq!(cached.geometry, built.geometry);
+    assert_eq!(cached.variant_path, built.variant_path);
+    assert_eq!(
+        cached.mapping.get_all_visual_positions(),
+        built.mapping.get_all_visual_positions()
+    );
+}
+
+#[test]
+fn test_build_for_layout_uses_selected_qmk_tree() {
+    let (qmk, _dir, cache) = setup();
+    let fork = TempDir::new().unwrap();
+    write(
+        &fork.path().join("keyboards/testkb/info.json"),
+        &INFO.replace(
+            r#"{"matrix": [0, 1], "x": 1, "y": 0}"#,
+            r#"{"matrix": [0, 1], "x": 1, "y": 0}, {"matrix": [0, 2], "x": 2, "y": 0}"#,
+        ),
+    );
+    let mut config = Config::default();
+    config.paths.qmk_firmware = Some(qmk.path().to_path_buf());
+    config.paths.qmk_trees = vec![crate::config::QmkTree {
+        name: "fork".to_string(),
+        path: fork.path().to_path_buf(),
+    }];
+    let mut metadata = LayoutMetadata {
+        keyboard: Some("testkb".to_string()),
+        ..LayoutMetadata::default()
+    };
+
+    let upstream = cache
+        .build_for_layout(
+            GeometryContext {
+                config: &config,
+                metadata: &metadata,
+            },
+            "LAYOUT",
+        )
+        .unwrap();
+    assert_eq!(upstream.geometry.keys.len(), 2);
+
+    metadata.qmk_tree = Some("fork".to_string());
+    let forked = cache
+        .build_for_layout(
+            GeometryContext {
+                config: &config,
+                metadata: &metadata,
+            },
+            "LAYOUT",
+        )
+        .unwrap();
+    assert_eq!(forked.geometry.keys.len(), 3);
+}
+
+#[test]
+fn test_unreadable_entry_is_a_miss() {
+    let (qmk, _dir, cache) = setup();
+    write(&cache.entry_path("testkb"), "not json");
+
+    let info = cache.keyboard_info(qmk.path(), &[], "testkb").unwrap();
+    assert_eq!(info.keyboard_name.as_deref(), Some("Test"));
+    assert!(cache.load("testkb").is_some());
+}
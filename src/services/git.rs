@@ -0,0 +1,272 @@
+//! Git integration for layouts kept in a git repository.
+//!
+//! Runs the `git` command line tool, so nothing here is active without git
+//! installed or outside a work tree. [`GitRepo::discover`] finds the
+//! repository containing a layout file; from there the TUI shows whether the
+//! file is committed, can commit it on save (`git.auto_commit`), and the web
+//! API lists the commits that touched it.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Field separator in `git log` output (ASCII unit separator).
+#[allow(dead_code)] // Used by the web history endpoint (lib target only)
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// Maximum number of change lines listed in a generated commit message.
+const MAX_MESSAGE_CHANGES: usize = 20;
+
+/// State of a layout file in its repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitFileStatus {
+    /// Not tracked by git yet
+    Untracked,
+    /// Has changes that are not committed
+    Modified,
+    /// Identical to the last commit
+    Committed,
+}
+
+impl std::fmt::Display for GitFileStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Untracked => write!(f, "untracked"),
+            Self::Modified => write!(f, "modified"),
+            Self::Committed => write!(f, "committed"),
+        }
+    }
+}
+
+/// A commit that touched a layout file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[allow(dead_code)] // Used by the web history endpoint (lib target only)
+pub struct GitCommit {
+    /// Full commit hash
+    pub hash: String,
+    /// Author name
+    pub author: String,
+    /// Author date
+    pub date: DateTime<Utc>,
+    /// First line of the commit message
+    pub summary: String,
+}
+
+/// A git work tree containing layout files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitRepo {
+    root: PathBuf,
+}
+
+impl GitRepo {
+    /// Finds the work tree containing `path` (a file or directory).
+    ///
+    /// Returns None outside a work tree or if git is not installed.
+    #[must_use]
+    pub fn discover(path: &Path) -> Option<Self> {
+        let dir = if path.is_dir() { path } else { path.parent()? };
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["rev-parse", "--show-toplevel"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let root = String::from_utf8(output.stdout).ok()?;
+        Some(Self {
+            root: PathBuf::from(root.trim_end_matches(['\n', '\r'])),
+        })
+    }
+
+    /// Returns the checked-out branch, or the short commit hash for a
+    /// detached HEAD.
+    ///
+    /// Returns None if git fails, e.g. for a detached HEAD in a repository
+    /// without commits.
+    #[must_use]
+    pub fn branch(&self) -> Option<String> {
+        let output = self
+            .run(&[
+                "symbolic-ref".as_ref(),
+                "--quiet".as_ref(),
+                "--short".as_ref(),
+                "HEAD".as_ref(),
+            ])
+            .or_else(|_| self.run(&["rev-parse".as_ref(), "--short".as_ref(), "HEAD".as_ref()]))
+            .ok()?;
+        let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!branch.is_empty()).then_some(branch)
+    }
+
+    /// Returns whether `file` is untracked, modified, or committed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if git fails.
+    pub fn file_status(&self, file: &Path) -> Result<GitFileStatus> {
+        let file = &pathspec(file);
+        let output = self.run(&[
+            "status".as_ref(),
+            "--porcelain".as_ref(),
+            "--untracked-files=all".as_ref(),
+            "--".as_ref(),
+            file.as_os_str(),
+        ])?;
+        let status = String::from_utf8_lossy(&output.stdout);
+        Ok(match status.lines().next() {
+            None => GitFileStatus::Committed,
+            Some(line) if line.starts_with("??") => GitFileStatus::Untracked,
+            Some(_) => GitFileStatus::Modified,
+        })
+    }
+
+    /// Commits the current contents of `file` alone, leaving anything else
+    /// that is staged untouched.
+    ///
+    /// Returns the new commit's hash, or None if the file has no changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if git fails (e.g. no author identity is configured).
+    pub fn commit_file(&self, file: &Path, message: &str) -> Result<Option<String>> {
+        let file = &pathspec(file);
+        if self.file_status(file)? == GitFileStatus::Committed {
+            return Ok(None);
+        }
+        self.run(&["add".as_ref(), "--".as_ref(), file.as_os_str()])?;
+        self.run(&[
+            "commit".as_ref(),
+            "--quiet".as_ref(),
+            "--message".as_ref(),
+            message.as_ref(),
+            "--only".as_ref(),
+            "--".as_ref(),
+            file.as_os_str(),
+        ])?;
+        let output = self.run(&["rev-parse".as_ref(), "HEAD".as_ref()])?;
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    }
+
+    /// Lists the commits that touched `file`, newest first, following renames.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if git fails.
+    #[allow(dead_code)] // Used by the web history endpoint (lib target only)
+    pub fn log(&self, file: &Path, limit: usize) -> Result<Vec<GitCommit>> {
+        let file = &pathspec(file);
+        // A repository without commits has no history
+        if self
+            .run(&[
+                "rev-parse".as_ref(),
+                "--verify".as_ref(),
+                "--quiet".as_ref(),
+                "HEAD".as_ref(),
+            ])
+            .is_err()
+        {
+            return Ok(Vec::new());
+        }
+        let format =
+            format!("--format=%H{FIELD_SEPARATOR}%an{FIELD_SEPARATOR}%aI{FIELD_SEPARATOR}%s");
+        let limit = format!("--max-count={limit}");
+        let output = self.run(&[
+            "log".as_ref(),
+            "--follow".as_ref(),
+            limit.as_ref(),
+            format.as_ref(),
+            "--".as_ref(),
+            file.as_os_str(),
+        ])?;
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(parse_log_line)
+            .collect())
+    }
+
+    /// Runs git in the work tree, failing on a non-zero exit.
+    fn run(&self, args: &[&std::ffi::OsStr]) -> Result<Output> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(args)
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!(
+                "git {} failed: {}",
+                args[0].to_string_lossy(),
+                stderr.trim()
+            );
+        }
+        Ok(output)
+    }
+}
+
+/// Resolves symlinks in `file` so it matches the canonical work tree root
+/// git reports (e.g. `/tmp` on macOS).
+fn pathspec(file: &Path) -> PathBuf {
+    file.canonicalize().unwrap_or_else(|_| file.to_path_buf())
+}
+
+/// Generates the message of an automatic commit of `filename`, listing the
+/// `changes` made since the previous save (see
+/// [`diff_layouts`](crate::services::audit::diff_layouts)).
+#[must_use]
+pub fn commit_message(filename: &str, changes: &[String]) -> String {
+    let mut message = match changes.len() {
+        0 => format!("Update {filename}"),
+        1 => format!("Update {filename}: {}", changes[0]),
+        n => format!("Update {filename} ({n} changes)"),
+    };
+    if changes.len() > 1 {
+        message.push('\n');
+        for change in changes.iter().take(MAX_MESSAGE_CHANGES) {
+            let _ = write!(message, "\n- {change}");
+        }
+        if changes.len() > MAX_MESSAGE_CHANGES {
+            let _ = write!(
+                message,
+                "\n- ... and {} more",
+                changes.len() - MAX_MESSAGE_CHANGES
+            );
+        }
+    }
+    message
+}
+
+/// Parses one line of `git log` output in the format used by [`GitRepo::log`].
+#[allow(dead_code)] // Used by the web history endpoint (lib target only)
+fn parse_log_line(line: &str) -> Option<GitCommit> {
+    let mut fields = line.splitn(4, FIELD_SEPARATOR);
+    let hash = fields.next()?.to_string();
+    let author = fields.next()?.to_string();
+    let date = DateTime::parse_from_rfc3339(fields.next()?)
+        .ok()?
+        .with_timezone(&Utc);
+    let summary = fields.next()?.to_string();
+    Some(GitCommit {
+        hash,
+        author,
+        date,
+        summary,
+    })
+}
+
+#[cfg(test)]
+mod tests;
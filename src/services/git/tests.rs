@@ -0,0 +1,126 @@
+use super::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Creates a repository with a committer identity, or None without git.
+fn init_repo() -> Option<(TempDir, GitRepo)> {
+    let dir = TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir.path())
+            .args(args)
+            .output()
+            .is_ok_and(|output| output.status.success())
+    };
+    if !git(&["init", "--quiet"]) {
+        return None;
+    }
+    assert!(git(&["config", "user.name", "Test User"]));
+    assert!(git(&["config", "user.email", "test@example.com"]));
+    assert!(git(&["config", "commit.gpgsign", "false"]));
+    let repo = GitRepo::discover(dir.path()).unwrap();
+    Some((dir, repo))
+}
+
+#[test]
+fn test_discover_from_subdirectory() {
+    let Some((dir, repo)) = init_repo() else {
+        return;
+    };
+    let sub = dir.path().join("layouts");
+    fs::create_dir(&sub).unwrap();
+
+    assert_eq!(GitRepo::discover(&sub), Some(repo.clone()));
+    assert_eq!(GitRepo::discover(&sub.join("corne.json")), Some(repo));
+}
+
+#[test]
+fn test_commit_file_and_status() {
+    let Some((dir, repo)) = init_repo() else {
+        return;
+    };
+    let layout = dir.path().join("corne.json");
+    let other = dir.path().join("notes.txt");
+    fs::write(&layout, "{}").unwrap();
+    fs::write(&other, "keep me out").unwrap();
+
+    assert_eq!(repo.file_status(&layout).unwrap(), GitFileStatus::Untracked);
+    assert_eq!(repo.log(&layout, 10).unwrap(), Vec::new());
+
+    let hash = repo
+        .commit_file(&layout, "Add corne.json")
+        .unwrap()
+        .unwrap();
+    assert_eq!(hash.len(), 40);
+    assert_eq!(repo.file_status(&layout).unwrap(), GitFileStatus::Committed);
+    assert_eq!(repo.file_status(&other).unwrap(), GitFileStatus::Untracked);
+    assert_eq!(repo.commit_file(&layout, "Nothing").unwrap(), None);
+
+    fs::write(&layout, "{\"changed\": true}").unwrap();
+    assert_eq!(repo.file_status(&layout).unwrap(), GitFileStatus::Modified);
+    repo.commit_file(&layout, "Update corne.json\n\n- details")
+        .unwrap()
+        .unwrap();
+
+    let log = repo.log(&layout, 10).unwrap();
+    assert_eq!(log.len(), 2);
+    assert_eq!(log[0].summary, "Update corne.json");
+    assert_eq!(log[0].author, "Test User");
+    assert_eq!(log[1].hash, hash);
+    assert_eq!(repo.log(&layout, 1).unwrap().len(), 1);
+}
+
+#[test]
+fn test_branch_and_detached_head() {
+    let Some((dir, repo)) = init_repo() else {
+        return;
+    };
+    repo.run(&[
+        "symbolic-ref".as_ref(),
+        "HEAD".as_ref(),
+        "refs/heads/layouts".as_ref(),
+    ])
+    .unwrap();
+    // A branch without commits yet
+    assert_eq!(repo.branch().as_deref(), Some("layouts"));
+
+    let layout = dir.path().join("corne.json");
+    fs::write(&layout, "{}").unwrap();
+    let hash = repo
+        .commit_file(&layout, "Add corne.json")
+        .unwrap()
+        .unwrap();
+    repo.run(&["checkout".as_ref(), "--quiet".as_ref(), "--detach".as_ref()])
+        .unwrap();
+    let short = repo.branch().unwrap();
+    assert!(short.len() >= 7);
+    assert!(hash.starts_with(&short));
+}
+
+#[test]
+fn test_commit_message_lists_changes() {
+    assert_eq!(commit_message("corne.json", &[]), "Update corne.json");
+    assert_eq!(
+        commit_message("corne.json", &["Renamed layer 1".to_string()]),
+        "Update corne.json: Renamed layer 1"
+    );
+
+    let changes: Vec<String> = (0..25).map(|i| format!("Change {i}")).collect();
+    let message = commit_message("corne.json", &changes);
+    let mut lines = message.lines();
+    assert_eq!(lines.next(), Some("Update corne.json (25 changes)"));
+    assert_eq!(lines.next(), Some(""));
+    assert_eq!(lines.next(), Some("- Change 0"));
+    assert!(message.ends_with("- ... and 5 more"));
+}
+
+#[test]
+fn test_parse_log_line() {
+    let line = "abc123\u{1f}Jo\u{1f}2026-10-15T12:00:00+02:00\u{1f}Update: a\u{1f}b";
+    let commit = parse_log_line(line).unwrap();
+    assert_eq!(commit.hash, "abc123");
+    assert_eq!(commit.date.to_rfc3339(), "2026-10-15T10:00:00+00:00");
+    assert_eq!(commit.summary, "Update: a\u{1f}b");
+    assert_eq!(parse_log_line("abc123"), None);
+}
@@ -0,0 +1,385 @@
+//! Live keymap preview over the QMK HID console.
+//!
+//! Firmware generated with the console enabled prints `lqmk:layer:<n>` when
+//! the active layer changes and `lqmk:key:<row>:<col>` for every key press
+//! (matrix position); with usage counting on it also prints
+//! `lqmk:usage:<layer>:<row>:<col>:<count>` batches of press counts.
+//! [`HidListener`] reads those lines in a background thread:
+//! - Linux: straight from the console's `/dev/hidraw*` node.
+//! - Everywhere else (or without hidraw access): from `qmk console`.
+//!
+//! The listener reconnects when the keyboard is unplugged and plugged back in.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Prefix of the console lines printed by generated firmware.
+pub const REPORT_PREFIX: &str = "lqmk:";
+
+/// Number of recent key presses kept by [`LiveState`].
+const RECENT_KEY_LIMIT: usize = 8;
+
+/// Delay between attempts to find the keyboard.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// HID usage page of the QMK console interface.
+const CONSOLE_USAGE_PAGE: u32 = 0xFF31;
+
+/// HID usage of the QMK console interface.
+const CONSOLE_USAGE: u32 = 0x74;
+
+/// Something the keyboard reported, or a change in the connection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HidEvent {
+    /// Started reading from a console
+    Connected {
+        /// Device or tool the reports come from
+        device: String,
+    },
+    /// The console went away; the listener keeps retrying
+    Disconnected,
+    /// The console could not be opened
+    Unavailable {
+        /// Why not
+        reason: String,
+    },
+    /// The highest active layer changed
+    Layer {
+        /// Layer index
+        layer: u8,
+    },
+    /// A key was pressed
+    KeyPressed {
+        /// Matrix row
+        row: u8,
+        /// Matrix column
+        col: u8,
+    },
+    /// Presses of a key counted by the firmware since its last report
+    Usage {
+        /// Layer the key was pressed on
+        layer: u8,
+        /// Matrix row
+        row: u8,
+        /// Matrix column
+        col: u8,
+        /// Number of presses
+        count: u32,
+    },
+}
+
+/// Parses a console line into a report, ignoring anything that isn't one.
+///
+/// Reports may be preceded by other text (e.g. the device prefix that
+/// `qmk console` adds).
+#[must_use]
+pub fn parse_report(line: &str) -> Option<HidEvent> {
+    let report = &line[line.find(REPORT_PREFIX)? + REPORT_PREFIX.len()..];
+    let mut fields = report.trim().split(':');
+    match fields.next()? {
+        "layer" => Some(HidEvent::Layer {
+            layer: fields.next()?.parse().ok()?,
+        }),
+        "key" => Some(HidEvent::KeyPressed {
+            row: fields.next()?.parse().ok()?,
+            col: fields.next()?.parse().ok()?,
+        }),
+        "usage" => Some(HidEvent::Usage {
+            layer: fields.next()?.parse().ok()?,
+            row: fields.next()?.parse().ok()?,
+            col: fields.next()?.parse().ok()?,
+            count: fields.next()?.parse().ok()?,
+        }),
+        _ => None,
+    }
+}
+
+/// What the keyboard has reported so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LiveState {
+    /// Device being read, while connected
+    pub device: Option<String>,
+    /// Last error opening the console
+    pub error: Option<String>,
+    /// Highest active layer, once reported
+    pub layer: Option<u8>,
+    /// Most recent key presses as matrix (row, col), newest last
+    pub recent_keys: VecDeque<(u8, u8)>,
+}
+
+impl LiveState {
+    /// Updates the state with `event`.
+    pub fn apply(&mut self, event: &HidEvent) {
+        match event {
+            HidEvent::Connected { device } => {
+                self.device = Some(device.clone());
+                self.error = None;
+            }
+            HidEvent::Disconnected => self.device = None,
+            HidEvent::Unavailable { reason } => {
+                self.device = None;
+                self.error = Some(reason.clone());
+            }
+            HidEvent::Layer { layer } => self.layer = Some(*layer),
+            HidEvent::KeyPressed { row, col } => {
+                if self.recent_keys.len() == RECENT_KEY_LIMIT {
+                    self.recent_keys.pop_front();
+                }
+                self.recent_keys.push_back((*row, *col));
+            }
+            HidEvent::Usage { .. } => {}
+        }
+    }
+}
+
+/// Reads live preview reports from the keyboard in a background thread.
+///
+/// Dropping the listener stops it. A blocked hidraw read only notices once
+/// the next report arrives or the keyboard is unplugged.
+#[derive(Debug)]
+pub struct HidListener {
+    events: Receiver<HidEvent>,
+    stop: Arc<AtomicBool>,
+    console: Arc<Mutex<Option<Child>>>,
+}
+
+impl HidListener {
+    /// Starts listening for the keyboard.
+    #[must_use]
+    pub fn start() -> Self {
+        let (tx, events) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let console = Arc::new(Mutex::new(None));
+        let listener = Self {
+            events,
+            stop: Arc::clone(&stop),
+            console: Arc::clone(&console),
+        };
+        thread::spawn(move || run(&tx, &stop, &console));
+        listener
+    }
+
+    /// Returns the events received since the last call, without blocking.
+    pub fn poll(&self) -> impl Iterator<Item = HidEvent> + '_ {
+        self.events.try_iter()
+    }
+
+    /// Waits up to `timeout` for the next event.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Timeout` if nothing arrived in time and `Disconnected` once the
+    /// listener thread has stopped.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<HidEvent, RecvTimeoutError> {
+        self.events.recv_timeout(timeout)
+    }
+}
+
+impl Drop for HidListener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Ok(mut console) = self.console.lock() {
+            if let Some(child) = console.as_mut() {
+                let _ = child.kill();
+            }
+        }
+    }
+}
+
+/// Listener thread: read from the console until stopped, reconnecting as needed.
+fn run(tx: &Sender<HidEvent>, stop: &AtomicBool, console: &Mutex<Option<Child>>) {
+    let mut last_error: Option<String> = None;
+    while !stop.load(Ordering::Relaxed) {
+        let result = match find_console_hidraw() {
+            Some((path, name)) => std::fs::File::open(&path)
+                .map_err(|e| format!("Cannot open {}: {e}", path.display()))
+                .map(|file| forward_reports(file, name, tx, stop)),
+            None => read_qmk_console(tx, stop, console),
+        };
+        match result {
+            Ok(true) => last_error = None,
+            // Receiver dropped
+            Ok(false) => return,
+            Err(reason) => {
+                if last_error.as_ref() != Some(&reason) {
+                    if tx
+                        .send(HidEvent::Unavailable {
+                            reason: reason.clone(),
+                        })
+                        .is_err()
+                    {
+                        return;
+                    }
+                    last_error = Some(reason);
+                }
+            }
+        }
+        thread::sleep(RECONNECT_INTERVAL);
+    }
+}
+
+/// Runs `qmk console` and forwards its reports until it exits.
+fn read_qmk_console(
+    tx: &Sender<HidEvent>,
+    stop: &AtomicBool,
+    console: &Mutex<Option<Child>>,
+) -> Result<bool, String> {
+    let mut child = Command::new("qmk")
+        .arg("console")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("No keyboard console found and qmk console failed to start: {e}"))?;
+    let stdout = child.stdout.take().ok_or("qmk console has no output")?;
+    if let Ok(mut slot) = console.lock() {
+        // The listener may have been dropped while the console started
+        if stop.load(Ordering::Relaxed) {
+            let _ = child.kill();
+        }
+        *slot = Some(child);
+    }
+    let forwarded = forward_reports(stdout, "qmk console".to_string(), tx, stop);
+    if let Ok(mut slot) = console.lock() {
+        if let Some(mut child) = slot.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+    Ok(forwarded)
+}
+
+/// Forwards the reports read from `source` until it ends or the listener
+/// stops. Returns false if the receiver is gone.
+fn forward_reports(
+    source: impl Read,
+    device: String,
+    tx: &Sender<HidEvent>,
+    stop: &AtomicBool,
+) -> bool {
+    if tx.send(HidEvent::Connected { device }).is_err() {
+        return false;
+    }
+    // Console reports are NUL-padded; treat padding like line breaks
+    let mut reader = BufReader::new(NulAsNewline(source));
+    let mut line = String::new();
+    while !stop.load(Ordering::Relaxed) {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                if let Some(event) = parse_report(&line) {
+                    if tx.send(event).is_err() {
+                        return false;
+                    }
+                }
+            }
+        }
+    }
+    tx.send(HidEvent::Disconnected).is_ok()
+}
+
+/// Reader adapter that turns NUL bytes into newlines.
+struct NulAsNewline<R>(R);
+
+impl<R: Read> Read for NulAsNewline<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.0.read(buf)?;
+        for byte in &mut buf[..read] {
+            if *byte == 0 {
+                *byte = b'\n';
+            }
+        }
+        Ok(read)
+    }
+}
+
+/// Finds a readable QMK console hidraw node and the keyboard's name.
+#[cfg(target_os = "linux")]
+fn find_console_hidraw() -> Option<(std::path::PathBuf, String)> {
+    let mut entries: Vec<_> = std::fs::read_dir("/sys/class/hidraw")
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+    entries.into_iter().find_map(|sys_path| {
+        let descriptor = std::fs::read(sys_path.join("device/report_descriptor")).ok()?;
+        if !is_console_descriptor(&descriptor) {
+            return None;
+        }
+        let dev_path = std::path::Path::new("/dev").join(sys_path.file_name()?);
+        std::fs::File::open(&dev_path).ok()?;
+        let name = std::fs::read_to_string(sys_path.join("device/uevent"))
+            .ok()
+            .and_then(|uevent| {
+                uevent
+                    .lines()
+                    .find_map(|line| line.strip_prefix("HID_NAME=").map(str::to_string))
+            })
+            .unwrap_or_else(|| dev_path.display().to_string());
+        Some((dev_path, name))
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn find_console_hidraw() -> Option<(std::path::PathBuf, String)> {
+    None
+}
+
+/// Returns true if a HID report descriptor declares the QMK console
+/// (usage page 0xFF31, usage 0x74).
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn is_console_descriptor(descriptor: &[u8]) -> bool {
+    let mut usage_page = 0;
+    let mut idx = 0;
+    while idx < descriptor.len() {
+        let prefix = descriptor[idx];
+        // Long items carry their size in the next byte
+        if prefix == 0xFE {
+            let size = usize::from(descriptor.get(idx + 1).copied().unwrap_or(0));
+            idx += 3 + size;
+            continue;
+        }
+        let size = match prefix & 0x03 {
+            3 => 4,
+            size => usize::from(size),
+        };
+        let Some(data) = descriptor.get(idx + 1..idx + 1 + size) else {
+            break;
+        };
+        let value = data
+            .iter()
+            .rev()
+            .fold(0u32, |acc, byte| (acc << 8) | u32::from(*byte));
+        match prefix & 0xFC {
+            // Usage Page (global)
+            0x04 => usage_page = value,
+            // Usage (local); a 4-byte usage includes its own page
+            0x08 => {
+                let (page, usage) = if size == 4 {
+                    (value >> 16, value & 0xFFFF)
+                } else {
+                    (usage_page, value)
+                };
+                if page == CONSOLE_USAGE_PAGE && usage == CONSOLE_USAGE {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+        idx += 1 + size;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,106 @@
+//! Tests for the live preview listener.
+
+use super::*;
+
+#[test]
+fn test_parse_report() {
+    assert_eq!(
+        parse_report("lqmk:layer:2\n"),
+        Some(HidEvent::Layer { layer: 2 })
+    );
+    assert_eq!(
+        parse_report("crkbd:rev1:1: lqmk:key:3:5"),
+        Some(HidEvent::KeyPressed { row: 3, col: 5 })
+    );
+    assert_eq!(
+        parse_report("lqmk:usage:1:0:4:12"),
+        Some(HidEvent::Usage {
+            layer: 1,
+            row: 0,
+            col: 4,
+            count: 12
+        })
+    );
+    assert_eq!(parse_report("lqmk:key:3"), None);
+    assert_eq!(parse_report("lqmk:usage:1:0:4"), None);
+    assert_eq!(parse_report("lqmk:layer:x"), None);
+    assert_eq!(parse_report("debug: matrix scan"), None);
+}
+
+#[test]
+fn test_live_state_keeps_recent_keys() {
+    let mut state = LiveState::default();
+    state.apply(&HidEvent::Connected {
+        device: "Corne".to_string(),
+    });
+    state.apply(&HidEvent::Layer { layer: 1 });
+    for col in 0..10 {
+        state.apply(&HidEvent::KeyPressed { row: 0, col });
+    }
+
+    assert_eq!(state.device.as_deref(), Some("Corne"));
+    assert_eq!(state.layer, Some(1));
+    assert_eq!(state.recent_keys.len(), RECENT_KEY_LIMIT);
+    assert_eq!(state.recent_keys.front(), Some(&(0, 2)));
+    assert_eq!(state.recent_keys.back(), Some(&(0, 9)));
+
+    state.apply(&HidEvent::Disconnected);
+    assert_eq!(state.device, None);
+    assert_eq!(state.layer, Some(1), "last layer stays visible");
+}
+
+#[test]
+fn test_forward_reports_reads_nul_padded_console_reports() {
+    // Two 32-byte console reports, the second carrying two lines
+    let mut data = b"lqmk:layer:1\n".to_vec();
+    data.resize(32, 0);
+    let mut second = b"lqmk:key:0:4\nlqmk:layer:0\n".to_vec();
+    second.resize(32, 0);
+    data.extend(second);
+
+    let (tx, rx) = mpsc::channel();
+    let stop = AtomicBool::new(false);
+    assert!(forward_reports(
+        data.as_slice(),
+        "test".to_string(),
+        &tx,
+        &stop
+    ));
+
+    let events: Vec<HidEvent> = rx.try_iter().collect();
+    assert_eq!(
+        events,
+        vec![
+            HidEvent::Connected {
+                device: "test".to_string()
+            },
+            HidEvent::Layer { layer: 1 },
+            HidEvent::KeyPressed { row: 0, col: 4 },
+            HidEvent::Layer { layer: 0 },
+            HidEvent::Disconnected,
+        ]
+    );
+}
+
+#[test]
+fn test_is_console_descriptor() {
+    // Usage Page (0xFF31), Usage (0x74), Collection (Application) ...
+    let console = [0x06, 0x31, 0xFF, 0x09, 0x74, 0xA1, 0x01, 0xC0];
+    // Usage Page (Generic Desktop), Usage (Keyboard)
+    let keyboard = [0x05, 0x01, 0x09, 0x06, 0xA1, 0x01, 0xC0];
+    // Raw HID: Usage Page (0xFF60), Usage (0x61)
+    let raw_hid = [0x06, 0x60, 0xFF, 0x09, 0x61, 0xA1, 0x01, 0xC0];
+
+    assert!(is_console_descriptor(&console));
+    assert!(!is_console_descriptor(&keyboard));
+    assert!(!is_console_descriptor(&raw_hid));
+    assert!(!is_console_descriptor(&[0x06, 0x31]), "truncated");
+}
+
+#[test]
+fn test_hid_event_json() {
+    let json = serde_json::to_value(HidEvent::KeyPressed { row: 1, col: 2 }).unwrap();
+    assert_eq!(json["type"], "key_pressed");
+    assert_eq!(json["row"], 1);
+    assert_eq!(json["col"], 2);
+}
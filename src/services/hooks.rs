@@ -0,0 +1,130 @@
+//! User commands run around saving, generating and building.
+//!
+//! Hooks are configured in the `[hooks]` section of the config file (see
+//! [`HooksConfig`]). A hook command gets the layout path as its first
+//! argument, followed by the files the step produced: keymap.c and config.h
+//! for `post_generate`, the firmware file for `post_build`. It runs in the
+//! layout's directory with `LAZYQMK_HOOK` set to the hook name, so one script
+//! can serve several hooks.
+//!
+//! Hooks run in the TUI and in the `generate` and `build` commands. The web
+//! server never runs them, since its requests may come from other machines.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+
+use crate::config::HooksConfig;
+use crate::services::process_tree::{ChildOutcome, OutputLine, TrackedChild};
+
+/// Lines of hook output kept for the error message of a failing hook.
+const ERROR_OUTPUT_LINES: usize = 5;
+
+/// A point at which a hook can run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    /// Before a layout is saved
+    PreSave,
+    /// After a layout is saved
+    PostSave,
+    /// After firmware files are generated
+    PostGenerate,
+    /// After firmware is built
+    PostBuild,
+}
+
+impl Hook {
+    /// Name of the hook in the config file and in `LAZYQMK_HOOK`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::PreSave => "pre_save",
+            Self::PostSave => "post_save",
+            Self::PostGenerate => "post_generate",
+            Self::PostBuild => "post_build",
+        }
+    }
+
+    /// The configured command (program and arguments); empty if unset.
+    #[must_use]
+    pub fn command(self, config: &HooksConfig) -> &[String] {
+        match self {
+            Self::PreSave => &config.pre_save,
+            Self::PostSave => &config.post_save,
+            Self::PostGenerate => &config.post_generate,
+            Self::PostBuild => &config.post_build,
+        }
+    }
+}
+
+impl fmt::Display for Hook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Runs the command configured for `hook` and waits for it to finish.
+///
+/// Returns `false` if no command is configured and `true` once it exited
+/// successfully. The command's output is only kept for error messages.
+///
+/// # Errors
+///
+/// Returns an error if the command cannot be started, exits with a non-zero
+/// status (the message includes the end of its output) or runs longer than
+/// `timeout_secs`.
+pub fn run_hook(
+    config: &HooksConfig,
+    hook: Hook,
+    layout_path: &Path,
+    artifacts: &[PathBuf],
+) -> Result<bool> {
+    let Some((program, args)) = hook.command(config).split_first() else {
+        return Ok(false);
+    };
+
+    let mut cmd = Command::new(program);
+    cmd.args(args)
+        .arg(layout_path)
+        .args(artifacts)
+        .env("LAZYQMK_HOOK", hook.name());
+    if let Some(dir) = layout_path.parent().filter(|dir| dir.is_dir()) {
+        cmd.current_dir(dir);
+    }
+
+    let mut child = TrackedChild::spawn(&mut cmd)
+        .with_context(|| format!("Failed to run {hook} hook `{program}`"))?;
+    let deadline = (config.timeout_secs > 0)
+        .then(|| Instant::now() + Duration::from_secs(config.timeout_secs));
+    let mut output = Vec::new();
+    let outcome = child
+        .wait_with_output(
+            &|| deadline.is_some_and(|deadline| Instant::now() >= deadline),
+            &mut |line| {
+                let (OutputLine::Stdout(line) | OutputLine::Stderr(line)) = line;
+                output.push(line);
+            },
+        )
+        .with_context(|| format!("Failed to wait for {hook} hook `{program}`"))?;
+
+    match outcome {
+        ChildOutcome::Exited(status) if status.success() => Ok(true),
+        ChildOutcome::Exited(status) => {
+            let tail = output[output.len().saturating_sub(ERROR_OUTPUT_LINES)..].join("\n");
+            if tail.trim().is_empty() {
+                bail!("{hook} hook `{program}` failed ({status})");
+            }
+            bail!("{hook} hook `{program}` failed ({status}):\n{tail}");
+        }
+        ChildOutcome::Stopped => bail!(
+            "{hook} hook `{program}` was stopped after {} seconds",
+            config.timeout_secs
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests;
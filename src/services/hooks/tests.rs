@@ -0,0 +1,99 @@
+//! Tests for services::hooks.
+
+use super::*;
+use tempfile::TempDir;
+
+/// Hooks config running `script` with `sh -c` for every hook. The script
+/// sees the layout and artifact paths as `$1`, `$2`, ...
+#[cfg(unix)]
+fn sh_hooks(script: &str) -> HooksConfig {
+    let command = vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        script.to_string(),
+        "hook".to_string(),
+    ];
+    HooksConfig {
+        pre_save: command.clone(),
+        post_save: command.clone(),
+        post_generate: command.clone(),
+        post_build: command,
+        ..HooksConfig::default()
+    }
+}
+
+#[test]
+fn test_unset_hook_does_nothing() {
+    let ran = run_hook(
+        &HooksConfig::default(),
+        Hook::PostSave,
+        Path::new("layout.json"),
+        &[],
+    )
+    .unwrap();
+
+    assert!(!ran);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_hook_gets_layout_and_artifact_paths() {
+    let dir = TempDir::new().unwrap();
+    let layout = dir.path().join("layout.json");
+    let keymap = dir.path().join("keymap.c");
+    let config = sh_hooks(r#"echo "$LAZYQMK_HOOK $1 $2" > "$PWD/hook.out""#);
+
+    let ran = run_hook(
+        &config,
+        Hook::PostGenerate,
+        &layout,
+        std::slice::from_ref(&keymap),
+    )
+    .unwrap();
+
+    assert!(ran);
+    let output = std::fs::read_to_string(dir.path().join("hook.out")).unwrap();
+    assert_eq!(
+        output.trim(),
+        format!("post_generate {} {}", layout.display(), keymap.display())
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_failing_hook_reports_its_output() {
+    let dir = TempDir::new().unwrap();
+    let config = sh_hooks("echo 'dotfiles repo is dirty' >&2; exit 3");
+
+    let err = run_hook(&config, Hook::PreSave, &dir.path().join("a.json"), &[]).unwrap_err();
+
+    let message = err.to_string();
+    assert!(message.starts_with("pre_save hook `sh` failed"));
+    assert!(message.contains("dotfiles repo is dirty"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_slow_hook_is_stopped() {
+    let dir = TempDir::new().unwrap();
+    let config = HooksConfig {
+        timeout_secs: 1,
+        ..sh_hooks("sleep 30")
+    };
+
+    let err = run_hook(&config, Hook::PostBuild, &dir.path().join("a.json"), &[]).unwrap_err();
+
+    assert!(err.to_string().contains("stopped after 1 seconds"));
+}
+
+#[test]
+fn test_missing_program_is_an_error() {
+    let config = HooksConfig {
+        post_save: vec!["lazyqmk-hook-that-does-not-exist".to_string()],
+        ..HooksConfig::default()
+    };
+
+    let err = run_hook(&config, Hook::PostSave, Path::new("layout.json"), &[]).unwrap_err();
+
+    assert!(err.to_string().contains("Failed to run post_save hook"));
+}
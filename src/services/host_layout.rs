@@ -0,0 +1,549 @@
+//! Host keyboard layout capture for key label previews.
+//!
+//! QMK sends key positions, not characters: `KC_Y` types "z" when the host
+//! uses a German layout. This module asks the operating system which
+//! character each basic key produces so the editor can label keys the way
+//! they will actually type:
+//! - Linux: the active XKB keymap, dumped with `xkbcomp` (X11) or compiled
+//!   with `xkbcli` from the `localectl` layout (Wayland and consoles).
+//! - Windows: the thread's keyboard layout via `ToUnicodeEx`.
+//! - macOS: the current input source via `UCKeyTranslate`.
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::process::Command;
+
+/// A basic QMK keycode and where its key sits on each platform.
+struct KeyPosition {
+    /// QMK keycode (e.g. "KC_A")
+    keycode: &'static str,
+    /// XKB key name (e.g. "AC01")
+    #[cfg_attr(any(windows, target_os = "macos"), allow(dead_code))]
+    xkb: &'static str,
+    /// PC set 1 scancode, used by Win32
+    #[cfg_attr(not(windows), allow(dead_code))]
+    scancode: u32,
+    /// macOS virtual keycode
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    mac: u16,
+}
+
+const fn pos(keycode: &'static str, xkb: &'static str, scancode: u32, mac: u16) -> KeyPosition {
+    KeyPosition {
+        keycode,
+        xkb,
+        scancode,
+        mac,
+    }
+}
+
+/// Keys whose character depends on the host layout.
+const KEY_POSITIONS: &[KeyPosition] = &[
+    pos("KC_GRV", "TLDE", 0x29, 50),
+    pos("KC_1", "AE01", 0x02, 18),
+    pos("KC_2", "AE02", 0x03, 19),
+    pos("KC_3", "AE03", 0x04, 20),
+    pos("KC_4", "AE04", 0x05, 21),
+    pos("KC_5", "AE05", 0x06, 23),
+    pos("KC_6", "AE06", 0x07, 22),
+    pos("KC_7", "AE07", 0x08, 26),
+    pos("KC_8", "AE08", 0x09, 28),
+    pos("KC_9", "AE09", 0x0A, 25),
+    pos("KC_0", "AE10", 0x0B, 29),
+    pos("KC_MINS", "AE11", 0x0C, 27),
+    pos("KC_EQL", "AE12", 0x0D, 24),
+    pos("KC_Q", "AD01", 0x10, 12),
+    pos("KC_W", "AD02", 0x11, 13),
+    pos("KC_E", "AD03", 0x12, 14),
+    pos("KC_R", "AD04", 0x13, 15),
+    pos("KC_T", "AD05", 0x14, 17),
+    pos("KC_Y", "AD06", 0x15, 16),
+    pos("KC_U", "AD07", 0x16, 32),
+    pos("KC_I", "AD08", 0x17, 34),
+    pos("KC_O", "AD09", 0x18, 31),
+    pos("KC_P", "AD10", 0x19, 35),
+    pos("KC_LBRC", "AD11", 0x1A, 33),
+    pos("KC_RBRC", "AD12", 0x1B, 30),
+    pos("KC_A", "AC01", 0x1E, 0),
+    pos("KC_S", "AC02", 0x1F, 1),
+    pos("KC_D", "AC03", 0x20, 2),
+    pos("KC_F", "AC04", 0x21, 3),
+    pos("KC_G", "AC05", 0x22, 5),
+    pos("KC_H", "AC06", 0x23, 4),
+    pos("KC_J", "AC07", 0x24, 38),
+    pos("KC_K", "AC08", 0x25, 40),
+    pos("KC_L", "AC09", 0x26, 37),
+    pos("KC_SCLN", "AC10", 0x27, 41),
+    pos("KC_QUOT", "AC11", 0x28, 39),
+    pos("KC_BSLS", "BKSL", 0x2B, 42),
+    pos("KC_NUHS", "BKSL", 0x2B, 42),
+    pos("KC_Z", "AB01", 0x2C, 6),
+    pos("KC_X", "AB02", 0x2D, 7),
+    pos("KC_C", "AB03", 0x2E, 8),
+    pos("KC_V", "AB04", 0x2F, 9),
+    pos("KC_B", "AB05", 0x30, 11),
+    pos("KC_N", "AB06", 0x31, 45),
+    pos("KC_M", "AB07", 0x32, 46),
+    pos("KC_COMM", "AB08", 0x33, 43),
+    pos("KC_DOT", "AB09", 0x34, 47),
+    pos("KC_SLSH", "AB10", 0x35, 44),
+    pos("KC_NUBS", "LSGT", 0x56, 10),
+];
+
+/// XKB keysym names for common non-ASCII-letter symbols.
+///
+/// Single-character names (letters and digits) and `Uxxxx` names are
+/// handled directly by [`keysym_char`].
+const KEYSYM_CHARS: &[(&str, char)] = &[
+    ("grave", '`'),
+    ("asciitilde", '~'),
+    ("exclam", '!'),
+    ("at", '@'),
+    ("numbersign", '#'),
+    ("dollar", '$'),
+    ("percent", '%'),
+    ("asciicircum", '^'),
+    ("ampersand", '&'),
+    ("asterisk", '*'),
+    ("parenleft", '('),
+    ("parenright", ')'),
+    ("minus", '-'),
+    ("underscore", '_'),
+    ("equal", '='),
+    ("plus", '+'),
+    ("bracketleft", '['),
+    ("bracketright", ']'),
+    ("braceleft", '{'),
+    ("braceright", '}'),
+    ("backslash", '\\'),
+    ("bar", '|'),
+    ("semicolon", ';'),
+    ("colon", ':'),
+    ("apostrophe", '\''),
+    ("quotedbl", '"'),
+    ("comma", ','),
+    ("period", '.'),
+    ("slash", '/'),
+    ("less", '<'),
+    ("greater", '>'),
+    ("question", '?'),
+    ("section", '§'),
+    ("degree", '°'),
+    ("sterling", '£'),
+    ("EuroSign", '€'),
+    ("mu", 'µ'),
+    ("twosuperior", '²'),
+    ("acute", '´'),
+    ("diaeresis", '¨'),
+    ("masculine", 'º'),
+    ("ordfeminine", 'ª'),
+    ("exclamdown", '¡'),
+    ("questiondown", '¿'),
+    ("onehalf", '½'),
+    ("notsign", '¬'),
+    ("guillemotleft", '«'),
+    ("guillemotright", '»'),
+    ("ssharp", 'ß'),
+    ("adiaeresis", 'ä'),
+    ("odiaeresis", 'ö'),
+    ("udiaeresis", 'ü'),
+    ("aring", 'å'),
+    ("ae", 'æ'),
+    ("oslash", 'ø'),
+    ("agrave", 'à'),
+    ("egrave", 'è'),
+    ("igrave", 'ì'),
+    ("ograve", 'ò'),
+    ("ugrave", 'ù'),
+    ("eacute", 'é'),
+    ("ccedilla", 'ç'),
+    ("ntilde", 'ñ'),
+    ("dead_grave", '`'),
+    ("dead_acute", '´'),
+    ("dead_circumflex", '^'),
+    ("dead_tilde", '~'),
+    ("dead_diaeresis", '¨'),
+    ("dead_cedilla", '¸'),
+    ("dead_abovering", '°'),
+];
+
+/// Characters the host layout produces for basic keycodes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostLayout {
+    /// Layout name as reported by the system (e.g. "de", "00000407")
+    name: String,
+    /// Display label by QMK keycode
+    labels: HashMap<&'static str, String>,
+}
+
+impl HostLayout {
+    /// Queries the operating system for the active keyboard layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layout cannot be read (missing tools, no
+    /// display, unsupported platform) or maps no keys.
+    pub fn detect() -> Result<Self> {
+        let layout = Self::detect_platform()?;
+        if layout.labels.is_empty() {
+            bail!("Host keyboard layout '{}' maps no keys", layout.name);
+        }
+        Ok(layout)
+    }
+
+    /// Layout name as reported by the system.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Label the host shows for `keycode`, if it is a layout-dependent key.
+    #[must_use]
+    pub fn label(&self, keycode: &str) -> Option<&str> {
+        self.labels.get(keycode).map(String::as_str)
+    }
+
+    /// Builds a layout from the unshifted character of each keycode.
+    fn from_chars(
+        name: impl Into<String>,
+        chars: impl IntoIterator<Item = (&'static str, char)>,
+    ) -> Self {
+        let labels = chars
+            .into_iter()
+            .filter(|(_, ch)| !ch.is_control() && !ch.is_whitespace())
+            .map(|(keycode, ch)| {
+                // Match the upper-case key caps, but keep characters like
+                // "ß" that have no single-letter upper case
+                let mut upper = ch.to_uppercase();
+                let label = match (upper.next(), upper.next()) {
+                    (Some(single), None) => single,
+                    _ => ch,
+                };
+                (keycode, label.to_string())
+            })
+            .collect();
+        Self {
+            name: name.into(),
+            labels,
+        }
+    }
+
+    /// Builds a layout from a textual XKB keymap (as printed by `xkbcomp`
+    /// or `xkbcli compile-keymap`), using the first group's base level.
+    #[cfg_attr(any(windows, target_os = "macos"), allow(dead_code))]
+    #[must_use]
+    pub fn from_xkb_keymap(name: impl Into<String>, keymap: &str) -> Self {
+        let symbols = keymap
+            .find("xkb_symbols")
+            .map_or(keymap, |start| &keymap[start..]);
+        let mut by_key: HashMap<&str, char> = HashMap::new();
+        let mut rest = symbols;
+        while let Some(start) = rest.find("key <") {
+            rest = &rest[start + 5..];
+            let Some(end) = rest.find('>') else { break };
+            let key_name = &rest[..end];
+            let body_end = rest.find("};").unwrap_or(rest.len());
+            let body = &rest[end..body_end];
+            // Skip the group index in `symbols[Group1]= [ ... ]`
+            let list = body
+                .find("symbols[")
+                .and_then(|at| body[at..].find(']').map(|close| &body[at + close + 1..]))
+                .unwrap_or(body);
+            let base = list
+                .find('[')
+                .and_then(|open| list[open + 1..].split([',', ']']).next())
+                .map(str::trim);
+            if let Some(ch) = base.and_then(keysym_char) {
+                by_key.entry(key_name).or_insert(ch);
+            }
+            rest = &rest[body_end..];
+        }
+
+        Self::from_chars(
+            name,
+            KEY_POSITIONS
+                .iter()
+                .filter_map(|key| by_key.get(key.xkb).map(|&ch| (key.keycode, ch))),
+        )
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn detect_platform() -> Result<Self> {
+        if let Ok(display) = std::env::var("DISPLAY") {
+            if let Ok(keymap) =
+                command_stdout(Command::new("xkbcomp").args(["-xkb", &display, "-"]))
+            {
+                let name = command_stdout(Command::new("setxkbmap").arg("-query"))
+                    .ok()
+                    .and_then(|query| field_value(&query, "layout:"))
+                    .unwrap_or_else(|| "xkb".to_string());
+                return Ok(Self::from_xkb_keymap(name, &keymap));
+            }
+        }
+
+        // Wayland compositors and consoles don't expose the keymap; compile
+        // the system default layout instead.
+        let status = command_stdout(Command::new("localectl").arg("status"))
+            .context("Failed to read the system keyboard layout (is localectl available?)")?;
+        let layout = field_value(&status, "X11 Layout:")
+            .context("No X11 keyboard layout configured in localectl")?;
+        let mut compile = Command::new("xkbcli");
+        compile.args(["compile-keymap", "--layout", &layout]);
+        if let Some(variant) = field_value(&status, "X11 Variant:") {
+            compile.args(["--variant", &variant]);
+        }
+        let keymap = command_stdout(&mut compile)
+            .context("Failed to compile the XKB keymap (is xkbcli installed?)")?;
+        Ok(Self::from_xkb_keymap(layout, &keymap))
+    }
+
+    #[cfg(windows)]
+    fn detect_platform() -> Result<Self> {
+        win32::detect()
+    }
+
+    #[cfg(target_os = "macos")]
+    fn detect_platform() -> Result<Self> {
+        macos::detect()
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn detect_platform() -> Result<Self> {
+        bail!("Host keyboard layout detection is not supported on this platform")
+    }
+}
+
+/// Converts an XKB keysym name to the character it types.
+fn keysym_char(name: &str) -> Option<char> {
+    let mut chars = name.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Some(ch);
+    }
+    if let Some(hex) = name.strip_prefix('U') {
+        if let Some(ch) = u32::from_str_radix(hex, 16).ok().and_then(char::from_u32) {
+            return Some(ch);
+        }
+    }
+    KEYSYM_CHARS
+        .iter()
+        .find(|(keysym, _)| *keysym == name)
+        .map(|&(_, ch)| ch)
+}
+
+/// Returns the value after `label` on the first line that starts with it.
+#[cfg_attr(not(all(unix, not(target_os = "macos"))), allow(dead_code))]
+fn field_value(output: &str, label: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(label))
+        .map(|value| value.trim().split(',').next().unwrap_or("").to_string())
+        .filter(|value| !value.is_empty() && value != "n/a")
+}
+
+/// Runs `cmd`, returning its stdout if it exits successfully.
+#[cfg_attr(not(all(unix, not(target_os = "macos"))), allow(dead_code))]
+fn command_stdout(cmd: &mut Command) -> Result<String> {
+    let output = cmd.output()?;
+    if !output.status.success() {
+        bail!(
+            "{} exited with {}",
+            cmd.get_program().display(),
+            output.status
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(windows)]
+mod win32 {
+    //! Win32 keyboard layout query.
+
+    use super::{HostLayout, KEY_POSITIONS};
+    use anyhow::Result;
+
+    /// `MapVirtualKeyExW` mode: scancode to virtual key
+    const MAPVK_VSC_TO_VK: u32 = 1;
+    /// `ToUnicodeEx` flag: don't change the keyboard state (dead keys)
+    const TOUNICODE_NO_STATE_CHANGE: u32 = 1 << 2;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn GetKeyboardLayout(thread_id: u32) -> isize;
+        fn GetKeyboardLayoutNameW(name: *mut u16) -> i32;
+        fn MapVirtualKeyExW(code: u32, map_type: u32, layout: isize) -> u32;
+        fn ToUnicodeEx(
+            virtual_key: u32,
+            scancode: u32,
+            key_state: *const u8,
+            buffer: *mut u16,
+            buffer_len: i32,
+            flags: u32,
+            layout: isize,
+        ) -> i32;
+    }
+
+    pub(super) fn detect() -> Result<HostLayout> {
+        let mut name = [0u16; 9];
+        // SAFETY: the name buffer holds KL_NAMELENGTH (9) UTF-16 units and
+        // the key state buffer holds the 256 entries ToUnicodeEx reads.
+        let (layout, name_ok) = unsafe {
+            (
+                GetKeyboardLayout(0),
+                GetKeyboardLayoutNameW(name.as_mut_ptr()),
+            )
+        };
+        let name = if name_ok == 0 {
+            "win32".to_string()
+        } else {
+            String::from_utf16_lossy(&name)
+                .trim_end_matches('\0')
+                .to_string()
+        };
+
+        let key_state = [0u8; 256];
+        let chars = KEY_POSITIONS.iter().filter_map(|key| {
+            let mut buffer = [0u16; 4];
+            // SAFETY: see above; buffer length matches the array size.
+            let written = unsafe {
+                let virtual_key = MapVirtualKeyExW(key.scancode, MAPVK_VSC_TO_VK, layout);
+                ToUnicodeEx(
+                    virtual_key,
+                    key.scancode,
+                    key_state.as_ptr(),
+                    buffer.as_mut_ptr(),
+                    4,
+                    TOUNICODE_NO_STATE_CHANGE,
+                    layout,
+                )
+            };
+            // Dead keys report -1 with their spacing character in the buffer
+            let len = usize::try_from(written.unsigned_abs())
+                .ok()?
+                .min(buffer.len());
+            char::decode_utf16(buffer[..len].iter().copied())
+                .next()?
+                .ok()
+                .map(|ch| (key.keycode, ch))
+        });
+        Ok(HostLayout::from_chars(name, chars))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    //! macOS input source query (Carbon Text Input Sources).
+
+    use super::{HostLayout, KEY_POSITIONS};
+    use anyhow::{bail, Result};
+    use std::ffi::c_void;
+
+    /// `UCKeyTranslate` action: the character shown on the key cap
+    const UC_KEY_ACTION_DISPLAY: u16 = 3;
+    /// `UCKeyTranslate` option: report dead keys as their spacing character
+    const UC_KEY_TRANSLATE_NO_DEAD_KEYS: u32 = 1;
+    /// `kCFStringEncodingUTF8`
+    const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+    #[link(name = "Carbon", kind = "framework")]
+    extern "C" {
+        static kTISPropertyUnicodeKeyLayoutData: *const c_void;
+        static kTISPropertyLocalizedName: *const c_void;
+        fn TISCopyCurrentKeyboardLayoutInputSource() -> *const c_void;
+        fn TISGetInputSourceProperty(source: *const c_void, key: *const c_void) -> *const c_void;
+        fn LMGetKbdType() -> u8;
+        fn UCKeyTranslate(
+            layout: *const c_void,
+            virtual_key: u16,
+            action: u16,
+            modifier_state: u32,
+            keyboard_type: u32,
+            options: u32,
+            dead_key_state: *mut u32,
+            max_len: usize,
+            actual_len: *mut usize,
+            chars: *mut u16,
+        ) -> i32;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFDataGetBytePtr(data: *const c_void) -> *const u8;
+        fn CFStringGetCString(
+            string: *const c_void,
+            buffer: *mut u8,
+            buffer_len: isize,
+            encoding: u32,
+        ) -> u8;
+        fn CFRelease(object: *const c_void);
+    }
+
+    pub(super) fn detect() -> Result<HostLayout> {
+        // SAFETY: the input source is released before returning; property
+        // values are borrowed from it ("Get" rule) and only used while it is
+        // alive. Output buffers are sized as passed.
+        unsafe {
+            let source = TISCopyCurrentKeyboardLayoutInputSource();
+            if source.is_null() {
+                bail!("No current keyboard input source");
+            }
+            let data = TISGetInputSourceProperty(source, kTISPropertyUnicodeKeyLayoutData);
+            if data.is_null() {
+                CFRelease(source);
+                bail!("Current input source has no Unicode key layout");
+            }
+            let layout = CFDataGetBytePtr(data).cast::<c_void>();
+            let keyboard_type = u32::from(LMGetKbdType());
+
+            let mut name_buf = [0u8; 128];
+            let name_ref = TISGetInputSourceProperty(source, kTISPropertyLocalizedName);
+            let name = if !name_ref.is_null()
+                && CFStringGetCString(
+                    name_ref,
+                    name_buf.as_mut_ptr(),
+                    128,
+                    CF_STRING_ENCODING_UTF8,
+                ) != 0
+            {
+                std::ffi::CStr::from_bytes_until_nul(&name_buf).map_or_else(
+                    |_| "macOS".to_string(),
+                    |s| s.to_string_lossy().into_owned(),
+                )
+            } else {
+                "macOS".to_string()
+            };
+
+            let chars: Vec<(&'static str, char)> = KEY_POSITIONS
+                .iter()
+                .filter_map(|key| {
+                    let mut dead_key_state = 0u32;
+                    let mut buffer = [0u16; 4];
+                    let mut len = 0usize;
+                    let status = UCKeyTranslate(
+                        layout,
+                        key.mac,
+                        UC_KEY_ACTION_DISPLAY,
+                        0,
+                        keyboard_type,
+                        UC_KEY_TRANSLATE_NO_DEAD_KEYS,
+                        &mut dead_key_state,
+                        buffer.len(),
+                        &mut len,
+                        buffer.as_mut_ptr(),
+                    );
+                    if status != 0 || len == 0 {
+                        return None;
+                    }
+                    char::decode_utf16(buffer[..len.min(buffer.len())].iter().copied())
+                        .next()?
+                        .ok()
+                        .map(|ch| (key.keycode, ch))
+                })
+                .collect();
+            CFRelease(source);
+            Ok(HostLayout::from_chars(name, chars))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
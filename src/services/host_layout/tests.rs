@@ -0,0 +1,77 @@
+//! Tests for host keyboard layout capture.
+
+use super::*;
+
+/// Excerpt of `xkbcomp` output for a German layout.
+const GERMAN_KEYMAP: &str = r#"
+xkb_keymap {
+xkb_keycodes "evdev+aliases(qwertz)" {
+    <AD06> = 29;
+    <AB01> = 52;
+};
+xkb_symbols "pc+de+inet(evdev)" {
+    name[group1]="German";
+
+    key <TLDE>               {         [ dead_circumflex,          degree ] };
+    key <AE01>               {         [               1,          exclam ] };
+    key <AE11>               {         [          ssharp,        question ] };
+    key <AD06>               {
+        type= "FOUR_LEVEL_SEMIALPHABETIC",
+        symbols[Group1]= [               z,               Z,       leftarrow,             yen ]
+    };
+    key <AC10>               {         [      odiaeresis,      Odiaeresis ] };
+    key <AB01>               {
+        type= "FOUR_LEVEL_SEMIALPHABETIC",
+        symbols[Group1]= [               y,               Y ]
+    };
+    key <AB08>               {         [           comma,       semicolon ] };
+    key <LSGT>               {         [            less,         greater ] };
+    key <SPCE>               {         [           space ] };
+};
+};
+"#;
+
+#[test]
+fn test_from_xkb_keymap_reads_base_level() {
+    let layout = HostLayout::from_xkb_keymap("de", GERMAN_KEYMAP);
+
+    assert_eq!(layout.name(), "de");
+    assert_eq!(layout.label("KC_Y"), Some("Z"));
+    assert_eq!(layout.label("KC_Z"), Some("Y"));
+    assert_eq!(layout.label("KC_SCLN"), Some("Ö"));
+    assert_eq!(
+        layout.label("KC_MINS"),
+        Some("ß"),
+        "no single-letter upper case"
+    );
+    assert_eq!(layout.label("KC_GRV"), Some("^"), "dead key");
+    assert_eq!(layout.label("KC_1"), Some("1"));
+    assert_eq!(layout.label("KC_COMM"), Some(","));
+    assert_eq!(layout.label("KC_NUBS"), Some("<"));
+}
+
+#[test]
+fn test_from_xkb_keymap_skips_unmapped_keys() {
+    let layout = HostLayout::from_xkb_keymap("de", GERMAN_KEYMAP);
+
+    assert_eq!(layout.label("KC_A"), None, "not in the keymap");
+    assert_eq!(layout.label("KC_SPC"), None, "not layout dependent");
+}
+
+#[test]
+fn test_keysym_char() {
+    assert_eq!(keysym_char("a"), Some('a'));
+    assert_eq!(keysym_char("U00E9"), Some('é'));
+    assert_eq!(keysym_char("bracketleft"), Some('['));
+    assert_eq!(keysym_char("ISO_Level3_Shift"), None);
+}
+
+#[test]
+fn test_field_value() {
+    let status =
+        "   System Locale: LANG=de_DE.UTF-8\n       X11 Layout: de,us\n      X11 Variant: n/a\n";
+
+    assert_eq!(field_value(status, "X11 Layout:"), Some("de".to_string()));
+    assert_eq!(field_value(status, "X11 Variant:"), None);
+    assert_eq!(field_value(status, "X11 Model:"), None);
+}
@@ -0,0 +1,78 @@
+//! Key search across all layers.
+//!
+//! [`search_keys`] finds keys whose keycode, label or description contains
+//! a query, so a keycode can be found without remembering which layer it
+//! lives on. Used by the TUI search popup (`/`) and the web search endpoint.
+
+use crate::models::{KeyDefinition, Layout, Position};
+
+/// A key matching a search query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySearchMatch {
+    /// Layer index of the key
+    pub layer: usize,
+    /// Name of the key's layer
+    pub layer_name: String,
+    /// Matrix position of the key
+    pub position: Position,
+    /// Keycode assigned to the key
+    pub keycode: String,
+    /// The key's custom label, if any
+    pub label: Option<String>,
+    /// The key's description, if any
+    pub description: Option<String>,
+}
+
+/// Returns the non-blank value of an optional annotation.
+fn annotation(value: Option<&String>) -> Option<&str> {
+    value.map(String::as_str).filter(|v| !v.trim().is_empty())
+}
+
+/// Returns true if the key's keycode, label or description contains the
+/// lowercased `query`.
+fn key_matches(key: &KeyDefinition, query: &str) -> bool {
+    key.keycode.to_lowercase().contains(query)
+        || [key.label.as_ref(), key.description.as_ref()]
+            .into_iter()
+            .filter_map(annotation)
+            .any(|text| text.to_lowercase().contains(query))
+}
+
+/// Finds keys whose keycode, label or description contains `query`.
+///
+/// Matching is case-insensitive. An empty query returns every key with a
+/// label or description, which lists the annotated keys of the layout.
+/// Results are ordered by layer, then by key order within the layer.
+#[must_use]
+pub fn search_keys(layout: &Layout, query: &str) -> Vec<KeySearchMatch> {
+    let query = query.trim().to_lowercase();
+    let mut matches = Vec::new();
+
+    for (layer_idx, layer) in layout.layers.iter().enumerate() {
+        for key in &layer.keys {
+            let label = annotation(key.label.as_ref());
+            let description = annotation(key.description.as_ref());
+            let found = if query.is_empty() {
+                label.is_some() || description.is_some()
+            } else {
+                key_matches(key, &query)
+            };
+            if !found {
+                continue;
+            }
+            matches.push(KeySearchMatch {
+                layer: layer_idx,
+                layer_name: layer.name.clone(),
+                position: key.position,
+                keycode: key.keycode.clone(),
+                label: label.map(str::to_string),
+                description: description.map(str::to_string),
+            });
+        }
+    }
+
+    matches
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,74 @@
+//! Tests for services::key_search.
+
+use super::*;
+use crate::models::{Layer, RgbColor};
+
+/// Two layers with a labeled key, described keys and plain keycodes.
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+
+    let mut base = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    base.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    base.add_key(
+        KeyDefinition::new(Position::new(0, 1), "LT(@nav, KC_SPC)").with_description("Thumb nav"),
+    );
+    base.add_key(KeyDefinition::new(Position::new(0, 2), "KC_B").with_description("  "));
+    layout.add_layer(base).unwrap();
+
+    let mut nav = Layer::new(1, "Nav", RgbColor::new(0, 255, 0)).unwrap();
+    let mut email = KeyDefinition::new(Position::new(1, 0), "M_EMAIL");
+    email.label = Some("Inbox".to_string());
+    nav.add_key(email);
+    nav.add_key(KeyDefinition::new(Position::new(1, 1), "KC_HOME").with_description("Line start"));
+    nav.add_key(KeyDefinition::new(Position::new(1, 2), "KC_A"));
+    layout.add_layer(nav).unwrap();
+
+    layout
+}
+
+#[test]
+fn test_empty_query_lists_annotated_keys() {
+    let layout = create_test_layout();
+
+    let matches = search_keys(&layout, " ");
+
+    let keycodes: Vec<&str> = matches.iter().map(|m| m.keycode.as_str()).collect();
+    assert_eq!(keycodes, ["LT(@nav, KC_SPC)", "M_EMAIL", "KC_HOME"]);
+    assert_eq!(matches[1].layer, 1);
+    assert_eq!(matches[1].layer_name, "Nav");
+    assert_eq!(matches[1].label.as_deref(), Some("Inbox"));
+    assert_eq!(matches[1].description, None);
+}
+
+#[test]
+fn test_keycode_matches_on_every_layer() {
+    let layout = create_test_layout();
+
+    let matches = search_keys(&layout, "kc_a");
+
+    let found: Vec<(usize, Position)> = matches.iter().map(|m| (m.layer, m.position)).collect();
+    assert_eq!(found, [(0, Position::new(0, 0)), (1, Position::new(1, 2))]);
+}
+
+#[test]
+fn test_label_and_description_match() {
+    let layout = create_test_layout();
+
+    let by_label = search_keys(&layout, "INBOX");
+    assert_eq!(by_label.len(), 1);
+    assert_eq!(by_label[0].keycode, "M_EMAIL");
+
+    let by_description = search_keys(&layout, "line");
+    assert_eq!(by_description.len(), 1);
+    assert_eq!(by_description[0].description.as_deref(), Some("Line start"));
+}
+
+#[test]
+fn test_blank_description_is_ignored() {
+    let layout = create_test_layout();
+
+    let matches = search_keys(&layout, "KC_B");
+
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].description, None);
+}
@@ -0,0 +1,193 @@
+//! Index of the keyboards available under a QMK tree and the keyboard roots.
+//!
+//! Walking `qmk_firmware/keyboards/` and parsing every `info.json` takes
+//! seconds on a full QMK checkout, so the result is kept as a
+//! [`KeyboardIndex`] that can be saved to disk and reused across runs. An
+//! index records the QMK path and keyboard roots it was built from;
+//! [`KeyboardIndex::matches`] tells callers when it no longer applies.
+//! Entries carry the keyboard's USB ID so connected keyboards can be found
+//! with [`KeyboardIndex::connected`].
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::parser::keyboard_json::{QmkInfoJson, VariantKeyboardJson};
+use crate::services::device_detect::{self, DetectedKeyboard, UsbDevice, UsbId, UsbIdResolver};
+
+/// Directory depth below a keyboards directory that is searched.
+const MAX_DEPTH: usize = 4;
+
+/// A keyboard with at least one layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyboardEntry {
+    /// Keyboard path relative to its keyboards directory (e.g. "crkbd/rev1")
+    pub path: String,
+    /// Number of layout variants
+    pub layout_count: usize,
+    /// USB vendor and product ID from the keyboard's JSON files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usb: Option<UsbId>,
+}
+
+/// Keyboards found under one QMK path and set of keyboard roots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyboardIndex {
+    /// QMK firmware directory the index was built from
+    pub qmk_path: PathBuf,
+    /// Extra keyboard roots the index was built from
+    pub keyboard_roots: Vec<PathBuf>,
+    /// When the directories were scanned (RFC 3339)
+    pub built_at: String,
+    /// Keyboards sorted by path; a path in several roots is listed once
+    pub keyboards: Vec<KeyboardEntry>,
+}
+
+impl KeyboardIndex {
+    /// Scans `qmk_path/keyboards` and every keyboard root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the QMK keyboards directory does not exist.
+    /// Missing keyboard roots are skipped.
+    pub fn build(qmk_path: &Path, keyboard_roots: &[PathBuf]) -> Result<Self> {
+        let keyboards_dir = qmk_path.join("keyboards");
+        anyhow::ensure!(
+            keyboards_dir.is_dir(),
+            "QMK keyboards directory not found: {}",
+            keyboards_dir.display()
+        );
+
+        let mut keyboards = Vec::new();
+        let mut resolver = UsbIdResolver::default();
+        scan_directory(
+            &keyboards_dir,
+            &keyboards_dir,
+            &mut resolver,
+            &mut keyboards,
+        );
+        for root in keyboard_roots {
+            scan_directory(root, root, &mut resolver, &mut keyboards);
+        }
+        keyboards.sort_by(|a, b| a.path.cmp(&b.path));
+        keyboards.dedup_by(|a, b| a.path == b.path);
+
+        Ok(Self {
+            qmk_path: qmk_path.to_path_buf(),
+            keyboard_roots: keyboard_roots.to_vec(),
+            built_at: chrono::Utc::now().to_rfc3339(),
+            keyboards,
+        })
+    }
+
+    /// Returns true if the index was built from `qmk_path` and `keyboard_roots`.
+    #[must_use]
+    pub fn matches(&self, qmk_path: &Path, keyboard_roots: &[PathBuf]) -> bool {
+        self.qmk_path == qmk_path && self.keyboard_roots == keyboard_roots
+    }
+
+    /// Matches `devices` against the keyboards' USB IDs.
+    #[must_use]
+    pub fn connected(&self, devices: &[UsbDevice]) -> Vec<DetectedKeyboard> {
+        device_detect::match_devices(
+            devices,
+            self.keyboards
+                .iter()
+                .filter_map(|entry| Some((entry.path.as_str(), entry.usb?))),
+        )
+    }
+
+    /// Loads an index saved with [`KeyboardIndex::save`].
+    ///
+    /// Returns `None` if the file is missing or unreadable; the index is then
+    /// rebuilt rather than treated as an error.
+    #[must_use]
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Writes the index to `path`, creating its parent directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        let json = serde_json::to_string(self).context("Failed to serialize keyboard index")?;
+        fs::write(path, json)
+            .with_context(|| format!("Failed to write keyboard index {}", path.display()))
+    }
+}
+
+fn scan_directory(
+    base_dir: &Path,
+    current_dir: &Path,
+    resolver: &mut UsbIdResolver,
+    keyboards: &mut Vec<KeyboardEntry>,
+) {
+    let Ok(entries) = fs::read_dir(current_dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if dir_name.starts_with('.') || dir_name == "keymaps" {
+            continue;
+        }
+
+        if path.join("info.json").exists() || path.join("keyboard.json").exists() {
+            if let Ok(rel_path) = path.strip_prefix(base_dir) {
+                let layout_count = layout_count(&path);
+                if layout_count > 0 {
+                    keyboards.push(KeyboardEntry {
+                        path: rel_path.to_string_lossy().replace('\\', "/"),
+                        layout_count,
+                        usb: resolver.resolve(base_dir, &path),
+                    });
+                }
+            }
+        }
+
+        let depth = current_dir
+            .strip_prefix(base_dir)
+            .map(|p| p.components().count())
+            .unwrap_or(0);
+        if depth < MAX_DEPTH {
+            scan_directory(base_dir, &path, resolver, keyboards);
+        }
+    }
+}
+
+fn layout_count(keyboard_dir: &Path) -> usize {
+    if let Ok(content) = fs::read_to_string(keyboard_dir.join("info.json")) {
+        if let Ok(info) = json5::from_str::<QmkInfoJson>(&content) {
+            if !info.layouts.is_empty() {
+                return info.layouts.len();
+            }
+        }
+    }
+
+    if let Ok(content) = fs::read_to_string(keyboard_dir.join("keyboard.json")) {
+        if let Ok(variant) = json5::from_str::<VariantKeyboardJson>(&content) {
+            if !variant.layouts.is_empty() {
+                return variant.layouts.len();
+            }
+        }
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests;
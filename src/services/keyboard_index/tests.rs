@@ -0,0 +1,95 @@
+//! Tests for services::keyboard_index.
+
+use super::*;
+use tempfile::TempDir;
+
+const ONE_LAYOUT: &str = r#"{"layouts": {"LAYOUT": {"layout": [{"x": 0, "y": 0}]}}}"#;
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn test_build_lists_keyboards_with_layouts() {
+    let qmk = TempDir::new().unwrap();
+    let root = TempDir::new().unwrap();
+    let keyboards = qmk.path().join("keyboards");
+    write(&keyboards.join("crkbd/rev1/info.json"), ONE_LAYOUT);
+    write(
+        &keyboards.join("crkbd/keymaps/default/info.json"),
+        ONE_LAYOUT,
+    );
+    write(&keyboards.join("nolayouts/info.json"), "{}");
+    write(
+        &root.path().join("myvendor/board/keyboard.json"),
+        ONE_LAYOUT,
+    );
+    write(&root.path().join("crkbd/rev1/info.json"), ONE_LAYOUT);
+
+    let index = KeyboardIndex::build(qmk.path(), &[root.path().to_path_buf()]).unwrap();
+
+    let paths: Vec<&str> = index.keyboards.iter().map(|k| k.path.as_str()).collect();
+    assert_eq!(paths, vec!["crkbd/rev1", "myvendor/board"]);
+    assert_eq!(index.keyboards[0].layout_count, 1);
+}
+
+#[test]
+fn test_build_records_usb_ids() {
+    let qmk = TempDir::new().unwrap();
+    let keyboards = qmk.path().join("keyboards");
+    write(
+        &keyboards.join("crkbd/info.json"),
+        r#"{"usb": {"vid": "0x4653", "pid": "0x0001"}}"#,
+    );
+    write(&keyboards.join("crkbd/rev1/keyboard.json"), ONE_LAYOUT);
+    write(&keyboards.join("planck/info.json"), ONE_LAYOUT);
+
+    let index = KeyboardIndex::build(qmk.path(), &[]).unwrap();
+    let corne = UsbId {
+        vid: 0x4653,
+        pid: 0x0001,
+    };
+    assert_eq!(index.keyboards[0].usb, Some(corne));
+    assert_eq!(index.keyboards[1].usb, None);
+
+    let device = UsbDevice {
+        id: corne,
+        manufacturer: None,
+        product: Some("Corne".to_string()),
+    };
+    let detected = index.connected(&[device]);
+    assert_eq!(detected.len(), 1);
+    assert_eq!(detected[0].keyboards, vec!["crkbd/rev1"]);
+}
+
+#[test]
+fn test_build_requires_keyboards_directory() {
+    let qmk = TempDir::new().unwrap();
+    assert!(KeyboardIndex::build(qmk.path(), &[]).is_err());
+}
+
+#[test]
+fn test_save_and_load_round_trip() {
+    let qmk = TempDir::new().unwrap();
+    write(&qmk.path().join("keyboards/planck/info.json"), ONE_LAYOUT);
+    let index = KeyboardIndex::build(qmk.path(), &[]).unwrap();
+    let file = qmk.path().join("cache/keyboard_index.json");
+
+    index.save(&file).unwrap();
+
+    assert_eq!(KeyboardIndex::load(&file), Some(index));
+    assert_eq!(KeyboardIndex::load(&qmk.path().join("missing.json")), None);
+}
+
+#[test]
+fn test_matches_checks_qmk_path_and_roots() {
+    let qmk = TempDir::new().unwrap();
+    fs::create_dir_all(qmk.path().join("keyboards")).unwrap();
+    let roots = vec![PathBuf::from("/extra")];
+    let index = KeyboardIndex::build(qmk.path(), &roots).unwrap();
+
+    assert!(index.matches(qmk.path(), &roots));
+    assert!(!index.matches(qmk.path(), &[]));
+    assert!(!index.matches(Path::new("/other/qmk"), &roots));
+}
@@ -0,0 +1,68 @@
+//! Building keyboards that live outside the QMK tree.
+//!
+//! `paths.keyboard_roots` lets users point LazyQMK at keyboards that were
+//! never upstreamed (a personal QMK fork, a vendor's out-of-tree directory).
+//! Geometry and layouts are read from those roots directly, but `qmk compile`
+//! only knows about `qmk_firmware/keyboards/`, so the keyboard is copied there
+//! before keymap files are written.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::parser::keyboard_json::keyboards_dir_for;
+
+/// Copies `keyboard` from its keyboard root into QMK's `keyboards/` directory.
+///
+/// The whole top-level directory (e.g. `myvendor/` for `myvendor/board/rev1`)
+/// is copied so parent `info.json` files and shared sources come along.
+/// Existing files are overwritten and nothing is deleted, so keymaps already
+/// in the QMK tree survive.
+///
+/// Returns the installed directory, or `None` when the keyboard is QMK's own.
+///
+/// # Errors
+///
+/// Returns an error if copying fails.
+pub fn install_keyboard(
+    qmk_path: &Path,
+    keyboard_roots: &[PathBuf],
+    keyboard: &str,
+) -> Result<Option<PathBuf>> {
+    let qmk_keyboards = qmk_path.join("keyboards");
+    let keyboards_dir = keyboards_dir_for(qmk_path, keyboard_roots, keyboard);
+    if keyboards_dir == qmk_keyboards {
+        return Ok(None);
+    }
+
+    let top_level = keyboard.split('/').next().unwrap_or(keyboard);
+    let target = qmk_keyboards.join(top_level);
+    copy_dir(&keyboards_dir.join(top_level), &target)?;
+    Ok(Some(target))
+}
+
+/// Recursively copies `src` into `dst`, overwriting files that exist.
+fn copy_dir(src: &Path, dst: &Path) -> Result<()> {
+    fs::create_dir_all(dst)
+        .with_context(|| format!("Failed to create directory {}", dst.display()))?;
+
+    for entry in
+        fs::read_dir(src).with_context(|| format!("Failed to read directory {}", src.display()))?
+    {
+        let entry = entry.context("Failed to read directory entry")?;
+        let path = entry.path();
+        let target = dst.join(entry.file_name());
+        if path.is_dir() {
+            copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target).with_context(|| {
+                format!("Failed to copy {} to {}", path.display(), target.display())
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,50 @@
+//! Tests for services::keyboard_roots.
+
+use super::*;
+use tempfile::TempDir;
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn test_install_keyboard_copies_top_level_directory() {
+    let qmk = TempDir::new().unwrap();
+    let root = TempDir::new().unwrap();
+    write(&qmk.path().join("keyboards/crkbd/info.json"), "{}");
+    write(&root.path().join("myvendor/info.json"), "{}");
+    write(&root.path().join("myvendor/board/keyboard.json"), "{}");
+    write(&root.path().join("myvendor/board/rules.mk"), "NEW");
+    write(&qmk.path().join("keyboards/myvendor/board/rules.mk"), "OLD");
+    write(
+        &qmk.path()
+            .join("keyboards/myvendor/board/keymaps/mine/keymap.c"),
+        "",
+    );
+
+    let installed =
+        install_keyboard(qmk.path(), &[root.path().to_path_buf()], "myvendor/board").unwrap();
+
+    let target = qmk.path().join("keyboards/myvendor");
+    assert_eq!(installed, Some(target.clone()));
+    assert!(target.join("info.json").exists());
+    assert!(target.join("board/keyboard.json").exists());
+    assert_eq!(
+        fs::read_to_string(target.join("board/rules.mk")).unwrap(),
+        "NEW"
+    );
+    // Existing keymaps are left alone
+    assert!(target.join("board/keymaps/mine/keymap.c").exists());
+}
+
+#[test]
+fn test_install_keyboard_skips_qmk_keyboards() {
+    let qmk = TempDir::new().unwrap();
+    let root = TempDir::new().unwrap();
+    write(&qmk.path().join("keyboards/crkbd/info.json"), "{}");
+
+    let installed = install_keyboard(qmk.path(), &[root.path().to_path_buf()], "crkbd").unwrap();
+
+    assert_eq!(installed, None);
+}
@@ -0,0 +1,112 @@
+//! Layer access consistency check.
+//!
+//! A layer that can only be reached by holding a key (`MO`, `LT` or `LM`)
+//! needs that key's position to stay transparent on the layer itself.
+//! Otherwise the layer's own key replaces the hold key while it is held,
+//! which typically leaves the user stuck or unable to reach the layer again.
+//! Layers that can also be toggled, switched to or one-shot are not flagged,
+//! since the user has another way in and out.
+//!
+//! [`find_layer_access_issues`] lists the offending positions and
+//! [`fix_layer_access_issues`] sets them to `KC_TRNS`.
+
+use crate::models::Layer;
+use crate::services::layer_refs::{build_layer_ref_index, LayerRef, LayerRefKind};
+
+/// Keycode the fix puts on the target layer.
+const TRANSPARENT: &str = "KC_TRNS";
+
+/// A hold key whose position its only target layer overrides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerAccessIssue {
+    /// The hold key (its `to_layer` and `position` are the position to fix)
+    pub layer_ref: LayerRef,
+    /// Non-transparent keycode on the target layer at that position
+    pub keycode: String,
+}
+
+impl LayerAccessIssue {
+    /// Layer whose key should become transparent.
+    #[must_use]
+    pub const fn layer(&self) -> usize {
+        self.layer_ref.to_layer
+    }
+
+    /// One-line description, e.g. "Layer 2 [3,4] has KC_A where MO(2) from Layer 0 is held".
+    #[must_use]
+    pub fn message(&self) -> String {
+        format!(
+            "Layer {} [{},{}] has {} where {} from Layer {} is held",
+            self.layer_ref.to_layer,
+            self.layer_ref.position.row,
+            self.layer_ref.position.col,
+            self.keycode,
+            self.layer_ref.keycode,
+            self.layer_ref.from_layer
+        )
+    }
+}
+
+/// Returns true for references that only activate a layer while held.
+const fn is_hold_only(kind: LayerRefKind) -> bool {
+    matches!(
+        kind,
+        LayerRefKind::Momentary | LayerRefKind::TapHold | LayerRefKind::LayerMod
+    )
+}
+
+/// Finds hold keys whose position is overridden on a layer that has no
+/// other way in, ordered by target layer.
+#[must_use]
+pub fn find_layer_access_issues(layers: &[Layer]) -> Vec<LayerAccessIssue> {
+    let index = build_layer_ref_index(layers);
+    let mut targets: Vec<usize> = index.keys().copied().collect();
+    targets.sort_unstable();
+
+    let mut issues = Vec::new();
+    for target in targets {
+        let refs: Vec<&LayerRef> = index[&target]
+            .iter()
+            .filter(|r| r.from_layer != target)
+            .collect();
+        if refs.is_empty() || !refs.iter().all(|r| is_hold_only(r.kind)) {
+            continue;
+        }
+
+        for layer_ref in refs {
+            let Some(key) = layers[target].get_key(layer_ref.position) else {
+                continue;
+            };
+            if !key.is_transparent() {
+                issues.push(LayerAccessIssue {
+                    layer_ref: layer_ref.clone(),
+                    keycode: key.keycode.clone(),
+                });
+            }
+        }
+    }
+    issues
+}
+
+/// Sets the position of each issue to `KC_TRNS` on its target layer.
+///
+/// Locked keys are left alone. Returns the number of keys changed.
+pub fn fix_layer_access_issues(layers: &mut [Layer], issues: &[LayerAccessIssue]) -> usize {
+    let mut fixed = 0;
+    for issue in issues {
+        let Some(key) = layers
+            .get_mut(issue.layer())
+            .and_then(|layer| layer.get_key_mut(issue.layer_ref.position))
+        else {
+            continue;
+        };
+        if !key.locked && !key.is_transparent() {
+            key.keycode = TRANSPARENT.to_string();
+            fixed += 1;
+        }
+    }
+    fixed
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,78 @@
+//! Tests for services::layer_access.
+
+use super::*;
+use crate::models::{KeyDefinition, Position, RgbColor};
+
+/// Layers with the given keycodes at positions (0, 0) and (0, 1).
+fn layers(keycodes: &[[&str; 2]]) -> Vec<Layer> {
+    keycodes
+        .iter()
+        .enumerate()
+        .map(|(idx, codes)| {
+            let mut layer =
+                Layer::new(idx as u8, format!("L{idx}"), RgbColor::new(0, 0, 0)).unwrap();
+            for (col, code) in codes.iter().enumerate() {
+                layer.add_key(KeyDefinition::new(Position::new(0, col as u8), *code));
+            }
+            layer
+        })
+        .collect()
+}
+
+#[test]
+fn test_flags_overridden_hold_key() {
+    let layers = layers(&[["MO(1)", "KC_A"], ["KC_ESC", "KC_B"]]);
+
+    let issues = find_layer_access_issues(&layers);
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].layer(), 1);
+    assert_eq!(issues[0].layer_ref.position, Position::new(0, 0));
+    assert_eq!(issues[0].keycode, "KC_ESC");
+    assert_eq!(
+        issues[0].message(),
+        "Layer 1 [0,0] has KC_ESC where MO(1) from Layer 0 is held"
+    );
+}
+
+#[test]
+fn test_transparent_target_is_fine() {
+    let layers = layers(&[["LT(1, KC_SPC)", "KC_A"], ["KC_TRNS", "KC_B"]]);
+    assert!(find_layer_access_issues(&layers).is_empty());
+}
+
+#[test]
+fn test_layer_with_another_way_in_is_not_flagged() {
+    let layers = layers(&[["MO(1)", "TG(1)"], ["KC_ESC", "KC_TRNS"]]);
+    assert!(find_layer_access_issues(&layers).is_empty());
+}
+
+#[test]
+fn test_self_reference_does_not_count_as_access() {
+    let layers = layers(&[["MO(1)", "KC_A"], ["KC_NO", "TG(1)"]]);
+
+    let issues = find_layer_access_issues(&layers);
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].keycode, "KC_NO");
+}
+
+#[test]
+fn test_fix_sets_transparent_and_skips_locked() {
+    let mut layers = layers(&[["MO(1)", "MO(2)"], ["KC_ESC", "KC_B"], ["KC_C", "KC_D"]]);
+    layers[2].get_key_mut(Position::new(0, 1)).unwrap().locked = true;
+    let issues = find_layer_access_issues(&layers);
+    assert_eq!(issues.len(), 2);
+
+    assert_eq!(fix_layer_access_issues(&mut layers, &issues), 1);
+
+    assert_eq!(
+        layers[1].get_key(Position::new(0, 0)).unwrap().keycode,
+        "KC_TRNS"
+    );
+    assert_eq!(
+        layers[2].get_key(Position::new(0, 1)).unwrap().keycode,
+        "KC_D"
+    );
+    assert_eq!(find_layer_access_issues(&layers).len(), 1);
+}
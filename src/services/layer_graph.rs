@@ -0,0 +1,175 @@
+//! Layer dependency graph.
+//!
+//! Lists which keys activate which layers (see [`crate::services::layer_refs`])
+//! and flags two kinds of layer that usually mean a bug:
+//!
+//! - Unreachable layers: no chain of layer keys leads to them from the base
+//!   layer.
+//! - Layers with no way back: a latching key (`TG`, `TO`, `TT` or `DF`)
+//!   turns them on, but nothing on the layer turns it off again, so the
+//!   keyboard is stuck on it until it is unplugged.
+//!
+//! Layers that are only held (`MO`, `LT`, `LM`) or one-shot (`OSL`) release
+//! by themselves and always have a way back.
+
+use std::collections::VecDeque;
+
+use crate::models::Layer;
+use crate::services::layer_refs::{
+    build_layer_ref_index, parse_layer_keycode, resolve_layer_target, LayerRef, LayerRefKind,
+};
+use crate::services::resolved_keys::resolve_key;
+
+/// One layer of the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerGraphNode {
+    /// Layer index
+    pub layer: usize,
+    /// Layer name
+    pub name: String,
+    /// Whether a chain of layer keys leads here from the base layer
+    pub reachable: bool,
+    /// Whether the layer can be left once a latching key turned it on
+    pub has_way_back: bool,
+    /// Latching keys on other layers that turn this layer on
+    pub latched_by: Vec<LayerRef>,
+}
+
+/// Layer keys of a layout and what they imply for each layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerGraph {
+    /// One node per layer, in layer order
+    pub nodes: Vec<LayerGraphNode>,
+    /// Layer keys between different layers, by source layer then key order
+    pub edges: Vec<LayerRef>,
+}
+
+/// Returns true for references that leave the layer on after the key is
+/// released.
+const fn is_latching(kind: LayerRefKind) -> bool {
+    matches!(
+        kind,
+        LayerRefKind::Toggle
+            | LayerRefKind::SwitchTo
+            | LayerRefKind::TapToggle
+            | LayerRefKind::DefaultSet
+    )
+}
+
+impl LayerGraph {
+    /// Builds the graph of `layers`.
+    #[must_use]
+    pub fn build(layers: &[Layer]) -> Self {
+        let mut edges: Vec<LayerRef> = build_layer_ref_index(layers)
+            .into_values()
+            .flatten()
+            .filter(|r| r.from_layer != r.to_layer)
+            .collect();
+        edges.sort_by_key(|r| (r.from_layer, r.position.row, r.position.col, r.to_layer));
+
+        let reachable = reachable_layers(layers.len(), &edges);
+        let nodes = layers
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                let latched_by: Vec<LayerRef> = edges
+                    .iter()
+                    .filter(|r| r.to_layer == index && is_latching(r.kind))
+                    .cloned()
+                    .collect();
+                LayerGraphNode {
+                    layer: index,
+                    name: layer.name.clone(),
+                    reachable: reachable[index],
+                    has_way_back: index == 0
+                        || latched_by.is_empty()
+                        || has_exit(layers, index, &latched_by),
+                    latched_by,
+                }
+            })
+            .collect();
+
+        Self { nodes, edges }
+    }
+
+    /// Layer keys on layer `layer`.
+    pub fn edges_from(&self, layer: usize) -> impl Iterator<Item = &LayerRef> {
+        self.edges.iter().filter(move |r| r.from_layer == layer)
+    }
+
+    /// One message per unreachable layer or layer with no way back, in
+    /// layer order.
+    #[must_use]
+    pub fn issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        for node in &self.nodes {
+            if !node.reachable {
+                issues.push(format!(
+                    "Layer {} ({}) can't be reached from the base layer",
+                    node.layer, node.name
+                ));
+            }
+            if !node.has_way_back {
+                let keys: Vec<String> = node
+                    .latched_by
+                    .iter()
+                    .map(|r| format!("{} on layer {}", r.keycode, r.from_layer))
+                    .collect();
+                issues.push(format!(
+                    "Layer {} ({}) has no way back: {} turns it on but no key on it turns it off",
+                    node.layer,
+                    node.name,
+                    keys.join(", ")
+                ));
+            }
+        }
+        issues
+    }
+}
+
+/// Marks the layers reachable from layer 0 over `edges`.
+fn reachable_layers(layer_count: usize, edges: &[LayerRef]) -> Vec<bool> {
+    let mut reachable = vec![false; layer_count];
+    if layer_count == 0 {
+        return reachable;
+    }
+    reachable[0] = true;
+    let mut queue = VecDeque::from([0]);
+    while let Some(layer) = queue.pop_front() {
+        for edge in edges.iter().filter(|r| r.from_layer == layer) {
+            if !reachable[edge.to_layer] {
+                reachable[edge.to_layer] = true;
+                queue.push_back(edge.to_layer);
+            }
+        }
+    }
+    reachable
+}
+
+/// Returns true if layer `layer` has a key that turns it off: `TG`/`TT` of
+/// itself, `TO`/`DF` of another layer, or a transparent key over one of the
+/// toggle keys in `latched_by`.
+fn has_exit(layers: &[Layer], layer: usize, latched_by: &[LayerRef]) -> bool {
+    let leaves = |keycode: &str| {
+        parse_layer_keycode(keycode).is_some_and(|(target, kind)| {
+            let target = resolve_layer_target(&target, layers);
+            match kind {
+                LayerRefKind::Toggle | LayerRefKind::TapToggle => target == Some(layer),
+                LayerRefKind::SwitchTo | LayerRefKind::DefaultSet => {
+                    target.is_some_and(|target| target != layer)
+                }
+                _ => false,
+            }
+        })
+    };
+
+    layers[layer].keys.iter().any(|key| leaves(&key.keycode))
+        || latched_by.iter().any(|r| {
+            matches!(r.kind, LayerRefKind::Toggle | LayerRefKind::TapToggle)
+                && resolve_key(layers, layer, r.position)
+                    .is_some_and(|resolved| leaves(&resolved.keycode))
+        })
+}
+
+#[cfg(test)]
+mod tests;
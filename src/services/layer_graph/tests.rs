@@ -0,0 +1,88 @@
+//! Tests for services::layer_graph.
+
+use super::*;
+use crate::models::{KeyDefinition, Position, RgbColor};
+
+/// Layers with the given keycodes at positions (0, 0) and (0, 1).
+fn layers(keycodes: &[[&str; 2]]) -> Vec<Layer> {
+    keycodes
+        .iter()
+        .enumerate()
+        .map(|(idx, codes)| {
+            let mut layer =
+                Layer::new(idx as u8, format!("L{idx}"), RgbColor::new(0, 0, 0)).unwrap();
+            for (col, code) in codes.iter().enumerate() {
+                layer.add_key(KeyDefinition::new(Position::new(0, col as u8), *code));
+            }
+            layer
+        })
+        .collect()
+}
+
+#[test]
+fn test_edges_list_layer_keys_between_layers() {
+    let layers = layers(&[["MO(1)", "TG(2)"], ["KC_TRNS", "TO(2)"], ["TG(2)", "KC_A"]]);
+
+    let graph = LayerGraph::build(&layers);
+
+    let edges: Vec<(usize, usize, &str)> = graph
+        .edges
+        .iter()
+        .map(|r| (r.from_layer, r.to_layer, r.keycode.as_str()))
+        .collect();
+    assert_eq!(edges, [(0, 1, "MO(1)"), (0, 2, "TG(2)"), (1, 2, "TO(2)")]);
+    assert_eq!(graph.edges_from(1).count(), 1);
+    assert!(graph.issues().is_empty());
+}
+
+#[test]
+fn test_flags_unreachable_layer() {
+    let layers = layers(&[["MO(1)", "KC_A"], ["KC_TRNS", "KC_B"], ["MO(1)", "KC_C"]]);
+
+    let graph = LayerGraph::build(&layers);
+
+    assert!(graph.nodes[1].reachable);
+    assert!(!graph.nodes[2].reachable);
+    assert_eq!(
+        graph.issues(),
+        ["Layer 2 (L2) can't be reached from the base layer"]
+    );
+}
+
+#[test]
+fn test_flags_latched_layer_with_no_way_back() {
+    let layers = layers(&[["KC_A", "TO(1)"], ["KC_B", "KC_TRNS"]]);
+
+    let graph = LayerGraph::build(&layers);
+
+    assert!(!graph.nodes[1].has_way_back);
+    assert_eq!(
+        graph.issues(),
+        ["Layer 1 (L1) has no way back: TO(1) on layer 0 turns it on but no key on it turns it off"]
+    );
+}
+
+#[test]
+fn test_exits_from_latched_layer() {
+    // Transparent key over the toggle key
+    let graph = LayerGraph::build(&layers(&[["TG(1)", "KC_A"], ["KC_TRNS", "KC_B"]]));
+    assert!(graph.nodes[1].has_way_back);
+
+    // Toggle key overridden on the layer
+    let graph = LayerGraph::build(&layers(&[["TG(1)", "KC_A"], ["KC_ESC", "KC_B"]]));
+    assert!(!graph.nodes[1].has_way_back);
+
+    // Switch back to the base layer
+    let graph = LayerGraph::build(&layers(&[["TG(1)", "KC_A"], ["KC_ESC", "TO(0)"]]));
+    assert!(graph.nodes[1].has_way_back);
+}
+
+#[test]
+fn test_held_layers_always_have_a_way_back() {
+    let layers = layers(&[["MO(1)", "OSL(1)"], ["KC_ESC", "KC_B"]]);
+
+    let graph = LayerGraph::build(&layers);
+
+    assert!(graph.nodes[1].has_way_back);
+    assert!(graph.nodes[1].latched_by.is_empty());
+}
@@ -0,0 +1,96 @@
+//! Horizontal layer mirroring based on keyboard geometry.
+//!
+//! Each key is paired with the key at the mirrored physical position (same
+//! row, reflected around the keyboard's vertical center line), so one half of
+//! a split keyboard can be copied onto the other. Keys without a partner,
+//! such as a center column, keep their assignment.
+
+use std::collections::HashMap;
+
+use crate::models::{KeyboardGeometry, Layer, Position};
+
+/// Maximum distance, in key units, between a reflected key center and the
+/// key it is paired with.
+#[allow(dead_code)] // Used by the web layer duplicate endpoint (lib target only)
+const MATCH_TOLERANCE: f32 = 0.5;
+
+/// Builds the mirror pairing for a keyboard: visual position to the visual
+/// position on the opposite side.
+///
+/// Keys are compared by their centers; a key is only paired when another key
+/// sits within half a key unit of its reflection.
+#[allow(dead_code)] // Used by the web layer duplicate endpoint (lib target only)
+#[must_use]
+pub fn mirror_positions(geometry: &KeyboardGeometry) -> HashMap<Position, Position> {
+    let centers: Vec<(Position, f32, f32)> = geometry
+        .keys
+        .iter()
+        .map(|key| {
+            let position = Position::new(key.visual_y.round() as u8, key.visual_x.round() as u8);
+            (
+                position,
+                key.width.mul_add(0.5, key.visual_x),
+                key.height.mul_add(0.5, key.visual_y),
+            )
+        })
+        .collect();
+
+    let left = geometry
+        .keys
+        .iter()
+        .map(|key| key.visual_x)
+        .fold(f32::INFINITY, f32::min);
+    let right = geometry
+        .keys
+        .iter()
+        .map(|key| key.visual_x + key.width)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let axis_sum = left + right;
+
+    let mut pairs = HashMap::new();
+    for &(position, x, y) in &centers {
+        let mirrored_x = axis_sum - x;
+        let partner = centers
+            .iter()
+            .map(|&(other, ox, oy)| (other, (ox - mirrored_x).hypot(oy - y)))
+            .filter(|&(_, distance)| distance <= MATCH_TOLERANCE)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((partner, _)) = partner {
+            if partner != position {
+                pairs.insert(position, partner);
+            }
+        }
+    }
+    pairs
+}
+
+/// Mirrors `layer` in place using `pairs` from [`mirror_positions`].
+///
+/// Every key takes the keycode, color, and category of its partner. Locked
+/// keys are left unchanged. Returns the number of keys that changed.
+#[allow(dead_code)] // Used by the web layer duplicate endpoint (lib target only)
+pub fn mirror_layer(layer: &mut Layer, pairs: &HashMap<Position, Position>) -> usize {
+    let source = layer.keys.clone();
+    let mut changed = 0;
+    for key in layer.keys.iter_mut().filter(|key| !key.locked) {
+        let Some(partner) = pairs
+            .get(&key.position)
+            .and_then(|partner| source.iter().find(|k| k.position == *partner))
+        else {
+            continue;
+        };
+        if key.keycode != partner.keycode
+            || key.color_override != partner.color_override
+            || key.category_id != partner.category_id
+        {
+            key.keycode.clone_from(&partner.keycode);
+            key.color_override = partner.color_override;
+            key.category_id.clone_from(&partner.category_id);
+            changed += 1;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,46 @@
+//! Tests for layer mirroring.
+
+use super::*;
+
+use crate::models::{KeyDefinition, KeyGeometry, RgbColor};
+
+/// Split board: two keys per half with a gap, plus a centered key below.
+fn split_geometry() -> KeyboardGeometry {
+    let mut geometry = KeyboardGeometry::new("split", "LAYOUT", 2, 4);
+    geometry.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+    geometry.add_key(KeyGeometry::new((0, 1), 1, 1.0, 0.0));
+    geometry.add_key(KeyGeometry::new((0, 2), 2, 4.0, 0.0));
+    geometry.add_key(KeyGeometry::new((0, 3), 3, 5.0, 0.0));
+    geometry.add_key(KeyGeometry::new((1, 0), 4, 2.5, 1.0));
+    geometry
+}
+
+#[test]
+fn test_mirror_positions_pairs_opposite_keys() {
+    let pairs = mirror_positions(&split_geometry());
+
+    assert_eq!(pairs.get(&Position::new(0, 0)), Some(&Position::new(0, 5)));
+    assert_eq!(pairs.get(&Position::new(0, 5)), Some(&Position::new(0, 0)));
+    assert_eq!(pairs.get(&Position::new(0, 1)), Some(&Position::new(0, 4)));
+    // The center key mirrors onto itself and is left out
+    assert_eq!(pairs.len(), 4);
+}
+
+#[test]
+fn test_mirror_layer_flips_keys_and_keeps_locked() {
+    let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A").with_category("nav"));
+    layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"));
+    layer.add_key(KeyDefinition::new(Position::new(0, 4), "KC_C"));
+    layer.add_key(KeyDefinition::new(Position::new(0, 5), "KC_D"));
+    layer.add_key(KeyDefinition::new(Position::new(1, 3), "KC_SPC"));
+    layer.keys[2].locked = true;
+
+    let changed = mirror_layer(&mut layer, &mirror_positions(&split_geometry()));
+
+    let keycodes: Vec<_> = layer.keys.iter().map(|k| k.keycode.as_str()).collect();
+    assert_eq!(keycodes, ["KC_D", "KC_C", "KC_C", "KC_A", "KC_SPC"]);
+    assert_eq!(layer.keys[3].category_id.as_deref(), Some("nav"));
+    assert_eq!(layer.keys[0].category_id, None);
+    assert_eq!(changed, 3);
+}
@@ -184,12 +184,6 @@ pub fn parse_layer_keycode(keycode: &str) -> Option<(LayerRefTarget, LayerRefKin
 pub fn build_layer_ref_index(layers: &[Layer]) -> HashMap<usize, Vec<LayerRef>> {
     let mut index: HashMap<usize, Vec<LayerRef>> = HashMap::new();
 
-    // Build map from layer UUID (string) to index for resolution
-    let mut id_to_index: HashMap<String, usize> = HashMap::new();
-    for (idx, layer) in layers.iter().enumerate() {
-        id_to_index.insert(layer.id.clone(), idx);
-    }
-
     for (from_layer_idx, layer) in layers.iter().enumerate() {
         for key in &layer.keys {
             // Skip transparent/no-op keys
@@ -199,35 +193,15 @@ pub fn build_layer_ref_index(layers: &[Layer]) -> HashMap<usize, Vec<LayerRef>>
 
             // Try to parse as layer keycode
             if let Some((target, kind)) = parse_layer_keycode(&key.keycode) {
-                match target {
-                    LayerRefTarget::Index(to_layer) => {
-                        // Only track references to existing layers
-                        if to_layer < layers.len() {
-                            let layer_ref = LayerRef {
-                                from_layer: from_layer_idx,
-                                to_layer,
-                                position: key.position,
-                                kind,
-                                keycode: key.keycode.clone(),
-                            };
-
-                            index.entry(to_layer).or_default().push(layer_ref);
-                        }
-                    }
-                    LayerRefTarget::Uuid(uuid) => {
-                        // Strip leading '@' if present and resolve against layer IDs
-                        let trimmed = uuid.strip_prefix('@').unwrap_or(uuid.as_str());
-                        if let Some(&to_layer) = id_to_index.get(trimmed) {
-                            let layer_ref = LayerRef {
-                                from_layer: from_layer_idx,
-                                to_layer,
-                                position: key.position,
-                                kind,
-                                keycode: key.keycode.clone(),
-                            };
-                            index.entry(to_layer).or_default().push(layer_ref);
-                        }
-                    }
+                if let Some(to_layer) = resolve_layer_target(&target, layers) {
+                    let layer_ref = LayerRef {
+                        from_layer: from_layer_idx,
+                        to_layer,
+                        position: key.position,
+                        kind,
+                        keycode: key.keycode.clone(),
+                    };
+                    index.entry(to_layer).or_default().push(layer_ref);
                 }
             }
         }
@@ -236,6 +210,72 @@ pub fn build_layer_ref_index(layers: &[Layer]) -> HashMap<usize, Vec<LayerRef>>
     index
 }
 
+/// Resolves a layer reference target to the index of an existing layer.
+///
+/// UUID targets (with or without a leading `@`) are matched against layer IDs.
+/// Returns `None` if the target layer doesn't exist.
+#[must_use]
+pub fn resolve_layer_target(target: &LayerRefTarget, layers: &[Layer]) -> Option<usize> {
+    match target {
+        LayerRefTarget::Index(index) => (*index < layers.len()).then_some(*index),
+        LayerRefTarget::Uuid(uuid) => {
+            let trimmed = uuid.strip_prefix('@').unwrap_or(uuid.as_str());
+            layers.iter().position(|layer| layer.id == trimmed)
+        }
+    }
+}
+
+/// Rewrites the layer index of a layer keycode with `map`.
+///
+/// Returns the new keycode if `keycode` targets a layer by index and `map`
+/// changes that index. Keycodes targeting a layer by `@id` are left alone,
+/// since layer IDs don't change when layers move.
+///
+/// # Examples
+/// ```
+/// use lazyqmk::services::layer_refs::remap_layer_index;
+///
+/// assert_eq!(remap_layer_index("LT(1, KC_SPC)", |n| n + 1), Some("LT(2, KC_SPC)".to_string()));
+/// assert_eq!(remap_layer_index("MO(@abc)", |n| n + 1), None);
+/// ```
+#[must_use]
+pub fn remap_layer_index(keycode: &str, map: impl Fn(usize) -> usize) -> Option<String> {
+    let (LayerRefTarget::Index(layer), _) = parse_layer_keycode(keycode)? else {
+        return None;
+    };
+    let new_layer = map(layer);
+    if new_layer == layer {
+        return None;
+    }
+
+    let open = keycode.find('(')? + 1;
+    let inner = &keycode[open..];
+    let start = open + inner.len() - inner.trim_start().len();
+    let end = start
+        + keycode[start..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+    Some(format!(
+        "{}{new_layer}{}",
+        &keycode[..start],
+        &keycode[end..]
+    ))
+}
+
+/// Points a layer keycode that targets the layer `from_id` by `@id` at
+/// `to_id` instead.
+///
+/// Returns `None` if `keycode` doesn't target `from_id`.
+#[must_use]
+pub fn retarget_layer_id(keycode: &str, from_id: &str, to_id: &str) -> Option<String> {
+    let (LayerRefTarget::Uuid(target), _) = parse_layer_keycode(keycode)? else {
+        return None;
+    };
+    (target.strip_prefix('@') == Some(from_id))
+        .then(|| keycode.replacen(&target, &format!("@{to_id}"), 1))
+}
+
 /// Check if a keycode is transparent (allows fallthrough to lower layers)
 #[must_use]
 pub fn is_transparent(keycode: &str) -> bool {
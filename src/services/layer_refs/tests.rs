@@ -4,7 +4,6 @@
 
 use super::*;
 
-use super::*;
 use crate::models::{KeyDefinition, Layer, RgbColor};
 
 #[test]
@@ -279,3 +278,56 @@ fn test_check_transparency_conflict_multiple_refs() {
     assert!(msg.contains("Layer 0"));
     assert!(msg.contains("Layer 2"));
 }
+
+#[test]
+fn test_resolve_layer_target() {
+    let layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
+    let layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
+    let lower_id = layer1.id.clone();
+    let layers = vec![layer0, layer1];
+
+    assert_eq!(
+        resolve_layer_target(&LayerRefTarget::Index(1), &layers),
+        Some(1)
+    );
+    assert_eq!(
+        resolve_layer_target(&LayerRefTarget::Index(2), &layers),
+        None
+    );
+    assert_eq!(
+        resolve_layer_target(&LayerRefTarget::Uuid(format!("@{lower_id}")), &layers),
+        Some(1)
+    );
+    assert_eq!(
+        resolve_layer_target(&LayerRefTarget::Uuid("@missing".to_string()), &layers),
+        None
+    );
+}
+
+#[test]
+fn test_remap_layer_index() {
+    let shift = |n: usize| if n >= 2 { n + 1 } else { n };
+
+    assert_eq!(remap_layer_index("MO(2)", shift), Some("MO(3)".to_string()));
+    assert_eq!(
+        remap_layer_index("LT( 2 , KC_SPC)", shift),
+        Some("LT( 3 , KC_SPC)".to_string())
+    );
+    assert_eq!(
+        remap_layer_index("LM(12, MOD_LSFT)", shift),
+        Some("LM(13, MOD_LSFT)".to_string())
+    );
+    assert_eq!(remap_layer_index("TG(1)", shift), None);
+    assert_eq!(remap_layer_index("MO(@abc)", shift), None);
+    assert_eq!(remap_layer_index("KC_A", shift), None);
+}
+
+#[test]
+fn test_retarget_layer_id() {
+    assert_eq!(
+        retarget_layer_id("LT(@abc, KC_SPC)", "abc", "xyz"),
+        Some("LT(@xyz, KC_SPC)".to_string())
+    );
+    assert_eq!(retarget_layer_id("MO(@abcd)", "abc", "xyz"), None);
+    assert_eq!(retarget_layer_id("MO(1)", "abc", "xyz"), None);
+}
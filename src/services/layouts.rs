@@ -16,11 +16,15 @@
 //! 3. The `.md` file is renamed to `.md.bak`
 //!
 //! This ensures zero-touch migration for existing users.
+//!
+//! Share bundles (`.lazyqmk`, see [`super::share_bundle`]) can be loaded like
+//! layout files; saving writes the layout next to the bundle as `.json`.
 
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use super::share_bundle::{ShareBundle, BUNDLE_EXTENSION};
 use crate::{models::Layout, parser};
 
 /// Service for managing layout file I/O operations.
@@ -32,12 +36,12 @@ pub struct LayoutService;
 impl LayoutService {
     /// Loads a layout from file, auto-detecting format.
     ///
-    /// Supports both `.json` (current) and `.md` (legacy) files.
+    /// Supports `.json` (current), `.md` (legacy) and `.lazyqmk` share bundles.
     /// When a `.md` file is loaded, it is automatically migrated to `.json`.
     ///
     /// # Arguments
     ///
-    /// * `path` - Path to the layout file (`.json` or `.md`)
+    /// * `path` - Path to the layout file (`.json`, `.md` or `.lazyqmk`)
     ///
     /// # Returns
     ///
@@ -70,6 +74,7 @@ impl LayoutService {
 
                 Ok(layout)
             }
+            Some(BUNDLE_EXTENSION) => ShareBundle::read(path).map(|bundle| bundle.layout),
             _ => {
                 // No recognized extension — try .json first, then .md as fallback
                 let json_path = path.with_extension("json");
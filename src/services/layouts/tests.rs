@@ -4,7 +4,6 @@
 
 use super::*;
 
-use super::*;
 use std::fs;
 use tempfile::TempDir;
 
@@ -72,7 +71,7 @@ fn test_load_without_extension_falls_back_to_md() -> Result<()> {
     let no_ext_path = tmp.path().join("legacy");
 
     // Create a minimal .md layout file
-    let md_content = r#"---
+    let md_content = r"---
 name: legacy_layout
 description: ''
 author: ''
@@ -92,7 +91,7 @@ version: '1.0'
 | C0 |
 |-----|
 | KC_A |
-"#;
+";
     fs::write(&md_path, md_content)?;
 
     // Load without extension → should find .md as fallback
@@ -131,12 +130,35 @@ fn test_save_always_uses_json_extension() -> Result<()> {
 }
 
 #[test]
-fn test_rename_file_if_needed_no_file() -> Result<()> {
+fn test_load_share_bundle() -> Result<()> {
+    let tmp = TempDir::new()?;
+    let path = tmp.path().join("shared.lazyqmk");
+
+    let mut layout = Layout::new("shared")?;
+    layout.add_layer(crate::models::Layer::new(
+        0,
+        "Base",
+        crate::models::RgbColor::new(255, 255, 255),
+    )?)?;
+    ShareBundle::new(&layout, None)?.write(&path)?;
+
+    let loaded = LayoutService::load(&path)?;
+    assert_eq!(loaded.metadata.name, "shared");
+
+    // Saving keeps the bundle and writes the layout next to it
+    LayoutService::save(&loaded, &path)?;
+    assert!(path.exists());
+    assert!(tmp.path().join("shared.json").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_rename_file_if_needed_no_file() {
     let path = Path::new("/tmp/nonexistent_layout_test_12345.json");
     let result = LayoutService::rename_file_if_needed(path, "New Name");
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), None);
-    Ok(())
 }
 
 #[test]
@@ -177,7 +199,7 @@ fn test_migrate_md_to_json_from_file() -> Result<()> {
     let bak_path = tmp.path().join("legacy.md.bak");
 
     // Create minimal markdown layout
-    let md_content = r#"---
+    let md_content = r"---
 name: migration_test
 description: ''
 author: tester
@@ -197,7 +219,7 @@ version: '1.0'
 | C0 |
 |-----|
 | KC_A |
-"#;
+";
     fs::write(&md_path, md_content)?;
 
     // Load via LayoutService (triggers migration)
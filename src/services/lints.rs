@@ -0,0 +1,290 @@
+//! Layout lints.
+//!
+//! Checks for layouts that load and compile but probably don't do what the
+//! user wants. Each [`LintRule`] has a default severity that the `[lints]`
+//! section of `config.toml` can override ([`LintConfig`]); rules set to
+//! `off` aren't checked. Structural problems that keep a layout from
+//! loading at all stay in [`Layout::validate`].
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::config::{LintConfig, LintSeverity};
+use crate::keycode_db::KeycodeDb;
+use crate::models::{KeyboardGeometry, Layout, Position};
+use crate::services::layer_graph::LayerGraph;
+
+/// Keycodes that jump to the bootloader.
+const BOOTLOADER_KEYCODES: [&str; 3] = ["QK_BOOT", "QK_BOOTLOADER", "RESET"];
+
+/// A check run by [`lint_layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// No chain of layer keys leads to a layer from the base layer
+    UnreachableLayer,
+    /// A latching key turns a layer on but nothing on it turns it off
+    LayerWithoutWayBack,
+    /// No key, combo or tap dance jumps to the bootloader
+    MissingBootloaderKey,
+    /// Transparent key on the base layer, which has nothing below it
+    TransparentOnBase,
+    /// Tap dance sending a keycode that doesn't exist
+    UnknownTapDanceKeycode,
+    /// Tap dance that no key uses
+    UnusedTapDance,
+    /// Keys sharing an RGB LED index
+    LedIndexCollision,
+}
+
+impl LintRule {
+    /// Every rule, in report order.
+    pub const ALL: [Self; 7] = [
+        Self::UnreachableLayer,
+        Self::LayerWithoutWayBack,
+        Self::MissingBootloaderKey,
+        Self::TransparentOnBase,
+        Self::UnknownTapDanceKeycode,
+        Self::UnusedTapDance,
+        Self::LedIndexCollision,
+    ];
+
+    /// ID used in `config.toml` and in reports.
+    #[must_use]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::UnreachableLayer => "unreachable_layer",
+            Self::LayerWithoutWayBack => "layer_without_way_back",
+            Self::MissingBootloaderKey => "missing_bootloader_key",
+            Self::TransparentOnBase => "transparent_on_base",
+            Self::UnknownTapDanceKeycode => "unknown_tap_dance_keycode",
+            Self::UnusedTapDance => "unused_tap_dance",
+            Self::LedIndexCollision => "led_index_collision",
+        }
+    }
+
+    /// Looks up a rule by its ID.
+    #[allow(dead_code)] // Used by config validation (lib target only)
+    #[must_use]
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rule| rule.id() == id)
+    }
+
+    /// Severity used when `config.toml` doesn't set one.
+    #[must_use]
+    pub const fn default_severity(self) -> LintSeverity {
+        match self {
+            Self::UnknownTapDanceKeycode | Self::LedIndexCollision => LintSeverity::Error,
+            Self::UnreachableLayer
+            | Self::LayerWithoutWayBack
+            | Self::MissingBootloaderKey
+            | Self::TransparentOnBase
+            | Self::UnusedTapDance => LintSeverity::Warning,
+        }
+    }
+
+    /// Severity of the rule under `config`.
+    #[must_use]
+    pub fn severity(self, config: &LintConfig) -> LintSeverity {
+        config
+            .severities
+            .get(self.id())
+            .copied()
+            .unwrap_or_else(|| self.default_severity())
+    }
+}
+
+/// A problem found by a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    /// Rule that found it
+    pub rule: LintRule,
+    /// Configured severity of the rule (never `Off`)
+    pub severity: LintSeverity,
+    /// Human-readable description
+    pub message: String,
+    /// Layer the problem is on, if it is on one
+    pub layer: Option<usize>,
+    /// Key the problem is on, if it is on one
+    pub position: Option<Position>,
+}
+
+impl Lint {
+    /// Returns true if the lint fails validation.
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        self.severity == LintSeverity::Error
+    }
+}
+
+/// Runs every rule that isn't off under `config` and returns what they
+/// found, in rule order.
+///
+/// The LED rule needs the keyboard geometry and is skipped without it.
+#[must_use]
+pub fn lint_layout(
+    layout: &Layout,
+    keycode_db: &KeycodeDb,
+    geometry: Option<&KeyboardGeometry>,
+    config: &LintConfig,
+) -> Vec<Lint> {
+    let mut lints = Vec::new();
+    for rule in LintRule::ALL {
+        let severity = rule.severity(config);
+        if severity == LintSeverity::Off {
+            continue;
+        }
+        let mut push = |message: String, layer: Option<usize>, position: Option<Position>| {
+            lints.push(Lint {
+                rule,
+                severity,
+                message,
+                layer,
+                position,
+            });
+        };
+        match rule {
+            LintRule::UnreachableLayer => {
+                for node in LayerGraph::build(&layout.layers).nodes {
+                    if !node.reachable {
+                        push(
+                            format!(
+                                "Layer {} ({}) can't be reached from the base layer",
+                                node.layer, node.name
+                            ),
+                            Some(node.layer),
+                            None,
+                        );
+                    }
+                }
+            }
+            LintRule::LayerWithoutWayBack => {
+                for node in LayerGraph::build(&layout.layers).nodes {
+                    if !node.has_way_back {
+                        push(
+                            format!(
+                                "Layer {} ({}) can be latched on but has no key that turns it off",
+                                node.layer, node.name
+                            ),
+                            Some(node.layer),
+                            None,
+                        );
+                    }
+                }
+            }
+            LintRule::MissingBootloaderKey => {
+                if !has_bootloader_key(layout) {
+                    push(
+                        "No key jumps to the bootloader (QK_BOOT); reflashing needs the reset button"
+                            .to_string(),
+                        None,
+                        None,
+                    );
+                }
+            }
+            LintRule::TransparentOnBase => {
+                for key in layout.layers.first().into_iter().flat_map(|l| &l.keys) {
+                    if key.is_transparent() {
+                        push(
+                            format!(
+                                "Transparent key at ({}, {}) on the base layer does nothing",
+                                key.position.row, key.position.col
+                            ),
+                            Some(0),
+                            Some(key.position),
+                        );
+                    }
+                }
+            }
+            LintRule::UnknownTapDanceKeycode => {
+                for td in &layout.tap_dances {
+                    let keycodes = std::iter::once(&td.single_tap)
+                        .chain(&td.double_tap)
+                        .chain(&td.hold);
+                    for keycode in keycodes {
+                        if !is_known_keycode(keycode_db, layout, keycode) {
+                            push(
+                                format!(
+                                    "Tap dance '{}' sends unknown keycode '{keycode}'",
+                                    td.name
+                                ),
+                                None,
+                                None,
+                            );
+                        }
+                    }
+                }
+            }
+            LintRule::UnusedTapDance => {
+                for name in layout.get_orphaned_tap_dances() {
+                    push(
+                        format!("Tap dance '{name}' is defined but never used in any layer"),
+                        None,
+                        None,
+                    );
+                }
+            }
+            LintRule::LedIndexCollision => {
+                let Some(geometry) = geometry else {
+                    continue;
+                };
+                let mut by_led: BTreeMap<u8, Vec<(u8, u8)>> = BTreeMap::new();
+                for key in &geometry.keys {
+                    by_led
+                        .entry(key.led_index)
+                        .or_default()
+                        .push(key.matrix_position);
+                }
+                for (led, keys) in by_led.into_iter().filter(|(_, keys)| keys.len() > 1) {
+                    let keys: Vec<String> = keys
+                        .iter()
+                        .map(|(row, col)| format!("[{row},{col}]"))
+                        .collect();
+                    push(
+                        format!(
+                            "Matrix keys {} share LED index {led}; their colors will overwrite each other",
+                            keys.join(", ")
+                        ),
+                        None,
+                        None,
+                    );
+                }
+            }
+        }
+    }
+    lints
+}
+
+/// Returns true if a key, keycode combo or tap dance sends a bootloader
+/// keycode.
+fn has_bootloader_key(layout: &Layout) -> bool {
+    let keycodes: HashSet<&str> = layout
+        .layers
+        .iter()
+        .flat_map(|layer| layer.keys.iter().map(|key| key.keycode.as_str()))
+        .chain(
+            layout
+                .combo_settings
+                .keycode_combos
+                .iter()
+                .map(|combo| combo.keycode.as_str()),
+        )
+        .chain(layout.tap_dances.iter().flat_map(|td| {
+            std::iter::once(td.single_tap.as_str())
+                .chain(td.double_tap.as_deref())
+                .chain(td.hold.as_deref())
+        }))
+        .collect();
+    BOOTLOADER_KEYCODES
+        .iter()
+        .any(|keycode| keycodes.contains(keycode))
+}
+
+/// Returns true if `keycode` is in the database, one of the layout's
+/// custom keycodes or an enabled editing helper keycode.
+fn is_known_keycode(keycode_db: &KeycodeDb, layout: &Layout, keycode: &str) -> bool {
+    keycode_db.is_valid(keycode)
+        || layout.get_custom_keycode(keycode).is_some()
+        || layout.editing_helpers.is_enabled_keycode(keycode)
+}
+
+#[cfg(test)]
+mod tests;
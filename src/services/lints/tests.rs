@@ -0,0 +1,132 @@
+//! Tests for services::lints.
+
+use super::*;
+use crate::models::{KeyDefinition, KeyGeometry, Layer, RgbColor, TapDanceAction};
+
+/// Layout with one layer per row of keycodes at positions (0, 0) and (0, 1).
+fn layout(keycodes: &[[&str; 2]]) -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    for (idx, codes) in keycodes.iter().enumerate() {
+        let mut layer = Layer::new(idx as u8, format!("L{idx}"), RgbColor::new(0, 0, 0)).unwrap();
+        for (col, code) in codes.iter().enumerate() {
+            layer.add_key(KeyDefinition::new(Position::new(0, col as u8), *code));
+        }
+        layout.add_layer(layer).unwrap();
+    }
+    layout
+}
+
+fn rules(lints: &[Lint]) -> Vec<LintRule> {
+    lints.iter().map(|lint| lint.rule).collect()
+}
+
+#[test]
+fn test_clean_layout_has_no_lints() {
+    let db = KeycodeDb::load().unwrap();
+    let layout = layout(&[["QK_BOOT", "MO(1)"], ["TO(0)", "KC_TRNS"]]);
+
+    assert!(lint_layout(&layout, &db, None, &LintConfig::default()).is_empty());
+}
+
+#[test]
+fn test_layer_and_base_layer_rules() {
+    let db = KeycodeDb::load().unwrap();
+    let layout = layout(&[["KC_TRNS", "TG(1)"], ["KC_A", "KC_B"], ["KC_C", "KC_D"]]);
+
+    let lints = lint_layout(&layout, &db, None, &LintConfig::default());
+
+    assert_eq!(
+        rules(&lints),
+        [
+            LintRule::UnreachableLayer,
+            LintRule::LayerWithoutWayBack,
+            LintRule::MissingBootloaderKey,
+            LintRule::TransparentOnBase,
+        ]
+    );
+    assert_eq!(lints[0].layer, Some(2));
+    assert_eq!(lints[1].layer, Some(1));
+    assert_eq!(lints[3].position, Some(Position::new(0, 0)));
+    assert!(lints.iter().all(|lint| !lint.is_error()));
+}
+
+#[test]
+fn test_tap_dance_rules() {
+    let db = KeycodeDb::load().unwrap();
+    let mut layout = layout(&[["QK_BOOT", "TD(used)"]]);
+    layout
+        .add_tap_dance(TapDanceAction::new("used", "KC_A").with_hold("KC_NOPE"))
+        .unwrap();
+    layout
+        .add_tap_dance(TapDanceAction::new("spare", "KC_B"))
+        .unwrap();
+
+    let lints = lint_layout(&layout, &db, None, &LintConfig::default());
+
+    assert_eq!(
+        rules(&lints),
+        [LintRule::UnknownTapDanceKeycode, LintRule::UnusedTapDance]
+    );
+    assert_eq!(
+        lints[0].message,
+        "Tap dance 'used' sends unknown keycode 'KC_NOPE'"
+    );
+    assert!(lints[0].is_error());
+    assert_eq!(
+        lints[1].message,
+        "Tap dance 'spare' is defined but never used in any layer"
+    );
+}
+
+#[test]
+fn test_bootloader_key_in_tap_dance_counts() {
+    let db = KeycodeDb::load().unwrap();
+    let mut layout = layout(&[["KC_A", "TD(boot)"]]);
+    layout
+        .add_tap_dance(TapDanceAction::new("boot", "KC_ESC").with_hold("QK_BOOT"))
+        .unwrap();
+
+    assert!(lint_layout(&layout, &db, None, &LintConfig::default()).is_empty());
+}
+
+#[test]
+fn test_led_index_collision_needs_geometry() {
+    let db = KeycodeDb::load().unwrap();
+    let layout = layout(&[["QK_BOOT", "KC_A"]]);
+    let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 1, 2);
+    geometry.add_key(KeyGeometry::new((0, 0), 3, 0.0, 0.0));
+    geometry.add_key(KeyGeometry::new((0, 1), 3, 1.0, 0.0));
+
+    assert!(lint_layout(&layout, &db, None, &LintConfig::default()).is_empty());
+    let lints = lint_layout(&layout, &db, Some(&geometry), &LintConfig::default());
+    assert_eq!(rules(&lints), [LintRule::LedIndexCollision]);
+    assert!(lints[0]
+        .message
+        .starts_with("Matrix keys [0,0], [0,1] share LED index 3"));
+}
+
+#[test]
+fn test_config_overrides_severity() {
+    let db = KeycodeDb::load().unwrap();
+    let layout = layout(&[["KC_TRNS", "KC_A"]]);
+    let mut config = LintConfig::default();
+    config
+        .severities
+        .insert("transparent_on_base".to_string(), LintSeverity::Error);
+    config
+        .severities
+        .insert("missing_bootloader_key".to_string(), LintSeverity::Off);
+
+    let lints = lint_layout(&layout, &db, None, &config);
+
+    assert_eq!(rules(&lints), [LintRule::TransparentOnBase]);
+    assert!(lints[0].is_error());
+}
+
+#[test]
+fn test_rule_ids_round_trip() {
+    for rule in LintRule::ALL {
+        assert_eq!(LintRule::from_id(rule.id()), Some(rule));
+    }
+    assert_eq!(LintRule::from_id("no_such_rule"), None);
+}
@@ -3,9 +3,40 @@
 //! This module contains services that encapsulate complex business logic
 //! and coordinate between different parts of the application.
 
+pub mod analysis;
+pub mod audit;
+pub mod backup;
+pub mod device_detect;
+#[cfg(feature = "usb")]
+pub mod flasher;
+pub mod fragments;
 pub mod geometry;
+pub mod geometry_cache;
+pub mod git;
+#[cfg(feature = "usb")]
+pub mod hid_listen;
+pub mod hooks;
+pub mod host_layout;
+pub mod key_search;
+#[allow(dead_code)] // Used by the web keyboard list (lib target only)
+pub mod keyboard_index;
+pub mod keyboard_roots;
+pub mod layer_access;
+pub mod layer_graph;
+pub mod layer_mirror;
 pub mod layer_refs;
 pub mod layouts;
+pub mod lints;
+pub mod process_tree;
+pub mod qmk_env;
+pub mod resolved_keys;
+pub mod share_bundle;
+pub mod split_leds;
+pub mod template_fit;
+pub mod thumb_optimizer;
+pub mod usage;
+pub mod web_bridge;
+pub mod workspace_lock;
 
 // Re-export GeometryService if it exists, otherwise just re-export the module
 // pub use geometry::GeometryService;
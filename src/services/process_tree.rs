@@ -0,0 +1,219 @@
+//! Child processes that are stopped together with everything they spawned.
+//!
+//! `qmk compile` runs `make`, which runs the compilers (and `ccache`).
+//! Killing only `qmk` leaves the rest running as orphans. [`TrackedChild`]
+//! therefore starts a command in a process group of its own and kills the
+//! whole tree when asked to, or when it is dropped while still running.
+//! Every tracked child is also recorded in a process-wide registry, so
+//! [`kill_all`] can stop whatever is left when the app quits.
+//!
+//! Trees are killed with `kill -KILL -- -<pgid>` on Linux and macOS and
+//! `taskkill /T /F` on Windows.
+
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+/// How often a running child is checked for `should_stop`.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Process (group) IDs of all running tracked children.
+static RUNNING: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+/// A line printed by a child process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputLine {
+    /// Line from stdout
+    Stdout(String),
+    /// Line from stderr
+    Stderr(String),
+}
+
+/// How a tracked child process ended.
+#[derive(Debug)]
+pub enum ChildOutcome {
+    /// The process exited by itself
+    Exited(ExitStatus),
+    /// The process tree was killed because `should_stop` returned true
+    Stopped,
+}
+
+/// A child process running in a process group of its own.
+#[derive(Debug)]
+pub struct TrackedChild {
+    child: Child,
+}
+
+impl TrackedChild {
+    /// Spawns `cmd` with piped output in a new process group.
+    ///
+    /// # Errors
+    ///
+    /// Returns the spawn error.
+    pub fn spawn(cmd: &mut Command) -> std::io::Result<Self> {
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        new_process_group(cmd);
+        let child = cmd.spawn()?;
+        running().push(child.id());
+        Ok(Self { child })
+    }
+
+    /// Streams the output of the child to `on_line` until it exits, killing
+    /// its process tree as soon as `should_stop` returns true.
+    ///
+    /// `should_stop` is checked between lines and at least every 200 ms, so
+    /// a child that prints nothing can still be stopped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if waiting for the child fails.
+    pub fn wait_with_output(
+        &mut self,
+        should_stop: &dyn Fn() -> bool,
+        on_line: &mut dyn FnMut(OutputLine),
+    ) -> std::io::Result<ChildOutcome> {
+        let (tx, rx) = mpsc::channel();
+        let mut readers = Vec::new();
+        if let Some(stdout) = self.child.stdout.take() {
+            readers.push(forward_lines(stdout, tx.clone(), OutputLine::Stdout));
+        }
+        if let Some(stderr) = self.child.stderr.take() {
+            readers.push(forward_lines(stderr, tx.clone(), OutputLine::Stderr));
+        }
+        drop(tx);
+
+        loop {
+            if should_stop() {
+                self.kill_tree();
+                // Grandchildren may still hold the pipes; don't wait for EOF
+                return Ok(ChildOutcome::Stopped);
+            }
+            match rx.recv_timeout(POLL_INTERVAL) {
+                Ok(line) => on_line(line),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        // Output is closed; wait for the exit while still honouring should_stop
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                untrack(self.child.id());
+                for reader in readers {
+                    let _ = reader.join();
+                }
+                return Ok(ChildOutcome::Exited(status));
+            }
+            if should_stop() {
+                self.kill_tree();
+                return Ok(ChildOutcome::Stopped);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Kills the child and every process it spawned, then reaps it.
+    pub fn kill_tree(&mut self) {
+        kill_process_tree(self.child.id());
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        untrack(self.child.id());
+    }
+}
+
+impl Drop for TrackedChild {
+    fn drop(&mut self) {
+        if matches!(self.child.try_wait(), Ok(None)) {
+            self.kill_tree();
+        }
+        untrack(self.child.id());
+    }
+}
+
+/// Kills the process trees of all running tracked children.
+///
+/// Called when the app quits, so that builds still running on worker
+/// threads don't outlive it. Returns the number of trees killed.
+pub fn kill_all() -> usize {
+    let pids = std::mem::take(&mut *running());
+    for &pid in &pids {
+        kill_process_tree(pid);
+    }
+    pids.len()
+}
+
+fn running() -> MutexGuard<'static, Vec<u32>> {
+    // A panic while holding the lock can't leave the list inconsistent
+    RUNNING
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+fn untrack(pid: u32) {
+    running().retain(|&running| running != pid);
+}
+
+/// Sends every line read from `source` through `tx`.
+fn forward_lines(
+    source: impl Read + Send + 'static,
+    tx: mpsc::Sender<OutputLine>,
+    wrap: fn(String) -> OutputLine,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        for line in BufReader::new(source).lines() {
+            let Ok(line) = line else { break };
+            if tx.send(wrap(line)).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Makes `cmd` the leader of a new process group.
+#[cfg(unix)]
+fn new_process_group(cmd: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    cmd.process_group(0);
+}
+
+/// Makes `cmd` the root of a new process group.
+#[cfg(windows)]
+fn new_process_group(cmd: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+    cmd.creation_flags(CREATE_NEW_PROCESS_GROUP);
+}
+
+#[cfg(not(any(unix, windows)))]
+fn new_process_group(_cmd: &mut Command) {}
+
+/// Kills the process group led by `pid`.
+#[cfg(unix)]
+fn kill_process_tree(pid: u32) {
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{pid}")])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+/// Kills `pid` and all of its descendants.
+#[cfg(windows)]
+fn kill_process_tree(pid: u32) {
+    let _ = Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[cfg(not(any(unix, windows)))]
+fn kill_process_tree(_pid: u32) {}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,87 @@
+use super::*;
+use std::time::Instant;
+
+#[cfg(unix)]
+fn sh(script: &str) -> TrackedChild {
+    TrackedChild::spawn(Command::new("sh").args(["-c", script])).unwrap()
+}
+
+/// True once `pid` is gone or only a zombie waiting to be reaped.
+#[cfg(target_os = "linux")]
+fn process_dead(pid: u32) -> bool {
+    std::fs::read_to_string(format!("/proc/{pid}/stat")).map_or(true, |stat| {
+        stat.rsplit(')')
+            .next()
+            .is_some_and(|s| s.trim_start().starts_with('Z'))
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn wait_until_dead(pid: u32) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !process_dead(pid) && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(20));
+    }
+    assert!(process_dead(pid), "process {pid} is still running");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_wait_with_output_collects_lines() {
+    let mut child = sh("echo out; echo err >&2");
+    let mut lines = Vec::new();
+    let outcome = child
+        .wait_with_output(&|| false, &mut |line| lines.push(line))
+        .unwrap();
+
+    assert!(matches!(outcome, ChildOutcome::Exited(status) if status.success()));
+    assert!(lines.contains(&OutputLine::Stdout("out".to_string())));
+    assert!(lines.contains(&OutputLine::Stderr("err".to_string())));
+    assert!(!running().contains(&child.child.id()));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_wait_with_output_stops_silent_child() {
+    let mut child = sh("sleep 30");
+    let started = Instant::now();
+    let stop_after = started + Duration::from_millis(300);
+    let outcome = child
+        .wait_with_output(&|| Instant::now() >= stop_after, &mut |_| {})
+        .unwrap();
+
+    assert!(matches!(outcome, ChildOutcome::Stopped));
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(!running().contains(&child.child.id()));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_stop_kills_grandchildren() {
+    let mut child = sh("sleep 30 & echo $!; wait");
+    let grandchild = std::cell::Cell::new(None);
+    let outcome = child
+        .wait_with_output(&|| grandchild.get().is_some(), &mut |line| {
+            if let OutputLine::Stdout(pid) = line {
+                grandchild.set(pid.trim().parse::<u32>().ok());
+            }
+        })
+        .unwrap();
+
+    assert!(matches!(outcome, ChildOutcome::Stopped));
+    let grandchild = grandchild.get().unwrap();
+    wait_until_dead(grandchild);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_drop_kills_running_tree() {
+    let child = sh("sleep 30 & sleep 30");
+    let pid = child.child.id();
+    assert!(running().contains(&pid));
+
+    drop(child);
+
+    assert!(!running().contains(&pid));
+    wait_until_dead(pid);
+}
@@ -0,0 +1,449 @@
+//! Health check and setup of the QMK build environment.
+//!
+//! [`check`] looks at everything a firmware build needs besides the layout:
+//! the QMK checkout, its git state and submodules, the `qmk` CLI and the
+//! compilers. Every problem comes with a fix, and the fixes `qmk setup` would
+//! make itself (cloning the firmware, initializing submodules) are
+//! [`SetupStep`]s that [`run_setup_step`] can run. The onboarding wizard and
+//! `GET /api/preflight` show the report.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::doctor::{DependencyChecker, DependencyStatus, ToolStatus};
+
+/// Repository cloned by [`SetupStep::Clone`].
+pub const QMK_FIRMWARE_REPO: &str = "https://github.com/qmk/qmk_firmware.git";
+
+/// Submodules listed by name in a check message before summarizing the rest.
+const MAX_LISTED_SUBMODULES: usize = 3;
+
+/// Severity of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckLevel {
+    /// Nothing to do
+    Ok,
+    /// Builds may work, but something deserves attention
+    Warning,
+    /// Builds will fail until this is fixed
+    Error,
+}
+
+/// A fix [`run_setup_step`] can apply, mirroring what `qmk setup` does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupStep {
+    /// Clone QMK firmware (with submodules) into the configured path
+    Clone,
+    /// Initialize and update the checkout's submodules
+    Submodules,
+}
+
+impl SetupStep {
+    /// Returns a short description of what the step does.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Clone => "Clone QMK firmware",
+            Self::Submodules => "Initialize QMK submodules",
+        }
+    }
+}
+
+/// Result of a single environment check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EnvCheck {
+    /// What was checked (e.g. "Submodules")
+    pub name: String,
+    /// How serious the finding is
+    pub level: CheckLevel,
+    /// What was found
+    pub message: String,
+    /// How to fix it, for warnings and errors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+    /// Setup step that fixes it automatically
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub setup_step: Option<SetupStep>,
+}
+
+impl EnvCheck {
+    /// Creates a passing check.
+    #[must_use]
+    pub fn ok(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            level: CheckLevel::Ok,
+            message: message.into(),
+            fix: None,
+            setup_step: None,
+        }
+    }
+
+    /// Creates a warning or error with the fix for it.
+    #[must_use]
+    pub fn problem(
+        name: impl Into<String>,
+        level: CheckLevel,
+        message: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            level,
+            message: message.into(),
+            fix: Some(fix.into()),
+            setup_step: None,
+        }
+    }
+
+    /// Attaches the setup step that fixes the problem.
+    #[must_use]
+    pub const fn with_setup_step(mut self, step: SetupStep) -> Self {
+        self.setup_step = Some(step);
+        self
+    }
+}
+
+/// All checks of the QMK environment, in the order they were run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct QmkEnvReport {
+    /// Individual checks
+    pub checks: Vec<EnvCheck>,
+}
+
+impl QmkEnvReport {
+    /// Returns true if no check failed (warnings are fine).
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| check.level != CheckLevel::Error)
+    }
+
+    /// Returns the setup steps that fix the problems found, without repeats.
+    #[must_use]
+    pub fn setup_steps(&self) -> Vec<SetupStep> {
+        let mut steps: Vec<SetupStep> = Vec::new();
+        for step in self.checks.iter().filter_map(|check| check.setup_step) {
+            if !steps.contains(&step) {
+                steps.push(step);
+            }
+        }
+        steps
+    }
+}
+
+/// Checks the QMK checkout at `qmk_path` and the build tools.
+///
+/// Runs git, `qmk` and the compilers, so it can take a moment.
+#[must_use]
+pub fn check(qmk_path: Option<&Path>) -> QmkEnvReport {
+    let mut checks = vec![check_checkout(qmk_path)];
+    if let Some(path) = qmk_path.filter(|_| checks[0].level == CheckLevel::Ok) {
+        checks.extend(check_git(path));
+    }
+    checks.extend(check_tools(&DependencyChecker::new()));
+    QmkEnvReport { checks }
+}
+
+/// Checks that `qmk_path` is set and points at a QMK firmware checkout.
+#[must_use]
+pub fn check_checkout(qmk_path: Option<&Path>) -> EnvCheck {
+    const NAME: &str = "QMK checkout";
+    let Some(path) = qmk_path else {
+        return EnvCheck::problem(
+            NAME,
+            CheckLevel::Error,
+            "No QMK firmware path configured",
+            "Choose your qmk_firmware directory, or a new one to clone QMK into",
+        );
+    };
+    if !path.exists() {
+        return EnvCheck::problem(
+            NAME,
+            CheckLevel::Error,
+            format!("{} does not exist", path.display()),
+            format!("Clone QMK firmware into {}", path.display()),
+        )
+        .with_setup_step(SetupStep::Clone);
+    }
+
+    // The same files the config requires of a QMK path
+    let missing: Vec<&str> = [
+        ("Makefile", path.join("Makefile").is_file()),
+        ("keyboards/", path.join("keyboards").is_dir()),
+    ]
+    .into_iter()
+    .filter_map(|(name, found)| (!found).then_some(name))
+    .collect();
+    if !missing.is_empty() {
+        return EnvCheck::problem(
+            NAME,
+            CheckLevel::Error,
+            format!(
+                "{} is not a QMK firmware checkout (missing {})",
+                path.display(),
+                missing.join(", ")
+            ),
+            "Choose the qmk_firmware directory itself, not a parent or subdirectory",
+        );
+    }
+    EnvCheck::ok(NAME, path.display().to_string())
+}
+
+/// Checks the git state of the checkout: local changes and submodules.
+fn check_git(qmk_path: &Path) -> Vec<EnvCheck> {
+    if !qmk_path.join(".git").exists() {
+        return vec![EnvCheck::problem(
+            "Git",
+            CheckLevel::Warning,
+            "Not a git checkout, so submodules and updates can't be checked",
+            format!("Clone QMK with git ({QMK_FIRMWARE_REPO}) instead of downloading an archive"),
+        )];
+    }
+
+    let mut checks = Vec::new();
+    match git(
+        qmk_path,
+        &[
+            "status",
+            "--porcelain",
+            "--untracked-files=no",
+            "--ignore-submodules",
+        ],
+    ) {
+        Ok(status) => {
+            let revision = git(qmk_path, &["rev-parse", "--abbrev-ref", "HEAD"])
+                .map(|branch| branch.trim().to_string())
+                .unwrap_or_default();
+            let changed = status.lines().filter(|line| !line.is_empty()).count();
+            checks.push(if changed == 0 {
+                EnvCheck::ok("Git", format!("Clean checkout on {revision}"))
+            } else {
+                EnvCheck::problem(
+                    "Git",
+                    CheckLevel::Warning,
+                    format!("{changed} changed file(s) on {revision}"),
+                    "Commit or stash the changes before updating QMK",
+                )
+            });
+        }
+        Err(e) => checks.push(EnvCheck::problem(
+            "Git",
+            CheckLevel::Warning,
+            format!("{e:#}"),
+            "Install git to check and update the checkout",
+        )),
+    }
+
+    if let Ok(status) = git(qmk_path, &["submodule", "status"]) {
+        checks.push(submodule_check(&SubmoduleStatus::parse(&status)));
+    }
+    checks
+}
+
+/// Submodules of a checkout grouped by state, from `git submodule status`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubmoduleStatus {
+    /// Checked out at the recorded commit
+    pub current: Vec<String>,
+    /// Not initialized
+    pub missing: Vec<String>,
+    /// Checked out at a different commit, or with merge conflicts
+    pub outdated: Vec<String>,
+}
+
+impl SubmoduleStatus {
+    /// Parses the output of `git submodule status`.
+    #[must_use]
+    pub fn parse(output: &str) -> Self {
+        let mut status = Self::default();
+        for line in output.lines() {
+            let mut chars = line.chars();
+            let Some(state) = chars.next() else {
+                continue;
+            };
+            let Some(path) = chars.as_str().split_whitespace().nth(1) else {
+                continue;
+            };
+            let path = path.to_string();
+            match state {
+                '-' => status.missing.push(path),
+                '+' | 'U' => status.outdated.push(path),
+                _ => status.current.push(path),
+            }
+        }
+        status
+    }
+}
+
+/// Turns the submodule states into a check.
+#[must_use]
+pub fn submodule_check(status: &SubmoduleStatus) -> EnvCheck {
+    const NAME: &str = "Submodules";
+    if !status.missing.is_empty() {
+        return EnvCheck::problem(
+            NAME,
+            CheckLevel::Error,
+            format!("Not initialized: {}", list_submodules(&status.missing)),
+            "Initialize them (`qmk git-submodule`)",
+        )
+        .with_setup_step(SetupStep::Submodules);
+    }
+    if !status.outdated.is_empty() {
+        return EnvCheck::problem(
+            NAME,
+            CheckLevel::Warning,
+            format!("Out of date: {}", list_submodules(&status.outdated)),
+            "Update them (`qmk git-submodule`)",
+        )
+        .with_setup_step(SetupStep::Submodules);
+    }
+    EnvCheck::ok(NAME, format!("{} up to date", status.current.len()))
+}
+
+/// Lists the first few submodule paths and counts the rest.
+fn list_submodules(paths: &[String]) -> String {
+    let list = paths
+        .iter()
+        .take(MAX_LISTED_SUBMODULES)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    match paths.len().checked_sub(MAX_LISTED_SUBMODULES) {
+        Some(more) if more > 0 => format!("{list} and {more} more"),
+        _ => list,
+    }
+}
+
+/// Checks the `qmk` CLI and the compilers.
+fn check_tools(checker: &DependencyChecker) -> Vec<EnvCheck> {
+    let qmk_cli = checker.check_qmk_cli();
+    let cli_check = match qmk_cli.status {
+        ToolStatus::Available => EnvCheck::ok(
+            "QMK CLI",
+            format!("Version {}", qmk_cli.version.unwrap_or_default()),
+        ),
+        ToolStatus::Missing => EnvCheck::problem(
+            "QMK CLI",
+            CheckLevel::Error,
+            "Not found in PATH; builds run `qmk compile`",
+            "Install it with `python3 -m pip install --user qmk`",
+        ),
+        ToolStatus::Unknown => EnvCheck::problem(
+            "QMK CLI",
+            CheckLevel::Warning,
+            qmk_cli.message,
+            "Check that `qmk --version` works in a terminal",
+        ),
+    };
+    vec![
+        cli_check,
+        compiler_check(&checker.check_arm_gcc(), &checker.check_avr_gcc()),
+    ]
+}
+
+/// Combines the ARM and AVR compiler checks: one of them is enough for
+/// keyboards with that kind of controller.
+#[must_use]
+pub fn compiler_check(arm: &DependencyStatus, avr: &DependencyStatus) -> EnvCheck {
+    const NAME: &str = "Compilers";
+    const FIX: &str = "Run `qmk setup`, which installs the toolchains for your platform";
+    let found = |status: &DependencyStatus| status.status == ToolStatus::Available;
+    match (found(arm), found(avr)) {
+        (true, true) => EnvCheck::ok(NAME, "ARM and AVR GCC found"),
+        (true, false) => EnvCheck::problem(
+            NAME,
+            CheckLevel::Warning,
+            "ARM GCC found, AVR GCC missing: AVR keyboards (ATmega32U4) won't build",
+            FIX,
+        ),
+        (false, true) => EnvCheck::problem(
+            NAME,
+            CheckLevel::Warning,
+            "AVR GCC found, ARM GCC missing: ARM keyboards (RP2040, STM32) won't build",
+            FIX,
+        ),
+        (false, false) => EnvCheck::problem(
+            NAME,
+            CheckLevel::Error,
+            "Neither ARM nor AVR GCC found",
+            FIX,
+        ),
+    }
+}
+
+/// Runs a setup step for the checkout at `qmk_path`.
+///
+/// Cloning downloads QMK with its submodules, which takes a few minutes.
+///
+/// # Errors
+///
+/// Returns an error if the target of a clone is a non-empty directory, if
+/// there is no checkout to update, or if git fails.
+pub fn run_setup_step(step: SetupStep, qmk_path: &Path) -> Result<()> {
+    match step {
+        SetupStep::Clone => {
+            let occupied = qmk_path
+                .read_dir()
+                .is_ok_and(|mut entries| entries.next().is_some());
+            if occupied {
+                bail!(
+                    "Not cloning QMK into {}: the directory is not empty",
+                    qmk_path.display()
+                );
+            }
+            run_git(
+                None,
+                &[
+                    "clone".as_ref(),
+                    "--depth=1".as_ref(),
+                    "--recurse-submodules".as_ref(),
+                    "--shallow-submodules".as_ref(),
+                    QMK_FIRMWARE_REPO.as_ref(),
+                    qmk_path.as_os_str(),
+                ],
+            )?;
+        }
+        SetupStep::Submodules => {
+            if !qmk_path.join(".git").exists() {
+                bail!("{} is not a git checkout", qmk_path.display());
+            }
+            git(qmk_path, &["submodule", "sync", "--recursive"])?;
+            git(qmk_path, &["submodule", "update", "--init", "--recursive"])?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs git in `dir`, returning its output.
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let args: Vec<&std::ffi::OsStr> = args.iter().map(std::convert::AsRef::as_ref).collect();
+    run_git(Some(dir), &args)
+}
+
+/// Runs git (in `dir`, if given), failing on a non-zero exit.
+fn run_git(dir: Option<&Path>, args: &[&std::ffi::OsStr]) -> Result<String> {
+    let mut command = Command::new("git");
+    if let Some(dir) = dir {
+        command.arg("-C").arg(dir);
+    }
+    let output = command.args(args).output().context("Failed to run git")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "git {} failed: {}",
+            args[0].to_string_lossy(),
+            stderr.trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests;
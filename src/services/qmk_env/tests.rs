@@ -0,0 +1,122 @@
+use super::*;
+use std::fs;
+use tempfile::TempDir;
+
+/// Creates the directories that make `dir` look like a QMK checkout.
+fn fake_checkout(dir: &Path) {
+    fs::create_dir_all(dir.join("keyboards")).unwrap();
+    fs::write(dir.join("Makefile"), "").unwrap();
+}
+
+#[test]
+fn test_checkout_problems() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let check = check_checkout(None);
+    assert_eq!(check.level, CheckLevel::Error);
+    assert_eq!(check.setup_step, None);
+
+    // A missing directory can be cloned into
+    let missing = temp_dir.path().join("qmk_firmware");
+    let check = check_checkout(Some(&missing));
+    assert_eq!(check.level, CheckLevel::Error);
+    assert_eq!(check.setup_step, Some(SetupStep::Clone));
+
+    let check = check_checkout(Some(temp_dir.path()));
+    assert_eq!(check.level, CheckLevel::Error);
+    assert!(check.message.contains("missing Makefile, keyboards/"));
+
+    fake_checkout(temp_dir.path());
+    assert_eq!(check_checkout(Some(temp_dir.path())).level, CheckLevel::Ok);
+}
+
+#[test]
+fn test_parse_submodule_status() {
+    let status = SubmoduleStatus::parse(
+        " 1a2b3c lib/chibios (ver20.3.4)\n\
+         -4d5e6f lib/lufa\n\
+         +7a8b9c lib/printf (heads/master)\n\
+         U0a1b2c lib/pico-sdk\n",
+    );
+
+    assert_eq!(status.current, vec!["lib/chibios"]);
+    assert_eq!(status.missing, vec!["lib/lufa"]);
+    assert_eq!(status.outdated, vec!["lib/printf", "lib/pico-sdk"]);
+}
+
+#[test]
+fn test_submodule_check_levels() {
+    let mut status = SubmoduleStatus {
+        current: vec!["lib/chibios".to_string()],
+        ..SubmoduleStatus::default()
+    };
+    assert_eq!(
+        submodule_check(&status),
+        EnvCheck::ok("Submodules", "1 up to date")
+    );
+
+    status.outdated = vec!["lib/printf".to_string()];
+    let check = submodule_check(&status);
+    assert_eq!(check.level, CheckLevel::Warning);
+    assert_eq!(check.setup_step, Some(SetupStep::Submodules));
+
+    status.missing = (1..=5).map(|i| format!("lib/m{i}")).collect();
+    let check = submodule_check(&status);
+    assert_eq!(check.level, CheckLevel::Error);
+    assert_eq!(
+        check.message,
+        "Not initialized: lib/m1, lib/m2, lib/m3 and 2 more"
+    );
+}
+
+#[test]
+fn test_compiler_check_needs_one_toolchain() {
+    let found = DependencyStatus::available("ARM GCC", "10.3.1");
+    let missing = DependencyStatus::missing("AVR GCC", "Not found");
+
+    assert_eq!(compiler_check(&found, &found).level, CheckLevel::Ok);
+    assert_eq!(compiler_check(&found, &missing).level, CheckLevel::Warning);
+    assert_eq!(compiler_check(&missing, &found).level, CheckLevel::Warning);
+    assert_eq!(compiler_check(&missing, &missing).level, CheckLevel::Error);
+}
+
+#[test]
+fn test_report_setup_steps() {
+    let report = QmkEnvReport {
+        checks: vec![
+            EnvCheck::ok("QMK checkout", "/qmk"),
+            EnvCheck::problem("Submodules", CheckLevel::Warning, "Out of date", "Update")
+                .with_setup_step(SetupStep::Submodules),
+            EnvCheck::problem("Other", CheckLevel::Warning, "Also", "Update")
+                .with_setup_step(SetupStep::Submodules),
+        ],
+    };
+    assert!(report.is_ready());
+    assert_eq!(report.setup_steps(), vec![SetupStep::Submodules]);
+
+    let report = check(None);
+    assert!(!report.is_ready());
+    assert_eq!(report.checks[0].name, "QMK checkout");
+}
+
+#[test]
+fn test_checkout_without_git_warns() {
+    let temp_dir = TempDir::new().unwrap();
+    fake_checkout(temp_dir.path());
+
+    let checks = check_git(temp_dir.path());
+    assert_eq!(checks.len(), 1);
+    assert_eq!(checks[0].level, CheckLevel::Warning);
+    assert!(checks[0].message.contains("Not a git checkout"));
+}
+
+#[test]
+fn test_setup_steps_refuse_unusable_targets() {
+    let temp_dir = TempDir::new().unwrap();
+    fake_checkout(temp_dir.path());
+
+    let err = run_setup_step(SetupStep::Clone, temp_dir.path()).unwrap_err();
+    assert!(err.to_string().contains("not empty"), "{err}");
+    let err = run_setup_step(SetupStep::Submodules, temp_dir.path()).unwrap_err();
+    assert!(err.to_string().contains("not a git checkout"), "{err}");
+}
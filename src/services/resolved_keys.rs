@@ -0,0 +1,59 @@
+//! Effective keycodes of transparent keys.
+//!
+//! A transparent key (`KC_TRNS`) passes the press on to the next active
+//! layer below it. Which layers are active depends on how a layer was
+//! reached, so [`resolve_key`] assumes every lower layer is: the key does
+//! what the first non-transparent key at the same position on a lower layer
+//! does. That matches the usual stack of momentary layers over the base
+//! layer. Used by the TUI transparency view (`Alt+T`) and the web
+//! resolved-layer endpoint.
+
+use crate::models::{Layer, Position};
+
+/// What a key of a layer does once transparency is resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedKey {
+    /// Matrix position of the key
+    pub position: Position,
+    /// Effective keycode
+    pub keycode: String,
+    /// Index of the layer the keycode comes from
+    pub from_layer: usize,
+}
+
+/// Resolves the key at `position` on layer `layer` through the layers below.
+///
+/// Keys that aren't transparent resolve to themselves, and so do keys that
+/// are transparent all the way down to the base layer.
+/// Returns `None` if the layer has no key at `position`.
+#[must_use]
+pub fn resolve_key(layers: &[Layer], layer: usize, position: Position) -> Option<ResolvedKey> {
+    let own = layers.get(layer)?.get_key(position)?;
+    let (from_layer, key) = (0..=layer)
+        .rev()
+        .filter_map(|index| Some((index, layers[index].get_key(position)?)))
+        .find(|(_, key)| !key.is_transparent())
+        .unwrap_or((layer, own));
+    Some(ResolvedKey {
+        position,
+        keycode: key.keycode.clone(),
+        from_layer,
+    })
+}
+
+/// Resolves every key of layer `layer`, in key order.
+///
+/// Returns `None` if the layer doesn't exist.
+#[must_use]
+#[allow(dead_code)] // Used by the web resolved-layer endpoint (lib target only)
+pub fn resolve_layer(layers: &[Layer], layer: usize) -> Option<Vec<ResolvedKey>> {
+    let keys = &layers.get(layer)?.keys;
+    Some(
+        keys.iter()
+            .filter_map(|key| resolve_key(layers, layer, key.position))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests;
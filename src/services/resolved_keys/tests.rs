@@ -0,0 +1,53 @@
+//! Tests for services::resolved_keys.
+
+use super::*;
+use crate::models::{KeyDefinition, RgbColor};
+
+/// Base, Nav and Fn layers of three keys; Fn is transparent except for one
+/// key and Nav is transparent over `KC_B`.
+fn create_layers() -> Vec<Layer> {
+    [
+        ("Base", ["KC_A", "KC_B", "KC_TRNS"]),
+        ("Nav", ["KC_LEFT", "KC_TRNS", "KC_TRNS"]),
+        ("Fn", ["KC_TRNS", "KC_TRNS", "KC_F1"]),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(idx, (name, keycodes))| {
+        let mut layer = Layer::new(idx as u8, name, RgbColor::new(0, 0, 0)).unwrap();
+        for (col, keycode) in keycodes.into_iter().enumerate() {
+            layer.add_key(KeyDefinition::new(Position::new(0, col as u8), keycode));
+        }
+        layer
+    })
+    .collect()
+}
+
+#[test]
+fn test_transparent_keys_resolve_through_lower_layers() {
+    let layers = create_layers();
+
+    let resolved = resolve_layer(&layers, 2).unwrap();
+    let summary: Vec<(&str, usize)> = resolved
+        .iter()
+        .map(|key| (key.keycode.as_str(), key.from_layer))
+        .collect();
+    assert_eq!(summary, [("KC_LEFT", 1), ("KC_B", 0), ("KC_F1", 2)]);
+}
+
+#[test]
+fn test_transparent_all_the_way_down_stays_transparent() {
+    let layers = create_layers();
+
+    let resolved = resolve_key(&layers, 1, Position::new(0, 2)).unwrap();
+    assert_eq!(resolved.keycode, "KC_TRNS");
+    assert_eq!(resolved.from_layer, 1);
+}
+
+#[test]
+fn test_missing_layer_or_key() {
+    let layers = create_layers();
+
+    assert!(resolve_layer(&layers, 3).is_none());
+    assert!(resolve_key(&layers, 0, Position::new(1, 0)).is_none());
+}
@@ -0,0 +1,276 @@
+//! Share bundles: a complete layout in a single `.lazyqmk` file.
+//!
+//! A bundle is a zip archive meant for posting on forums and chats:
+//!
+//! - `manifest.json` — bundle format version, layout name, keyboard, and the
+//!   app version that wrote it
+//! - `layout.json` — the layout file itself, including its categories, tap
+//!   dances, macros, and key overrides (colors are stored as RGB values, so
+//!   no palette is needed to render them)
+//! - `preview.svg` — every layer rendered as SVG, when the keyboard geometry
+//!   was available at export time
+//! - `geometry.json` — snapshot of that keyboard geometry (key positions,
+//!   matrix, LED indices), so the layout renders on machines without the
+//!   keyboard in their QMK tree, or without a QMK tree at all
+//! - `keymap.c` — the generated keymap, for reference and for building with
+//!   plain QMK
+//!
+//! Bundles can be imported with `lazyqmk bundle import`, the web API, or by
+//! opening them in the TUI; [`LayoutService::load`](super::LayoutService::load)
+//! reads the layout straight out of a bundle, and [`bundled_geometry`] the
+//! geometry to render it with.
+
+use std::fs;
+use std::io::{Cursor, Read, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use zip::write::SimpleFileOptions;
+use zip::{DateTime, ZipArchive, ZipWriter};
+
+use super::geometry::GeometryResult;
+use crate::config::Config;
+use crate::export::export_to_svg;
+use crate::firmware::FirmwareGenerator;
+use crate::keycode_db::KeycodeDb;
+use crate::models::{KeyboardGeometry, Layout, VisualLayoutMapping};
+use crate::parser::json_serde::parse_json_layout_str;
+
+/// Extension of share bundle files.
+pub const BUNDLE_EXTENSION: &str = "lazyqmk";
+
+/// Current bundle format version; bundles from newer versions are rejected.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const LAYOUT_FILE: &str = "layout.json";
+const PREVIEW_FILE: &str = "preview.svg";
+const GEOMETRY_FILE: &str = "geometry.json";
+const KEYMAP_FILE: &str = "keymap.c";
+
+/// Largest entry read from a bundle (bundles come from other people).
+const MAX_ENTRY_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Describes the contents of a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// Bundle format version
+    pub format_version: u32,
+    /// Layout name
+    pub name: String,
+    /// Target keyboard, if set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyboard: Option<String>,
+    /// Layout variant, if set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_variant: Option<String>,
+    /// Version of the app that wrote the bundle
+    pub app_version: String,
+    /// Whether the bundle contains `preview.svg`
+    #[serde(default)]
+    pub preview: bool,
+    /// Whether the bundle contains `geometry.json`
+    #[serde(default)]
+    pub geometry: bool,
+    /// Whether the bundle contains `keymap.c`
+    #[serde(default)]
+    pub keymap: bool,
+}
+
+/// A layout packaged for sharing.
+#[derive(Debug, Clone)]
+pub struct ShareBundle {
+    /// Bundle description
+    pub manifest: BundleManifest,
+    /// The shared layout
+    pub layout: Layout,
+    /// SVG rendering of every layer
+    pub preview_svg: Option<String>,
+    /// Keyboard geometry the layout was made on
+    pub geometry: Option<KeyboardGeometry>,
+    /// Generated keymap.c
+    pub keymap_c: Option<String>,
+}
+
+impl ShareBundle {
+    /// Packages `layout`, rendering a preview and embedding a geometry
+    /// snapshot when `geometry` is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the preview cannot be rendered.
+    pub fn new(layout: &Layout, geometry: Option<&KeyboardGeometry>) -> Result<Self> {
+        let preview_svg = geometry
+            .map(|geometry| export_to_svg(layout, geometry))
+            .transpose()
+            .context("Failed to render preview")?;
+        Ok(Self {
+            manifest: BundleManifest {
+                format_version: BUNDLE_FORMAT_VERSION,
+                name: layout.metadata.name.clone(),
+                keyboard: layout.metadata.keyboard.clone(),
+                layout_variant: layout.metadata.layout_variant.clone(),
+                app_version: env!("CARGO_PKG_VERSION").to_string(),
+                preview: preview_svg.is_some(),
+                geometry: geometry.is_some(),
+                keymap: false,
+            },
+            layout: layout.clone(),
+            preview_svg,
+            geometry: geometry.cloned(),
+            keymap_c: None,
+        })
+    }
+
+    /// Adds the generated keymap.c. Does nothing without a geometry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the keymap cannot be generated.
+    pub fn generate_keymap(&mut self, config: &Config, keycode_db: &KeycodeDb) -> Result<()> {
+        let Some(geometry) = &self.geometry else {
+            return Ok(());
+        };
+        let mapping = VisualLayoutMapping::build(geometry);
+        let keymap_c = FirmwareGenerator::new(&self.layout, geometry, &mapping, config, keycode_db)
+            .generate_keymap_c()
+            .context("Failed to generate keymap.c")?;
+        self.keymap_c = Some(keymap_c);
+        self.manifest.keymap = true;
+        Ok(())
+    }
+
+    /// Writes the bundle as a zip archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        // Pinned timestamp so the same layout always gives the same bundle
+        let options = SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .last_modified_time(DateTime::default())
+            .unix_permissions(0o644);
+
+        let manifest = serde_json::to_string_pretty(&self.manifest)?;
+        let layout = serde_json::to_string_pretty(&self.layout)?;
+        let mut entries = vec![(MANIFEST_FILE, manifest), (LAYOUT_FILE, layout)];
+        if let Some(svg) = &self.preview_svg {
+            entries.push((PREVIEW_FILE, svg.clone()));
+        }
+        if let Some(geometry) = &self.geometry {
+            entries.push((GEOMETRY_FILE, serde_json::to_string_pretty(geometry)?));
+        }
+        if let Some(keymap_c) = &self.keymap_c {
+            entries.push((KEYMAP_FILE, keymap_c.clone()));
+        }
+        for (name, content) in entries {
+            zip.start_file(name, options)?;
+            zip.write_all(content.as_bytes())?;
+        }
+
+        Ok(zip.finish()?.into_inner())
+    }
+
+    /// Reads a bundle from zip archive bytes and validates its layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive is not a bundle, was written by a
+    /// newer format version, or holds an invalid layout.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut archive =
+            ZipArchive::new(Cursor::new(bytes)).context("Not a LazyQMK bundle (not a zip file)")?;
+
+        let manifest: BundleManifest = serde_json::from_str(
+            &read_entry(&mut archive, MANIFEST_FILE)?
+                .context("Not a LazyQMK bundle (no manifest.json)")?,
+        )
+        .context("Invalid bundle manifest")?;
+        if manifest.format_version > BUNDLE_FORMAT_VERSION {
+            bail!(
+                "Bundle format version {} is newer than this version of LazyQMK supports ({BUNDLE_FORMAT_VERSION}); please update",
+                manifest.format_version
+            );
+        }
+
+        let layout = parse_json_layout_str(
+            &read_entry(&mut archive, LAYOUT_FILE)?.context("Bundle has no layout.json")?,
+        )
+        .context("Invalid layout in bundle")?;
+        let preview_svg = read_entry(&mut archive, PREVIEW_FILE)?;
+        let geometry = read_entry(&mut archive, GEOMETRY_FILE)?
+            .map(|json| serde_json::from_str::<KeyboardGeometry>(&json))
+            .transpose()
+            .context("Invalid geometry in bundle")?;
+        let keymap_c = read_entry(&mut archive, KEYMAP_FILE)?;
+
+        Ok(Self {
+            manifest,
+            layout,
+            preview_svg,
+            geometry,
+            keymap_c,
+        })
+    }
+
+    /// Writes the bundle to `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle cannot be serialized or written.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_bytes()?)
+            .with_context(|| format!("Failed to write bundle {}", path.display()))
+    }
+
+    /// Reads the bundle at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid bundle.
+    pub fn read(path: &Path) -> Result<Self> {
+        let bytes =
+            fs::read(path).with_context(|| format!("Failed to read bundle {}", path.display()))?;
+        Self::from_bytes(&bytes).with_context(|| format!("Failed to open {}", path.display()))
+    }
+}
+
+/// Returns the geometry embedded in the bundle at `path`.
+///
+/// Returns `None` if `path` isn't a bundle, can't be read, or has no
+/// geometry snapshot.
+#[must_use]
+pub fn bundled_geometry(path: &Path) -> Option<GeometryResult> {
+    if !path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(BUNDLE_EXTENSION))
+    {
+        return None;
+    }
+    let geometry = ShareBundle::read(path).ok()?.geometry?;
+    Some(GeometryResult::from_geometry(geometry))
+}
+
+/// Reads a UTF-8 entry, or `None` if the archive doesn't contain it.
+fn read_entry(archive: &mut ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<Option<String>> {
+    let entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {name} from bundle")),
+    };
+    if entry.size() > MAX_ENTRY_BYTES {
+        bail!("{name} in bundle is too large");
+    }
+    let mut content = String::new();
+    entry
+        .take(MAX_ENTRY_BYTES)
+        .read_to_string(&mut content)
+        .with_context(|| format!("Failed to read {name} from bundle"))?;
+    Ok(Some(content))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,115 @@
+//! Tests for services::share_bundle.
+
+use super::*;
+use crate::models::{KeyDefinition, KeyGeometry, KeyOverride, Layer, Position, RgbColor};
+use tempfile::TempDir;
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Shared").unwrap();
+    layout.metadata.keyboard = Some("test".to_string());
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_BSPC"));
+    layout.add_layer(layer).unwrap();
+    layout.key_overrides = vec![KeyOverride::parse("Shift+KC_BSPC = KC_DEL").unwrap()];
+    layout
+}
+
+fn geometry() -> KeyboardGeometry {
+    let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 1, 2);
+    geometry.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+    geometry.add_key(KeyGeometry::new((0, 1), 1, 1.0, 0.0));
+    geometry
+}
+
+#[test]
+fn test_bundle_round_trip_with_preview() {
+    let layout = create_test_layout();
+    let bundle = ShareBundle::new(&layout, Some(&geometry())).unwrap();
+
+    let read = ShareBundle::from_bytes(&bundle.to_bytes().unwrap()).unwrap();
+
+    assert_eq!(read.manifest, bundle.manifest);
+    assert_eq!(read.manifest.name, "Shared");
+    assert_eq!(read.manifest.keyboard.as_deref(), Some("test"));
+    assert!(read.manifest.preview);
+    assert!(read.preview_svg.unwrap().starts_with("<svg"));
+    assert_eq!(read.layout.layers[0].keys, layout.layers[0].keys);
+    assert_eq!(read.layout.key_overrides, layout.key_overrides);
+}
+
+#[test]
+fn test_bundle_without_geometry_has_no_preview() {
+    let bundle = ShareBundle::new(&create_test_layout(), None).unwrap();
+    assert!(!bundle.manifest.preview);
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("shared.lazyqmk");
+    bundle.write(&path).unwrap();
+    let read = ShareBundle::read(&path).unwrap();
+
+    assert!(read.preview_svg.is_none());
+    assert_eq!(read.layout.metadata.name, "Shared");
+}
+
+#[test]
+fn test_bundle_output_is_stable() {
+    let bundle = ShareBundle::new(&create_test_layout(), Some(&geometry())).unwrap();
+
+    assert_eq!(bundle.to_bytes().unwrap(), bundle.to_bytes().unwrap());
+}
+
+#[test]
+fn test_rejects_non_bundles_and_newer_formats() {
+    assert!(ShareBundle::from_bytes(b"not a zip").is_err());
+
+    let mut bundle = ShareBundle::new(&create_test_layout(), None).unwrap();
+    bundle.manifest.format_version = BUNDLE_FORMAT_VERSION + 1;
+    let err = ShareBundle::from_bytes(&bundle.to_bytes().unwrap()).unwrap_err();
+
+    assert!(format!("{err:#}").contains("newer"));
+}
+
+#[test]
+fn test_bundle_embeds_geometry_and_keymap() {
+    let mut bundle = ShareBundle::new(&create_test_layout(), Some(&geometry())).unwrap();
+    let keycode_db = KeycodeDb::load().unwrap();
+    bundle
+        .generate_keymap(&Config::default(), &keycode_db)
+        .unwrap();
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("shared.lazyqmk");
+    bundle.write(&path).unwrap();
+    let read = ShareBundle::read(&path).unwrap();
+
+    assert!(read.manifest.geometry);
+    assert!(read.manifest.keymap);
+    assert_eq!(read.geometry, Some(geometry()));
+    assert!(read.keymap_c.unwrap().contains("KC_BSPC"));
+
+    // The TUI renders bundles from the embedded geometry
+    let result = bundled_geometry(&path).unwrap();
+    assert_eq!(result.geometry, geometry());
+    assert!(result
+        .mapping
+        .get_all_visual_positions()
+        .contains(&Position::new(0, 1)));
+}
+
+#[test]
+fn test_bundle_without_geometry_has_no_keymap() {
+    let mut bundle = ShareBundle::new(&create_test_layout(), None).unwrap();
+    bundle
+        .generate_keymap(&Config::default(), &KeycodeDb::load().unwrap())
+        .unwrap();
+
+    assert!(!bundle.manifest.geometry);
+    assert!(bundle.keymap_c.is_none());
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("shared.lazyqmk");
+    bundle.write(&path).unwrap();
+    assert!(bundled_geometry(&path).is_none());
+    assert!(bundled_geometry(&dir.path().join("layout.json")).is_none());
+}
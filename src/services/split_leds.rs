@@ -0,0 +1,234 @@
+//! LED index handling for split keyboards.
+//!
+//! On a split board with RGB Matrix, `rgb_matrix.split_count` says how many
+//! LEDs each half drives: indices below the left count are on the left
+//! half, the rest on the right. The matrix is split the same way, with the
+//! right half's rows after the left half's. When `rgb_matrix.layout` gives a
+//! right-half LED a left-half matrix position (a common copy-paste mistake
+//! where the right half's rows were never offset), per-key colors end up on
+//! the wrong half.
+//!
+//! [`split_matrix_to_led`] builds the matrix → LED map used for geometry,
+//! moves such LEDs to the mirrored row on their own half when that position
+//! is free, and reports what it could not fix.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::parser::keyboard_json::{
+    build_matrix_to_led_map, LayoutDefinition, RgbMatrixConfig, SplitConfig,
+};
+
+/// One half of a split keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Half {
+    /// Left half (LEDs before the left count, the first half of the rows)
+    Left,
+    /// Right half
+    Right,
+}
+
+impl fmt::Display for Half {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Left => "left",
+            Self::Right => "right",
+        })
+    }
+}
+
+/// How LEDs and matrix rows divide between the halves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitLedLayout {
+    /// LEDs on the left half
+    pub left_count: u8,
+    /// LEDs on the right half
+    pub right_count: u8,
+    /// Matrix rows per half
+    pub half_rows: u8,
+}
+
+impl SplitLedLayout {
+    /// Returns the layout of a split board, or `None` if the board isn't
+    /// split or `rgb_matrix` has no `split_count`.
+    ///
+    /// `matrix_rows` is the row count of the whole matrix (both halves).
+    #[must_use]
+    pub fn detect(
+        rgb: &RgbMatrixConfig,
+        split: Option<&SplitConfig>,
+        matrix_rows: u8,
+    ) -> Option<Self> {
+        let [left_count, right_count] = rgb.split_count?;
+        if split.is_some_and(|split| !split.enabled) || matrix_rows < 2 {
+            return None;
+        }
+        Some(Self {
+            left_count,
+            right_count,
+            half_rows: matrix_rows / 2,
+        })
+    }
+
+    /// Half driving LED `led_index`.
+    #[must_use]
+    pub const fn led_half(self, led_index: u8) -> Half {
+        if led_index < self.left_count {
+            Half::Left
+        } else {
+            Half::Right
+        }
+    }
+
+    /// Half a matrix row belongs to.
+    #[must_use]
+    pub const fn row_half(self, row: u8) -> Half {
+        if row < self.half_rows {
+            Half::Left
+        } else {
+            Half::Right
+        }
+    }
+
+    /// The same row on the other half.
+    const fn mirrored_row(self, row: u8) -> Option<u8> {
+        match self.row_half(row) {
+            Half::Left => row.checked_add(self.half_rows),
+            Half::Right => Some(row - self.half_rows),
+        }
+    }
+}
+
+/// A problem with the LED layout of a split keyboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SplitLedIssue {
+    /// `rgb_matrix.layout` doesn't have as many LEDs as `split_count` adds up to
+    CountMismatch {
+        /// Sum of `split_count`
+        expected: usize,
+        /// Entries in `rgb_matrix.layout`
+        actual: usize,
+    },
+    /// An LED sits on one half but names a key of the other half, and the
+    /// matching key on its own half is taken
+    WrongHalf {
+        /// LED index
+        led_index: u8,
+        /// Matrix position [row, col] given for the LED
+        matrix: (u8, u8),
+        /// Half driving the LED
+        led_half: Half,
+    },
+}
+
+impl SplitLedIssue {
+    /// One-line description.
+    #[must_use]
+    pub fn message(&self) -> String {
+        match self {
+            Self::CountMismatch { expected, actual } => format!(
+                "rgb_matrix.layout has {actual} LEDs but split_count adds up to {expected}"
+            ),
+            Self::WrongHalf {
+                led_index,
+                matrix,
+                led_half,
+            } => format!(
+                "LED {led_index} is on the {led_half} half but mapped to matrix [{}, {}] on the other half; its color will show on the wrong key",
+                matrix.0, matrix.1
+            ),
+        }
+    }
+}
+
+/// Matrix → LED map for a keyboard, corrected for split LED offsets.
+#[derive(Debug, Clone, Default)]
+pub struct SplitLedMap {
+    /// Matrix position (row, col) → LED index
+    pub matrix_to_led: HashMap<(u8, u8), u8>,
+    /// LEDs moved to the mirrored row on their own half
+    pub corrected: usize,
+    /// Problems that were not corrected
+    pub issues: Vec<SplitLedIssue>,
+}
+
+/// Builds the matrix → LED map, correcting LEDs whose matrix position is on
+/// the other half of a split keyboard.
+///
+/// Boards that aren't split (see [`SplitLedLayout::detect`]) get the plain
+/// [`build_matrix_to_led_map`] result.
+#[must_use]
+pub fn split_matrix_to_led(
+    rgb: &RgbMatrixConfig,
+    split: Option<&SplitConfig>,
+    matrix_rows: u8,
+) -> SplitLedMap {
+    let Some(layout) = SplitLedLayout::detect(rgb, split, matrix_rows) else {
+        return SplitLedMap {
+            matrix_to_led: build_matrix_to_led_map(rgb),
+            ..SplitLedMap::default()
+        };
+    };
+
+    let mut result = SplitLedMap::default();
+    let expected = usize::from(layout.left_count) + usize::from(layout.right_count);
+    if rgb.layout.len() != expected {
+        result.issues.push(SplitLedIssue::CountMismatch {
+            expected,
+            actual: rgb.layout.len(),
+        });
+    }
+
+    let listed: HashSet<(u8, u8)> = rgb
+        .layout
+        .iter()
+        .filter_map(|led| led.matrix.map(|[row, col]| (row, col)))
+        .collect();
+    let mut misplaced = Vec::new();
+    for (index, led) in rgb.layout.iter().enumerate() {
+        let (Some([row, col]), Ok(led_index)) = (led.matrix, u8::try_from(index)) else {
+            continue;
+        };
+        if layout.led_half(led_index) == layout.row_half(row) {
+            result.matrix_to_led.insert((row, col), led_index);
+        } else {
+            misplaced.push((led_index, (row, col)));
+        }
+    }
+
+    // Misplaced LEDs go to their own half's matching key if no LED claims it
+    for (led_index, (row, col)) in misplaced {
+        let target = layout.mirrored_row(row).map(|row| (row, col));
+        match target {
+            Some(target) if !listed.contains(&target) => {
+                result.matrix_to_led.insert(target, led_index);
+                result.corrected += 1;
+            }
+            _ => {
+                result.matrix_to_led.entry((row, col)).or_insert(led_index);
+                result.issues.push(SplitLedIssue::WrongHalf {
+                    led_index,
+                    matrix: (row, col),
+                    led_half: layout.led_half(led_index),
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// Row count of the matrix used by `layout` (highest row + 1).
+#[must_use]
+pub fn matrix_rows(layout: &LayoutDefinition) -> u8 {
+    layout
+        .layout
+        .iter()
+        .filter_map(|key| key.matrix)
+        .map(|[row, _]| row.saturating_add(1))
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests;
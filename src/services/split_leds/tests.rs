@@ -0,0 +1,110 @@
+//! Tests for services::split_leds.
+
+use super::*;
+use crate::parser::keyboard_json::RgbLedEntry;
+
+/// RGB config for a 2x2 split board (rows 0 left, 1 right) with LEDs at
+/// the given matrix positions.
+fn rgb(split_count: Option<[u8; 2]>, matrix: &[[u8; 2]]) -> RgbMatrixConfig {
+    RgbMatrixConfig {
+        split_count,
+        layout: matrix
+            .iter()
+            .map(|&matrix| RgbLedEntry {
+                matrix: Some(matrix),
+                x: 0,
+                y: 0,
+                flags: 4,
+            })
+            .collect(),
+    }
+}
+
+fn split_enabled() -> SplitConfig {
+    SplitConfig {
+        enabled: true,
+        transport: None,
+    }
+}
+
+#[test]
+fn test_consistent_split_is_unchanged() {
+    let rgb = rgb(Some([2, 2]), &[[0, 0], [0, 1], [1, 1], [1, 0]]);
+
+    let map = split_matrix_to_led(&rgb, Some(&split_enabled()), 2);
+
+    assert_eq!(map.matrix_to_led, build_matrix_to_led_map(&rgb));
+    assert_eq!(map.corrected, 0);
+    assert!(map.issues.is_empty());
+}
+
+#[test]
+fn test_right_half_leds_on_left_rows_are_moved() {
+    // Right half LEDs 2 and 3 repeat the left half's matrix positions
+    let rgb = rgb(Some([2, 2]), &[[0, 0], [0, 1], [0, 1], [0, 0]]);
+
+    let map = split_matrix_to_led(&rgb, Some(&split_enabled()), 2);
+
+    assert_eq!(map.corrected, 2);
+    assert!(map.issues.is_empty());
+    assert_eq!(map.matrix_to_led[&(0, 0)], 0);
+    assert_eq!(map.matrix_to_led[&(0, 1)], 1);
+    assert_eq!(map.matrix_to_led[&(1, 1)], 2);
+    assert_eq!(map.matrix_to_led[&(1, 0)], 3);
+}
+
+#[test]
+fn test_uncorrectable_led_is_flagged() {
+    // LED 1 and 2 name keys of the other half whose mirrors are taken
+    let rgb = rgb(Some([2, 2]), &[[0, 0], [1, 0], [0, 0], [1, 1]]);
+
+    let map = split_matrix_to_led(&rgb, Some(&split_enabled()), 2);
+
+    assert_eq!(map.corrected, 0);
+    assert_eq!(map.matrix_to_led[&(0, 0)], 0);
+    assert_eq!(
+        map.issues,
+        vec![
+            SplitLedIssue::WrongHalf {
+                led_index: 1,
+                matrix: (1, 0),
+                led_half: Half::Left,
+            },
+            SplitLedIssue::WrongHalf {
+                led_index: 2,
+                matrix: (0, 0),
+                led_half: Half::Right,
+            },
+        ]
+    );
+    assert!(map.issues[1].message().contains("right half"));
+}
+
+#[test]
+fn test_count_mismatch_is_flagged() {
+    let rgb = rgb(Some([2, 2]), &[[0, 0], [0, 1], [1, 0]]);
+
+    let map = split_matrix_to_led(&rgb, None, 2);
+
+    assert_eq!(
+        map.issues,
+        vec![SplitLedIssue::CountMismatch {
+            expected: 4,
+            actual: 3
+        }]
+    );
+}
+
+#[test]
+fn test_non_split_boards_use_plain_map() {
+    let leds = [[0, 0], [0, 1], [0, 1], [0, 0]];
+
+    let without_count = split_matrix_to_led(&rgb(None, &leds), Some(&split_enabled()), 2);
+    let disabled = split_matrix_to_led(&rgb(Some([2, 2]), &leds), Some(&SplitConfig::default()), 2);
+
+    for map in [without_count, disabled] {
+        assert_eq!(map.corrected, 0);
+        assert!(map.issues.is_empty());
+        assert_eq!(map.matrix_to_led[&(0, 0)], 3);
+    }
+}
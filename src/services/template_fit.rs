@@ -0,0 +1,188 @@
+//! Fitting a template onto a keyboard it wasn't made for.
+//!
+//! A template saved on a Corne has keys at positions a Lily58 may not have.
+//! Instead of rejecting the template or silently dropping those keys,
+//! [`TemplateFit::plan`] lists the template keys with no position on the
+//! destination and the destination positions the template leaves empty, and
+//! proposes where each unplaced key goes. Callers let the user change the
+//! proposal with [`TemplateFit::assign`], then [`TemplateFit::apply`] moves
+//! the keys using the keyboard target remapping ([`Layout::for_target`]) and
+//! fills the remaining positions with `KC_NO`.
+
+use std::collections::{BTreeSet, HashSet};
+
+use anyhow::Result;
+
+use crate::models::{KeyboardTarget, Layout, Position, PositionRemap};
+use crate::services::geometry::{adjust_layers_to_positions, GeometryAdjustReport};
+
+/// Name of the temporary keyboard target used to move template keys.
+const FIT_TARGET: &str = "template-fit";
+
+/// How a template's keys are placed on a destination keyboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateFit {
+    /// Template positions that don't exist on the destination, in reading order
+    pub unplaced: Vec<Position>,
+    /// Destination positions no template key uses, in reading order
+    pub free: Vec<Position>,
+    /// Where each unplaced key goes (`to: None` drops it)
+    pub remap: Vec<PositionRemap>,
+}
+
+impl TemplateFit {
+    /// Compares the template's key positions with `destination` and proposes
+    /// a placement: unplaced keys fill the free positions in reading order,
+    /// and any left over are dropped.
+    #[must_use]
+    pub fn plan(template: &Layout, destination: &HashSet<Position>) -> Self {
+        let used: BTreeSet<(u8, u8)> = template
+            .layers
+            .iter()
+            .flat_map(|layer| &layer.keys)
+            .map(|key| (key.position.row, key.position.col))
+            .collect();
+        let mut free: Vec<Position> = destination
+            .iter()
+            .filter(|pos| !used.contains(&(pos.row, pos.col)))
+            .copied()
+            .collect();
+        free.sort_by_key(|pos| (pos.row, pos.col));
+
+        let unplaced: Vec<Position> = used
+            .into_iter()
+            .map(|(row, col)| Position::new(row, col))
+            .filter(|pos| !destination.contains(pos))
+            .collect();
+
+        let remap = unplaced
+            .iter()
+            .enumerate()
+            .map(|(idx, &from)| PositionRemap {
+                from,
+                to: free.get(idx).copied(),
+            })
+            .collect();
+
+        Self {
+            unplaced,
+            free,
+            remap,
+        }
+    }
+
+    /// Returns true if some template keys have no position on the destination
+    /// and the user should confirm where they go.
+    #[must_use]
+    pub fn needs_mapping(&self) -> bool {
+        !self.unplaced.is_empty()
+    }
+
+    /// Returns where the unplaced key at `from` currently goes.
+    #[must_use]
+    pub fn target_of(&self, from: Position) -> Option<Position> {
+        self.remap
+            .iter()
+            .find(|remap| remap.from == from)
+            .and_then(|remap| remap.to)
+    }
+
+    /// Free positions the key at `from` can move to: those not taken by
+    /// another unplaced key, in reading order.
+    #[must_use]
+    pub fn available(&self, from: Position) -> Vec<Position> {
+        let taken: HashSet<Position> = self
+            .remap
+            .iter()
+            .filter(|remap| remap.from != from)
+            .filter_map(|remap| remap.to)
+            .collect();
+        self.free
+            .iter()
+            .filter(|pos| !taken.contains(pos))
+            .copied()
+            .collect()
+    }
+
+    /// Moves the unplaced key at `from` to `to`, or drops it when `to` is None.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `from` isn't an unplaced key, or `to` isn't a free
+    /// position or is already taken by another key.
+    pub fn assign(&mut self, from: Position, to: Option<Position>) -> Result<()> {
+        if let Some(to) = to {
+            if !self.available(from).contains(&to) {
+                anyhow::bail!(
+                    "Position ({}, {}) is not free on the destination keyboard",
+                    to.row,
+                    to.col
+                );
+            }
+        }
+        let remap = self
+            .remap
+            .iter_mut()
+            .find(|remap| remap.from == from)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Template key ({}, {}) fits the destination keyboard as is",
+                    from.row,
+                    from.col
+                )
+            })?;
+        remap.to = to;
+        Ok(())
+    }
+
+    /// Replaces the proposal with `remap`, validating every entry.
+    ///
+    /// Unplaced keys missing from `remap` are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`Self::assign`].
+    #[allow(dead_code)] // Used by the web apply-template endpoint (lib target only)
+    pub fn assign_all(&mut self, remap: &[PositionRemap]) -> Result<()> {
+        for entry in &mut self.remap {
+            entry.to = None;
+        }
+        for entry in remap {
+            self.assign(entry.from, entry.to)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `template` placed on the destination keyboard.
+    ///
+    /// The result describes `keyboard` and `layout_variant`, is no longer a
+    /// template, and drops the template's own keyboard targets since their
+    /// positions referred to the template's keyboard. Every layer is fitted
+    /// to `destination`, with empty positions set to `KC_NO`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two keys of a layer end up on the same position.
+    pub fn apply(
+        &self,
+        template: &Layout,
+        keyboard: &str,
+        layout_variant: &str,
+        destination: &HashSet<Position>,
+    ) -> Result<(Layout, GeometryAdjustReport)> {
+        let mut target = KeyboardTarget::new(FIT_TARGET, keyboard, layout_variant);
+        target.converter.clone_from(&template.metadata.converter);
+        target.remap.clone_from(&self.remap);
+
+        let mut layout = template.clone();
+        layout.metadata.targets = vec![target];
+        let mut layout = layout.for_target(FIT_TARGET)?;
+        layout.metadata.is_template = false;
+
+        let report = adjust_layers_to_positions(&mut layout.layers, destination);
+        Ok((layout, report))
+    }
+}
+
+#[cfg(test)]
+mod tests;
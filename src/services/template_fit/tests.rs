@@ -0,0 +1,128 @@
+//! Tests for services::template_fit.
+
+use super::*;
+use crate::models::{KeyDefinition, Layer, RgbColor};
+
+/// Template with `rows` x `cols` keys on two layers, keycodes named by position.
+fn template(rows: u8, cols: u8) -> Layout {
+    let mut layout = Layout::new("Template").unwrap();
+    layout.metadata.is_template = true;
+    layout.metadata.keyboard = Some("corne".to_string());
+    for (idx, name) in ["Base", "Nav"].into_iter().enumerate() {
+        let mut layer = Layer::new(idx as u8, name, RgbColor::new(0, 0, 80)).unwrap();
+        for row in 0..rows {
+            for col in 0..cols {
+                layer.add_key(KeyDefinition::new(
+                    Position::new(row, col),
+                    format!("KC_{row}{col}"),
+                ));
+            }
+        }
+        layout.add_layer(layer).unwrap();
+    }
+    layout
+}
+
+fn grid(rows: u8, cols: u8) -> HashSet<Position> {
+    (0..rows)
+        .flat_map(|row| (0..cols).map(move |col| Position::new(row, col)))
+        .collect()
+}
+
+fn keycode_at(layout: &Layout, layer: usize, pos: Position) -> Option<&str> {
+    layout.layers[layer]
+        .keys
+        .iter()
+        .find(|key| key.position == pos)
+        .map(|key| key.keycode.as_str())
+}
+
+#[test]
+fn test_plan_proposes_free_positions_in_reading_order() {
+    // 3x2 template onto a 2x3 keyboard: row 2 is missing, column 2 is free
+    let fit = TemplateFit::plan(&template(3, 2), &grid(2, 3));
+
+    assert!(fit.needs_mapping());
+    assert_eq!(fit.unplaced, vec![Position::new(2, 0), Position::new(2, 1)]);
+    assert_eq!(fit.free, vec![Position::new(0, 2), Position::new(1, 2)]);
+    assert_eq!(
+        fit.target_of(Position::new(2, 0)),
+        Some(Position::new(0, 2))
+    );
+    assert_eq!(
+        fit.target_of(Position::new(2, 1)),
+        Some(Position::new(1, 2))
+    );
+}
+
+#[test]
+fn test_plan_drops_keys_without_free_positions() {
+    let fit = TemplateFit::plan(&template(3, 3), &grid(2, 3));
+
+    assert_eq!(fit.unplaced.len(), 3);
+    assert!(fit.free.is_empty());
+    assert!(fit.remap.iter().all(|remap| remap.to.is_none()));
+}
+
+#[test]
+fn test_plan_same_geometry_needs_no_mapping() {
+    let fit = TemplateFit::plan(&template(2, 3), &grid(2, 3));
+
+    assert!(!fit.needs_mapping());
+    assert!(fit.free.is_empty());
+}
+
+#[test]
+fn test_assign_rejects_taken_and_unknown_positions() {
+    let mut fit = TemplateFit::plan(&template(3, 2), &grid(2, 3));
+    let (a, b) = (Position::new(2, 0), Position::new(2, 1));
+
+    // (1, 2) is taken by the other unplaced key
+    assert!(fit.assign(a, Some(Position::new(1, 2))).is_err());
+    // (0, 0) is used by the template itself
+    assert!(fit.assign(a, Some(Position::new(0, 0))).is_err());
+    // (0, 0) fits as is
+    assert!(fit.assign(Position::new(0, 0), None).is_err());
+
+    fit.assign(b, None).unwrap();
+    assert_eq!(
+        fit.available(a),
+        vec![Position::new(0, 2), Position::new(1, 2)]
+    );
+    fit.assign(a, Some(Position::new(1, 2))).unwrap();
+    assert_eq!(fit.target_of(a), Some(Position::new(1, 2)));
+}
+
+#[test]
+fn test_apply_moves_keys_and_fills_empty_positions() {
+    let template = template(3, 2);
+    let destination = grid(2, 3);
+    let mut fit = TemplateFit::plan(&template, &destination);
+    fit.assign(Position::new(2, 1), None).unwrap();
+
+    let (layout, report) = fit
+        .apply(&template, "lily58/rev1", "LAYOUT", &destination)
+        .unwrap();
+
+    assert_eq!(layout.metadata.keyboard.as_deref(), Some("lily58/rev1"));
+    assert_eq!(layout.metadata.layout_variant.as_deref(), Some("LAYOUT"));
+    assert!(!layout.metadata.is_template);
+    assert!(layout.metadata.targets.is_empty());
+    for layer in 0..2 {
+        assert_eq!(layout.layers[layer].keys.len(), 6);
+        assert_eq!(
+            keycode_at(&layout, layer, Position::new(0, 2)),
+            Some("KC_20")
+        );
+        assert_eq!(
+            keycode_at(&layout, layer, Position::new(1, 2)),
+            Some("KC_NO")
+        );
+        assert_eq!(
+            keycode_at(&layout, layer, Position::new(0, 0)),
+            Some("KC_00")
+        );
+    }
+    assert_eq!(report.added_count(), 2);
+    assert_eq!(report.removed_count(), 0);
+}
@@ -0,0 +1,566 @@
+//! Thumb and hold key optimizer.
+//!
+//! Scores how well a layout's hold keys serve a text corpus and suggests
+//! base-layer swaps that make typing it less awkward. Hold keys are layer
+//! keys (`MO`, `LT`, `TT`, `LM`) and modifiers (`KC_LSFT`, `MT`, `LSFT_T`, ...).
+//!
+//! Two costs are counted, weighted by corpus frequency:
+//!
+//! - **Same-hand holds**: a character that needs a layer or Shift held while
+//!   every key providing that hold is on the same hand as the character.
+//! - **Roll conflicts**: a dual-role key whose tap character is followed by
+//!   a key on the same hand (the roll triggers the hold), or by a character
+//!   that needs the same key held right after tapping it.
+//!
+//! Hands and thumb keys come from [`PositionNames`]. Swaps are suggested
+//! between base-layer hold keys and thumb keys; each suggestion is scored
+//! against the current layout on its own so they can be applied selectively.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::models::{KeyboardGeometry, Layer, Layout, Position, PositionNames};
+use crate::services::layer_refs::{parse_layer_keycode, LayerRefTarget};
+
+/// Character and bigram frequencies of a text corpus.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorpusStats {
+    /// How often each character occurs
+    pub chars: HashMap<char, u64>,
+    /// How often each character pair occurs
+    pub bigrams: HashMap<(char, char), u64>,
+}
+
+/// On-disk statistics format: `{"chars": {"a": 10}, "bigrams": {"th": 5}}`.
+#[derive(Debug, Deserialize)]
+struct CorpusStatsFile {
+    #[serde(default)]
+    chars: HashMap<String, u64>,
+    #[serde(default)]
+    bigrams: HashMap<String, u64>,
+}
+
+impl CorpusStats {
+    /// Counts characters and bigrams in plain text.
+    #[must_use]
+    pub fn from_text(text: &str) -> Self {
+        let mut stats = Self::default();
+        let mut previous = None;
+        for c in text.chars().filter(|c| *c != '\r') {
+            *stats.chars.entry(c).or_default() += 1;
+            if let Some(prev) = previous {
+                *stats.bigrams.entry((prev, c)).or_default() += 1;
+            }
+            previous = Some(c);
+        }
+        stats
+    }
+
+    /// Parses precomputed statistics from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is malformed or a key is not one
+    /// character (`chars`) or two characters (`bigrams`) long.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let file: CorpusStatsFile =
+            serde_json::from_str(json).context("Failed to parse corpus statistics")?;
+
+        let mut stats = Self::default();
+        for (key, count) in file.chars {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => *stats.chars.entry(c).or_default() += count,
+                _ => bail!("Invalid character key {key:?}: expected one character"),
+            }
+        }
+        for (key, count) in file.bigrams {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next(), chars.next()) {
+                (Some(a), Some(b), None) => *stats.bigrams.entry((a, b)).or_default() += count,
+                _ => bail!("Invalid bigram key {key:?}: expected two characters"),
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Reads a corpus file: JSON statistics when it ends in `.json`,
+    /// plain text otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or the JSON is invalid.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read corpus {}", path.display()))?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json(&content)
+        } else {
+            Ok(Self::from_text(&content))
+        }
+    }
+}
+
+/// Something a key provides while held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Hold {
+    /// Activates a layer
+    Layer(usize),
+    /// Holds Shift
+    Shift,
+    /// Holds another modifier (Ctrl, Alt, GUI)
+    OtherMod,
+}
+
+/// A base-layer key that provides at least one hold.
+#[derive(Debug, Clone)]
+struct HoldKey {
+    position: Position,
+    left: bool,
+    holds: Vec<Hold>,
+    /// Whether the key also types something when tapped
+    dual_role: bool,
+}
+
+/// Where and how a character is typed.
+#[derive(Debug, Clone, Copy)]
+struct Stroke {
+    layer: usize,
+    position: Position,
+    left: bool,
+    shift: bool,
+}
+
+/// Cost of a layout for a corpus.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ThumbScore {
+    /// Weighted count of characters typed with the hand holding their layer or Shift
+    pub same_hand_holds: u64,
+    /// Weighted count of rolls off a dual-role key that trigger its hold
+    pub roll_conflicts: u64,
+}
+
+impl ThumbScore {
+    /// Combined cost (lower is better).
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.same_hand_holds + self.roll_conflicts
+    }
+}
+
+/// A suggested swap of two base-layer keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapSuggestion {
+    /// First key position (visual coordinates)
+    pub first: Position,
+    /// Second key position (visual coordinates)
+    pub second: Position,
+    /// Keycode currently at `first`
+    pub first_keycode: String,
+    /// Keycode currently at `second`
+    pub second_keycode: String,
+    /// Score after applying only this swap
+    pub score: ThumbScore,
+    /// How much the total cost drops
+    pub improvement: u64,
+}
+
+/// Current score and swaps that improve it, best first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThumbReport {
+    /// Score of the layout as it is
+    pub score: ThumbScore,
+    /// Improving swaps, sorted by improvement (largest first)
+    pub suggestions: Vec<SwapSuggestion>,
+}
+
+/// Scores `layout` against `stats` and suggests improving swaps.
+#[must_use]
+pub fn analyze(layout: &Layout, geometry: &KeyboardGeometry, stats: &CorpusStats) -> ThumbReport {
+    let names = PositionNames::build(geometry);
+    let score = score_layers(&layout.layers, &names, stats);
+    let Some(base) = layout.layers.first() else {
+        return ThumbReport {
+            score,
+            suggestions: Vec::new(),
+        };
+    };
+
+    // Swap candidates: unlocked thumb keys and hold keys
+    let candidates: Vec<Position> = base
+        .keys
+        .iter()
+        .filter(|key| !key.locked && names.get(key.position).is_some())
+        .filter(|key| names.is_thumb(key.position) || !holds_of(&key.keycode).is_empty())
+        .map(|key| key.position)
+        .collect();
+
+    let mut suggestions = Vec::new();
+    for (idx, &first) in candidates.iter().enumerate() {
+        for &second in &candidates[idx + 1..] {
+            let (Some(a), Some(b)) = (base.get_key(first), base.get_key(second)) else {
+                continue;
+            };
+            if a.keycode == b.keycode
+                || (holds_of(&a.keycode).is_empty() && holds_of(&b.keycode).is_empty())
+            {
+                continue;
+            }
+
+            let mut layers = layout.layers.clone();
+            swap_keys(&mut layers[0], first, second);
+            let swapped = score_layers(&layers, &names, stats);
+            if swapped.total() < score.total() {
+                suggestions.push(SwapSuggestion {
+                    first,
+                    second,
+                    first_keycode: a.keycode.clone(),
+                    second_keycode: b.keycode.clone(),
+                    score: swapped,
+                    improvement: score.total() - swapped.total(),
+                });
+            }
+        }
+    }
+
+    suggestions.sort_by(|a, b| {
+        b.improvement
+            .cmp(&a.improvement)
+            .then_with(|| sort_key(a).cmp(&sort_key(b)))
+    });
+
+    ThumbReport { score, suggestions }
+}
+
+/// Orders suggestions with equal improvement by position.
+const fn sort_key(swap: &SwapSuggestion) -> (u8, u8, u8, u8) {
+    (
+        swap.first.row,
+        swap.first.col,
+        swap.second.row,
+        swap.second.col,
+    )
+}
+
+/// Applies a suggested swap to the base layer.
+///
+/// # Errors
+///
+/// Returns an error if either key is missing, locked, or no longer holds
+/// the keycode the suggestion was made for.
+pub fn apply_swap(layout: &mut Layout, swap: &SwapSuggestion) -> Result<()> {
+    let Some(base) = layout.layers.first_mut() else {
+        bail!("Layout has no layers");
+    };
+    for (position, keycode) in [
+        (swap.first, &swap.first_keycode),
+        (swap.second, &swap.second_keycode),
+    ] {
+        match base.get_key(position) {
+            None => bail!("No key at ({}, {})", position.row, position.col),
+            Some(key) if key.locked => {
+                bail!("Key at ({}, {}) is locked", position.row, position.col)
+            }
+            Some(key) if key.keycode != *keycode => bail!(
+                "Key at ({}, {}) changed to {} since the suggestion was made",
+                position.row,
+                position.col,
+                key.keycode
+            ),
+            Some(_) => {}
+        }
+    }
+    swap_keys(base, swap.first, swap.second);
+    Ok(())
+}
+
+/// Swaps two keys (keycode, label, colors, ...) while keeping their positions.
+fn swap_keys(layer: &mut Layer, first: Position, second: Position) {
+    let first_idx = layer.keys.iter().position(|k| k.position == first);
+    let second_idx = layer.keys.iter().position(|k| k.position == second);
+    if let (Some(a), Some(b)) = (first_idx, second_idx) {
+        layer.keys.swap(a, b);
+        layer.keys[a].position = first;
+        layer.keys[b].position = second;
+    }
+}
+
+/// Scores layers against the corpus.
+fn score_layers(layers: &[Layer], names: &PositionNames, stats: &CorpusStats) -> ThumbScore {
+    let Some(base) = layers.first() else {
+        return ThumbScore::default();
+    };
+
+    let hold_keys: Vec<HoldKey> = base
+        .keys
+        .iter()
+        .filter_map(|key| {
+            let holds = holds_of(&key.keycode);
+            let left = names.is_left(key.position)?;
+            (!holds.is_empty()).then(|| HoldKey {
+                position: key.position,
+                left,
+                holds,
+                dual_role: tap_keycode(&key.keycode) != key.keycode,
+            })
+        })
+        .collect();
+    let strokes = build_strokes(layers, names, &hold_keys);
+
+    let providers = |hold: Hold| -> Vec<&HoldKey> {
+        hold_keys
+            .iter()
+            .filter(|k| k.holds.contains(&hold))
+            .collect()
+    };
+    let required_holds = |stroke: &Stroke| {
+        let mut holds = Vec::new();
+        if stroke.layer > 0 {
+            holds.push(Hold::Layer(stroke.layer));
+        }
+        if stroke.shift {
+            holds.push(Hold::Shift);
+        }
+        holds
+    };
+
+    let mut score = ThumbScore::default();
+    for (c, &count) in &stats.chars {
+        let Some(stroke) = strokes.get(c) else {
+            continue;
+        };
+        for hold in required_holds(stroke) {
+            let keys = providers(hold);
+            if !keys.is_empty() && keys.iter().all(|k| k.left == stroke.left) {
+                score.same_hand_holds += count;
+            }
+        }
+    }
+
+    for (&(a, b), &count) in &stats.bigrams {
+        let (Some(first), Some(second)) = (strokes.get(&a), strokes.get(&b)) else {
+            continue;
+        };
+        let Some(key) = hold_keys
+            .iter()
+            .find(|k| k.dual_role && first.layer == 0 && k.position == first.position)
+        else {
+            continue;
+        };
+        let rolls_same_hand = second.layer == 0
+            && second.left == key.left
+            && second.position != key.position
+            && !second.shift;
+        let needs_same_key = required_holds(second).into_iter().any(|hold| {
+            let keys = providers(hold);
+            keys.len() == 1 && keys[0].position == key.position
+        });
+        if rolls_same_hand || needs_same_key {
+            score.roll_conflicts += count;
+        }
+    }
+
+    score
+}
+
+/// Finds how each character is typed: the base layer first, then layers a
+/// base-layer hold key can reach.
+fn build_strokes(
+    layers: &[Layer],
+    names: &PositionNames,
+    hold_keys: &[HoldKey],
+) -> HashMap<char, Stroke> {
+    let mut strokes = HashMap::new();
+    for (layer_idx, layer) in layers.iter().enumerate() {
+        let reachable = layer_idx == 0
+            || hold_keys
+                .iter()
+                .any(|k| k.holds.contains(&Hold::Layer(layer_idx)));
+        if !reachable {
+            continue;
+        }
+        for key in &layer.keys {
+            let Some(left) = names.is_left(key.position) else {
+                continue;
+            };
+            for (c, shift) in typed_chars(tap_keycode(&key.keycode)) {
+                strokes.entry(c).or_insert(Stroke {
+                    layer: layer_idx,
+                    position: key.position,
+                    left,
+                    shift,
+                });
+            }
+        }
+    }
+    strokes
+}
+
+/// Returns what a keycode provides while held.
+fn holds_of(keycode: &str) -> Vec<Hold> {
+    if let Some((LayerRefTarget::Index(layer), kind)) = parse_layer_keycode(keycode) {
+        if kind.is_hold_like() {
+            return vec![Hold::Layer(layer)];
+        }
+        return Vec::new();
+    }
+
+    let mods = if let Some(inner) = keycode
+        .strip_prefix("MT(")
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        inner.split_once(',').map_or("", |(mods, _)| mods)
+    } else if let Some((mods, _)) = keycode.split_once("_T(") {
+        mods
+    } else if matches!(
+        keycode,
+        "KC_LSFT" | "KC_RSFT" | "KC_LSHIFT" | "KC_RSHIFT" | "KC_LEFT_SHIFT" | "KC_RIGHT_SHIFT"
+    ) {
+        return vec![Hold::Shift];
+    } else if [
+        "KC_LCTL", "KC_RCTL", "KC_LALT", "KC_RALT", "KC_LGUI", "KC_RGUI", "KC_LCMD", "KC_RCMD",
+        "KC_LOPT", "KC_ROPT",
+    ]
+    .contains(&keycode)
+    {
+        return vec![Hold::OtherMod];
+    } else {
+        return Vec::new();
+    };
+
+    let mut holds = Vec::new();
+    if ["SFT", "SHIFT", "MEH", "HYPR", "ALL", "C_S"]
+        .iter()
+        .any(|m| mods.contains(m))
+    {
+        holds.push(Hold::Shift);
+    }
+    if [
+        "CTL", "CTRL", "ALT", "OPT", "GUI", "CMD", "WIN", "MEH", "HYPR", "C_S",
+    ]
+    .iter()
+    .any(|m| mods.contains(m))
+    {
+        holds.push(Hold::OtherMod);
+    }
+    holds
+}
+
+/// Returns true if the keycode holds a modifier (plain modifier or mod-tap).
+#[must_use]
+pub fn is_modifier(keycode: &str) -> bool {
+    holds_of(keycode)
+        .iter()
+        .any(|hold| matches!(hold, Hold::Shift | Hold::OtherMod))
+}
+
+/// Returns the keycode a key sends when tapped (`LT(1, KC_SPC)` -> `KC_SPC`).
+#[must_use]
+pub fn tap_keycode(keycode: &str) -> &str {
+    let inner = if let Some(inner) = keycode
+        .strip_prefix("LT(")
+        .or_else(|| keycode.strip_prefix("MT("))
+    {
+        inner.strip_suffix(')').and_then(|s| s.split_once(','))
+    } else {
+        keycode
+            .split_once("_T(")
+            .and_then(|(_, rest)| rest.strip_suffix(')'))
+            .map(|kc| ("", kc))
+    };
+    inner.map_or(keycode, |(_, kc)| kc.trim())
+}
+
+/// Characters a tap keycode types, each with whether Shift must be held.
+#[must_use]
+pub fn typed_chars(keycode: &str) -> Vec<(char, bool)> {
+    if let Some(inner) = keycode
+        .strip_prefix("S(")
+        .or_else(|| keycode.strip_prefix("LSFT("))
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        // Shifted keycodes send Shift themselves
+        return key_chars(inner)
+            .and_then(|(_, shifted)| shifted)
+            .map(|c| vec![(c, false)])
+            .unwrap_or_default();
+    }
+    if let Some(c) = shifted_alias(keycode) {
+        return vec![(c, false)];
+    }
+    key_chars(keycode).map_or_else(Vec::new, |(plain, shifted)| {
+        let mut chars = vec![(plain, false)];
+        chars.extend(shifted.map(|c| (c, true)));
+        chars
+    })
+}
+
+/// Unshifted and shifted characters of a US ANSI keycode.
+fn key_chars(keycode: &str) -> Option<(char, Option<char>)> {
+    let name = keycode.strip_prefix("KC_")?;
+    if let [letter] = name.as_bytes() {
+        if letter.is_ascii_uppercase() {
+            let c = char::from(*letter);
+            return Some((c.to_ascii_lowercase(), Some(c)));
+        }
+        if letter.is_ascii_digit() {
+            const SHIFTED_DIGITS: [char; 10] = [')', '!', '@', '#', '$', '%', '^', '&', '*', '('];
+            let c = char::from(*letter);
+            return Some((c, Some(SHIFTED_DIGITS[usize::from(letter - b'0')])));
+        }
+    }
+    let chars = match name {
+        "SPC" | "SPACE" => (' ', None),
+        "ENT" | "ENTER" => ('\n', None),
+        "TAB" => ('\t', None),
+        "MINS" | "MINUS" => ('-', Some('_')),
+        "EQL" | "EQUAL" => ('=', Some('+')),
+        "LBRC" | "LEFT_BRACKET" => ('[', Some('{')),
+        "RBRC" | "RIGHT_BRACKET" => (']', Some('}')),
+        "BSLS" | "BACKSLASH" => ('\\', Some('|')),
+        "SCLN" | "SEMICOLON" => (';', Some(':')),
+        "QUOT" | "QUOTE" => ('\'', Some('"')),
+        "GRV" | "GRAVE" => ('`', Some('~')),
+        "COMM" | "COMMA" => (',', Some('<')),
+        "DOT" => ('.', Some('>')),
+        "SLSH" | "SLASH" => ('/', Some('?')),
+        _ => return None,
+    };
+    Some(chars)
+}
+
+/// Character sent by a shifted-symbol alias such as `KC_EXLM`.
+fn shifted_alias(keycode: &str) -> Option<char> {
+    let c = match keycode.strip_prefix("KC_")? {
+        "EXLM" => '!',
+        "AT" => '@',
+        "HASH" => '#',
+        "DLR" => '$',
+        "PERC" => '%',
+        "CIRC" => '^',
+        "AMPR" => '&',
+        "ASTR" => '*',
+        "LPRN" => '(',
+        "RPRN" => ')',
+        "UNDS" => '_',
+        "PLUS" => '+',
+        "LCBR" => '{',
+        "RCBR" => '}',
+        "PIPE" => '|',
+        "COLN" => ':',
+        "DQUO" => '"',
+        "TILD" => '~',
+        "LABK" => '<',
+        "RABK" => '>',
+        "QUES" => '?',
+        _ => return None,
+    };
+    Some(c)
+}
+
+#[cfg(test)]
+mod tests;
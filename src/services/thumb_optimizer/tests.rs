@@ -0,0 +1,178 @@
+//! Tests for thumb_optimizer.
+
+use super::*;
+use crate::models::{KeyDefinition, KeyGeometry, RgbColor};
+
+/// Split board: two 6-key rows (3 per hand) and a 4-key thumb row.
+fn create_test_geometry() -> KeyboardGeometry {
+    let mut geom = KeyboardGeometry::new("test", "LAYOUT", 3, 6);
+    let mut led = 0;
+    for row in 0u8..2 {
+        for col in 0u8..6 {
+            geom.add_key(KeyGeometry::new(
+                (row, col),
+                led,
+                f32::from(col),
+                f32::from(row),
+            ));
+            led += 1;
+        }
+    }
+    for col in 1u8..5 {
+        geom.add_key(KeyGeometry::new((2, col), led, f32::from(col), 2.0));
+        led += 1;
+    }
+    geom
+}
+
+fn create_layer(number: u8, keycodes: &[&[&str]]) -> Layer {
+    let mut layer = Layer::new(number, format!("Layer {number}"), RgbColor::new(0, 0, 0)).unwrap();
+    for (row, keycodes) in keycodes.iter().enumerate() {
+        let offset = usize::from(row == 2);
+        for (col, keycode) in keycodes.iter().enumerate() {
+            layer.add_key(KeyDefinition::new(
+                Position::new(row as u8, (col + offset) as u8),
+                *keycode,
+            ));
+        }
+    }
+    layer
+}
+
+/// Shift and the symbol layer key sit on the same hands as what they modify.
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    layout
+        .add_layer(create_layer(
+            0,
+            &[
+                &["KC_A", "KC_B", "KC_C", "KC_D", "KC_E", "KC_F"],
+                &["KC_G", "KC_H", "KC_I", "KC_J", "KC_K", "KC_L"],
+                &["KC_ENT", "KC_LSFT", "MO(1)", "KC_SPC"],
+            ],
+        ))
+        .unwrap();
+    layout
+        .add_layer(create_layer(
+            1,
+            &[
+                &[
+                    "KC_TRNS", "KC_TRNS", "KC_TRNS", "KC_EXLM", "KC_QUES", "KC_TRNS",
+                ],
+                &["KC_TRNS"; 6],
+                &["KC_TRNS"; 4],
+            ],
+        ))
+        .unwrap();
+    layout
+}
+
+#[test]
+fn test_corpus_stats_from_text() {
+    let stats = CorpusStats::from_text("aab\r\n");
+
+    assert_eq!(stats.chars[&'a'], 2);
+    assert_eq!(stats.chars[&'\n'], 1);
+    assert!(!stats.chars.contains_key(&'\r'));
+    assert_eq!(stats.bigrams[&('a', 'a')], 1);
+    assert_eq!(stats.bigrams[&('b', '\n')], 1);
+}
+
+#[test]
+fn test_corpus_stats_from_json() {
+    let stats = CorpusStats::from_json(r#"{"chars": {"A": 3}, "bigrams": {"th": 5}}"#).unwrap();
+    assert_eq!(stats.chars[&'A'], 3);
+    assert_eq!(stats.bigrams[&('t', 'h')], 5);
+
+    assert!(CorpusStats::from_json(r#"{"bigrams": {"the": 1}}"#).is_err());
+}
+
+#[test]
+fn test_corpus_stats_from_file_by_extension() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let text = dir.path().join("corpus.txt");
+    let json = dir.path().join("corpus.json");
+    fs::write(&text, "aa").unwrap();
+    fs::write(&json, r#"{"chars": {"a": 7}}"#).unwrap();
+
+    assert_eq!(CorpusStats::from_file(&text).unwrap().chars[&'a'], 2);
+    assert_eq!(CorpusStats::from_file(&json).unwrap().chars[&'a'], 7);
+    assert!(CorpusStats::from_file(&dir.path().join("missing.txt")).is_err());
+}
+
+#[test]
+fn test_same_hand_holds_are_scored() {
+    let stats = CorpusStats::from_text("Ab! ?");
+    let report = analyze(&create_test_layout(), &create_test_geometry(), &stats);
+
+    // 'A' (left) needs the left Shift; '!' and '?' (right) need the right MO(1)
+    assert_eq!(report.score.same_hand_holds, 3);
+}
+
+#[test]
+fn test_suggests_swapping_hold_keys_across_hands() {
+    let stats = CorpusStats::from_text("Ab! ?");
+    let report = analyze(&create_test_layout(), &create_test_geometry(), &stats);
+
+    let best = &report.suggestions[0];
+    assert_eq!(
+        (best.first_keycode.as_str(), best.second_keycode.as_str()),
+        ("KC_LSFT", "MO(1)")
+    );
+    assert_eq!(best.score.same_hand_holds, 0);
+    assert_eq!(best.improvement, 3);
+}
+
+#[test]
+fn test_roll_conflicts_on_dual_role_keys() {
+    let mut layout = create_test_layout();
+    // Space doubles as the only symbol layer key on the right thumb
+    layout.layers[0].keys[14].keycode = "KC_BSPC".to_string();
+    layout.layers[0].keys[15].keycode = "LT(1, KC_SPC)".to_string();
+    let stats = CorpusStats::from_text(" d ?");
+
+    let report = analyze(&layout, &create_test_geometry(), &stats);
+
+    // " d" rolls onto the same hand, " ?" needs the same key held after tapping
+    assert_eq!(report.score.roll_conflicts, 2);
+}
+
+#[test]
+fn test_apply_swap_keeps_positions() {
+    let mut layout = create_test_layout();
+    let stats = CorpusStats::from_text("A");
+    let report = analyze(&layout, &create_test_geometry(), &stats);
+    let swap = report.suggestions[0].clone();
+
+    apply_swap(&mut layout, &swap).unwrap();
+
+    let base = &layout.layers[0];
+    assert_eq!(
+        base.get_key(swap.first).unwrap().keycode,
+        swap.second_keycode
+    );
+    assert_eq!(
+        base.get_key(swap.second).unwrap().keycode,
+        swap.first_keycode
+    );
+    // Applying again fails because the keys moved
+    assert!(apply_swap(&mut layout, &swap).is_err());
+}
+
+#[test]
+fn test_locked_keys_are_not_moved() {
+    let mut layout = create_test_layout();
+    for key in &mut layout.layers[0].keys {
+        if key.keycode == "KC_LSFT" {
+            key.locked = true;
+        }
+    }
+    let stats = CorpusStats::from_text("A");
+
+    let report = analyze(&layout, &create_test_geometry(), &stats);
+
+    assert!(report
+        .suggestions
+        .iter()
+        .all(|swap| swap.first_keycode != "KC_LSFT" && swap.second_keycode != "KC_LSFT"));
+}
@@ -0,0 +1,110 @@
+//! Key usage recorded from the keyboard.
+//!
+//! Firmware generated with usage counting on (`usage_counting` in the layout)
+//! reports batches of press counts per layer and matrix position on the HID
+//! console (see [`crate::services::hid_listen`]). The collector,
+//! `lazyqmk collect-usage`, adds them up in a [`UsageLog`] stored next to the
+//! layout file as `<layout>.usage`, keyed by visual position like the layout
+//! itself. The log stays on this machine; the layout stats popup and the web
+//! stats endpoint turn it into a heatmap of real typing.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::Position;
+
+/// Extension of usage log files.
+pub const USAGE_EXTENSION: &str = "usage";
+
+/// Recorded presses of one key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyUsage {
+    /// Layer index
+    pub layer: usize,
+    /// Key position
+    pub position: Position,
+    /// Presses recorded so far
+    pub presses: u64,
+}
+
+/// Presses recorded for a layout.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageLog {
+    /// Pressed keys, by layer and position
+    #[serde(default)]
+    pub keys: Vec<KeyUsage>,
+}
+
+impl UsageLog {
+    /// Path of the usage log for the layout at `layout_path`.
+    #[must_use]
+    pub fn path_for(layout_path: &Path) -> PathBuf {
+        layout_path.with_extension(USAGE_EXTENSION)
+    }
+
+    /// Loads the log at `path`; a missing file is an empty log.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read usage log {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid usage log {}", path.display()))
+    }
+
+    /// Writes the log to `path` (temp file + rename).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content =
+            serde_json::to_string_pretty(self).context("Failed to serialize usage log")?;
+        let temp_path = path.with_extension("usage.tmp");
+        fs::write(&temp_path, content)
+            .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to write usage log {}", path.display()))
+    }
+
+    /// Adds `presses` to the key at `position` on `layer`.
+    pub fn record(&mut self, layer: usize, position: Position, presses: u64) {
+        let key = (layer, position.row, position.col);
+        match self.keys.binary_search_by_key(&key, |usage| {
+            (usage.layer, usage.position.row, usage.position.col)
+        }) {
+            Ok(idx) => self.keys[idx].presses += presses,
+            Err(idx) => self.keys.insert(
+                idx,
+                KeyUsage {
+                    layer,
+                    position,
+                    presses,
+                },
+            ),
+        }
+    }
+
+    /// Total presses recorded.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.keys.iter().map(|usage| usage.presses).sum()
+    }
+
+    /// Returns true if nothing has been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,45 @@
+//! Tests for services::usage.
+
+use super::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_record_adds_up_presses_in_order() {
+    let mut log = UsageLog::default();
+    log.record(1, Position::new(0, 2), 3);
+    log.record(0, Position::new(1, 0), 5);
+    log.record(1, Position::new(0, 2), 4);
+
+    assert_eq!(
+        log.keys,
+        vec![
+            KeyUsage {
+                layer: 0,
+                position: Position::new(1, 0),
+                presses: 5,
+            },
+            KeyUsage {
+                layer: 1,
+                position: Position::new(0, 2),
+                presses: 7,
+            },
+        ]
+    );
+    assert_eq!(log.total(), 12);
+}
+
+#[test]
+fn test_save_and_load_round_trip() {
+    let temp = TempDir::new().unwrap();
+    let path = UsageLog::path_for(&temp.path().join("corne.json"));
+    assert_eq!(path, temp.path().join("corne.usage"));
+
+    // Nothing recorded yet
+    assert!(UsageLog::load(&path).unwrap().is_empty());
+
+    let mut log = UsageLog::default();
+    log.record(0, Position::new(2, 3), 9);
+    log.save(&path).unwrap();
+
+    assert_eq!(UsageLog::load(&path).unwrap(), log);
+}
@@ -0,0 +1,150 @@
+//! Opening the TUI's layout in the web editor.
+//!
+//! The web editor needs a `lazyqmk web` server for the layout's directory. A
+//! server already running on that workspace is found through the address it
+//! advertises in its workspace lock
+//! ([`web_server_url`](crate::services::workspace_lock::web_server_url)).
+//! Otherwise [`WebServer::start`] runs one as a child process on a free local
+//! port; it is stopped when the [`WebServer`] is dropped.
+//!
+//! Both processes coordinate saves to the layout file through the workspace,
+//! so edits made on either side show up on the other.
+
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+
+use crate::services::workspace_lock;
+
+/// How long to wait for a started server to listen.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay between checks for a starting server.
+const STARTUP_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A `lazyqmk web` server started for a workspace; stopped on drop.
+#[derive(Debug)]
+pub struct WebServer {
+    child: Child,
+    url: String,
+}
+
+impl WebServer {
+    /// Starts a web server for `workspace` and waits until it listens.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server can't be started or doesn't come up
+    /// within a few seconds.
+    pub fn start(workspace: &Path) -> Result<Self> {
+        let exe = std::env::current_exe().context("Failed to locate the LazyQMK executable")?;
+        let child = Command::new(exe)
+            .arg("web")
+            .arg("--workspace")
+            .arg(workspace)
+            .args(["--port", "0"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to start the web server")?;
+        let mut server = Self {
+            child,
+            url: String::new(),
+        };
+
+        let deadline = Instant::now() + STARTUP_TIMEOUT;
+        loop {
+            if let Some(url) = workspace_lock::web_server_url(workspace) {
+                server.url = url;
+                return Ok(server);
+            }
+            if !server.is_running() {
+                bail!("The web server exited during startup (this build may not include it)");
+            }
+            if Instant::now() >= deadline {
+                bail!("The web server did not start within {STARTUP_TIMEOUT:?}");
+            }
+            thread::sleep(STARTUP_POLL_INTERVAL);
+        }
+    }
+
+    /// Address the server listens on.
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns true while the server process is running.
+    pub fn is_running(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+}
+
+impl Drop for WebServer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Returns the web editor URL of `filename` on the server at `base_url`.
+#[must_use]
+pub fn layout_url(base_url: &str, filename: &str) -> String {
+    let encoded: String = filename
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+                char::from(byte).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect();
+    format!("{}/layouts/{encoded}", base_url.trim_end_matches('/'))
+}
+
+/// Opens `url` in the default browser.
+///
+/// # Errors
+///
+/// Returns an error if the system's URL opener can't be started.
+pub fn open_browser(url: &str) -> Result<()> {
+    let mut command = opener(url);
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to open a browser - visit {url} instead"))?;
+    // Reap the opener once it hands the URL to the browser
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn opener(url: &str) -> Command {
+    let mut command = Command::new("open");
+    command.arg(url);
+    command
+}
+
+#[cfg(windows)]
+fn opener(url: &str) -> Command {
+    let mut command = Command::new("cmd");
+    command.args(["/C", "start", "", url]);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn opener(url: &str) -> Command {
+    let mut command = Command::new("xdg-open");
+    command.arg(url);
+    command
+}
+
+#[cfg(test)]
+mod tests;
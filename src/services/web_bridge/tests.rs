@@ -0,0 +1,13 @@
+use super::*;
+
+#[test]
+fn test_layout_url_encodes_filename() {
+    assert_eq!(
+        layout_url("http://127.0.0.1:3001", "corne.json"),
+        "http://127.0.0.1:3001/layouts/corne.json"
+    );
+    assert_eq!(
+        layout_url("http://127.0.0.1:3001/", "my layout&v2.json"),
+        "http://127.0.0.1:3001/layouts/my%20layout%26v2.json"
+    );
+}
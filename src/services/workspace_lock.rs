@@ -0,0 +1,257 @@
+//! Coordination between LazyQMK processes editing the same workspace.
+//!
+//! The TUI and the web server can both have the layouts of one directory open.
+//! Each process registers itself for as long as it runs with a lock file in
+//! `.lazyqmk/locks/` (`tui-<pid>.lock` or `web-<pid>.lock`), so either side can
+//! tell who else is there. The lock files are advisory; saves are coordinated
+//! with [`FileStamp`]s. A process remembers the stamp of the file it loaded or
+//! last wrote and treats any other stamp on disk as a save by someone else:
+//! - The TUI reloads a layout without unsaved edits, and asks before
+//!   overwriting one that has them.
+//! - The web server tells the sessions watching the layout to reload it.
+//!
+//! Once listening, the web server also writes its address into its lock file
+//! so the TUI can open the workspace in a browser without starting a second
+//! server (see [`web_server_url`]).
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Extension of lock files.
+const LOCK_EXTENSION: &str = "lock";
+
+/// Kind of LazyQMK process using a workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceClient {
+    /// Terminal editor
+    Tui,
+    /// Web server (`lazyqmk web`)
+    Web,
+}
+
+impl WorkspaceClient {
+    /// Name used in lock file names.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Tui => "tui",
+            Self::Web => "web",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "tui" => Some(Self::Tui),
+            "web" => Some(Self::Web),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for WorkspaceClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tui => write!(f, "the LazyQMK TUI"),
+            Self::Web => write!(f, "the LazyQMK web server"),
+        }
+    }
+}
+
+/// Another running process using the same workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkspacePeer {
+    /// What kind of process it is
+    pub client: WorkspaceClient,
+    /// Its process id
+    pub pid: u32,
+}
+
+impl std::fmt::Display for WorkspacePeer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (pid {})", self.client, self.pid)
+    }
+}
+
+/// This process's registration in a workspace; removed on drop.
+#[derive(Debug)]
+pub struct WorkspaceLock {
+    workspace: PathBuf,
+    path: PathBuf,
+}
+
+impl WorkspaceLock {
+    /// Registers this process as `client` of the workspace directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock directory or file cannot be written.
+    pub fn acquire(workspace: &Path, client: WorkspaceClient) -> Result<Self> {
+        let dir = lock_dir(workspace);
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create lock directory: {}", dir.display()))?;
+        let pid = std::process::id();
+        let path = dir.join(format!("{}-{pid}.{LOCK_EXTENSION}", client.as_str()));
+        fs::write(&path, format!("{pid}\n"))
+            .with_context(|| format!("Failed to write lock file: {}", path.display()))?;
+        Ok(Self {
+            workspace: workspace.to_path_buf(),
+            path,
+        })
+    }
+
+    /// Workspace directory this lock belongs to.
+    #[must_use]
+    pub fn workspace(&self) -> &Path {
+        &self.workspace
+    }
+
+    /// Records the address this process serves the workspace on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock file cannot be written.
+    #[allow(dead_code)] // Used by the web server (lib target only)
+    pub fn advertise(&self, url: &str) -> Result<()> {
+        fs::write(&self.path, format!("{}\n{url}\n", std::process::id()))
+            .with_context(|| format!("Failed to write lock file: {}", self.path.display()))
+    }
+
+    /// Lists the other running processes registered in the workspace.
+    ///
+    /// Lock files left behind by processes that are no longer running are
+    /// removed.
+    #[must_use]
+    pub fn peers(&self) -> Vec<WorkspacePeer> {
+        let Ok(entries) = fs::read_dir(lock_dir(&self.workspace)) else {
+            return Vec::new();
+        };
+        let own_pid = std::process::id();
+        let mut peers: Vec<WorkspacePeer> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let peer = parse_lock_name(&path)?;
+                if peer.pid == own_pid {
+                    return None;
+                }
+                if !process_alive(peer.pid) {
+                    let _ = fs::remove_file(&path);
+                    return None;
+                }
+                Some(peer)
+            })
+            .collect();
+        peers.sort_by_key(|peer| peer.pid);
+        peers
+    }
+
+    /// Describes who else may have written to the workspace, for messages.
+    #[must_use]
+    pub fn describe_peers(&self) -> String {
+        let peers = self.peers();
+        if peers.is_empty() {
+            return "another program".to_string();
+        }
+        peers
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl Drop for WorkspaceLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Returns the address of a running web server serving `workspace`, if any.
+#[must_use]
+pub fn web_server_url(workspace: &Path) -> Option<String> {
+    let mut locks: Vec<_> = fs::read_dir(lock_dir(workspace))
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    locks.sort();
+    locks.into_iter().find_map(|path| {
+        let peer = parse_lock_name(&path)?;
+        if peer.client != WorkspaceClient::Web || !process_alive(peer.pid) {
+            return None;
+        }
+        let contents = fs::read_to_string(&path).ok()?;
+        let url = contents.lines().nth(1)?.trim();
+        (!url.is_empty()).then(|| url.to_string())
+    })
+}
+
+/// Identifies one version of a file on disk (modification time and size).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileStamp {
+    /// Reads the stamp of `path`, or `None` if it can't be read.
+    #[must_use]
+    pub fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+/// Directory holding the lock files of `workspace`.
+fn lock_dir(workspace: &Path) -> PathBuf {
+    workspace.join(".lazyqmk").join("locks")
+}
+
+/// Parses a lock file name such as `web-1234.lock`.
+fn parse_lock_name(path: &Path) -> Option<WorkspacePeer> {
+    if path.extension()? != LOCK_EXTENSION {
+        return None;
+    }
+    let (client, pid) = path.file_stem()?.to_str()?.split_once('-')?;
+    Some(WorkspacePeer {
+        client: WorkspaceClient::parse(client)?,
+        pid: pid.parse().ok()?,
+    })
+}
+
+/// Returns true if a process with `pid` is running.
+#[cfg(target_os = "linux")]
+fn process_alive(pid: u32) -> bool {
+    Path::new("/proc").join(pid.to_string()).exists()
+}
+
+/// Returns true if a process with `pid` is running.
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_alive(pid: u32) -> bool {
+    // `kill -0` fails with EPERM for other users' processes, which still exist
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::piped())
+        .output()
+        .is_ok_and(|output| {
+            output.status.success()
+                || String::from_utf8_lossy(&output.stderr).contains("not permitted")
+        })
+}
+
+/// Returns true if a process with `pid` is running.
+#[cfg(windows)]
+fn process_alive(pid: u32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+}
+
+#[cfg(test)]
+mod tests;
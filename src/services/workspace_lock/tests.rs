@@ -0,0 +1,94 @@
+use super::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_acquire_creates_and_drop_removes_lock_file() {
+    let dir = TempDir::new().unwrap();
+    let lock = WorkspaceLock::acquire(dir.path(), WorkspaceClient::Web).unwrap();
+    let path = lock_dir(dir.path()).join(format!("web-{}.lock", std::process::id()));
+    assert!(path.is_file());
+    assert_eq!(lock.workspace(), dir.path());
+
+    drop(lock);
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_peers_skip_own_process() {
+    let dir = TempDir::new().unwrap();
+    let tui = WorkspaceLock::acquire(dir.path(), WorkspaceClient::Tui).unwrap();
+    let _web = WorkspaceLock::acquire(dir.path(), WorkspaceClient::Web).unwrap();
+
+    assert!(tui.peers().is_empty());
+    assert_eq!(tui.describe_peers(), "another program");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_peers_report_running_processes_and_remove_stale_locks() {
+    let dir = TempDir::new().unwrap();
+    let lock = WorkspaceLock::acquire(dir.path(), WorkspaceClient::Tui).unwrap();
+    let locks = lock_dir(dir.path());
+    // pid 1 always runs; u32::MAX never does
+    fs::write(locks.join("web-1.lock"), "1\n").unwrap();
+    let stale = locks.join(format!("tui-{}.lock", u32::MAX));
+    fs::write(&stale, "").unwrap();
+    fs::write(locks.join("notes.txt"), "").unwrap();
+
+    assert_eq!(
+        lock.peers(),
+        vec![WorkspacePeer {
+            client: WorkspaceClient::Web,
+            pid: 1
+        }]
+    );
+    assert_eq!(lock.describe_peers(), "the LazyQMK web server (pid 1)");
+    assert!(!stale.exists());
+}
+
+#[test]
+fn test_parse_lock_name() {
+    assert_eq!(
+        parse_lock_name(Path::new("/w/.lazyqmk/locks/tui-42.lock")),
+        Some(WorkspacePeer {
+            client: WorkspaceClient::Tui,
+            pid: 42
+        })
+    );
+    assert_eq!(parse_lock_name(Path::new("web-42.tmp")), None);
+    assert_eq!(parse_lock_name(Path::new("cli-42.lock")), None);
+    assert_eq!(parse_lock_name(Path::new("web-x.lock")), None);
+}
+
+#[test]
+fn test_file_stamp_changes_when_file_is_rewritten() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("layout.json");
+    assert_eq!(FileStamp::of(&path), None);
+
+    fs::write(&path, "{}").unwrap();
+    let first = FileStamp::of(&path).unwrap();
+    assert_eq!(FileStamp::of(&path), Some(first));
+
+    fs::write(&path, "{\"changed\": true}").unwrap();
+    assert_ne!(FileStamp::of(&path), Some(first));
+}
+
+#[test]
+fn test_web_server_url_from_advertised_lock() {
+    let dir = TempDir::new().unwrap();
+    assert_eq!(web_server_url(dir.path()), None);
+
+    // Registered but not listening yet
+    let lock = WorkspaceLock::acquire(dir.path(), WorkspaceClient::Web).unwrap();
+    assert_eq!(web_server_url(dir.path()), None);
+
+    lock.advertise("http://127.0.0.1:3001").unwrap();
+    assert_eq!(
+        web_server_url(dir.path()).as_deref(),
+        Some("http://127.0.0.1:3001")
+    );
+
+    drop(lock);
+    assert_eq!(web_server_url(dir.path()), None);
+}
@@ -31,12 +31,22 @@ pub enum Action {
     NextLayer,
     /// Switch to the previous layer.
     PreviousLayer,
+    /// Switch directly to the layer with the given index.
+    JumpToLayer(u8),
+    /// Show the layer that the hold layer key under the cursor activates over the current one.
+    HoldLayerKey,
 
     // === KEY EDITING ===
     /// Open the keycode picker dialog for editing a key.
     OpenKeycodePicker,
     /// Clear the current key, removing its keycode.
     ClearKey,
+    /// Clear the current key (or selection) to `KC_NO`, blocking lower layers.
+    ClearKeyToNone,
+    /// Lock or unlock the current key (or selection) against bulk edits.
+    ToggleKeyLock,
+    /// Make the current layer's keys under its hold keys transparent.
+    FixLayerAccess,
 
     // === CLIPBOARD ===
     /// Copy the current key to the clipboard.
@@ -45,6 +55,8 @@ pub enum Action {
     CutKey,
     /// Paste the clipboard contents to the current key.
     PasteKey,
+    /// Paste copied keys back at the positions they were copied from.
+    PasteKeyInPlace,
     /// Undo the last paste operation.
     UndoPaste,
 
@@ -57,16 +69,24 @@ pub enum Action {
     StartRectangleSelect,
     /// Swap properties between two keys.
     SwapKeys,
+    /// Copy the selected keys onto another layer at the same positions.
+    CopyToLayer,
 
     // === COLORS ===
     /// Open color picker to set color for the individual key.
     SetIndividualKeyColor,
     /// Open color picker to set color for the entire layer.
     SetLayerColor,
+    /// Open color picker to set the layer's underglow (non-key LED) color.
+    SetUnderglowColor,
     /// Toggle the visibility of layer colors.
     ToggleLayerColors,
     /// Toggle the visibility of colors for all layers.
     ToggleAllLayerColors,
+    /// Cycle the keyboard between firmware, category, and override colors.
+    CycleColorView,
+    /// Show what transparent keys resolve to from the layers below.
+    ToggleResolvedKeys,
 
     // === CATEGORIES ===
     /// Open the category manager dialog.
@@ -85,22 +105,48 @@ pub enum Action {
     EditMetadata,
     /// Open the tap dance editor dialog.
     OpenTapDanceEditor,
+    /// Search keys by keycode, label or description and jump to a match.
+    SearchKeys,
+    /// Open the macro editor dialog.
+    OpenMacroEditor,
+    /// Open the Unicode map editor dialog.
+    OpenUnicodeMapEditor,
+    /// Open the custom keycode editor dialog.
+    OpenCustomKeycodeEditor,
+    /// Open the keycode combo editor dialog.
+    OpenComboEditor,
+    /// Open the rotary encoder editor for the current layer.
+    OpenEncoderEditor,
+    /// Show layout statistics and the typing heatmap.
+    ShowLayoutStats,
+    /// Show which keys activate which layers and flag stuck layers.
+    ShowLayerGraph,
 
     // === FILE OPERATIONS ===
     /// Save the current keyboard configuration.
     Save,
     /// Export the current layout to markdown format.
     ExportLayout,
+    /// Restore the layout from one of its automatic backups.
+    RestoreBackup,
+    /// Open the layout in the web editor, starting a local server if needed.
+    OpenInBrowser,
     /// Quit the application.
     Quit,
 
     // === BUILD & FIRMWARE ===
     /// Build the firmware for the current keyboard.
     BuildFirmware,
+    /// Check the generated keymap for C errors without building.
+    CheckFirmware,
     /// Generate the firmware code for the current keyboard.
     GenerateFirmware,
     /// View the build log from the last firmware build.
     ViewBuildLog,
+    /// Flash the last built firmware onto a keyboard in bootloader mode.
+    FlashFirmware,
+    /// Start or stop showing the keyboard's active layer and key presses.
+    ToggleLivePreview,
 
     // === TEMPLATES ===
     /// Open the template browser to load a template configuration.
@@ -113,6 +159,8 @@ pub enum Action {
     SetupWizard,
     /// Switch to a different layout variant.
     SwitchLayoutVariant,
+    /// Switch the QMK checkout the layout builds against.
+    SwitchQmkTree,
 
     // === HELP ===
     /// Toggle the help panel display.
@@ -123,6 +171,23 @@ pub enum Action {
     Cancel,
 }
 
+impl Action {
+    /// Returns true if this action leaves a held layer key (Alt+H) down.
+    #[must_use]
+    pub const fn keeps_layer_held(self) -> bool {
+        matches!(
+            self,
+            Self::NavigateUp
+                | Self::NavigateDown
+                | Self::NavigateLeft
+                | Self::NavigateRight
+                | Self::JumpToFirst
+                | Self::JumpToLast
+                | Self::HoldLayerKey
+        )
+    }
+}
+
 /// Shortcut registry that maps key events to actions for a given context.
 ///
 /// This is the central source of truth for all keyboard shortcuts in the application.
@@ -195,11 +260,20 @@ impl ShortcutRegistry {
         self.register(ctx, K::Tab, M::SHIFT, Action::PreviousLayer);
         // Some terminals emit BackTab with the SHIFT modifier set
         self.register(ctx, K::BackTab, M::SHIFT, Action::PreviousLayer);
+        for digit in 0..=9u8 {
+            let key = K::Char(char::from(b'0' + digit));
+            self.register(ctx, key, M::ALT, Action::JumpToLayer(digit));
+        }
+        self.register(ctx, K::Char('h'), M::ALT, Action::HoldLayerKey);
 
         // === KEY EDITING ===
         self.register(ctx, K::Enter, M::NONE, Action::OpenKeycodePicker);
         self.register(ctx, K::Backspace, M::NONE, Action::ClearKey);
         self.register(ctx, K::Delete, M::NONE, Action::ClearKey);
+        self.register(ctx, K::Delete, M::SHIFT, Action::ClearKeyToNone);
+        self.register(ctx, K::Backspace, M::ALT, Action::ClearKeyToNone);
+        self.register(ctx, K::Char('X'), M::SHIFT, Action::ToggleKeyLock);
+        self.register(ctx, K::Char('F'), M::SHIFT, Action::FixLayerAccess);
 
         // === CLIPBOARD ===
         self.register(ctx, K::Char('y'), M::NONE, Action::CopyKey);
@@ -207,6 +281,7 @@ impl ShortcutRegistry {
         self.register(ctx, K::Char('x'), M::CONTROL, Action::CutKey);
         self.register(ctx, K::Char('p'), M::NONE, Action::PasteKey);
         self.register(ctx, K::Char('v'), M::CONTROL, Action::PasteKey);
+        self.register(ctx, K::Char('p'), M::ALT, Action::PasteKeyInPlace);
         self.register(ctx, K::Char('z'), M::CONTROL, Action::UndoPaste);
 
         // === SELECTION ===
@@ -214,12 +289,16 @@ impl ShortcutRegistry {
         self.register(ctx, K::Char(' '), M::NONE, Action::ToggleCurrentKey);
         self.register(ctx, K::Char('R'), M::SHIFT, Action::StartRectangleSelect);
         self.register(ctx, K::Char('W'), M::SHIFT, Action::SwapKeys);
+        self.register(ctx, K::Char('y'), M::ALT, Action::CopyToLayer);
 
         // === COLORS (v0.4.0: c = individual, Shift+C = layer) ===
         self.register(ctx, K::Char('c'), M::NONE, Action::SetIndividualKeyColor);
         self.register(ctx, K::Char('C'), M::SHIFT, Action::SetLayerColor);
+        self.register(ctx, K::Char('c'), M::ALT, Action::SetUnderglowColor);
         self.register(ctx, K::Char('v'), M::NONE, Action::ToggleLayerColors);
         self.register(ctx, K::Char('V'), M::ALT, Action::ToggleAllLayerColors);
+        self.register(ctx, K::Char('I'), M::SHIFT, Action::CycleColorView);
+        self.register(ctx, K::Char('t'), M::ALT, Action::ToggleResolvedKeys);
 
         // === CATEGORIES (v0.4.0: Shift+K = manager, Ctrl+K/L = quick assign) ===
         self.register(ctx, K::Char('K'), M::SHIFT, Action::OpenCategoryManager);
@@ -231,16 +310,29 @@ impl ShortcutRegistry {
         self.register(ctx, K::Char('S'), M::SHIFT, Action::OpenSettings);
         self.register(ctx, K::Char('E'), M::SHIFT, Action::EditMetadata);
         self.register(ctx, K::Char('D'), M::SHIFT, Action::OpenTapDanceEditor);
+        self.register(ctx, K::Char('/'), M::NONE, Action::SearchKeys);
+        self.register(ctx, K::Char('M'), M::SHIFT, Action::OpenMacroEditor);
+        self.register(ctx, K::Char('U'), M::SHIFT, Action::OpenUnicodeMapEditor);
+        self.register(ctx, K::Char('J'), M::SHIFT, Action::OpenCustomKeycodeEditor);
+        self.register(ctx, K::Char('O'), M::SHIFT, Action::OpenComboEditor);
+        self.register(ctx, K::Char('N'), M::SHIFT, Action::OpenEncoderEditor);
+        self.register(ctx, K::Char('A'), M::SHIFT, Action::ShowLayoutStats);
+        self.register(ctx, K::Char('G'), M::SHIFT, Action::ShowLayerGraph);
 
         // === FILE OPERATIONS ===
         self.register(ctx, K::Char('s'), M::CONTROL, Action::Save);
         self.register(ctx, K::Char('e'), M::CONTROL, Action::ExportLayout);
+        self.register(ctx, K::Char('r'), M::CONTROL, Action::RestoreBackup);
+        self.register(ctx, K::Char('o'), M::CONTROL, Action::OpenInBrowser);
         self.register(ctx, K::Char('q'), M::CONTROL, Action::Quit);
 
         // === BUILD & FIRMWARE (v0.4.0: Shift+B = build log) ===
         self.register(ctx, K::Char('b'), M::CONTROL, Action::BuildFirmware);
+        self.register(ctx, K::Char('b'), M::ALT, Action::CheckFirmware);
         self.register(ctx, K::Char('g'), M::CONTROL, Action::GenerateFirmware);
         self.register(ctx, K::Char('B'), M::SHIFT, Action::ViewBuildLog);
+        self.register(ctx, K::Char('f'), M::CONTROL, Action::FlashFirmware);
+        self.register(ctx, K::Char('P'), M::SHIFT, Action::ToggleLivePreview);
 
         // === TEMPLATES ===
         self.register(ctx, K::Char('t'), M::NONE, Action::BrowseTemplates);
@@ -249,6 +341,7 @@ impl ShortcutRegistry {
         // === CONFIGURATION ===
         self.register(ctx, K::Char('w'), M::CONTROL, Action::SetupWizard);
         self.register(ctx, K::Char('Y'), M::SHIFT, Action::SwitchLayoutVariant);
+        self.register(ctx, K::Char('Q'), M::SHIFT, Action::SwitchQmkTree);
 
         // === HELP ===
         self.register(ctx, K::Char('?'), M::NONE, Action::ToggleHelp);
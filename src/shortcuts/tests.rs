@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_basic_lookup() {
     let registry = ShortcutRegistry::new();
@@ -17,6 +15,14 @@ fn test_basic_lookup() {
     // Test save
     let event = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
     assert_eq!(registry.lookup("main", event), Some(Action::Save));
+
+    // Test restore from backup
+    let event = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+    assert_eq!(registry.lookup("main", event), Some(Action::RestoreBackup));
+
+    // Test open in browser
+    let event = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL);
+    assert_eq!(registry.lookup("main", event), Some(Action::OpenInBrowser));
 }
 
 #[test]
@@ -51,6 +57,48 @@ fn test_v040_shortcuts() {
     let event = KeyEvent::new(KeyCode::Char('B'), KeyModifiers::SHIFT);
     assert_eq!(registry.lookup("main", event), Some(Action::ViewBuildLog));
 
+    // Test flash shortcut
+    let event = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL);
+    assert_eq!(registry.lookup("main", event), Some(Action::FlashFirmware));
+
+    // Test keymap check shortcut
+    let event = KeyEvent::new(KeyCode::Char('b'), KeyModifiers::ALT);
+    assert_eq!(registry.lookup("main", event), Some(Action::CheckFirmware));
+
+    // Test color view shortcut
+    let event = KeyEvent::new(KeyCode::Char('I'), KeyModifiers::SHIFT);
+    assert_eq!(registry.lookup("main", event), Some(Action::CycleColorView));
+
+    // Test layer hold preview shortcut
+    let event = KeyEvent::new(KeyCode::Char('h'), KeyModifiers::ALT);
+    assert_eq!(registry.lookup("main", event), Some(Action::HoldLayerKey));
+
+    // Test layer access fix shortcut
+    let event = KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT);
+    assert_eq!(registry.lookup("main", event), Some(Action::FixLayerAccess));
+
+    // Test layout stats shortcut
+    let event = KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT);
+    assert_eq!(
+        registry.lookup("main", event),
+        Some(Action::ShowLayoutStats)
+    );
+
+    // Test layer graph shortcut
+    let event = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+    assert_eq!(registry.lookup("main", event), Some(Action::ShowLayerGraph));
+
+    // Test QMK tree switcher shortcut
+    let event = KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::SHIFT);
+    assert_eq!(registry.lookup("main", event), Some(Action::SwitchQmkTree));
+
+    // Test live preview shortcut
+    let event = KeyEvent::new(KeyCode::Char('P'), KeyModifiers::SHIFT);
+    assert_eq!(
+        registry.lookup("main", event),
+        Some(Action::ToggleLivePreview)
+    );
+
     // Test safer layout variant shortcut
     let event = KeyEvent::new(KeyCode::Char('Y'), KeyModifiers::SHIFT);
     assert_eq!(
@@ -104,6 +152,16 @@ fn test_destructive_shortcuts_require_deliberate_keys() {
         Some(Action::ClearKey),
         "backspace should clear key"
     );
+    assert_eq!(
+        registry.lookup("main", KeyEvent::new(KeyCode::Delete, KeyModifiers::SHIFT)),
+        Some(Action::ClearKeyToNone),
+        "Shift+Delete should clear key to KC_NO"
+    );
+    assert_eq!(
+        registry.lookup("main", KeyEvent::new(KeyCode::Char('p'), KeyModifiers::ALT)),
+        Some(Action::PasteKeyInPlace),
+        "Alt+P should paste in place"
+    );
     assert_eq!(
         registry.lookup(
             "main",
@@ -177,3 +235,18 @@ fn test_layer_navigation_shortcuts() {
         "Tab+SHIFT should also be mapped to PreviousLayer"
     );
 }
+
+#[test]
+fn test_alt_digit_jumps_to_layer() {
+    let registry = ShortcutRegistry::new();
+
+    let alt_three = KeyEvent::new(KeyCode::Char('3'), KeyModifiers::ALT);
+    assert_eq!(
+        registry.lookup("main", alt_three),
+        Some(Action::JumpToLayer(3))
+    );
+
+    // Plain digits stay unbound
+    let three = KeyEvent::new(KeyCode::Char('3'), KeyModifiers::NONE);
+    assert_eq!(registry.lookup("main", three), None);
+}
@@ -5,18 +5,27 @@
 
 use anyhow::{Context, Result};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use crate::config::Config;
 use crate::firmware::BuildState;
 use crate::keycode_db::KeycodeDb;
 use crate::models::{
-    ComboAction, ComboDefinition, KeyboardGeometry, Layout, Position, VisualLayoutMapping,
+    ComboAction, ComboDefinition, KeyboardGeometry, Layer, Layout, Position, PositionNames,
+    VisualLayoutMapping,
 };
 use crate::services::geometry::{
-    build_geometry_for_layout, extract_base_keyboard, GeometryContext,
+    adjust_layers_to_positions, extract_base_keyboard, GeometryAdjustReport, GeometryContext,
 };
+use crate::services::geometry_cache::build_geometry_for_layout_cached;
+use crate::services::git::{GitFileStatus, GitRepo};
+use crate::services::hid_listen::{HidListener, LiveState};
+use crate::services::host_layout::HostLayout;
 use crate::services::layer_refs::{build_layer_ref_index, LayerRef};
+use crate::services::web_bridge::WebServer;
+use crate::services::workspace_lock::{FileStamp, WorkspaceClient, WorkspaceLock};
+use crate::services::LayoutService;
 use crate::tui::build_log::BuildLog;
 use crate::tui::category_manager::{CategoryManager, CategoryManagerState};
 use crate::tui::category_picker::CategoryPicker;
@@ -37,6 +46,12 @@ use crate::tui::template_browser::TemplateBrowser;
 use crate::tui::theme::Theme;
 use crate::tui::PopupType;
 
+/// How often the layout file is checked for saves made by other processes.
+const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often the git status of the layout file is re-read.
+const GIT_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Category picker context - what are we setting the category for?
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CategoryPickerContext {
@@ -228,6 +243,51 @@ pub enum SelectionMode {
     },
 }
 
+/// A key being dragged with the mouse onto another key to swap them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyDrag {
+    /// Key the drag started on
+    pub from: Position,
+    /// Key under the mouse pointer, if any
+    pub over: Option<Position>,
+}
+
+/// Which colors the keyboard widget shows
+///
+/// Cycled with Shift+I to debug why a key lights up in a given color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorView {
+    /// Colors the generated firmware uses, after every precedence rule
+    #[default]
+    Firmware,
+    /// Key and layer category colors only
+    Categories,
+    /// Individual key color overrides only
+    Overrides,
+}
+
+impl ColorView {
+    /// Returns the view after this one.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Firmware => Self::Categories,
+            Self::Categories => Self::Overrides,
+            Self::Overrides => Self::Firmware,
+        }
+    }
+
+    /// Short name shown in the keyboard title and status bar.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Firmware => "firmware colors",
+            Self::Categories => "category colors only",
+            Self::Overrides => "overrides only",
+        }
+    }
+}
+
 /// Active component - holds the currently active popup component
 ///
 /// This enum wraps all component types that implement the Component or `ContextualComponent` trait.
@@ -267,20 +327,65 @@ pub enum ActiveComponent {
     SettingsManager(crate::tui::settings_manager::SettingsManager),
     /// Layout variant picker component (for switching QMK layout variants)
     LayoutVariantPicker(LayoutVariantPicker),
+    /// Key description search component
+    KeySearch(crate::tui::key_search::KeySearch),
+    /// Copy keys to another layer component
+    CopyToLayer(crate::tui::copy_to_layer::CopyToLayer),
+    /// Macro editor component
+    MacroEditor(crate::tui::macro_editor::MacroEditor),
+    /// Unicode map editor component
+    UnicodeMapEditor(crate::tui::unicode_map_editor::UnicodeMapEditor),
+    /// Custom keycode editor component
+    CustomKeycodeEditor(crate::tui::custom_keycode_editor::CustomKeycodeEditor),
+    /// Keycode combo editor component
+    ComboEditor(crate::tui::combo_editor::ComboEditor),
+    /// Rotary encoder editor component
+    EncoderEditor(crate::tui::encoder_editor::EncoderEditor),
+    /// Backup restore picker component
+    BackupPicker(crate::tui::backup_picker::BackupPicker),
+    /// QMK tree picker component
+    QmkTreePicker(crate::tui::qmk_tree_picker::QmkTreePicker),
+    /// Placement of template keys the open keyboard has no position for
+    TemplateFit(Box<crate::tui::template_fit::TemplateFitDialog>),
+    /// Values for the placeholders of a template being applied
+    TemplatePlaceholders(Box<crate::tui::template_placeholders::TemplatePlaceholderDialog>),
+    /// Layout statistics popup
+    LayoutStats(crate::tui::layout_stats::LayoutStatsView),
+    /// Layer graph popup
+    LayerGraph(crate::tui::layer_graph::LayerGraphView),
 }
 
 /// Application state - single source of truth
 ///
 /// All UI components read from this state immutably.
 /// Only event handlers modify state explicitly.
+#[allow(clippy::struct_excessive_bools)] // Independent UI flags
 pub struct AppState {
     // Core data
     /// Current keyboard layout
     pub layout: Layout,
     /// Path to source layout file
     pub source_path: Option<PathBuf>,
+    /// Git branch of the repository containing `source_path` (if any)
+    pub git_branch: Option<String>,
+    /// Git work tree containing `source_path` (if any)
+    pub git_repo: Option<GitRepo>,
+    /// Whether `source_path` is committed in `git_repo`
+    pub git_status: Option<GitFileStatus>,
+    /// When `git_status` was last refreshed
+    pub last_git_check: Instant,
     /// Whether layout has unsaved changes
     pub dirty: bool,
+    /// Registration in the workspace containing `source_path`, so a web
+    /// server on the same directory knows the TUI is there
+    pub workspace_lock: Option<WorkspaceLock>,
+    /// Version of `source_path` on disk when it was loaded or last saved here
+    pub disk_stamp: Option<FileStamp>,
+    /// Version of `source_path` saved by another process that the user was
+    /// warned about; saving overwrites it without asking again
+    pub foreign_disk_stamp: Option<FileStamp>,
+    /// When `source_path` was last checked for changes on disk
+    pub last_disk_check: Instant,
 
     // UI state
     /// Current UI theme
@@ -343,6 +448,14 @@ pub struct AppState {
     pub selection_mode: Option<SelectionMode>,
     /// Selected keys in selection mode (positions on current layer)
     pub selected_keys: Vec<Position>,
+    /// Which colors the keyboard widget shows
+    pub color_view: ColorView,
+    /// Layer shown over the current one while a layer key is held (Alt+H)
+    pub held_layer: Option<usize>,
+    /// Transparent keys show what they resolve to from lower layers (Alt+T)
+    pub show_resolved_keys: bool,
+    /// Key being dragged onto another key with the mouse
+    pub key_drag: Option<KeyDrag>,
 
     // System resources
     /// Keycode database
@@ -351,13 +464,38 @@ pub struct AppState {
     pub geometry: KeyboardGeometry,
     /// Visual-to-matrix position mapping
     pub mapping: VisualLayoutMapping,
+    /// Human-readable key names derived from the geometry
+    pub position_names: PositionNames,
+    /// Host keyboard layout used for key labels (when enabled in config)
+    pub host_layout: Option<HostLayout>,
     /// Application configuration
     pub config: Config,
+    /// Whether the recent keycodes in `config` changed since it was saved
+    pub recent_keycodes_changed: bool,
 
     // Firmware build state
     /// Current firmware build state (if building)
     pub build_state: Option<BuildState>,
 
+    // Live preview
+    /// Listener for the keyboard's console reports (while live preview runs)
+    pub hid_listener: Option<HidListener>,
+    /// Active layer and key presses reported by the keyboard
+    pub live_state: LiveState,
+
+    /// Web server started by "Open in browser" (stopped on exit)
+    pub web_server: Option<WebServer>,
+
+    // Geometry adjustment review
+    /// Keys added/dropped when fitting layers to the geometry, shown for review before saving
+    pub geometry_adjust_report: Option<GeometryAdjustReport>,
+    /// Layers as they were before the pending adjustments, restored if the review is declined
+    pub geometry_adjust_original: Option<Vec<Layer>>,
+    /// Scroll offset of the review popup, in lines
+    pub geometry_review_scroll: usize,
+    /// Whether to quit once the review is accepted and the layout saved
+    pub quit_after_geometry_review: bool,
+
     // Layer reference tracking
     /// Index of layer references (which keys on which layers reference this layer)
     /// Key: target layer index, Value: list of references to that layer
@@ -405,11 +543,22 @@ impl AppState {
 
         // Build initial layer reference index
         let layer_refs = build_layer_ref_index(&layout.layers);
+        let position_names = PositionNames::build(&geometry);
+        let git_repo = source_path.as_deref().and_then(GitRepo::discover);
+        let git_branch = git_repo.as_ref().and_then(GitRepo::branch);
 
-        Ok(Self {
+        let mut state = Self {
             layout,
             source_path,
+            git_branch,
+            git_repo,
+            git_status: None,
+            last_git_check: Instant::now(),
             dirty: false,
+            workspace_lock: None,
+            disk_stamp: None,
+            foreign_disk_stamp: None,
+            last_disk_check: Instant::now(),
             theme,
             current_layer: 0,
             selected_position,
@@ -433,15 +582,46 @@ impl AppState {
             flash_highlight: None,
             selection_mode: None,
             selected_keys: Vec::new(),
+            color_view: ColorView::default(),
+            held_layer: None,
+            show_resolved_keys: false,
+            key_drag: None,
             keycode_db,
             geometry,
             mapping,
+            position_names,
+            host_layout: None,
             config,
+            recent_keycodes_changed: false,
             build_state: None,
+            hid_listener: None,
+            live_state: LiveState::default(),
+            web_server: None,
+            geometry_adjust_report: None,
+            geometry_adjust_original: None,
+            geometry_review_scroll: 0,
+            quit_after_geometry_review: false,
             layer_refs,
             should_quit: false,
             return_to_settings_after_picker: false,
-        })
+        };
+        if state.config.ui.host_layout_labels {
+            state.refresh_host_layout();
+        }
+        state.sync_custom_keycodes();
+        state.track_source_file();
+        state.refresh_git_status();
+        if let Some(lock) = &state.workspace_lock {
+            let peers = lock.peers();
+            if !peers.is_empty() {
+                let names: Vec<String> = peers.iter().map(ToString::to_string).collect();
+                state.set_status(format!(
+                    "{} is also using this workspace - its saves are reloaded here",
+                    names.join(", ")
+                ));
+            }
+        }
+        Ok(state)
     }
 
     /// Get the currently selected key (mutable)
@@ -482,14 +662,11 @@ impl AppState {
             self.set_error(format!("Maximum of {MAX_COMBOS} combos allowed"));
             return;
         }
-        self.layout
-            .combo_settings
-            .combos
-            .push(ComboDefinition::new(
-                Position { row: 0, col: 0 },
-                Position { row: 0, col: 0 },
-                ComboAction::DisableEffects,
-            ));
+        self.layout.combo_settings.combos.push(ComboDefinition::new(
+            Position { row: 0, col: 0 },
+            Position { row: 0, col: 0 },
+            ComboAction::DisableEffects,
+        ));
         self.mark_dirty();
     }
 
@@ -521,11 +698,12 @@ impl AppState {
         };
 
         // Build geometry using the service
-        let geo_result = build_geometry_for_layout(geo_context, layout_name)?;
+        let geo_result = build_geometry_for_layout_cached(geo_context, layout_name)?;
 
         // Update AppState with new geometry and mapping
         self.geometry = geo_result.geometry;
         self.mapping = geo_result.mapping;
+        self.position_names = PositionNames::build(&self.geometry);
 
         // Store the layout variant in the layout metadata for persistence
         self.layout.metadata.layout_variant = Some(layout_name.to_string());
@@ -553,10 +731,11 @@ impl AppState {
     /// - Keys are added as `KC_NO` for new positions
     /// - Existing keys at valid positions are preserved
     ///
+    /// Any keys added or dropped are collected in `geometry_adjust_report` and
+    /// presented for review before the next save.
+    ///
     /// Call this after loading a layout to ensure keys match the geometry.
     pub fn adjust_layers_to_geometry(&mut self) -> Result<()> {
-        use crate::models::layer::KeyDefinition;
-
         // Get all valid positions from the mapping
         let valid_positions: std::collections::HashSet<Position> = self
             .mapping
@@ -564,32 +743,57 @@ impl AppState {
             .into_iter()
             .collect();
 
-        // Adjust each layer
-        for layer in &mut self.layout.layers {
-            // Keep only keys that are still in valid positions
-            layer
-                .keys
-                .retain(|key| valid_positions.contains(&key.position));
-
-            // Find which positions are missing
-            let existing_positions: std::collections::HashSet<Position> =
-                layer.keys.iter().map(|k| k.position).collect();
-
-            let missing_positions: Vec<Position> = valid_positions
-                .iter()
-                .filter(|pos| !existing_positions.contains(pos))
-                .copied()
-                .collect();
-
-            // Add KC_NO keys for missing positions
-            for pos in missing_positions {
-                layer.add_key(KeyDefinition::new(pos, "KC_NO"));
-            }
+        let original = self.layout.layers.clone();
+        let report = adjust_layers_to_positions(&mut self.layout.layers, &valid_positions);
+        if report.is_empty() {
+            return Ok(());
         }
+        self.geometry_adjust_original.get_or_insert(original);
+
+        let report = match self.geometry_adjust_report.take() {
+            Some(mut pending) => {
+                pending.merge(report);
+                pending
+            }
+            None => report,
+        };
+        self.set_status_with_style(
+            format!("{} - review shown on save", report.summary()),
+            self.theme.warning,
+        );
+        self.geometry_adjust_report = Some(report);
 
         Ok(())
     }
 
+    /// Undoes the pending geometry adjustments and discards their report.
+    ///
+    /// Positions filled with `KC_NO` are removed again and dropped keys are
+    /// put back. Layers are matched by id, so edits made since the adjustment
+    /// are kept, including keys assigned to added positions.
+    pub fn revert_geometry_adjustments(&mut self) {
+        self.geometry_adjust_report = None;
+        let Some(original) = self.geometry_adjust_original.take() else {
+            return;
+        };
+
+        for layer in &mut self.layout.layers {
+            let Some(before) = original.iter().find(|l| l.id == layer.id) else {
+                continue;
+            };
+            layer.keys.retain(|key| {
+                key.keycode != "KC_NO" || before.keys.iter().any(|k| k.position == key.position)
+            });
+            for key in &before.keys {
+                if layer.get_key(key.position).is_none() {
+                    layer.add_key(key.clone());
+                }
+            }
+        }
+        self.refresh_layer_refs();
+        self.mark_dirty();
+    }
+
     /// Set status message
     pub fn set_status(&mut self, message: impl Into<String>) {
         self.status_message = message.into();
@@ -618,6 +822,199 @@ impl AppState {
         self.error_message = None;
     }
 
+    /// Re-detect the host keyboard layout after `host_layout_labels` changes.
+    ///
+    /// Detection failures are shown as an error and leave the default labels.
+    pub fn refresh_host_layout(&mut self) {
+        self.host_layout = None;
+        if !self.config.ui.host_layout_labels {
+            return;
+        }
+        match HostLayout::detect() {
+            Ok(layout) => {
+                self.set_status(format!("Key labels follow host layout: {}", layout.name()));
+                self.host_layout = Some(layout);
+            }
+            Err(e) => self.set_error(format!("Host layout labels unavailable: {e}")),
+        }
+    }
+
+    /// Label for a basic keycode: the host layout's character when host
+    /// layout labels are active, otherwise the keycode without `KC_`.
+    #[must_use]
+    pub fn key_label<'a>(&'a self, keycode: &'a str) -> &'a str {
+        self.host_layout
+            .as_ref()
+            .and_then(|layout| layout.label(keycode))
+            .unwrap_or_else(|| keycode.strip_prefix("KC_").unwrap_or(keycode))
+    }
+
+    /// Re-detect the git branch and repository after `source_path` changes
+    /// (e.g. Save As).
+    pub fn refresh_git_branch(&mut self) {
+        self.git_repo = self.source_path.as_deref().and_then(GitRepo::discover);
+        self.git_branch = self.git_repo.as_ref().and_then(GitRepo::branch);
+        self.refresh_git_status();
+    }
+
+    /// Re-read whether `source_path` is committed in its git repository.
+    pub fn refresh_git_status(&mut self) {
+        self.last_git_check = Instant::now();
+        self.git_status = match (&self.git_repo, &self.source_path) {
+            (Some(repo), Some(path)) => repo.file_status(path).ok(),
+            _ => None,
+        };
+    }
+
+    /// Refresh the git status at most once per [`GIT_CHECK_INTERVAL`], to
+    /// notice commits made outside the TUI.
+    pub fn check_git_status(&mut self) {
+        if self.git_repo.is_some() && self.last_git_check.elapsed() >= GIT_CHECK_INTERVAL {
+            self.refresh_git_status();
+        }
+    }
+
+    /// Start tracking `source_path` after it changes (load, rename, template):
+    /// register in its workspace and remember the version on disk.
+    pub fn track_source_file(&mut self) {
+        self.disk_stamp = self.source_path.as_deref().and_then(FileStamp::of);
+        self.foreign_disk_stamp = None;
+
+        let workspace = self
+            .source_path
+            .as_deref()
+            .filter(|path| path.is_file())
+            .and_then(Path::parent)
+            .map(|dir| {
+                if dir.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    dir
+                }
+            });
+        if self.workspace_lock.as_ref().map(WorkspaceLock::workspace) != workspace {
+            // Release first: re-registering in the same directory reuses the file name
+            self.workspace_lock = None;
+            self.workspace_lock =
+                workspace.and_then(|dir| WorkspaceLock::acquire(dir, WorkspaceClient::Tui).ok());
+        }
+    }
+
+    /// Check `source_path` for saves made by another process, such as the web
+    /// server, at most once per [`DISK_CHECK_INTERVAL`].
+    ///
+    /// Without unsaved edits the layout is reloaded. With unsaved edits the
+    /// user is warned once that saving will overwrite the other change.
+    pub fn check_disk_changes(&mut self) {
+        if self.last_disk_check.elapsed() < DISK_CHECK_INTERVAL {
+            return;
+        }
+        self.last_disk_check = Instant::now();
+        let Some(path) = self.source_path.clone() else {
+            return;
+        };
+        let Some(stamp) = self.foreign_change(&path) else {
+            return;
+        };
+
+        let name = display_name(&path);
+        let writer = self.describe_other_writers();
+        if self.dirty {
+            self.foreign_disk_stamp = Some(stamp);
+            self.set_error(format!(
+                "{name} was saved by {writer} - saving here will overwrite that change"
+            ));
+            return;
+        }
+        match LayoutService::load(&path) {
+            Ok(layout) => {
+                self.replace_layout(layout);
+                self.disk_stamp = Some(stamp);
+                self.foreign_disk_stamp = None;
+                self.set_status(format!("Reloaded {name} after a save by {writer}"));
+            }
+            Err(e) => {
+                self.foreign_disk_stamp = Some(stamp);
+                self.set_error(format!(
+                    "{name} changed on disk but could not be reloaded: {e}"
+                ));
+            }
+        }
+    }
+
+    /// Returns true if saving may overwrite `source_path`.
+    ///
+    /// The first save over a change made by another process is refused with
+    /// a warning; saving again overwrites it.
+    pub fn confirm_overwrite_on_save(&mut self) -> bool {
+        let Some(path) = self.source_path.clone() else {
+            return true;
+        };
+        let Some(stamp) = self.foreign_change(&path) else {
+            return true;
+        };
+        self.foreign_disk_stamp = Some(stamp);
+        self.set_error(format!(
+            "{} was saved by {} since it was loaded - save again to overwrite it",
+            display_name(&path),
+            self.describe_other_writers()
+        ));
+        false
+    }
+
+    /// Record that `source_path` was just written by this process.
+    pub fn mark_saved_to_disk(&mut self) {
+        self.disk_stamp = self.source_path.as_deref().and_then(FileStamp::of);
+        self.foreign_disk_stamp = None;
+    }
+
+    /// Returns the version of `path` on disk if it was written by someone
+    /// else and the user hasn't been warned about it yet.
+    fn foreign_change(&self, path: &Path) -> Option<FileStamp> {
+        let stamp = FileStamp::of(path)?;
+        (Some(stamp) != self.disk_stamp && Some(stamp) != self.foreign_disk_stamp).then_some(stamp)
+    }
+
+    fn describe_other_writers(&self) -> String {
+        self.workspace_lock.as_ref().map_or_else(
+            || "another program".to_string(),
+            WorkspaceLock::describe_peers,
+        )
+    }
+
+    /// Replace the layout with a version reloaded from disk, keeping the
+    /// current layer where it still exists.
+    fn replace_layout(&mut self, layout: Layout) {
+        let variant_changed = layout.metadata.layout_variant != self.layout.metadata.layout_variant;
+        self.layout = layout;
+        self.geometry_adjust_report = None;
+        self.geometry_adjust_original = None;
+        if variant_changed {
+            if let Some(variant) = self.layout.metadata.layout_variant.clone() {
+                if let Err(e) = self.rebuild_geometry(&variant) {
+                    self.set_error(format!("Failed to rebuild geometry: {e}"));
+                }
+            }
+        }
+        self.current_layer = self
+            .current_layer
+            .min(self.layout.layers.len().saturating_sub(1));
+        self.selection_mode = None;
+        self.selected_keys.clear();
+        self.refresh_layer_refs();
+        self.sync_custom_keycodes();
+        self.mark_clean();
+    }
+
+    /// Replace the layout with a restored backup.
+    ///
+    /// The restored layout is an unsaved change until the next save, which
+    /// backs up the file it replaces.
+    pub fn restore_layout(&mut self, layout: Layout) {
+        self.replace_layout(layout);
+        self.mark_dirty();
+    }
+
     /// Refresh the layer reference index after layer changes
     ///
     /// Call this after operations that add/remove/modify layer-switching keycodes:
@@ -629,6 +1026,15 @@ impl AppState {
         self.layer_refs = build_layer_ref_index(&self.layout.layers);
     }
 
+    /// Make the layout's custom keycodes and enabled editing helpers
+    /// searchable in the keycode database
+    pub fn sync_custom_keycodes(&mut self) {
+        self.keycode_db.set_layout_keycodes(
+            &self.layout.custom_keycodes,
+            &self.layout.editing_helpers.enabled(),
+        );
+    }
+
     // === Component Management Methods (Component Trait Pattern) ===
 
     /// Open the color picker component
@@ -642,10 +1048,34 @@ impl AppState {
         self.active_popup = Some(PopupType::ColorPicker);
     }
 
+    /// Create a keycode picker with the user's last language, favorites and
+    /// recent keycodes
+    #[must_use]
+    pub fn keycode_picker(&self) -> KeycodePicker {
+        let mut picker =
+            KeycodePicker::with_language(self.config.ui.last_language.clone(), &self.keycode_db);
+        picker.set_keycode_lists(
+            &self.config.ui.favorite_keycodes,
+            &self.config.ui.recent_keycodes,
+        );
+        picker
+    }
+
+    /// Save the config if keycodes were picked since it was last saved.
+    ///
+    /// Recent keycodes are only kept in memory while picking; this runs when
+    /// the layout is saved and when the editor closes.
+    pub fn save_recent_keycodes(&mut self) {
+        if self.recent_keycodes_changed {
+            self.recent_keycodes_changed = false;
+            let _ = self.config.save();
+        }
+    }
+
     /// Open the keycode picker component
     pub fn open_keycode_picker(&mut self) {
-        let picker =
-            KeycodePicker::with_language(self.config.ui.last_language.clone(), &self.keycode_db);
+        self.sync_custom_keycodes();
+        let picker = self.keycode_picker();
         self.active_component = Some(ActiveComponent::KeycodePicker(picker));
         self.active_popup = Some(PopupType::KeycodePicker);
     }
@@ -695,7 +1125,8 @@ impl AppState {
 
     /// Open the layout variant picker component (for switching QMK keyboard layout variants)
     pub fn open_layout_variant_picker(&mut self, qmk_path: &PathBuf, keyboard: &str) -> Result<()> {
-        let picker = LayoutVariantPicker::new(qmk_path, keyboard);
+        let picker =
+            LayoutVariantPicker::new(qmk_path, &self.config.paths.keyboard_roots, keyboard);
         self.active_component = Some(ActiveComponent::LayoutVariantPicker(picker));
         self.active_popup = Some(PopupType::LayoutPicker);
         Ok(())
@@ -729,9 +1160,120 @@ impl AppState {
         self.active_popup = Some(PopupType::TapDanceEditor);
     }
 
+    /// Open the macro editor component
+    pub fn open_macro_editor(&mut self) {
+        let editor = crate::tui::macro_editor::MacroEditor::new(&self.layout);
+        self.active_component = Some(ActiveComponent::MacroEditor(editor));
+        self.active_popup = Some(PopupType::MacroEditor);
+    }
+
+    /// Open the Unicode map editor component; Enter applies `key_kind` keycodes
+    pub fn open_unicode_map_editor(
+        &mut self,
+        key_kind: crate::tui::unicode_map_editor::UnicodeKeyKind,
+    ) {
+        let editor = crate::tui::unicode_map_editor::UnicodeMapEditor::new(&self.layout, key_kind);
+        self.active_component = Some(ActiveComponent::UnicodeMapEditor(editor));
+        self.active_popup = Some(PopupType::UnicodeMapEditor);
+    }
+
+    /// Open the custom keycode editor component
+    pub fn open_custom_keycode_editor(&mut self) {
+        let editor = crate::tui::custom_keycode_editor::CustomKeycodeEditor::new(&self.layout);
+        self.active_component = Some(ActiveComponent::CustomKeycodeEditor(editor));
+        self.active_popup = Some(PopupType::CustomKeycodeEditor);
+    }
+
+    /// Open the combo editor component, prefilling new combos with the
+    /// selected keys (or the key under the cursor)
+    pub fn open_combo_editor(&mut self) {
+        let selection = if self.selected_keys.is_empty() {
+            vec![self.selected_position]
+        } else {
+            self.selected_keys.clone()
+        };
+        let editor = crate::tui::combo_editor::ComboEditor::new(&self.layout, &selection);
+        self.active_component = Some(ActiveComponent::ComboEditor(editor));
+        self.active_popup = Some(PopupType::ComboEditor);
+    }
+
+    /// Open the encoder editor component for the current layer
+    pub fn open_encoder_editor(&mut self) {
+        let Some(layer) = self.layout.layers.get(self.current_layer) else {
+            return;
+        };
+        let editor =
+            crate::tui::encoder_editor::EncoderEditor::new(layer, self.geometry.encoder_count);
+        self.active_component = Some(ActiveComponent::EncoderEditor(editor));
+        self.active_popup = Some(PopupType::EncoderEditor);
+    }
+
+    /// Open the backup picker for `backups` of the open layout (newest first)
+    pub fn open_backup_picker(&mut self, backups: Vec<crate::services::backup::BackupInfo>) {
+        let picker = crate::tui::backup_picker::BackupPicker::new(backups, self.dirty);
+        self.active_component = Some(ActiveComponent::BackupPicker(picker));
+        self.active_popup = Some(PopupType::BackupPicker);
+    }
+
+    /// Open the picker for the QMK tree the layout builds against
+    pub fn open_qmk_tree_picker(&mut self) {
+        let picker = crate::tui::qmk_tree_picker::QmkTreePicker::new(
+            &self.config,
+            self.layout.metadata.qmk_tree.as_deref(),
+        );
+        self.active_component = Some(ActiveComponent::QmkTreePicker(picker));
+        self.active_popup = Some(PopupType::QmkTreePicker);
+    }
+
+    /// Open the layout statistics popup, with the recorded key usage if there
+    /// is any (no corpus heatmap until a corpus is loaded)
+    pub fn open_layout_stats(&mut self) {
+        let analysis =
+            crate::services::analysis::analyze_layout(&self.layout, Some(&self.geometry), None);
+        let mut view = crate::tui::layout_stats::LayoutStatsView::new(analysis);
+        if let Some(path) = &self.source_path {
+            let path = crate::services::usage::UsageLog::path_for(path);
+            match crate::services::usage::UsageLog::load(&path) {
+                Ok(usage) if !usage.is_empty() => {
+                    view = view.with_usage(crate::services::analysis::usage_heatmap(
+                        &self.layout,
+                        Some(&self.geometry),
+                        &usage,
+                    ));
+                }
+                Ok(_) => {}
+                Err(e) => self.set_error(format!("{e:#}")),
+            }
+        }
+        self.active_component = Some(ActiveComponent::LayoutStats(view));
+        self.active_popup = Some(PopupType::LayoutStats);
+    }
+
+    /// Open the layer graph popup for `graph` of the open layout
+    pub fn open_layer_graph(&mut self, graph: crate::services::layer_graph::LayerGraph) {
+        let view = crate::tui::layer_graph::LayerGraphView::new(graph);
+        self.active_component = Some(ActiveComponent::LayerGraph(view));
+        self.active_popup = Some(PopupType::LayerGraph);
+    }
+
+    /// Open the key description search component
+    pub fn open_key_search(&mut self) {
+        let search = crate::tui::key_search::KeySearch::new();
+        self.active_component = Some(ActiveComponent::KeySearch(search));
+        self.active_popup = Some(PopupType::KeySearch);
+    }
+
     /// Close the currently active component
     pub fn close_component(&mut self) {
         self.active_component = None;
         self.active_popup = None;
     }
 }
+
+/// File name of `path` for status messages.
+fn display_name(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
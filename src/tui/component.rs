@@ -53,6 +53,8 @@ pub enum ColorPickerContext {
     IndividualKey,
     /// Setting layer default color
     LayerDefault,
+    /// Setting layer underglow color (LEDs without a key)
+    LayerUnderglow,
     /// Setting category color
     Category,
     /// Coloring multiple selected keys
@@ -10,9 +10,8 @@ use ratatui::{
 };
 use std::path::PathBuf;
 
-use crate::parser::keyboard_json::{
-    extract_layout_variants, parse_keyboard_info_json, LayoutVariant,
-};
+use crate::parser::keyboard_json::{extract_layout_variants, LayoutVariant};
+use crate::services::geometry_cache::keyboard_info_cached;
 
 /// Layout picker dialog state
 #[derive(Debug, Clone)]
@@ -40,9 +39,10 @@ impl LayoutPickerState {
     pub fn load_layouts(
         &mut self,
         qmk_path: &PathBuf,
+        keyboard_roots: &[PathBuf],
         keyboard: &str,
     ) -> Result<(), anyhow::Error> {
-        match parse_keyboard_info_json(qmk_path, keyboard) {
+        match keyboard_info_cached(qmk_path, keyboard_roots, keyboard) {
             Ok(info) => {
                 self.layouts = extract_layout_variants(&info);
                 self.selected_index = 0;
@@ -105,10 +105,10 @@ pub struct LayoutPicker {
 impl LayoutPicker {
     /// Create a new `LayoutPicker` with layouts pre-loaded
     #[must_use]
-    pub fn new(qmk_path: &PathBuf, keyboard: &str) -> Self {
+    pub fn new(qmk_path: &PathBuf, keyboard_roots: &[PathBuf], keyboard: &str) -> Self {
         let mut state = LayoutPickerState::new();
         // Attempt to load layouts on creation (ignore errors)
-        let _ = state.load_layouts(qmk_path, keyboard);
+        let _ = state.load_layouts(qmk_path, keyboard_roots, keyboard);
         Self {
             state,
             keyboard: keyboard.to_string(),
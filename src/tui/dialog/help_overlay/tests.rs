@@ -4,7 +4,6 @@
 
 use super::*;
 
-use super::*;
 use ratatui::backend::TestBackend;
 use ratatui::Terminal;
 
@@ -223,10 +223,40 @@ pub mod contexts {
     pub const TEMPLATE_SAVE: &str = "template_save";
     /// Setup wizard
     pub const SETUP_WIZARD: &str = "setup_wizard";
+    /// Folder browser for path settings
+    pub const FILE_BROWSER: &str = "file_browser";
     /// Unsaved changes prompt
     pub const UNSAVED_PROMPT: &str = "unsaved_prompt";
+    /// Geometry adjustment review before saving
+    pub const GEOMETRY_REVIEW: &str = "geometry_review";
     /// Tap dance editor popup
     pub const TAP_DANCE_EDITOR: &str = "tap_dance_editor";
+    /// Key description search popup
+    pub const KEY_SEARCH: &str = "key_search";
+    /// Copy keys to layer popup
+    pub const COPY_TO_LAYER: &str = "copy_to_layer";
+    /// Macro editor popup
+    pub const MACRO_EDITOR: &str = "macro_editor";
+    /// Unicode map editor popup
+    pub const UNICODE_MAP_EDITOR: &str = "unicode_map_editor";
+    /// Custom keycode editor popup
+    pub const CUSTOM_KEYCODE_EDITOR: &str = "custom_keycode_editor";
+    /// Combo editor popup
+    pub const COMBO_EDITOR: &str = "combo_editor";
+    /// Encoder editor popup
+    pub const ENCODER_EDITOR: &str = "encoder_editor";
+    /// Backup restore picker
+    pub const BACKUP_PICKER: &str = "backup_picker";
+    /// Placing a template made for another keyboard
+    pub const TEMPLATE_FIT: &str = "template_fit";
+    /// Entering values for template placeholders
+    pub const TEMPLATE_PLACEHOLDERS: &str = "template_placeholders";
+    /// Layout statistics popup
+    pub const LAYOUT_STATS: &str = "layout_stats";
+    /// Layer graph popup
+    pub const LAYER_GRAPH: &str = "layer_graph";
+    /// QMK tree picker popup
+    pub const QMK_TREE_PICKER: &str = "qmk_tree_picker";
     /// Clipboard operations (informational)
     pub const CLIPBOARD: &str = "clipboard";
     /// Parameterized keycodes (informational)
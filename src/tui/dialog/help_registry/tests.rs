@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_load_help_registry() {
     let registry = HelpRegistry::load().expect("Failed to load help registry");
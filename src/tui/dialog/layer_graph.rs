@@ -0,0 +1,195 @@
+//! Layer graph popup.
+//!
+//! Shows the layer keys of each layer from [`crate::services::layer_graph`],
+//! with unreachable layers and layers that can't be left listed first.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::services::layer_graph::LayerGraph;
+use crate::tui::theme::Theme;
+use crate::tui::Component;
+
+/// Lines scrolled by Page Up / Page Down.
+const PAGE_LINES: usize = 10;
+
+/// Events emitted by the layer graph popup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerGraphEvent {
+    /// User closed the popup
+    Closed,
+}
+
+/// Layer graph popup state
+#[derive(Debug, Clone)]
+pub struct LayerGraphView {
+    /// Graph being shown
+    graph: LayerGraph,
+    /// First report line shown
+    scroll: usize,
+}
+
+impl LayerGraphView {
+    /// Creates the popup for `graph`.
+    #[must_use]
+    pub const fn new(graph: LayerGraph) -> Self {
+        Self { graph, scroll: 0 }
+    }
+
+    /// Returns the report as plain text lines. Section headings are the
+    /// lines that don't start with a space.
+    #[must_use]
+    pub fn report(&self) -> Vec<String> {
+        let issues = self.graph.issues();
+        let mut lines = vec![format!("Issues ({})", issues.len())];
+        if issues.is_empty() {
+            lines.push("  none".to_string());
+        }
+        lines.extend(issues.into_iter().map(|issue| format!("  ! {issue}")));
+
+        for node in &self.graph.nodes {
+            lines.push(String::new());
+            let mut heading = format!("Layer {}: {}", node.layer, node.name);
+            if !node.reachable {
+                heading.push_str(" [unreachable]");
+            }
+            if !node.has_way_back {
+                heading.push_str(" [no way back]");
+            }
+            lines.push(heading);
+
+            let mut edges = self.graph.edges_from(node.layer).peekable();
+            if edges.peek().is_none() {
+                lines.push("  no layer keys".to_string());
+            }
+            for edge in edges {
+                let target = self
+                    .graph
+                    .nodes
+                    .get(edge.to_layer)
+                    .map_or("?", |target| target.name.as_str());
+                lines.push(format!(
+                    "  → Layer {} ({target})  {} at ({},{}), {}",
+                    edge.to_layer,
+                    edge.keycode,
+                    edge.position.row,
+                    edge.position.col,
+                    edge.kind.display_name()
+                ));
+            }
+        }
+        lines
+    }
+}
+
+impl Component for LayerGraphView {
+    type Event = LayerGraphEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        let last_line = self.report().len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Some(LayerGraphEvent::Closed),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = (self.scroll + 1).min(last_line),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(PAGE_LINES),
+            KeyCode::PageDown => self.scroll = (self.scroll + PAGE_LINES).min(last_line),
+            KeyCode::Home => self.scroll = 0,
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, _area: Rect, theme: &Theme) {
+        let area = centered_rect(70, 80, frame.area());
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(theme.background)),
+            area,
+        );
+
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        let lines: Vec<Line> = self
+            .report()
+            .into_iter()
+            .map(|line| {
+                if line.starts_with("  !") {
+                    Line::from(Span::styled(line, Style::default().fg(theme.warning)))
+                } else if line.is_empty() || line.starts_with(' ') {
+                    Line::from(line)
+                } else {
+                    Line::from(Span::styled(
+                        line,
+                        Style::default()
+                            .fg(theme.primary)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                }
+            })
+            .collect();
+        let report = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Layer Graph ")
+                    .style(Style::default().bg(theme.background)),
+            )
+            .style(Style::default().bg(theme.background).fg(theme.text))
+            .scroll((u16::try_from(self.scroll).unwrap_or(u16::MAX), 0));
+        frame.render_widget(report, chunks[0]);
+
+        let key_style = Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD);
+        let help = Line::from(vec![
+            Span::styled("↑/↓", key_style),
+            Span::raw(" Scroll  "),
+            Span::styled("Esc", key_style),
+            Span::raw(" Close"),
+        ]);
+        frame.render_widget(
+            Paragraph::new(help)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Help ")
+                        .style(Style::default().bg(theme.background)),
+                )
+                .style(Style::default().bg(theme.background).fg(theme.text)),
+            chunks[1],
+        );
+    }
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
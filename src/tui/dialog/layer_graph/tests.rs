@@ -0,0 +1,47 @@
+//! Tests for layer_graph.
+
+use super::*;
+
+use crossterm::event::KeyModifiers;
+
+use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+/// Base toggles Nav on, and Nav has no way back.
+fn create_view() -> LayerGraphView {
+    let layers: Vec<Layer> = [("Base", "TG(1)"), ("Nav", "KC_ESC")]
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (name, keycode))| {
+            let mut layer = Layer::new(idx as u8, name, RgbColor::new(0, 0, 0)).unwrap();
+            layer.add_key(KeyDefinition::new(Position::new(0, 0), keycode));
+            layer
+        })
+        .collect();
+    LayerGraphView::new(LayerGraph::build(&layers))
+}
+
+#[test]
+fn test_report_lists_issues_and_layer_keys() {
+    let report = create_view().report();
+
+    assert_eq!(report[0], "Issues (1)");
+    assert!(report[1].starts_with("  ! Layer 1 (Nav) has no way back"));
+    assert!(report.contains(&"Layer 0: Base".to_string()));
+    assert!(report.contains(&"  → Layer 1 (Nav)  TG(1) at (0,0), Toggle (TG)".to_string()));
+    assert!(report.contains(&"Layer 1: Nav [no way back]".to_string()));
+    assert!(report.contains(&"  no layer keys".to_string()));
+}
+
+#[test]
+fn test_esc_closes() {
+    let mut view = create_view();
+    assert_eq!(view.handle_input(key(KeyCode::Down)), None);
+    assert_eq!(
+        view.handle_input(key(KeyCode::Esc)),
+        Some(LayerGraphEvent::Closed)
+    );
+}
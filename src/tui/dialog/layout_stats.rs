@@ -0,0 +1,401 @@
+//! Layout statistics popup.
+//!
+//! Shows the per-layer statistics from [`crate::services::analysis`]. Pressing
+//! `c` asks for a corpus file (plain text, or JSON statistics ending in
+//! `.json`); once loaded, the popup adds the hand and home row share of the
+//! corpus and the keys pressed most while typing it. Key usage recorded by
+//! `lazyqmk collect-usage` is shown the same way.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::models::{Finger, Hand};
+use crate::services::analysis::{EffortScore, Heatmap, LayerStats, LayoutAnalysis};
+use crate::tui::theme::Theme;
+use crate::tui::Component;
+
+/// Number of keys listed under "Most pressed".
+const HOTTEST_KEYS: usize = 10;
+
+/// Lines scrolled by Page Up / Page Down.
+const PAGE_LINES: usize = 10;
+
+/// Events emitted by the layout statistics popup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutStatsEvent {
+    /// User entered the path of a corpus file to build the heatmap from
+    LoadCorpus(String),
+    /// User closed the popup
+    Closed,
+}
+
+/// Layout statistics popup state
+#[derive(Debug, Clone)]
+pub struct LayoutStatsView {
+    /// Statistics being shown
+    analysis: LayoutAnalysis,
+    /// Name of the corpus the heatmap was built from
+    corpus_name: Option<String>,
+    /// Heatmap of the key presses recorded on the keyboard
+    usage: Option<Heatmap>,
+    /// Corpus path being typed, while the prompt is open
+    corpus_input: Option<String>,
+    /// First report line shown
+    scroll: usize,
+}
+
+impl LayoutStatsView {
+    /// Creates the popup for `analysis`.
+    #[must_use]
+    pub const fn new(analysis: LayoutAnalysis) -> Self {
+        Self {
+            analysis,
+            corpus_name: None,
+            usage: None,
+            corpus_input: None,
+            scroll: 0,
+        }
+    }
+
+    /// Adds the heatmap of the key presses recorded on the keyboard.
+    #[must_use]
+    pub fn with_usage(mut self, usage: Heatmap) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
+    /// Replaces the statistics with ones that include a heatmap for the
+    /// corpus `corpus_name`.
+    pub fn set_analysis(&mut self, analysis: LayoutAnalysis, corpus_name: String) {
+        self.analysis = analysis;
+        self.corpus_name = Some(corpus_name);
+    }
+
+    /// Returns the corpus path being typed, if the prompt is open.
+    #[must_use]
+    pub fn corpus_input(&self) -> Option<&str> {
+        self.corpus_input.as_deref()
+    }
+
+    /// Returns the report as plain text lines. Section headings are the
+    /// lines that don't start with a space.
+    #[must_use]
+    pub fn report(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for layer in &self.analysis.layers {
+            layer_lines(layer, &mut lines);
+            lines.push(String::new());
+        }
+        match &self.analysis.heatmap {
+            Some(heatmap) => heatmap_lines(
+                heatmap,
+                &format!(
+                    "Heatmap: {}",
+                    self.corpus_name.as_deref().unwrap_or("corpus")
+                ),
+                "characters",
+                &self.analysis,
+                &mut lines,
+            ),
+            None => lines.push("Heatmap: press c to load a corpus file".to_string()),
+        }
+        if let Some(effort) = &self.analysis.effort {
+            lines.push(String::new());
+            effort_lines(effort, &mut lines);
+        }
+        if let Some(usage) = &self.usage {
+            lines.push(String::new());
+            heatmap_lines(
+                usage,
+                "Recorded usage (collect-usage)",
+                "presses",
+                &self.analysis,
+                &mut lines,
+            );
+        }
+        lines
+    }
+
+    fn handle_prompt_input(&mut self, key: KeyEvent) -> Option<LayoutStatsEvent> {
+        let input = self.corpus_input.as_mut()?;
+        match key.code {
+            KeyCode::Esc => self.corpus_input = None,
+            KeyCode::Enter => {
+                let path = input.trim().to_string();
+                self.corpus_input = None;
+                if !path.is_empty() {
+                    return Some(LayoutStatsEvent::LoadCorpus(path));
+                }
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        None
+    }
+}
+
+impl Component for LayoutStatsView {
+    type Event = LayoutStatsEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        if self.corpus_input.is_some() {
+            return self.handle_prompt_input(key);
+        }
+
+        let last_line = self.report().len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Some(LayoutStatsEvent::Closed),
+            KeyCode::Char('c') => self.corpus_input = Some(String::new()),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = (self.scroll + 1).min(last_line),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(PAGE_LINES),
+            KeyCode::PageDown => self.scroll = (self.scroll + PAGE_LINES).min(last_line),
+            KeyCode::Home => self.scroll = 0,
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, _area: Rect, theme: &Theme) {
+        let area = centered_rect(70, 80, frame.area());
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(theme.background)),
+            area,
+        );
+
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        let lines: Vec<Line> = self
+            .report()
+            .into_iter()
+            .map(|line| {
+                if line.is_empty() || line.starts_with(' ') {
+                    Line::from(line)
+                } else {
+                    Line::from(Span::styled(
+                        line,
+                        Style::default()
+                            .fg(theme.primary)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                }
+            })
+            .collect();
+        let report = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Layout Stats ")
+                    .style(Style::default().bg(theme.background)),
+            )
+            .style(Style::default().bg(theme.background).fg(theme.text))
+            .scroll((u16::try_from(self.scroll).unwrap_or(u16::MAX), 0));
+        frame.render_widget(report, chunks[0]);
+
+        let key_style = Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD);
+        let (title, help) = match self.corpus_input() {
+            Some(input) => (
+                " Corpus File ",
+                Line::from(vec![
+                    Span::raw(input.to_string()),
+                    Span::styled("█", Style::default().fg(theme.primary)),
+                    Span::raw("  "),
+                    Span::styled("Enter", key_style),
+                    Span::raw(" Load  "),
+                    Span::styled("Esc", key_style),
+                    Span::raw(" Cancel"),
+                ]),
+            ),
+            None => (
+                " Help ",
+                Line::from(vec![
+                    Span::styled("↑/↓", key_style),
+                    Span::raw(" Scroll  "),
+                    Span::styled("c", key_style),
+                    Span::raw(" Load corpus  "),
+                    Span::styled("Esc", key_style),
+                    Span::raw(" Close"),
+                ]),
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(help)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(title)
+                        .style(Style::default().bg(theme.background)),
+                )
+                .style(Style::default().bg(theme.background).fg(theme.text)),
+            chunks[1],
+        );
+    }
+}
+
+/// Appends the report section of one layer.
+fn layer_lines(layer: &LayerStats, lines: &mut Vec<String>) {
+    lines.push(format!("Layer {}: {}", layer.layer, layer.name));
+    lines.push(format!(
+        "  {} keys: {} assigned, {} transparent, {} empty",
+        layer.keys, layer.assigned, layer.transparent, layer.unassigned
+    ));
+    if let Some(home_row) = layer.home_row {
+        lines.push(format!(
+            "  Home row: {} of {} assigned, {} mod-taps",
+            home_row.assigned, home_row.keys, home_row.mod_taps
+        ));
+    }
+
+    let modifiers = layer.modifiers;
+    let unplaced = if modifiers.unplaced > 0 {
+        format!(", {} without a hand", modifiers.unplaced)
+    } else {
+        String::new()
+    };
+    lines.push(format!(
+        "  Modifiers: {} left, {} right{unplaced}",
+        modifiers.left, modifiers.right
+    ));
+
+    if layer.duplicates.is_empty() {
+        lines.push("  Duplicates: none".to_string());
+    } else {
+        lines.push("  Duplicates:".to_string());
+        for duplicate in &layer.duplicates {
+            let positions: Vec<String> = duplicate
+                .positions
+                .iter()
+                .map(|p| format!("({},{})", p.row, p.col))
+                .collect();
+            lines.push(format!(
+                "    {} ×{} {}",
+                duplicate.keycode,
+                duplicate.positions.len(),
+                positions.join(" ")
+            ));
+        }
+    }
+}
+
+/// Appends the heatmap section.
+fn heatmap_lines(
+    heatmap: &Heatmap,
+    title: &str,
+    unit: &str,
+    analysis: &LayoutAnalysis,
+    lines: &mut Vec<String>,
+) {
+    lines.push(title.to_string());
+    lines.push(format!(
+        "  {} {unit}, {} not on any key",
+        heatmap.total, heatmap.untyped
+    ));
+    let typed = heatmap.total - heatmap.untyped;
+    lines.push(format!(
+        "  Left hand {}, right hand {}, home row {}",
+        percent(heatmap.left, typed),
+        percent(heatmap.right, typed),
+        percent(heatmap.home_row, typed)
+    ));
+    lines.push("  Most pressed:".to_string());
+    for key in heatmap.keys.iter().take(HOTTEST_KEYS) {
+        let layer_name = analysis
+            .layers
+            .get(key.layer)
+            .map_or("?", |layer| layer.name.as_str());
+        lines.push(format!(
+            "    {layer_name} ({},{}) {} - {} ({})",
+            key.position.row,
+            key.position.col,
+            key.keycode,
+            key.presses,
+            percent(key.presses, typed)
+        ));
+    }
+}
+
+/// Appends the base layer effort section.
+fn effort_lines(effort: &EffortScore, lines: &mut Vec<String>) {
+    lines.push("Effort (base layer, lower is better)".to_string());
+    lines.push(format!(
+        "  {:.2} per character, {:.2} key widths of travel per character",
+        effort.effort_per_char(),
+        effort.distance_per_char()
+    ));
+    lines.push(format!(
+        "  Home keys {}, same-finger bigrams {:.1}%, hand alternation {}",
+        percent(effort.home_keys, effort.typed),
+        if effort.bigrams == 0 {
+            0.0
+        } else {
+            effort.same_finger_bigrams as f64 * 100.0 / effort.bigrams as f64
+        },
+        percent(effort.hand_alternations, effort.bigrams)
+    ));
+    let fingers: Vec<String> = effort
+        .fingers
+        .iter()
+        .map(|load| {
+            let hand = match load.hand {
+                Hand::Left => "L",
+                Hand::Right => "R",
+            };
+            let finger = match load.finger {
+                Finger::Thumb => "thumb",
+                Finger::Index => "index",
+                Finger::Middle => "middle",
+                Finger::Ring => "ring",
+                Finger::Pinky => "pinky",
+            };
+            format!("{hand} {finger} {}", percent(load.presses, effort.typed))
+        })
+        .collect();
+    lines.push(format!("  Fingers: {}", fingers.join(", ")));
+}
+
+/// Formats `part` as a whole percentage of `total`.
+fn percent(part: u64, total: u64) -> String {
+    if total == 0 {
+        return "0%".to_string();
+    }
+    format!("{:.0}%", part as f64 * 100.0 / total as f64)
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,116 @@
+//! Tests for layout_stats.
+
+use super::*;
+
+use crossterm::event::KeyModifiers;
+
+use crate::models::{KeyDefinition, Layer, Layout, Position, RgbColor};
+use crate::services::analysis::{analyze_layout, usage_heatmap};
+use crate::services::thumb_optimizer::CorpusStats;
+use crate::services::usage::UsageLog;
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    for (col, keycode) in ["KC_A", "KC_B", "KC_A", "KC_NO"].iter().enumerate() {
+        layer.add_key(KeyDefinition::new(Position::new(0, col as u8), *keycode));
+    }
+    layout.add_layer(layer).unwrap();
+    layout
+}
+
+fn create_view() -> LayoutStatsView {
+    let analysis = analyze_layout(&create_test_layout(), None, None);
+    LayoutStatsView::new(analysis)
+}
+
+#[test]
+fn test_report_lists_layer_stats() {
+    let report = create_view().report();
+
+    assert_eq!(report[0], "Layer 0: Base");
+    assert!(report.contains(&"  4 keys: 3 assigned, 0 transparent, 1 empty".to_string()));
+    assert!(report.contains(&"    KC_A ×2 (0,0) (0,2)".to_string()));
+    assert_eq!(
+        report.last().unwrap(),
+        "Heatmap: press c to load a corpus file"
+    );
+}
+
+#[test]
+fn test_report_includes_heatmap_after_loading_corpus() {
+    let mut view = create_view();
+    let corpus = CorpusStats::from_text("aab!");
+    let analysis = analyze_layout(&create_test_layout(), None, Some(&corpus));
+
+    view.set_analysis(analysis, "notes.txt".to_string());
+    let report = view.report();
+
+    assert!(report.contains(&"Heatmap: notes.txt".to_string()));
+    assert!(report.contains(&"  4 characters, 1 not on any key".to_string()));
+    assert!(report.contains(&"    Base (0,0) KC_A - 2 (67%)".to_string()));
+}
+
+#[test]
+fn test_report_includes_recorded_usage() {
+    let mut usage = UsageLog::default();
+    usage.record(0, Position::new(0, 1), 3);
+    let heatmap = usage_heatmap(&create_test_layout(), None, &usage);
+
+    let report = create_view().with_usage(heatmap).report();
+
+    assert!(report.contains(&"Recorded usage (collect-usage)".to_string()));
+    assert!(report.contains(&"  3 presses, 0 not on any key".to_string()));
+    assert!(report.contains(&"    Base (0,1) KC_B - 3 (100%)".to_string()));
+}
+
+#[test]
+fn test_corpus_prompt_emits_path() {
+    let mut view = create_view();
+
+    assert_eq!(view.handle_input(key(KeyCode::Char('c'))), None);
+    for c in "a.txt".chars() {
+        view.handle_input(key(KeyCode::Char(c)));
+    }
+    assert_eq!(view.corpus_input(), Some("a.txt"));
+
+    let event = view.handle_input(key(KeyCode::Enter));
+    assert_eq!(
+        event,
+        Some(LayoutStatsEvent::LoadCorpus("a.txt".to_string()))
+    );
+    assert_eq!(view.corpus_input(), None);
+}
+
+#[test]
+fn test_escape_cancels_prompt_before_closing() {
+    let mut view = create_view();
+    view.handle_input(key(KeyCode::Char('c')));
+    // 'q' is typed into the prompt rather than closing the popup
+    assert_eq!(view.handle_input(key(KeyCode::Char('q'))), None);
+
+    assert_eq!(view.handle_input(key(KeyCode::Esc)), None);
+    assert_eq!(view.corpus_input(), None);
+    assert_eq!(
+        view.handle_input(key(KeyCode::Esc)),
+        Some(LayoutStatsEvent::Closed)
+    );
+}
+
+#[test]
+fn test_scroll_stops_at_last_line() {
+    let mut view = create_view();
+    let last_line = view.report().len() - 1;
+
+    view.handle_input(key(KeyCode::PageDown));
+    view.handle_input(key(KeyCode::PageDown));
+    assert_eq!(view.scroll, last_line);
+
+    view.handle_input(key(KeyCode::Home));
+    view.handle_input(key(KeyCode::Up));
+    assert_eq!(view.scroll, 0);
+}
@@ -3,6 +3,8 @@
 pub mod config_dialogs;
 pub mod help_overlay;
 pub mod help_registry;
+pub mod layer_graph;
+pub mod layout_stats;
 pub mod onboarding_wizard;
 pub mod onboarding_wizard_render;
 pub mod status_bar;
@@ -1,8 +1,8 @@
 //! Onboarding wizard for first-time setup and configuration.
 //!
 //! This module implements a step-by-step wizard to guide users through
-//! initial configuration: QMK path, keyboard selection, layout variant,
-//! output paths, and layout file settings.
+//! initial configuration: QMK path, a check of the QMK environment,
+//! keyboard selection, layout variant, output paths, and layout file settings.
 
 // Allow small types passed by reference for API consistency
 #![allow(clippy::trivially_copy_pass_by_ref)]
@@ -10,13 +10,18 @@
 use anyhow::{Context, Result};
 use crossterm::event::{KeyCode, KeyEvent};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 use crate::config::Config;
 use crate::parser::keyboard_json::{
     extract_layout_names, parse_keyboard_info_json, scan_keyboards,
 };
+use crate::services::device_detect;
+use crate::services::qmk_env::{self, QmkEnvReport, SetupStep};
+use crate::tui::file_browser::{FileBrowser, FileBrowserEvent};
 use crate::tui::layout_picker::LayoutPickerState;
+use crate::tui::Component;
 
 /// Onboarding wizard steps
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -25,6 +30,8 @@ pub enum WizardStep {
     Welcome,
     /// Enter QMK firmware path
     QmkPath,
+    /// Check the QMK checkout and build tools
+    Environment,
     /// Select keyboard from scanned list
     KeyboardSelection,
     /// Select layout variant
@@ -44,7 +51,8 @@ impl WizardStep {
     pub const fn next(&self) -> Option<Self> {
         match self {
             Self::Welcome => Some(Self::QmkPath),
-            Self::QmkPath => Some(Self::KeyboardSelection),
+            Self::QmkPath => Some(Self::Environment),
+            Self::Environment => Some(Self::KeyboardSelection),
             Self::KeyboardSelection => Some(Self::LayoutSelection),
             Self::LayoutSelection => Some(Self::LayoutName),
             Self::LayoutName => Some(Self::OutputPath),
@@ -59,7 +67,8 @@ impl WizardStep {
         match self {
             Self::Welcome => None,
             Self::QmkPath => Some(Self::Welcome),
-            Self::KeyboardSelection => Some(Self::QmkPath),
+            Self::Environment => Some(Self::QmkPath),
+            Self::KeyboardSelection => Some(Self::Environment),
             Self::LayoutSelection => Some(Self::KeyboardSelection),
             Self::LayoutName => Some(Self::LayoutSelection),
             Self::OutputPath => Some(Self::LayoutName),
@@ -73,6 +82,7 @@ impl WizardStep {
         match self {
             Self::Welcome => "Welcome to LazyQMK",
             Self::QmkPath => "Connect QMK Firmware",
+            Self::Environment => "Check QMK Setup",
             Self::KeyboardSelection => "Choose Keyboard",
             Self::LayoutSelection => "Choose Layout Variant",
             Self::LayoutName => "Name Layout File",
@@ -87,21 +97,31 @@ impl WizardStep {
         match self {
             Self::Welcome => 1,
             Self::QmkPath => 2,
-            Self::KeyboardSelection => 3,
-            Self::LayoutSelection => 4,
-            Self::LayoutName => 5,
-            Self::OutputPath => 6,
-            Self::Confirmation => 7,
+            Self::Environment => 3,
+            Self::KeyboardSelection => 4,
+            Self::LayoutSelection => 5,
+            Self::LayoutName => 6,
+            Self::OutputPath => 7,
+            Self::Confirmation => 8,
         }
     }
 
     /// Gets the total number of steps
     #[must_use]
     pub const fn total_steps() -> usize {
-        7
+        8
     }
 }
 
+/// A setup step running on a background thread
+#[derive(Debug, Clone)]
+pub struct SetupTask {
+    /// Step being run
+    pub step: SetupStep,
+    /// Outcome, filled in when the thread finishes
+    result: Arc<Mutex<Option<Result<(), String>>>>,
+}
+
 /// Focus state for keyboard selection step
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum KeyboardSelectionFocus {
@@ -131,8 +151,12 @@ pub struct OnboardingWizardState {
     pub inputs: HashMap<String, String>,
     /// Current text input buffer
     pub input_buffer: String,
+    /// Directory browser for the QMK path and output path steps
+    pub path_browser: Option<FileBrowser>,
     /// List of available keyboards (populated after QMK path is set)
     pub available_keyboards: Vec<String>,
+    /// Keyboards matching a connected USB device, listed first
+    pub detected_keyboards: Vec<String>,
     /// Filter text for keyboard search
     pub keyboard_filter: String,
     /// Selected keyboard index in list
@@ -155,6 +179,12 @@ pub struct OnboardingWizardState {
     pub welcome_choice: Option<WelcomeChoice>,
     /// Selected welcome option index
     pub welcome_selected_index: usize,
+    /// Extra keyboard directories searched alongside QMK's `keyboards/`
+    pub keyboard_roots: Vec<PathBuf>,
+    /// Health check of the chosen QMK checkout and build tools
+    pub env_report: Option<QmkEnvReport>,
+    /// Setup step fixing the environment, while it runs
+    pub setup_task: Option<SetupTask>,
 }
 
 impl OnboardingWizardState {
@@ -176,7 +206,9 @@ impl OnboardingWizardState {
             current_step: WizardStep::Welcome,
             inputs: HashMap::new(),
             input_buffer: String::new(),
+            path_browser: None,
             available_keyboards: Vec::new(),
+            detected_keyboards: Vec::new(),
             keyboard_filter: String::new(),
             keyboard_selected_index: 0,
             keyboard_selection_focus: KeyboardSelectionFocus::FilterInput,
@@ -188,6 +220,9 @@ impl OnboardingWizardState {
             existing_layouts,
             welcome_choice: None,
             welcome_selected_index: 0,
+            keyboard_roots: Vec::new(),
+            env_report: None,
+            setup_task: None,
         }
     }
 
@@ -198,12 +233,16 @@ impl OnboardingWizardState {
     ///
     /// # Arguments
     /// * `qmk_path` - The already-configured QMK firmware path
+    /// * `keyboard_roots` - Extra keyboard directories from the config
     ///
     /// # Returns
     /// * `Ok(Self)` - Wizard state ready for keyboard selection
     /// * `Err` - If keyboard scanning fails
-    pub fn new_for_keyboard_selection(qmk_path: &std::path::Path) -> Result<Self> {
-        let keyboards = scan_keyboards(qmk_path)?;
+    pub fn new_for_keyboard_selection(
+        qmk_path: &std::path::Path,
+        keyboard_roots: &[PathBuf],
+    ) -> Result<Self> {
+        let keyboards = scan_keyboards(qmk_path, keyboard_roots)?;
 
         let mut inputs = HashMap::new();
         inputs.insert(
@@ -211,11 +250,13 @@ impl OnboardingWizardState {
             qmk_path.to_string_lossy().to_string(),
         );
 
-        Ok(Self {
+        let mut state = Self {
             current_step: WizardStep::KeyboardSelection,
             inputs,
             input_buffer: String::new(),
-            available_keyboards: keyboards,
+            path_browser: None,
+            available_keyboards: Vec::new(),
+            detected_keyboards: Vec::new(),
             keyboard_filter: String::new(),
             keyboard_selected_index: 0,
             keyboard_selection_focus: KeyboardSelectionFocus::FilterInput,
@@ -227,7 +268,12 @@ impl OnboardingWizardState {
             existing_layouts: Vec::new(), // Not used in keyboard selection mode
             welcome_choice: None,         // Not used in keyboard selection mode
             welcome_selected_index: 0,
-        })
+            keyboard_roots: keyboard_roots.to_vec(),
+            env_report: None,
+            setup_task: None,
+        };
+        state.set_keyboards(qmk_path, keyboards);
+        Ok(state)
     }
 
     /// Creates a wizard state for creating a new layout.
@@ -249,7 +295,7 @@ impl OnboardingWizardState {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("QMK firmware path not configured"))?;
 
-        let keyboards = scan_keyboards(qmk_path)?;
+        let keyboards = scan_keyboards(qmk_path, &config.paths.keyboard_roots)?;
 
         let mut inputs = HashMap::new();
         inputs.insert(
@@ -262,11 +308,13 @@ impl OnboardingWizardState {
             config.build.output_dir.display().to_string(),
         );
 
-        Ok(Self {
+        let mut state = Self {
             current_step: WizardStep::KeyboardSelection,
             inputs,
             input_buffer: String::new(),
-            available_keyboards: keyboards,
+            path_browser: None,
+            available_keyboards: Vec::new(),
+            detected_keyboards: Vec::new(),
             keyboard_filter: String::new(),
             keyboard_selected_index: 0,
             keyboard_selection_focus: KeyboardSelectionFocus::FilterInput,
@@ -278,7 +326,22 @@ impl OnboardingWizardState {
             existing_layouts: Vec::new(), // Not used in new layout mode
             welcome_choice: None,         // Not used in new layout mode
             welcome_selected_index: 0,
-        })
+            keyboard_roots: config.paths.keyboard_roots.clone(),
+            env_report: None,
+            setup_task: None,
+        };
+        state.set_keyboards(qmk_path, keyboards);
+        Ok(state)
+    }
+
+    /// Sets the keyboards to choose from, moving connected ones to the top.
+    fn set_keyboards(&mut self, qmk_path: &Path, mut keyboards: Vec<String>) {
+        self.detected_keyboards =
+            device_detect::detect_connected(qmk_path, &self.keyboard_roots, &keyboards);
+        keyboards.retain(|keyboard| !self.detected_keyboards.contains(keyboard));
+        self.available_keyboards = self.detected_keyboards.clone();
+        self.available_keyboards.extend(keyboards);
+        self.keyboard_selected_index = 0;
     }
 
     /// Gets the filtered list of keyboards based on current filter
@@ -297,8 +360,18 @@ impl OnboardingWizardState {
 
     /// Advances to the next step
     #[allow(dead_code)] // bin/lib split: wizard flow helper
-    #[allow(clippy::too_many_lines)]
     pub fn next_step(&mut self) -> Result<()> {
+        let step = self.current_step;
+        let result = self.advance();
+        if self.current_step != step {
+            self.sync_path_browser();
+        }
+        result
+    }
+
+    /// Validates the current step and moves past it
+    #[allow(clippy::too_many_lines)]
+    fn advance(&mut self) -> Result<()> {
         self.error_message = None;
 
         match self.current_step {
@@ -335,12 +408,20 @@ impl OnboardingWizardState {
                 self.inputs
                     .insert("qmk_path".to_string(), self.input_buffer.clone());
                 self.input_buffer.clear();
+                self.recheck_environment();
+                self.current_step = WizardStep::Environment;
+            }
+            WizardStep::Environment => {
+                if self.setup_task.is_some() {
+                    self.error_message = Some("Wait for the setup step to finish".to_string());
+                    return Ok(());
+                }
 
                 // Scan keyboards
-                match scan_keyboards(&qmk_path) {
+                let qmk_path = PathBuf::from(self.inputs.get("qmk_path").unwrap());
+                match scan_keyboards(&qmk_path, &self.keyboard_roots) {
                     Ok(keyboards) => {
-                        self.available_keyboards = keyboards;
-                        self.keyboard_selected_index = 0;
+                        self.set_keyboards(&qmk_path, keyboards);
                         self.current_step = WizardStep::KeyboardSelection;
                     }
                     Err(e) => {
@@ -364,7 +445,7 @@ impl OnboardingWizardState {
 
                 // Parse keyboard info.json to get layouts
                 let qmk_path = PathBuf::from(self.inputs.get("qmk_path").unwrap());
-                match parse_keyboard_info_json(&qmk_path, &keyboard) {
+                match parse_keyboard_info_json(&qmk_path, &self.keyboard_roots, &keyboard) {
                     Ok(info) => {
                         self.available_layouts = extract_layout_names(&info);
                         self.layout_selected_index = 0;
@@ -468,6 +549,11 @@ impl OnboardingWizardState {
                 }
             }
 
+            // Check the environment if the wizard started past that step
+            if self.current_step == WizardStep::Environment && self.env_report.is_none() {
+                self.recheck_environment();
+            }
+
             // Clear keyboard filter when returning to keyboard selection
             if self.current_step == WizardStep::KeyboardSelection {
                 self.keyboard_filter.clear();
@@ -487,7 +573,93 @@ impl OnboardingWizardState {
                     self.input_buffer = output_path.clone();
                 }
             }
+
+            self.sync_path_browser();
+        }
+    }
+
+    /// Opens the directory browser at the input buffer on path steps and
+    /// drops it on every other step
+    fn sync_path_browser(&mut self) {
+        self.path_browser = matches!(
+            self.current_step,
+            WizardStep::QmkPath | WizardStep::OutputPath
+        )
+        .then(|| FileBrowser::new(&self.input_buffer));
+    }
+
+    /// Forwards a key to the directory browser of a path step.
+    ///
+    /// Picking a directory fills the input buffer and advances; backing out
+    /// of the browser goes to the previous step.
+    fn handle_path_browser_input(&mut self, key: KeyEvent) -> Result<()> {
+        let browser = self
+            .path_browser
+            .get_or_insert_with(|| FileBrowser::new(&self.input_buffer));
+        match browser.handle_input(key) {
+            Some(FileBrowserEvent::Selected(path)) => {
+                self.input_buffer = path.display().to_string();
+                self.next_step()?;
+            }
+            Some(FileBrowserEvent::Cancelled) => self.previous_step(),
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Re-runs the environment checks for the chosen QMK path
+    fn recheck_environment(&mut self) {
+        let qmk_path = self.inputs.get("qmk_path").map(PathBuf::from);
+        self.env_report = Some(qmk_env::check(qmk_path.as_deref()));
+    }
+
+    /// Starts the first setup step the environment report suggests on a
+    /// background thread
+    fn start_setup(&mut self) {
+        if self.setup_task.is_some() {
+            return;
+        }
+        let step = self
+            .env_report
+            .as_ref()
+            .and_then(|report| report.setup_steps().first().copied());
+        let (Some(step), Some(qmk_path)) = (step, self.inputs.get("qmk_path")) else {
+            self.error_message = Some("Nothing to set up automatically".to_string());
+            return;
+        };
+
+        self.error_message = None;
+        let qmk_path = PathBuf::from(qmk_path);
+        let result = Arc::new(Mutex::new(None));
+        let task_result = Arc::clone(&result);
+        std::thread::spawn(move || {
+            let outcome = qmk_env::run_setup_step(step, &qmk_path).map_err(|e| format!("{e:#}"));
+            *task_result.lock().expect("setup result lock poisoned") = Some(outcome);
+        });
+        self.setup_task = Some(SetupTask { step, result });
+    }
+
+    /// Picks up a finished setup step and checks the environment again.
+    ///
+    /// Called once per frame by the loops showing the wizard.
+    pub fn poll_setup(&mut self) {
+        let Some(task) = &self.setup_task else {
+            return;
+        };
+        let Some(outcome) = task
+            .result
+            .lock()
+            .expect("setup result lock poisoned")
+            .take()
+        else {
+            return;
+        };
+        let step = task.step;
+        self.setup_task = None;
+        if let Err(e) = outcome {
+            self.error_message = Some(format!("{} failed: {e}", step.description()));
         }
+        self.recheck_environment();
     }
 
     /// Builds a Config from the collected inputs
@@ -526,6 +698,9 @@ impl OnboardingWizardState {
                 .insert("qmk_path".to_string(), qmk_path.display().to_string());
             wizard.input_buffer = qmk_path.display().to_string();
         }
+        wizard
+            .keyboard_roots
+            .clone_from(&config.paths.keyboard_roots);
 
         // Note: keyboard and layout are now per-layout in metadata, not in config
         // The wizard is for initial setup only
@@ -597,9 +772,7 @@ pub fn handle_input(state: &mut OnboardingWizardState, key: KeyEvent) -> Result<
     match state.current_step {
         WizardStep::Welcome => match key.code {
             KeyCode::Up | KeyCode::Char('k') => {
-                if state.welcome_selected_index > 0 {
-                    state.welcome_selected_index -= 1;
-                }
+                state.welcome_selected_index = state.welcome_selected_index.saturating_sub(1);
             }
             KeyCode::Down | KeyCode::Char('j') => {
                 let max_index = state.get_welcome_options_count().saturating_sub(1);
@@ -626,19 +799,21 @@ pub fn handle_input(state: &mut OnboardingWizardState, key: KeyEvent) -> Result<
             KeyCode::Esc => return Ok(true), // Exit
             _ => {}
         },
-        WizardStep::QmkPath => match key.code {
-            KeyCode::Enter => {
+        WizardStep::QmkPath | WizardStep::OutputPath => state.handle_path_browser_input(key)?,
+        // Keys other than Enter wait while a setup step runs
+        WizardStep::Environment if state.setup_task.is_some() => {
+            if key.code == KeyCode::Enter {
                 state.next_step()?;
             }
-            KeyCode::Backspace => {
-                state.input_buffer.pop();
-            }
-            KeyCode::Char(c) => {
-                state.input_buffer.push(c);
-            }
-            KeyCode::Esc => {
-                state.previous_step();
-            }
+        }
+        WizardStep::Environment => match key.code {
+            KeyCode::Enter => state.next_step()?,
+            KeyCode::Char('s') => state.start_setup(),
+            KeyCode::Char('r') => {
+                state.error_message = None;
+                state.recheck_environment();
+            }
+            KeyCode::Esc => state.previous_step(),
             _ => {}
         },
         WizardStep::KeyboardSelection => match state.keyboard_selection_focus {
@@ -707,9 +882,7 @@ pub fn handle_input(state: &mut OnboardingWizardState, key: KeyEvent) -> Result<
                     state.keyboard_selection_focus = KeyboardSelectionFocus::FilterInput;
                 }
                 KeyCode::Up | KeyCode::Char('k') => {
-                    if state.keyboard_selected_index > 0 {
-                        state.keyboard_selected_index -= 1;
-                    }
+                    state.keyboard_selected_index = state.keyboard_selected_index.saturating_sub(1);
                 }
                 KeyCode::Down | KeyCode::Char('j') => {
                     let filtered_count = state.get_filtered_keyboards().len();
@@ -729,14 +902,11 @@ pub fn handle_input(state: &mut OnboardingWizardState, key: KeyEvent) -> Result<
         },
         WizardStep::LayoutSelection => match key.code {
             KeyCode::Up | KeyCode::Char('k') => {
-                if state.layout_selected_index > 0 {
-                    state.layout_selected_index -= 1;
-                }
+                state.layout_selected_index = state.layout_selected_index.saturating_sub(1);
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                if state.layout_selected_index < state.available_layouts.len().saturating_sub(1) {
-                    state.layout_selected_index += 1;
-                }
+                state.layout_selected_index = (state.layout_selected_index + 1)
+                    .min(state.available_layouts.len().saturating_sub(1));
             }
             KeyCode::Enter => {
                 state.next_step()?;
@@ -769,21 +939,6 @@ pub fn handle_input(state: &mut OnboardingWizardState, key: KeyEvent) -> Result<
             }
             _ => {}
         },
-        WizardStep::OutputPath => match key.code {
-            KeyCode::Enter => {
-                state.next_step()?;
-            }
-            KeyCode::Backspace => {
-                state.input_buffer.pop();
-            }
-            KeyCode::Char(c) => {
-                state.input_buffer.push(c);
-            }
-            KeyCode::Esc => {
-                state.previous_step();
-            }
-            _ => {}
-        },
         WizardStep::Confirmation => match key.code {
             KeyCode::Enter => {
                 state.next_step()?;
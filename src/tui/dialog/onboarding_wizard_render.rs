@@ -1,6 +1,8 @@
 //! Rendering code for the onboarding wizard.
 //! Extracted from `onboarding_wizard` to keep that file under 1000 lines.
 
+use std::borrow::Cow;
+
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -13,7 +15,9 @@ use super::help_registry::HelpRegistry;
 use super::onboarding_wizard::{
     KeyboardSelectionFocus, OnboardingWizardState, WelcomeChoice, WizardStep,
 };
-use crate::tui::Theme;
+use crate::services::qmk_env::CheckLevel;
+use crate::tui::file_browser::FileBrowser;
+use crate::tui::{Component, Theme};
 
 /// Renders the onboarding wizard
 pub fn render(f: &mut Frame, state: &OnboardingWizardState, theme: &Theme) {
@@ -57,6 +61,7 @@ pub fn render(f: &mut Frame, state: &OnboardingWizardState, theme: &Theme) {
     match state.current_step {
         WizardStep::Welcome => render_welcome(f, state, vertical_chunks[1], theme),
         WizardStep::QmkPath => render_qmk_path_input(f, state, vertical_chunks[1], theme),
+        WizardStep::Environment => render_environment(f, state, vertical_chunks[1], theme),
         WizardStep::KeyboardSelection => {
             render_keyboard_selection(f, state, vertical_chunks[1], theme);
         }
@@ -142,37 +147,90 @@ fn render_welcome(f: &mut Frame, state: &OnboardingWizardState, area: Rect, them
     f.render_stateful_widget(list, list_area[1], &mut list_state);
 }
 
-/// Render QMK path input screen
+/// Render QMK path selection screen
 fn render_qmk_path_input(f: &mut Frame, state: &OnboardingWizardState, area: Rect, theme: &Theme) {
     let app_name = HelpRegistry::default().app_name().to_string();
+    let browser = path_browser(state);
+    let dir = browser.current_dir();
+    let status = if dir.join("Makefile").exists() && dir.join("keyboards").is_dir() {
+        Span::styled(
+            "✓ This folder is a QMK firmware checkout",
+            Style::default().fg(theme.success),
+        )
+    } else {
+        Span::styled(
+            "Tip: usually ~/qmk_firmware or /opt/homebrew/share/qmk_firmware",
+            Style::default().fg(theme.text_muted),
+        )
+    };
     let text = vec![
-        Line::from(vec![Span::styled(
+        Line::from(Span::styled(
             format!("{app_name} needs to know where your QMK firmware is located."),
             Style::default().fg(theme.text),
-        )]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("QMK Path: ", Style::default().fg(theme.primary)),
-            Span::styled(&state.input_buffer, Style::default().fg(theme.text)),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Tip: ", Style::default().fg(theme.text_muted)),
-            Span::raw(
-                "You can usually find this at ~/qmk_firmware or /opt/homebrew/share/qmk_firmware",
-            ),
-        ]),
+        )),
+        Line::from(status),
     ];
 
-    let paragraph = Paragraph::new(text)
+    render_path_browser(f, &browser, text, " QMK Path ", area, theme);
+}
+
+/// Render the QMK environment checks with their fixes
+fn render_environment(f: &mut Frame, state: &OnboardingWizardState, area: Rect, theme: &Theme) {
+    let mut lines = Vec::new();
+    for check in state.env_report.iter().flat_map(|report| &report.checks) {
+        let (icon, color) = match check.level {
+            CheckLevel::Ok => ("✓", theme.success),
+            CheckLevel::Warning => ("!", theme.warning),
+            CheckLevel::Error => ("✗", theme.error),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{icon} "), Style::default().fg(color)),
+            Span::styled(
+                format!("{}: ", check.name),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(&check.message, Style::default().fg(theme.text)),
+        ]));
+        if let Some(fix) = &check.fix {
+            lines.push(Line::from(Span::styled(
+                format!("    → {fix}"),
+                Style::default().fg(theme.text_muted),
+            )));
+        }
+    }
+
+    lines.push(Line::from(""));
+    let summary = match (&state.setup_task, &state.env_report) {
+        (Some(task), _) => Span::styled(
+            format!("{}... this can take a few minutes", task.step.description()),
+            Style::default().fg(theme.accent),
+        ),
+        (None, Some(report)) => match report.setup_steps().first() {
+            Some(step) => Span::styled(
+                format!("Press s to {}", step.description().to_lowercase()),
+                Style::default().fg(theme.accent),
+            ),
+            None if report.is_ready() => Span::styled(
+                "Ready to build firmware",
+                Style::default().fg(theme.success),
+            ),
+            None => Span::styled(
+                "Fix the problems above to build firmware; editing works without them",
+                Style::default().fg(theme.text_muted),
+            ),
+        },
+        (None, None) => Span::raw(""),
+    };
+    lines.push(Line::from(summary));
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(ratatui::widgets::Wrap { trim: false })
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" QMK Path ")
+                .title(" QMK Environment ")
                 .style(Style::default().fg(theme.primary)),
-        )
-        .style(Style::default().fg(theme.text));
-
+        );
     f.render_widget(paragraph, area);
 }
 
@@ -228,7 +286,14 @@ fn render_keyboard_selection(
             } else {
                 Style::default().fg(theme.text)
             };
-            ListItem::new(Line::from(Span::styled(name, style)))
+            let mut spans = vec![Span::styled(name, style)];
+            if state.detected_keyboards.contains(name) {
+                spans.push(Span::styled(
+                    " (connected)",
+                    Style::default().fg(theme.success),
+                ));
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -329,35 +394,62 @@ fn render_layout_name_input(
     f.render_widget(paragraph, area);
 }
 
-/// Render output path input screen
+/// Render output path selection screen
 fn render_output_path_input(
     f: &mut Frame,
     state: &OnboardingWizardState,
     area: Rect,
     theme: &Theme,
 ) {
+    let browser = path_browser(state);
     let text = vec![
-        Line::from(vec![
-            Span::styled("Output Path: ", Style::default().fg(theme.primary)),
-            Span::styled(&state.input_buffer, Style::default().fg(theme.text)),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Tip: ", Style::default().fg(theme.text_muted)),
-            Span::raw("Build artifacts will be placed in this folder"),
-        ]),
+        Line::from(Span::styled(
+            "Build artifacts will be placed in the chosen folder.",
+            Style::default().fg(theme.text),
+        )),
+        Line::from(Span::styled(
+            "Tip: Ctrl+N creates a new folder here",
+            Style::default().fg(theme.text_muted),
+        )),
     ];
 
-    let paragraph = Paragraph::new(text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Build Output ")
-                .style(Style::default().fg(theme.primary)),
-        )
-        .style(Style::default().fg(theme.text));
+    render_path_browser(f, &browser, text, " Build Output ", area, theme);
+}
 
-    f.render_widget(paragraph, area);
+/// Returns the directory browser of a path step, opening one at the input
+/// buffer if the step has none yet
+fn path_browser(state: &OnboardingWizardState) -> Cow<'_, FileBrowser> {
+    state.path_browser.as_ref().map_or_else(
+        || Cow::Owned(FileBrowser::new(&state.input_buffer)),
+        Cow::Borrowed,
+    )
+}
+
+/// Render a path step: an explanation above the directory browser
+fn render_path_browser(
+    f: &mut Frame,
+    browser: &FileBrowser,
+    text: Vec<Line>,
+    title: &str,
+    area: Rect,
+    theme: &Theme,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .style(Style::default().fg(theme.primary));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(9)])
+        .split(inner);
+    f.render_widget(
+        Paragraph::new(text).style(Style::default().fg(theme.text)),
+        chunks[0],
+    );
+    browser.render(f, chunks[1], theme);
 }
 
 /// Render confirmation screen
@@ -396,9 +488,13 @@ fn render_instructions(f: &mut Frame, state: &OnboardingWizardState, area: Rect,
 
     let instructions = match state.current_step {
         WizardStep::Welcome => "↑↓: Choose path  |  Enter: Continue  |  Esc: Exit",
-        WizardStep::QmkPath | WizardStep::LayoutName | WizardStep::OutputPath => {
-            "Enter: Continue  |  Backspace: Delete  |  Esc: Back"
+        WizardStep::QmkPath | WizardStep::OutputPath => {
+            "↑↓: Navigate  |  Enter: Open  |  Tab: Use folder  |  Backspace: Up  |  Esc: Back"
+        }
+        WizardStep::Environment => {
+            "Enter: Continue  |  s: Run setup step  |  r: Check again  |  Esc: Back"
         }
+        WizardStep::LayoutName => "Enter: Continue  |  Backspace: Delete  |  Esc: Back",
         WizardStep::KeyboardSelection => {
             "Tab/Shift+Tab: Move focus  |  Type: Filter  |  ↑↓: Navigate  |  Enter: Select"
         }
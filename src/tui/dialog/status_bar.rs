@@ -1,14 +1,22 @@
 //! Status bar widget for displaying status messages and help
+//!
+//! The first line is built from the segments configured in
+//! `ui.status_bar_segments` (see [`StatusBarSegment`]).
+
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use ratatui::{
     layout::Rect,
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
 use super::help_registry::{self, HelpRegistry};
+use crate::config::StatusBarSegment;
+use crate::firmware::BuildStatus;
+use crate::services::git::GitFileStatus;
 use crate::tui::{AppState, Theme};
 
 /// Status bar widget
@@ -17,39 +25,35 @@ pub struct StatusBar;
 impl StatusBar {
     /// Render the status bar with contextual help
     pub fn render(f: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
-        let build_status_line = if let Some(build_state) = &state.build_state {
-            let status = &build_state.status;
-            let color = match status {
-                crate::firmware::BuildStatus::Idle => theme.inactive,
-                crate::firmware::BuildStatus::Validating
-                | crate::firmware::BuildStatus::Generating
-                | crate::firmware::BuildStatus::Compiling => theme.warning,
-                crate::firmware::BuildStatus::Success => theme.success,
-                crate::firmware::BuildStatus::Failed => theme.error,
-            };
+        let segments = &state.config.ui.status_bar_segments;
 
-            Some(Line::from(vec![
-                Span::styled("Build: ", Style::default().fg(theme.primary)),
-                Span::styled(status.to_string(), Style::default().fg(color)),
-                if !build_state.last_message.is_empty() {
-                    Span::styled(" • ", Style::default().fg(theme.text_muted))
-                } else {
-                    Span::raw("")
-                },
-                if !build_state.last_message.is_empty() {
-                    Span::styled(
-                        build_state.last_message.clone(),
-                        Style::default().fg(theme.text_muted),
-                    )
-                } else {
-                    Span::raw("")
-                },
-            ]))
-        } else {
-            None
-        };
+        // The build line is only shown when the build status isn't already a segment
+        let build_status_line = state
+            .build_state
+            .as_ref()
+            .filter(|_| !segments.contains(&StatusBarSegment::BuildStatus))
+            .map(|build_state| {
+                let color = Self::build_status_color(&build_state.status, theme);
+                Line::from(vec![
+                    Span::styled("Build: ", Style::default().fg(theme.primary)),
+                    Span::styled(build_state.status.to_string(), Style::default().fg(color)),
+                    if !build_state.last_message.is_empty() {
+                        Span::styled(" • ", Style::default().fg(theme.text_muted))
+                    } else {
+                        Span::raw("")
+                    },
+                    if !build_state.last_message.is_empty() {
+                        Span::styled(
+                            build_state.last_message.clone(),
+                            Style::default().fg(theme.text_muted),
+                        )
+                    } else {
+                        Span::raw("")
+                    },
+                ])
+            });
         let mut status_text: Vec<Line> = Vec::new();
-        status_text.push(Self::get_selection_summary_line(state, theme));
+        status_text.push(Self::get_segments_line(state, theme));
 
         if let Some(error) = &state.error_message {
             status_text.push(Line::from(vec![
@@ -84,32 +88,245 @@ impl StatusBar {
         f.render_widget(status, area);
     }
 
-    fn get_selection_summary_line(state: &AppState, theme: &Theme) -> Line<'static> {
-        let dirty_label = if state.dirty {
-            "Unsaved changes"
-        } else {
-            "Saved"
-        };
-        let dirty_color = if state.dirty {
-            theme.warning
-        } else {
-            theme.success
-        };
+    /// Build the summary line from the segments configured in `ui.status_bar_segments`.
+    ///
+    /// The live preview segment is appended while live preview is running even
+    /// if it isn't configured.
+    fn get_segments_line(state: &AppState, theme: &Theme) -> Line<'static> {
+        let configured = &state.config.ui.status_bar_segments;
+        let live_preview = (state.hid_listener.is_some()
+            && !configured.contains(&StatusBarSegment::LivePreview))
+        .then_some(StatusBarSegment::LivePreview);
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        for segment in configured.iter().copied().chain(live_preview) {
+            let Some(segment_spans) = Self::render_segment(segment, state, theme) else {
+                continue;
+            };
+            if !spans.is_empty() {
+                spans.push(Span::styled("  |  ", Style::default().fg(theme.text_muted)));
+            }
+            spans.extend(segment_spans);
+        }
+        Line::from(spans)
+    }
 
-        let key_summary = state.get_selected_key().map_or_else(
-            || "No key selected".to_string(),
-            |key| {
-                format!(
-                    "Layer {} • Key ({}, {}) • {}",
-                    state.current_layer,
-                    state.selected_position.row,
-                    state.selected_position.col,
-                    key.keycode
-                )
-            },
+    /// Render a single status bar segment, or `None` if it has nothing to show.
+    fn render_segment(
+        segment: StatusBarSegment,
+        state: &AppState,
+        theme: &Theme,
+    ) -> Option<Vec<Span<'static>>> {
+        let label = |text: &'static str| Span::styled(text, Style::default().fg(theme.primary));
+        match segment {
+            StatusBarSegment::Mode => Some(vec![
+                label("Mode: "),
+                Span::styled(Self::mode_label(state), Style::default().fg(theme.accent)),
+            ]),
+            StatusBarSegment::SelectedKey => {
+                let key_summary = state.get_selected_key().map_or_else(
+                    || "No key selected".to_string(),
+                    |key| {
+                        let pos = state.selected_position;
+                        let location = state.position_names.get(pos).map_or_else(
+                            || format!("Key ({}, {})", pos.row, pos.col),
+                            |name| format!("{name} ({}, {})", pos.row, pos.col),
+                        );
+                        format!(
+                            "Layer {} • {} • {}",
+                            state.current_layer, location, key.keycode
+                        )
+                    },
+                );
+                Some(vec![
+                    label("Selection: "),
+                    Span::styled(key_summary, Style::default().fg(theme.text)),
+                ])
+            }
+            StatusBarSegment::Draft => {
+                let (dirty_label, dirty_color) = if state.dirty {
+                    ("Unsaved changes", theme.warning)
+                } else {
+                    ("Saved", theme.success)
+                };
+                let mut spans = vec![
+                    label("Draft: "),
+                    Span::styled(dirty_label, Style::default().fg(dirty_color)),
+                ];
+                if let Some(status) = state.git_status {
+                    spans.push(Span::styled(" • ", Style::default().fg(theme.text_muted)));
+                    spans.push(Span::styled(
+                        format!("git {status}"),
+                        Style::default().fg(Self::git_status_color(status, theme)),
+                    ));
+                }
+                Some(spans)
+            }
+            StatusBarSegment::Clipboard => {
+                let preview = state.clipboard.get_preview()?;
+                let verb = if state.clipboard.is_cut() {
+                    "Move"
+                } else {
+                    "Copy"
+                };
+                Some(vec![
+                    label("Clipboard: "),
+                    Span::styled(format!("{verb} "), Style::default().fg(theme.text_muted)),
+                    Span::styled(preview, Style::default().fg(theme.accent)),
+                ])
+            }
+            StatusBarSegment::BuildStatus => {
+                let build_state = state.build_state.as_ref()?;
+                Some(vec![
+                    label("Build: "),
+                    Span::styled(
+                        build_state.status.to_string(),
+                        Style::default().fg(Self::build_status_color(&build_state.status, theme)),
+                    ),
+                ])
+            }
+            StatusBarSegment::LayerChain => {
+                let chain = Self::layer_chain(state)
+                    .into_iter()
+                    .filter_map(|idx| state.layout.layers.get(idx))
+                    .map(|layer| layer.name.as_str())
+                    .collect::<Vec<_>>();
+                let text = if chain.is_empty() {
+                    let name = state
+                        .layout
+                        .layers
+                        .get(state.current_layer)
+                        .map_or("?", |layer| layer.name.as_str());
+                    format!("{name} (unreachable)")
+                } else {
+                    chain.join(" › ")
+                };
+                Some(vec![
+                    label("Layers: "),
+                    Span::styled(text, Style::default().fg(theme.text)),
+                ])
+            }
+            StatusBarSegment::GitBranch => {
+                let branch = state.git_branch.clone()?;
+                let mut spans = vec![
+                    label("Git: "),
+                    Span::styled(branch, Style::default().fg(theme.accent)),
+                ];
+                if let Some(status) = state.git_status {
+                    spans.push(Span::styled(
+                        format!(" ({status})"),
+                        Style::default().fg(Self::git_status_color(status, theme)),
+                    ));
+                }
+                Some(spans)
+            }
+            StatusBarSegment::LivePreview => {
+                state.hid_listener.as_ref()?;
+                Some(Self::live_preview_spans(state, theme))
+            }
+        }
+    }
+
+    /// Color of the layout file's git status.
+    const fn git_status_color(status: GitFileStatus, theme: &Theme) -> Color {
+        match status {
+            GitFileStatus::Committed => theme.success,
+            GitFileStatus::Modified => theme.warning,
+            GitFileStatus::Untracked => theme.text_muted,
+        }
+    }
+
+    /// Live preview summary: the reported layer and the labels of the most
+    /// recently pressed keys, or the connection state until reports arrive.
+    fn live_preview_spans(state: &AppState, theme: &Theme) -> Vec<Span<'static>> {
+        let live = &state.live_state;
+        let mut spans = vec![Span::styled("Live: ", Style::default().fg(theme.primary))];
+        if live.device.is_none() {
+            let (text, color) = live.error.as_ref().map_or_else(
+                || ("waiting for keyboard".to_string(), theme.text_muted),
+                |error| (error.clone(), theme.warning),
+            );
+            spans.push(Span::styled(text, Style::default().fg(color)));
+            return spans;
+        }
+
+        let Some(layer_idx) = live.layer else {
+            spans.push(Span::styled(
+                "connected, no reports yet",
+                Style::default().fg(theme.text_muted),
+            ));
+            return spans;
+        };
+        let layer = state.layout.layers.get(usize::from(layer_idx));
+        let layer_text = layer.map_or_else(
+            || format!("L{layer_idx}"),
+            |layer| format!("L{layer_idx} {}", layer.name),
         );
+        spans.push(Span::styled(layer_text, Style::default().fg(theme.accent)));
+
+        let keys: Vec<String> = live
+            .recent_keys
+            .iter()
+            .map(|&(row, col)| {
+                state
+                    .mapping
+                    .matrix_to_visual_pos(row, col)
+                    .and_then(|pos| layer?.get_key(pos))
+                    .map_or_else(
+                        || format!("({row},{col})"),
+                        |key| state.key_label(&key.keycode).to_string(),
+                    )
+            })
+            .collect();
+        if !keys.is_empty() {
+            spans.push(Span::styled(" • ", Style::default().fg(theme.text_muted)));
+            spans.push(Span::styled(
+                keys.join(" "),
+                Style::default().fg(theme.text),
+            ));
+        }
+        spans
+    }
+
+    /// Shortest chain of layer indices from the base layer to the current layer,
+    /// following layer-switching keycodes. Empty if the layer is unreachable.
+    fn layer_chain(state: &AppState) -> Vec<usize> {
+        let target = state.current_layer;
+        let mut previous: HashMap<usize, usize> = HashMap::new();
+        let mut queue = VecDeque::from([0]);
+        let mut visited = HashSet::from([0]);
 
-        let mode_label = if matches!(
+        while let Some(layer) = queue.pop_front() {
+            if layer == target {
+                let mut chain = vec![layer];
+                let mut current = layer;
+                while let Some(&prev) = previous.get(&current) {
+                    chain.push(prev);
+                    current = prev;
+                }
+                chain.reverse();
+                return chain;
+            }
+            let mut next_layers: Vec<usize> = state
+                .layer_refs
+                .values()
+                .flatten()
+                .filter(|layer_ref| layer_ref.from_layer == layer)
+                .map(|layer_ref| layer_ref.to_layer)
+                .collect();
+            next_layers.sort_unstable();
+            for next in next_layers {
+                if visited.insert(next) {
+                    previous.insert(next, layer);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        Vec::new()
+    }
+
+    fn mode_label(state: &AppState) -> &'static str {
+        if matches!(
             state.active_popup,
             Some(crate::tui::PopupType::SettingsManager)
         ) {
@@ -126,21 +343,33 @@ impl StatusBar {
             }
         } else {
             "Edit"
-        };
+        }
+    }
 
-        Line::from(vec![
-            Span::styled("Mode: ", Style::default().fg(theme.primary)),
-            Span::styled(mode_label, Style::default().fg(theme.accent)),
-            Span::styled("  |  ", Style::default().fg(theme.text_muted)),
-            Span::styled("Selection: ", Style::default().fg(theme.primary)),
-            Span::styled(key_summary, Style::default().fg(theme.text)),
-            Span::styled("  |  Draft: ", Style::default().fg(theme.primary)),
-            Span::styled(dirty_label, Style::default().fg(dirty_color)),
-        ])
+    fn build_status_color(status: &BuildStatus, theme: &Theme) -> Color {
+        match status {
+            BuildStatus::Idle => theme.inactive,
+            BuildStatus::Validating
+            | BuildStatus::Generating
+            | BuildStatus::Compiling
+            | BuildStatus::Checking
+            | BuildStatus::Flashing => theme.warning,
+            BuildStatus::Success => theme.success,
+            BuildStatus::Failed => theme.error,
+        }
     }
 
     fn get_clipboard_or_note_line(state: &AppState, theme: &Theme) -> Line<'static> {
-        if let Some(preview) = state.clipboard.get_preview() {
+        let clipboard_in_segments = state
+            .config
+            .ui
+            .status_bar_segments
+            .contains(&StatusBarSegment::Clipboard);
+        if let Some(preview) = state
+            .clipboard
+            .get_preview()
+            .filter(|_| !clipboard_in_segments)
+        {
             let clipboard_type = if state.clipboard.is_single() {
                 if state.clipboard.is_cut() {
                     "Move 1 key"
@@ -259,13 +488,45 @@ impl StatusBar {
             Some(PopupType::HelpOverlay) => help_registry::contexts::HELP,
             Some(PopupType::BuildLog) => help_registry::contexts::BUILD_LOG,
             Some(PopupType::MetadataEditor) => help_registry::contexts::METADATA_EDITOR,
-            Some(PopupType::SettingsManager) => help_registry::contexts::SETTINGS_MANAGER,
+            Some(PopupType::SettingsManager) => {
+                if let Some(crate::tui::ActiveComponent::SettingsManager(manager)) =
+                    &state.active_component
+                {
+                    if matches!(
+                        manager.state().mode,
+                        crate::tui::settings_manager::ManagerMode::EditingPath { .. }
+                    ) {
+                        return help_registry::contexts::FILE_BROWSER;
+                    }
+                }
+                help_registry::contexts::SETTINGS_MANAGER
+            }
             Some(PopupType::ModifierPicker) => help_registry::contexts::MODIFIER_PICKER,
             Some(PopupType::TapDanceEditor) => help_registry::contexts::TAP_DANCE_EDITOR,
+            Some(PopupType::KeySearch) => help_registry::contexts::KEY_SEARCH,
+            Some(PopupType::CopyToLayer) => help_registry::contexts::COPY_TO_LAYER,
+            Some(PopupType::MacroEditor) => help_registry::contexts::MACRO_EDITOR,
+            Some(PopupType::UnicodeMapEditor) => help_registry::contexts::UNICODE_MAP_EDITOR,
+            Some(PopupType::CustomKeycodeEditor) => help_registry::contexts::CUSTOM_KEYCODE_EDITOR,
+            Some(PopupType::ComboEditor) => help_registry::contexts::COMBO_EDITOR,
+            Some(PopupType::EncoderEditor) => help_registry::contexts::ENCODER_EDITOR,
+            Some(PopupType::BackupPicker) => help_registry::contexts::BACKUP_PICKER,
             Some(PopupType::TemplateBrowser) => help_registry::contexts::TEMPLATE_BROWSER,
+            Some(PopupType::TemplateFit) => help_registry::contexts::TEMPLATE_FIT,
+            Some(PopupType::TemplatePlaceholders) => help_registry::contexts::TEMPLATE_PLACEHOLDERS,
+            Some(PopupType::LayoutStats) => help_registry::contexts::LAYOUT_STATS,
+            Some(PopupType::LayerGraph) => help_registry::contexts::LAYER_GRAPH,
+            Some(PopupType::QmkTreePicker) => help_registry::contexts::QMK_TREE_PICKER,
             Some(PopupType::TemplateSaveDialog) => help_registry::contexts::TEMPLATE_SAVE,
-            Some(PopupType::SetupWizard) => help_registry::contexts::SETUP_WIZARD,
+            Some(PopupType::SetupWizard) => {
+                if state.wizard_state.path_browser.is_some() {
+                    help_registry::contexts::FILE_BROWSER
+                } else {
+                    help_registry::contexts::SETUP_WIZARD
+                }
+            }
             Some(PopupType::UnsavedChangesPrompt) => help_registry::contexts::UNSAVED_PROMPT,
+            Some(PopupType::GeometryAdjustReview) => help_registry::contexts::GEOMETRY_REVIEW,
             _ => {
                 // Check for selection mode
                 if state.selection_mode.is_some() {
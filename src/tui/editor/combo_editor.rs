@@ -0,0 +1,457 @@
+//! Combo Editor Component
+//!
+//! Lists the layout's keycode combos and edits them in a two-field form:
+//! trigger keys as base-layer `row,col` positions (e.g. `0,1 0,2`) and the
+//! keycode the combo sends. New combos are prefilled with the keys selected
+//! when the editor was opened. Saving and deleting are reported as events;
+//! the handler applies them to the layout.
+
+use crate::models::{KeycodeCombo, Layout, Position};
+use crate::tui::theme::Theme;
+use crate::tui::Component;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// Events emitted by the combo editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComboEditorEvent {
+    /// User saved a new or edited combo
+    Saved {
+        /// Index of the combo being replaced (None when creating)
+        index: Option<usize>,
+        /// The validated combo
+        combo: KeycodeCombo,
+    },
+    /// User wants to delete the combo at this index
+    Delete(usize),
+    /// User closed the editor
+    Cancelled,
+}
+
+/// Which form field has focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComboField {
+    /// Trigger key positions
+    Keys,
+    /// Output keycode
+    Keycode,
+}
+
+/// Draft being edited in the form
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ComboDraft {
+    /// Index of the combo being edited (None when creating)
+    index: Option<usize>,
+    keys: String,
+    keycode: String,
+    focus: ComboField,
+    error: Option<String>,
+}
+
+/// Combo Editor component state
+#[derive(Debug, Clone)]
+pub struct ComboEditor {
+    /// Index of the highlighted combo
+    selected: usize,
+    /// Keycode combos from the layout (read-only view)
+    combos: Vec<KeycodeCombo>,
+    /// Base-layer keycode at each position, for display and validation
+    base_keys: Vec<(Position, String)>,
+    /// Keys selected when the editor was opened (prefills new combos)
+    selection: Vec<Position>,
+    /// Form state while creating or editing
+    draft: Option<ComboDraft>,
+}
+
+impl ComboEditor {
+    /// Creates a new combo editor with combos from the layout
+    #[must_use]
+    pub fn new(layout: &Layout, selection: &[Position]) -> Self {
+        let base_keys = layout
+            .layers
+            .first()
+            .map(|layer| {
+                layer
+                    .keys
+                    .iter()
+                    .map(|key| (key.position, key.keycode.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            selected: 0,
+            combos: layout.combo_settings.keycode_combos.clone(),
+            base_keys,
+            selection: selection.to_vec(),
+            draft: None,
+        }
+    }
+
+    /// Shows `message` under the form, keeping the draft open.
+    ///
+    /// Used by the handler when a saved combo is rejected (e.g. unknown keycode).
+    pub fn set_error(&mut self, message: impl Into<String>) {
+        if let Some(draft) = self.draft.as_mut() {
+            draft.error = Some(message.into());
+        }
+    }
+
+    fn base_keycode(&self, position: Position) -> Option<&str> {
+        self.base_keys
+            .iter()
+            .find(|(pos, _)| *pos == position)
+            .map(|(_, keycode)| keycode.as_str())
+    }
+
+    /// Describes a combo as its base-layer trigger keycodes and output.
+    fn describe(&self, combo: &KeycodeCombo) -> String {
+        let keys: Vec<&str> = combo
+            .keys
+            .iter()
+            .map(|position| self.base_keycode(*position).unwrap_or("?"))
+            .collect();
+        format!("{} → {}", keys.join(" + "), combo.keycode)
+    }
+
+    fn open_form(&mut self, index: Option<usize>) {
+        let existing = index.and_then(|idx| self.combos.get(idx));
+        let keys = existing.map_or(&self.selection, |combo| &combo.keys);
+        self.draft = Some(ComboDraft {
+            index,
+            keys: format_positions(keys),
+            keycode: existing.map(|c| c.keycode.clone()).unwrap_or_default(),
+            focus: if index.is_none() && keys.len() >= 2 {
+                ComboField::Keycode
+            } else {
+                ComboField::Keys
+            },
+            error: None,
+        });
+    }
+
+    /// Parse and validate the draft, returning the combo or an error message
+    fn build_draft(&self, draft: &ComboDraft) -> Result<KeycodeCombo, String> {
+        let keys = parse_positions(&draft.keys)?;
+        if let Some(missing) = keys.iter().find(|p| self.base_keycode(**p).is_none()) {
+            return Err(format!(
+                "No key at {},{} on the base layer",
+                missing.row, missing.col
+            ));
+        }
+
+        let combo = KeycodeCombo::new(keys, draft.keycode.trim());
+        combo.validate().map_err(|e| e.to_string())?;
+
+        let duplicate = self
+            .combos
+            .iter()
+            .enumerate()
+            .any(|(idx, existing)| Some(idx) != draft.index && existing.same_keys(&combo));
+        if duplicate {
+            return Err("A combo with the same keys already exists".to_string());
+        }
+        Ok(combo)
+    }
+
+    fn handle_form_input(&mut self, key: KeyEvent) -> Option<ComboEditorEvent> {
+        let mut draft = self.draft.take()?;
+        match key.code {
+            KeyCode::Esc => return None,
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                draft.focus = match draft.focus {
+                    ComboField::Keys => ComboField::Keycode,
+                    ComboField::Keycode => ComboField::Keys,
+                };
+            }
+            KeyCode::Enter => match self.build_draft(&draft) {
+                Ok(combo) => {
+                    // Keep the form open so the handler can report a rejection
+                    let index = draft.index;
+                    self.draft = Some(draft);
+                    return Some(ComboEditorEvent::Saved { index, combo });
+                }
+                Err(message) => draft.error = Some(message),
+            },
+            KeyCode::Backspace => {
+                match draft.focus {
+                    ComboField::Keys => draft.keys.pop(),
+                    ComboField::Keycode => draft.keycode.pop(),
+                };
+                draft.error = None;
+            }
+            KeyCode::Char(c) => {
+                match draft.focus {
+                    ComboField::Keys => draft.keys.push(c),
+                    ComboField::Keycode => draft.keycode.push(c),
+                }
+                draft.error = None;
+            }
+            _ => {}
+        }
+        self.draft = Some(draft);
+        None
+    }
+}
+
+/// Formats positions as space-separated `row,col` pairs.
+fn format_positions(positions: &[Position]) -> String {
+    positions
+        .iter()
+        .map(|p| format!("{},{}", p.row, p.col))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses space- or `+`-separated `row,col` pairs (parentheses optional).
+fn parse_positions(text: &str) -> Result<Vec<Position>, String> {
+    text.split(|c: char| c.is_whitespace() || c == '+')
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            let pair = token.trim_start_matches('(').trim_end_matches(')');
+            pair.split_once(',')
+                .and_then(|(row, col)| Some(Position::new(row.parse().ok()?, col.parse().ok()?)))
+                .ok_or_else(|| format!("Invalid key '{token}' (expected row,col)"))
+        })
+        .collect()
+}
+
+impl Component for ComboEditor {
+    type Event = ComboEditorEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        if self.draft.is_some() {
+            return self.handle_form_input(key);
+        }
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.combos.len().saturating_sub(1));
+            }
+            KeyCode::Char('n') => self.open_form(None),
+            KeyCode::Enter | KeyCode::Char('e') if self.selected < self.combos.len() => {
+                self.open_form(Some(self.selected));
+            }
+            KeyCode::Char('D') if self.selected < self.combos.len() => {
+                return Some(ComboEditorEvent::Delete(self.selected));
+            }
+            KeyCode::Esc => return Some(ComboEditorEvent::Cancelled),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, _area: Rect, theme: &Theme) {
+        let area = centered_rect(70, 60, frame.area());
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(theme.background)),
+            area,
+        );
+
+        match &self.draft {
+            Some(draft) => self.render_form(frame, area, draft, theme),
+            None => self.render_list(frame, area, theme),
+        }
+    }
+}
+
+impl ComboEditor {
+    fn render_list(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        let items: Vec<ListItem> = if self.combos.is_empty() {
+            vec![ListItem::new(
+                Line::from("No keycode combos defined. Press 'n' to create one.")
+                    .style(Style::default().fg(theme.text_muted)),
+            )]
+        } else {
+            self.combos
+                .iter()
+                .enumerate()
+                .map(|(idx, combo)| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("Combo {}: ", idx + 1),
+                            Style::default().fg(theme.accent),
+                        ),
+                        Span::raw(self.describe(combo)),
+                    ]))
+                })
+                .collect()
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Keycode Combos ")
+                    .style(Style::default().bg(theme.background)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(theme.highlight_bg)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(theme.background).fg(theme.text));
+
+        let mut list_state = ListState::default();
+        if !self.combos.is_empty() {
+            list_state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        let help = help_line(
+            &[
+                ("↑/↓", "Navigate"),
+                ("n", "New"),
+                ("Enter/e", "Edit"),
+                ("D", "Delete"),
+                ("Esc", "Close"),
+            ],
+            theme,
+        );
+        frame.render_widget(help, chunks[1]);
+    }
+
+    fn render_form(&self, frame: &mut Frame, area: Rect, draft: &ComboDraft, theme: &Theme) {
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Keys
+                Constraint::Length(3), // Keycode
+                Constraint::Min(3),    // Trigger preview / error
+                Constraint::Length(3), // Help
+            ])
+            .split(area);
+
+        for (chunk, field, title, value) in [
+            (chunks[0], ComboField::Keys, " Keys (row,col) ", &draft.keys),
+            (chunks[1], ComboField::Keycode, " Keycode ", &draft.keycode),
+        ] {
+            let focused = draft.focus == field;
+            let mut spans = vec![Span::raw(value.as_str())];
+            if focused {
+                spans.push(Span::styled("█", Style::default().fg(theme.primary)));
+            }
+            let border = if focused {
+                theme.primary
+            } else {
+                theme.text_muted
+            };
+            let input = Paragraph::new(Line::from(spans))
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(border)),
+                )
+                .style(Style::default().fg(theme.text));
+            frame.render_widget(input, chunk);
+        }
+
+        let hint = draft.error.as_ref().map_or_else(
+            || {
+                let preview = parse_positions(&draft.keys)
+                    .ok()
+                    .filter(|keys| !keys.is_empty())
+                    .map(|keys| {
+                        let names: Vec<&str> = keys
+                            .iter()
+                            .map(|p| self.base_keycode(*p).unwrap_or("?"))
+                            .collect();
+                        format!("Triggers: {}", names.join(" + "))
+                    })
+                    .unwrap_or_else(|| {
+                        "Select keys (Shift+V) before opening the editor to prefill them."
+                            .to_string()
+                    });
+                Line::from(Span::styled(preview, Style::default().fg(theme.text_muted)))
+            },
+            |error| {
+                Line::from(Span::styled(
+                    error.as_str(),
+                    Style::default().fg(theme.error),
+                ))
+            },
+        );
+        let title = if draft.index.is_some() {
+            " Edit Combo "
+        } else {
+            " New Combo "
+        };
+        frame.render_widget(
+            Paragraph::new(hint).block(Block::default().title(title).borders(Borders::ALL)),
+            chunks[2],
+        );
+
+        let help = help_line(
+            &[("Tab", "Switch field"), ("Enter", "Save"), ("Esc", "Back")],
+            theme,
+        );
+        frame.render_widget(help, chunks[3]);
+    }
+}
+
+fn help_line<'a>(entries: &[(&'a str, &'a str)], theme: &Theme) -> Paragraph<'a> {
+    let spans: Vec<Span> = entries
+        .iter()
+        .flat_map(|(key, label)| {
+            [
+                Span::styled(
+                    *key,
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" {label}  ")),
+            ]
+        })
+        .collect();
+
+    Paragraph::new(Line::from(spans))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Help ")
+                .style(Style::default().bg(theme.background)),
+        )
+        .style(Style::default().bg(theme.background).fg(theme.text))
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
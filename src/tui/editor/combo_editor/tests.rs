@@ -0,0 +1,132 @@
+//! Tests for combo_editor.
+
+use super::*;
+use crate::models::{KeyDefinition, Layer, RgbColor};
+use crossterm::event::KeyModifiers;
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+    for (col, keycode) in ["KC_J", "KC_K", "KC_L"].into_iter().enumerate() {
+        layer.add_key(KeyDefinition::new(Position::new(0, col as u8), keycode));
+    }
+    layout.layers.push(layer);
+    layout
+        .combo_settings
+        .add_keycode_combo(KeycodeCombo::new(
+            vec![Position::new(0, 0), Position::new(0, 1)],
+            "KC_ESC",
+        ))
+        .unwrap();
+    layout
+}
+
+fn press(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn type_text(editor: &mut ComboEditor, text: &str) {
+    for c in text.chars() {
+        assert_eq!(editor.handle_input(press(KeyCode::Char(c))), None);
+    }
+}
+
+#[test]
+fn test_parse_positions() {
+    assert_eq!(
+        parse_positions("0,1 (0,2)+1,3").unwrap(),
+        vec![
+            Position::new(0, 1),
+            Position::new(0, 2),
+            Position::new(1, 3)
+        ]
+    );
+    assert!(parse_positions("0;1").is_err());
+    assert_eq!(
+        format_positions(&[Position::new(0, 1), Position::new(2, 0)]),
+        "0,1 2,0"
+    );
+}
+
+#[test]
+fn test_describe_uses_base_layer_keycodes() {
+    let layout = create_test_layout();
+    let editor = ComboEditor::new(&layout, &[]);
+
+    assert_eq!(
+        editor.describe(&layout.combo_settings.keycode_combos[0]),
+        "KC_J + KC_K → KC_ESC"
+    );
+}
+
+#[test]
+fn test_new_combo_prefills_selected_keys() {
+    let selection = [Position::new(0, 1), Position::new(0, 2)];
+    let mut editor = ComboEditor::new(&create_test_layout(), &selection);
+
+    // With two keys selected, focus starts on the keycode field
+    editor.handle_input(press(KeyCode::Char('n')));
+    type_text(&mut editor, "KC_TAB");
+
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Enter)),
+        Some(ComboEditorEvent::Saved {
+            index: None,
+            combo: KeycodeCombo::new(selection.to_vec(), "KC_TAB"),
+        })
+    );
+    // The form stays open until the handler accepts the combo
+    assert!(editor.draft.is_some());
+}
+
+#[test]
+fn test_edit_keeps_index() {
+    let mut editor = ComboEditor::new(&create_test_layout(), &[]);
+
+    editor.handle_input(press(KeyCode::Enter));
+    editor.handle_input(press(KeyCode::Tab));
+    for _ in 0.."KC_ESC".len() {
+        editor.handle_input(press(KeyCode::Backspace));
+    }
+    type_text(&mut editor, "KC_GRV");
+
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Enter)),
+        Some(ComboEditorEvent::Saved {
+            index: Some(0),
+            combo: KeycodeCombo::new(vec![Position::new(0, 0), Position::new(0, 1)], "KC_GRV"),
+        })
+    );
+}
+
+#[test]
+fn test_invalid_form_stays_open_with_error() {
+    let mut editor = ComboEditor::new(&create_test_layout(), &[]);
+
+    // Same keys as the existing combo, in another order
+    editor.handle_input(press(KeyCode::Char('n')));
+    type_text(&mut editor, "0,1 0,0");
+    editor.handle_input(press(KeyCode::Tab));
+    type_text(&mut editor, "KC_A");
+    assert_eq!(editor.handle_input(press(KeyCode::Enter)), None);
+    let error = editor.draft.as_ref().and_then(|d| d.error.clone()).unwrap();
+    assert!(error.contains("same keys"), "{error}");
+
+    // Key that is not on the base layer
+    editor.handle_input(press(KeyCode::Tab));
+    type_text(&mut editor, " 3,3");
+    assert_eq!(editor.handle_input(press(KeyCode::Enter)), None);
+    let error = editor.draft.as_ref().and_then(|d| d.error.clone()).unwrap();
+    assert!(error.contains("3,3"), "{error}");
+
+    // Esc returns to the list, then closes the editor
+    assert_eq!(editor.handle_input(press(KeyCode::Esc)), None);
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Char('D'))),
+        Some(ComboEditorEvent::Delete(0))
+    );
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Esc)),
+        Some(ComboEditorEvent::Cancelled)
+    );
+}
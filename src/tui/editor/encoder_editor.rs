@@ -0,0 +1,394 @@
+//! Encoder Editor Component
+//!
+//! Lists the keyboard's rotary encoders for the current layer and edits
+//! their rotation keycodes in a two-field form (counter-clockwise and
+//! clockwise). Encoders without a binding use the firmware defaults. Saving
+//! and clearing are reported as events; the handler applies them to the
+//! layer.
+
+use crate::models::{EncoderBinding, Layer};
+use crate::tui::theme::Theme;
+use crate::tui::Component;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// Events emitted by the encoder editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncoderEditorEvent {
+    /// User saved a binding for an encoder
+    Saved {
+        /// Encoder index
+        encoder: u8,
+        /// The new binding (keycodes trimmed, not yet checked against the database)
+        binding: EncoderBinding,
+    },
+    /// User reset an encoder to the default binding
+    Cleared(u8),
+    /// User closed the editor
+    Cancelled,
+}
+
+/// Which form field has focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncoderField {
+    /// Counter-clockwise keycode
+    Ccw,
+    /// Clockwise keycode
+    Cw,
+}
+
+/// Draft being edited in the form
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EncoderDraft {
+    encoder: u8,
+    ccw: String,
+    cw: String,
+    focus: EncoderField,
+    error: Option<String>,
+}
+
+/// Encoder Editor component state
+#[derive(Debug, Clone)]
+pub struct EncoderEditor {
+    /// Index of the highlighted encoder
+    selected: usize,
+    /// Name of the layer being edited (for the title)
+    layer_name: String,
+    /// Binding for each encoder (None uses the default)
+    bindings: Vec<Option<EncoderBinding>>,
+    /// Form state while editing
+    draft: Option<EncoderDraft>,
+}
+
+impl EncoderEditor {
+    /// Creates an encoder editor for `layer` on a keyboard with `encoder_count` encoders
+    #[must_use]
+    pub fn new(layer: &Layer, encoder_count: u8) -> Self {
+        Self {
+            selected: 0,
+            layer_name: layer.name.clone(),
+            bindings: (0..encoder_count)
+                .map(|encoder| layer.encoder_map.get(encoder).cloned())
+                .collect(),
+            draft: None,
+        }
+    }
+
+    /// Records that `encoder` now uses `binding` (None for the default) and
+    /// returns to the list.
+    ///
+    /// Called by the handler once a save or reset has been applied to the layer.
+    pub fn update_binding(&mut self, encoder: u8, binding: Option<EncoderBinding>) {
+        if let Some(slot) = self.bindings.get_mut(usize::from(encoder)) {
+            *slot = binding;
+        }
+        self.draft = None;
+    }
+
+    /// Shows `message` under the form, keeping the draft open.
+    ///
+    /// Used by the handler when a saved binding is rejected (e.g. unknown keycode).
+    pub fn set_error(&mut self, message: impl Into<String>) {
+        if let Some(draft) = self.draft.as_mut() {
+            draft.error = Some(message.into());
+        }
+    }
+
+    fn open_form(&mut self) {
+        let Ok(encoder) = u8::try_from(self.selected) else {
+            return;
+        };
+        let existing = self.bindings.get(self.selected).cloned().flatten();
+        self.draft = Some(EncoderDraft {
+            encoder,
+            ccw: existing.as_ref().map(|b| b.ccw.clone()).unwrap_or_default(),
+            cw: existing.map(|b| b.cw).unwrap_or_default(),
+            focus: EncoderField::Ccw,
+            error: None,
+        });
+    }
+
+    fn handle_form_input(&mut self, key: KeyEvent) -> Option<EncoderEditorEvent> {
+        let mut draft = self.draft.take()?;
+        match key.code {
+            KeyCode::Esc => return None,
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                draft.focus = match draft.focus {
+                    EncoderField::Ccw => EncoderField::Cw,
+                    EncoderField::Cw => EncoderField::Ccw,
+                };
+            }
+            KeyCode::Enter => {
+                let (ccw, cw) = (draft.ccw.trim(), draft.cw.trim());
+                if ccw.is_empty() || cw.is_empty() {
+                    draft.error = Some("Both directions need a keycode".to_string());
+                } else {
+                    // Keep the form open so the handler can report a rejection
+                    let event = EncoderEditorEvent::Saved {
+                        encoder: draft.encoder,
+                        binding: EncoderBinding::new(ccw, cw),
+                    };
+                    self.draft = Some(draft);
+                    return Some(event);
+                }
+            }
+            KeyCode::Backspace => {
+                match draft.focus {
+                    EncoderField::Ccw => draft.ccw.pop(),
+                    EncoderField::Cw => draft.cw.pop(),
+                };
+                draft.error = None;
+            }
+            KeyCode::Char(c) => {
+                match draft.focus {
+                    EncoderField::Ccw => draft.ccw.push(c),
+                    EncoderField::Cw => draft.cw.push(c),
+                }
+                draft.error = None;
+            }
+            _ => {}
+        }
+        self.draft = Some(draft);
+        None
+    }
+}
+
+impl Component for EncoderEditor {
+    type Event = EncoderEditorEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        if self.draft.is_some() {
+            return self.handle_form_input(key);
+        }
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.bindings.len().saturating_sub(1));
+            }
+            KeyCode::Enter | KeyCode::Char('e') if self.selected < self.bindings.len() => {
+                self.open_form();
+            }
+            KeyCode::Char('D')
+                if self
+                    .bindings
+                    .get(self.selected)
+                    .is_some_and(Option::is_some) =>
+            {
+                return u8::try_from(self.selected)
+                    .ok()
+                    .map(EncoderEditorEvent::Cleared);
+            }
+            KeyCode::Esc => return Some(EncoderEditorEvent::Cancelled),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, _area: Rect, theme: &Theme) {
+        let area = centered_rect(60, 50, frame.area());
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(theme.background)),
+            area,
+        );
+
+        match &self.draft {
+            Some(draft) => Self::render_form(frame, area, draft, theme),
+            None => self.render_list(frame, area, theme),
+        }
+    }
+}
+
+impl EncoderEditor {
+    fn render_list(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        let items: Vec<ListItem> = if self.bindings.is_empty() {
+            vec![ListItem::new(
+                Line::from("This keyboard has no rotary encoders.")
+                    .style(Style::default().fg(theme.text_muted)),
+            )]
+        } else {
+            self.bindings
+                .iter()
+                .enumerate()
+                .map(|(idx, binding)| {
+                    let label = Span::styled(
+                        format!("Encoder {idx}: "),
+                        Style::default().fg(theme.accent),
+                    );
+                    let value = binding.as_ref().map_or_else(
+                        || Span::styled("(default)", Style::default().fg(theme.text_muted)),
+                        |b| Span::raw(format!("↺ {}  ↻ {}", b.ccw, b.cw)),
+                    );
+                    ListItem::new(Line::from(vec![label, value]))
+                })
+                .collect()
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Encoders - {} ", self.layer_name))
+                    .style(Style::default().bg(theme.background)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(theme.highlight_bg)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(theme.background).fg(theme.text));
+
+        let mut list_state = ListState::default();
+        if !self.bindings.is_empty() {
+            list_state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        let help = help_line(
+            &[
+                ("↑/↓", "Navigate"),
+                ("Enter/e", "Edit"),
+                ("D", "Reset to default"),
+                ("Esc", "Close"),
+            ],
+            theme,
+        );
+        frame.render_widget(help, chunks[1]);
+    }
+
+    fn render_form(frame: &mut Frame, area: Rect, draft: &EncoderDraft, theme: &Theme) {
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Counter-clockwise
+                Constraint::Length(3), // Clockwise
+                Constraint::Min(3),    // Hint / error
+                Constraint::Length(3), // Help
+            ])
+            .split(area);
+
+        for (chunk, field, title, value) in [
+            (
+                chunks[0],
+                EncoderField::Ccw,
+                " ↺ Counter-clockwise ",
+                &draft.ccw,
+            ),
+            (chunks[1], EncoderField::Cw, " ↻ Clockwise ", &draft.cw),
+        ] {
+            let focused = draft.focus == field;
+            let mut spans = vec![Span::raw(value.as_str())];
+            if focused {
+                spans.push(Span::styled("█", Style::default().fg(theme.primary)));
+            }
+            let border = if focused {
+                theme.primary
+            } else {
+                theme.text_muted
+            };
+            let input = Paragraph::new(Line::from(spans))
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(border)),
+                )
+                .style(Style::default().fg(theme.text));
+            frame.render_widget(input, chunk);
+        }
+
+        let hint = draft.error.as_ref().map_or_else(
+            || {
+                Line::from(Span::styled(
+                    "Any keycode works, e.g. KC_VOLD / KC_VOLU or KC_PGUP / KC_PGDN.",
+                    Style::default().fg(theme.text_muted),
+                ))
+            },
+            |error| {
+                Line::from(Span::styled(
+                    error.as_str(),
+                    Style::default().fg(theme.error),
+                ))
+            },
+        );
+        frame.render_widget(
+            Paragraph::new(hint).block(
+                Block::default()
+                    .title(format!(" Encoder {} ", draft.encoder))
+                    .borders(Borders::ALL),
+            ),
+            chunks[2],
+        );
+
+        let help = help_line(
+            &[("Tab", "Switch field"), ("Enter", "Save"), ("Esc", "Back")],
+            theme,
+        );
+        frame.render_widget(help, chunks[3]);
+    }
+}
+
+fn help_line<'a>(entries: &[(&'a str, &'a str)], theme: &Theme) -> Paragraph<'a> {
+    let spans: Vec<Span> = entries
+        .iter()
+        .flat_map(|(key, label)| {
+            [
+                Span::styled(
+                    *key,
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" {label}  ")),
+            ]
+        })
+        .collect();
+
+    Paragraph::new(Line::from(spans))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Help ")
+                .style(Style::default().bg(theme.background)),
+        )
+        .style(Style::default().bg(theme.background).fg(theme.text))
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,80 @@
+//! Tests for encoder_editor.
+
+use super::*;
+use crate::models::RgbColor;
+use crossterm::event::KeyModifiers;
+
+fn create_test_layer() -> Layer {
+    let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+    layer
+        .encoder_map
+        .set(1, EncoderBinding::new("KC_VOLD", "KC_VOLU"));
+    layer
+}
+
+fn press(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn type_text(editor: &mut EncoderEditor, text: &str) {
+    for c in text.chars() {
+        assert_eq!(editor.handle_input(press(KeyCode::Char(c))), None);
+    }
+}
+
+#[test]
+fn test_new_lists_every_encoder() {
+    let editor = EncoderEditor::new(&create_test_layer(), 3);
+
+    assert_eq!(
+        editor.bindings,
+        vec![None, Some(EncoderBinding::new("KC_VOLD", "KC_VOLU")), None]
+    );
+}
+
+#[test]
+fn test_edit_prefills_and_saves_binding() {
+    let mut editor = EncoderEditor::new(&create_test_layer(), 2);
+    editor.handle_input(press(KeyCode::Down));
+    editor.handle_input(press(KeyCode::Enter));
+
+    // Replace the clockwise keycode
+    editor.handle_input(press(KeyCode::Tab));
+    for _ in 0.."KC_VOLU".len() {
+        editor.handle_input(press(KeyCode::Backspace));
+    }
+    type_text(&mut editor, "KC_MUTE");
+
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Enter)),
+        Some(EncoderEditorEvent::Saved {
+            encoder: 1,
+            binding: EncoderBinding::new("KC_VOLD", "KC_MUTE"),
+        })
+    );
+}
+
+#[test]
+fn test_save_requires_both_directions() {
+    let mut editor = EncoderEditor::new(&create_test_layer(), 1);
+    editor.handle_input(press(KeyCode::Enter));
+    type_text(&mut editor, "KC_LEFT");
+
+    assert_eq!(editor.handle_input(press(KeyCode::Enter)), None);
+    assert!(editor.draft.as_ref().unwrap().error.is_some());
+}
+
+#[test]
+fn test_reset_only_for_bound_encoders() {
+    let mut editor = EncoderEditor::new(&create_test_layer(), 2);
+    assert_eq!(editor.handle_input(press(KeyCode::Char('D'))), None);
+
+    editor.handle_input(press(KeyCode::Down));
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Char('D'))),
+        Some(EncoderEditorEvent::Cleared(1))
+    );
+
+    editor.update_binding(1, None);
+    assert_eq!(editor.bindings[1], None);
+}
@@ -40,6 +40,8 @@ pub enum KeyEditorMode {
     View,
     /// Editing the description field
     EditDescription,
+    /// Editing the per-key tapping term
+    EditTappingTerm,
 }
 
 /// What part of a combo keycode is being edited
@@ -176,6 +178,8 @@ pub struct KeyEditorState {
     pub original_description: Option<String>,
     /// When editing a combo keycode part, which part and the parsed type
     pub combo_edit: Option<(ComboEditPart, ComboKeycodeType)>,
+    /// Buffer for editing the per-key tapping term (empty = layout default)
+    pub tapping_term_buffer: String,
 }
 
 impl Default for KeyEditorState {
@@ -196,6 +200,7 @@ impl KeyEditorState {
             cursor_position: 0,
             original_description: None,
             combo_edit: None,
+            tapping_term_buffer: String::new(),
         }
     }
 
@@ -208,6 +213,7 @@ impl KeyEditorState {
         self.cursor_position = self.description_buffer.len();
         self.original_description.clone_from(&key.description);
         self.combo_edit = None;
+        self.tapping_term_buffer.clear();
     }
 
     /// Start editing the description
@@ -298,6 +304,40 @@ impl KeyEditorState {
         matches!(self.mode, KeyEditorMode::EditDescription)
     }
 
+    /// Start editing the tapping term, prefilled with the key's override
+    pub fn start_edit_tapping_term(&mut self, current: Option<u16>) {
+        self.mode = KeyEditorMode::EditTappingTerm;
+        self.tapping_term_buffer = current.map(|term| term.to_string()).unwrap_or_default();
+    }
+
+    /// Check if we're editing the tapping term
+    #[must_use]
+    pub const fn is_editing_tapping_term(&self) -> bool {
+        matches!(self.mode, KeyEditorMode::EditTappingTerm)
+    }
+
+    /// Stop editing the tapping term
+    pub fn finish_edit_tapping_term(&mut self) {
+        self.mode = KeyEditorMode::View;
+        self.tapping_term_buffer.clear();
+    }
+
+    /// Parse the tapping term buffer: `Ok(None)` clears the override.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the value is not a number between 50 and 1000.
+    pub fn parsed_tapping_term(&self) -> Result<Option<u16>, String> {
+        let value = self.tapping_term_buffer.trim();
+        if value.is_empty() {
+            return Ok(None);
+        }
+        match value.parse::<u16>() {
+            Ok(term) if (50..=1000).contains(&term) => Ok(Some(term)),
+            _ => Err("Tapping term must be between 50 and 1000ms".to_string()),
+        }
+    }
+
     /// Reset the editor state
     pub fn reset(&mut self) {
         *self = Self::new();
@@ -584,6 +624,7 @@ pub fn render_key_editor(f: &mut Frame, state: &AppState) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Keycode display
+            Constraint::Length(1), // Tapping term
             Constraint::Length(4), // Tap-hold breakdown (if applicable)
             Constraint::Length(4), // Description
             Constraint::Min(2),    // Spacer
@@ -612,6 +653,38 @@ pub fn render_key_editor(f: &mut Frame, state: &AppState) {
     );
     f.render_widget(keycode_display, chunks[0]);
 
+    // Tapping term for this position (all layers)
+    let tap_hold = &state.layout.tap_hold_settings;
+    let tapping_term_line = if editor_state.is_editing_tapping_term() {
+        Line::from(vec![
+            Span::styled("Tapping term: ", Style::default().fg(theme.text_muted)),
+            Span::styled(
+                format!("{}█ ms", editor_state.tapping_term_buffer),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(
+                format!("  (empty = layout default {}ms)", tap_hold.tapping_term),
+                Style::default().fg(theme.text_muted),
+            ),
+        ])
+    } else {
+        let value = tap_hold.tapping_term_for(editor_state.position);
+        let source = if tap_hold.key_tapping_term(editor_state.position).is_some() {
+            "this key"
+        } else {
+            "layout default"
+        };
+        Line::from(vec![
+            Span::styled("Tapping term: ", Style::default().fg(theme.text_muted)),
+            Span::styled(format!("{value}ms"), Style::default().fg(theme.text)),
+            Span::styled(
+                format!(" ({source})"),
+                Style::default().fg(theme.text_muted),
+            ),
+        ])
+    };
+    f.render_widget(Paragraph::new(tapping_term_line), chunks[1]);
+
     // Keycode breakdown using the database
     let tap_hold_content = if let Some((label1, val1, label2, val2)) =
         get_keycode_breakdown(&state.keycode_db, &key.keycode, Some(&state.layout))
@@ -647,7 +720,7 @@ pub fn render_key_editor(f: &mut Frame, state: &AppState) {
             .borders(Borders::BOTTOM)
             .border_style(Style::default().fg(theme.inactive)),
     );
-    f.render_widget(tap_hold_display, chunks[1]);
+    f.render_widget(tap_hold_display, chunks[2]);
 
     // Description field
     let desc_style = if editor_state.is_editing() {
@@ -683,7 +756,7 @@ pub fn render_key_editor(f: &mut Frame, state: &AppState) {
                 Style::default().fg(theme.inactive)
             }),
     );
-    f.render_widget(description_display, chunks[2]);
+    f.render_widget(description_display, chunks[3]);
 
     // Check if this is a combo keycode (for showing H/T options)
     let combo_type = parse_combo_keycode(&state.keycode_db, &key.keycode);
@@ -691,7 +764,7 @@ pub fn render_key_editor(f: &mut Frame, state: &AppState) {
     let is_mod_combo = matches!(combo_type, Some(ComboKeycodeType::ModCombo { .. }));
 
     // Actions bar
-    let actions = if editor_state.is_editing() {
+    let actions = if editor_state.is_editing() || editor_state.is_editing_tapping_term() {
         Line::from(vec![
             Span::styled(
                 "Enter",
@@ -740,6 +813,13 @@ pub fn render_key_editor(f: &mut Frame, state: &AppState) {
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(": Edit note  ", Style::default().fg(theme.text_muted)),
+                Span::styled(
+                    "P",
+                    Style::default()
+                        .fg(theme.primary)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(": Tapping term  ", Style::default().fg(theme.text_muted)),
                 Span::styled(
                     "Esc",
                     Style::default()
@@ -787,6 +867,13 @@ pub fn render_key_editor(f: &mut Frame, state: &AppState) {
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(": Edit note  ", Style::default().fg(theme.text_muted)),
+                Span::styled(
+                    "P",
+                    Style::default()
+                        .fg(theme.primary)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(": Tapping term  ", Style::default().fg(theme.text_muted)),
                 Span::styled(
                     "Esc",
                     Style::default()
@@ -812,6 +899,13 @@ pub fn render_key_editor(f: &mut Frame, state: &AppState) {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(": Edit note  ", Style::default().fg(theme.text_muted)),
+            Span::styled(
+                "P",
+                Style::default()
+                    .fg(theme.primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(": Tapping term  ", Style::default().fg(theme.text_muted)),
             Span::styled(
                 "C",
                 Style::default()
@@ -830,7 +924,7 @@ pub fn render_key_editor(f: &mut Frame, state: &AppState) {
     };
 
     let actions_bar = Paragraph::new(actions);
-    f.render_widget(actions_bar, chunks[4]);
+    f.render_widget(actions_bar, chunks[5]);
 }
 
 /// Handle input for the key editor
@@ -838,6 +932,11 @@ pub fn render_key_editor(f: &mut Frame, state: &AppState) {
 pub fn handle_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> anyhow::Result<bool> {
     use crossterm::event::KeyCode;
 
+    if state.key_editor_state.is_editing_tapping_term() {
+        handle_tapping_term_input(state, key.code);
+        return Ok(false);
+    }
+
     // Check if we're in edit mode first (immutable borrow)
     let is_editing = state.key_editor_state.is_editing();
 
@@ -901,6 +1000,14 @@ pub fn handle_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> an
                 state.key_editor_state.start_edit_description();
                 state.set_status("Editing key note. Enter saves. Esc cancels.");
             }
+            KeyCode::Char('p' | 'P') => {
+                let position = state.key_editor_state.position;
+                let current = state.layout.tap_hold_settings.key_tapping_term(position);
+                state.key_editor_state.start_edit_tapping_term(current);
+                state.set_status(
+                    "Tapping term for this key on all layers (ms). Empty uses the layout default.",
+                );
+            }
             KeyCode::Char('c' | 'C') => {
                 // Open color picker
                 if let Some(key) = state.get_selected_key() {
@@ -976,5 +1083,42 @@ pub fn handle_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> an
     Ok(false)
 }
 
+/// Handle input while editing the per-key tapping term
+fn handle_tapping_term_input(state: &mut AppState, code: crossterm::event::KeyCode) {
+    use crossterm::event::KeyCode;
+
+    match code {
+        KeyCode::Esc => {
+            state.key_editor_state.finish_edit_tapping_term();
+            state.set_status("Cancelled tapping term edit");
+        }
+        KeyCode::Enter => match state.key_editor_state.parsed_tapping_term() {
+            Ok(term) => {
+                let position = state.key_editor_state.position;
+                state
+                    .layout
+                    .tap_hold_settings
+                    .set_key_tapping_term(position, term);
+                state.key_editor_state.finish_edit_tapping_term();
+                state.mark_dirty();
+                match term {
+                    Some(term) => state.set_status(format!("Tapping term for this key: {term}ms")),
+                    None => state.set_status("Key uses the layout tapping term"),
+                }
+            }
+            Err(message) => state.set_error(message),
+        },
+        KeyCode::Backspace => {
+            state.key_editor_state.tapping_term_buffer.pop();
+        }
+        KeyCode::Char(c)
+            if c.is_ascii_digit() && state.key_editor_state.tapping_term_buffer.len() < 4 =>
+        {
+            state.key_editor_state.tapping_term_buffer.push(c);
+        }
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_is_key_assigned() {
     assert!(!is_key_assigned("KC_NO"));
@@ -191,3 +189,21 @@ fn test_extract_td_name() {
     assert_eq!(extract_td_name("TD()"), None); // Empty name
     assert_eq!(extract_td_name("KC_A"), None); // Not a TD
 }
+
+#[test]
+fn test_parsed_tapping_term() {
+    let mut state = KeyEditorState::new();
+    state.start_edit_tapping_term(Some(230));
+    assert!(state.is_editing_tapping_term());
+    assert_eq!(state.parsed_tapping_term(), Ok(Some(230)));
+
+    state.tapping_term_buffer.clear();
+    assert_eq!(state.parsed_tapping_term(), Ok(None));
+
+    state.tapping_term_buffer = "20".to_string();
+    assert!(state.parsed_tapping_term().is_err());
+
+    state.finish_edit_tapping_term();
+    assert_eq!(state.mode, KeyEditorMode::View);
+    assert!(state.tapping_term_buffer.is_empty());
+}
@@ -4,7 +4,11 @@
 //! - Simple keycodes (`KC_A`, `KC_SPC`, etc.)
 //! - Tap-hold keycodes (LT, MT, LM, `SH_T`) with dual-line display
 //! - Color type indicators in border (i=individual, k=category, L=layer, d=default)
-//! - RGB color borders based on the color priority system
+//! - A held layer (Alt+H) overlaid on the current layer's keys
+//! - Transparent keys resolved through the lower layers (Alt+T), dimmed
+//! - Drop target marker (`>`) while a key is dragged with the mouse
+//! - RGB color borders based on the color priority system, or only the
+//!   category colors or color overrides (see [`ColorView`])
 
 use ratatui::{
     layout::Rect,
@@ -15,8 +19,9 @@ use ratatui::{
 };
 
 use crate::keycode_db::TapHoldType;
-use crate::models::{ComboAction, ComboDefinition, Position};
-use crate::tui::AppState;
+use crate::models::{ComboAction, ComboDefinition, Layer, Position};
+use crate::services::resolved_keys::resolve_key;
+use crate::tui::{AppState, ColorView};
 
 /// Returns the combo action triggered when this key position participates in
 /// a non-placeholder combo, or `None` if the key is not part of any combo.
@@ -45,16 +50,8 @@ impl KeyboardWidget {
     /// Render the keyboard widget
     #[allow(clippy::too_many_lines)]
     pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
-        use crate::models::keyboard_geometry::terminal_scale;
-
         let theme = &state.theme;
 
-        // Get unified scale factor from config (1.0 = 100%)
-        // Apply to base scale factors from keyboard_geometry
-        let scale_multiplier = state.config.ui.keyboard_scale;
-        let scale_x = terminal_scale::DEFAULT_X_SCALE * scale_multiplier;
-        let scale_y = terminal_scale::DEFAULT_Y_SCALE * scale_multiplier;
-
         // Get current layer
         let layer = if let Some(layer) = state.layout.layers.get(state.current_layer) {
             layer
@@ -84,6 +81,26 @@ impl KeyboardWidget {
             format!(" Layer {}: {} ", state.current_layer, layer.name)
         };
 
+        // Name the color view unless it shows the firmware colors
+        let title = match state.color_view {
+            ColorView::Firmware => title,
+            view => format!("{title}[{}] ", view.label()),
+        };
+
+        // Layer shown over this one while a layer key is held (Alt+H)
+        let held_layer = state
+            .held_layer
+            .and_then(|index| state.layout.layers.get(index).map(|held| (index, held)));
+        let title = match held_layer {
+            Some((index, held)) => format!("{title}[holding {index}: {}] ", held.name),
+            None => title,
+        };
+        let title = if state.show_resolved_keys {
+            format!("{title}[resolved] ")
+        } else {
+            title
+        };
+
         // Render outer container
         let outer_block = Block::default()
             .title(title)
@@ -92,81 +109,21 @@ impl KeyboardWidget {
             .style(Style::default().bg(theme.background));
         f.render_widget(outer_block, area);
 
-        // Calculate inner area for keys (inside the outer border)
-        let inner_area = Rect {
-            x: area.x + 1,
-            y: area.y + 1,
-            width: area.width.saturating_sub(2),
-            height: area.height.saturating_sub(2),
-        };
+        let (inner_area, keys_area, footer_height) = Self::areas(area, state);
 
-        let footer_height = if inner_area.height > KEYBOARD_FOOTER_HEIGHT + 4 {
-            KEYBOARD_FOOTER_HEIGHT
-        } else {
-            0
-        };
-        let keys_area = Rect {
-            x: inner_area.x,
-            y: inner_area.y,
-            width: inner_area.width,
-            height: inner_area.height.saturating_sub(footer_height),
-        };
-
-        // Calculate key dimensions from scaled values
-        // Base: 1u width = scale_x chars, 1u height = scale_y lines
-        // Add 2 for borders, enforce minimums for content visibility
-        let default_key_width: usize = ((scale_x + 2.0) as usize).max(7);
-        let default_key_height: usize = ((scale_y + 2.0) as usize).max(4);
+        // Label buffers reused across keys to avoid per-key allocations
+        let mut top_label = String::new();
+        let mut bottom_label = String::new();
 
         // Render each key as an individual block
         for key in &layer.keys {
             let row = key.position.row as usize;
             let col = key.position.col as usize;
 
-            // Try to get actual key geometry for position-aware rendering
-            let key_geometry = state
-                .mapping
-                .visual_to_matrix_pos(key.position.row, key.position.col)
-                .and_then(|matrix_pos| state.geometry.get_key_by_matrix(matrix_pos));
-
-            // Use actual geometry dimensions if available, otherwise use defaults
-            // For grid-based layouts (visual row/col), we still position based on grid
-            // but can use actual key dimensions for sizing
-            let (key_width, key_height) = if let Some(geom) = key_geometry {
-                // Use actual key dimensions from geometry with custom scale, with minimum for borders
-                let w = (geom.terminal_width_with_scale(scale_x) as usize).max(default_key_width);
-                let h = (geom.terminal_height_with_scale(scale_y) as usize).max(default_key_height);
-                (w, h)
-            } else {
-                (default_key_width, default_key_height)
-            };
-
-            // Calculate key position based on visual grid
-            // Note: For proper physical layout rendering, we'd use terminal_x/y,
-            // but the current navigation system expects a grid-based layout
-            let key_x = keys_area.x + (col * default_key_width) as u16;
-            let key_y = keys_area.y + (row * default_key_height) as u16;
-
-            // Skip if key is outside visible area
-            if key_x >= keys_area.x + keys_area.width || key_y >= keys_area.y + keys_area.height {
+            let Some(key_area) = Self::key_area(keys_area, key.position, state) else {
                 continue;
-            }
-
-            let key_area = Rect {
-                x: key_x,
-                y: key_y,
-                width: key_width.min((keys_area.x + keys_area.width).saturating_sub(key_x) as usize)
-                    as u16,
-                height: key_height
-                    .min((keys_area.y + keys_area.height).saturating_sub(key_y) as usize)
-                    as u16,
             };
 
-            // Skip if key area is too small
-            if key_area.width < 7 || key_area.height < 4 {
-                continue;
-            }
-
             let is_selected = row == state.selected_position.row as usize
                 && col == state.selected_position.col as usize;
 
@@ -186,81 +143,152 @@ impl KeyboardWidget {
                     false
                 };
 
+            // Check if a mouse drag would drop onto this key
+            let is_drop_target = state
+                .key_drag
+                .is_some_and(|drag| drag.over == Some(key.position) && drag.from != key.position);
+
             // Check if this key should flash (paste feedback)
             let is_flashing = state
                 .flash_highlight
                 .is_some_and(|(layer, pos, _)| layer == state.current_layer && pos == key.position);
 
-            // Resolve key color for display (respects colors_enabled and inactive_key_behavior)
-            let (key_color, color_indicator) = if let Some(current_layer) =
-                state.layout.layers.get(state.current_layer)
-            {
-                if !current_layer.layer_colors_enabled {
-                    // Layer colors disabled - use theme text_muted for visible border
-                    (theme.text_muted, "-")
-                } else {
-                    // Use resolve_display_color which considers inactive_key_behavior
-                    let (rgb, is_key_specific) =
-                        state.layout.resolve_display_color(state.current_layer, key);
-
-                    // Apply RGB settings (brightness and master switch)
-                    let final_rgb = state.layout.apply_rgb_settings(rgb);
-
-                    // Check if the color is too dark to be visible (e.g., black from "Off" behavior or master switch)
-                    // If brightness is below threshold, use theme.text_muted for visibility
-                    let brightness =
-                        (u16::from(final_rgb.r) + u16::from(final_rgb.g) + u16::from(final_rgb.b))
-                            / 3;
-                    let color = if brightness < 30 {
-                        // Color too dark for TUI visibility, use muted theme color
-                        theme.text_muted
-                    } else {
-                        Color::Rgb(final_rgb.r, final_rgb.g, final_rgb.b)
-                    };
-
-                    let indicator = if is_key_specific {
-                        if key.color_override.is_some() {
-                            "i" // Individual override
-                        } else {
-                            "c" // Key category
-                        }
+            // Resolve key color for the selected color view
+            let has_key_category = key
+                .category_id
+                .as_deref()
+                .is_some_and(|id| state.layout.get_category(id).is_some());
+            let (rgb, color_indicator) = match state.color_view {
+                // Layer colors disabled - the firmware leaves these LEDs off
+                ColorView::Firmware if !layer.layer_colors_enabled => (None, "-"),
+                ColorView::Firmware => {
+                    let indicator = if key.color_override.is_some() {
+                        "i" // Individual override
+                    } else if has_key_category {
+                        "c" // Key category
                     } else if layer.category_id.is_some() {
                         "L" // Layer category
                     } else {
                         "d" // Layer default
                     };
-                    (color, indicator)
+                    let rgb = state
+                        .layout
+                        .resolve_firmware_color(state.current_layer, key);
+                    (Some(rgb), indicator)
+                }
+                ColorView::Categories => {
+                    let rgb = state
+                        .layout
+                        .resolve_category_color(state.current_layer, key);
+                    let indicator = match rgb {
+                        None => "-",
+                        Some(_) if has_key_category => "c",
+                        Some(_) => "L",
+                    };
+                    (rgb, indicator)
+                }
+                ColorView::Overrides => {
+                    let indicator = if key.color_override.is_some() {
+                        "i"
+                    } else {
+                        "-"
+                    };
+                    (key.color_override, indicator)
                 }
-            } else {
-                // Fallback - use theme text_muted for visible border
-                (theme.text_muted, "-")
+            };
+
+            // Colors too dark to see (e.g., black from "Off" behavior or the
+            // master switch) and uncolored keys use the muted theme color
+            let key_color = match rgb {
+                Some(rgb) if (u16::from(rgb.r) + u16::from(rgb.g) + u16::from(rgb.b)) / 3 >= 30 => {
+                    Color::Rgb(rgb.r, rgb.g, rgb.b)
+                }
+                _ => theme.text_muted,
             };
 
             // Parse keycode to determine if it's a tap-hold type
             let tap_hold = Self::parse_tap_hold_keycode(&key.keycode, state);
 
+            // Key the held layer puts at this position, unless it falls through
+            let held_key = held_layer
+                .and_then(|(_, held)| held.get_key(key.position))
+                .filter(|held_key| !held_key.is_transparent());
+
+            // Key a transparent key falls through to (Alt+T)
+            let resolved = if state.show_resolved_keys && key.is_transparent() {
+                resolve_key(&state.layout.layers, state.current_layer, key.position)
+                    .filter(|resolved| resolved.from_layer != state.current_layer)
+            } else {
+                None
+            };
+
             // Build content lines based on keycode type
-            let content: Vec<Line> = if let Some(th) = &tap_hold {
+            top_label.clear();
+            bottom_label.clear();
+            let content: [Line; 2] = if let Some(held_key) = held_key {
+                // Held layer: current key dimmed on top, held layer's key below
+                Self::write_label(&mut top_label, ' ', state.key_label(&key.keycode), 5);
+                let held_label = state.key_label(&held_key.keycode);
+                Self::write_label(&mut bottom_label, '▲', held_label, 5);
+                [
+                    Line::from(Span::styled(
+                        top_label.as_str(),
+                        Style::default()
+                            .fg(theme.text_muted)
+                            .add_modifier(Modifier::DIM),
+                    )),
+                    Line::from(Span::styled(
+                        bottom_label.as_str(),
+                        Style::default()
+                            .fg(theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    )),
+                ]
+            } else if let Some(resolved) = &resolved {
+                // Transparent key: source layer on top, its keycode dimmed below
+                Self::write_label(&mut top_label, '▽', &format!("L{}", resolved.from_layer), 5);
+                Self::write_label(
+                    &mut bottom_label,
+                    ' ',
+                    state.key_label(&resolved.keycode),
+                    5,
+                );
+                [
+                    Line::from(Span::styled(
+                        top_label.as_str(),
+                        Style::default().fg(theme.text_muted),
+                    )),
+                    Line::from(Span::styled(
+                        bottom_label.as_str(),
+                        Style::default()
+                            .fg(theme.text_muted)
+                            .add_modifier(Modifier::DIM),
+                    )),
+                ]
+            } else if let Some(th) = &tap_hold {
                 // Tap-hold keycode: show hold on top, tap on bottom
-                vec![
-                    Line::from(vec![Span::styled(
-                        format!("▼{:<5}", Self::truncate(&th.hold, 5)),
+                Self::write_label(&mut top_label, '▼', &th.hold, 5);
+                Self::write_label(&mut bottom_label, ' ', &th.tap, 5);
+                [
+                    Line::from(Span::styled(
+                        top_label.as_str(),
                         Style::default().fg(theme.text_muted),
-                    )]),
-                    Line::from(vec![Span::styled(
-                        format!(" {:<5}", Self::truncate(&th.tap, 5)),
+                    )),
+                    Line::from(Span::styled(
+                        bottom_label.as_str(),
                         Style::default().fg(theme.text),
-                    )]),
+                    )),
                 ]
             } else {
                 // Simple keycode: center vertically with two lines
-                let display = crate::keycode_db::format::strip_kc_prefix(&key.keycode);
-                vec![
+                let display = state.key_label(&key.keycode);
+                Self::write_label(&mut bottom_label, ' ', display, 5);
+                [
                     Line::from(""), // Empty first line for vertical centering
-                    Line::from(vec![Span::styled(
-                        format!(" {:<5}", Self::truncate(&display, 5)),
+                    Line::from(Span::styled(
+                        bottom_label.as_str(),
                         Style::default().fg(theme.text),
-                    )]),
+                    )),
                 ]
             };
 
@@ -283,9 +311,7 @@ impl KeyboardWidget {
             };
             let (combo_overlay_color, combo_letter) = match combo_action {
                 Some(ComboAction::Bootloader) => (Some(theme.combo_bootloader), Some('B')),
-                Some(ComboAction::DisableEffects) => {
-                    (Some(theme.combo_disable_effects), Some('E'))
-                }
+                Some(ComboAction::DisableEffects) => (Some(theme.combo_disable_effects), Some('E')),
                 Some(ComboAction::DisableLighting) => {
                     (Some(theme.combo_disable_lighting), Some('L'))
                 }
@@ -301,12 +327,16 @@ impl KeyboardWidget {
                 '*'
             } else if is_selected {
                 '@'
+            } else if is_drop_target {
+                '>'
             } else if is_swap_first {
                 's'
             } else if is_in_selection {
                 '+'
             } else if is_cut_source {
                 'x'
+            } else if key.locked {
+                '#'
             } else if has_hold_like_inbound {
                 '!'
             } else {
@@ -322,7 +352,7 @@ impl KeyboardWidget {
                 key_color,
                 is_selected,
                 is_cut_source,
-                is_in_selection,
+                is_in_selection || is_drop_target,
                 is_swap_first,
                 is_flashing,
                 has_hold_like_inbound,
@@ -345,6 +375,117 @@ impl KeyboardWidget {
         }
     }
 
+    /// Returns the inner area (inside the outer border), the area keys are
+    /// drawn in, and the footer height for a widget drawn into `area`.
+    fn areas(area: Rect, state: &AppState) -> (Rect, Rect, u16) {
+        let inner_area = Rect {
+            x: area.x + 1,
+            y: area.y + 1,
+            width: area.width.saturating_sub(2),
+            height: area.height.saturating_sub(2),
+        };
+
+        // Keyboards with encoders get an extra footer row for their bindings
+        let footer_rows = if state.geometry.encoder_count > 0 {
+            KEYBOARD_FOOTER_HEIGHT + 1
+        } else {
+            KEYBOARD_FOOTER_HEIGHT
+        };
+        let footer_height = if inner_area.height > footer_rows + 4 {
+            footer_rows
+        } else {
+            0
+        };
+        let keys_area = Rect {
+            x: inner_area.x,
+            y: inner_area.y,
+            width: inner_area.width,
+            height: inner_area.height.saturating_sub(footer_height),
+        };
+        (inner_area, keys_area, footer_height)
+    }
+
+    /// Returns where the key at `position` is drawn inside `keys_area`, or
+    /// `None` if it is outside the visible area or too small to draw.
+    fn key_area(keys_area: Rect, position: Position, state: &AppState) -> Option<Rect> {
+        use crate::models::keyboard_geometry::terminal_scale;
+
+        // Get unified scale factor from config (1.0 = 100%)
+        // Apply to base scale factors from keyboard_geometry
+        let scale_multiplier = state.config.ui.keyboard_scale;
+        let scale_x = terminal_scale::DEFAULT_X_SCALE * scale_multiplier;
+        let scale_y = terminal_scale::DEFAULT_Y_SCALE * scale_multiplier;
+
+        // Calculate key dimensions from scaled values
+        // Base: 1u width = scale_x chars, 1u height = scale_y lines
+        // Add 2 for borders, enforce minimums for content visibility
+        let default_key_width: usize = ((scale_x + 2.0) as usize).max(7);
+        let default_key_height: usize = ((scale_y + 2.0) as usize).max(4);
+
+        // Try to get actual key geometry for position-aware rendering
+        let key_geometry = state
+            .mapping
+            .visual_to_matrix_pos(position.row, position.col)
+            .and_then(|matrix_pos| state.geometry.get_key_by_matrix(matrix_pos));
+
+        // Use actual geometry dimensions if available, otherwise use defaults
+        // For grid-based layouts (visual row/col), we still position based on grid
+        // but can use actual key dimensions for sizing
+        let (key_width, key_height) = if let Some(geom) = key_geometry {
+            // Use actual key dimensions from geometry with custom scale, with minimum for borders
+            let w = (geom.terminal_width_with_scale(scale_x) as usize).max(default_key_width);
+            let h = (geom.terminal_height_with_scale(scale_y) as usize).max(default_key_height);
+            (w, h)
+        } else {
+            (default_key_width, default_key_height)
+        };
+
+        // Calculate key position based on visual grid
+        // Note: For proper physical layout rendering, we'd use terminal_x/y,
+        // but the current navigation system expects a grid-based layout
+        let key_x = keys_area.x + (usize::from(position.col) * default_key_width) as u16;
+        let key_y = keys_area.y + (usize::from(position.row) * default_key_height) as u16;
+
+        // Skip if key is outside visible area
+        if key_x >= keys_area.x + keys_area.width || key_y >= keys_area.y + keys_area.height {
+            return None;
+        }
+
+        let key_area = Rect {
+            x: key_x,
+            y: key_y,
+            width: key_width.min((keys_area.x + keys_area.width).saturating_sub(key_x) as usize)
+                as u16,
+            height: key_height.min((keys_area.y + keys_area.height).saturating_sub(key_y) as usize)
+                as u16,
+        };
+
+        // Skip if key area is too small
+        if key_area.width < 7 || key_area.height < 4 {
+            return None;
+        }
+        Some(key_area)
+    }
+
+    /// Returns the position of the key drawn at the given screen cell of a
+    /// widget drawn into `area`.
+    #[must_use]
+    pub fn key_at(area: Rect, state: &AppState, column: u16, row: u16) -> Option<Position> {
+        let layer = state.layout.layers.get(state.current_layer)?;
+        let (_, keys_area, _) = Self::areas(area, state);
+        let cell = ratatui::layout::Position::new(column, row);
+
+        // Later keys are drawn over earlier ones, so the last match is visible
+        layer
+            .keys
+            .iter()
+            .rev()
+            .map(|key| key.position)
+            .find(|&position| {
+                Self::key_area(keys_area, position, state).is_some_and(|rect| rect.contains(cell))
+            })
+    }
+
     fn render_footer(f: &mut Frame, area: Rect, state: &AppState) {
         let theme = &state.theme;
         let selected_key = state.get_selected_key();
@@ -364,33 +505,66 @@ impl KeyboardWidget {
         let actions =
             "Actions: Enter key actions  Ctrl+S save  Ctrl+B build  Shift+Y layout variant  ? help";
         let mut legend = format!(
-            "Legend: @ selected  + multi  x cut  s swap  ! inbound hold  * flash  • {}  • red outer border = inbound hold target",
+            "Legend: @ selected  + multi  x cut  s swap  > drop  # locked  ! inbound hold  * flash  • {}  • red outer border = inbound hold target",
             Self::color_indicator_legend()
         );
 
         // Only show the combo legend when combos are enabled and at least one
         // combo is defined; otherwise the row would be noise.
-        if state.layout.combo_settings.enabled
-            && !state.layout.combo_settings.combos.is_empty()
-        {
+        if state.layout.combo_settings.enabled && !state.layout.combo_settings.combos.is_empty() {
             legend.push_str(
                 "  • B Bootloader combo  E Disable-Effects combo  L Disable-Lighting combo",
             );
         }
 
-        let footer = Paragraph::new(vec![
+        let mut lines = vec![
             Line::from(Span::styled(
                 selection_text,
                 Style::default().fg(theme.text),
             )),
             Line::from(Span::styled(actions, Style::default().fg(theme.text_muted))),
             Line::from(Span::styled(legend, Style::default().fg(theme.text_muted))),
-        ])
-        .style(Style::default().bg(theme.background));
+        ];
+        if let Some(layer) = state.layout.layers.get(state.current_layer) {
+            if let Some(summary) = Self::encoder_summary(layer, state.geometry.encoder_count) {
+                lines.push(Line::from(Span::styled(
+                    summary,
+                    Style::default().fg(theme.text),
+                )));
+            }
+        }
+        let footer = Paragraph::new(lines).style(Style::default().bg(theme.background));
 
         f.render_widget(footer, area);
     }
 
+    /// Summarizes the layer's encoder bindings, e.g.
+    /// `Encoders (Shift+N): E0 ↺ VOLD ↻ VOLU  E1 default`.
+    ///
+    /// Returns `None` when the keyboard has no encoders.
+    fn encoder_summary(layer: &Layer, encoder_count: u8) -> Option<String> {
+        use crate::keycode_db::format::strip_kc_prefix;
+
+        if encoder_count == 0 {
+            return None;
+        }
+        let encoders: Vec<String> = (0..encoder_count)
+            .map(|encoder| {
+                layer.encoder_map.get(encoder).map_or_else(
+                    || format!("E{encoder} default"),
+                    |binding| {
+                        format!(
+                            "E{encoder} ↺ {} ↻ {}",
+                            strip_kc_prefix(&binding.ccw),
+                            strip_kc_prefix(&binding.cw)
+                        )
+                    },
+                )
+            })
+            .collect();
+        Some(format!("Encoders (Shift+N): {}", encoders.join("  ")))
+    }
+
     fn color_indicator_legend() -> &'static str {
         "i key override  c key category  L layer category  d layer default  - colors off"
     }
@@ -414,84 +588,79 @@ impl KeyboardWidget {
         theme: &crate::tui::Theme,
     ) {
         // Determine colors based on selection, cut state, multi-selection, swap, flash, and inbound holds
-        let (
-            border_style,
-            content_bg,
-            content_fg,
-            overlay_border_color,
-            combo_overlay_color,
-        ) = if is_flashing {
-            // Flash highlight: bright accent background
-            (
-                Style::default()
-                    .fg(theme.success)
-                    .add_modifier(Modifier::BOLD),
-                Some(theme.success),
-                theme.background,
-                None,
-                combo_overlay_color,
-            )
-        } else if is_selected {
-            (
-                Style::default()
-                    .fg(theme.accent)
-                    .add_modifier(Modifier::BOLD),
-                Some(theme.accent),
-                theme.background,
-                None,
-                combo_overlay_color,
-            )
-        } else if is_swap_first {
-            // First key in swap mode: distinct yellow/warning border
-            (
-                Style::default()
-                    .fg(theme.warning)
-                    .add_modifier(Modifier::BOLD),
-                Some(theme.surface),
-                theme.text,
-                None,
-                combo_overlay_color,
-            )
-        } else if is_in_selection {
-            // Multi-selection: highlighted but not as prominent as primary selection
-            (
-                Style::default()
-                    .fg(theme.warning)
-                    .add_modifier(Modifier::BOLD),
-                Some(theme.surface),
-                theme.text,
-                None,
-                combo_overlay_color,
-            )
-        } else if is_cut_source {
-            // Cut source: dimmed appearance
-            (
-                Style::default()
-                    .fg(border_color)
-                    .add_modifier(Modifier::DIM),
-                None,
-                theme.text_muted,
-                None,
-                combo_overlay_color,
-            )
-        } else if has_hold_like_inbound {
-            // Inbound hold-like reference: keep base border color, but add red overlay border
-            (
-                Style::default().fg(border_color),
-                None,
-                theme.text,
-                Some(theme.error),
-                combo_overlay_color,
-            )
-        } else {
-            (
-                Style::default().fg(border_color),
-                None,
-                theme.text,
-                None,
-                combo_overlay_color,
-            )
-        };
+        let (border_style, content_bg, content_fg, overlay_border_color, combo_overlay_color) =
+            if is_flashing {
+                // Flash highlight: bright accent background
+                (
+                    Style::default()
+                        .fg(theme.success)
+                        .add_modifier(Modifier::BOLD),
+                    Some(theme.success),
+                    theme.background,
+                    None,
+                    combo_overlay_color,
+                )
+            } else if is_selected {
+                (
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                    Some(theme.accent),
+                    theme.background,
+                    None,
+                    combo_overlay_color,
+                )
+            } else if is_swap_first {
+                // First key in swap mode: distinct yellow/warning border
+                (
+                    Style::default()
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                    Some(theme.surface),
+                    theme.text,
+                    None,
+                    combo_overlay_color,
+                )
+            } else if is_in_selection {
+                // Multi-selection: highlighted but not as prominent as primary selection
+                (
+                    Style::default()
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                    Some(theme.surface),
+                    theme.text,
+                    None,
+                    combo_overlay_color,
+                )
+            } else if is_cut_source {
+                // Cut source: dimmed appearance
+                (
+                    Style::default()
+                        .fg(border_color)
+                        .add_modifier(Modifier::DIM),
+                    None,
+                    theme.text_muted,
+                    None,
+                    combo_overlay_color,
+                )
+            } else if has_hold_like_inbound {
+                // Inbound hold-like reference: keep base border color, but add red overlay border
+                (
+                    Style::default().fg(border_color),
+                    None,
+                    theme.text,
+                    Some(theme.error),
+                    combo_overlay_color,
+                )
+            } else {
+                (
+                    Style::default().fg(border_color),
+                    None,
+                    theme.text,
+                    None,
+                    combo_overlay_color,
+                )
+            };
 
         // Draw the custom border with indicator in top-right corner
         let buf = f.buffer_mut();
@@ -643,8 +812,9 @@ impl KeyboardWidget {
             TapHoldType::LayerTap => {
                 // LT(layer, keycode) - Layer Tap
                 let layer_display = Self::resolve_layer_display(&info.arg1, state);
-                let tap_display =
-                    crate::keycode_db::format::strip_kc_prefix(info.arg2.as_deref().unwrap_or(""));
+                let tap_display = state
+                    .key_label(info.arg2.as_deref().unwrap_or(""))
+                    .to_string();
                 Some(TapHoldKeycode {
                     hold: format!("L{layer_display}"),
                     tap: tap_display,
@@ -653,8 +823,9 @@ impl KeyboardWidget {
             TapHoldType::ModTap => {
                 // MT(mod, keycode) - Custom Mod Tap
                 let mod_display = crate::keycode_db::format::format_modifier(&info.arg1);
-                let tap_display =
-                    crate::keycode_db::format::strip_kc_prefix(info.arg2.as_deref().unwrap_or(""));
+                let tap_display = state
+                    .key_label(info.arg2.as_deref().unwrap_or(""))
+                    .to_string();
                 Some(TapHoldKeycode {
                     hold: mod_display,
                     tap: tap_display,
@@ -668,7 +839,7 @@ impl KeyboardWidget {
                     .get_mod_tap_display(&info.prefix)
                     .unwrap_or("MOD")
                     .to_string();
-                let tap_display = crate::keycode_db::format::strip_kc_prefix(&info.arg1);
+                let tap_display = state.key_label(&info.arg1).to_string();
                 Some(TapHoldKeycode {
                     hold: mod_display,
                     tap: tap_display,
@@ -686,7 +857,7 @@ impl KeyboardWidget {
             }
             TapHoldType::SwapHands => {
                 // SH_T(keycode) - Swap Hands Tap
-                let tap_display = crate::keycode_db::format::strip_kc_prefix(&info.arg1);
+                let tap_display = state.key_label(&info.arg1).to_string();
                 Some(TapHoldKeycode {
                     hold: "SWAP".to_string(),
                     tap: tap_display,
@@ -709,13 +880,15 @@ impl KeyboardWidget {
         layer_ref.to_string()
     }
 
-    /// Truncate a string to a maximum length
-    fn truncate(s: &str, max_len: usize) -> String {
-        if s.len() <= max_len {
-            s.to_string()
-        } else {
-            s.chars().take(max_len).collect()
+    /// Appends `marker` followed by `text` truncated and padded to `width` characters
+    fn write_label(buf: &mut String, marker: char, text: &str, width: usize) {
+        buf.push(marker);
+        let mut len = 0;
+        for ch in text.chars().take(width) {
+            buf.push(ch);
+            len += 1;
         }
+        buf.extend(std::iter::repeat_n(' ', width - len));
     }
 }
 
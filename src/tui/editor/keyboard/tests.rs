@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_format_simple_keycode() {
     assert_eq!(crate::keycode_db::format::strip_kc_prefix("KC_A"), "A");
@@ -38,10 +36,18 @@ fn test_format_modifier() {
 }
 
 #[test]
-fn test_truncate() {
-    assert_eq!(KeyboardWidget::truncate("ABC", 5), "ABC");
-    assert_eq!(KeyboardWidget::truncate("ABCDEF", 5), "ABCDE");
-    assert_eq!(KeyboardWidget::truncate("", 5), "");
+fn test_write_label() {
+    let mut buf = String::new();
+    KeyboardWidget::write_label(&mut buf, ' ', "ABC", 5);
+    assert_eq!(buf, " ABC  ");
+
+    buf.clear();
+    KeyboardWidget::write_label(&mut buf, '▼', "ABCDEF", 5);
+    assert_eq!(buf, "▼ABCDE");
+
+    buf.clear();
+    KeyboardWidget::write_label(&mut buf, ' ', "", 5);
+    assert_eq!(buf, "      ");
 }
 
 #[test]
@@ -117,3 +123,42 @@ fn test_combo_action_at_ignores_placeholders() {
 fn test_combo_action_at_handles_empty_list() {
     assert_eq!(combo_action_at(&[], Position::new(0, 0)), None);
 }
+
+#[test]
+fn test_encoder_summary() {
+    use crate::models::{EncoderBinding, RgbColor};
+
+    let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+    assert_eq!(KeyboardWidget::encoder_summary(&layer, 0), None);
+
+    layer
+        .encoder_map
+        .set(0, EncoderBinding::new("KC_VOLD", "KC_VOLU"));
+    assert_eq!(
+        KeyboardWidget::encoder_summary(&layer, 2).as_deref(),
+        Some("Encoders (Shift+N): E0 ↺ VOLD ↻ VOLU  E1 default")
+    );
+}
+
+#[test]
+fn test_host_layout_labels() {
+    let mut state = AppState::new(
+        crate::models::Layout::new("Host").unwrap(),
+        None,
+        crate::models::KeyboardGeometry::new("test", "test", 1, 1),
+        crate::models::VisualLayoutMapping::default(),
+        crate::config::Config::default(),
+    )
+    .unwrap();
+    assert_eq!(state.key_label("KC_Y"), "Y");
+
+    state.host_layout = Some(crate::services::host_layout::HostLayout::from_xkb_keymap(
+        "de",
+        "xkb_symbols { key <AD06> { [ z, Z ] }; };",
+    ));
+    assert_eq!(state.key_label("KC_Y"), "Z");
+    assert_eq!(state.key_label("KC_ENT"), "ENT");
+
+    let tap_hold = KeyboardWidget::parse_tap_hold_keycode("LCTL_T(KC_Y)", &state).unwrap();
+    assert_eq!(tap_hold.tap, "Z");
+}
@@ -30,6 +30,8 @@ pub enum MetadataEditorEvent {
         author: String,
         /// Updated tags
         tags: Vec<String>,
+        /// Updated QMK converter (`None` for the stock controller)
+        converter: Option<String>,
         /// Whether the name was changed (for renaming)
         name_changed: bool,
     },
@@ -51,6 +53,8 @@ pub enum MetadataField {
     Author,
     /// Layout tags field
     Tags,
+    /// QMK converter field
+    Converter,
 }
 
 impl MetadataField {
@@ -61,7 +65,8 @@ impl MetadataField {
             Self::Name => Self::Description,
             Self::Description => Self::Author,
             Self::Author => Self::Tags,
-            Self::Tags => Self::Name,
+            Self::Tags => Self::Converter,
+            Self::Converter => Self::Name,
         }
     }
 
@@ -69,10 +74,11 @@ impl MetadataField {
     #[must_use]
     pub const fn previous(self) -> Self {
         match self {
-            Self::Name => Self::Tags,
+            Self::Name => Self::Converter,
             Self::Description => Self::Name,
             Self::Author => Self::Description,
             Self::Tags => Self::Author,
+            Self::Converter => Self::Tags,
         }
     }
 
@@ -84,6 +90,7 @@ impl MetadataField {
             Self::Description => "Description",
             Self::Author => "Author",
             Self::Tags => "Tags",
+            Self::Converter => "Controller Converter",
         }
     }
 
@@ -95,6 +102,10 @@ impl MetadataField {
             Self::Description => "Long description of the layout",
             Self::Author => "Creator name",
             Self::Tags => "Comma-separated keywords (lowercase, hyphens only)",
+            Self::Converter => {
+                "QMK converter for a swapped controller, passed to qmk compile as CONVERT_TO \
+                 (e.g. kb2040, rp2040_ce, elite_pi, liatris, proton_c). Leave empty for the stock MCU"
+            }
         }
     }
 }
@@ -112,6 +123,8 @@ pub struct MetadataEditorState {
     pub author: String,
     /// Tags field value (comma-separated)
     pub tags_input: String,
+    /// Converter field value (empty for none)
+    pub converter: String,
     /// Original name when editor was opened (for detecting renames)
     pub original_name: String,
 }
@@ -126,6 +139,7 @@ impl MetadataEditorState {
             description: metadata.description.clone(),
             author: metadata.author.clone(),
             tags_input: metadata.tags.join(", "),
+            converter: metadata.converter.clone().unwrap_or_default(),
             original_name: metadata.name.clone(),
         }
     }
@@ -137,6 +151,7 @@ impl MetadataEditorState {
             MetadataField::Description => &mut self.description,
             MetadataField::Author => &mut self.author,
             MetadataField::Tags => &mut self.tags_input,
+            MetadataField::Converter => &mut self.converter,
         }
     }
 
@@ -160,6 +175,13 @@ impl MetadataEditorState {
             .collect()
     }
 
+    /// Parse the converter, treating an empty field as none.
+    #[must_use]
+    pub fn parse_converter(&self) -> Option<String> {
+        let converter = self.converter.trim().to_lowercase();
+        (!converter.is_empty()).then_some(converter)
+    }
+
     /// Validate the metadata.
     pub fn validate(&self) -> Result<(), String> {
         // Name validation
@@ -185,6 +207,10 @@ impl MetadataEditorState {
             }
         }
 
+        if let Some(converter) = self.parse_converter() {
+            LayoutMetadata::validate_converter(&converter).map_err(|e| e.to_string())?;
+        }
+
         Ok(())
     }
 
@@ -203,6 +229,7 @@ impl Default for MetadataEditorState {
             description: String::new(),
             author: String::new(),
             tags_input: String::new(),
+            converter: String::new(),
             original_name: String::new(),
         }
     }
@@ -238,6 +265,7 @@ impl Component for MetadataEditor {
                         description: self.state.description.clone(),
                         author: self.state.author.clone(),
                         tags: self.state.parse_tags(),
+                        converter: self.state.parse_converter(),
                         name_changed: self.state.name_changed(),
                     })
                 } else {
@@ -257,7 +285,7 @@ impl Component for MetadataEditor {
 
 /// Render the metadata editor dialog.
 pub fn render_metadata_editor(f: &mut Frame, state: &MetadataEditorState, theme: &Theme) {
-    let area = centered_rect(70, 60, f.area());
+    let area = centered_rect(70, 70, f.area());
 
     // Clear the background
     f.render_widget(Clear, area);
@@ -279,6 +307,7 @@ pub fn render_metadata_editor(f: &mut Frame, state: &MetadataEditorState, theme:
             Constraint::Length(3), // Description
             Constraint::Length(3), // Author
             Constraint::Length(3), // Tags
+            Constraint::Length(3), // Converter
             Constraint::Min(1),    // Help text
             Constraint::Length(2), // Controls
         ])
@@ -318,13 +347,21 @@ pub fn render_metadata_editor(f: &mut Frame, state: &MetadataEditorState, theme:
         state.active_field == MetadataField::Tags,
         theme,
     );
+    render_field(
+        f,
+        chunks[4],
+        MetadataField::Converter,
+        &state.converter,
+        state.active_field == MetadataField::Converter,
+        theme,
+    );
 
     // Render help text for active field
     let help_text = state.active_field.help_text();
     let help_paragraph = Paragraph::new(help_text)
         .style(Style::default().fg(theme.text_muted))
         .wrap(Wrap { trim: true });
-    f.render_widget(help_paragraph, chunks[4]);
+    f.render_widget(help_paragraph, chunks[5]);
 
     // Render controls
     let controls_text = vec![Line::from(vec![
@@ -358,7 +395,7 @@ pub fn render_metadata_editor(f: &mut Frame, state: &MetadataEditorState, theme:
         Span::raw(" prev field"),
     ])];
     let controls = Paragraph::new(controls_text).alignment(Alignment::Center);
-    f.render_widget(controls, chunks[5]);
+    f.render_widget(controls, chunks[6]);
 }
 
 /// Render a single field.
@@ -0,0 +1,167 @@
+//! Layer minimap strip rendered under the keyboard widget.
+//!
+//! Each layer is drawn as a tiny tile showing its color fingerprint: one
+//! terminal cell per key column and one half-block per key row. The current
+//! layer's tile is highlighted. Clicking a tile switches to that layer.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::models::{Layer, Position};
+use crate::tui::AppState;
+
+/// Minimum inner width of a tile, so the layer number fits in the title.
+const MIN_TILE_INNER_WIDTH: u16 = 5;
+
+/// Gap between two tiles.
+const TILE_GAP: u16 = 1;
+
+/// Layer minimap strip.
+pub struct LayerMinimap;
+
+impl LayerMinimap {
+    /// Returns the height of the strip for the current layout.
+    #[must_use]
+    pub fn height(state: &AppState) -> u16 {
+        let (rows, _) = grid_size(&state.layout.layers);
+        rows.div_ceil(2) + 2
+    }
+
+    /// Returns the screen area of each visible tile, as `(layer index, area)`.
+    ///
+    /// When not all layers fit, the visible window is centered on the
+    /// current layer.
+    #[must_use]
+    pub fn tile_areas(area: Rect, state: &AppState) -> Vec<(usize, Rect)> {
+        let layer_count = state.layout.layers.len();
+        let (_, cols) = grid_size(&state.layout.layers);
+        let tile_width = cols.max(MIN_TILE_INNER_WIDTH) + 2;
+        let fit = usize::from((area.width + TILE_GAP) / (tile_width + TILE_GAP));
+        if fit == 0 || layer_count == 0 {
+            return Vec::new();
+        }
+
+        let visible = fit.min(layer_count);
+        let start = state
+            .current_layer
+            .saturating_sub(visible / 2)
+            .min(layer_count - visible);
+
+        (start..start + visible)
+            .enumerate()
+            .map(|(slot, layer)| {
+                let x = area.x + slot as u16 * (tile_width + TILE_GAP);
+                (layer, Rect::new(x, area.y, tile_width, area.height))
+            })
+            .collect()
+    }
+
+    /// Returns the layer whose tile contains the given screen cell.
+    #[must_use]
+    pub fn layer_at(area: Rect, state: &AppState, column: u16, row: u16) -> Option<usize> {
+        let cell = ratatui::layout::Position::new(column, row);
+        Self::tile_areas(area, state)
+            .into_iter()
+            .find(|(_, tile)| tile.contains(cell))
+            .map(|(layer, _)| layer)
+    }
+
+    /// Renders the strip into `area`.
+    pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
+        let theme = &state.theme;
+        let (rows, cols) = grid_size(&state.layout.layers);
+
+        for (layer_idx, tile) in Self::tile_areas(area, state) {
+            let Some(layer) = state.layout.layers.get(layer_idx) else {
+                continue;
+            };
+            let is_current = layer_idx == state.current_layer;
+            let border_style = if is_current {
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_muted)
+            };
+
+            let title_width = usize::from(tile.width.saturating_sub(2));
+            let mut title = format!("{layer_idx} {}", layer.name);
+            if title.chars().count() > title_width {
+                title = title.chars().take(title_width).collect();
+            }
+
+            let colors = key_colors(state, layer_idx, layer, rows, cols);
+            let lines: Vec<Line> = (0..rows.div_ceil(2))
+                .map(|half_row| {
+                    let top = usize::from(half_row * 2);
+                    let spans: Vec<Span> = (0..usize::from(cols))
+                        .map(|col| {
+                            let upper = colors[top][col].unwrap_or(theme.background);
+                            let lower = colors
+                                .get(top + 1)
+                                .and_then(|row| row[col])
+                                .unwrap_or(theme.background);
+                            Span::styled("▀", Style::default().fg(upper).bg(lower))
+                        })
+                        .collect();
+                    Line::from(spans)
+                })
+                .collect();
+
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .title(Span::styled(title, border_style))
+                .style(Style::default().bg(theme.background));
+            f.render_widget(Paragraph::new(lines).block(block), tile);
+        }
+    }
+}
+
+/// Returns the visual grid size `(rows, cols)` shared by all layers.
+fn grid_size(layers: &[Layer]) -> (u16, u16) {
+    layers
+        .iter()
+        .flat_map(|layer| &layer.keys)
+        .fold((0, 0), |(rows, cols), key| {
+            (
+                rows.max(u16::from(key.position.row) + 1),
+                cols.max(u16::from(key.position.col) + 1),
+            )
+        })
+}
+
+/// Resolves the display color of every grid cell of `layer` (`None` = no key).
+fn key_colors(
+    state: &AppState,
+    layer_idx: usize,
+    layer: &Layer,
+    rows: u16,
+    cols: u16,
+) -> Vec<Vec<Option<Color>>> {
+    (0..rows)
+        .map(|row| {
+            (0..cols)
+                .map(|col| {
+                    let position = Position::new(row as u8, col as u8);
+                    layer.get_key(position).map(|key| {
+                        if !layer.layer_colors_enabled {
+                            return state.theme.text_muted;
+                        }
+                        let (rgb, _) = state.layout.resolve_display_color(layer_idx, key);
+                        let rgb = state.layout.apply_rgb_settings(rgb);
+                        Color::Rgb(rgb.r, rgb.g, rgb.b)
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,58 @@
+//! Tests for the layer minimap.
+
+use super::*;
+use crate::config::Config;
+use crate::models::{KeyDefinition, KeyboardGeometry, Layout, RgbColor, VisualLayoutMapping};
+
+fn create_test_state(layer_count: u8) -> AppState {
+    let mut layout = Layout::new("Minimap").unwrap();
+    for number in 0..layer_count {
+        let mut layer = Layer::new(number, format!("L{number}"), RgbColor::new(1, 2, 3)).unwrap();
+        for row in 0..3 {
+            for col in 0..6 {
+                layer.add_key(KeyDefinition::new(Position::new(row, col), "KC_A"));
+            }
+        }
+        layout.add_layer(layer).unwrap();
+    }
+    AppState::new(
+        layout,
+        None,
+        KeyboardGeometry::new("test", "test", 3, 6),
+        VisualLayoutMapping::default(),
+        Config::default(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_height_uses_half_block_rows() {
+    let state = create_test_state(2);
+    // 3 key rows -> 2 half-block lines + 2 border lines
+    assert_eq!(LayerMinimap::height(&state), 4);
+}
+
+#[test]
+fn test_tiles_follow_current_layer_and_hit_testing() {
+    let mut state = create_test_state(6);
+    // 6 columns + 2 borders = 8 wide, 1 gap -> 3 tiles fit into 26 columns
+    let area = Rect::new(0, 20, 26, 4);
+
+    let tiles = LayerMinimap::tile_areas(area, &state);
+    assert_eq!(
+        tiles.iter().map(|(layer, _)| *layer).collect::<Vec<_>>(),
+        vec![0, 1, 2]
+    );
+
+    state.current_layer = 5;
+    let tiles = LayerMinimap::tile_areas(area, &state);
+    assert_eq!(
+        tiles.iter().map(|(layer, _)| *layer).collect::<Vec<_>>(),
+        vec![3, 4, 5]
+    );
+
+    assert_eq!(LayerMinimap::layer_at(area, &state, 0, 21), Some(3));
+    assert_eq!(LayerMinimap::layer_at(area, &state, 10, 22), Some(4));
+    assert_eq!(LayerMinimap::layer_at(area, &state, 8, 22), None, "gap");
+    assert_eq!(LayerMinimap::layer_at(area, &state, 10, 5), None, "above");
+}
@@ -1,7 +1,10 @@
-//! Editor module: key and metadata editing UI components.
+//! Editor module: key, metadata, combo, and encoder editing UI components.
 
+pub mod combo_editor;
+pub mod encoder_editor;
 pub mod key_editor;
 pub mod keyboard;
 pub mod metadata_editor;
+pub mod minimap;
 
 pub use crate::tui::AppState;
@@ -4,12 +4,14 @@
 
 use anyhow::Result;
 use crossterm::event::{self, Event};
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal};
 use std::io;
 use std::time::Duration;
 
+use crate::firmware::BuildStatus;
 use crate::tui::app_state::AppState;
-use crate::tui::input::handle_key_event;
+use crate::tui::handlers::actions::handle_build_finished;
+use crate::tui::input::{handle_key_event, handle_mouse_event};
 use crate::tui::render::render;
 use crate::tui::theme::Theme;
 
@@ -38,30 +40,53 @@ pub fn run_tui(
         if event::poll(Duration::from_millis(100))? {
             match event::read()? {
                 Event::Key(key) => {
-                    if handle_key_event(state, key)? {
+                    let should_quit = handle_key_event(state, key)?;
+                    if should_quit {
                         break;
                     }
                 }
+                Event::Mouse(mouse) => {
+                    let size = terminal.size()?;
+                    let screen = Rect::new(0, 0, size.width, size.height);
+                    handle_mouse_event(state, mouse, screen)?;
+                }
                 Event::Resize(_, _) => {
                     // Terminal resized, will re-render on next loop
                 }
-                // Ignore focus/mouse/paste — not used by this app
+                // Ignore focus/paste — not used by this app
                 _ => {}
             }
         }
 
-        // Poll build state for updates
-        if let Some(build_state) = &mut state.build_state {
-            if build_state.poll() {
-                // Build message received, will update on next render
+        // Poll build state for updates (rendered on the next loop)
+        let build_succeeded = state.build_state.as_mut().is_some_and(|build_state| {
+            let compiling = build_state.status == BuildStatus::Compiling;
+            build_state.poll() && compiling && build_state.status == BuildStatus::Success
+        });
+        if build_succeeded {
+            handle_build_finished(state);
+        }
+
+        // Pick up setup steps started from the setup wizard
+        state.wizard_state.poll_setup();
+
+        // Apply live preview reports from the keyboard
+        if let Some(listener) = &state.hid_listener {
+            for event in listener.poll() {
+                state.live_state.apply(&event);
             }
         }
 
+        // Pick up saves made by the web server or other programs
+        state.check_disk_changes();
+        state.check_git_status();
+
         // Check if should quit
         if state.should_quit {
             break;
         }
     }
 
+    state.save_recent_keycodes();
     Ok(())
 }
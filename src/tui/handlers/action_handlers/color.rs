@@ -60,6 +60,20 @@ pub fn handle_set_layer_color(state: &mut AppState) -> Result<bool> {
     Ok(false)
 }
 
+/// Handle set underglow color action
+pub fn handle_set_underglow_color(state: &mut AppState) -> Result<bool> {
+    // Set color of LEDs without a key on this layer (Alt+C)
+    if state.layout.layers.get(state.current_layer).is_some() {
+        let current_color = state.layout.resolve_underglow_color(state.current_layer);
+        state.open_color_picker(
+            crate::tui::component::ColorPickerContext::LayerUnderglow,
+            current_color,
+        );
+        state.set_status("Setting layer underglow color - Enter to apply, clear to follow layer");
+    }
+    Ok(false)
+}
+
 /// Handle toggle layer colors action
 pub fn handle_toggle_layer_colors(state: &mut AppState) -> Result<bool> {
     // Toggle colors for current layer (v)
@@ -75,6 +89,14 @@ pub fn handle_toggle_layer_colors(state: &mut AppState) -> Result<bool> {
     Ok(false)
 }
 
+/// Handle cycle color view action
+pub fn handle_cycle_color_view(state: &mut AppState) -> Result<bool> {
+    // Cycle firmware / category / override colors (Shift+I)
+    state.color_view = state.color_view.next();
+    state.set_status(format!("Showing {}", state.color_view.label()));
+    Ok(false)
+}
+
 /// Handle toggle all layer colors action
 pub fn handle_toggle_all_layer_colors(state: &mut AppState) -> Result<bool> {
     // Toggle colors for all layers (Alt+V)
@@ -1,11 +1,18 @@
 // File operations action handlers
 
 use crate::export::export_to_markdown;
+use crate::services::audit::{self, AuditEntry, AuditSource};
+use crate::services::backup::BackupStore;
+use crate::services::git;
+use crate::services::hooks::{self, Hook};
+use crate::services::share_bundle::{ShareBundle, BUNDLE_EXTENSION};
+use crate::services::web_bridge::{self, WebServer};
+use crate::services::workspace_lock;
 use crate::services::LayoutService;
 use crate::tui::{AppState, ExportFilenameDialogState, PopupType, TemplateSaveDialogState};
 use anyhow::Result;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Handle quit action
 pub fn handle_quit(state: &mut AppState) -> Result<bool> {
@@ -20,15 +27,181 @@ pub fn handle_quit(state: &mut AppState) -> Result<bool> {
 /// Handle save action
 pub fn handle_save(state: &mut AppState) -> Result<bool> {
     if let Some(path) = &state.source_path.clone() {
-        LayoutService::save(&state.layout, path)?;
-        state.mark_clean();
-        state.set_status("Saved");
+        if !state.confirm_overwrite_on_save() {
+            return Ok(false);
+        }
+        if state.geometry_adjust_report.is_some() {
+            state.geometry_review_scroll = 0;
+            state.active_popup = Some(PopupType::GeometryAdjustReview);
+            state.set_status("Review geometry adjustments - Enter: save, Esc: revert");
+            return Ok(false);
+        }
+        if save_layout(state, path)? {
+            state.mark_clean();
+        }
     } else {
         state.set_error("No file path set");
     }
     Ok(false)
 }
 
+/// Handle save-and-quit from the unsaved changes prompt.
+///
+/// Saves like [`handle_save`]. If that opens the geometry review, the editor
+/// quits once the review is accepted instead. Returns true if the editor
+/// should quit now.
+pub fn handle_save_and_quit(state: &mut AppState) -> Result<bool> {
+    state.active_popup = None;
+    if state.source_path.is_none() {
+        return Ok(true);
+    }
+    handle_save(state)?;
+    if state.active_popup == Some(PopupType::GeometryAdjustReview) {
+        state.quit_after_geometry_review = true;
+        return Ok(false);
+    }
+    Ok(!state.dirty)
+}
+
+/// Save the current layout to `path` and record the change in the audit log.
+///
+/// The `pre_save` hook runs first; if it fails the layout is not saved and
+/// `false` is returned. The file being replaced is backed up, and with
+/// `git.auto_commit` the saved file is committed if it is in a git
+/// repository. Saving persists any pending geometry adjustments, so their
+/// report is cleared, and records a snapshot of the keyboard geometry so the
+/// layout renders without a QMK tree. Backup, audit, commit and `post_save` hook failures
+/// are reported in the status bar but never fail the save.
+pub fn save_layout(state: &mut AppState, path: &Path) -> Result<bool> {
+    if let Err(e) = hooks::run_hook(&state.config.hooks, Hook::PreSave, path, &[]) {
+        state.set_error(format!("Not saved: {e:#}"));
+        return Ok(false);
+    }
+
+    let before = LayoutService::load(path).ok();
+    let backup = state
+        .config
+        .backup_dir()
+        .and_then(|dir| BackupStore::new(dir).backup(path));
+    let geometry = state.geometry.clone();
+    state.layout.record_geometry(&geometry);
+    LayoutService::save(&state.layout, path)?;
+    state.geometry_adjust_report = None;
+    state.geometry_adjust_original = None;
+    state.mark_saved_to_disk();
+    state.save_recent_keycodes();
+    state.set_status("Saved");
+    if let Err(e) = backup {
+        state.set_error(format!(
+            "Saved, but failed to back up the previous version: {e}"
+        ));
+    }
+
+    let entry = AuditEntry::new(
+        audit::local_actor(),
+        AuditSource::Tui,
+        "save",
+        before.as_ref(),
+        &state.layout,
+    );
+    if let Err(e) = audit::record(path, &entry) {
+        state.set_error(format!("Saved, but failed to write audit log: {e}"));
+    }
+
+    if state.config.git.auto_commit {
+        if let Some(repo) = state.git_repo.clone() {
+            let filename = path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            );
+            match repo.commit_file(path, &git::commit_message(&filename, &entry.changes)) {
+                Ok(Some(hash)) => {
+                    state.set_status(format!(
+                        "Saved and committed {}",
+                        &hash[..hash.len().min(7)]
+                    ));
+                }
+                Ok(None) => {}
+                Err(e) => state.set_error(format!("Saved, but failed to commit: {e}")),
+            }
+        }
+    }
+    if let Err(e) = hooks::run_hook(&state.config.hooks, Hook::PostSave, path, &[]) {
+        state.set_error(format!("Saved, but {e:#}"));
+    }
+    state.refresh_git_status();
+    Ok(true)
+}
+
+/// Handle restore from backup action
+pub fn handle_restore_backup(state: &mut AppState) -> Result<bool> {
+    let Some(path) = state.source_path.clone() else {
+        state.set_error("No file path set - backups are taken when the layout is saved");
+        return Ok(false);
+    };
+    let backups = state
+        .config
+        .backup_dir()
+        .and_then(|dir| BackupStore::new(dir).list(&path));
+    match backups {
+        Ok(backups) => {
+            state.open_backup_picker(backups);
+            state.set_status("Restore from backup - Enter: restore, Esc: cancel");
+        }
+        Err(e) => state.set_error(format!("Failed to list backups: {e}")),
+    }
+    Ok(false)
+}
+
+/// Handle open in browser action
+///
+/// Reuses a web server already running on the layout's directory, or starts
+/// one that runs until the TUI exits.
+pub fn handle_open_in_browser(state: &mut AppState) -> Result<bool> {
+    let Some(path) = state.source_path.clone().filter(|path| path.is_file()) else {
+        state.set_error("Save the layout first (Ctrl+S) to open it in the browser");
+        return Ok(false);
+    };
+    let Some(filename) = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+    else {
+        state.set_error("Layout path has no file name");
+        return Ok(false);
+    };
+    let workspace = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    let base_url = match workspace_lock::web_server_url(&workspace) {
+        Some(url) => url,
+        None => {
+            // Stop a server started earlier for another directory
+            state.web_server = None;
+            match WebServer::start(&workspace) {
+                Ok(server) => state.web_server.insert(server).url().to_string(),
+                Err(e) => {
+                    state.set_error(format!("Failed to start the web server: {e:#}"));
+                    return Ok(false);
+                }
+            }
+        }
+    };
+
+    let url = web_bridge::layout_url(&base_url, &filename);
+    if let Err(e) = web_bridge::open_browser(&url) {
+        state.set_error(format!("{e:#}"));
+    } else if state.dirty {
+        state.set_status(format!(
+            "Opened {url} - unsaved changes show up there after saving (Ctrl+S)"
+        ));
+    } else {
+        state.set_status(format!("Opened {url}"));
+    }
+    Ok(false)
+}
+
 /// Handle export layout action
 pub fn handle_export_layout(state: &mut AppState) -> Result<bool> {
     state.export_filename_dialog_state =
@@ -38,11 +211,9 @@ pub fn handle_export_layout(state: &mut AppState) -> Result<bool> {
     Ok(false)
 }
 
-/// Perform the actual export to markdown
+/// Perform the actual export to markdown, or to a share bundle when the
+/// filename ends in `.lazyqmk`
 pub fn perform_export(state: &mut AppState, filename: &str) -> Result<()> {
-    // Generate markdown content
-    let markdown_content = export_to_markdown(&state.layout, &state.geometry, &state.keycode_db)?;
-
     // Determine output path
     let output_path = if filename.contains('/') || filename.contains('\\') {
         // Absolute or relative path provided
@@ -60,7 +231,25 @@ pub fn perform_export(state: &mut AppState, filename: &str) -> Result<()> {
         }
     };
 
-    // Write to file
+    if output_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(BUNDLE_EXTENSION))
+    {
+        let mut bundle = ShareBundle::new(&state.layout, Some(&state.geometry))?;
+        let keymap = bundle.generate_keymap(&state.config, &state.keycode_db);
+        bundle.write(&output_path)?;
+        match keymap {
+            Ok(()) => state.set_status(format!("✓ Bundled to: {}", output_path.display())),
+            Err(e) => state.set_status(format!(
+                "✓ Bundled to: {} (without keymap.c: {e:#})",
+                output_path.display()
+            )),
+        }
+        return Ok(());
+    }
+
+    // Generate markdown content and write to file
+    let markdown_content = export_to_markdown(&state.layout, &state.geometry, &state.keycode_db)?;
     fs::write(&output_path, markdown_content)?;
 
     state.set_status(format!("✓ Exported to: {}", output_path.display()));
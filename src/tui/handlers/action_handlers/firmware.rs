@@ -1,8 +1,16 @@
 // Firmware action handlers
 
-use crate::tui::handlers::actions::{handle_firmware_build, handle_firmware_generation};
+use crate::models::FirmwareFeature;
+use crate::services::hid_listen::{HidListener, LiveState};
+use crate::tui::handlers::actions::{
+    handle_firmware_build, handle_firmware_check, handle_firmware_generation,
+};
 use crate::tui::AppState;
 use anyhow::Result;
+use std::time::Duration;
+
+/// How long to wait for the keyboard to enter bootloader mode
+const FLASH_WAIT: Duration = Duration::from_secs(30);
 
 /// Handle build firmware action
 pub fn handle_build_firmware(state: &mut AppState) -> Result<bool> {
@@ -10,8 +18,49 @@ pub fn handle_build_firmware(state: &mut AppState) -> Result<bool> {
     Ok(false)
 }
 
+/// Handle check firmware action
+pub fn handle_check_firmware(state: &mut AppState) -> Result<bool> {
+    handle_firmware_check(state)?;
+    Ok(false)
+}
+
 /// Handle generate firmware action
 pub fn handle_generate_firmware(state: &mut AppState) -> Result<bool> {
     handle_firmware_generation(state)?;
     Ok(false)
 }
+
+/// Handle flash firmware action
+pub fn handle_flash_firmware(state: &mut AppState) -> Result<bool> {
+    let Some(build_state) = state.build_state.as_mut() else {
+        state.set_error("No firmware built yet - build first (Ctrl+B)");
+        return Ok(false);
+    };
+
+    match build_state.start_flash(FLASH_WAIT) {
+        Ok(()) => state.set_status(
+            "Flashing - put the keyboard in bootloader mode (reset button), check Shift+B",
+        ),
+        Err(e) => state.set_error(e.to_string()),
+    }
+    Ok(false)
+}
+
+/// Handle toggle live preview action
+pub fn handle_toggle_live_preview(state: &mut AppState) -> Result<bool> {
+    if state.hid_listener.take().is_some() {
+        state.set_status("Live preview stopped");
+        return Ok(false);
+    }
+
+    state.live_state = LiveState::default();
+    state.hid_listener = Some(HidListener::start());
+    if state.layout.firmware_features.get(FirmwareFeature::Console) == Some(true) {
+        state.set_status("Live preview started - waiting for the keyboard console");
+    } else {
+        state.set_status(
+            "Live preview started - enable Console in Settings → Firmware features & size and flash to see reports",
+        );
+    }
+    Ok(false)
+}
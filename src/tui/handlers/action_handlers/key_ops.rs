@@ -1,8 +1,11 @@
 //! Key operations action handlers.
 //!
-//! Contains 6 action handlers for direct key manipulation: clear, copy, cut,
-//! paste, fill, and swap. Total file size ~315 lines — well under the
-//! AGENTS.md 500-line threshold, so no module split is warranted.
+//! Contains action handlers for direct key manipulation: clear, copy, cut,
+//! paste, undo, selection toggle, and lock. Total file size ~480 lines —
+//! under the AGENTS.md 500-line threshold, so no module split is warranted.
+//!
+//! Locked keys are skipped by clear and paste, and are never cleared as the
+//! source of a cut.
 //!
 //! The largest handler (`handle_paste_key`, ~178 lines) covers multi-key
 //! paste across selections and clipboard modes, which is intrinsically
@@ -10,34 +13,78 @@
 //! `LazyQMK-hdz1` / `LazyQMK-mu47.1` (closed-as-no-op, 2026-07-19).
 
 use crate::models::Position;
+use crate::services::layer_access::{find_layer_access_issues, fix_layer_access_issues};
 use crate::tui::{clipboard, AppState};
 use anyhow::Result;
 
+/// Status shown when an operation refuses to touch a locked key.
+const LOCKED_KEY_ERROR: &str = "Key is locked - Shift+X to unlock";
+
 /// Handle clear key action
 pub fn handle_clear_key(state: &mut AppState) -> Result<bool> {
+    clear_keys(state, "KC_TRNS");
+    Ok(false)
+}
+
+/// Handle clear key to `KC_NO` action
+pub fn handle_clear_key_to_none(state: &mut AppState) -> Result<bool> {
+    clear_keys(state, "KC_NO");
+    Ok(false)
+}
+
+/// Sets the selected keys (or the current key) to `keycode`, skipping locked
+/// keys. Clearing a selection can be undone with Ctrl+Z.
+fn clear_keys(state: &mut AppState, keycode: &str) {
     if state.selection_mode.is_some() && !state.selected_keys.is_empty() {
-        // Clear all selected keys
-        let layer = state.current_layer;
+        // Clear all selected keys except locked ones
+        let layer_index = state.current_layer;
+        let mut undo_keys = Vec::new();
+        let mut locked = 0;
         for pos in &state.selected_keys.clone() {
-            if let Some(layer) = state.layout.layers.get_mut(layer) {
+            if let Some(layer) = state.layout.layers.get_mut(layer_index) {
                 if let Some(key) = layer.keys.iter_mut().find(|k| k.position == *pos) {
-                    key.keycode = "KC_TRNS".to_string();
+                    if key.locked {
+                        locked += 1;
+                    } else {
+                        undo_keys.push((*pos, clipboard::ClipboardContent::from_key(key)));
+                        key.keycode = keycode.to_string();
+                    }
                 }
             }
         }
-        let count = state.selected_keys.len();
+        let count = undo_keys.len();
         state.selected_keys.clear();
         state.selection_mode = None;
-        state.mark_dirty();
+        if count > 0 {
+            state.clipboard.save_undo(
+                layer_index,
+                undo_keys,
+                format!("Cleared {count} keys to {keycode}"),
+            );
+            state.mark_dirty();
+            state.refresh_layer_refs();
+        }
         state.set_status(format!(
-            "Cleared {count} keys to KC_TRNS (clipboard unchanged)"
+            "Cleared {count} keys to {keycode}{} (clipboard unchanged)",
+            locked_suffix(locked)
         ));
+    } else if state.get_selected_key().is_some_and(|key| key.locked) {
+        state.set_error(LOCKED_KEY_ERROR);
     } else if let Some(key) = state.get_selected_key_mut() {
-        key.keycode = "KC_TRNS".to_string();
+        key.keycode = keycode.to_string();
         state.mark_dirty();
-        state.set_status("Key cleared to KC_TRNS (clipboard unchanged)");
+        state.refresh_layer_refs();
+        state.set_status(format!("Key cleared to {keycode} (clipboard unchanged)"));
+    }
+}
+
+/// Formats the "N locked skipped" note appended to bulk operation statuses.
+pub(super) fn locked_suffix(locked: usize) -> String {
+    if locked == 0 {
+        String::new()
+    } else {
+        format!(", {locked} locked skipped")
     }
-    Ok(false)
 }
 
 /// Handle copy key action
@@ -51,14 +98,7 @@ pub fn handle_copy_key(state: &mut AppState) -> Result<bool> {
         for pos in &state.selected_keys {
             if let Some(layer) = state.layout.layers.get(layer) {
                 if let Some(key) = layer.keys.iter().find(|k| k.position == *pos) {
-                    keys.push((
-                        *pos,
-                        clipboard::ClipboardContent {
-                            keycode: key.keycode.clone(),
-                            color_override: key.color_override,
-                            category_id: key.category_id.clone(),
-                        },
-                    ));
+                    keys.push((*pos, clipboard::ClipboardContent::from_key(key)));
                 }
             }
         }
@@ -94,14 +134,7 @@ pub fn handle_cut_key(state: &mut AppState) -> Result<bool> {
         for pos in &state.selected_keys {
             if let Some(layer_ref) = state.layout.layers.get(layer) {
                 if let Some(key) = layer_ref.keys.iter().find(|k| k.position == *pos) {
-                    keys.push((
-                        *pos,
-                        clipboard::ClipboardContent {
-                            keycode: key.keycode.clone(),
-                            color_override: key.color_override,
-                            category_id: key.category_id.clone(),
-                        },
-                    ));
+                    keys.push((*pos, clipboard::ClipboardContent::from_key(key)));
                 }
             }
         }
@@ -131,6 +164,24 @@ pub fn handle_cut_key(state: &mut AppState) -> Result<bool> {
 
 /// Handle paste key action
 pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
+    paste_keys(state, false)
+}
+
+/// Handle paste key in place action
+///
+/// Pastes a multi-key copy at the positions it was copied from, typically
+/// onto another layer.
+pub fn handle_paste_key_in_place(state: &mut AppState) -> Result<bool> {
+    if state.clipboard.has_content() && !state.clipboard.is_multi() {
+        state.set_error("Paste in place needs keys copied from a selection");
+        return Ok(false);
+    }
+    paste_keys(state, true)
+}
+
+/// Pastes the clipboard at the current key, or with `in_place` at the
+/// positions a multi-key copy came from.
+fn paste_keys(state: &mut AppState, in_place: bool) -> Result<bool> {
     // Check if clipboard has content
     if !state.clipboard.has_content() {
         state.set_error("Nothing to paste");
@@ -143,7 +194,11 @@ pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
             // Calculate target positions relative to current position
             // The anchor is the reference point, we need to shift all keys
             let anchor = multi.anchor;
-            let current = state.selected_position;
+            let current = if in_place {
+                anchor
+            } else {
+                state.selected_position
+            };
 
             // Calculate offset from anchor to current position
             let row_offset = current.row as isize - anchor.row as isize;
@@ -152,6 +207,7 @@ pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
             // Collect valid target positions and save undo state
             let mut paste_targets: Vec<(Position, clipboard::ClipboardContent)> = Vec::new();
             let mut undo_keys: Vec<(Position, clipboard::ClipboardContent)> = Vec::new();
+            let mut locked_count = 0;
 
             for (pos, content) in &multi.keys {
                 // Calculate target position
@@ -165,20 +221,20 @@ pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
                 {
                     let target_pos = Position::new(target_row as u8, target_col as u8);
 
-                    // Check if target position is valid
-                    if state.mapping.is_valid_position(target_pos) {
+                    let is_locked = state
+                        .layout
+                        .layers
+                        .get(state.current_layer)
+                        .is_some_and(|layer| layer.is_key_locked(target_pos));
+                    if is_locked {
+                        locked_count += 1;
+                    } else if state.mapping.is_valid_position(target_pos) {
                         // Save original for undo
                         if let Some(layer) = state.layout.layers.get(state.current_layer) {
                             if let Some(key) = layer.keys.iter().find(|k| k.position == target_pos)
                             {
-                                undo_keys.push((
-                                    target_pos,
-                                    clipboard::ClipboardContent {
-                                        keycode: key.keycode.clone(),
-                                        color_override: key.color_override,
-                                        category_id: key.category_id.clone(),
-                                    },
-                                ));
+                                undo_keys
+                                    .push((target_pos, clipboard::ClipboardContent::from_key(key)));
                             }
                         }
                         paste_targets.push((target_pos, content.clone()));
@@ -187,7 +243,11 @@ pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
             }
 
             if paste_targets.is_empty() {
-                state.set_error("No valid positions for paste");
+                if locked_count > 0 {
+                    state.set_error(LOCKED_KEY_ERROR);
+                } else {
+                    state.set_error("No valid positions for paste");
+                }
                 return Ok(false);
             }
 
@@ -214,10 +274,14 @@ pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
                 }
             }
 
-            // Clear cut sources if this was a cut operation
+            // Clear cut sources if this was a cut operation (locked sources are kept)
             for (layer_idx, pos) in cut_sources {
                 if let Some(layer) = state.layout.layers.get_mut(layer_idx) {
-                    if let Some(source_key) = layer.keys.iter_mut().find(|k| k.position == pos) {
+                    if let Some(source_key) = layer
+                        .keys
+                        .iter_mut()
+                        .find(|k| k.position == pos && !k.locked)
+                    {
                         source_key.keycode = "KC_TRNS".to_string();
                         source_key.color_override = None;
                         source_key.category_id = None;
@@ -230,20 +294,24 @@ pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
             state.flash_highlight = Some((state.current_layer, current, 5));
 
             state.mark_dirty();
-            state.set_status(format!("Pasted {paste_count} keys"));
+            state.set_status(format!(
+                "Pasted {paste_count} keys{}",
+                locked_suffix(locked_count)
+            ));
         }
     } else if let Some(content) = state.clipboard.get_content().cloned() {
         // Single key paste (original logic)
+        if state.get_selected_key().is_some_and(|key| key.locked) {
+            state.set_error(LOCKED_KEY_ERROR);
+            return Ok(false);
+        }
+
         // Get cut source before modifying clipboard
         let cut_source = state.clipboard.get_cut_source();
 
         // Save undo state before making changes
         if let Some(key) = state.get_selected_key() {
-            let original = clipboard::ClipboardContent {
-                keycode: key.keycode.clone(),
-                color_override: key.color_override,
-                category_id: key.category_id.clone(),
-            };
+            let original = clipboard::ClipboardContent::from_key(key);
             state.clipboard.save_undo(
                 state.current_layer,
                 vec![(state.selected_position, original)],
@@ -263,10 +331,14 @@ pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
             state.flash_highlight = Some((state.current_layer, state.selected_position, 5));
         }
 
-        // If this was a cut operation, clear the source key
+        // If this was a cut operation, clear the source key (unless it is locked)
         if let Some((layer_idx, pos)) = cut_source {
             if let Some(layer) = state.layout.layers.get_mut(layer_idx) {
-                if let Some(source_key) = layer.keys.iter_mut().find(|k| k.position == pos) {
+                if let Some(source_key) = layer
+                    .keys
+                    .iter_mut()
+                    .find(|k| k.position == pos && !k.locked)
+                {
                     source_key.keycode = "KC_TRNS".to_string();
                     source_key.color_override = None;
                     source_key.category_id = None;
@@ -284,12 +356,23 @@ pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
 pub fn handle_undo_paste(state: &mut AppState) -> Result<bool> {
     // Use get_undo() to peek at undo info before taking it
     if let Some(undo_info) = state.clipboard.get_undo() {
-        let key_count = undo_info.original_keys.len();
+        let key_count = if undo_info.swapped.is_some() {
+            2
+        } else {
+            undo_info.original_keys.len()
+        };
         let layer_idx = undo_info.layer_index;
         let description = undo_info.description.clone();
 
         // Now take and apply the undo
         if let Some(undo) = state.clipboard.take_undo() {
+            // Swap swapped keys back
+            if let Some((first, second)) = undo.swapped {
+                if let Some(layer) = state.layout.layers.get_mut(layer_idx) {
+                    layer.swap_keys(first, second);
+                }
+            }
+
             // Restore original keys
             for (pos, content) in undo.original_keys {
                 if let Some(layer) = state.layout.layers.get_mut(layer_idx) {
@@ -322,3 +405,76 @@ pub fn handle_toggle_current_key(state: &mut AppState) -> Result<bool> {
     }
     Ok(false)
 }
+
+/// Handle toggle key lock action
+///
+/// With a selection, locks every selected key unless all of them are already
+/// locked, in which case they are all unlocked.
+pub fn handle_toggle_key_lock(state: &mut AppState) -> Result<bool> {
+    if state.selection_mode.is_some() && !state.selected_keys.is_empty() {
+        let positions = state.selected_keys.clone();
+        let Some(layer) = state.layout.layers.get_mut(state.current_layer) else {
+            return Ok(false);
+        };
+        let lock = !positions.iter().all(|pos| layer.is_key_locked(*pos));
+        let mut count = 0;
+        for key in layer
+            .keys
+            .iter_mut()
+            .filter(|k| positions.contains(&k.position))
+        {
+            key.locked = lock;
+            count += 1;
+        }
+        state.selection_mode = None;
+        state.selected_keys.clear();
+        state.mark_dirty();
+        let verb = if lock { "Locked" } else { "Unlocked" };
+        state.set_status(format!("{verb} {count} keys"));
+    } else if let Some(key) = state.get_selected_key_mut() {
+        key.locked = !key.locked;
+        let status = if key.locked {
+            "Key locked - skipped by clear, paste, and layer copies"
+        } else {
+            "Key unlocked"
+        };
+        state.mark_dirty();
+        state.set_status(status);
+    } else {
+        state.set_error("No key to lock");
+    }
+    Ok(false)
+}
+
+/// Handle fix layer access action
+///
+/// Sets the keys of the current layer that sit under its hold keys (see
+/// [`crate::services::layer_access`]) to `KC_TRNS`. Locked keys are skipped.
+pub fn handle_fix_layer_access(state: &mut AppState) -> Result<bool> {
+    let layer = state.current_layer;
+    let issues: Vec<_> = find_layer_access_issues(&state.layout.layers)
+        .into_iter()
+        .filter(|issue| issue.layer() == layer)
+        .collect();
+    if issues.is_empty() {
+        state.set_status(format!("Layer {layer} has no overridden hold keys"));
+        return Ok(false);
+    }
+
+    let mut positions: Vec<Position> = issues.iter().map(|i| i.layer_ref.position).collect();
+    positions.sort_unstable_by_key(|p| (p.row, p.col));
+    positions.dedup();
+    let fixed = fix_layer_access_issues(&mut state.layout.layers, &issues);
+    if fixed > 0 {
+        state.mark_dirty();
+        state.refresh_layer_refs();
+    }
+    state.set_status(format!(
+        "Set {fixed} keys to KC_TRNS on layer {layer}{}",
+        locked_suffix(positions.len() - fixed)
+    ));
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,175 @@
+//! Tests for key operation handlers.
+
+use super::*;
+use crate::config::Config;
+use crate::models::{
+    KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, RgbColor, VisualLayoutMapping,
+};
+use crate::tui::SelectionMode;
+
+/// Single row of four keys with the key at (0, 3) locked.
+fn create_test_state() -> AppState {
+    let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 1, 4);
+    for col in 0..4u8 {
+        geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+    }
+    let mapping = VisualLayoutMapping::build(&geometry);
+
+    let mut layout = Layout::new("Locks").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(1, 2, 3)).unwrap();
+    for (col, keycode) in ["KC_A", "KC_B", "KC_C", "QK_BOOT"].into_iter().enumerate() {
+        layer.add_key(KeyDefinition::new(Position::new(0, col as u8), keycode));
+    }
+    layer.get_key_mut(Position::new(0, 3)).unwrap().locked = true;
+    layout.add_layer(layer).unwrap();
+
+    AppState::new(layout, None, geometry, mapping, Config::default()).unwrap()
+}
+
+fn keycode_at(state: &AppState, col: u8) -> &str {
+    &state.layout.layers[0]
+        .get_key(Position::new(0, col))
+        .unwrap()
+        .keycode
+}
+
+#[test]
+fn test_clear_skips_locked_keys() {
+    let mut state = create_test_state();
+    state.selection_mode = Some(SelectionMode::Normal);
+    state.selected_keys = vec![Position::new(0, 2), Position::new(0, 3)];
+
+    handle_clear_key(&mut state).unwrap();
+    assert_eq!(keycode_at(&state, 2), "KC_TRNS");
+    assert_eq!(keycode_at(&state, 3), "QK_BOOT");
+
+    state.selected_position = Position::new(0, 3);
+    handle_clear_key(&mut state).unwrap();
+    assert_eq!(keycode_at(&state, 3), "QK_BOOT");
+}
+
+#[test]
+fn test_paste_skips_locked_targets_and_sources() {
+    let mut state = create_test_state();
+
+    // Multi-key paste onto (0, 2)..(0, 3) only writes the unlocked key
+    state.selection_mode = Some(SelectionMode::Normal);
+    state.selected_keys = vec![Position::new(0, 0), Position::new(0, 1)];
+    handle_copy_key(&mut state).unwrap();
+    state.selected_position = Position::new(0, 2);
+    handle_paste_key(&mut state).unwrap();
+    assert_eq!(keycode_at(&state, 2), "KC_A");
+    assert_eq!(keycode_at(&state, 3), "QK_BOOT");
+
+    // Single paste onto a locked key is refused
+    state.selected_position = Position::new(0, 0);
+    handle_copy_key(&mut state).unwrap();
+    state.selected_position = Position::new(0, 3);
+    handle_paste_key(&mut state).unwrap();
+    assert_eq!(keycode_at(&state, 3), "QK_BOOT");
+
+    // Cutting a locked key moves a copy but keeps the source
+    handle_cut_key(&mut state).unwrap();
+    state.selected_position = Position::new(0, 1);
+    handle_paste_key(&mut state).unwrap();
+    assert_eq!(keycode_at(&state, 1), "QK_BOOT");
+    assert_eq!(keycode_at(&state, 3), "QK_BOOT");
+}
+
+#[test]
+fn test_toggle_lock_on_selection() {
+    let mut state = create_test_state();
+    state.selection_mode = Some(SelectionMode::Normal);
+    state.selected_keys = vec![Position::new(0, 0), Position::new(0, 3)];
+
+    handle_toggle_key_lock(&mut state).unwrap();
+    assert!(state.layout.layers[0].is_key_locked(Position::new(0, 0)));
+    assert!(state.layout.layers[0].is_key_locked(Position::new(0, 3)));
+
+    state.selection_mode = Some(SelectionMode::Normal);
+    state.selected_keys = vec![Position::new(0, 0), Position::new(0, 3)];
+    handle_toggle_key_lock(&mut state).unwrap();
+    assert!(!state.layout.layers[0].is_key_locked(Position::new(0, 0)));
+    assert!(!state.layout.layers[0].is_key_locked(Position::new(0, 3)));
+}
+
+#[test]
+fn test_fix_layer_access_makes_hold_position_transparent() {
+    let mut state = create_test_state();
+    state.layout.layers[0].keys[0].keycode = "MO(1)".to_string();
+    let mut nav = Layer::new(1, "Nav", RgbColor::new(0, 0, 0)).unwrap();
+    for col in 0..4u8 {
+        nav.add_key(KeyDefinition::new(Position::new(0, col), "KC_ESC"));
+    }
+    state.layout.add_layer(nav).unwrap();
+
+    // Nothing to fix on the base layer
+    handle_fix_layer_access(&mut state).unwrap();
+    assert!(!state.dirty);
+
+    state.current_layer = 1;
+    handle_fix_layer_access(&mut state).unwrap();
+
+    let nav = &state.layout.layers[1];
+    assert_eq!(nav.get_key(Position::new(0, 0)).unwrap().keycode, "KC_TRNS");
+    assert_eq!(nav.get_key(Position::new(0, 1)).unwrap().keycode, "KC_ESC");
+    assert!(state.dirty);
+    assert_eq!(state.status_message, "Set 1 keys to KC_TRNS on layer 1");
+}
+
+#[test]
+fn test_clear_selection_to_none_can_be_undone() {
+    let mut state = create_test_state();
+    state.selection_mode = Some(SelectionMode::Normal);
+    state.selected_keys = vec![
+        Position::new(0, 0),
+        Position::new(0, 1),
+        Position::new(0, 3),
+    ];
+
+    handle_clear_key_to_none(&mut state).unwrap();
+    assert_eq!(keycode_at(&state, 0), "KC_NO");
+    assert_eq!(keycode_at(&state, 1), "KC_NO");
+    assert_eq!(keycode_at(&state, 3), "QK_BOOT");
+    assert!(state.selection_mode.is_none());
+    assert_eq!(
+        state.status_message,
+        "Cleared 2 keys to KC_NO, 1 locked skipped (clipboard unchanged)"
+    );
+
+    handle_undo_paste(&mut state).unwrap();
+    assert_eq!(keycode_at(&state, 0), "KC_A");
+    assert_eq!(keycode_at(&state, 1), "KC_B");
+}
+
+#[test]
+fn test_paste_in_place_copies_block_to_another_layer() {
+    let mut state = create_test_state();
+    let mut nav = Layer::new(1, "Nav", RgbColor::new(0, 0, 0)).unwrap();
+    for col in 0..4u8 {
+        nav.add_key(KeyDefinition::new(Position::new(0, col), "KC_TRNS"));
+    }
+    state.layout.add_layer(nav).unwrap();
+
+    // A single-key copy has no original positions
+    handle_copy_key(&mut state).unwrap();
+    state.current_layer = 1;
+    handle_paste_key_in_place(&mut state).unwrap();
+    assert_eq!(state.layout.layers[1].keys[0].keycode, "KC_TRNS");
+
+    state.current_layer = 0;
+    state.selection_mode = Some(SelectionMode::Normal);
+    state.selected_keys = vec![Position::new(0, 1), Position::new(0, 2)];
+    handle_copy_key(&mut state).unwrap();
+
+    // The cursor position doesn't matter
+    state.current_layer = 1;
+    state.selected_position = Position::new(0, 3);
+    handle_paste_key_in_place(&mut state).unwrap();
+    let keycodes: Vec<&str> = state.layout.layers[1]
+        .keys
+        .iter()
+        .map(|k| k.keycode.as_str())
+        .collect();
+    assert_eq!(keycodes, ["KC_TRNS", "KC_B", "KC_C", "KC_TRNS"]);
+}
@@ -5,7 +5,10 @@ use anyhow::Result;
 
 /// Handle switch layout variant action
 pub fn handle_switch_layout_variant(state: &mut AppState) -> Result<bool> {
-    let qmk_path = match &state.config.paths.qmk_firmware {
+    let qmk_path = match state
+        .config
+        .qmk_tree_path(state.layout.metadata.qmk_tree.as_deref())
+    {
         Some(path) => path.clone(),
         None => {
             state.set_error("QMK firmware path not configured");
@@ -24,3 +27,10 @@ pub fn handle_switch_layout_variant(state: &mut AppState) -> Result<bool> {
     state.set_status("Select layout variant - ↑↓: Navigate, Enter: Apply, Esc: Cancel");
     Ok(false)
 }
+
+/// Handle switch QMK tree action
+pub fn handle_switch_qmk_tree(state: &mut AppState) -> Result<bool> {
+    state.open_qmk_tree_picker();
+    state.set_status("Select QMK tree - ↑↓: Navigate, Enter: Apply, Esc: Cancel");
+    Ok(false)
+}
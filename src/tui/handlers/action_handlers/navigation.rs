@@ -1,6 +1,8 @@
 // Navigation action handlers
 
 use crate::models::{Position, VisualLayoutMapping};
+use crate::services::layer_access::find_layer_access_issues;
+use crate::services::layer_refs::{parse_layer_keycode, resolve_layer_target};
 use crate::tui::AppState;
 use crate::tui::SelectionMode;
 use anyhow::Result;
@@ -113,11 +115,69 @@ pub fn handle_next_layer(state: &mut AppState) -> Result<bool> {
     } else {
         state.current_layer = 0;
     }
-    state.set_status(format!("Layer {}", state.current_layer));
+    show_layer_status(state);
     state.clear_error();
     Ok(false)
 }
 
+/// Handle jump to layer action (Alt+digit or minimap click)
+pub fn handle_jump_to_layer(state: &mut AppState, index: usize) -> Result<bool> {
+    if index >= state.layout.layers.len() {
+        state.set_status(format!("Layer {index} does not exist"));
+        return Ok(false);
+    }
+
+    state.current_layer = index;
+    show_layer_status(state);
+    state.clear_error();
+    Ok(false)
+}
+
+/// Handle hold layer key action (Alt+H)
+///
+/// Shows the layer that the hold layer key (MO, LT, TT, LM) under the cursor
+/// activates over the current layer, like holding the key would. Pressing
+/// Alt+H again, or any key other than navigation, releases it.
+pub fn handle_hold_layer_key(state: &mut AppState) -> Result<bool> {
+    if state.held_layer.take().is_some() {
+        state.set_status("Layer key released");
+        return Ok(false);
+    }
+
+    let Some(key) = state.get_selected_key() else {
+        return Ok(false);
+    };
+    let target = parse_layer_keycode(&key.keycode)
+        .filter(|(_, kind)| kind.is_hold_like())
+        .and_then(|(target, _)| resolve_layer_target(&target, &state.layout.layers));
+    let Some(target) = target else {
+        state.set_error("Alt+H holds a layer key (MO, LT, TT or LM) - move the cursor onto one");
+        return Ok(false);
+    };
+
+    let keycode = key.keycode.clone();
+    state.held_layer = Some(target);
+    state.set_status(format!(
+        "Holding {keycode}: layer {target} ({}) shown over layer {} - any key releases",
+        state.layout.layers[target].name, state.current_layer
+    ));
+    Ok(false)
+}
+
+/// Handle toggle resolved keys action (Alt+T)
+///
+/// Transparent keys show, dimmed, the keycode they resolve to from the
+/// layers below (see [`crate::services::resolved_keys`]).
+pub fn handle_toggle_resolved_keys(state: &mut AppState) -> Result<bool> {
+    state.show_resolved_keys = !state.show_resolved_keys;
+    state.set_status(if state.show_resolved_keys {
+        "Transparent keys show what they resolve to from lower layers"
+    } else {
+        "Transparent keys shown as KC_TRNS"
+    });
+    Ok(false)
+}
+
 /// Handle previous layer action
 pub fn handle_previous_layer(state: &mut AppState) -> Result<bool> {
     if state.layout.layers.is_empty() {
@@ -130,7 +190,33 @@ pub fn handle_previous_layer(state: &mut AppState) -> Result<bool> {
     } else {
         state.current_layer = state.layout.layers.len() - 1;
     }
-    state.set_status(format!("Layer {}", state.current_layer));
+    show_layer_status(state);
     state.clear_error();
     Ok(false)
 }
+
+/// Shows the current layer number, warning when one of the layer's keys
+/// overrides the only hold key that reaches it.
+fn show_layer_status(state: &mut AppState) {
+    let layer = state.current_layer;
+    let issues: Vec<_> = find_layer_access_issues(&state.layout.layers)
+        .into_iter()
+        .filter(|issue| issue.layer() == layer)
+        .collect();
+    let Some(first) = issues.first() else {
+        state.set_status(format!("Layer {layer}"));
+        return;
+    };
+
+    let more = match issues.len() {
+        1 => String::new(),
+        n => format!(" (+{} more)", n - 1),
+    };
+    state.set_status_with_style(
+        format!("{}{more} - Shift+F sets KC_TRNS", first.message()),
+        state.theme.error,
+    );
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+//! Tests for navigation handlers.
+
+use super::*;
+use crate::config::Config;
+use crate::models::{KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, RgbColor};
+use crate::shortcuts::Action;
+use crate::tui::handlers::dispatch_action;
+
+/// Single row of three keys: `MO(1)`, `KC_A` and `LT(@<nav id>, KC_SPC)` on
+/// the base layer, and a second "Nav" layer.
+fn create_test_state() -> AppState {
+    let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 1, 3);
+    for col in 0..3u8 {
+        geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+    }
+    let mapping = VisualLayoutMapping::build(&geometry);
+
+    let mut layout = Layout::new("Hold").unwrap();
+    let mut nav = Layer::new(1, "Nav", RgbColor::new(0, 0, 255)).unwrap();
+    for col in 0..3u8 {
+        nav.add_key(KeyDefinition::new(Position::new(0, col), "KC_LEFT"));
+    }
+    let mut base = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
+    let lt = format!("LT(@{}, KC_SPC)", nav.id);
+    for (col, keycode) in ["MO(1)", "KC_A", lt.as_str()].into_iter().enumerate() {
+        base.add_key(KeyDefinition::new(Position::new(0, col as u8), keycode));
+    }
+    layout.add_layer(base).unwrap();
+    layout.add_layer(nav).unwrap();
+
+    AppState::new(layout, None, geometry, mapping, Config::default()).unwrap()
+}
+
+#[test]
+fn test_hold_layer_key_toggles_held_layer() {
+    let mut state = create_test_state();
+
+    handle_hold_layer_key(&mut state).unwrap();
+    assert_eq!(state.held_layer, Some(1));
+
+    handle_hold_layer_key(&mut state).unwrap();
+    assert_eq!(state.held_layer, None);
+
+    // Layer keys that target a layer by ID work too
+    state.selected_position = Position::new(0, 2);
+    handle_hold_layer_key(&mut state).unwrap();
+    assert_eq!(state.held_layer, Some(1));
+}
+
+#[test]
+fn test_hold_layer_key_requires_hold_layer_key() {
+    let mut state = create_test_state();
+    state.selected_position = Position::new(0, 1);
+
+    handle_hold_layer_key(&mut state).unwrap();
+    assert_eq!(state.held_layer, None);
+    assert!(state.error_message.is_some());
+}
+
+#[test]
+fn test_held_layer_survives_navigation_only() {
+    let mut state = create_test_state();
+    handle_hold_layer_key(&mut state).unwrap();
+
+    dispatch_action(&mut state, Action::NavigateRight).unwrap();
+    assert_eq!(state.held_layer, Some(1));
+
+    dispatch_action(&mut state, Action::Cancel).unwrap();
+    assert_eq!(state.held_layer, None);
+
+    state.selected_position = Position::new(0, 0);
+    handle_hold_layer_key(&mut state).unwrap();
+    dispatch_action(&mut state, Action::NextLayer).unwrap();
+    assert_eq!(state.held_layer, None);
+}
@@ -1,5 +1,6 @@
 // Popup management action handlers
 
+use super::selection;
 use crate::models::KeyDefinition;
 use crate::tui::editor::key_editor;
 use crate::tui::{onboarding_wizard, ActiveComponent, AppState, PopupType};
@@ -89,7 +90,21 @@ pub fn handle_view_build_log(state: &mut AppState) -> Result<bool> {
 }
 
 /// Handle open keycode picker action
+///
+/// With a selection, the picked keycode is assigned to every selected key.
 pub fn handle_open_keycode_picker(state: &mut AppState) -> Result<bool> {
+    if selection::has_bulk_selection(state) {
+        let count = state.selected_keys.len();
+        state.open_keycode_picker();
+        if let Some(ActiveComponent::KeycodePicker(ref mut picker)) = state.active_component {
+            picker.set_flow_context(
+                format!("Selection → {count} keys"),
+                "Enter assigns the keycode to every selected key. Locked keys are skipped.",
+            );
+        }
+        state.set_status(format!("Select keycode for {count} selected keys"));
+        return Ok(false);
+    }
     match get_selected_key_info(state) {
         Some((key, _)) => {
             open_key_editor(state, &key);
@@ -133,7 +148,10 @@ pub fn handle_setup_wizard(state: &mut AppState) -> Result<bool> {
         }
     };
 
-    match onboarding_wizard::OnboardingWizardState::new_for_keyboard_selection(&qmk_path) {
+    match onboarding_wizard::OnboardingWizardState::new_for_keyboard_selection(
+        &qmk_path,
+        &state.config.paths.keyboard_roots,
+    ) {
         Ok(wizard_state) => {
             state.wizard_state = wizard_state;
             state.active_popup = Some(PopupType::SetupWizard);
@@ -154,3 +172,87 @@ pub fn handle_open_tap_dance_editor(state: &mut AppState) -> Result<bool> {
         "Tap Dance Editor - visible actions shown in status bar and help",
     )
 }
+
+/// Handle open macro editor action
+pub fn handle_open_macro_editor(state: &mut AppState) -> Result<bool> {
+    open_popup_with_status(
+        state,
+        crate::tui::AppState::open_macro_editor,
+        "Macro Editor - n new, e edit, Enter applies MACRO() to the key",
+    )
+}
+
+/// Handle open Unicode map editor action
+pub fn handle_open_unicode_map_editor(state: &mut AppState) -> Result<bool> {
+    open_popup_with_status(
+        state,
+        |state| {
+            state.open_unicode_map_editor(crate::tui::unicode_map_editor::UnicodeKeyKind::Map);
+        },
+        "Unicode Map Editor - n new, e edit, Enter applies UM() to the key",
+    )
+}
+
+/// Handle open custom keycode editor action
+pub fn handle_open_custom_keycode_editor(state: &mut AppState) -> Result<bool> {
+    open_popup_with_status(
+        state,
+        crate::tui::AppState::open_custom_keycode_editor,
+        "Custom Keycodes - n new, e edit, Enter applies the keycode to the key",
+    )
+}
+
+/// Handle open combo editor action
+pub fn handle_open_combo_editor(state: &mut AppState) -> Result<bool> {
+    open_popup_with_status(
+        state,
+        crate::tui::AppState::open_combo_editor,
+        "Combo Editor - n new (prefilled with selected keys), Enter edit, D delete",
+    )
+}
+
+/// Handle open encoder editor action
+pub fn handle_open_encoder_editor(state: &mut AppState) -> Result<bool> {
+    if state.geometry.encoder_count == 0 {
+        state.set_error("This keyboard has no rotary encoders");
+        return Ok(false);
+    }
+    open_popup_with_status(
+        state,
+        crate::tui::AppState::open_encoder_editor,
+        "Encoder Editor - Enter edit current layer's encoder, D reset to default",
+    )
+}
+
+/// Handle layout stats action
+pub fn handle_show_layout_stats(state: &mut AppState) -> Result<bool> {
+    open_popup_with_status(
+        state,
+        crate::tui::AppState::open_layout_stats,
+        "Layout stats - Press c to load a corpus file for the typing heatmap",
+    )
+}
+
+/// Handle layer graph action
+pub fn handle_show_layer_graph(state: &mut AppState) -> Result<bool> {
+    let graph = crate::services::layer_graph::LayerGraph::build(&state.layout.layers);
+    let issues = graph.issues().len();
+    state.open_layer_graph(graph);
+    if issues == 0 {
+        state.set_status("Layer graph - every layer is reachable and can be left");
+    } else {
+        state.set_error(format!(
+            "Layer graph - {issues} issue(s): unreachable layers or layers with no way back"
+        ));
+    }
+    Ok(false)
+}
+
+/// Handle key search action
+pub fn handle_open_key_search(state: &mut AppState) -> Result<bool> {
+    open_popup_with_status(
+        state,
+        crate::tui::AppState::open_key_search,
+        "Search keys - Type a keycode, label or description, Enter to jump",
+    )
+}
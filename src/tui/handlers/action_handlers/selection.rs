@@ -1,8 +1,68 @@
 // Selection action handlers
 
-use crate::tui::{AppState, SelectionMode};
+use super::key_ops::locked_suffix;
+use crate::models::Position;
+use crate::tui::clipboard::{apply_layer_paste, plan_layer_paste, ClipboardContent};
+use crate::tui::copy_to_layer::CopyToLayer;
+use crate::tui::{ActiveComponent, AppState, PopupType, SelectionMode};
 use anyhow::Result;
 
+/// Returns true if a selection (not a pending swap) holds keys that bulk
+/// edits apply to.
+#[must_use]
+pub fn has_bulk_selection(state: &AppState) -> bool {
+    matches!(
+        state.selection_mode,
+        Some(SelectionMode::Normal | SelectionMode::Rectangle { .. })
+    ) && !state.selected_keys.is_empty()
+}
+
+/// Assigns `keycode` to every selected key on the current layer, skipping
+/// locked keys, and ends the selection. Can be undone with Ctrl+Z.
+///
+/// Returns false, changing nothing, when there is no bulk selection.
+pub fn assign_keycode_to_selection(state: &mut AppState, keycode: &str) -> bool {
+    if !has_bulk_selection(state) {
+        return false;
+    }
+
+    let layer_index = state.current_layer;
+    let positions = std::mem::take(&mut state.selected_keys);
+    state.selection_mode = None;
+    let mut undo_keys = Vec::new();
+    let mut locked = 0;
+    if let Some(layer) = state.layout.layers.get_mut(layer_index) {
+        for key in layer
+            .keys
+            .iter_mut()
+            .filter(|k| positions.contains(&k.position))
+        {
+            if key.locked {
+                locked += 1;
+            } else {
+                undo_keys.push((key.position, ClipboardContent::from_key(key)));
+                key.keycode = keycode.to_string();
+            }
+        }
+    }
+
+    let count = undo_keys.len();
+    if count > 0 {
+        state.clipboard.save_undo(
+            layer_index,
+            undo_keys,
+            format!("Assigned {keycode} to {count} keys"),
+        );
+        state.mark_dirty();
+        state.refresh_layer_refs();
+    }
+    state.set_status(format!(
+        "Assigned {keycode} to {count} keys{} - Ctrl+Z to undo",
+        locked_suffix(locked)
+    ));
+    true
+}
+
 /// Handle toggle selection mode action
 pub fn handle_toggle_selection_mode(state: &mut AppState) -> Result<bool> {
     if state.selection_mode.is_some() {
@@ -16,12 +76,78 @@ pub fn handle_toggle_selection_mode(state: &mut AppState) -> Result<bool> {
         state.selected_keys.clear();
         state.selected_keys.push(state.selected_position);
         state.set_status(
-            "Selection mode - Space: toggle, y: copy, Ctrl+X: cut for move, Del: clear, Esc: cancel",
+            "Selection mode - Space: toggle, Enter: assign, y: copy, Ctrl+X: cut, Del: clear, Esc: cancel",
         );
     }
     Ok(false)
 }
 
+/// Handle copy to layer action
+///
+/// Opens the target layer picker for the selected keys, or the current key
+/// without a selection.
+pub fn handle_copy_to_layer(state: &mut AppState) -> Result<bool> {
+    if state.layout.layers.len() < 2 {
+        state.set_error("Add another layer to copy keys to");
+        return Ok(false);
+    }
+    let positions = if has_bulk_selection(state) {
+        state.selected_keys.clone()
+    } else {
+        vec![state.selected_position]
+    };
+    let count = positions.len();
+    let picker = CopyToLayer::new(state.current_layer, positions, state.layout.layers.len());
+    state.active_component = Some(ActiveComponent::CopyToLayer(picker));
+    state.active_popup = Some(PopupType::CopyToLayer);
+    state.set_status(format!(
+        "Copy {count} key(s) to layer - review conflicts, Enter to copy"
+    ));
+    Ok(false)
+}
+
+/// Copies the keys at `positions` from layer `source` onto layer `target` at
+/// the same positions, skipping locked target keys, and ends the selection.
+/// Can be undone with Ctrl+Z.
+pub fn copy_keys_to_layer(
+    state: &mut AppState,
+    source: usize,
+    positions: &[Position],
+    target: usize,
+) {
+    let (Some(source_layer), Some(target_layer)) = (
+        state.layout.layers.get(source),
+        state.layout.layers.get(target),
+    ) else {
+        state.set_error("Invalid layer");
+        return;
+    };
+    let plan = plan_layer_paste(source_layer, target_layer, positions);
+
+    state.selection_mode = None;
+    state.selected_keys.clear();
+    let originals = apply_layer_paste(&mut state.layout.layers[target], &plan);
+    let count = originals.len();
+    if count > 0 {
+        state.clipboard.save_undo(
+            target,
+            originals,
+            format!("Copied {count} keys to layer {target}"),
+        );
+        state.mark_dirty();
+        state.refresh_layer_refs();
+    }
+
+    let overwritten = match plan.conflicts.len() {
+        0 => String::new(),
+        n => format!(", {n} overwritten"),
+    };
+    state.set_status(format!(
+        "Copied {count} keys to layer {target}{overwritten}{} - Ctrl+Z to undo",
+        locked_suffix(plan.locked.len())
+    ));
+}
+
 /// Handle start rectangle select action
 pub fn handle_start_rectangle_select(state: &mut AppState) -> Result<bool> {
     if state.selection_mode.is_some() {
@@ -31,7 +157,7 @@ pub fn handle_start_rectangle_select(state: &mut AppState) -> Result<bool> {
         });
         state.selected_keys.clear();
         state.selected_keys.push(state.selected_position);
-        state.set_status("Rectangle select - move to opposite corner, Enter to confirm");
+        state.set_status("Rectangle select - move to opposite corner, Enter to assign a keycode");
     } else {
         // Enter rectangle selection mode
         state.selection_mode = Some(SelectionMode::Rectangle {
@@ -39,7 +165,7 @@ pub fn handle_start_rectangle_select(state: &mut AppState) -> Result<bool> {
         });
         state.selected_keys.clear();
         state.selected_keys.push(state.selected_position);
-        state.set_status("Rectangle select - move to opposite corner, Enter to confirm");
+        state.set_status("Rectangle select - move to opposite corner, Enter to assign a keycode");
     }
     Ok(false)
 }
@@ -68,36 +194,10 @@ pub fn handle_swap_keys(state: &mut AppState) -> Result<bool> {
                 return Ok(false);
             }
 
-            // Get the current layer
-            let layer = if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
-                layer
-            } else {
-                state.set_status("Invalid layer");
-                return Ok(false);
-            };
-
-            // Find indices of both keys
-            let first_idx = layer.keys.iter().position(|k| k.position == first_pos);
-            let second_idx = layer.keys.iter().position(|k| k.position == second_pos);
-
-            match (first_idx, second_idx) {
-                (Some(idx1), Some(idx2)) => {
-                    // Layer guarantees unique positions per key (see Layer::validate in
-                    // src/models/layout.rs), so both indices refer to distinct entries.
-                    // Swapping whole KeyDefinition structs preserves all 7 fields (keycode,
-                    // label, color_override, category_id, combo_participant, description,
-                    // position). The previous field-by-field copy silently dropped 3 of them.
-                    layer.keys.swap(idx1, idx2);
-
-                    // Mark dirty and exit swap mode
-                    state.dirty = true;
-                    state.selection_mode = None;
-                    state.selected_keys.clear();
-                    state.set_status("Keys swapped");
-                }
-                _ => {
-                    state.set_status("Invalid key positions");
-                }
+            if swap_keys(state, first_pos, second_pos) {
+                // Exit swap mode
+                state.selection_mode = None;
+                state.selected_keys.clear();
             }
 
             Ok(false)
@@ -114,3 +214,35 @@ pub fn handle_swap_keys(state: &mut AppState) -> Result<bool> {
         }
     }
 }
+
+/// Swaps two keys on the current layer and records an undo entry (Ctrl+Z).
+///
+/// Used by swap mode (Shift+W) and mouse drag. Returns true if the keys were
+/// swapped; otherwise the reason is shown to the user.
+pub fn swap_keys(state: &mut AppState, first: Position, second: Position) -> bool {
+    let layer_index = state.current_layer;
+    let Some(layer) = state.layout.layers.get_mut(layer_index) else {
+        state.set_status("Invalid layer");
+        return false;
+    };
+
+    if layer.is_key_locked(first) || layer.is_key_locked(second) {
+        state.set_error("Cannot swap a locked key - Shift+X to unlock");
+        return false;
+    }
+
+    if !layer.swap_keys(first, second) {
+        state.set_status("Invalid key positions");
+        return false;
+    }
+
+    state
+        .clipboard
+        .save_swap_undo(layer_index, first, second, "Swapped keys".to_string());
+    state.mark_dirty();
+    state.set_status("Keys swapped - Ctrl+Z to undo");
+    true
+}
+
+#[cfg(test)]
+mod tests;
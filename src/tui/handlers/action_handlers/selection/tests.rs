@@ -0,0 +1,116 @@
+//! Tests for selection handlers.
+
+use super::*;
+use crate::config::Config;
+use crate::models::{
+    KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, RgbColor, VisualLayoutMapping,
+};
+use crate::tui::handlers::action_handlers::key_ops::handle_undo_paste;
+
+/// Single row of four keys with the key at (0, 3) locked.
+fn create_test_state() -> AppState {
+    let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 1, 4);
+    for col in 0..4u8 {
+        geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+    }
+    let mapping = VisualLayoutMapping::build(&geometry);
+
+    let mut layout = Layout::new("Bulk").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(1, 2, 3)).unwrap();
+    for (col, keycode) in ["KC_A", "KC_B", "KC_C", "QK_BOOT"].into_iter().enumerate() {
+        layer.add_key(KeyDefinition::new(Position::new(0, col as u8), keycode));
+    }
+    layer.get_key_mut(Position::new(0, 3)).unwrap().locked = true;
+    layout.add_layer(layer).unwrap();
+
+    AppState::new(layout, None, geometry, mapping, Config::default()).unwrap()
+}
+
+fn keycodes(state: &AppState) -> Vec<&str> {
+    state.layout.layers[0]
+        .keys
+        .iter()
+        .map(|k| k.keycode.as_str())
+        .collect()
+}
+
+#[test]
+fn test_assign_keycode_to_selection() {
+    let mut state = create_test_state();
+    state.selection_mode = Some(SelectionMode::Rectangle {
+        start: Position::new(0, 1),
+    });
+    state.selected_keys = vec![
+        Position::new(0, 1),
+        Position::new(0, 2),
+        Position::new(0, 3),
+    ];
+
+    assert!(assign_keycode_to_selection(&mut state, "MO(1)"));
+    assert_eq!(keycodes(&state), ["KC_A", "MO(1)", "MO(1)", "QK_BOOT"]);
+    assert!(state.selection_mode.is_none());
+    assert!(state.selected_keys.is_empty());
+    assert!(state.dirty);
+    assert_eq!(
+        state.status_message,
+        "Assigned MO(1) to 2 keys, 1 locked skipped - Ctrl+Z to undo"
+    );
+
+    handle_undo_paste(&mut state).unwrap();
+    assert_eq!(keycodes(&state), ["KC_A", "KC_B", "KC_C", "QK_BOOT"]);
+}
+
+#[test]
+fn test_assign_keycode_needs_bulk_selection() {
+    let mut state = create_test_state();
+    assert!(!assign_keycode_to_selection(&mut state, "KC_X"));
+
+    // A pending swap isn't a selection to edit
+    state.selection_mode = Some(SelectionMode::Swap {
+        first: Position::new(0, 0),
+    });
+    state.selected_keys = vec![Position::new(0, 0)];
+    assert!(!assign_keycode_to_selection(&mut state, "KC_X"));
+    assert_eq!(keycodes(&state), ["KC_A", "KC_B", "KC_C", "QK_BOOT"]);
+    assert!(!state.dirty);
+}
+
+#[test]
+fn test_copy_keys_to_layer_can_be_undone() {
+    let mut state = create_test_state();
+    let mut nav = Layer::new(1, "Nav", RgbColor::new(0, 0, 0)).unwrap();
+    for (col, keycode) in ["KC_TRNS", "KC_ESC", "KC_TRNS", "KC_TRNS"]
+        .into_iter()
+        .enumerate()
+    {
+        nav.add_key(KeyDefinition::new(Position::new(0, col as u8), keycode));
+    }
+    state.layout.add_layer(nav).unwrap();
+
+    state.selection_mode = Some(SelectionMode::Normal);
+    state.selected_keys = vec![Position::new(0, 0), Position::new(0, 1)];
+    handle_copy_to_layer(&mut state).unwrap();
+    assert_eq!(state.active_popup, Some(PopupType::CopyToLayer));
+
+    copy_keys_to_layer(
+        &mut state,
+        0,
+        &[Position::new(0, 0), Position::new(0, 1)],
+        1,
+    );
+    let nav: Vec<&str> = state.layout.layers[1]
+        .keys
+        .iter()
+        .map(|k| k.keycode.as_str())
+        .collect();
+    assert_eq!(nav, ["KC_A", "KC_B", "KC_TRNS", "KC_TRNS"]);
+    assert!(state.selection_mode.is_none());
+    assert_eq!(
+        state.status_message,
+        "Copied 2 keys to layer 1, 1 overwritten - Ctrl+Z to undo"
+    );
+
+    handle_undo_paste(&mut state).unwrap();
+    assert_eq!(state.layout.layers[1].keys[1].keycode, "KC_ESC");
+    assert_eq!(keycodes(&state), ["KC_A", "KC_B", "KC_C", "QK_BOOT"]);
+}
@@ -3,6 +3,8 @@
 use anyhow::Result;
 
 use crate::firmware::BuildState;
+use crate::parser::keyboard_json::keyboards_dir_for;
+use crate::services::hooks::{self, Hook};
 use crate::shortcuts::Action;
 use crate::tui::AppState;
 
@@ -48,6 +50,11 @@ pub(super) fn handle_firmware_generation(state: &mut AppState) -> Result<()> {
     match generator.generate() {
         Ok((keymap_path, config_path)) => {
             state.set_status(format!("✓ Generated: {keymap_path}, {config_path}"));
+            run_hook(
+                state,
+                Hook::PostGenerate,
+                &[keymap_path.into(), config_path.into()],
+            );
         }
         Err(e) => {
             state.set_error(format!("Generation failed: {e}"));
@@ -57,30 +64,34 @@ pub(super) fn handle_firmware_generation(state: &mut AppState) -> Result<()> {
     Ok(())
 }
 
-/// Handle firmware build in background
-pub(super) fn handle_firmware_build(state: &mut AppState) -> Result<()> {
+/// QMK tree, keyboard, keymap and converter to build or check.
+type BuildTarget = (std::path::PathBuf, String, String, Option<String>);
+
+/// Generate the firmware files and work out what to compile, for a build or
+/// a check. Returns `None` after reporting why nothing can be compiled.
+fn prepare_build(state: &mut AppState) -> Result<Option<BuildTarget>> {
     // Generate firmware files first (keymap.c, config.h)
     handle_firmware_generation(state)?;
 
     // Check that QMK firmware path is configured
-    let qmk_path = if let Some(path) = &state.config.paths.qmk_firmware {
+    let qmk_path = if let Some(path) = state
+        .config
+        .qmk_tree_path(state.layout.metadata.qmk_tree.as_deref())
+    {
         path.clone()
     } else {
         state.set_error("QMK firmware path not configured");
-        return Ok(());
+        return Ok(None);
     };
 
-    // Initialize build state if needed
-    if state.build_state.is_none() {
-        state.build_state = Some(BuildState::new());
-    }
-
-    let build_state = state.build_state.as_mut().unwrap();
-
     // Check if build already in progress
-    if build_state.is_building() {
+    if state
+        .build_state
+        .as_ref()
+        .is_some_and(BuildState::is_building)
+    {
         state.set_error("Build already in progress");
-        return Ok(());
+        return Ok(None);
     }
 
     // Determine correct keyboard variant path for building
@@ -93,29 +104,94 @@ pub(super) fn handle_firmware_build(state: &mut AppState) -> Result<()> {
     let build_keyboard = state
         .config
         .build
-        .determine_keyboard_variant(&qmk_path, &base_keyboard, key_count)
+        .determine_keyboard_variant(
+            &keyboards_dir_for(
+                &qmk_path,
+                &state.config.paths.keyboard_roots,
+                &base_keyboard,
+            ),
+            &base_keyboard,
+            key_count,
+        )
         .unwrap_or_else(|e| {
             // Log warning but fall back to configured keyboard path
             eprintln!("Warning: Could not determine variant: {e}");
             keyboard.to_string()
         });
 
-    // Start the build
     let keymap = state
         .layout
         .metadata
         .keymap_name
         .clone()
         .unwrap_or_else(|| "default".to_string());
-    build_state.start_build(qmk_path, build_keyboard, keymap)?;
+    let converter = state.layout.metadata.converter.clone();
+    Ok(Some((qmk_path, build_keyboard, keymap, converter)))
+}
+
+/// Handle firmware build in background
+pub(super) fn handle_firmware_build(state: &mut AppState) -> Result<()> {
+    let Some((qmk_path, keyboard, keymap, converter)) = prepare_build(state)? else {
+        return Ok(());
+    };
+
+    // Start the build
+    state
+        .build_state
+        .get_or_insert_with(BuildState::new)
+        .start_build(qmk_path, keyboard, keymap, converter)?;
 
     state.set_status("Build started - check status with Shift+B");
 
     Ok(())
 }
 
+/// Handle the syntax-only check of the generated keymap in background
+pub(super) fn handle_firmware_check(state: &mut AppState) -> Result<()> {
+    let Some((qmk_path, keyboard, keymap, converter)) = prepare_build(state)? else {
+        return Ok(());
+    };
+
+    state
+        .build_state
+        .get_or_insert_with(BuildState::new)
+        .start_check(qmk_path, keyboard, keymap, converter)?;
+
+    state.set_status("Checking keymap.c - see the build log with Shift+B");
+
+    Ok(())
+}
+
+/// Run the `post_build` hook for the firmware of a build that just finished.
+pub fn handle_build_finished(state: &mut AppState) {
+    let firmware = state
+        .build_state
+        .as_ref()
+        .and_then(|build_state| build_state.firmware_path.clone());
+    run_hook(state, Hook::PostBuild, firmware.as_slice());
+}
+
+/// Run `hook` for the open layout file, reporting a failure in the status bar.
+fn run_hook(state: &mut AppState, hook: Hook, artifacts: &[std::path::PathBuf]) {
+    let Some(path) = state.source_path.clone() else {
+        return;
+    };
+    if let Err(e) = hooks::run_hook(&state.config.hooks, hook, &path, artifacts) {
+        state.set_error(format!("{e:#}"));
+    }
+}
+
 /// Dispatch action to appropriate handler
 pub fn dispatch_action(state: &mut AppState, action: Action) -> Result<bool> {
+    // Moving the cursor keeps a held layer key down, anything else releases it
+    if state.held_layer.is_some() && !action.keeps_layer_held() {
+        state.held_layer = None;
+        if action == Action::Cancel {
+            state.set_status("Layer key released");
+            return Ok(false);
+        }
+    }
+
     match action {
         // Navigation (8 actions)
         Action::NavigateUp => navigation::handle_navigate_up(state),
@@ -126,43 +202,63 @@ pub fn dispatch_action(state: &mut AppState, action: Action) -> Result<bool> {
         Action::JumpToLast => navigation::handle_jump_to_last(state),
         Action::NextLayer => navigation::handle_next_layer(state),
         Action::PreviousLayer => navigation::handle_previous_layer(state),
+        Action::JumpToLayer(index) => navigation::handle_jump_to_layer(state, usize::from(index)),
+        Action::HoldLayerKey => navigation::handle_hold_layer_key(state),
+        Action::ToggleResolvedKeys => navigation::handle_toggle_resolved_keys(state),
 
-        // File operations (4 actions)
+        // File operations (6 actions)
         Action::Quit => file_ops::handle_quit(state),
         Action::Save => file_ops::handle_save(state),
         Action::ExportLayout => file_ops::handle_export_layout(state),
+        Action::RestoreBackup => file_ops::handle_restore_backup(state),
+        Action::OpenInBrowser => file_ops::handle_open_in_browser(state),
         Action::SaveAsTemplate => file_ops::handle_save_as_template(state),
 
-        // Popup management (10 actions)
+        // Popup management (11 actions)
         Action::OpenKeycodePicker => popups::handle_open_keycode_picker(state),
         Action::OpenLayerManager => popups::handle_open_layer_manager(state),
         Action::OpenCategoryManager => popups::handle_open_category_manager(state),
         Action::OpenSettings => popups::handle_open_settings(state),
         Action::EditMetadata => popups::handle_edit_metadata(state),
         Action::OpenTapDanceEditor => popups::handle_open_tap_dance_editor(state),
+        Action::SearchKeys => popups::handle_open_key_search(state),
+        Action::OpenMacroEditor => popups::handle_open_macro_editor(state),
+        Action::OpenUnicodeMapEditor => popups::handle_open_unicode_map_editor(state),
+        Action::OpenCustomKeycodeEditor => popups::handle_open_custom_keycode_editor(state),
+        Action::OpenComboEditor => popups::handle_open_combo_editor(state),
+        Action::OpenEncoderEditor => popups::handle_open_encoder_editor(state),
+        Action::ShowLayoutStats => popups::handle_show_layout_stats(state),
+        Action::ShowLayerGraph => popups::handle_show_layer_graph(state),
         Action::SetupWizard => popups::handle_setup_wizard(state),
         Action::BrowseTemplates => popups::handle_browse_templates(state),
         Action::ViewBuildLog => popups::handle_view_build_log(state),
         Action::ToggleHelp => popups::handle_toggle_help(state),
 
-        // Key operations (6 actions)
+        // Key operations (10 actions)
         Action::ClearKey => key_ops::handle_clear_key(state),
+        Action::ClearKeyToNone => key_ops::handle_clear_key_to_none(state),
+        Action::ToggleKeyLock => key_ops::handle_toggle_key_lock(state),
+        Action::FixLayerAccess => key_ops::handle_fix_layer_access(state),
         Action::CopyKey => key_ops::handle_copy_key(state),
         Action::CutKey => key_ops::handle_cut_key(state),
         Action::PasteKey => key_ops::handle_paste_key(state),
+        Action::PasteKeyInPlace => key_ops::handle_paste_key_in_place(state),
         Action::UndoPaste => key_ops::handle_undo_paste(state),
         Action::ToggleCurrentKey => key_ops::handle_toggle_current_key(state),
 
-        // Selection (3 actions)
+        // Selection (4 actions)
         Action::ToggleSelectionMode => selection::handle_toggle_selection_mode(state),
         Action::StartRectangleSelect => selection::handle_start_rectangle_select(state),
         Action::SwapKeys => selection::handle_swap_keys(state),
+        Action::CopyToLayer => selection::handle_copy_to_layer(state),
 
-        // Color management (4 actions)
+        // Color management (6 actions)
         Action::SetIndividualKeyColor => color::handle_set_individual_key_color(state),
         Action::SetLayerColor => color::handle_set_layer_color(state),
+        Action::SetUnderglowColor => color::handle_set_underglow_color(state),
         Action::ToggleLayerColors => color::handle_toggle_layer_colors(state),
         Action::ToggleAllLayerColors => color::handle_toggle_all_layer_colors(state),
+        Action::CycleColorView => color::handle_cycle_color_view(state),
 
         // Category assignment (2 actions)
         Action::AssignCategoryToKey => category::handle_assign_category_to_key(state),
@@ -170,10 +266,14 @@ pub fn dispatch_action(state: &mut AppState, action: Action) -> Result<bool> {
 
         // Firmware (2 actions)
         Action::BuildFirmware => firmware::handle_build_firmware(state),
+        Action::CheckFirmware => firmware::handle_check_firmware(state),
         Action::GenerateFirmware => firmware::handle_generate_firmware(state),
+        Action::FlashFirmware => firmware::handle_flash_firmware(state),
+        Action::ToggleLivePreview => firmware::handle_toggle_live_preview(state),
 
         // Layout (1 action)
         Action::SwitchLayoutVariant => layout::handle_switch_layout_variant(state),
+        Action::SwitchQmkTree => layout::handle_switch_qmk_tree(state),
 
         // Cancel (1 action)
         Action::Cancel => {
@@ -0,0 +1,54 @@
+//! Backup picker input handler (Component trait pattern)
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::services::backup::BackupStore;
+use crate::tui::backup_picker::BackupPickerEvent;
+use crate::tui::component::Component;
+use crate::tui::{ActiveComponent, AppState};
+
+/// Handle input for the backup picker (Component trait pattern)
+pub fn handle_backup_picker_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    let Some(ActiveComponent::BackupPicker(mut picker)) = state.active_component.take() else {
+        state.set_error("Backup picker component not found");
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    match picker.handle_input(key) {
+        Some(BackupPickerEvent::Restore(backup)) => {
+            let Some(path) = state.source_path.clone() else {
+                state.active_popup = None;
+                state.set_error("No file path set");
+                return Ok(false);
+            };
+            let restored = state
+                .config
+                .backup_dir()
+                .and_then(|dir| BackupStore::new(dir).load(&path, &backup.id));
+            match restored {
+                Ok(layout) => {
+                    state.restore_layout(layout);
+                    state.active_popup = None;
+                    let created = backup.created_at.with_timezone(&chrono::Local);
+                    state.set_status(format!(
+                        "Restored backup from {} - press Ctrl+S to keep it",
+                        created.format("%Y-%m-%d %H:%M:%S")
+                    ));
+                    return Ok(false);
+                }
+                Err(e) => state.set_error(format!("Failed to restore backup: {e:#}")),
+            }
+        }
+        Some(BackupPickerEvent::Cancelled) => {
+            state.active_popup = None;
+            state.set_status("Restore cancelled");
+            return Ok(false);
+        }
+        None => {}
+    }
+
+    state.active_component = Some(ActiveComponent::BackupPicker(picker));
+    Ok(false)
+}
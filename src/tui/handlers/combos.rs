@@ -0,0 +1,67 @@
+//! Combo editor input handler (Component trait pattern)
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::tui::combo_editor::{ComboEditor, ComboEditorEvent};
+use crate::tui::component::Component;
+use crate::tui::{ActiveComponent, AppState};
+
+/// Handle input for the combo editor (Component trait pattern)
+pub fn handle_combo_editor_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    let Some(ActiveComponent::ComboEditor(mut editor)) = state.active_component.take() else {
+        state.set_error("Combo editor component not found");
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    match editor.handle_input(key) {
+        Some(ComboEditorEvent::Saved { index, combo }) => {
+            if !state.keycode_db.is_valid(&combo.keycode) {
+                editor.set_error(format!("Unknown keycode '{}'", combo.keycode));
+                state.active_component = Some(ActiveComponent::ComboEditor(editor));
+                return Ok(false);
+            }
+
+            let settings = &mut state.layout.combo_settings;
+            let result = match index {
+                Some(index) => settings.update_keycode_combo(index, combo),
+                None => settings.add_keycode_combo(combo),
+            };
+            match result {
+                Ok(()) => {
+                    // A saved combo is useless while the feature is switched off
+                    let enabled_now = !settings.enabled;
+                    settings.enabled = true;
+                    state.layout.metadata.touch();
+                    state.mark_dirty();
+                    if enabled_now {
+                        state.set_status("Saved combo (combos enabled)");
+                    } else {
+                        state.set_status("Saved combo");
+                    }
+                    editor = ComboEditor::new(&state.layout, &[]);
+                }
+                Err(e) => editor.set_error(e.to_string()),
+            }
+        }
+        Some(ComboEditorEvent::Delete(index)) => {
+            if index < state.layout.combo_settings.keycode_combos.len() {
+                state.layout.combo_settings.keycode_combos.remove(index);
+                state.layout.metadata.touch();
+                state.mark_dirty();
+                state.set_status(format!("Deleted combo {}", index + 1));
+            }
+            editor = ComboEditor::new(&state.layout, &[]);
+        }
+        Some(ComboEditorEvent::Cancelled) => {
+            state.active_popup = None;
+            state.set_status("Combo editor closed");
+            return Ok(false);
+        }
+        None => {}
+    }
+
+    state.active_component = Some(ActiveComponent::ComboEditor(editor));
+    Ok(false)
+}
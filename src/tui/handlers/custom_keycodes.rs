@@ -0,0 +1,135 @@
+//! Custom keycode editor input handler (Component trait pattern)
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::keycode_db::KeycodeDb;
+use crate::models::CustomKeycode;
+use crate::tui::component::Component;
+use crate::tui::custom_keycode_editor::{CustomKeycodeEditor, CustomKeycodeEditorEvent};
+use crate::tui::{ActiveComponent, AppState};
+
+/// Handle input for the custom keycode editor (Component trait pattern)
+pub fn handle_custom_keycode_editor_input(
+    state: &mut AppState,
+    key: event::KeyEvent,
+) -> Result<bool> {
+    let Some(ActiveComponent::CustomKeycodeEditor(mut editor)) = state.active_component.take()
+    else {
+        state.set_error("Custom keycode editor component not found");
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    match editor.handle_input(key) {
+        Some(CustomKeycodeEditorEvent::Selected(keycode)) => {
+            if let Some(key) = state.get_selected_key_mut() {
+                key.keycode = keycode.clone();
+                state.mark_dirty();
+                state.set_status(format!("Applied: {keycode}"));
+            } else {
+                state.set_error("No key selected");
+            }
+
+            state.active_popup = None;
+            return Ok(false);
+        }
+        Some(CustomKeycodeEditorEvent::Saved { original, custom }) => {
+            let name = custom.name.clone();
+            match save_custom_keycode(state, original.as_deref(), custom) {
+                Ok(()) => {
+                    state.sync_custom_keycodes();
+                    state.mark_dirty();
+                    state.set_status(format!("Saved custom keycode '{name}'"));
+                    editor = CustomKeycodeEditor::new(&state.layout);
+                }
+                // Keep the form open so the snippet isn't lost
+                Err(e) => editor.set_form_error(e.to_string()),
+            }
+        }
+        Some(CustomKeycodeEditorEvent::Delete(name)) => {
+            // Keys using a deleted keycode would no longer compile
+            let uses = state
+                .layout
+                .layers
+                .iter()
+                .flat_map(|layer| &layer.keys)
+                .filter(|key| key.keycode == name)
+                .count();
+            if uses > 0 {
+                state.set_error(format!(
+                    "Custom keycode '{name}' is used by {uses} key(s); reassign them first"
+                ));
+            } else {
+                state.layout.remove_custom_keycode(&name);
+                state.sync_custom_keycodes();
+                state.mark_dirty();
+                state.set_status(format!("Deleted custom keycode '{name}'"));
+                editor = CustomKeycodeEditor::new(&state.layout);
+            }
+        }
+        Some(CustomKeycodeEditorEvent::Cancelled) => {
+            state.active_popup = None;
+            state.set_status("Custom keycode editor closed");
+            return Ok(false);
+        }
+        None => {}
+    }
+
+    state.active_component = Some(ActiveComponent::CustomKeycodeEditor(editor));
+    Ok(false)
+}
+
+/// Add a custom keycode or replace the one named `original`, renaming keys
+/// that use it. The layout is left unchanged if validation fails.
+fn save_custom_keycode(
+    state: &mut AppState,
+    original: Option<&str>,
+    custom: CustomKeycode,
+) -> Result<()> {
+    let builtin = state.keycode_db.get(&custom.name).is_some_and(|def| {
+        def.category != KeycodeDb::CUSTOM_CATEGORY && def.category != KeycodeDb::HELPERS_CATEGORY
+    });
+    if builtin {
+        anyhow::bail!("'{}' is already a QMK keycode", custom.name);
+    }
+
+    let previous = state.layout.custom_keycodes.clone();
+    let new_name = custom.name.clone();
+    let old_name = match original {
+        Some(original) => {
+            let Some(index) = previous.iter().position(|c| c.name == original) else {
+                anyhow::bail!("Custom keycode '{original}' not found");
+            };
+            state.layout.custom_keycodes[index] = custom;
+            original.to_string()
+        }
+        None => {
+            state.layout.add_custom_keycode(custom)?;
+            new_name.clone()
+        }
+    };
+
+    rename_keycode(state, &old_name, &new_name);
+    if let Err(e) = state.layout.validate_custom_keycodes() {
+        rename_keycode(state, &new_name, &old_name);
+        state.layout.custom_keycodes = previous;
+        return Err(e);
+    }
+
+    state.layout.metadata.touch();
+    Ok(())
+}
+
+fn rename_keycode(state: &mut AppState, from: &str, to: &str) {
+    if from == to {
+        return;
+    }
+    for layer in &mut state.layout.layers {
+        for key in &mut layer.keys {
+            if key.keycode == from {
+                key.keycode = to.to_string();
+            }
+        }
+    }
+}
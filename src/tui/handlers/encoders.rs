@@ -0,0 +1,53 @@
+//! Encoder editor input handler (Component trait pattern)
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::tui::component::Component;
+use crate::tui::encoder_editor::EncoderEditorEvent;
+use crate::tui::{ActiveComponent, AppState};
+
+/// Handle input for the encoder editor (Component trait pattern)
+pub fn handle_encoder_editor_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    let Some(ActiveComponent::EncoderEditor(mut editor)) = state.active_component.take() else {
+        state.set_error("Encoder editor component not found");
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    match editor.handle_input(key) {
+        Some(EncoderEditorEvent::Saved { encoder, binding }) => {
+            let invalid = [&binding.ccw, &binding.cw]
+                .into_iter()
+                .find(|keycode| !state.keycode_db.is_valid(keycode));
+            if let Some(keycode) = invalid {
+                editor.set_error(format!("Unknown keycode '{keycode}'"));
+            } else if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
+                layer.encoder_map.set(encoder, binding.clone());
+                state.layout.metadata.touch();
+                state.mark_dirty();
+                state.set_status(format!("Saved encoder {encoder}"));
+                editor.update_binding(encoder, Some(binding));
+            }
+        }
+        Some(EncoderEditorEvent::Cleared(encoder)) => {
+            if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
+                if layer.encoder_map.remove(encoder).is_some() {
+                    state.layout.metadata.touch();
+                    state.mark_dirty();
+                    state.set_status(format!("Encoder {encoder} reset to default"));
+                }
+            }
+            editor.update_binding(encoder, None);
+        }
+        Some(EncoderEditorEvent::Cancelled) => {
+            state.active_popup = None;
+            state.set_status("Encoder editor closed");
+            return Ok(false);
+        }
+        None => {}
+    }
+
+    state.active_component = Some(ActiveComponent::EncoderEditor(editor));
+    Ok(false)
+}
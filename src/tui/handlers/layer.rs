@@ -1,8 +1,12 @@
 //! Layer manager input handler (Component trait pattern)
 
+use std::collections::HashSet;
+
 use anyhow::Result;
 use crossterm::event;
 
+use crate::models::Position;
+use crate::services::fragments::{self, LayerFragment};
 use crate::tui::component::Component;
 use crate::tui::{ActiveComponent, AppState, LayerManagerEvent};
 
@@ -79,55 +83,68 @@ pub fn handle_layer_manager_input(state: &mut AppState, key: event::KeyEvent) ->
                 }
             }
             LayerManagerEvent::LayerReordered { from, to } => {
-                // Reorder layers (swap)
-                state.layout.layers.swap(from, to);
+                // Move the layer; layer keycodes are rewritten to follow it
+                if let Err(e) = state.layout.move_layer(from, to) {
+                    state.set_error(format!("Failed to move layer: {e}"));
+                } else {
+                    // Adjust current_layer if needed
+                    if state.current_layer == from {
+                        state.current_layer = to;
+                    } else if state.current_layer == to {
+                        state.current_layer = from;
+                    }
 
-                // Renumber layers
-                for (i, layer) in state.layout.layers.iter_mut().enumerate() {
-                    layer.number = i as u8;
-                }
+                    state.mark_dirty();
+                    state.refresh_layer_refs(); // Update layer reference index
+                    state.set_status(format!("Layer moved to position {to}"));
 
-                // Adjust current_layer if needed
-                if state.current_layer == from {
-                    state.current_layer = to;
-                } else if state.current_layer == to {
-                    state.current_layer = from;
+                    // Update component with new layers
+                    manager.set_layers(state.layout.layers.clone());
                 }
-
-                state.mark_dirty();
-                state.set_status(format!("Layer moved to position {to}"));
-
-                // Update component with new layers
-                manager.set_layers(state.layout.layers.clone());
             }
-            LayerManagerEvent::LayerDuplicated {
-                source_index,
-                layer,
-            } => {
-                // Duplicate layer
-                state.layout.layers.push(layer);
-                state.mark_dirty();
-                state.set_status(format!(
-                    "Duplicated layer {} as '{}'",
-                    source_index,
-                    state.layout.layers.last().unwrap().name
-                ));
+            LayerManagerEvent::LayerDuplicated { source_index, name } => {
+                // Insert the copy after its source, shifting later layers
+                match state.layout.duplicate_layer(source_index, &name) {
+                    Ok(index) => {
+                        if state.current_layer >= index {
+                            state.current_layer += 1;
+                        }
+                        state.mark_dirty();
+                        state.refresh_layer_refs(); // Update layer reference index
+                        state.set_status(format!(
+                            "Duplicated layer {source_index} as '{name}' (layer {index})"
+                        ));
 
-                // Update component with new layers
-                manager.set_layers(state.layout.layers.clone());
+                        // Update component with new layers
+                        manager.set_layers(state.layout.layers.clone());
+                        manager.select(index);
+                    }
+                    Err(e) => state.set_error(format!("Failed to duplicate layer: {e}")),
+                }
             }
             LayerManagerEvent::LayerKeysCopied { from, to, keys } => {
-                // Copy keys from source to target
+                // Copy keys from source to target, leaving locked target keys alone
                 if let Some(target) = state.layout.layers.get_mut(to) {
+                    let mut locked = 0;
                     for (pos, keycode, color, category) in keys {
                         if let Some(key) = target.keys.iter_mut().find(|k| k.position == pos) {
+                            if key.locked {
+                                locked += 1;
+                                continue;
+                            }
                             key.keycode = keycode;
                             key.color_override = color;
                             key.category_id = category;
                         }
                     }
                     state.mark_dirty();
-                    state.set_status(format!("Copied layer {from} to layer {to}"));
+                    if locked > 0 {
+                        state.set_status(format!(
+                            "Copied layer {from} to layer {to} ({locked} locked keys kept)"
+                        ));
+                    } else {
+                        state.set_status(format!("Copied layer {from} to layer {to}"));
+                    }
 
                     // Update component with new layers
                     manager.set_layers(state.layout.layers.clone());
@@ -139,10 +156,21 @@ pub fn handle_layer_manager_input(state: &mut AppState, key: event::KeyEvent) ->
                 keys1,
                 keys2,
             } => {
-                // Swap keys between two layers
+                // Swap keys between two layers. Positions locked on either
+                // layer stay as they are on both, so no key is lost.
+                let locked: HashSet<Position> = [layer1, layer2]
+                    .iter()
+                    .filter_map(|&idx| state.layout.layers.get(idx))
+                    .flat_map(|layer| layer.keys.iter().filter(|k| k.locked))
+                    .map(|k| k.position)
+                    .collect();
+
                 // Apply keys2 to layer1
                 if let Some(layer) = state.layout.layers.get_mut(layer1) {
                     for (pos, keycode, color, category) in &keys2 {
+                        if locked.contains(pos) {
+                            continue;
+                        }
                         if let Some(key) = layer.keys.iter_mut().find(|k| k.position == *pos) {
                             key.keycode = keycode.clone();
                             key.color_override = *color;
@@ -154,6 +182,9 @@ pub fn handle_layer_manager_input(state: &mut AppState, key: event::KeyEvent) ->
                 // Apply keys1 to layer2
                 if let Some(layer) = state.layout.layers.get_mut(layer2) {
                     for (pos, keycode, color, category) in &keys1 {
+                        if locked.contains(pos) {
+                            continue;
+                        }
                         if let Some(key) = layer.keys.iter_mut().find(|k| k.position == *pos) {
                             key.keycode = keycode.clone();
                             key.color_override = *color;
@@ -163,7 +194,14 @@ pub fn handle_layer_manager_input(state: &mut AppState, key: event::KeyEvent) ->
                 }
 
                 state.mark_dirty();
-                state.set_status(format!("Swapped layers {layer1} and {layer2}"));
+                if locked.is_empty() {
+                    state.set_status(format!("Swapped layers {layer1} and {layer2}"));
+                } else {
+                    state.set_status(format!(
+                        "Swapped layers {layer1} and {layer2} ({} locked positions kept)",
+                        locked.len()
+                    ));
+                }
 
                 // Update component with new layers
                 manager.set_layers(state.layout.layers.clone());
@@ -183,6 +221,61 @@ pub fn handle_layer_manager_input(state: &mut AppState, key: event::KeyEvent) ->
                     manager.set_layers(state.layout.layers.clone());
                 }
             }
+            LayerManagerEvent::FragmentExportRequested { index } => {
+                let saved = LayerFragment::extract(&state.layout, index, &state.keycode_db)
+                    .and_then(|fragment| {
+                        fragments::save_to_library(&fragment, &fragments::fragments_dir()?)
+                    });
+                match saved {
+                    Ok(path) => state.set_status(format!(
+                        "Layer {index} saved as fragment {}",
+                        path.display()
+                    )),
+                    Err(e) => state.set_error(format!("Failed to save fragment: {e}")),
+                }
+            }
+            LayerManagerEvent::FragmentPickerRequested => {
+                match fragments::fragments_dir().and_then(|dir| fragments::list_library(&dir)) {
+                    Ok(saved) => {
+                        let entries = saved
+                            .into_iter()
+                            .map(|(path, fragment)| {
+                                let name = if fragment.source_layout.is_empty() {
+                                    fragment.name
+                                } else {
+                                    format!("{} (from {})", fragment.name, fragment.source_layout)
+                                };
+                                (name, path)
+                            })
+                            .collect();
+                        manager.start_applying_fragment(entries);
+                    }
+                    Err(e) => state.set_error(format!("Failed to list fragments: {e}")),
+                }
+            }
+            LayerManagerEvent::FragmentSelected { target_index, path } => {
+                let applied = LayerFragment::load(&path).and_then(|fragment| {
+                    fragment.apply(&mut state.layout, target_index, &state.keycode_db)
+                });
+                match applied {
+                    Ok(report) => {
+                        state.mark_dirty();
+                        state.refresh_layer_refs();
+                        let summary = format!(
+                            "Fragment applied to layer {target_index}: {}",
+                            report.summary()
+                        );
+                        if let Some(warning) = report.warnings.first() {
+                            let style = state.theme.warning;
+                            state.set_status_with_style(format!("{summary} - {warning}"), style);
+                        } else {
+                            state.set_status(summary);
+                        }
+                        manager.set_layers(state.layout.layers.clone());
+                    }
+                    Err(e) => state.set_error(format!("Failed to apply fragment: {e}")),
+                }
+            }
             LayerManagerEvent::LayerSwitched { index } => {
                 // Switch to selected layer
                 state.current_layer = index;
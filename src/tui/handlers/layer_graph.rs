@@ -0,0 +1,26 @@
+//! Layer graph input handler (Component trait pattern)
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::tui::component::Component;
+use crate::tui::layer_graph::LayerGraphEvent;
+use crate::tui::{ActiveComponent, AppState};
+
+/// Handle input for the layer graph popup (Component trait pattern)
+pub fn handle_layer_graph_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    let Some(ActiveComponent::LayerGraph(mut view)) = state.active_component.take() else {
+        state.set_error("Layer graph component not found");
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    if view.handle_input(key) == Some(LayerGraphEvent::Closed) {
+        state.active_popup = None;
+        state.set_status("Layer graph closed");
+        return Ok(false);
+    }
+
+    state.active_component = Some(ActiveComponent::LayerGraph(view));
+    Ok(false)
+}
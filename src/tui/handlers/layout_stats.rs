@@ -0,0 +1,58 @@
+//! Layout stats input handler (Component trait pattern)
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::services::analysis::analyze_layout;
+use crate::services::thumb_optimizer::CorpusStats;
+use crate::tui::component::Component;
+use crate::tui::layout_stats::LayoutStatsEvent;
+use crate::tui::{ActiveComponent, AppState};
+
+/// Handle input for the layout stats popup (Component trait pattern)
+pub fn handle_layout_stats_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    let Some(ActiveComponent::LayoutStats(mut view)) = state.active_component.take() else {
+        state.set_error("Layout stats component not found");
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    match view.handle_input(key) {
+        Some(LayoutStatsEvent::LoadCorpus(input)) => {
+            let path = corpus_path(state.source_path.as_deref(), &input);
+            match CorpusStats::from_file(&path) {
+                Ok(corpus) => {
+                    let analysis =
+                        analyze_layout(&state.layout, Some(&state.geometry), Some(&corpus));
+                    let name = path
+                        .file_name()
+                        .map_or_else(|| input.clone(), |name| name.to_string_lossy().into_owned());
+                    state.set_status(format!("Heatmap built from {name}"));
+                    view.set_analysis(analysis, name);
+                }
+                Err(e) => state.set_error(format!("Failed to load corpus: {e:#}")),
+            }
+        }
+        Some(LayoutStatsEvent::Closed) => {
+            state.active_popup = None;
+            state.set_status("Layout stats closed");
+            return Ok(false);
+        }
+        None => {}
+    }
+
+    state.active_component = Some(ActiveComponent::LayoutStats(view));
+    Ok(false)
+}
+
+/// Resolves a corpus path typed by the user; relative paths are taken from
+/// the directory of the open layout file.
+fn corpus_path(layout_path: Option<&Path>, input: &str) -> PathBuf {
+    let path = PathBuf::from(input);
+    match layout_path.and_then(Path::parent) {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path,
+    }
+}
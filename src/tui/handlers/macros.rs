@@ -0,0 +1,114 @@
+//! Macro editor input handler (Component trait pattern)
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::tui::component::Component;
+use crate::tui::macro_editor::{MacroEditor, MacroEditorEvent};
+use crate::tui::{ActiveComponent, AppState};
+
+/// Handle input for the macro editor (Component trait pattern)
+pub fn handle_macro_editor_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    let Some(ActiveComponent::MacroEditor(mut editor)) = state.active_component.take() else {
+        state.set_error("Macro editor component not found");
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    match editor.handle_input(key) {
+        Some(MacroEditorEvent::Selected(name)) => {
+            let Some(macro_def) = state.layout.get_macro(&name) else {
+                state.set_error(format!("Macro '{name}' not found"));
+                state.active_component = Some(ActiveComponent::MacroEditor(editor));
+                return Ok(false);
+            };
+
+            let keycode = macro_def.keycode();
+            if let Some(key) = state.get_selected_key_mut() {
+                key.keycode = keycode.clone();
+                state.mark_dirty();
+                state.set_status(format!("Applied: {keycode}"));
+            } else {
+                state.set_error("No key selected");
+            }
+
+            state.active_popup = None;
+            return Ok(false);
+        }
+        Some(MacroEditorEvent::Saved {
+            original,
+            macro_def,
+        }) => {
+            let name = macro_def.name.clone();
+            let result = match original {
+                Some(original) => replace_macro(state, &original, macro_def),
+                None => state.layout.add_macro(macro_def),
+            };
+            match result {
+                Ok(()) => {
+                    state.mark_dirty();
+                    state.set_status(format!("Saved macro '{name}'"));
+                }
+                Err(e) => state.set_error(format!("Failed to save macro: {e}")),
+            }
+            editor = MacroEditor::new(&state.layout);
+        }
+        Some(MacroEditorEvent::Delete(name)) => {
+            // Keys referencing a missing macro would fail layout validation
+            let keycode = format!("MACRO({name})");
+            let uses = state
+                .layout
+                .layers
+                .iter()
+                .flat_map(|layer| &layer.keys)
+                .filter(|key| key.keycode == keycode)
+                .count();
+            if uses > 0 {
+                state.set_error(format!(
+                    "Macro '{name}' is used by {uses} key(s); reassign them first"
+                ));
+            } else {
+                state.layout.remove_macro(&name);
+                state.mark_dirty();
+                state.set_status(format!("Deleted macro '{name}'"));
+                editor = MacroEditor::new(&state.layout);
+            }
+        }
+        Some(MacroEditorEvent::Cancelled) => {
+            state.active_popup = None;
+            state.set_status("Macro editor closed");
+            return Ok(false);
+        }
+        None => {}
+    }
+
+    state.active_component = Some(ActiveComponent::MacroEditor(editor));
+    Ok(false)
+}
+
+/// Replace the macro named `original`, renaming `MACRO()` references on keys.
+fn replace_macro(
+    state: &mut AppState,
+    original: &str,
+    macro_def: crate::models::MacroDefinition,
+) -> Result<()> {
+    let Some(index) = state.layout.macros.iter().position(|m| m.name == original) else {
+        anyhow::bail!("Macro '{original}' not found");
+    };
+
+    let old_keycode = state.layout.macros[index].keycode();
+    let new_keycode = macro_def.keycode();
+    if old_keycode != new_keycode {
+        for layer in &mut state.layout.layers {
+            for key in &mut layer.keys {
+                if key.keycode == old_keycode {
+                    key.keycode.clone_from(&new_keycode);
+                }
+            }
+        }
+    }
+
+    state.layout.macros[index] = macro_def;
+    state.layout.metadata.touch();
+    Ok(())
+}
@@ -2,20 +2,41 @@
 
 pub mod action_handlers;
 pub mod actions;
+pub mod backups;
 pub mod category;
+pub mod combos;
+pub mod custom_keycodes;
+pub mod encoders;
 pub mod layer;
+pub mod layer_graph;
+pub mod layout_stats;
+pub mod macros;
 pub mod main;
 pub mod popups;
+pub mod qmk_trees;
 pub mod settings;
 pub mod tap_dance;
 pub mod templates;
+pub mod unicode;
 
 // Re-export handler functions
 pub use actions::dispatch_action;
+pub use backups::handle_backup_picker_input;
 pub use category::handle_category_manager_input;
+pub use combos::handle_combo_editor_input;
+pub use custom_keycodes::handle_custom_keycode_editor_input;
+pub use encoders::handle_encoder_editor_input;
 pub use layer::handle_layer_manager_input;
+pub use layer_graph::handle_layer_graph_input;
+pub use layout_stats::handle_layout_stats_input;
+pub use macros::handle_macro_editor_input;
 pub use main::handle_main_input;
 pub use popups::handle_popup_input;
+pub use qmk_trees::handle_qmk_tree_picker_input;
 pub use settings::handle_settings_manager_input;
 pub use tap_dance::handle_tap_dance_editor_input;
-pub use templates::{handle_template_browser_input, handle_template_save_dialog_input};
+pub use templates::{
+    handle_template_browser_input, handle_template_fit_input, handle_template_placeholders_input,
+    handle_template_save_dialog_input,
+};
+pub use unicode::handle_unicode_map_editor_input;
@@ -7,11 +7,11 @@
 use anyhow::Result;
 use crossterm::event::{self, KeyCode, KeyModifiers};
 
-use crate::services::LayoutService;
 use crate::tui::component::Component;
+use crate::tui::handlers::action_handlers::file_ops;
 use crate::tui::handlers::popups::parameterized::open_tap_dance_picker_with_context;
-use crate::tui::keycode_picker;
 use crate::tui::onboarding_wizard;
+use crate::tui::render::review_line_count;
 use crate::tui::{AppState, PopupType};
 
 /// Handle input for setup wizard
@@ -236,10 +236,7 @@ pub fn handle_tap_dance_form_input(state: &mut AppState, key: event::KeyEvent) -
                         state.set_status("Tap dance form cancelled");
                     }
                     crate::tui::TapDanceFormContext::FromKeycodePicker => {
-                        let mut picker = keycode_picker::KeycodePicker::with_language(
-                            state.config.ui.last_language.clone(),
-                            &state.keycode_db,
-                        );
+                        let mut picker = state.keycode_picker();
                         picker.set_flow_context(
                             "Tap dance setup".to_string(),
                             "Esc closes picker. Choose TD() again to reopen tap dance form.",
@@ -319,20 +316,69 @@ pub fn handle_export_filename_dialog_input(
     }
 }
 
+/// Handle input for the geometry adjustment review shown before saving
+pub fn handle_geometry_review_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    /// Lines scrolled by Page Up / Page Down.
+    const PAGE_LINES: usize = 10;
+
+    let last_line = state.geometry_adjust_report.as_ref().map_or(0, |report| {
+        review_line_count(report, &state.theme).saturating_sub(1)
+    });
+    let scroll = &mut state.geometry_review_scroll;
+    match key.code {
+        KeyCode::Enter => {
+            // Accept adjustments and save
+            state.active_popup = None;
+            let quit = std::mem::take(&mut state.quit_after_geometry_review);
+            if let Some(path) = &state.source_path.clone() {
+                if file_ops::save_layout(state, path)? {
+                    state.mark_clean();
+                    if quit {
+                        state.should_quit = true;
+                        return Ok(true);
+                    }
+                }
+            }
+            Ok(false)
+        }
+        KeyCode::Esc => {
+            // Undo the adjustments and cancel the save
+            state.active_popup = None;
+            state.quit_after_geometry_review = false;
+            state.revert_geometry_adjustments();
+            state.set_status("Geometry adjustments reverted - save cancelled");
+            Ok(false)
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            *scroll = scroll.saturating_sub(1);
+            Ok(false)
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            *scroll = (*scroll + 1).min(last_line);
+            Ok(false)
+        }
+        KeyCode::PageUp => {
+            *scroll = scroll.saturating_sub(PAGE_LINES);
+            Ok(false)
+        }
+        KeyCode::PageDown => {
+            *scroll = (*scroll + PAGE_LINES).min(last_line);
+            Ok(false)
+        }
+        _ => Ok(false),
+    }
+}
+
 /// Handle input for unsaved changes prompt
 pub fn handle_unsaved_prompt_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
     match key.code {
         KeyCode::Char('s' | 'S')
             if key.modifiers.is_empty() || key.modifiers == KeyModifiers::CONTROL =>
         {
-            // Save and quit
-            if let Some(path) = &state.source_path.clone() {
-                LayoutService::save(&state.layout, path)?;
-                state.mark_clean();
-                state.set_status("Saved");
-            }
-            state.should_quit = true;
-            Ok(true)
+            // Save and quit; stay open if the save was cancelled or waits for review
+            let quit = file_ops::handle_save_and_quit(state)?;
+            state.should_quit = quit;
+            Ok(quit)
         }
         KeyCode::Char('q' | 'Q')
             if key.modifiers.is_empty() || key.modifiers == KeyModifiers::CONTROL =>
@@ -5,7 +5,7 @@
 //!
 //! This module is split into:
 //! - `parameterized` — parameterized keycode flows + keycode/category/tap/modifier pickers
-//! - `pickers` — color, layout, layer, layout-variant, tap-keycode, modifier pickers
+//! - `pickers` — color, layout, layer, layout-variant, tap-keycode, modifier pickers, key search
 //! - `dialogs` — build-log, help-overlay, metadata-editor, setup-wizard, tap-dance-form, export, unsaved-changes
 
 pub mod dialogs;
@@ -23,18 +23,21 @@ use crate::tui::editor::key_editor;
 use crate::tui::{ActiveComponent, AppState, PopupType};
 
 pub use dialogs::{
-    handle_export_filename_dialog_input, handle_setup_wizard_input, handle_tap_dance_form_input,
-    handle_unsaved_prompt_input,
+    handle_export_filename_dialog_input, handle_geometry_review_input, handle_setup_wizard_input,
+    handle_tap_dance_form_input, handle_unsaved_prompt_input,
 };
 #[cfg(test)]
 pub use parameterized::extract_td_name;
-pub use parameterized::{handle_category_picker_event, handle_keycode_picker_event};
+pub use parameterized::{
+    handle_category_picker_event, handle_keycode_picker_event, remember_keycode_picker_choices,
+};
 #[cfg(test)]
 pub use pickers::is_basic_or_layer_keycode;
 pub use pickers::{
-    handle_build_log_input, handle_color_picker_event, handle_help_overlay_input,
-    handle_layer_picker_input, handle_layout_picker_input, handle_metadata_editor_input,
-    handle_modifier_picker_input, handle_tap_keycode_picker_input,
+    handle_build_log_input, handle_color_picker_event, handle_copy_to_layer_input,
+    handle_help_overlay_input, handle_key_search_input, handle_layer_picker_input,
+    handle_layout_picker_input, handle_metadata_editor_input, handle_modifier_picker_input,
+    handle_tap_keycode_picker_input,
 };
 
 /// Dispatch popup key events to the appropriate handler based on
@@ -48,6 +51,7 @@ pub fn handle_popup_input(state: &mut AppState, key: event::KeyEvent) -> Result<
             // Use ContextualComponent trait pattern
             if let Some(ActiveComponent::KeycodePicker(ref mut picker)) = state.active_component {
                 if let Some(event) = picker.handle_input(key, &state.keycode_db) {
+                    remember_keycode_picker_choices(state, &event);
                     return handle_keycode_picker_event(state, event);
                 }
             }
@@ -70,9 +74,14 @@ pub fn handle_popup_input(state: &mut AppState, key: event::KeyEvent) -> Result<
         Some(PopupType::LayerManager) => super::handle_layer_manager_input(state, key),
         Some(PopupType::LayerPicker) => handle_layer_picker_input(state, key),
         Some(PopupType::TemplateBrowser) => super::handle_template_browser_input(state, key),
+        Some(PopupType::TemplateFit) => super::handle_template_fit_input(state, key),
+        Some(PopupType::TemplatePlaceholders) => {
+            super::handle_template_placeholders_input(state, key)
+        }
         Some(PopupType::TemplateSaveDialog) => super::handle_template_save_dialog_input(state, key),
         Some(PopupType::ExportFilenameDialog) => handle_export_filename_dialog_input(state, key),
         Some(PopupType::UnsavedChangesPrompt) => handle_unsaved_prompt_input(state, key),
+        Some(PopupType::GeometryAdjustReview) => handle_geometry_review_input(state, key),
         Some(PopupType::BuildLog) => handle_build_log_input(state, key),
         Some(PopupType::HelpOverlay) => handle_help_overlay_input(state, key),
         Some(PopupType::MetadataEditor) => handle_metadata_editor_input(state, key),
@@ -84,6 +93,19 @@ pub fn handle_popup_input(state: &mut AppState, key: event::KeyEvent) -> Result<
         Some(PopupType::KeyEditor) => key_editor::handle_input(state, key),
         Some(PopupType::TapDanceEditor) => super::handle_tap_dance_editor_input(state, key),
         Some(PopupType::TapDanceForm) => handle_tap_dance_form_input(state, key),
+        Some(PopupType::KeySearch) => handle_key_search_input(state, key),
+        Some(PopupType::CopyToLayer) => handle_copy_to_layer_input(state, key),
+        Some(PopupType::MacroEditor) => super::handle_macro_editor_input(state, key),
+        Some(PopupType::UnicodeMapEditor) => super::handle_unicode_map_editor_input(state, key),
+        Some(PopupType::CustomKeycodeEditor) => {
+            super::handle_custom_keycode_editor_input(state, key)
+        }
+        Some(PopupType::ComboEditor) => super::handle_combo_editor_input(state, key),
+        Some(PopupType::EncoderEditor) => super::handle_encoder_editor_input(state, key),
+        Some(PopupType::BackupPicker) => super::handle_backup_picker_input(state, key),
+        Some(PopupType::LayoutStats) => super::handle_layout_stats_input(state, key),
+        Some(PopupType::LayerGraph) => super::handle_layer_graph_input(state, key),
+        Some(PopupType::QmkTreePicker) => super::handle_qmk_tree_picker_input(state, key),
         _ => {
             // Escape closes any popup
             if key.code == KeyCode::Esc {
@@ -7,8 +7,10 @@ use anyhow::Result;
 
 use crate::keycode_db::{KeycodeDb, ParamType};
 use crate::tui::editor::key_editor;
+use crate::tui::handlers::action_handlers::selection::assign_keycode_to_selection;
 use crate::tui::handlers::popups::pickers::{is_basic_keycode, is_basic_or_layer_keycode};
 use crate::tui::keycode_picker::{self, KeycodePickerEvent};
+use crate::tui::unicode_map_editor::UnicodeKeyKind;
 use crate::tui::{ActiveComponent, AppState, PopupType};
 
 fn tap_dance_flow_label(target: crate::tui::tap_dance_form::FormRow) -> &'static str {
@@ -27,10 +29,7 @@ pub fn open_tap_dance_picker_with_context(
     target: crate::tui::tap_dance_form::FormRow,
     status: &str,
 ) {
-    let mut picker = keycode_picker::KeycodePicker::with_language(
-        state.config.ui.last_language.clone(),
-        &state.keycode_db,
-    );
+    let mut picker = state.keycode_picker();
     picker.set_flow_context(
         format!("Tap dance → {}", tap_dance_flow_label(target)),
         "Esc returns to tap dance form without losing draft. Enter applies chosen keycode.",
@@ -149,6 +148,21 @@ fn open_picker_for_param_index(state: &mut AppState, param_idx: usize) {
             state.pending_keycode.reset(); // stop further param flow
             state.set_status("Tap Dance: fill name/single/double (hold optional)");
         }
+        ParamType::Macro => {
+            state.open_macro_editor();
+            state.pending_keycode.reset(); // the editor applies MACRO(name) itself
+            state.set_status("Macro: Enter applies the selected macro, n creates one");
+        }
+        ParamType::Unicode => {
+            let key_kind = if prefix == "UC" {
+                UnicodeKeyKind::Codepoint
+            } else {
+                UnicodeKeyKind::Map
+            };
+            state.open_unicode_map_editor(key_kind);
+            state.pending_keycode.reset(); // the editor applies UM()/UC() itself
+            state.set_status("Unicode: Enter applies the selected glyph, n creates one");
+        }
     }
 }
 
@@ -183,11 +197,14 @@ pub fn handle_parameter_collected(state: &mut AppState, param_value: String) {
     } else {
         // All params collected - build final keycode
         if let Some(final_keycode) = state.pending_keycode.build_keycode() {
-            if let Some(key) = state.get_selected_key_mut() {
-                key.keycode = final_keycode.clone();
-                state.mark_dirty();
-                state.refresh_layer_refs(); // Update layer reference index
-                state.set_status(format!("Assigned: {final_keycode}"));
+            // A multi-key selection gets the keycode on every selected key
+            if !assign_keycode_to_selection(state, &final_keycode) {
+                if let Some(key) = state.get_selected_key_mut() {
+                    key.keycode = final_keycode.clone();
+                    state.mark_dirty();
+                    state.refresh_layer_refs(); // Update layer reference index
+                    state.set_status(format!("Assigned: {final_keycode}"));
+                }
             }
         } else {
             state.set_error("Failed to build final keycode");
@@ -198,26 +215,39 @@ pub fn handle_parameter_collected(state: &mut AppState, param_value: String) {
     }
 }
 
+/// Remember the keycode picker's language, favorites and the picked keycode.
+///
+/// Saves the config when the language or favorites changed. A picked keycode
+/// is only recorded in memory; [`AppState::save_recent_keycodes`] saves it
+/// later.
+pub fn remember_keycode_picker_choices(state: &mut AppState, event: &KeycodePickerEvent) {
+    // Read from the active component (not the legacy state)
+    let Some(ActiveComponent::KeycodePicker(ref picker)) = state.active_component else {
+        return;
+    };
+    let ui = &mut state.config.ui;
+    let preferences_changed = ui.last_language != picker.state().selected_language
+        || ui.favorite_keycodes != picker.state().favorites;
+
+    ui.last_language
+        .clone_from(&picker.state().selected_language);
+    ui.favorite_keycodes.clone_from(&picker.state().favorites);
+    if let KeycodePickerEvent::KeycodeSelected(keycode) = event {
+        ui.record_recent_keycode(keycode);
+        state.recent_keycodes_changed = true;
+    }
+
+    if preferences_changed {
+        state.recent_keycodes_changed = false;
+        let _ = state.config.save();
+    }
+}
+
 /// Handle keycode picker events
 pub fn handle_keycode_picker_event(
     state: &mut AppState,
     event: KeycodePickerEvent,
 ) -> Result<bool> {
-    // Save last-used language to config if changed
-    // Get the selected language from the active component (not the legacy state)
-    let selected_language =
-        if let Some(ActiveComponent::KeycodePicker(ref picker)) = state.active_component {
-            picker.state().selected_language.clone()
-        } else {
-            None
-        };
-
-    if selected_language != state.config.ui.last_language {
-        state.config.ui.last_language = selected_language;
-        // Save config to persist the language preference
-        let _ = state.config.save();
-    }
-
     match event {
         KeycodePickerEvent::KeycodeSelected(keycode) => {
             // If user selected TD() directly from the picker, launch the tap dance form
@@ -360,12 +390,17 @@ pub fn handle_keycode_picker_event(
                 return Ok(false);
             }
 
-            // Parameterized keycodes (LT/MT/LM/SH_T/OSM/XXX_T)
             // Parameterized keycodes (LT/MT/LM/SH_T/OSM/XXX_T)
             if start_parameterized_keycode_flow(state, &keycode) {
                 return Ok(false);
             }
 
+            // Bulk assignment to a multi-key selection
+            if assign_keycode_to_selection(state, &keycode) {
+                state.close_component();
+                return Ok(false);
+            }
+
             // Normal keycode assignment
             // Check for transparency conflicts before assigning (need to do this before mut borrow)
             use crate::services::layer_refs::check_transparency_conflict;
@@ -452,7 +487,12 @@ pub fn handle_category_picker_event(
                     if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
                         let mut count = 0;
                         for pos in &state.selected_keys {
-                            if let Some(key) = layer.keys.iter_mut().find(|k| k.position == *pos) {
+                            // Locked keys keep their category
+                            if let Some(key) = layer
+                                .keys
+                                .iter_mut()
+                                .find(|k| k.position == *pos && !k.locked)
+                            {
                                 key.category_id.clone_from(&category_id);
                                 count += 1;
                             }
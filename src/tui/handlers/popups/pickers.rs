@@ -49,6 +49,16 @@ pub fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) ->
                                 ));
                             }
                         }
+                        crate::tui::component::ColorPickerContext::LayerUnderglow => {
+                            if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
+                                layer.underglow_color = Some(color);
+                                state.mark_dirty();
+                                state.set_status(format!(
+                                    "Set layer underglow color to {}",
+                                    color.to_hex()
+                                ));
+                            }
+                        }
                         crate::tui::component::ColorPickerContext::Category => {
                             use crate::tui::category_manager::ManagerMode;
                             use crate::tui::CategoryManager;
@@ -98,8 +108,11 @@ pub fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) ->
                             if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
                                 let mut count = 0;
                                 for pos in &state.selected_keys {
-                                    if let Some(key) =
-                                        layer.keys.iter_mut().find(|k| k.position == *pos)
+                                    // Locked keys keep their color
+                                    if let Some(key) = layer
+                                        .keys
+                                        .iter_mut()
+                                        .find(|k| k.position == *pos && !k.locked)
                                     {
                                         key.color_override = Some(color);
                                         count += 1;
@@ -152,6 +165,13 @@ pub fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) ->
                                 state.set_status("Reset layer color to white");
                             }
                         }
+                        crate::tui::component::ColorPickerContext::LayerUnderglow => {
+                            if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
+                                layer.underglow_color = None;
+                                state.mark_dirty();
+                                state.set_status("Underglow follows the layer color");
+                            }
+                        }
                         crate::tui::component::ColorPickerContext::Category => {
                             state.set_error("Categories must have a color");
                             return Ok(false); // Don't close
@@ -161,8 +181,11 @@ pub fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) ->
                             if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
                                 let mut count = 0;
                                 for pos in &state.selected_keys {
-                                    if let Some(key) =
-                                        layer.keys.iter_mut().find(|k| k.position == *pos)
+                                    // Locked keys keep their color
+                                    if let Some(key) = layer
+                                        .keys
+                                        .iter_mut()
+                                        .find(|k| k.position == *pos && !k.locked)
                                     {
                                         key.color_override = None;
                                         count += 1;
@@ -304,6 +327,7 @@ fn handle_metadata_editor_event(
             description,
             author,
             tags,
+            converter,
             name_changed,
         } => {
             // Apply changes to layout
@@ -311,6 +335,7 @@ fn handle_metadata_editor_event(
             state.layout.metadata.description = description;
             state.layout.metadata.author = author;
             state.layout.metadata.tags = tags;
+            state.layout.metadata.converter = converter;
             state.layout.metadata.modified = chrono::Utc::now();
             state.mark_dirty();
 
@@ -320,6 +345,8 @@ fn handle_metadata_editor_event(
                     match LayoutService::rename_file_if_needed(old_path, &name) {
                         Ok(Some(new_path)) => {
                             state.source_path = Some(new_path);
+                            state.refresh_git_branch();
+                            state.track_source_file();
                             state.set_status(format!("Layout renamed to '{name}'"));
                         }
                         Ok(None) => {
@@ -439,7 +466,7 @@ pub fn handle_layer_picker_input(state: &mut AppState, key: event::KeyEvent) ->
     };
 
     // Handle input and get event
-    if let Some(event) = component.handle_input(key, &state.layout.layers) {
+    if let Some(event) = component.handle_input(key, &state.layout) {
         // Process the event
         handle_layer_picker_event(state, event)?;
     } else {
@@ -450,6 +477,60 @@ pub fn handle_layer_picker_input(state: &mut AppState, key: event::KeyEvent) ->
     Ok(false)
 }
 
+/// Handle input for the key description search popup
+pub fn handle_key_search_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    use crate::tui::key_search::KeySearchEvent;
+
+    let Some(ActiveComponent::KeySearch(mut search)) = state.active_component.take() else {
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    match search.handle_input(key, &state.layout) {
+        Some(KeySearchEvent::KeySelected { layer, position }) => {
+            state.close_component();
+            state.current_layer = layer;
+            state.selected_position = position;
+            state.set_status(format!(
+                "Jumped to layer {layer}, key ({}, {})",
+                position.row, position.col
+            ));
+        }
+        Some(KeySearchEvent::Cancelled) => {
+            state.close_component();
+            state.set_status("Search cancelled");
+        }
+        None => state.active_component = Some(ActiveComponent::KeySearch(search)),
+    }
+
+    Ok(false)
+}
+
+/// Handle input for the copy to layer popup
+pub fn handle_copy_to_layer_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    use crate::tui::copy_to_layer::CopyToLayerEvent;
+    use crate::tui::handlers::action_handlers::selection::copy_keys_to_layer;
+
+    let Some(ActiveComponent::CopyToLayer(mut picker)) = state.active_component.take() else {
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    match picker.handle_input(key, &state.layout) {
+        Some(CopyToLayerEvent::LayerSelected(target)) => {
+            state.close_component();
+            copy_keys_to_layer(state, picker.source_layer(), picker.positions(), target);
+        }
+        Some(CopyToLayerEvent::Cancelled) => {
+            state.close_component();
+            state.set_status("Copy to layer cancelled");
+        }
+        None => state.active_component = Some(ActiveComponent::CopyToLayer(picker)),
+    }
+
+    Ok(false)
+}
+
 /// Handle events from the `LayerPicker` component
 fn handle_layer_picker_event(
     state: &mut AppState,
@@ -25,9 +25,19 @@ fn create_test_state() -> AppState {
         idle_effect_settings: crate::models::IdleEffectSettings::default(),
         rgb_overlay_ripple: crate::models::RgbOverlayRippleSettings::default(),
         palette_fx: crate::models::PaletteFxSettings::default(),
+        rgb_conditions: crate::models::RgbConditionalSettings::default(),
+        firmware_features: crate::models::FirmwareFeatures::default(),
         tap_hold_settings: crate::models::TapHoldSettings::default(),
         combo_settings: crate::models::ComboSettings::default(),
         tap_dances: vec![],
+        macros: vec![],
+        custom_keycodes: Vec::new(),
+        key_overrides: Vec::new(),
+        alt_repeat_keys: Vec::new(),
+        unicode_map: Vec::new(),
+        unicode_input_modes: Vec::new(),
+        usage_counting: false,
+        editing_helpers: crate::models::EditingHelpers::default(),
     };
     let mut state = AppState::new(
         layout,
@@ -37,6 +47,8 @@ fn create_test_state() -> AppState {
         crate::config::Config::default(),
     )
     .unwrap();
+    // Keep backups of saved test layouts out of the real config directory
+    state.config.paths.backup_dir = Some(std::env::temp_dir().join("lazyqmk-test-backups"));
     // Add a dummy key to select
     state.selected_position = crate::models::Position::new(0, 0);
     state
@@ -265,3 +277,524 @@ fn test_is_basic_or_layer_keycode() {
     // Layer references should be rejected
     assert!(!is_basic_or_layer_keycode("MO(@layer_id)"));
 }
+
+#[test]
+fn test_save_with_geometry_adjustments_opens_review() {
+    use crate::models::{KeyDefinition, KeyGeometry, Layer, Position, RgbColor};
+    use crate::tui::handlers::action_handlers::file_ops::handle_save;
+    use crate::tui::render::review_line_count;
+
+    let tmp = tempfile::TempDir::new().unwrap();
+    let path = tmp.path().join("adjusted.json");
+    let mut state = create_test_state();
+    state.source_path = Some(path.clone());
+    state
+        .geometry
+        .add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+    state
+        .geometry
+        .add_key(KeyGeometry::new((0, 1), 1, 1.0, 0.0));
+    state.mapping = crate::models::VisualLayoutMapping::build(&state.geometry);
+    let mut base = Layer::new(0, "Base", RgbColor::default()).unwrap();
+    base.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    base.add_key(KeyDefinition::new(Position::new(3, 3), "KC_B"));
+    state.layout.layers.push(base);
+
+    state.adjust_layers_to_geometry().unwrap();
+    let report = state.geometry_adjust_report.clone().unwrap();
+    assert_eq!(report.added_count(), 1);
+    assert_eq!(report.removed_count(), 1);
+    // Edited after the adjustment
+    state.layout.layers[0].keys[0].keycode = "KC_Z".to_string();
+
+    handle_save(&mut state).unwrap();
+    assert_eq!(state.active_popup, Some(PopupType::GeometryAdjustReview));
+    assert!(!path.exists(), "save must wait for review");
+
+    let key = |code| event::KeyEvent::new(code, event::KeyModifiers::NONE);
+    for _ in 0..50 {
+        handle_geometry_review_input(&mut state, key(KeyCode::Down)).unwrap();
+    }
+    let last_line = review_line_count(&report, &state.theme) - 1;
+    assert_eq!(state.geometry_review_scroll, last_line);
+
+    // Esc undoes the adjustments but keeps the later edit
+    handle_geometry_review_input(&mut state, key(KeyCode::Esc)).unwrap();
+    assert_eq!(state.active_popup, None);
+    assert!(!path.exists());
+    assert!(state.geometry_adjust_report.is_none());
+    let layer = &state.layout.layers[0];
+    assert_eq!(layer.keys.len(), 2);
+    assert_eq!(layer.get_key(Position::new(0, 0)).unwrap().keycode, "KC_Z");
+    assert_eq!(layer.get_key(Position::new(3, 3)).unwrap().keycode, "KC_B");
+    assert!(layer.get_key(Position::new(0, 1)).is_none());
+
+    // Save and quit goes through the review too
+    state.adjust_layers_to_geometry().unwrap();
+    state.mark_dirty();
+    state.active_popup = Some(PopupType::UnsavedChangesPrompt);
+    assert!(!handle_unsaved_prompt_input(&mut state, key(KeyCode::Char('s'))).unwrap());
+    assert_eq!(state.active_popup, Some(PopupType::GeometryAdjustReview));
+    assert!(!path.exists());
+    assert!(!state.should_quit);
+
+    assert!(handle_geometry_review_input(&mut state, key(KeyCode::Enter)).unwrap());
+    assert!(path.exists());
+    assert!(state.should_quit);
+    assert!(state.geometry_adjust_report.is_none());
+    assert!(!state.dirty);
+}
+
+#[test]
+fn test_save_asks_before_overwriting_a_save_made_elsewhere() {
+    use crate::services::LayoutService;
+    use crate::tui::handlers::action_handlers::file_ops::handle_save;
+
+    let tmp = tempfile::TempDir::new().unwrap();
+    let path = tmp.path().join("shared.json");
+    let mut state = create_test_state();
+    let base = crate::models::Layer::new(0, "Base", crate::models::RgbColor::default()).unwrap();
+    state.layout.layers.push(base);
+    state.source_path = Some(path.clone());
+    handle_save(&mut state).unwrap();
+    assert!(path.exists());
+
+    // Another process, such as the web server, saves the same file
+    let mut other = state.layout.clone();
+    other.metadata.name = "Edited in the browser".to_string();
+    LayoutService::save(&other, &path).unwrap();
+
+    state.mark_dirty();
+    handle_save(&mut state).unwrap();
+    assert!(state
+        .error_message
+        .as_deref()
+        .unwrap()
+        .contains("save again"));
+    assert!(state.dirty);
+    assert_eq!(
+        LayoutService::load(&path).unwrap().metadata.name,
+        "Edited in the browser"
+    );
+
+    handle_save(&mut state).unwrap();
+    assert!(!state.dirty);
+    assert_ne!(
+        LayoutService::load(&path).unwrap().metadata.name,
+        "Edited in the browser"
+    );
+}
+
+#[test]
+fn test_disk_changes_reload_a_layout_without_unsaved_edits() {
+    use crate::services::LayoutService;
+    use crate::tui::handlers::action_handlers::file_ops::handle_save;
+    use std::time::{Duration, Instant};
+
+    let tmp = tempfile::TempDir::new().unwrap();
+    let path = tmp.path().join("shared.json");
+    let mut state = create_test_state();
+    let base = crate::models::Layer::new(0, "Base", crate::models::RgbColor::default()).unwrap();
+    state.layout.layers.push(base);
+    state.source_path = Some(path.clone());
+    handle_save(&mut state).unwrap();
+
+    let mut other = state.layout.clone();
+    other.metadata.name = "Edited in the browser".to_string();
+    LayoutService::save(&other, &path).unwrap();
+
+    state.last_disk_check = Instant::now().checked_sub(Duration::from_secs(2)).unwrap();
+    state.check_disk_changes();
+    assert_eq!(state.layout.metadata.name, "Edited in the browser");
+    assert!(state.status_message.starts_with("Reloaded shared.json"));
+    assert!(!state.dirty);
+
+    // Unsaved edits are kept; the user is warned instead
+    state.mark_dirty();
+    other.metadata.name = "Edited again".to_string();
+    LayoutService::save(&other, &path).unwrap();
+    state.last_disk_check = Instant::now().checked_sub(Duration::from_secs(2)).unwrap();
+    state.check_disk_changes();
+    assert_eq!(state.layout.metadata.name, "Edited in the browser");
+    assert!(state
+        .error_message
+        .as_deref()
+        .unwrap()
+        .contains("saving here will overwrite"));
+}
+
+#[test]
+fn test_key_search_jumps_to_described_key() {
+    use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+
+    let mut state = create_test_state();
+    for (idx, name) in ["Base", "Macros"].into_iter().enumerate() {
+        let mut layer = Layer::new(idx as u8, name, RgbColor::new(0, 0, 0)).unwrap();
+        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+        layer.add_key(KeyDefinition::new(Position::new(2, 3), "KC_B"));
+        state.layout.layers.push(layer);
+    }
+    state.layout.layers[1].keys[1].description = Some("Types my email".to_string());
+
+    state.open_key_search();
+    for c in "email".chars() {
+        let key = event::KeyEvent::new(KeyCode::Char(c), event::KeyModifiers::NONE);
+        handle_popup_input(&mut state, key).unwrap();
+    }
+    let enter = event::KeyEvent::new(KeyCode::Enter, event::KeyModifiers::NONE);
+    handle_popup_input(&mut state, enter).unwrap();
+
+    assert_eq!(state.active_popup, None);
+    assert_eq!(state.current_layer, 1);
+    assert_eq!(state.selected_position, Position::new(2, 3));
+}
+
+#[test]
+fn test_key_editor_sets_and_clears_per_key_tapping_term() {
+    use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+
+    let mut state = create_test_state();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(1, 2), "LGUI_T(KC_A)"));
+    state.layout.layers.push(layer);
+    state.selected_position = Position::new(1, 2);
+    let key = state.layout.layers[0].keys[0].clone();
+    state.key_editor_state.init_for_key(&key, 0);
+    state.active_popup = Some(PopupType::KeyEditor);
+
+    let press = |state: &mut AppState, code| {
+        handle_popup_input(state, event::KeyEvent::new(code, event::KeyModifiers::NONE)).unwrap();
+    };
+    for code in [
+        KeyCode::Char('p'),
+        KeyCode::Char('2'),
+        KeyCode::Char('6'),
+        KeyCode::Char('0'),
+        KeyCode::Enter,
+    ] {
+        press(&mut state, code);
+    }
+    assert_eq!(
+        state
+            .layout
+            .tap_hold_settings
+            .key_tapping_term(Position::new(1, 2)),
+        Some(260)
+    );
+    assert!(!state.key_editor_state.is_editing_tapping_term());
+
+    // Clearing the buffer removes the override
+    press(&mut state, KeyCode::Char('p'));
+    for _ in 0..3 {
+        press(&mut state, KeyCode::Backspace);
+    }
+    press(&mut state, KeyCode::Enter);
+    assert!(state
+        .layout
+        .tap_hold_settings
+        .per_key_tapping_terms
+        .is_empty());
+}
+
+#[test]
+fn test_macro_editor_applies_macro_and_keeps_used_macros() {
+    use crate::models::{KeyDefinition, Layer, MacroDefinition, Position, RgbColor};
+
+    let mut state = create_test_state();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    state.layout.layers.push(layer);
+    state
+        .layout
+        .add_macro(MacroDefinition::from_sequence("email", "me@example.com").unwrap())
+        .unwrap();
+    state.selected_position = Position::new(0, 0);
+
+    state.open_macro_editor();
+    let enter = event::KeyEvent::new(KeyCode::Enter, event::KeyModifiers::NONE);
+    handle_popup_input(&mut state, enter).unwrap();
+
+    assert_eq!(state.active_popup, None);
+    assert_eq!(state.layout.layers[0].keys[0].keycode, "MACRO(email)");
+
+    // Deleting a macro that a key still uses is refused
+    state.open_macro_editor();
+    let delete = event::KeyEvent::new(KeyCode::Char('D'), event::KeyModifiers::SHIFT);
+    handle_popup_input(&mut state, delete).unwrap();
+
+    assert!(state.layout.get_macro("email").is_some());
+    assert_eq!(state.active_popup, Some(PopupType::MacroEditor));
+}
+
+#[test]
+fn test_unicode_map_editor_renames_glyph_on_keys() {
+    use crate::models::{KeyDefinition, Layer, Position, RgbColor, UnicodeGlyph};
+    use crate::tui::unicode_map_editor::UnicodeKeyKind;
+
+    let mut state = create_test_state();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    state.layout.layers.push(layer);
+    state.layout.unicode_map = vec![UnicodeGlyph::new("snek", 0x1F40D)];
+    state.selected_position = Position::new(0, 0);
+
+    state.open_unicode_map_editor(UnicodeKeyKind::Map);
+    let enter = event::KeyEvent::new(KeyCode::Enter, event::KeyModifiers::NONE);
+    handle_popup_input(&mut state, enter).unwrap();
+    assert_eq!(state.active_popup, None);
+    assert_eq!(state.layout.layers[0].keys[0].keycode, "UM(snek)");
+
+    // Renaming the glyph updates keys that use it
+    state.open_unicode_map_editor(UnicodeKeyKind::Map);
+    for code in [KeyCode::Char('e'), KeyCode::Char('2'), KeyCode::Enter] {
+        handle_popup_input(
+            &mut state,
+            event::KeyEvent::new(code, event::KeyModifiers::NONE),
+        )
+        .unwrap();
+    }
+    assert_eq!(state.layout.unicode_map[0].name, "snek2");
+    assert_eq!(state.layout.layers[0].keys[0].keycode, "UM(snek2)");
+
+    // Deleting a glyph that a key still uses is refused
+    let delete = event::KeyEvent::new(KeyCode::Char('D'), event::KeyModifiers::SHIFT);
+    handle_popup_input(&mut state, delete).unwrap();
+    assert!(state.layout.get_unicode_glyph("snek2").is_some());
+    assert_eq!(state.active_popup, Some(PopupType::UnicodeMapEditor));
+}
+
+#[test]
+fn test_custom_keycode_editor_saves_renames_and_applies() {
+    use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+
+    let mut state = create_test_state();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    state.layout.layers.push(layer);
+    state.selected_position = Position::new(0, 0);
+    let press = |state: &mut AppState, code: KeyCode, modifiers: event::KeyModifiers| {
+        handle_popup_input(state, event::KeyEvent::new(code, modifiers)).unwrap();
+    };
+    let type_text = |state: &mut AppState, text: &str| {
+        for c in text.chars() {
+            handle_popup_input(
+                state,
+                event::KeyEvent::new(KeyCode::Char(c), event::KeyModifiers::NONE),
+            )
+            .unwrap();
+        }
+    };
+
+    // Names the keycode database already defines are rejected
+    state.open_custom_keycode_editor();
+    press(&mut state, KeyCode::Char('n'), event::KeyModifiers::NONE);
+    type_text(&mut state, "cw_togg");
+    press(&mut state, KeyCode::Tab, event::KeyModifiers::NONE);
+    press(&mut state, KeyCode::Tab, event::KeyModifiers::NONE);
+    type_text(&mut state, "reset_keyboard();");
+    press(&mut state, KeyCode::Char('s'), event::KeyModifiers::CONTROL);
+    assert!(state.layout.custom_keycodes.is_empty());
+
+    // Fix the name in the still-open form and save
+    press(&mut state, KeyCode::BackTab, event::KeyModifiers::NONE);
+    press(&mut state, KeyCode::BackTab, event::KeyModifiers::NONE);
+    for _ in 0.."CW_TOGG".len() {
+        press(&mut state, KeyCode::Backspace, event::KeyModifiers::NONE);
+    }
+    type_text(&mut state, "CK_BOOT");
+    press(&mut state, KeyCode::Char('s'), event::KeyModifiers::CONTROL);
+    assert_eq!(state.layout.custom_keycodes[0].name, "CK_BOOT");
+    assert!(state.keycode_db.is_valid("CK_BOOT"));
+
+    // Enter applies it to the selected key
+    press(&mut state, KeyCode::Enter, event::KeyModifiers::NONE);
+    assert_eq!(state.active_popup, None);
+    assert_eq!(state.layout.layers[0].keys[0].keycode, "CK_BOOT");
+
+    // Renaming updates keys that use it
+    state.open_custom_keycode_editor();
+    press(&mut state, KeyCode::Char('e'), event::KeyModifiers::NONE);
+    type_text(&mut state, "2");
+    press(&mut state, KeyCode::Char('s'), event::KeyModifiers::CONTROL);
+    assert_eq!(state.layout.custom_keycodes[0].name, "CK_BOOT2");
+    assert_eq!(state.layout.layers[0].keys[0].keycode, "CK_BOOT2");
+    assert!(!state.keycode_db.is_valid("CK_BOOT"));
+
+    // Deleting a keycode that a key still uses is refused
+    press(&mut state, KeyCode::Char('D'), event::KeyModifiers::SHIFT);
+    assert!(state.layout.get_custom_keycode("CK_BOOT2").is_some());
+    assert_eq!(state.active_popup, Some(PopupType::CustomKeycodeEditor));
+}
+
+#[test]
+fn test_combo_editor_saves_combo_from_selection() {
+    use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+
+    let mut state = create_test_state();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_J"));
+    layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_K"));
+    state.layout.layers.push(layer);
+    state.selected_keys = vec![Position::new(0, 0), Position::new(0, 1)];
+
+    state.open_combo_editor();
+    for code in std::iter::once(KeyCode::Char('n'))
+        .chain("KC_NOPE".chars().map(KeyCode::Char))
+        .chain([KeyCode::Enter])
+    {
+        let key = event::KeyEvent::new(code, event::KeyModifiers::NONE);
+        handle_popup_input(&mut state, key).unwrap();
+    }
+
+    // Unknown keycodes are rejected and the form stays open
+    assert!(state.layout.combo_settings.keycode_combos.is_empty());
+    assert_eq!(state.active_popup, Some(PopupType::ComboEditor));
+
+    for code in [KeyCode::Backspace; 4]
+        .into_iter()
+        .chain("ESC".chars().map(KeyCode::Char))
+        .chain([KeyCode::Enter])
+    {
+        let key = event::KeyEvent::new(code, event::KeyModifiers::NONE);
+        handle_popup_input(&mut state, key).unwrap();
+    }
+
+    let combos = &state.layout.combo_settings;
+    assert!(combos.enabled);
+    assert_eq!(combos.keycode_combos.len(), 1);
+    assert_eq!(combos.keycode_combos[0].keycode, "KC_ESC");
+    assert!(state.dirty);
+}
+
+#[test]
+fn test_restore_backup_loads_previous_save_as_unsaved_changes() {
+    use crate::tui::handlers::action_handlers::file_ops::{handle_restore_backup, handle_save};
+    use crate::tui::handlers::handle_backup_picker_input;
+
+    let tmp = tempfile::TempDir::new().unwrap();
+    let path = tmp.path().join("backed_up.json");
+    let mut state = create_test_state();
+    state.config.paths.backup_dir = Some(tmp.path().join("backups"));
+    let base = crate::models::Layer::new(0, "Base", crate::models::RgbColor::default()).unwrap();
+    state.layout.layers.push(base);
+    state.layout.metadata.name = "First".to_string();
+    state.source_path = Some(path);
+    handle_save(&mut state).unwrap();
+
+    state.layout.metadata.name = "Second".to_string();
+    state.mark_dirty();
+    handle_save(&mut state).unwrap();
+    assert!(!state.dirty);
+
+    handle_restore_backup(&mut state).unwrap();
+    assert_eq!(state.active_popup, Some(PopupType::BackupPicker));
+    let enter = event::KeyEvent::new(KeyCode::Enter, event::KeyModifiers::NONE);
+    handle_backup_picker_input(&mut state, enter).unwrap();
+
+    assert!(state.active_popup.is_none());
+    assert_eq!(state.layout.metadata.name, "First");
+    assert!(state.dirty);
+    assert!(state.status_message.starts_with("Restored backup"));
+}
+
+#[test]
+fn test_save_auto_commits_in_git_repository() {
+    use crate::services::git::GitFileStatus;
+    use crate::tui::handlers::action_handlers::file_ops::handle_save;
+    use std::process::Command;
+
+    let tmp = tempfile::TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(tmp.path())
+            .args(args)
+            .output()
+            .is_ok_and(|output| output.status.success())
+    };
+    if !git(&["init", "--quiet"]) {
+        return;
+    }
+    git(&["config", "user.name", "Test User"]);
+    git(&["config", "user.email", "test@example.com"]);
+    git(&["config", "commit.gpgsign", "false"]);
+
+    let path = tmp.path().join("versioned.json");
+    let mut state = create_test_state();
+    state.config.git.auto_commit = true;
+    let base = crate::models::Layer::new(0, "Base", crate::models::RgbColor::default()).unwrap();
+    state.layout.layers.push(base);
+    state.source_path = Some(path.clone());
+    state.refresh_git_branch();
+    assert!(state.git_repo.is_some());
+
+    handle_save(&mut state).unwrap();
+    assert!(state.status_message.starts_with("Saved and committed"));
+    assert_eq!(state.git_status, Some(GitFileStatus::Committed));
+
+    state.layout.metadata.name = "Renamed".to_string();
+    state.mark_dirty();
+    handle_save(&mut state).unwrap();
+    let log = state.git_repo.as_ref().unwrap().log(&path, 10).unwrap();
+    assert_eq!(log.len(), 2);
+    assert!(log[0].summary.starts_with("Update versioned.json"));
+
+    // Without auto-commit the save stays uncommitted
+    state.config.git.auto_commit = false;
+    state.layout.metadata.name = "Renamed again".to_string();
+    handle_save(&mut state).unwrap();
+    assert_eq!(state.status_message, "Saved");
+    assert_eq!(state.git_status, Some(GitFileStatus::Modified));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_failing_pre_save_hook_cancels_save() {
+    use crate::tui::handlers::action_handlers::file_ops::handle_save;
+
+    let tmp = tempfile::TempDir::new().unwrap();
+    let path = tmp.path().join("hooked.json");
+    let mut state = create_test_state();
+    let base = crate::models::Layer::new(0, "Base", crate::models::RgbColor::default()).unwrap();
+    state.layout.layers.push(base);
+    state.source_path = Some(path.clone());
+    state.mark_dirty();
+    state.config.hooks.pre_save = vec!["false".to_string()];
+
+    handle_save(&mut state).unwrap();
+    assert!(!path.exists());
+    assert!(state.dirty);
+    assert!(state
+        .error_message
+        .as_deref()
+        .is_some_and(|e| e.starts_with("Not saved: pre_save hook")));
+
+    // The post-save hook runs after the file is written
+    state.config.hooks.pre_save.clear();
+    state.config.hooks.post_save = vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        r#"test -f "$1" && touch "$1.synced""#.to_string(),
+        "hook".to_string(),
+    ];
+    handle_save(&mut state).unwrap();
+    assert!(!state.dirty);
+    assert!(tmp.path().join("hooked.json.synced").exists());
+}
+
+#[test]
+fn test_picked_keycodes_are_saved_later() {
+    let mut state = create_test_state();
+    // Never write the real config from tests
+    state.config.safe_mode = true;
+    state.open_keycode_picker();
+
+    let event = KeycodePickerEvent::KeycodeSelected("KC_A".to_string());
+    remember_keycode_picker_choices(&mut state, &event);
+
+    assert_eq!(state.config.ui.recent_keycodes, vec!["KC_A".to_string()]);
+    assert!(state.recent_keycodes_changed);
+
+    state.save_recent_keycodes();
+    assert!(!state.recent_keycodes_changed);
+}
@@ -0,0 +1,53 @@
+//! QMK tree picker input handler (Component trait pattern)
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::config::DEFAULT_QMK_TREE;
+use crate::tui::component::Component;
+use crate::tui::qmk_tree_picker::QmkTreePickerEvent;
+use crate::tui::{ActiveComponent, AppState};
+
+/// Handle input for the QMK tree picker (Component trait pattern)
+pub fn handle_qmk_tree_picker_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    let Some(ActiveComponent::QmkTreePicker(mut picker)) = state.active_component.take() else {
+        state.set_error("QMK tree picker component not found");
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    match picker.handle_input(key) {
+        Some(QmkTreePickerEvent::Select(tree)) => {
+            state.active_popup = None;
+            let name = tree.clone().unwrap_or_else(|| DEFAULT_QMK_TREE.to_string());
+            if tree == state.layout.metadata.qmk_tree {
+                state.set_status(format!("Already building against QMK tree '{name}'"));
+                return Ok(false);
+            }
+
+            // The keyboard may differ between trees, so re-read its geometry
+            let previous = std::mem::replace(&mut state.layout.metadata.qmk_tree, tree);
+            if let Some(layout_variant) = state.layout.metadata.layout_variant.clone() {
+                if let Err(e) = state.rebuild_geometry(&layout_variant) {
+                    state.layout.metadata.qmk_tree = previous;
+                    state.set_error(format!("Keyboard not usable with QMK tree '{name}': {e}"));
+                    return Ok(false);
+                }
+            }
+            state.mark_dirty();
+            state.set_status(format!(
+                "Building against QMK tree '{name}' - press Ctrl+S to keep it"
+            ));
+            return Ok(false);
+        }
+        Some(QmkTreePickerEvent::Cancelled) => {
+            state.active_popup = None;
+            state.set_status("QMK tree unchanged");
+            return Ok(false);
+        }
+        None => {}
+    }
+
+    state.active_component = Some(ActiveComponent::QmkTreePicker(picker));
+    Ok(false)
+}
@@ -2,8 +2,9 @@
 
 use anyhow::Result;
 
+use crate::firmware::size_estimate;
 use crate::models::{
-    ComboAction, HoldDecisionMode, PaletteFxEffect, PaletteFxPalette, RgbBrightness,
+    ComboAction, EditingHelper, HoldDecisionMode, PaletteFxEffect, PaletteFxPalette, RgbBrightness,
     RgbMatrixEffect, RgbSaturation, RippleColorMode, TapHoldPreset, UncoloredKeyBehavior,
 };
 use crate::tui::settings_manager::SettingItem;
@@ -94,8 +95,8 @@ pub(super) fn apply_settings(state: &mut AppState) -> Result<()> {
                 }
             }
             crate::tui::settings_manager::ManagerMode::EditingPath { setting, .. } => {
-                if let Some(value) = manager_state.get_string_value() {
-                    apply_path_setting(state, *setting, value.to_string())?;
+                if let Some(path) = manager_state.get_path_value() {
+                    apply_path_setting(state, *setting, path.display().to_string())?;
                 }
             }
             crate::tui::settings_manager::ManagerMode::SelectingIdleEffectMode { .. } => {
@@ -285,6 +286,26 @@ fn apply_numeric_setting(state: &mut AppState, setting: SettingItem, value: u16)
             };
             state.set_status(format!("Idle effect duration set to: {display}"));
         }
+        SettingItem::RgbDimAfter => {
+            state.layout.rgb_conditions.dim_after_minutes = value;
+            if value == 0 {
+                state.set_status("Dimming after inactivity disabled");
+            } else {
+                state.set_status(format!("Lighting dims after {value} min without input"));
+            }
+        }
+        SettingItem::RgbDimBrightness => {
+            state.layout.rgb_conditions.dim_brightness_pct = value as u8;
+            state.set_status(format!("Dimmed brightness set to: {value}%"));
+        }
+        SettingItem::RgbWpmThreshold => {
+            state.layout.rgb_conditions.wpm_threshold = value as u8;
+            if value == 0 {
+                state.set_status("WPM effect disabled");
+            } else {
+                state.set_status(format!("WPM effect threshold set to: {value}"));
+            }
+        }
         SettingItem::OverlayRippleMaxRipples => {
             state.layout.rgb_overlay_ripple.max_ripples = value as u8;
             state.set_status(format!("Overlay ripple max ripples set to: {value}"));
@@ -321,6 +342,10 @@ fn apply_numeric_setting(state: &mut AppState, setting: SettingItem, value: u16)
         SettingItem::ComboHoldDuration(idx) => {
             update_combo_hold_duration(state, idx, value);
         }
+        SettingItem::NumWordLayer => {
+            state.layout.editing_helpers.num_word_layer = value as u8;
+            state.set_status(format!("Num Word layer set to: {value}"));
+        }
         _ => {}
     }
 }
@@ -362,6 +387,26 @@ fn apply_boolean_setting(state: &mut AppState, setting: SettingItem, value: bool
                 state.set_status(format!("Show help on startup set to: {display}"));
             }
         }
+        SettingItem::ShowLayerMinimap => {
+            state.config.ui.show_layer_minimap = value;
+            if let Err(e) = state.config.save() {
+                state.set_status(format!("Failed to save config: {e}"));
+            } else {
+                let display = if value { "On" } else { "Off" };
+                state.set_status(format!("Layer minimap set to: {display}"));
+            }
+        }
+        SettingItem::HostLayoutLabels => {
+            state.config.ui.host_layout_labels = value;
+            if let Err(e) = state.config.save() {
+                state.set_status(format!("Failed to save config: {e}"));
+            } else if value {
+                state.refresh_host_layout();
+            } else {
+                state.host_layout = None;
+                state.set_status("Host layout labels set to: Off");
+            }
+        }
         SettingItem::IdleEffectEnabled => {
             state.layout.idle_effect_settings.enabled = value;
             let display = if value { "On" } else { "Off" };
@@ -437,6 +482,32 @@ fn apply_boolean_setting(state: &mut AppState, setting: SettingItem, value: bool
                 state.set_status("PaletteFX all palettes disabled");
             }
         }
+        // Firmware features
+        SettingItem::FirmwareFeature(feature) => {
+            state.layout.firmware_features.set(feature, Some(value));
+            let estimate = size_estimate::estimate(&state.layout, &state.geometry);
+            let display = if value { "On" } else { "Off" };
+            let capacity = estimate
+                .capacity_bytes
+                .map(|capacity| format!(" of {}", size_estimate::format_kb(capacity)))
+                .unwrap_or_default();
+            state.set_status(format!(
+                "{} set to: {display} (firmware ~{}{capacity})",
+                feature.display_name(),
+                size_estimate::format_kb(estimate.total_bytes())
+            ));
+        }
+        SettingItem::EditingHelper(helper) => apply_editing_helper(state, helper, value),
+        SettingItem::UsageCounting => {
+            state.layout.usage_counting = value;
+            if value {
+                state.set_status(
+                    "Usage counting on - build and flash, then run lazyqmk collect-usage",
+                );
+            } else {
+                state.set_status("Usage counting off");
+            }
+        }
         _ => {}
     }
 }
@@ -454,11 +525,138 @@ fn apply_string_setting(state: &mut AppState, setting: SettingItem, value: Strin
             state.layout.metadata.touch();
             state.set_status(format!("Keymap name set to: {keymap}"));
         }
+        SettingItem::RgbOffHours => {
+            let value = value.trim();
+            if value.is_empty() {
+                state.layout.rgb_conditions.off_hours = None;
+                state.mark_dirty();
+                state.set_status("Lighting off hours disabled");
+            } else if let Some(range) = crate::models::RgbOffHours::parse(value) {
+                if range.start_minute == range.end_minute {
+                    state.set_error("Off hours start and end must differ");
+                } else {
+                    state.layout.rgb_conditions.off_hours = Some(range);
+                    state.mark_dirty();
+                    state.set_status(format!("Lighting off hours set to: {range}"));
+                }
+            } else {
+                state.set_error("Off hours must look like 22:00-07:00");
+            }
+        }
+        SettingItem::AddKeyOverride | SettingItem::KeyOverride(_) => {
+            apply_key_override(state, setting, value.trim());
+        }
+        SettingItem::AddAltRepeatKey | SettingItem::AltRepeatKey(_) => {
+            apply_alt_repeat_key(state, setting, value.trim());
+        }
+        SettingItem::UnicodeInputModes => apply_unicode_input_modes(state, &value),
+        SettingItem::Userspace => {
+            let value = value.trim();
+            let previous = std::mem::replace(
+                &mut state.config.build.userspace,
+                (!value.is_empty()).then(|| value.to_string()),
+            );
+            if let Err(e) = state.config.save() {
+                state.config.build.userspace = previous;
+                state.set_error(format!("Failed to save config: {e}"));
+            } else if value.is_empty() {
+                state.set_status("Userspace cleared - keymaps are generated standalone");
+            } else {
+                state.set_status(format!("Userspace set to: users/{value}/"));
+            }
+        }
         _ => {}
     }
     Ok(())
 }
 
+/// Set the Unicode input modes from a comma-separated list of mode names.
+fn apply_unicode_input_modes(state: &mut AppState, value: &str) {
+    use crate::models::UnicodeInputMode;
+
+    let mut modes = Vec::new();
+    for name in value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let Some(mode) = UnicodeInputMode::from_name(name) else {
+            let known = UnicodeInputMode::ALL.map(UnicodeInputMode::display_name);
+            state.set_error(format!(
+                "Unknown Unicode input mode '{name}' (use {})",
+                known.join(", ")
+            ));
+            return;
+        };
+        if !modes.contains(&mode) {
+            modes.push(mode);
+        }
+    }
+
+    state.layout.unicode_input_modes = modes;
+    state.mark_dirty();
+    if state.layout.unicode_input_modes.is_empty() {
+        state.set_status("Unicode input modes reset to QMK default");
+    } else {
+        state.set_status(format!(
+            "Unicode input modes: {}",
+            state.layout.unicode_input_modes_display()
+        ));
+    }
+}
+
+/// Add or replace a key override from its compact form.
+fn apply_key_override(state: &mut AppState, setting: SettingItem, value: &str) {
+    if value.is_empty() {
+        return;
+    }
+    let Some(key_override) = crate::models::KeyOverride::parse(value) else {
+        state.set_error("Key override must look like Shift+KC_BSPC = KC_DEL");
+        return;
+    };
+
+    let previous = state.layout.key_overrides.clone();
+    match setting {
+        SettingItem::KeyOverride(idx) if idx < previous.len() => {
+            state.layout.key_overrides[idx] = key_override.clone();
+        }
+        _ => state.layout.key_overrides.push(key_override.clone()),
+    }
+    if let Err(e) = state.layout.validate_key_overrides() {
+        state.layout.key_overrides = previous;
+        state.set_error(e.to_string());
+        return;
+    }
+    state.mark_dirty();
+    state.set_status(format!("Key override set: {key_override}"));
+}
+
+/// Add or replace an alt repeat override from its compact form.
+fn apply_alt_repeat_key(state: &mut AppState, setting: SettingItem, value: &str) {
+    if value.is_empty() {
+        return;
+    }
+    let Some(alt_repeat) = crate::models::AltRepeatKey::parse(value) else {
+        state.set_error("Alt repeat override must look like KC_LEFT = KC_RGHT");
+        return;
+    };
+
+    let previous = state.layout.alt_repeat_keys.clone();
+    match setting {
+        SettingItem::AltRepeatKey(idx) if idx < previous.len() => {
+            state.layout.alt_repeat_keys[idx] = alt_repeat.clone();
+        }
+        _ => state.layout.alt_repeat_keys.push(alt_repeat.clone()),
+    }
+    if let Err(e) = state.layout.validate_alt_repeat_keys() {
+        state.layout.alt_repeat_keys = previous;
+        state.set_error(e.to_string());
+        return;
+    }
+    state.mark_dirty();
+    state.set_status(format!("Alt repeat override set: {alt_repeat}"));
+}
+
 /// Apply a path setting value
 fn apply_path_setting(state: &mut AppState, setting: SettingItem, value: String) -> Result<()> {
     match setting {
@@ -593,3 +791,29 @@ pub(super) fn apply_combo_action(state: &mut AppState, combo_idx: usize, action:
         ));
     }
 }
+
+/// Switch an editing helper on or off, refusing to turn off a helper whose
+/// keycode is still assigned to keys.
+fn apply_editing_helper(state: &mut AppState, helper: EditingHelper, value: bool) {
+    if !value {
+        let uses = state
+            .layout
+            .layers
+            .iter()
+            .flat_map(|layer| &layer.keys)
+            .filter(|key| key.keycode == helper.keycode())
+            .count();
+        if uses > 0 {
+            state.set_error(format!(
+                "{} is used by {uses} key(s); reassign them first",
+                helper.keycode()
+            ));
+            return;
+        }
+    }
+
+    state.layout.editing_helpers.set_enabled(helper, value);
+    state.sync_custom_keycodes();
+    let display = if value { "On" } else { "Off" };
+    state.set_status(format!("{} set to: {display}", helper.display_name()));
+}
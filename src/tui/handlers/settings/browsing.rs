@@ -2,6 +2,7 @@
 
 use anyhow::Result;
 
+use crate::firmware::size_estimate;
 use crate::tui::settings_manager::SettingItem;
 use crate::tui::{ActiveComponent, AppState, PopupType};
 
@@ -130,7 +131,7 @@ pub(super) fn handle_browsing_enter(state: &mut AppState) -> Result<bool> {
                 SettingItem::QmkFirmwarePath => {
                     manager.state_mut().start_editing_path(
                         *setting,
-                        state
+                        &state
                             .config
                             .paths
                             .qmk_firmware
@@ -150,7 +151,7 @@ pub(super) fn handle_browsing_enter(state: &mut AppState) -> Result<bool> {
 
                     // Start wizard directly at keyboard selection step
                     // This closes the settings manager temporarily
-                    match crate::tui::onboarding_wizard::OnboardingWizardState::new_for_keyboard_selection(&qmk_path) {
+                    match crate::tui::onboarding_wizard::OnboardingWizardState::new_for_keyboard_selection(&qmk_path, &state.config.paths.keyboard_roots) {
                         Ok(wizard_state) => {
                             state.wizard_state = wizard_state;
                             state.active_component = None;
@@ -213,14 +214,30 @@ pub(super) fn handle_browsing_enter(state: &mut AppState) -> Result<bool> {
                 SettingItem::OutputDir => {
                     manager.state_mut().start_editing_path(
                         *setting,
-                        state.config.build.output_dir.to_string_lossy().to_string(),
+                        &state.config.build.output_dir.to_string_lossy(),
                     );
                 }
+                SettingItem::Userspace => {
+                    let userspace = state.config.build.userspace.clone().unwrap_or_default();
+                    manager
+                        .state_mut()
+                        .start_editing_string(*setting, userspace);
+                }
                 SettingItem::ShowHelpOnStartup => {
                     manager
                         .state_mut()
                         .start_toggling_boolean(*setting, state.config.ui.show_help_on_startup);
                 }
+                SettingItem::ShowLayerMinimap => {
+                    manager
+                        .state_mut()
+                        .start_toggling_boolean(*setting, state.config.ui.show_layer_minimap);
+                }
+                SettingItem::HostLayoutLabels => {
+                    manager
+                        .state_mut()
+                        .start_toggling_boolean(*setting, state.config.ui.host_layout_labels);
+                }
                 SettingItem::ThemeMode => {
                     let selected = match state.config.ui.theme_mode {
                         crate::config::ThemeMode::Dark => 1,
@@ -260,6 +277,42 @@ pub(super) fn handle_browsing_enter(state: &mut AppState) -> Result<bool> {
                         .state_mut()
                         .start_editing_numeric(*setting, current_secs, 0, 3600, 300);
                 }
+                SettingItem::RgbDimAfter => {
+                    manager.state_mut().start_editing_numeric(
+                        *setting,
+                        state.layout.rgb_conditions.dim_after_minutes,
+                        0,
+                        240,
+                        10,
+                    );
+                }
+                SettingItem::RgbDimBrightness => {
+                    manager.state_mut().start_editing_numeric(
+                        *setting,
+                        u16::from(state.layout.rgb_conditions.dim_brightness_pct),
+                        0,
+                        100,
+                        30,
+                    );
+                }
+                SettingItem::RgbWpmThreshold => {
+                    manager.state_mut().start_editing_numeric(
+                        *setting,
+                        u16::from(state.layout.rgb_conditions.wpm_threshold),
+                        0,
+                        250,
+                        60,
+                    );
+                }
+                SettingItem::RgbOffHours => {
+                    let current = state
+                        .layout
+                        .rgb_conditions
+                        .off_hours
+                        .map(|range| range.to_string())
+                        .unwrap_or_default();
+                    manager.state_mut().start_editing_string(*setting, current);
+                }
                 SettingItem::IdleEffectMode => {
                     manager.state_mut().start_selecting_idle_effect_mode(
                         state.layout.idle_effect_settings.idle_effect_mode,
@@ -440,6 +493,50 @@ pub(super) fn handle_browsing_enter(state: &mut AppState) -> Result<bool> {
                     state.set_status(format!("Removed combo {}", idx + 1));
                     return Ok(false);
                 }
+                SettingItem::AddKeyOverride => {
+                    manager
+                        .state_mut()
+                        .start_editing_string(*setting, String::new());
+                }
+                SettingItem::KeyOverride(idx) => {
+                    let current = state
+                        .layout
+                        .key_overrides
+                        .get(idx)
+                        .map(ToString::to_string)
+                        .unwrap_or_default();
+                    manager.state_mut().start_editing_string(*setting, current);
+                }
+                SettingItem::RemoveKeyOverride(idx) => {
+                    if idx < state.layout.key_overrides.len() {
+                        state.layout.key_overrides.remove(idx);
+                        state.mark_dirty();
+                        state.set_status(format!("Removed key override {}", idx + 1));
+                    }
+                    return Ok(false);
+                }
+                SettingItem::AddAltRepeatKey => {
+                    manager
+                        .state_mut()
+                        .start_editing_string(*setting, String::new());
+                }
+                SettingItem::AltRepeatKey(idx) => {
+                    let current = state
+                        .layout
+                        .alt_repeat_keys
+                        .get(idx)
+                        .map(ToString::to_string)
+                        .unwrap_or_default();
+                    manager.state_mut().start_editing_string(*setting, current);
+                }
+                SettingItem::RemoveAltRepeatKey(idx) => {
+                    if idx < state.layout.alt_repeat_keys.len() {
+                        state.layout.alt_repeat_keys.remove(idx);
+                        state.mark_dirty();
+                        state.set_status(format!("Removed alt repeat override {}", idx + 1));
+                    }
+                    return Ok(false);
+                }
                 SettingItem::ComboKey1(_idx) | SettingItem::ComboKey2(_idx) => {
                     // Signal to parent to enter key selection mode
                     // The parent will handle the actual key navigation
@@ -477,6 +574,37 @@ pub(super) fn handle_browsing_enter(state: &mut AppState) -> Result<bool> {
                         .start_selecting_combo_action(idx, current);
                     state.set_status("Select action - ↑↓ to choose, Enter to apply");
                 }
+                SettingItem::FirmwareFeature(feature) => {
+                    manager.state_mut().start_toggling_boolean(
+                        *setting,
+                        size_estimate::is_enabled(&state.layout, &state.geometry, feature),
+                    );
+                }
+                SettingItem::EditingHelper(helper) => {
+                    manager.state_mut().start_toggling_boolean(
+                        *setting,
+                        state.layout.editing_helpers.is_enabled(helper),
+                    );
+                }
+                SettingItem::NumWordLayer => {
+                    let max_layer = state.layout.layers.len().saturating_sub(1).max(1);
+                    manager.state_mut().start_editing_numeric(
+                        *setting,
+                        u16::from(state.layout.editing_helpers.num_word_layer),
+                        1,
+                        u16::try_from(max_layer).unwrap_or(u16::MAX),
+                        1,
+                    );
+                }
+                SettingItem::UsageCounting => {
+                    manager
+                        .state_mut()
+                        .start_toggling_boolean(*setting, state.layout.usage_counting);
+                }
+                SettingItem::UnicodeInputModes => {
+                    let current = state.layout.unicode_input_modes_display();
+                    manager.state_mut().start_editing_string(*setting, current);
+                }
             }
             state.set_status("Select option with ↑↓, Enter to apply");
         }
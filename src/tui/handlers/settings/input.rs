@@ -3,6 +3,7 @@
 use anyhow::Result;
 use crossterm::event;
 
+use crate::firmware::size_estimate;
 use crate::tui::settings_manager::{ManagerMode, SettingsManagerContext};
 use crate::tui::{ActiveComponent, AppState};
 
@@ -50,6 +51,7 @@ pub fn handle_settings_manager_input(state: &mut AppState, key: event::KeyEvent)
             tap_hold_settings: state.layout.tap_hold_settings.clone(),
             config: state.config.clone(),
             layout: state.layout.clone(),
+            size_estimate: size_estimate::estimate(&state.layout, &state.geometry),
         };
 
         // Handle input and check for events
@@ -139,6 +141,7 @@ pub fn handle_selecting_action_input(state: &mut AppState, key: event::KeyEvent)
             tap_hold_settings: state.layout.tap_hold_settings.clone(),
             config: state.config.clone(),
             layout: state.layout.clone(),
+            size_estimate: size_estimate::estimate(&state.layout, &state.geometry),
         };
         if let Some(event) = manager.handle_input_with_context(key, &context) {
             return super::event::handle_settings_manager_event(state, event);
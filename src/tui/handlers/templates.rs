@@ -3,14 +3,18 @@
 use anyhow::{Context, Result};
 use crossterm::event::{self, KeyCode, KeyModifiers};
 
+use crate::models::Layout;
 use crate::services::LayoutService;
-use crate::tui::{component::Component, template_browser::TemplateBrowserState, AppState};
+use crate::tui::template_fit::{TemplateFitDialog, TemplateFitEvent};
+use crate::tui::template_placeholders::{TemplatePlaceholderDialog, TemplatePlaceholderEvent};
+use crate::tui::{
+    component::Component, template_browser::TemplateBrowserState, ActiveComponent, AppState,
+    PopupType,
+};
 
 /// Handle input for template browser
 pub fn handle_template_browser_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
     // Use Component trait pattern
-    use crate::tui::ActiveComponent;
-
     if let Some(ActiveComponent::TemplateBrowser(ref mut browser)) = state.active_component {
         if let Some(event) = browser.handle_input(key) {
             return handle_template_browser_event(state, event);
@@ -32,12 +36,15 @@ fn handle_template_browser_event(
             match LayoutService::load(&path)
                 .with_context(|| format!("Loading template from {}", path.display()))
             {
-                Ok(layout) => {
-                    state.layout = layout;
-                    state.source_path = None; // New layout from template
-                    state.mark_dirty(); // Mark as dirty since it's unsaved
-                    state.close_component();
-                    state.set_status("Template loaded");
+                Ok(template) if template.required_placeholders().is_empty() => {
+                    load_template(state, template);
+                }
+                Ok(template) => {
+                    let dialog = TemplatePlaceholderDialog::new(template);
+                    state.active_component =
+                        Some(ActiveComponent::TemplatePlaceholders(Box::new(dialog)));
+                    state.active_popup = Some(PopupType::TemplatePlaceholders);
+                    state.set_status("Enter values for the template's placeholders");
                 }
                 Err(e) => {
                     state.set_error(format!("Failed to load template: {e}"));
@@ -57,6 +64,135 @@ fn handle_template_browser_event(
     Ok(false)
 }
 
+/// Loads `template` as the new layout, fitting it to the open keyboard first
+/// when it was made for a different keyboard or layout variant.
+///
+/// Template keys the open keyboard has no position for open the template fit
+/// dialog so the user decides where they go.
+fn load_template(state: &mut AppState, template: Layout) {
+    let (Some(keyboard), Some(variant)) = (
+        state.layout.metadata.keyboard.clone(),
+        state.layout.metadata.layout_variant.clone(),
+    ) else {
+        set_template_layout(state, template);
+        state.close_component();
+        state.set_status("Template loaded");
+        return;
+    };
+    if template.metadata.keyboard.as_deref() == Some(keyboard.as_str())
+        && template.metadata.layout_variant.as_deref() == Some(variant.as_str())
+    {
+        set_template_layout(state, template);
+        state.close_component();
+        state.set_status("Template loaded");
+        return;
+    }
+
+    let destination = state
+        .mapping
+        .get_all_visual_positions()
+        .into_iter()
+        .collect();
+    let dialog = TemplateFitDialog::new(template, keyboard, variant, destination);
+    if dialog.needs_mapping() {
+        state.active_component = Some(ActiveComponent::TemplateFit(Box::new(dialog)));
+        state.active_popup = Some(PopupType::TemplateFit);
+        state.set_status("Template doesn't fit this keyboard - choose where its extra keys go");
+        return;
+    }
+    state.close_component();
+    apply_template_fit(state, &dialog);
+}
+
+/// Places the template of `dialog` on the open keyboard and loads it.
+fn apply_template_fit(state: &mut AppState, dialog: &TemplateFitDialog) {
+    match dialog.apply() {
+        Ok((mut layout, report)) => {
+            // The keyboard stays the same, so does the controller conversion
+            layout
+                .metadata
+                .converter
+                .clone_from(&state.layout.metadata.converter);
+            set_template_layout(state, layout);
+            if report.is_empty() {
+                state.set_status("Template loaded");
+            } else {
+                state.set_status(format!("Template loaded: {}", report.summary()));
+            }
+        }
+        Err(e) => state.set_error(format!("Failed to fit template: {e:#}")),
+    }
+}
+
+/// Replaces the layout with one created from a template.
+fn set_template_layout(state: &mut AppState, layout: Layout) {
+    state.layout = layout;
+    state.source_path = None; // New layout from template
+    state.refresh_git_branch();
+    state.track_source_file();
+    state.mark_dirty(); // Mark as dirty since it's unsaved
+}
+
+/// Handle input for the template fit dialog
+pub fn handle_template_fit_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    let Some(ActiveComponent::TemplateFit(mut dialog)) = state.active_component.take() else {
+        state.set_error("Template fit dialog not found");
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    match dialog.handle_input(key) {
+        Some(TemplateFitEvent::Apply) => {
+            state.active_popup = None;
+            apply_template_fit(state, &dialog);
+            return Ok(false);
+        }
+        Some(TemplateFitEvent::Cancelled) => {
+            state.active_popup = None;
+            state.set_status("Template not loaded");
+            return Ok(false);
+        }
+        None => {}
+    }
+
+    state.active_component = Some(ActiveComponent::TemplateFit(dialog));
+    Ok(false)
+}
+
+/// Handle input for the template placeholder dialog
+pub fn handle_template_placeholders_input(
+    state: &mut AppState,
+    key: event::KeyEvent,
+) -> Result<bool> {
+    let Some(ActiveComponent::TemplatePlaceholders(mut dialog)) = state.active_component.take()
+    else {
+        state.set_error("Template placeholder dialog not found");
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    match dialog.handle_input(key) {
+        Some(TemplatePlaceholderEvent::Apply) => match dialog.fill() {
+            Ok(template) => {
+                state.active_popup = None;
+                load_template(state, template);
+                return Ok(false);
+            }
+            // Keep the dialog open so the value can be fixed
+            Err(e) => state.set_error(format!("{e:#}")),
+        },
+        Some(TemplatePlaceholderEvent::Cancelled) => {
+            state.active_popup = None;
+            state.set_status("Template not loaded");
+            return Ok(false);
+        }
+        None => {}
+    }
+
+    state.active_component = Some(ActiveComponent::TemplatePlaceholders(dialog));
+    Ok(false)
+}
+
 /// Handle input for template save dialog
 pub fn handle_template_save_dialog_input(
     state: &mut AppState,
@@ -0,0 +1,118 @@
+//! Unicode map editor input handler (Component trait pattern)
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::models::layout::unicode::um_name;
+use crate::models::UnicodeGlyph;
+use crate::tui::component::Component;
+use crate::tui::unicode_map_editor::{UnicodeMapEditor, UnicodeMapEditorEvent};
+use crate::tui::{ActiveComponent, AppState};
+
+/// Handle input for the Unicode map editor (Component trait pattern)
+pub fn handle_unicode_map_editor_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    let Some(ActiveComponent::UnicodeMapEditor(mut editor)) = state.active_component.take() else {
+        state.set_error("Unicode map editor component not found");
+        state.active_popup = None;
+        return Ok(false);
+    };
+
+    match editor.handle_input(key) {
+        Some(UnicodeMapEditorEvent::Selected(keycode)) => {
+            if let Some(key) = state.get_selected_key_mut() {
+                key.keycode = keycode.clone();
+                state.mark_dirty();
+                state.set_status(format!("Applied: {keycode}"));
+            } else {
+                state.set_error("No key selected");
+            }
+
+            state.active_popup = None;
+            return Ok(false);
+        }
+        Some(UnicodeMapEditorEvent::Saved { original, glyph }) => {
+            let name = glyph.name.clone();
+            match save_glyph(state, original.as_deref(), glyph) {
+                Ok(()) => {
+                    state.mark_dirty();
+                    state.set_status(format!("Saved glyph '{name}'"));
+                }
+                Err(e) => state.set_error(format!("Failed to save glyph: {e}")),
+            }
+            editor = UnicodeMapEditor::new(&state.layout, editor.key_kind());
+        }
+        Some(UnicodeMapEditorEvent::Delete(name)) => {
+            // Keys referencing a missing glyph would fail layout validation
+            let uses = state
+                .layout
+                .layers
+                .iter()
+                .flat_map(|layer| &layer.keys)
+                .filter(|key| um_name(&key.keycode) == Some(name.as_str()))
+                .count();
+            if uses > 0 {
+                state.set_error(format!(
+                    "Glyph '{name}' is used by {uses} key(s); reassign them first"
+                ));
+            } else {
+                state.layout.unicode_map.retain(|g| g.name != name);
+                state.layout.metadata.touch();
+                state.mark_dirty();
+                state.set_status(format!("Deleted glyph '{name}'"));
+                editor = UnicodeMapEditor::new(&state.layout, editor.key_kind());
+            }
+        }
+        Some(UnicodeMapEditorEvent::Cancelled) => {
+            state.active_popup = None;
+            state.set_status("Unicode map editor closed");
+            return Ok(false);
+        }
+        None => {}
+    }
+
+    state.active_component = Some(ActiveComponent::UnicodeMapEditor(editor));
+    Ok(false)
+}
+
+/// Add a glyph or replace the one named `original`, renaming `UM()`
+/// references on keys. The layout is left unchanged if validation fails.
+fn save_glyph(state: &mut AppState, original: Option<&str>, glyph: UnicodeGlyph) -> Result<()> {
+    let previous_map = state.layout.unicode_map.clone();
+    let new_keycode = glyph.keycode();
+    let old_keycode = match original {
+        Some(original) => {
+            let Some(index) = previous_map.iter().position(|g| g.name == original) else {
+                anyhow::bail!("Glyph '{original}' not found");
+            };
+            state.layout.unicode_map[index] = glyph;
+            previous_map[index].keycode()
+        }
+        None => {
+            state.layout.unicode_map.push(glyph);
+            new_keycode.clone()
+        }
+    };
+
+    rename_keycode(state, &old_keycode, &new_keycode);
+    if let Err(e) = state.layout.validate_unicode() {
+        rename_keycode(state, &new_keycode, &old_keycode);
+        state.layout.unicode_map = previous_map;
+        return Err(e);
+    }
+
+    state.layout.metadata.touch();
+    Ok(())
+}
+
+fn rename_keycode(state: &mut AppState, from: &str, to: &str) {
+    if from == to {
+        return;
+    }
+    for layer in &mut state.layout.layers {
+        for key in &mut layer.keys {
+            if key.keycode == from {
+                key.keycode = to.to_string();
+            }
+        }
+    }
+}
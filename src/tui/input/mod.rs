@@ -1,7 +1,9 @@
 //! Input handling dispatch for the TUI.
 //!
-//! Routes keyboard events to the appropriate handler based on current state.
+//! Routes keyboard and mouse events to the appropriate handler based on current state.
 
 mod dispatch;
+mod mouse;
 
 pub use dispatch::handle_key_event;
+pub use mouse::handle_mouse_event;
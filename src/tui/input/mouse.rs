@@ -0,0 +1,83 @@
+//! Mouse event dispatch.
+//!
+//! Clicks on the layer minimap switch layers. On the keyboard, a click moves
+//! the cursor and dragging a key onto another swaps them. Everything else is
+//! ignored.
+
+use anyhow::Result;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+
+use crate::tui::app_state::{AppState, KeyDrag};
+use crate::tui::editor::keyboard::KeyboardWidget;
+use crate::tui::editor::minimap::LayerMinimap;
+use crate::tui::handlers::action_handlers::{navigation, selection};
+use crate::tui::render::{keyboard_area, minimap_area};
+
+/// Handle mouse input events on a screen of size `screen`
+pub fn handle_mouse_event(state: &mut AppState, mouse: MouseEvent, screen: Rect) -> Result<bool> {
+    if state.active_popup.is_some() || state.error_message.is_some() {
+        state.key_drag = None;
+        return Ok(false);
+    }
+
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            let layer = minimap_area(screen, state)
+                .and_then(|area| LayerMinimap::layer_at(area, state, mouse.column, mouse.row));
+            if let Some(index) = layer {
+                return navigation::handle_jump_to_layer(state, index);
+            }
+
+            let key = KeyboardWidget::key_at(
+                keyboard_area(screen, state),
+                state,
+                mouse.column,
+                mouse.row,
+            );
+            if let Some(position) = key {
+                state.selected_position = position;
+                state.key_drag = Some(KeyDrag {
+                    from: position,
+                    over: Some(position),
+                });
+            }
+            Ok(false)
+        }
+        MouseEventKind::Drag(MouseButton::Left) => {
+            let over = KeyboardWidget::key_at(
+                keyboard_area(screen, state),
+                state,
+                mouse.column,
+                mouse.row,
+            );
+            if let Some(drag) = &mut state.key_drag {
+                drag.over = over;
+                if over != Some(drag.from) {
+                    state.set_status("Drop on a key to swap, outside the keyboard to cancel");
+                }
+            }
+            Ok(false)
+        }
+        MouseEventKind::Up(MouseButton::Left) => {
+            let Some(drag) = state.key_drag.take() else {
+                return Ok(false);
+            };
+            match drag.over {
+                Some(to) if to != drag.from => {
+                    if selection::swap_keys(state, drag.from, to) {
+                        state.selected_position = to;
+                    }
+                }
+                // Released on the starting key: a plain click
+                Some(_) => {}
+                None => state.set_status("Drag cancelled"),
+            }
+            Ok(false)
+        }
+        _ => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests;
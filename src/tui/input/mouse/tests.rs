@@ -0,0 +1,141 @@
+//! Tests for mouse dispatch.
+
+use super::*;
+use crate::config::Config;
+use crate::models::{
+    KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, Position, RgbColor,
+    VisualLayoutMapping,
+};
+use crate::tui::handlers::action_handlers::key_ops;
+use crossterm::event::KeyModifiers;
+
+const SCREEN: Rect = Rect::new(0, 0, 160, 50);
+
+/// Single row of three keys: `KC_A`, `KC_B` and `KC_C`.
+fn create_test_state() -> AppState {
+    let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 1, 3);
+    for col in 0..3u8 {
+        geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+    }
+    let mapping = VisualLayoutMapping::build(&geometry);
+
+    let mut layout = Layout::new("Drag").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(1, 2, 3)).unwrap();
+    for (col, keycode) in ["KC_A", "KC_B", "KC_C"].into_iter().enumerate() {
+        layer.add_key(KeyDefinition::new(Position::new(0, col as u8), keycode).with_label(keycode));
+    }
+    layout.add_layer(layer).unwrap();
+
+    AppState::new(layout, None, geometry, mapping, Config::default()).unwrap()
+}
+
+/// Returns a screen cell inside the key at `position`.
+fn cell_of(state: &AppState, position: Position) -> (u16, u16) {
+    let area = keyboard_area(SCREEN, state);
+    (0..SCREEN.height)
+        .flat_map(|row| (0..SCREEN.width).map(move |column| (column, row)))
+        .find(|&(column, row)| KeyboardWidget::key_at(area, state, column, row) == Some(position))
+        .expect("key is drawn")
+}
+
+fn mouse(state: &mut AppState, kind: MouseEventKind, (column, row): (u16, u16)) {
+    let event = MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    };
+    handle_mouse_event(state, event, SCREEN).unwrap();
+}
+
+fn drag(state: &mut AppState, from: (u16, u16), to: (u16, u16)) {
+    mouse(state, MouseEventKind::Down(MouseButton::Left), from);
+    mouse(state, MouseEventKind::Drag(MouseButton::Left), to);
+    mouse(state, MouseEventKind::Up(MouseButton::Left), to);
+}
+
+fn keycodes(state: &AppState) -> Vec<&str> {
+    (0..3)
+        .map(|col| {
+            state.layout.layers[0]
+                .get_key(Position::new(0, col))
+                .unwrap()
+                .keycode
+                .as_str()
+        })
+        .collect()
+}
+
+#[test]
+fn test_drag_swaps_keys() {
+    let mut state = create_test_state();
+    let from = cell_of(&state, Position::new(0, 0));
+    let to = cell_of(&state, Position::new(0, 2));
+
+    drag(&mut state, from, to);
+    assert_eq!(keycodes(&state), ["KC_C", "KC_B", "KC_A"]);
+    assert_eq!(state.selected_position, Position::new(0, 2));
+    assert!(state.key_drag.is_none());
+    assert!(state.dirty);
+
+    // Labels travel with their keys
+    let key = state.layout.layers[0].get_key(Position::new(0, 0)).unwrap();
+    assert_eq!(key.label.as_deref(), Some("KC_C"));
+
+    // Ctrl+Z swaps them back
+    key_ops::handle_undo_paste(&mut state).unwrap();
+    assert_eq!(keycodes(&state), ["KC_A", "KC_B", "KC_C"]);
+}
+
+#[test]
+fn test_drag_tracks_drop_target() {
+    let mut state = create_test_state();
+    let from = cell_of(&state, Position::new(0, 0));
+    let to = cell_of(&state, Position::new(0, 1));
+
+    mouse(&mut state, MouseEventKind::Down(MouseButton::Left), from);
+    mouse(&mut state, MouseEventKind::Drag(MouseButton::Left), to);
+    assert_eq!(
+        state.key_drag,
+        Some(KeyDrag {
+            from: Position::new(0, 0),
+            over: Some(Position::new(0, 1)),
+        })
+    );
+}
+
+#[test]
+fn test_click_moves_cursor_without_swapping() {
+    let mut state = create_test_state();
+    let cell = cell_of(&state, Position::new(0, 1));
+
+    drag(&mut state, cell, cell);
+    assert_eq!(state.selected_position, Position::new(0, 1));
+    assert_eq!(keycodes(&state), ["KC_A", "KC_B", "KC_C"]);
+    assert!(!state.dirty);
+}
+
+#[test]
+fn test_drop_outside_keyboard_cancels() {
+    let mut state = create_test_state();
+    let from = cell_of(&state, Position::new(0, 0));
+
+    drag(&mut state, from, (SCREEN.width - 1, SCREEN.height - 1));
+    assert_eq!(keycodes(&state), ["KC_A", "KC_B", "KC_C"]);
+    assert!(state.key_drag.is_none());
+}
+
+#[test]
+fn test_drag_refuses_locked_keys() {
+    let mut state = create_test_state();
+    state.layout.layers[0]
+        .get_key_mut(Position::new(0, 2))
+        .unwrap()
+        .locked = true;
+    let from = cell_of(&state, Position::new(0, 0));
+    let to = cell_of(&state, Position::new(0, 2));
+
+    drag(&mut state, from, to);
+    assert_eq!(keycodes(&state), ["KC_A", "KC_B", "KC_C"]);
+    assert!(state.error_message.is_some());
+}
@@ -4,7 +4,7 @@
 //! keys within and across layers. Supports both single-key and multi-key
 //! selection operations, plus undo functionality.
 
-use crate::models::{Position, RgbColor};
+use crate::models::{KeyDefinition, Layer, Position, RgbColor};
 
 /// Content stored in the clipboard (key data without position).
 #[derive(Debug, Clone)]
@@ -17,6 +17,18 @@ pub struct ClipboardContent {
     pub category_id: Option<String>,
 }
 
+impl ClipboardContent {
+    /// Captures the keycode, color, and category of `key`.
+    #[must_use]
+    pub fn from_key(key: &KeyDefinition) -> Self {
+        Self {
+            keycode: key.keycode.clone(),
+            color_override: key.color_override,
+            category_id: key.category_id.clone(),
+        }
+    }
+}
+
 /// Content for multi-key clipboard operations (with relative positions).
 #[derive(Debug, Clone)]
 pub struct MultiKeyContent {
@@ -35,6 +47,79 @@ pub struct UndoState {
     pub original_keys: Vec<(Position, ClipboardContent)>,
     /// Description of the operation (for status message)
     pub description: String,
+    /// Two keys swapped by the operation; undo swaps them back instead of
+    /// restoring `original_keys`
+    pub swapped: Option<(Position, Position)>,
+}
+
+/// A key that pasting onto another layer would overwrite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerPasteConflict {
+    /// Position of the key on both layers
+    pub position: Position,
+    /// Keycode the target layer has now
+    pub current: String,
+    /// Keycode that would replace it
+    pub incoming: String,
+}
+
+/// What pasting keys onto another layer at the same positions would change.
+///
+/// Built by [`plan_layer_paste`] so the change can be previewed before
+/// [`apply_layer_paste`] writes it.
+#[derive(Debug, Clone, Default)]
+pub struct LayerPastePlan {
+    /// Keys to write to the target layer
+    pub writes: Vec<(Position, ClipboardContent)>,
+    /// Target keys that are assigned (not `KC_TRNS`/`KC_NO`) and would change
+    pub conflicts: Vec<LayerPasteConflict>,
+    /// Target keys skipped because they are locked
+    pub locked: Vec<Position>,
+}
+
+/// Plans copying the keys at `positions` from `source` onto `target` at the
+/// same positions.
+///
+/// Positions missing from either layer are ignored.
+#[must_use]
+pub fn plan_layer_paste(source: &Layer, target: &Layer, positions: &[Position]) -> LayerPastePlan {
+    let mut plan = LayerPastePlan::default();
+    for &position in positions {
+        let (Some(from), Some(to)) = (source.get_key(position), target.get_key(position)) else {
+            continue;
+        };
+        if to.locked {
+            plan.locked.push(position);
+            continue;
+        }
+        if !to.is_transparent() && !to.is_no_op() && to.keycode != from.keycode {
+            plan.conflicts.push(LayerPasteConflict {
+                position,
+                current: to.keycode.clone(),
+                incoming: from.keycode.clone(),
+            });
+        }
+        plan.writes
+            .push((position, ClipboardContent::from_key(from)));
+    }
+    plan
+}
+
+/// Writes a planned paste to `target`, returning the replaced keys for undo.
+pub fn apply_layer_paste(
+    target: &mut Layer,
+    plan: &LayerPastePlan,
+) -> Vec<(Position, ClipboardContent)> {
+    let mut originals = Vec::new();
+    for (position, content) in &plan.writes {
+        if let Some(key) = target.get_key_mut(*position) {
+            originals.push((*position, ClipboardContent::from_key(key)));
+            key.keycode.clone_from(&content.keycode);
+            key.color_override = content.color_override;
+            key.category_id.clone_from(&content.category_id);
+        }
+    }
+    originals
 }
 
 /// Clipboard state for key operations.
@@ -268,6 +353,23 @@ impl KeyClipboard {
             layer_index,
             original_keys: keys,
             description,
+            swapped: None,
+        });
+    }
+
+    /// Save undo state for swapping the keys at `first` and `second`.
+    pub fn save_swap_undo(
+        &mut self,
+        layer_index: usize,
+        first: Position,
+        second: Position,
+        description: String,
+    ) {
+        self.undo_state = Some(UndoState {
+            layer_index,
+            original_keys: Vec::new(),
+            description,
+            swapped: Some((first, second)),
         });
     }
 
@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_clipboard_new() {
     let clipboard = KeyClipboard::new();
@@ -214,3 +212,37 @@ fn test_undo_state() {
 
     assert!(!clipboard.can_undo());
 }
+
+fn layer_with(keycodes: &[&str]) -> Layer {
+    let mut layer = Layer::new(0, "Layer", RgbColor::new(0, 0, 0)).unwrap();
+    for (col, keycode) in keycodes.iter().enumerate() {
+        layer.add_key(KeyDefinition::new(Position::new(0, col as u8), *keycode));
+    }
+    layer
+}
+
+#[test]
+fn test_plan_layer_paste_reports_conflicts_and_locked_keys() {
+    let source = layer_with(&["KC_LEFT", "KC_DOWN", "KC_RGHT", "KC_UP"]);
+    let mut target = layer_with(&["KC_TRNS", "KC_NO", "KC_ESC", "KC_UP"]);
+    target.get_key_mut(Position::new(0, 3)).unwrap().locked = true;
+    let positions: Vec<Position> = (0..5).map(|col| Position::new(0, col)).collect();
+
+    let plan = plan_layer_paste(&source, &target, &positions);
+    assert_eq!(plan.writes.len(), 3);
+    assert_eq!(
+        plan.conflicts,
+        [LayerPasteConflict {
+            position: Position::new(0, 2),
+            current: "KC_ESC".to_string(),
+            incoming: "KC_RGHT".to_string(),
+        }]
+    );
+    assert_eq!(plan.locked, [Position::new(0, 3)]);
+
+    let originals = apply_layer_paste(&mut target, &plan);
+    assert_eq!(originals.len(), 3);
+    assert_eq!(originals[2].1.keycode, "KC_ESC");
+    let keycodes: Vec<&str> = target.keys.iter().map(|k| k.keycode.as_str()).collect();
+    assert_eq!(keycodes, ["KC_LEFT", "KC_DOWN", "KC_RGHT", "KC_UP"]);
+}
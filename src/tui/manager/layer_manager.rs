@@ -1,8 +1,11 @@
 //! Layer manager for CRUD operations on layers.
 //!
-//! Provides a UI for creating, renaming, reordering, toggling colors, and deleting layers.
+//! Provides a UI for creating, renaming, reordering, toggling colors, and deleting layers,
+//! and for exporting and applying layer fragments.
 //! Accessible via Shift+L shortcut.
 
+use std::path::PathBuf;
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{layout::Rect, Frame};
 
@@ -30,7 +33,7 @@ pub enum LayerManagerEvent {
         /// New name
         name: String,
     },
-    /// User reordered layers (swap)
+    /// User moved a layer to a neighbouring position
     LayerReordered {
         /// Original index
         from: usize,
@@ -41,8 +44,8 @@ pub enum LayerManagerEvent {
     LayerDuplicated {
         /// Source layer index
         source_index: usize,
-        /// The new layer
-        layer: Layer,
+        /// Name of the copy
+        name: String,
     },
     /// User copied keys from one layer to another
     LayerKeysCopied {
@@ -71,6 +74,20 @@ pub enum LayerManagerEvent {
         /// New enabled state
         enabled: bool,
     },
+    /// User wants to save a layer as a reusable fragment
+    FragmentExportRequested {
+        /// Index of the layer to export
+        index: usize,
+    },
+    /// User wants to pick a saved fragment to apply into the selected layer
+    FragmentPickerRequested,
+    /// User picked a saved fragment to apply
+    FragmentSelected {
+        /// Index of the target layer
+        target_index: usize,
+        /// Path of the fragment file
+        path: PathBuf,
+    },
     /// User wants to switch to a layer
     LayerSwitched {
         /// Index of the layer to switch to
@@ -125,6 +142,15 @@ pub enum ManagerMode {
         /// Currently selected swap target
         target_selected: usize,
     },
+    /// Picking a saved fragment to apply into a layer
+    ApplyingFragment {
+        /// Index of the target layer
+        target_index: usize,
+        /// Saved fragments (display name, path)
+        fragments: Vec<(String, PathBuf)>,
+        /// Currently selected fragment
+        selected: usize,
+    },
 }
 
 /// State for the layer manager dialog
@@ -232,6 +258,15 @@ impl LayerManagerState {
         };
     }
 
+    /// Start picking a fragment to apply into the selected layer
+    pub fn start_applying_fragment(&mut self, fragments: Vec<(String, PathBuf)>) {
+        self.mode = ManagerMode::ApplyingFragment {
+            target_index: self.selected,
+            fragments,
+            selected: 0,
+        };
+    }
+
     /// Navigate in copy-to or swap mode
     pub const fn select_target_previous(&mut self, layer_count: usize) {
         match &mut self.mode {
@@ -336,6 +371,11 @@ impl LayerManager {
         }
     }
 
+    /// Select the layer at `index`
+    pub const fn select(&mut self, index: usize) {
+        self.state.selected = index;
+    }
+
     /// Update the layers list (needed after CRUD operations)
     pub fn set_layers(&mut self, layers: Vec<Layer>) {
         self.cached_layers = layers;
@@ -366,6 +406,7 @@ impl Component for LayerManager {
                 source_index,
                 target_selected,
             } => self.handle_swapping_input(key, *source_index, *target_selected),
+            ManagerMode::ApplyingFragment { .. } => self.handle_fragment_input(key),
         }
     }
 
@@ -375,6 +416,11 @@ impl Component for LayerManager {
 }
 
 impl LayerManager {
+    /// Start picking one of `fragments` to apply into the selected layer
+    pub fn start_applying_fragment(&mut self, fragments: Vec<(String, PathBuf)>) {
+        self.state.start_applying_fragment(fragments);
+    }
+
     /// Handle input in browsing mode
     fn handle_browsing_input(&mut self, key: KeyEvent) -> Option<LayerManagerEvent> {
         match key.code {
@@ -472,6 +518,10 @@ impl LayerManager {
                     None
                 }
             }
+            KeyCode::Char('x') => Some(LayerManagerEvent::FragmentExportRequested {
+                index: self.state.selected,
+            }),
+            KeyCode::Char('f') => Some(LayerManagerEvent::FragmentPickerRequested),
             KeyCode::Char('s') => {
                 // Start swap with another layer
                 if self.cached_layers.len() <= 1 {
@@ -534,36 +584,11 @@ impl LayerManager {
                         }
                         ManagerMode::Duplicating { source_index, .. } => {
                             let source_index = *source_index;
-                            let new_index = self.cached_layers.len();
-
-                            if let Some(source) = self.cached_layers.get(source_index) {
-                                if let Ok(mut new_layer) =
-                                    Layer::new(new_index as u8, &input, source.default_color)
-                                {
-                                    // Copy all keys from source
-                                    for key in &source.keys {
-                                        use crate::models::layer::KeyDefinition;
-                                        let mut new_key =
-                                            KeyDefinition::new(key.position, &key.keycode);
-                                        new_key.color_override = key.color_override;
-                                        new_key.category_id = key.category_id.clone();
-                                        new_layer.add_key(new_key);
-                                    }
-                                    // Copy layer settings
-                                    new_layer.layer_colors_enabled = source.layer_colors_enabled;
-                                    new_layer.category_id = source.category_id.clone();
-
-                                    self.state.cancel();
-                                    Some(LayerManagerEvent::LayerDuplicated {
-                                        source_index,
-                                        layer: new_layer,
-                                    })
-                                } else {
-                                    None
-                                }
-                            } else {
-                                None
-                            }
+                            self.state.cancel();
+                            Some(LayerManagerEvent::LayerDuplicated {
+                                source_index,
+                                name: input,
+                            })
                         }
                         _ => None,
                     }
@@ -729,5 +754,50 @@ impl LayerManager {
     }
 }
 
+impl LayerManager {
+    /// Handle fragment picker input
+    fn handle_fragment_input(&mut self, key: KeyEvent) -> Option<LayerManagerEvent> {
+        let ManagerMode::ApplyingFragment {
+            target_index,
+            fragments,
+            selected,
+        } = &mut self.state.mode
+        else {
+            return None;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.state.cancel();
+                Some(LayerManagerEvent::Cancelled)
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if !fragments.is_empty() {
+                    *selected = selected.checked_sub(1).unwrap_or(fragments.len() - 1);
+                }
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if !fragments.is_empty() {
+                    *selected = (*selected + 1) % fragments.len();
+                }
+                None
+            }
+            KeyCode::Enter => {
+                let event =
+                    fragments
+                        .get(*selected)
+                        .map(|(_, path)| LayerManagerEvent::FragmentSelected {
+                            target_index: *target_index,
+                            path: path.clone(),
+                        });
+                self.state.cancel();
+                event
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Render the layer manager dialog — lives in `layer_manager_render` to keep this file under 1000 lines.
 pub use super::layer_manager_render::render_layer_manager;
@@ -125,6 +125,21 @@ pub fn render_layer_manager(
                 theme,
             );
         }
+        ManagerMode::ApplyingFragment {
+            target_index,
+            fragments,
+            selected,
+        } => {
+            render_fragment_picker(
+                f,
+                inner_area,
+                *target_index,
+                fragments,
+                *selected,
+                layers,
+                theme,
+            );
+        }
     }
 }
 
@@ -141,7 +156,7 @@ fn render_layer_list(
         .direction(ratatui::layout::Direction::Vertical)
         .constraints([
             Constraint::Min(5),    // Layer list
-            Constraint::Length(8), // Help text (more lines now)
+            Constraint::Length(9), // Help text (more lines now)
         ])
         .split(area);
 
@@ -217,6 +232,12 @@ fn render_layer_list(
             Span::styled("v", Style::default().fg(theme.primary)),
             Span::raw(": Layer colors on/off"),
         ]),
+        Line::from(vec![
+            Span::styled("x", Style::default().fg(theme.primary)),
+            Span::raw(": Save as fragment  "),
+            Span::styled("f", Style::default().fg(theme.primary)),
+            Span::raw(": Apply fragment here"),
+        ]),
         Line::from(vec![
             Span::styled("↑/↓", Style::default().fg(theme.primary)),
             Span::raw(": Move selection  "),
@@ -449,3 +470,81 @@ fn render_layer_picker(
 
     f.render_widget(help_widget, chunks[2]);
 }
+
+/// Render the saved fragment picker for applying a fragment into a layer
+fn render_fragment_picker(
+    f: &mut Frame,
+    area: Rect,
+    target_index: usize,
+    fragments: &[(String, std::path::PathBuf)],
+    selected: usize,
+    layers: &[Layer],
+    theme: &Theme,
+) {
+    let chunks = ratatui::layout::Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Target info
+            Constraint::Min(5),    // Fragment list
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    let target_name = layers.get(target_index).map_or("?", |l| l.name.as_str());
+    let info = Paragraph::new(format!("Into Layer {target_index}: {target_name}"))
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.text));
+    f.render_widget(info, chunks[0]);
+
+    let items: Vec<ListItem> = if fragments.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "No saved fragments - press x on a layer to save one",
+            Style::default().fg(theme.text_muted),
+        )))]
+    } else {
+        fragments
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| {
+                let is_selected = i == selected;
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let prefix = if is_selected { "→ " } else { "  " };
+                ListItem::new(Line::from(vec![
+                    Span::raw(prefix),
+                    Span::styled(name.as_str(), style),
+                ]))
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Select Fragment - Apply Fragment"),
+        )
+        .highlight_style(Style::default().bg(theme.surface));
+
+    f.render_widget(list, chunks[1]);
+
+    let help = vec![Line::from(vec![
+        Span::styled("↑/↓", Style::default().fg(theme.primary)),
+        Span::raw(": Select  "),
+        Span::styled("Enter", Style::default().fg(theme.primary)),
+        Span::raw(": Apply  "),
+        Span::styled("Esc", Style::default().fg(theme.primary)),
+        Span::raw(": Cancel"),
+    ])];
+
+    let help_widget = Paragraph::new(help)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.text_muted));
+
+    f.render_widget(help_widget, chunks[2]);
+}
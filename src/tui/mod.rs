@@ -32,8 +32,8 @@ pub mod settings_manager;
 pub mod terminal;
 
 pub use app_state::{
-    ActiveComponent, AppState, CategoryPickerContext, ExportFilenameDialogState, SelectionMode,
-    TapDanceFormContext, TemplateSaveDialogState,
+    ActiveComponent, AppState, CategoryPickerContext, ColorView, ExportFilenameDialogState,
+    SelectionMode, TapDanceFormContext, TemplateSaveDialogState,
 };
 pub use event_loop::run_tui;
 pub use popup_type::{popup_border_style, popup_title, PopupType};
@@ -44,14 +44,17 @@ pub use component::Component;
 pub use config_dialogs::LayoutPickerEvent as LayoutVariantPickerEvent;
 
 pub use dialog::{
-    config_dialogs, help_overlay, help_registry, onboarding_wizard, status_bar, theme,
+    config_dialogs, help_overlay, help_registry, layer_graph, layout_stats, onboarding_wizard,
+    status_bar, theme,
 };
-pub use editor::{keyboard, metadata_editor};
+pub use editor::{combo_editor, encoder_editor, keyboard, metadata_editor};
 pub use manager::{build_log, category_manager, clipboard, layer_manager};
 
 pub use picker::{
-    category_picker, color_picker, keycode_picker, layer_picker, layout_picker, modifier_picker,
-    tap_dance_editor, tap_dance_form, template_browser,
+    backup_picker, category_picker, color_picker, copy_to_layer, custom_keycode_editor,
+    file_browser, key_search, keycode_picker, layer_picker, layout_picker, macro_editor,
+    modifier_picker, qmk_tree_picker, tap_dance_editor, tap_dance_form, template_browser,
+    template_fit, template_placeholders, unicode_map_editor,
 };
 
 pub use category_manager::CategoryManager;
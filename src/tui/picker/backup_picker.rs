@@ -0,0 +1,235 @@
+//! Backup picker for restoring the layout from an automatic backup.
+//!
+//! Lists the backups of the open layout file, newest first. Restoring emits
+//! an event; the handler loads the backup into the editor as unsaved changes.
+//! With unsaved edits open, Enter has to be pressed twice.
+
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::services::backup::BackupInfo;
+use crate::tui::theme::Theme;
+use crate::tui::Component;
+
+/// Events emitted by the backup picker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackupPickerEvent {
+    /// User chose a backup to restore
+    Restore(BackupInfo),
+    /// User closed the picker
+    Cancelled,
+}
+
+/// Backup picker component state
+#[derive(Debug, Clone)]
+pub struct BackupPicker {
+    /// Backups of the open layout, newest first
+    backups: Vec<BackupInfo>,
+    /// Index of the highlighted backup
+    selected: usize,
+    /// Whether restoring discards unsaved edits
+    unsaved_changes: bool,
+    /// Set after the first Enter when unsaved edits would be lost
+    confirming: bool,
+}
+
+impl BackupPicker {
+    /// Creates a picker for `backups` (newest first).
+    #[must_use]
+    pub const fn new(backups: Vec<BackupInfo>, unsaved_changes: bool) -> Self {
+        Self {
+            backups,
+            selected: 0,
+            unsaved_changes,
+            confirming: false,
+        }
+    }
+
+    /// Returns the highlighted backup.
+    #[must_use]
+    pub fn selected(&self) -> Option<&BackupInfo> {
+        self.backups.get(self.selected)
+    }
+}
+
+impl Component for BackupPicker {
+    type Event = BackupPickerEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                self.confirming = false;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.backups.len().saturating_sub(1));
+                self.confirming = false;
+            }
+            KeyCode::Enter => {
+                let backup = self.selected()?.clone();
+                if self.unsaved_changes && !self.confirming {
+                    self.confirming = true;
+                    return None;
+                }
+                return Some(BackupPickerEvent::Restore(backup));
+            }
+            KeyCode::Esc => return Some(BackupPickerEvent::Cancelled),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, _area: Rect, theme: &Theme) {
+        let area = centered_rect(60, 60, frame.area());
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(theme.background)),
+            area,
+        );
+
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        let now = Utc::now();
+        let items: Vec<ListItem> = if self.backups.is_empty() {
+            vec![ListItem::new(
+                Line::from("No backups yet. One is taken every time the layout is saved.")
+                    .style(Style::default().fg(theme.text_muted)),
+            )]
+        } else {
+            self.backups
+                .iter()
+                .map(|backup| {
+                    let created = backup.created_at.with_timezone(&Local);
+                    ListItem::new(Line::from(vec![
+                        Span::raw(created.format("%Y-%m-%d %H:%M:%S").to_string()),
+                        Span::styled(
+                            format!(
+                                "  {}  {}",
+                                age(backup.created_at, now),
+                                format_size(backup.size)
+                            ),
+                            Style::default().fg(theme.text_muted),
+                        ),
+                    ]))
+                })
+                .collect()
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Restore from Backup ")
+                    .style(Style::default().bg(theme.background)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(theme.highlight_bg)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(theme.background).fg(theme.text));
+
+        let mut list_state = ListState::default();
+        if !self.backups.is_empty() {
+            list_state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        let help = if self.confirming {
+            Line::from(Span::styled(
+                "Unsaved changes will be lost - Enter again to restore, Esc to keep them",
+                Style::default().fg(theme.warning),
+            ))
+        } else {
+            Line::from(vec![
+                Span::styled(
+                    "↑/↓",
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" Navigate  "),
+                Span::styled(
+                    "Enter",
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" Restore  "),
+                Span::styled(
+                    "Esc",
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" Close"),
+            ])
+        };
+        frame.render_widget(
+            Paragraph::new(help)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Help ")
+                        .style(Style::default().bg(theme.background)),
+                )
+                .style(Style::default().bg(theme.background).fg(theme.text)),
+            chunks[1],
+        );
+    }
+}
+
+/// Describes how long ago `created_at` was (e.g. "5 min ago").
+fn age(created_at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let minutes = (now - created_at).num_minutes().max(0);
+    match minutes {
+        0 => "just now".to_string(),
+        1..=59 => format!("{minutes} min ago"),
+        60..=1439 => format!("{} h ago", minutes / 60),
+        _ => format!("{} d ago", minutes / 1440),
+    }
+}
+
+/// Formats a file size in bytes or KiB.
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,72 @@
+use super::*;
+use crossterm::event::KeyModifiers;
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn backups() -> Vec<BackupInfo> {
+    [
+        "20261015T120000.000000Z.json",
+        "20261015T110000.000000Z.json",
+    ]
+    .into_iter()
+    .map(|id| BackupInfo {
+        id: id.to_string(),
+        created_at: DateTime::parse_from_rfc3339("2026-10-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc),
+        size: 100,
+    })
+    .collect()
+}
+
+#[test]
+fn test_enter_restores_selected_backup() {
+    let mut picker = BackupPicker::new(backups(), false);
+
+    assert_eq!(picker.handle_input(key(KeyCode::Down)), None);
+    assert_eq!(
+        picker.handle_input(key(KeyCode::Enter)),
+        Some(BackupPickerEvent::Restore(backups()[1].clone()))
+    );
+}
+
+#[test]
+fn test_unsaved_changes_need_second_enter() {
+    let mut picker = BackupPicker::new(backups(), true);
+
+    assert_eq!(picker.handle_input(key(KeyCode::Enter)), None);
+    assert_eq!(
+        picker.handle_input(key(KeyCode::Enter)),
+        Some(BackupPickerEvent::Restore(backups()[0].clone()))
+    );
+
+    // Moving away asks again
+    let mut picker = BackupPicker::new(backups(), true);
+    picker.handle_input(key(KeyCode::Enter));
+    picker.handle_input(key(KeyCode::Down));
+    assert_eq!(picker.handle_input(key(KeyCode::Enter)), None);
+}
+
+#[test]
+fn test_empty_list_and_escape() {
+    let mut picker = BackupPicker::new(Vec::new(), false);
+
+    assert_eq!(picker.handle_input(key(KeyCode::Enter)), None);
+    assert_eq!(
+        picker.handle_input(key(KeyCode::Esc)),
+        Some(BackupPickerEvent::Cancelled)
+    );
+}
+
+#[test]
+fn test_age_and_size_labels() {
+    let now = Utc::now();
+    assert_eq!(age(now, now), "just now");
+    assert_eq!(age(now - chrono::Duration::minutes(5), now), "5 min ago");
+    assert_eq!(age(now - chrono::Duration::hours(3), now), "3 h ago");
+    assert_eq!(age(now - chrono::Duration::days(2), now), "2 d ago");
+    assert_eq!(format_size(512), "512 B");
+    assert_eq!(format_size(2048), "2.0 KiB");
+}
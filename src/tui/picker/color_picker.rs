@@ -293,6 +293,7 @@ impl ColorPicker {
         match self.context {
             ColorPickerContext::IndividualKey => "Individual Key Color · Palette",
             ColorPickerContext::LayerDefault => "Layer Color · Palette",
+            ColorPickerContext::LayerUnderglow => "Underglow Color · Palette",
             ColorPickerContext::Category => "Category Color · Palette",
             ColorPickerContext::MultiKeySelection => "Multiple Keys Color · Palette",
             ColorPickerContext::OverlayRippleFixedColor => "Ripple Fixed Color · Palette",
@@ -304,6 +305,7 @@ impl ColorPicker {
         match self.context {
             ColorPickerContext::IndividualKey => "Individual Key Color · Custom RGB",
             ColorPickerContext::LayerDefault => "Layer Color · Custom RGB",
+            ColorPickerContext::LayerUnderglow => "Underglow Color · Custom RGB",
             ColorPickerContext::Category => "Category Color · Custom RGB",
             ColorPickerContext::MultiKeySelection => "Multiple Keys Color · Custom RGB",
             ColorPickerContext::OverlayRippleFixedColor => "Ripple Fixed Color · Custom RGB",
@@ -0,0 +1,274 @@
+//! Layer picker for copying a block of keys onto another layer.
+//!
+//! The keys keep their positions on the target layer. The highlighted
+//! layer's conflicts (assigned keys that would be overwritten) and locked
+//! keys are previewed before anything is written; see
+//! [`plan_layer_paste`].
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::models::Position;
+use crate::tui::clipboard::{plan_layer_paste, LayerPastePlan};
+use crate::tui::component::ContextualComponent;
+use crate::tui::Theme;
+
+/// Events emitted by the `CopyToLayer` component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyToLayerEvent {
+    /// User picked the layer to copy the keys to
+    LayerSelected(usize),
+    /// User cancelled without copying
+    Cancelled,
+}
+
+/// `CopyToLayer` component that implements the `ContextualComponent` trait
+#[derive(Debug, Clone)]
+pub struct CopyToLayer {
+    /// Layer the keys are copied from
+    source_layer: usize,
+    /// Positions of the keys to copy
+    positions: Vec<Position>,
+    /// Index of the highlighted target layer
+    selected: usize,
+}
+
+impl CopyToLayer {
+    /// Create a picker for copying `positions` from `source_layer`.
+    ///
+    /// The layer after the source is highlighted first.
+    #[must_use]
+    pub fn new(source_layer: usize, positions: Vec<Position>, layer_count: usize) -> Self {
+        Self {
+            source_layer,
+            positions,
+            selected: (source_layer + 1) % layer_count.max(1),
+        }
+    }
+
+    /// Layer the keys are copied from
+    #[must_use]
+    pub const fn source_layer(&self) -> usize {
+        self.source_layer
+    }
+
+    /// Positions of the keys to copy
+    #[must_use]
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// Index of the highlighted target layer
+    #[must_use]
+    pub const fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Plans the copy onto the highlighted layer, or `None` if it is the
+    /// source layer.
+    #[must_use]
+    pub fn plan(&self, layout: &crate::models::Layout) -> Option<LayerPastePlan> {
+        if self.selected == self.source_layer {
+            return None;
+        }
+        let source = layout.layers.get(self.source_layer)?;
+        let target = layout.layers.get(self.selected)?;
+        Some(plan_layer_paste(source, target, &self.positions))
+    }
+}
+
+impl ContextualComponent for CopyToLayer {
+    type Context = crate::models::Layout;
+    type Event = CopyToLayerEvent;
+
+    fn handle_input(&mut self, key: KeyEvent, layout: &Self::Context) -> Option<Self::Event> {
+        let layer_count = layout.layers.len();
+        match key.code {
+            KeyCode::Esc => Some(CopyToLayerEvent::Cancelled),
+            KeyCode::Enter if self.selected != self.source_layer => {
+                Some(CopyToLayerEvent::LayerSelected(self.selected))
+            }
+            KeyCode::Up | KeyCode::Char('k') if layer_count > 0 => {
+                self.selected = self.selected.checked_sub(1).unwrap_or(layer_count - 1);
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') if layer_count > 0 => {
+                self.selected = (self.selected + 1) % layer_count;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn render(&self, f: &mut Frame, _area: Rect, theme: &Theme, layout: &Self::Context) {
+        render_copy_to_layer(f, self, layout, theme);
+    }
+}
+
+/// Render the copy to layer popup
+fn render_copy_to_layer(
+    f: &mut Frame,
+    picker: &CopyToLayer,
+    layout: &crate::models::Layout,
+    theme: &Theme,
+) {
+    let area = centered_rect(80, 70, f.area());
+
+    // Clear the background area first
+    f.render_widget(Clear, area);
+
+    // Render opaque background with theme color
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),    // Layer list + preview
+            Constraint::Length(1), // Instructions
+        ])
+        .split(area);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(chunks[0]);
+
+    let items: Vec<ListItem> = layout
+        .layers
+        .iter()
+        .enumerate()
+        .map(|(idx, layer)| {
+            let mut spans = vec![
+                Span::styled(
+                    format!("Layer {idx}: "),
+                    Style::default().fg(theme.text_muted),
+                ),
+                Span::styled(&layer.name, Style::default().fg(theme.text)),
+            ];
+            if idx == picker.source_layer() {
+                spans.push(Span::styled(
+                    " (source)",
+                    Style::default().fg(theme.text_muted),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!(" Copy {} key(s) to ", picker.positions().len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.primary)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(theme.surface)
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(picker.selected()));
+    f.render_stateful_widget(list, body[0], &mut list_state);
+
+    let preview = Paragraph::new(preview_lines(picker, layout, theme)).block(
+        Block::default()
+            .title(" Preview ")
+            .borders(Borders::ALL)
+            .style(Style::default().bg(theme.background)),
+    );
+    f.render_widget(preview, body[1]);
+
+    let instructions = Paragraph::new(Line::from(vec![
+        Span::styled("↑↓", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" Target layer  "),
+        Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" Copy  "),
+        Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" Cancel"),
+    ]));
+    f.render_widget(instructions, chunks[1]);
+}
+
+/// Describes what copying onto the highlighted layer would change.
+fn preview_lines(
+    picker: &CopyToLayer,
+    layout: &crate::models::Layout,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let muted = Style::default().fg(theme.text_muted);
+    let Some(plan) = picker.plan(layout) else {
+        return vec![Line::styled("Pick a layer other than the source", muted)];
+    };
+
+    let mut lines = vec![Line::styled(
+        format!("{} key(s) will be written", plan.writes.len()),
+        Style::default().fg(theme.text),
+    )];
+    if plan.conflicts.is_empty() {
+        lines.push(Line::styled("No assigned keys are overwritten", muted));
+    } else {
+        lines.push(Line::styled(
+            format!(
+                "{} assigned key(s) will be overwritten:",
+                plan.conflicts.len()
+            ),
+            Style::default().fg(theme.warning),
+        ));
+        for conflict in &plan.conflicts {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  ({}, {}) ", conflict.position.row, conflict.position.col),
+                    muted,
+                ),
+                Span::styled(conflict.current.clone(), Style::default().fg(theme.error)),
+                Span::styled(" → ", muted),
+                Span::styled(
+                    conflict.incoming.clone(),
+                    Style::default().fg(theme.success),
+                ),
+            ]));
+        }
+    }
+    if !plan.locked.is_empty() {
+        lines.push(Line::styled(
+            format!("{} locked key(s) will be skipped", plan.locked.len()),
+            muted,
+        ));
+    }
+    lines
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,74 @@
+//! Tests for the copy to layer picker.
+
+use super::*;
+use crate::models::{KeyDefinition, Layer, RgbColor};
+use crossterm::event::KeyModifiers;
+
+fn press(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+/// Three layers of two keys: Base (KC_A, KC_B), Nav (KC_UP, KC_TRNS) and an
+/// empty Fn layer.
+fn create_layout() -> crate::models::Layout {
+    let mut layout = crate::models::Layout::new("Copy").unwrap();
+    for (idx, (name, keycodes)) in [
+        ("Base", ["KC_A", "KC_B"]),
+        ("Nav", ["KC_UP", "KC_TRNS"]),
+        ("Fn", ["KC_TRNS", "KC_TRNS"]),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let mut layer = Layer::new(idx as u8, name, RgbColor::new(0, 0, 0)).unwrap();
+        for (col, keycode) in keycodes.into_iter().enumerate() {
+            layer.add_key(KeyDefinition::new(Position::new(0, col as u8), keycode));
+        }
+        layout.add_layer(layer).unwrap();
+    }
+    layout
+}
+
+#[test]
+fn test_starts_on_layer_after_source() {
+    let picker = CopyToLayer::new(2, vec![Position::new(0, 0)], 3);
+    assert_eq!(picker.selected(), 0);
+    let picker = CopyToLayer::new(0, vec![Position::new(0, 0)], 3);
+    assert_eq!(picker.selected(), 1);
+}
+
+#[test]
+fn test_previews_conflicts_of_highlighted_layer() {
+    let layout = create_layout();
+    let mut picker = CopyToLayer::new(0, vec![Position::new(0, 0), Position::new(0, 1)], 3);
+
+    let plan = picker.plan(&layout).unwrap();
+    assert_eq!(plan.writes.len(), 2);
+    assert_eq!(plan.conflicts.len(), 1);
+    assert_eq!(plan.conflicts[0].current, "KC_UP");
+    assert_eq!(plan.conflicts[0].incoming, "KC_A");
+
+    assert_eq!(picker.handle_input(press(KeyCode::Down), &layout), None);
+    assert!(picker.plan(&layout).unwrap().conflicts.is_empty());
+}
+
+#[test]
+fn test_source_layer_cannot_be_picked() {
+    let layout = create_layout();
+    let mut picker = CopyToLayer::new(0, vec![Position::new(0, 0)], 3);
+
+    assert_eq!(picker.handle_input(press(KeyCode::Up), &layout), None);
+    assert_eq!(picker.selected(), 0);
+    assert!(picker.plan(&layout).is_none());
+    assert_eq!(picker.handle_input(press(KeyCode::Enter), &layout), None);
+
+    assert_eq!(picker.handle_input(press(KeyCode::Up), &layout), None);
+    assert_eq!(
+        picker.handle_input(press(KeyCode::Enter), &layout),
+        Some(CopyToLayerEvent::LayerSelected(2))
+    );
+    assert_eq!(
+        picker.handle_input(press(KeyCode::Esc), &layout),
+        Some(CopyToLayerEvent::Cancelled)
+    );
+}
@@ -0,0 +1,431 @@
+//! Custom Keycode Editor Component
+//!
+//! Lists the layout's custom keycodes and edits them in a three-field form
+//! (name, description and the C snippet run in `process_record_user`).
+//! Enter applies the highlighted keycode to the selected key. Saving and
+//! deleting are reported as events; the handler applies them to the layout.
+
+use crate::models::{CustomKeycode, Layout};
+use crate::tui::theme::Theme;
+use crate::tui::Component;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// Events emitted by the custom keycode editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CustomKeycodeEditorEvent {
+    /// User selected a custom keycode; carries its name
+    Selected(String),
+    /// User saved a new or edited custom keycode
+    Saved {
+        /// Name of the keycode being replaced (None when creating)
+        original: Option<String>,
+        /// The validated custom keycode
+        custom: CustomKeycode,
+    },
+    /// User wants to delete a custom keycode
+    Delete(String),
+    /// User closed the editor
+    Cancelled,
+}
+
+/// Which form field has focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomKeycodeField {
+    /// Keycode name
+    Name,
+    /// Description shown in the keycode picker
+    Description,
+    /// C snippet
+    Behavior,
+}
+
+impl CustomKeycodeField {
+    const fn next(self) -> Self {
+        match self {
+            Self::Name => Self::Description,
+            Self::Description => Self::Behavior,
+            Self::Behavior => Self::Name,
+        }
+    }
+
+    const fn previous(self) -> Self {
+        match self {
+            Self::Name => Self::Behavior,
+            Self::Description => Self::Name,
+            Self::Behavior => Self::Description,
+        }
+    }
+}
+
+/// Draft being edited in the form
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CustomKeycodeDraft {
+    /// Name of the keycode being edited (None when creating)
+    original: Option<String>,
+    name: String,
+    description: String,
+    behavior: String,
+    focus: CustomKeycodeField,
+    error: Option<String>,
+}
+
+/// Custom Keycode Editor component state
+#[derive(Debug, Clone)]
+pub struct CustomKeycodeEditor {
+    /// Index of the highlighted keycode
+    selected: usize,
+    /// Custom keycodes from the layout (read-only view)
+    keycodes: Vec<CustomKeycode>,
+    /// Form state while creating or editing
+    draft: Option<CustomKeycodeDraft>,
+}
+
+impl CustomKeycodeEditor {
+    /// Creates a new custom keycode editor with keycodes from the layout
+    #[must_use]
+    pub fn new(layout: &Layout) -> Self {
+        Self {
+            selected: 0,
+            keycodes: layout.custom_keycodes.clone(),
+            draft: None,
+        }
+    }
+
+    /// Shows an error on the open form (e.g. when the handler rejects a save)
+    pub fn set_form_error(&mut self, message: impl Into<String>) {
+        if let Some(draft) = &mut self.draft {
+            draft.error = Some(message.into());
+        }
+    }
+
+    fn open_form(&mut self, existing: Option<&CustomKeycode>) {
+        self.draft = Some(CustomKeycodeDraft {
+            original: existing.map(|c| c.name.clone()),
+            name: existing.map(|c| c.name.clone()).unwrap_or_default(),
+            description: existing.map(|c| c.description.clone()).unwrap_or_default(),
+            behavior: existing.map(|c| c.behavior.clone()).unwrap_or_default(),
+            focus: CustomKeycodeField::Name,
+            error: None,
+        });
+    }
+
+    /// Validate the draft, returning the custom keycode or an error message
+    fn build_draft(&self, draft: &CustomKeycodeDraft) -> Result<CustomKeycode, String> {
+        let name = draft.name.trim();
+        let duplicate = self
+            .keycodes
+            .iter()
+            .any(|c| c.name == name && draft.original.as_deref() != Some(c.name.as_str()));
+        if duplicate {
+            return Err(format!("Custom keycode '{name}' already exists"));
+        }
+
+        let custom = CustomKeycode::new(name, draft.behavior.trim_end(), draft.description.trim());
+        custom.validate().map_err(|e| e.to_string())?;
+        Ok(custom)
+    }
+
+    fn handle_form_input(&mut self, key: KeyEvent) -> Option<CustomKeycodeEditorEvent> {
+        let mut draft = self.draft.take()?;
+        match key.code {
+            KeyCode::Esc => return None,
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                match self.build_draft(&draft) {
+                    Ok(custom) => {
+                        let original = draft.original.clone();
+                        // Keep the form open until the handler accepts the save
+                        self.draft = Some(draft);
+                        return Some(CustomKeycodeEditorEvent::Saved { original, custom });
+                    }
+                    Err(message) => draft.error = Some(message),
+                }
+            }
+            KeyCode::Tab | KeyCode::Down => draft.focus = draft.focus.next(),
+            KeyCode::BackTab | KeyCode::Up => draft.focus = draft.focus.previous(),
+            KeyCode::Enter => match draft.focus {
+                CustomKeycodeField::Behavior => draft.behavior.push('\n'),
+                field => draft.focus = field.next(),
+            },
+            KeyCode::Backspace => {
+                match draft.focus {
+                    CustomKeycodeField::Name => draft.name.pop(),
+                    CustomKeycodeField::Description => draft.description.pop(),
+                    CustomKeycodeField::Behavior => draft.behavior.pop(),
+                };
+                draft.error = None;
+            }
+            KeyCode::Char(c) => {
+                match draft.focus {
+                    CustomKeycodeField::Name => draft.name.push(c.to_ascii_uppercase()),
+                    CustomKeycodeField::Description => draft.description.push(c),
+                    CustomKeycodeField::Behavior => draft.behavior.push(c),
+                }
+                draft.error = None;
+            }
+            _ => {}
+        }
+        self.draft = Some(draft);
+        None
+    }
+}
+
+impl Component for CustomKeycodeEditor {
+    type Event = CustomKeycodeEditorEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        if self.draft.is_some() {
+            return self.handle_form_input(key);
+        }
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.keycodes.len().saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                return self
+                    .keycodes
+                    .get(self.selected)
+                    .map(|c| CustomKeycodeEditorEvent::Selected(c.name.clone()));
+            }
+            KeyCode::Char('n') => self.open_form(None),
+            KeyCode::Char('e') => {
+                let existing = self.keycodes.get(self.selected).cloned();
+                if existing.is_some() {
+                    self.open_form(existing.as_ref());
+                }
+            }
+            KeyCode::Char('D') => {
+                return self
+                    .keycodes
+                    .get(self.selected)
+                    .map(|c| CustomKeycodeEditorEvent::Delete(c.name.clone()));
+            }
+            KeyCode::Esc => return Some(CustomKeycodeEditorEvent::Cancelled),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, _area: Rect, theme: &Theme) {
+        let area = centered_rect(75, 70, frame.area());
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(theme.background)),
+            area,
+        );
+
+        match &self.draft {
+            Some(draft) => render_form(frame, area, draft, theme),
+            None => self.render_list(frame, area, theme),
+        }
+    }
+}
+
+impl CustomKeycodeEditor {
+    fn render_list(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        let items: Vec<ListItem> = if self.keycodes.is_empty() {
+            vec![ListItem::new(
+                Line::from("No custom keycodes defined. Press 'n' to create one.")
+                    .style(Style::default().fg(theme.text_muted)),
+            )]
+        } else {
+            self.keycodes
+                .iter()
+                .map(|c| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{}: ", c.name), Style::default().fg(theme.accent)),
+                        Span::raw(c.display_description().to_string()),
+                    ]))
+                })
+                .collect()
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Custom Keycodes ")
+                    .style(Style::default().bg(theme.background)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(theme.highlight_bg)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(theme.background).fg(theme.text));
+
+        let mut list_state = ListState::default();
+        if !self.keycodes.is_empty() {
+            list_state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        let help = help_line(
+            &[
+                ("↑/↓", "Navigate"),
+                ("Enter", "Apply"),
+                ("n", "New"),
+                ("e", "Edit"),
+                ("D", "Delete"),
+                ("Esc", "Close"),
+            ],
+            theme,
+        );
+        frame.render_widget(help, chunks[1]);
+    }
+}
+
+fn render_form(frame: &mut Frame, area: Rect, draft: &CustomKeycodeDraft, theme: &Theme) {
+    let chunks = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Name
+            Constraint::Length(3), // Description
+            Constraint::Min(5),    // Behavior
+            Constraint::Length(3), // Hint / error
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    for (chunk, field, title, value) in [
+        (chunks[0], CustomKeycodeField::Name, " Name ", &draft.name),
+        (
+            chunks[1],
+            CustomKeycodeField::Description,
+            " Description ",
+            &draft.description,
+        ),
+        (
+            chunks[2],
+            CustomKeycodeField::Behavior,
+            " Behavior (C) ",
+            &draft.behavior,
+        ),
+    ] {
+        let focused = draft.focus == field;
+        let mut lines: Vec<Line> = value
+            .split('\n')
+            .map(|line| Line::from(line.to_string()))
+            .collect();
+        if focused {
+            if let Some(last) = lines.last_mut() {
+                last.push_span(Span::styled("█", Style::default().fg(theme.primary)));
+            }
+        }
+        let border = if focused {
+            theme.primary
+        } else {
+            theme.text_muted
+        };
+        let input = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(border)),
+            )
+            .style(Style::default().fg(theme.text));
+        frame.render_widget(input, chunk);
+    }
+
+    let hint = draft.error.as_ref().map_or_else(
+        || {
+            Line::from(Span::styled(
+                "Runs on press and release (record->event.pressed); the key is consumed unless it returns.",
+                Style::default().fg(theme.text_muted),
+            ))
+        },
+        |error| {
+            Line::from(Span::styled(
+                error.as_str(),
+                Style::default().fg(theme.error),
+            ))
+        },
+    );
+    let title = if draft.original.is_some() {
+        " Edit Custom Keycode "
+    } else {
+        " New Custom Keycode "
+    };
+    frame.render_widget(
+        Paragraph::new(hint).block(Block::default().title(title).borders(Borders::ALL)),
+        chunks[3],
+    );
+
+    let help = help_line(
+        &[
+            ("Tab", "Switch field"),
+            ("Enter", "Next field / new line"),
+            ("Ctrl+S", "Save"),
+            ("Esc", "Back"),
+        ],
+        theme,
+    );
+    frame.render_widget(help, chunks[4]);
+}
+
+fn help_line<'a>(entries: &[(&'a str, &'a str)], theme: &Theme) -> Paragraph<'a> {
+    let spans: Vec<Span> = entries
+        .iter()
+        .flat_map(|(key, label)| {
+            [
+                Span::styled(
+                    *key,
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" {label}  ")),
+            ]
+        })
+        .collect();
+
+    Paragraph::new(Line::from(spans))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Help ")
+                .style(Style::default().bg(theme.background)),
+        )
+        .style(Style::default().bg(theme.background).fg(theme.text))
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
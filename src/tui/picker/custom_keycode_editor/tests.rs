@@ -0,0 +1,122 @@
+//! Tests for custom_keycode_editor.
+
+use super::*;
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    layout.custom_keycodes = vec![
+        CustomKeycode::new("CK_EMAIL", "SEND_STRING(\"me@example.com\");", "Type email"),
+        CustomKeycode::new("CK_RESET", "reset_keyboard();", ""),
+    ];
+    layout
+}
+
+fn press(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn ctrl_s() -> KeyEvent {
+    KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)
+}
+
+fn type_text(editor: &mut CustomKeycodeEditor, text: &str) {
+    for c in text.chars() {
+        assert_eq!(editor.handle_input(press(KeyCode::Char(c))), None);
+    }
+}
+
+#[test]
+fn test_enter_applies_keycode_name() {
+    let mut editor = CustomKeycodeEditor::new(&create_test_layout());
+    editor.handle_input(press(KeyCode::Down));
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Enter)),
+        Some(CustomKeycodeEditorEvent::Selected("CK_RESET".to_string()))
+    );
+}
+
+#[test]
+fn test_delete_and_cancel() {
+    let mut editor = CustomKeycodeEditor::new(&create_test_layout());
+
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Char('D'))),
+        Some(CustomKeycodeEditorEvent::Delete("CK_EMAIL".to_string()))
+    );
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Esc)),
+        Some(CustomKeycodeEditorEvent::Cancelled)
+    );
+}
+
+#[test]
+fn test_create_multiline_keycode() {
+    let mut editor = CustomKeycodeEditor::new(&create_test_layout());
+
+    editor.handle_input(press(KeyCode::Char('n')));
+    assert!(editor.draft.is_some());
+    // Names are uppercased as they are typed
+    type_text(&mut editor, "ck_hi");
+    editor.handle_input(press(KeyCode::Enter));
+    type_text(&mut editor, "Greet");
+    editor.handle_input(press(KeyCode::Tab));
+    type_text(&mut editor, "if (record->event.pressed) {");
+    // Enter in the behavior field adds a line instead of moving on
+    editor.handle_input(press(KeyCode::Enter));
+    type_text(&mut editor, "    SEND_STRING(\"hi\");");
+    editor.handle_input(press(KeyCode::Enter));
+    type_text(&mut editor, "}");
+
+    assert_eq!(
+        editor.handle_input(ctrl_s()),
+        Some(CustomKeycodeEditorEvent::Saved {
+            original: None,
+            custom: CustomKeycode::new(
+                "CK_HI",
+                "if (record->event.pressed) {\n    SEND_STRING(\"hi\");\n}",
+                "Greet"
+            ),
+        })
+    );
+    // The form stays open until the handler accepts the save
+    assert!(editor.draft.is_some());
+}
+
+#[test]
+fn test_invalid_draft_shows_error() {
+    let mut editor = CustomKeycodeEditor::new(&create_test_layout());
+
+    editor.handle_input(press(KeyCode::Char('n')));
+    type_text(&mut editor, "ck_reset");
+    editor.handle_input(press(KeyCode::Tab));
+    editor.handle_input(press(KeyCode::Tab));
+    type_text(&mut editor, "tap_code(KC_A);");
+    assert_eq!(editor.handle_input(ctrl_s()), None);
+    assert_eq!(
+        editor.draft.as_ref().and_then(|d| d.error.clone()),
+        Some("Custom keycode 'CK_RESET' already exists".to_string())
+    );
+
+    // Esc returns to the list without saving
+    editor.handle_input(press(KeyCode::Esc));
+    assert!(editor.draft.is_none());
+}
+
+#[test]
+fn test_edit_keeps_original_name() {
+    let mut editor = CustomKeycodeEditor::new(&create_test_layout());
+
+    editor.handle_input(press(KeyCode::Char('e')));
+    for _ in 0.."EMAIL".len() {
+        editor.handle_input(press(KeyCode::Backspace));
+    }
+    type_text(&mut editor, "MAIL");
+
+    assert_eq!(
+        editor.handle_input(ctrl_s()),
+        Some(CustomKeycodeEditorEvent::Saved {
+            original: Some("CK_EMAIL".to_string()),
+            custom: CustomKeycode::new("CK_MAIL", "SEND_STRING(\"me@example.com\");", "Type email"),
+        })
+    );
+}
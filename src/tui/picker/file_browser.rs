@@ -0,0 +1,404 @@
+//! Directory browser for choosing paths (QMK firmware, build output).
+//!
+//! Embedded in the onboarding wizard and the settings manager instead of a
+//! free-text path field. Lists the subdirectories of the current directory;
+//! typing filters the list, Enter opens the highlighted directory and the
+//! "Use this folder" entry (or Tab) picks the current one. Ctrl+N creates a
+//! directory and Ctrl+L jumps to a typed path. Hidden directories are listed
+//! only while the filter starts with a dot.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::tui::theme::Theme;
+use crate::tui::Component;
+
+/// Events emitted by the file browser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileBrowserEvent {
+    /// User picked a directory
+    Selected(PathBuf),
+    /// User backed out without picking
+    Cancelled,
+}
+
+/// A row of the directory listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrowserEntry {
+    /// Picks the current directory
+    UseCurrent,
+    /// Goes up to the parent directory
+    Parent,
+    /// A subdirectory (name only)
+    Directory(String),
+}
+
+/// Text prompt shown below the listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrowserPrompt {
+    /// Name of a new directory to create in the current one
+    CreateDirectory(String),
+    /// Path to jump to
+    GoTo(String),
+}
+
+/// File browser component state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileBrowser {
+    /// Directory being listed
+    dir: PathBuf,
+    /// Subdirectory names of `dir`, sorted case-insensitively
+    subdirs: Vec<String>,
+    /// Filter typed by the user
+    filter: String,
+    /// Index of the highlighted entry
+    selected: usize,
+    /// Active text prompt, if any
+    prompt: Option<BrowserPrompt>,
+    /// Last error (unreadable directory, failed mkdir, ...)
+    error: Option<String>,
+}
+
+impl FileBrowser {
+    /// Creates a browser starting at `path`.
+    ///
+    /// If `path` doesn't exist, the browser opens its nearest existing
+    /// ancestor; an empty path opens the home directory.
+    #[must_use]
+    pub fn new(path: &str) -> Self {
+        let start = if path.trim().is_empty() {
+            dirs::home_dir().unwrap_or_else(|| PathBuf::from("."))
+        } else {
+            expand_home(path.trim())
+        };
+        let dir = start
+            .ancestors()
+            .find(|dir| dir.is_dir())
+            .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+
+        let mut browser = Self {
+            dir: PathBuf::new(),
+            subdirs: Vec::new(),
+            filter: String::new(),
+            selected: 0,
+            prompt: None,
+            error: None,
+        };
+        browser.open(dir);
+        browser
+    }
+
+    /// Returns the directory being listed.
+    #[must_use]
+    pub fn current_dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the rows shown for the current directory and filter.
+    #[must_use]
+    pub fn entries(&self) -> Vec<BrowserEntry> {
+        let mut entries = vec![BrowserEntry::UseCurrent];
+        if self.dir.parent().is_some() {
+            entries.push(BrowserEntry::Parent);
+        }
+        let filter = self.filter.to_lowercase();
+        let show_hidden = filter.starts_with('.');
+        entries.extend(
+            self.subdirs
+                .iter()
+                .filter(|name| show_hidden || !name.starts_with('.'))
+                .filter(|name| name.to_lowercase().contains(&filter))
+                .map(|name| BrowserEntry::Directory(name.clone())),
+        );
+        entries
+    }
+
+    /// Returns the highlighted entry.
+    #[must_use]
+    pub fn selected_entry(&self) -> Option<BrowserEntry> {
+        self.entries().into_iter().nth(self.selected)
+    }
+
+    /// Lists `dir` and makes it the current directory.
+    ///
+    /// Leaves the browser where it is if `dir` can't be read.
+    fn open(&mut self, dir: PathBuf) {
+        match read_subdirs(&dir) {
+            Ok(subdirs) => {
+                self.dir = dir;
+                self.subdirs = subdirs;
+                self.filter.clear();
+                self.selected = 0;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(format!("Cannot open {}: {e}", dir.display())),
+        }
+    }
+
+    /// Opens the parent directory, highlighting the one just left.
+    fn open_parent(&mut self) {
+        let Some(parent) = self.dir.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        let left = self
+            .dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string());
+        self.open(parent);
+        if let Some(left) = left {
+            if let Some(idx) = self
+                .entries()
+                .iter()
+                .position(|entry| *entry == BrowserEntry::Directory(left.clone()))
+            {
+                self.selected = idx;
+            }
+        }
+    }
+
+    /// Acts on the highlighted entry.
+    fn activate(&mut self) -> Option<FileBrowserEvent> {
+        match self.selected_entry()? {
+            BrowserEntry::UseCurrent => return Some(FileBrowserEvent::Selected(self.dir.clone())),
+            BrowserEntry::Parent => self.open_parent(),
+            BrowserEntry::Directory(name) => self.open(self.dir.join(name)),
+        }
+        None
+    }
+
+    /// Runs the prompt's action with the text typed so far.
+    fn submit_prompt(&mut self, prompt: BrowserPrompt) {
+        match prompt {
+            BrowserPrompt::CreateDirectory(name) => {
+                let name = name.trim();
+                if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+                    self.error = Some(format!("Invalid folder name: '{name}'"));
+                    return;
+                }
+                let path = self.dir.join(name);
+                match fs::create_dir(&path) {
+                    Ok(()) => self.open(path),
+                    Err(e) => {
+                        self.error = Some(format!("Cannot create {}: {e}", path.display()));
+                    }
+                }
+            }
+            BrowserPrompt::GoTo(path) => {
+                let path = expand_home(path.trim());
+                if path.is_dir() {
+                    self.open(path);
+                } else {
+                    self.error = Some(format!("Not a directory: {}", path.display()));
+                }
+            }
+        }
+    }
+
+    /// Handles a key while a prompt is open.
+    fn handle_prompt_input(&mut self, key: KeyEvent) {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+        let (BrowserPrompt::CreateDirectory(text) | BrowserPrompt::GoTo(text)) = prompt;
+        match key.code {
+            KeyCode::Char(c) => text.push(c),
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Enter => {
+                if let Some(prompt) = self.prompt.take() {
+                    self.submit_prompt(prompt);
+                }
+            }
+            KeyCode::Esc => self.prompt = None,
+            _ => {}
+        }
+    }
+}
+
+impl Component for FileBrowser {
+    type Event = FileBrowserEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        if self.prompt.is_some() {
+            self.handle_prompt_input(key);
+            return None;
+        }
+
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('n') if ctrl => {
+                self.prompt = Some(BrowserPrompt::CreateDirectory(String::new()));
+                self.error = None;
+            }
+            KeyCode::Char('l') if ctrl => {
+                self.prompt = Some(BrowserPrompt::GoTo(self.dir.display().to_string()));
+                self.error = None;
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.entries().len().saturating_sub(1));
+            }
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = self.entries().len().saturating_sub(1),
+            KeyCode::Enter | KeyCode::Right => return self.activate(),
+            KeyCode::Tab => return Some(FileBrowserEvent::Selected(self.dir.clone())),
+            KeyCode::Left => self.open_parent(),
+            KeyCode::Backspace => {
+                if self.filter.pop().is_none() {
+                    self.open_parent();
+                } else {
+                    self.selected = 0;
+                }
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.filter.push(c);
+                // Highlight the first match rather than "Use this folder"
+                self.selected = self
+                    .entries()
+                    .iter()
+                    .position(|entry| matches!(entry, BrowserEntry::Directory(_)))
+                    .unwrap_or(0);
+            }
+            KeyCode::Esc => {
+                if self.filter.is_empty() {
+                    return Some(FileBrowserEvent::Cancelled);
+                }
+                self.filter.clear();
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Current directory
+                Constraint::Min(3),    // Listing
+                Constraint::Length(3), // Filter / prompt / error
+            ])
+            .split(area);
+
+        frame.render_widget(
+            Paragraph::new(self.dir.display().to_string())
+                .style(Style::default().fg(theme.text))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(theme.primary))
+                        .title(" Folder "),
+                ),
+            chunks[0],
+        );
+
+        let items: Vec<ListItem> = self
+            .entries()
+            .into_iter()
+            .map(|entry| match entry {
+                BrowserEntry::UseCurrent => ListItem::new(Line::from(Span::styled(
+                    "✓ Use this folder",
+                    Style::default().fg(theme.success),
+                ))),
+                BrowserEntry::Parent => ListItem::new(Line::from(Span::styled(
+                    "../",
+                    Style::default().fg(theme.text_muted),
+                ))),
+                BrowserEntry::Directory(name) => ListItem::new(Line::from(format!("{name}/"))),
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(" Folders "))
+            .highlight_style(
+                Style::default()
+                    .bg(theme.highlight_bg)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().fg(theme.text));
+        let mut list_state = ListState::default();
+        list_state.select(Some(self.selected));
+        frame.render_stateful_widget(list, chunks[1], &mut list_state);
+
+        let key_style = Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD);
+        let (title, line) = match (&self.prompt, &self.error) {
+            (Some(BrowserPrompt::CreateDirectory(name)), _) => (
+                " New Folder (Enter: create, Esc: cancel) ",
+                Line::from(format!("{name}▌")),
+            ),
+            (Some(BrowserPrompt::GoTo(path)), _) => (
+                " Go To (Enter: open, Esc: cancel) ",
+                Line::from(format!("{path}▌")),
+            ),
+            (None, Some(error)) => (
+                " Error ",
+                Line::from(Span::styled(
+                    format!("⚠ {error}"),
+                    Style::default().fg(theme.error),
+                )),
+            ),
+            (None, None) if !self.filter.is_empty() => (
+                " Filter (Esc: clear) ",
+                Line::from(format!("{}▌", self.filter)),
+            ),
+            (None, None) => (
+                " Keys ",
+                Line::from(vec![
+                    Span::styled("Type", key_style),
+                    Span::raw(" Filter  "),
+                    Span::styled("Enter", key_style),
+                    Span::raw(" Open  "),
+                    Span::styled("Tab", key_style),
+                    Span::raw(" Use folder  "),
+                    Span::styled("Ctrl+N", key_style),
+                    Span::raw(" New folder  "),
+                    Span::styled("Ctrl+L", key_style),
+                    Span::raw(" Go to"),
+                ]),
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(line)
+                .style(Style::default().fg(theme.text))
+                .block(Block::default().borders(Borders::ALL).title(title)),
+            chunks[2],
+        );
+    }
+}
+
+/// Returns the names of the subdirectories of `dir`, sorted case-insensitively.
+fn read_subdirs(dir: &Path) -> std::io::Result<Vec<String>> {
+    let mut subdirs: Vec<String> = fs::read_dir(dir)?
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    subdirs.sort_by_key(|name| name.to_lowercase());
+    Ok(subdirs)
+}
+
+/// Expands a leading `~` to the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            home.join(rest.trim_start_matches(['/', '\\']))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests;
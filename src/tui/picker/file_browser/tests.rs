@@ -0,0 +1,175 @@
+use super::*;
+use tempfile::TempDir;
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn ctrl(c: char) -> KeyEvent {
+    KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+}
+
+fn type_text(browser: &mut FileBrowser, text: &str) {
+    for c in text.chars() {
+        browser.handle_input(key(KeyCode::Char(c)));
+    }
+}
+
+/// Creates `qmk_firmware/` and `Projects/` (plus a file and a hidden dir).
+fn workspace() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    fs::create_dir(tmp.path().join("qmk_firmware")).unwrap();
+    fs::create_dir(tmp.path().join("Projects")).unwrap();
+    fs::create_dir(tmp.path().join(".cache")).unwrap();
+    fs::write(tmp.path().join("notes.txt"), "").unwrap();
+    tmp
+}
+
+#[test]
+fn test_lists_visible_subdirectories_sorted() {
+    let tmp = workspace();
+    let browser = FileBrowser::new(&tmp.path().display().to_string());
+
+    assert_eq!(browser.current_dir(), tmp.path());
+    assert_eq!(
+        browser.entries(),
+        vec![
+            BrowserEntry::UseCurrent,
+            BrowserEntry::Parent,
+            BrowserEntry::Directory("Projects".to_string()),
+            BrowserEntry::Directory("qmk_firmware".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_starts_at_nearest_existing_ancestor() {
+    let tmp = workspace();
+    let missing = tmp.path().join("qmk_firmware/builds/nested");
+    let browser = FileBrowser::new(&missing.display().to_string());
+
+    assert_eq!(browser.current_dir(), tmp.path().join("qmk_firmware"));
+}
+
+#[test]
+fn test_filter_narrows_listing_and_enter_opens_match() {
+    let tmp = workspace();
+    let mut browser = FileBrowser::new(&tmp.path().display().to_string());
+
+    type_text(&mut browser, "QMK");
+    assert_eq!(
+        browser.selected_entry(),
+        Some(BrowserEntry::Directory("qmk_firmware".to_string()))
+    );
+    assert_eq!(browser.entries().len(), 3);
+
+    assert_eq!(browser.handle_input(key(KeyCode::Enter)), None);
+    assert_eq!(browser.current_dir(), tmp.path().join("qmk_firmware"));
+    assert!(browser.filter.is_empty());
+}
+
+#[test]
+fn test_dot_filter_shows_hidden_directories() {
+    let tmp = workspace();
+    let mut browser = FileBrowser::new(&tmp.path().display().to_string());
+
+    type_text(&mut browser, ".ca");
+    assert_eq!(
+        browser.selected_entry(),
+        Some(BrowserEntry::Directory(".cache".to_string()))
+    );
+}
+
+#[test]
+fn test_backspace_on_empty_filter_goes_to_parent() {
+    let tmp = workspace();
+    let mut browser = FileBrowser::new(&tmp.path().join("Projects").display().to_string());
+
+    browser.handle_input(key(KeyCode::Backspace));
+    assert_eq!(browser.current_dir(), tmp.path());
+    assert_eq!(
+        browser.selected_entry(),
+        Some(BrowserEntry::Directory("Projects".to_string()))
+    );
+}
+
+#[test]
+fn test_use_current_entry_and_tab_select_directory() {
+    let tmp = workspace();
+    let mut browser = FileBrowser::new(&tmp.path().display().to_string());
+
+    assert_eq!(
+        browser.handle_input(key(KeyCode::Enter)),
+        Some(FileBrowserEvent::Selected(tmp.path().to_path_buf()))
+    );
+    assert_eq!(
+        browser.handle_input(key(KeyCode::Tab)),
+        Some(FileBrowserEvent::Selected(tmp.path().to_path_buf()))
+    );
+}
+
+#[test]
+fn test_esc_clears_filter_before_cancelling() {
+    let tmp = workspace();
+    let mut browser = FileBrowser::new(&tmp.path().display().to_string());
+
+    type_text(&mut browser, "pro");
+    assert_eq!(browser.handle_input(key(KeyCode::Esc)), None);
+    assert!(browser.filter.is_empty());
+    assert_eq!(
+        browser.handle_input(key(KeyCode::Esc)),
+        Some(FileBrowserEvent::Cancelled)
+    );
+}
+
+#[test]
+fn test_create_directory_prompt_creates_and_opens_folder() {
+    let tmp = workspace();
+    let mut browser = FileBrowser::new(&tmp.path().display().to_string());
+
+    browser.handle_input(ctrl('n'));
+    assert_eq!(
+        browser.prompt.as_ref(),
+        Some(&BrowserPrompt::CreateDirectory(String::new()))
+    );
+    type_text(&mut browser, "builds");
+    browser.handle_input(key(KeyCode::Enter));
+
+    assert!(browser.prompt.as_ref().is_none());
+    assert!(tmp.path().join("builds").is_dir());
+    assert_eq!(browser.current_dir(), tmp.path().join("builds"));
+}
+
+#[test]
+fn test_create_directory_rejects_nested_names() {
+    let tmp = workspace();
+    let mut browser = FileBrowser::new(&tmp.path().display().to_string());
+
+    browser.handle_input(ctrl('n'));
+    type_text(&mut browser, "a/b");
+    browser.handle_input(key(KeyCode::Enter));
+
+    assert!(browser.error.is_some());
+    assert!(!tmp.path().join("a").exists());
+    assert_eq!(browser.current_dir(), tmp.path());
+}
+
+#[test]
+fn test_go_to_prompt_opens_typed_path() {
+    let tmp = workspace();
+    let mut browser = FileBrowser::new(&tmp.path().join("Projects").display().to_string());
+
+    browser.handle_input(ctrl('l'));
+    for _ in 0.."Projects".len() {
+        browser.handle_input(key(KeyCode::Backspace));
+    }
+    type_text(&mut browser, "qmk_firmware");
+    browser.handle_input(key(KeyCode::Enter));
+    assert_eq!(browser.current_dir(), tmp.path().join("qmk_firmware"));
+
+    browser.handle_input(ctrl('l'));
+    type_text(&mut browser, "/missing");
+    browser.handle_input(key(KeyCode::Enter));
+    assert!(browser.error.is_some());
+    assert_eq!(browser.current_dir(), tmp.path().join("qmk_firmware"));
+}
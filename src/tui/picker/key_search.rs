@@ -0,0 +1,234 @@
+//! Key search for jumping to keys on any layer.
+//!
+//! Lists keys across all layers whose keycode, label or description matches
+//! what the user types (see [`search_keys`]). Selecting a result jumps to
+//! that key's layer and position.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::models::Position;
+use crate::services::key_search::search_keys;
+use crate::tui::component::ContextualComponent;
+use crate::tui::Theme;
+
+/// Events emitted by the `KeySearch` component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeySearchEvent {
+    /// User picked a key to jump to
+    KeySelected {
+        /// Layer index of the key
+        layer: usize,
+        /// Matrix position of the key
+        position: Position,
+    },
+    /// User cancelled without jumping
+    Cancelled,
+}
+
+/// `KeySearch` component that implements the `ContextualComponent` trait
+#[derive(Debug, Clone, Default)]
+pub struct KeySearch {
+    /// Current search text
+    query: String,
+    /// Index of the highlighted result
+    selected: usize,
+}
+
+impl KeySearch {
+    /// Create a new `KeySearch` with an empty query
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current search text
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Index of the highlighted result
+    #[must_use]
+    pub const fn selected(&self) -> usize {
+        self.selected
+    }
+}
+
+impl ContextualComponent for KeySearch {
+    type Context = crate::models::Layout;
+    type Event = KeySearchEvent;
+
+    fn handle_input(&mut self, key: KeyEvent, layout: &Self::Context) -> Option<Self::Event> {
+        let matches = search_keys(layout, &self.query);
+        match key.code {
+            KeyCode::Esc => Some(KeySearchEvent::Cancelled),
+            KeyCode::Enter => matches
+                .get(self.selected)
+                .map(|m| KeySearchEvent::KeySelected {
+                    layer: m.layer,
+                    position: m.position,
+                }),
+            KeyCode::Up => {
+                if !matches.is_empty() {
+                    self.selected = self.selected.checked_sub(1).unwrap_or(matches.len() - 1);
+                }
+                None
+            }
+            KeyCode::Down => {
+                if !matches.is_empty() {
+                    self.selected = (self.selected + 1) % matches.len();
+                }
+                None
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.selected = 0;
+                None
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.selected = 0;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn render(&self, f: &mut Frame, _area: Rect, theme: &Theme, layout: &Self::Context) {
+        render_key_search(f, self, layout, theme);
+    }
+}
+
+/// Render the key search popup
+fn render_key_search(
+    f: &mut Frame,
+    search: &KeySearch,
+    layout: &crate::models::Layout,
+    theme: &Theme,
+) {
+    let area = centered_rect(70, 60, f.area());
+
+    // Clear the background area first
+    f.render_widget(Clear, area);
+
+    // Render opaque background with theme color
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Search input
+            Constraint::Min(3),    // Results
+            Constraint::Length(1), // Instructions
+        ])
+        .split(area);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::raw(search.query()),
+        Span::styled("█", Style::default().fg(theme.primary)),
+    ]))
+    .block(
+        Block::default()
+            .title(" Search Keys ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.primary)),
+    )
+    .style(Style::default().fg(theme.text));
+    f.render_widget(input, chunks[0]);
+
+    let matches = search_keys(layout, search.query());
+    let items: Vec<ListItem> = if matches.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "  No keys match",
+            Style::default()
+                .fg(theme.text_muted)
+                .add_modifier(Modifier::ITALIC),
+        )))]
+    } else {
+        matches
+            .iter()
+            .map(|m| {
+                let mut spans = vec![
+                    Span::styled(
+                        format!("{} ({},{}) ", m.layer_name, m.position.row, m.position.col),
+                        Style::default().fg(theme.text_muted),
+                    ),
+                    Span::styled(format!("{} ", m.keycode), Style::default().fg(theme.accent)),
+                ];
+                if let Some(label) = &m.label {
+                    spans.push(Span::styled(
+                        format!("[{label}] "),
+                        Style::default().fg(theme.primary),
+                    ));
+                }
+                if let Some(description) = &m.description {
+                    spans.push(Span::raw(description));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!(" {} key(s) ", matches.len()))
+                .borders(Borders::ALL),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(theme.surface)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("► ");
+
+    let mut list_state = ListState::default();
+    if !matches.is_empty() {
+        list_state.select(Some(search.selected()));
+    }
+    f.render_stateful_widget(list, chunks[1], &mut list_state);
+
+    let instructions = Paragraph::new(Line::from(vec![
+        Span::styled("Type", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" Filter  "),
+        Span::styled("↑↓", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" Navigate  "),
+        Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" Jump to key  "),
+        Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" Cancel"),
+    ]));
+    f.render_widget(instructions, chunks[2]);
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
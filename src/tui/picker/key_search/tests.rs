@@ -0,0 +1,86 @@
+//! Tests for key_search.
+
+use super::*;
+use crate::models::{KeyDefinition, Layer, RgbColor};
+use crossterm::event::KeyModifiers;
+
+/// Two layers with three described keys between them.
+fn create_test_layout() -> crate::models::Layout {
+    let mut layout = crate::models::Layout::new("Test").unwrap();
+
+    let mut base = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    base.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+    base.add_key(
+        KeyDefinition::new(Position::new(0, 1), "LT(@nav, KC_SPC)").with_description("Thumb nav"),
+    );
+    base.add_key(KeyDefinition::new(Position::new(0, 2), "KC_B").with_description("  "));
+    layout.add_layer(base).unwrap();
+
+    let mut nav = Layer::new(1, "Nav", RgbColor::new(0, 255, 0)).unwrap();
+    nav.add_key(
+        KeyDefinition::new(Position::new(1, 0), "M_EMAIL").with_description("Types my email"),
+    );
+    nav.add_key(KeyDefinition::new(Position::new(1, 1), "KC_HOME").with_description("Line start"));
+    layout.add_layer(nav).unwrap();
+
+    layout
+}
+
+fn press(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+#[test]
+fn test_typing_finds_plain_keycodes() {
+    let layout = create_test_layout();
+    let mut search = KeySearch::new();
+
+    for c in "kc_a".chars() {
+        search.handle_input(press(KeyCode::Char(c)), &layout);
+    }
+
+    assert_eq!(
+        search.handle_input(press(KeyCode::Enter), &layout),
+        Some(KeySearchEvent::KeySelected {
+            layer: 0,
+            position: Position::new(0, 0),
+        })
+    );
+}
+
+#[test]
+fn test_typing_filters_and_enter_selects() {
+    let layout = create_test_layout();
+    let mut search = KeySearch::new();
+
+    for c in "line".chars() {
+        assert_eq!(search.handle_input(press(KeyCode::Char(c)), &layout), None);
+    }
+    assert_eq!(search.query(), "line");
+
+    assert_eq!(
+        search.handle_input(press(KeyCode::Enter), &layout),
+        Some(KeySearchEvent::KeySelected {
+            layer: 1,
+            position: Position::new(1, 1),
+        })
+    );
+}
+
+#[test]
+fn test_navigation_wraps_and_enter_without_matches_is_ignored() {
+    let layout = create_test_layout();
+    let mut search = KeySearch::new();
+
+    search.handle_input(press(KeyCode::Up), &layout);
+    assert_eq!(search.selected(), 2);
+    search.handle_input(press(KeyCode::Down), &layout);
+    assert_eq!(search.selected(), 0);
+
+    search.handle_input(press(KeyCode::Char('z')), &layout);
+    assert_eq!(search.handle_input(press(KeyCode::Enter), &layout), None);
+    assert_eq!(
+        search.handle_input(press(KeyCode::Esc), &layout),
+        Some(KeySearchEvent::Cancelled)
+    );
+}
@@ -1,6 +1,9 @@
 //! Keycode picker dialog for selecting keycodes
 //!
 //! This module implements the `KeycodePicker` component for self-contained keycode selection.
+//!
+//! The sidebar starts with "All", the user's Favorites and the recently
+//! picked keycodes, followed by the database categories and "Languages".
 
 use crossterm::event::{self, KeyCode, KeyModifiers};
 use ratatui::{
@@ -15,6 +18,21 @@ use super::keycode_picker_helpers::centered_rect;
 use crate::keycode_db::KeycodeDb;
 use crate::tui::{component::ContextualComponent, popup_border_style, popup_title, PopupType};
 
+/// Width of the keycode column in the keycode list.
+const CODE_COLUMN_WIDTH: usize = 16;
+
+/// Spaces used to pad keycodes to [`CODE_COLUMN_WIDTH`] without allocating.
+const CODE_COLUMN_PADDING: &str = "                ";
+
+/// Sidebar index of the Favorites list.
+const FAVORITES_INDEX: usize = 1;
+
+/// Sidebar index of the Recent list.
+const RECENT_INDEX: usize = 2;
+
+/// Sidebar entries before the first keycode category (All, Favorites, Recent).
+const LEADING_ENTRIES: usize = 3;
+
 /// Events emitted by the `KeycodePicker` component
 #[derive(Debug, Clone)]
 pub enum KeycodePickerEvent {
@@ -43,7 +61,7 @@ pub struct KeycodePickerState {
     pub search: String,
     /// Selected keycode index in the list
     pub selected: usize,
-    /// Current category index (0 = All, last = Languages)
+    /// Current category index (0 = All, 1 = Favorites, 2 = Recent, last = Languages)
     pub category_index: usize,
     /// Which pane has focus
     pub focus: PickerFocus,
@@ -53,6 +71,10 @@ pub struct KeycodePickerState {
     pub selected_language: Option<String>,
     /// Selected language index in language list
     pub language_list_index: usize,
+    /// Favorite keycodes, in the user's order
+    pub favorites: Vec<String>,
+    /// Recently picked keycodes, most recent first
+    pub recent: Vec<String>,
 }
 
 impl Default for KeycodePickerState {
@@ -73,6 +95,8 @@ impl KeycodePickerState {
             sidebar_scroll: 0,
             selected_language: None,
             language_list_index: 0,
+            favorites: Vec::new(),
+            recent: Vec::new(),
         }
     }
 
@@ -89,9 +113,7 @@ impl KeycodePickerState {
                 let language_list_index =
                     languages.iter().position(|l| l.id == *lang_id).unwrap_or(0);
 
-                // Languages category is at index: categories.len() + 1
-                // (index 0 = "All", then regular categories, then "Languages")
-                let languages_category_index = keycode_db.categories().len() + 1;
+                let languages_category_index = languages_index(keycode_db);
 
                 Self {
                     search: String::new(),
@@ -101,13 +123,28 @@ impl KeycodePickerState {
                     sidebar_scroll: 0,
                     selected_language: Some(lang_id.clone()),
                     language_list_index,
+                    favorites: Vec::new(),
+                    recent: Vec::new(),
                 }
             }
             None => Self::new(),
         }
     }
 
-    /// Reset to initial state
+    /// Adds the keycode to the favorites, or removes it if it is one.
+    ///
+    /// Returns true if the keycode is a favorite afterwards.
+    pub fn toggle_favorite(&mut self, keycode: &str) -> bool {
+        if let Some(index) = self.favorites.iter().position(|fav| fav == keycode) {
+            self.favorites.remove(index);
+            false
+        } else {
+            self.favorites.push(keycode.to_string());
+            true
+        }
+    }
+
+    /// Reset to initial state (favorites and recent keycodes are kept)
     pub fn reset(&mut self) {
         self.search.clear();
         self.selected = 0;
@@ -165,6 +202,12 @@ impl KeycodePicker {
         });
     }
 
+    /// Set the Favorites and Recent lists shown in the sidebar.
+    pub fn set_keycode_lists(&mut self, favorites: &[String], recent: &[String]) {
+        self.state.favorites = favorites.to_vec();
+        self.state.recent = recent.to_vec();
+    }
+
     /// Get the current state (for rendering with parent context)
     #[must_use]
     pub const fn state(&self) -> &KeycodePickerState {
@@ -187,7 +230,7 @@ impl ContextualComponent for KeycodePicker {
         key: event::KeyEvent,
         context: &Self::Context,
     ) -> Option<Self::Event> {
-        let total_categories = context.categories().len() + 2; // +1 for "All", +1 for "Languages"
+        let total_categories = languages_index(context) + 1;
 
         match self.state.focus {
             PickerFocus::Sidebar => self.handle_sidebar_input(key, total_categories, context),
@@ -210,8 +253,7 @@ impl ContextualComponent for KeycodePicker {
 impl KeycodePicker {
     /// Check if the Languages category is selected
     fn is_languages_selected(&self, context: &KeycodeDb) -> bool {
-        // Languages is the last category (after all regular categories)
-        self.state.category_index == context.categories().len() + 1
+        self.state.category_index == languages_index(context)
     }
 
     /// Handle input when sidebar has focus
@@ -414,6 +456,16 @@ impl KeycodePicker {
                     (self.state.selected + 10).min(keycodes.len().saturating_sub(1));
                 None
             }
+            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let keycodes = get_filtered_keycodes_from_context(&self.state, context);
+                if let Some(keycode) = keycodes.get(self.state.selected).map(|kc| kc.code.clone()) {
+                    self.state.toggle_favorite(&keycode);
+                    // Keep the selection in range when the Favorites list shrinks
+                    let remaining = get_filtered_keycodes_from_context(&self.state, context).len();
+                    self.state.selected = self.state.selected.min(remaining.saturating_sub(1));
+                }
+                None
+            }
             KeyCode::Char(c) => {
                 // Add to search (includes j, k, h, l when search is active)
                 self.state.search.push(c);
@@ -431,6 +483,11 @@ impl KeycodePicker {
     }
 }
 
+/// Sidebar index of the Languages entry, after all regular categories.
+fn languages_index(context: &KeycodeDb) -> usize {
+    context.categories().len() + LEADING_ENTRIES
+}
+
 /// Get filtered keycodes based on state and context (helper for component)
 pub fn get_filtered_keycodes_from_context<'a>(
     picker_state: &KeycodePickerState,
@@ -439,9 +496,16 @@ pub fn get_filtered_keycodes_from_context<'a>(
     let categories = context.categories();
     let category_index = picker_state.category_index;
 
+    match category_index {
+        FAVORITES_INDEX => {
+            return context.search_in_list(&picker_state.search, &picker_state.favorites)
+        }
+        RECENT_INDEX => return context.search_in_list(&picker_state.search, &picker_state.recent),
+        _ => {}
+    }
+
     // Check if Languages category is selected
-    let languages_index = categories.len() + 1;
-    if category_index == languages_index {
+    if category_index == languages_index(context) {
         // Return language-specific keycodes if a language is selected
         if let Some(ref lang_id) = picker_state.selected_language {
             return context.search_in_language(&picker_state.search, lang_id);
@@ -450,11 +514,10 @@ pub fn get_filtered_keycodes_from_context<'a>(
         return Vec::new();
     }
 
-    let active_category = if category_index == 0 {
-        None
-    } else {
-        categories.get(category_index - 1).map(|c| c.id.as_str())
-    };
+    let active_category = category_index
+        .checked_sub(LEADING_ENTRIES)
+        .and_then(|i| categories.get(i))
+        .map(|c| c.id.as_str());
 
     if let Some(cat_id) = active_category {
         context.search_in_category(&picker_state.search, cat_id)
@@ -572,8 +635,7 @@ fn render_keycode_picker_internal(
         .split(content_area);
 
     // Check if Languages category is selected
-    let languages_index = categories.len() + 1;
-    let is_languages_mode = category_index == languages_index;
+    let is_languages_mode = category_index == languages_index(context);
 
     // Search box (hide when in language selector mode without language selected)
     let show_search = !is_languages_mode || picker_state.selected_language.is_some();
@@ -669,44 +731,44 @@ fn render_keycode_picker_internal(
     }
 
     // Get filtered keycodes based on search and category
-    let keycodes = if is_languages_mode {
-        // Language-specific keycodes
-        if let Some(ref lang_id) = picker_state.selected_language {
-            context.search_in_language(&picker_state.search, lang_id)
-        } else {
-            Vec::new()
-        }
+    let keycodes = get_filtered_keycodes_from_context(picker_state, context);
+
+    // Only the rows that fit are built; a fresh list state would scroll the
+    // selection to the bottom row, so the window ends at the selection.
+    let visible_rows = usize::from(content_chunks[1].height.saturating_sub(2)).max(1);
+    let selected = picker_state.selected.min(keycodes.len().saturating_sub(1));
+    let window_start = if focus == PickerFocus::Keycodes {
+        selected.saturating_sub(visible_rows - 1)
     } else {
-        let active_category = if category_index == 0 {
-            None
-        } else {
-            categories.get(category_index - 1).map(|c| c.id.as_str())
-        };
-
-        if let Some(cat_id) = active_category {
-            context.search_in_category(&picker_state.search, cat_id)
-        } else {
-            context.search(&picker_state.search)
-        }
+        0
     };
 
-    // Build list items with better formatting
+    // Build list items with better formatting, borrowing from the database
+    let code_style = Style::default()
+        .fg(theme.primary)
+        .add_modifier(Modifier::BOLD);
+    let name_style = Style::default().fg(theme.text);
+    let desc_style = Style::default().fg(theme.text_muted);
+    let favorite_style = Style::default().fg(theme.accent);
     let list_items: Vec<ListItem> = keycodes
         .iter()
+        .skip(window_start)
+        .take(visible_rows)
         .map(|keycode| {
-            let code_style = Style::default()
-                .fg(theme.primary)
-                .add_modifier(Modifier::BOLD);
-            let name_style = Style::default().fg(theme.text);
-            let desc_style = Style::default().fg(theme.text_muted);
-
+            let padding = CODE_COLUMN_WIDTH.saturating_sub(keycode.code.chars().count());
             let mut spans = vec![
-                Span::styled(format!("{:16}", keycode.code), code_style),
-                Span::styled(&keycode.name, name_style),
+                Span::styled(keycode.code.as_str(), code_style),
+                Span::raw(&CODE_COLUMN_PADDING[..padding]),
+                Span::styled(keycode.name.as_str(), name_style),
             ];
 
+            if category_index != FAVORITES_INDEX && picker_state.favorites.contains(&keycode.code) {
+                spans.push(Span::styled(" ★", favorite_style));
+            }
+
             if let Some(desc) = &keycode.description {
-                spans.push(Span::styled(format!(" - {desc}"), desc_style));
+                spans.push(Span::styled(" - ", desc_style));
+                spans.push(Span::styled(desc.as_str(), desc_style));
             }
 
             ListItem::new(Line::from(spans))
@@ -723,12 +785,15 @@ fn render_keycode_picker_internal(
         } else {
             "Languages".to_string()
         }
-    } else if category_index == 0 {
-        "All".to_string()
     } else {
-        categories
-            .get(category_index - 1)
-            .map_or_else(|| "Unknown".to_string(), |c| c.name.clone())
+        match category_index {
+            0 => "All".to_string(),
+            FAVORITES_INDEX => "Favorites".to_string(),
+            RECENT_INDEX => "Recent".to_string(),
+            _ => categories
+                .get(category_index - LEADING_ENTRIES)
+                .map_or_else(|| "Unknown".to_string(), |c| c.name.clone()),
+        }
     };
 
     let list_border_color = if focus == PickerFocus::Keycodes {
@@ -756,9 +821,7 @@ fn render_keycode_picker_internal(
     // Create list state for highlighting
     let mut list_state = ListState::default();
     if focus == PickerFocus::Keycodes {
-        list_state.select(Some(
-            picker_state.selected.min(keycodes.len().saturating_sub(1)),
-        ));
+        list_state.select(Some(selected - window_start));
     }
 
     f.render_stateful_widget(list, content_chunks[1], &mut list_state);
@@ -825,6 +888,13 @@ fn render_keycode_picker_internal(
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" Apply  "),
+            Span::styled(
+                "Ctrl+F",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" Favorite  "),
             Span::styled(
                 "Esc",
                 Style::default()
@@ -923,8 +993,8 @@ fn render_sidebar(
         theme.surface
     };
 
-    // Build category list items: "All" + all categories + "Languages"
-    let mut items: Vec<ListItem> = Vec::with_capacity(categories.len() + 2);
+    // Build category list items: "All", Favorites, Recent + all categories + "Languages"
+    let mut items: Vec<ListItem> = Vec::with_capacity(categories.len() + LEADING_ENTRIES + 1);
 
     // "All" option
     let all_style = if selected == 0 {
@@ -939,9 +1009,22 @@ fn render_sidebar(
         " All", all_style,
     )])));
 
+    // Favorites and Recent lists, accented like "Languages"
+    for (index, name) in [(FAVORITES_INDEX, " ★ Favorites"), (RECENT_INDEX, " Recent")] {
+        let style = if selected == index {
+            Style::default()
+                .fg(theme.background)
+                .bg(theme.accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.accent)
+        };
+        items.push(ListItem::new(Line::from(vec![Span::styled(name, style)])));
+    }
+
     // Category items
     for (i, cat) in categories.iter().enumerate() {
-        let style = if selected == i + 1 {
+        let style = if selected == i + LEADING_ENTRIES {
             Style::default()
                 .fg(theme.background)
                 .bg(theme.primary)
@@ -961,7 +1044,7 @@ fn render_sidebar(
     }
 
     // "Languages" option at the end
-    let languages_idx = categories.len() + 1;
+    let languages_idx = categories.len() + LEADING_ENTRIES;
     let languages_style = if selected == languages_idx {
         Style::default()
             .fg(theme.background)
@@ -997,3 +1080,6 @@ fn render_sidebar(
 
     f.render_stateful_widget(list, area, &mut list_state);
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+//! Tests for keycode_picker.
+
+use super::*;
+use crossterm::event::KeyEvent;
+
+fn press(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn codes(picker: &KeycodePicker, db: &KeycodeDb) -> Vec<String> {
+    get_filtered_keycodes_from_context(picker.state(), db)
+        .iter()
+        .map(|kc| kc.code.clone())
+        .collect()
+}
+
+#[test]
+fn test_favorites_and_recent_lists_follow_all() {
+    let db = KeycodeDb::load().unwrap();
+    let mut picker = KeycodePicker::new();
+    picker.set_keycode_lists(
+        &["MT()".to_string(), "LT()".to_string()],
+        &["KC_ESC".to_string()],
+    );
+
+    picker.state.focus = PickerFocus::Sidebar;
+    picker.handle_input(press(KeyCode::Down), &db);
+    assert_eq!(codes(&picker, &db), vec!["MT()", "LT()"]);
+
+    picker.handle_input(press(KeyCode::Down), &db);
+    assert_eq!(codes(&picker, &db), vec!["KC_ESC"]);
+
+    // The first database category comes after the two lists
+    picker.handle_input(press(KeyCode::Down), &db);
+    let first_category = &db.categories()[0].id;
+    assert!(get_filtered_keycodes_from_context(picker.state(), &db)
+        .iter()
+        .all(|kc| &kc.category == first_category));
+
+    picker.handle_input(press(KeyCode::End), &db);
+    assert!(picker.is_languages_selected(&db));
+}
+
+#[test]
+fn test_ctrl_f_toggles_favorite() {
+    let db = KeycodeDb::load().unwrap();
+    let mut picker = KeycodePicker::new();
+    for c in "KC_ESC".chars() {
+        picker.handle_input(press(KeyCode::Char(c)), &db);
+    }
+    let selected = codes(&picker, &db)[0].clone();
+    let ctrl_f = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL);
+
+    assert!(picker.handle_input(ctrl_f, &db).is_none());
+    assert_eq!(picker.state().favorites, vec![selected]);
+    // The shortcut doesn't end up in the search
+    assert_eq!(picker.state().search, "KC_ESC");
+
+    picker.handle_input(ctrl_f, &db);
+    assert!(picker.state().favorites.is_empty());
+}
+
+#[test]
+fn test_reset_keeps_lists() {
+    let mut state = KeycodePickerState::new();
+    state.toggle_favorite("LT()");
+    state.recent = vec!["KC_A".to_string()];
+    state.category_index = FAVORITES_INDEX;
+
+    state.reset();
+
+    assert_eq!(state.category_index, 0);
+    assert_eq!(state.favorites, vec!["LT()"]);
+    assert_eq!(state.recent, vec!["KC_A"]);
+}
@@ -4,21 +4,29 @@
 //! allows them to pick which layer the keycode should reference.
 //! The selected layer is stored as a UUID reference (@`layer_id`) rather
 //! than a numeric index, making it stable across layer reordering.
+//!
+//! Each entry shows the layer's color and how many keys already reference
+//! it, and the highlighted layer is previewed as a mini keymap so the right
+//! target is easy to spot.
 
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
-use crate::models::Layer;
+use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+use crate::services::layer_refs::{build_layer_ref_index, parse_layer_keycode, LayerRefTarget};
 
 use crate::tui::Theme;
 
+/// Characters shown per key in the mini keymap preview.
+const PREVIEW_LABEL_WIDTH: usize = 3;
+
 /// Events emitted by the `LayerPicker` component
 #[derive(Debug, Clone)]
 pub enum LayerPickerEvent {
@@ -112,27 +120,28 @@ impl LayerPicker {
 }
 
 impl crate::tui::component::ContextualComponent for LayerPicker {
-    type Context = Vec<crate::models::Layer>;
+    type Context = crate::models::Layout;
     type Event = LayerPickerEvent;
 
-    fn handle_input(&mut self, key: KeyEvent, layers: &Self::Context) -> Option<Self::Event> {
+    fn handle_input(&mut self, key: KeyEvent, layout: &Self::Context) -> Option<Self::Event> {
+        let layer_count = layout.layers.len();
         match key.code {
             KeyCode::Esc => Some(LayerPickerEvent::Cancelled),
             KeyCode::Enter => Some(LayerPickerEvent::LayerSelected(self.state.selected)),
             KeyCode::Up | KeyCode::Char('k') => {
-                self.state.select_previous(layers.len());
+                self.state.select_previous(layer_count);
                 None
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                self.state.select_next(layers.len());
+                self.state.select_next(layer_count);
                 None
             }
             _ => None,
         }
     }
 
-    fn render(&self, f: &mut Frame, _area: Rect, theme: &Theme, layers: &Self::Context) {
-        render_layer_picker_component(f, self, layers, theme);
+    fn render(&self, f: &mut Frame, _area: Rect, theme: &Theme, layout: &Self::Context) {
+        render_layer_picker_component(f, self, layout, theme);
     }
 }
 
@@ -148,11 +157,12 @@ impl LayerPicker {
 pub fn render_layer_picker_component(
     f: &mut Frame,
     picker: &LayerPicker,
-    layers: &[Layer],
+    layout: &crate::models::Layout,
     theme: &Theme,
 ) {
     let state = picker.state();
-    let area = centered_rect(50, 60, f.area());
+    let layers = &layout.layers;
+    let area = centered_rect(80, 70, f.area());
 
     // Clear the background area first
     f.render_widget(Clear, area);
@@ -165,7 +175,7 @@ pub fn render_layer_picker_component(
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Title
-            Constraint::Min(5),    // Layer list
+            Constraint::Min(5),    // Layer list + preview
             Constraint::Length(3), // Help text
         ])
         .split(area);
@@ -188,17 +198,31 @@ pub fn render_layer_picker_component(
         .style(Style::default().fg(theme.text));
     f.render_widget(preview_text, chunks[0]);
 
-    // Build list items
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(chunks[1]);
+
+    // Build list items: color swatch, name, and reference count
+    let ref_counts = reference_counts(layers);
     let list_items: Vec<ListItem> = layers
         .iter()
         .enumerate()
         .map(|(idx, layer)| {
+            let color = to_color(layer_color(layout, idx));
+            let refs = match ref_counts[idx] {
+                0 => String::new(),
+                1 => " (1 ref)".to_string(),
+                n => format!(" ({n} refs)"),
+            };
             let content = Line::from(vec![
+                Span::styled("■ ", Style::default().fg(color)),
                 Span::styled(
                     format!("Layer {idx}: "),
                     Style::default().fg(theme.text_muted),
                 ),
                 Span::styled(&layer.name, Style::default().fg(theme.text)),
+                Span::styled(refs, Style::default().fg(theme.text_muted)),
             ]);
             ListItem::new(content)
         })
@@ -224,7 +248,25 @@ pub fn render_layer_picker_component(
     let mut list_state = ListState::default();
     list_state.select(Some(state.selected.min(layers.len().saturating_sub(1))));
 
-    f.render_stateful_widget(list, chunks[1], &mut list_state);
+    f.render_stateful_widget(list, body[0], &mut list_state);
+
+    // Mini keymap of the highlighted layer
+    let preview_title = layers.get(state.selected).map_or_else(
+        || " Preview ".to_string(),
+        |layer| format!(" Layer {}: {} ", state.selected, layer.name),
+    );
+    let preview_lines = if layers.get(state.selected).is_some() {
+        preview_lines(layout, state.selected, theme)
+    } else {
+        Vec::new()
+    };
+    let preview_widget = Paragraph::new(preview_lines).block(
+        Block::default()
+            .title(preview_title)
+            .borders(Borders::ALL)
+            .style(Style::default().bg(theme.background)),
+    );
+    f.render_widget(preview_widget, body[1]);
 
     // Help text
     let help = Paragraph::new("^|v: Navigate | Enter: Select | Esc: Cancel")
@@ -237,6 +279,132 @@ pub fn render_layer_picker_component(
     f.render_widget(help, chunks[2]);
 }
 
+/// Counts the keys referencing each layer (MO, LT, TG, ...), by layer index.
+#[must_use]
+pub fn reference_counts(layers: &[Layer]) -> Vec<usize> {
+    let index = build_layer_ref_index(layers);
+    (0..layers.len())
+        .map(|idx| index.get(&idx).map_or(0, Vec::len))
+        .collect()
+}
+
+/// Short label for a key in the mini keymap (at most three characters).
+///
+/// Layer keycodes show their prefix and target index (e.g. `MO2`), and
+/// transparent keys a down-pointing triangle.
+#[must_use]
+pub fn mini_label(key: &KeyDefinition, layers: &[Layer]) -> String {
+    if key.is_transparent() {
+        return "▽".to_string();
+    }
+    if key.is_no_op() {
+        return String::new();
+    }
+
+    let label = match parse_layer_keycode(&key.keycode) {
+        Some((target, _)) => {
+            let prefix = key.keycode.split('(').next().unwrap_or_default();
+            let target = match target {
+                LayerRefTarget::Index(idx) => Some(idx),
+                LayerRefTarget::Uuid(id) => layers
+                    .iter()
+                    .position(|layer| id.strip_prefix('@') == Some(layer.id.as_str())),
+            };
+            target.map_or_else(|| format!("{prefix}?"), |idx| format!("{prefix}{idx}"))
+        }
+        None => crate::keycode_db::format::strip_kc_prefix(&key.keycode),
+    };
+    label.chars().take(PREVIEW_LABEL_WIDTH).collect()
+}
+
+/// Builds the mini keymap of a layer followed by where it is referenced from.
+fn preview_lines(
+    layout: &crate::models::Layout,
+    layer_idx: usize,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let layers = &layout.layers;
+    let Some(layer) = layers.get(layer_idx) else {
+        return Vec::new();
+    };
+    let (rows, cols) = layer.keys.iter().fold((0u8, 0u8), |(rows, cols), key| {
+        (
+            rows.max(key.position.row + 1),
+            cols.max(key.position.col + 1),
+        )
+    });
+
+    let mut lines: Vec<Line<'static>> = (0..rows)
+        .map(|row| {
+            let spans: Vec<Span<'static>> = (0..cols)
+                .map(|col| {
+                    let Some(key) = layer.get_key(Position::new(row, col)) else {
+                        return Span::raw(" ".repeat(PREVIEW_LABEL_WIDTH + 1));
+                    };
+                    let (rgb, _) = layout.resolve_display_color(layer_idx, key);
+                    let rgb = layout.apply_rgb_settings(rgb);
+                    let text = format!(
+                        "{:<width$} ",
+                        mini_label(key, layers),
+                        width = PREVIEW_LABEL_WIDTH
+                    );
+                    Span::styled(text, Style::default().fg(to_color(rgb)))
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect();
+
+    let index = build_layer_ref_index(layers);
+    let refs = index.get(&layer_idx).map_or(&[][..], Vec::as_slice);
+    lines.push(Line::default());
+    if refs.is_empty() {
+        lines.push(Line::styled(
+            "Not referenced by any key yet",
+            Style::default().fg(theme.text_muted),
+        ));
+    } else {
+        lines.push(Line::styled(
+            "Referenced by:",
+            Style::default().fg(theme.text_muted),
+        ));
+        for layer_ref in refs {
+            let from = layers
+                .get(layer_ref.from_layer)
+                .map_or("?", |layer| layer.name.as_str());
+            lines.push(Line::styled(
+                format!(
+                    "  {} on {from} ({}, {})",
+                    layer_ref.kind.display_name(),
+                    layer_ref.position.row,
+                    layer_ref.position.col
+                ),
+                Style::default().fg(theme.text),
+            ));
+        }
+    }
+
+    lines
+}
+
+/// Returns the layer-level color (layer category color or layer default).
+fn layer_color(layout: &crate::models::Layout, layer_idx: usize) -> RgbColor {
+    let Some(layer) = layout.layers.get(layer_idx) else {
+        return RgbColor::new(0, 0, 0);
+    };
+    let color = layer
+        .category_id
+        .as_deref()
+        .and_then(|id| layout.get_category(id))
+        .map_or(layer.default_color, |category| category.color);
+    layout.apply_rgb_settings(color)
+}
+
+/// Converts an RGB color to a terminal color.
+const fn to_color(rgb: RgbColor) -> Color {
+    Color::Rgb(rgb.r, rgb.g, rgb.b)
+}
+
 /// Helper to create a centered rectangle
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
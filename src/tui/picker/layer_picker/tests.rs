@@ -4,7 +4,6 @@
 
 use super::*;
 
-use super::*;
 use crate::models::RgbColor;
 
 fn create_test_layers() -> Vec<Layer> {
@@ -63,3 +62,29 @@ fn test_navigation() {
     state.select_previous(layer_count);
     assert_eq!(state.selected, 2);
 }
+
+#[test]
+fn test_reference_counts() {
+    let mut layers = create_test_layers();
+    let nav_ref = format!("LT(@{}, KC_SPC)", layers[1].id);
+    layers[0].add_key(KeyDefinition::new(Position::new(0, 0), "MO(2)"));
+    layers[0].add_key(KeyDefinition::new(Position::new(0, 1), nav_ref));
+    layers[2].add_key(KeyDefinition::new(Position::new(0, 0), "TG(1)"));
+
+    assert_eq!(reference_counts(&layers), vec![0, 2, 1]);
+}
+
+#[test]
+fn test_mini_label() {
+    let layers = create_test_layers();
+    let label =
+        |keycode: &str| mini_label(&KeyDefinition::new(Position::new(0, 0), keycode), &layers);
+
+    assert_eq!(label("KC_A"), "A");
+    assert_eq!(label("KC_ENTER"), "ENT");
+    assert_eq!(label("KC_TRNS"), "▽");
+    assert_eq!(label("KC_NO"), "");
+    assert_eq!(label("MO(2)"), "MO2");
+    assert_eq!(label(&format!("LT(@{}, KC_SPC)", layers[1].id)), "LT1");
+    assert_eq!(label("TG(@missing)"), "TG?");
+}
@@ -1,7 +1,8 @@
 //! Layout picker for loading saved layouts.
 //!
 //! This module provides UI components for browsing and loading
-//! saved layout files from ~/.config/LazyQMK/layouts/
+//! saved layout files from ~/.config/LazyQMK/layouts/. Share bundles
+//! (`.lazyqmk`) dropped into that directory are listed too.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Local};
@@ -17,6 +18,7 @@ use std::path::PathBuf;
 
 use crate::config::Config;
 use crate::models::LayoutMetadata;
+use crate::services::share_bundle::BUNDLE_EXTENSION;
 use crate::services::LayoutService;
 
 /// Layout file information with path and metadata.
@@ -133,10 +135,14 @@ impl LayoutPickerState {
 
             let ext = path.extension().and_then(|s| s.to_str());
 
-            // Process .json and legacy .md files
+            // Process .json and legacy .md files, and share bundles not yet
+            // saved as .json (saving writes the .json next to the bundle)
             let layout_result = match ext {
                 Some("json") => LayoutService::load(&path),
                 Some("md") => LayoutService::load(&path), // triggers auto-migration
+                Some(BUNDLE_EXTENSION) if !path.with_extension("json").exists() => {
+                    LayoutService::load(&path)
+                }
                 _ => continue,
             };
 
@@ -0,0 +1,380 @@
+//! Macro Editor Component
+//!
+//! Lists the layout's macros and edits them in a two-field form (name and
+//! compact sequence, e.g. `git status{KC_ENT}`). Saving and deleting are
+//! reported as events; the handler applies them to the layout.
+
+use crate::models::{Layout, MacroDefinition};
+use crate::tui::theme::Theme;
+use crate::tui::Component;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// Events emitted by the macro editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacroEditorEvent {
+    /// User selected a macro to apply to the current key
+    Selected(String),
+    /// User saved a new or edited macro
+    Saved {
+        /// Name of the macro being replaced (None when creating)
+        original: Option<String>,
+        /// The validated macro definition
+        macro_def: MacroDefinition,
+    },
+    /// User wants to delete a macro
+    Delete(String),
+    /// User closed the editor
+    Cancelled,
+}
+
+/// Which form field has focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacroField {
+    /// Macro name
+    Name,
+    /// Compact step sequence
+    Sequence,
+}
+
+/// Draft being edited in the form
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MacroDraft {
+    /// Name of the macro being edited (None when creating)
+    original: Option<String>,
+    name: String,
+    sequence: String,
+    focus: MacroField,
+    error: Option<String>,
+}
+
+/// Macro Editor component state
+#[derive(Debug, Clone)]
+pub struct MacroEditor {
+    /// Index of the highlighted macro
+    selected: usize,
+    /// Macros from the layout (read-only view)
+    macros: Vec<MacroDefinition>,
+    /// Form state while creating or editing
+    draft: Option<MacroDraft>,
+}
+
+impl MacroEditor {
+    /// Creates a new macro editor with macros from the layout
+    #[must_use]
+    pub fn new(layout: &Layout) -> Self {
+        Self {
+            selected: 0,
+            macros: layout.macros.clone(),
+            draft: None,
+        }
+    }
+
+    /// Get the currently selected macro name
+    fn selected_name(&self) -> Option<String> {
+        self.macros.get(self.selected).map(|m| m.name.clone())
+    }
+
+    fn open_form(&mut self, existing: Option<&MacroDefinition>) {
+        self.draft = Some(MacroDraft {
+            original: existing.map(|m| m.name.clone()),
+            name: existing.map(|m| m.name.clone()).unwrap_or_default(),
+            sequence: existing.map(MacroDefinition::sequence).unwrap_or_default(),
+            focus: MacroField::Name,
+            error: None,
+        });
+    }
+
+    /// Parse and validate the draft, returning the definition or an error message
+    fn build_draft(&self, draft: &MacroDraft) -> Result<MacroDefinition, String> {
+        let name = draft.name.trim();
+        let duplicate = self
+            .macros
+            .iter()
+            .any(|m| m.name == name && draft.original.as_deref() != Some(name));
+        if duplicate {
+            return Err(format!("Macro '{name}' already exists"));
+        }
+
+        let macro_def =
+            MacroDefinition::from_sequence(name, &draft.sequence).map_err(|e| e.to_string())?;
+        macro_def.validate().map_err(|e| e.to_string())?;
+        Ok(macro_def)
+    }
+
+    fn handle_form_input(&mut self, key: KeyEvent) -> Option<MacroEditorEvent> {
+        let mut draft = self.draft.take()?;
+        match key.code {
+            KeyCode::Esc => return None,
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                draft.focus = match draft.focus {
+                    MacroField::Name => MacroField::Sequence,
+                    MacroField::Sequence => MacroField::Name,
+                };
+            }
+            KeyCode::Enter => match self.build_draft(&draft) {
+                Ok(macro_def) => {
+                    return Some(MacroEditorEvent::Saved {
+                        original: draft.original,
+                        macro_def,
+                    });
+                }
+                Err(message) => draft.error = Some(message),
+            },
+            KeyCode::Backspace => {
+                match draft.focus {
+                    MacroField::Name => draft.name.pop(),
+                    MacroField::Sequence => draft.sequence.pop(),
+                };
+                draft.error = None;
+            }
+            KeyCode::Char(c) => {
+                match draft.focus {
+                    MacroField::Name => draft.name.push(c),
+                    MacroField::Sequence => draft.sequence.push(c),
+                }
+                draft.error = None;
+            }
+            _ => {}
+        }
+        self.draft = Some(draft);
+        None
+    }
+}
+
+impl Component for MacroEditor {
+    type Event = MacroEditorEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        if self.draft.is_some() {
+            return self.handle_form_input(key);
+        }
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.macros.len().saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                return self.selected_name().map(MacroEditorEvent::Selected);
+            }
+            KeyCode::Char('n') => self.open_form(None),
+            KeyCode::Char('e') => {
+                let existing = self.macros.get(self.selected).cloned();
+                if existing.is_some() {
+                    self.open_form(existing.as_ref());
+                }
+            }
+            KeyCode::Char('D') => {
+                return self.selected_name().map(MacroEditorEvent::Delete);
+            }
+            KeyCode::Esc => return Some(MacroEditorEvent::Cancelled),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, _area: Rect, theme: &Theme) {
+        let area = centered_rect(70, 60, frame.area());
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(theme.background)),
+            area,
+        );
+
+        match &self.draft {
+            Some(draft) => render_form(frame, area, draft, theme),
+            None => self.render_list(frame, area, theme),
+        }
+    }
+}
+
+impl MacroEditor {
+    fn render_list(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        let items: Vec<ListItem> = if self.macros.is_empty() {
+            vec![ListItem::new(
+                Line::from("No macros defined. Press 'n' to create one.")
+                    .style(Style::default().fg(theme.text_muted)),
+            )]
+        } else {
+            self.macros
+                .iter()
+                .map(|m| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{}: ", m.name), Style::default().fg(theme.accent)),
+                        Span::raw(m.sequence()),
+                    ]))
+                })
+                .collect()
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Macros ")
+                    .style(Style::default().bg(theme.background)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(theme.highlight_bg)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(theme.background).fg(theme.text));
+
+        let mut list_state = ListState::default();
+        if !self.macros.is_empty() {
+            list_state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        let help = help_line(
+            &[
+                ("↑/↓", "Navigate"),
+                ("Enter", "Apply"),
+                ("n", "New"),
+                ("e", "Edit"),
+                ("D", "Delete"),
+                ("Esc", "Close"),
+            ],
+            theme,
+        );
+        frame.render_widget(help, chunks[1]);
+    }
+}
+
+fn render_form(frame: &mut Frame, area: Rect, draft: &MacroDraft, theme: &Theme) {
+    let chunks = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Name
+            Constraint::Length(3), // Sequence
+            Constraint::Min(3),    // Syntax hint / error
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    for (chunk, field, title, value) in [
+        (chunks[0], MacroField::Name, " Name ", &draft.name),
+        (
+            chunks[1],
+            MacroField::Sequence,
+            " Sequence ",
+            &draft.sequence,
+        ),
+    ] {
+        let focused = draft.focus == field;
+        let mut spans = vec![Span::raw(value.as_str())];
+        if focused {
+            spans.push(Span::styled("█", Style::default().fg(theme.primary)));
+        }
+        let border = if focused {
+            theme.primary
+        } else {
+            theme.text_muted
+        };
+        let input = Paragraph::new(Line::from(spans))
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(border)),
+            )
+            .style(Style::default().fg(theme.text));
+        frame.render_widget(input, chunk);
+    }
+
+    let hint = draft.error.as_ref().map_or_else(
+        || {
+            Line::from(Span::styled(
+                "Text is typed as-is. {KC_ENT} taps a key, {100ms} waits, {{ types a brace.",
+                Style::default().fg(theme.text_muted),
+            ))
+        },
+        |error| {
+            Line::from(Span::styled(
+                error.as_str(),
+                Style::default().fg(theme.error),
+            ))
+        },
+    );
+    let title = if draft.original.is_some() {
+        " Edit Macro "
+    } else {
+        " New Macro "
+    };
+    frame.render_widget(
+        Paragraph::new(hint).block(Block::default().title(title).borders(Borders::ALL)),
+        chunks[2],
+    );
+
+    let help = help_line(
+        &[("Tab", "Switch field"), ("Enter", "Save"), ("Esc", "Back")],
+        theme,
+    );
+    frame.render_widget(help, chunks[3]);
+}
+
+fn help_line<'a>(entries: &[(&'a str, &'a str)], theme: &Theme) -> Paragraph<'a> {
+    let spans: Vec<Span> = entries
+        .iter()
+        .flat_map(|(key, label)| {
+            [
+                Span::styled(
+                    *key,
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" {label}  ")),
+            ]
+        })
+        .collect();
+
+    Paragraph::new(Line::from(spans))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Help ")
+                .style(Style::default().bg(theme.background)),
+        )
+        .style(Style::default().bg(theme.background).fg(theme.text))
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
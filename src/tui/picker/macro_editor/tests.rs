@@ -0,0 +1,105 @@
+//! Tests for macro_editor.
+
+use super::*;
+use crossterm::event::KeyModifiers;
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    layout
+        .add_macro(MacroDefinition::from_sequence("email", "me@example.com").unwrap())
+        .unwrap();
+    layout
+        .add_macro(MacroDefinition::from_sequence("ls", "ls -la{KC_ENT}").unwrap())
+        .unwrap();
+    layout
+}
+
+fn press(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn type_text(editor: &mut MacroEditor, text: &str) {
+    for c in text.chars() {
+        assert_eq!(editor.handle_input(press(KeyCode::Char(c))), None);
+    }
+}
+
+#[test]
+fn test_enter_selects_highlighted_macro() {
+    let mut editor = MacroEditor::new(&create_test_layout());
+
+    editor.handle_input(press(KeyCode::Down));
+    editor.handle_input(press(KeyCode::Down));
+
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Enter)),
+        Some(MacroEditorEvent::Selected("ls".to_string()))
+    );
+}
+
+#[test]
+fn test_delete_and_cancel() {
+    let mut editor = MacroEditor::new(&create_test_layout());
+
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Char('D'))),
+        Some(MacroEditorEvent::Delete("email".to_string()))
+    );
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Esc)),
+        Some(MacroEditorEvent::Cancelled)
+    );
+}
+
+#[test]
+fn test_create_macro_from_form() {
+    let mut editor = MacroEditor::new(&create_test_layout());
+
+    editor.handle_input(press(KeyCode::Char('n')));
+    type_text(&mut editor, "sig");
+    editor.handle_input(press(KeyCode::Tab));
+    type_text(&mut editor, "Regards{KC_ENT}");
+
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Enter)),
+        Some(MacroEditorEvent::Saved {
+            original: None,
+            macro_def: MacroDefinition::from_sequence("sig", "Regards{KC_ENT}").unwrap(),
+        })
+    );
+}
+
+#[test]
+fn test_edit_prefills_form_and_keeps_original_name() {
+    let mut editor = MacroEditor::new(&create_test_layout());
+
+    editor.handle_input(press(KeyCode::Char('e')));
+    editor.handle_input(press(KeyCode::Tab));
+    editor.handle_input(press(KeyCode::Backspace));
+
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Enter)),
+        Some(MacroEditorEvent::Saved {
+            original: Some("email".to_string()),
+            macro_def: MacroDefinition::from_sequence("email", "me@example.co").unwrap(),
+        })
+    );
+}
+
+#[test]
+fn test_invalid_form_stays_open_with_error() {
+    let mut editor = MacroEditor::new(&create_test_layout());
+
+    // Duplicate name
+    editor.handle_input(press(KeyCode::Char('n')));
+    type_text(&mut editor, "ls");
+    editor.handle_input(press(KeyCode::Tab));
+    type_text(&mut editor, "x");
+    assert_eq!(editor.handle_input(press(KeyCode::Enter)), None);
+    let error = editor.draft.as_ref().and_then(|d| d.error.clone()).unwrap();
+    assert!(error.contains("already exists"), "{error}");
+
+    // Esc returns to the list instead of closing the editor
+    assert_eq!(editor.handle_input(press(KeyCode::Esc)), None);
+    assert!(editor.draft.is_none());
+}
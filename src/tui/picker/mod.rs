@@ -1,12 +1,22 @@
 //! Picker components — popups for selecting keycodes, layers, layouts, colors, etc.
 
+pub mod backup_picker;
 pub mod category_picker;
 pub mod color_picker;
+pub mod copy_to_layer;
+pub mod custom_keycode_editor;
+pub mod file_browser;
+pub mod key_search;
 pub mod keycode_picker;
 pub mod keycode_picker_helpers;
 pub mod layer_picker;
 pub mod layout_picker;
+pub mod macro_editor;
 pub mod modifier_picker;
+pub mod qmk_tree_picker;
 pub mod tap_dance_editor;
 pub mod tap_dance_form;
 pub mod template_browser;
+pub mod template_fit;
+pub mod template_placeholders;
+pub mod unicode_map_editor;
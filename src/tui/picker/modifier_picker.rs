@@ -1,6 +1,9 @@
 //! Modifier picker dialog for selecting QMK modifiers
 //!
 //! Used for `MT()` and `LM()` keycodes that require modifier selection.
+//! Modifiers combine into a `MOD_LCTL | MOD_LSFT` mask shown live as its
+//! abbreviation (`C+S`). QMK mod masks are one-sided, so picking a modifier
+//! from the other hand moves the whole combination to that hand.
 //!
 //! This module implements the Component trait: `ModifierPicker` for self-contained UI components
 
@@ -43,6 +46,11 @@ pub enum QmkModifier {
     RGui = 0b1000_0000,
 }
 
+/// Bits of the left-hand modifiers in a selection.
+const LEFT_MODS: u8 = 0b0000_1111;
+/// Bits of the right-hand modifiers in a selection.
+const RIGHT_MODS: u8 = 0b1111_0000;
+
 impl QmkModifier {
     /// All modifiers in display order (left side first, then right side)
     pub const ALL: [Self; 8] = [
@@ -81,6 +89,23 @@ impl QmkModifier {
             Self::LGui | Self::RGui => "GUI",
         }
     }
+
+    /// Single-letter abbreviation (C, S, A, G)
+    #[must_use]
+    pub const fn letter(&self) -> char {
+        match self {
+            Self::LCtrl | Self::RCtrl => 'C',
+            Self::LShift | Self::RShift => 'S',
+            Self::LAlt | Self::RAlt => 'A',
+            Self::LGui | Self::RGui => 'G',
+        }
+    }
+
+    /// Whether this is a right-hand modifier
+    #[must_use]
+    pub const fn is_right(&self) -> bool {
+        (*self as u8) & RIGHT_MODS != 0
+    }
 }
 
 /// Preset modifier combinations
@@ -126,6 +151,8 @@ pub struct ModifierPickerState {
     pub selected_mods: u8,
     /// Current focus position (0-7 for individual mods, 8-9 for presets)
     pub focus: usize,
+    /// One-line hint about the last action (side switch, empty selection)
+    pub notice: Option<&'static str>,
 }
 
 impl Default for ModifierPickerState {
@@ -141,12 +168,59 @@ impl ModifierPickerState {
         Self {
             selected_mods: 0,
             focus: 0,
+            notice: None,
         }
     }
 
     /// Toggle a modifier by its bit value
+    ///
+    /// Turning on a modifier from the other hand than the current selection
+    /// moves the selection to that hand first, since QMK cannot mix left and
+    /// right modifiers in one mask.
     pub const fn toggle_mod(&mut self, mod_bit: u8) {
-        self.selected_mods ^= mod_bit;
+        let to_right = mod_bit & RIGHT_MODS != 0;
+        if self.selected_mods != 0 && to_right != self.is_right_side() {
+            self.switch_side();
+            self.selected_mods |= mod_bit;
+        } else {
+            self.selected_mods ^= mod_bit;
+            self.notice = None;
+        }
+    }
+
+    /// Whether the selection uses right-hand modifiers
+    #[must_use]
+    pub const fn is_right_side(&self) -> bool {
+        self.selected_mods & RIGHT_MODS != 0
+    }
+
+    /// Moves the selection to the other hand (`C+S` <-> `RC+RS`)
+    pub const fn switch_side(&mut self) {
+        if self.is_right_side() {
+            self.selected_mods = (self.selected_mods & RIGHT_MODS) >> 4;
+            self.notice = Some("Switched to left-hand modifiers");
+        } else {
+            self.selected_mods = (self.selected_mods & LEFT_MODS) << 4;
+            self.notice = Some("Switched to right-hand modifiers");
+        }
+    }
+
+    /// Toggle the modifier with the given letter (C, S, A, G) on the active hand
+    ///
+    /// The active hand is the selection's, or the focused column's when
+    /// nothing is selected.
+    pub fn toggle_letter(&mut self, letter: char) {
+        let right = if self.selected_mods == 0 {
+            (4..8).contains(&self.focus)
+        } else {
+            self.is_right_side()
+        };
+        if let Some(modifier) = QmkModifier::ALL
+            .iter()
+            .find(|m| m.is_right() == right && m.letter() == letter.to_ascii_uppercase())
+        {
+            self.toggle_mod(*modifier as u8);
+        }
     }
 
     /// Check if a modifier is selected
@@ -168,6 +242,7 @@ impl ModifierPickerState {
             } else {
                 self.selected_mods = ModifierPreset::Meh.bits();
             }
+            self.notice = None;
         } else if self.focus == 9 {
             // Hyper preset
             if self.selected_mods == ModifierPreset::Hyper.bits() {
@@ -175,6 +250,7 @@ impl ModifierPickerState {
             } else {
                 self.selected_mods = ModifierPreset::Hyper.bits();
             }
+            self.notice = None;
         }
     }
 
@@ -249,6 +325,31 @@ impl ModifierPickerState {
     pub const fn has_selection(&self) -> bool {
         self.selected_mods != 0
     }
+
+    /// Short form of the selection, e.g. "C+S" or "RC+RA"
+    #[must_use]
+    pub fn abbreviation(&self) -> String {
+        QmkModifier::ALL
+            .iter()
+            .filter(|modifier| self.is_selected(**modifier as u8))
+            .map(|modifier| {
+                if modifier.is_right() {
+                    format!("R{}", modifier.letter())
+                } else {
+                    modifier.letter().to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("+")
+    }
+
+    /// The preset matching the selection, if any
+    #[must_use]
+    pub fn preset(&self) -> Option<ModifierPreset> {
+        [ModifierPreset::Meh, ModifierPreset::Hyper]
+            .into_iter()
+            .find(|preset| preset.bits() == self.selected_mods)
+    }
 }
 
 /// Events emitted by the `ModifierPicker` component
@@ -307,13 +408,34 @@ impl Component for ModifierPicker {
 
         match key.code {
             KeyCode::Esc => Some(ModifierPickerEvent::Cancelled),
-            KeyCode::Enter => Some(ModifierPickerEvent::ModifiersSelected(
-                self.get_modifiers_list(),
-            )),
+            KeyCode::Enter => {
+                if self.state.has_selection() {
+                    Some(ModifierPickerEvent::ModifiersSelected(
+                        self.get_modifiers_list(),
+                    ))
+                } else {
+                    self.state.notice = Some("Select at least one modifier");
+                    None
+                }
+            }
             KeyCode::Char(' ') => {
                 self.state.toggle_focused();
                 None
             }
+            KeyCode::Char(letter @ ('c' | 's' | 'a' | 'g' | 'C' | 'S' | 'A' | 'G')) => {
+                self.state.toggle_letter(letter);
+                None
+            }
+            KeyCode::Char('r' | 'R') => {
+                if self.state.has_selection() {
+                    self.state.switch_side();
+                } else if self.state.focus < 4 {
+                    self.state.focus_right();
+                } else {
+                    self.state.focus_left();
+                }
+                None
+            }
             KeyCode::Up | KeyCode::Char('k') => {
                 self.state.focus_up();
                 None
@@ -410,13 +532,37 @@ fn render_modifier_picker_component(f: &mut Frame, picker: &ModifierPicker, them
         theme,
     );
 
-    // Selected display
-    let selected_text = if picker.state.has_selection() {
-        format!(" Selected: {}", picker.state.to_mod_string())
+    // Live result: abbreviation, then the QMK mask it produces
+    let state = &picker.state;
+    let mut result_line = vec![Span::styled(
+        " Result: ",
+        Style::default().fg(theme.text_muted),
+    )];
+    if state.has_selection() {
+        let abbreviation = state.preset().map_or_else(
+            || state.abbreviation(),
+            |preset| preset.display_name().to_string(),
+        );
+        result_line.push(Span::styled(
+            abbreviation,
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(StyleModifier::BOLD),
+        ));
+        result_line.push(Span::styled(
+            format!("  = {}", state.to_mod_string()),
+            Style::default().fg(theme.text),
+        ));
     } else {
-        " Selected: (none)".to_string()
-    };
-    let selected = Paragraph::new(selected_text).style(Style::default().fg(theme.accent));
+        result_line.push(Span::styled("(none)", Style::default().fg(theme.text)));
+    }
+    let notice_line = Line::styled(
+        state
+            .notice
+            .map_or_else(String::new, |notice| format!(" {notice}")),
+        Style::default().fg(theme.warning),
+    );
+    let selected = Paragraph::new(vec![Line::from(result_line), notice_line]);
     f.render_widget(selected, chunks[6]);
 
     // Help text
@@ -435,6 +581,20 @@ fn render_modifier_picker_component(f: &mut Frame, picker: &ModifierPicker, them
                 .add_modifier(StyleModifier::BOLD),
         ),
         Span::raw(" Toggle  "),
+        Span::styled(
+            "c/s/a/g",
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(StyleModifier::BOLD),
+        ),
+        Span::raw(" Quick toggle  "),
+        Span::styled(
+            "r",
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(StyleModifier::BOLD),
+        ),
+        Span::raw(" Other hand  "),
         Span::styled(
             "Enter",
             Style::default()
@@ -451,7 +611,7 @@ fn render_modifier_picker_component(f: &mut Frame, picker: &ModifierPicker, them
         Span::raw(" Cancel"),
     ];
     let help = Paragraph::new(vec![
-        Line::from("Columns map to QMK left/right modifier bits."),
+        Line::from("Columns map to QMK left/right modifier bits; one hand per combination."),
         Line::from(help_spans),
     ])
     .style(Style::default().fg(theme.text_muted));
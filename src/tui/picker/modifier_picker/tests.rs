@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_modifier_picker_initial_state() {
     let state = ModifierPickerState::new();
@@ -122,3 +120,66 @@ fn test_focus_navigation() {
     state.focus_up();
     assert_eq!(state.focus, 8);
 }
+
+#[test]
+fn test_abbreviation_left_and_right() {
+    let mut state = ModifierPickerState::new();
+    state.toggle_mod(QmkModifier::LCtrl as u8);
+    state.toggle_mod(QmkModifier::LShift as u8);
+    assert_eq!(state.abbreviation(), "C+S");
+
+    let mut state = ModifierPickerState::new();
+    state.toggle_mod(QmkModifier::RAlt as u8);
+    state.toggle_mod(QmkModifier::RGui as u8);
+    assert_eq!(state.abbreviation(), "RA+RG");
+    assert_eq!(state.to_mod_string(), "MOD_RALT | MOD_RGUI");
+}
+
+#[test]
+fn test_toggle_other_hand_moves_selection() {
+    let mut state = ModifierPickerState::new();
+    state.toggle_mod(QmkModifier::LCtrl as u8);
+    state.toggle_mod(QmkModifier::LShift as u8);
+
+    state.toggle_mod(QmkModifier::RAlt as u8);
+
+    assert!(state.is_right_side());
+    assert_eq!(state.to_mod_string(), "MOD_RCTL | MOD_RSFT | MOD_RALT");
+    assert!(state.notice.is_some());
+
+    state.switch_side();
+    assert_eq!(state.abbreviation(), "C+S+A");
+}
+
+#[test]
+fn test_toggle_letter_uses_active_hand() {
+    let mut state = ModifierPickerState::new();
+    state.toggle_letter('c');
+    state.toggle_letter('S');
+    assert_eq!(state.abbreviation(), "C+S");
+
+    let mut state = ModifierPickerState::new();
+    state.focus_right();
+    state.toggle_letter('g');
+    assert_eq!(state.abbreviation(), "RG");
+}
+
+#[test]
+fn test_preset_detection() {
+    let mut state = ModifierPickerState::new();
+    state.toggle_letter('c');
+    state.toggle_letter('s');
+    assert_eq!(state.preset(), None);
+    state.toggle_letter('a');
+    assert_eq!(state.preset(), Some(ModifierPreset::Meh));
+    state.toggle_letter('g');
+    assert_eq!(state.preset(), Some(ModifierPreset::Hyper));
+}
+
+#[test]
+fn test_enter_without_selection_keeps_picker_open() {
+    let mut picker = ModifierPicker::new();
+    let event = picker.handle_input(KeyEvent::from(crossterm::event::KeyCode::Enter));
+    assert!(event.is_none());
+    assert!(picker.state.notice.is_some());
+}
@@ -0,0 +1,205 @@
+//! QMK tree picker for switching the QMK checkout the layout builds against.
+//!
+//! Lists the default QMK firmware path and the named trees from the config
+//! (e.g. a personal fork or vial-qmk). Choosing one emits an event; the
+//! handler stores it in the layout metadata and rebuilds the geometry.
+
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::config::{Config, DEFAULT_QMK_TREE};
+use crate::tui::theme::Theme;
+use crate::tui::Component;
+
+/// Events emitted by the QMK tree picker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QmkTreePickerEvent {
+    /// User chose a tree (`None` is the default tree)
+    Select(Option<String>),
+    /// User closed the picker
+    Cancelled,
+}
+
+/// QMK tree picker component state
+#[derive(Debug, Clone)]
+pub struct QmkTreePicker {
+    /// Tree names and checkouts, the default tree first
+    trees: Vec<(String, Option<PathBuf>)>,
+    /// Index of the tree the layout uses now
+    current: usize,
+    /// Index of the highlighted tree
+    selected: usize,
+}
+
+impl QmkTreePicker {
+    /// Creates a picker for the trees of `config`, highlighting `current`
+    /// (the layout's `qmk_tree`).
+    #[must_use]
+    pub fn new(config: &Config, current: Option<&str>) -> Self {
+        let trees: Vec<(String, Option<PathBuf>)> = config
+            .qmk_tree_names()
+            .into_iter()
+            .map(|name| (name.to_string(), config.qmk_tree_path(Some(name)).cloned()))
+            .collect();
+        let current = current
+            .and_then(|name| trees.iter().position(|(tree, _)| tree == name))
+            .unwrap_or(0);
+        Self {
+            trees,
+            current,
+            selected: current,
+        }
+    }
+
+    /// Returns the name of the highlighted tree.
+    #[must_use]
+    pub fn selected(&self) -> Option<&str> {
+        self.trees.get(self.selected).map(|(name, _)| name.as_str())
+    }
+}
+
+impl Component for QmkTreePicker {
+    type Event = QmkTreePickerEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.trees.len().saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                let name = self.selected()?;
+                let tree = (name != DEFAULT_QMK_TREE).then(|| name.to_string());
+                return Some(QmkTreePickerEvent::Select(tree));
+            }
+            KeyCode::Esc => return Some(QmkTreePickerEvent::Cancelled),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, _area: Rect, theme: &Theme) {
+        let area = centered_rect(60, 50, frame.area());
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(theme.background)),
+            area,
+        );
+
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        let items: Vec<ListItem> = self
+            .trees
+            .iter()
+            .enumerate()
+            .map(|(index, (name, path))| {
+                let marker = if index == self.current { "● " } else { "  " };
+                let path = path
+                    .as_ref()
+                    .map_or_else(|| "not configured".to_string(), |p| p.display().to_string());
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{marker}{name}")),
+                    Span::styled(format!("  {path}"), Style::default().fg(theme.text_muted)),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" QMK Tree ")
+                    .style(Style::default().bg(theme.background)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(theme.highlight_bg)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(theme.background).fg(theme.text));
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(self.selected));
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        let help = if self.trees.len() > 1 {
+            Line::from(vec![
+                Span::styled(
+                    "↑/↓",
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" Navigate  "),
+                Span::styled(
+                    "Enter",
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" Use tree  "),
+                Span::styled(
+                    "Esc",
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" Close"),
+            ])
+        } else {
+            Line::from(Span::styled(
+                "Add more checkouts as [[paths.qmk_trees]] in config.toml",
+                Style::default().fg(theme.text_muted),
+            ))
+        };
+        frame.render_widget(
+            Paragraph::new(help)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Help ")
+                        .style(Style::default().bg(theme.background)),
+                )
+                .style(Style::default().bg(theme.background).fg(theme.text)),
+            chunks[1],
+        );
+    }
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
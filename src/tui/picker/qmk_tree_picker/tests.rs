@@ -0,0 +1,61 @@
+use super::*;
+use crate::config::QmkTree;
+use crossterm::event::KeyModifiers;
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn config() -> Config {
+    let mut config = Config::new();
+    config.paths.qmk_firmware = Some(PathBuf::from("/qmk/upstream"));
+    config.paths.qmk_trees = vec![
+        QmkTree {
+            name: "fork".to_string(),
+            path: PathBuf::from("/qmk/fork"),
+        },
+        QmkTree {
+            name: "vial".to_string(),
+            path: PathBuf::from("/qmk/vial"),
+        },
+    ];
+    config
+}
+
+#[test]
+fn test_lists_default_tree_first() {
+    let picker = QmkTreePicker::new(&config(), None);
+
+    assert_eq!(picker.selected(), Some(DEFAULT_QMK_TREE));
+    assert_eq!(
+        picker.trees[1],
+        ("fork".to_string(), Some(PathBuf::from("/qmk/fork")))
+    );
+}
+
+#[test]
+fn test_enter_selects_named_tree() {
+    let mut picker = QmkTreePicker::new(&config(), Some("fork"));
+    assert_eq!(picker.selected(), Some("fork"));
+
+    assert_eq!(picker.handle_input(key(KeyCode::Down)), None);
+    assert_eq!(
+        picker.handle_input(key(KeyCode::Enter)),
+        Some(QmkTreePickerEvent::Select(Some("vial".to_string())))
+    );
+}
+
+#[test]
+fn test_default_tree_clears_selection() {
+    let mut picker = QmkTreePicker::new(&config(), Some("missing"));
+    assert_eq!(picker.selected(), Some(DEFAULT_QMK_TREE));
+
+    assert_eq!(
+        picker.handle_input(key(KeyCode::Enter)),
+        Some(QmkTreePickerEvent::Select(None))
+    );
+    assert_eq!(
+        picker.handle_input(key(KeyCode::Esc)),
+        Some(QmkTreePickerEvent::Cancelled)
+    );
+}
@@ -4,8 +4,6 @@
 
 use super::*;
 
-use super::*;
-
 #[test]
 fn test_template_browser_state_new() {
     let state = TemplateBrowserState::new();
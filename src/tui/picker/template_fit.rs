@@ -0,0 +1,278 @@
+//! Mapping step for templates made for a different keyboard.
+//!
+//! Shown when a template has keys at positions the open keyboard lacks. Each
+//! such key is listed with its proposed destination from
+//! [`TemplateFit::plan`]; the user moves it to another free position or
+//! drops it, then applies. Positions left empty get `KC_NO`.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::models::{Layout, Position};
+use crate::services::geometry::GeometryAdjustReport;
+use crate::services::template_fit::TemplateFit;
+use crate::tui::theme::Theme;
+use crate::tui::Component;
+
+/// Events emitted by the template fit dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateFitEvent {
+    /// User confirmed the placement
+    Apply,
+    /// User cancelled loading the template
+    Cancelled,
+}
+
+/// Template fit dialog state
+#[derive(Debug, Clone)]
+pub struct TemplateFitDialog {
+    /// The template being placed
+    template: Layout,
+    /// Destination keyboard
+    keyboard: String,
+    /// Destination layout variant
+    layout_variant: String,
+    /// Visual positions of the destination keyboard
+    destination: HashSet<Position>,
+    /// Current placement of the unplaced keys
+    fit: TemplateFit,
+    /// Index of the highlighted unplaced key
+    selected: usize,
+}
+
+impl TemplateFitDialog {
+    /// Plans placing `template` on `keyboard`/`layout_variant`, whose keys sit
+    /// at `destination`.
+    #[must_use]
+    pub fn new(
+        template: Layout,
+        keyboard: impl Into<String>,
+        layout_variant: impl Into<String>,
+        destination: HashSet<Position>,
+    ) -> Self {
+        let fit = TemplateFit::plan(&template, &destination);
+        Self {
+            template,
+            keyboard: keyboard.into(),
+            layout_variant: layout_variant.into(),
+            destination,
+            fit,
+            selected: 0,
+        }
+    }
+
+    /// Returns true if some template keys need a decision from the user.
+    #[must_use]
+    pub fn needs_mapping(&self) -> bool {
+        self.fit.needs_mapping()
+    }
+
+    /// Returns the template placed on the destination keyboard.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two keys of a layer end up on the same position.
+    pub fn apply(&self) -> Result<(Layout, GeometryAdjustReport)> {
+        self.fit.apply(
+            &self.template,
+            &self.keyboard,
+            &self.layout_variant,
+            &self.destination,
+        )
+    }
+
+    /// Moves the highlighted key to the next (`forward`) or previous choice:
+    /// dropped, then each available free position in reading order.
+    fn cycle_target(&mut self, forward: bool) {
+        let Some(&from) = self.fit.unplaced.get(self.selected) else {
+            return;
+        };
+        let mut choices: Vec<Option<Position>> = vec![None];
+        choices.extend(self.fit.available(from).into_iter().map(Some));
+        let current = self.fit.target_of(from);
+        let idx = choices.iter().position(|c| *c == current).unwrap_or(0);
+        let next = if forward {
+            (idx + 1) % choices.len()
+        } else {
+            (idx + choices.len() - 1) % choices.len()
+        };
+        // Choices come from `available`, so assigning can't fail
+        let _ = self.fit.assign(from, choices[next]);
+    }
+
+    /// Base layer keycode at `position` of the template.
+    fn base_keycode(&self, position: Position) -> &str {
+        self.template
+            .layers
+            .first()
+            .and_then(|layer| layer.keys.iter().find(|key| key.position == position))
+            .map_or("KC_TRNS", |key| key.keycode.as_str())
+    }
+}
+
+impl Component for TemplateFitDialog {
+    type Event = TemplateFitEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.fit.unplaced.len().saturating_sub(1));
+            }
+            KeyCode::Right | KeyCode::Char('l') => self.cycle_target(true),
+            KeyCode::Left | KeyCode::Char('h') => self.cycle_target(false),
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(&from) = self.fit.unplaced.get(self.selected) {
+                    let _ = self.fit.assign(from, None);
+                }
+            }
+            KeyCode::Enter => return Some(TemplateFitEvent::Apply),
+            KeyCode::Esc => return Some(TemplateFitEvent::Cancelled),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, _area: Rect, theme: &Theme) {
+        let area = centered_rect(60, 60, frame.area());
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(theme.background)),
+            area,
+        );
+
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4),
+                Constraint::Min(3),
+                Constraint::Length(3),
+            ])
+            .split(area);
+
+        let intro = format!(
+            "{} key(s) of \"{}\" have no position on {} ({}). Choose where each goes; \
+             positions left empty get KC_NO.",
+            self.fit.unplaced.len(),
+            self.template.metadata.name,
+            self.keyboard,
+            self.layout_variant
+        );
+        frame.render_widget(
+            Paragraph::new(intro)
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Fit Template to Keyboard ")
+                        .style(Style::default().bg(theme.background)),
+                )
+                .style(Style::default().bg(theme.background).fg(theme.text)),
+            chunks[0],
+        );
+
+        let items: Vec<ListItem> = self
+            .fit
+            .unplaced
+            .iter()
+            .map(|&from| {
+                let target = match self.fit.target_of(from) {
+                    Some(to) => Span::raw(format!("({}, {})", to.row, to.col)),
+                    None => Span::styled("drop", Style::default().fg(theme.warning)),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("({}, {})  ", from.row, from.col)),
+                    Span::styled(
+                        format!("{:<16}", self.base_keycode(from)),
+                        Style::default().fg(theme.text_muted),
+                    ),
+                    Span::raw(" → "),
+                    target,
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Template key → keyboard position ")
+                    .style(Style::default().bg(theme.background)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(theme.highlight_bg)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(theme.background).fg(theme.text));
+
+        let mut list_state = ListState::default();
+        if !self.fit.unplaced.is_empty() {
+            list_state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, chunks[1], &mut list_state);
+
+        let key_style = Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD);
+        let help = Line::from(vec![
+            Span::styled("↑/↓", key_style),
+            Span::raw(" Select  "),
+            Span::styled("←/→", key_style),
+            Span::raw(" Move  "),
+            Span::styled("d", key_style),
+            Span::raw(" Drop  "),
+            Span::styled("Enter", key_style),
+            Span::raw(" Apply  "),
+            Span::styled("Esc", key_style),
+            Span::raw(" Cancel"),
+        ]);
+        frame.render_widget(
+            Paragraph::new(help)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Help ")
+                        .style(Style::default().bg(theme.background)),
+                )
+                .style(Style::default().bg(theme.background).fg(theme.text)),
+            chunks[2],
+        );
+    }
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,63 @@
+use super::*;
+use crate::models::{KeyDefinition, Layer, RgbColor};
+use crossterm::event::KeyModifiers;
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+/// 3x2 template on a 2x3 keyboard: (2, 0) and (2, 1) don't fit,
+/// (0, 2) and (1, 2) are free.
+fn dialog() -> TemplateFitDialog {
+    let mut template = Layout::new("Template").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 80)).unwrap();
+    for row in 0..3 {
+        for col in 0..2 {
+            layer.add_key(KeyDefinition::new(Position::new(row, col), "KC_A"));
+        }
+    }
+    template.add_layer(layer).unwrap();
+    let destination = (0..2)
+        .flat_map(|row| (0..3).map(move |col| Position::new(row, col)))
+        .collect();
+    TemplateFitDialog::new(template, "test_keyboard", "LAYOUT_test", destination)
+}
+
+#[test]
+fn test_cycle_moves_key_through_free_positions_and_drop() {
+    let mut dialog = dialog();
+    let from = Position::new(2, 0);
+    assert!(dialog.needs_mapping());
+    assert_eq!(dialog.fit.target_of(from), Some(Position::new(0, 2)));
+
+    // (1, 2) is taken by the other key, so the next choice wraps to drop
+    assert_eq!(dialog.handle_input(key(KeyCode::Right)), None);
+    assert_eq!(dialog.fit.target_of(from), None);
+    dialog.handle_input(key(KeyCode::Left));
+    assert_eq!(dialog.fit.target_of(from), Some(Position::new(0, 2)));
+
+    // Dropping the second key frees (1, 2) for the first
+    dialog.handle_input(key(KeyCode::Down));
+    dialog.handle_input(key(KeyCode::Char('d')));
+    assert_eq!(dialog.fit.target_of(Position::new(2, 1)), None);
+    dialog.handle_input(key(KeyCode::Up));
+    dialog.handle_input(key(KeyCode::Right));
+    assert_eq!(dialog.fit.target_of(from), Some(Position::new(1, 2)));
+}
+
+#[test]
+fn test_enter_applies_and_esc_cancels() {
+    let mut dialog = dialog();
+    assert_eq!(
+        dialog.handle_input(key(KeyCode::Enter)),
+        Some(TemplateFitEvent::Apply)
+    );
+    assert_eq!(
+        dialog.handle_input(key(KeyCode::Esc)),
+        Some(TemplateFitEvent::Cancelled)
+    );
+
+    let (layout, _) = dialog.apply().unwrap();
+    assert_eq!(layout.layers[0].keys.len(), 6);
+    assert_eq!(layout.metadata.keyboard.as_deref(), Some("test_keyboard"));
+}
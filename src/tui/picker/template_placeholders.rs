@@ -0,0 +1,280 @@
+//! Prompt for template placeholders.
+//!
+//! Shown when a template declares or uses `${NAME}` placeholders. Each
+//! placeholder is listed with its prompt and current value: free-form values
+//! are typed, values with fixed choices are cycled. Applying fills in the
+//! template with [`Layout::fill_placeholders`] before it is loaded.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::models::{Layout, TemplatePlaceholder};
+use crate::tui::theme::Theme;
+use crate::tui::Component;
+
+/// Events emitted by the template placeholder dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplatePlaceholderEvent {
+    /// User confirmed the values
+    Apply,
+    /// User cancelled loading the template
+    Cancelled,
+}
+
+/// Template placeholder dialog state
+#[derive(Debug, Clone)]
+pub struct TemplatePlaceholderDialog {
+    /// The template being applied
+    template: Layout,
+    /// Placeholders to ask for
+    placeholders: Vec<TemplatePlaceholder>,
+    /// Entered value of each placeholder
+    values: Vec<String>,
+    /// Index of the highlighted placeholder
+    selected: usize,
+}
+
+impl TemplatePlaceholderDialog {
+    /// Asks for the placeholders of `template`, starting from their defaults
+    /// (or first choice).
+    #[must_use]
+    pub fn new(template: Layout) -> Self {
+        let placeholders = template.required_placeholders();
+        let values = placeholders
+            .iter()
+            .map(|p| {
+                p.default
+                    .clone()
+                    .or_else(|| p.choices.first().cloned())
+                    .unwrap_or_default()
+            })
+            .collect();
+        Self {
+            template,
+            placeholders,
+            values,
+            selected: 0,
+        }
+    }
+
+    /// Returns the template with the entered values filled in.
+    ///
+    /// Empty values fall back to the placeholder's default.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value is missing or not allowed.
+    pub fn fill(&self) -> Result<Layout> {
+        let values: HashMap<String, String> = self
+            .placeholders
+            .iter()
+            .zip(&self.values)
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(p, value)| (p.name.clone(), value.clone()))
+            .collect();
+        self.template.fill_placeholders(&values)
+    }
+
+    /// Moves the highlighted value to the next (`forward`) or previous choice.
+    fn cycle_choice(&mut self, forward: bool) {
+        let Some(placeholder) = self.placeholders.get(self.selected) else {
+            return;
+        };
+        let choices = &placeholder.choices;
+        if choices.is_empty() {
+            return;
+        }
+        let value = &mut self.values[self.selected];
+        let next = match choices.iter().position(|c| c == value) {
+            Some(idx) if forward => (idx + 1) % choices.len(),
+            Some(idx) => (idx + choices.len() - 1) % choices.len(),
+            None => 0,
+        };
+        value.clone_from(&choices[next]);
+    }
+
+    /// Returns true if the highlighted placeholder takes typed values.
+    fn selected_is_free_form(&self) -> bool {
+        self.placeholders
+            .get(self.selected)
+            .is_some_and(|p| p.choices.is_empty())
+    }
+
+    fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(self.placeholders.len().saturating_sub(1));
+    }
+}
+
+impl Component for TemplatePlaceholderDialog {
+    type Event = TemplatePlaceholderEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        match key.code {
+            KeyCode::Up | KeyCode::BackTab => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Tab => self.select_next(),
+            KeyCode::Right => self.cycle_choice(true),
+            KeyCode::Left => self.cycle_choice(false),
+            KeyCode::Char(c) if self.selected_is_free_form() => {
+                self.values[self.selected].push(c);
+            }
+            KeyCode::Backspace if self.selected_is_free_form() => {
+                self.values[self.selected].pop();
+            }
+            KeyCode::Enter => return Some(TemplatePlaceholderEvent::Apply),
+            KeyCode::Esc => return Some(TemplatePlaceholderEvent::Cancelled),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, _area: Rect, theme: &Theme) {
+        let area = centered_rect(60, 60, frame.area());
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(theme.background)),
+            area,
+        );
+
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4),
+                Constraint::Min(3),
+                Constraint::Length(3),
+            ])
+            .split(area);
+
+        let intro = format!(
+            "\"{}\" asks for {} value(s). Empty values use the default.",
+            self.template.metadata.name,
+            self.placeholders.len()
+        );
+        frame.render_widget(
+            Paragraph::new(intro)
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Template Placeholders ")
+                        .style(Style::default().bg(theme.background)),
+                )
+                .style(Style::default().bg(theme.background).fg(theme.text)),
+            chunks[0],
+        );
+
+        let items: Vec<ListItem> = self
+            .placeholders
+            .iter()
+            .zip(&self.values)
+            .map(|(placeholder, value)| {
+                let value = if value.is_empty() {
+                    Span::styled(
+                        placeholder.default.as_deref().unwrap_or("(required)"),
+                        Style::default().fg(theme.text_muted),
+                    )
+                } else if placeholder.choices.is_empty() {
+                    Span::raw(value.clone())
+                } else {
+                    Span::raw(format!("‹ {value} ›"))
+                };
+                let mut lines = vec![Line::from(vec![
+                    Span::styled(
+                        format!("{:<20}", format!("${{{}}}", placeholder.name)),
+                        Style::default().fg(theme.accent),
+                    ),
+                    value,
+                ])];
+                if !placeholder.description.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}", placeholder.description),
+                        Style::default().fg(theme.text_muted),
+                    )));
+                }
+                ListItem::new(lines)
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Placeholder → value ")
+                    .style(Style::default().bg(theme.background)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(theme.highlight_bg)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(theme.background).fg(theme.text));
+
+        let mut list_state = ListState::default();
+        if !self.placeholders.is_empty() {
+            list_state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, chunks[1], &mut list_state);
+
+        let key_style = Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD);
+        let help = Line::from(vec![
+            Span::styled("↑/↓", key_style),
+            Span::raw(" Select  "),
+            Span::styled("Type", key_style),
+            Span::raw(" Edit  "),
+            Span::styled("←/→", key_style),
+            Span::raw(" Choice  "),
+            Span::styled("Enter", key_style),
+            Span::raw(" Apply  "),
+            Span::styled("Esc", key_style),
+            Span::raw(" Cancel"),
+        ]);
+        frame.render_widget(
+            Paragraph::new(help)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Help ")
+                        .style(Style::default().bg(theme.background)),
+                )
+                .style(Style::default().bg(theme.background).fg(theme.text)),
+            chunks[2],
+        );
+    }
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
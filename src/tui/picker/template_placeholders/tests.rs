@@ -0,0 +1,76 @@
+use super::*;
+use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+use crossterm::event::KeyModifiers;
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+/// Template using `${OS_MOD}` (choice of LGUI/LCTL) and `${NAV_NAME}`
+/// (free-form, no default).
+fn dialog() -> TemplatePlaceholderDialog {
+    let mut template = Layout::new("Template").unwrap();
+    let mut base = Layer::new(0, "Base", RgbColor::new(0, 0, 80)).unwrap();
+    base.add_key(KeyDefinition::new(Position::new(0, 0), "KC_${OS_MOD}"));
+    template.add_layer(base).unwrap();
+    let mut nav = Layer::new(1, "${NAV_NAME}", RgbColor::new(0, 80, 0)).unwrap();
+    nav.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
+    template.add_layer(nav).unwrap();
+
+    let mut os_mod = TemplatePlaceholder::new("OS_MOD");
+    os_mod.choices = vec!["LGUI".to_string(), "LCTL".to_string()];
+    template.metadata.placeholders.push(os_mod);
+    TemplatePlaceholderDialog::new(template)
+}
+
+#[test]
+fn test_choices_cycle_and_free_form_values_are_typed() {
+    let mut dialog = dialog();
+    assert_eq!(dialog.values, vec!["LGUI".to_string(), String::new()]);
+
+    // Typing does nothing on a placeholder with choices
+    dialog.handle_input(key(KeyCode::Char('x')));
+    assert_eq!(dialog.values[0], "LGUI");
+    dialog.handle_input(key(KeyCode::Right));
+    assert_eq!(dialog.values[0], "LCTL");
+    dialog.handle_input(key(KeyCode::Right));
+    assert_eq!(dialog.values[0], "LGUI");
+    dialog.handle_input(key(KeyCode::Left));
+    assert_eq!(dialog.values[0], "LCTL");
+
+    dialog.handle_input(key(KeyCode::Down));
+    for c in "Navx".chars() {
+        dialog.handle_input(key(KeyCode::Char(c)));
+    }
+    dialog.handle_input(key(KeyCode::Backspace));
+    assert_eq!(dialog.values[1], "Nav");
+}
+
+#[test]
+fn test_fill_requires_values_and_substitutes_them() {
+    let mut dialog = dialog();
+    assert!(dialog.fill().is_err(), "NAV_NAME has no value or default");
+
+    dialog.handle_input(key(KeyCode::Tab));
+    for c in "Nav".chars() {
+        dialog.handle_input(key(KeyCode::Char(c)));
+    }
+    assert_eq!(
+        dialog.handle_input(key(KeyCode::Enter)),
+        Some(TemplatePlaceholderEvent::Apply)
+    );
+
+    let layout = dialog.fill().unwrap();
+    assert_eq!(layout.layers[0].keys[0].keycode, "KC_LGUI");
+    assert_eq!(layout.layers[1].name, "Nav");
+    assert!(layout.metadata.placeholders.is_empty());
+}
+
+#[test]
+fn test_escape_cancels() {
+    let mut dialog = dialog();
+    assert_eq!(
+        dialog.handle_input(key(KeyCode::Esc)),
+        Some(TemplatePlaceholderEvent::Cancelled)
+    );
+}
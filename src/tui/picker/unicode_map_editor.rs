@@ -0,0 +1,424 @@
+//! Unicode Map Editor Component
+//!
+//! Lists the layout's named Unicode glyphs and edits them in a two-field form
+//! (name and character, e.g. `snek` and `🐍` or `U+1F40D`). Enter applies the
+//! highlighted glyph as `UM(name)`, or as `UC(0x...)` when the editor was
+//! opened for a `UC()` key. Saving and deleting are reported as events; the
+//! handler applies them to the layout.
+
+use crate::models::layout::unicode::{format_codepoint, parse_codepoint};
+use crate::models::{Layout, UnicodeGlyph};
+use crate::tui::theme::Theme;
+use crate::tui::Component;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// Which keycode Enter applies to the selected key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnicodeKeyKind {
+    /// `UM(name)` referencing the glyph by name
+    Map,
+    /// `UC(0x...)` with the glyph's code point
+    Codepoint,
+}
+
+/// Events emitted by the Unicode map editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnicodeMapEditorEvent {
+    /// User selected a glyph; carries the keycode to apply to the current key
+    Selected(String),
+    /// User saved a new or edited glyph
+    Saved {
+        /// Name of the glyph being replaced (None when creating)
+        original: Option<String>,
+        /// The validated glyph
+        glyph: UnicodeGlyph,
+    },
+    /// User wants to delete a glyph
+    Delete(String),
+    /// User closed the editor
+    Cancelled,
+}
+
+/// Which form field has focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphField {
+    /// Glyph name
+    Name,
+    /// Character or code point
+    Character,
+}
+
+/// Draft being edited in the form
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GlyphDraft {
+    /// Name of the glyph being edited (None when creating)
+    original: Option<String>,
+    name: String,
+    character: String,
+    focus: GlyphField,
+    error: Option<String>,
+}
+
+/// Unicode Map Editor component state
+#[derive(Debug, Clone)]
+pub struct UnicodeMapEditor {
+    /// Index of the highlighted glyph
+    selected: usize,
+    /// Glyphs from the layout (read-only view)
+    glyphs: Vec<UnicodeGlyph>,
+    /// Keycode style applied on Enter
+    key_kind: UnicodeKeyKind,
+    /// Form state while creating or editing
+    draft: Option<GlyphDraft>,
+}
+
+impl UnicodeMapEditor {
+    /// Creates a new Unicode map editor with glyphs from the layout
+    #[must_use]
+    pub fn new(layout: &Layout, key_kind: UnicodeKeyKind) -> Self {
+        Self {
+            selected: 0,
+            glyphs: layout.unicode_map.clone(),
+            key_kind,
+            draft: None,
+        }
+    }
+
+    /// Returns the keycode style Enter applies
+    #[must_use]
+    pub const fn key_kind(&self) -> UnicodeKeyKind {
+        self.key_kind
+    }
+
+    /// Returns the keycode Enter applies for a glyph
+    fn keycode_for(&self, glyph: &UnicodeGlyph) -> String {
+        match self.key_kind {
+            UnicodeKeyKind::Map => glyph.keycode(),
+            UnicodeKeyKind::Codepoint => format!("UC(0x{:04X})", glyph.codepoint),
+        }
+    }
+
+    /// Get the currently selected glyph name
+    fn selected_name(&self) -> Option<String> {
+        self.glyphs.get(self.selected).map(|g| g.name.clone())
+    }
+
+    fn open_form(&mut self, existing: Option<&UnicodeGlyph>) {
+        self.draft = Some(GlyphDraft {
+            original: existing.map(|g| g.name.clone()),
+            name: existing.map(|g| g.name.clone()).unwrap_or_default(),
+            character: existing
+                .and_then(UnicodeGlyph::character)
+                .map(String::from)
+                .unwrap_or_default(),
+            focus: GlyphField::Name,
+            error: None,
+        });
+    }
+
+    /// Parse and validate the draft, returning the glyph or an error message
+    fn build_draft(&self, draft: &GlyphDraft) -> Result<UnicodeGlyph, String> {
+        let name = draft.name.trim();
+        let duplicate = self.glyphs.iter().any(|g| {
+            g.name.eq_ignore_ascii_case(name) && draft.original.as_deref() != Some(g.name.as_str())
+        });
+        if duplicate {
+            return Err(format!("Glyph '{name}' already exists"));
+        }
+
+        let codepoint = parse_codepoint(&draft.character)
+            .ok_or_else(|| "Enter one character, U+XXXX or 0xXXXX".to_string())?;
+        let glyph = UnicodeGlyph::new(name, codepoint);
+        glyph.validate().map_err(|e| e.to_string())?;
+        Ok(glyph)
+    }
+
+    fn handle_form_input(&mut self, key: KeyEvent) -> Option<UnicodeMapEditorEvent> {
+        let mut draft = self.draft.take()?;
+        match key.code {
+            KeyCode::Esc => return None,
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                draft.focus = match draft.focus {
+                    GlyphField::Name => GlyphField::Character,
+                    GlyphField::Character => GlyphField::Name,
+                };
+            }
+            KeyCode::Enter => match self.build_draft(&draft) {
+                Ok(glyph) => {
+                    return Some(UnicodeMapEditorEvent::Saved {
+                        original: draft.original,
+                        glyph,
+                    });
+                }
+                Err(message) => draft.error = Some(message),
+            },
+            KeyCode::Backspace => {
+                match draft.focus {
+                    GlyphField::Name => draft.name.pop(),
+                    GlyphField::Character => draft.character.pop(),
+                };
+                draft.error = None;
+            }
+            KeyCode::Char(c) => {
+                match draft.focus {
+                    GlyphField::Name => draft.name.push(c),
+                    GlyphField::Character => draft.character.push(c),
+                }
+                draft.error = None;
+            }
+            _ => {}
+        }
+        self.draft = Some(draft);
+        None
+    }
+}
+
+impl Component for UnicodeMapEditor {
+    type Event = UnicodeMapEditorEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        if self.draft.is_some() {
+            return self.handle_form_input(key);
+        }
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.glyphs.len().saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                return self
+                    .glyphs
+                    .get(self.selected)
+                    .map(|glyph| UnicodeMapEditorEvent::Selected(self.keycode_for(glyph)));
+            }
+            KeyCode::Char('n') => self.open_form(None),
+            KeyCode::Char('e') => {
+                let existing = self.glyphs.get(self.selected).cloned();
+                if existing.is_some() {
+                    self.open_form(existing.as_ref());
+                }
+            }
+            KeyCode::Char('D') => {
+                return self.selected_name().map(UnicodeMapEditorEvent::Delete);
+            }
+            KeyCode::Esc => return Some(UnicodeMapEditorEvent::Cancelled),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, _area: Rect, theme: &Theme) {
+        let area = centered_rect(70, 60, frame.area());
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(theme.background)),
+            area,
+        );
+
+        match &self.draft {
+            Some(draft) => render_form(frame, area, draft, theme),
+            None => self.render_list(frame, area, theme),
+        }
+    }
+}
+
+impl UnicodeMapEditor {
+    fn render_list(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = RatatuiLayout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(3)])
+            .split(area);
+
+        let items: Vec<ListItem> = if self.glyphs.is_empty() {
+            vec![ListItem::new(
+                Line::from("No glyphs defined. Press 'n' to create one.")
+                    .style(Style::default().fg(theme.text_muted)),
+            )]
+        } else {
+            self.glyphs
+                .iter()
+                .map(|g| {
+                    let character = g.character().map(String::from).unwrap_or_default();
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{}: ", g.name), Style::default().fg(theme.accent)),
+                        Span::raw(format!("{character}  ")),
+                        Span::styled(
+                            format_codepoint(g.codepoint),
+                            Style::default().fg(theme.text_muted),
+                        ),
+                    ]))
+                })
+                .collect()
+        };
+
+        let title = match self.key_kind {
+            UnicodeKeyKind::Map => " Unicode Map ",
+            UnicodeKeyKind::Codepoint => " Unicode Map (applies UC) ",
+        };
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .style(Style::default().bg(theme.background)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(theme.highlight_bg)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(theme.background).fg(theme.text));
+
+        let mut list_state = ListState::default();
+        if !self.glyphs.is_empty() {
+            list_state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        let help = help_line(
+            &[
+                ("↑/↓", "Navigate"),
+                ("Enter", "Apply"),
+                ("n", "New"),
+                ("e", "Edit"),
+                ("D", "Delete"),
+                ("Esc", "Close"),
+            ],
+            theme,
+        );
+        frame.render_widget(help, chunks[1]);
+    }
+}
+
+fn render_form(frame: &mut Frame, area: Rect, draft: &GlyphDraft, theme: &Theme) {
+    let chunks = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Name
+            Constraint::Length(3), // Character
+            Constraint::Min(3),    // Syntax hint / error
+            Constraint::Length(3), // Help
+        ])
+        .split(area);
+
+    for (chunk, field, title, value) in [
+        (chunks[0], GlyphField::Name, " Name ", &draft.name),
+        (
+            chunks[1],
+            GlyphField::Character,
+            " Character ",
+            &draft.character,
+        ),
+    ] {
+        let focused = draft.focus == field;
+        let mut spans = vec![Span::raw(value.as_str())];
+        if focused {
+            spans.push(Span::styled("█", Style::default().fg(theme.primary)));
+        }
+        let border = if focused {
+            theme.primary
+        } else {
+            theme.text_muted
+        };
+        let input = Paragraph::new(Line::from(spans))
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(border)),
+            )
+            .style(Style::default().fg(theme.text));
+        frame.render_widget(input, chunk);
+    }
+
+    let hint = draft.error.as_ref().map_or_else(
+        || {
+            Line::from(Span::styled(
+                "Type or paste the character, or enter its code point as U+1F40D or 0x1F40D.",
+                Style::default().fg(theme.text_muted),
+            ))
+        },
+        |error| {
+            Line::from(Span::styled(
+                error.as_str(),
+                Style::default().fg(theme.error),
+            ))
+        },
+    );
+    let title = if draft.original.is_some() {
+        " Edit Glyph "
+    } else {
+        " New Glyph "
+    };
+    frame.render_widget(
+        Paragraph::new(hint).block(Block::default().title(title).borders(Borders::ALL)),
+        chunks[2],
+    );
+
+    let help = help_line(
+        &[("Tab", "Switch field"), ("Enter", "Save"), ("Esc", "Back")],
+        theme,
+    );
+    frame.render_widget(help, chunks[3]);
+}
+
+fn help_line<'a>(entries: &[(&'a str, &'a str)], theme: &Theme) -> Paragraph<'a> {
+    let spans: Vec<Span> = entries
+        .iter()
+        .flat_map(|(key, label)| {
+            [
+                Span::styled(
+                    *key,
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" {label}  ")),
+            ]
+        })
+        .collect();
+
+    Paragraph::new(Line::from(spans))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Help ")
+                .style(Style::default().bg(theme.background)),
+        )
+        .style(Style::default().bg(theme.background).fg(theme.text))
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,122 @@
+//! Tests for unicode_map_editor.
+
+use super::*;
+use crossterm::event::KeyModifiers;
+
+fn create_test_layout() -> Layout {
+    let mut layout = Layout::new("Test").unwrap();
+    layout.unicode_map = vec![
+        UnicodeGlyph::new("snek", 0x1F40D),
+        UnicodeGlyph::new("eacute", 0xE9),
+    ];
+    layout
+}
+
+fn press(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn type_text(editor: &mut UnicodeMapEditor, text: &str) {
+    for c in text.chars() {
+        assert_eq!(editor.handle_input(press(KeyCode::Char(c))), None);
+    }
+}
+
+#[test]
+fn test_enter_applies_um_or_uc_keycode() {
+    let mut editor = UnicodeMapEditor::new(&create_test_layout(), UnicodeKeyKind::Map);
+    editor.handle_input(press(KeyCode::Down));
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Enter)),
+        Some(UnicodeMapEditorEvent::Selected("UM(eacute)".to_string()))
+    );
+
+    let mut editor = UnicodeMapEditor::new(&create_test_layout(), UnicodeKeyKind::Codepoint);
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Enter)),
+        Some(UnicodeMapEditorEvent::Selected("UC(0x1F40D)".to_string()))
+    );
+}
+
+#[test]
+fn test_delete_and_cancel() {
+    let mut editor = UnicodeMapEditor::new(&create_test_layout(), UnicodeKeyKind::Map);
+
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Char('D'))),
+        Some(UnicodeMapEditorEvent::Delete("snek".to_string()))
+    );
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Esc)),
+        Some(UnicodeMapEditorEvent::Cancelled)
+    );
+}
+
+#[test]
+fn test_create_glyph_from_character_or_code_point() {
+    let mut editor = UnicodeMapEditor::new(&create_test_layout(), UnicodeKeyKind::Map);
+
+    editor.handle_input(press(KeyCode::Char('n')));
+    type_text(&mut editor, "lambda");
+    editor.handle_input(press(KeyCode::Tab));
+    type_text(&mut editor, "λ");
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Enter)),
+        Some(UnicodeMapEditorEvent::Saved {
+            original: None,
+            glyph: UnicodeGlyph::new("lambda", 0x3BB),
+        })
+    );
+
+    editor.handle_input(press(KeyCode::Char('n')));
+    type_text(&mut editor, "heart");
+    editor.handle_input(press(KeyCode::Tab));
+    type_text(&mut editor, "U+2764");
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Enter)),
+        Some(UnicodeMapEditorEvent::Saved {
+            original: None,
+            glyph: UnicodeGlyph::new("heart", 0x2764),
+        })
+    );
+}
+
+#[test]
+fn test_edit_prefills_form_and_keeps_original_name() {
+    let mut editor = UnicodeMapEditor::new(&create_test_layout(), UnicodeKeyKind::Map);
+
+    editor.handle_input(press(KeyCode::Char('e')));
+    type_text(&mut editor, "_2");
+
+    assert_eq!(
+        editor.handle_input(press(KeyCode::Enter)),
+        Some(UnicodeMapEditorEvent::Saved {
+            original: Some("snek".to_string()),
+            glyph: UnicodeGlyph::new("snek_2", 0x1F40D),
+        })
+    );
+}
+
+#[test]
+fn test_invalid_form_shows_error_instead_of_saving() {
+    let mut editor = UnicodeMapEditor::new(&create_test_layout(), UnicodeKeyKind::Map);
+
+    // Names differing only in case collide as C enum members
+    editor.handle_input(press(KeyCode::Char('n')));
+    type_text(&mut editor, "SNEK");
+    editor.handle_input(press(KeyCode::Tab));
+    type_text(&mut editor, "x");
+    assert_eq!(editor.handle_input(press(KeyCode::Enter)), None);
+    assert!(editor.draft.as_ref().unwrap().error.is_some());
+
+    // More than one character is rejected
+    editor.handle_input(press(KeyCode::Tab));
+    type_text(&mut editor, "_new");
+    editor.handle_input(press(KeyCode::Tab));
+    type_text(&mut editor, "yz");
+    assert_eq!(editor.handle_input(press(KeyCode::Enter)), None);
+    assert_eq!(
+        editor.draft.as_ref().unwrap().error.as_deref(),
+        Some("Enter one character, U+XXXX or 0xXXXX")
+    );
+}
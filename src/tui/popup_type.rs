@@ -68,6 +68,8 @@ pub enum PopupType {
     MetadataEditor,
     /// Unsaved changes confirmation popup
     UnsavedChangesPrompt,
+    /// Review of keys added/dropped to fit the geometry, shown before saving
+    GeometryAdjustReview,
     /// Layout picker popup
     LayoutPicker,
     /// Setup wizard popup
@@ -84,6 +86,32 @@ pub enum PopupType {
     TapDanceEditor,
     /// Tap dance form dialog (create/edit)
     TapDanceForm,
+    /// Search of keys across all layers
+    KeySearch,
+    /// Target layer for copying a block of keys, with conflict preview
+    CopyToLayer,
+    /// Macro editor popup (list, create, edit)
+    MacroEditor,
+    /// Unicode map editor popup (named glyphs for UM/UC keys)
+    UnicodeMapEditor,
+    /// Custom keycode editor popup (C snippet keycodes)
+    CustomKeycodeEditor,
+    /// Keycode combo editor popup (list, create, edit)
+    ComboEditor,
+    /// Rotary encoder editor popup (per-layer rotation keycodes)
+    EncoderEditor,
+    /// Picker for restoring the layout from an automatic backup
+    BackupPicker,
+    /// Picker for the QMK checkout the layout builds against
+    QmkTreePicker,
+    /// Placement of template keys the open keyboard has no position for
+    TemplateFit,
+    /// Values for the placeholders of a template being applied
+    TemplatePlaceholders,
+    /// Layout statistics and typing heatmap
+    LayoutStats,
+    /// Layer keys per layer, with unreachable and inescapable layers
+    LayerGraph,
 }
 
 impl PopupType {
@@ -97,20 +125,33 @@ impl PopupType {
             | Self::LayerPicker
             | Self::LayoutPicker
             | Self::TapKeycodePicker
-            | Self::ModifierPicker => PopupVisualKind::Picker,
+            | Self::ModifierPicker
+            | Self::KeySearch
+            | Self::CopyToLayer
+            | Self::BackupPicker
+            | Self::QmkTreePicker => PopupVisualKind::Picker,
             Self::CategoryManager
             | Self::LayerManager
             | Self::TemplateBrowser
+            | Self::TemplateFit
+            | Self::TemplatePlaceholders
             | Self::MetadataEditor
             | Self::KeyEditor
             | Self::TapDanceEditor
             | Self::TapDanceForm
+            | Self::MacroEditor
+            | Self::UnicodeMapEditor
+            | Self::CustomKeycodeEditor
+            | Self::ComboEditor
+            | Self::EncoderEditor
             | Self::TemplateSaveDialog
             | Self::ExportFilenameDialog => PopupVisualKind::Editor,
             Self::SettingsManager => PopupVisualKind::Settings,
             Self::SetupWizard => PopupVisualKind::Wizard,
-            Self::BuildLog | Self::HelpOverlay => PopupVisualKind::Feedback,
-            Self::UnsavedChangesPrompt => PopupVisualKind::Confirm,
+            Self::BuildLog | Self::HelpOverlay | Self::LayoutStats | Self::LayerGraph => {
+                PopupVisualKind::Feedback
+            }
+            Self::UnsavedChangesPrompt | Self::GeometryAdjustReview => PopupVisualKind::Confirm,
         }
     }
 }
@@ -0,0 +1,106 @@
+//! Review popup for keys added or dropped while fitting layers to the geometry.
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::services::geometry::GeometryAdjustReport;
+use crate::tui::app_state::AppState;
+use crate::tui::popup_type::{popup_border_style, popup_title, PopupType};
+use crate::tui::theme::Theme;
+
+use super::centered_rect;
+
+/// Added positions listed on one line of the report.
+const POSITIONS_PER_LINE: usize = 6;
+
+/// Render the geometry adjustment review shown before saving
+pub fn render_geometry_review(f: &mut Frame, state: &AppState) {
+    let Some(report) = &state.geometry_adjust_report else {
+        return;
+    };
+    let theme = &state.theme;
+    let area = centered_rect(70, 70, f.area());
+
+    f.render_widget(Clear, area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, area);
+
+    let popup = Paragraph::new(report_lines(report, theme))
+        .wrap(Wrap { trim: false })
+        .scroll((
+            u16::try_from(state.geometry_review_scroll).unwrap_or(u16::MAX),
+            0,
+        ))
+        .block(
+            Block::default()
+                .title(popup_title(
+                    &PopupType::GeometryAdjustReview,
+                    "Geometry adjustments",
+                ))
+                .borders(Borders::ALL)
+                .border_style(popup_border_style(&PopupType::GeometryAdjustReview, theme))
+                .style(Style::default().fg(theme.text)),
+        );
+
+    f.render_widget(popup, area);
+}
+
+/// Number of lines in the review, for limiting how far it scrolls.
+pub fn review_line_count(report: &GeometryAdjustReport, theme: &Theme) -> usize {
+    report_lines(report, theme).len()
+}
+
+/// Build the report text: summary, per-layer changes, and key hints.
+fn report_lines(report: &GeometryAdjustReport, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            report.summary(),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from("  [Enter] Accept adjustments and save"),
+        Line::from("  [Esc] Revert adjustments and cancel save"),
+        Line::from("  [↑/↓] Scroll"),
+    ];
+
+    for layer in &report.layers {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("Layer {}: {}", layer.layer_index, layer.layer_name),
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        )));
+
+        // A few positions per line, so the line count matches what is drawn
+        for chunk in layer.added.chunks(POSITIONS_PER_LINE) {
+            let positions: Vec<String> = chunk
+                .iter()
+                .map(|pos| format!("({}, {})", pos.row, pos.col))
+                .collect();
+            lines.push(Line::from(vec![
+                Span::styled("  + KC_NO at ", Style::default().fg(theme.success)),
+                Span::raw(positions.join(" ")),
+            ]));
+        }
+
+        for key in &layer.removed {
+            lines.push(Line::from(vec![
+                Span::styled("  - dropped ", Style::default().fg(theme.error)),
+                Span::raw(format!(
+                    "{} at ({}, {})",
+                    key.keycode, key.position.row, key.position.col
+                )),
+            ]));
+        }
+    }
+
+    lines
+}
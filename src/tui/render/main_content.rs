@@ -1,13 +1,48 @@
 //! Main content area rendering.
 //!
-//! Renders the keyboard widget in the central content area.
+//! Renders the keyboard widget in the central content area, with the layer
+//! minimap strip underneath when enabled.
 
 use ratatui::{layout::Rect, Frame};
 
 use crate::tui::app_state::AppState;
+use crate::tui::editor::minimap::LayerMinimap;
 use crate::tui::keyboard::KeyboardWidget;
 
-/// Render main content (keyboard widget)
+/// Minimum height left for the keyboard widget before the minimap is hidden.
+const MIN_KEYBOARD_HEIGHT: u16 = 10;
+
+/// Splits the main content area into the keyboard area and the minimap area.
+///
+/// The minimap is only shown when enabled, the layout has more than one
+/// layer, and there is room left for the keyboard.
+pub(super) fn split_main_content(area: Rect, state: &AppState) -> (Rect, Option<Rect>) {
+    if !state.config.ui.show_layer_minimap || state.layout.layers.len() < 2 {
+        return (area, None);
+    }
+
+    let minimap_height = LayerMinimap::height(state);
+    if area.height < minimap_height + MIN_KEYBOARD_HEIGHT {
+        return (area, None);
+    }
+
+    let keyboard_area = Rect {
+        height: area.height - minimap_height,
+        ..area
+    };
+    let minimap_area = Rect {
+        y: area.y + keyboard_area.height,
+        height: minimap_height,
+        ..area
+    };
+    (keyboard_area, Some(minimap_area))
+}
+
+/// Render main content (keyboard widget and layer minimap)
 pub(super) fn render_main_content(f: &mut Frame, area: Rect, state: &AppState) {
-    KeyboardWidget::render(f, area, state);
+    let (keyboard_area, minimap_area) = split_main_content(area, state);
+    KeyboardWidget::render(f, keyboard_area, state);
+    if let Some(minimap_area) = minimap_area {
+        LayerMinimap::render(f, minimap_area, state);
+    }
 }
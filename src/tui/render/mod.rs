@@ -2,6 +2,7 @@
 //!
 //! Orchestrates the title bar, main content, status bar, popups, and error overlay.
 
+mod geometry_review;
 mod main_content;
 mod title_bar;
 
@@ -22,7 +23,9 @@ use crate::tui::popup_type::{popup_border_style, popup_title, PopupType};
 use crate::tui::settings_manager;
 use crate::tui::status_bar::StatusBar;
 use crate::tui::theme::Theme;
-use main_content::render_main_content;
+use geometry_review::render_geometry_review;
+pub use geometry_review::review_line_count;
+use main_content::{render_main_content, split_main_content};
 use title_bar::render_title_bar;
 
 /// Render the UI from current state
@@ -32,14 +35,7 @@ pub fn render(f: &mut Frame, state: &AppState) {
     let full_bg = Block::default().style(Style::default().bg(state.theme.background));
     f.render_widget(full_bg, f.area());
 
-    let chunks = RatatuiLayout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(4), // Title bar (border + 2 content lines)
-            Constraint::Min(10),   // Main content
-            Constraint::Length(6), // Status bar (increased for description + clipboard + build + help)
-        ])
-        .split(f.area());
+    let chunks = screen_chunks(f.area());
 
     // Title bar with dirty indicator
     render_title_bar(f, chunks[0], state);
@@ -61,6 +57,28 @@ pub fn render(f: &mut Frame, state: &AppState) {
     }
 }
 
+/// Split the screen into title bar, main content, and status bar areas
+fn screen_chunks(area: Rect) -> std::rc::Rc<[Rect]> {
+    RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4), // Title bar (border + 2 content lines)
+            Constraint::Min(10),   // Main content
+            Constraint::Length(6), // Status bar (increased for description + clipboard + build + help)
+        ])
+        .split(area)
+}
+
+/// Returns where the keyboard widget is drawn on a screen of size `area`
+pub fn keyboard_area(area: Rect, state: &AppState) -> Rect {
+    split_main_content(screen_chunks(area)[1], state).0
+}
+
+/// Returns where the layer minimap is drawn on a screen of size `area`, if shown
+pub fn minimap_area(area: Rect, state: &AppState) -> Option<Rect> {
+    split_main_content(screen_chunks(area)[1], state).1
+}
+
 /// Render active popup
 fn render_popup(f: &mut Frame, popup_type: &PopupType, state: &AppState) {
     match popup_type {
@@ -97,7 +115,7 @@ fn render_popup(f: &mut Frame, popup_type: &PopupType, state: &AppState) {
         PopupType::LayerPicker => {
             // Use ContextualComponent trait pattern
             if let Some(ActiveComponent::LayerPicker(ref picker)) = state.active_component {
-                picker.render(f, f.area(), &state.theme, &state.layout.layers);
+                picker.render(f, f.area(), &state.theme, &state.layout);
             }
         }
         PopupType::TemplateBrowser => {
@@ -114,6 +132,9 @@ fn render_popup(f: &mut Frame, popup_type: &PopupType, state: &AppState) {
         PopupType::UnsavedChangesPrompt => {
             render_unsaved_prompt(f, &state.theme);
         }
+        PopupType::GeometryAdjustReview => {
+            render_geometry_review(f, state);
+        }
         PopupType::BuildLog => {
             // Use ContextualComponent trait pattern
             if let Some(ActiveComponent::BuildLog(ref log)) = state.active_component {
@@ -152,6 +173,10 @@ fn render_popup(f: &mut Frame, popup_type: &PopupType, state: &AppState) {
                     tap_hold_settings: state.layout.tap_hold_settings.clone(),
                     config: state.config.clone(),
                     layout: state.layout.clone(),
+                    size_estimate: crate::firmware::size_estimate::estimate(
+                        &state.layout,
+                        &state.geometry,
+                    ),
                 };
                 manager.render_with_context(f, f.area(), &state.theme, &context);
             }
@@ -175,6 +200,72 @@ fn render_popup(f: &mut Frame, popup_type: &PopupType, state: &AppState) {
                 editor.render(f, f.area(), &state.theme);
             }
         }
+        PopupType::MacroEditor => {
+            if let Some(ActiveComponent::MacroEditor(ref editor)) = state.active_component {
+                editor.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::UnicodeMapEditor => {
+            if let Some(ActiveComponent::UnicodeMapEditor(ref editor)) = state.active_component {
+                editor.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::CustomKeycodeEditor => {
+            if let Some(ActiveComponent::CustomKeycodeEditor(ref editor)) = state.active_component {
+                editor.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::ComboEditor => {
+            if let Some(ActiveComponent::ComboEditor(ref editor)) = state.active_component {
+                editor.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::EncoderEditor => {
+            if let Some(ActiveComponent::EncoderEditor(ref editor)) = state.active_component {
+                editor.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::BackupPicker => {
+            if let Some(ActiveComponent::BackupPicker(ref picker)) = state.active_component {
+                picker.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::TemplateFit => {
+            if let Some(ActiveComponent::TemplateFit(ref dialog)) = state.active_component {
+                dialog.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::TemplatePlaceholders => {
+            if let Some(ActiveComponent::TemplatePlaceholders(ref dialog)) = state.active_component
+            {
+                dialog.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::LayoutStats => {
+            if let Some(ActiveComponent::LayoutStats(ref view)) = state.active_component {
+                view.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::LayerGraph => {
+            if let Some(ActiveComponent::LayerGraph(ref view)) = state.active_component {
+                view.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::QmkTreePicker => {
+            if let Some(ActiveComponent::QmkTreePicker(ref picker)) = state.active_component {
+                picker.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::KeySearch => {
+            if let Some(ActiveComponent::KeySearch(ref search)) = state.active_component {
+                search.render(f, f.area(), &state.theme, &state.layout);
+            }
+        }
+        PopupType::CopyToLayer => {
+            if let Some(ActiveComponent::CopyToLayer(ref picker)) = state.active_component {
+                picker.render(f, f.area(), &state.theme, &state.layout);
+            }
+        }
         PopupType::TapDanceForm => {
             if let Some(ActiveComponent::TapDanceForm(ref form)) = state.active_component {
                 form.render(f, f.area(), &state.theme);
@@ -456,7 +547,7 @@ fn render_export_filename_dialog(f: &mut Frame, state: &AppState) {
         .split(area);
 
     // Title
-    let title = Paragraph::new("Export Layout to Markdown")
+    let title = Paragraph::new("Export Layout to Markdown or Share Bundle")
         .style(
             Style::default()
                 .fg(theme.primary)
@@ -481,6 +572,9 @@ fn render_export_filename_dialog(f: &mut Frame, state: &AppState) {
     let help_text = vec![
         Line::from(""),
         Line::from("Type: enter filename | Backspace: delete"),
+        Line::from(
+            "End the name with .lazyqmk to bundle the layout with its preview, geometry and keymap.c",
+        ),
     ];
     let help = Paragraph::new(help_text).style(Style::default().fg(theme.text_muted));
     f.render_widget(help, chunks[2]);
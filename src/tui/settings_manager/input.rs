@@ -9,7 +9,10 @@ use crate::models::{
     HoldDecisionMode, PaletteFxEffect, PaletteFxPalette, RgbMatrixEffect, RippleColorMode,
     TapHoldPreset,
 };
+use crate::tui::file_browser::FileBrowserEvent;
+use crate::tui::Component;
 
+use super::ManagerMode;
 use super::SettingItem;
 use super::SettingsManager;
 use super::SettingsManagerContext;
@@ -257,21 +260,16 @@ impl SettingsManager {
     }
 
     pub(super) fn handle_path_editing(&mut self, key: KeyEvent) -> Option<SettingsManagerEvent> {
-        match key.code {
-            KeyCode::Esc => {
+        let ManagerMode::EditingPath { browser, .. } = &mut self.state.mode else {
+            return None;
+        };
+        match browser.handle_input(key)? {
+            // The browser stays on the chosen directory; apply reads it from there
+            FileBrowserEvent::Selected(_) => Some(SettingsManagerEvent::SettingsUpdated),
+            FileBrowserEvent::Cancelled => {
                 self.state.cancel();
                 None
             }
-            KeyCode::Char(c) => {
-                self.state.handle_string_char_input(c);
-                None
-            }
-            KeyCode::Backspace => {
-                self.state.handle_string_backspace();
-                None
-            }
-            KeyCode::Enter => Some(SettingsManagerEvent::SettingsUpdated),
-            _ => None,
         }
     }
 
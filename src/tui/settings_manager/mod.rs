@@ -7,11 +7,13 @@
 use crossterm::event::KeyEvent;
 use ratatui::{layout::Rect, Frame};
 
+use crate::firmware::size_estimate::SizeEstimate;
 use crate::models::{
-    IdleEffectSettings, RgbBrightness, RgbOverlayRippleSettings, TapHoldSettings,
-    UncoloredKeyBehavior,
+    EditingHelper, FirmwareFeature, IdleEffectSettings, RgbBrightness, RgbOverlayRippleSettings,
+    TapHoldSettings, UncoloredKeyBehavior,
 };
 
+use super::file_browser::FileBrowser;
 use super::Theme;
 
 mod input;
@@ -29,6 +31,7 @@ enum RgbSubgroup {
     Idle,
     Ripple,
     PaletteFx,
+    Conditions,
 }
 
 impl RgbSubgroup {
@@ -38,6 +41,7 @@ impl RgbSubgroup {
             Self::Idle => "Idle lighting",
             Self::Ripple => "Press ripple",
             Self::PaletteFx => "PaletteFX effects",
+            Self::Conditions => "Conditional lighting",
         }
     }
 }
@@ -60,6 +64,14 @@ pub enum SettingGroup {
     TapHold,
     /// Two-key hold combo settings
     Combos,
+    /// Modifier + key overrides
+    KeyOverrides,
+    /// Repeat Key and Alt Repeat Key overrides
+    RepeatKey,
+    /// Select Word, Sentence Case and Num Word helpers
+    EditingHelpers,
+    /// Optional firmware features and their flash cost
+    Features,
 }
 
 impl SettingGroup {
@@ -73,6 +85,10 @@ impl SettingGroup {
             Self::Rgb => "Lighting behavior",
             Self::TapHold => "Tap-hold tuning",
             Self::Combos => "Combos & quick actions",
+            Self::KeyOverrides => "Key overrides",
+            Self::RepeatKey => "Repeat key",
+            Self::EditingHelpers => "Editing helpers",
+            Self::Features => "Firmware features & size",
         }
     }
 
@@ -101,6 +117,8 @@ pub enum SettingItem {
     OutputFormat,
     /// Build output directory
     OutputDir,
+    /// QMK userspace generated keymaps pull in
+    Userspace,
 
     // === UI Settings (Global) ===
     /// Display help on startup
@@ -109,6 +127,10 @@ pub enum SettingItem {
     ThemeMode,
     /// Unified keyboard scale factor
     KeyboardScale,
+    /// Show per-layer minimaps under the keyboard
+    ShowLayerMinimap,
+    /// Label keys with the host keyboard layout's characters
+    HostLayoutLabels,
 
     // === RGB Settings (Per-Layout) ===
     /// Master switch for all RGB LEDs
@@ -165,6 +187,14 @@ pub enum SettingItem {
     OverlayRippleWaveCount,
     /// Delay between waves in milliseconds (50-500)
     OverlayRippleWaveDelay,
+    /// Minutes of inactivity before dimming (0 = never)
+    RgbDimAfter,
+    /// Brightness while dimmed (percent of current brightness)
+    RgbDimBrightness,
+    /// Typing speed that switches to the WPM effect (0 = disabled)
+    RgbWpmThreshold,
+    /// Daily time range with lighting off (HH:MM-HH:MM)
+    RgbOffHours,
 
     // === PaletteFX Settings (Per-Layout) ===
     /// `PaletteFX` master switch
@@ -211,6 +241,36 @@ pub enum SettingItem {
     ComboHoldDuration(usize),
     /// Action performed by the combo at the given index
     ComboAction(usize),
+
+    // === Key Overrides (Per-Layout) ===
+    /// Add a new key override to the layout
+    AddKeyOverride,
+    /// Edit the key override at the given index
+    KeyOverride(usize),
+    /// Remove the key override at the given index
+    RemoveKeyOverride(usize),
+
+    // === Repeat Key (Per-Layout) ===
+    /// Add a new alt repeat override to the layout
+    AddAltRepeatKey,
+    /// Edit the alt repeat override at the given index
+    AltRepeatKey(usize),
+    /// Remove the alt repeat override at the given index
+    RemoveAltRepeatKey(usize),
+
+    // === Editing Helpers (Per-Layout) ===
+    /// Switch an editing helper and its keycode on or off
+    EditingHelper(EditingHelper),
+    /// Layer Num Word turns on
+    NumWordLayer,
+
+    // === Firmware Features (Per-Layout) ===
+    /// Override for an optional firmware feature (`rules.mk` flag)
+    FirmwareFeature(FirmwareFeature),
+    /// Count key presses in the firmware for `collect-usage`
+    UsageCounting,
+    /// Host input modes for `UC()`/`UM()` keys
+    UnicodeInputModes,
 }
 
 impl SettingItem {
@@ -227,10 +287,13 @@ impl SettingItem {
             Self::KeymapName,
             Self::OutputFormat,
             Self::OutputDir,
+            Self::Userspace,
             // UI (Global)
             Self::ShowHelpOnStartup,
             Self::ThemeMode,
             Self::KeyboardScale,
+            Self::ShowLayerMinimap,
+            Self::HostLayoutLabels,
             // RGB (Per-Layout)
             Self::RgbEnabled,
             Self::RgbBrightness,
@@ -259,6 +322,10 @@ impl SettingItem {
             Self::OverlayRippleKeyActionPalette,
             Self::OverlayRippleWaveCount,
             Self::OverlayRippleWaveDelay,
+            Self::RgbDimAfter,
+            Self::RgbDimBrightness,
+            Self::RgbWpmThreshold,
+            Self::RgbOffHours,
             // PaletteFX (Per-Layout)
             Self::PaletteFxEnabled,
             Self::PaletteFxDefaultEffect,
@@ -288,6 +355,29 @@ impl SettingItem {
             items.push(Self::RemoveCombo(idx));
         }
 
+        // Key overrides (Per-Layout)
+        items.push(Self::AddKeyOverride);
+        for idx in 0..layout.key_overrides.len() {
+            items.push(Self::KeyOverride(idx));
+            items.push(Self::RemoveKeyOverride(idx));
+        }
+
+        // Alt repeat overrides (Per-Layout)
+        items.push(Self::AddAltRepeatKey);
+        for idx in 0..layout.alt_repeat_keys.len() {
+            items.push(Self::AltRepeatKey(idx));
+            items.push(Self::RemoveAltRepeatKey(idx));
+        }
+
+        // Editing helpers (Per-Layout)
+        items.extend(EditingHelper::ALL.map(Self::EditingHelper));
+        items.push(Self::NumWordLayer);
+
+        // Firmware features (Per-Layout)
+        items.extend(FirmwareFeature::ALL.map(Self::FirmwareFeature));
+        items.push(Self::UsageCounting);
+        items.push(Self::UnicodeInputModes);
+
         items
     }
 
@@ -300,8 +390,13 @@ impl SettingItem {
             | Self::LayoutVariant
             | Self::KeymapName
             | Self::OutputFormat
-            | Self::OutputDir => SettingGroup::Build,
-            Self::ShowHelpOnStartup | Self::ThemeMode | Self::KeyboardScale => SettingGroup::Ui,
+            | Self::OutputDir
+            | Self::Userspace => SettingGroup::Build,
+            Self::ShowHelpOnStartup
+            | Self::ThemeMode
+            | Self::KeyboardScale
+            | Self::ShowLayerMinimap
+            | Self::HostLayoutLabels => SettingGroup::Ui,
             Self::RgbEnabled
             | Self::RgbBrightness
             | Self::RgbSaturation
@@ -329,6 +424,10 @@ impl SettingItem {
             | Self::OverlayRippleKeyActionPalette
             | Self::OverlayRippleWaveCount
             | Self::OverlayRippleWaveDelay
+            | Self::RgbDimAfter
+            | Self::RgbDimBrightness
+            | Self::RgbWpmThreshold
+            | Self::RgbOffHours
             | Self::PaletteFxEnabled
             | Self::PaletteFxDefaultEffect
             | Self::PaletteFxDefaultPalette
@@ -349,6 +448,16 @@ impl SettingItem {
             | Self::ComboKey2(_)
             | Self::ComboHoldDuration(_)
             | Self::ComboAction(_) => SettingGroup::Combos,
+            Self::AddKeyOverride | Self::KeyOverride(_) | Self::RemoveKeyOverride(_) => {
+                SettingGroup::KeyOverrides
+            }
+            Self::AddAltRepeatKey | Self::AltRepeatKey(_) | Self::RemoveAltRepeatKey(_) => {
+                SettingGroup::RepeatKey
+            }
+            Self::EditingHelper(_) | Self::NumWordLayer => SettingGroup::EditingHelpers,
+            Self::FirmwareFeature(_) | Self::UsageCounting | Self::UnicodeInputModes => {
+                SettingGroup::Features
+            }
         }
     }
 
@@ -387,6 +496,10 @@ impl SettingItem {
             | Self::PaletteFxDefaultPalette
             | Self::PaletteFxEnableAllEffects
             | Self::PaletteFxEnableAllPalettes => Some(RgbSubgroup::PaletteFx),
+            Self::RgbDimAfter
+            | Self::RgbDimBrightness
+            | Self::RgbWpmThreshold
+            | Self::RgbOffHours => Some(RgbSubgroup::Conditions),
             _ => None,
         }
     }
@@ -401,9 +514,12 @@ impl SettingItem {
             Self::KeymapName => "Keymap Name".to_string(),
             Self::OutputFormat => "Output Format".to_string(),
             Self::OutputDir => "Build Output Folder".to_string(),
+            Self::Userspace => "QMK Userspace".to_string(),
             Self::ShowHelpOnStartup => "Show Help on Startup".to_string(),
             Self::ThemeMode => "Theme Mode".to_string(),
             Self::KeyboardScale => "Keyboard Scale".to_string(),
+            Self::ShowLayerMinimap => "Layer Minimap".to_string(),
+            Self::HostLayoutLabels => "Host Layout Labels".to_string(),
             Self::RgbEnabled => "Lighting Enabled".to_string(),
             Self::RgbBrightness => "Lighting Brightness".to_string(),
             Self::RgbSaturation => "RGB Saturation".to_string(),
@@ -431,6 +547,10 @@ impl SettingItem {
             Self::OverlayRippleKeyActionPalette => "Reactive Key Palette".to_string(),
             Self::OverlayRippleWaveCount => "Ripple Waves per Key".to_string(),
             Self::OverlayRippleWaveDelay => "Delay Between Waves".to_string(),
+            Self::RgbDimAfter => "Dim After Inactivity".to_string(),
+            Self::RgbDimBrightness => "Dimmed Brightness".to_string(),
+            Self::RgbWpmThreshold => "WPM Effect Threshold".to_string(),
+            Self::RgbOffHours => "Lighting Off Hours".to_string(),
             Self::PaletteFxEnabled => "PaletteFX Effects".to_string(),
             Self::PaletteFxDefaultEffect => "PaletteFX Default Effect".to_string(),
             Self::PaletteFxDefaultPalette => "PaletteFX Default Palette".to_string(),
@@ -452,6 +572,20 @@ impl SettingItem {
             Self::ComboKey2(idx) => format!("Combo {} Key 2", idx + 1),
             Self::ComboHoldDuration(idx) => format!("Combo {} Hold Duration", idx + 1),
             Self::ComboAction(idx) => format!("Combo {} Action", idx + 1),
+            // Key Overrides
+            Self::AddKeyOverride => "Add Key Override".to_string(),
+            Self::KeyOverride(idx) => format!("Key Override {}", idx + 1),
+            Self::RemoveKeyOverride(idx) => format!("Remove Key Override {}", idx + 1),
+            // Repeat Key
+            Self::AddAltRepeatKey => "Add Alt Repeat Override".to_string(),
+            Self::AltRepeatKey(idx) => format!("Alt Repeat Override {}", idx + 1),
+            Self::RemoveAltRepeatKey(idx) => format!("Remove Alt Repeat Override {}", idx + 1),
+            // Editing Helpers
+            Self::EditingHelper(helper) => helper.display_name().to_string(),
+            Self::NumWordLayer => "Num Word Layer".to_string(),
+            Self::FirmwareFeature(feature) => feature.display_name().to_string(),
+            Self::UsageCounting => "Usage Counting".to_string(),
+            Self::UnicodeInputModes => "Unicode Input Modes".to_string(),
         }
     }
 
@@ -477,11 +611,23 @@ impl SettingItem {
                 "Firmware file type to export after build, such as uf2, hex, or bin.".to_string()
             }
             Self::OutputDir => "Folder where built firmware files should be written.".to_string(),
+            Self::Userspace => {
+                "Your QMK userspace name (users/<name>/). Generated keymaps set USER_NAME to it \
+                 and its shared rules.mk is created if missing. Empty for standalone keymaps."
+                    .to_string()
+            }
             Self::ShowHelpOnStartup => "Display help overlay when application starts".to_string(),
             Self::ThemeMode => "Color theme: Auto (follow OS), Dark, or Light".to_string(),
             Self::KeyboardScale => {
                 "Keyboard display size: 1.0 = default, 0.5 = half, 2.0 = double".to_string()
             }
+            Self::ShowLayerMinimap => {
+                "Show a color minimap of every layer under the keyboard".to_string()
+            }
+            Self::HostLayoutLabels => {
+                "Label keys with what your computer's keyboard layout types (e.g. Z on KC_Y for German)"
+                    .to_string()
+            }
             Self::RgbEnabled => "Turn all keyboard lighting on or off.".to_string(),
             Self::RgbBrightness => "Overall keyboard lighting brightness (0-100%).".to_string(),
             Self::RgbSaturation => {
@@ -543,6 +689,19 @@ impl SettingItem {
             Self::OverlayRippleWaveDelay => {
                 "Delay between consecutive waves in milliseconds (50-500ms).".to_string()
             }
+            Self::RgbDimAfter => {
+                "Minutes without input before lighting dims. Use 0 to never dim.".to_string()
+            }
+            Self::RgbDimBrightness => {
+                "Brightness while dimmed, as a percentage of the normal brightness.".to_string()
+            }
+            Self::RgbWpmThreshold => {
+                "Typing speed that switches to the WPM effect. Use 0 to disable.".to_string()
+            }
+            Self::RgbOffHours => {
+                "Daily range with lighting off, e.g. 22:00-07:00. Needs the host clock over Raw HID; leave empty to disable."
+                    .to_string()
+            }
             Self::PaletteFxEnabled => {
                 "Enable PaletteFX community module effects instead of custom ripple overlay."
                     .to_string()
@@ -583,6 +742,49 @@ impl SettingItem {
                 format!("Hold duration in milliseconds for Combo {} (50-2000ms)", idx + 1)
             }
             Self::ComboAction(idx) => format!("Action performed by Combo {}", idx + 1),
+            // Key Overrides
+            Self::AddKeyOverride => {
+                "Add a modifier + key override, e.g. Shift+KC_BSPC = KC_DEL (up to 32)".to_string()
+            }
+            Self::KeyOverride(idx) => format!(
+                "Modifiers (Ctrl, Shift, Alt, Gui) + trigger key = replacement for Key Override {}",
+                idx + 1
+            ),
+            Self::RemoveKeyOverride(idx) => {
+                format!("Remove Key Override {} from the layout", idx + 1)
+            }
+            // Repeat Key
+            Self::AddAltRepeatKey => {
+                "What Alt Repeat (QK_AREP) sends after a key, e.g. KC_LEFT = KC_RGHT (up to 32)"
+                    .to_string()
+            }
+            Self::AltRepeatKey(idx) => format!(
+                "Last key = key Alt Repeat sends for Alt Repeat Override {}",
+                idx + 1
+            ),
+            Self::RemoveAltRepeatKey(idx) => {
+                format!("Remove Alt Repeat Override {} from the layout", idx + 1)
+            }
+            // Editing Helpers
+            Self::EditingHelper(helper) => format!(
+                "{} (adds the {} keycode to the picker)",
+                helper.description(),
+                helper.keycode()
+            ),
+            Self::NumWordLayer => {
+                "Layer NUMWORD turns on until a non-number key is typed".to_string()
+            }
+            Self::FirmwareFeature(feature) => format!(
+                "Include {} in the firmware ({} in rules.mk). Sizes are rough estimates for this keyboard's MCU.",
+                feature.display_name(),
+                feature.rules_mk_flag()
+            ),
+            Self::UsageCounting => {
+                "Count presses per key and layer in the firmware (RAM only, enables the console) for lazyqmk collect-usage".to_string()
+            }
+            Self::UnicodeInputModes => {
+                "Host input modes for Unicode keys, comma-separated (macOS, Linux, Windows, WinCompose, BSD, Emacs). UC_NEXT cycles through them.".to_string()
+            }
         }
     }
 
@@ -658,12 +860,12 @@ pub enum ManagerMode {
         /// Currently highlighted option index
         selected_option: usize,
     },
-    /// Editing a path (QMK path, output dir)
+    /// Choosing a directory (QMK path, output dir)
     EditingPath {
         /// Which setting is being edited
         setting: SettingItem,
-        /// Current value
-        value: String,
+        /// Directory browser, starting at the current value
+        browser: FileBrowser,
     },
     /// Selecting idle effect mode
     SelectingIdleEffectMode {
@@ -748,6 +950,8 @@ pub struct SettingsManagerContext {
     pub config: crate::config::Config,
     /// Current layout (for layout-specific settings)
     pub layout: crate::models::Layout,
+    /// Approximate firmware size with the current feature selection
+    pub size_estimate: SizeEstimate,
 }
 
 /// `SettingsManager` component that implements the Component trait
@@ -848,6 +1052,7 @@ impl SettingsManager {
             &context.tap_hold_settings,
             &context.config,
             &context.layout,
+            &context.size_estimate,
             theme,
         );
     }
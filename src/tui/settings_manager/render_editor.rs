@@ -8,8 +8,10 @@ use ratatui::{
     Frame,
 };
 
+use super::FileBrowser;
 use super::SettingItem;
 use super::Theme;
+use crate::tui::Component;
 
 /// Render numeric editor for integer values
 pub(super) fn render_numeric_editor(
@@ -250,16 +252,15 @@ pub(super) fn render_path_editor(
     f: &mut Frame,
     area: Rect,
     setting: SettingItem,
-    value: &str,
+    browser: &FileBrowser,
     theme: &Theme,
 ) {
     let chunks = ratatui::layout::Layout::default()
         .direction(ratatui::layout::Direction::Vertical)
         .constraints([
             Constraint::Length(2), // Title
-            Constraint::Length(3), // Input field
-            Constraint::Min(2),    // Description + current path
-            Constraint::Length(4), // Help
+            Constraint::Length(2), // Description
+            Constraint::Min(9),    // Directory browser
         ])
         .split(area);
 
@@ -273,66 +274,13 @@ pub(super) fn render_path_editor(
         );
     f.render_widget(title_text, chunks[0]);
 
-    // Input field with cursor
-    let display_value = format!("{value}▌");
-    let input_block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme.primary))
-        .title("Path");
-    let input_text = Paragraph::new(display_value)
-        .style(Style::default().fg(theme.text))
-        .block(input_block);
-    f.render_widget(input_text, chunks[1]);
-
-    // Description and path validation hint
-    let path_exists = std::path::Path::new(value).exists();
-    let status_line = if value.is_empty() {
-        Line::from(Span::styled(
-            "Enter a path",
-            Style::default().fg(theme.text_muted),
-        ))
-    } else if path_exists {
-        Line::from(Span::styled(
-            "✓ Path exists",
-            Style::default().fg(theme.primary),
-        ))
-    } else {
-        Line::from(Span::styled(
-            "⚠ Path does not exist (will be created if needed)",
-            Style::default().fg(theme.warning),
-        ))
-    };
-
-    let desc = vec![
-        Line::from(setting.description()),
-        Line::from(""),
-        status_line,
-    ];
-
-    let desc_text = Paragraph::new(desc)
+    let desc_text = Paragraph::new(setting.description())
         .alignment(Alignment::Center)
         .wrap(ratatui::widgets::Wrap { trim: true })
         .style(Style::default().fg(theme.text_muted));
-    f.render_widget(desc_text, chunks[2]);
-
-    // Help text
-    let help = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Enter", Style::default().fg(theme.primary)),
-            Span::raw(": Apply  "),
-            Span::styled("Esc", Style::default().fg(theme.primary)),
-            Span::raw(": Cancel  "),
-            Span::styled("Backspace", Style::default().fg(theme.primary)),
-            Span::raw(": Delete"),
-        ]),
-    ];
-
-    let help_widget = Paragraph::new(help)
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(theme.text_muted));
+    f.render_widget(desc_text, chunks[1]);
 
-    f.render_widget(help_widget, chunks[3]);
+    browser.render(f, chunks[2], theme);
 }
 
 /// Render boolean toggle
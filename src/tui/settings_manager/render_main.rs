@@ -11,6 +11,7 @@ use ratatui::{
     Frame,
 };
 
+use crate::firmware::size_estimate::{self, SizeEstimate};
 use crate::models::{
     IdleEffectSettings, RgbBrightness, RgbOverlayRippleSettings, TapHoldSettings,
     UncoloredKeyBehavior,
@@ -44,6 +45,7 @@ pub(super) fn render_settings_manager(
     tap_hold_settings: &TapHoldSettings,
     config: &crate::config::Config,
     layout: &crate::models::Layout,
+    size_estimate: &SizeEstimate,
     theme: &Theme,
 ) {
     // Center the dialog (80% width, 80% height)
@@ -94,6 +96,7 @@ pub(super) fn render_settings_manager(
                 tap_hold_settings,
                 config,
                 layout,
+                size_estimate,
                 theme,
             );
         }
@@ -135,8 +138,8 @@ pub(super) fn render_settings_manager(
         ManagerMode::SelectingThemeMode { selected_option } => {
             render_theme_mode_selector(f, inner_area, *selected_option, theme);
         }
-        ManagerMode::EditingPath { setting, value } => {
-            render_path_editor(f, inner_area, *setting, value, theme);
+        ManagerMode::EditingPath { setting, browser } => {
+            render_path_editor(f, inner_area, *setting, browser, theme);
         }
         ManagerMode::SelectingIdleEffectMode { selected_option } => {
             render_idle_effect_mode_selector(f, inner_area, *selected_option, theme);
@@ -179,6 +182,7 @@ fn render_settings_list(
     tap_hold_settings: &TapHoldSettings,
     config: &crate::config::Config,
     layout: &crate::models::Layout,
+    size_estimate: &SizeEstimate,
     theme: &Theme,
 ) {
     // Split area for task summary, list and help text
@@ -238,12 +242,16 @@ fn render_settings_list(
                 // Add spacing between groups
                 items.push(ListItem::new(Line::from("")));
             }
-            items.push(ListItem::new(Line::from(vec![Span::styled(
+            let mut header = vec![Span::styled(
                 format!("── {} ──", group.display_name()),
                 Style::default()
                     .fg(theme.primary)
                     .add_modifier(Modifier::BOLD),
-            )])));
+            )];
+            if group == SettingGroup::Features {
+                header.push(size_total_span(size_estimate, theme));
+            }
+            items.push(ListItem::new(Line::from(header)));
             current_group = Some(group);
             current_rgb_subgroup = None;
         }
@@ -303,13 +311,25 @@ fn render_settings_list(
             Span::styled("[L] ", Style::default().fg(theme.text_muted))
         };
 
-        let content = Line::from(vec![
+        let mut spans = vec![
             Span::styled(marker, Style::default().fg(theme.primary)),
             scope_indicator,
             Span::styled(setting.display_name(), style),
             Span::styled(": ", Style::default().fg(theme.text_muted)),
             Span::styled(value, Style::default().fg(theme.success)),
-        ]);
+        ];
+        // Annotate feature toggles with their flash cost
+        if let SettingItem::FirmwareFeature(feature) = setting {
+            if let Some(estimate) = size_estimate.feature(feature) {
+                let cost = if estimate.enabled {
+                    format!("  +{}", size_estimate::format_kb(estimate.bytes))
+                } else {
+                    format!("  ({} if on)", size_estimate::format_kb(estimate.bytes))
+                };
+                spans.push(Span::styled(cost, Style::default().fg(theme.text_muted)));
+            }
+        }
+        let content = Line::from(spans);
 
         items.push(ListItem::new(content));
         display_index += 1;
@@ -348,6 +368,35 @@ fn render_settings_list(
     f.render_widget(help, chunks[2]);
 }
 
+/// Running firmware size total shown next to the features header.
+///
+/// Uses the warning color once the estimate no longer fits the MCU.
+fn size_total_span(estimate: &SizeEstimate, theme: &Theme) -> Span<'static> {
+    let total = estimate.total_bytes();
+    let text = match estimate.capacity_bytes {
+        Some(capacity) => format!(
+            " ~{} of {} ({}%)",
+            size_estimate::format_kb(total),
+            size_estimate::format_kb(capacity),
+            u64::from(total) * 100 / u64::from(capacity)
+        ),
+        None => format!(
+            " ~{} (unknown MCU capacity)",
+            size_estimate::format_kb(total)
+        ),
+    };
+    if estimate.exceeds_capacity() {
+        Span::styled(
+            format!("{text} – likely too large"),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Span::styled(text, Style::default().fg(theme.text_muted))
+    }
+}
+
 /// Get display string for a setting value
 pub(super) fn get_setting_value_display(
     setting: SettingItem,
@@ -386,6 +435,11 @@ pub(super) fn get_setting_value_display(
             .and_then(|l| l.metadata.output_format.clone())
             .unwrap_or_else(|| "<not set>".to_string()),
         SettingItem::OutputDir => config.build.output_dir.display().to_string(),
+        SettingItem::Userspace => config
+            .build
+            .userspace
+            .clone()
+            .unwrap_or_else(|| "<not set>".to_string()),
         // Global: UI
         SettingItem::ShowHelpOnStartup => if config.ui.show_help_on_startup {
             "On"
@@ -399,6 +453,18 @@ pub(super) fn get_setting_value_display(
             crate::config::ThemeMode::Light => "Light".to_string(),
         },
         SettingItem::KeyboardScale => format!("{:.0}%", config.ui.keyboard_scale * 100.0),
+        SettingItem::ShowLayerMinimap => if config.ui.show_layer_minimap {
+            "On"
+        } else {
+            "Off"
+        }
+        .to_string(),
+        SettingItem::HostLayoutLabels => if config.ui.host_layout_labels {
+            "On"
+        } else {
+            "Off"
+        }
+        .to_string(),
         // Per-Layout: RGB
         SettingItem::RgbEnabled => if rgb_enabled { "On" } else { "Off" }.to_string(),
         SettingItem::RgbBrightness => format!("{}%", rgb_brightness.as_percent()),
@@ -462,6 +528,25 @@ pub(super) fn get_setting_value_display(
             .display_name()
             .to_string(),
         SettingItem::UncoloredKeyBehavior => format!("{}%", uncolored_key_behavior.as_percent()),
+        SettingItem::RgbDimAfter => match layout.map(|l| l.rgb_conditions.dim_after_minutes) {
+            Some(0) | None => "Never".to_string(),
+            Some(minutes) => format!("{minutes} min"),
+        },
+        SettingItem::RgbDimBrightness => format!(
+            "{}%",
+            layout.map_or(30, |l| l.rgb_conditions.dim_brightness_pct)
+        ),
+        SettingItem::RgbWpmThreshold => match layout.map(|l| &l.rgb_conditions) {
+            Some(rules) if rules.wpm_threshold > 0 => format!(
+                "{} WPM → {}",
+                rules.wpm_threshold,
+                rules.wpm_effect.display_name()
+            ),
+            _ => "Disabled".to_string(),
+        },
+        SettingItem::RgbOffHours => layout
+            .and_then(|l| l.rgb_conditions.off_hours)
+            .map_or_else(|| "Disabled".to_string(), |range| range.to_string()),
         // Per-Layout: Overlay Ripple
         SettingItem::OverlayRippleEnabled => if overlay_ripple_settings.enabled {
             "On"
@@ -582,6 +667,28 @@ pub(super) fn get_setting_value_display(
                 || "<not set>".to_string(),
                 |c| c.action.display_name().to_string(),
             ),
+        // Per-Layout: Key Overrides
+        SettingItem::AddKeyOverride => "<add new override>".to_string(),
+        SettingItem::KeyOverride(idx) => layout
+            .and_then(|l| l.key_overrides.get(idx))
+            .map_or_else(|| "<not set>".to_string(), ToString::to_string),
+        SettingItem::RemoveKeyOverride(idx) => {
+            layout.and_then(|l| l.key_overrides.get(idx)).map_or_else(
+                || "<not set>".to_string(),
+                |_| "<press Enter to remove>".to_string(),
+            )
+        }
+        // Per-Layout: Repeat Key
+        SettingItem::AddAltRepeatKey => "<add new override>".to_string(),
+        SettingItem::AltRepeatKey(idx) => layout
+            .and_then(|l| l.alt_repeat_keys.get(idx))
+            .map_or_else(|| "<not set>".to_string(), ToString::to_string),
+        SettingItem::RemoveAltRepeatKey(idx) => {
+            layout.and_then(|l| l.alt_repeat_keys.get(idx)).map_or_else(
+                || "<not set>".to_string(),
+                |_| "<press Enter to remove>".to_string(),
+            )
+        }
         // Per-Layout: PaletteFX
         SettingItem::PaletteFxEnabled => layout
             .map(|l| if l.palette_fx.enabled { "On" } else { "Off" })
@@ -613,5 +720,35 @@ pub(super) fn get_setting_value_display(
             })
             .unwrap_or("Off")
             .to_string(),
+        // Per-Layout: Editing helpers
+        SettingItem::EditingHelper(helper) => layout
+            .map(|l| {
+                if l.editing_helpers.is_enabled(helper) {
+                    "On"
+                } else {
+                    "Off"
+                }
+            })
+            .unwrap_or("Off")
+            .to_string(),
+        SettingItem::NumWordLayer => layout
+            .map(|l| l.editing_helpers.num_word_layer.to_string())
+            .unwrap_or_else(|| "1".to_string()),
+        // Per-Layout: Firmware features
+        SettingItem::FirmwareFeature(feature) => {
+            match layout.and_then(|l| l.firmware_features.get(feature)) {
+                Some(true) => "On".to_string(),
+                Some(false) => "Off".to_string(),
+                None => "Keyboard default".to_string(),
+            }
+        }
+        SettingItem::UsageCounting => layout
+            .map(|l| if l.usage_counting { "On" } else { "Off" })
+            .unwrap_or("Off")
+            .to_string(),
+        SettingItem::UnicodeInputModes => layout
+            .map(crate::models::Layout::unicode_input_modes_display)
+            .filter(|modes| !modes.is_empty())
+            .unwrap_or_else(|| "QMK default".to_string()),
     }
 }
@@ -5,7 +5,7 @@ use crate::models::{
     RippleColorMode, TapHoldPreset,
 };
 
-use super::{ManagerMode, SettingItem, SettingsManagerState};
+use super::{FileBrowser, ManagerMode, SettingItem, SettingsManagerState};
 
 impl SettingsManagerState {
     /// Create a new settings manager state
@@ -314,11 +314,11 @@ impl SettingsManagerState {
         };
     }
 
-    /// Start editing a path value
-    pub fn start_editing_path(&mut self, setting: SettingItem, current: String) {
+    /// Start choosing a path value, browsing from the current one
+    pub fn start_editing_path(&mut self, setting: SettingItem, current: &str) {
         self.mode = ManagerMode::EditingPath {
             setting,
-            value: current,
+            browser: FileBrowser::new(current),
         };
     }
 
@@ -430,9 +430,7 @@ impl SettingsManagerState {
     /// Handle character input for string/path editing
     pub fn handle_string_char_input(&mut self, c: char) {
         match &mut self.mode {
-            ManagerMode::EditingString { value, .. } | ManagerMode::EditingPath { value, .. } => {
-                value.push(c);
-            }
+            ManagerMode::EditingString { value, .. } => value.push(c),
             _ => {}
         }
     }
@@ -440,7 +438,7 @@ impl SettingsManagerState {
     /// Handle backspace for string/path editing
     pub fn handle_string_backspace(&mut self) {
         match &mut self.mode {
-            ManagerMode::EditingString { value, .. } | ManagerMode::EditingPath { value, .. } => {
+            ManagerMode::EditingString { value, .. } => {
                 value.pop();
             }
             _ => {}
@@ -451,9 +449,16 @@ impl SettingsManagerState {
     #[must_use]
     pub fn get_string_value(&self) -> Option<&str> {
         match &self.mode {
-            ManagerMode::EditingString { value, .. } | ManagerMode::EditingPath { value, .. } => {
-                Some(value)
-            }
+            ManagerMode::EditingString { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the directory being browsed while choosing a path
+    #[must_use]
+    pub fn get_path_value(&self) -> Option<&std::path::Path> {
+        match &self.mode {
+            ManagerMode::EditingPath { browser, .. } => Some(browser.current_dir()),
             _ => None,
         }
     }
@@ -1,8 +1,8 @@
 //! Tests for the settings manager.
 
 use crate::models::{
-    IdleEffectSettings, RgbBrightness, RgbMatrixEffect, RgbOverlayRippleSettings, TapHoldSettings,
-    UncoloredKeyBehavior,
+    EditingHelper, IdleEffectSettings, RgbBrightness, RgbMatrixEffect, RgbOverlayRippleSettings,
+    TapHoldSettings, UncoloredKeyBehavior,
 };
 
 use super::render_main::get_setting_value_display;
@@ -51,6 +51,75 @@ fn test_setting_item_all_includes_idle_effect_settings() {
     assert!(all_settings.contains(&SettingItem::IdleEffectMode));
 }
 
+#[test]
+fn test_setting_item_all_lists_key_overrides() {
+    let mut layout = crate::models::Layout::new("test").unwrap();
+    layout.key_overrides =
+        vec![crate::models::KeyOverride::parse("Shift+KC_BSPC = KC_DEL").unwrap()];
+    let all_settings = SettingItem::all(&layout);
+
+    let add = all_settings
+        .iter()
+        .position(|s| *s == SettingItem::AddKeyOverride)
+        .unwrap();
+    assert_eq!(
+        all_settings[add + 1..add + 3],
+        [
+            SettingItem::KeyOverride(0),
+            SettingItem::RemoveKeyOverride(0)
+        ]
+    );
+    assert_eq!(
+        SettingItem::KeyOverride(0).group(),
+        super::SettingGroup::KeyOverrides
+    );
+}
+
+#[test]
+fn test_setting_item_all_lists_alt_repeat_keys() {
+    let mut layout = crate::models::Layout::new("test").unwrap();
+    layout.alt_repeat_keys = vec![crate::models::AltRepeatKey::new("KC_LEFT", "KC_RGHT")];
+    let all_settings = SettingItem::all(&layout);
+
+    let add = all_settings
+        .iter()
+        .position(|s| *s == SettingItem::AddAltRepeatKey)
+        .unwrap();
+    assert_eq!(
+        all_settings[add + 1..add + 3],
+        [
+            SettingItem::AltRepeatKey(0),
+            SettingItem::RemoveAltRepeatKey(0)
+        ]
+    );
+    assert_eq!(
+        SettingItem::AltRepeatKey(0).group(),
+        super::SettingGroup::RepeatKey
+    );
+}
+
+#[test]
+fn test_setting_item_all_lists_editing_helpers() {
+    let layout = crate::models::Layout::new("test").unwrap();
+    let all_settings = SettingItem::all(&layout);
+
+    let first = all_settings
+        .iter()
+        .position(|s| *s == SettingItem::EditingHelper(EditingHelper::SelectWord))
+        .unwrap();
+    assert_eq!(
+        all_settings[first + 1..first + 3],
+        [
+            SettingItem::EditingHelper(EditingHelper::SentenceCase),
+            SettingItem::EditingHelper(EditingHelper::NumWord)
+        ]
+    );
+    assert_eq!(
+        SettingItem::NumWordLayer.group(),
+        super::SettingGroup::EditingHelpers
+    );
+}
+
 #[test]
 fn test_idle_effect_settings_belong_to_rgb_group() {
     assert_eq!(
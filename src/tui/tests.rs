@@ -158,3 +158,14 @@ fn test_build_keycode_empty_params() {
     let result = state.build_keycode();
     assert!(result.is_none(), "Empty params should return None");
 }
+
+#[test]
+fn test_color_view_cycles_through_all_views() {
+    use super::ColorView;
+
+    let view = ColorView::default();
+    assert_eq!(view, ColorView::Firmware);
+    assert_eq!(view.next(), ColorView::Categories);
+    assert_eq!(view.next().next(), ColorView::Overrides);
+    assert_eq!(view.next().next().next(), ColorView::Firmware);
+}
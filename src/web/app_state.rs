@@ -2,13 +2,29 @@
 //!
 //! Extracted from src/web/mod.rs as part of LazyQMK-2rf6.2.
 
-use std::path::PathBuf;
-use std::sync::{Arc, RwLock};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use axum::http::StatusCode;
+use tracing::warn;
 
 use crate::config::Config;
 use crate::keycode_db::KeycodeDb;
+use crate::models::Layout;
+use crate::services::backup::BackupStore;
+use crate::services::workspace_lock::{WorkspaceClient, WorkspaceLock};
+use crate::services::LayoutService;
 use crate::web::build_jobs::BuildJobManager;
+use crate::web::collab::CollabHub;
+use crate::web::error::AppError;
 use crate::web::generate_jobs::GenerateJobManager;
+use crate::web::geometry_cache::{CachedGeometry, GeometryCache};
+use crate::web::history::EditHistory;
+use crate::web::keyboard_cache::KeyboardCache;
+use crate::web::layout_store::{self, AsyncLayoutStore, LayoutStore, MemoryLayoutStore};
+use crate::web::sandbox::SandboxDir;
+use crate::web::shutdown::ShutdownTrigger;
+use crate::web::watchdog::timeout_from_secs;
 
 #[cfg(test)]
 use crate::web::build_jobs::MockFirmwareBuilder;
@@ -23,18 +39,48 @@ pub struct AppState {
     pub(crate) config: Arc<RwLock<Config>>,
     /// Keycode database (immutable after load)
     pub(crate) keycode_db: Arc<KeycodeDb>,
-    /// Working directory for layout files (defaults to current dir)
+    /// Working directory for layout files (defaults to current dir); scratch
+    /// space for jobs and audit logs when layouts are stored remotely or in
+    /// memory
     pub(crate) workspace_root: PathBuf,
+    /// Where layouts are loaded from and saved to
+    pub(crate) layouts: AsyncLayoutStore,
     /// Build job manager for background firmware builds
     pub(crate) build_manager: Arc<BuildJobManager>,
     /// Generate job manager for firmware generation and zip packaging
     pub(crate) generate_manager: Arc<GenerateJobManager>,
+    /// Keyboard geometry shared across layout handlers
+    pub(crate) geometry_cache: Arc<GeometryCache>,
+    /// Keyboard list served by `GET /api/keyboards`
+    pub(crate) keyboard_cache: Arc<KeyboardCache>,
+    /// Reject all mutating requests (fixed at startup)
+    pub(crate) read_only: bool,
+    /// Accept `POST /api/shutdown` (fixed at startup)
+    pub(crate) allow_shutdown: bool,
+    /// Set when the server should stop
+    pub(crate) shutdown: Arc<ShutdownTrigger>,
+    /// Live change broadcast between sessions editing the same layout
+    pub(crate) collab: Arc<CollabHub>,
+    /// Undo/redo journal of the changes made through the API
+    pub(crate) history: Arc<EditHistory>,
+    /// Registration in the workspace, so a TUI on the same directory knows
+    /// the server is there (local store only)
+    pub(crate) workspace_lock: Option<Arc<WorkspaceLock>>,
+    /// Scratch directory of a sandbox server (see [`crate::web::sandbox`]),
+    /// removed with the last clone of the state
+    pub(crate) sandbox: Option<Arc<SandboxDir>>,
 }
 
 impl AppState {
     /// Creates a new application state.
     pub fn new(config: Config, workspace_root: PathBuf) -> anyhow::Result<Self> {
         let keycode_db = Arc::new(KeycodeDb::load()?);
+        let OpenedWorkspace {
+            layouts,
+            workspace_root,
+            sandbox,
+        } = open_workspace(&config, workspace_root)?;
+        let workspace_lock = register_workspace(layouts.as_ref(), &workspace_root);
 
         // Set up build job manager
         let logs_dir = workspace_root.join(".lazyqmk").join("build_logs");
@@ -58,12 +104,34 @@ impl AppState {
             Arc::clone(&keycode_db),
         );
 
+        let job_timeout = timeout_from_secs(config.web.job_timeout_secs);
+        build_manager.set_job_timeout(job_timeout);
+        generate_manager.set_job_timeout(job_timeout);
+        build_manager.set_worker_count(config.web.build_workers);
+        build_manager.set_cpus_per_job(config.web.build_cpus_per_job);
+
+        let keyboard_cache = Arc::new(KeyboardCache::new(
+            workspace_root.join(".lazyqmk").join("keyboard_index.json"),
+        ));
+        let read_only = config.web.read_only;
+        let allow_shutdown = config.web.allow_shutdown;
+
         Ok(Self {
             config: Arc::new(RwLock::new(config)),
             keycode_db,
             workspace_root,
+            layouts: AsyncLayoutStore::new(layouts),
             build_manager,
             generate_manager,
+            geometry_cache: Arc::new(GeometryCache::new()),
+            keyboard_cache,
+            read_only,
+            allow_shutdown,
+            shutdown: Arc::new(ShutdownTrigger::new()),
+            collab: Arc::new(CollabHub::new()),
+            history: Arc::new(EditHistory::new()),
+            workspace_lock,
+            sandbox,
         })
     }
 
@@ -71,6 +139,12 @@ impl AppState {
     #[cfg(test)]
     pub fn with_mock_builder(config: Config, workspace_root: PathBuf) -> anyhow::Result<Self> {
         let keycode_db = Arc::new(KeycodeDb::load()?);
+        let OpenedWorkspace {
+            layouts,
+            workspace_root,
+            sandbox,
+        } = open_workspace(&config, workspace_root)?;
+        let workspace_lock = register_workspace(layouts.as_ref(), &workspace_root);
 
         // Set up build job manager with mock builder
         let logs_dir = workspace_root.join(".lazyqmk").join("build_logs");
@@ -98,12 +172,34 @@ impl AppState {
             mock_worker,
         );
 
+        let job_timeout = timeout_from_secs(config.web.job_timeout_secs);
+        build_manager.set_job_timeout(job_timeout);
+        generate_manager.set_job_timeout(job_timeout);
+        build_manager.set_worker_count(config.web.build_workers);
+        build_manager.set_cpus_per_job(config.web.build_cpus_per_job);
+
+        let keyboard_cache = Arc::new(KeyboardCache::new(
+            workspace_root.join(".lazyqmk").join("keyboard_index.json"),
+        ));
+        let read_only = config.web.read_only;
+        let allow_shutdown = config.web.allow_shutdown;
+
         Ok(Self {
             config: Arc::new(RwLock::new(config)),
             keycode_db,
             workspace_root,
+            layouts: AsyncLayoutStore::new(layouts),
             build_manager,
             generate_manager,
+            geometry_cache: Arc::new(GeometryCache::new()),
+            keyboard_cache,
+            read_only,
+            allow_shutdown,
+            shutdown: Arc::new(ShutdownTrigger::new()),
+            collab: Arc::new(CollabHub::new()),
+            history: Arc::new(EditHistory::new()),
+            workspace_lock,
+            sandbox,
         })
     }
 
@@ -112,4 +208,129 @@ impl AppState {
     pub fn workspace_root(&self) -> &PathBuf {
         &self.workspace_root
     }
+
+    /// Returns true if `filename` exists in the layout store.
+    pub(crate) async fn layout_exists(&self, filename: &str) -> Result<bool, AppError> {
+        self.layouts.exists(filename).await.map_err(|e| {
+            AppError::with_details(
+                StatusCode::BAD_GATEWAY,
+                "Failed to reach layout storage",
+                Some(e.to_string()),
+            )
+        })
+    }
+
+    /// Makes `layout` available as `filename` in the workspace directory so
+    /// build and generate jobs can read it from disk.
+    ///
+    /// A no-op for the local store, whose files already live there.
+    pub(crate) fn stage_layout(&self, filename: &str, layout: &Layout) -> anyhow::Result<()> {
+        let path = self.workspace_root.join(filename);
+        if self.layouts.local_path(filename).as_ref() == Some(&path) {
+            return Ok(());
+        }
+        LayoutService::save(layout, &path)
+    }
+
+    /// Returns true once the server has been asked to shut down.
+    #[must_use]
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutdown.is_triggered()
+    }
+
+    /// Returns true if the server rejects all mutating requests.
+    #[must_use]
+    pub const fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns true if the server runs in sandbox mode and never writes
+    /// outside its scratch directory.
+    #[must_use]
+    pub const fn is_sandboxed(&self) -> bool {
+        self.sandbox.is_some()
+    }
+
+    /// Returns the cached geometry for `layout` under the QMK tree it
+    /// selects.
+    pub(crate) fn layout_geometry(&self, layout: &Layout) -> Option<Arc<CachedGeometry>> {
+        let config = self
+            .config
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        self.geometry_cache.for_layout(
+            config
+                .qmk_tree_path(layout.metadata.qmk_tree.as_deref())
+                .map(PathBuf::as_path),
+            &config.paths.keyboard_roots,
+            layout,
+        )
+    }
+}
+
+/// Layout store and working directory picked by [`open_workspace`].
+struct OpenedWorkspace {
+    layouts: Arc<dyn LayoutStore>,
+    /// Directory for job output and other scratch files
+    workspace_root: PathBuf,
+    sandbox: Option<Arc<SandboxDir>>,
+}
+
+/// Opens the layout store selected by `config`.
+///
+/// In sandbox mode the workspace layouts are only read into memory and a
+/// temporary directory replaces `workspace_root` as scratch space.
+fn open_workspace(config: &Config, workspace_root: PathBuf) -> anyhow::Result<OpenedWorkspace> {
+    if !config.web.sandbox {
+        let layouts = layout_store::open(
+            config.web.storage_url.as_deref(),
+            &workspace_root,
+            backup_store(config),
+        )?;
+        return Ok(OpenedWorkspace {
+            layouts,
+            workspace_root,
+            sandbox: None,
+        });
+    }
+
+    let sandbox = SandboxDir::create()?;
+    Ok(OpenedWorkspace {
+        layouts: Arc::new(MemoryLayoutStore::seeded_from(&workspace_root)),
+        workspace_root: sandbox.path().to_path_buf(),
+        sandbox: Some(Arc::new(sandbox)),
+    })
+}
+
+/// Registers the server in `workspace_root` when layouts are stored there.
+///
+/// Failing to write the lock file only loses the coordination with the TUI,
+/// so it is logged rather than returned.
+fn register_workspace(
+    layouts: &dyn LayoutStore,
+    workspace_root: &Path,
+) -> Option<Arc<WorkspaceLock>> {
+    layouts.local_path("")?;
+    match WorkspaceLock::acquire(workspace_root, WorkspaceClient::Web) {
+        Ok(lock) => Some(Arc::new(lock)),
+        Err(e) => {
+            warn!(error = %e, "failed to register in the workspace");
+            None
+        }
+    }
+}
+
+/// Opens the backup directory from `config`.
+///
+/// Without a usable config directory layouts are saved without backups,
+/// which is logged rather than returned.
+fn backup_store(config: &Config) -> Option<BackupStore> {
+    match config.backup_dir() {
+        Ok(dir) => Some(BackupStore::new(dir)),
+        Err(e) => {
+            warn!(error = %e, "layout backups disabled");
+            None
+        }
+    }
 }
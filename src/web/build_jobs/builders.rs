@@ -5,14 +5,19 @@
 //! Also provides the artifact-discovery and SHA256 helpers.
 
 use std::fs::{self, File};
-use std::io::{BufRead, BufReader, Read, Write};
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::Command;
 use std::thread;
 use std::time::Duration;
 
 use sha2::{Digest, Sha256};
 
+use crate::firmware::builder::{
+    check_keymap, compile_args, enhance_qmk_error, CompilerDiagnostic, KeymapCheck,
+};
+use crate::services::process_tree::{ChildOutcome, OutputLine, TrackedChild};
+
 use super::ARTIFACT_EXTENSIONS;
 use super::{BuildArtifact, BuildResult, FirmwareBuilder};
 
@@ -29,17 +34,21 @@ impl FirmwareBuilder for RealFirmwareBuilder {
         qmk_path: &PathBuf,
         keyboard: &str,
         keymap: &str,
+        converter: Option<&str>,
+        parallel_jobs: Option<usize>,
         output_dir: &Path,
         job_id: &str,
         log_writer: &mut dyn Write,
         is_cancelled: &dyn Fn() -> bool,
     ) -> Result<BuildResult, String> {
+        let mut args = compile_args(keyboard, keymap, converter);
+        if let Some(jobs) = parallel_jobs {
+            args.push("-j".to_string());
+            args.push(jobs.to_string());
+        }
+
         let _ = writeln!(log_writer, "[INFO] Starting QMK compile...");
-        let _ = writeln!(
-            log_writer,
-            "[INFO] Running: qmk compile -kb {} -km {}",
-            keyboard, keymap
-        );
+        let _ = writeln!(log_writer, "[INFO] Running: qmk {}", args.join(" "));
 
         // Check for cancellation before starting
         if is_cancelled() {
@@ -48,72 +57,41 @@ impl FirmwareBuilder for RealFirmwareBuilder {
         }
 
         let mut cmd = Command::new("qmk");
-        cmd.arg("compile")
-            .arg("-kb")
-            .arg(keyboard)
-            .arg("-km")
-            .arg(keymap)
-            .current_dir(qmk_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
-        // Spawn the process instead of waiting for output
-        let mut child = cmd
-            .spawn()
-            .map_err(|e| format!("Failed to execute qmk: {e}"))?;
-
-        // Get handles for stdout/stderr
-        let stdout = child
-            .stdout
-            .take()
-            .ok_or_else(|| "Failed to capture stdout".to_string())?;
-        let stderr = child
-            .stderr
-            .take()
-            .ok_or_else(|| "Failed to capture stderr".to_string())?;
-
-        // Stream stdout in background
-        let stdout_reader = BufReader::new(stdout);
-        for line in stdout_reader.lines() {
-            // Check for cancellation periodically
-            if is_cancelled() {
-                let _ = writeln!(log_writer, "[INFO] Build cancelled, killing process...");
-                let _ = child.kill();
-                let _ = child.wait();
-                return Err("Build cancelled".to_string());
-            }
-
-            if let Ok(line) = line {
-                let level = if line.contains("error") || line.contains("Error") {
-                    "ERROR"
-                } else {
-                    "INFO"
-                };
-                let _ = writeln!(log_writer, "[{level}] {line}");
-            }
-        }
+        cmd.args(&args).current_dir(qmk_path);
+
+        // Own process group, so stopping the build also stops make and the compilers
+        let mut child =
+            TrackedChild::spawn(&mut cmd).map_err(|e| format!("Failed to execute qmk: {e}"))?;
+
+        // Stream output until the process exits, is cancelled or times out
+        let outcome = child
+            .wait_with_output(is_cancelled, &mut |line| match line {
+                OutputLine::Stdout(line) => {
+                    let level = if line.contains("error") || line.contains("Error") {
+                        "ERROR"
+                    } else {
+                        "INFO"
+                    };
+                    let _ = writeln!(log_writer, "[{level}] {line}");
+                }
+                OutputLine::Stderr(line) => {
+                    if !line.trim().is_empty() {
+                        let _ = writeln!(log_writer, "[ERROR] {line}");
+                    }
+                }
+            })
+            .map_err(|e| format!("Failed to wait for process: {e}"))?;
 
-        // Stream stderr
-        let stderr_reader = BufReader::new(stderr);
-        for line in stderr_reader.lines() {
-            if is_cancelled() {
-                let _ = writeln!(log_writer, "[INFO] Build cancelled, killing process...");
-                let _ = child.kill();
-                let _ = child.wait();
+        let status = match outcome {
+            ChildOutcome::Exited(status) => status,
+            ChildOutcome::Stopped => {
+                let _ = writeln!(
+                    log_writer,
+                    "[INFO] Build stopped, killed qmk and its children"
+                );
                 return Err("Build cancelled".to_string());
             }
-
-            if let Ok(line) = line {
-                if !line.trim().is_empty() {
-                    let _ = writeln!(log_writer, "[ERROR] {line}");
-                }
-            }
-        }
-
-        // Wait for process to complete
-        let status = child
-            .wait()
-            .map_err(|e| format!("Failed to wait for process: {e}"))?;
+        };
 
         // Final cancellation check
         if is_cancelled() {
@@ -145,6 +123,17 @@ impl FirmwareBuilder for RealFirmwareBuilder {
             artifacts,
         })
     }
+
+    fn check(
+        &self,
+        qmk_path: &Path,
+        keyboard: &str,
+        keymap: &str,
+        converter: Option<&str>,
+    ) -> Result<KeymapCheck, String> {
+        check_keymap(qmk_path, keyboard, keymap, converter)
+            .map_err(|e| enhance_qmk_error(&format!("{e:#}")))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -319,6 +308,8 @@ impl FirmwareBuilder for MockFirmwareBuilder {
         _qmk_path: &PathBuf,
         keyboard: &str,
         keymap: &str,
+        _converter: Option<&str>,
+        _parallel_jobs: Option<usize>,
         output_dir: &Path,
         job_id: &str,
         log_writer: &mut dyn Write,
@@ -387,6 +378,36 @@ impl FirmwareBuilder for MockFirmwareBuilder {
             Err(err)
         }
     }
+
+    fn check(
+        &self,
+        _qmk_path: &Path,
+        keyboard: &str,
+        keymap: &str,
+        _converter: Option<&str>,
+    ) -> Result<KeymapCheck, String> {
+        let mut check = KeymapCheck {
+            passed: self.should_succeed,
+            syntax_checked: true,
+            diagnostics: Vec::new(),
+            log: vec![format!("Mock check of {keymap} keymap for {keyboard}")],
+        };
+        if !self.should_succeed {
+            let message = self
+                .error_message
+                .clone()
+                .unwrap_or_else(|| "Mock check failed".to_string());
+            check.log.push(message.clone());
+            check.diagnostics.push(CompilerDiagnostic {
+                file: format!("keyboards/{keyboard}/keymaps/{keymap}/keymap.c"),
+                line: 1,
+                column: Some(1),
+                severity: "error".to_string(),
+                message,
+            });
+        }
+        Ok(check)
+    }
 }
 
 // ---------------------------------------------------------------------------
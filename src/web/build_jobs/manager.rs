@@ -1,27 +1,32 @@
 //! Build job manager — coordinates background firmware builds.
 //!
 //! Contains [`BuildJobManager`], its companion types ([`BuildCommand`],
-//! [`DeployResult`]), and the full `impl` block with all public and
-//! private methods.
+//! [`BuildQueue`], [`DeployResult`]), and the full `impl` block with all
+//! public and private methods.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
-use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
 use std::thread;
 use std::time::Duration;
 
 use crate::config::Config;
+use crate::firmware::builder::KeymapCheck;
 use crate::firmware::generator::FirmwareGenerator;
+use crate::firmware::user_code;
 use crate::firmware::validator::FirmwareValidator;
 use crate::keycode_db::KeycodeDb;
 use crate::services::geometry::{self, GeometryContext};
+use crate::services::keyboard_roots;
 use crate::services::LayoutService;
 
+use super::super::job_events::{JobEvent, JobEvents, JobKind, LogTee};
+use super::super::watchdog::JobDeadline;
 use super::CancelJobResponse;
 use super::JobLogsResponse;
-use super::MAX_CONCURRENT_BUILDS;
+use super::MAX_QUEUED_BUILDS;
 use super::{is_valid_artifact_id, parse_log_line};
 use super::{BuildArtifact, BuildJob, FirmwareBuilder, JobStatus, LogEntry};
 
@@ -29,22 +34,91 @@ use super::{BuildArtifact, BuildJob, FirmwareBuilder, JobStatus, LogEntry};
 // Internal types
 // ---------------------------------------------------------------------------
 
+/// QMK checkout the layout at `layout_path` builds against: the configured
+/// QMK tree it selects, or `default` (the server's QMK path).
+pub(crate) fn layout_qmk_path(layout_path: &Path, default: PathBuf) -> PathBuf {
+    let Some(tree) = LayoutService::load(layout_path)
+        .ok()
+        .and_then(|layout| layout.metadata.qmk_tree)
+    else {
+        return default;
+    };
+    Config::load()
+        .ok()
+        .and_then(|config| {
+            config
+                .paths
+                .qmk_trees
+                .into_iter()
+                .find(|qmk_tree| qmk_tree.name == tree)
+        })
+        .map_or(default, |qmk_tree| qmk_tree.path)
+}
+
 /// Result of deploying keymap files to the QMK tree.
 enum DeployResult {
     /// We created the keymap directory and all files — safe to remove the entire directory.
     CreatedDirectory(PathBuf),
     /// Directory already existed — only remove the files we wrote.
-    ExistingDirectory(PathBuf),
+    ExistingDirectory(PathBuf, Vec<&'static str>),
     /// Deployment was skipped (e.g., layout file missing in tests).
     Skipped,
 }
 
+impl DeployResult {
+    /// Removes what the deployment wrote to the QMK tree.
+    fn clean_up(&self) {
+        match self {
+            Self::CreatedDirectory(dir) => {
+                if dir.exists() {
+                    if let Err(e) = fs::remove_dir_all(dir) {
+                        tracing::warn!(
+                            dir = %dir.display(),
+                            error = %e,
+                            "failed to clean up created keymap directory"
+                        );
+                    }
+                }
+            }
+            Self::ExistingDirectory(dir, files) => {
+                // Only remove files we created
+                for file in files {
+                    let path = dir.join(file);
+                    if path.exists() {
+                        if let Err(e) = fs::remove_file(&path) {
+                            tracing::warn!(error = %e, "failed to clean up {file}");
+                        }
+                    }
+                }
+            }
+            Self::Skipped => {}
+        }
+    }
+}
+
+/// Layout and keymap to deploy into the QMK tree.
+struct DeployTarget<'a> {
+    layout_filename: &'a str,
+    /// Path to the layout markdown file.
+    layout_path: &'a Path,
+    keyboard: &'a str,
+    keymap: &'a str,
+    /// Keyboard target of the layout to deploy, if not its primary keyboard.
+    target: Option<&'a str>,
+    qmk_path: &'a Path,
+    keycode_db: &'a KeycodeDb,
+}
+
 /// Build command to be executed by worker thread.
 struct BuildCommand {
     job_id: String,
     layout_filename: String,
     keyboard: String,
     keymap: String,
+    /// QMK converter passed as `CONVERT_TO`, if any.
+    converter: Option<String>,
+    /// Keyboard target of the layout, if not its primary keyboard.
+    target: Option<String>,
     qmk_path: PathBuf,
     log_path: PathBuf,
     /// Job-specific output directory for artifacts.
@@ -55,6 +129,74 @@ struct BuildCommand {
     keycode_db: Arc<KeycodeDb>,
 }
 
+impl BuildCommand {
+    /// What the command deploys before building.
+    fn target(&self) -> DeployTarget<'_> {
+        DeployTarget {
+            layout_filename: &self.layout_filename,
+            layout_path: &self.layout_path,
+            keyboard: &self.keyboard,
+            keymap: &self.keymap,
+            target: self.target.as_deref(),
+            qmk_path: &self.qmk_path,
+            keycode_db: &self.keycode_db,
+        }
+    }
+
+    /// Keymap directory the command writes in the QMK tree.
+    fn keymap_key(&self) -> String {
+        keymap_key(&self.keyboard, &self.keymap)
+    }
+}
+
+/// Identifies the keymap directory of `keyboard` and `keymap`; two builds
+/// with the same key must not run at the same time.
+fn keymap_key(keyboard: &str, keymap: &str) -> String {
+    format!("{keyboard}:{keymap}")
+}
+
+/// Builds waiting for a worker, and the keymaps being built.
+#[derive(Default)]
+struct BuildQueue {
+    /// Commands not yet taken by a worker, oldest first.
+    pending: VecDeque<BuildCommand>,
+    /// Keymap keys of running builds and checks.
+    active: HashSet<String>,
+    /// Number of worker threads wanted.
+    workers: usize,
+    /// Number of worker threads running.
+    spawned: usize,
+}
+
+impl BuildQueue {
+    /// Takes the oldest command whose keymap isn't being built, marking
+    /// its keymap active.
+    fn take_next(&mut self) -> Option<BuildCommand> {
+        let idx = self
+            .pending
+            .iter()
+            .position(|cmd| !self.active.contains(&cmd.keymap_key()))?;
+        let cmd = self.pending.remove(idx)?;
+        self.active.insert(cmd.keymap_key());
+        Some(cmd)
+    }
+
+    /// Removes the pending command of `job_id`, if a worker hasn't taken it.
+    fn remove(&mut self, job_id: &str) -> Option<BuildCommand> {
+        let idx = self.pending.iter().position(|cmd| cmd.job_id == job_id)?;
+        self.pending.remove(idx)
+    }
+
+    /// Returns the 1-based queue position of each pending job.
+    fn positions(&self) -> HashMap<String, usize> {
+        self.pending
+            .iter()
+            .enumerate()
+            .map(|(idx, cmd)| (cmd.job_id.clone(), idx + 1))
+            .collect()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // BuildJobManager
 // ---------------------------------------------------------------------------
@@ -65,16 +207,22 @@ pub struct BuildJobManager {
     jobs: RwLock<HashMap<String, BuildJob>>,
     /// Set of cancelled job IDs.
     cancelled: RwLock<std::collections::HashSet<String>>,
-    /// Number of currently running jobs.
+    /// Number of queued or running jobs and running checks.
     running_count: Mutex<usize>,
-    /// Channel sender for build commands.
-    command_tx: Mutex<Option<mpsc::Sender<BuildCommand>>>,
+    /// Builds waiting for a worker.
+    queue: Mutex<BuildQueue>,
+    /// Signals workers that the queue or the worker count changed.
+    queue_changed: Condvar,
     /// Directory for storing job logs.
     pub(crate) logs_dir: PathBuf,
     /// Directory for storing build artifacts.
     pub(crate) output_dir: PathBuf,
     /// QMK firmware path from config.
     qmk_path: RwLock<Option<PathBuf>>,
+    /// Time a build may run before it is stopped (None = no limit).
+    job_timeout: RwLock<Option<Duration>>,
+    /// CPUs each build may use (None = QMK's default).
+    cpus_per_job: RwLock<Option<usize>>,
     /// Firmware builder (real or mock).
     builder: Arc<dyn FirmwareBuilder>,
     /// Maximum age of artifacts in hours (default: 168 = 7 days).
@@ -83,6 +231,8 @@ pub struct BuildJobManager {
     max_total_artifacts: usize,
     /// Keycode database for firmware generation during keymap deployment.
     keycode_db: Arc<KeycodeDb>,
+    /// Status changes and log lines for live subscribers.
+    events: JobEvents,
 }
 
 impl BuildJobManager {
@@ -125,9 +275,9 @@ impl BuildJobManager {
             .unwrap_or_else(std::sync::PoisonError::into_inner)
     }
 
-    /// Locks the command channel sender slot.
-    fn command_tx_lock(&self) -> std::sync::MutexGuard<'_, Option<mpsc::Sender<BuildCommand>>> {
-        self.command_tx
+    /// Locks the build queue.
+    fn queue_lock(&self) -> std::sync::MutexGuard<'_, BuildQueue> {
+        self.queue
             .lock()
             .unwrap_or_else(std::sync::PoisonError::into_inner)
     }
@@ -171,40 +321,83 @@ impl BuildJobManager {
             jobs: RwLock::new(HashMap::new()),
             cancelled: RwLock::new(std::collections::HashSet::new()),
             running_count: Mutex::new(0),
-            command_tx: Mutex::new(None),
+            queue: Mutex::new(BuildQueue::default()),
+            queue_changed: Condvar::new(),
             logs_dir,
             output_dir,
             qmk_path: RwLock::new(qmk_path),
+            job_timeout: RwLock::new(None),
+            cpus_per_job: RwLock::new(None),
             builder,
             max_artifacts_age_hours: 168, // 7 days
             max_total_artifacts: 50,
             keycode_db,
+            events: JobEvents::new(),
         });
 
-        // Start worker thread
-        manager.start_worker();
+        // Start a single worker; `set_worker_count` adds more
+        manager.set_worker_count(1);
 
         manager
     }
 
-    /// Starts the background worker thread.
-    fn start_worker(self: &Arc<Self>) {
-        let (tx, rx) = mpsc::channel::<BuildCommand>();
-        *self.command_tx_lock() = Some(tx);
+    /// Sets the number of builds run at the same time (at least 1).
+    ///
+    /// Extra workers are started right away. When the count shrinks,
+    /// workers stop once their current build is done.
+    pub fn set_worker_count(self: &Arc<Self>, workers: usize) {
+        let workers = workers.max(1);
+        let mut queue = self.queue_lock();
+        queue.workers = workers;
+        while queue.spawned < workers {
+            queue.spawned += 1;
+            let manager = Arc::clone(self);
+            thread::spawn(move || manager.run_worker());
+        }
+        drop(queue);
+        self.queue_changed.notify_all();
+    }
+
+    /// Returns the number of builds run at the same time.
+    pub fn worker_count(&self) -> usize {
+        self.queue_lock().workers
+    }
 
-        let manager = Arc::clone(self);
+    /// Runs queued builds until more workers run than are wanted.
+    fn run_worker(self: &Arc<Self>) {
+        loop {
+            let cmd = {
+                let mut queue = self.queue_lock();
+                loop {
+                    if queue.spawned > queue.workers {
+                        queue.spawned -= 1;
+                        return;
+                    }
+                    if let Some(cmd) = queue.take_next() {
+                        break cmd;
+                    }
+                    queue = self
+                        .queue_changed
+                        .wait(queue)
+                        .unwrap_or_else(std::sync::PoisonError::into_inner);
+                }
+            };
 
-        thread::spawn(move || {
-            for cmd in rx {
-                manager.process_build(cmd);
-            }
-        });
+            self.process_build(&cmd);
+
+            self.queue_lock().active.remove(&cmd.keymap_key());
+            self.queue_changed.notify_all();
+        }
     }
 
     /// Processes a build command.
-    fn process_build(self: &Arc<Self>, cmd: BuildCommand) {
+    fn process_build(self: &Arc<Self>, cmd: &BuildCommand) {
         // Check if cancelled before starting
         if self.is_cancelled(&cmd.job_id) {
+            {
+                let mut count = self.running_count_lock();
+                *count = count.saturating_sub(1);
+            }
             self.update_job_status(&cmd.job_id, JobStatus::Cancelled, None, None, Vec::new());
             return;
         }
@@ -216,6 +409,7 @@ impl BuildJobManager {
                 job.status = JobStatus::Running;
                 job.started_at = Some(chrono::Utc::now().to_rfc3339());
                 job.progress = 10;
+                self.publish_status(job);
             }
         }
 
@@ -225,8 +419,11 @@ impl BuildJobManager {
             .append(true)
             .open(&cmd.log_path);
 
+        let deadline = JobDeadline::start(self.job_timeout());
+
         let (result, deploy_result) = match log_file {
-            Ok(mut file) => {
+            Ok(file) => {
+                let mut file = LogTee::new(file, &self.events, JobKind::Build, &cmd.job_id);
                 let _ = writeln!(file, "[INFO] Build started at {}", chrono::Utc::now());
 
                 // Check if cancelled during setup
@@ -236,20 +433,23 @@ impl BuildJobManager {
                 } else {
                     // Deploy keymap files before building
                     let _ = writeln!(file, "[INFO] Deploying keymap to QMK tree...");
-                    match Self::deploy_keymap(&cmd, &mut file) {
+                    match Self::deploy_keymap(&cmd.target(), &mut file) {
                         Ok(deploy_res) => {
                             let _ = writeln!(file, "[INFO] Keymap deployed successfully");
 
-                            // Create cancellation check closure
+                            // The build stops when cancelled or past its deadline
                             let job_id = cmd.job_id.clone();
                             let manager = Arc::clone(self);
-                            let is_cancelled = move || manager.is_cancelled(&job_id);
+                            let is_cancelled =
+                                move || manager.is_cancelled(&job_id) || deadline.expired();
 
                             // Run the build with cancellation callback
                             let build_result = self.builder.build(
                                 &cmd.qmk_path,
                                 &cmd.keyboard,
                                 &cmd.keymap,
+                                cmd.converter.as_deref(),
+                                self.cpus_per_job(),
                                 &cmd.output_dir,
                                 &cmd.job_id,
                                 &mut file,
@@ -275,35 +475,7 @@ impl BuildJobManager {
         };
 
         // Clean up deployed keymap (before decrementing running_count to prevent race)
-        match &deploy_result {
-            DeployResult::CreatedDirectory(dir) => {
-                if dir.exists() {
-                    if let Err(e) = fs::remove_dir_all(dir) {
-                        tracing::warn!(
-                            dir = %dir.display(),
-                            error = %e,
-                            "failed to clean up created keymap directory"
-                        );
-                    }
-                }
-            }
-            DeployResult::ExistingDirectory(dir) => {
-                // Only remove files we created
-                let keymap_c_path = dir.join("keymap.c");
-                if keymap_c_path.exists() {
-                    if let Err(e) = fs::remove_file(&keymap_c_path) {
-                        tracing::warn!(error = %e, "failed to clean up keymap.c");
-                    }
-                }
-                let config_h_path = dir.join("config.h");
-                if config_h_path.exists() {
-                    if let Err(e) = fs::remove_file(&config_h_path) {
-                        tracing::warn!(error = %e, "failed to clean up config.h");
-                    }
-                }
-            }
-            DeployResult::Skipped => {}
-        }
+        deploy_result.clean_up();
 
         // Decrement running count
         {
@@ -317,6 +489,16 @@ impl BuildJobManager {
             return;
         }
 
+        // A build stopped by the watchdog reports the timeout, not "cancelled"
+        let result = match result {
+            Err(_) if deadline.expired() => {
+                let reason = deadline.reason("Build");
+                self.append_log(&cmd.job_id, &format!("[ERROR] {reason}"));
+                Err(reason)
+            }
+            result => result,
+        };
+
         // Update job with result
         match result {
             Ok(build_result) => {
@@ -349,53 +531,67 @@ impl BuildJobManager {
     /// Returns a `DeployResult` indicating what was created, so that cleanup
     /// can be done safely without removing pre-existing user files.
     fn deploy_keymap(
-        cmd: &BuildCommand,
+        target: &DeployTarget<'_>,
         log_writer: &mut dyn Write,
     ) -> Result<DeployResult, String> {
         // Skip deployment if the layout file doesn't exist (e.g. in tests with mock builders).
         // In production, the HTTP handler validates file existence before starting a build.
-        if !cmd.layout_path.exists() {
+        if !target.layout_path.exists() {
             let _ = writeln!(
                 log_writer,
                 "[WARN] Layout file not found, skipping keymap deployment: {}",
-                cmd.layout_path.display()
+                target.layout_path.display()
             );
             return Ok(DeployResult::Skipped);
         }
 
         // Load the layout
-        let _ = writeln!(log_writer, "[INFO] Loading layout: {}", cmd.layout_filename);
-        let layout = LayoutService::load(&cmd.layout_path)
+        let _ = writeln!(
+            log_writer,
+            "[INFO] Loading layout: {}",
+            target.layout_filename
+        );
+        let layout = LayoutService::load(target.layout_path)
             .map_err(|e| format!("Failed to load layout: {e}"))?;
 
-        // Get layout variant
-        let layout_variant = layout
-            .metadata
-            .layout_variant
-            .as_ref()
-            .ok_or("Layout has no layout variant defined")?;
-
-        let _ = writeln!(log_writer, "[INFO] Layout variant: {layout_variant}");
-
         // Build config with QMK path
         let mut config = Config::load().unwrap_or_default();
-        config.paths.qmk_firmware = Some(cmd.qmk_path.clone());
+        config.pin_qmk_path(target.qmk_path.to_path_buf());
 
         // Build geometry
         let _ = writeln!(log_writer, "[INFO] Building keyboard geometry...");
-        let geo_context = GeometryContext {
-            config: &config,
-            metadata: &layout.metadata,
+        let (layout, geometry, mapping) = if let Some(name) = target.target {
+            let _ = writeln!(log_writer, "[INFO] Keyboard target: {name}");
+            let build = geometry::build_target(&config, &layout, name)
+                .map_err(|e| format!("Failed to prepare keyboard target: {e:#}"))?;
+            if !build.adjustments.is_empty() {
+                let _ = writeln!(
+                    log_writer,
+                    "[INFO] {} key(s) left off, {} position(s) filled with KC_NO",
+                    build.adjustments.removed_count(),
+                    build.adjustments.added_count()
+                );
+            }
+            (build.layout, build.geometry, build.mapping)
+        } else {
+            let layout_variant = layout
+                .metadata
+                .layout_variant
+                .as_ref()
+                .ok_or("Layout has no layout variant defined")?;
+            let _ = writeln!(log_writer, "[INFO] Layout variant: {layout_variant}");
+            let geo_context = GeometryContext {
+                config: &config,
+                metadata: &layout.metadata,
+            };
+            let geo_result = geometry::build_geometry_for_layout(geo_context, layout_variant)
+                .map_err(|e| format!("Failed to build geometry: {e}"))?;
+            (layout, geo_result.geometry, geo_result.mapping)
         };
 
-        let geo_result = geometry::build_geometry_for_layout(geo_context, layout_variant)
-            .map_err(|e| format!("Failed to build geometry: {e}"))?;
-        let geometry = geo_result.geometry;
-        let mapping = geo_result.mapping;
-
         // Validate layout
         let _ = writeln!(log_writer, "[INFO] Validating layout...");
-        let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &cmd.keycode_db);
+        let validator = FirmwareValidator::new(&layout, &geometry, &mapping, target.keycode_db);
         let report = validator
             .validate()
             .map_err(|e| format!("Validation failed: {e}"))?;
@@ -413,7 +609,7 @@ impl BuildJobManager {
         // Generate firmware files
         let _ = writeln!(log_writer, "[INFO] Generating firmware files...");
         let generator =
-            FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &cmd.keycode_db);
+            FirmwareGenerator::new(&layout, &geometry, &mapping, &config, target.keycode_db);
 
         let keymap_c = generator
             .generate_keymap_c()
@@ -433,13 +629,28 @@ impl BuildJobManager {
             config_h.len()
         );
 
+        // Keyboards from an extra keyboard root must be in the QMK tree to compile
+        if let Some(installed) = keyboard_roots::install_keyboard(
+            target.qmk_path,
+            &config.paths.keyboard_roots,
+            target.keyboard,
+        )
+        .map_err(|e| format!("Failed to install keyboard: {e:#}"))?
+        {
+            let _ = writeln!(
+                log_writer,
+                "[INFO] Installed keyboard into {}",
+                installed.display()
+            );
+        }
+
         // Compute keymap directory
-        let keymap_dir = cmd
+        let keymap_dir = target
             .qmk_path
             .join("keyboards")
-            .join(&cmd.keyboard)
+            .join(target.keyboard)
             .join("keymaps")
-            .join(&cmd.keymap);
+            .join(target.keymap);
 
         // Check if directory already exists
         let dir_existed = keymap_dir.exists();
@@ -448,14 +659,36 @@ impl BuildJobManager {
         fs::create_dir_all(&keymap_dir)
             .map_err(|e| format!("Failed to create keymap directory: {e}"))?;
 
-        // Write files to QMK tree
+        // Write files to QMK tree, keeping user regions of replaced files
         let keymap_c_path = keymap_dir.join("keymap.c");
+        let keymap_c = user_code::preserve_from_file(&keymap_c, &keymap_c_path)
+            .map_err(|e| format!("Failed to read existing keymap.c: {e}"))?;
         fs::write(&keymap_c_path, &keymap_c)
             .map_err(|e| format!("Failed to write keymap.c: {e}"))?;
 
         let config_h_path = keymap_dir.join("config.h");
+        let config_h = user_code::preserve_from_file(&config_h, &config_h_path)
+            .map_err(|e| format!("Failed to read existing config.h: {e}"))?;
         fs::write(&config_h_path, &config_h)
             .map_err(|e| format!("Failed to write config.h: {e}"))?;
+        let mut written = vec!["keymap.c", "config.h"];
+
+        // A userspace keymap sets USER_NAME in its rules.mk
+        if config.build.userspace.is_some() {
+            fs::write(keymap_dir.join("rules.mk"), generator.generate_rules_mk())
+                .map_err(|e| format!("Failed to write rules.mk: {e}"))?;
+            written.push("rules.mk");
+            if let Some(created) = generator
+                .create_userspace()
+                .map_err(|e| format!("Failed to create userspace: {e:#}"))?
+            {
+                let _ = writeln!(
+                    log_writer,
+                    "[INFO] Created userspace rules {}",
+                    created.display()
+                );
+            }
+        }
 
         let _ = writeln!(
             log_writer,
@@ -465,7 +698,7 @@ impl BuildJobManager {
 
         // Return appropriate result based on whether directory existed
         if dir_existed {
-            Ok(DeployResult::ExistingDirectory(keymap_dir))
+            Ok(DeployResult::ExistingDirectory(keymap_dir, written))
         } else {
             Ok(DeployResult::CreatedDirectory(keymap_dir))
         }
@@ -497,9 +730,30 @@ impl BuildJobManager {
             job.error = error;
             job.firmware_path = firmware_path;
             job.artifacts = artifacts;
+            self.publish_status(job);
         }
     }
 
+    /// Publishes the current state of `job` to live subscribers.
+    fn publish_status(&self, job: &BuildJob) {
+        self.events
+            .publish(JobEvent::BuildStatus { job: job.clone() });
+    }
+
+    /// Appends `line` to the log of `job_id`, creating the log if needed.
+    fn append_log(&self, job_id: &str, line: &str) {
+        let log_path = self.logs_dir.join(format!("{job_id}.log"));
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(log_path) {
+            let _ = writeln!(file, "{line}");
+        }
+        self.events.log_line(JobKind::Build, job_id, line);
+    }
+
+    /// Subscribes to status changes and log lines of all build jobs.
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<JobEvent> {
+        self.events.subscribe()
+    }
+
     /// Cleans up old artifacts based on age and count limits.
     ///
     /// This method removes artifacts that are:
@@ -596,6 +850,32 @@ impl BuildJobManager {
         layout_filename: String,
         keyboard: String,
         keymap: String,
+        converter: Option<String>,
+        layout_path: PathBuf,
+    ) -> Result<BuildJob, String> {
+        self.start_build_for_target(
+            layout_filename,
+            keyboard,
+            keymap,
+            converter,
+            None,
+            layout_path,
+        )
+    }
+
+    /// Queues a new build job, of the layout's keyboard target `target` if
+    /// set.
+    ///
+    /// `keyboard`, `keymap` and `converter` describe what is built, so for
+    /// a target they come from the target. Returns the created job or an
+    /// error if the build cannot be queued.
+    pub fn start_build_for_target(
+        self: &Arc<Self>,
+        layout_filename: String,
+        keyboard: String,
+        keymap: String,
+        converter: Option<String>,
+        target: Option<String>,
         layout_path: PathBuf,
     ) -> Result<BuildJob, String> {
         // Trigger artifact cleanup in background (async to avoid blocking)
@@ -611,19 +891,19 @@ impl BuildJobManager {
             .unwrap()
             .clone()
             .ok_or_else(|| "QMK firmware path not configured".to_string())?;
+        let qmk_path = layout_qmk_path(&layout_path, qmk_path);
 
-        // Check concurrency limit
-        {
-            let count = self.running_count_lock();
-            if *count >= MAX_CONCURRENT_BUILDS {
-                return Err(
-                    "Build already in progress. Please wait for it to complete.".to_string()
-                );
-            }
+        // Check queue limit
+        if self.queue_lock().pending.len() >= MAX_QUEUED_BUILDS {
+            return Err(format!(
+                "Build queue is full ({MAX_QUEUED_BUILDS} builds waiting). Please wait for some to complete."
+            ));
         }
 
         // Create job
-        let job = BuildJob::new(layout_filename.clone(), keyboard.clone(), keymap.clone());
+        let mut job = BuildJob::new(layout_filename.clone(), keyboard.clone(), keymap.clone());
+        job.converter.clone_from(&converter);
+        job.target.clone_from(&target);
         let job_id = job.id.clone();
 
         // Store job
@@ -631,6 +911,7 @@ impl BuildJobManager {
             let mut jobs = self.jobs_write();
             jobs.insert(job_id.clone(), job.clone());
         }
+        self.publish_status(&job);
 
         // Create log file path
         let log_path = self.logs_dir.join(format!("{job_id}.log"));
@@ -644,12 +925,14 @@ impl BuildJobManager {
             *count += 1;
         }
 
-        // Send command to worker
+        // Queue the command for the workers
         let cmd = BuildCommand {
             job_id,
             layout_filename,
             keyboard,
             keymap,
+            converter,
+            target,
             qmk_path,
             log_path,
             output_dir,
@@ -658,20 +941,84 @@ impl BuildJobManager {
         };
 
         {
-            let tx = self.command_tx_lock();
-            if let Some(sender) = tx.as_ref() {
-                sender
-                    .send(cmd)
-                    .map_err(|e| format!("Failed to queue build: {e}"))?;
-            }
+            let mut queue = self.queue_lock();
+            queue.pending.push_back(cmd);
+            job.queue_position = Some(queue.pending.len());
         }
+        self.queue_changed.notify_all();
 
         Ok(job)
     }
 
+    /// Deploys the keymap for a layout and checks it for C errors without
+    /// building, waiting for the check to finish.
+    ///
+    /// Returns an error if no QMK path is configured, a build of the same
+    /// keymap is running, or the keymap couldn't be deployed or checked.
+    pub fn check_keymap(
+        &self,
+        layout_filename: &str,
+        keyboard: &str,
+        keymap: &str,
+        converter: Option<&str>,
+        layout_path: &Path,
+    ) -> Result<KeymapCheck, String> {
+        let qmk_path = self
+            .qmk_path
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| "QMK firmware path not configured".to_string())?;
+        let qmk_path = layout_qmk_path(layout_path, qmk_path);
+
+        // The check writes the same keymap directory as a build
+        let key = keymap_key(keyboard, keymap);
+        if !self.queue_lock().active.insert(key.clone()) {
+            return Err(
+                "A build of this keymap is in progress. Please wait for it to complete."
+                    .to_string(),
+            );
+        }
+        *self.running_count_lock() += 1;
+
+        let target = DeployTarget {
+            layout_filename,
+            layout_path,
+            keyboard,
+            keymap,
+            target: None,
+            qmk_path: &qmk_path,
+            keycode_db: &self.keycode_db,
+        };
+        let mut deploy_log = Vec::new();
+        let result = Self::deploy_keymap(&target, &mut deploy_log).and_then(|deploy_result| {
+            let check = self.builder.check(&qmk_path, keyboard, keymap, converter);
+            deploy_result.clean_up();
+            check
+        });
+
+        {
+            let mut count = self.running_count_lock();
+            *count = count.saturating_sub(1);
+        }
+        self.queue_lock().active.remove(&key);
+        self.queue_changed.notify_all();
+
+        let mut check = result?;
+        let mut log: Vec<String> = String::from_utf8_lossy(&deploy_log)
+            .lines()
+            .map(String::from)
+            .collect();
+        log.append(&mut check.log);
+        check.log = log;
+        Ok(check)
+    }
+
     /// Gets the status of a job.
     pub fn get_job(&self, job_id: &str) -> Option<BuildJob> {
-        self.jobs_read().get(job_id).cloned()
+        let mut job = self.jobs_read().get(job_id).cloned()?;
+        job.queue_position = self.queue_lock().positions().remove(job_id);
+        Some(job)
     }
 
     /// Gets the logs for a job.
@@ -746,6 +1093,12 @@ impl BuildJobManager {
                     // Mark as cancelled
                     self.cancelled_write().insert(job_id.to_string());
 
+                    // A job still in the queue never reaches a worker
+                    if self.queue_lock().remove(job_id).is_some() {
+                        let mut count = self.running_count_lock();
+                        *count = count.saturating_sub(1);
+                    }
+
                     // Update job status
                     self.update_job_status(job_id, JobStatus::Cancelled, None, None, Vec::new());
 
@@ -767,9 +1120,52 @@ impl BuildJobManager {
         }
     }
 
-    /// Lists all jobs.
+    /// Returns the number of pending or running jobs.
+    pub fn active_job_count(&self) -> usize {
+        self.jobs_read()
+            .values()
+            .filter(|job| matches!(job.status, JobStatus::Pending | JobStatus::Running))
+            .count()
+    }
+
+    /// Cancels every pending or running job, noting `reason` in each job's log.
+    ///
+    /// Returns the number of jobs cancelled.
+    pub fn cancel_all(&self, reason: &str) -> usize {
+        let active: Vec<String> = self
+            .jobs_read()
+            .values()
+            .filter(|job| matches!(job.status, JobStatus::Pending | JobStatus::Running))
+            .map(|job| job.id.clone())
+            .collect();
+
+        for job_id in &active {
+            self.append_log(job_id, &format!("[WARN] {reason}"));
+            self.cancel_job(job_id);
+        }
+        active.len()
+    }
+
+    /// Returns true when the workers have no queued or running build left.
+    ///
+    /// Cancelled jobs only count as finished once their worker has cleaned
+    /// up the keymap files it deployed into the QMK tree.
+    pub fn is_idle(&self) -> bool {
+        *self.running_count_lock() == 0
+    }
+
+    /// Lists all jobs, newest first, with the queue position of pending ones.
     pub fn list_jobs(&self) -> Vec<BuildJob> {
-        let mut list: Vec<_> = self.jobs_read().values().cloned().collect();
+        let positions = self.queue_lock().positions();
+        let mut list: Vec<_> = self
+            .jobs_read()
+            .values()
+            .cloned()
+            .map(|mut job| {
+                job.queue_position = positions.get(&job.id).copied();
+                job
+            })
+            .collect();
         list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
         list
     }
@@ -779,6 +1175,42 @@ impl BuildJobManager {
         *self.qmk_path_write() = path;
     }
 
+    /// Sets how long a build may run before it is stopped (None = no limit).
+    ///
+    /// Applies to builds started afterwards.
+    pub fn set_job_timeout(&self, timeout: Option<Duration>) {
+        *self
+            .job_timeout
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = timeout;
+    }
+
+    /// Returns the current build time limit.
+    fn job_timeout(&self) -> Option<Duration> {
+        *self
+            .job_timeout
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Sets how many CPUs each build may use (0 = QMK's default).
+    ///
+    /// Applies to builds started afterwards.
+    pub fn set_cpus_per_job(&self, cpus: usize) {
+        *self
+            .cpus_per_job
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = (cpus > 0).then_some(cpus);
+    }
+
+    /// Returns the current per-build CPU limit.
+    fn cpus_per_job(&self) -> Option<usize> {
+        *self
+            .cpus_per_job
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
     /// Gets the artifacts for a completed job.
     pub fn get_artifacts(&self, job_id: &str) -> Option<Vec<BuildArtifact>> {
         self.jobs
@@ -7,11 +7,25 @@
 //! ## Design
 //!
 //! - Jobs are identified by UUIDs
-//! - Concurrency limit of 1 (single build at a time)
+//! - A pool of `web.build_workers` threads (1 by default) runs builds from
+//!   a shared queue
 //! - Logs are persisted to disk for durability
-//! - Uses mpsc channels for thread communication
 //! - Firmware artifacts (.uf2/.bin/.hex) are copied to job-specific directories
 //!
+//! ## Queue
+//!
+//! Started builds wait in a FIFO queue until a worker is free. Builds of
+//! the same keyboard and keymap write the same keymap directory in the QMK
+//! tree, so a worker skips over a queued build while another one for the
+//! same keymap runs. Pending jobs report their place in the queue as
+//! `queue_position` (1 = next). At most [`MAX_QUEUED_BUILDS`] builds wait at
+//! a time.
+//!
+//! Each build passes `web.build_cpus_per_job` to `qmk compile -j`, so
+//! parallel builds don't each start a compiler per CPU. A build of a
+//! layout's keyboard target (`target` in the request) deploys the layout
+//! as seen by that target.
+//!
 //! ## Artifact Management
 //!
 //! After a successful build, firmware artifacts are discovered in QMK's `.build`
@@ -36,6 +50,21 @@
 //! - Build logs reflect the cancellation event
 //! - Partial artifacts are preserved (not automatically cleaned)
 //!
+//! ## Timeouts
+//!
+//! Builds running longer than `web.job_timeout_secs` (30 minutes by default,
+//! 0 disables the limit) are stopped like a cancelled build and marked
+//! `Failed` with a message explaining the timeout. `qmk` runs in its own
+//! process group, so `make` and the compilers are killed with it.
+//!
+//! ## Keymap Checks
+//!
+//! [`BuildJobManager::check_keymap`] deploys the keymap and runs the
+//! syntax-only preflight from [`crate::firmware::builder::check_keymap`]
+//! while the caller waits, instead of queueing a job. It is refused while a
+//! build of the same keymap runs, since both write the keymap into the QMK
+//! tree, and queued builds of the keymap wait for it.
+//!
 //! ## Mock Support
 //!
 //! For testing, a mock builder can be injected that simulates builds without
@@ -54,6 +83,8 @@ use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::firmware::builder::KeymapCheck;
+
 #[cfg(test)]
 use crate::keycode_db::KeycodeDb;
 #[cfg(test)]
@@ -64,10 +95,11 @@ use std::thread;
 pub(crate) use builders::is_valid_artifact_id;
 pub use builders::{MockFirmwareBuilder, RealFirmwareBuilder};
 pub(crate) use log_parse::parse_log_line;
+pub(crate) use manager::layout_qmk_path;
 pub use manager::BuildJobManager;
 
-/// Maximum number of concurrent builds.
-const MAX_CONCURRENT_BUILDS: usize = 1;
+/// Maximum number of builds waiting in the queue.
+const MAX_QUEUED_BUILDS: usize = 32;
 
 /// Supported firmware artifact extensions.
 const ARTIFACT_EXTENSIONS: &[&str] = &["uf2", "bin", "hex"];
@@ -131,6 +163,15 @@ pub struct BuildJob {
     pub keyboard: String,
     /// Keymap name.
     pub keymap: String,
+    /// QMK converter (`CONVERT_TO`) for a replacement controller.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converter: Option<String>,
+    /// Keyboard target of the layout being built, if not its primary keyboard.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Place in the build queue while pending (1 = next to start).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_position: Option<usize>,
     /// Time when job was created.
     pub created_at: String,
     /// Time when job started running (if started).
@@ -162,6 +203,9 @@ impl BuildJob {
             layout_filename,
             keyboard,
             keymap,
+            converter: None,
+            target: None,
+            queue_position: None,
             created_at: chrono::Utc::now().to_rfc3339(),
             started_at: None,
             completed_at: None,
@@ -189,6 +233,10 @@ pub struct LogEntry {
 pub struct StartBuildRequest {
     /// Layout filename to build.
     pub layout_filename: String,
+    /// Build this keyboard target of the layout instead of its primary
+    /// keyboard.
+    #[serde(default)]
+    pub target: Option<String>,
 }
 
 /// Response for starting a build job.
@@ -242,6 +290,8 @@ pub trait FirmwareBuilder: Send + Sync {
     /// * `qmk_path` - Path to QMK firmware directory
     /// * `keyboard` - Keyboard identifier
     /// * `keymap` - Keymap name
+    /// * `converter` - QMK converter passed as `CONVERT_TO`, if any
+    /// * `parallel_jobs` - CPUs the compile may use (`qmk compile -j`), if limited
     /// * `output_dir` - Directory to copy artifacts into
     /// * `job_id` - Job identifier (for generating download URLs)
     /// * `log_writer` - Writer for build log output
@@ -253,9 +303,23 @@ pub trait FirmwareBuilder: Send + Sync {
         qmk_path: &PathBuf,
         keyboard: &str,
         keymap: &str,
+        converter: Option<&str>,
+        parallel_jobs: Option<usize>,
         output_dir: &Path,
         job_id: &str,
         log_writer: &mut dyn Write,
         is_cancelled: &dyn Fn() -> bool,
     ) -> Result<BuildResult, String>;
+
+    /// Checks the keymap already deployed to the QMK tree for C errors
+    /// without building the firmware.
+    ///
+    /// Returns `Err(error_message)` if the check couldn't run.
+    fn check(
+        &self,
+        qmk_path: &Path,
+        keyboard: &str,
+        keymap: &str,
+        converter: Option<&str>,
+    ) -> Result<KeymapCheck, String>;
 }
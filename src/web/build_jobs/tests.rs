@@ -4,7 +4,6 @@
 
 use super::*;
 
-use super::*;
 use std::time::Duration;
 
 fn test_keycode_db() -> Arc<KeycodeDb> {
@@ -69,6 +68,7 @@ fn test_start_build_success() {
         "test.md".to_string(),
         "crkbd".to_string(),
         "default".to_string(),
+        None,
         dummy_layout_path(),
     );
 
@@ -101,6 +101,7 @@ fn test_start_build_no_qmk_path() {
         "test.md".to_string(),
         "crkbd".to_string(),
         "default".to_string(),
+        None,
         dummy_layout_path(),
     );
 
@@ -131,6 +132,7 @@ fn test_cancel_job() {
             "test.md".to_string(),
             "crkbd".to_string(),
             "default".to_string(),
+            None,
             dummy_layout_path(),
         )
         .unwrap();
@@ -146,6 +148,54 @@ fn test_cancel_job() {
     assert_eq!(updated.status, JobStatus::Cancelled);
 }
 
+#[test]
+fn test_cancel_all_leaves_worker_idle() {
+    let temp_dir = std::env::temp_dir().join(format!("lazyqmk_test_{}", Uuid::new_v4()));
+    let manager = BuildJobManager::with_builder(
+        temp_dir.join("logs"),
+        temp_dir.join("output"),
+        Some(PathBuf::from("/tmp/qmk")),
+        Arc::new(MockFirmwareBuilder {
+            build_duration_ms: 300,
+            should_succeed: true,
+            error_message: None,
+        }),
+        test_keycode_db(),
+    );
+
+    let job = manager
+        .start_build(
+            "test.md".to_string(),
+            "crkbd".to_string(),
+            "default".to_string(),
+            None,
+            dummy_layout_path(),
+        )
+        .unwrap();
+    assert_eq!(manager.active_job_count(), 1);
+
+    assert_eq!(manager.cancel_all("Cancelled: server shutting down"), 1);
+    assert_eq!(manager.active_job_count(), 0);
+    assert_eq!(manager.cancel_all("again"), 0);
+
+    // The worker releases its slot even for jobs cancelled before they started
+    for _ in 0..40 {
+        if manager.is_idle() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(25));
+    }
+    assert!(manager.is_idle());
+    assert_eq!(
+        manager.get_job(&job.id).unwrap().status,
+        JobStatus::Cancelled
+    );
+
+    let log_path = temp_dir.join("logs").join(format!("{}.log", job.id));
+    let log = std::fs::read_to_string(log_path).unwrap();
+    assert!(log.contains("server shutting down"));
+}
+
 #[test]
 fn test_list_jobs() {
     let manager = create_test_manager();
@@ -155,6 +205,7 @@ fn test_list_jobs() {
         "a.md".to_string(),
         "crkbd".to_string(),
         "default".to_string(),
+        None,
         dummy_layout_path(),
     );
     thread::sleep(Duration::from_millis(10));
@@ -162,6 +213,7 @@ fn test_list_jobs() {
         "b.md".to_string(),
         "crkbd".to_string(),
         "test".to_string(),
+        None,
         dummy_layout_path(),
     );
 
@@ -170,6 +222,103 @@ fn test_list_jobs() {
     assert!(!jobs.is_empty());
 }
 
+fn create_slow_manager(build_duration_ms: u64) -> Arc<BuildJobManager> {
+    let temp_dir = std::env::temp_dir().join(format!("lazyqmk_test_{}", Uuid::new_v4()));
+    BuildJobManager::with_builder(
+        temp_dir.join("logs"),
+        temp_dir.join("output"),
+        Some(PathBuf::from("/tmp/qmk")),
+        Arc::new(MockFirmwareBuilder {
+            build_duration_ms,
+            should_succeed: true,
+            error_message: None,
+        }),
+        test_keycode_db(),
+    )
+}
+
+fn start_mock_build(manager: &Arc<BuildJobManager>, keymap: &str) -> BuildJob {
+    manager
+        .start_build(
+            "test.md".to_string(),
+            "crkbd".to_string(),
+            keymap.to_string(),
+            None,
+            dummy_layout_path(),
+        )
+        .unwrap()
+}
+
+fn wait_until_idle(manager: &BuildJobManager) {
+    for _ in 0..80 {
+        if manager.is_idle() {
+            return;
+        }
+        thread::sleep(Duration::from_millis(25));
+    }
+    panic!("builds did not finish");
+}
+
+#[test]
+fn test_queued_builds_report_position() {
+    let manager = create_slow_manager(300);
+
+    let first = start_mock_build(&manager, "a");
+    let second = start_mock_build(&manager, "b");
+    let third = start_mock_build(&manager, "c");
+    assert!(third.queue_position.is_some());
+
+    // The single worker has taken the first build; the others wait in order
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(manager.get_job(&first.id).unwrap().queue_position, None);
+    assert_eq!(manager.get_job(&second.id).unwrap().queue_position, Some(1));
+    let listed = manager.list_jobs();
+    let third_listed = listed.iter().find(|job| job.id == third.id).unwrap();
+    assert_eq!(third_listed.queue_position, Some(2));
+
+    // A cancelled queued build leaves the queue and never runs
+    assert!(manager.cancel_job(&second.id).success);
+    assert_eq!(manager.get_job(&second.id).unwrap().queue_position, None);
+    assert_eq!(manager.get_job(&third.id).unwrap().queue_position, Some(1));
+
+    wait_until_idle(&manager);
+    assert_eq!(
+        manager.get_job(&second.id).unwrap().status,
+        JobStatus::Cancelled
+    );
+    assert!(manager.get_job(&second.id).unwrap().started_at.is_none());
+    assert_eq!(
+        manager.get_job(&third.id).unwrap().status,
+        JobStatus::Completed
+    );
+}
+
+#[test]
+fn test_worker_pool_runs_builds_in_parallel() {
+    let manager = create_slow_manager(300);
+    manager.set_worker_count(2);
+    assert_eq!(manager.worker_count(), 2);
+
+    let a = start_mock_build(&manager, "a");
+    let b = start_mock_build(&manager, "b");
+    // Same keymap as `a`: waits for it even though a worker is free
+    let c = start_mock_build(&manager, "a");
+
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(manager.get_job(&a.id).unwrap().status, JobStatus::Running);
+    assert_eq!(manager.get_job(&b.id).unwrap().status, JobStatus::Running);
+    assert_eq!(manager.get_job(&c.id).unwrap().status, JobStatus::Pending);
+    assert_eq!(manager.get_job(&c.id).unwrap().queue_position, Some(1));
+
+    wait_until_idle(&manager);
+    for job in [&a, &b, &c] {
+        assert_eq!(
+            manager.get_job(&job.id).unwrap().status,
+            JobStatus::Completed
+        );
+    }
+}
+
 #[test]
 fn test_parse_log_line() {
     let (level, msg) = parse_log_line("[INFO] Build started");
@@ -206,6 +355,7 @@ fn test_mock_builder_failure() {
             "test.md".to_string(),
             "crkbd".to_string(),
             "default".to_string(),
+            None,
             dummy_layout_path(),
         )
         .unwrap();
@@ -247,6 +397,7 @@ fn test_build_job_has_artifacts_after_success() {
             "test.md".to_string(),
             "crkbd".to_string(),
             "default".to_string(),
+            None,
             dummy_layout_path(),
         )
         .unwrap();
@@ -280,6 +431,7 @@ fn test_get_artifact_path_validates_artifact_id() {
             "test.md".to_string(),
             "crkbd".to_string(),
             "default".to_string(),
+            None,
             dummy_layout_path(),
         )
         .unwrap();
@@ -312,6 +464,7 @@ fn test_cancel_job_interrupts_mock_build() {
             "test.md".to_string(),
             "crkbd".to_string(),
             "default".to_string(),
+            None,
             dummy_layout_path(),
         )
         .unwrap();
@@ -330,6 +483,45 @@ fn test_cancel_job_interrupts_mock_build() {
     assert_eq!(updated.status, JobStatus::Cancelled);
 }
 
+#[test]
+fn test_build_past_timeout_fails() {
+    let temp_dir = std::env::temp_dir().join(format!("lazyqmk_test_{}", Uuid::new_v4()));
+    let mock_builder = Arc::new(MockFirmwareBuilder {
+        build_duration_ms: 2000,
+        should_succeed: true,
+        error_message: None,
+    });
+    let manager = BuildJobManager::with_builder(
+        temp_dir.join("logs"),
+        temp_dir.join("output"),
+        Some(PathBuf::from("/tmp/qmk")),
+        mock_builder,
+        test_keycode_db(),
+    );
+    manager.set_job_timeout(Some(Duration::from_millis(100)));
+
+    let job = manager
+        .start_build(
+            "test.md".to_string(),
+            "crkbd".to_string(),
+            "default".to_string(),
+            None,
+            dummy_layout_path(),
+        )
+        .unwrap();
+
+    thread::sleep(Duration::from_millis(800));
+
+    let updated = manager.get_job(&job.id).unwrap();
+    assert_eq!(updated.status, JobStatus::Failed);
+    assert!(updated.error.unwrap().contains("timed out"));
+    let log = manager.get_logs(&job.id, 0, 1000).unwrap();
+    assert!(log
+        .logs
+        .iter()
+        .any(|entry| entry.message.contains("timed out")));
+}
+
 #[test]
 fn test_cleanup_removes_old_artifacts() {
     let manager = create_test_manager();
@@ -340,6 +532,7 @@ fn test_cleanup_removes_old_artifacts() {
             "test1.md".to_string(),
             "crkbd".to_string(),
             "default".to_string(),
+            None,
             dummy_layout_path(),
         )
         .unwrap();
@@ -381,6 +574,7 @@ fn test_cleanup_preserves_active_jobs() {
             "running.md".to_string(),
             "crkbd".to_string(),
             "default".to_string(),
+            None,
             dummy_layout_path(),
         )
         .unwrap();
@@ -0,0 +1,155 @@
+//! Live change broadcast between web sessions editing the same layout.
+//!
+//! Mutating layout handlers publish what they changed to a per-layout
+//! channel, and `GET /api/layouts/{filename}/events` streams those events to
+//! every open session as Server-Sent Events. Key edits are granular (one
+//! event per changed key), so concurrent edits to different keys merge and
+//! edits to the same key resolve last-write-wins in the order the server
+//! applied them. Changes that rewrite the whole layout (full save, fragment
+//! apply, variant switch) send a single `layout_replaced` event telling
+//! clients to reload.
+//!
+//! Sessions tag their requests with the `X-LazyQMK-Client` header; each event
+//! carries that id as `origin` so a session can skip echoes of its own edits.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use serde::Serialize;
+use tokio::sync::{broadcast, OwnedMutexGuard};
+
+use crate::models::KeyDefinition;
+
+/// Events buffered per layout before a slow subscriber starts lagging.
+const CHANNEL_CAPACITY: usize = 256;
+
+/// What changed in a layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LayoutChange {
+    /// One key was updated; `key` is its full new state
+    KeyUpdated {
+        /// Layer index
+        layer: usize,
+        /// New key definition (includes its position)
+        key: KeyDefinition,
+    },
+    /// The layout was rewritten as a whole; clients should reload it
+    LayoutReplaced,
+}
+
+/// A change broadcast to the sessions watching a layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LayoutEvent {
+    /// Server-wide sequence number; later events win over earlier ones
+    pub seq: u64,
+    /// Client id of the session that made the change, if it sent one
+    pub origin: Option<String>,
+    /// User who made the change
+    pub actor: String,
+    /// The change itself
+    #[serde(flatten)]
+    pub change: LayoutChange,
+}
+
+/// Per-layout event channels and edit locks.
+#[derive(Debug, Default)]
+pub struct CollabHub {
+    channels: Mutex<HashMap<String, broadcast::Sender<LayoutEvent>>>,
+    edit_locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+    next_seq: AtomicU64,
+}
+
+impl CollabHub {
+    /// Creates a hub with no subscribers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribes to the changes of `filename`.
+    pub fn subscribe(&self, filename: &str) -> broadcast::Receiver<LayoutEvent> {
+        let mut channels = self.channels.lock().unwrap_or_else(PoisonError::into_inner);
+        channels.retain(|_, tx| tx.receiver_count() > 0);
+        channels
+            .entry(filename.to_string())
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Returns the number of sessions watching `filename`.
+    #[must_use]
+    pub fn subscriber_count(&self, filename: &str) -> usize {
+        self.channels
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(filename)
+            .map_or(0, broadcast::Sender::receiver_count)
+    }
+
+    /// Returns the filenames of the layouts that have sessions watching them.
+    #[must_use]
+    pub fn watched_layouts(&self) -> Vec<String> {
+        let mut watched: Vec<String> = self
+            .channels
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|(_, tx)| tx.receiver_count() > 0)
+            .map(|(filename, _)| filename.clone())
+            .collect();
+        watched.sort();
+        watched
+    }
+
+    /// Sends `changes` to every session watching `filename`, in order.
+    pub fn publish(
+        &self,
+        filename: &str,
+        origin: Option<&str>,
+        actor: &str,
+        changes: impl IntoIterator<Item = LayoutChange>,
+    ) {
+        let Some(tx) = self
+            .channels
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(filename)
+            .cloned()
+        else {
+            return;
+        };
+
+        for change in changes {
+            let event = LayoutEvent {
+                seq: self.next_seq.fetch_add(1, Ordering::Relaxed) + 1,
+                origin: origin.map(str::to_string),
+                actor: actor.to_string(),
+                change,
+            };
+            // No receivers left is fine; the channel is pruned on next subscribe.
+            let _ = tx.send(event);
+        }
+    }
+
+    /// Waits for exclusive edit access to `filename`.
+    ///
+    /// Held across load-modify-save so concurrent edits to different keys of
+    /// the same layout are applied one after another instead of overwriting
+    /// each other.
+    pub async fn lock_layout(&self, filename: &str) -> OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self
+                .edit_locks
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+            Arc::clone(locks.entry(filename.to_string()).or_default())
+        };
+        lock.lock_owned().await
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,100 @@
+//! Tests for the live change broadcast hub.
+
+use super::*;
+
+use std::time::Duration;
+
+use crate::models::Position;
+
+fn key_change(keycode: &str) -> LayoutChange {
+    LayoutChange::KeyUpdated {
+        layer: 0,
+        key: KeyDefinition::new(Position::new(0, 1), keycode),
+    }
+}
+
+#[test]
+fn test_publish_without_subscribers_is_noop() {
+    let hub = CollabHub::new();
+    hub.publish("a.json", None, "web", [LayoutChange::LayoutReplaced]);
+    assert_eq!(hub.subscriber_count("a.json"), 0);
+}
+
+#[test]
+fn test_subscribers_receive_events_in_order() {
+    let hub = CollabHub::new();
+    let mut rx = hub.subscribe("a.json");
+    let mut other = hub.subscribe("b.json");
+
+    hub.publish(
+        "a.json",
+        Some("tab-1"),
+        "alice",
+        [key_change("KC_A"), key_change("KC_B")],
+    );
+
+    let first = rx.try_recv().unwrap();
+    let second = rx.try_recv().unwrap();
+    assert_eq!(first.origin.as_deref(), Some("tab-1"));
+    assert_eq!(first.actor, "alice");
+    assert_eq!(first.change, key_change("KC_A"));
+    assert_eq!(second.change, key_change("KC_B"));
+    assert!(second.seq > first.seq);
+    assert!(other.try_recv().is_err());
+}
+
+#[test]
+fn test_event_serializes_flat_with_type_tag() {
+    let event = LayoutEvent {
+        seq: 7,
+        origin: None,
+        actor: "web".to_string(),
+        change: key_change("KC_A"),
+    };
+    let json = serde_json::to_value(&event).unwrap();
+
+    assert_eq!(json["type"], "key_updated");
+    assert_eq!(json["seq"], 7);
+    assert_eq!(json["layer"], 0);
+    assert_eq!(json["key"]["keycode"], "KC_A");
+}
+
+#[test]
+fn test_dropped_subscribers_are_pruned() {
+    let hub = CollabHub::new();
+    drop(hub.subscribe("a.json"));
+    let _rx = hub.subscribe("b.json");
+
+    assert_eq!(hub.subscriber_count("a.json"), 0);
+    assert!(!hub.channels.lock().unwrap().contains_key("a.json"));
+}
+
+#[test]
+fn test_watched_layouts_lists_layouts_with_subscribers() {
+    let hub = CollabHub::new();
+    let _b = hub.subscribe("b.json");
+    let _a = hub.subscribe("a.json");
+    drop(hub.subscribe("c.json"));
+
+    assert_eq!(hub.watched_layouts(), vec!["a.json", "b.json"]);
+}
+
+#[tokio::test]
+async fn test_lock_layout_serializes_edits_per_layout() {
+    let hub = CollabHub::new();
+    let guard = hub.lock_layout("a.json").await;
+
+    // A different layout is not blocked.
+    let _other = hub.lock_layout("b.json").await;
+
+    let blocked = tokio::time::timeout(Duration::from_millis(50), hub.lock_layout("a.json"))
+        .await
+        .is_err();
+    assert!(blocked);
+
+    drop(guard);
+    let reacquired = tokio::time::timeout(Duration::from_millis(50), hub.lock_layout("a.json"))
+        .await
+        .is_ok();
+    assert!(reacquired);
+}
@@ -4,11 +4,20 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::config::LintSeverity;
+use crate::firmware::validator::{ValidationErrorKind, ValidationReport};
 use crate::keycode_db::{KeycodeCategory, KeycodeDefinition};
 use crate::models::{
-    ComboSettings, IdleEffectSettings, RgbColor, RgbOverlayRippleSettings, TapDanceAction,
-    TapHoldSettings,
+    ComboSettings, IdleEffectSettings, KeyTappingTerm, RgbColor, RgbOverlayRippleSettings,
+    TapDanceAction, TapHoldSettings,
 };
+use crate::services::analysis::{Heatmap, LayoutAnalysis};
+use crate::services::audit::AuditEntry;
+use crate::services::backup::BackupInfo;
+use crate::services::fragments::{FragmentApplyReport, LayerFragment};
+use crate::services::git::{GitCommit, GitFileStatus};
+use crate::services::lints::Lint;
+use crate::services::qmk_env::{QmkEnvReport, SetupStep};
 
 /// Health check response.
 #[derive(Debug, Serialize)]
@@ -82,6 +91,29 @@ impl From<&KeycodeDefinition> for KeycodeInfo {
     }
 }
 
+/// Favorite and recent keycodes of the keycode picker.
+#[derive(Debug, Serialize)]
+pub struct KeycodeFavoritesResponse {
+    /// Favorite keycodes, in the user's order.
+    pub favorites: Vec<String>,
+    /// Recently picked keycodes, most recent first.
+    pub recent: Vec<String>,
+}
+
+/// Request body for `POST /api/keycodes/favorites`.
+#[derive(Debug, Deserialize)]
+pub struct AddFavoriteKeycodeRequest {
+    /// Keycode to add.
+    pub keycode: String,
+}
+
+/// Request body for `PUT /api/keycodes/favorites`.
+#[derive(Debug, Deserialize)]
+pub struct SetFavoriteKeycodesRequest {
+    /// Favorite keycodes in their new order.
+    pub favorites: Vec<String>,
+}
+
 /// Category list response.
 #[derive(Debug, Serialize)]
 pub struct CategoryListResponse {
@@ -119,6 +151,22 @@ pub struct ConfigResponse {
     pub output_dir: String,
     /// Workspace root directory where layout files are stored.
     pub workspace_root: String,
+    /// Whether the server rejects all mutating requests.
+    pub read_only: bool,
+    /// Whether `POST /api/shutdown` is accepted.
+    pub shutdown_enabled: bool,
+    /// Whether the server runs in sandbox mode (layouts in memory, nothing
+    /// written outside a temporary directory).
+    pub sandbox: bool,
+    /// Seconds a build or generate job may run (0 = no limit).
+    pub job_timeout_secs: u64,
+    /// Number of firmware builds run at the same time.
+    pub build_workers: usize,
+    /// CPUs each build may use (0 = QMK's default).
+    pub build_cpus_per_job: usize,
+    /// Names of the QMK trees a layout can select as `qmk_tree`, the
+    /// default tree first.
+    pub qmk_trees: Vec<String>,
 }
 
 /// Configuration update request.
@@ -126,6 +174,15 @@ pub struct ConfigResponse {
 pub struct ConfigUpdateRequest {
     /// New path to QMK firmware directory.
     pub qmk_firmware_path: Option<String>,
+    /// New job time limit in seconds (0 = no limit).
+    #[serde(default)]
+    pub job_timeout_secs: Option<u64>,
+    /// New number of builds run at the same time.
+    #[serde(default)]
+    pub build_workers: Option<usize>,
+    /// New per-build CPU limit (0 = QMK's default).
+    #[serde(default)]
+    pub build_cpus_per_job: Option<usize>,
 }
 
 /// Swap keys request.
@@ -148,6 +205,38 @@ pub struct KeyPosition {
     pub col: u8,
 }
 
+/// Single-key assignment request.
+///
+/// Omitted fields are left unchanged; `null` clears `color_override` or
+/// `category_id`.
+#[derive(Debug, Default, Deserialize)]
+#[allow(clippy::option_option)] // Outer None = omitted, Some(None) = explicit null
+pub struct AssignKeyRequest {
+    /// New QMK keycode.
+    #[serde(default)]
+    pub keycode: Option<String>,
+    /// New individual color override (`null` clears it).
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub color_override: Option<Option<RgbColor>>,
+    /// New category assignment (`null` clears it).
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub category_id: Option<Option<String>>,
+    /// Lock or unlock the key against bulk edits.
+    #[serde(default)]
+    pub locked: Option<bool>,
+}
+
+/// Deserializes a field that is present in the payload as `Some`, so that an
+/// explicit `null` can be told apart from an omitted field.
+#[allow(clippy::option_option)] // See `AssignKeyRequest`
+fn deserialize_present<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
 /// Preflight check response for onboarding flow.
 #[derive(Debug, Serialize)]
 pub struct PreflightResponse {
@@ -159,6 +248,18 @@ pub struct PreflightResponse {
     pub first_run: bool,
     /// QMK firmware path if configured.
     pub qmk_firmware_path: Option<String>,
+    /// Health check of the QMK checkout and build tools, with fixes.
+    pub environment: QmkEnvReport,
+}
+
+/// Request to run a QMK setup step from the onboarding flow.
+#[derive(Debug, Deserialize)]
+pub struct PreflightSetupRequest {
+    /// Step to run (`clone` or `submodules`).
+    pub step: SetupStep,
+    /// Checkout to set up; the configured QMK path if omitted.
+    #[serde(default)]
+    pub qmk_path: Option<String>,
 }
 
 // ============================================================================
@@ -175,6 +276,95 @@ pub struct ValidationResponse {
     pub error: Option<String>,
     /// List of warnings (non-fatal issues).
     pub warnings: Vec<String>,
+    /// Findings of the layout lints, errors and warnings.
+    pub lints: Vec<LintDto>,
+}
+
+/// A finding of a layout lint.
+#[derive(Debug, Serialize)]
+pub struct LintDto {
+    /// Rule ID, e.g. "transparent_on_base".
+    pub rule: &'static str,
+    /// Configured severity of the rule.
+    pub severity: LintSeverity,
+    /// Human-readable message.
+    pub message: String,
+    /// Layer index, if the finding is tied to a layer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layer: Option<usize>,
+    /// Row, if the finding is tied to a key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row: Option<u8>,
+    /// Column, if the finding is tied to a key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub col: Option<u8>,
+}
+
+impl From<Lint> for LintDto {
+    fn from(lint: Lint) -> Self {
+        Self {
+            rule: lint.rule.id(),
+            severity: lint.severity,
+            message: lint.message,
+            layer: lint.layer,
+            row: lint.position.map(|p| p.row),
+            col: lint.position.map(|p| p.col),
+        }
+    }
+}
+
+/// Structured validation findings attached to a refused generate/build job.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobValidationDto {
+    /// Errors that block the job.
+    pub errors: Vec<ValidationIssueDto>,
+    /// Non-blocking warnings.
+    pub warnings: Vec<String>,
+}
+
+/// A single validation error with its location.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationIssueDto {
+    /// Error category.
+    pub kind: ValidationErrorKind,
+    /// Layer index, if the error is tied to a layer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layer: Option<usize>,
+    /// Visual row, if the error is tied to a key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row: Option<u8>,
+    /// Visual column, if the error is tied to a key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub col: Option<u8>,
+    /// Human-readable key name (e.g., "left home row pinky"), if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_name: Option<String>,
+    /// Human-readable message.
+    pub message: String,
+    /// Optional fix suggestion.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl From<&ValidationReport> for JobValidationDto {
+    fn from(report: &ValidationReport) -> Self {
+        Self {
+            errors: report
+                .errors
+                .iter()
+                .map(|e| ValidationIssueDto {
+                    kind: e.kind,
+                    layer: e.layer,
+                    row: e.row,
+                    col: e.col,
+                    position_name: e.position_name.clone(),
+                    message: e.message.clone(),
+                    suggestion: e.suggestion.clone(),
+                })
+                .collect(),
+            warnings: report.warnings.iter().map(ToString::to_string).collect(),
+        }
+    }
 }
 
 /// Inspect response with layout details.
@@ -287,6 +477,16 @@ pub struct KeyDisplayDto {
     pub tertiary: Option<String>,
 }
 
+impl From<crate::keycode_db::KeyDisplay> for KeyDisplayDto {
+    fn from(display: crate::keycode_db::KeyDisplay) -> Self {
+        Self {
+            primary: display.primary,
+            secondary: display.secondary,
+            tertiary: display.tertiary,
+        }
+    }
+}
+
 /// Type of action in a multi-action keycode.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -329,6 +529,292 @@ pub struct KeyDetailActionDto {
     pub description: String,
 }
 
+impl From<crate::keycode_db::KeyDetailAction> for KeyDetailActionDto {
+    fn from(action: crate::keycode_db::KeyDetailAction) -> Self {
+        Self {
+            kind: action.kind.into(),
+            code: action.code,
+            description: action.description,
+        }
+    }
+}
+
+/// Query parameters for keycode display preview.
+#[derive(Debug, Deserialize)]
+pub struct KeycodeDisplayQuery {
+    /// Keycode to preview (e.g., "LT(2,KC_SPC)").
+    pub code: String,
+    /// Optional layout filename used to resolve tap dances and `@uuid` layer refs.
+    pub layout: Option<String>,
+}
+
+/// Display preview for an arbitrary keycode.
+#[derive(Debug, Clone, Serialize)]
+pub struct KeycodeDisplayResponse {
+    /// The keycode as given in the request.
+    pub code: String,
+    /// Whether the keycode is known to the keycode database.
+    pub valid: bool,
+    /// Short labels for in-key display
+    pub display: KeyDisplayDto,
+    /// Full action breakdown for Key Details panel
+    pub details: Vec<KeyDetailActionDto>,
+}
+
+/// Query parameters for the audit log endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditLogQuery {
+    /// Return only the most recent N entries.
+    pub limit: Option<usize>,
+}
+
+/// Query parameters for the key search endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct KeySearchQuery {
+    /// Text to find in keycodes, labels and descriptions. Empty lists the
+    /// keys that have a label or description.
+    #[serde(default)]
+    pub q: String,
+}
+
+/// A key matching a search query.
+#[derive(Debug, Serialize)]
+pub struct KeySearchMatchDto {
+    /// Layer index.
+    pub layer: usize,
+    /// Layer name.
+    pub layer_name: String,
+    /// Row of the key.
+    pub row: u8,
+    /// Column of the key.
+    pub col: u8,
+    /// Assigned keycode.
+    pub keycode: String,
+    /// Custom label.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Key description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Keys of a layout matching a search query, ordered by layer.
+#[derive(Debug, Serialize)]
+pub struct KeySearchResponse {
+    /// Layout filename.
+    pub filename: String,
+    /// The query as given.
+    pub query: String,
+    /// Matching keys.
+    pub matches: Vec<KeySearchMatchDto>,
+}
+
+/// A key of a layer with transparency resolved through the layers below.
+#[derive(Debug, Serialize)]
+pub struct ResolvedKeyDto {
+    /// Row of the key.
+    pub row: u8,
+    /// Column of the key.
+    pub col: u8,
+    /// Keycode assigned on the layer itself.
+    pub keycode: String,
+    /// Keycode the key sends: its own, or for a transparent key the first
+    /// non-transparent keycode at the same position on a lower layer.
+    pub effective_keycode: String,
+    /// Index of the layer the effective keycode comes from.
+    pub from_layer: usize,
+}
+
+/// The keys of one layer with transparency resolved.
+#[derive(Debug, Serialize)]
+pub struct ResolvedLayerResponse {
+    /// Layout filename.
+    pub filename: String,
+    /// Layer index.
+    pub layer: usize,
+    /// Layer name.
+    pub name: String,
+    /// Keys in layer order.
+    pub keys: Vec<ResolvedKeyDto>,
+}
+
+/// A layer of the layer graph.
+#[derive(Debug, Serialize)]
+pub struct LayerGraphNodeDto {
+    /// Layer index.
+    pub layer: usize,
+    /// Layer name.
+    pub name: String,
+    /// Whether a chain of layer keys leads to the layer from the base layer.
+    pub reachable: bool,
+    /// Whether the layer can be left once a latching key turned it on.
+    pub has_way_back: bool,
+}
+
+/// A key that activates another layer.
+#[derive(Debug, Serialize)]
+pub struct LayerGraphEdgeDto {
+    /// Layer the key is on.
+    pub from_layer: usize,
+    /// Layer the key activates.
+    pub to_layer: usize,
+    /// Row of the key.
+    pub row: u8,
+    /// Column of the key.
+    pub col: u8,
+    /// Layer keycode, e.g. "MO(1)".
+    pub keycode: String,
+    /// How the key activates the layer, e.g. "Toggle (TG)".
+    pub kind: String,
+}
+
+/// Which keys activate which layers, and the layers that look broken.
+#[derive(Debug, Serialize)]
+pub struct LayerGraphResponse {
+    /// Layout filename.
+    pub filename: String,
+    /// Layers in layer order.
+    pub layers: Vec<LayerGraphNodeDto>,
+    /// Layer keys by source layer.
+    pub edges: Vec<LayerGraphEdgeDto>,
+    /// Unreachable layers and layers with no way back.
+    pub issues: Vec<String>,
+}
+
+/// Query parameters for the layout statistics endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct LayoutStatsQuery {
+    /// Corpus file in the workspace to build a typing heatmap from: plain
+    /// text, or JSON statistics when it ends in `.json`.
+    pub corpus: Option<String>,
+}
+
+/// Per-layer statistics of a layout and an optional typing heatmap.
+#[derive(Debug, Serialize)]
+pub struct LayoutStatsResponse {
+    /// Layout filename.
+    pub filename: String,
+    /// Layer statistics and heatmap.
+    #[serde(flatten)]
+    pub analysis: LayoutAnalysis,
+    /// Heatmap of the key presses recorded with `lazyqmk collect-usage`.
+    pub usage: Option<Heatmap>,
+}
+
+/// Query parameters for the shutdown endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct ShutdownQuery {
+    /// Shut down even if build or generate jobs are still running.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Response of an accepted shutdown request.
+#[derive(Debug, Serialize)]
+pub struct ShutdownResponse {
+    /// Human-readable status message.
+    pub message: String,
+    /// Number of active jobs that will be cancelled.
+    pub cancelled_jobs: usize,
+}
+
+/// Audit log of a layout file, oldest entry first.
+#[derive(Debug, Serialize)]
+pub struct AuditLogResponse {
+    /// Layout filename.
+    pub filename: String,
+    /// Recorded modifications.
+    pub entries: Vec<AuditEntry>,
+}
+
+/// Backups of a layout file, newest first.
+#[derive(Debug, Serialize)]
+pub struct BackupListResponse {
+    /// Layout filename.
+    pub filename: String,
+    /// Stored backups.
+    pub backups: Vec<BackupInfo>,
+}
+
+/// Result of restoring a backup.
+#[derive(Debug, Serialize)]
+pub struct BackupRestoreResponse {
+    /// Layout filename.
+    pub filename: String,
+    /// Backup that now holds the layout.
+    pub restored: BackupInfo,
+}
+
+/// Query parameters for the git history endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct GitHistoryQuery {
+    /// Return at most N commits (default 50).
+    pub limit: Option<usize>,
+}
+
+/// Git commits that touched a layout file.
+#[derive(Debug, Serialize)]
+pub struct GitHistoryResponse {
+    /// Layout filename.
+    pub filename: String,
+    /// Whether the layout is in a git work tree.
+    pub in_repository: bool,
+    /// Whether the file is committed (None outside a work tree).
+    pub status: Option<GitFileStatus>,
+    /// Commits, newest first.
+    pub commits: Vec<GitCommit>,
+}
+
+/// Request to apply a layer fragment into a layer slot.
+///
+/// Exactly one of `fragment` or `source_filename` must be set.
+#[derive(Debug, Deserialize)]
+pub struct ApplyFragmentRequest {
+    /// Fragment to apply, as returned by the fragment export endpoint.
+    #[serde(default)]
+    pub fragment: Option<LayerFragment>,
+    /// Workspace layout to take the layer from.
+    #[serde(default)]
+    pub source_filename: Option<String>,
+    /// Layer of `source_filename` to apply.
+    #[serde(default)]
+    pub source_layer: Option<usize>,
+}
+
+/// Query parameters for the layer duplicate endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct DuplicateLayerQuery {
+    /// Name of the new layer (defaults to the source name plus " Copy").
+    pub name: Option<String>,
+    /// Flip the copy horizontally using the keyboard geometry.
+    #[serde(default)]
+    pub mirror: bool,
+}
+
+/// Result of duplicating a layer.
+#[derive(Debug, Serialize)]
+pub struct DuplicateLayerResponse {
+    /// Index of the new layer.
+    pub layer: usize,
+    /// ID of the new layer.
+    pub id: String,
+    /// Name of the new layer.
+    pub name: String,
+    /// Number of keys changed by mirroring (0 without `mirror`).
+    pub mirrored_keys: usize,
+}
+
+/// Result of applying a layer fragment.
+#[derive(Debug, Serialize)]
+pub struct ApplyFragmentResponse {
+    /// Layout filename that was modified.
+    pub filename: String,
+    /// Layer slot the fragment was applied into.
+    pub layer: usize,
+    /// What was applied, added, and skipped.
+    pub report: FragmentApplyReport,
+}
+
 /// Complete key render metadata for a single key.
 #[derive(Debug, Clone, Serialize)]
 pub struct KeyRenderMetadata {
@@ -358,6 +844,63 @@ pub struct LayerRenderMetadata {
     pub name: String,
     /// Per-key render metadata
     pub keys: Vec<KeyRenderMetadata>,
+    /// Encoder rotation actions on this layer, one per encoder
+    pub encoders: Vec<EncoderRenderMetadata>,
+    /// Combos that fire while this layer is active
+    pub combos: Vec<ComboRenderMetadata>,
+}
+
+/// One rotation direction of an encoder.
+#[derive(Debug, Clone, Serialize)]
+pub struct EncoderActionRenderMetadata {
+    /// Keycode sent
+    pub keycode: String,
+    /// Short labels for the encoder ring
+    pub display: KeyDisplayDto,
+}
+
+/// Rotation actions of one encoder on a layer.
+#[derive(Debug, Clone, Serialize)]
+pub struct EncoderRenderMetadata {
+    /// Encoder index
+    pub encoder: u8,
+    /// Counter-clockwise action
+    pub ccw: EncoderActionRenderMetadata,
+    /// Clockwise action
+    pub cw: EncoderActionRenderMetadata,
+    /// True when the layer sets no binding and the firmware default is shown
+    pub default: bool,
+}
+
+/// Kind of combo in render metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComboRenderKind {
+    /// Two-key hold combo (`combo_settings.combos`)
+    Hold,
+    /// Keycode combo (`combo_settings.keycode_combos`)
+    Keycode,
+}
+
+/// A combo drawn on a layer.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComboRenderMetadata {
+    /// Hold or keycode combo
+    pub kind: ComboRenderKind,
+    /// Index into the combo list of its kind
+    pub index: usize,
+    /// Visual positions of the trigger keys
+    pub positions: Vec<crate::models::Position>,
+    /// Visual indices of the trigger keys (same order as `positions`)
+    pub visual_indices: Vec<u8>,
+    /// Keycode sent (keycode combos only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keycode: Option<String>,
+    /// Short labels for the combo arc
+    pub display: KeyDisplayDto,
+    /// How long both keys must be held (hold combos only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hold_duration_ms: Option<u16>,
 }
 
 /// Export response with markdown content.
@@ -369,6 +912,24 @@ pub struct ExportResponse {
     pub suggested_filename: String,
 }
 
+/// keymap-drawer export response.
+#[derive(Debug, Serialize)]
+pub struct KeymapDrawerExportResponse {
+    /// keymap-drawer YAML keymap.
+    pub yaml: String,
+    /// Suggested filename for download.
+    pub suggested_filename: String,
+}
+
+/// QMK keymap.json export response.
+#[derive(Debug, Serialize)]
+pub struct QmkJsonExportResponse {
+    /// QMK keymap JSON, built with `qmk compile keymap.json`.
+    pub keymap_json: String,
+    /// Suggested filename for download.
+    pub suggested_filename: String,
+}
+
 /// Idle effect settings for API.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IdleEffectSettingsDto {
@@ -437,6 +998,9 @@ pub struct TapHoldSettingsDto {
     pub flow_tap_term: Option<u16>,
     /// Whether chordal hold is enabled.
     pub chordal_hold: bool,
+    /// Per-key tapping term overrides.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub per_key_tapping_terms: Vec<KeyTappingTerm>,
     /// Preset name.
     pub preset: String,
 }
@@ -451,6 +1015,7 @@ impl From<&TapHoldSettings> for TapHoldSettingsDto {
             tapping_toggle: s.tapping_toggle,
             flow_tap_term: s.flow_tap_term,
             chordal_hold: s.chordal_hold,
+            per_key_tapping_terms: s.per_key_tapping_terms.clone(),
             preset: s.preset.display_name().to_string(),
         }
     }
@@ -579,6 +1144,9 @@ pub struct ComboSettingsDto {
     /// List of combo definitions (max [`crate::models::layout::MAX_COMBOS`]).
     #[serde(default)]
     pub combos: Vec<ComboDefinitionDto>,
+    /// Combos that send a keycode when their keys are pressed together.
+    #[serde(default)]
+    pub keycode_combos: Vec<crate::models::KeycodeCombo>,
 }
 
 impl From<&ComboSettings> for ComboSettingsDto {
@@ -590,6 +1158,7 @@ impl From<&ComboSettings> for ComboSettingsDto {
                 .iter()
                 .map(ComboDefinitionDto::from)
                 .collect(),
+            keycode_combos: settings.keycode_combos.clone(),
         }
     }
 }
@@ -636,6 +1205,85 @@ impl From<&TapDanceAction> for TapDanceDto {
     }
 }
 
+/// Tap dance with the number of keys bound to it.
+#[derive(Debug, Serialize)]
+pub struct TapDanceUsageDto {
+    /// The tap dance definition.
+    #[serde(flatten)]
+    pub tap_dance: TapDanceDto,
+    /// Number of keys across all layers assigned `TD(name)`.
+    pub usage_count: usize,
+}
+
+/// Response for listing a layout's tap dances.
+#[derive(Debug, Serialize)]
+pub struct TapDanceListResponse {
+    /// Tap dances in definition order.
+    pub tap_dances: Vec<TapDanceUsageDto>,
+}
+
+/// Response for creating, updating, or deleting a tap dance.
+#[derive(Debug, Serialize)]
+pub struct TapDanceMutationResponse {
+    /// The tap dance as saved (omitted after a delete).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tap_dance: Option<TapDanceDto>,
+    /// Number of keys whose `TD()` keycode was rewritten.
+    pub updated_keys: usize,
+}
+
+/// Request body for creating a category.
+#[derive(Debug, Deserialize)]
+pub struct CreateCategoryRequest {
+    /// Kebab-case ID; derived from the name when omitted.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Display name.
+    pub name: String,
+    /// Category color.
+    pub color: RgbColor,
+}
+
+/// Request body for updating a category; omitted fields are unchanged.
+#[derive(Debug, Deserialize)]
+pub struct CategoryUpdateRequest {
+    /// New display name.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// New color.
+    #[serde(default)]
+    pub color: Option<RgbColor>,
+}
+
+/// Category with the number of layers and keys assigned to it.
+#[derive(Debug, Serialize)]
+pub struct LayoutCategoryUsage {
+    /// The category definition.
+    #[serde(flatten)]
+    pub category: crate::models::Category,
+    /// Number of layers assigned this category.
+    pub layer_count: usize,
+    /// Number of keys across all layers assigned this category.
+    pub key_count: usize,
+}
+
+/// Response for listing a layout's categories.
+#[derive(Debug, Serialize)]
+pub struct LayoutCategoryListResponse {
+    /// Categories in definition order.
+    pub categories: Vec<LayoutCategoryUsage>,
+}
+
+/// Response for creating, updating, or deleting a category.
+#[derive(Debug, Serialize)]
+pub struct CategoryMutationResponse {
+    /// The category as saved (omitted after a delete).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<crate::models::Category>,
+    /// Number of layer and key assignments cleared by a delete.
+    pub cleared_references: usize,
+}
+
 // ============================================================================
 // Layout DTO Types (for frontend compatibility)
 // ============================================================================
@@ -666,6 +1314,9 @@ pub struct KeyAssignmentDto {
     /// Optional user description
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Whether the key is locked against bulk edits
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
 }
 
 /// Layer DTO with enriched key data.
@@ -686,6 +1337,12 @@ pub struct LayerDto {
     pub keys: Vec<KeyAssignmentDto>,
     /// Whether layer-level RGB colors are enabled
     pub layer_colors_enabled: bool,
+    /// Fixed color for LEDs without a key (follows the layer color if absent)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underglow_color: Option<RgbColor>,
+    /// Rotary encoder bindings keyed by encoder index
+    #[serde(skip_serializing_if = "crate::models::EncoderMap::is_empty")]
+    pub encoder_map: crate::models::EncoderMap,
 }
 
 /// Complete layout DTO with enriched layer data.
@@ -711,12 +1368,32 @@ pub struct LayoutDto {
     pub rgb_overlay_ripple: RgbOverlayRippleSettingsDto,
     /// `PaletteFX` settings
     pub palette_fx: PaletteFxSettingsDto,
+    /// Conditional RGB behaviors
+    pub rgb_conditions: crate::models::RgbConditionalSettings,
+    /// Firmware feature overrides
+    pub firmware_features: crate::models::FirmwareFeatures,
     /// Tap-hold settings
     pub tap_hold_settings: TapHoldSettingsDto,
     /// Tap dance definitions
     pub tap_dances: Vec<TapDanceDto>,
+    /// Macro definitions
+    pub macros: Vec<crate::models::MacroDefinition>,
+    /// Custom keycode definitions
+    pub custom_keycodes: Vec<crate::models::CustomKeycode>,
+    /// Key override definitions
+    pub key_overrides: Vec<crate::models::KeyOverride>,
+    /// Alt repeat key overrides
+    pub alt_repeat_keys: Vec<crate::models::AltRepeatKey>,
+    /// Unicode map glyphs
+    pub unicode_map: Vec<crate::models::UnicodeGlyph>,
+    /// Unicode input modes
+    pub unicode_input_modes: Vec<crate::models::UnicodeInputMode>,
     /// Combo settings
     pub combo_settings: ComboSettingsDto,
+    /// Count key presses in the firmware
+    pub usage_counting: bool,
+    /// Editing helper switches
+    pub editing_helpers: crate::models::EditingHelpers,
 }
 
 /// Layout DTO for save requests (accepts optional fields from frontend).
@@ -760,15 +1437,45 @@ pub struct LayoutSaveDto {
     /// `PaletteFX` settings
     #[serde(default)]
     pub palette_fx: Option<PaletteFxSettingsDto>,
+    /// Conditional RGB behaviors
+    #[serde(default)]
+    pub rgb_conditions: crate::models::RgbConditionalSettings,
+    /// Firmware feature overrides
+    #[serde(default)]
+    pub firmware_features: crate::models::FirmwareFeatures,
     /// Tap-hold settings
     #[serde(default)]
     pub tap_hold_settings: Option<TapHoldSettingsDto>,
     /// Tap dance definitions
     #[serde(default)]
     pub tap_dances: Vec<TapDanceDto>,
+    /// Macro definitions
+    #[serde(default)]
+    pub macros: Vec<crate::models::MacroDefinition>,
+    /// Custom keycode definitions
+    #[serde(default)]
+    pub custom_keycodes: Vec<crate::models::CustomKeycode>,
+    /// Key override definitions
+    #[serde(default)]
+    pub key_overrides: Vec<crate::models::KeyOverride>,
+    /// Alt repeat key overrides
+    #[serde(default)]
+    pub alt_repeat_keys: Vec<crate::models::AltRepeatKey>,
+    /// Unicode map glyphs
+    #[serde(default)]
+    pub unicode_map: Vec<crate::models::UnicodeGlyph>,
+    /// Unicode input modes
+    #[serde(default)]
+    pub unicode_input_modes: Vec<crate::models::UnicodeInputMode>,
     /// Combo settings
     #[serde(default)]
     pub combo_settings: Option<ComboSettingsDto>,
+    /// Count key presses in the firmware
+    #[serde(default)]
+    pub usage_counting: bool,
+    /// Editing helper switches
+    #[serde(default)]
+    pub editing_helpers: crate::models::EditingHelpers,
 }
 
 fn default_rgb_enabled_true() -> bool {
@@ -800,6 +1507,12 @@ pub struct LayerSaveDto {
     /// Whether layer-level RGB colors are enabled
     #[serde(default = "default_layer_colors_true")]
     pub layer_colors_enabled: bool,
+    /// Fixed color for LEDs without a key (follows the layer color if absent)
+    #[serde(default)]
+    pub underglow_color: Option<RgbColor>,
+    /// Rotary encoder bindings keyed by encoder index
+    #[serde(default)]
+    pub encoder_map: crate::models::EncoderMap,
     /// Legacy field from TypeScript interface (ignored)
     #[serde(default, skip_deserializing)]
     pub color: Option<String>,
@@ -831,4 +1544,20 @@ pub struct KeyAssignmentSaveDto {
     pub category_id: Option<String>,
     /// Optional user description
     pub description: Option<String>,
+    /// Whether the key is locked against bulk edits
+    #[serde(default)]
+    pub locked: bool,
+}
+
+/// Result of an undo or redo.
+#[derive(Debug, Serialize)]
+pub struct HistoryResponse {
+    /// Layout filename.
+    pub filename: String,
+    /// Action of the change that was undone or redone (e.g. `swap_keys`).
+    pub action: String,
+    /// Changes that can still be undone.
+    pub undo_steps: usize,
+    /// Changes that can be redone.
+    pub redo_steps: usize,
 }
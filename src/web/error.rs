@@ -7,6 +7,9 @@ use axum::response::{IntoResponse, Response};
 use axum::Json;
 use serde::Serialize;
 
+use crate::firmware::validator::ValidationReport;
+use crate::web::dto::JobValidationDto;
+
 /// API error response.
 #[derive(Debug, Serialize)]
 pub struct ApiError {
@@ -15,6 +18,9 @@ pub struct ApiError {
     /// Optional additional details.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<String>,
+    /// Structured validation findings when a job was refused.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation: Option<JobValidationDto>,
 }
 
 impl ApiError {
@@ -23,6 +29,7 @@ impl ApiError {
         Self {
             error: error.into(),
             details: None,
+            validation: None,
         }
     }
 }
@@ -75,6 +82,22 @@ impl AppError {
             error: ApiError {
                 error: msg.into(),
                 details: details.into(),
+                validation: None,
+            },
+        }
+    }
+
+    /// 422 Unprocessable Entity carrying the structured validation report.
+    pub fn validation_failed(report: &ValidationReport) -> Self {
+        Self {
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            error: ApiError {
+                error: format!(
+                    "Layout validation failed with {} error(s)",
+                    report.errors.len()
+                ),
+                details: Some(report.format_message()),
+                validation: Some(report.into()),
             },
         }
     }
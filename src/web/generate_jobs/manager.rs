@@ -9,15 +9,22 @@ use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
 use std::sync::{mpsc, Arc, Mutex, RwLock};
 use std::thread;
+use std::time::Duration;
 
+use crate::firmware::emitter::EmitterRegistry;
 use crate::keycode_db::KeycodeDb;
 use tracing::{info, warn};
 
+use super::super::build_jobs::layout_qmk_path;
+use super::super::job_events::{JobEvent, JobEvents, JobKind, LogTee};
+use super::super::watchdog::JobDeadline;
 use super::parse_log_line;
+use super::qmk_lint::run_qmk_lint;
 use super::workers::RealGenerateWorker;
 use super::{
     CancelGenerateJobResponse, GenerateCommand, GenerateJob, GenerateJobHealth,
-    GenerateJobLogsResponse, GenerateJobStatus, GenerateWorker, LogEntry, MAX_CONCURRENT_JOBS,
+    GenerateJobLogsResponse, GenerateJobStatus, GenerateOptions, GenerateWorker, LogEntry,
+    MAX_CONCURRENT_JOBS,
 };
 
 /// Generate job manager that coordinates background generation.
@@ -38,10 +45,14 @@ pub struct GenerateJobManager {
     pub(crate) workspace_root: PathBuf,
     /// QMK firmware path from config.
     pub(crate) qmk_path: RwLock<Option<PathBuf>>,
+    /// Time a generation may run before it is stopped (None = no limit).
+    pub(crate) job_timeout: RwLock<Option<Duration>>,
     /// Generate worker (real or mock).
     pub(crate) worker: Arc<dyn GenerateWorker>,
     /// Keycode database.
     pub(crate) keycode_db: Arc<KeycodeDb>,
+    /// Status changes and log lines for live subscribers.
+    pub(crate) events: JobEvents,
 }
 
 impl GenerateJobManager {
@@ -112,7 +123,27 @@ impl GenerateJobManager {
             workspace_root,
             qmk_path,
             keycode_db,
-            Arc::new(RealGenerateWorker),
+            Arc::new(RealGenerateWorker::default()),
+        )
+    }
+
+    /// Creates a new generate job manager whose jobs can use the emitters in
+    /// `emitters` in addition to QMK.
+    pub fn with_emitters(
+        logs_dir: PathBuf,
+        output_dir: PathBuf,
+        workspace_root: PathBuf,
+        qmk_path: Option<PathBuf>,
+        keycode_db: Arc<KeycodeDb>,
+        emitters: EmitterRegistry,
+    ) -> Arc<Self> {
+        Self::with_worker(
+            logs_dir,
+            output_dir,
+            workspace_root,
+            qmk_path,
+            keycode_db,
+            Arc::new(RealGenerateWorker { emitters }),
         )
     }
 
@@ -138,8 +169,10 @@ impl GenerateJobManager {
             output_dir,
             workspace_root,
             qmk_path: RwLock::new(qmk_path),
+            job_timeout: RwLock::new(None),
             worker,
             keycode_db,
+            events: JobEvents::new(),
         });
 
         // Start worker thread
@@ -177,6 +210,10 @@ impl GenerateJobManager {
         // Check if cancelled before starting
         if self.is_cancelled(&cmd.job_id) {
             info!(job_id = %cmd.job_id, "Job was cancelled before processing");
+            {
+                let mut count = self.running_count_lock();
+                *count = count.saturating_sub(1);
+            }
             self.update_job_status(&cmd.job_id, GenerateJobStatus::Cancelled, None, None);
             return;
         }
@@ -188,6 +225,7 @@ impl GenerateJobManager {
                 job.status = GenerateJobStatus::Running;
                 job.started_at = Some(chrono::Utc::now().to_rfc3339());
                 job.progress = 10;
+                self.publish_status(job);
             }
         }
 
@@ -197,8 +235,11 @@ impl GenerateJobManager {
             .append(true)
             .open(&cmd.log_path);
 
+        let deadline = JobDeadline::start(self.job_timeout());
+
         let result = match log_file {
-            Ok(mut file) => {
+            Ok(file) => {
+                let mut file = LogTee::new(file, &self.events, JobKind::Generate, &cmd.job_id);
                 let _ = writeln!(file, "[INFO] Generate started at {}", chrono::Utc::now());
 
                 // Check if cancelled during setup
@@ -207,7 +248,28 @@ impl GenerateJobManager {
                     Err("Generation cancelled".to_string())
                 } else {
                     // Run the generation
-                    self.worker.generate(&cmd, &mut file, &self.keycode_db)
+                    let result = self.worker.generate(&cmd, &mut file, &self.keycode_db);
+                    if let (Ok(zip_path), true) = (&result, cmd.options.qmk_lint) {
+                        let should_stop = || self.is_cancelled(&cmd.job_id) || deadline.expired();
+                        let report = run_qmk_lint(
+                            &cmd.qmk_path,
+                            &cmd.keyboard,
+                            zip_path,
+                            &cmd.job_id,
+                            &mut file,
+                            &should_stop,
+                        );
+                        if let Some(job) = self.jobs_write().get_mut(&cmd.job_id) {
+                            job.qmk_lint = Some(report);
+                        }
+                    }
+                    if deadline.expired() {
+                        let reason = deadline.reason("Generation");
+                        let _ = writeln!(file, "[ERROR] {reason}");
+                        Err(reason)
+                    } else {
+                        result
+                    }
                 }
             }
             Err(e) => Err(format!("Failed to open log file: {e}")),
@@ -278,10 +340,22 @@ impl GenerateJobManager {
             };
             job.error = error;
             job.zip_path = zip_path;
+            self.publish_status(job);
         }
     }
 
-    /// Starts a new generate job.
+    /// Publishes the current state of `job` to live subscribers.
+    fn publish_status(&self, job: &GenerateJob) {
+        self.events
+            .publish(JobEvent::GenerateStatus { job: job.clone() });
+    }
+
+    /// Subscribes to status changes and log lines of all generate jobs.
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<JobEvent> {
+        self.events.subscribe()
+    }
+
+    /// Starts a new generate job with default options.
     ///
     /// Returns the created job or an error if generation cannot be started.
     pub fn start_generate(
@@ -289,6 +363,24 @@ impl GenerateJobManager {
         layout_filename: String,
         keyboard: String,
         layout_variant: String,
+    ) -> Result<GenerateJob, String> {
+        self.start_generate_with_options(
+            layout_filename,
+            keyboard,
+            layout_variant,
+            GenerateOptions::default(),
+        )
+    }
+
+    /// Starts a new generate job with optional stages enabled.
+    ///
+    /// Returns the created job or an error if generation cannot be started.
+    pub fn start_generate_with_options(
+        self: &Arc<Self>,
+        layout_filename: String,
+        keyboard: String,
+        layout_variant: String,
+        options: GenerateOptions,
     ) -> Result<GenerateJob, String> {
         // Check QMK path
         let qmk_path = self
@@ -313,9 +405,10 @@ impl GenerateJobManager {
         if !layout_path.exists() {
             return Err(format!("Layout file not found: {layout_filename}"));
         }
+        let qmk_path = layout_qmk_path(&layout_path, qmk_path);
 
         // Create job
-        let job = GenerateJob::new(layout_filename.clone(), keyboard, layout_variant);
+        let job = GenerateJob::new(layout_filename.clone(), keyboard.clone(), layout_variant);
         let job_id = job.id.clone();
 
         // Store job
@@ -323,6 +416,7 @@ impl GenerateJobManager {
             let mut jobs = self.jobs_write();
             jobs.insert(job_id.clone(), job.clone());
         }
+        self.publish_status(&job);
 
         // Create log file path
         let log_path = self.logs_dir.join(format!("{job_id}.log"));
@@ -345,6 +439,8 @@ impl GenerateJobManager {
             qmk_path,
             log_path: log_path.clone(),
             output_dir: job_output_dir,
+            keyboard,
+            options,
         };
 
         // Check if worker is running and send command
@@ -538,6 +634,52 @@ impl GenerateJobManager {
         }
     }
 
+    /// Returns the number of pending or running jobs.
+    pub fn active_job_count(&self) -> usize {
+        self.jobs_read()
+            .values()
+            .filter(|job| {
+                matches!(
+                    job.status,
+                    GenerateJobStatus::Pending | GenerateJobStatus::Running
+                )
+            })
+            .count()
+    }
+
+    /// Cancels every pending or running job, noting `reason` in each job's log.
+    ///
+    /// Returns the number of jobs cancelled.
+    pub fn cancel_all(&self, reason: &str) -> usize {
+        let active: Vec<String> = self
+            .jobs_read()
+            .values()
+            .filter(|job| {
+                matches!(
+                    job.status,
+                    GenerateJobStatus::Pending | GenerateJobStatus::Running
+                )
+            })
+            .map(|job| job.id.clone())
+            .collect();
+
+        for job_id in &active {
+            let log_path = self.logs_dir.join(format!("{job_id}.log"));
+            if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(log_path) {
+                let _ = writeln!(file, "[WARN] {reason}");
+            }
+            self.events
+                .log_line(JobKind::Generate, job_id, &format!("[WARN] {reason}"));
+            self.cancel_job(job_id);
+        }
+        active.len()
+    }
+
+    /// Returns true when the worker has no queued or running generation left.
+    pub fn is_idle(&self) -> bool {
+        *self.running_count_lock() == 0
+    }
+
     /// Lists all jobs.
     pub fn list_jobs(&self) -> Vec<GenerateJob> {
         let mut list: Vec<_> = self.jobs_read().values().cloned().collect();
@@ -549,4 +691,23 @@ impl GenerateJobManager {
     pub fn set_qmk_path(&self, path: Option<PathBuf>) {
         *self.qmk_path_write() = path;
     }
+
+    /// Sets how long a generation may run before it is stopped (None = no
+    /// limit).
+    ///
+    /// Applies to generations started afterwards.
+    pub fn set_job_timeout(&self, timeout: Option<Duration>) {
+        *self
+            .job_timeout
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = timeout;
+    }
+
+    /// Returns the current generation time limit.
+    fn job_timeout(&self) -> Option<Duration> {
+        *self
+            .job_timeout
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
 }
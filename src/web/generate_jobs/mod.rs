@@ -9,7 +9,8 @@
 //! - Concurrency limit of 1 (single generation at a time)
 //! - Logs are persisted to disk for durability
 //! - Uses mpsc channels for thread communication
-//! - Generated zip contains: layout source, keymap.c, config.h, manifest.json, logs
+//! - Generated zip contains: layout source, keymap.c, config.h, manifest.json, logs,
+//!   plus the files of any extra [`crate::firmware::FirmwareEmitter`]s requested
 //!
 //! ## Security
 //!
@@ -18,6 +19,7 @@
 
 pub mod log_parse;
 pub mod manager;
+pub mod qmk_lint;
 pub mod workers;
 
 #[cfg(test)]
@@ -37,12 +39,8 @@ use crate::keycode_db::KeycodeDb;
 #[cfg(test)]
 use std::collections::HashMap;
 #[cfg(test)]
-use std::collections::HashSet;
-#[cfg(test)]
 use std::fs::{self, File};
 #[cfg(test)]
-use std::io::{BufRead, BufReader};
-#[cfg(test)]
 use std::path::Path;
 #[cfg(test)]
 use std::sync::{mpsc, Arc, Mutex, RwLock};
@@ -58,11 +56,12 @@ use zip::ZipWriter;
 // ---------------------------------------------------------------------------
 pub(crate) use log_parse::parse_log_line;
 pub use manager::GenerateJobManager;
+pub use qmk_lint::{QmkLintFinding, QmkLintLevel, QmkLintReport};
 
-#[cfg(test)]
-pub(crate) use workers::add_file_to_zip;
 #[cfg(test)]
 pub(crate) use workers::MockGenerateWorker;
+#[cfg(test)]
+pub(crate) use workers::{add_file_to_zip, create_firmware_zip};
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -139,6 +138,9 @@ pub struct GenerateJob {
     pub download_url: Option<String>,
     /// Progress percentage (0-100).
     pub progress: u8,
+    /// Findings of the optional `qmk lint` stage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qmk_lint: Option<QmkLintReport>,
 }
 
 impl GenerateJob {
@@ -158,10 +160,44 @@ impl GenerateJob {
             zip_path: None,
             download_url: Some(format!("/api/generate/jobs/{id}/download")),
             progress: 0,
+            qmk_lint: None,
         }
     }
 }
 
+// ---------------------------------------------------------------------------
+// GenerateOptions
+// ---------------------------------------------------------------------------
+
+/// Optional stages for a generate job.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GenerateOptions {
+    /// Run `qmk lint` on the keyboard after generating the firmware files.
+    #[serde(default)]
+    pub qmk_lint: bool,
+    /// Generate for this keyboard target of the layout instead of its
+    /// primary keyboard.
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Comma-separated ids of extra emitters (e.g. `zmk,via`) whose files
+    /// are added to the archive next to the QMK files.
+    #[serde(default)]
+    pub emitters: Option<String>,
+}
+
+impl GenerateOptions {
+    /// Returns the requested extra emitter ids.
+    pub(crate) fn emitter_ids(&self) -> Vec<&str> {
+        self.emitters
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .collect()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // LogEntry
 // ---------------------------------------------------------------------------
@@ -244,6 +280,8 @@ pub(crate) struct GenerateCommand {
     pub(crate) qmk_path: PathBuf,
     pub(crate) log_path: PathBuf,
     pub(crate) output_dir: PathBuf,
+    pub(crate) keyboard: String,
+    pub(crate) options: GenerateOptions,
 }
 
 /// Trait for generate workers, allowing mock injection for tests.
@@ -0,0 +1,236 @@
+//! Optional `qmk lint` stage for generate jobs.
+//!
+//! After the firmware files were generated, the keymap files from the job's
+//! archive are copied into a temporary keymap of the keyboard in the QMK
+//! firmware tree, `qmk lint -kb <keyboard> -km <keymap>` runs on it, and the
+//! findings are attached to the job. The temporary keymap is removed
+//! afterwards. The stage is informational: findings never fail the job.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+
+use crate::services::process_tree::{ChildOutcome, OutputLine, TrackedChild};
+use crate::web::watchdog::JobDeadline;
+
+/// Longest a lint may run, even without a job timeout.
+const LINT_TIMEOUT: Duration = Duration::from_mins(5);
+
+/// Archive files copied into the temporary keymap.
+const KEYMAP_FILES: &[&str] = &["keymap.c", "config.h", "rules.mk"];
+
+/// Severity of a `qmk lint` finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QmkLintLevel {
+    /// Reported by QMK as a warning.
+    Warning,
+    /// Reported by QMK as an error.
+    Error,
+}
+
+/// A single finding reported by `qmk lint`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QmkLintFinding {
+    /// Severity of the finding.
+    pub level: QmkLintLevel,
+    /// Message as printed by QMK.
+    pub message: String,
+}
+
+/// Result of the `qmk lint` stage attached to a generate job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QmkLintReport {
+    /// True if `qmk lint` exited successfully without error findings.
+    pub passed: bool,
+    /// Warnings and errors reported by QMK.
+    pub findings: Vec<QmkLintFinding>,
+    /// Set when the lint could not be run (e.g. QMK CLI missing).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Runs `qmk lint` on the keymap generated by job `job_id` and logs its
+/// output to `log_writer`.
+///
+/// `archive` is the job's zip file. The lint (and anything it spawned) is
+/// killed once `should_stop` returns true or it runs longer than
+/// [`LINT_TIMEOUT`].
+pub(crate) fn run_qmk_lint(
+    qmk_path: &Path,
+    keyboard: &str,
+    archive: &Path,
+    job_id: &str,
+    log_writer: &mut dyn Write,
+    should_stop: &dyn Fn() -> bool,
+) -> QmkLintReport {
+    let failed = |error: String, log_writer: &mut dyn Write| {
+        let _ = writeln!(log_writer, "[WARN] {error}");
+        QmkLintReport {
+            passed: false,
+            findings: Vec::new(),
+            error: Some(error),
+        }
+    };
+
+    let keymap = lint_keymap_name(job_id);
+    let _deployed = match LintKeymap::deploy(qmk_path, keyboard, &keymap, archive) {
+        Ok(deployed) => deployed,
+        Err(e) => return failed(format!("Failed to prepare qmk lint: {e}"), log_writer),
+    };
+    let _ = writeln!(
+        log_writer,
+        "[INFO] Running: qmk lint -kb {keyboard} -km {keymap}"
+    );
+
+    let mut cmd = Command::new("qmk");
+    cmd.arg("lint")
+        .arg("-kb")
+        .arg(keyboard)
+        .arg("-km")
+        .arg(&keymap)
+        .current_dir(qmk_path);
+    let mut child = match TrackedChild::spawn(&mut cmd) {
+        Ok(child) => child,
+        Err(e) => return failed(format!("Failed to execute qmk lint: {e}"), log_writer),
+    };
+
+    let deadline = JobDeadline::start(Some(LINT_TIMEOUT));
+    let stop = || should_stop() || deadline.expired();
+    let mut combined = String::new();
+    let outcome = child.wait_with_output(&stop, &mut |line| {
+        let (OutputLine::Stdout(line) | OutputLine::Stderr(line)) = line;
+        combined.push_str(&line);
+        combined.push('\n');
+    });
+    let status = match outcome {
+        Ok(ChildOutcome::Exited(status)) => status,
+        Ok(ChildOutcome::Stopped) if deadline.expired() => {
+            return failed(
+                format!(
+                    "qmk lint timed out after {} min and was stopped",
+                    LINT_TIMEOUT.as_secs() / 60
+                ),
+                log_writer,
+            )
+        }
+        Ok(ChildOutcome::Stopped) => {
+            return failed(
+                "qmk lint was stopped before it finished".to_string(),
+                log_writer,
+            )
+        }
+        Err(e) => return failed(format!("Failed to wait for qmk lint: {e}"), log_writer),
+    };
+
+    for line in combined.lines().filter(|l| !l.trim().is_empty()) {
+        let _ = writeln!(log_writer, "[INFO] qmk lint: {line}");
+    }
+
+    let findings = parse_lint_output(&combined);
+    let passed = status.success() && !findings.iter().any(|f| f.level == QmkLintLevel::Error);
+    let _ = writeln!(
+        log_writer,
+        "[INFO] qmk lint {} with {} finding(s)",
+        if passed { "passed" } else { "failed" },
+        findings.len()
+    );
+
+    QmkLintReport {
+        passed,
+        findings,
+        error: None,
+    }
+}
+
+/// Name of the temporary keymap linted for job `job_id`.
+fn lint_keymap_name(job_id: &str) -> String {
+    format!("lazyqmk_lint_{}", job_id.replace('-', "_"))
+}
+
+/// A keymap written into the QMK tree for linting, removed when dropped.
+#[derive(Debug)]
+pub(crate) struct LintKeymap {
+    dir: PathBuf,
+}
+
+impl LintKeymap {
+    /// Copies the keymap files of `archive` to
+    /// `keyboards/<keyboard>/keymaps/<keymap>/` in the QMK tree.
+    ///
+    /// Fails if the keyboard is not in the tree or the keymap already exists,
+    /// so no user files are overwritten.
+    pub(crate) fn deploy(
+        qmk_path: &Path,
+        keyboard: &str,
+        keymap: &str,
+        archive: &Path,
+    ) -> io::Result<Self> {
+        let keyboard_dir = qmk_path.join("keyboards").join(keyboard);
+        if !keyboard_dir.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("keyboard {keyboard} is not in the QMK tree"),
+            ));
+        }
+        let mut zip = ZipArchive::new(File::open(archive)?).map_err(io::Error::other)?;
+
+        let dir = keyboard_dir.join("keymaps").join(keymap);
+        fs::create_dir_all(dir.parent().unwrap_or(&keyboard_dir))?;
+        fs::create_dir(&dir)?;
+        // From here on the directory is ours to remove
+        let deployed = Self { dir };
+        for name in KEYMAP_FILES {
+            let Ok(mut file) = zip.by_name(name) else {
+                continue;
+            };
+            io::copy(&mut file, &mut File::create(deployed.dir.join(name))?)?;
+        }
+        Ok(deployed)
+    }
+}
+
+impl Drop for LintKeymap {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Extracts warnings and errors from `qmk lint` output.
+///
+/// QMK prefixes log lines with `⚠`/`☒` symbols, or with `WARNING`/`ERROR`
+/// when unicode output is disabled. Summary lines are skipped.
+pub(crate) fn parse_lint_output(output: &str) -> Vec<QmkLintFinding> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (level, rest) = if let Some(rest) = line.strip_prefix('⚠') {
+                (QmkLintLevel::Warning, rest)
+            } else if let Some(rest) = line.strip_prefix("WARNING") {
+                (QmkLintLevel::Warning, rest)
+            } else if let Some(rest) = line.strip_prefix('☒') {
+                (QmkLintLevel::Error, rest)
+            } else if let Some(rest) = line.strip_prefix("ERROR") {
+                (QmkLintLevel::Error, rest)
+            } else {
+                return None;
+            };
+
+            let message = rest.trim_start_matches(':').trim();
+            if message.is_empty() || message.starts_with("Lint check") {
+                return None;
+            }
+
+            Some(QmkLintFinding {
+                level,
+                message: message.to_string(),
+            })
+        })
+        .collect()
+}
@@ -4,9 +4,10 @@
 
 use super::*;
 
-use super::*;
 use std::time::Duration;
 
+use crate::firmware::EmittedFile;
+
 fn create_test_manager(workspace_root: &Path) -> Arc<GenerateJobManager> {
     let temp_dir = std::env::temp_dir().join(format!("lazyqmk_gen_test_{}", Uuid::new_v4()));
     let mock_worker = Arc::new(MockGenerateWorker {
@@ -280,6 +281,49 @@ fn test_add_file_to_zip_validation() {
     assert!(result.unwrap_err().contains("Invalid filename"));
 }
 
+#[test]
+fn test_firmware_zip_is_reproducible() {
+    let temp_dir = std::env::temp_dir().join(format!("lazyqmk_zip_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&temp_dir).unwrap();
+    let manifest = serde_json::json!({ "version": "1.0" });
+
+    let write = |name: &str| {
+        let path = temp_dir.join(name);
+        create_firmware_zip(
+            &path,
+            &[
+                EmittedFile::new("keymap.c", "// keymap\n"),
+                EmittedFile::new("config.h", "// config\n"),
+                EmittedFile::new("zmk/test.keymap", "/ { };\n"),
+            ],
+            "# Layout\n",
+            "[INFO] Done\n",
+            &manifest,
+        )
+        .unwrap();
+        fs::read(path).unwrap()
+    };
+
+    let first = write("first.zip");
+    thread::sleep(Duration::from_millis(1100));
+    let second = write("second.zip");
+    assert_eq!(first, second);
+
+    let archive = zip::ZipArchive::new(std::io::Cursor::new(first)).unwrap();
+    let names: Vec<&str> = archive.file_names().collect();
+    assert_eq!(
+        names,
+        [
+            "keymap.c",
+            "config.h",
+            "zmk/test.keymap",
+            "layout.md",
+            "generate.log",
+            "manifest.json"
+        ]
+    );
+}
+
 #[test]
 fn test_worker_not_running_returns_error() {
     let temp_dir = std::env::temp_dir().join(format!("lazyqmk_gen_test_{}", Uuid::new_v4()));
@@ -302,8 +346,10 @@ fn test_worker_not_running_returns_error() {
         output_dir: temp_dir.join("output"),
         workspace_root: temp_dir.clone(),
         qmk_path: RwLock::new(Some(PathBuf::from("/tmp/qmk"))),
+        job_timeout: RwLock::new(None),
         worker: mock_worker,
         keycode_db,
+        events: crate::web::job_events::JobEvents::new(),
     });
 
     // Ensure directories exist
@@ -384,8 +430,10 @@ fn test_send_failure_rolls_back_state() {
         output_dir: temp_dir.join("output"),
         workspace_root: temp_dir.clone(),
         qmk_path: RwLock::new(Some(PathBuf::from("/tmp/qmk"))),
+        job_timeout: RwLock::new(None),
         worker: mock_worker,
         keycode_db,
+        events: crate::web::job_events::JobEvents::new(),
     });
 
     // Ensure directories exist
@@ -458,3 +506,104 @@ fn test_health_accessor() {
         "Max concurrent jobs should match constant"
     );
 }
+
+#[test]
+fn test_parse_qmk_lint_output() {
+    let output = "Ψ Linting keyboard crkbd\n\
+                  ⚠ crkbd: Missing readme.md\n\
+                  WARNING crkbd/rev1: LAYOUT_split_3x5_3: Layout not tested\n\
+                  ☒ crkbd: Invalid keyboard.json\n\
+                  ☒ Lint check failed!\n";
+
+    let findings = qmk_lint::parse_lint_output(output);
+
+    assert_eq!(findings.len(), 3);
+    assert_eq!(findings[0].level, QmkLintLevel::Warning);
+    assert_eq!(findings[0].message, "crkbd: Missing readme.md");
+    assert_eq!(findings[1].level, QmkLintLevel::Warning);
+    assert_eq!(findings[2].level, QmkLintLevel::Error);
+    assert_eq!(findings[2].message, "crkbd: Invalid keyboard.json");
+}
+
+#[test]
+fn test_qmk_lint_stage_attaches_report() {
+    let temp_dir = std::env::temp_dir().join(format!("lazyqmk_gen_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&temp_dir).unwrap();
+    fs::write(temp_dir.join("test.md"), "---\nname: Test\n---\n").unwrap();
+
+    // A QMK path that does not exist makes `qmk lint` fail to start, which
+    // must be reported on the job without failing it.
+    let manager = GenerateJobManager::with_worker(
+        temp_dir.join("logs"),
+        temp_dir.join("output"),
+        temp_dir.clone(),
+        Some(temp_dir.join("missing_qmk")),
+        Arc::new(KeycodeDb::load().unwrap()),
+        Arc::new(MockGenerateWorker {
+            duration_ms: 50,
+            ..MockGenerateWorker::default()
+        }),
+    );
+
+    let job = manager
+        .start_generate_with_options(
+            "test.md".to_string(),
+            "crkbd".to_string(),
+            "LAYOUT_split_3x6_3".to_string(),
+            GenerateOptions {
+                qmk_lint: true,
+                ..GenerateOptions::default()
+            },
+        )
+        .unwrap();
+
+    thread::sleep(Duration::from_millis(300));
+
+    let updated = manager.get_job(&job.id).unwrap();
+    assert_eq!(updated.status, GenerateJobStatus::Completed);
+    let report = updated.qmk_lint.expect("lint report should be attached");
+    assert!(!report.passed);
+    assert!(report.error.is_some());
+}
+
+#[test]
+fn test_generate_options_emitter_ids() {
+    let options = GenerateOptions {
+        emitters: Some(" zmk, ,via,".to_string()),
+        ..GenerateOptions::default()
+    };
+    assert_eq!(options.emitter_ids(), ["zmk", "via"]);
+    assert!(GenerateOptions::default().emitter_ids().is_empty());
+}
+
+#[test]
+fn test_lint_keymap_is_deployed_and_removed() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let qmk_path = temp_dir.path().join("qmk");
+    let keymaps = qmk_path.join("keyboards/crkbd/rev1/keymaps");
+    fs::create_dir_all(keymaps.join("default")).unwrap();
+    let archive = temp_dir.path().join("firmware.zip");
+    let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+    for (name, contents) in [("keymap.c", "// keymap\n"), ("layout.md", "# Layout\n")] {
+        zip.start_file(name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(contents.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+
+    let deployed =
+        qmk_lint::LintKeymap::deploy(&qmk_path, "crkbd/rev1", "lazyqmk_lint_1", &archive).unwrap();
+    let dir = keymaps.join("lazyqmk_lint_1");
+    assert_eq!(
+        fs::read_to_string(dir.join("keymap.c")).unwrap(),
+        "// keymap\n"
+    );
+    assert!(!dir.join("layout.md").exists());
+    // An existing keymap is never overwritten
+    assert!(qmk_lint::LintKeymap::deploy(&qmk_path, "crkbd/rev1", "default", &archive).is_err());
+    assert!(qmk_lint::LintKeymap::deploy(&qmk_path, "missing", "x", &archive).is_err());
+
+    drop(deployed);
+    assert!(!dir.exists());
+    assert!(keymaps.join("default").exists());
+}
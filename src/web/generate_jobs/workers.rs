@@ -3,6 +3,11 @@
 //! Contains [`RealGenerateWorker`] which runs the full firmware-generation
 //! pipeline, and [`MockGenerateWorker`] which simulates generation for
 //! testing.  Also provides the zip-packing helpers.
+//!
+//! Firmware archives are reproducible: entries are written in a fixed order
+//! with a fixed timestamp, permissions and compression level, and neither the
+//! manifest nor the bundled log carries the time of generation. Identical
+//! layouts therefore produce byte-identical zips.
 
 use std::fs::{self, File};
 use std::io::Write;
@@ -10,10 +15,10 @@ use std::path::{Path, PathBuf};
 use std::thread;
 
 use zip::write::SimpleFileOptions;
-use zip::ZipWriter;
+use zip::{DateTime, ZipWriter};
 
 use crate::config::Config;
-use crate::firmware::generator::FirmwareGenerator;
+use crate::firmware::emitter::{EmitContext, EmittedFile, EmitterRegistry};
 use crate::firmware::validator::FirmwareValidator;
 use crate::keycode_db::KeycodeDb;
 use crate::services::geometry::{self, GeometryContext};
@@ -26,7 +31,13 @@ use super::{GenerateCommand, GenerateWorker};
 // ---------------------------------------------------------------------------
 
 /// Real generate worker that produces firmware files.
-pub(crate) struct RealGenerateWorker;
+///
+/// The QMK files and any extra files a job asks for come from the
+/// worker's [`EmitterRegistry`].
+#[derive(Default)]
+pub(crate) struct RealGenerateWorker {
+    pub(crate) emitters: EmitterRegistry,
+}
 
 impl GenerateWorker for RealGenerateWorker {
     fn generate(
@@ -35,6 +46,9 @@ impl GenerateWorker for RealGenerateWorker {
         log_writer: &mut dyn Write,
         keycode_db: &KeycodeDb,
     ) -> Result<PathBuf, String> {
+        // Keep this run's log lines for the archive; the job log also holds
+        // timestamped lines written by the manager
+        let log_writer = &mut ArchiveLog::new(log_writer);
         let _ = writeln!(log_writer, "[INFO] Starting firmware generation...");
         let _ = writeln!(log_writer, "[INFO] Layout: {}", cmd.layout_filename);
 
@@ -43,6 +57,41 @@ impl GenerateWorker for RealGenerateWorker {
         let layout = LayoutService::load(&cmd.layout_path)
             .map_err(|e| format!("Failed to load layout: {e}"))?;
 
+        // Build config with QMK path
+        let mut config = Config::load().unwrap_or_default();
+        config.pin_qmk_path(cmd.qmk_path.clone());
+        config.build.output_dir.clone_from(&cmd.output_dir);
+
+        // Build geometry
+        let _ = writeln!(log_writer, "[INFO] Building keyboard geometry...");
+        let (layout, geometry, mapping) = if let Some(target) = &cmd.options.target {
+            let _ = writeln!(log_writer, "[INFO] Keyboard target: {target}");
+            let build = geometry::build_target(&config, &layout, target)
+                .map_err(|e| format!("Failed to prepare keyboard target: {e:#}"))?;
+            if !build.adjustments.is_empty() {
+                let _ = writeln!(
+                    log_writer,
+                    "[INFO] {} key(s) left off, {} position(s) filled with KC_NO",
+                    build.adjustments.removed_count(),
+                    build.adjustments.added_count()
+                );
+            }
+            (build.layout, build.geometry, build.mapping)
+        } else {
+            let layout_variant = layout
+                .metadata
+                .layout_variant
+                .as_ref()
+                .ok_or("Layout has no layout variant defined")?;
+            let geo_context = GeometryContext {
+                config: &config,
+                metadata: &layout.metadata,
+            };
+            let geo_result = geometry::build_geometry_for_layout(geo_context, layout_variant)
+                .map_err(|e| format!("Failed to build geometry: {e}"))?;
+            (layout, geo_result.geometry, geo_result.mapping)
+        };
+
         // Get keyboard and layout variant
         let keyboard = layout
             .metadata
@@ -58,23 +107,6 @@ impl GenerateWorker for RealGenerateWorker {
         let _ = writeln!(log_writer, "[INFO] Keyboard: {keyboard}");
         let _ = writeln!(log_writer, "[INFO] Layout variant: {layout_variant}");
 
-        // Build config with QMK path
-        let mut config = Config::load().unwrap_or_default();
-        config.paths.qmk_firmware = Some(cmd.qmk_path.clone());
-        config.build.output_dir.clone_from(&cmd.output_dir);
-
-        // Build geometry
-        let _ = writeln!(log_writer, "[INFO] Building keyboard geometry...");
-        let geo_context = GeometryContext {
-            config: &config,
-            metadata: &layout.metadata,
-        };
-
-        let geo_result = geometry::build_geometry_for_layout(geo_context, layout_variant)
-            .map_err(|e| format!("Failed to build geometry: {e}"))?;
-        let geometry = geo_result.geometry;
-        let mapping = geo_result.mapping;
-
         // Validate layout
         let _ = writeln!(log_writer, "[INFO] Validating layout...");
         let validator = FirmwareValidator::new(&layout, &geometry, &mapping, keycode_db);
@@ -94,25 +126,27 @@ impl GenerateWorker for RealGenerateWorker {
 
         // Generate firmware files
         let _ = writeln!(log_writer, "[INFO] Generating firmware files...");
-        let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, keycode_db);
-
-        let keymap_c = generator
-            .generate_keymap_c()
-            .map_err(|e| format!("Failed to generate keymap.c: {e}"))?;
-        let config_h = generator
-            .generate_merged_config_h()
-            .map_err(|e| format!("Failed to generate config.h: {e}"))?;
+        let emitter_ids = cmd.options.emitter_ids();
+        let ctx = EmitContext {
+            layout: &layout,
+            geometry: &geometry,
+            mapping: &mapping,
+            config: &config,
+            keycode_db,
+        };
+        let files = self
+            .emitters
+            .emit(&emitter_ids, &ctx)
+            .map_err(|e| format!("{e:#}"))?;
 
-        let _ = writeln!(
-            log_writer,
-            "[INFO] Generated keymap.c ({} bytes)",
-            keymap_c.len()
-        );
-        let _ = writeln!(
-            log_writer,
-            "[INFO] Generated config.h ({} bytes)",
-            config_h.len()
-        );
+        for file in &files {
+            let _ = writeln!(
+                log_writer,
+                "[INFO] Generated {} ({} bytes)",
+                file.name,
+                file.contents.len()
+            );
+        }
 
         // Create output directory
         fs::create_dir_all(&cmd.output_dir)
@@ -122,28 +156,28 @@ impl GenerateWorker for RealGenerateWorker {
         let layout_source = fs::read_to_string(&cmd.layout_path)
             .map_err(|e| format!("Failed to read layout source: {e}"))?;
 
-        // Read logs so far
-        let logs_content = fs::read_to_string(&cmd.log_path).unwrap_or_default();
-
-        // Create manifest
-        let manifest = serde_json::json!({
+        // Create manifest (no generation time, so archives stay reproducible)
+        let mut manifest = serde_json::json!({
             "version": "1.0",
             "generator": "lazyqmk",
-            "generated_at": chrono::Utc::now().to_rfc3339(),
             "layout": {
                 "name": layout.metadata.name,
                 "filename": cmd.layout_filename,
                 "keyboard": keyboard,
                 "layout_variant": layout_variant,
             },
-            "files": [
-                "keymap.c",
-                "config.h",
-                "layout.md",
-                "generate.log",
-                "manifest.json"
-            ]
+            "files": files
+                .iter()
+                .map(|file| file.name.as_str())
+                .chain(ARCHIVE_SOURCE_FILES)
+                .collect::<Vec<_>>(),
         });
+        if !emitter_ids.is_empty() {
+            manifest["emitters"] = serde_json::json!(emitter_ids);
+        }
+        if let Some(target) = &cmd.options.target {
+            manifest["layout"]["target"] = serde_json::json!(target);
+        }
 
         // Create zip file
         let keyboard_clean = keyboard.replace('/', "_");
@@ -152,14 +186,8 @@ impl GenerateWorker for RealGenerateWorker {
 
         let _ = writeln!(log_writer, "[INFO] Creating zip archive: {}", zip_filename);
 
-        create_firmware_zip(
-            &zip_path,
-            &keymap_c,
-            &config_h,
-            &layout_source,
-            &logs_content,
-            &manifest,
-        )?;
+        let logs_content = String::from_utf8_lossy(log_writer.captured()).into_owned();
+        create_firmware_zip(&zip_path, &files, &layout_source, &logs_content, &manifest)?;
 
         let _ = writeln!(
             log_writer,
@@ -171,35 +199,89 @@ impl GenerateWorker for RealGenerateWorker {
     }
 }
 
+/// Log writer that also keeps a copy of everything written for the archive.
+struct ArchiveLog<'a> {
+    inner: &'a mut dyn Write,
+    captured: Vec<u8>,
+}
+
+impl<'a> ArchiveLog<'a> {
+    fn new(inner: &'a mut dyn Write) -> Self {
+        Self {
+            inner,
+            captured: Vec::new(),
+        }
+    }
+
+    fn captured(&self) -> &[u8] {
+        &self.captured
+    }
+}
+
+impl Write for ArchiveLog<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.captured.extend_from_slice(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Zip helpers
 // ---------------------------------------------------------------------------
 
-/// Creates a firmware zip archive with safe filename handling.
-fn create_firmware_zip(
+/// Files written after the emitted files, in the order they are written.
+const ARCHIVE_SOURCE_FILES: [&str; 3] = ["layout.md", "generate.log", "manifest.json"];
+
+/// Deflate level used for archive entries (fixed so output is stable).
+const ARCHIVE_COMPRESSION_LEVEL: i64 = 6;
+
+/// Entry options shared by all archive files.
+///
+/// The timestamp is pinned to the zip epoch (1980-01-01 00:00) so archives
+/// don't depend on when they were built.
+fn archive_file_options() -> SimpleFileOptions {
+    SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .compression_level(Some(ARCHIVE_COMPRESSION_LEVEL))
+        .last_modified_time(DateTime::default())
+        .unix_permissions(0o644)
+}
+
+/// Creates a reproducible firmware zip archive with safe filename handling.
+///
+/// The emitted files come first, in order, followed by the layout source,
+/// the log and the manifest.
+pub(crate) fn create_firmware_zip(
     zip_path: &Path,
-    keymap_c: &str,
-    config_h: &str,
+    files: &[EmittedFile],
     layout_source: &str,
     logs: &str,
     manifest: &serde_json::Value,
 ) -> Result<(), String> {
     let file = File::create(zip_path).map_err(|e| format!("Failed to create zip file: {e}"))?;
     let mut zip = ZipWriter::new(file);
-
-    let options = SimpleFileOptions::default()
-        .compression_method(zip::CompressionMethod::Deflated)
-        .unix_permissions(0o644);
-
-    // Add files with safe, fixed names (no user input in filenames)
-    add_file_to_zip(&mut zip, "keymap.c", keymap_c.as_bytes(), options)?;
-    add_file_to_zip(&mut zip, "config.h", config_h.as_bytes(), options)?;
-    add_file_to_zip(&mut zip, "layout.md", layout_source.as_bytes(), options)?;
-    add_file_to_zip(&mut zip, "generate.log", logs.as_bytes(), options)?;
+    let options = archive_file_options();
 
     let manifest_str = serde_json::to_string_pretty(manifest)
         .map_err(|e| format!("Failed to serialize manifest: {e}"))?;
-    add_file_to_zip(&mut zip, "manifest.json", manifest_str.as_bytes(), options)?;
+    for file in files {
+        add_file_to_zip(&mut zip, &file.name, file.contents.as_bytes(), options)?;
+    }
+
+    // Add files with safe, fixed names (no user input in filenames)
+    let contents = [
+        layout_source.as_bytes(),
+        logs.as_bytes(),
+        manifest_str.as_bytes(),
+    ];
+    for (name, content) in ARCHIVE_SOURCE_FILES.into_iter().zip(contents) {
+        add_file_to_zip(&mut zip, name, content, options)?;
+    }
 
     zip.finish()
         .map_err(|e| format!("Failed to finalize zip: {e}"))?;
@@ -0,0 +1,234 @@
+//! Shared, cached keyboard geometry lookup for web handlers.
+//!
+//! `get_layout`, `get_render_metadata`, `export_layout`, and the single-key
+//! assign endpoint all need the geometry for a layout's keyboard. Parsing
+//! info.json and building the geometry dominates their response time on large
+//! keyboards, so the result is built once per (QMK path, keyboard, variant)
+//! and shared across requests.
+//!
+//! The cache is cleared when the configuration changes (e.g. a new QMK path).
+//! An entry is also rebuilt when the keyboard's JSON files change on disk
+//! (e.g. after `lazyqmk refresh` or a QMK update), checked by modification
+//! time like the TUI's [`services::geometry_cache`](crate::services::geometry_cache).
+//!
+//! When the keyboard isn't in the QMK tree (or there is none), geometry
+//! snapshots from imported share bundles and the layout's own snapshot are
+//! used instead, so imported layouts still render.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::models::{KeyboardGeometry, Layout, Position};
+use crate::parser;
+use crate::services::geometry_cache::{source_stamps, SourceStamp};
+
+/// Layout variant assumed when a layout does not specify one.
+const DEFAULT_LAYOUT_VARIANT: &str = "LAYOUT";
+
+/// Geometry-derived data for a single key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyGeometryInfo {
+    /// Layout array index from info.json
+    pub visual_index: u8,
+    /// Matrix position [row, col]
+    pub matrix_position: [u8; 2],
+    /// RGB LED index
+    pub led_index: u8,
+}
+
+/// Parsed geometry plus a visual-position lookup table.
+#[derive(Debug)]
+pub struct CachedGeometry {
+    /// Full keyboard geometry
+    pub geometry: KeyboardGeometry,
+    /// Per-key geometry data keyed by visual position
+    pub keys: HashMap<Position, KeyGeometryInfo>,
+}
+
+impl CachedGeometry {
+    fn new(geometry: KeyboardGeometry) -> Self {
+        let keys = geometry
+            .keys
+            .iter()
+            .map(|k| {
+                let position = Position::new(k.visual_y.round() as u8, k.visual_x.round() as u8);
+                let info = KeyGeometryInfo {
+                    visual_index: k.layout_index,
+                    matrix_position: [k.matrix_position.0, k.matrix_position.1],
+                    led_index: k.led_index,
+                };
+                (position, info)
+            })
+            .collect();
+
+        Self { geometry, keys }
+    }
+
+    /// Returns geometry data for the key at `position`, if the geometry has one.
+    #[must_use]
+    pub fn key(&self, position: Position) -> Option<KeyGeometryInfo> {
+        self.keys.get(&position).copied()
+    }
+}
+
+/// Cache key: QMK firmware path, keyboard, and layout variant.
+type GeometryKey = (PathBuf, String, String);
+
+/// Snapshot key: keyboard and layout variant.
+type SnapshotKey = (String, String);
+
+/// A built geometry and the keyboard files it was built from.
+#[derive(Debug)]
+struct CacheEntry {
+    /// Modification times of the keyboard's JSON files when it was built
+    sources: Vec<SourceStamp>,
+    geometry: Arc<CachedGeometry>,
+}
+
+/// Thread-safe cache of built keyboard geometries.
+#[derive(Debug, Default)]
+pub struct GeometryCache {
+    entries: RwLock<HashMap<GeometryKey, CacheEntry>>,
+    snapshots: RwLock<HashMap<SnapshotKey, Arc<CachedGeometry>>>,
+}
+
+impl GeometryCache {
+    /// Creates an empty cache.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the geometry for `layout`'s keyboard and variant.
+    ///
+    /// Falls back to the `LAYOUT` variant when none is set, and to a bundle
+    /// snapshot, then the layout's own snapshot, when no QMK path is
+    /// configured or the geometry cannot be built. Returns `None` when the layout has no keyboard or neither is
+    /// available; failures are not cached so a later fix on disk is picked up.
+    #[must_use]
+    pub fn for_layout(
+        &self,
+        qmk_path: Option<&Path>,
+        keyboard_roots: &[PathBuf],
+        layout: &Layout,
+    ) -> Option<Arc<CachedGeometry>> {
+        let keyboard = layout.metadata.keyboard.as_deref()?;
+        let variant = layout
+            .metadata
+            .layout_variant
+            .as_deref()
+            .unwrap_or(DEFAULT_LAYOUT_VARIANT);
+        qmk_path
+            .and_then(|qmk_path| self.get(qmk_path, keyboard_roots, keyboard, variant))
+            .or_else(|| {
+                self.snapshots
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .get(&(keyboard.to_string(), variant.to_string()))
+                    .cloned()
+            })
+            .or_else(|| {
+                layout
+                    .snapshot_geometry()
+                    .map(|geometry| Arc::new(CachedGeometry::new(geometry)))
+            })
+    }
+
+    /// Stores `geometry` as the fallback for `layout`'s keyboard and variant.
+    ///
+    /// Does nothing if the layout has no keyboard.
+    pub fn insert_snapshot(&self, layout: &Layout, geometry: KeyboardGeometry) {
+        let Some(keyboard) = layout.metadata.keyboard.clone() else {
+            return;
+        };
+        let variant = layout
+            .metadata
+            .layout_variant
+            .clone()
+            .unwrap_or_else(|| DEFAULT_LAYOUT_VARIANT.to_string());
+        self.snapshots
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert((keyboard, variant), Arc::new(CachedGeometry::new(geometry)));
+    }
+
+    /// Returns the geometry for `keyboard`/`variant`, building it on a miss.
+    ///
+    /// An entry is rebuilt when the keyboard's JSON files have changed since
+    /// it was built. `keyboard_roots` are searched before QMK's own
+    /// keyboards. They are not part of the cache key; the cache is cleared
+    /// when the config changes.
+    #[must_use]
+    pub fn get(
+        &self,
+        qmk_path: &Path,
+        keyboard_roots: &[PathBuf],
+        keyboard: &str,
+        variant: &str,
+    ) -> Option<Arc<CachedGeometry>> {
+        let key = (
+            qmk_path.to_path_buf(),
+            keyboard.to_string(),
+            variant.to_string(),
+        );
+        let sources = source_stamps(qmk_path, keyboard_roots, keyboard);
+
+        if let Some(entry) = self
+            .entries
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+            .filter(|entry| entry.sources == sources)
+        {
+            return Some(Arc::clone(&entry.geometry));
+        }
+
+        let info =
+            parser::keyboard_json::parse_keyboard_info_json(qmk_path, keyboard_roots, keyboard)
+                .ok()?;
+        let geometry =
+            parser::keyboard_json::build_keyboard_geometry_with_rgb(&info, keyboard, variant, None)
+                .ok()?;
+        let cached = Arc::new(CachedGeometry::new(geometry));
+
+        self.entries
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                key,
+                CacheEntry {
+                    sources,
+                    geometry: Arc::clone(&cached),
+                },
+            );
+
+        Some(cached)
+    }
+
+    /// Drops all cached geometries; snapshots are kept.
+    pub fn clear(&self) {
+        self.entries
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Returns the number of cached geometries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns true if nothing is cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,109 @@
+//! Tests for the geometry cache.
+
+use super::*;
+
+use std::fs;
+use tempfile::TempDir;
+
+fn write_test_keyboard(qmk_path: &Path) {
+    let keyboard_dir = qmk_path.join("keyboards").join("test_keyboard");
+    fs::create_dir_all(&keyboard_dir).unwrap();
+    let info_json = serde_json::json!({
+        "keyboard_name": "test_keyboard",
+        "layouts": {
+            "LAYOUT": {
+                "layout": [
+                    {"matrix": [0, 0], "x": 0, "y": 0},
+                    {"matrix": [0, 1], "x": 1, "y": 0},
+                    {"matrix": [1, 0], "x": 0, "y": 1},
+                    {"matrix": [1, 1], "x": 1, "y": 1}
+                ]
+            }
+        }
+    });
+    fs::write(keyboard_dir.join("info.json"), info_json.to_string()).unwrap();
+}
+
+fn test_layout(keyboard: Option<&str>) -> Layout {
+    let mut layout = Layout::new("cache_test").unwrap();
+    layout.metadata.keyboard = keyboard.map(str::to_string);
+    layout
+}
+
+#[test]
+fn test_for_layout_without_qmk_or_keyboard_is_none() {
+    let cache = GeometryCache::new();
+    let tmp = TempDir::new().unwrap();
+
+    assert!(cache
+        .for_layout(None, &[], &test_layout(Some("test_keyboard")))
+        .is_none());
+    assert!(cache
+        .for_layout(Some(tmp.path()), &[], &test_layout(None))
+        .is_none());
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_for_layout_builds_once_and_reuses() {
+    let tmp = TempDir::new().unwrap();
+    write_test_keyboard(tmp.path());
+    let cache = GeometryCache::new();
+    let layout = test_layout(Some("test_keyboard"));
+
+    let first = cache.for_layout(Some(tmp.path()), &[], &layout).unwrap();
+    let second = cache.for_layout(Some(tmp.path()), &[], &layout).unwrap();
+
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(cache.len(), 1);
+
+    let key = first.key(Position::new(1, 1)).unwrap();
+    assert_eq!(key.visual_index, 3);
+    assert_eq!(key.matrix_position, [1, 1]);
+}
+
+#[test]
+fn test_failures_are_not_cached_and_clear_empties() {
+    let tmp = TempDir::new().unwrap();
+    let cache = GeometryCache::new();
+    let layout = test_layout(Some("test_keyboard"));
+
+    assert!(cache.for_layout(Some(tmp.path()), &[], &layout).is_none());
+    assert!(cache.is_empty());
+
+    write_test_keyboard(tmp.path());
+    assert!(cache.for_layout(Some(tmp.path()), &[], &layout).is_some());
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_changed_info_json_is_rebuilt() {
+    let tmp = TempDir::new().unwrap();
+    write_test_keyboard(tmp.path());
+    let cache = GeometryCache::new();
+    let layout = test_layout(Some("test_keyboard"));
+    let first = cache.for_layout(Some(tmp.path()), &[], &layout).unwrap();
+    assert_eq!(first.geometry.keys.len(), 4);
+
+    // e.g. `lazyqmk refresh` or a QMK update rewrote info.json
+    let info_path = tmp.path().join("keyboards/test_keyboard/info.json");
+    let info_json = serde_json::json!({
+        "keyboard_name": "test_keyboard",
+        "layouts": {"LAYOUT": {"layout": [{"matrix": [0, 0], "x": 0, "y": 0}]}}
+    });
+    fs::write(&info_path, info_json.to_string()).unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&info_path)
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() + std::time::Duration::from_mins(1))
+        .unwrap();
+
+    let second = cache.for_layout(Some(tmp.path()), &[], &layout).unwrap();
+    assert_eq!(second.geometry.keys.len(), 1);
+    assert_eq!(cache.len(), 1);
+    let third = cache.for_layout(Some(tmp.path()), &[], &layout).unwrap();
+    assert!(Arc::ptr_eq(&second, &third));
+}
@@ -0,0 +1,171 @@
+//! Per-layout undo/redo journal for web API edits.
+//!
+//! Every mutating layout handler records the layout before and after its
+//! change. `POST /api/layouts/{filename}/history/undo` restores the previous
+//! version and `/redo` reapplies it, so the frontend gets undo without
+//! diffing layouts itself. The journal is shared by all sessions editing a
+//! layout and lives in memory; it is lost when the server restarts.
+//!
+//! A step is only undone or redone while the stored layout is still the
+//! version the journal expects. If the layout was changed some other way
+//! (e.g. saved by the TUI), the journal for that layout is discarded instead
+//! of overwriting that change.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use crate::models::Layout;
+
+/// Steps kept per layout; older steps are dropped.
+pub const HISTORY_LIMIT: usize = 50;
+
+/// One recorded change.
+#[derive(Debug, Clone)]
+struct Step {
+    action: String,
+    before: Layout,
+    after: Layout,
+}
+
+/// Undo and redo stacks of one layout.
+#[derive(Debug, Default)]
+struct Journal {
+    undo: Vec<Step>,
+    redo: Vec<Step>,
+}
+
+impl Journal {
+    /// Stack a move in `direction` takes from, and the one it moves to.
+    fn stacks(&mut self, direction: HistoryDirection) -> (&mut Vec<Step>, &mut Vec<Step>) {
+        match direction {
+            HistoryDirection::Undo => (&mut self.undo, &mut self.redo),
+            HistoryDirection::Redo => (&mut self.redo, &mut self.undo),
+        }
+    }
+}
+
+/// Direction of a history move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryDirection {
+    /// Restore the version before the latest change
+    Undo,
+    /// Reapply the latest undone change
+    Redo,
+}
+
+/// Why a history move could not be made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryError {
+    /// Nothing to undo or redo
+    Empty,
+    /// The layout no longer matches the journal; the journal was cleared
+    Diverged,
+}
+
+/// A history move ready to be saved.
+#[derive(Debug, Clone)]
+pub struct HistoryMove {
+    /// Action of the step that was undone or redone (e.g. `swap_keys`)
+    pub action: String,
+    /// Layout to save
+    pub layout: Layout,
+}
+
+/// Undo/redo journals of all layouts, keyed by filename.
+#[derive(Debug, Default)]
+pub struct EditHistory {
+    journals: Mutex<HashMap<String, Journal>>,
+}
+
+impl EditHistory {
+    /// Creates an empty history.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `action` changed `filename` from `before` to `after`.
+    ///
+    /// Clears the redo stack. Changes that leave the layout as it was are
+    /// not recorded.
+    pub fn record(&self, filename: &str, action: &str, before: &Layout, after: &Layout) {
+        if before == after {
+            return;
+        }
+        let mut journals = self.journals.lock().unwrap_or_else(PoisonError::into_inner);
+        let journal = journals.entry(filename.to_string()).or_default();
+        journal.redo.clear();
+        if journal.undo.len() == HISTORY_LIMIT {
+            journal.undo.remove(0);
+        }
+        journal.undo.push(Step {
+            action: action.to_string(),
+            before: before.clone(),
+            after: after.clone(),
+        });
+        drop(journals);
+    }
+
+    /// Takes the next undo or redo step for `filename`, given the layout
+    /// currently stored.
+    ///
+    /// The step moves to the opposite stack; call [`Self::revert`] if saving
+    /// its layout fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HistoryError::Empty`] if there is no step, and
+    /// [`HistoryError::Diverged`] (clearing the journal) if `current` is not
+    /// the version the step starts from.
+    pub fn take(
+        &self,
+        filename: &str,
+        direction: HistoryDirection,
+        current: &Layout,
+    ) -> Result<HistoryMove, HistoryError> {
+        let mut journals = self.journals.lock().unwrap_or_else(PoisonError::into_inner);
+        let journal = journals.get_mut(filename).ok_or(HistoryError::Empty)?;
+        let (from, to) = journal.stacks(direction);
+        let step = from.last().ok_or(HistoryError::Empty)?;
+        let (expected, target) = match direction {
+            HistoryDirection::Undo => (&step.after, &step.before),
+            HistoryDirection::Redo => (&step.before, &step.after),
+        };
+        if expected != current {
+            journals.remove(filename);
+            return Err(HistoryError::Diverged);
+        }
+
+        let history_move = HistoryMove {
+            action: step.action.clone(),
+            layout: target.clone(),
+        };
+        to.extend(from.pop());
+        drop(journals);
+        Ok(history_move)
+    }
+
+    /// Undoes a [`Self::take`] whose layout could not be saved.
+    pub fn revert(&self, filename: &str, direction: HistoryDirection) {
+        let mut journals = self.journals.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(journal) = journals.get_mut(filename) else {
+            return;
+        };
+        let (from, to) = journal.stacks(direction);
+        from.extend(to.pop());
+        drop(journals);
+    }
+
+    /// Returns the number of steps that can be undone and redone.
+    #[must_use]
+    pub fn depth(&self, filename: &str) -> (usize, usize) {
+        self.journals
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(filename)
+            .map_or((0, 0), |journal| (journal.undo.len(), journal.redo.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests;
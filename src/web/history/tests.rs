@@ -0,0 +1,96 @@
+//! Tests for the undo/redo journal.
+
+use super::*;
+use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+
+fn layout_with(keycode: &str) -> Layout {
+    let mut layout = Layout::new("History").unwrap();
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+    layer.add_key(KeyDefinition::new(Position::new(0, 0), keycode));
+    layout.add_layer(layer).unwrap();
+    layout
+}
+
+#[test]
+fn test_undo_and_redo_walk_the_journal() {
+    let history = EditHistory::new();
+    let (a, b, c) = (
+        layout_with("KC_A"),
+        layout_with("KC_B"),
+        layout_with("KC_C"),
+    );
+    history.record("x.json", "assign_key", &a, &b);
+    history.record("x.json", "swap_keys", &b, &c);
+    assert_eq!(history.depth("x.json"), (2, 0));
+
+    let undone = history.take("x.json", HistoryDirection::Undo, &c).unwrap();
+    assert_eq!(undone.action, "swap_keys");
+    assert_eq!(undone.layout, b);
+    assert_eq!(history.depth("x.json"), (1, 1));
+
+    let redone = history.take("x.json", HistoryDirection::Redo, &b).unwrap();
+    assert_eq!(redone.layout, c);
+    assert_eq!(history.depth("x.json"), (2, 0));
+}
+
+#[test]
+fn test_new_change_clears_redo() {
+    let history = EditHistory::new();
+    let (a, b, c) = (
+        layout_with("KC_A"),
+        layout_with("KC_B"),
+        layout_with("KC_C"),
+    );
+    history.record("x.json", "assign_key", &a, &b);
+    history.take("x.json", HistoryDirection::Undo, &b).unwrap();
+
+    history.record("x.json", "assign_key", &a, &c);
+    assert_eq!(history.depth("x.json"), (1, 0));
+    assert_eq!(
+        history
+            .take("x.json", HistoryDirection::Redo, &c)
+            .unwrap_err(),
+        HistoryError::Empty
+    );
+}
+
+#[test]
+fn test_diverged_layout_clears_the_journal() {
+    let history = EditHistory::new();
+    let (a, b) = (layout_with("KC_A"), layout_with("KC_B"));
+    history.record("x.json", "assign_key", &a, &b);
+
+    let edited_elsewhere = layout_with("KC_Z");
+    assert_eq!(
+        history
+            .take("x.json", HistoryDirection::Undo, &edited_elsewhere)
+            .unwrap_err(),
+        HistoryError::Diverged
+    );
+    assert_eq!(history.depth("x.json"), (0, 0));
+}
+
+#[test]
+fn test_revert_restores_a_failed_move() {
+    let history = EditHistory::new();
+    let (a, b) = (layout_with("KC_A"), layout_with("KC_B"));
+    history.record("x.json", "assign_key", &a, &b);
+
+    history.take("x.json", HistoryDirection::Undo, &b).unwrap();
+    history.revert("x.json", HistoryDirection::Undo);
+    assert_eq!(history.depth("x.json"), (1, 0));
+}
+
+#[test]
+fn test_journal_is_limited_and_skips_no_op_changes() {
+    let history = EditHistory::new();
+    let (a, b) = (layout_with("KC_A"), layout_with("KC_B"));
+    history.record("x.json", "save", &a, &a);
+    assert_eq!(history.depth("x.json"), (0, 0));
+
+    for _ in 0..HISTORY_LIMIT + 5 {
+        history.record("x.json", "assign_key", &a, &b);
+    }
+    assert_eq!(history.depth("x.json"), (HISTORY_LIMIT, 0));
+    assert_eq!(history.depth("other.json"), (0, 0));
+}
@@ -0,0 +1,180 @@
+//! Live progress of build and generate jobs.
+//!
+//! Each job manager publishes a [`JobEvent`] whenever a job changes status
+//! and for every line written to a job's log. `GET /api/ws` forwards these
+//! events to WebSocket clients, so the frontend no longer has to poll
+//! `GET .../logs` while a long build runs. Logs are still written to disk;
+//! the events are a live copy.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use super::build_jobs::{self, BuildJob};
+use super::generate_jobs::{self, GenerateJob};
+
+/// Events buffered before a slow subscriber starts lagging.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Which job manager a log line belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    /// Firmware build (`qmk compile`)
+    Build,
+    /// Firmware generation (keymap files and zip)
+    Generate,
+}
+
+/// A change to a build or generate job.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobEvent {
+    /// A build job was queued or changed status; `job` is its new state
+    BuildStatus {
+        /// Full job state, as returned by `GET /api/build/jobs/{job_id}`
+        job: BuildJob,
+    },
+    /// A line was appended to a build job's log
+    BuildLog {
+        /// Job the line belongs to
+        job_id: String,
+        /// The log line
+        entry: build_jobs::LogEntry,
+    },
+    /// A generate job was queued or changed status; `job` is its new state
+    GenerateStatus {
+        /// Full job state, as returned by `GET /api/generate/jobs/{job_id}`
+        job: GenerateJob,
+    },
+    /// A line was appended to a generate job's log
+    GenerateLog {
+        /// Job the line belongs to
+        job_id: String,
+        /// The log line
+        entry: generate_jobs::LogEntry,
+    },
+}
+
+/// Broadcast channel for the events of one job manager.
+#[derive(Debug, Clone)]
+pub struct JobEvents {
+    tx: broadcast::Sender<JobEvent>,
+}
+
+impl Default for JobEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JobEvents {
+    /// Creates a channel with no subscribers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            tx: broadcast::channel(CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// Subscribes to the events published from now on.
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<JobEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Sends `event` to every subscriber. Without subscribers it is dropped.
+    pub fn publish(&self, event: JobEvent) {
+        let _ = self.tx.send(event);
+    }
+
+    /// Publishes a log line (`[LEVEL] message`) of the `kind` job `job_id`.
+    pub fn log_line(&self, kind: JobKind, job_id: &str, line: &str) {
+        if self.tx.receiver_count() == 0 {
+            return;
+        }
+        let timestamp = chrono::Utc::now().to_rfc3339();
+        let job_id = job_id.to_string();
+        let event = match kind {
+            JobKind::Build => {
+                let (level, message) = build_jobs::parse_log_line(line);
+                JobEvent::BuildLog {
+                    job_id,
+                    entry: build_jobs::LogEntry {
+                        timestamp,
+                        level,
+                        message,
+                    },
+                }
+            }
+            JobKind::Generate => {
+                let (level, message) = generate_jobs::parse_log_line(line);
+                JobEvent::GenerateLog {
+                    job_id,
+                    entry: generate_jobs::LogEntry {
+                        timestamp,
+                        level,
+                        message,
+                    },
+                }
+            }
+        };
+        self.publish(event);
+    }
+}
+
+/// Log writer that also publishes every complete line as a [`JobEvent`].
+pub struct LogTee<'a, W: Write> {
+    inner: W,
+    events: &'a JobEvents,
+    kind: JobKind,
+    job_id: &'a str,
+    /// Bytes written since the last newline
+    pending: Vec<u8>,
+}
+
+impl<'a, W: Write> LogTee<'a, W> {
+    /// Wraps `inner`, the log of the `kind` job `job_id`.
+    pub fn new(inner: W, events: &'a JobEvents, kind: JobKind, job_id: &'a str) -> Self {
+        Self {
+            inner,
+            events,
+            kind,
+            job_id,
+            pending: Vec::new(),
+        }
+    }
+
+    fn publish(&self, line: &[u8]) {
+        let line = String::from_utf8_lossy(line);
+        self.events
+            .log_line(self.kind, self.job_id, line.trim_end_matches('\r'));
+    }
+}
+
+impl<W: Write> Write for LogTee<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.pending.extend_from_slice(&buf[..written]);
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            self.publish(&line[..end]);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Drop for LogTee<'_, W> {
+    fn drop(&mut self) {
+        if !self.pending.is_empty() {
+            self.publish(&self.pending);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
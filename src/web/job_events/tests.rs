@@ -0,0 +1,58 @@
+//! Tests for web::job_events.
+
+use super::*;
+
+fn log_entries(rx: &mut broadcast::Receiver<JobEvent>) -> Vec<(String, String, String)> {
+    let mut entries = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        match event {
+            JobEvent::BuildLog { job_id, entry } => {
+                entries.push((job_id, entry.level, entry.message));
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+    entries
+}
+
+#[test]
+fn test_log_tee_publishes_complete_lines() {
+    let events = JobEvents::new();
+    let mut rx = events.subscribe();
+    let mut file = Vec::new();
+
+    {
+        let mut log = LogTee::new(&mut file, &events, JobKind::Build, "job-1");
+        write!(log, "[INFO] Compiling").unwrap();
+        assert!(log_entries(&mut rx).is_empty(), "partial line published");
+        writeln!(log, " keymap.c").unwrap();
+        write!(log, "[ERROR] first\r\nsecond\nunterminated").unwrap();
+    }
+
+    assert_eq!(
+        log_entries(&mut rx),
+        vec![
+            ("job-1".into(), "INFO".into(), "Compiling keymap.c".into()),
+            ("job-1".into(), "ERROR".into(), "first".into()),
+            ("job-1".into(), "INFO".into(), "second".into()),
+            ("job-1".into(), "INFO".into(), "unterminated".into()),
+        ]
+    );
+    assert_eq!(
+        String::from_utf8(file).unwrap(),
+        "[INFO] Compiling keymap.c\n[ERROR] first\r\nsecond\nunterminated"
+    );
+}
+
+#[test]
+fn test_event_serialization_is_tagged() {
+    let events = JobEvents::new();
+    let mut rx = events.subscribe();
+    events.log_line(JobKind::Generate, "gen-1", "[WARN] careful");
+
+    let json = serde_json::to_value(rx.try_recv().unwrap()).unwrap();
+    assert_eq!(json["type"], "generate_log");
+    assert_eq!(json["job_id"], "gen-1");
+    assert_eq!(json["entry"]["level"], "WARN");
+    assert_eq!(json["entry"]["message"], "careful");
+}
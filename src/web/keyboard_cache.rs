@@ -0,0 +1,123 @@
+//! Cached keyboard list for `GET /api/keyboards`.
+//!
+//! Scanning a full QMK tree takes seconds, so the [`KeyboardIndex`] is kept
+//! in memory and saved under `.lazyqmk/` in the workspace. An index loaded
+//! from disk is served right away and rebuilt in the background, so keyboards
+//! added since the last run show up on a later request without anyone
+//! waiting for the scan.
+//!
+//! The in-memory index is dropped when the configuration changes; an index
+//! built for another QMK path or set of keyboard roots is never served.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use tracing::warn;
+
+use crate::services::keyboard_index::KeyboardIndex;
+
+/// Thread-safe keyboard index with an on-disk copy.
+#[derive(Debug)]
+pub struct KeyboardCache {
+    /// Where the index is persisted
+    file: PathBuf,
+    current: RwLock<Option<Arc<KeyboardIndex>>>,
+    /// Set while a background rebuild runs
+    refreshing: AtomicBool,
+}
+
+impl KeyboardCache {
+    /// Creates an empty cache persisted at `file`.
+    #[must_use]
+    pub fn new(file: PathBuf) -> Self {
+        Self {
+            file,
+            current: RwLock::new(None),
+            refreshing: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the index for `qmk_path` and `keyboard_roots` without scanning.
+    ///
+    /// Falls back to the index saved on disk; serving that one also starts a
+    /// background rebuild. Returns `None` when neither applies, in which case
+    /// the caller rebuilds with [`KeyboardCache::rebuild`].
+    pub fn cached(
+        self: &Arc<Self>,
+        qmk_path: &Path,
+        keyboard_roots: &[PathBuf],
+    ) -> Option<Arc<KeyboardIndex>> {
+        if let Some(index) = self
+            .current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .filter(|index| index.matches(qmk_path, keyboard_roots))
+        {
+            return Some(Arc::clone(index));
+        }
+
+        let index = KeyboardIndex::load(&self.file)
+            .filter(|index| index.matches(qmk_path, keyboard_roots))
+            .map(Arc::new)?;
+        self.store(Arc::clone(&index));
+        self.refresh_in_background(qmk_path, keyboard_roots);
+        Some(index)
+    }
+
+    /// Scans the keyboard directories and replaces the cached index.
+    ///
+    /// Blocks for the duration of the scan. Failing to save the index is
+    /// logged; the new index is still used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the QMK keyboards directory does not exist.
+    pub fn rebuild(
+        &self,
+        qmk_path: &Path,
+        keyboard_roots: &[PathBuf],
+    ) -> anyhow::Result<Arc<KeyboardIndex>> {
+        let index = Arc::new(KeyboardIndex::build(qmk_path, keyboard_roots)?);
+        if let Err(e) = index.save(&self.file) {
+            warn!(error = %e, "failed to save keyboard index");
+        }
+        self.store(Arc::clone(&index));
+        Ok(index)
+    }
+
+    /// Drops the in-memory index.
+    pub fn clear(&self) {
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    /// Returns true while a background rebuild is running.
+    #[must_use]
+    pub fn is_refreshing(&self) -> bool {
+        self.refreshing.load(Ordering::Acquire)
+    }
+
+    fn store(&self, index: Arc<KeyboardIndex>) {
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Some(index);
+    }
+
+    /// Rebuilds on a blocking thread unless a rebuild is already running.
+    fn refresh_in_background(self: &Arc<Self>, qmk_path: &Path, keyboard_roots: &[PathBuf]) {
+        if self.refreshing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let cache = Arc::clone(self);
+        let qmk_path = qmk_path.to_path_buf();
+        let keyboard_roots = keyboard_roots.to_vec();
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = cache.rebuild(&qmk_path, &keyboard_roots) {
+                warn!(error = %e, "background keyboard index refresh failed");
+            }
+            cache.refreshing.store(false, Ordering::Release);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,73 @@
+//! Tests for the keyboard list cache.
+
+use super::*;
+
+use std::fs;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn add_keyboard(qmk_path: &Path, name: &str) {
+    let keyboard_dir = qmk_path.join("keyboards").join(name);
+    fs::create_dir_all(&keyboard_dir).unwrap();
+    fs::write(
+        keyboard_dir.join("info.json"),
+        r#"{"layouts": {"LAYOUT": {"layout": [{"x": 0, "y": 0}]}}}"#,
+    )
+    .unwrap();
+}
+
+fn paths(index: &KeyboardIndex) -> Vec<&str> {
+    index.keyboards.iter().map(|k| k.path.as_str()).collect()
+}
+
+#[test]
+fn test_rebuild_is_served_from_memory() {
+    let tmp = TempDir::new().unwrap();
+    add_keyboard(tmp.path(), "planck");
+    let cache = Arc::new(KeyboardCache::new(tmp.path().join("index.json")));
+
+    assert!(cache.cached(tmp.path(), &[]).is_none());
+    cache.rebuild(tmp.path(), &[]).unwrap();
+    add_keyboard(tmp.path(), "preonic");
+
+    let index = cache.cached(tmp.path(), &[]).unwrap();
+    assert_eq!(paths(&index), vec!["planck"]);
+    assert!(cache.cached(Path::new("/other/qmk"), &[]).is_none());
+}
+
+#[tokio::test]
+async fn test_disk_index_is_served_then_refreshed() {
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("index.json");
+    add_keyboard(tmp.path(), "planck");
+    KeyboardCache::new(file.clone())
+        .rebuild(tmp.path(), &[])
+        .unwrap();
+    add_keyboard(tmp.path(), "preonic");
+
+    let cache = Arc::new(KeyboardCache::new(file));
+    let index = cache.cached(tmp.path(), &[]).unwrap();
+    assert_eq!(paths(&index), vec!["planck"]);
+
+    for _ in 0..100 {
+        if !cache.is_refreshing() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let index = cache.cached(tmp.path(), &[]).unwrap();
+    assert_eq!(paths(&index), vec!["planck", "preonic"]);
+}
+
+#[test]
+fn test_clear_drops_memory_index() {
+    let tmp = TempDir::new().unwrap();
+    add_keyboard(tmp.path(), "planck");
+    let cache = Arc::new(KeyboardCache::new(tmp.path().join("missing/index.json")));
+    cache.rebuild(tmp.path(), &[]).unwrap();
+    fs::remove_file(tmp.path().join("missing/index.json")).unwrap();
+
+    cache.clear();
+
+    assert!(cache.cached(tmp.path(), &[]).is_none());
+}
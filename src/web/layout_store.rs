@@ -0,0 +1,318 @@
+//! Pluggable storage for the layouts served by the web API.
+//!
+//! Handlers never touch layout files directly; they go through the
+//! [`LayoutStore`] held by [`AppState`](super::AppState). Three backends exist:
+//!
+//! - [`LocalLayoutStore`] — a directory on disk (the `--workspace`), backed by
+//!   [`LayoutService`]. This is the default.
+//! - [`HttpLayoutStore`] — a WebDAV or plain HTTP server, selected with
+//!   `lazyqmk web --storage https://host/layouts/` or `[web] storage_url`. This
+//!   lets the server run stateless in a container.
+//! - [`MemoryLayoutStore`] — layouts kept in memory, seeded from the
+//!   workspace, selected with `--storage memory:` or `lazyqmk web --sandbox`.
+//!   Saves never reach the disk.
+//!
+//! With a remote or memory store the workspace directory is still used as
+//! local scratch space: build/generate job output, audit logs, and copies of layouts staged
+//! for firmware jobs live there.
+//!
+//! The local store backs up each file before overwriting it (see
+//! [`crate::services::backup`]); remote stores keep no backups.
+
+mod http;
+mod memory;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use anyhow::{bail, Context, Result};
+use tracing::warn;
+
+use crate::models::Layout;
+use crate::services::backup::{BackupInfo, BackupStore};
+use crate::services::workspace_lock::FileStamp;
+use crate::services::LayoutService;
+
+pub use http::HttpLayoutStore;
+pub use memory::MemoryLayoutStore;
+
+/// Storage URL selecting [`MemoryLayoutStore`].
+pub const MEMORY_STORAGE_URL: &str = "memory:";
+
+/// Storage backend for layout files.
+///
+/// Filenames are bare names such as `corne.json`, already checked with
+/// [`validate_filename`](super::validation::validate_filename).
+pub trait LayoutStore: Send + Sync {
+    /// Human-readable location of the store (directory or URL), for logs.
+    fn describe(&self) -> String;
+
+    /// Lists the filenames of all stored layouts.
+    fn list(&self) -> Result<Vec<String>>;
+
+    /// Returns true if a layout named `filename` exists.
+    fn exists(&self, filename: &str) -> Result<bool>;
+
+    /// Loads the layout named `filename`.
+    fn load(&self, filename: &str) -> Result<Layout>;
+
+    /// Saves `layout` as `filename`, replacing any existing layout.
+    fn save(&self, filename: &str, layout: &Layout) -> Result<()>;
+
+    /// Returns the on-disk path of `filename` if this store keeps layouts
+    /// on the local file system.
+    fn local_path(&self, _filename: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns true if `filename` was written by someone other than this
+    /// store (e.g. the TUI) since the previous call or this store's last
+    /// save. The first call for a file only records its current version.
+    ///
+    /// Stores that can't tell always return false.
+    fn modified_externally(&self, _filename: &str) -> bool {
+        false
+    }
+
+    /// Lists the backups of `filename`, newest first.
+    ///
+    /// Stores without backups return an error.
+    fn backups(&self, _filename: &str) -> Result<Vec<BackupInfo>> {
+        bail!("Backups are only kept for layouts stored on this machine")
+    }
+
+    /// Loads the backup `id` of `filename`.
+    ///
+    /// Stores without backups return an error.
+    fn load_backup(&self, _filename: &str, _id: &str) -> Result<Layout> {
+        bail!("Backups are only kept for layouts stored on this machine")
+    }
+}
+
+/// Async access to a [`LayoutStore`] for request handlers.
+///
+/// Store calls block on file or network I/O, so each one runs on tokio's
+/// blocking thread pool instead of a runtime worker thread.
+#[derive(Clone)]
+pub struct AsyncLayoutStore(Arc<dyn LayoutStore>);
+
+impl AsyncLayoutStore {
+    /// Wraps `store` for use from async code.
+    #[must_use]
+    pub fn new(store: Arc<dyn LayoutStore>) -> Self {
+        Self(store)
+    }
+
+    /// See [`LayoutStore::describe`].
+    #[must_use]
+    pub fn describe(&self) -> String {
+        self.0.describe()
+    }
+
+    /// See [`LayoutStore::local_path`].
+    #[must_use]
+    pub fn local_path(&self, filename: &str) -> Option<PathBuf> {
+        self.0.local_path(filename)
+    }
+
+    /// See [`LayoutStore::modified_externally`].
+    #[must_use]
+    pub fn modified_externally(&self, filename: &str) -> bool {
+        self.0.modified_externally(filename)
+    }
+
+    /// See [`LayoutStore::list`].
+    pub async fn list(&self) -> Result<Vec<String>> {
+        self.run(|store| store.list()).await
+    }
+
+    /// See [`LayoutStore::exists`].
+    pub async fn exists(&self, filename: &str) -> Result<bool> {
+        let filename = filename.to_string();
+        self.run(move |store| store.exists(&filename)).await
+    }
+
+    /// See [`LayoutStore::load`].
+    pub async fn load(&self, filename: &str) -> Result<Layout> {
+        let filename = filename.to_string();
+        self.run(move |store| store.load(&filename)).await
+    }
+
+    /// See [`LayoutStore::save`].
+    pub async fn save(&self, filename: &str, layout: &Layout) -> Result<()> {
+        let filename = filename.to_string();
+        let layout = layout.clone();
+        self.run(move |store| store.save(&filename, &layout)).await
+    }
+
+    /// See [`LayoutStore::backups`].
+    pub async fn backups(&self, filename: &str) -> Result<Vec<BackupInfo>> {
+        let filename = filename.to_string();
+        self.run(move |store| store.backups(&filename)).await
+    }
+
+    /// See [`LayoutStore::load_backup`].
+    pub async fn load_backup(&self, filename: &str, id: &str) -> Result<Layout> {
+        let filename = filename.to_string();
+        let id = id.to_string();
+        self.run(move |store| store.load_backup(&filename, &id))
+            .await
+    }
+
+    /// Runs `call` with the store on the blocking thread pool.
+    async fn run<T, F>(&self, call: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn LayoutStore) -> Result<T> + Send + 'static,
+    {
+        let store = Arc::clone(&self.0);
+        tokio::task::spawn_blocking(move || call(store.as_ref()))
+            .await
+            .context("Layout store task failed")?
+    }
+}
+
+/// Opens the store selected by `storage_url`, or the local workspace
+/// directory when no URL is configured.
+///
+/// `memory:` keeps layouts in memory, starting from a copy of the
+/// workspace layouts.
+///
+/// `backups` receives a copy of each local file before it is overwritten.
+///
+/// # Errors
+///
+/// Returns an error if the URL uses an unsupported scheme or is malformed.
+pub fn open(
+    storage_url: Option<&str>,
+    workspace_root: &Path,
+    backups: Option<BackupStore>,
+) -> Result<Arc<dyn LayoutStore>> {
+    match storage_url.map(str::trim).filter(|url| !url.is_empty()) {
+        None => {
+            let mut store = LocalLayoutStore::new(workspace_root);
+            if let Some(backups) = backups {
+                store = store.with_backups(backups);
+            }
+            Ok(Arc::new(store))
+        }
+        Some(url) if url.starts_with("http://") || url.starts_with("https://") => {
+            Ok(Arc::new(HttpLayoutStore::new(url)?))
+        }
+        Some(MEMORY_STORAGE_URL) => Ok(Arc::new(MemoryLayoutStore::seeded_from(workspace_root))),
+        Some(url) => bail!(
+            "Unsupported storage URL: {url} (expected http://, https:// or {MEMORY_STORAGE_URL})"
+        ),
+    }
+}
+
+/// Layouts stored as files in a local directory.
+#[derive(Debug, Clone)]
+pub struct LocalLayoutStore {
+    root: PathBuf,
+    /// Last known version of each file, for [`LayoutStore::modified_externally`]
+    stamps: Arc<Mutex<HashMap<String, FileStamp>>>,
+    /// Where files are backed up before they're overwritten
+    backups: Option<BackupStore>,
+}
+
+impl LocalLayoutStore {
+    /// Creates a store for the layouts in `root`.
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            stamps: Arc::default(),
+            backups: None,
+        }
+    }
+
+    /// Backs up each file into `backups` before overwriting it.
+    #[must_use]
+    pub fn with_backups(mut self, backups: BackupStore) -> Self {
+        self.backups = Some(backups);
+        self
+    }
+
+    fn backup_store(&self) -> Result<&BackupStore> {
+        self.backups
+            .as_ref()
+            .context("Backups are disabled for this workspace")
+    }
+}
+
+impl LayoutStore for LocalLayoutStore {
+    fn describe(&self) -> String {
+        self.root.display().to_string()
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let entries = fs::read_dir(&self.root)
+            .with_context(|| format!("Failed to read {}", self.root.display()))?;
+
+        Ok(entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext == "json" || ext == "md")
+            })
+            .filter_map(|path| Some(path.file_name()?.to_string_lossy().to_string()))
+            .collect())
+    }
+
+    fn exists(&self, filename: &str) -> Result<bool> {
+        Ok(self.root.join(filename).exists())
+    }
+
+    fn load(&self, filename: &str) -> Result<Layout> {
+        LayoutService::load(&self.root.join(filename))
+    }
+
+    fn save(&self, filename: &str, layout: &Layout) -> Result<()> {
+        let path = self.root.join(filename);
+        if let Some(backups) = &self.backups {
+            // A failed backup must not stop the save itself
+            if let Err(e) = backups.backup(&path) {
+                warn!(error = %e, path = %path.display(), "failed to back up layout");
+            }
+        }
+        LayoutService::save(layout, &path)?;
+        if let Some(stamp) = FileStamp::of(&path) {
+            self.stamps
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(filename.to_string(), stamp);
+        }
+        Ok(())
+    }
+
+    fn local_path(&self, filename: &str) -> Option<PathBuf> {
+        Some(self.root.join(filename))
+    }
+
+    fn modified_externally(&self, filename: &str) -> bool {
+        let Some(stamp) = FileStamp::of(&self.root.join(filename)) else {
+            return false;
+        };
+        let previous = self
+            .stamps
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(filename.to_string(), stamp);
+        previous.is_some_and(|previous| previous != stamp)
+    }
+
+    fn backups(&self, filename: &str) -> Result<Vec<BackupInfo>> {
+        self.backup_store()?.list(&self.root.join(filename))
+    }
+
+    fn load_backup(&self, filename: &str, id: &str) -> Result<Layout> {
+        self.backup_store()?.load(&self.root.join(filename), id)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,91 @@
+//! Layout store that keeps layouts in memory.
+//!
+//! Nothing is ever written to disk: saves replace the in-memory copy and are
+//! lost when the server stops. The store can be seeded from a directory,
+//! whose files are only read. This backs sandbox servers (demo and
+//! playground instances) and tests that must not touch real files.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+use anyhow::{Context, Result};
+use tracing::warn;
+
+use super::{LayoutStore, LocalLayoutStore};
+use crate::models::Layout;
+
+/// Layouts held in memory for the lifetime of the server.
+#[derive(Debug, Default)]
+pub struct MemoryLayoutStore {
+    layouts: Mutex<BTreeMap<String, Layout>>,
+}
+
+impl MemoryLayoutStore {
+    /// Creates an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a store holding a copy of the layouts in `dir`.
+    ///
+    /// Layouts that fail to load are skipped with a warning; a missing
+    /// directory gives an empty store.
+    #[must_use]
+    pub fn seeded_from(dir: &Path) -> Self {
+        let source = LocalLayoutStore::new(dir);
+        let filenames = if dir.is_dir() {
+            source.list().unwrap_or_else(|e| {
+                warn!(error = %e, dir = %dir.display(), "failed to list seed layouts");
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+
+        let layouts = filenames
+            .into_iter()
+            .filter_map(|filename| match source.load(&filename) {
+                Ok(layout) => Some((filename, layout)),
+                Err(e) => {
+                    warn!(error = %e, filename, "skipping seed layout");
+                    None
+                }
+            })
+            .collect();
+        Self {
+            layouts: Mutex::new(layouts),
+        }
+    }
+
+    fn layouts(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Layout>> {
+        self.layouts.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl LayoutStore for MemoryLayoutStore {
+    fn describe(&self) -> String {
+        "memory (changes are discarded when the server stops)".to_string()
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        Ok(self.layouts().keys().cloned().collect())
+    }
+
+    fn exists(&self, filename: &str) -> Result<bool> {
+        Ok(self.layouts().contains_key(filename))
+    }
+
+    fn load(&self, filename: &str) -> Result<Layout> {
+        self.layouts()
+            .get(filename)
+            .cloned()
+            .with_context(|| format!("Layout not found: {filename}"))
+    }
+
+    fn save(&self, filename: &str, layout: &Layout) -> Result<()> {
+        self.layouts().insert(filename.to_string(), layout.clone());
+        Ok(())
+    }
+}
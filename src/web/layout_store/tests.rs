@@ -0,0 +1,297 @@
+//! Tests for the layout stores.
+
+use super::*;
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::Mutex;
+use std::thread;
+
+use tempfile::TempDir;
+
+use crate::models::{Layer, RgbColor};
+
+fn test_layout(name: &str) -> Layout {
+    let mut layout = Layout::new(name).unwrap();
+    layout
+        .add_layer(Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap())
+        .unwrap();
+    layout
+}
+
+/// Resources held by the test server, keyed by encoded filename.
+type ServerFiles = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+/// Minimal WebDAV server keeping resources in memory.
+///
+/// Answers PROPFIND with a chunked multistatus unless `webdav` is false, in
+/// which case it answers 405 and serves a JSON listing on `GET /layouts/`.
+fn spawn_server(webdav: bool) -> (String, ServerFiles) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://alice:secret@{}/layouts",
+        listener.local_addr().unwrap()
+    );
+    let files = ServerFiles::default();
+    let server_files = Arc::clone(&files);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+
+            let mut content_length = 0;
+            let mut authorized = false;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                let lower = line.to_ascii_lowercase();
+                if let Some(len) = lower.strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                // base64("alice:secret")
+                if lower.trim() == "authorization: basic ywxpy2u6c2vjcmv0" {
+                    authorized = true;
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let mut files = server_files.lock().unwrap();
+            let (status, response_body, chunked) = if !authorized {
+                ("401 Unauthorized", Vec::new(), false)
+            } else {
+                match (method.as_str(), path.as_str()) {
+                    ("PROPFIND", "/layouts/") if webdav => {
+                        let mut xml = String::from(
+                            "<?xml version=\"1.0\"?><D:multistatus xmlns:D=\"DAV:\">\
+                             <D:response><D:href>/layouts/</D:href></D:response>",
+                        );
+                        for name in files.keys() {
+                            let _ = write!(
+                                xml,
+                                "<D:response><D:href>/layouts/{name}</D:href></D:response>"
+                            );
+                        }
+                        xml.push_str("</D:multistatus>");
+                        ("207 Multi-Status", xml.into_bytes(), true)
+                    }
+                    ("PROPFIND", _) => ("405 Method Not Allowed", Vec::new(), false),
+                    ("GET", "/layouts/") => {
+                        let names: Vec<_> = files.keys().cloned().collect();
+                        ("200 OK", serde_json::to_vec(&names).unwrap(), false)
+                    }
+                    ("GET" | "HEAD", path) => match files.get(path.trim_start_matches("/layouts/"))
+                    {
+                        Some(content) => ("200 OK", content.clone(), false),
+                        None => ("404 Not Found", Vec::new(), false),
+                    },
+                    ("PUT", path) => {
+                        files.insert(path.trim_start_matches("/layouts/").to_string(), body);
+                        ("201 Created", Vec::new(), false)
+                    }
+                    _ => ("400 Bad Request", Vec::new(), false),
+                }
+            };
+            drop(files);
+
+            let mut response = format!("HTTP/1.1 {status}\r\nConnection: close\r\n").into_bytes();
+            if chunked {
+                response.extend_from_slice(b"Transfer-Encoding: chunked\r\n\r\n");
+                for chunk in response_body.chunks(40) {
+                    response.extend_from_slice(format!("{:x}\r\n", chunk.len()).as_bytes());
+                    response.extend_from_slice(chunk);
+                    response.extend_from_slice(b"\r\n");
+                }
+                response.extend_from_slice(b"0\r\n\r\n");
+            } else {
+                response.extend_from_slice(
+                    format!("Content-Length: {}\r\n\r\n", response_body.len()).as_bytes(),
+                );
+                if method != "HEAD" {
+                    response.extend_from_slice(&response_body);
+                }
+            }
+            stream.write_all(&response).unwrap();
+        }
+    });
+
+    (url, files)
+}
+
+#[test]
+fn test_open_defaults_to_local_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = open(None, temp_dir.path(), None).unwrap();
+
+    assert_eq!(store.describe(), temp_dir.path().display().to_string());
+    assert_eq!(
+        store.local_path("a.json"),
+        Some(temp_dir.path().join("a.json"))
+    );
+}
+
+#[test]
+fn test_open_rejects_unsupported_scheme() {
+    let temp_dir = TempDir::new().unwrap();
+    let err = open(Some("s3://bucket/layouts"), temp_dir.path(), None)
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("Unsupported storage URL"));
+}
+
+#[test]
+fn test_local_store_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = LocalLayoutStore::new(temp_dir.path());
+    let layout = test_layout("Local");
+
+    assert!(!store.exists("local.json").unwrap());
+    store.save("local.json", &layout).unwrap();
+    fs::write(temp_dir.path().join("notes.txt"), "ignored").unwrap();
+
+    assert!(store.exists("local.json").unwrap());
+    assert_eq!(store.list().unwrap(), vec!["local.json".to_string()]);
+    assert_eq!(store.load("local.json").unwrap().metadata.name, "Local");
+}
+
+#[test]
+fn test_open_memory_store_seeds_from_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    LayoutService::save(&test_layout("Seed"), &temp_dir.path().join("seed.json")).unwrap();
+    let store = open(Some(MEMORY_STORAGE_URL), temp_dir.path(), None).unwrap();
+
+    assert_eq!(store.list().unwrap(), vec!["seed.json".to_string()]);
+    assert_eq!(store.load("seed.json").unwrap().metadata.name, "Seed");
+    assert_eq!(store.local_path("seed.json"), None);
+}
+
+#[test]
+fn test_memory_store_never_writes() {
+    let temp_dir = TempDir::new().unwrap();
+    let seed_path = temp_dir.path().join("seed.json");
+    LayoutService::save(&test_layout("Seed"), &seed_path).unwrap();
+    fs::write(temp_dir.path().join("broken.json"), "not a layout").unwrap();
+    let before = fs::read_to_string(&seed_path).unwrap();
+
+    let store = MemoryLayoutStore::seeded_from(temp_dir.path());
+    // Unreadable layouts are skipped
+    assert_eq!(store.list().unwrap(), vec!["seed.json".to_string()]);
+
+    store.save("seed.json", &test_layout("Edited")).unwrap();
+    store.save("new.json", &test_layout("New")).unwrap();
+    assert_eq!(store.load("seed.json").unwrap().metadata.name, "Edited");
+    assert!(store.exists("new.json").unwrap());
+    assert!(store.load("missing.json").is_err());
+
+    assert_eq!(fs::read_to_string(&seed_path).unwrap(), before);
+    assert!(!temp_dir.path().join("new.json").exists());
+    assert!(store.backups("seed.json").is_err());
+}
+
+#[test]
+fn test_memory_store_without_seed_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = MemoryLayoutStore::seeded_from(&temp_dir.path().join("missing"));
+    assert!(store.list().unwrap().is_empty());
+    assert!(MemoryLayoutStore::new().list().unwrap().is_empty());
+}
+
+#[test]
+fn test_local_store_detects_external_writes() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = LocalLayoutStore::new(temp_dir.path());
+    assert!(!store.modified_externally("local.json"));
+
+    // Own saves are not external changes
+    store.save("local.json", &test_layout("Local")).unwrap();
+    assert!(!store.modified_externally("local.json"));
+
+    LayoutService::save(
+        &test_layout("Edited elsewhere"),
+        &temp_dir.path().join("local.json"),
+    )
+    .unwrap();
+    assert!(store.modified_externally("local.json"));
+    assert!(!store.modified_externally("local.json"));
+}
+
+#[test]
+fn test_local_store_backs_up_before_overwriting() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = LocalLayoutStore::new(temp_dir.path())
+        .with_backups(BackupStore::new(temp_dir.path().join("backups")));
+
+    store.save("local.json", &test_layout("First")).unwrap();
+    assert!(store.backups("local.json").unwrap().is_empty());
+
+    store.save("local.json", &test_layout("Second")).unwrap();
+    let backups = store.backups("local.json").unwrap();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(
+        store
+            .load_backup("local.json", &backups[0].id)
+            .unwrap()
+            .metadata
+            .name,
+        "First"
+    );
+
+    // Without a backup store, backups are unavailable
+    assert!(LocalLayoutStore::new(temp_dir.path())
+        .backups("local.json")
+        .is_err());
+}
+
+#[test]
+fn test_http_store_round_trip_over_webdav() {
+    let (url, files) = spawn_server(true);
+    let store = open(Some(&url), Path::new("."), None).unwrap();
+    let layout = test_layout("Remote");
+
+    assert!(!store.exists("remote layout.json").unwrap());
+    store.save("remote layout.json", &layout).unwrap();
+
+    assert!(files.lock().unwrap().contains_key("remote%20layout.json"));
+    assert!(store.exists("remote layout.json").unwrap());
+    assert_eq!(
+        store.list().unwrap(),
+        vec!["remote layout.json".to_string()]
+    );
+    assert_eq!(
+        store.load("remote layout.json").unwrap().metadata.name,
+        "Remote"
+    );
+    assert!(store.local_path("remote layout.json").is_none());
+    assert!(!store.describe().contains("secret"));
+}
+
+#[test]
+fn test_http_store_lists_plain_http_server() {
+    let (url, _files) = spawn_server(false);
+    let store = HttpLayoutStore::new(&url).unwrap();
+
+    store.save("plain.json", &test_layout("Plain")).unwrap();
+
+    assert_eq!(store.list().unwrap(), vec!["plain.json".to_string()]);
+}
+
+#[test]
+fn test_http_store_load_missing_layout_fails() {
+    let (url, _files) = spawn_server(true);
+    let store = HttpLayoutStore::new(&url).unwrap();
+
+    let err = store.load("missing.json").unwrap_err();
+    assert!(err.to_string().contains("Layout not found"));
+}
@@ -11,18 +11,36 @@
 //! - `GET /api/layouts/{filename}` - Load and parse a layout file
 //! - `PUT /api/layouts/{filename}` - Save a layout file
 //! - `POST /api/layouts/{filename}/swap-keys` - Swap two keys in a layout
-//! - `POST /api/layouts/{filename}/generate` - Generate firmware and start job
+//! - `POST /api/layouts/{filename}/layers/{layer}/keys/{row}/{col}` - Assign a single key
+//! - `GET /api/layouts/{filename}/search?q=` - Find keys on all layers by keycode, label or description
+//! - `GET /api/layouts/{filename}/stats` - Get per-layer statistics (optional `?corpus=` file for a typing heatmap)
+//! - `GET /api/layouts/{filename}/audit` - Get the layout's modification history (optional `?limit=`)
+//! - `GET /api/layouts/{filename}/events` - Stream live changes made by other sessions (SSE)
+//! - `GET /api/layouts/{filename}/backups` - List the layout's automatic backups, newest first
+//! - `POST /api/layouts/{filename}/backups/{id}/restore` - Replace the layout with one of its backups
+//! - `GET /api/layouts/{filename}/history` - List the git commits that touched the layout (optional `?limit=`)
+//! - `POST /api/layouts/{filename}/history/undo` - Undo the latest change made through the API
+//! - `POST /api/layouts/{filename}/history/redo` - Reapply the latest undone change
+//! - `POST /api/layouts/{filename}/generate` - Generate firmware and start job (optional `?qmk_lint=true`, `?target=NAME`)
 //! - `POST /api/layouts/{filename}/save-as-template` - Save layout as template
-//! - `GET /api/layouts/{filename}/render-metadata` - Get key display metadata for rendering
+//! - `GET /api/layouts/{filename}/render-metadata` - Get key, encoder and combo display metadata for rendering
 //! - `GET /api/templates` - List available templates
 //! - `GET /api/templates/{filename}` - Get a specific template
-//! - `POST /api/templates/{filename}/apply` - Apply template to create new layout
+//! - `POST /api/templates/{filename}/apply` - Apply template to create new layout (optionally fitted to another keyboard, with placeholder values)
+//! - `POST /api/templates/{filename}/fit` - Preview placing a template on another keyboard
 //! - `GET /api/keycodes` - Query keycode database (optional ?search=)
 //! - `GET /api/keycodes/categories` - List keycode categories
+//! - `GET /api/keycodes/dump` - Dump the merged keycode database with self-test results
+//! - `GET /api/keycodes/favorites` - List the keycode picker's favorite and recent keycodes
+//! - `POST /api/keycodes/favorites` - Add a favorite keycode
+//! - `PUT /api/keycodes/favorites` - Replace (reorder) the favorite keycodes
+//! - `DELETE /api/keycodes/favorites/{keycode}` - Remove a favorite keycode
 //! - `GET /api/config` - Get current configuration
 //! - `PUT /api/config` - Update configuration
-//! - `GET /api/preflight` - Check application state for onboarding flow
+//! - `GET /api/preflight` - Check application state and the QMK environment for onboarding flow
+//! - `POST /api/preflight/setup` - Run a QMK setup step (clone, submodules) and check again
 //! - `GET /api/keyboards/{keyboard}/geometry/{layout}` - Get keyboard geometry
+//! - `POST /api/keyboards/refresh` - Rescan the keyboard directories for `GET /api/keyboards`
 //! - `POST /api/build/start` - Start a firmware build job
 //! - `GET /api/build/jobs` - List all build jobs
 //! - `GET /api/build/jobs/{job_id}` - Get build job status
@@ -36,21 +54,36 @@
 //! - `POST /api/generate/jobs/{job_id}/cancel` - Cancel a generate job
 //! - `GET /api/generate/jobs/{job_id}/download` - Download generated zip file
 //! - `GET /api/generate/health` - Get generate job system health status
+//! - `GET /api/ws` - Stream build and generate job status changes and log lines (WebSocket)
+//! - `GET /api/live-preview/events` - Stream the keyboard's active layer and key presses (SSE)
+//! - `POST /api/shutdown` - Stop the server (requires `--allow-shutdown`; `?force=true` cancels running jobs)
 
 pub mod app_state;
 pub mod build_jobs;
+pub mod collab;
 pub mod dto;
 pub mod error;
 pub mod generate_jobs;
+pub mod geometry_cache;
+pub mod history;
+pub mod job_events;
+pub mod keyboard_cache;
+pub mod layout_store;
+pub mod read_only;
+mod render;
 pub mod routes;
+pub mod sandbox;
+pub mod shutdown;
 pub mod static_files;
 pub mod validation;
+pub mod watchdog;
+pub mod workspace_watch;
 
-use std::net::SocketAddr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::path::PathBuf;
 
 use axum::Router;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::config::Config;
 
@@ -71,7 +104,10 @@ pub fn create_router(state: AppState) -> Router {
     routes::router(state).fallback(static_handler)
 }
 
-/// Runs the web server.
+/// Runs the web server until Ctrl+C, SIGTERM, or an accepted shutdown request.
+///
+/// On shutdown, in-flight requests complete, then active build and generate
+/// jobs are cancelled and given [`shutdown::JOB_DRAIN_TIMEOUT`] to clean up.
 ///
 /// # Arguments
 ///
@@ -88,16 +124,49 @@ pub async fn run_server(
     addr: SocketAddr,
 ) -> anyhow::Result<()> {
     let state = AppState::new(config, workspace_root)?;
-    let app = create_router(state);
+    let app = create_router(state.clone());
 
     info!("Starting LazyQMK web server on {}", addr);
+    info!("Layouts stored in {}", state.layouts.describe());
+    if let Some(lock) = &state.workspace_lock {
+        for peer in lock.peers() {
+            info!("{peer} is also using this workspace; its saves are announced to web sessions");
+        }
+    }
+    tokio::spawn(workspace_watch::watch(state.clone()));
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    let local_addr = listener.local_addr()?;
+    info!("Listening on http://{local_addr}");
+    if let Some(lock) = &state.workspace_lock {
+        if let Err(e) = lock.advertise(&local_url(local_addr)) {
+            warn!(error = %e, "Failed to advertise the server address to the TUI");
+        }
+    }
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown::wait_for_shutdown(state.clone()))
+        .await?;
+
+    shutdown::drain_jobs(&state, shutdown::JOB_DRAIN_TIMEOUT).await;
+    let killed = crate::services::process_tree::kill_all();
+    if killed > 0 {
+        warn!(killed, "Killed child processes left running after shutdown");
+    }
+    info!("LazyQMK web server stopped");
 
     Ok(())
 }
 
+/// URL for reaching a server bound to `addr` from this machine.
+fn local_url(addr: SocketAddr) -> String {
+    let ip = match addr.ip() {
+        IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        ip => ip,
+    };
+    format!("http://{}", SocketAddr::new(ip, addr.port()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,6 +220,19 @@ mod tests {
         assert!(validate_keyboard_path("/etc/keyboard").is_err());
     }
 
+    #[test]
+    fn test_local_url_replaces_unspecified_address() {
+        assert_eq!(
+            local_url("0.0.0.0:3001".parse().unwrap()),
+            "http://127.0.0.1:3001"
+        );
+        assert_eq!(local_url("[::]:3001".parse().unwrap()), "http://[::1]:3001");
+        assert_eq!(
+            local_url("192.168.1.2:80".parse().unwrap()),
+            "http://192.168.1.2:80"
+        );
+    }
+
     #[test]
     fn test_validate_keyboard_path_empty() {
         assert!(validate_keyboard_path("").is_err());
@@ -0,0 +1,44 @@
+//! Read-only server mode.
+//!
+//! When enabled (`lazyqmk web --read-only` or `[web] read_only = true` in
+//! config.toml), every request that could modify state is rejected with
+//! 403 Forbidden. Layouts, keycodes, geometry, and existing job results stay
+//! browsable, which makes it safe to share a layout browser publicly.
+
+use axum::{
+    extract::{Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use super::error::AppError;
+use super::AppState;
+
+/// Rejects mutating requests when the server runs in read-only mode.
+///
+/// Only `GET`, `HEAD`, and `OPTIONS` (CORS preflight) pass through.
+pub async fn reject_mutations(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if state.read_only && !is_safe_method(request.method()) {
+        return AppError::with_details(
+            StatusCode::FORBIDDEN,
+            "Server is running in read-only mode",
+            Some(format!(
+                "{} {} is not allowed",
+                request.method(),
+                request.uri().path()
+            )),
+        )
+        .into_response();
+    }
+
+    next.run(request).await
+}
+
+fn is_safe_method(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
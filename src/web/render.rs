@@ -0,0 +1,205 @@
+//! Shared helpers for building per-key render metadata.
+//!
+//! Used by `GET /api/layouts/{filename}/render-metadata` and the single-key
+//! assign endpoint so both produce identical `KeyRenderMetadata`.
+//!
+//! Layers also carry their encoder actions and the combos that fire on them.
+//! Hold combos only fire on the base layer. Keycode combos trigger on the
+//! base-layer keycodes at their positions, so they fire on any layer whose
+//! keys there are transparent or send the same keycodes.
+
+use std::collections::HashMap;
+
+use crate::firmware::generator::default_encoder_binding;
+use crate::keycode_db::{KeyDisplayMetadata, KeycodeDb, TapDanceDisplayInfo};
+use crate::models::{KeyDefinition, Layer, Layout, Position, TapDanceAction};
+
+use super::dto::{
+    ComboRenderKind, ComboRenderMetadata, EncoderActionRenderMetadata, EncoderRenderMetadata,
+    KeyDisplayDto, KeyRenderMetadata,
+};
+use super::geometry_cache::CachedGeometry;
+
+/// Per-layout lookup tables needed to render key display metadata.
+pub struct KeyRenderContext<'a> {
+    keycode_db: &'a KeycodeDb,
+    layout: &'a Layout,
+    tap_dance_map: HashMap<&'a str, &'a TapDanceAction>,
+    layer_id_to_number: HashMap<String, u8>,
+}
+
+impl<'a> KeyRenderContext<'a> {
+    /// Builds the tap dance and layer-id lookup tables for `layout`.
+    pub fn new(keycode_db: &'a KeycodeDb, layout: &'a Layout) -> Self {
+        let tap_dance_map = layout
+            .tap_dances
+            .iter()
+            .map(|td| (td.name.as_str(), td))
+            .collect();
+        let layer_id_to_number = layout
+            .layers
+            .iter()
+            .map(|layer| (layer.id.clone(), layer.number))
+            .collect();
+
+        Self {
+            keycode_db,
+            layout,
+            tap_dance_map,
+            layer_id_to_number,
+        }
+    }
+
+    /// Builds render metadata for a single key.
+    pub fn key_metadata(&self, key: &KeyDefinition, visual_index: u8) -> KeyRenderMetadata {
+        let meta = self.display_metadata(&key.keycode);
+
+        KeyRenderMetadata {
+            visual_index,
+            display: meta.display.into(),
+            details: meta.details.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Builds the encoder actions of `layer` for `encoder_count` encoders.
+    ///
+    /// Encoders the layer leaves unset show the firmware defaults.
+    pub fn layer_encoders(&self, layer: &Layer, encoder_count: u8) -> Vec<EncoderRenderMetadata> {
+        (0..encoder_count)
+            .map(|encoder| {
+                let (ccw, cw, default) = layer.encoder_map.get(encoder).map_or_else(
+                    || {
+                        let (ccw, cw) = default_encoder_binding(usize::from(encoder));
+                        (ccw, cw, true)
+                    },
+                    |binding| (binding.ccw.as_str(), binding.cw.as_str(), false),
+                );
+                EncoderRenderMetadata {
+                    encoder,
+                    ccw: self.encoder_action(ccw),
+                    cw: self.encoder_action(cw),
+                    default,
+                }
+            })
+            .collect()
+    }
+
+    /// Builds the combos that fire while layer `layer_index` is active.
+    ///
+    /// Combos whose keys can't be placed (no geometry entry and no key on
+    /// the layer) are left out.
+    pub fn layer_combos(
+        &self,
+        layer_index: usize,
+        geometry: Option<&CachedGeometry>,
+    ) -> Vec<ComboRenderMetadata> {
+        let settings = &self.layout.combo_settings;
+        let (Some(base), Some(layer)) =
+            (self.layout.get_layer(0), self.layout.get_layer(layer_index))
+        else {
+            return Vec::new();
+        };
+        if !settings.enabled {
+            return Vec::new();
+        }
+
+        let visual_indices = |positions: &[Position]| -> Option<Vec<u8>> {
+            positions
+                .iter()
+                .map(|&position| {
+                    geometry
+                        .and_then(|geometry| geometry.key(position))
+                        .map(|info| info.visual_index)
+                        .or_else(|| {
+                            let idx = layer.keys.iter().position(|k| k.position == position)?;
+                            u8::try_from(idx).ok()
+                        })
+                })
+                .collect()
+        };
+
+        let mut combos = Vec::new();
+        if layer_index == 0 {
+            for (index, combo) in settings.combos.iter().enumerate() {
+                if combo.placeholder {
+                    continue;
+                }
+                let positions = vec![combo.key1, combo.key2];
+                let Some(indices) = visual_indices(&positions) else {
+                    continue;
+                };
+                combos.push(ComboRenderMetadata {
+                    kind: ComboRenderKind::Hold,
+                    index,
+                    positions,
+                    visual_indices: indices,
+                    keycode: None,
+                    display: KeyDisplayDto {
+                        primary: combo.action.display_name().to_string(),
+                        secondary: None,
+                        tertiary: None,
+                    },
+                    hold_duration_ms: Some(combo.hold_duration_ms),
+                });
+            }
+        }
+
+        for (index, combo) in settings.keycode_combos.iter().enumerate() {
+            // Triggers are the base-layer keycodes; other layers must send
+            // the same keycodes (or fall through) at every trigger position
+            let fires = combo.keys.iter().all(|&position| {
+                let Some(key) = layer.get_key(position) else {
+                    return layer_index == 0;
+                };
+                key.is_transparent()
+                    || base
+                        .get_key(position)
+                        .is_some_and(|base_key| base_key.keycode == key.keycode)
+            });
+            if !fires {
+                continue;
+            }
+            let Some(indices) = visual_indices(&combo.keys) else {
+                continue;
+            };
+            combos.push(ComboRenderMetadata {
+                kind: ComboRenderKind::Keycode,
+                index,
+                positions: combo.keys.clone(),
+                visual_indices: indices,
+                keycode: Some(combo.keycode.clone()),
+                display: self.display_metadata(&combo.keycode).display.into(),
+                hold_duration_ms: None,
+            });
+        }
+
+        combos
+    }
+
+    fn encoder_action(&self, keycode: &str) -> EncoderActionRenderMetadata {
+        EncoderActionRenderMetadata {
+            keycode: keycode.to_string(),
+            display: self.display_metadata(keycode).display.into(),
+        }
+    }
+
+    /// Looks up display metadata for `keycode`, resolving tap dances and
+    /// layer references.
+    fn display_metadata(&self, keycode: &str) -> KeyDisplayMetadata {
+        let td_info = self
+            .keycode_db
+            .parse_tap_dance_keycode(keycode)
+            .and_then(|td_name| self.tap_dance_map.get(td_name.as_str()))
+            .map(|td| TapDanceDisplayInfo {
+                single_tap: td.single_tap.clone(),
+                double_tap: td.double_tap.clone(),
+                hold: td.hold.clone(),
+            });
+
+        self.keycode_db.get_display_metadata(
+            keycode,
+            td_info.as_ref(),
+            Some(&self.layer_id_to_number),
+        )
+    }
+}
@@ -0,0 +1,82 @@
+//! Layout audit log endpoint and recording helper for mutating handlers.
+
+use std::path::Path as FsPath;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+
+use crate::models::Layout;
+use crate::services::audit::{self, AuditEntry, AuditSource};
+
+use super::super::dto::{AuditLogQuery, AuditLogResponse};
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+
+/// Request header identifying the user making a change.
+pub const ACTOR_HEADER: &str = "x-lazyqmk-user";
+
+/// Actor recorded when a request carries no user header.
+const DEFAULT_ACTOR: &str = "web";
+
+/// GET /api/layouts/{filename}/audit - Get the modification history of a layout.
+pub(super) async fn get_audit_log(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Json<AuditLogResponse>, AppError> {
+    let filename = validate_filename(&filename)?;
+    let filename = with_json_ext(filename);
+    let path = state.workspace_root.join(&filename);
+
+    if !state.layout_exists(&filename).await? {
+        return Err(AppError::not_found(format!(
+            "Layout file not found: {filename}"
+        )));
+    }
+
+    let mut entries = audit::read(&path).map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to read audit log",
+            Some(e.to_string()),
+        )
+    })?;
+
+    if let Some(limit) = query.limit {
+        let skip = entries.len().saturating_sub(limit);
+        entries.drain(..skip);
+    }
+
+    Ok(Json(AuditLogResponse { filename, entries }))
+}
+
+/// Records a web API change to the layout at `path`.
+///
+/// The actor is taken from the `X-LazyQMK-User` header. Failures are logged
+/// and never fail the request that made the change.
+pub(super) fn record_change(
+    headers: &HeaderMap,
+    path: &FsPath,
+    action: &str,
+    before: Option<&Layout>,
+    after: &Layout,
+) {
+    let entry = AuditEntry::new(actor(headers), AuditSource::Web, action, before, after);
+    if let Err(e) = audit::record(path, &entry) {
+        tracing::warn!(error = %e, path = %path.display(), "failed to record audit entry");
+    }
+}
+
+/// Returns the user named by the `X-LazyQMK-User` header, or `"web"`.
+pub(super) fn actor(headers: &HeaderMap) -> &str {
+    headers
+        .get(ACTOR_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(DEFAULT_ACTOR)
+}
@@ -0,0 +1,90 @@
+//! Layout backup endpoints: list the automatic backups and restore one.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+
+use crate::services::backup::BackupInfo;
+
+use super::super::collab::LayoutChange;
+use super::super::dto::{BackupListResponse, BackupRestoreResponse};
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+use super::fragments::load_workspace_layout;
+use super::{audit, events};
+
+/// GET /api/layouts/{filename}/backups - List a layout's backups, newest first.
+pub(super) async fn list_backups(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Json<BackupListResponse>, AppError> {
+    let filename = with_json_ext(validate_filename(&filename)?);
+    if !state.layout_exists(&filename).await? {
+        return Err(AppError::not_found(format!(
+            "Layout file not found: {filename}"
+        )));
+    }
+
+    let backups = stored_backups(&state, &filename).await?;
+    Ok(Json(BackupListResponse { filename, backups }))
+}
+
+/// POST /api/layouts/{filename}/backups/{id}/restore - Replace a layout with one of its backups.
+///
+/// The layout being replaced is backed up first, so a restore can itself be
+/// undone by restoring that backup (or through the edit history).
+pub(super) async fn restore_backup(
+    State(state): State<AppState>,
+    Path((filename, id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Json<BackupRestoreResponse>, AppError> {
+    let filename = with_json_ext(validate_filename(&filename)?);
+    let _edit = state.collab.lock_layout(&filename).await;
+    let (path, filename, current) = load_workspace_layout(&state, &filename).await?;
+
+    let restored = stored_backups(&state, &filename)
+        .await?
+        .into_iter()
+        .find(|backup| backup.id == id)
+        .ok_or_else(|| AppError::not_found(format!("Backup not found: {id}")))?;
+    let layout = state
+        .layouts
+        .load_backup(&filename, &id)
+        .await
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Backup is not a readable layout",
+                Some(format!("{e:#}")),
+            )
+        })?;
+
+    state.layouts.save(&filename, &layout).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to save layout",
+            Some(e.to_string()),
+        )
+    })?;
+    audit::record_change(&headers, &path, "restore_backup", Some(&current), &layout);
+    state
+        .history
+        .record(&filename, "restore_backup", &current, &layout);
+    events::broadcast_changes(&state, &headers, &filename, [LayoutChange::LayoutReplaced]);
+
+    Ok(Json(BackupRestoreResponse { filename, restored }))
+}
+
+/// Lists the backups of `filename`, mapping a store without backups to 501.
+async fn stored_backups(state: &AppState, filename: &str) -> Result<Vec<BackupInfo>, AppError> {
+    state.layouts.backups(filename).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::NOT_IMPLEMENTED,
+            "Backups are not available for this layout",
+            Some(e.to_string()),
+        )
+    })
+}
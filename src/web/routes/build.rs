@@ -9,11 +9,13 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::services::LayoutService;
+use crate::firmware::builder::KeymapCheck;
+#[cfg(feature = "usb")]
+use crate::services::flasher::Flasher;
 
 use super::super::build_jobs;
 use super::super::error::AppError;
-use super::super::validation::{validate_filename, with_json_ext};
+use super::super::validation::{validate_filename, validate_layout_for_job, with_json_ext};
 use super::super::AppState;
 
 /// Query parameters for fetching build logs.
@@ -40,22 +42,58 @@ pub(super) struct BuildArtifactsResponse {
     pub artifacts: Vec<build_jobs::BuildArtifact>,
 }
 
+/// Query parameters for flashing a build.
+#[cfg(feature = "usb")]
+#[derive(Debug, Deserialize)]
+pub(super) struct FlashQuery {
+    /// Artifact to flash (e.g. "uf2"); defaults to whichever format the
+    /// detected bootloader accepts.
+    pub artifact: Option<String>,
+}
+
+/// Response for flashing a build.
+#[cfg(feature = "usb")]
+#[derive(Debug, Serialize)]
+pub(super) struct FlashResponse {
+    /// Job ID.
+    pub job_id: String,
+    /// ID of the flashed artifact.
+    pub artifact_id: String,
+    /// Description of the bootloader that was flashed.
+    pub target: String,
+    /// Output of the flashing step.
+    pub log: Vec<String>,
+}
+
 /// POST /api/build/start - Start a firmware build job.
+///
+/// The job waits in the build queue until a worker is free. Set `target`
+/// to build one of the layout's keyboard targets instead of its primary
+/// keyboard.
 pub(super) async fn start_build(
     State(state): State<AppState>,
     Json(request): Json<build_jobs::StartBuildRequest>,
 ) -> Result<Json<build_jobs::StartBuildResponse>, AppError> {
+    // Builds write the keymap into the QMK tree
+    if state.is_sandboxed() {
+        return Err(AppError::with_details(
+            StatusCode::FORBIDDEN,
+            "Firmware builds are disabled in sandbox mode",
+            Some("Use generate to download the firmware source instead".to_string()),
+        ));
+    }
+
     let filename = validate_filename(&request.layout_filename)?;
     let filename = with_json_ext(filename);
     let path = state.workspace_root.join(&filename);
 
-    if !path.exists() {
+    if !state.layout_exists(&filename).await? {
         return Err(AppError::not_found(format!(
             "Layout file not found: {filename}"
         )));
     }
 
-    let layout = LayoutService::load(&path).map_err(|e| {
+    let layout = state.layouts.load(&filename).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to load layout",
@@ -63,18 +101,43 @@ pub(super) async fn start_build(
         )
     })?;
 
-    let keyboard = layout.metadata.keyboard.clone().ok_or_else(|| {
-        AppError::bad_request("Layout has no keyboard defined - cannot build firmware")
+    let geometry = state.layout_geometry(&layout);
+    validate_layout_for_job(
+        &layout,
+        &state.keycode_db,
+        geometry.as_ref().map(|g| &g.geometry),
+    )?;
+
+    state.stage_layout(&filename, &layout).map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to stage layout for the job",
+            Some(e.to_string()),
+        )
     })?;
 
-    let keymap = layout
-        .metadata
+    // The job reports the keyboard and keymap actually built
+    let metadata = match &request.target {
+        Some(target) => {
+            layout
+                .for_target(target)
+                .map_err(|e| AppError::bad_request(e.to_string()))?
+                .metadata
+        }
+        None => layout.metadata,
+    };
+
+    let keyboard = metadata.keyboard.ok_or_else(|| {
+        AppError::bad_request("Layout has no keyboard defined - cannot build firmware")
+    })?;
+    let keymap = metadata
         .keymap_name
         .unwrap_or_else(|| "default".to_string());
+    let converter = metadata.converter;
 
     let job = state
         .build_manager
-        .start_build(filename, keyboard, keymap, path)
+        .start_build_for_target(filename, keyboard, keymap, converter, request.target, path)
         .map_err(|e| {
             AppError::with_details(StatusCode::SERVICE_UNAVAILABLE, e, Option::<String>::None)
         })?;
@@ -82,7 +145,88 @@ pub(super) async fn start_build(
     Ok(Json(build_jobs::StartBuildResponse { job }))
 }
 
-/// GET /api/build/jobs - List all build jobs.
+/// POST /api/layouts/{filename}/check - Check the generated keymap for C
+/// errors without building the firmware.
+///
+/// Deploys the keymap like a build, then compiles only `keymap.c` with
+/// `-fsyntax-only`. A failing check is a 200 with `passed: false`.
+pub(super) async fn check_layout(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Json<KeymapCheck>, AppError> {
+    // Checks write the keymap into the QMK tree, like builds
+    if state.is_sandboxed() {
+        return Err(AppError::with_details(
+            StatusCode::FORBIDDEN,
+            "Firmware checks are disabled in sandbox mode",
+            Some("Use generate to download the firmware source instead".to_string()),
+        ));
+    }
+
+    let filename = validate_filename(&filename)?;
+    let filename = with_json_ext(filename);
+    let path = state.workspace_root.join(&filename);
+
+    if !state.layout_exists(&filename).await? {
+        return Err(AppError::not_found(format!(
+            "Layout file not found: {filename}"
+        )));
+    }
+
+    let layout = state.layouts.load(&filename).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to load layout",
+            Some(e.to_string()),
+        )
+    })?;
+
+    let keyboard = layout.metadata.keyboard.clone().ok_or_else(|| {
+        AppError::bad_request("Layout has no keyboard defined - cannot check firmware")
+    })?;
+
+    let geometry = state.layout_geometry(&layout);
+    validate_layout_for_job(
+        &layout,
+        &state.keycode_db,
+        geometry.as_ref().map(|g| &g.geometry),
+    )?;
+
+    state.stage_layout(&filename, &layout).map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to stage layout for the check",
+            Some(e.to_string()),
+        )
+    })?;
+
+    let keymap = layout
+        .metadata
+        .keymap_name
+        .unwrap_or_else(|| "default".to_string());
+    let converter = layout.metadata.converter;
+
+    let manager = state.build_manager.clone();
+    let check = tokio::task::spawn_blocking(move || {
+        manager.check_keymap(&filename, &keyboard, &keymap, converter.as_deref(), &path)
+    })
+    .await
+    .map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Check task failed",
+            Some(e.to_string()),
+        )
+    })?
+    .map_err(|e| {
+        AppError::with_details(StatusCode::SERVICE_UNAVAILABLE, e, Option::<String>::None)
+    })?;
+
+    Ok(Json(check))
+}
+
+/// GET /api/build/jobs - List all build jobs, with the queue position of
+/// pending ones.
 pub(super) async fn list_build_jobs(
     State(state): State<AppState>,
 ) -> Json<Vec<build_jobs::BuildJob>> {
@@ -188,3 +332,87 @@ pub(super) async fn download_build_artifact(
 
     Ok(response)
 }
+
+/// POST /api/build/jobs/{job_id}/flash - Flash a build onto a keyboard in bootloader mode.
+///
+/// The keyboard must already be in bootloader mode; it is detected once and
+/// not waited for.
+#[cfg(feature = "usb")]
+pub(super) async fn flash_build_job(
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+    Query(query): Query<FlashQuery>,
+) -> Result<Json<FlashResponse>, AppError> {
+    let job = state
+        .build_manager
+        .get_job(&job_id)
+        .ok_or_else(|| AppError::not_found(format!("Build job not found: {job_id}")))?;
+    if job.status != build_jobs::JobStatus::Completed {
+        return Err(AppError::with_details(
+            StatusCode::CONFLICT,
+            "Build job has not completed",
+            Some(format!("Job {job_id} is {}", job.status)),
+        ));
+    }
+
+    let artifact_ids: Vec<String> = match query.artifact {
+        Some(id) => vec![id],
+        None => job.artifacts.iter().map(|a| a.id.clone()).collect(),
+    };
+    let artifacts = artifact_ids
+        .iter()
+        .map(|id| {
+            state
+                .build_manager
+                .get_artifact_path(&job_id, id)
+                .filter(|path| path.exists())
+                .ok_or_else(|| {
+                    AppError::not_found(format!("Artifact '{id}' not found for job '{job_id}'"))
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let flasher = Flasher::system();
+    let (artifact, target) = flasher.find_target(&artifacts).map_err(|e| {
+        AppError::with_details(
+            StatusCode::CONFLICT,
+            "No keyboard ready to flash",
+            Some(e.to_string()),
+        )
+    })?;
+    // Artifact IDs are the file extension, which is what the bootloader accepts
+    let artifact_id = target.artifact_extension().to_string();
+    let target_name = target.to_string();
+    let log = tokio::task::spawn_blocking(move || {
+        let mut log = Vec::new();
+        flasher
+            .flash(&artifact, &target, &mut log)
+            .map(|()| log)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Flash task failed",
+            Some(e.to_string()),
+        )
+    })?
+    .map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to flash firmware",
+            Some(e),
+        )
+    })?;
+
+    Ok(Json(FlashResponse {
+        job_id,
+        artifact_id,
+        target: target_name,
+        log: String::from_utf8_lossy(&log)
+            .lines()
+            .map(String::from)
+            .collect(),
+    }))
+}
@@ -0,0 +1,219 @@
+//! Category CRUD endpoints and the color palette.
+//!
+//! Mirrors the TUI category manager: categories can be created, renamed,
+//! recolored, and deleted without sending the whole layout back. Deleting a
+//! category clears it from every layer and key that referenced it.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+
+use crate::models::{Category, ColorPalette, Layout};
+
+use super::super::dto::{
+    CategoryMutationResponse, CategoryUpdateRequest, CreateCategoryRequest,
+    LayoutCategoryListResponse, LayoutCategoryUsage,
+};
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+use super::fragments::load_workspace_layout;
+use super::tap_dances::save_layout;
+
+/// Derives a kebab-case category ID from a display name, as the TUI does.
+fn id_from_name(name: &str) -> String {
+    name.trim().to_lowercase().replace(' ', "-")
+}
+
+/// Clears `id` from every layer and key, returning how many were cleared.
+fn clear_category_references(layout: &mut Layout, id: &str) -> usize {
+    let mut cleared = 0;
+    for layer in &mut layout.layers {
+        if layer.category_id.as_deref() == Some(id) {
+            layer.category_id = None;
+            cleared += 1;
+        }
+        for key in &mut layer.keys {
+            if key.category_id.as_deref() == Some(id) {
+                key.category_id = None;
+                cleared += 1;
+            }
+        }
+    }
+    cleared
+}
+
+/// GET /api/layouts/{filename}/categories - List categories with their usage.
+pub(super) async fn list_categories(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Json<LayoutCategoryListResponse>, AppError> {
+    let (_, _, layout) = load_workspace_layout(&state, &filename).await?;
+
+    let categories = layout
+        .categories
+        .iter()
+        .map(|category| {
+            let id = Some(category.id.as_str());
+            LayoutCategoryUsage {
+                category: category.clone(),
+                layer_count: layout
+                    .layers
+                    .iter()
+                    .filter(|layer| layer.category_id.as_deref() == id)
+                    .count(),
+                key_count: layout
+                    .layers
+                    .iter()
+                    .flat_map(|layer| &layer.keys)
+                    .filter(|key| key.category_id.as_deref() == id)
+                    .count(),
+            }
+        })
+        .collect();
+
+    Ok(Json(LayoutCategoryListResponse { categories }))
+}
+
+/// POST /api/layouts/{filename}/categories - Create a category.
+///
+/// The ID defaults to the name in kebab-case when omitted.
+pub(super) async fn create_category(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+    headers: HeaderMap,
+    Json(request): Json<CreateCategoryRequest>,
+) -> Result<Json<CategoryMutationResponse>, AppError> {
+    let filename = with_json_ext(validate_filename(&filename)?);
+    let _edit = state.collab.lock_layout(&filename).await;
+    let (path, filename, mut layout) = load_workspace_layout(&state, &filename).await?;
+    let before = layout.clone();
+
+    let id = request
+        .id
+        .map_or_else(|| id_from_name(&request.name), |id| id.trim().to_string());
+    let category = Category::new(id, request.name.trim(), request.color)
+        .map_err(|e| AppError::bad_request(e.to_string()))?;
+    if layout.get_category(&category.id).is_some() {
+        return Err(AppError::with_details(
+            StatusCode::CONFLICT,
+            "Category already exists",
+            Some(format!("Category already exists: {}", category.id)),
+        ));
+    }
+    layout
+        .add_category(category.clone())
+        .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    save_layout(
+        &state,
+        &headers,
+        &path,
+        &filename,
+        "create_category",
+        &before,
+        &layout,
+    )
+    .await?;
+
+    Ok(Json(CategoryMutationResponse {
+        category: Some(category),
+        cleared_references: 0,
+    }))
+}
+
+/// PUT /api/layouts/{filename}/categories/{id} - Rename or recolor a category.
+///
+/// Fields left out of the body keep their current value. The ID is fixed so
+/// key and layer assignments stay valid.
+pub(super) async fn update_category(
+    State(state): State<AppState>,
+    Path((filename, id)): Path<(String, String)>,
+    headers: HeaderMap,
+    Json(request): Json<CategoryUpdateRequest>,
+) -> Result<Json<CategoryMutationResponse>, AppError> {
+    let filename = with_json_ext(validate_filename(&filename)?);
+    let _edit = state.collab.lock_layout(&filename).await;
+    let (path, filename, mut layout) = load_workspace_layout(&state, &filename).await?;
+    let before = layout.clone();
+
+    let category = layout
+        .categories
+        .iter_mut()
+        .find(|c| c.id == id)
+        .ok_or_else(|| AppError::not_found(format!("Category not found: {id}")))?;
+    if let Some(name) = request.name {
+        category
+            .set_name(name.trim())
+            .map_err(|e| AppError::bad_request(e.to_string()))?;
+    }
+    if let Some(color) = request.color {
+        category.set_color(color);
+    }
+    let category = category.clone();
+    layout.metadata.touch();
+
+    save_layout(
+        &state,
+        &headers,
+        &path,
+        &filename,
+        "update_category",
+        &before,
+        &layout,
+    )
+    .await?;
+
+    Ok(Json(CategoryMutationResponse {
+        category: Some(category),
+        cleared_references: 0,
+    }))
+}
+
+/// DELETE /api/layouts/{filename}/categories/{id} - Delete a category.
+///
+/// Layers and keys assigned the category fall back to having none.
+pub(super) async fn delete_category(
+    State(state): State<AppState>,
+    Path((filename, id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Json<CategoryMutationResponse>, AppError> {
+    let filename = with_json_ext(validate_filename(&filename)?);
+    let _edit = state.collab.lock_layout(&filename).await;
+    let (path, filename, mut layout) = load_workspace_layout(&state, &filename).await?;
+    let before = layout.clone();
+
+    if layout.remove_category(&id).is_none() {
+        return Err(AppError::not_found(format!("Category not found: {id}")));
+    }
+    let cleared_references = clear_category_references(&mut layout, &id);
+
+    save_layout(
+        &state,
+        &headers,
+        &path,
+        &filename,
+        "delete_category",
+        &before,
+        &layout,
+    )
+    .await?;
+
+    Ok(Json(CategoryMutationResponse {
+        category: None,
+        cleared_references,
+    }))
+}
+
+/// GET /api/palette - The curated color palette offered by the color picker.
+pub(super) async fn get_palette() -> Result<Json<ColorPalette>, AppError> {
+    ColorPalette::load().map(Json).map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to load color palette",
+            Some(e.to_string()),
+        )
+    })
+}
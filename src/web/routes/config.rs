@@ -4,9 +4,13 @@ use std::path::PathBuf;
 
 use axum::{extract::State, http::StatusCode, Json};
 
-use super::super::dto::{ConfigResponse, ConfigUpdateRequest, PreflightResponse};
+use super::super::dto::{
+    ConfigResponse, ConfigUpdateRequest, PreflightResponse, PreflightSetupRequest,
+};
 use super::super::error::AppError;
+use super::super::watchdog::timeout_from_secs;
 use super::super::AppState;
+use crate::services::qmk_env::{self, QmkEnvReport};
 
 /// GET /api/config - Get current configuration.
 pub(super) async fn get_config(State(state): State<AppState>) -> Json<ConfigResponse> {
@@ -28,6 +32,20 @@ pub(super) async fn get_config(State(state): State<AppState>) -> Json<ConfigResp
             .display()
             .to_string(),
         workspace_root: state.workspace_root.display().to_string(),
+        read_only: state.read_only,
+        shutdown_enabled: state.allow_shutdown,
+        sandbox: state.is_sandboxed(),
+        job_timeout_secs: state.config.read().unwrap().web.job_timeout_secs,
+        build_workers: state.config.read().unwrap().web.build_workers,
+        build_cpus_per_job: state.config.read().unwrap().web.build_cpus_per_job,
+        qmk_trees: state
+            .config
+            .read()
+            .unwrap()
+            .qmk_tree_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
     })
 }
 
@@ -48,6 +66,18 @@ pub(super) async fn update_config(
         config.paths.qmk_firmware = Some(path);
     }
 
+    if let Some(secs) = request.job_timeout_secs {
+        config.web.job_timeout_secs = secs;
+    }
+
+    if let Some(workers) = request.build_workers {
+        config.web.build_workers = workers;
+    }
+
+    if let Some(cpus) = request.build_cpus_per_job {
+        config.web.build_cpus_per_job = cpus;
+    }
+
     config.validate().map_err(|e| {
         AppError::with_details(
             StatusCode::BAD_REQUEST,
@@ -56,15 +86,27 @@ pub(super) async fn update_config(
         )
     })?;
 
-    config.save().map_err(|e| {
-        AppError::with_details(
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Failed to save configuration",
-            Some(e.to_string()),
-        )
-    })?;
+    // A sandbox server only applies the change to itself
+    if !state.is_sandboxed() {
+        config.save().map_err(|e| {
+            AppError::with_details(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to save configuration",
+                Some(e.to_string()),
+            )
+        })?;
+    }
 
+    let job_timeout = timeout_from_secs(config.web.job_timeout_secs);
+    state
+        .build_manager
+        .set_worker_count(config.web.build_workers);
+    state
+        .build_manager
+        .set_cpus_per_job(config.web.build_cpus_per_job);
     *state.config.write().expect("config lock poisoned") = config;
+    state.build_manager.set_job_timeout(job_timeout);
+    state.generate_manager.set_job_timeout(job_timeout);
 
     let new_qmk_path = state
         .config
@@ -75,12 +117,18 @@ pub(super) async fn update_config(
         .clone();
     state.build_manager.set_qmk_path(new_qmk_path.clone());
     state.generate_manager.set_qmk_path(new_qmk_path);
+    state.geometry_cache.clear();
+    state.keyboard_cache.clear();
 
     Ok(StatusCode::NO_CONTENT)
 }
 
 /// GET /api/preflight - Check application state for onboarding flow.
-pub(super) async fn get_preflight(State(state): State<AppState>) -> Json<PreflightResponse> {
+///
+/// Includes a health check of the QMK checkout and build tools.
+pub(super) async fn get_preflight(
+    State(state): State<AppState>,
+) -> Result<Json<PreflightResponse>, AppError> {
     let qmk_configured = state
         .config
         .read()
@@ -99,23 +147,88 @@ pub(super) async fn get_preflight(State(state): State<AppState>) -> Json<Preflig
         .as_ref()
         .map(|p| p.display().to_string());
 
-    let has_layouts = std::fs::read_dir(&state.workspace_root)
-        .map(|entries| {
-            entries.filter_map(Result::ok).any(|entry| {
-                entry
-                    .path()
-                    .extension()
-                    .is_some_and(|ext| ext == "json" || ext == "md")
-            })
-        })
-        .unwrap_or(false);
+    let has_layouts = state
+        .layouts
+        .list()
+        .await
+        .is_ok_and(|filenames| !filenames.is_empty());
 
     let first_run = !has_layouts && !qmk_configured;
 
-    Json(PreflightResponse {
+    let qmk_path = qmk_firmware_path.as_ref().map(PathBuf::from);
+    let environment = check_environment(qmk_path).await?;
+
+    Ok(Json(PreflightResponse {
         qmk_configured,
         has_layouts,
         first_run,
         qmk_firmware_path,
-    })
+        environment,
+    }))
+}
+
+/// POST /api/preflight/setup - Run a QMK setup step (clone QMK or
+/// initialize its submodules) and check the environment again.
+///
+/// Runs until the step finishes, which can take minutes.
+pub(super) async fn run_preflight_setup(
+    State(state): State<AppState>,
+    Json(request): Json<PreflightSetupRequest>,
+) -> Result<Json<QmkEnvReport>, AppError> {
+    // Setup writes to the QMK tree
+    if state.is_sandboxed() {
+        return Err(AppError::with_details(
+            StatusCode::FORBIDDEN,
+            "QMK setup is disabled in sandbox mode",
+            Option::<String>::None,
+        ));
+    }
+
+    let qmk_path = request
+        .qmk_path
+        .map(PathBuf::from)
+        .or_else(|| {
+            state
+                .config
+                .read()
+                .expect("config lock poisoned")
+                .paths
+                .qmk_firmware
+                .clone()
+        })
+        .ok_or_else(|| AppError::bad_request("No QMK firmware path configured"))?;
+
+    let step = request.step;
+    let step_path = qmk_path.clone();
+    tokio::task::spawn_blocking(move || qmk_env::run_setup_step(step, &step_path))
+        .await
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Setup task failed",
+                Some(e.to_string()),
+            )
+        })?
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("{} failed", step.description()),
+                Some(format!("{e:#}")),
+            )
+        })?;
+
+    Ok(Json(check_environment(Some(qmk_path)).await?))
+}
+
+/// Runs the QMK environment checks off the async runtime.
+async fn check_environment(qmk_path: Option<PathBuf>) -> Result<QmkEnvReport, AppError> {
+    tokio::task::spawn_blocking(move || qmk_env::check(qmk_path.as_deref()))
+        .await
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Environment check failed",
+                Some(e.to_string()),
+            )
+        })
 }
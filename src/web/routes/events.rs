@@ -0,0 +1,89 @@
+//! Live layout change stream and publishing helper for mutating handlers.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::stream::{self, Stream};
+use tokio::sync::broadcast::error::RecvError;
+
+use super::super::collab::{LayoutChange, LayoutEvent};
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+use super::audit;
+
+/// Request header identifying the browser session making a change.
+pub const CLIENT_HEADER: &str = "x-lazyqmk-client";
+
+/// GET /api/layouts/{filename}/events - Stream changes made by other sessions.
+///
+/// Sends a `change` event per [`LayoutEvent`]. A `resync` event means the
+/// session fell behind and missed events; it should reload the layout. The
+/// stream ends when the server shuts down.
+pub(super) async fn layout_events(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    let filename = validate_filename(&filename)?;
+    let filename = with_json_ext(filename);
+
+    if !state.layout_exists(&filename).await? {
+        return Err(AppError::not_found(format!(
+            "Layout file not found: {filename}"
+        )));
+    }
+
+    let rx = state.collab.subscribe(&filename);
+    let events = stream::unfold(
+        (rx, Arc::clone(&state.shutdown)),
+        |(mut rx, shutdown)| async move {
+            let event = tokio::select! {
+                () = shutdown.triggered() => return None,
+                received = rx.recv() => next_event(received)?,
+            };
+            Some((Ok(event), (rx, shutdown)))
+        },
+    );
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Converts a received broadcast message into an SSE event, or `None` once
+/// the channel is closed.
+fn next_event(received: Result<LayoutEvent, RecvError>) -> Option<Event> {
+    match received {
+        Ok(event) => Some(
+            Event::default()
+                .event("change")
+                .json_data(&event)
+                .unwrap_or_else(|_| Event::default().event("resync").data("{}")),
+        ),
+        Err(RecvError::Lagged(_)) => Some(Event::default().event("resync").data("{}")),
+        Err(RecvError::Closed) => None,
+    }
+}
+
+/// Publishes `changes` to the other sessions watching `filename`.
+///
+/// The origin and actor are taken from the `X-LazyQMK-Client` and
+/// `X-LazyQMK-User` headers.
+pub(super) fn broadcast_changes(
+    state: &AppState,
+    headers: &HeaderMap,
+    filename: &str,
+    changes: impl IntoIterator<Item = LayoutChange>,
+) {
+    let origin = headers
+        .get(CLIENT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    state
+        .collab
+        .publish(filename, origin, audit::actor(headers), changes);
+}
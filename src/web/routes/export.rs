@@ -1,17 +1,18 @@
-//! Layout export endpoint.
+//! Layout export endpoints.
 
 use axum::{
+    body::Body,
     extract::{Path, State},
-    http::StatusCode,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 
 use crate::export;
 use crate::models::Layout;
-use crate::parser;
-use crate::services::LayoutService;
+use crate::services::share_bundle::{ShareBundle, BUNDLE_EXTENSION};
 
-use super::super::dto::ExportResponse;
+use super::super::dto::{ExportResponse, KeymapDrawerExportResponse, QmkJsonExportResponse};
 use super::super::error::AppError;
 use super::super::validation::validate_filename;
 use super::super::AppState;
@@ -50,12 +51,9 @@ fn generate_simple_markdown(layout: &Layout) -> String {
     output
 }
 
-/// GET /api/layouts/{filename}/export - Export layout to markdown.
-pub(super) async fn export_layout(
-    State(state): State<AppState>,
-    Path(filename): Path<String>,
-) -> Result<Json<ExportResponse>, AppError> {
-    let filename = validate_filename(&filename)?;
+/// Loads the layout behind an export request.
+async fn load_for_export(state: &AppState, filename: &str) -> Result<Layout, AppError> {
+    let filename = validate_filename(filename)?;
 
     let filename = if std::path::Path::new(filename)
         .extension()
@@ -66,54 +64,50 @@ pub(super) async fn export_layout(
         format!("{filename}.json")
     };
 
-    let path = state.workspace_root.join(&filename);
-
-    if !path.exists() {
+    if !state.layout_exists(&filename).await? {
         return Err(AppError::not_found(format!(
             "Layout file not found: {filename}"
         )));
     }
 
-    let layout = LayoutService::load(&path).map_err(|e| {
+    state.layouts.load(&filename).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to load layout",
             Some(e.to_string()),
         )
-    })?;
-
-    let geometry = if let (Some(keyboard), Some(layout_variant)) = (
-        layout.metadata.keyboard.as_ref(),
-        layout.metadata.layout_variant.as_ref(),
-    ) {
-        let qmk_path = state
-            .config
-            .read()
-            .expect("config lock poisoned")
-            .paths
-            .qmk_firmware
-            .clone();
-        if let Some(qmk_path) = qmk_path {
-            parser::keyboard_json::parse_keyboard_info_json(&qmk_path, keyboard)
-                .ok()
-                .and_then(|info| {
-                    parser::keyboard_json::build_keyboard_geometry_with_rgb(
-                        &info,
-                        keyboard,
-                        layout_variant,
-                        None,
-                    )
-                    .ok()
-                })
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+    })
+}
+
+/// Builds the download name `<layout>_export_<date>.<extension>`.
+fn suggested_filename(layout: &Layout, extension: &str) -> String {
+    let layout_name = layout
+        .metadata
+        .name
+        .to_lowercase()
+        .replace(' ', "_")
+        .replace(|c: char| !c.is_alphanumeric() && c != '_', "");
+    let date = chrono::Utc::now().format("%Y%m%d");
+    format!("{layout_name}_export_{date}.{extension}")
+}
+
+/// GET /api/layouts/{filename}/export - Export layout to markdown.
+pub(super) async fn export_layout(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Json<ExportResponse>, AppError> {
+    let layout = load_for_export(&state, &filename).await?;
+
+    // Only export diagrams for layouts with an explicit variant.
+    let geometry = layout
+        .metadata
+        .layout_variant
+        .is_some()
+        .then(|| state.layout_geometry(&layout))
+        .flatten();
 
     let markdown = if let Some(geom) = geometry {
-        export::export_to_markdown(&layout, &geom, &state.keycode_db).map_err(|e| {
+        export::export_to_markdown(&layout, &geom.geometry, &state.keycode_db).map_err(|e| {
             AppError::with_details(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Failed to export layout",
@@ -124,17 +118,127 @@ pub(super) async fn export_layout(
         generate_simple_markdown(&layout)
     };
 
-    let layout_name = layout
-        .metadata
-        .name
-        .to_lowercase()
-        .replace(' ', "_")
-        .replace(|c: char| !c.is_alphanumeric() && c != '_', "");
-    let date = chrono::Utc::now().format("%Y%m%d");
-    let suggested_filename = format!("{layout_name}_export_{date}.md");
-
     Ok(Json(ExportResponse {
         markdown,
-        suggested_filename,
+        suggested_filename: suggested_filename(&layout, "md"),
+    }))
+}
+
+/// GET /api/layouts/{filename}/export/keymap-drawer - Export layout as
+/// keymap-drawer YAML.
+pub(super) async fn export_keymap_drawer(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Json<KeymapDrawerExportResponse>, AppError> {
+    let layout = load_for_export(&state, &filename).await?;
+
+    // keymap-drawer needs the key order of the physical layout
+    let geometry = layout
+        .metadata
+        .layout_variant
+        .is_some()
+        .then(|| state.layout_geometry(&layout))
+        .flatten()
+        .ok_or_else(|| {
+            AppError::bad_request(
+                "keymap-drawer export needs a keyboard and layout variant with known geometry",
+            )
+        })?;
+
+    let yaml = export::export_to_keymap_drawer(&layout, &geometry.geometry, &state.keycode_db)
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to export layout",
+                Some(e.to_string()),
+            )
+        })?;
+
+    Ok(Json(KeymapDrawerExportResponse {
+        yaml,
+        suggested_filename: suggested_filename(&layout, "yaml"),
     }))
 }
+
+/// GET /api/layouts/{filename}/export/qmk-json - Export layout as a QMK
+/// `keymap.json`.
+pub(super) async fn export_qmk_json(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Json<QmkJsonExportResponse>, AppError> {
+    let layout = load_for_export(&state, &filename).await?;
+
+    // keymap.json lists keys in the order of the QMK layout macro
+    let geometry = layout
+        .metadata
+        .layout_variant
+        .is_some()
+        .then(|| state.layout_geometry(&layout))
+        .flatten()
+        .ok_or_else(|| {
+            AppError::bad_request(
+                "keymap.json export needs a keyboard and layout variant with known geometry",
+            )
+        })?;
+
+    let keymap_json = export::export_to_qmk_json(&layout, &geometry.geometry, &state.keycode_db)
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to export layout",
+                Some(e.to_string()),
+            )
+        })?;
+
+    Ok(Json(QmkJsonExportResponse {
+        keymap_json,
+        suggested_filename: suggested_filename(&layout, "keymap.json"),
+    }))
+}
+
+/// GET /api/layouts/{filename}/bundle - Download the layout as a `.lazyqmk`
+/// share bundle.
+///
+/// The bundle includes an SVG preview, the geometry and the generated keymap.c
+/// when the keyboard geometry is known.
+pub(super) async fn export_bundle(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Response, AppError> {
+    let layout = load_for_export(&state, &filename).await?;
+
+    let geometry = layout
+        .metadata
+        .layout_variant
+        .is_some()
+        .then(|| state.layout_geometry(&layout))
+        .flatten();
+
+    let bytes = ShareBundle::new(&layout, geometry.as_ref().map(|g| &g.geometry))
+        .and_then(|mut bundle| {
+            // A layout that doesn't generate yet is still worth sharing
+            let config = state.config.read().unwrap().clone();
+            let _ = bundle.generate_keymap(&config, &state.keycode_db);
+            bundle.to_bytes()
+        })
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to bundle layout",
+                Some(format!("{e:#}")),
+            )
+        })?;
+
+    let download_name = suggested_filename(&layout, BUNDLE_EXTENSION);
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip"),
+            (
+                header::CONTENT_DISPOSITION,
+                &format!("attachment; filename=\"{download_name}\""),
+            ),
+        ],
+        Body::from(bytes),
+    )
+        .into_response())
+}
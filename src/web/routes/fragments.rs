@@ -0,0 +1,110 @@
+//! Layer fragment endpoints: export a layer and apply it into another layout.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+
+use crate::models::Layout;
+use crate::services::fragments::LayerFragment;
+
+use super::super::collab::LayoutChange;
+use super::super::dto::{ApplyFragmentRequest, ApplyFragmentResponse};
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+use super::{audit, events};
+
+/// Loads the layout `filename` from the workspace, returning its path too.
+pub(super) async fn load_workspace_layout(
+    state: &AppState,
+    filename: &str,
+) -> Result<(std::path::PathBuf, String, Layout), AppError> {
+    let filename = with_json_ext(validate_filename(filename)?);
+    let path = state.workspace_root.join(&filename);
+
+    if !state.layout_exists(&filename).await? {
+        return Err(AppError::not_found(format!(
+            "Layout file not found: {filename}"
+        )));
+    }
+
+    let layout = state.layouts.load(&filename).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to load layout",
+            Some(e.to_string()),
+        )
+    })?;
+
+    Ok((path, filename, layout))
+}
+
+/// GET /api/layouts/{filename}/layers/{layer}/fragment - Export a layer as a fragment.
+pub(super) async fn export_fragment(
+    State(state): State<AppState>,
+    Path((filename, layer)): Path<(String, usize)>,
+) -> Result<Json<LayerFragment>, AppError> {
+    let (_, _, layout) = load_workspace_layout(&state, &filename).await?;
+
+    LayerFragment::extract(&layout, layer, &state.keycode_db)
+        .map(Json)
+        .map_err(|e| AppError::bad_request(e.to_string()))
+}
+
+/// POST /api/layouts/{filename}/layers/{layer}/apply-fragment - Apply a fragment into a layer slot.
+///
+/// The fragment is either sent inline or taken from a layer of another
+/// workspace layout (`source_filename` + `source_layer`).
+pub(super) async fn apply_fragment(
+    State(state): State<AppState>,
+    Path((filename, layer)): Path<(String, usize)>,
+    headers: HeaderMap,
+    Json(request): Json<ApplyFragmentRequest>,
+) -> Result<Json<ApplyFragmentResponse>, AppError> {
+    let fragment = match (request.fragment, request.source_filename) {
+        (Some(fragment), None) => fragment,
+        (None, Some(source_filename)) => {
+            let source_layer = request.source_layer.ok_or_else(|| {
+                AppError::bad_request("source_layer is required with source_filename")
+            })?;
+            let (_, _, source) = load_workspace_layout(&state, &source_filename).await?;
+            LayerFragment::extract(&source, source_layer, &state.keycode_db)
+                .map_err(|e| AppError::bad_request(e.to_string()))?
+        }
+        _ => {
+            return Err(AppError::bad_request(
+                "Provide either a fragment or a source_filename, not both",
+            ))
+        }
+    };
+
+    let filename = with_json_ext(validate_filename(&filename)?);
+    let _edit = state.collab.lock_layout(&filename).await;
+    let (path, filename, mut layout) = load_workspace_layout(&state, &filename).await?;
+    let before = layout.clone();
+
+    let report = fragment
+        .apply(&mut layout, layer, &state.keycode_db)
+        .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    state.layouts.save(&filename, &layout).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to save layout",
+            Some(e.to_string()),
+        )
+    })?;
+    audit::record_change(&headers, &path, "apply_fragment", Some(&before), &layout);
+    state
+        .history
+        .record(&filename, "apply_fragment", &before, &layout);
+    events::broadcast_changes(&state, &headers, &filename, [LayoutChange::LayoutReplaced]);
+
+    Ok(Json(ApplyFragmentResponse {
+        filename,
+        layer,
+        report,
+    }))
+}
@@ -9,29 +9,32 @@ use axum::{
 };
 use serde::Deserialize;
 
-use crate::services::LayoutService;
-
 use super::super::error::AppError;
 use super::super::generate_jobs;
-use super::super::validation::{validate_filename, with_json_ext};
+use super::super::validation::{validate_filename, validate_layout_for_job, with_json_ext};
 use super::super::AppState;
 
 /// POST /api/layouts/{filename}/generate - Generate firmware and return job info.
+///
+/// Pass `?qmk_lint=true` to run `qmk lint` on the keyboard after generation;
+/// its findings are attached to the job as `qmk_lint`. Pass `?target=NAME`
+/// to generate for one of the layout's keyboard targets instead of its
+/// primary keyboard.
 pub(super) async fn generate_firmware(
     State(state): State<AppState>,
     Path(filename): Path<String>,
+    Query(options): Query<generate_jobs::GenerateOptions>,
 ) -> Result<Json<generate_jobs::StartGenerateResponse>, AppError> {
     let filename = validate_filename(&filename)?;
     let filename = with_json_ext(filename);
-    let path = state.workspace_root.join(&filename);
 
-    if !path.exists() {
+    if !state.layout_exists(&filename).await? {
         return Err(AppError::not_found(format!(
             "Layout file not found: {filename}"
         )));
     }
 
-    let layout = LayoutService::load(&path).map_err(|e| {
+    let layout = state.layouts.load(&filename).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to load layout",
@@ -39,17 +42,43 @@ pub(super) async fn generate_firmware(
         )
     })?;
 
-    let keyboard = layout.metadata.keyboard.clone().ok_or_else(|| {
+    let geometry = state.layout_geometry(&layout);
+    validate_layout_for_job(
+        &layout,
+        &state.keycode_db,
+        geometry.as_ref().map(|g| &g.geometry),
+    )?;
+
+    state.stage_layout(&filename, &layout).map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to stage layout for the job",
+            Some(e.to_string()),
+        )
+    })?;
+
+    // The job reports the keyboard the firmware is generated for
+    let metadata = match &options.target {
+        Some(target) => {
+            layout
+                .for_target(target)
+                .map_err(|e| AppError::bad_request(e.to_string()))?
+                .metadata
+        }
+        None => layout.metadata,
+    };
+
+    let keyboard = metadata.keyboard.ok_or_else(|| {
         AppError::bad_request("Layout has no keyboard defined - cannot generate firmware")
     })?;
 
-    let layout_variant = layout.metadata.layout_variant.ok_or_else(|| {
+    let layout_variant = metadata.layout_variant.ok_or_else(|| {
         AppError::bad_request("Layout has no layout variant defined - cannot generate firmware")
     })?;
 
     let job = state
         .generate_manager
-        .start_generate(filename.clone(), keyboard, layout_variant)
+        .start_generate_with_options(filename.clone(), keyboard, layout_variant, options)
         .map_err(|e| {
             AppError::with_details(
                 StatusCode::INTERNAL_SERVER_ERROR,
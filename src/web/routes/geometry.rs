@@ -1,26 +1,35 @@
 //! Keyboard geometry, keyboard listing, and layout variant endpoints.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
     Json,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::models::PaletteFxSettings;
 use crate::models::{
-    ComboSettings, IdleEffectSettings, KeyDefinition, Layer, Layout, LayoutMetadata, Position,
-    RgbBrightness, RgbColor, RgbOverlayRippleSettings, RgbSaturation, TapHoldSettings,
-    UncoloredKeyBehavior,
+    ComboSettings, EditingHelpers, EncoderMap, FirmwareFeatures, IdleEffectSettings, KeyDefinition,
+    Layer, Layout, LayoutMetadata, Position, RgbBrightness, RgbColor, RgbConditionalSettings,
+    RgbOverlayRippleSettings, RgbSaturation, TapHoldSettings, UncoloredKeyBehavior,
 };
 use crate::parser;
-use crate::services::LayoutService;
+use crate::services::device_detect::{self, DetectedKeyboard};
+use crate::services::keyboard_index::{KeyboardEntry, KeyboardIndex};
+use crate::services::layouts::sanitize_filename;
+use crate::services::share_bundle::ShareBundle;
+use crate::services::split_leds::{self, SplitLedIssue};
 
+use super::super::collab::LayoutChange;
 use super::super::error::AppError;
 use super::super::validation::{validate_filename, validate_keyboard_path, with_json_ext};
 use super::super::AppState;
+use super::{audit, events};
 
 #[derive(Debug, Serialize)]
 pub(super) struct GeometryResponse {
@@ -31,6 +40,24 @@ pub(super) struct GeometryResponse {
     pub matrix_cols: u8,
     pub encoder_count: u8,
     pub position_to_visual_index: HashMap<String, u8>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub leds: Vec<LedGeometryInfo>,
+    /// Split LED mapping problems (see `services::split_leds`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub split_led_warnings: Vec<String>,
+}
+
+/// Physical RGB LED, positioned in keyboard units (LED center).
+#[derive(Debug, Serialize)]
+pub(super) struct LedGeometryInfo {
+    pub led_index: u8,
+    pub x: f32,
+    pub y: f32,
+    pub flags: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matrix_row: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matrix_col: Option<u8>,
 }
 
 #[derive(Debug, Serialize)]
@@ -47,15 +74,11 @@ pub(super) struct KeyGeometryInfo {
     pub visual_index: u8,
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct KeyboardInfo {
-    pub path: String,
-    pub layout_count: usize,
-}
-
 #[derive(Debug, Serialize)]
 pub(super) struct KeyboardListResponse {
-    pub keyboards: Vec<KeyboardInfo>,
+    pub keyboards: Vec<KeyboardEntry>,
+    /// Connected USB devices that match keyboards of the list
+    pub detected: Vec<DetectedKeyboard>,
 }
 
 #[derive(Debug, Serialize)]
@@ -85,27 +108,40 @@ pub(super) async fn get_geometry(
         .qmk_firmware
         .clone()
         .ok_or_else(|| AppError::bad_request("QMK firmware path not configured"))?;
+    let keyboard_roots = state.config.read().unwrap().paths.keyboard_roots.clone();
 
-    let keyboard_info = parser::keyboard_json::parse_keyboard_info_json(&qmk_path, &keyboard)
-        .map_err(|e| {
-            AppError::with_details(
-                StatusCode::NOT_FOUND,
-                format!("Failed to parse keyboard info for '{keyboard}'"),
-                Some(e.to_string()),
-            )
-        })?;
+    let keyboard_info =
+        parser::keyboard_json::parse_keyboard_info_json(&qmk_path, &keyboard_roots, &keyboard)
+            .map_err(|e| {
+                AppError::with_details(
+                    StatusCode::NOT_FOUND,
+                    format!("Failed to parse keyboard info for '{keyboard}'"),
+                    Some(e.to_string()),
+                )
+            })?;
 
-    let _layout_def = keyboard_info.layouts.get(&layout).ok_or_else(|| {
+    let layout_def = keyboard_info.layouts.get(&layout).ok_or_else(|| {
         AppError::not_found(format!(
             "Layout '{layout}' not found in keyboard '{keyboard}'"
         ))
     })?;
 
-    let geometry = parser::keyboard_json::build_keyboard_geometry_with_rgb(
+    let variant =
+        parser::keyboard_json::parse_variant_keyboard_json(&qmk_path, &keyboard_roots, &keyboard);
+    let split = variant
+        .as_ref()
+        .and_then(|variant| variant.split.clone())
+        .or_else(|| keyboard_info.split.clone());
+    let rgb_matrix = variant.and_then(|variant| variant.rgb_matrix);
+    let split_map = rgb_matrix.as_ref().map(|rgb| {
+        split_leds::split_matrix_to_led(rgb, split.as_ref(), split_leds::matrix_rows(layout_def))
+    });
+
+    let mut geometry = parser::keyboard_json::build_keyboard_geometry_with_rgb(
         &keyboard_info,
         &keyboard,
         &layout,
-        None,
+        split_map.as_ref().map(|map| &map.matrix_to_led),
     )
     .map_err(|e| {
         AppError::with_details(
@@ -114,6 +150,10 @@ pub(super) async fn get_geometry(
             Some(e.to_string()),
         )
     })?;
+    geometry.leds = rgb_matrix
+        .as_ref()
+        .map(parser::keyboard_json::build_led_geometry)
+        .unwrap_or_default();
 
     let keys: Vec<KeyGeometryInfo> = geometry
         .keys
@@ -142,6 +182,22 @@ pub(super) async fn get_geometry(
         })
         .collect();
 
+    let leds: Vec<LedGeometryInfo> = geometry
+        .leds
+        .iter()
+        .map(|led| {
+            let (x, y) = geometry.led_position_in_units(led);
+            LedGeometryInfo {
+                led_index: led.led_index,
+                x,
+                y,
+                flags: led.flags,
+                matrix_row: led.matrix_position.map(|(row, _)| row),
+                matrix_col: led.matrix_position.map(|(_, col)| col),
+            }
+        })
+        .collect();
+
     Ok(Json(GeometryResponse {
         keyboard,
         layout,
@@ -150,87 +206,106 @@ pub(super) async fn get_geometry(
         matrix_cols: geometry.matrix_cols,
         encoder_count: geometry.encoder_count,
         position_to_visual_index,
+        leds,
+        split_led_warnings: split_map
+            .iter()
+            .flat_map(|map| &map.issues)
+            .map(SplitLedIssue::message)
+            .collect(),
     }))
 }
 
-fn scan_keyboard_directory(
-    base_dir: &std::path::Path,
-    current_dir: &std::path::Path,
-    keyboards: &mut Vec<KeyboardInfo>,
-) {
-    let Ok(entries) = std::fs::read_dir(current_dir) else {
-        return;
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if !path.is_dir() {
-            continue;
-        }
-
-        let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-        if dir_name.starts_with('.') || dir_name == "keymaps" {
-            continue;
-        }
+/// GET /api/keyboards - List available keyboards in the QMK keyboards directory
+/// and any configured extra keyboard roots.
+///
+/// Served from the keyboard index; the directories are only scanned when no
+/// index exists yet for the configured paths. `detected` lists the keyboards
+/// plugged in right now, matched by USB vendor and product ID.
+pub(super) async fn list_keyboards(
+    State(state): State<AppState>,
+) -> Result<Json<KeyboardListResponse>, AppError> {
+    let (qmk_path, keyboard_roots) = keyboard_paths(&state)?;
+    if let Some(index) = state.keyboard_cache.cached(&qmk_path, &keyboard_roots) {
+        return keyboard_list(&index).await;
+    }
+    rebuild_keyboard_index(&state, qmk_path, keyboard_roots).await
+}
 
-        let info_json = path.join("info.json");
-        let keyboard_json = path.join("keyboard.json");
-
-        if info_json.exists() || keyboard_json.exists() {
-            if let Ok(rel_path) = path.strip_prefix(base_dir) {
-                let keyboard_path = rel_path.to_string_lossy().replace('\\', "/");
-                let layout_count = get_keyboard_layout_count(&path);
-                if layout_count > 0 {
-                    keyboards.push(KeyboardInfo {
-                        path: keyboard_path,
-                        layout_count,
-                    });
-                }
-            }
-        }
+/// POST /api/keyboards/refresh - Rescan the keyboard directories and return
+/// the new keyboard list.
+pub(super) async fn refresh_keyboards(
+    State(state): State<AppState>,
+) -> Result<Json<KeyboardListResponse>, AppError> {
+    let (qmk_path, keyboard_roots) = keyboard_paths(&state)?;
+    rebuild_keyboard_index(&state, qmk_path, keyboard_roots).await
+}
 
-        let depth = current_dir
-            .strip_prefix(base_dir)
-            .map(|p| p.components().count())
-            .unwrap_or(0);
-        if depth < 4 {
-            scan_keyboard_directory(base_dir, &path, keyboards);
-        }
-    }
+/// Returns the configured QMK path and extra keyboard roots.
+fn keyboard_paths(state: &AppState) -> Result<(PathBuf, Vec<PathBuf>), AppError> {
+    let config = state.config.read().unwrap();
+    let qmk_path = config
+        .paths
+        .qmk_firmware
+        .clone()
+        .ok_or_else(|| AppError::bad_request("QMK firmware path not configured"))?;
+    Ok((qmk_path, config.paths.keyboard_roots.clone()))
 }
 
-fn get_keyboard_layout_count(keyboard_dir: &std::path::Path) -> usize {
-    let info_json = keyboard_dir.join("info.json");
-    if info_json.exists() {
-        if let Ok(content) = std::fs::read_to_string(&info_json) {
-            if let Ok(info) = json5::from_str::<parser::keyboard_json::QmkInfoJson>(&content) {
-                if !info.layouts.is_empty() {
-                    return info.layouts.len();
-                }
-            }
-        }
+async fn rebuild_keyboard_index(
+    state: &AppState,
+    qmk_path: PathBuf,
+    keyboard_roots: Vec<PathBuf>,
+) -> Result<Json<KeyboardListResponse>, AppError> {
+    if !qmk_path.join("keyboards").exists() {
+        return Err(AppError::not_found("QMK keyboards directory not found"));
     }
 
-    let keyboard_json = keyboard_dir.join("keyboard.json");
-    if keyboard_json.exists() {
-        if let Ok(content) = std::fs::read_to_string(&keyboard_json) {
-            if let Ok(variant) =
-                json5::from_str::<parser::keyboard_json::VariantKeyboardJson>(&content)
-            {
-                if !variant.layouts.is_empty() {
-                    return variant.layouts.len();
-                }
-            }
-        }
-    }
+    let cache = Arc::clone(&state.keyboard_cache);
+    let index = tokio::task::spawn_blocking(move || cache.rebuild(&qmk_path, &keyboard_roots))
+        .await
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Keyboard scan task failed",
+                Some(e.to_string()),
+            )
+        })?
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to scan keyboards",
+                Some(format!("{e:#}")),
+            )
+        })?;
 
-    0
+    keyboard_list(&index).await
 }
 
-/// GET /api/keyboards - List available keyboards by scanning QMK keyboards directory.
-pub(super) async fn list_keyboards(
+/// Lists the keyboards of `index` along with the connected ones.
+async fn keyboard_list(index: &KeyboardIndex) -> Result<Json<KeyboardListResponse>, AppError> {
+    let devices = tokio::task::spawn_blocking(device_detect::connected_devices)
+        .await
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "USB device scan failed",
+                Some(e.to_string()),
+            )
+        })?;
+
+    Ok(Json(KeyboardListResponse {
+        keyboards: index.keyboards.clone(),
+        detected: index.connected(&devices),
+    }))
+}
+
+/// GET /api/keyboards/{keyboard}/layouts - Get layout variants for a keyboard.
+pub(super) async fn list_keyboard_layouts(
     State(state): State<AppState>,
-) -> Result<Json<KeyboardListResponse>, AppError> {
+    Path(keyboard): Path<String>,
+) -> Result<Json<LayoutVariantsResponse>, AppError> {
+    validate_keyboard_path(&keyboard)?;
+
     let qmk_path = state
         .config
         .read()
@@ -239,24 +314,47 @@ pub(super) async fn list_keyboards(
         .qmk_firmware
         .clone()
         .ok_or_else(|| AppError::bad_request("QMK firmware path not configured"))?;
+    let keyboard_roots = state.config.read().unwrap().paths.keyboard_roots.clone();
 
-    let keyboards_dir = qmk_path.join("keyboards");
-    if !keyboards_dir.exists() {
-        return Err(AppError::not_found("QMK keyboards directory not found"));
-    }
+    let keyboard_info =
+        parser::keyboard_json::parse_keyboard_info_json(&qmk_path, &keyboard_roots, &keyboard)
+            .map_err(|e| {
+                AppError::with_details(
+                    StatusCode::NOT_FOUND,
+                    format!("Failed to parse keyboard info for '{keyboard}'"),
+                    Some(e.to_string()),
+                )
+            })?;
+
+    let variants: Vec<LayoutVariantInfo> =
+        parser::keyboard_json::extract_layout_variants(&keyboard_info)
+            .into_iter()
+            .map(|v| LayoutVariantInfo {
+                name: v.name,
+                key_count: v.key_count,
+            })
+            .collect();
 
-    let mut keyboards = Vec::new();
-    scan_keyboard_directory(&keyboards_dir, &keyboards_dir, &mut keyboards);
-    keyboards.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(Json(LayoutVariantsResponse { keyboard, variants }))
+}
 
-    Ok(Json(KeyboardListResponse { keyboards }))
+#[derive(Debug, Serialize)]
+pub(super) struct KeyboardInfoJsonResponse {
+    pub keyboard: String,
+    /// Merged files relative to the keyboards directory, parents first.
+    pub sources: Vec<String>,
+    /// The resolved info.json.
+    pub info: serde_json::Value,
 }
 
-/// GET /api/keyboards/{keyboard}/layouts - Get layout variants for a keyboard.
-pub(super) async fn list_keyboard_layouts(
+/// GET /api/keyboards/{keyboard}/info - Get the keyboard's resolved info.json.
+///
+/// Returns the raw QMK configuration after merging parent `info.json` and
+/// `keyboard.json` files, including fields LazyQMK does not model itself.
+pub(super) async fn get_keyboard_info(
     State(state): State<AppState>,
     Path(keyboard): Path<String>,
-) -> Result<Json<LayoutVariantsResponse>, AppError> {
+) -> Result<Json<KeyboardInfoJsonResponse>, AppError> {
     validate_keyboard_path(&keyboard)?;
 
     let qmk_path = state
@@ -267,26 +365,35 @@ pub(super) async fn list_keyboard_layouts(
         .qmk_firmware
         .clone()
         .ok_or_else(|| AppError::bad_request("QMK firmware path not configured"))?;
+    let keyboard_roots = state.config.read().unwrap().paths.keyboard_roots.clone();
 
-    let keyboard_info = parser::keyboard_json::parse_keyboard_info_json(&qmk_path, &keyboard)
+    let resolved = parser::keyboard_json::resolve_info_json(&qmk_path, &keyboard_roots, &keyboard)
         .map_err(|e| {
             AppError::with_details(
                 StatusCode::NOT_FOUND,
-                format!("Failed to parse keyboard info for '{keyboard}'"),
+                format!("Failed to resolve info.json for '{keyboard}'"),
                 Some(e.to_string()),
             )
         })?;
 
-    let variants: Vec<LayoutVariantInfo> =
-        parser::keyboard_json::extract_layout_variants(&keyboard_info)
-            .into_iter()
-            .map(|v| LayoutVariantInfo {
-                name: v.name,
-                key_count: v.key_count,
-            })
-            .collect();
+    let keyboards_dir =
+        parser::keyboard_json::keyboards_dir_for(&qmk_path, &keyboard_roots, &keyboard);
+    let sources = resolved
+        .sources
+        .iter()
+        .map(|path| {
+            path.strip_prefix(&keyboards_dir)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect();
 
-    Ok(Json(LayoutVariantsResponse { keyboard, variants }))
+    Ok(Json(KeyboardInfoJsonResponse {
+        keyboard,
+        sources,
+        info: resolved.info,
+    }))
 }
 
 #[derive(Debug, Deserialize)]
@@ -301,6 +408,47 @@ pub(super) struct CreateLayoutRequest {
     pub author: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub(super) struct ImportLayoutRequest {
+    pub filename: String,
+    pub name: String,
+    /// Defaults to the keyboard named in `keymap_json`
+    #[serde(default)]
+    pub keyboard: Option<String>,
+    /// Defaults to the LAYOUT macro used in the keymap
+    #[serde(default)]
+    pub layout_variant: Option<String>,
+    /// Contents of the keymap.c to import
+    #[serde(default)]
+    pub keymap_c: Option<String>,
+    /// Contents of the QMK keymap.json to import, instead of `keymap_c`
+    #[serde(default)]
+    pub keymap_json: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct ImportLayoutResponse {
+    pub layout: Layout,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct ImportBundleQuery {
+    /// Target layout filename (defaults to the bundled layout's name)
+    #[serde(default)]
+    pub filename: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct ImportBundleResponse {
+    pub filename: String,
+    pub layout: Layout,
+    /// Whether the bundle's geometry snapshot is used when the keyboard
+    /// isn't in the QMK tree
+    pub geometry: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub(super) struct SwitchVariantRequest {
     pub layout_variant: String,
@@ -318,14 +466,14 @@ pub(super) struct SwitchVariantResponse {
 /// POST /api/layouts - Create a new layout.
 pub(super) async fn create_layout(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(request): Json<CreateLayoutRequest>,
 ) -> Result<Json<Layout>, AppError> {
     let filename = validate_filename(&request.filename)?;
     let filename = with_json_ext(filename);
     validate_keyboard_path(&request.keyboard)?;
 
-    let target_path = state.workspace_root.join(&filename);
-    if target_path.exists() {
+    if state.layout_exists(&filename).await? {
         return Err(AppError::with_details(
             StatusCode::CONFLICT,
             "Layout file already exists",
@@ -341,17 +489,20 @@ pub(super) async fn create_layout(
         .qmk_firmware
         .clone()
         .ok_or_else(|| AppError::bad_request("QMK firmware path not configured"))?;
+    let keyboard_roots = state.config.read().unwrap().paths.keyboard_roots.clone();
 
-    let keyboard_info =
-        parser::keyboard_json::parse_keyboard_info_json(&qmk_path, &request.keyboard).map_err(
-            |e| {
-                AppError::with_details(
-                    StatusCode::NOT_FOUND,
-                    format!("Failed to parse keyboard info for '{}'", request.keyboard),
-                    Some(e.to_string()),
-                )
-            },
-        )?;
+    let keyboard_info = parser::keyboard_json::parse_keyboard_info_json(
+        &qmk_path,
+        &keyboard_roots,
+        &request.keyboard,
+    )
+    .map_err(|e| {
+        AppError::with_details(
+            StatusCode::NOT_FOUND,
+            format!("Failed to parse keyboard info for '{}'", request.keyboard),
+            Some(e.to_string()),
+        )
+    })?;
 
     let layout_def = keyboard_info
         .layouts
@@ -380,6 +531,7 @@ pub(super) async fn create_layout(
             category_id: None,
             combo_participant: false,
             description: None,
+            locked: false,
         });
     }
 
@@ -391,6 +543,8 @@ pub(super) async fn create_layout(
         category_id: None,
         keys: base_keys,
         layer_colors_enabled: true,
+        underglow_color: None,
+        encoder_map: EncoderMap::default(),
     };
 
     let metadata = LayoutMetadata {
@@ -406,6 +560,11 @@ pub(super) async fn create_layout(
         keyboard: Some(request.keyboard),
         keymap_name: Some("default".to_string()),
         output_format: Some("uf2".to_string()),
+        converter: None,
+        targets: Vec::new(),
+        placeholders: Vec::new(),
+        geometry: None,
+        qmk_tree: None,
     };
 
     let layout = Layout {
@@ -421,39 +580,219 @@ pub(super) async fn create_layout(
         idle_effect_settings: IdleEffectSettings::default(),
         rgb_overlay_ripple: RgbOverlayRippleSettings::default(),
         palette_fx: PaletteFxSettings::default(),
+        rgb_conditions: RgbConditionalSettings::default(),
+        firmware_features: FirmwareFeatures::default(),
         tap_hold_settings: TapHoldSettings::default(),
         combo_settings: ComboSettings::default(),
         tap_dances: vec![],
+        macros: vec![],
+        custom_keycodes: Vec::new(),
+        key_overrides: Vec::new(),
+        alt_repeat_keys: Vec::new(),
+        unicode_map: Vec::new(),
+        unicode_input_modes: Vec::new(),
+        usage_counting: false,
+        editing_helpers: EditingHelpers::default(),
     };
 
-    LayoutService::save(&layout, &target_path).map_err(|e| {
+    state.layouts.save(&filename, &layout).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to save layout",
             Some(e.to_string()),
         )
     })?;
+    let target_path = state.workspace_root.join(&filename);
+    audit::record_change(&headers, &target_path, "create", None, &layout);
 
     Ok(Json(layout))
 }
 
+/// POST /api/layouts/import - Create a layout from an existing QMK keymap.c
+/// or keymap.json.
+pub(super) async fn import_layout(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<ImportLayoutRequest>,
+) -> Result<Json<ImportLayoutResponse>, AppError> {
+    let filename = validate_filename(&request.filename)?;
+    let filename = with_json_ext(filename);
+
+    if state.layout_exists(&filename).await? {
+        return Err(AppError::with_details(
+            StatusCode::CONFLICT,
+            "Layout file already exists",
+            Some(format!("Layout file already exists: {filename}")),
+        ));
+    }
+
+    let (parsed, json) = match (&request.keymap_c, &request.keymap_json) {
+        (Some(source), None) => {
+            let parsed = parser::keymap_c::parse_keymap_c(source).map_err(|e| {
+                AppError::with_details(
+                    StatusCode::BAD_REQUEST,
+                    "Failed to parse keymap.c",
+                    Some(format!("{e:#}")),
+                )
+            })?;
+            (parsed, None)
+        }
+        (None, Some(source)) => {
+            let json = parser::qmk_json::parse_qmk_json(source).map_err(|e| {
+                AppError::with_details(
+                    StatusCode::BAD_REQUEST,
+                    "Failed to parse keymap.json",
+                    Some(format!("{e:#}")),
+                )
+            })?;
+            (json.keymap.clone(), Some(json))
+        }
+        _ => {
+            return Err(AppError::bad_request(
+                "Provide exactly one of keymap_c and keymap_json",
+            ))
+        }
+    };
+
+    let keyboard = request
+        .keyboard
+        .or_else(|| json.as_ref().and_then(|json| json.keyboard.clone()))
+        .ok_or_else(|| AppError::bad_request("No keyboard given for the imported keymap"))?;
+    validate_keyboard_path(&keyboard)?;
+
+    let qmk_path = state
+        .config
+        .read()
+        .unwrap()
+        .paths
+        .qmk_firmware
+        .clone()
+        .ok_or_else(|| AppError::bad_request("QMK firmware path not configured"))?;
+    let keyboard_roots = state.config.read().unwrap().paths.keyboard_roots.clone();
+
+    let variant = request
+        .layout_variant
+        .unwrap_or_else(|| parsed.layout_macro.clone());
+    let cached = state
+        .geometry_cache
+        .get(&qmk_path, &keyboard_roots, &keyboard, &variant)
+        .ok_or_else(|| {
+            AppError::not_found(format!(
+                "Layout variant '{variant}' not found in keyboard '{keyboard}'"
+            ))
+        })?;
+
+    let layout = json
+        .as_ref()
+        .map_or_else(
+            || parsed.to_layout(&request.name, &cached.geometry),
+            |json| json.to_layout(&request.name, &cached.geometry),
+        )
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Keymap does not fit the keyboard layout",
+                Some(format!("{e:#}")),
+            )
+        })?;
+
+    state.layouts.save(&filename, &layout).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to save layout",
+            Some(e.to_string()),
+        )
+    })?;
+    let target_path = state.workspace_root.join(&filename);
+    audit::record_change(&headers, &target_path, "import", None, &layout);
+
+    Ok(Json(ImportLayoutResponse {
+        layout,
+        warnings: parsed.warnings,
+    }))
+}
+
+/// POST /api/layouts/import-bundle - Create a layout from a `.lazyqmk` share
+/// bundle sent as the request body.
+pub(super) async fn import_bundle(
+    State(state): State<AppState>,
+    Query(query): Query<ImportBundleQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<ImportBundleResponse>, AppError> {
+    let bundle = ShareBundle::from_bytes(&body).map_err(|e| {
+        AppError::with_details(
+            StatusCode::BAD_REQUEST,
+            "Invalid share bundle",
+            Some(format!("{e:#}")),
+        )
+    })?;
+
+    let filename = match &query.filename {
+        Some(filename) => with_json_ext(validate_filename(filename)?),
+        None => with_json_ext(&sanitize_filename(&bundle.layout.metadata.name)),
+    };
+    if state.layout_exists(&filename).await? {
+        return Err(AppError::with_details(
+            StatusCode::CONFLICT,
+            "Layout file already exists",
+            Some(format!("Layout file already exists: {filename}")),
+        ));
+    }
+
+    state
+        .layouts
+        .save(&filename, &bundle.layout)
+        .await
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to save layout",
+                Some(e.to_string()),
+            )
+        })?;
+    let has_geometry = bundle.geometry.is_some();
+    if let Some(geometry) = bundle.geometry {
+        state
+            .geometry_cache
+            .insert_snapshot(&bundle.layout, geometry);
+    }
+    let target_path = state.workspace_root.join(&filename);
+    audit::record_change(
+        &headers,
+        &target_path,
+        "import_bundle",
+        None,
+        &bundle.layout,
+    );
+
+    Ok(Json(ImportBundleResponse {
+        filename,
+        layout: bundle.layout,
+        geometry: has_geometry,
+    }))
+}
+
 /// POST /api/layouts/{filename}/switch-variant - Switch layout to a different variant.
 pub(super) async fn switch_layout_variant(
     State(state): State<AppState>,
     Path(filename): Path<String>,
+    headers: HeaderMap,
     Json(request): Json<SwitchVariantRequest>,
 ) -> Result<Json<SwitchVariantResponse>, AppError> {
     let filename = validate_filename(&filename)?;
     let filename = with_json_ext(filename);
     let path = state.workspace_root.join(&filename);
 
-    if !path.exists() {
+    let _edit = state.collab.lock_layout(&filename).await;
+
+    if !state.layout_exists(&filename).await? {
         return Err(AppError::not_found(format!(
             "Layout file not found: {filename}"
         )));
     }
 
-    let mut layout = LayoutService::load(&path).map_err(|e| {
+    let mut layout = state.layouts.load(&filename).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to load layout",
@@ -461,6 +800,8 @@ pub(super) async fn switch_layout_variant(
         )
     })?;
 
+    let before = layout.clone();
+
     let keyboard = layout.metadata.keyboard.clone().ok_or_else(|| {
         AppError::bad_request("Layout has no keyboard defined - cannot switch variant")
     })?;
@@ -473,15 +814,17 @@ pub(super) async fn switch_layout_variant(
         .qmk_firmware
         .clone()
         .ok_or_else(|| AppError::bad_request("QMK firmware path not configured"))?;
+    let keyboard_roots = state.config.read().unwrap().paths.keyboard_roots.clone();
 
-    let keyboard_info = parser::keyboard_json::parse_keyboard_info_json(&qmk_path, &keyboard)
-        .map_err(|e| {
-            AppError::with_details(
-                StatusCode::NOT_FOUND,
-                format!("Failed to parse keyboard info for '{keyboard}'"),
-                Some(e.to_string()),
-            )
-        })?;
+    let keyboard_info =
+        parser::keyboard_json::parse_keyboard_info_json(&qmk_path, &keyboard_roots, &keyboard)
+            .map_err(|e| {
+                AppError::with_details(
+                    StatusCode::NOT_FOUND,
+                    format!("Failed to parse keyboard info for '{keyboard}'"),
+                    Some(e.to_string()),
+                )
+            })?;
 
     let new_layout_def = keyboard_info
         .layouts
@@ -527,6 +870,7 @@ pub(super) async fn switch_layout_variant(
                     category_id: None,
                     combo_participant: false,
                     description: None,
+                    locked: false,
                 });
             }
         } else if new_key_count < layer.keys.len() {
@@ -544,13 +888,18 @@ pub(super) async fn switch_layout_variant(
         }
     }
 
-    LayoutService::save(&layout, &path).map_err(|e| {
+    state.layouts.save(&filename, &layout).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to save layout",
             Some(e.to_string()),
         )
     })?;
+    audit::record_change(&headers, &path, "switch_variant", Some(&before), &layout);
+    state
+        .history
+        .record(&filename, "switch_variant", &before, &layout);
+    events::broadcast_changes(&state, &headers, &filename, [LayoutChange::LayoutReplaced]);
 
     Ok(Json(SwitchVariantResponse {
         layout,
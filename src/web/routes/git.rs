@@ -0,0 +1,70 @@
+//! Git history endpoint for layouts kept in a git repository.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+
+use crate::services::git::GitRepo;
+
+use super::super::dto::{GitHistoryQuery, GitHistoryResponse};
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+
+/// Commits returned when the request sets no limit.
+const DEFAULT_LIMIT: usize = 50;
+
+/// GET /api/layouts/{filename}/history - List the git commits that touched a layout.
+///
+/// Layouts outside a git work tree (or in remote storage) have no history.
+pub(super) async fn get_git_history(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+    Query(query): Query<GitHistoryQuery>,
+) -> Result<Json<GitHistoryResponse>, AppError> {
+    let filename = with_json_ext(validate_filename(&filename)?);
+    if !state.layout_exists(&filename).await? {
+        return Err(AppError::not_found(format!(
+            "Layout file not found: {filename}"
+        )));
+    }
+
+    let path = state.layouts.local_path(&filename);
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    let history = tokio::task::spawn_blocking(move || {
+        let Some(path) = path else {
+            return Ok(None);
+        };
+        let Some(repo) = GitRepo::discover(&path) else {
+            return Ok(None);
+        };
+        Ok::<_, anyhow::Error>(Some((repo.file_status(&path)?, repo.log(&path, limit)?)))
+    })
+    .await
+    .map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Git history task failed",
+            Some(e.to_string()),
+        )
+    })?
+    .map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to read git history",
+            Some(format!("{e:#}")),
+        )
+    })?;
+
+    let (status, commits) = history.map_or((None, Vec::new()), |(status, commits)| {
+        (Some(status), commits)
+    });
+    Ok(Json(GitHistoryResponse {
+        filename,
+        in_repository: status.is_some(),
+        status,
+        commits,
+    }))
+}
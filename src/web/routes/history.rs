@@ -0,0 +1,93 @@
+//! Undo/redo endpoints backed by the per-layout edit journal.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+
+use super::super::collab::LayoutChange;
+use super::super::dto::HistoryResponse;
+use super::super::error::AppError;
+use super::super::history::{HistoryDirection, HistoryError};
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+use super::fragments::load_workspace_layout;
+use super::{audit, events};
+
+/// POST /api/layouts/{filename}/history/undo - Undo the latest web API change.
+pub(super) async fn undo(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<HistoryResponse>, AppError> {
+    step(&state, &headers, &filename, HistoryDirection::Undo).await
+}
+
+/// POST /api/layouts/{filename}/history/redo - Reapply the latest undone change.
+pub(super) async fn redo(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<HistoryResponse>, AppError> {
+    step(&state, &headers, &filename, HistoryDirection::Redo).await
+}
+
+/// Moves `filename` one step through its journal and saves the result.
+async fn step(
+    state: &AppState,
+    headers: &HeaderMap,
+    filename: &str,
+    direction: HistoryDirection,
+) -> Result<Json<HistoryResponse>, AppError> {
+    let filename = with_json_ext(validate_filename(filename)?);
+    let _edit = state.collab.lock_layout(&filename).await;
+    let (path, filename, current) = load_workspace_layout(state, &filename).await?;
+    let operation = match direction {
+        HistoryDirection::Undo => "undo",
+        HistoryDirection::Redo => "redo",
+    };
+
+    let history_move = state
+        .history
+        .take(&filename, direction, &current)
+        .map_err(|e| match e {
+            HistoryError::Empty => AppError::with_details(
+                StatusCode::CONFLICT,
+                format!("Nothing to {operation}"),
+                None,
+            ),
+            HistoryError::Diverged => AppError::with_details(
+                StatusCode::CONFLICT,
+                "Layout was changed outside the web editor",
+                Some(format!(
+                    "{filename} no longer matches its edit history, so the history was cleared"
+                )),
+            ),
+        })?;
+
+    if let Err(e) = state.layouts.save(&filename, &history_move.layout).await {
+        state.history.revert(&filename, direction);
+        return Err(AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to save layout",
+            Some(e.to_string()),
+        ));
+    }
+    audit::record_change(
+        headers,
+        &path,
+        operation,
+        Some(&current),
+        &history_move.layout,
+    );
+    events::broadcast_changes(state, headers, &filename, [LayoutChange::LayoutReplaced]);
+
+    let (undo_steps, redo_steps) = state.history.depth(&filename);
+    Ok(Json(HistoryResponse {
+        filename,
+        action: history_move.action,
+        undo_steps,
+        redo_steps,
+    }))
+}
@@ -7,8 +7,6 @@ use axum::{
 };
 use regex::Regex;
 
-use crate::services::LayoutService;
-
 use super::super::dto::{
     InspectLayer, InspectMetadata, InspectResponse, InspectSettings, InspectTapDance,
 };
@@ -23,15 +21,14 @@ pub(super) async fn inspect_layout(
 ) -> Result<Json<InspectResponse>, AppError> {
     let filename = validate_filename(&filename)?;
     let filename = with_json_ext(filename);
-    let path = state.workspace_root.join(&filename);
 
-    if !path.exists() {
+    if !state.layout_exists(&filename).await? {
         return Err(AppError::not_found(format!(
             "Layout file not found: {filename}"
         )));
     }
 
-    let layout = LayoutService::load(&path).map_err(|e| {
+    let layout = state.layouts.load(&filename).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to load layout",
@@ -1,14 +1,24 @@
-//! Keycode search and category listing endpoints.
+//! Keycode search, category listing, display preview, and favorites endpoints.
+
+use std::collections::HashMap;
 
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
     Json,
 };
 
 use super::super::dto::{
-    CategoryInfo, CategoryListResponse, KeycodeInfo, KeycodeListResponse, KeycodeQuery,
+    AddFavoriteKeycodeRequest, CategoryInfo, CategoryListResponse, KeycodeDisplayQuery,
+    KeycodeDisplayResponse, KeycodeFavoritesResponse, KeycodeInfo, KeycodeListResponse,
+    KeycodeQuery, SetFavoriteKeycodesRequest,
 };
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
 use super::super::AppState;
+use crate::config::UiConfig;
+use crate::keycode_db::TapDanceDisplayInfo;
 
 /// GET /api/keycodes - Query keycode database.
 pub(super) async fn list_keycodes(
@@ -47,3 +57,174 @@ pub(super) async fn list_categories(State(state): State<AppState>) -> Json<Categ
 
     Json(CategoryListResponse { categories })
 }
+
+/// GET /api/keycodes/dump - Dump the merged keycode database for inspection.
+///
+/// Includes every category, language and keycode after language files are
+/// merged, plus any problems found by the database self-test.
+pub(super) async fn dump_keycode_db(State(state): State<AppState>) -> Response {
+    Json(state.keycode_db.dump()).into_response()
+}
+
+/// GET /api/keycodes/display?code=LT(2,KC_SPC) - Preview display metadata for a keycode.
+///
+/// Returns the same label/detail breakdown used by render-metadata. When a
+/// `layout` filename is given, tap dances and `@uuid` layer references are
+/// resolved against that layout.
+pub(super) async fn get_keycode_display(
+    State(state): State<AppState>,
+    Query(query): Query<KeycodeDisplayQuery>,
+) -> Result<Json<KeycodeDisplayResponse>, AppError> {
+    let code = query.code.trim();
+    if code.is_empty() {
+        return Err(AppError::bad_request("Keycode cannot be empty"));
+    }
+
+    let layout = match &query.layout {
+        Some(filename) => {
+            let filename = with_json_ext(validate_filename(filename)?);
+            if !state.layout_exists(&filename).await? {
+                return Err(AppError::not_found(format!(
+                    "Layout file not found: {filename}"
+                )));
+            }
+            Some(state.layouts.load(&filename).await.map_err(|e| {
+                AppError::with_details(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to load layout",
+                    Some(e.to_string()),
+                )
+            })?)
+        }
+        None => None,
+    };
+
+    let td_info = layout.as_ref().and_then(|layout| {
+        let td_name = state.keycode_db.parse_tap_dance_keycode(code)?;
+        layout
+            .tap_dances
+            .iter()
+            .find(|td| td.name == td_name)
+            .map(|td| TapDanceDisplayInfo {
+                single_tap: td.single_tap.clone(),
+                double_tap: td.double_tap.clone(),
+                hold: td.hold.clone(),
+            })
+    });
+    let layer_id_to_number: Option<HashMap<String, u8>> = layout.as_ref().map(|layout| {
+        layout
+            .layers
+            .iter()
+            .map(|layer| (layer.id.clone(), layer.number))
+            .collect()
+    });
+
+    let meta =
+        state
+            .keycode_db
+            .get_display_metadata(code, td_info.as_ref(), layer_id_to_number.as_ref());
+
+    Ok(Json(KeycodeDisplayResponse {
+        code: code.to_string(),
+        valid: state.keycode_db.is_valid(code),
+        display: meta.display.into(),
+        details: meta.details.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// GET /api/keycodes/favorites - List the keycode picker's favorite and recent keycodes.
+pub(super) async fn get_favorites(State(state): State<AppState>) -> Json<KeycodeFavoritesResponse> {
+    let config = state.config.read().expect("config lock poisoned");
+    Json(favorites_response(&config.ui))
+}
+
+/// POST /api/keycodes/favorites - Append a keycode to the favorites.
+///
+/// Adding a keycode that already is a favorite leaves the list unchanged.
+pub(super) async fn add_favorite(
+    State(state): State<AppState>,
+    Json(request): Json<AddFavoriteKeycodeRequest>,
+) -> Result<Json<KeycodeFavoritesResponse>, AppError> {
+    let keycode = validate_keycode(&state, &request.keycode)?;
+    update_favorites(&state, |ui| {
+        ui.add_favorite_keycode(&keycode);
+        Ok(())
+    })
+}
+
+/// PUT /api/keycodes/favorites - Replace the favorites, e.g. to reorder them.
+pub(super) async fn set_favorites(
+    State(state): State<AppState>,
+    Json(request): Json<SetFavoriteKeycodesRequest>,
+) -> Result<Json<KeycodeFavoritesResponse>, AppError> {
+    let mut favorites: Vec<String> = Vec::with_capacity(request.favorites.len());
+    for keycode in &request.favorites {
+        let keycode = validate_keycode(&state, keycode)?;
+        if !favorites.contains(&keycode) {
+            favorites.push(keycode);
+        }
+    }
+    update_favorites(&state, |ui| {
+        ui.favorite_keycodes = favorites;
+        Ok(())
+    })
+}
+
+/// DELETE /api/keycodes/favorites/{keycode} - Remove a keycode from the favorites.
+pub(super) async fn remove_favorite(
+    State(state): State<AppState>,
+    Path(keycode): Path<String>,
+) -> Result<Json<KeycodeFavoritesResponse>, AppError> {
+    update_favorites(&state, |ui| {
+        if ui.remove_favorite_keycode(&keycode) {
+            Ok(())
+        } else {
+            Err(AppError::not_found(format!(
+                "Keycode is not a favorite: {keycode}"
+            )))
+        }
+    })
+}
+
+fn favorites_response(ui: &UiConfig) -> KeycodeFavoritesResponse {
+    KeycodeFavoritesResponse {
+        favorites: ui.favorite_keycodes.clone(),
+        recent: ui.recent_keycodes.clone(),
+    }
+}
+
+/// Trims `keycode` and checks it against the keycode database.
+fn validate_keycode(state: &AppState, keycode: &str) -> Result<String, AppError> {
+    let keycode = keycode.trim();
+    if keycode.is_empty() {
+        return Err(AppError::bad_request("Keycode cannot be empty"));
+    }
+    if !state.keycode_db.is_valid(keycode) {
+        return Err(AppError::bad_request(format!("Unknown keycode: {keycode}")));
+    }
+    Ok(keycode.to_string())
+}
+
+/// Applies `change` to the UI config and saves it (a sandbox server only
+/// keeps the change in memory, like `PUT /api/config`).
+fn update_favorites(
+    state: &AppState,
+    change: impl FnOnce(&mut UiConfig) -> Result<(), AppError>,
+) -> Result<Json<KeycodeFavoritesResponse>, AppError> {
+    let mut config = state.config.write().expect("config lock poisoned");
+    let mut updated = config.clone();
+    change(&mut updated.ui)?;
+
+    if updated != *config && !state.is_sandboxed() {
+        updated.save().map_err(|e| {
+            AppError::with_details(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to save configuration",
+                Some(e.to_string()),
+            )
+        })?;
+    }
+
+    *config = updated;
+    Ok(Json(favorites_response(&config.ui)))
+}
@@ -0,0 +1,183 @@
+//! Single-key edit endpoints.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+
+use crate::keycode_db::KeycodeDb;
+use crate::models::{Layout, Position};
+
+use super::super::collab::LayoutChange;
+use super::super::dto::{AssignKeyRequest, KeyRenderMetadata};
+use super::super::error::AppError;
+use super::super::render::KeyRenderContext;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+use super::{audit, events};
+
+/// Maximum number of "did you mean" suggestions attached to an invalid keycode error.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// POST /api/layouts/{filename}/layers/{layer}/keys/{row}/{col} - Assign a single key.
+///
+/// Updates the keycode, color override, category, and/or lock of one key and
+/// returns the key's refreshed render metadata. Locks only guard against bulk
+/// edits, so a locked key can still be assigned here directly.
+pub(super) async fn assign_key(
+    State(state): State<AppState>,
+    Path((filename, layer, row, col)): Path<(String, usize, u8, u8)>,
+    headers: HeaderMap,
+    Json(request): Json<AssignKeyRequest>,
+) -> Result<Json<KeyRenderMetadata>, AppError> {
+    let filename = validate_filename(&filename)?;
+    let filename = with_json_ext(filename);
+    let path = state.workspace_root.join(&filename);
+    let _edit = state.collab.lock_layout(&filename).await;
+
+    if !state.layout_exists(&filename).await? {
+        return Err(AppError::not_found(format!(
+            "Layout file not found: {filename}"
+        )));
+    }
+
+    let mut layout = state.layouts.load(&filename).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to load layout",
+            Some(e.to_string()),
+        )
+    })?;
+
+    if layer >= layout.layers.len() {
+        return Err(AppError::bad_request(format!(
+            "Invalid layer number: {layer}"
+        )));
+    }
+
+    let position = Position::new(row, col);
+    let key_idx = layout.layers[layer]
+        .keys
+        .iter()
+        .position(|k| k.position == position)
+        .ok_or_else(|| {
+            AppError::not_found(format!(
+                "No key at position ({row}, {col}) on layer {layer}"
+            ))
+        })?;
+
+    if let Some(keycode) = &request.keycode {
+        validate_keycode(&layout, &state.keycode_db, keycode)?;
+    }
+    if let Some(Some(category_id)) = &request.category_id {
+        if layout.get_category(category_id).is_none() {
+            return Err(AppError::bad_request(format!(
+                "Unknown category: {category_id}"
+            )));
+        }
+    }
+
+    let before = layout.clone();
+    let key = &mut layout.layers[layer].keys[key_idx];
+    if let Some(keycode) = request.keycode {
+        key.keycode = keycode.trim().to_string();
+    }
+    if let Some(color_override) = request.color_override {
+        key.color_override = color_override;
+    }
+    if let Some(category_id) = request.category_id {
+        key.category_id = category_id;
+    }
+    if let Some(locked) = request.locked {
+        key.locked = locked;
+    }
+    layout.metadata.touch();
+
+    state.layouts.save(&filename, &layout).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to save layout",
+            Some(e.to_string()),
+        )
+    })?;
+    audit::record_change(&headers, &path, "assign_key", Some(&before), &layout);
+    state
+        .history
+        .record(&filename, "assign_key", &before, &layout);
+    events::broadcast_changes(
+        &state,
+        &headers,
+        &filename,
+        [LayoutChange::KeyUpdated {
+            layer,
+            key: layout.layers[layer].keys[key_idx].clone(),
+        }],
+    );
+
+    let visual_index = state
+        .layout_geometry(&layout)
+        .and_then(|geometry| geometry.key(position))
+        .map_or(key_idx as u8, |info| info.visual_index);
+    let render_ctx = KeyRenderContext::new(&state.keycode_db, &layout);
+
+    Ok(Json(render_ctx.key_metadata(
+        &layout.layers[layer].keys[key_idx],
+        visual_index,
+    )))
+}
+
+/// Checks that `keycode` is known to the keycode database and that any tap
+/// dance or layer it references exists in `layout`.
+pub(super) fn validate_keycode(
+    layout: &Layout,
+    keycode_db: &KeycodeDb,
+    keycode: &str,
+) -> Result<(), AppError> {
+    let keycode = keycode.trim();
+    if keycode.is_empty() {
+        return Err(AppError::bad_request("Keycode must not be empty"));
+    }
+
+    if let Some(td_name) = keycode_db.parse_tap_dance_keycode(keycode) {
+        if !layout.tap_dances.iter().any(|td| td.name == td_name) {
+            return Err(AppError::bad_request(format!(
+                "Unknown tap dance: {td_name}"
+            )));
+        }
+        return Ok(());
+    }
+
+    if !keycode_db.is_valid(keycode) {
+        let suggestions: Vec<&str> = keycode_db
+            .search(keycode)
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|k| k.code.as_str())
+            .collect();
+        let details =
+            (!suggestions.is_empty()).then(|| format!("Did you mean: {}?", suggestions.join(", ")));
+        return Err(AppError::with_details(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid keycode: {keycode}"),
+            details,
+        ));
+    }
+
+    if keycode_db.parse_layer_keycode(keycode).is_some() {
+        let target = layout
+            .resolve_layer_keycode(keycode, keycode_db)
+            .and_then(|resolved| {
+                keycode_db
+                    .parse_layer_keycode(&resolved)
+                    .and_then(|(_, layer_ref, _)| layer_ref.parse::<usize>().ok())
+            });
+        if target.is_none_or(|idx| idx >= layout.layers.len()) {
+            return Err(AppError::bad_request(format!(
+                "Keycode references a layer that does not exist: {keycode}"
+            )));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Layer graph endpoint.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+
+use crate::services::layer_graph::LayerGraph;
+
+use super::super::dto::{LayerGraphEdgeDto, LayerGraphNodeDto, LayerGraphResponse};
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+
+/// GET /api/layouts/{filename}/layer-graph - Which keys activate which
+/// layers, with unreachable layers and layers with no way back flagged.
+pub(super) async fn get_layer_graph(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Json<LayerGraphResponse>, AppError> {
+    let filename = validate_filename(&filename)?;
+    let filename = with_json_ext(filename);
+
+    if !state.layout_exists(&filename).await? {
+        return Err(AppError::not_found(format!(
+            "Layout file not found: {filename}"
+        )));
+    }
+
+    let layout = state.layouts.load(&filename).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to load layout",
+            Some(e.to_string()),
+        )
+    })?;
+
+    let graph = LayerGraph::build(&layout.layers);
+    let issues = graph.issues();
+    let layers = graph
+        .nodes
+        .into_iter()
+        .map(|node| LayerGraphNodeDto {
+            layer: node.layer,
+            name: node.name,
+            reachable: node.reachable,
+            has_way_back: node.has_way_back,
+        })
+        .collect();
+    let edges = graph
+        .edges
+        .into_iter()
+        .map(|edge| LayerGraphEdgeDto {
+            from_layer: edge.from_layer,
+            to_layer: edge.to_layer,
+            row: edge.position.row,
+            col: edge.position.col,
+            keycode: edge.keycode,
+            kind: edge.kind.display_name().to_string(),
+        })
+        .collect();
+
+    Ok(Json(LayerGraphResponse {
+        filename,
+        layers,
+        edges,
+        issues,
+    }))
+}
@@ -0,0 +1,82 @@
+//! Layer duplication endpoint.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+
+use crate::services::layer_mirror;
+
+use super::super::dto::{DuplicateLayerQuery, DuplicateLayerResponse};
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+use super::fragments::load_workspace_layout;
+use super::tap_dances::save_layout;
+
+/// POST /api/layouts/{filename}/layers/{layer}/duplicate - Copy a layer to the end.
+///
+/// With `mirror=true` the copy is flipped horizontally: each key takes the
+/// assignment of the key at the mirrored physical position, which needs the
+/// keyboard geometry from QMK.
+pub(super) async fn duplicate_layer(
+    State(state): State<AppState>,
+    Path((filename, layer)): Path<(String, usize)>,
+    Query(query): Query<DuplicateLayerQuery>,
+    headers: HeaderMap,
+) -> Result<Json<DuplicateLayerResponse>, AppError> {
+    let filename = with_json_ext(validate_filename(&filename)?);
+    let _edit = state.collab.lock_layout(&filename).await;
+    let (path, filename, mut layout) = load_workspace_layout(&state, &filename).await?;
+    let before = layout.clone();
+
+    let source = layout
+        .layers
+        .get(layer)
+        .ok_or_else(|| AppError::not_found(format!("Layer {layer} not found")))?;
+    let name = query.name.map_or_else(
+        || format!("{} Copy", source.name),
+        |name| name.trim().to_string(),
+    );
+    let number =
+        u8::try_from(layout.layers.len()).map_err(|_| AppError::bad_request("Too many layers"))?;
+    let mut copy = source
+        .duplicate(number, name)
+        .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    let mirrored_keys = if query.mirror {
+        let geometry = state.layout_geometry(&layout).ok_or_else(|| {
+            AppError::bad_request(
+                "Mirroring needs the keyboard geometry; check the QMK firmware path and the layout's keyboard",
+            )
+        })?;
+        let pairs = layer_mirror::mirror_positions(&geometry.geometry);
+        layer_mirror::mirror_layer(&mut copy, &pairs)
+    } else {
+        0
+    };
+
+    let response = DuplicateLayerResponse {
+        layer: layout.layers.len(),
+        id: copy.id.clone(),
+        name: copy.name.clone(),
+        mirrored_keys,
+    };
+    layout
+        .add_layer(copy)
+        .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    save_layout(
+        &state,
+        &headers,
+        &path,
+        &filename,
+        "duplicate_layer",
+        &before,
+        &layout,
+    )
+    .await?;
+
+    Ok(Json(response))
+}
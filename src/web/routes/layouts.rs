@@ -1,11 +1,9 @@
 //! Layout CRUD, key swap, firmware generation, template save, render metadata,
 //! create layout, and switch variant endpoints.
 
-use std::collections::HashMap;
-
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     Json,
 };
 
@@ -17,8 +15,8 @@ use crate::models::{
     HoldDecisionMode, PaletteFxEffect, PaletteFxPalette, RippleColorMode, TapHoldPreset,
 };
 use crate::models::{PaletteFxSettings, RgbMatrixEffect};
-use crate::parser;
 
+use super::super::collab::LayoutChange;
 use super::super::dto::{
     ComboActionDto, ComboSettingsDto, IdleEffectSettingsDto, PaletteFxSettingsDto,
     RgbOverlayRippleSettingsDto, TapDanceDto, TapHoldSettingsDto,
@@ -26,16 +24,17 @@ use super::super::dto::{
 use crate::services::LayoutService;
 
 use super::super::dto::{
-    KeyAssignmentDto, KeyDetailActionDto, KeyDisplayDto, KeyRenderMetadata, LayerDto,
-    LayerRenderMetadata, LayoutDto, LayoutListResponse, LayoutSaveDto, LayoutSummary,
-    RenderMetadataResponse,
+    KeyAssignmentDto, KeyRenderMetadata, LayerDto, LayerRenderMetadata, LayoutDto,
+    LayoutListResponse, LayoutSaveDto, LayoutSummary, RenderMetadataResponse,
 };
 use super::super::error::AppError;
+use super::super::render::KeyRenderContext;
 use super::super::validation::{validate_filename, with_json_ext};
 use super::super::AppState;
 use super::templates::{
     get_template_dir, sanitize_template_filename, SaveTemplateRequest, TemplateInfo,
 };
+use super::{audit, events};
 
 fn parse_ripple_color_mode(name: &str) -> RippleColorMode {
     match name {
@@ -97,6 +96,7 @@ fn convert_dto_to_layout(dto: LayoutSaveDto) -> Layout {
                         category_id: key_dto.category_id,
                         combo_participant: false,
                         description: key_dto.description,
+                        locked: key_dto.locked,
                     }
                 })
                 .collect();
@@ -113,6 +113,8 @@ fn convert_dto_to_layout(dto: LayoutSaveDto) -> Layout {
                 category_id: layer_dto.category_id,
                 keys,
                 layer_colors_enabled: layer_dto.layer_colors_enabled,
+                underglow_color: layer_dto.underglow_color,
+                encoder_map: layer_dto.encoder_map,
             }
         })
         .collect();
@@ -171,6 +173,7 @@ fn convert_dto_to_layout(dto: LayoutSaveDto) -> Layout {
             tapping_toggle: th_dto.tapping_toggle,
             flow_tap_term: th_dto.flow_tap_term,
             chordal_hold: th_dto.chordal_hold,
+            per_key_tapping_terms: th_dto.per_key_tapping_terms,
             preset,
         }
     } else {
@@ -210,6 +213,7 @@ fn convert_dto_to_layout(dto: LayoutSaveDto) -> Layout {
         ComboSettings {
             enabled: combo_dto.enabled,
             combos,
+            keycode_combos: combo_dto.keycode_combos,
         }
     } else {
         ComboSettings::default()
@@ -240,9 +244,19 @@ fn convert_dto_to_layout(dto: LayoutSaveDto) -> Layout {
         idle_effect_settings,
         rgb_overlay_ripple,
         palette_fx,
+        rgb_conditions: dto.rgb_conditions,
+        firmware_features: dto.firmware_features,
         tap_hold_settings,
         combo_settings,
         tap_dances,
+        macros: dto.macros,
+        custom_keycodes: dto.custom_keycodes,
+        key_overrides: dto.key_overrides,
+        alt_repeat_keys: dto.alt_repeat_keys,
+        unicode_map: dto.unicode_map,
+        unicode_input_modes: dto.unicode_input_modes,
+        usage_counting: dto.usage_counting,
+        editing_helpers: dto.editing_helpers,
     }
 }
 
@@ -252,39 +266,22 @@ pub(super) async fn list_layouts(
 ) -> Result<Json<LayoutListResponse>, AppError> {
     let mut layouts = Vec::new();
 
-    let entries = std::fs::read_dir(&state.workspace_root).map_err(|e| {
+    let filenames = state.layouts.list().await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
-            "Failed to read workspace directory",
+            "Failed to list layouts",
             Some(e.to_string()),
         )
     })?;
 
-    for entry in entries {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        let path = entry.path();
-
-        if path
-            .extension()
-            .is_some_and(|ext| ext == "json" || ext == "md")
-        {
-            let filename = match path.file_name() {
-                Some(name) => name.to_string_lossy().to_string(),
-                None => continue,
-            };
-
-            if let Ok(layout) = LayoutService::load(&path) {
-                layouts.push(LayoutSummary {
-                    filename,
-                    name: layout.metadata.name.clone(),
-                    description: layout.metadata.description.clone(),
-                    modified: layout.metadata.modified.to_rfc3339(),
-                });
-            }
+    for filename in filenames {
+        if let Ok(layout) = state.layouts.load(&filename).await {
+            layouts.push(LayoutSummary {
+                filename,
+                name: layout.metadata.name.clone(),
+                description: layout.metadata.description.clone(),
+                modified: layout.metadata.modified.to_rfc3339(),
+            });
         }
     }
 
@@ -300,15 +297,14 @@ pub(super) async fn get_layout(
 ) -> Result<Json<LayoutDto>, AppError> {
     let filename = validate_filename(&filename)?;
     let filename = with_json_ext(filename);
-    let path = state.workspace_root.join(&filename);
 
-    if !path.exists() {
+    if !state.layout_exists(&filename).await? {
         return Err(AppError::not_found(format!(
             "Layout file not found: {filename}"
         )));
     }
 
-    let layout = LayoutService::load(&path).map_err(|e| {
+    let layout = state.layouts.load(&filename).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to load layout",
@@ -316,57 +312,7 @@ pub(super) async fn get_layout(
         )
     })?;
 
-    let qmk_path = state
-        .config
-        .read()
-        .expect("config lock poisoned")
-        .paths
-        .qmk_firmware
-        .clone();
-    let position_to_geometry: HashMap<String, (u8, [u8; 2], u8)> =
-        if let (Some(keyboard), Some(qmk_path)) =
-            (layout.metadata.keyboard.as_ref(), qmk_path.as_ref())
-        {
-            let layout_variant = layout
-                .metadata
-                .layout_variant
-                .clone()
-                .unwrap_or_else(|| "LAYOUT".to_string());
-
-            parser::keyboard_json::parse_keyboard_info_json(qmk_path, keyboard)
-                .ok()
-                .and_then(|keyboard_info| {
-                    parser::keyboard_json::build_keyboard_geometry_with_rgb(
-                        &keyboard_info,
-                        keyboard,
-                        &layout_variant,
-                        None,
-                    )
-                    .ok()
-                })
-                .map(|geometry| {
-                    geometry
-                        .keys
-                        .iter()
-                        .map(|k| {
-                            let row = k.visual_y.round() as u8;
-                            let col = k.visual_x.round() as u8;
-                            let pos_key = format!("{row},{col}");
-                            (
-                                pos_key,
-                                (
-                                    k.layout_index,
-                                    [k.matrix_position.0, k.matrix_position.1],
-                                    k.led_index,
-                                ),
-                            )
-                        })
-                        .collect()
-                })
-                .unwrap_or_default()
-        } else {
-            HashMap::new()
-        };
+    let geometry = state.layout_geometry(&layout);
 
     let layers: Vec<LayerDto> = layout
         .layers
@@ -377,14 +323,16 @@ pub(super) async fn get_layout(
                 .iter()
                 .enumerate()
                 .map(|(idx, key)| {
-                    let pos_key = format!("{},{}", key.position.row, key.position.col);
-                    let (visual_index, matrix_position, led_index) = position_to_geometry
-                        .get(&pos_key)
-                        .copied()
-                        .unwrap_or_else(|| {
-                            let idx_u8 = idx as u8;
-                            (idx_u8, [idx_u8, 0], idx_u8)
-                        });
+                    let (visual_index, matrix_position, led_index) = geometry
+                        .as_ref()
+                        .and_then(|geometry| geometry.key(key.position))
+                        .map_or_else(
+                            || {
+                                let idx_u8 = idx as u8;
+                                (idx_u8, [idx_u8, 0], idx_u8)
+                            },
+                            |info| (info.visual_index, info.matrix_position, info.led_index),
+                        );
 
                     KeyAssignmentDto {
                         keycode: key.keycode.clone(),
@@ -395,6 +343,7 @@ pub(super) async fn get_layout(
                         color_override: key.color_override,
                         category_id: key.category_id.clone(),
                         description: key.description.clone(),
+                        locked: key.locked,
                     }
                 })
                 .collect();
@@ -407,6 +356,8 @@ pub(super) async fn get_layout(
                 category_id: layer.category_id.clone(),
                 keys,
                 layer_colors_enabled: layer.layer_colors_enabled,
+                underglow_color: layer.underglow_color,
+                encoder_map: layer.encoder_map.clone(),
             }
         })
         .collect();
@@ -422,9 +373,19 @@ pub(super) async fn get_layout(
         idle_effect_settings: IdleEffectSettingsDto::from(&layout.idle_effect_settings),
         rgb_overlay_ripple: RgbOverlayRippleSettingsDto::from(&layout.rgb_overlay_ripple),
         palette_fx: PaletteFxSettingsDto::from(&layout.palette_fx),
+        rgb_conditions: layout.rgb_conditions,
+        firmware_features: layout.firmware_features,
         tap_hold_settings: TapHoldSettingsDto::from(&layout.tap_hold_settings),
         tap_dances: layout.tap_dances.iter().map(TapDanceDto::from).collect(),
+        macros: layout.macros,
+        custom_keycodes: layout.custom_keycodes,
+        key_overrides: layout.key_overrides,
+        alt_repeat_keys: layout.alt_repeat_keys,
+        unicode_map: layout.unicode_map,
+        unicode_input_modes: layout.unicode_input_modes,
         combo_settings: ComboSettingsDto::from(&layout.combo_settings),
+        usage_counting: layout.usage_counting,
+        editing_helpers: layout.editing_helpers,
     };
 
     Ok(Json(layout_dto))
@@ -434,13 +395,16 @@ pub(super) async fn get_layout(
 pub(super) async fn save_layout(
     State(state): State<AppState>,
     Path(filename): Path<String>,
+    headers: HeaderMap,
     Json(layout_dto): Json<LayoutSaveDto>,
 ) -> Result<StatusCode, AppError> {
     let filename = validate_filename(&filename)?;
     let filename = with_json_ext(filename);
     let path = state.workspace_root.join(&filename);
 
-    let layout = convert_dto_to_layout(layout_dto);
+    let _edit = state.collab.lock_layout(&filename).await;
+
+    let mut layout = convert_dto_to_layout(layout_dto);
 
     layout.validate().map_err(|e| {
         AppError::with_details(
@@ -450,13 +414,28 @@ pub(super) async fn save_layout(
         )
     })?;
 
-    LayoutService::save(&layout, &path).map_err(|e| {
+    let before = state.layouts.load(&filename).await.ok();
+
+    // Keep a geometry snapshot so the layout renders without a QMK tree
+    if layout.metadata.geometry.is_none() {
+        layout.metadata.geometry = before.as_ref().and_then(|b| b.metadata.geometry.clone());
+    }
+    if let Some(geometry) = state.layout_geometry(&layout) {
+        layout.record_geometry(&geometry.geometry);
+    }
+
+    state.layouts.save(&filename, &layout).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to save layout",
             Some(e.to_string()),
         )
     })?;
+    audit::record_change(&headers, &path, "save", before.as_ref(), &layout);
+    if let Some(before) = &before {
+        state.history.record(&filename, "save", before, &layout);
+    }
+    events::broadcast_changes(&state, &headers, &filename, [LayoutChange::LayoutReplaced]);
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -465,19 +444,22 @@ pub(super) async fn save_layout(
 pub(super) async fn swap_keys(
     State(state): State<AppState>,
     Path(filename): Path<String>,
+    headers: HeaderMap,
     Json(request): Json<crate::web::dto::SwapKeysRequest>,
 ) -> Result<StatusCode, AppError> {
     let filename = validate_filename(&filename)?;
     let filename = with_json_ext(filename);
     let path = state.workspace_root.join(&filename);
 
-    if !path.exists() {
+    let _edit = state.collab.lock_layout(&filename).await;
+
+    if !state.layout_exists(&filename).await? {
         return Err(AppError::not_found(format!(
             "Layout file not found: {filename}"
         )));
     }
 
-    let mut layout = LayoutService::load(&path).map_err(|e| {
+    let mut layout = state.layouts.load(&filename).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to load layout",
@@ -498,6 +480,7 @@ pub(super) async fn swap_keys(
         return Err(AppError::bad_request("Cannot swap a key with itself"));
     }
 
+    let before = layout.clone();
     let layer = &mut layout.layers[request.layer as usize];
 
     let first_idx = layer.keys.iter().position(|k| {
@@ -510,15 +493,34 @@ pub(super) async fn swap_keys(
 
     match (first_idx, second_idx) {
         (Some(idx1), Some(idx2)) => {
-            layer.keys.swap(idx1, idx2);
+            if layer.keys[idx1].locked || layer.keys[idx2].locked {
+                return Err(AppError::bad_request(
+                    "Cannot swap a locked key; unlock it first",
+                ));
+            }
+            layer.swap_keys(layer.keys[idx1].position, layer.keys[idx2].position);
 
-            LayoutService::save(&layout, &path).map_err(|e| {
+            state.layouts.save(&filename, &layout).await.map_err(|e| {
                 AppError::with_details(
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "Failed to save layout after swap",
                     Some(e.to_string()),
                 )
             })?;
+            audit::record_change(&headers, &path, "swap_keys", Some(&before), &layout);
+            state
+                .history
+                .record(&filename, "swap_keys", &before, &layout);
+            let layer = &layout.layers[request.layer as usize];
+            events::broadcast_changes(
+                &state,
+                &headers,
+                &filename,
+                [idx1, idx2].map(|idx| LayoutChange::KeyUpdated {
+                    layer: request.layer as usize,
+                    key: layer.keys[idx].clone(),
+                }),
+            );
 
             Ok(StatusCode::NO_CONTENT)
         }
@@ -533,15 +535,14 @@ pub(super) async fn get_render_metadata(
 ) -> Result<Json<RenderMetadataResponse>, AppError> {
     let filename = validate_filename(&filename)?;
     let filename = with_json_ext(filename);
-    let path = state.workspace_root.join(&filename);
 
-    if !path.exists() {
+    if !state.layout_exists(&filename).await? {
         return Err(AppError::not_found(format!(
             "Layout file not found: {filename}"
         )));
     }
 
-    let layout = LayoutService::load(&path).map_err(|e| {
+    let layout = state.layouts.load(&filename).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to load layout",
@@ -549,110 +550,37 @@ pub(super) async fn get_render_metadata(
         )
     })?;
 
-    let qmk_path = state
-        .config
-        .read()
-        .expect("config lock poisoned")
-        .paths
-        .qmk_firmware
-        .clone();
-    let position_to_visual_index: HashMap<String, u8> = if let (Some(keyboard), Some(qmk_path)) =
-        (layout.metadata.keyboard.as_ref(), qmk_path.as_ref())
-    {
-        let layout_variant = layout
-            .metadata
-            .layout_variant
-            .clone()
-            .unwrap_or_else(|| "LAYOUT".to_string());
-
-        parser::keyboard_json::parse_keyboard_info_json(qmk_path, keyboard)
-            .ok()
-            .and_then(|keyboard_info| {
-                parser::keyboard_json::build_keyboard_geometry_with_rgb(
-                    &keyboard_info,
-                    keyboard,
-                    &layout_variant,
-                    None,
-                )
-                .ok()
-            })
-            .map(|geometry| {
-                geometry
-                    .keys
-                    .iter()
-                    .map(|k| {
-                        let row = k.visual_y.round() as u8;
-                        let col = k.visual_x.round() as u8;
-                        let pos_key = format!("{row},{col}");
-                        (pos_key, k.layout_index)
-                    })
-                    .collect()
-            })
-            .unwrap_or_default()
-    } else {
-        HashMap::new()
-    };
-
-    let tap_dance_map: HashMap<String, &TapDanceAction> = layout
-        .tap_dances
-        .iter()
-        .map(|td| (td.name.clone(), td))
-        .collect();
+    let geometry = state.layout_geometry(&layout);
+    let render_ctx = KeyRenderContext::new(&state.keycode_db, &layout);
 
-    let layer_id_to_number: HashMap<String, u8> = layout
-        .layers
-        .iter()
-        .map(|layer| (layer.id.clone(), layer.number))
-        .collect();
+    let encoder_count = geometry.as_ref().map_or_else(
+        || {
+            // Without geometry, show the encoders the layers bind
+            layout
+                .layers
+                .iter()
+                .flat_map(|layer| layer.encoder_map.iter().map(|(encoder, _)| encoder + 1))
+                .max()
+                .unwrap_or(0)
+        },
+        |geometry| geometry.geometry.encoder_count,
+    );
 
     let layers: Vec<LayerRenderMetadata> = layout
         .layers
         .iter()
-        .map(|layer| {
+        .enumerate()
+        .map(|(layer_index, layer)| {
             let keys: Vec<KeyRenderMetadata> = layer
                 .keys
                 .iter()
                 .enumerate()
                 .map(|(idx, key)| {
-                    let pos_key = format!("{},{}", key.position.row, key.position.col);
-                    let visual_index = position_to_visual_index
-                        .get(&pos_key)
-                        .copied()
-                        .unwrap_or(idx as u8);
-
-                    let td_info = state
-                        .keycode_db
-                        .parse_tap_dance_keycode(&key.keycode)
-                        .and_then(|td_name| tap_dance_map.get(&td_name))
-                        .map(|td| crate::keycode_db::TapDanceDisplayInfo {
-                            single_tap: td.single_tap.clone(),
-                            double_tap: td.double_tap.clone(),
-                            hold: td.hold.clone(),
-                        });
-
-                    let meta = state.keycode_db.get_display_metadata(
-                        &key.keycode,
-                        td_info.as_ref(),
-                        Some(&layer_id_to_number),
-                    );
-
-                    KeyRenderMetadata {
-                        visual_index,
-                        display: KeyDisplayDto {
-                            primary: meta.display.primary,
-                            secondary: meta.display.secondary,
-                            tertiary: meta.display.tertiary,
-                        },
-                        details: meta
-                            .details
-                            .into_iter()
-                            .map(|d| KeyDetailActionDto {
-                                kind: d.kind.into(),
-                                code: d.code,
-                                description: d.description,
-                            })
-                            .collect(),
-                    }
+                    let visual_index = geometry
+                        .as_ref()
+                        .and_then(|geometry| geometry.key(key.position))
+                        .map_or(idx as u8, |info| info.visual_index);
+                    render_ctx.key_metadata(key, visual_index)
                 })
                 .collect();
 
@@ -660,6 +588,8 @@ pub(super) async fn get_render_metadata(
                 number: layer.number,
                 name: layer.name.clone(),
                 keys,
+                encoders: render_ctx.layer_encoders(layer, encoder_count),
+                combos: render_ctx.layer_combos(layer_index, geometry.as_deref()),
             }
         })
         .collect();
@@ -674,7 +604,7 @@ pub(super) async fn save_as_template(
     Json(request): Json<SaveTemplateRequest>,
 ) -> Result<Json<TemplateInfo>, AppError> {
     let filename = validate_filename(&filename)?;
-    let template_dir = get_template_dir()?;
+    let template_dir = get_template_dir(&state)?;
 
     if !template_dir.exists() {
         std::fs::create_dir_all(&template_dir).map_err(|e| {
@@ -687,15 +617,13 @@ pub(super) async fn save_as_template(
     }
 
     let filename = with_json_ext(filename);
-    let source_path = state.workspace_root.join(&filename);
-
-    if !source_path.exists() {
+    if !state.layout_exists(&filename).await? {
         return Err(AppError::not_found(format!(
             "Source layout not found: {filename}"
         )));
     }
 
-    let mut layout = LayoutService::load(&source_path).map_err(|e| {
+    let mut layout = state.layouts.load(&filename).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to load source layout",
@@ -728,6 +656,13 @@ pub(super) async fn save_as_template(
         }
     }
     layout.metadata.tags = request.tags;
+    if let Some(placeholders) = request.placeholders {
+        layout.metadata.placeholders = placeholders;
+        layout
+            .metadata
+            .validate_placeholders()
+            .map_err(|e| AppError::bad_request(e.to_string()))?;
+    }
     layout.metadata.is_template = true;
     layout.metadata.modified = chrono::Utc::now();
 
@@ -758,5 +693,6 @@ pub(super) async fn save_as_template(
         tags: layout.metadata.tags.clone(),
         created: layout.metadata.created.to_rfc3339(),
         layer_count: layout.layers.len(),
+        placeholders: layout.required_placeholders(),
     }))
 }
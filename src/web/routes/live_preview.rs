@@ -0,0 +1,64 @@
+//! Live keymap preview stream: layer changes and key presses reported by the
+//! keyboard over its HID console.
+
+use std::convert::Infallible;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::stream::{self, Stream};
+use tokio::sync::mpsc;
+
+use super::super::AppState;
+use crate::services::hid_listen::{HidEvent, HidListener};
+
+/// How often the forwarding thread checks whether the client went away.
+const CLIENT_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// GET /api/live-preview/events - Stream reports from the connected keyboard.
+///
+/// Sends an `hid` event per [`HidEvent`] (JSON tagged by `type`). The keyboard
+/// only reports when its firmware was built with the console enabled. The
+/// listener stops when the client disconnects or the server shuts down.
+pub(super) async fn live_preview_events(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    std::thread::spawn(move || forward_events(&HidListener::start(), &tx));
+
+    let events = stream::unfold(
+        (rx, Arc::clone(&state.shutdown)),
+        |(mut rx, shutdown)| async move {
+            let event = tokio::select! {
+                () = shutdown.triggered() => return None,
+                received = rx.recv() => received?,
+            };
+            let event = Event::default()
+                .event("hid")
+                .json_data(&event)
+                .unwrap_or_else(|_| Event::default().comment("unserializable event"));
+            Some((Ok(event), (rx, shutdown)))
+        },
+    );
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// Copies listener events to the stream until the client disconnects.
+fn forward_events(listener: &HidListener, tx: &mpsc::UnboundedSender<HidEvent>) {
+    loop {
+        match listener.recv_timeout(CLIENT_CHECK_INTERVAL) {
+            Ok(event) => {
+                if tx.send(event).is_err() {
+                    return;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) if !tx.is_closed() => {}
+            Err(_) => return,
+        }
+    }
+}
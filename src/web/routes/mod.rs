@@ -3,22 +3,41 @@
 //! Each submodule contains the handlers for a group of related endpoints.
 //! This module exposes a single `router()` function that wires all handlers.
 
+pub mod audit;
+pub mod backups;
 pub mod build;
+pub mod categories;
 pub mod config;
+pub mod events;
 pub mod export;
+pub mod fragments;
 pub mod generate;
 pub mod geometry;
+pub mod git;
 pub mod health;
+pub mod history;
 pub mod inspect;
 pub mod keycodes;
+pub mod keys;
+pub mod layer_graph;
+pub mod layers;
 pub mod layouts;
+#[cfg(feature = "usb")]
+pub mod live_preview;
+pub mod resolved;
+pub mod search;
+pub mod shutdown;
+pub mod stats;
+pub mod tap_dances;
 pub mod templates;
 pub mod validate;
+pub mod ws;
 
-use axum::{routing::get, Router};
+use axum::{middleware, routing::get, Router};
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 
+use super::read_only;
 use super::static_files::static_handler;
 use super::AppState;
 
@@ -30,7 +49,7 @@ pub fn router(state: AppState) -> Router {
         .allow_methods(Any)
         .allow_headers(Any);
 
-    Router::new()
+    let api = Router::new()
         // Health check
         .route("/health", get(health::health_check))
         // Effects
@@ -42,9 +61,78 @@ pub fn router(state: AppState) -> Router {
             "/api/layouts/{filename}/swap-keys",
             axum::routing::post(layouts::swap_keys),
         )
+        .route(
+            "/api/layouts/{filename}/layers/{layer}/keys/{row}/{col}",
+            axum::routing::post(keys::assign_key),
+        )
+        .route(
+            "/api/layouts/{filename}/layers/{layer}/fragment",
+            get(fragments::export_fragment),
+        )
+        .route(
+            "/api/layouts/{filename}/layers/{layer}/apply-fragment",
+            axum::routing::post(fragments::apply_fragment),
+        )
+        .route(
+            "/api/layouts/{filename}/layers/{layer}/duplicate",
+            axum::routing::post(layers::duplicate_layer),
+        )
+        .route(
+            "/api/layouts/{filename}/tap-dances",
+            get(tap_dances::list_tap_dances).post(tap_dances::create_tap_dance),
+        )
+        .route(
+            "/api/layouts/{filename}/tap-dances/{name}",
+            axum::routing::put(tap_dances::update_tap_dance)
+                .delete(tap_dances::delete_tap_dance),
+        )
+        .route(
+            "/api/layouts/{filename}/categories",
+            get(categories::list_categories).post(categories::create_category),
+        )
+        .route(
+            "/api/layouts/{filename}/categories/{id}",
+            axum::routing::put(categories::update_category)
+                .delete(categories::delete_category),
+        )
         .route("/api/layouts/{filename}/validate", get(validate::validate_layout))
         .route("/api/layouts/{filename}/inspect", get(inspect::inspect_layout))
+        .route("/api/layouts/{filename}/search", get(search::search_layout_keys))
+        .route(
+            "/api/layouts/{filename}/resolved/{layer}",
+            get(resolved::get_resolved_layer),
+        )
+        .route(
+            "/api/layouts/{filename}/layer-graph",
+            get(layer_graph::get_layer_graph),
+        )
+        .route("/api/layouts/{filename}/stats", get(stats::get_layout_stats))
+        .route("/api/layouts/{filename}/audit", get(audit::get_audit_log))
+        .route("/api/layouts/{filename}/backups", get(backups::list_backups))
+        .route(
+            "/api/layouts/{filename}/backups/{id}/restore",
+            axum::routing::post(backups::restore_backup),
+        )
+        .route("/api/layouts/{filename}/events", get(events::layout_events))
+        .route("/api/layouts/{filename}/history", get(git::get_git_history))
+        .route(
+            "/api/layouts/{filename}/history/undo",
+            axum::routing::post(history::undo),
+        )
+        .route(
+            "/api/layouts/{filename}/history/redo",
+            axum::routing::post(history::redo),
+        )
         .route("/api/layouts/{filename}/export", get(export::export_layout))
+        .route(
+            "/api/layouts/{filename}/export/keymap-drawer",
+            get(export::export_keymap_drawer),
+        )
+        .route(
+            "/api/layouts/{filename}/export/qmk-json",
+            get(export::export_qmk_json),
+        )
+        .route("/api/layouts/{filename}/bundle", get(export::export_bundle))
         .route(
             "/api/layouts/{filename}/render-metadata",
             get(layouts::get_render_metadata),
@@ -53,6 +141,10 @@ pub fn router(state: AppState) -> Router {
             "/api/layouts/{filename}/generate",
             axum::routing::post(generate::generate_firmware),
         )
+        .route(
+            "/api/layouts/{filename}/check",
+            axum::routing::post(build::check_layout),
+        )
         .route(
             "/api/layouts/{filename}/save-as-template",
             axum::routing::post(layouts::save_as_template),
@@ -64,13 +156,35 @@ pub fn router(state: AppState) -> Router {
             "/api/templates/{filename}/apply",
             axum::routing::post(templates::apply_template),
         )
+        .route(
+            "/api/templates/{filename}/fit",
+            axum::routing::post(templates::fit_template),
+        )
         // Keycode endpoints
         .route("/api/keycodes", get(keycodes::list_keycodes))
         .route("/api/keycodes/categories", get(keycodes::list_categories))
+        .route("/api/keycodes/display", get(keycodes::get_keycode_display))
+        .route("/api/keycodes/dump", get(keycodes::dump_keycode_db))
+        .route(
+            "/api/keycodes/favorites",
+            get(keycodes::get_favorites)
+                .post(keycodes::add_favorite)
+                .put(keycodes::set_favorites),
+        )
+        .route(
+            "/api/keycodes/favorites/{keycode}",
+            axum::routing::delete(keycodes::remove_favorite),
+        )
+        // Color palette
+        .route("/api/palette", get(categories::get_palette))
         // Config endpoints
         .route("/api/config", get(config::get_config).put(config::update_config))
         // Preflight endpoint for onboarding
         .route("/api/preflight", get(config::get_preflight))
+        .route(
+            "/api/preflight/setup",
+            axum::routing::post(config::run_preflight_setup),
+        )
         // Geometry endpoint
         .route(
             "/api/keyboards/{keyboard}/geometry/{layout}",
@@ -78,11 +192,24 @@ pub fn router(state: AppState) -> Router {
         )
         // Keyboard & Setup Wizard endpoints
         .route("/api/keyboards", get(geometry::list_keyboards))
+        .route(
+            "/api/keyboards/refresh",
+            axum::routing::post(geometry::refresh_keyboards),
+        )
         .route(
             "/api/keyboards/{keyboard}/layouts",
             get(geometry::list_keyboard_layouts),
         )
+        .route("/api/keyboards/{keyboard}/info", get(geometry::get_keyboard_info))
         .route("/api/layouts", axum::routing::post(geometry::create_layout))
+        .route(
+            "/api/layouts/import",
+            axum::routing::post(geometry::import_layout),
+        )
+        .route(
+            "/api/layouts/import-bundle",
+            axum::routing::post(geometry::import_bundle),
+        )
         .route(
             "/api/layouts/{filename}/switch-variant",
             axum::routing::post(geometry::switch_layout_variant),
@@ -117,6 +244,31 @@ pub fn router(state: AppState) -> Router {
             get(generate::download_generate_zip),
         )
         .route("/api/generate/health", get(generate::get_generate_health))
+        // Live job progress
+        .route("/api/ws", get(ws::job_socket))
+        // Server lifecycle
+        .route(
+            "/api/shutdown",
+            axum::routing::post(shutdown::request_shutdown),
+        );
+
+    // Endpoints that talk to the keyboard over USB
+    #[cfg(feature = "usb")]
+    let api = api
+        .route(
+            "/api/build/jobs/{job_id}/flash",
+            axum::routing::post(build::flash_build_job),
+        )
+        // Live keymap preview
+        .route(
+            "/api/live-preview/events",
+            get(live_preview::live_preview_events),
+        );
+
+    api.layer(middleware::from_fn_with_state(
+            state.clone(),
+            read_only::reject_mutations,
+        ))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(state)
@@ -0,0 +1,61 @@
+//! Resolved layer endpoint.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+
+use crate::services::resolved_keys::resolve_layer;
+
+use super::super::dto::{ResolvedKeyDto, ResolvedLayerResponse};
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+
+/// GET /api/layouts/{filename}/resolved/{layer} - Keys of a layer with
+/// transparent keys resolved to the keycodes of the layers below.
+pub(super) async fn get_resolved_layer(
+    State(state): State<AppState>,
+    Path((filename, layer)): Path<(String, usize)>,
+) -> Result<Json<ResolvedLayerResponse>, AppError> {
+    let filename = validate_filename(&filename)?;
+    let filename = with_json_ext(filename);
+
+    if !state.layout_exists(&filename).await? {
+        return Err(AppError::not_found(format!(
+            "Layout file not found: {filename}"
+        )));
+    }
+
+    let layout = state.layouts.load(&filename).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to load layout",
+            Some(e.to_string()),
+        )
+    })?;
+
+    let resolved = resolve_layer(&layout.layers, layer)
+        .ok_or_else(|| AppError::not_found(format!("Layer {layer} not found")))?;
+    let source = &layout.layers[layer];
+    let keys = source
+        .keys
+        .iter()
+        .zip(resolved)
+        .map(|(key, resolved)| ResolvedKeyDto {
+            row: key.position.row,
+            col: key.position.col,
+            keycode: key.keycode.clone(),
+            effective_keycode: resolved.keycode,
+            from_layer: resolved.from_layer,
+        })
+        .collect();
+
+    Ok(Json(ResolvedLayerResponse {
+        filename,
+        layer,
+        name: source.name.clone(),
+        keys,
+    }))
+}
@@ -0,0 +1,58 @@
+//! Key search endpoint.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+
+use crate::services::key_search::search_keys;
+
+use super::super::dto::{KeySearchMatchDto, KeySearchQuery, KeySearchResponse};
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+
+/// GET /api/layouts/{filename}/search?q= - Find keys on all layers by
+/// keycode, label or description.
+pub(super) async fn search_layout_keys(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+    Query(query): Query<KeySearchQuery>,
+) -> Result<Json<KeySearchResponse>, AppError> {
+    let filename = validate_filename(&filename)?;
+    let filename = with_json_ext(filename);
+
+    if !state.layout_exists(&filename).await? {
+        return Err(AppError::not_found(format!(
+            "Layout file not found: {filename}"
+        )));
+    }
+
+    let layout = state.layouts.load(&filename).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to load layout",
+            Some(e.to_string()),
+        )
+    })?;
+
+    let matches = search_keys(&layout, &query.q)
+        .into_iter()
+        .map(|m| KeySearchMatchDto {
+            layer: m.layer,
+            layer_name: m.layer_name,
+            row: m.position.row,
+            col: m.position.col,
+            keycode: m.keycode,
+            label: m.label,
+            description: m.description,
+        })
+        .collect();
+
+    Ok(Json(KeySearchResponse {
+        filename,
+        query: query.q,
+        matches,
+    }))
+}
@@ -0,0 +1,50 @@
+//! Server shutdown endpoint (for desktop-app wrappers).
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+
+use super::super::dto::{ShutdownQuery, ShutdownResponse};
+use super::super::error::AppError;
+use super::super::shutdown::active_job_count;
+use super::super::AppState;
+
+/// POST /api/shutdown - Stop the server gracefully.
+///
+/// Only available when the server was started with `--allow-shutdown`.
+/// Refuses with 409 Conflict while build or generate jobs are active, unless
+/// `?force=true` is passed, in which case those jobs are cancelled. The
+/// response is sent before the server stops.
+pub(super) async fn request_shutdown(
+    State(state): State<AppState>,
+    Query(query): Query<ShutdownQuery>,
+) -> Result<(StatusCode, Json<ShutdownResponse>), AppError> {
+    if !state.allow_shutdown {
+        return Err(AppError::with_details(
+            StatusCode::FORBIDDEN,
+            "Shutdown endpoint is disabled",
+            Some("Start the server with `lazyqmk web --allow-shutdown` to enable it".to_string()),
+        ));
+    }
+
+    let active_jobs = active_job_count(&state);
+    if active_jobs > 0 && !query.force {
+        return Err(AppError::with_details(
+            StatusCode::CONFLICT,
+            format!("{active_jobs} build/generate job(s) still running"),
+            Some("Wait for them to finish, or retry with ?force=true to cancel them".to_string()),
+        ));
+    }
+
+    state.shutdown.trigger();
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ShutdownResponse {
+            message: "Server is shutting down".to_string(),
+            cancelled_jobs: active_jobs,
+        }),
+    ))
+}
@@ -0,0 +1,86 @@
+//! Layout statistics endpoint.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+
+use crate::services::analysis::{analyze_layout, usage_heatmap};
+use crate::services::thumb_optimizer::CorpusStats;
+use crate::services::usage::UsageLog;
+
+use super::super::dto::{LayoutStatsQuery, LayoutStatsResponse};
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+
+/// GET /api/layouts/{filename}/stats - Get per-layer statistics.
+///
+/// With `?corpus=<file>` (a file in the workspace) the response also has a
+/// typing heatmap and the base layer effort score for that corpus. Hand and
+/// home row figures and the effort score need the keyboard geometry and are
+/// left out when it isn't available. Key presses recorded next to the
+/// layout file by `lazyqmk collect-usage` come back as a `usage` heatmap.
+pub(super) async fn get_layout_stats(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+    Query(query): Query<LayoutStatsQuery>,
+) -> Result<Json<LayoutStatsResponse>, AppError> {
+    let filename = validate_filename(&filename)?;
+    let filename = with_json_ext(filename);
+
+    if !state.layout_exists(&filename).await? {
+        return Err(AppError::not_found(format!(
+            "Layout file not found: {filename}"
+        )));
+    }
+
+    let corpus = match query.corpus.as_deref() {
+        Some(name) => {
+            let path = state.workspace_root.join(validate_filename(name)?);
+            if !path.is_file() {
+                return Err(AppError::not_found(format!(
+                    "Corpus file not found: {name}"
+                )));
+            }
+            let stats = CorpusStats::from_file(&path).map_err(|e| {
+                AppError::with_details(
+                    StatusCode::BAD_REQUEST,
+                    "Invalid corpus file",
+                    Some(format!("{e:#}")),
+                )
+            })?;
+            Some(stats)
+        }
+        None => None,
+    };
+
+    let layout = state.layouts.load(&filename).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to load layout",
+            Some(e.to_string()),
+        )
+    })?;
+
+    let geometry = state.layout_geometry(&layout);
+    let geometry = geometry.as_ref().map(|cached| &cached.geometry);
+    let analysis = analyze_layout(&layout, geometry, corpus.as_ref());
+
+    let usage_path = UsageLog::path_for(&state.workspace_root.join(&filename));
+    let usage = UsageLog::load(&usage_path).map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to load usage log",
+            Some(format!("{e:#}")),
+        )
+    })?;
+    let usage = (!usage.is_empty()).then(|| usage_heatmap(&layout, geometry, &usage));
+
+    Ok(Json(LayoutStatsResponse {
+        filename,
+        analysis,
+        usage,
+    }))
+}
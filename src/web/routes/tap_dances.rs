@@ -0,0 +1,269 @@
+//! Tap dance CRUD endpoints.
+//!
+//! Lets clients manage a layout's tap dances without sending the whole
+//! layout back. Renames and deletes rewrite the `TD()` keycodes of the keys
+//! that use the tap dance so the layout stays valid.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+
+use crate::models::{Layout, TapDanceAction};
+
+use super::super::collab::LayoutChange;
+use super::super::dto::{
+    TapDanceDto, TapDanceListResponse, TapDanceMutationResponse, TapDanceUsageDto,
+};
+use super::super::error::AppError;
+use super::super::validation::{validate_filename, with_json_ext};
+use super::super::AppState;
+use super::fragments::load_workspace_layout;
+use super::keys::validate_keycode;
+use super::{audit, events};
+
+/// Keycode left on keys whose tap dance is deleted.
+const DELETED_TAP_DANCE_KEYCODE: &str = "KC_TRNS";
+
+/// Saves `layout`, records the audit entry and undo step, and notifies other
+/// editors.
+pub(super) async fn save_layout(
+    state: &AppState,
+    headers: &HeaderMap,
+    path: &std::path::Path,
+    filename: &str,
+    operation: &str,
+    before: &Layout,
+    layout: &Layout,
+) -> Result<(), AppError> {
+    state.layouts.save(filename, layout).await.map_err(|e| {
+        AppError::with_details(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to save layout",
+            Some(e.to_string()),
+        )
+    })?;
+    audit::record_change(headers, path, operation, Some(before), layout);
+    state.history.record(filename, operation, before, layout);
+    events::broadcast_changes(state, headers, filename, [LayoutChange::LayoutReplaced]);
+    Ok(())
+}
+
+/// Builds a tap dance from the request, checking its name and keycodes.
+///
+/// Each action keycode must be valid for the layout; tap dances cannot
+/// reference other tap dances.
+fn build_tap_dance(
+    state: &AppState,
+    layout: &Layout,
+    request: TapDanceDto,
+) -> Result<TapDanceAction, AppError> {
+    let trim = |keycode: String| keycode.trim().to_string();
+    let tap_dance = TapDanceAction {
+        name: request.name.trim().to_string(),
+        single_tap: trim(request.single_tap),
+        double_tap: request.double_tap.map(trim),
+        hold: request.hold.map(trim),
+    };
+    tap_dance
+        .validate()
+        .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    let keycodes = std::iter::once(&tap_dance.single_tap)
+        .chain(tap_dance.double_tap.as_ref())
+        .chain(tap_dance.hold.as_ref());
+    for keycode in keycodes {
+        if state.keycode_db.parse_tap_dance_keycode(keycode).is_some() {
+            return Err(AppError::bad_request(format!(
+                "Tap dance '{}' cannot reference another tap dance: {keycode}",
+                tap_dance.name
+            )));
+        }
+        validate_keycode(layout, &state.keycode_db, keycode)?;
+    }
+
+    Ok(tap_dance)
+}
+
+/// Rewrites every key assigned `TD(from)` to `replacement`, returning how
+/// many keys changed.
+fn replace_tap_dance_keycodes(layout: &mut Layout, from: &str, replacement: &str) -> usize {
+    let keycode = format!("TD({from})");
+    let mut updated = 0;
+    for key in layout
+        .layers
+        .iter_mut()
+        .flat_map(|layer| layer.keys.iter_mut())
+    {
+        if key.keycode == keycode {
+            key.keycode = replacement.to_string();
+            updated += 1;
+        }
+    }
+    updated
+}
+
+/// Counts the keys assigned `TD(name)` across all layers.
+fn usage_count(layout: &Layout, name: &str) -> usize {
+    let keycode = format!("TD({name})");
+    layout
+        .layers
+        .iter()
+        .flat_map(|layer| &layer.keys)
+        .filter(|key| key.keycode == keycode)
+        .count()
+}
+
+/// GET /api/layouts/{filename}/tap-dances - List tap dances with their usage.
+pub(super) async fn list_tap_dances(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Json<TapDanceListResponse>, AppError> {
+    let (_, _, layout) = load_workspace_layout(&state, &filename).await?;
+
+    let tap_dances = layout
+        .tap_dances
+        .iter()
+        .map(|td| TapDanceUsageDto {
+            tap_dance: TapDanceDto::from(td),
+            usage_count: usage_count(&layout, &td.name),
+        })
+        .collect();
+
+    Ok(Json(TapDanceListResponse { tap_dances }))
+}
+
+/// POST /api/layouts/{filename}/tap-dances - Create a tap dance.
+pub(super) async fn create_tap_dance(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+    headers: HeaderMap,
+    Json(request): Json<TapDanceDto>,
+) -> Result<Json<TapDanceMutationResponse>, AppError> {
+    let filename = with_json_ext(validate_filename(&filename)?);
+    let _edit = state.collab.lock_layout(&filename).await;
+    let (path, filename, mut layout) = load_workspace_layout(&state, &filename).await?;
+    let before = layout.clone();
+
+    let tap_dance = build_tap_dance(&state, &layout, request)?;
+    if layout.get_tap_dance(&tap_dance.name).is_some() {
+        return Err(AppError::with_details(
+            StatusCode::CONFLICT,
+            "Tap dance already exists",
+            Some(format!("Tap dance already exists: {}", tap_dance.name)),
+        ));
+    }
+    let dto = TapDanceDto::from(&tap_dance);
+    layout
+        .add_tap_dance(tap_dance)
+        .map_err(|e| AppError::bad_request(e.to_string()))?;
+
+    save_layout(
+        &state,
+        &headers,
+        &path,
+        &filename,
+        "create_tap_dance",
+        &before,
+        &layout,
+    )
+    .await?;
+
+    Ok(Json(TapDanceMutationResponse {
+        tap_dance: Some(dto),
+        updated_keys: 0,
+    }))
+}
+
+/// PUT /api/layouts/{filename}/tap-dances/{name} - Update or rename a tap dance.
+///
+/// When the body carries a different name, keys assigned `TD(name)` are
+/// rewritten to the new name.
+pub(super) async fn update_tap_dance(
+    State(state): State<AppState>,
+    Path((filename, name)): Path<(String, String)>,
+    headers: HeaderMap,
+    Json(request): Json<TapDanceDto>,
+) -> Result<Json<TapDanceMutationResponse>, AppError> {
+    let filename = with_json_ext(validate_filename(&filename)?);
+    let _edit = state.collab.lock_layout(&filename).await;
+    let (path, filename, mut layout) = load_workspace_layout(&state, &filename).await?;
+    let before = layout.clone();
+
+    let index = layout
+        .tap_dances
+        .iter()
+        .position(|td| td.name == name)
+        .ok_or_else(|| AppError::not_found(format!("Tap dance not found: {name}")))?;
+
+    let tap_dance = build_tap_dance(&state, &layout, request)?;
+    let renamed = tap_dance.name != name;
+    if renamed && layout.get_tap_dance(&tap_dance.name).is_some() {
+        return Err(AppError::with_details(
+            StatusCode::CONFLICT,
+            "Tap dance already exists",
+            Some(format!("Tap dance already exists: {}", tap_dance.name)),
+        ));
+    }
+
+    let updated_keys = if renamed {
+        replace_tap_dance_keycodes(&mut layout, &name, &format!("TD({})", tap_dance.name))
+    } else {
+        0
+    };
+    let dto = TapDanceDto::from(&tap_dance);
+    layout.tap_dances[index] = tap_dance;
+    layout.metadata.touch();
+
+    save_layout(
+        &state,
+        &headers,
+        &path,
+        &filename,
+        "update_tap_dance",
+        &before,
+        &layout,
+    )
+    .await?;
+
+    Ok(Json(TapDanceMutationResponse {
+        tap_dance: Some(dto),
+        updated_keys,
+    }))
+}
+
+/// DELETE /api/layouts/{filename}/tap-dances/{name} - Delete a tap dance.
+///
+/// Keys assigned `TD(name)` are set to `KC_TRNS`.
+pub(super) async fn delete_tap_dance(
+    State(state): State<AppState>,
+    Path((filename, name)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Json<TapDanceMutationResponse>, AppError> {
+    let filename = with_json_ext(validate_filename(&filename)?);
+    let _edit = state.collab.lock_layout(&filename).await;
+    let (path, filename, mut layout) = load_workspace_layout(&state, &filename).await?;
+    let before = layout.clone();
+
+    if layout.remove_tap_dance(&name).is_none() {
+        return Err(AppError::not_found(format!("Tap dance not found: {name}")));
+    }
+    let updated_keys = replace_tap_dance_keycodes(&mut layout, &name, DELETED_TAP_DANCE_KEYCODE);
+
+    save_layout(
+        &state,
+        &headers,
+        &path,
+        &filename,
+        "delete_tap_dance",
+        &before,
+        &layout,
+    )
+    .await?;
+
+    Ok(Json(TapDanceMutationResponse {
+        tap_dance: None,
+        updated_keys,
+    }))
+}
@@ -1,21 +1,24 @@
 //! Template endpoints.
 
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     Json,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::config::Config;
-use crate::models::Layout;
+use crate::models::{Layout, Position, PositionRemap, TemplatePlaceholder};
+use crate::services::template_fit::TemplateFit;
 use crate::services::LayoutService;
 
 use super::super::error::AppError;
 use super::super::validation::validate_filename;
 use super::super::AppState;
+use super::audit;
 
 /// Template info for API response.
 #[derive(Debug, Serialize)]
@@ -27,6 +30,9 @@ pub(super) struct TemplateInfo {
     pub tags: Vec<String>,
     pub created: String,
     pub layer_count: usize,
+    /// Values to ask for when applying the template
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub placeholders: Vec<TemplatePlaceholder>,
 }
 
 /// Template list response.
@@ -41,16 +47,65 @@ pub(super) struct SaveTemplateRequest {
     pub name: String,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Placeholders to declare (`${NAME}` used in the layout), replacing
+    /// the layout's own
+    #[serde(default)]
+    pub placeholders: Option<Vec<TemplatePlaceholder>>,
 }
 
 /// Apply template request.
 #[derive(Debug, Deserialize)]
 pub(super) struct ApplyTemplateRequest {
     pub target_filename: String,
+    /// Keyboard to place the template on (defaults to the template's own)
+    #[serde(default)]
+    pub keyboard: Option<String>,
+    /// Layout variant of `keyboard`
+    #[serde(default)]
+    pub layout_variant: Option<String>,
+    /// Where template keys without a position on `keyboard` go, as returned
+    /// (and possibly edited) from the fit endpoint
+    #[serde(default)]
+    pub remap: Option<Vec<PositionRemap>>,
+    /// Value of each template placeholder (`${NAME}`); declared defaults
+    /// fill the rest
+    #[serde(default)]
+    pub placeholders: HashMap<String, String>,
+}
+
+/// Template fit request: the keyboard the template should be placed on.
+#[derive(Debug, Deserialize)]
+pub(super) struct TemplateFitRequest {
+    pub keyboard: String,
+    pub layout_variant: String,
+}
+
+/// A template key with no position on the destination keyboard.
+#[derive(Debug, Serialize)]
+pub(super) struct UnplacedKeyInfo {
+    pub position: Position,
+    /// Base layer keycode, to help the user decide where the key goes
+    pub keycode: String,
 }
 
-/// Get the platform-specific template directory.
-pub(super) fn get_template_dir() -> Result<PathBuf, AppError> {
+/// Template fit response: what doesn't fit and the proposed placement.
+#[derive(Debug, Serialize)]
+pub(super) struct TemplateFitResponse {
+    pub keyboard: String,
+    pub layout_variant: String,
+    pub unplaced: Vec<UnplacedKeyInfo>,
+    /// Destination positions the template leaves empty
+    pub free: Vec<Position>,
+    /// Proposed placement of the unplaced keys (`to` omitted drops the key)
+    pub remap: Vec<PositionRemap>,
+}
+
+/// Get the platform-specific template directory, or the scratch template
+/// directory of a sandbox server.
+pub(super) fn get_template_dir(state: &AppState) -> Result<PathBuf, AppError> {
+    if state.is_sandboxed() {
+        return Ok(state.workspace_root.join(".lazyqmk").join("templates"));
+    }
     Config::config_dir()
         .map(|dir| dir.join("templates"))
         .map_err(|e| {
@@ -78,8 +133,10 @@ pub(super) fn sanitize_template_filename(name: &str) -> String {
 }
 
 /// GET /api/templates - List all available templates.
-pub(super) async fn list_templates() -> Result<Json<TemplateListResponse>, AppError> {
-    let template_dir = get_template_dir()?;
+pub(super) async fn list_templates(
+    State(state): State<AppState>,
+) -> Result<Json<TemplateListResponse>, AppError> {
+    let template_dir = get_template_dir(&state)?;
 
     if !template_dir.exists() {
         std::fs::create_dir_all(&template_dir).map_err(|e| {
@@ -123,6 +180,7 @@ pub(super) async fn list_templates() -> Result<Json<TemplateListResponse>, AppEr
                         tags: layout.metadata.tags.clone(),
                         created: layout.metadata.created.to_rfc3339(),
                         layer_count: layout.layers.len(),
+                        placeholders: layout.required_placeholders(),
                     });
                 }
             }
@@ -134,10 +192,10 @@ pub(super) async fn list_templates() -> Result<Json<TemplateListResponse>, AppEr
     Ok(Json(TemplateListResponse { templates }))
 }
 
-/// GET /api/templates/{filename} - Get a specific template.
-pub(super) async fn get_template(Path(filename): Path<String>) -> Result<Json<Layout>, AppError> {
-    let filename = validate_filename(&filename)?;
-    let template_dir = get_template_dir()?;
+/// Loads the template `filename` (`.json` is appended when missing).
+fn load_template(state: &AppState, filename: &str) -> Result<Layout, AppError> {
+    let filename = validate_filename(filename)?;
+    let template_dir = get_template_dir(state)?;
 
     let filename = if std::path::Path::new(filename)
         .extension()
@@ -168,48 +226,132 @@ pub(super) async fn get_template(Path(filename): Path<String>) -> Result<Json<La
         return Err(AppError::bad_request("File is not a template"));
     }
 
-    Ok(Json(layout))
+    Ok(layout)
+}
+
+/// GET /api/templates/{filename} - Get a specific template.
+pub(super) async fn get_template(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Json<Layout>, AppError> {
+    load_template(&state, &filename).map(Json)
+}
+
+/// Returns the visual key positions of `keyboard`'s `layout_variant`.
+fn destination_positions(
+    state: &AppState,
+    keyboard: &str,
+    layout_variant: &str,
+) -> Result<HashSet<Position>, AppError> {
+    let paths = state.config.read().unwrap().paths.clone();
+    let qmk_path = paths
+        .qmk_firmware
+        .ok_or_else(|| AppError::bad_request("QMK firmware path not configured"))?;
+    let cached = state
+        .geometry_cache
+        .get(&qmk_path, &paths.keyboard_roots, keyboard, layout_variant)
+        .ok_or_else(|| {
+            AppError::not_found(format!(
+                "Layout variant '{layout_variant}' not found in keyboard '{keyboard}'"
+            ))
+        })?;
+    Ok(cached.keys.keys().copied().collect())
+}
+
+/// POST /api/templates/{filename}/fit - Preview placing a template on another keyboard.
+///
+/// Lists the template keys with no position on the keyboard, the positions
+/// the template leaves empty, and a proposed placement. Pass the placement,
+/// edited as needed, as `remap` to the apply endpoint.
+pub(super) async fn fit_template(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+    Json(request): Json<TemplateFitRequest>,
+) -> Result<Json<TemplateFitResponse>, AppError> {
+    let template = load_template(&state, &filename)?;
+    let destination = destination_positions(&state, &request.keyboard, &request.layout_variant)?;
+    let fit = TemplateFit::plan(&template, &destination);
+
+    let base_keycode = |position: Position| {
+        template
+            .layers
+            .first()
+            .and_then(|layer| layer.keys.iter().find(|key| key.position == position))
+            .map_or_else(|| "KC_TRNS".to_string(), |key| key.keycode.clone())
+    };
+    let unplaced = fit
+        .unplaced
+        .iter()
+        .map(|&position| UnplacedKeyInfo {
+            position,
+            keycode: base_keycode(position),
+        })
+        .collect();
+
+    Ok(Json(TemplateFitResponse {
+        keyboard: request.keyboard,
+        layout_variant: request.layout_variant,
+        unplaced,
+        free: fit.free,
+        remap: fit.remap,
+    }))
 }
 
 /// POST /api/templates/{filename}/apply - Apply template to create new layout.
+///
+/// Every `${NAME}` in the template is replaced with `placeholders[NAME]` or
+/// the placeholder's default before the template is placed.
 pub(super) async fn apply_template(
     State(state): State<AppState>,
     Path(filename): Path<String>,
+    headers: HeaderMap,
     Json(request): Json<ApplyTemplateRequest>,
 ) -> Result<Json<Layout>, AppError> {
-    let filename = validate_filename(&filename)?;
     let target_filename = validate_filename(&request.target_filename)?;
-    let template_dir = get_template_dir()?;
+    let template = load_template(&state, &filename)?
+        .fill_placeholders(&request.placeholders)
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::BAD_REQUEST,
+                "Template placeholders could not be filled in",
+                Some(format!("{e:#}")),
+            )
+        })?;
 
-    let filename = if std::path::Path::new(filename)
-        .extension()
-        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
-    {
-        filename.to_string()
-    } else {
-        format!("{filename}.json")
+    let mut layout = match (&request.keyboard, &request.layout_variant) {
+        (Some(keyboard), Some(layout_variant)) => {
+            let destination = destination_positions(&state, keyboard, layout_variant)?;
+            let mut fit = TemplateFit::plan(&template, &destination);
+            match &request.remap {
+                Some(remap) => fit
+                    .assign_all(remap)
+                    .map_err(|e| AppError::bad_request(e.to_string()))?,
+                None if fit.needs_mapping() => {
+                    return Err(AppError::with_details(
+                        StatusCode::CONFLICT,
+                        "Template does not fit the keyboard",
+                        Some(format!(
+                            "{} template key(s) have no position on '{keyboard}'. \
+                             Choose where they go with POST /api/templates/{filename}/fit \
+                             and pass the result as `remap`",
+                            fit.unplaced.len()
+                        )),
+                    ));
+                }
+                None => {}
+            }
+            fit.apply(&template, keyboard, layout_variant, &destination)
+                .map_err(|e| AppError::bad_request(e.to_string()))?
+                .0
+        }
+        (None, None) => template,
+        _ => {
+            return Err(AppError::bad_request(
+                "keyboard and layout_variant must be given together",
+            ))
+        }
     };
 
-    let template_path = template_dir.join(&filename);
-
-    if !template_path.exists() {
-        return Err(AppError::not_found(format!(
-            "Template not found: {filename}"
-        )));
-    }
-
-    let mut layout = LayoutService::load(&template_path).map_err(|e| {
-        AppError::with_details(
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Failed to load template",
-            Some(e.to_string()),
-        )
-    })?;
-
-    if !layout.metadata.is_template {
-        return Err(AppError::bad_request("File is not a template"));
-    }
-
     layout.metadata.is_template = false;
     layout.metadata.created = chrono::Utc::now();
     layout.metadata.modified = chrono::Utc::now();
@@ -223,9 +365,7 @@ pub(super) async fn apply_template(
         format!("{target_filename}.json")
     };
 
-    let target_path = state.workspace_root.join(&target_filename);
-
-    if target_path.exists() {
+    if state.layout_exists(&target_filename).await? {
         return Err(AppError::with_details(
             StatusCode::CONFLICT,
             "Layout file already exists",
@@ -233,13 +373,19 @@ pub(super) async fn apply_template(
         ));
     }
 
-    LayoutService::save(&layout, &target_path).map_err(|e| {
-        AppError::with_details(
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Failed to save layout",
-            Some(e.to_string()),
-        )
-    })?;
+    state
+        .layouts
+        .save(&target_filename, &layout)
+        .await
+        .map_err(|e| {
+            AppError::with_details(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to save layout",
+                Some(e.to_string()),
+            )
+        })?;
+    let target_path = state.workspace_root.join(&target_filename);
+    audit::record_change(&headers, &target_path, "apply_template", None, &layout);
 
     Ok(Json(layout))
 }
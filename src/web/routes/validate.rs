@@ -6,29 +6,33 @@ use axum::{
     Json,
 };
 
-use crate::services::LayoutService;
+use crate::services::lints::lint_layout;
 
-use super::super::dto::ValidationResponse;
+use super::super::dto::{LintDto, ValidationResponse};
 use super::super::error::AppError;
 use super::super::validation::{validate_filename, with_json_ext};
 use super::super::AppState;
 
 /// GET /api/layouts/{filename}/validate - Validate a layout.
+///
+/// Checks the layout structure, then runs the layout lints with the
+/// severities configured under `[lints]` (the LED rule only when the
+/// keyboard geometry is available). The layout is valid if the structure is
+/// sound and no lint is an error.
 pub(super) async fn validate_layout(
     State(state): State<AppState>,
     Path(filename): Path<String>,
 ) -> Result<Json<ValidationResponse>, AppError> {
     let filename = validate_filename(&filename)?;
     let filename = with_json_ext(filename);
-    let path = state.workspace_root.join(&filename);
 
-    if !path.exists() {
+    if !state.layout_exists(&filename).await? {
         return Err(AppError::not_found(format!(
             "Layout file not found: {filename}"
         )));
     }
 
-    let layout = LayoutService::load(&path).map_err(|e| {
+    let layout = state.layouts.load(&filename).await.map_err(|e| {
         AppError::with_details(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Failed to load layout",
@@ -36,21 +40,40 @@ pub(super) async fn validate_layout(
         )
     })?;
 
-    let mut warnings = Vec::new();
-    for name in &layout.get_orphaned_tap_dances() {
-        warnings.push(format!("Tap dance '{name}' is defined but not used"));
-    }
-
-    match layout.validate() {
-        Ok(()) => Ok(Json(ValidationResponse {
-            valid: true,
-            error: None,
-            warnings,
-        })),
-        Err(e) => Ok(Json(ValidationResponse {
+    if let Err(e) = layout.validate() {
+        return Ok(Json(ValidationResponse {
             valid: false,
             error: Some(e.to_string()),
-            warnings,
-        })),
+            warnings: Vec::new(),
+            lints: Vec::new(),
+        }));
     }
+
+    let lint_config = state
+        .config
+        .read()
+        .expect("config lock poisoned")
+        .lints
+        .clone();
+    let geometry = state.layout_geometry(&layout);
+    let lints = lint_layout(
+        &layout,
+        &state.keycode_db,
+        geometry.as_ref().map(|geom| &geom.geometry),
+        &lint_config,
+    );
+    let (errors, warnings): (Vec<_>, Vec<_>) = lints.iter().partition(|lint| lint.is_error());
+
+    Ok(Json(ValidationResponse {
+        valid: errors.is_empty(),
+        error: (!errors.is_empty()).then(|| {
+            errors
+                .iter()
+                .map(|lint| lint.message.as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        }),
+        warnings: warnings.iter().map(|lint| lint.message.clone()).collect(),
+        lints: lints.into_iter().map(LintDto::from).collect(),
+    }))
 }
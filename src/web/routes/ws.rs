@@ -0,0 +1,146 @@
+//! WebSocket push of build and generate job progress.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        ws::{rejection::WebSocketUpgradeRejection, Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    http::{header, HeaderMap, StatusCode},
+    response::Response,
+};
+use tokio::sync::broadcast::error::RecvError;
+
+use super::super::build_jobs::JobStatus;
+use super::super::error::AppError;
+use super::super::generate_jobs::GenerateJobStatus;
+use super::super::job_events::JobEvent;
+use super::super::AppState;
+
+/// Message sent when the connection fell behind and missed events.
+const RESYNC_MESSAGE: &str = r#"{"type":"resync"}"#;
+
+/// GET /api/ws - Stream job status changes and log lines over a WebSocket.
+///
+/// Each text message is a JSON [`JobEvent`] (`build_status`, `build_log`,
+/// `generate_status` or `generate_log`). On connect, the current state of
+/// every pending or running job is sent first. A `resync` message means
+/// events were missed; clients should refetch the jobs they show. Client
+/// messages are ignored. The socket closes when the server shuts down.
+///
+/// Handshakes from a page on another origin are refused with 403.
+pub(super) async fn job_socket(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    upgrade: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+) -> Result<Response, AppError> {
+    check_origin(&headers)?;
+    let upgrade = upgrade.map_err(|e| {
+        AppError::with_details(
+            StatusCode::BAD_REQUEST,
+            "Expected a WebSocket upgrade request",
+            e.body_text(),
+        )
+    })?;
+    Ok(upgrade.on_upgrade(move |socket| stream_jobs(state, socket)))
+}
+
+/// Accepts a handshake only if its `Origin` is the server's own host.
+///
+/// CORS does not apply to WebSockets, so without this any web page could
+/// read job logs. Clients that send no `Origin` (not browsers) are allowed.
+fn check_origin(headers: &HeaderMap) -> Result<(), AppError> {
+    let Some(origin) = headers.get(header::ORIGIN) else {
+        return Ok(());
+    };
+    let origin_host = origin
+        .to_str()
+        .ok()
+        .and_then(|origin| origin.split_once("://"))
+        .map(|(_, host)| host);
+    let host = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok());
+    if origin_host.is_some() && origin_host == host {
+        Ok(())
+    } else {
+        Err(AppError::with_details(
+            StatusCode::FORBIDDEN,
+            "WebSocket connections from other origins are not allowed",
+            origin.to_str().ok().map(str::to_string),
+        ))
+    }
+}
+
+/// Sends job events to the client until either side closes.
+async fn stream_jobs(state: AppState, mut socket: WebSocket) {
+    // Subscribe before the snapshot so no transition falls in between
+    let mut builds = state.build_manager.subscribe();
+    let mut generates = state.generate_manager.subscribe();
+
+    for event in active_jobs(&state) {
+        if send_event(&mut socket, &event).await.is_err() {
+            return;
+        }
+    }
+
+    let shutdown = Arc::clone(&state.shutdown);
+    loop {
+        let received = tokio::select! {
+            () = shutdown.triggered() => break,
+            // Pings are answered by the socket itself
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) => {
+                    // The reply to the client's close frame goes out on the
+                    // next read, which then ends the stream
+                    while let Some(Ok(_)) = socket.recv().await {}
+                    return;
+                }
+                Some(Err(_)) | None => return,
+                Some(Ok(_)) => continue,
+            },
+            received = builds.recv() => received,
+            received = generates.recv() => received,
+        };
+        let sent = match received {
+            Ok(event) => send_event(&mut socket, &event).await,
+            Err(RecvError::Lagged(_)) => socket.send(Message::Text(RESYNC_MESSAGE.into())).await,
+            Err(RecvError::Closed) => break,
+        };
+        if sent.is_err() {
+            break;
+        }
+    }
+
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+/// Status events for the pending and running jobs, oldest first.
+fn active_jobs(state: &AppState) -> Vec<JobEvent> {
+    let builds = state
+        .build_manager
+        .list_jobs()
+        .into_iter()
+        .rev()
+        .filter(|job| matches!(job.status, JobStatus::Pending | JobStatus::Running))
+        .map(|job| JobEvent::BuildStatus { job });
+    let generates = state
+        .generate_manager
+        .list_jobs()
+        .into_iter()
+        .rev()
+        .filter(|job| {
+            matches!(
+                job.status,
+                GenerateJobStatus::Pending | GenerateJobStatus::Running
+            )
+        })
+        .map(|job| JobEvent::GenerateStatus { job });
+    builds.chain(generates).collect()
+}
+
+async fn send_event(socket: &mut WebSocket, event: &JobEvent) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(event).map_err(axum::Error::new)?;
+    socket.send(Message::Text(text.into())).await
+}
@@ -0,0 +1,50 @@
+//! Sandbox server mode.
+//!
+//! When enabled (`lazyqmk web --sandbox`), the server never writes outside a
+//! temporary directory, which makes it safe for demo and playground
+//! instances and for tests:
+//!
+//! - Layouts are kept in a [`MemoryLayoutStore`](super::layout_store::MemoryLayoutStore)
+//!   seeded from the workspace, whose files are only read.
+//! - Job output, audit logs, templates, and other scratch files go to a
+//!   temporary directory that is removed when the server stops.
+//! - Configuration changes apply to the running server but aren't saved.
+//! - Firmware builds are rejected, since they write into the QMK tree.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+/// Temporary scratch directory of a sandbox server, removed on drop.
+#[derive(Debug)]
+pub struct SandboxDir {
+    path: PathBuf,
+}
+
+impl SandboxDir {
+    /// Creates a fresh directory under the system temp directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created.
+    pub fn create() -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("lazyqmk-sandbox-{}", Uuid::new_v4()));
+        fs::create_dir_all(&path)
+            .with_context(|| format!("Failed to create sandbox directory {}", path.display()))?;
+        Ok(Self { path })
+    }
+
+    /// Path of the directory.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for SandboxDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,129 @@
+//! Graceful web server shutdown.
+//!
+//! The server stops on Ctrl+C, SIGTERM, or an accepted `POST /api/shutdown`
+//! (only available with `lazyqmk web --allow-shutdown`). In-flight requests
+//! finish first; then pending and running build and generate jobs are
+//! cancelled and the worker threads get a grace period to clean up (e.g.
+//! removing keymap files deployed into the QMK tree) before the process
+//! exits.
+
+use std::time::{Duration, Instant};
+
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use super::AppState;
+
+/// How long shutdown waits for cancelled jobs to wind down.
+pub const JOB_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Poll interval while waiting for job workers to become idle.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Log line written to every job cancelled by a shutdown.
+const SHUTDOWN_CANCEL_REASON: &str = "Cancelled: server shutting down";
+
+/// Shared flag that asks the server to shut down.
+#[derive(Debug)]
+pub struct ShutdownTrigger {
+    tx: watch::Sender<bool>,
+}
+
+impl ShutdownTrigger {
+    /// Creates an untriggered shutdown flag.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            tx: watch::Sender::new(false),
+        }
+    }
+
+    /// Requests shutdown. Calling it again has no further effect.
+    pub fn trigger(&self) {
+        self.tx.send_replace(true);
+    }
+
+    /// Returns true once shutdown has been requested.
+    #[must_use]
+    pub fn is_triggered(&self) -> bool {
+        *self.tx.borrow()
+    }
+
+    /// Resolves once shutdown has been requested.
+    pub async fn triggered(&self) {
+        let mut rx = self.tx.subscribe();
+        // The sender lives in `self`, so the channel cannot close while we wait.
+        let _ = rx.wait_for(|triggered| *triggered).await;
+    }
+}
+
+impl Default for ShutdownTrigger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the number of pending or running build and generate jobs.
+#[must_use]
+pub fn active_job_count(state: &AppState) -> usize {
+    state.build_manager.active_job_count() + state.generate_manager.active_job_count()
+}
+
+/// Resolves when the server should stop: Ctrl+C, SIGTERM, or the trigger.
+pub async fn wait_for_shutdown(state: AppState) {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!(error = %e, "failed to listen for Ctrl+C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                warn!(error = %e, "failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => info!("Received Ctrl+C, shutting down"),
+        () = terminate => info!("Received SIGTERM, shutting down"),
+        () = state.shutdown.triggered() => info!("Shutdown requested via API"),
+    }
+    state.shutdown.trigger();
+}
+
+/// Cancels all active jobs and waits up to `timeout` for the workers to
+/// finish cleaning up.
+///
+/// Returns the number of jobs that were cancelled.
+pub async fn drain_jobs(state: &AppState, timeout: Duration) -> usize {
+    let cancelled = state.build_manager.cancel_all(SHUTDOWN_CANCEL_REASON)
+        + state.generate_manager.cancel_all(SHUTDOWN_CANCEL_REASON);
+    if cancelled > 0 {
+        info!(cancelled, "Cancelled active jobs for shutdown");
+    }
+
+    let deadline = Instant::now() + timeout;
+    while !(state.build_manager.is_idle() && state.generate_manager.is_idle()) {
+        if Instant::now() >= deadline {
+            warn!("Jobs still running after shutdown grace period; exiting anyway");
+            break;
+        }
+        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
+
+    cancelled
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,36 @@
+//! Tests for graceful shutdown.
+
+use super::*;
+use crate::config::Config;
+
+#[tokio::test]
+async fn test_trigger_resolves_waiters() {
+    let trigger = ShutdownTrigger::new();
+    assert!(!trigger.is_triggered());
+
+    trigger.trigger();
+    trigger.trigger();
+    assert!(trigger.is_triggered());
+
+    // Resolves immediately once triggered, even for late subscribers
+    tokio::time::timeout(Duration::from_secs(1), trigger.triggered())
+        .await
+        .expect("triggered() should resolve");
+}
+
+#[tokio::test]
+async fn test_wait_for_shutdown_returns_on_trigger() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let state =
+        AppState::with_mock_builder(Config::default(), temp_dir.path().to_path_buf()).unwrap();
+
+    let waiter = tokio::spawn(wait_for_shutdown(state.clone()));
+    state.shutdown.trigger();
+    tokio::time::timeout(Duration::from_secs(1), waiter)
+        .await
+        .expect("shutdown future should resolve")
+        .unwrap();
+
+    assert_eq!(drain_jobs(&state, Duration::from_secs(1)).await, 0);
+    assert_eq!(active_job_count(&state), 0);
+}
@@ -1,8 +1,12 @@
-//! Validation helpers for filename and keyboard path.
+//! Validation helpers for filename, keyboard path, and job preconditions.
 //!
 //! Extracted from src/web/mod.rs as part of LazyQMK-2rf6.2.
 
-use super::error::ApiError;
+use crate::firmware::validator::FirmwareValidator;
+use crate::keycode_db::KeycodeDb;
+use crate::models::{KeyboardGeometry, Layout, PositionNames};
+
+use super::error::{ApiError, AppError};
 
 /// Validates a filename to prevent path traversal attacks.
 ///
@@ -70,3 +74,24 @@ pub(crate) fn validate_keyboard_path(keyboard: &str) -> Result<(), ApiError> {
 
     Ok(())
 }
+
+/// Checks a layout before a generate/build job is started.
+///
+/// Runs the geometry-independent firmware checks (layout structure, keycodes,
+/// tap dance usage) so that broken layouts are rejected immediately with a
+/// structured report instead of failing minutes into a job.
+pub(crate) fn validate_layout_for_job(
+    layout: &Layout,
+    keycode_db: &KeycodeDb,
+    geometry: Option<&KeyboardGeometry>,
+) -> Result<(), AppError> {
+    let mut report = FirmwareValidator::validate_without_geometry(layout, keycode_db);
+    if let Some(geometry) = geometry {
+        report.annotate_positions(&PositionNames::build(geometry));
+    }
+    if report.is_valid() {
+        Ok(())
+    } else {
+        Err(AppError::validation_failed(&report))
+    }
+}
@@ -0,0 +1,69 @@
+//! Time limits for build and generate jobs.
+//!
+//! A compile can hang without printing anything, e.g. on a license prompt
+//! or a stalled submodule fetch. Jobs therefore get a [`JobDeadline`] from
+//! `web.job_timeout_secs` (0 disables it). Child processes are started with
+//! [`TrackedChild`](crate::services::process_tree::TrackedChild), so a job
+//! past its deadline is stopped together with everything it spawned (`make`,
+//! compilers, `git`).
+
+use std::time::{Duration, Instant};
+
+/// The point in time after which a job is stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JobDeadline {
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+}
+
+impl JobDeadline {
+    /// Starts the clock for a job limited to `timeout` (None = no limit).
+    #[must_use]
+    pub fn start(timeout: Option<Duration>) -> Self {
+        Self {
+            timeout,
+            deadline: timeout.map(|timeout| Instant::now() + timeout),
+        }
+    }
+
+    /// Returns true once the job has run longer than its timeout.
+    #[must_use]
+    pub fn expired(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Describes why a job past its deadline was stopped.
+    #[must_use]
+    pub fn reason(&self, what: &str) -> String {
+        let timeout = self.timeout.unwrap_or_default();
+        format!(
+            "{what} timed out after {} and was stopped. It may be waiting for input \
+             (e.g. a prompt) or on a hung download; set web.job_timeout_secs to change the limit.",
+            format_duration(timeout)
+        )
+    }
+}
+
+/// Converts the `web.job_timeout_secs` setting into a timeout (0 = none).
+#[must_use]
+pub const fn timeout_from_secs(secs: u64) -> Option<Duration> {
+    if secs == 0 {
+        None
+    } else {
+        Some(Duration::from_secs(secs))
+    }
+}
+
+/// Formats a timeout as minutes and seconds (e.g. "30 min", "90 s").
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 60 && secs.is_multiple_of(60) {
+        format!("{} min", secs / 60)
+    } else {
+        format!("{secs} s")
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,30 @@
+//! Tests for the job watchdog.
+
+use super::*;
+
+#[test]
+fn test_timeout_from_secs_zero_disables() {
+    assert_eq!(timeout_from_secs(0), None);
+    assert_eq!(timeout_from_secs(90), Some(Duration::from_secs(90)));
+}
+
+#[test]
+fn test_deadline_without_timeout_never_expires() {
+    assert!(!JobDeadline::start(None).expired());
+}
+
+#[test]
+fn test_deadline_expires() {
+    assert!(JobDeadline::start(Some(Duration::ZERO)).expired());
+    assert!(!JobDeadline::start(Some(Duration::from_mins(1))).expired());
+}
+
+#[test]
+fn test_deadline_reason() {
+    let reason = JobDeadline::start(Some(Duration::from_mins(30))).reason("Build");
+    assert!(reason.starts_with("Build timed out after 30 min"));
+    assert!(reason.contains("web.job_timeout_secs"));
+
+    let reason = JobDeadline::start(Some(Duration::from_secs(90))).reason("Generation");
+    assert!(reason.starts_with("Generation timed out after 90 s"));
+}
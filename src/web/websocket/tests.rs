@@ -0,0 +1,63 @@
+//! Tests for web::websocket.
+
+use super::*;
+
+#[test]
+fn test_accept_key_matches_rfc_example() {
+    // RFC 6455, section 1.3
+    assert_eq!(
+        accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+        "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+    );
+}
+
+#[test]
+fn test_base64_padding() {
+    assert_eq!(base64_encode(b""), "");
+    assert_eq!(base64_encode(b"f"), "Zg==");
+    assert_eq!(base64_encode(b"fo"), "Zm8=");
+    assert_eq!(base64_encode(b"foo"), "Zm9v");
+    assert_eq!(base64_encode(b"foob"), "Zm9vYg==");
+}
+
+#[test]
+fn test_encode_frame_length_forms() {
+    assert_eq!(encode_frame(OPCODE_TEXT, b"hi"), vec![0x81, 2, b'h', b'i']);
+
+    let medium = encode_frame(OPCODE_TEXT, &[0; 300]);
+    assert_eq!(&medium[..4], &[0x81, 126, 0x01, 0x2C]);
+    assert_eq!(medium.len(), 4 + 300);
+
+    let large = encode_frame(OPCODE_TEXT, &vec![0; 70_000]);
+    assert_eq!(&large[..2], &[0x81, 127]);
+    assert_eq!(&large[2..10], &70_000u64.to_be_bytes());
+}
+
+#[tokio::test]
+async fn test_read_frame_unmasks_client_frames() {
+    let mask = [1, 2, 3, 4];
+    let mut bytes = vec![0x89, 0x80 | 4];
+    bytes.extend_from_slice(&mask);
+    bytes.extend(b"ping".iter().zip(mask.iter().cycle()).map(|(b, m)| b ^ m));
+    bytes.extend_from_slice(&[0x88, 0x80, 0, 0, 0, 0]);
+    let mut reader = bytes.as_slice();
+
+    assert_eq!(
+        read_frame(&mut reader).await.unwrap(),
+        Some(ClientFrame::Ping(b"ping".to_vec()))
+    );
+    assert_eq!(
+        read_frame(&mut reader).await.unwrap(),
+        Some(ClientFrame::Close)
+    );
+    assert_eq!(read_frame(&mut reader).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_read_frame_rejects_oversized_payload() {
+    let mut bytes = vec![0x81, 127];
+    bytes.extend_from_slice(&(1u64 << 20).to_be_bytes());
+    let mut reader = bytes.as_slice();
+
+    assert!(read_frame(&mut reader).await.is_err());
+}
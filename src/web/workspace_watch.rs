@@ -0,0 +1,65 @@
+//! Announces layout saves made outside the web server.
+//!
+//! The TUI (or any other program) may save a layout that web sessions have
+//! open. The server polls the layouts that sessions are watching and sends
+//! them a `layout_replaced` event when one changed on disk without going
+//! through the server, so they reload instead of editing a stale copy.
+
+use std::time::Duration;
+
+use super::collab::LayoutChange;
+use super::AppState;
+
+/// How often watched layouts are checked for outside saves.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Actor reported for outside saves when no other LazyQMK process is known.
+const EXTERNAL_ACTOR: &str = "external";
+
+/// Checks the watched layouts every [`POLL_INTERVAL`] until shutdown.
+pub async fn watch(state: AppState) {
+    loop {
+        tokio::select! {
+            () = state.shutdown.triggered() => return,
+            () = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+        announce_external_changes(&state);
+    }
+}
+
+/// Sends `layout_replaced` to the sessions of every watched layout that was
+/// saved outside the server since the last check. Returns how many layouts
+/// changed.
+pub fn announce_external_changes(state: &AppState) -> usize {
+    let changed: Vec<String> = state
+        .collab
+        .watched_layouts()
+        .into_iter()
+        .filter(|filename| state.layouts.modified_externally(filename))
+        .collect();
+    if changed.is_empty() {
+        return 0;
+    }
+
+    let actor = state
+        .workspace_lock
+        .as_ref()
+        .map(|lock| lock.peers())
+        .filter(|peers| !peers.is_empty())
+        .map_or_else(
+            || EXTERNAL_ACTOR.to_string(),
+            |peers| {
+                peers
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+        );
+    for filename in &changed {
+        state
+            .collab
+            .publish(filename, None, &actor, [LayoutChange::LayoutReplaced]);
+    }
+    changed.len()
+}
@@ -0,0 +1,143 @@
+//! End-to-end tests for `lazyqmk analyze` command.
+
+#![cfg(feature = "tui")]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use lazyqmk::models::Position;
+use tempfile::TempDir;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Writes a 2x3 layout named `name` whose base layer holds `keycodes` in
+/// row-major order.
+fn create_layout(temp: &TempDir, name: &str, keycodes: [&str; 6]) -> PathBuf {
+    let mut layout = test_layout_basic(2, 3);
+    layout.metadata.name = name.to_string();
+    for (idx, keycode) in (0u8..).zip(keycodes) {
+        let position = Position::new(idx / 3, idx % 3);
+        layout.layers[0].get_key_mut(position).unwrap().keycode = keycode.to_string();
+    }
+
+    let path = temp.path().join(format!("{name}.json"));
+    write_layout_file(&layout, &path).unwrap();
+    path
+}
+
+fn run_analyze(layout: &Path, corpus: &Path, qmk_path: &Path, extra: &[&str]) -> Output {
+    Command::new(lazyqmk_bin())
+        .args([
+            "analyze",
+            "--layout",
+            layout.to_str().unwrap(),
+            "--corpus",
+            corpus.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+        ])
+        .args(extra)
+        .output()
+        .expect("Failed to execute command")
+}
+
+#[test]
+fn test_analyze_compares_layouts_as_json() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let first = create_layout(
+        &temp,
+        "first",
+        ["KC_A", "KC_B", "KC_C", "KC_D", "KC_E", "KC_F"],
+    );
+    let second = create_layout(
+        &temp,
+        "second",
+        ["KC_A", "KC_B", "KC_SPC", "KC_D", "KC_E", "KC_ENT"],
+    );
+    let corpus = temp.path().join("corpus.txt");
+    fs::write(&corpus, "Abc fab").unwrap();
+
+    let output = run_analyze(
+        &first,
+        &corpus,
+        &config.paths.qmk_firmware.unwrap(),
+        &["--compare", second.to_str().unwrap(), "--json"],
+    );
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let result: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Should parse JSON output");
+    let layouts = result["layouts"].as_array().unwrap();
+    assert_eq!(layouts.len(), 2);
+    assert_eq!(layouts[0]["name"], "first");
+    assert_eq!(layouts[1]["name"], "second");
+    // The first layout lacks the space, the second lacks c and f
+    assert_eq!(layouts[0]["total"], 7);
+    assert_eq!(layouts[0]["typed"], 6);
+    assert_eq!(layouts[1]["typed"], 5);
+    assert!(layouts[0]["effort_per_char"].as_f64().unwrap() > 0.0);
+    assert!(layouts[0]["fingers"].is_array());
+}
+
+#[test]
+fn test_analyze_prints_table() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let layout = create_layout(
+        &temp,
+        "alphas",
+        ["KC_A", "KC_B", "KC_C", "KC_D", "KC_E", "KC_F"],
+    );
+    let corpus = temp.path().join("corpus.json");
+    fs::write(
+        &corpus,
+        r#"{"chars": {"a": 3, "b": 1}, "bigrams": {"ab": 1}}"#,
+    )
+    .unwrap();
+
+    let output = run_analyze(&layout, &corpus, &config.paths.qmk_firmware.unwrap(), &[]);
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("(4 characters)"), "stdout: {stdout}");
+    assert!(stdout.contains("alphas"));
+    assert!(stdout.contains("Effort per char"));
+    assert!(stdout.contains("Typed"));
+    assert!(stdout.contains("100.0%"));
+}
+
+#[test]
+fn test_analyze_missing_corpus_fails() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let layout = create_layout(
+        &temp,
+        "alphas",
+        ["KC_A", "KC_B", "KC_C", "KC_D", "KC_E", "KC_F"],
+    );
+
+    let output = run_analyze(
+        &layout,
+        &temp.path().join("missing.txt"),
+        &config.paths.qmk_firmware.unwrap(),
+        &[],
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to read corpus"));
+}
@@ -0,0 +1,135 @@
+//! End-to-end tests for `lazyqmk batch` command.
+
+#![cfg(feature = "tui")]
+
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Test workspace with one valid and one broken layout, plus a fake QMK tree.
+struct Workspace {
+    layouts: TempDir,
+    config_temp: TempDir,
+    qmk_path: std::path::PathBuf,
+}
+
+fn create_workspace() -> Workspace {
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let layouts = TempDir::new().unwrap();
+
+    write_layout_file(&test_layout_basic(2, 3), &layouts.path().join("good.json")).unwrap();
+    let mut broken = test_layout_basic(2, 3);
+    broken.metadata.layout_variant = Some("LAYOUT_missing".to_string());
+    write_layout_file(&broken, &layouts.path().join("broken.json")).unwrap();
+
+    Workspace {
+        layouts,
+        config_temp,
+        qmk_path: config.paths.qmk_firmware.unwrap(),
+    }
+}
+
+fn run_batch(workspace: &Workspace, extra: &[&str]) -> Output {
+    Command::new(lazyqmk_bin())
+        .args([
+            "batch",
+            "--workspace",
+            workspace.layouts.path().to_str().unwrap(),
+            "--qmk-path",
+            workspace.qmk_path.to_str().unwrap(),
+            "--out-dir",
+            workspace.config_temp.path().join("out").to_str().unwrap(),
+        ])
+        .args(extra)
+        .output()
+        .expect("Failed to execute command")
+}
+
+#[test]
+fn test_batch_generate_and_export_all_layouts() {
+    let workspace = create_workspace();
+
+    let output = run_batch(&workspace, &["--generate", "--export", "svg,md", "--json"]);
+
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "One broken layout should fail the batch. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let result: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Should parse JSON output");
+    assert_eq!(result["succeeded"], 1);
+    assert_eq!(result["failed"], 1);
+
+    let broken = &result["layouts"][0];
+    assert_eq!(broken["file"], "broken.json");
+    assert_eq!(broken["tasks"].as_array().unwrap().len(), 3);
+    assert!(broken["tasks"][0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("LAYOUT_missing"));
+
+    let good = &result["layouts"][1];
+    assert_eq!(good["file"], "good.json");
+    let tasks: Vec<_> = good["tasks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|task| {
+            (
+                task["task"].as_str().unwrap(),
+                task["ok"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(tasks, vec![("generate", true), ("svg", true), ("md", true)]);
+
+    let out = workspace.config_temp.path().join("out").join("good");
+    assert!(out.join("keymap.c").exists());
+    assert!(out.join("config.h").exists());
+    let svg = std::fs::read_to_string(out.join("good.svg")).unwrap();
+    assert!(svg.starts_with("<svg "));
+    assert!(out.join("good.md").exists());
+}
+
+#[test]
+fn test_batch_prints_summary_table() {
+    let workspace = create_workspace();
+
+    let output = run_batch(&workspace, &["--export", "svg"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Layout       svg"), "stdout: {stdout}");
+    assert!(stdout.contains("good.json    ✓"));
+    assert!(stdout.contains("broken.json  ✗"));
+    assert!(stdout.contains("1 succeeded, 1 failed"));
+}
+
+#[test]
+fn test_batch_requires_a_task() {
+    let workspace = create_workspace();
+
+    let output = run_batch(&workspace, &[]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Nothing to do"));
+}
+
+#[test]
+fn test_batch_rejects_unknown_export_format() {
+    let workspace = create_workspace();
+
+    let output = run_batch(&workspace, &["--export", "pdf"]);
+
+    assert_ne!(output.status.code(), Some(0));
+}
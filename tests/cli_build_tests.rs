@@ -0,0 +1,145 @@
+//! End-to-end tests for `lazyqmk build` command.
+//!
+//! `qmk` is replaced by a shell script on `PATH`, so these tests only run on
+//! Unix.
+
+#![cfg(feature = "tui")]
+#![cfg(unix)]
+#![allow(unused_variables)] // Temp dirs must be kept alive even if not directly accessed
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+mod fixtures;
+use fixtures::*;
+use tempfile::TempDir;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Creates a fake `qmk` that runs `script` and returns its directory.
+fn fake_qmk(script: &str) -> TempDir {
+    let bin_dir = TempDir::new().expect("Failed to create temp dir");
+    let qmk = bin_dir.path().join("qmk");
+    fs::write(&qmk, format!("#!/bin/sh\n{script}\n")).expect("Failed to write fake qmk");
+    fs::set_permissions(&qmk, fs::Permissions::from_mode(0o755))
+        .expect("Failed to make fake qmk executable");
+    bin_dir
+}
+
+/// Runs `lazyqmk build` with the fake `qmk` first on `PATH` and an isolated
+/// config directory.
+fn run_build(bin_dir: &Path, config_dir: &Path, args: &[&str]) -> Output {
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    Command::new(lazyqmk_bin())
+        .arg("build")
+        .args(args)
+        .env("PATH", path)
+        .env("LAZYQMK_CONFIG_DIR", config_dir)
+        .output()
+        .expect("Failed to execute command")
+}
+
+fn qmk_path(config: &lazyqmk::config::Config) -> PathBuf {
+    config.paths.qmk_firmware.clone().unwrap()
+}
+
+#[test]
+fn test_build_compiles_and_copies_firmware() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = qmk_path(&config);
+    let out_dir = config_temp.path().join("firmware");
+    let bin_dir = fake_qmk(
+        "echo \"qmk $*\"\nmkdir -p .build\necho firmware > .build/test_keyboard_test_keymap.hex",
+    );
+
+    let output = run_build(
+        bin_dir.path(),
+        config_temp.path(),
+        &[
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+        ],
+    );
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Build should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("qmk compile -kb test_keyboard -km test_keymap"));
+    assert!(out_dir.join("test_keyboard_test_keymap.hex").exists());
+    // Firmware files are generated into the QMK keymap directory first
+    assert!(qmk_path
+        .join("keyboards/test_keyboard/keymaps/test_keymap/keymap.c")
+        .exists());
+}
+
+#[test]
+fn test_build_compile_failure_exits_with_build_error() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = qmk_path(&config);
+    let bin_dir = fake_qmk("echo 'keymap.c:1: error: boom' >&2\nexit 1");
+
+    let output = run_build(
+        bin_dir.path(),
+        config_temp.path(),
+        &[
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+        ],
+    );
+
+    assert_eq!(
+        output.status.code(),
+        Some(3),
+        "Compile failure should exit 3"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("error: boom"),
+        "qmk output should be passed through"
+    );
+    assert!(stderr.contains("qmk compile failed"));
+}
+
+#[test]
+fn test_build_invalid_layout_exits_with_validation_error() {
+    let layout = test_layout_with_invalid_keycode();
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = qmk_path(&config);
+    let bin_dir = fake_qmk("echo 'qmk should not run' >&2\nexit 1");
+
+    let output = run_build(
+        bin_dir.path(),
+        config_temp.path(),
+        &[
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("qmk should not run"));
+}
@@ -0,0 +1,123 @@
+//! End-to-end tests for `lazyqmk bundle` commands.
+
+#![cfg(feature = "tui")]
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(lazyqmk_bin())
+        .args(args)
+        .output()
+        .expect("Failed to execute command")
+}
+
+fn path_str(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+#[test]
+fn test_bundle_export_import_round_trip() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let layout = test_layout_basic(2, 3);
+    let layout_path = temp.path().join("layout.json");
+    write_layout_file(&layout, &layout_path).unwrap();
+    let bundle_path = temp.path().join("shared.lazyqmk");
+
+    let output = run(&[
+        "bundle",
+        "export",
+        "--layout",
+        path_str(&layout_path),
+        "--out",
+        path_str(&bundle_path),
+        "--qmk-path",
+        path_str(config.paths.qmk_firmware.as_ref().unwrap()),
+    ]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("with preview"));
+    assert!(bundle_path.exists());
+
+    let imported_path = temp.path().join("imported.json");
+    let preview_path = temp.path().join("preview.svg");
+    let keymap_path = temp.path().join("keymap.c");
+    let output = run(&[
+        "bundle",
+        "import",
+        "--bundle",
+        path_str(&bundle_path),
+        "--out",
+        path_str(&imported_path),
+        "--preview",
+        path_str(&preview_path),
+        "--keymap",
+        path_str(&keymap_path),
+    ]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let imported = lazyqmk::services::LayoutService::load(&imported_path).unwrap();
+    assert_eq!(imported.metadata.name, layout.metadata.name);
+    assert_eq!(imported.layers.len(), layout.layers.len());
+    assert!(fs::read_to_string(&preview_path).unwrap().contains("<svg"));
+    assert!(fs::read_to_string(&keymap_path)
+        .unwrap()
+        .contains("const uint16_t PROGMEM keymaps"));
+
+    let bundle = lazyqmk::services::share_bundle::ShareBundle::read(&bundle_path).unwrap();
+    assert!(bundle.manifest.geometry);
+    assert_eq!(bundle.geometry.unwrap().layout_name, "LAYOUT_test");
+}
+
+#[test]
+fn test_bundle_import_refuses_existing_output() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let layout_path = temp.path().join("layout.json");
+    write_layout_file(&test_layout_basic(2, 3), &layout_path).unwrap();
+
+    let output = run(&[
+        "bundle",
+        "export",
+        "--layout",
+        path_str(&layout_path),
+        "--no-preview",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    let bundle_path = temp.path().join("layout.lazyqmk");
+    assert!(bundle_path.exists());
+
+    // Default output is layout.json, which already exists
+    let output = run(&["bundle", "import", "--bundle", path_str(&bundle_path)]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already exists"));
+}
+
+#[test]
+fn test_bundle_import_rejects_invalid_file() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let bogus = temp.path().join("bogus.lazyqmk");
+    fs::write(&bogus, "not a zip").unwrap();
+
+    let output = run(&["bundle", "import", "--bundle", path_str(&bogus)]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Not a LazyQMK bundle"));
+    assert!(!temp.path().join("bogus.json").exists());
+}
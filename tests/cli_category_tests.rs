@@ -1,5 +1,7 @@
 //! End-to-end tests for `lazyqmk category` commands.
 
+#![cfg(feature = "tui")]
+
 use serde::{Deserialize, Serialize};
 use std::process::Command;
 
@@ -0,0 +1,69 @@
+//! End-to-end tests for `lazyqmk collect-usage` command.
+
+#![cfg(feature = "tui")]
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+fn create_layout(temp: &TempDir, usage_counting: bool) -> PathBuf {
+    let mut layout = test_layout_basic(2, 3);
+    layout.usage_counting = usage_counting;
+    let path = temp.path().join("layout.json");
+    write_layout_file(&layout, &path).unwrap();
+    path
+}
+
+fn run_collect(layout: &Path, qmk_path: &Path) -> Output {
+    Command::new(lazyqmk_bin())
+        .args([
+            "collect-usage",
+            "--layout",
+            layout.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+            "--duration",
+            "0",
+        ])
+        .output()
+        .expect("Failed to execute command")
+}
+
+#[test]
+fn test_collect_usage_requires_opt_in() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let layout = create_layout(&temp, false);
+
+    let output = run_collect(&layout, &config.paths.qmk_firmware.unwrap());
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Usage counting is off"));
+}
+
+#[test]
+fn test_collect_usage_without_reports_writes_nothing() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let layout = create_layout(&temp, true);
+
+    let output = run_collect(&layout, &config.paths.qmk_firmware.unwrap());
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("layout.usage"), "stdout: {stdout}");
+    assert!(stdout.contains("No key presses reported"));
+    assert!(!temp.path().join("layout.usage").exists());
+}
@@ -1,5 +1,7 @@
 //! End-to-end tests for `lazyqmk config` commands.
 
+#![cfg(feature = "tui")]
+
 use std::path::PathBuf;
 use std::process::Command;
 use std::sync::Mutex;
@@ -299,6 +301,67 @@ fn test_config_set_qmk_path_invalid_directory() {
     );
 }
 
+#[test]
+fn test_config_set_keyboard_roots() {
+    let _lock = CONFIG_TEST_LOCK.lock().unwrap();
+    let config_temp = tempfile::TempDir::new().expect("Failed to create config temp dir");
+    let config_dir = config_temp.path().to_path_buf();
+    let root = tempfile::TempDir::new().expect("Failed to create root temp dir");
+    let root_path = root.path().to_str().unwrap();
+
+    let output = isolated_config_command(
+        &["config", "set", "--keyboard-root", root_path],
+        &config_dir,
+    )
+    .output()
+    .expect("Failed to execute command");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Setting keyboard root should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = isolated_config_command(&["config", "show", "--json"], &config_dir)
+        .output()
+        .expect("Failed to execute command");
+    let result: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Should parse JSON output");
+    assert_eq!(
+        result["paths"]["keyboard_roots"],
+        serde_json::json!([root_path])
+    );
+
+    let output = isolated_config_command(&["config", "set", "--clear-keyboard-roots"], &config_dir)
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = isolated_config_command(&["config", "show", "--json"], &config_dir)
+        .output()
+        .expect("Failed to execute command");
+    let result: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Should parse JSON output");
+    assert!(result["paths"].get("keyboard_roots").is_none());
+}
+
+#[test]
+fn test_config_set_keyboard_root_invalid_directory() {
+    let config_temp = tempfile::TempDir::new().expect("Failed to create config temp dir");
+    let output = isolated_config_command(
+        &["config", "set", "--keyboard-root", "/nonexistent/keyboards"],
+        &config_temp.path().to_path_buf(),
+    )
+    .output()
+    .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "Setting a missing keyboard root should fail with exit code 1"
+    );
+}
+
 #[test]
 fn test_config_set_multiple_values() {
     let _lock = CONFIG_TEST_LOCK.lock().unwrap();
@@ -1,4 +1,6 @@
 //! End-to-end tests for `lazyqmk export` command.
+
+#![cfg(feature = "tui")]
 #![allow(unused_variables)] // Temp dirs must be kept alive even if not directly accessed
 
 use std::fs;
@@ -180,3 +182,168 @@ fn test_export_multiple_layers() {
         "Should have default color for layers"
     );
 }
+
+#[test]
+fn test_export_keymap_drawer_format() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let out_path = config_temp.path().join("keymap.yaml");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "export",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            config
+                .paths
+                .qmk_firmware
+                .as_ref()
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "--format",
+            "keymap-drawer",
+            "--output",
+            out_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Export should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = fs::read_to_string(&out_path).expect("Failed to read export file");
+    let yaml: serde_yml::Value = serde_yml::from_str(&content).expect("Export should be YAML");
+    assert_eq!(yaml["layers"]["Base"].as_sequence().unwrap().len(), 6);
+    assert!(yaml["layers"]["Function"].is_sequence());
+    assert!(yaml["layout"]["qmk_keyboard"].is_string());
+}
+
+#[test]
+fn test_export_via_format() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let out_path = config_temp.path().join("keymap.json");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "export",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            config
+                .paths
+                .qmk_firmware
+                .as_ref()
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "--format",
+            "via",
+            "--output",
+            out_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Export should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = fs::read_to_string(&out_path).expect("Failed to read export file");
+    let json: serde_json::Value = serde_json::from_str(&content).expect("Export should be JSON");
+    // usb.vid 0xFEED, usb.pid 0x0000 from the fixture's info.json
+    assert_eq!(json["vendorProductId"], 0xFEED_0000_u32);
+    assert_eq!(json["layers"].as_array().unwrap().len(), 2);
+    assert_eq!(json["layers"][0].as_array().unwrap().len(), 6);
+}
+
+#[test]
+fn test_export_qmk_json_format() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, _layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let out_path = config_temp.path().join("exported.keymap.json");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "export",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            config
+                .paths
+                .qmk_firmware
+                .as_ref()
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "--format",
+            "qmk-json",
+            "--output",
+            out_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Export should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = fs::read_to_string(&out_path).expect("Failed to read export file");
+    let json: serde_json::Value = serde_json::from_str(&content).expect("Export should be JSON");
+    assert_eq!(json["layout"], "LAYOUT_test");
+    assert_eq!(json["layers"].as_array().unwrap().len(), 2);
+    assert_eq!(json["layers"][0].as_array().unwrap().len(), 6);
+}
+
+#[test]
+fn test_export_zmk_format() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let out_path = config_temp.path().join("test.keymap");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "export",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            config
+                .paths
+                .qmk_firmware
+                .as_ref()
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "--format",
+            "zmk",
+            "--output",
+            out_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Export should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = fs::read_to_string(&out_path).expect("Failed to read export file");
+    assert!(content.contains("compatible = \"zmk,keymap\";"));
+    assert!(content.contains("base_layer {"));
+    assert!(content.contains("function_layer {"));
+}
@@ -1,4 +1,6 @@
 //! End-to-end tests for `lazyqmk generate` command.
+
+#![cfg(feature = "tui")]
 #![allow(unused_variables)] // Temp dirs must be kept alive even if not directly accessed
 
 use std::fs;
@@ -133,6 +135,93 @@ fn test_generate_deterministic_output() {
     );
 }
 
+#[test]
+fn test_generate_output_is_stable_without_flag() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, _layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.as_ref().unwrap();
+
+    let generate = |out_dir: &std::path::Path| {
+        let output = Command::new(lazyqmk_bin())
+            .args([
+                "generate",
+                "--layout",
+                layout_path.to_str().unwrap(),
+                "--qmk-path",
+                qmk_path.to_str().unwrap(),
+                "--out-dir",
+                out_dir.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(0));
+    };
+    let out1 = config_temp.path().join("out1");
+    let out2 = config_temp.path().join("out2");
+    generate(&out1);
+    generate(&out2);
+
+    // Output is byte-identical with no normalization
+    for file in ["keymap.c", "config.h"] {
+        assert_eq!(
+            fs::read_to_string(out1.join(file)).unwrap(),
+            fs::read_to_string(out2.join(file)).unwrap(),
+            "{file} differs between runs"
+        );
+    }
+}
+
+#[test]
+fn test_generate_preserves_user_code_regions() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, _layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.as_ref().unwrap();
+    let out_dir = config_temp.path().join("output");
+
+    let generate = || {
+        let output = Command::new(lazyqmk_bin())
+            .args([
+                "generate",
+                "--layout",
+                layout_path.to_str().unwrap(),
+                "--qmk-path",
+                qmk_path.to_str().unwrap(),
+                "--out-dir",
+                out_dir.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(0));
+    };
+    generate();
+
+    // Add hand-written code inside the user regions
+    for (file, user_code) in [
+        ("keymap.c", "void my_helper(void) {}\n"),
+        ("config.h", "#define MY_FLAG 1\n"),
+    ] {
+        let path = out_dir.join(file);
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.ends_with("// LAZYQMK:BEGIN user\n// LAZYQMK:END user\n"));
+        let edited = content.replace(
+            "// LAZYQMK:BEGIN user\n",
+            &format!("// LAZYQMK:BEGIN user\n{user_code}"),
+        );
+        fs::write(&path, edited).unwrap();
+    }
+
+    generate();
+
+    let keymap_c = fs::read_to_string(out_dir.join("keymap.c")).unwrap();
+    assert!(
+        keymap_c.contains("// LAZYQMK:BEGIN user\nvoid my_helper(void) {}\n// LAZYQMK:END user\n")
+    );
+    let config_h = fs::read_to_string(out_dir.join("config.h")).unwrap();
+    assert!(config_h.contains("// LAZYQMK:BEGIN user\n#define MY_FLAG 1\n// LAZYQMK:END user\n"));
+}
+
 #[test]
 fn test_generate_format_keymap_only() {
     let layout = test_layout_basic(2, 3);
@@ -543,3 +632,152 @@ fn test_generate_missing_qmk_path() {
         "Missing QMK path should exit with code 2"
     );
 }
+
+/// Adds `small_keyboard` (one row of two keys) to the temp QMK checkout and a
+/// `small` target to `layout` that moves key (1, 2) onto (0, 1).
+fn add_small_target(layout: &mut lazyqmk::models::Layout, qmk_path: &std::path::Path) {
+    use lazyqmk::models::{KeyboardTarget, Position, PositionRemap};
+
+    let keyboard_dir = qmk_path.join("keyboards").join("small_keyboard");
+    fs::create_dir_all(&keyboard_dir).expect("Failed to create keyboard dir");
+    let info_json = serde_json::json!({
+        "keyboard_name": "small_keyboard",
+        "matrix_pins": {"cols": ["F0", "F1"], "rows": ["D0"]},
+        "layouts": {
+            "LAYOUT_small": {
+                "layout": [
+                    {"matrix": [0, 0], "x": 0, "y": 0},
+                    {"matrix": [0, 1], "x": 1, "y": 0}
+                ]
+            }
+        }
+    });
+    fs::write(
+        keyboard_dir.join("info.json"),
+        serde_json::to_string_pretty(&info_json).unwrap(),
+    )
+    .expect("Failed to write info.json");
+
+    let mut target = KeyboardTarget::new("small", "small_keyboard", "LAYOUT_small");
+    target.remap = vec![
+        PositionRemap {
+            from: Position::new(0, 1),
+            to: None,
+        },
+        PositionRemap {
+            from: Position::new(1, 2),
+            to: Some(Position::new(0, 1)),
+        },
+    ];
+    layout.metadata.targets.push(target);
+}
+
+#[test]
+fn test_generate_keyboard_target() {
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let mut layout = test_layout_basic(2, 3);
+    add_small_target(&mut layout, &qmk_path);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let out_dir = config_temp.path().join("output");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "generate",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            "--target",
+            "small",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Target generation should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let keymap = fs::read_to_string(out_dir.join("keymap.c")).expect("keymap.c should exist");
+    assert!(keymap.contains("LAYOUT_small"));
+    assert!(keymap.contains("KC_5"), "Remapped key should be kept");
+    assert!(!keymap.contains("KC_1"), "Dropped key should be left off");
+    assert!(
+        !keymap.contains("KC_4"),
+        "Key outside the target should be left off"
+    );
+}
+
+#[test]
+fn test_generate_all_targets_writes_target_subdirectories() {
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let mut layout = test_layout_basic(2, 3);
+    add_small_target(&mut layout, &qmk_path);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let out_dir = config_temp.path().join("output");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "generate",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            "--all-targets",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Generation should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let primary = fs::read_to_string(out_dir.join("keymap.c")).expect("keymap.c should exist");
+    assert!(primary.contains("LAYOUT_test"));
+    let small =
+        fs::read_to_string(out_dir.join("small").join("keymap.c")).expect("target keymap.c");
+    assert!(small.contains("LAYOUT_small"));
+}
+
+#[test]
+fn test_generate_unknown_target_fails() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let out_dir = config_temp.path().join("output");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "generate",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            config
+                .paths
+                .qmk_firmware
+                .as_ref()
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            "--target",
+            "missing",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_ne!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("missing"));
+}
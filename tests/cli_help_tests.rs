@@ -1,5 +1,7 @@
 //! End-to-end tests for `lazyqmk help` command.
 
+#![cfg(feature = "tui")]
+
 use std::process::Command;
 
 /// Path to the lazyqmk binary
@@ -0,0 +1,214 @@
+//! End-to-end tests for `lazyqmk import` command.
+
+#![cfg(feature = "tui")]
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use lazyqmk::models::Position;
+use lazyqmk::services::LayoutService;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Two-layer keymap for the 2x3 `test_keyboard` fixture.
+const KEYMAP_C: &str = r"
+enum layers { _BASE, _NAV };
+
+tap_dance_action_t tap_dance_actions[] = {
+    [TD_ESC] = ACTION_TAP_DANCE_DOUBLE(KC_ESC, KC_CAPS),
+};
+
+const uint16_t PROGMEM keymaps[][MATRIX_ROWS][MATRIX_COLS] = {
+    [_BASE] = LAYOUT_test(
+        TD(TD_ESC), KC_A,    KC_B,
+        MO(_NAV),   KC_SPC,  KC_ENT
+    ),
+    [_NAV] = LAYOUT_test(
+        _______, KC_UP,   XXXXXXX,
+        _______, KC_LEFT, KC_RGHT
+    )
+};
+";
+
+fn run_import(keymap: &Path, out: &Path, qmk_path: &Path, extra: &[&str]) -> Output {
+    Command::new(lazyqmk_bin())
+        .args([
+            "import",
+            "--keymap",
+            keymap.to_str().unwrap(),
+            "--keyboard",
+            "test_keyboard",
+            "--out",
+            out.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+        ])
+        .args(extra)
+        .output()
+        .expect("Failed to execute command")
+}
+
+#[test]
+fn test_import_creates_layout() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let keymap = temp.path().join("keymap.c");
+    fs::write(&keymap, KEYMAP_C).unwrap();
+    let out = temp.path().join("imported.json");
+
+    let output = run_import(&keymap, &out, &config.paths.qmk_firmware.unwrap(), &[]);
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let layout = LayoutService::load(&out).unwrap();
+    assert_eq!(layout.metadata.name, "imported");
+    assert_eq!(layout.metadata.keyboard.as_deref(), Some("test_keyboard"));
+    assert_eq!(layout.layers.len(), 2);
+    assert_eq!(layout.layers[1].name, "Nav");
+    assert_eq!(layout.tap_dances[0].name, "esc");
+
+    let base = &layout.layers[0];
+    assert_eq!(
+        base.get_key(Position::new(0, 0)).unwrap().keycode,
+        "TD(esc)"
+    );
+    assert_eq!(
+        base.get_key(Position::new(1, 0)).unwrap().keycode,
+        format!("MO(@{})", layout.layers[1].id)
+    );
+    assert_eq!(
+        layout.layers[1]
+            .get_key(Position::new(0, 2))
+            .unwrap()
+            .keycode,
+        "KC_NO"
+    );
+}
+
+#[test]
+fn test_import_qmk_keymap_json() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let keymap = temp.path().join("keymap.json");
+    fs::write(
+        &keymap,
+        r#"{
+            "keyboard": "test_keyboard",
+            "keymap": "shared",
+            "layout": "LAYOUT_test",
+            "layers": [
+                ["KC_ESC", "KC_A", "KC_B", "MO(1)", "KC_SPC", "KC_ENT"],
+                ["KC_TRNS", "KC_UP", "TD(0)", "KC_TRNS", "KC_LEFT", "KC_RGHT"]
+            ]
+        }"#,
+    )
+    .unwrap();
+    let out = temp.path().join("imported.json");
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+
+    // The keyboard comes from the keymap.json
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "import",
+            "--keymap",
+            keymap.to_str().unwrap(),
+            "--out",
+            out.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("TD(0)"));
+    let layout = LayoutService::load(&out).unwrap();
+    assert_eq!(layout.metadata.keyboard.as_deref(), Some("test_keyboard"));
+    assert_eq!(layout.metadata.keymap_name.as_deref(), Some("shared"));
+    assert_eq!(
+        layout.layers[0]
+            .get_key(Position::new(1, 0))
+            .unwrap()
+            .keycode,
+        format!("MO(@{})", layout.layers[1].id)
+    );
+    assert_eq!(
+        layout.layers[1]
+            .get_key(Position::new(0, 2))
+            .unwrap()
+            .keycode,
+        "KC_NO"
+    );
+}
+
+#[test]
+fn test_import_json_output() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let keymap = temp.path().join("keymap.c");
+    fs::write(&keymap, KEYMAP_C).unwrap();
+    let out = temp.path().join("imported.json");
+
+    let output = run_import(
+        &keymap,
+        &out,
+        &config.paths.qmk_firmware.unwrap(),
+        &["--name", "My Keymap", "--json"],
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["layout_variant"], "LAYOUT_test");
+    assert_eq!(json["layers"], serde_json::json!(["Base", "Nav"]));
+    assert_eq!(json["tap_dances"], 1);
+    assert_eq!(
+        LayoutService::load(&out).unwrap().metadata.name,
+        "My Keymap"
+    );
+}
+
+#[test]
+fn test_import_rejects_key_count_mismatch() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let keymap = temp.path().join("keymap.c");
+    fs::write(
+        &keymap,
+        "const uint16_t keymaps[][2][3] = { LAYOUT_test(KC_A, KC_B) };",
+    )
+    .unwrap();
+    let out = temp.path().join("imported.json");
+
+    let output = run_import(&keymap, &out, &config.paths.qmk_firmware.unwrap(), &[]);
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("has 2 keys"), "stderr: {stderr}");
+    assert!(!out.exists());
+}
+
+#[test]
+fn test_import_refuses_to_overwrite() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let keymap = temp.path().join("keymap.c");
+    fs::write(&keymap, KEYMAP_C).unwrap();
+    let out = temp.path().join("existing.json");
+    fs::write(&out, "{}").unwrap();
+
+    let output = run_import(&keymap, &out, &config.paths.qmk_firmware.unwrap(), &[]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already exists"));
+}
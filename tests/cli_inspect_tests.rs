@@ -1,4 +1,6 @@
 //! End-to-end tests for `lazyqmk inspect` command.
+
+#![cfg(feature = "tui")]
 #![allow(unused_variables)] // Temp dirs must be kept alive even if not directly accessed
 
 use std::process::Command;
@@ -1,5 +1,7 @@
 //! End-to-end tests for `lazyqmk layer-refs` command.
 
+#![cfg(feature = "tui")]
+
 use lazyqmk::models::{Layer, Layout, RgbColor};
 use std::process::Command;
 
@@ -0,0 +1,146 @@
+//! End-to-end tests for `lazyqmk optimize-thumbs` command.
+
+#![cfg(feature = "tui")]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use lazyqmk::models::{Layout, Position};
+use lazyqmk::services::LayoutService;
+use tempfile::TempDir;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Sets the keycode at `(row, col)` on `layer`.
+fn set_key(layout: &mut Layout, layer: usize, row: u8, col: u8, keycode: &str) {
+    layout.layers[layer]
+        .get_key_mut(Position::new(row, col))
+        .unwrap()
+        .keycode = keycode.to_string();
+}
+
+/// 2x3 board where column 0 is the left hand and columns 1-2 the right.
+///
+/// Shift sits on the left with the letter it shifts, and the symbol layer
+/// key on the right with the symbol it reaches.
+fn create_layout(temp: &TempDir) -> PathBuf {
+    let mut layout = test_layout_basic(2, 3);
+    set_key(&mut layout, 0, 0, 0, "KC_A");
+    set_key(&mut layout, 0, 0, 1, "KC_B");
+    set_key(&mut layout, 0, 0, 2, "KC_SPC");
+    set_key(&mut layout, 0, 1, 0, "KC_LSFT");
+    set_key(&mut layout, 0, 1, 1, "MO(1)");
+    set_key(&mut layout, 0, 1, 2, "KC_ENT");
+    set_key(&mut layout, 1, 0, 2, "KC_EXLM");
+
+    let path = temp.path().join("layout.json");
+    write_layout_file(&layout, &path).unwrap();
+    path
+}
+
+fn run_optimize(layout: &Path, corpus: &Path, qmk_path: &Path, extra: &[&str]) -> Output {
+    Command::new(lazyqmk_bin())
+        .args([
+            "optimize-thumbs",
+            "--layout",
+            layout.to_str().unwrap(),
+            "--corpus",
+            corpus.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+        ])
+        .args(extra)
+        .output()
+        .expect("Failed to execute command")
+}
+
+#[test]
+fn test_optimize_thumbs_suggests_swap() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let layout = create_layout(&temp);
+    let corpus = temp.path().join("corpus.txt");
+    fs::write(&corpus, "Ab! Ab!\n").unwrap();
+
+    let output = run_optimize(
+        &layout,
+        &corpus,
+        &config.paths.qmk_firmware.unwrap(),
+        &["--json"],
+    );
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let result: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Should parse JSON output");
+    assert_eq!(result["score"]["same_hand_holds"], 4);
+    let best = &result["suggestions"][0];
+    assert_eq!(best["number"], 1);
+    assert_eq!(best["first"]["keycode"], "KC_LSFT");
+    assert_eq!(best["second"]["keycode"], "MO(1)");
+    assert_eq!(best["improvement"], 4);
+    assert_eq!(best["score"]["total"], 0);
+}
+
+#[test]
+fn test_optimize_thumbs_applies_selected_swap() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let layout = create_layout(&temp);
+    let corpus = temp.path().join("corpus.json");
+    fs::write(&corpus, r#"{"chars": {"A": 10, "!": 5}}"#).unwrap();
+
+    let output = run_optimize(
+        &layout,
+        &corpus,
+        &config.paths.qmk_firmware.unwrap(),
+        &["--apply", "1"],
+    );
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Current score: 15"), "stdout: {stdout}");
+    assert!(stdout.contains("✓ Applied 1 swap(s)"));
+
+    let saved = LayoutService::load(&layout).unwrap();
+    let base = &saved.layers[0];
+    assert_eq!(base.get_key(Position::new(1, 0)).unwrap().keycode, "MO(1)");
+    assert_eq!(
+        base.get_key(Position::new(1, 1)).unwrap().keycode,
+        "KC_LSFT"
+    );
+}
+
+#[test]
+fn test_optimize_thumbs_rejects_unknown_suggestion() {
+    let (config, temp) = temp_config_with_qmk(None);
+    let layout = create_layout(&temp);
+    let corpus = temp.path().join("corpus.txt");
+    fs::write(&corpus, "Ab!").unwrap();
+    let before = fs::read_to_string(&layout).unwrap();
+
+    let output = run_optimize(
+        &layout,
+        &corpus,
+        &config.paths.qmk_firmware.unwrap(),
+        &["--apply", "9"],
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No suggestion #9"));
+    assert_eq!(fs::read_to_string(&layout).unwrap(), before);
+}
@@ -0,0 +1,203 @@
+//! End-to-end tests for `--quiet`, `--json` and exit codes across commands.
+
+#![cfg(feature = "tui")]
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Runs lazyqmk with an empty config directory.
+fn run(args: &[&str], config_dir: &Path) -> Output {
+    Command::new(lazyqmk_bin())
+        .args(args)
+        .env("LAZYQMK_CONFIG_DIR", config_dir)
+        .output()
+        .expect("Failed to execute command")
+}
+
+fn path_str(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+fn stdout_json(output: &Output) -> serde_json::Value {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    serde_json::from_str(&stdout).unwrap_or_else(|e| panic!("Invalid JSON ({e}): {stdout}"))
+}
+
+#[test]
+fn test_missing_qmk_path_is_config_error() {
+    let (layout_path, temp) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let config_dir = temp.path().join("config");
+
+    let output = run(&["build", path_str(&layout_path)], &config_dir);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("QMK firmware path not configured"));
+
+    let output = run(&["build", path_str(&layout_path), "--json"], &config_dir);
+    assert_eq!(output.status.code(), Some(4));
+    let error = &stdout_json(&output)["error"];
+    assert_eq!(error["kind"], "config");
+    assert_eq!(error["exit_code"], 4);
+    assert!(error["message"]
+        .as_str()
+        .unwrap()
+        .contains("QMK firmware path not configured"));
+}
+
+#[test]
+fn test_json_error_kinds() {
+    let temp = tempfile::tempdir().unwrap();
+    let missing = temp.path().join("missing.json");
+
+    let output = run(
+        &["validate", path_str(&missing), "--json"],
+        &temp.path().join("config"),
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stdout_json(&output)["error"]["kind"], "io");
+
+    let output = run(
+        &["show-help", "no_such_topic", "--json"],
+        &temp.path().join("config"),
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout_json(&output)["error"]["kind"], "validation");
+}
+
+#[test]
+fn test_failed_report_is_the_only_json_document() {
+    let (layout_path, temp) = create_temp_layout_file(&test_layout_with_invalid_keycode());
+
+    let output = run(
+        &["validate", path_str(&layout_path), "--json"],
+        &temp.path().join("config"),
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let report = stdout_json(&output);
+    assert_eq!(report["valid"], false);
+    assert!(report.get("error").is_none());
+}
+
+#[test]
+fn test_quiet_suppresses_output() {
+    let (layout_path, temp) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let (config, _config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let out_dir = temp.path().join("out");
+
+    let output = run(
+        &[
+            "--quiet",
+            "generate",
+            path_str(&layout_path),
+            "--qmk-path",
+            path_str(&qmk_path),
+            "--out-dir",
+            path_str(&out_dir),
+        ],
+        &temp.path().join("config"),
+    );
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(output.stdout.is_empty());
+    assert!(out_dir.join("keymap.c").exists());
+
+    // Errors still reach stderr
+    let output = run(
+        &[
+            "validate",
+            "-q",
+            path_str(&temp.path().join("missing.json")),
+        ],
+        &temp.path().join("config"),
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: "));
+}
+
+#[test]
+fn test_generate_and_export_json() {
+    let (layout_path, temp) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let (config, _config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let out_dir = temp.path().join("out");
+
+    let output = run(
+        &[
+            "generate",
+            path_str(&layout_path),
+            "--qmk-path",
+            path_str(&qmk_path),
+            "--out-dir",
+            path_str(&out_dir),
+            "--json",
+        ],
+        &temp.path().join("config"),
+    );
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let response = stdout_json(&output);
+    let files: Vec<&str> = response["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| file.as_str().unwrap())
+        .collect();
+    assert!(files.contains(&path_str(&out_dir.join("keymap.c"))));
+    assert!(files.contains(&path_str(&out_dir.join("config.h"))));
+
+    let export_path = temp.path().join("layout.md");
+    let output = run(
+        &[
+            "export",
+            path_str(&layout_path),
+            "--qmk-path",
+            path_str(&qmk_path),
+            "--output",
+            path_str(&export_path),
+            "--json",
+        ],
+        &temp.path().join("config"),
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let response = stdout_json(&output);
+    assert_eq!(response["output"], path_str(&export_path));
+    assert_eq!(response["format"], "md");
+}
+
+#[test]
+fn test_show_help_json() {
+    let temp = tempfile::tempdir().unwrap();
+
+    let output = run(&["show-help", "--json"], temp.path());
+    assert_eq!(output.status.code(), Some(0));
+    let topics = stdout_json(&output);
+    assert!(topics
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|topic| topic["topic"] == "main"));
+
+    let output = run(&["show-help", "main", "--json"], temp.path());
+    assert_eq!(output.status.code(), Some(0));
+    assert!(!stdout_json(&output)["bindings"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+}
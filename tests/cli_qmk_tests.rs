@@ -5,6 +5,8 @@
 //! - `list-layouts`: List layout variants for a specific keyboard with key counts
 //! - `geometry`: Display coordinate mappings for keyboard layout
 
+#![cfg(feature = "tui")]
+
 use std::path::PathBuf;
 use std::process::Command;
 
@@ -240,6 +242,70 @@ fn test_list_keyboards_invalid_qmk_path() {
     );
 }
 
+/// Test: keyboards from configured keyboard roots are listed and resolved
+#[test]
+fn test_list_keyboards_and_layouts_from_keyboard_root() {
+    let fixture_path = mock_qmk_fixture();
+    let config_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let root = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let board_dir = root.path().join("myvendor/board");
+    std::fs::create_dir_all(&board_dir).unwrap();
+    let keyboard_json = serde_json::json!({
+        "keyboard_name": "board",
+        "layouts": {
+            "LAYOUT_mine": {
+                "layout": [
+                    {"matrix": [0, 0], "x": 0, "y": 0},
+                    {"matrix": [0, 1], "x": 1, "y": 0}
+                ]
+            }
+        }
+    });
+    std::fs::write(board_dir.join("keyboard.json"), keyboard_json.to_string()).unwrap();
+
+    let run = |args: &[&str]| {
+        Command::new(lazyqmk_bin())
+            .args(args)
+            .env("LAZYQMK_CONFIG_DIR", config_dir.path())
+            .env("LAZYQMK_QMK_FIXTURE", &fixture_path)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(&[
+        "config",
+        "set",
+        "--keyboard-root",
+        root.path().to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = run(&["list-keyboards", "--qmk-path", "dummy", "--json"]);
+    assert_eq!(output.status.code(), Some(0));
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let keyboards = result["keyboards"].as_array().unwrap();
+    assert!(keyboards.contains(&serde_json::json!("myvendor/board")));
+    assert!(keyboards.contains(&serde_json::json!("crkbd")));
+
+    let output = run(&[
+        "list-layouts",
+        "--qmk-path",
+        "dummy",
+        "--keyboard",
+        "myvendor/board",
+        "--json",
+    ]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["layouts"][0]["name"], "LAYOUT_mine");
+    assert_eq!(result["layouts"][0]["key_count"], 2);
+}
+
 // ============================================================================
 // list-layouts TESTS
 // ============================================================================
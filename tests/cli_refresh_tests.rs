@@ -0,0 +1,163 @@
+//! End-to-end tests for `lazyqmk refresh` command.
+
+#![cfg(feature = "tui")]
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Runs `lazyqmk refresh --json` with the given extra arguments.
+fn run_refresh(workspace: &Path, qmk_path: &Path, extra: &[&str]) -> (Output, serde_json::Value) {
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "refresh",
+            "--workspace",
+            workspace.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+            "--json",
+        ])
+        .args(extra)
+        .output()
+        .expect("Failed to execute command");
+    let result = serde_json::from_slice(&output.stdout).unwrap_or_else(|e| {
+        panic!(
+            "Should parse JSON output ({e}). stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+    (output, result)
+}
+
+/// Runs git in `dir`, panicking on failure.
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .expect("Failed to run git");
+    assert!(
+        status.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&status.stderr)
+    );
+}
+
+#[test]
+fn test_refresh_reports_broken_layouts() {
+    let (config, _config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let workspace = TempDir::new().unwrap();
+
+    write_layout_file(
+        &test_layout_basic(2, 3),
+        &workspace.path().join("good.json"),
+    )
+    .unwrap();
+    let mut broken = test_layout_basic(2, 3);
+    broken.metadata.layout_variant = Some("LAYOUT_missing".to_string());
+    write_layout_file(&broken, &workspace.path().join("broken.json")).unwrap();
+
+    let (output, result) = run_refresh(workspace.path(), &qmk_path, &[]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(result["qmk"]["status"], "skipped");
+    assert_eq!(result["broken"], 1);
+    assert_eq!(result["layouts"][0]["file"], "broken.json");
+    assert_eq!(result["layouts"][0]["status"], "broken");
+    assert!(result["layouts"][0]["errors"][0]
+        .as_str()
+        .unwrap()
+        .contains("LAYOUT_missing"));
+    assert_eq!(result["layouts"][1]["file"], "good.json");
+    assert_eq!(result["layouts"][1]["status"], "ok");
+}
+
+#[test]
+fn test_refresh_generate_writes_firmware() {
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let workspace = TempDir::new().unwrap();
+    let out_dir = config_temp.path().join("output");
+
+    write_layout_file(
+        &test_layout_basic(2, 3),
+        &workspace.path().join("good.json"),
+    )
+    .unwrap();
+
+    let (output, result) = run_refresh(
+        workspace.path(),
+        &qmk_path,
+        &[
+            "--no-update",
+            "--generate",
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+        ],
+    );
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(result["qmk"]["reason"], "--no-update");
+    let generated = result["layouts"][0]["generated"].as_str().unwrap();
+    assert!(Path::new(generated).join("keymap.c").exists());
+    assert!(Path::new(generated).starts_with(&out_dir));
+    assert!(qmk_path
+        .join("keyboards/test_keyboard/keymaps/test_keymap/keymap.c")
+        .exists());
+}
+
+#[test]
+fn test_refresh_pulls_checkout_and_revalidates() {
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let upstream = config.paths.qmk_firmware.unwrap();
+    git(&upstream, &["init", "-q"]);
+    git(&upstream, &["add", "-A"]);
+    git(&upstream, &["commit", "-q", "-m", "initial"]);
+
+    let checkout = config_temp.path().join("checkout");
+    git(
+        config_temp.path(),
+        &["clone", "-q", upstream.to_str().unwrap(), "checkout"],
+    );
+
+    let workspace = TempDir::new().unwrap();
+    write_layout_file(
+        &test_layout_basic(2, 3),
+        &workspace.path().join("good.json"),
+    )
+    .unwrap();
+
+    let (output, result) = run_refresh(workspace.path(), &checkout, &[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(result["qmk"]["status"], "up_to_date");
+
+    // Upstream renames the layout macro
+    let info_path = upstream.join("keyboards/test_keyboard/info.json");
+    let info = fs::read_to_string(&info_path).unwrap();
+    fs::write(&info_path, info.replace("LAYOUT_test", "LAYOUT_renamed")).unwrap();
+    git(&upstream, &["commit", "-q", "-am", "rename layout"]);
+
+    let (output, result) = run_refresh(workspace.path(), &checkout, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(result["qmk"]["status"], "updated");
+    assert_ne!(result["qmk"]["from"], result["qmk"]["to"]);
+    assert_eq!(result["layouts"][0]["status"], "broken");
+}
@@ -1,5 +1,7 @@
 //! End-to-end tests for `lazyqmk tap-dance` command.
 
+#![cfg(feature = "tui")]
+
 use std::fs;
 use std::process::Command;
 
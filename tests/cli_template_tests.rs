@@ -1,5 +1,7 @@
 //! End-to-end tests for `lazyqmk template` commands.
 
+#![cfg(feature = "tui")]
+
 use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
@@ -45,6 +47,9 @@ fn cleanup_templates() {
 
 #[test]
 fn test_template_list_empty_directory() {
+    let _lock = TEMPLATE_TEST_LOCK.lock().unwrap();
+    cleanup_templates();
+
     let output = Command::new(lazyqmk_bin())
         .args(["template", "list"])
         .output()
@@ -424,6 +429,78 @@ fn test_template_apply_output_file_exists_fails() {
     );
 }
 
+#[test]
+fn test_template_apply_fills_placeholders() {
+    let _lock = TEMPLATE_TEST_LOCK.lock().unwrap();
+    cleanup_templates();
+
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[0].keys[0].keycode = "${OS_MOD}".to_string();
+    layout.layers[0].name = "${BASE_NAME}".to_string();
+    let (layout_path, _layout_temp) = create_temp_layout_file(&layout);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "template",
+            "save",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--name",
+            "placeholder_test_template",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(0));
+
+    let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let output_path = temp_dir.path().join("mac.json");
+
+    // Missing values fail without writing anything
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "template",
+            "apply",
+            "--name",
+            "placeholder_test_template",
+            "--out",
+            output_path.to_str().unwrap(),
+            "--set",
+            "OS_MOD=KC_LGUI",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("${BASE_NAME}"));
+    assert!(!output_path.exists());
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "template",
+            "apply",
+            "--name",
+            "placeholder_test_template",
+            "--out",
+            output_path.to_str().unwrap(),
+            "--set",
+            "OS_MOD=KC_LGUI",
+            "--set",
+            "BASE_NAME=Mac",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Applying with all values should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = fs::read_to_string(&output_path).expect("Failed to read output file");
+    assert!(content.contains("KC_LGUI"));
+    assert!(content.contains("Mac"));
+    assert!(!content.contains("${"));
+}
+
 // ============================================================================
 // Integration Tests
 // ============================================================================
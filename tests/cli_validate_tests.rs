@@ -1,5 +1,7 @@
 //! End-to-end tests for `lazyqmk validate` command.
 
+#![cfg(feature = "tui")]
+
 use std::process::Command;
 
 mod fixtures;
@@ -57,10 +59,13 @@ fn test_validate_valid_layout_json() {
 
     assert_eq!(result["valid"], true, "Should be valid");
     assert!(result["errors"].is_array(), "Should have errors array");
-    assert_eq!(
-        result["errors"].as_array().unwrap().len(),
-        0,
-        "Should have no errors"
+    assert!(
+        result["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|msg| msg["severity"] == "warning"),
+        "Should have no errors, only lint warnings"
     );
     assert!(result["checks"].is_object(), "Should have checks object");
 }
@@ -251,7 +256,9 @@ fn test_validate_with_tap_dances() {
 
 #[test]
 fn test_validate_with_layer_refs() {
-    let layout = test_layout_with_layer_refs();
+    let mut layout = test_layout_with_layer_refs();
+    // Let the TG key on layer 0 fall through so the toggled layer can be left
+    layout.layers[1].keys[2].keycode = "KC_TRNS".to_string();
     let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
 
     let output = Command::new(lazyqmk_bin())
@@ -281,3 +288,31 @@ fn test_validate_with_layer_refs() {
         "Layer refs check should pass"
     );
 }
+
+#[test]
+fn test_validate_positional_file() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["validate", layout_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Positional layout file should be accepted. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_validate_requires_layout_file() {
+    let output = Command::new(lazyqmk_bin())
+        .args(["validate"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_ne!(output.status.code(), Some(0));
+}
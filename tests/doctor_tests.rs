@@ -64,7 +64,7 @@ fn test_qmk_firmware_file_not_directory() {
 }
 
 #[test]
-#[ignore] // Requires actual QMK submodule
+#[ignore = "requires actual QMK submodule"]
 fn test_qmk_firmware_valid_directory() {
     let checker = DependencyChecker::new();
     let qmk_path = Path::new("qmk_firmware");
@@ -88,8 +88,8 @@ fn test_custom_timeout_placeholder() {
     // `with_timeout` was removed in the dead-code cleanup since `command_timeout`
     // was never read. This test stays as a placeholder so the test surface
     // doesn't change; the timeout arg is no longer necessary.
-    let _checker = DependencyChecker::new();
-    let statuses = _checker.check_all(None);
+    let checker = DependencyChecker::new();
+    let statuses = checker.check_all(None);
     assert_eq!(statuses.len(), 4);
 }
 
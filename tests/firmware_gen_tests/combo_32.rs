@@ -31,17 +31,14 @@ fn test_combo_settings_exhaust_32_combos_generated() {
     // Walk the grid top-to-bottom, two adjacent columns per combo. With
     // 32 combos × 2 positions = 64 keys, every pair is unique.
     for combo_idx in 0..COMBO_CAP {
-        let row = (combo_idx / (COLS / 2)) as u8;
-        let pair_col = (combo_idx % (COLS / 2)) as u8;
+        let row = u8::try_from(combo_idx / (COLS / 2)).unwrap();
+        let pair_col = u8::try_from(combo_idx % (COLS / 2)).unwrap();
         let col1 = pair_col * 2;
         let col2 = col1 + 1;
 
         let action = actions[combo_idx % actions.len()].clone();
-        let combo = ComboDefinition::new(
-            Position::new(row, col1),
-            Position::new(row, col2),
-            action,
-        );
+        let combo =
+            ComboDefinition::new(Position::new(row, col1), Position::new(row, col2), action);
         layout.combo_settings.combos.push(combo);
     }
 
@@ -125,8 +122,7 @@ fn test_combo_settings_exhaust_32_combos_generated() {
     //    the layout has custom colors, SOLID_COLOR otherwise. Both are valid.
     assert!(
         keymap_content.contains("rgb_matrix_mode_noeeprom(RGB_MATRIX_SOLID_COLOR)")
-            || keymap_content
-                .contains("rgb_matrix_mode_noeeprom(RGB_MATRIX_TUI_LAYER_COLORS)"),
+            || keymap_content.contains("rgb_matrix_mode_noeeprom(RGB_MATRIX_TUI_LAYER_COLORS)"),
         "DisableEffects action should emit rgb_matrix_mode_noeeprom(...); got: {}",
         keymap_content
     );
@@ -7,6 +7,7 @@
 use super::helpers::*;
 use lazyqmk::firmware::{FirmwareGenerator, FirmwareValidator};
 use lazyqmk::keycode_db::KeycodeDb;
+use lazyqmk::models::RgbColor;
 use std::fs;
 use std::path::PathBuf;
 
@@ -132,6 +133,67 @@ fn test_generation_led_ordering() {
     assert!(kc2_pos < kc5_pos, "KC_2 should appear before KC_5");
 }
 
+#[test]
+fn test_generation_includes_underglow_leds() {
+    // Arrange
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let mut layout = create_test_layout();
+    layout.rgb_overlay_ripple.enabled = true;
+    let geometry = create_test_geometry_with_underglow();
+    let mut mapping = create_test_mapping();
+    mapping.led_to_matrix = vec![(0, 0); 2];
+    mapping.matrix_to_led.clear();
+    for key in &geometry.keys {
+        mapping.led_to_matrix.push(key.matrix_position);
+        mapping
+            .matrix_to_led
+            .insert(key.matrix_position, key.led_index);
+    }
+    let config = create_test_config(&temp_dir);
+
+    let keycode_db = KeycodeDb::load().expect("Failed to load keycode database");
+
+    // Act
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let (keymap_path, _) = generator.generate().expect("Generation should succeed");
+    let content = fs::read_to_string(&keymap_path).expect("Should be able to read keymap.c");
+
+    // Assert - color table covers all 8 LEDs; underglow takes the layer color
+    let table_start = content
+        .find("layer_base_colors[2][8][3] = {")
+        .expect("Color table should cover underglow LEDs");
+    let layer1 = &content[table_start..]
+        .split("    {\n")
+        .nth(2)
+        .expect("Should find layer 1 colors");
+    let colors: Vec<&str> = layer1
+        .lines()
+        .take(8)
+        .map(|line| line.trim().trim_end_matches(','))
+        .collect();
+    assert_eq!(colors.len(), 8);
+    assert!(
+        colors.iter().all(|color| *color == colors[2]),
+        "Underglow should match uncolored layer 1 keys: {colors:?}"
+    );
+
+    // A fixed underglow color replaces the layer color on underglow LEDs only
+    layout.layers[1].underglow_color = Some(RgbColor::new(255, 0, 0));
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let (keymap_path, _) = generator.generate().expect("Generation should succeed");
+    let content = fs::read_to_string(&keymap_path).expect("Should be able to read keymap.c");
+    let table_start = content.find("layer_base_colors[2][8][3] = {").unwrap();
+    let layer1 = content[table_start..].split("    {\n").nth(2).unwrap();
+    let lines: Vec<&str> = layer1.lines().take(3).map(str::trim).collect();
+    assert_eq!(lines[0], "{255,   0,   0},");
+    assert_eq!(lines[1], "{255,   0,   0},");
+    assert_eq!(lines[2], colors[2].to_string() + ",");
+
+    // Underglow LEDs borrow the matrix position of the nearest key
+    assert!(content.contains("lazyqmk_led_to_matrix_row[8] = { 1, 0, 0, 0, 0, 1, 1, 1 };"));
+    assert!(content.contains("lazyqmk_led_to_matrix_col[8] = { 2, 0, 0, 1, 2, 0, 1, 2 };"));
+}
+
 #[test]
 fn test_generation_with_categories() {
     // Arrange
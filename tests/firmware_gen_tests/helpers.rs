@@ -3,19 +3,21 @@
 //! Each topic module does `use super::helpers::*;` to bring these into scope.
 
 use chrono::Utc;
-use lazyqmk::config::{BuildConfig, Config, PathConfig, UiConfig};
+use lazyqmk::config::{
+    BuildConfig, Config, GitConfig, HooksConfig, LintConfig, PathConfig, UiConfig, WebConfig,
+};
 use lazyqmk::models::{
-    KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, LayoutMetadata, Position,
-    RgbColor, VisualLayoutMapping,
+    EncoderMap, KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, LayoutMetadata,
+    LedGeometry, Position, RgbColor, VisualLayoutMapping,
 };
 use std::collections::HashMap;
 
 // Re-exported for topic modules via `use super::helpers::*;`.
-pub(super) use std::fs;
-pub(super) use tempfile::TempDir;
+pub use std::fs;
+pub use tempfile::TempDir;
 
 /// Creates a minimal test layout for firmware generation.
-pub(super) fn create_test_layout() -> Layout {
+pub fn create_test_layout() -> Layout {
     let metadata = LayoutMetadata {
         name: "Test Layout".to_string(),
         description: "Integration test layout".to_string(),
@@ -29,6 +31,11 @@ pub(super) fn create_test_layout() -> Layout {
         keyboard: Some("test_kb".to_string()),
         keymap_name: Some("test_keymap".to_string()),
         output_format: Some("uf2".to_string()),
+        converter: None,
+        targets: Vec::new(),
+        placeholders: Vec::new(),
+        geometry: None,
+        qmk_tree: None,
     };
 
     // Create a simple 2x3 layout (6 keys)
@@ -43,6 +50,7 @@ pub(super) fn create_test_layout() -> Layout {
                 category_id: None,
                 combo_participant: false,
                 description: None,
+                locked: false,
             });
         }
     }
@@ -55,6 +63,8 @@ pub(super) fn create_test_layout() -> Layout {
         category_id: None,
         keys: keys.clone(),
         layer_colors_enabled: true,
+        underglow_color: None,
+        encoder_map: EncoderMap::default(),
     };
 
     // Second layer with some transparent keys
@@ -70,6 +80,8 @@ pub(super) fn create_test_layout() -> Layout {
         category_id: None,
         keys: layer1_keys,
         layer_colors_enabled: true,
+        underglow_color: None,
+        encoder_map: EncoderMap::default(),
     };
 
     Layout {
@@ -80,6 +92,8 @@ pub(super) fn create_test_layout() -> Layout {
         idle_effect_settings: lazyqmk::models::IdleEffectSettings::default(),
         rgb_overlay_ripple: lazyqmk::models::RgbOverlayRippleSettings::default(),
         palette_fx: lazyqmk::models::PaletteFxSettings::default(),
+        rgb_conditions: lazyqmk::models::RgbConditionalSettings::default(),
+        firmware_features: lazyqmk::models::FirmwareFeatures::default(),
         tap_hold_settings: lazyqmk::models::TapHoldSettings::default(),
         rgb_enabled: true,
         rgb_brightness: lazyqmk::models::RgbBrightness::default(),
@@ -87,12 +101,20 @@ pub(super) fn create_test_layout() -> Layout {
         rgb_matrix_default_speed: 127,
         rgb_timeout_ms: 0,
         tap_dances: vec![],
+        macros: vec![],
+        custom_keycodes: Vec::new(),
+        key_overrides: Vec::new(),
+        alt_repeat_keys: Vec::new(),
+        unicode_map: Vec::new(),
+        unicode_input_modes: Vec::new(),
+        usage_counting: false,
+        editing_helpers: lazyqmk::models::EditingHelpers::default(),
         combo_settings: lazyqmk::models::ComboSettings::default(),
     }
 }
 
 /// Creates a test keyboard geometry matching the test layout.
-pub(super) fn create_test_geometry() -> KeyboardGeometry {
+pub fn create_test_geometry() -> KeyboardGeometry {
     let mut keys = Vec::new();
 
     // Map positions to matrix coordinates and LED indices
@@ -108,6 +130,7 @@ pub(super) fn create_test_geometry() -> KeyboardGeometry {
                 width: 1.0,
                 height: 1.0,
                 rotation: 0.0,
+                label: None,
             };
             keys.push(key_geo);
         }
@@ -120,11 +143,50 @@ pub(super) fn create_test_geometry() -> KeyboardGeometry {
         matrix_cols: 3,
         keys,
         encoder_count: 0,
+        leds: Vec::new(),
+        processor: None,
+        features: std::collections::BTreeMap::new(),
+    }
+}
+
+/// Creates the test geometry with two underglow LEDs wired before the keys.
+///
+/// LED 0 sits near key (1, 2) and LED 1 near key (0, 0); key LEDs follow as
+/// indices 2-7.
+pub fn create_test_geometry_with_underglow() -> KeyboardGeometry {
+    let mut geometry = create_test_geometry();
+    geometry.leds = vec![
+        LedGeometry {
+            led_index: 0,
+            matrix_position: None,
+            x: 210,
+            y: 64,
+            flags: 2,
+        },
+        LedGeometry {
+            led_index: 1,
+            matrix_position: None,
+            x: 0,
+            y: 4,
+            flags: 2,
+        },
+    ];
+    for key in &mut geometry.keys {
+        key.led_index += 2;
+        let (row, col) = key.matrix_position;
+        geometry.leds.push(LedGeometry {
+            led_index: key.led_index,
+            matrix_position: Some((row, col)),
+            x: col * 100,
+            y: row * 60,
+            flags: 4,
+        });
     }
+    geometry
 }
 
 /// Creates a test visual layout mapping.
-pub(super) fn create_test_mapping() -> VisualLayoutMapping {
+pub fn create_test_mapping() -> VisualLayoutMapping {
     let mut led_to_matrix = Vec::new();
     let mut matrix_to_led = HashMap::new();
     let mut layout_to_matrix = Vec::new();
@@ -162,7 +224,7 @@ pub(super) fn create_test_mapping() -> VisualLayoutMapping {
 }
 
 /// Creates a test config with temporary directory.
-pub(super) fn create_test_config(temp_dir: &TempDir) -> Config {
+pub fn create_test_config(temp_dir: &TempDir) -> Config {
     let qmk_path = temp_dir.path().join("qmk_firmware");
     fs::create_dir_all(&qmk_path).unwrap();
 
@@ -177,10 +239,19 @@ pub(super) fn create_test_config(temp_dir: &TempDir) -> Config {
     Config {
         paths: PathConfig {
             qmk_firmware: Some(qmk_path),
+            backup_dir: None,
+            keyboard_roots: Vec::new(),
+            qmk_trees: Vec::new(),
         },
         build: BuildConfig {
             output_dir: temp_dir.path().to_path_buf(),
+            userspace: None,
         },
         ui: UiConfig::default(),
+        web: WebConfig::default(),
+        git: GitConfig::default(),
+        hooks: HooksConfig::default(),
+        lints: LintConfig::default(),
+        safe_mode: false,
     }
 }
@@ -618,7 +618,8 @@ fn test_rgb_overlay_ripple_color_mode_honored_in_background_lighting_path() {
     hs_layout.rgb_overlay_ripple.enabled = true;
     hs_layout.rgb_overlay_ripple.color_mode = lazyqmk::models::RippleColorMode::HueShift;
     hs_layout.rgb_overlay_ripple.hue_shift_deg = 60;
-    let hs_generator = FirmwareGenerator::new(&hs_layout, &geometry, &mapping, &config, &keycode_db);
+    let hs_generator =
+        FirmwareGenerator::new(&hs_layout, &geometry, &mapping, &config, &keycode_db);
     let hs_keymap = hs_generator
         .generate_keymap_c()
         .expect("Should generate keymap.c");
@@ -2,12 +2,14 @@
 #![allow(dead_code)] // Some fixtures reserved for future tests
 
 use chrono::{TimeZone, Utc};
-use lazyqmk::config::{BuildConfig, Config, PathConfig, UiConfig};
+use lazyqmk::config::{
+    BuildConfig, Config, GitConfig, HooksConfig, LintConfig, PathConfig, UiConfig, WebConfig,
+};
 use lazyqmk::models::{
-    Category, ComboSettings, IdleEffectSettings, KeyDefinition, KeyGeometry, KeyboardGeometry,
-    Layer, Layout, LayoutMetadata, PaletteFxSettings, Position, RgbBrightness, RgbColor,
-    RgbMatrixEffect, RgbOverlayRippleSettings, RgbSaturation, TapDanceAction, TapHoldSettings,
-    UncoloredKeyBehavior, VisualLayoutMapping,
+    Category, ComboSettings, EncoderMap, FirmwareFeatures, IdleEffectSettings, KeyDefinition,
+    KeyGeometry, KeyboardGeometry, Layer, Layout, LayoutMetadata, PaletteFxSettings, Position,
+    RgbBrightness, RgbColor, RgbConditionalSettings, RgbMatrixEffect, RgbOverlayRippleSettings,
+    RgbSaturation, TapDanceAction, TapHoldSettings, UncoloredKeyBehavior, VisualLayoutMapping,
 };
 use std::collections::HashMap;
 use std::fs;
@@ -41,6 +43,11 @@ pub fn test_layout_basic(rows: usize, cols: usize) -> Layout {
         keyboard: Some("test_keyboard".to_string()),
         keymap_name: Some("test_keymap".to_string()),
         output_format: Some("uf2".to_string()),
+        converter: None,
+        targets: Vec::new(),
+        placeholders: Vec::new(),
+        geometry: None,
+        qmk_tree: None,
     };
 
     // Layer 0: Base layer with simple keycodes
@@ -59,6 +66,7 @@ pub fn test_layout_basic(rows: usize, cols: usize) -> Layout {
                 category_id: None,
                 combo_participant: false,
                 description: None,
+                locked: false,
             });
         }
     }
@@ -71,6 +79,8 @@ pub fn test_layout_basic(rows: usize, cols: usize) -> Layout {
         category_id: None,
         keys: base_keys,
         layer_colors_enabled: true,
+        underglow_color: None,
+        encoder_map: EncoderMap::default(),
     };
 
     // Layer 1: Function layer with some transparent keys
@@ -93,6 +103,7 @@ pub fn test_layout_basic(rows: usize, cols: usize) -> Layout {
                 category_id: None,
                 combo_participant: false,
                 description: None,
+                locked: false,
             });
         }
     }
@@ -105,6 +116,8 @@ pub fn test_layout_basic(rows: usize, cols: usize) -> Layout {
         category_id: None,
         keys: func_keys,
         layer_colors_enabled: true,
+        underglow_color: None,
+        encoder_map: EncoderMap::default(),
     };
 
     Layout {
@@ -120,8 +133,18 @@ pub fn test_layout_basic(rows: usize, cols: usize) -> Layout {
         idle_effect_settings: IdleEffectSettings::default(),
         rgb_overlay_ripple: RgbOverlayRippleSettings::default(),
         palette_fx: PaletteFxSettings::default(),
+        rgb_conditions: RgbConditionalSettings::default(),
+        firmware_features: FirmwareFeatures::default(),
         tap_hold_settings: TapHoldSettings::default(),
         tap_dances: vec![],
+        macros: vec![],
+        custom_keycodes: Vec::new(),
+        key_overrides: Vec::new(),
+        alt_repeat_keys: Vec::new(),
+        unicode_map: Vec::new(),
+        unicode_input_modes: Vec::new(),
+        usage_counting: false,
+        editing_helpers: lazyqmk::models::EditingHelpers::default(),
         combo_settings: ComboSettings::default(),
     }
 }
@@ -235,6 +258,7 @@ pub fn test_geometry_basic(rows: usize, cols: usize) -> KeyboardGeometry {
                 width: 1.0,
                 height: 1.0,
                 rotation: 0.0,
+                label: None,
             };
             keys.push(key_geo);
         }
@@ -247,6 +271,9 @@ pub fn test_geometry_basic(rows: usize, cols: usize) -> KeyboardGeometry {
         matrix_cols: cols as u8,
         keys,
         encoder_count: 0,
+        leds: Vec::new(),
+        processor: None,
+        features: std::collections::BTreeMap::new(),
     }
 }
 
@@ -361,11 +388,20 @@ pub fn temp_config_with_qmk(qmk_path: Option<PathBuf>) -> (Config, TempDir) {
     let config = Config {
         paths: PathConfig {
             qmk_firmware: Some(qmk_path),
+            backup_dir: Some(temp_dir.path().join("backups")),
+            keyboard_roots: Vec::new(),
+            qmk_trees: Vec::new(),
         },
         build: BuildConfig {
             output_dir: temp_dir.path().to_path_buf(),
+            userspace: None,
         },
         ui: UiConfig::default(),
+        web: WebConfig::default(),
+        git: GitConfig::default(),
+        hooks: HooksConfig::default(),
+        lints: LintConfig::default(),
+        safe_mode: false,
     };
 
     (config, temp_dir)
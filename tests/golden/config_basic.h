@@ -1,10 +1,9 @@
 // Generated by lazyqmk
 // Layout: Test Layout
-// Generated: <timestamp>
 
 #pragma once
 
-// Add keymap-specific configuration here
+// Add keymap-specific configuration in the user region at the end
 
 // Idle Effect Configuration
 #define LQMK_IDLE_TIMEOUT_MS 60000
@@ -16,4 +15,7 @@
 #    undef RGB_MATRIX_DEFAULT_MODE
 #    define RGB_MATRIX_DEFAULT_MODE RGB_MATRIX_TUI_LAYER_COLORS
 #    define LAYER_BASE_COLORS_LAYER_COUNT 2
-#endif
\ No newline at end of file
+#endif
+
+// LAZYQMK:BEGIN user
+// LAZYQMK:END user
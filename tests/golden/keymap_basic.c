@@ -109,3 +109,6 @@ void keyboard_post_init_user(void) {
 #endif // LQMK_IDLE_TIMEOUT_MS
 #endif // RGB_MATRIX_ENABLE
 
+
+// LAZYQMK:BEGIN user
+// LAZYQMK:END user
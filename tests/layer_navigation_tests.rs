@@ -4,11 +4,15 @@
 //! - Tab cycles forward through layers (0 -> 1 -> 2 -> 0)
 //! - Shift+Tab cycles backward through layers (0 -> 2 -> 1 -> 0)
 
+#![cfg(feature = "tui")]
+
 use chrono::Utc;
-use lazyqmk::config::{BuildConfig, Config, PathConfig, UiConfig};
+use lazyqmk::config::{
+    BuildConfig, Config, GitConfig, HooksConfig, LintConfig, PathConfig, UiConfig, WebConfig,
+};
 use lazyqmk::models::{
-    KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, LayoutMetadata, Position,
-    RgbColor, VisualLayoutMapping,
+    EncoderMap, KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, LayoutMetadata,
+    Position, RgbColor, VisualLayoutMapping,
 };
 use lazyqmk::tui::AppState;
 use std::collections::HashMap;
@@ -28,6 +32,11 @@ fn create_test_layout_with_layers() -> Layout {
         keyboard: Some("test_kb".to_string()),
         keymap_name: Some("test_keymap".to_string()),
         output_format: Some("uf2".to_string()),
+        converter: None,
+        targets: Vec::new(),
+        placeholders: Vec::new(),
+        geometry: None,
+        qmk_tree: None,
     };
 
     // Create a simple 2x3 layout (6 keys)
@@ -42,6 +51,7 @@ fn create_test_layout_with_layers() -> Layout {
                 category_id: None,
                 combo_participant: false,
                 description: None,
+                locked: false,
             });
         }
     }
@@ -55,6 +65,8 @@ fn create_test_layout_with_layers() -> Layout {
         category_id: None,
         keys: keys.clone(),
         layer_colors_enabled: true,
+        underglow_color: None,
+        encoder_map: EncoderMap::default(),
     };
 
     let layer1 = Layer {
@@ -65,6 +77,8 @@ fn create_test_layout_with_layers() -> Layout {
         category_id: None,
         keys: keys.clone(),
         layer_colors_enabled: true,
+        underglow_color: None,
+        encoder_map: EncoderMap::default(),
     };
 
     let layer2 = Layer {
@@ -75,6 +89,8 @@ fn create_test_layout_with_layers() -> Layout {
         category_id: None,
         keys: keys.clone(),
         layer_colors_enabled: true,
+        underglow_color: None,
+        encoder_map: EncoderMap::default(),
     };
 
     Layout {
@@ -85,6 +101,8 @@ fn create_test_layout_with_layers() -> Layout {
         idle_effect_settings: lazyqmk::models::IdleEffectSettings::default(),
         rgb_overlay_ripple: lazyqmk::models::RgbOverlayRippleSettings::default(),
         palette_fx: lazyqmk::models::PaletteFxSettings::default(),
+        rgb_conditions: lazyqmk::models::RgbConditionalSettings::default(),
+        firmware_features: lazyqmk::models::FirmwareFeatures::default(),
         tap_hold_settings: lazyqmk::models::TapHoldSettings::default(),
         rgb_enabled: true,
         rgb_brightness: lazyqmk::models::RgbBrightness::default(),
@@ -92,6 +110,14 @@ fn create_test_layout_with_layers() -> Layout {
         rgb_matrix_default_speed: 127,
         rgb_timeout_ms: 0,
         tap_dances: vec![],
+        macros: vec![],
+        custom_keycodes: Vec::new(),
+        key_overrides: Vec::new(),
+        alt_repeat_keys: Vec::new(),
+        unicode_map: Vec::new(),
+        unicode_input_modes: Vec::new(),
+        usage_counting: false,
+        editing_helpers: lazyqmk::models::EditingHelpers::default(),
         combo_settings: lazyqmk::models::ComboSettings::default(),
     }
 }
@@ -112,6 +138,7 @@ fn create_test_geometry() -> KeyboardGeometry {
                 width: 1.0,
                 height: 1.0,
                 rotation: 0.0,
+                label: None,
             };
             keys.push(key_geo);
         }
@@ -124,6 +151,9 @@ fn create_test_geometry() -> KeyboardGeometry {
         matrix_cols: 3,
         keys,
         encoder_count: 0,
+        leds: Vec::new(),
+        processor: None,
+        features: std::collections::BTreeMap::new(),
     }
 }
 
@@ -167,11 +197,22 @@ fn create_test_mapping() -> VisualLayoutMapping {
 /// Creates a test config
 fn create_test_config() -> Config {
     Config {
-        paths: PathConfig { qmk_firmware: None },
+        paths: PathConfig {
+            qmk_firmware: None,
+            backup_dir: None,
+            keyboard_roots: Vec::new(),
+            qmk_trees: Vec::new(),
+        },
         build: BuildConfig {
             output_dir: std::env::temp_dir(),
+            userspace: None,
         },
         ui: UiConfig::default(),
+        web: WebConfig::default(),
+        git: GitConfig::default(),
+        hooks: HooksConfig::default(),
+        lints: LintConfig::default(),
+        safe_mode: false,
     }
 }
 
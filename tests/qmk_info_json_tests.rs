@@ -30,7 +30,7 @@ fn test_parse_crkbd_info_json() {
     }
 
     let qmk_path = get_qmk_path();
-    let result = parse_keyboard_info_json(&qmk_path, "crkbd/rev1");
+    let result = parse_keyboard_info_json(&qmk_path, &[], "crkbd/rev1");
 
     assert!(
         result.is_ok(),
@@ -62,7 +62,7 @@ fn test_build_crkbd_geometry() {
     }
 
     let qmk_path = get_qmk_path();
-    let info = parse_keyboard_info_json(&qmk_path, "crkbd/rev1")
+    let info = parse_keyboard_info_json(&qmk_path, &[], "crkbd/rev1")
         .expect("Failed to parse crkbd/rev1 info.json");
 
     let layouts = extract_layout_names(&info);
@@ -108,7 +108,7 @@ fn test_build_crkbd_visual_mapping() {
     }
 
     let qmk_path = get_qmk_path();
-    let info = parse_keyboard_info_json(&qmk_path, "crkbd/rev1")
+    let info = parse_keyboard_info_json(&qmk_path, &[], "crkbd/rev1")
         .expect("Failed to parse crkbd/rev1 info.json");
     let layouts = extract_layout_names(&info);
     let layout_name = &layouts[0];
@@ -166,7 +166,7 @@ fn test_parse_multiple_keyboards() {
     let keyboards = vec!["crkbd", "ferris/sweep", "keebart/corne_choc_pro/standard"];
 
     for keyboard in keyboards {
-        let result = parse_keyboard_info_json(&qmk_path, keyboard);
+        let result = parse_keyboard_info_json(&qmk_path, &[], keyboard);
 
         if result.is_err() {
             eprintln!(
@@ -224,7 +224,7 @@ fn test_parse_split_config_keyboard() {
     let qmk_path = get_qmk_path();
 
     // Test 1upkeyboards/pi50/grid which has split configuration
-    let result = parse_keyboard_info_json(&qmk_path, "1upkeyboards/pi50/grid");
+    let result = parse_keyboard_info_json(&qmk_path, &[], "1upkeyboards/pi50/grid");
 
     assert!(
         result.is_ok(),
@@ -299,7 +299,7 @@ fn test_parse_keebart_corne_choc_pro() {
     let qmk_path = get_qmk_path();
 
     // Test keebart/corne_choc_pro/standard
-    let result = parse_keyboard_info_json(&qmk_path, "keebart/corne_choc_pro/standard");
+    let result = parse_keyboard_info_json(&qmk_path, &[], "keebart/corne_choc_pro/standard");
 
     assert!(
         result.is_ok(),
@@ -351,7 +351,7 @@ fn test_parse_keebart_corne_choc_pro() {
     );
 
     // Also test the mini variant if it exists
-    let mini_result = parse_keyboard_info_json(&qmk_path, "keebart/corne_choc_pro/mini");
+    let mini_result = parse_keyboard_info_json(&qmk_path, &[], "keebart/corne_choc_pro/mini");
     if let Ok(mini_info) = mini_result {
         let mini_layouts = extract_layout_names(&mini_info);
         println!("keebart/corne_choc_pro/mini layouts: {:?}", mini_layouts);
@@ -381,7 +381,7 @@ fn test_scan_keyboards_finds_crkbd() {
     }
 
     let qmk_path = get_qmk_path();
-    let keyboards = lazyqmk::parser::keyboard_json::scan_keyboards(&qmk_path)
+    let keyboards = lazyqmk::parser::keyboard_json::scan_keyboards(&qmk_path, &[])
         .expect("Failed to scan keyboards");
 
     assert!(
@@ -457,7 +457,7 @@ fn test_parse_json5_keyboard() {
     let qmk_path = get_qmk_path();
 
     // Test splitkb/aurora/lily58/rev1 which has JSON5 comments
-    let result = parse_keyboard_info_json(&qmk_path, "splitkb/aurora/lily58/rev1");
+    let result = parse_keyboard_info_json(&qmk_path, &[], "splitkb/aurora/lily58/rev1");
 
     assert!(
         result.is_ok(),
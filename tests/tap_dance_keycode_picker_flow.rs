@@ -1,4 +1,5 @@
 #![allow(missing_docs)]
+#![cfg(feature = "tui")]
 //! Test the flow when selecting TD() from the keycode picker
 
 use lazyqmk::config::Config;
@@ -258,6 +258,7 @@ fn test_get_orphaned_tap_dances() {
 // Inline Wizard Tests (TD parameterized keycode flow)
 // ============================================================================
 
+#[cfg(feature = "tui")]
 #[test]
 fn test_td_inline_wizard_two_way_flow() {
     // Test the inline wizard flow: TD() -> name -> single -> double (skip hold) -> final TD(name)
@@ -303,6 +304,7 @@ fn test_td_inline_wizard_two_way_flow() {
     assert_eq!(final_keycode.unwrap(), "TD(esc_caps, KC_ESC, KC_CAPS)");
 }
 
+#[cfg(feature = "tui")]
 #[test]
 fn test_td_inline_wizard_three_way_flow() {
     // Test the inline wizard flow with hold parameter
@@ -344,6 +346,7 @@ fn test_td_inline_wizard_three_way_flow() {
     );
 }
 
+#[cfg(feature = "tui")]
 #[test]
 fn test_lt_flow_regression() {
     // Ensure LT flow still works: LT() -> layer -> keycode
@@ -380,6 +383,7 @@ fn test_lt_flow_regression() {
     assert_eq!(final_keycode.unwrap(), "LT(@layer1, KC_SPC)");
 }
 
+#[cfg(feature = "tui")]
 #[test]
 fn test_pending_keycode_build_formats_correctly() {
     // Test that build_keycode produces correct format
@@ -9,25 +9,67 @@
 
 mod fixtures;
 
+#[path = "web_api_tests/audit.rs"]
+mod audit;
+#[path = "web_api_tests/backups.rs"]
+mod backups;
 #[path = "web_api_tests/build.rs"]
 mod build;
+#[path = "web_api_tests/bundles.rs"]
+mod bundles;
+#[path = "web_api_tests/categories.rs"]
+mod categories;
 #[path = "web_api_tests/config.rs"]
 mod config;
+#[path = "web_api_tests/events.rs"]
+mod events;
+#[path = "web_api_tests/fragments.rs"]
+mod fragments;
 #[path = "web_api_tests/generate.rs"]
 mod generate;
 #[path = "web_api_tests/geometry.rs"]
 mod geometry;
+#[path = "web_api_tests/git.rs"]
+mod git;
 #[path = "web_api_tests/health.rs"]
 mod health;
 #[path = "web_api_tests/helpers.rs"]
 mod helpers;
+#[path = "web_api_tests/history.rs"]
+mod history;
 #[path = "web_api_tests/keyboard_wizard.rs"]
 mod keyboard_wizard;
 #[path = "web_api_tests/keycodes.rs"]
 mod keycodes;
+#[path = "web_api_tests/keys.rs"]
+mod keys;
+#[path = "web_api_tests/layer_graph.rs"]
+mod layer_graph;
+#[path = "web_api_tests/layers.rs"]
+mod layers;
 #[path = "web_api_tests/layouts.rs"]
 mod layouts;
+#[path = "web_api_tests/live_preview.rs"]
+mod live_preview;
 #[path = "web_api_tests/preflight.rs"]
 mod preflight;
+#[path = "web_api_tests/read_only.rs"]
+mod read_only;
+#[path = "web_api_tests/resolved.rs"]
+mod resolved;
+#[path = "web_api_tests/sandbox.rs"]
+mod sandbox;
+#[path = "web_api_tests/search.rs"]
+mod search;
+#[path = "web_api_tests/shutdown.rs"]
+mod shutdown;
+#[path = "web_api_tests/stats.rs"]
+mod stats;
+#[path = "web_api_tests/tap_dances.rs"]
+mod tap_dances;
 #[path = "web_api_tests/templates.rs"]
 mod templates;
+#[path = "web_api_tests/validate.rs"]
+mod validate;
+#[path = "web_api_tests/ws.rs"]
+mod ws;
@@ -0,0 +1,74 @@
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+
+#[tokio::test]
+async fn test_audit_log_records_web_mutations() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("audited.json");
+    write_layout_file(&test_layout_basic(2, 3), &layout_path).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/audited.json/audit").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["entries"].as_array().unwrap().len(), 0);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/layouts/audited.json/layers/0/keys/0/1")
+                .header("Content-Type", "application/json")
+                .header("X-LazyQMK-User", "alice")
+                .body(Body::from(r#"{"keycode":"KC_Q"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/audited.json/swap-keys",
+        json!({
+            "layer": 0,
+            "first_position": { "row": 0, "col": 0 },
+            "second_position": { "row": 0, "col": 1 }
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, json) = get_json(&app, "/api/layouts/audited.json/audit").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["filename"], "audited.json");
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(entries[0]["actor"], "alice");
+    assert_eq!(entries[0]["source"], "web");
+    assert_eq!(entries[0]["action"], "assign_key");
+    assert!(entries[0]["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|c| c.as_str().unwrap().ends_with("→ KC_Q")));
+
+    assert_eq!(entries[1]["actor"], "web");
+    assert_eq!(entries[1]["action"], "swap_keys");
+
+    let (status, json) = get_json(&app, "/api/layouts/audited.json/audit?limit=1").await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["action"], "swap_keys");
+}
+
+#[tokio::test]
+async fn test_audit_log_missing_layout_returns_404() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, _) = get_json(&app, "/api/layouts/missing.json/audit").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
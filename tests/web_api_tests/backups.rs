@@ -0,0 +1,71 @@
+use lazyqmk::services::LayoutService;
+
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+
+fn layout_named(name: &str) -> Value {
+    let mut layout = test_layout_basic(1, 3);
+    layout.metadata.name = name.to_string();
+    serde_json::to_value(&layout).unwrap()
+}
+
+#[tokio::test]
+async fn test_save_backs_up_and_restore_brings_it_back() {
+    let (state, temp_dir) = create_test_state();
+    let path = temp_dir.path().join("backed_up.json");
+    let app = create_router(state);
+
+    // The first save has nothing to back up
+    let status = put_json(&app, "/api/layouts/backed_up.json", layout_named("First")).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, json) = get_json(&app, "/api/layouts/backed_up.json/backups").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["backups"].as_array().unwrap().len(), 0);
+
+    let status = put_json(&app, "/api/layouts/backed_up.json", layout_named("Second")).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, json) = get_json(&app, "/api/layouts/backed_up.json/backups").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["filename"], "backed_up.json");
+    let backups = json["backups"].as_array().unwrap();
+    assert_eq!(backups.len(), 1);
+    let id = backups[0]["id"].as_str().unwrap().to_string();
+
+    let (status, json) = post_json(
+        &app,
+        &format!("/api/layouts/backed_up.json/backups/{id}/restore"),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["restored"]["id"], id);
+    assert_eq!(LayoutService::load(&path).unwrap().metadata.name, "First");
+
+    // Restoring backs up the replaced layout and can be undone
+    let (_, json) = get_json(&app, "/api/layouts/backed_up.json/backups").await;
+    assert_eq!(json["backups"].as_array().unwrap().len(), 2);
+    let (status, json) =
+        post_json(&app, "/api/layouts/backed_up.json/history/undo", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["action"], "restore_backup");
+    assert_eq!(LayoutService::load(&path).unwrap().metadata.name, "Second");
+}
+
+#[tokio::test]
+async fn test_backup_errors() {
+    let (state, temp_dir) = create_test_state();
+    let path = temp_dir.path().join("backed_up.json");
+    write_layout_file(&test_layout_basic(1, 3), &path).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, _) = get_json(&app, "/api/layouts/missing.json/backups").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/backed_up.json/backups/20200101T000000.000000Z.json/restore",
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
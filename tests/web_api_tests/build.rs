@@ -1,4 +1,4 @@
-use super::fixtures::{test_layout_basic, write_layout_file};
+use super::fixtures::{test_layout_basic, test_layout_with_invalid_keycode, write_layout_file};
 use super::helpers::*;
 
 #[tokio::test]
@@ -89,6 +89,18 @@ async fn test_cancel_build_job_not_found() {
     assert!(json["message"].as_str().unwrap().contains("not found"));
 }
 
+#[tokio::test]
+async fn test_flash_build_job_not_found() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, json) =
+        post_json(&app, "/api/build/jobs/nonexistent-job-id/flash", json!({})).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(json["error"].as_str().unwrap().contains("not found"));
+}
+
 #[tokio::test]
 async fn test_start_build_no_qmk_path() {
     let (state, temp_dir) = create_test_state();
@@ -117,3 +129,71 @@ async fn test_start_build_no_qmk_path() {
         .unwrap()
         .contains("QMK firmware path not configured"));
 }
+
+#[tokio::test]
+async fn test_start_build_rejects_invalid_layout() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+
+    let layout = test_layout_with_invalid_keycode();
+    let filename = "invalid_layout.json";
+    let path = temp_dir.path().join(filename);
+    write_layout_file(&layout, &path).expect("Failed to write layout");
+
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/build/start",
+        json!({ "layout_filename": filename }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(json["validation"]["errors"][0]["kind"], "invalid_keycode");
+}
+
+#[tokio::test]
+async fn test_check_layout_missing_layout() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, json) = post_json(&app, "/api/layouts/nonexistent/check", json!({})).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(json["error"].as_str().unwrap().contains("not found"));
+}
+
+#[tokio::test]
+async fn test_check_layout_without_qmk_path() {
+    let (state, temp_dir) = create_test_state();
+
+    let layout = test_layout_basic(2, 3);
+    let path = temp_dir.path().join("check_me.json");
+    write_layout_file(&layout, &path).expect("Failed to write layout");
+
+    let app = create_router(state);
+
+    let (status, json) = post_json(&app, "/api/layouts/check_me/check", json!({})).await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(json["error"]
+        .as_str()
+        .unwrap()
+        .contains("QMK firmware path not configured"));
+}
+
+#[tokio::test]
+async fn test_check_layout_rejects_invalid_layout() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+
+    let layout = test_layout_with_invalid_keycode();
+    let path = temp_dir.path().join("invalid_layout.json");
+    write_layout_file(&layout, &path).expect("Failed to write layout");
+
+    let app = create_router(state);
+
+    let (status, json) = post_json(&app, "/api/layouts/invalid_layout/check", json!({})).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(json["validation"]["errors"][0]["kind"], "invalid_keycode");
+}
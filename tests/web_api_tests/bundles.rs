@@ -0,0 +1,150 @@
+use super::fixtures::{test_geometry_basic, test_layout_basic, write_layout_file};
+use super::helpers::*;
+use lazyqmk::services::share_bundle::ShareBundle;
+
+async fn post_bundle(app: &axum::Router, uri: &str, bytes: Vec<u8>) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("Content-Type", "application/octet-stream")
+                .body(Body::from(bytes))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+    (status, json)
+}
+
+#[tokio::test]
+async fn test_export_bundle() {
+    let (state, temp_dir) = create_test_state();
+    write_layout_file(
+        &test_layout_basic(2, 3),
+        &temp_dir.path().join("share.json"),
+    )
+    .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/layouts/share.json/bundle")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/zip");
+    assert!(response.headers()["content-disposition"]
+        .to_str()
+        .unwrap()
+        .ends_with(".lazyqmk\""));
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let bundle = ShareBundle::from_bytes(&body).expect("valid bundle");
+    assert_eq!(bundle.layout.metadata.name, "Test Layout");
+    assert_eq!(bundle.layout.layers.len(), 2);
+
+    let (status, _) = get_json(&app, "/api/layouts/missing.json/bundle").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_import_bundle() {
+    let (state, temp_dir) = create_test_state();
+    let bytes = ShareBundle::new(&test_layout_basic(2, 3), None)
+        .unwrap()
+        .to_bytes()
+        .unwrap();
+    let app = create_router(state);
+
+    let (status, json) = post_bundle(
+        &app,
+        "/api/layouts/import-bundle?filename=shared",
+        bytes.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["filename"], "shared.json");
+    assert_eq!(json["layout"]["metadata"]["name"], "Test Layout");
+    assert!(temp_dir.path().join("shared.json").exists());
+
+    // Importing again must not overwrite the existing layout
+    let (status, _) = post_bundle(
+        &app,
+        "/api/layouts/import-bundle?filename=shared",
+        bytes.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Without a filename the layout name is used
+    let (status, json) = post_bundle(&app, "/api/layouts/import-bundle", bytes).await;
+    assert_eq!(status, StatusCode::OK);
+    let filename = json["filename"].as_str().unwrap();
+    assert_ne!(filename, "shared.json");
+    assert!(temp_dir.path().join(filename).exists());
+}
+
+#[tokio::test]
+async fn test_import_bundle_rejects_invalid() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, json) =
+        post_bundle(&app, "/api/layouts/import-bundle", b"not a bundle".to_vec()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json["error"].as_str().unwrap().contains("bundle"));
+
+    let (status, _) = post_bundle(
+        &app,
+        "/api/layouts/import-bundle?filename=..%2Fescape",
+        ShareBundle::new(&test_layout_basic(1, 1), None)
+            .unwrap()
+            .to_bytes()
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_imported_bundle_renders_from_bundled_geometry() {
+    // No QMK path configured, so only the bundle knows the geometry
+    let (state, _temp_dir) = create_test_state();
+    let bytes = ShareBundle::new(&test_layout_basic(2, 3), Some(&test_geometry_basic(2, 3)))
+        .unwrap()
+        .to_bytes()
+        .unwrap();
+    let app = create_router(state);
+
+    let (status, json) = post_bundle(
+        &app,
+        "/api/layouts/import-bundle?filename=standalone",
+        bytes,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["geometry"], true);
+
+    let (status, json) = get_json(&app, "/api/layouts/standalone.json").await;
+    assert_eq!(status, StatusCode::OK);
+    let key = json["layers"][0]["keys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|key| key["position"] == json!({"row": 1, "col": 2}))
+        .unwrap();
+    assert_eq!(key["matrix_position"], json!([1, 2]));
+    assert_eq!(key["visual_index"], 5);
+}
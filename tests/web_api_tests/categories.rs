@@ -0,0 +1,135 @@
+use super::fixtures::{test_layout_with_categories, write_layout_file};
+use super::helpers::*;
+
+#[tokio::test]
+async fn test_list_categories_reports_usage() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("cats.json");
+    write_layout_file(&test_layout_with_categories(), &layout_path)
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/cats.json/categories").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let categories = json["categories"].as_array().unwrap();
+    assert_eq!(categories.len(), 2);
+    assert_eq!(categories[0]["id"], "navigation");
+    assert_eq!(categories[0]["color"]["g"], 255);
+    assert_eq!(categories[0]["key_count"], 1);
+    assert_eq!(categories[0]["layer_count"], 0);
+}
+
+#[tokio::test]
+async fn test_create_category_derives_id_and_rejects_duplicates() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("cats.json");
+    write_layout_file(&test_layout_with_categories(), &layout_path)
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/layouts/cats.json/categories",
+        json!({ "name": "Media Keys", "color": { "r": 0, "g": 0, "b": 255 } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["category"]["id"], "media-keys");
+
+    let saved = lazyqmk::services::LayoutService::load(&layout_path).unwrap();
+    assert_eq!(saved.get_category("media-keys").unwrap().name, "Media Keys");
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/cats.json/categories",
+        json!({ "id": "navigation", "name": "Nav", "color": { "r": 0, "g": 0, "b": 0 } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/cats.json/categories",
+        json!({ "id": "Bad Id", "name": "Bad", "color": { "r": 0, "g": 0, "b": 0 } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_update_category_changes_only_given_fields() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("cats.json");
+    write_layout_file(&test_layout_with_categories(), &layout_path)
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let status = put_json(
+        &app,
+        "/api/layouts/cats.json/categories/numbers",
+        json!({ "color": { "r": 1, "g": 2, "b": 3 } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let saved = lazyqmk::services::LayoutService::load(&layout_path).unwrap();
+    let category = saved.get_category("numbers").unwrap();
+    assert_eq!(category.name, "Numbers");
+    assert_eq!(category.color, lazyqmk::models::RgbColor::new(1, 2, 3));
+
+    let status = put_json(
+        &app,
+        "/api/layouts/cats.json/categories/missing",
+        json!({ "name": "Missing" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let status = put_json(
+        &app,
+        "/api/layouts/cats.json/categories/numbers",
+        json!({ "name": "" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_delete_category_clears_references() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("cats.json");
+    let mut layout = test_layout_with_categories();
+    layout.layers[0].category_id = Some("navigation".to_string());
+    write_layout_file(&layout, &layout_path).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = delete_json(&app, "/api/layouts/cats.json/categories/navigation").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["cleared_references"], 2);
+    let saved = lazyqmk::services::LayoutService::load(&layout_path).unwrap();
+    assert!(saved.get_category("navigation").is_none());
+    assert!(saved.layers[0].category_id.is_none());
+    assert!(saved.layers[0].keys[0].category_id.is_none());
+    assert_eq!(
+        saved.layers[0].keys[1].category_id.as_deref(),
+        Some("numbers")
+    );
+}
+
+#[tokio::test]
+async fn test_get_palette() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/palette").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let colors = json["colors"].as_array().unwrap();
+    assert!(!colors.is_empty());
+    assert!(colors[0]["shades"][0]["hex"]
+        .as_str()
+        .unwrap()
+        .starts_with('#'));
+}
@@ -14,6 +14,8 @@ async fn test_get_config() {
     assert!(json["workspace_root"].is_string());
     let workspace_root = json["workspace_root"].as_str().unwrap();
     assert_eq!(workspace_root, temp_dir.path().to_str().unwrap());
+    // Only the default QMK tree is configured
+    assert_eq!(json["qmk_trees"], serde_json::json!(["default"]));
 }
 
 #[tokio::test]
@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use lazyqmk::web::workspace_watch::announce_external_changes;
+
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+
+/// Reads SSE frames until `needle` has appeared `count` times, or panics after
+/// a timeout.
+async fn read_until(body: &mut Body, needle: &str, count: usize) -> String {
+    let mut received = String::new();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while received.matches(needle).count() < count {
+            let frame = body.frame().await.expect("stream ended").unwrap();
+            if let Some(data) = frame.data_ref() {
+                received.push_str(&String::from_utf8_lossy(data));
+            }
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("no '{needle}' event; got: {received}"));
+    received
+}
+
+async fn open_event_stream(app: &axum::Router, filename: &str) -> Body {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/layouts/{filename}/events"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    response.into_body()
+}
+
+#[tokio::test]
+async fn test_events_broadcast_assigned_key_to_other_sessions() {
+    let (state, temp_dir) = create_test_state();
+    write_layout_file(&test_layout_basic(2, 3), &temp_dir.path().join("live.json"))
+        .expect("Failed to write layout");
+    let app = create_router(state);
+    let mut events = open_event_stream(&app, "live.json").await;
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/layouts/live.json/layers/0/keys/0/1")
+                .header("content-type", "application/json")
+                .header("x-lazyqmk-client", "tab-2")
+                .header("x-lazyqmk-user", "alice")
+                .body(Body::from(json!({ "keycode": "KC_Q" }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let received = read_until(&mut events, "key_updated", 1).await;
+    let data = received
+        .lines()
+        .find_map(|line| line.strip_prefix("data: "))
+        .expect("event has data");
+    let event: Value = serde_json::from_str(data).unwrap();
+    assert_eq!(event["origin"], "tab-2");
+    assert_eq!(event["actor"], "alice");
+    assert_eq!(event["layer"], 0);
+    assert_eq!(event["key"]["keycode"], "KC_Q");
+    assert_eq!(event["key"]["position"]["col"], 1);
+}
+
+#[tokio::test]
+async fn test_events_send_key_updates_for_swap_and_replace_for_save() {
+    let (state, temp_dir) = create_test_state();
+    let layout = test_layout_basic(2, 3);
+    write_layout_file(&layout, &temp_dir.path().join("live.json")).expect("Failed to write layout");
+    let app = create_router(state);
+    let mut events = open_event_stream(&app, "live.json").await;
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/live.json/swap-keys",
+        json!({
+            "layer": 0,
+            "first_position": { "row": 0, "col": 0 },
+            "second_position": { "row": 0, "col": 1 }
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let received = read_until(&mut events, "key_updated", 2).await;
+    assert!(received.contains("\"col\":0") && received.contains("\"col\":1"));
+
+    let (_, layout_json) = get_json(&app, "/api/layouts/live.json").await;
+    let status = put_json(&app, "/api/layouts/live.json", layout_json).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    read_until(&mut events, "layout_replaced", 1).await;
+}
+
+#[tokio::test]
+async fn test_events_announce_layouts_saved_outside_the_server() {
+    let (state, temp_dir) = create_test_state();
+    let path = temp_dir.path().join("live.json");
+    write_layout_file(&test_layout_basic(2, 3), &path).expect("Failed to write layout");
+    let app = create_router(state.clone());
+    let mut events = open_event_stream(&app, "live.json").await;
+
+    // The first check only records the version on disk
+    assert_eq!(announce_external_changes(&state), 0);
+
+    // Saves made through the API are already announced by their handler
+    let (_, layout_json) = get_json(&app, "/api/layouts/live.json").await;
+    let status = put_json(&app, "/api/layouts/live.json", layout_json).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    read_until(&mut events, "layout_replaced", 1).await;
+    assert_eq!(announce_external_changes(&state), 0);
+
+    // A save by another program, such as the TUI, is announced once
+    write_layout_file(&test_layout_basic(3, 3), &path).expect("Failed to write layout");
+    assert_eq!(announce_external_changes(&state), 1);
+    assert_eq!(announce_external_changes(&state), 0);
+    let received = read_until(&mut events, "layout_replaced", 1).await;
+    assert!(
+        received.contains("\"actor\":\"external\""),
+        "got: {received}"
+    );
+}
+
+#[tokio::test]
+async fn test_events_missing_layout() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, _) = get_json(&app, "/api/layouts/missing.json/events").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
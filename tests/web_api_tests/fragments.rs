@@ -0,0 +1,101 @@
+use super::fixtures::{test_layout_basic, test_layout_with_tap_dances, write_layout_file};
+use super::helpers::*;
+
+#[tokio::test]
+async fn test_export_fragment_includes_referenced_tap_dances() {
+    let (state, temp_dir) = create_test_state();
+    write_layout_file(
+        &test_layout_with_tap_dances(),
+        &temp_dir.path().join("source.json"),
+    )
+    .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/source.json/layers/0/fragment").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["name"], "Base");
+    assert_eq!(json["source_layout"], "Test Layout");
+    assert_eq!(json["layer"]["keys"][0]["keycode"], "TD(esc_caps)");
+    assert_eq!(json["tap_dances"].as_array().unwrap().len(), 2);
+
+    let (status, _) = get_json(&app, "/api/layouts/source.json/layers/9/fragment").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_apply_fragment_from_other_layout() {
+    let (state, temp_dir) = create_test_state();
+    write_layout_file(
+        &test_layout_with_tap_dances(),
+        &temp_dir.path().join("source.json"),
+    )
+    .expect("Failed to write layout");
+    write_layout_file(
+        &test_layout_basic(2, 3),
+        &temp_dir.path().join("target.json"),
+    )
+    .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/layouts/target.json/layers/1/apply-fragment",
+        json!({ "source_filename": "source.json", "source_layer": 0 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["filename"], "target.json");
+    assert_eq!(json["layer"], 1);
+    assert_eq!(json["report"]["keys_applied"], 6);
+    assert_eq!(
+        json["report"]["tap_dances_added"].as_array().unwrap().len(),
+        2
+    );
+
+    let (_, layout) = get_json(&app, "/api/layouts/target.json").await;
+    let layer = &layout["layers"][1];
+    assert_eq!(layer["name"], "Base");
+    assert_eq!(layer["id"], "11111111-1111-1111-1111-111111111111");
+    assert_eq!(layer["keys"][0]["keycode"], "TD(esc_caps)");
+    assert_eq!(layout["tap_dances"].as_array().unwrap().len(), 2);
+
+    let (_, audit) = get_json(&app, "/api/layouts/target.json/audit").await;
+    assert_eq!(audit["entries"][0]["action"], "apply_fragment");
+}
+
+#[tokio::test]
+async fn test_apply_fragment_inline_and_invalid_requests() {
+    let (state, temp_dir) = create_test_state();
+    write_layout_file(
+        &test_layout_basic(2, 3),
+        &temp_dir.path().join("target.json"),
+    )
+    .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (_, fragment) = get_json(&app, "/api/layouts/target.json/layers/0/fragment").await;
+    let (status, json) = post_json(
+        &app,
+        "/api/layouts/target.json/layers/1/apply-fragment",
+        json!({ "fragment": fragment }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["report"]["keys_applied"], 6);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/target.json/layers/1/apply-fragment",
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/target.json/layers/7/apply-fragment",
+        json!({ "fragment": fragment }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -1,4 +1,4 @@
-use super::fixtures::{test_layout_basic, write_layout_file};
+use super::fixtures::{test_layout_basic, test_layout_with_invalid_keycode, write_layout_file};
 use super::helpers::*;
 
 #[tokio::test]
@@ -85,6 +85,69 @@ async fn test_generate_firmware_starts_job() {
     assert_eq!(json["job"]["layout_filename"], "test_layout.json");
 }
 
+#[tokio::test]
+async fn test_generate_firmware_for_keyboard_target() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+
+    let mut layout = test_layout_basic(2, 3);
+    layout
+        .metadata
+        .targets
+        .push(lazyqmk::models::KeyboardTarget::new(
+            "other",
+            "other_keyboard",
+            "LAYOUT_other",
+        ));
+    let path = temp_dir.path().join("targets.json");
+    write_layout_file(&layout, &path).expect("Failed to write layout");
+
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/layouts/targets.json/generate?target=other",
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["job"]["keyboard"], "other_keyboard");
+
+    let (status, json) = post_json(
+        &app,
+        "/api/layouts/targets.json/generate?target=missing",
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json["error"].as_str().unwrap().contains("missing"));
+}
+
+#[tokio::test]
+async fn test_generate_firmware_rejects_invalid_layout_before_job() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+
+    let layout = test_layout_with_invalid_keycode();
+    let path = temp_dir.path().join("invalid.json");
+    write_layout_file(&layout, &path).expect("Failed to write layout");
+
+    let app = create_router(state);
+
+    let (status, json) = post_json(&app, "/api/layouts/invalid.json/generate", json!({})).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let errors = json["validation"]["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0]["kind"], "invalid_keycode");
+    assert_eq!(errors[0]["layer"], 0);
+    assert_eq!(errors[0]["row"], 0);
+    assert_eq!(errors[0]["col"], 0);
+
+    // No job should have been queued
+    let (status, jobs) = get_json(&app, "/api/generate/jobs").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(jobs.as_array().unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn test_generate_job_status() {
     let (state, temp_dir) = create_test_state_with_qmk();
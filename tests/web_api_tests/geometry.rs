@@ -67,3 +67,45 @@ async fn test_get_geometry_path_traversal_rejected() {
         .unwrap()
         .contains("path traversal not allowed"));
 }
+
+#[tokio::test]
+async fn test_get_keyboard_info_merges_variant() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+    let variant_dir = temp_dir
+        .path()
+        .join("qmk_firmware/keyboards/test_keyboard/rev2");
+    std::fs::create_dir_all(&variant_dir).unwrap();
+    std::fs::write(
+        variant_dir.join("keyboard.json"),
+        r#"{"manufacturer": "Variant", "features": {"rgb_matrix": true}}"#,
+    )
+    .unwrap();
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/keyboards/test_keyboard%2Frev2/info").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["keyboard"], "test_keyboard/rev2");
+    assert_eq!(
+        json["sources"],
+        json!([
+            "test_keyboard/info.json",
+            "test_keyboard/rev2/keyboard.json"
+        ])
+    );
+    assert_eq!(json["info"]["manufacturer"], "Variant");
+    assert_eq!(json["info"]["features"]["rgb_matrix"], true);
+    assert_eq!(json["info"]["matrix_pins"]["rows"], json!(["D0", "D1"]));
+}
+
+#[tokio::test]
+async fn test_get_keyboard_info_not_found() {
+    let (state, _temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state);
+
+    let (status, _) = get_json(&app, "/api/keyboards/nonexistent/info").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = get_json(&app, "/api/keyboards/..%2F..%2Fetc/info").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,67 @@
+use std::process::Command;
+
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+
+/// Runs git in `dir`; returns false if git is unavailable or fails.
+fn git(dir: &std::path::Path, args: &[&str]) -> bool {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+#[tokio::test]
+async fn test_git_history_lists_commits_of_layout() {
+    let (state, temp_dir) = create_test_state();
+    let dir = temp_dir.path();
+    if !git(dir, &["init", "--quiet"]) {
+        return;
+    }
+    git(dir, &["config", "user.name", "Test User"]);
+    git(dir, &["config", "user.email", "test@example.com"]);
+    git(dir, &["config", "commit.gpgsign", "false"]);
+
+    let path = dir.join("versioned.json");
+    write_layout_file(&test_layout_basic(1, 3), &path).expect("Failed to write layout");
+    assert!(git(dir, &["add", "versioned.json"]));
+    assert!(git(dir, &["commit", "--quiet", "-m", "Add layout"]));
+    let mut layout = test_layout_basic(1, 3);
+    layout.metadata.name = "Renamed".to_string();
+    write_layout_file(&layout, &path).expect("Failed to write layout");
+    assert!(git(dir, &["commit", "--quiet", "-am", "Rename layout"]));
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/versioned.json/history").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["in_repository"], true);
+    assert_eq!(json["status"], "committed");
+    let commits = json["commits"].as_array().unwrap();
+    assert_eq!(commits.len(), 2);
+    assert_eq!(commits[0]["summary"], "Rename layout");
+    assert_eq!(commits[0]["author"], "Test User");
+    assert_eq!(commits[1]["summary"], "Add layout");
+
+    let (_, json) = get_json(&app, "/api/layouts/versioned.json/history?limit=1").await;
+    assert_eq!(json["commits"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_git_history_outside_repository() {
+    let (state, temp_dir) = create_test_state();
+    let path = temp_dir.path().join("plain.json");
+    write_layout_file(&test_layout_basic(1, 3), &path).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/plain.json/history").await;
+    assert_eq!(status, StatusCode::OK);
+    if json["in_repository"] == false {
+        assert!(json["status"].is_null());
+        assert_eq!(json["commits"].as_array().unwrap().len(), 0);
+    }
+
+    let (status, _) = get_json(&app, "/api/layouts/missing.json/history").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
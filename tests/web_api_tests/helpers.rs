@@ -4,23 +4,25 @@
 
 // Re-exported types and modules for use by sibling topic modules.
 // Each topic module does `use super::helpers::*;` to bring these into scope.
-pub(super) use axum::body::Body;
-pub(super) use axum::http::Request;
-pub(super) use axum::http::StatusCode;
-pub(super) use http_body_util::BodyExt;
-pub(super) use serde_json::{json, Value};
-pub(super) use std::fs;
-pub(super) use tempfile::TempDir;
-pub(super) use tower::ServiceExt;
-
-pub(super) use lazyqmk::config::{BuildConfig, Config, PathConfig, UiConfig};
-pub(super) use lazyqmk::web::{create_router, AppState};
+pub use axum::body::Body;
+pub use axum::http::Request;
+pub use axum::http::StatusCode;
+pub use http_body_util::BodyExt;
+pub use serde_json::{json, Value};
+pub use std::fs;
+pub use tempfile::TempDir;
+pub use tower::ServiceExt;
+
+pub use lazyqmk::config::{
+    BuildConfig, Config, GitConfig, HooksConfig, LintConfig, PathConfig, UiConfig, WebConfig,
+};
+pub use lazyqmk::web::{create_router, AppState};
 
 // Re-export test_layout_basic from fixtures (needed by test_template_basic below)
-pub(super) use super::fixtures::{test_layout_basic, write_layout_file};
+pub use super::fixtures::test_layout_basic;
 
 /// Creates a test layout marked as a template.
-pub(super) fn test_template_basic(rows: usize, cols: usize, name: &str) -> lazyqmk::models::Layout {
+pub fn test_template_basic(rows: usize, cols: usize, name: &str) -> lazyqmk::models::Layout {
     let mut layout = test_layout_basic(rows, cols);
     layout.metadata.is_template = true;
     layout.metadata.name = name.to_string();
@@ -30,15 +32,26 @@ pub(super) fn test_template_basic(rows: usize, cols: usize, name: &str) -> lazyq
 }
 
 /// Creates a test AppState with a temporary workspace.
-pub(super) fn create_test_state() -> (AppState, TempDir) {
+pub fn create_test_state() -> (AppState, TempDir) {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
 
     let config = Config {
-        paths: PathConfig { qmk_firmware: None },
+        paths: PathConfig {
+            qmk_firmware: None,
+            backup_dir: Some(temp_dir.path().join("backups")),
+            keyboard_roots: Vec::new(),
+            qmk_trees: Vec::new(),
+        },
         build: BuildConfig {
             output_dir: temp_dir.path().to_path_buf(),
+            userspace: None,
         },
         ui: UiConfig::default(),
+        web: WebConfig::default(),
+        git: GitConfig::default(),
+        hooks: HooksConfig::default(),
+        lints: LintConfig::default(),
+        safe_mode: false,
     };
 
     let state =
@@ -48,7 +61,7 @@ pub(super) fn create_test_state() -> (AppState, TempDir) {
 }
 
 /// Creates a test AppState with a mock QMK firmware directory.
-pub(super) fn create_test_state_with_qmk() -> (AppState, TempDir) {
+pub fn create_test_state_with_qmk() -> (AppState, TempDir) {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let qmk_path = temp_dir.path().join("qmk_firmware");
 
@@ -99,11 +112,20 @@ pub(super) fn create_test_state_with_qmk() -> (AppState, TempDir) {
     let config = Config {
         paths: PathConfig {
             qmk_firmware: Some(qmk_path),
+            backup_dir: Some(temp_dir.path().join("backups")),
+            keyboard_roots: Vec::new(),
+            qmk_trees: Vec::new(),
         },
         build: BuildConfig {
             output_dir: temp_dir.path().to_path_buf(),
+            userspace: None,
         },
         ui: UiConfig::default(),
+        web: WebConfig::default(),
+        git: GitConfig::default(),
+        hooks: HooksConfig::default(),
+        lints: LintConfig::default(),
+        safe_mode: false,
     };
 
     let state =
@@ -113,7 +135,7 @@ pub(super) fn create_test_state_with_qmk() -> (AppState, TempDir) {
 }
 
 /// Helper to make a GET request and get the response body as JSON.
-pub(super) async fn get_json(app: &axum::Router, uri: &str) -> (StatusCode, Value) {
+pub async fn get_json(app: &axum::Router, uri: &str) -> (StatusCode, Value) {
     let response = app
         .clone()
         .oneshot(
@@ -134,7 +156,7 @@ pub(super) async fn get_json(app: &axum::Router, uri: &str) -> (StatusCode, Valu
 }
 
 /// Helper to make a PUT request with JSON body.
-pub(super) async fn put_json(app: &axum::Router, uri: &str, body: Value) -> StatusCode {
+pub async fn put_json(app: &axum::Router, uri: &str, body: Value) -> StatusCode {
     let response = app
         .clone()
         .oneshot(
@@ -152,7 +174,7 @@ pub(super) async fn put_json(app: &axum::Router, uri: &str, body: Value) -> Stat
 }
 
 /// Helper to make a POST request with JSON body and get the response.
-pub(super) async fn post_json(app: &axum::Router, uri: &str, body: Value) -> (StatusCode, Value) {
+pub async fn post_json(app: &axum::Router, uri: &str, body: Value) -> (StatusCode, Value) {
     let response = app
         .clone()
         .oneshot(
@@ -173,9 +195,30 @@ pub(super) async fn post_json(app: &axum::Router, uri: &str, body: Value) -> (St
     (status, json)
 }
 
+/// Helper to make a DELETE request and get the response body as JSON.
+pub async fn delete_json(app: &axum::Router, uri: &str) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+    (status, json)
+}
+
 /// Creates a test state with a custom template directory for isolated tests.
 /// Returns the state, temp workspace dir, and a path to use for templates.
-pub(super) fn create_test_state_with_template_dir() -> (AppState, TempDir, std::path::PathBuf) {
+pub fn create_test_state_with_template_dir() -> (AppState, TempDir, std::path::PathBuf) {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
 
     // Use the platform config directory for templates (what the endpoints use)
@@ -187,11 +230,22 @@ pub(super) fn create_test_state_with_template_dir() -> (AppState, TempDir, std::
     fs::create_dir_all(&template_dir).expect("Failed to create template dir");
 
     let config = Config {
-        paths: PathConfig { qmk_firmware: None },
+        paths: PathConfig {
+            qmk_firmware: None,
+            backup_dir: Some(temp_dir.path().join("backups")),
+            keyboard_roots: Vec::new(),
+            qmk_trees: Vec::new(),
+        },
         build: BuildConfig {
             output_dir: temp_dir.path().to_path_buf(),
+            userspace: None,
         },
         ui: UiConfig::default(),
+        web: WebConfig::default(),
+        git: GitConfig::default(),
+        hooks: HooksConfig::default(),
+        lints: LintConfig::default(),
+        safe_mode: false,
     };
 
     let state =
@@ -201,7 +255,7 @@ pub(super) fn create_test_state_with_template_dir() -> (AppState, TempDir, std::
 }
 
 /// Helper to clean up a specific template file after test.
-pub(super) fn cleanup_template(template_dir: &std::path::Path, filename: &str) {
+pub fn cleanup_template(template_dir: &std::path::Path, filename: &str) {
     let path = template_dir.join(filename);
     if path.exists() {
         let _ = fs::remove_file(path);
@@ -0,0 +1,104 @@
+use lazyqmk::services::LayoutService;
+
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+
+fn keycodes(path: &std::path::Path) -> Vec<String> {
+    LayoutService::load(path).unwrap().layers[0]
+        .keys
+        .iter()
+        .map(|key| key.keycode.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_undo_and_redo_assign_and_swap() {
+    let (state, temp_dir) = create_test_state();
+    let path = temp_dir.path().join("history.json");
+    write_layout_file(&test_layout_basic(1, 3), &path).expect("Failed to write layout");
+    let original = keycodes(&path);
+    let app = create_router(state);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/history.json/layers/0/keys/0/0",
+        json!({ "keycode": "KC_Q" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let assigned = keycodes(&path);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/history.json/swap-keys",
+        json!({
+            "layer": 0,
+            "first_position": { "row": 0, "col": 0 },
+            "second_position": { "row": 0, "col": 1 }
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let swapped = keycodes(&path);
+
+    let (status, json) = post_json(&app, "/api/layouts/history.json/history/undo", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["action"], "swap_keys");
+    assert_eq!(json["undo_steps"], 1);
+    assert_eq!(json["redo_steps"], 1);
+    assert_eq!(keycodes(&path), assigned);
+
+    let (status, json) = post_json(&app, "/api/layouts/history.json/history/undo", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["action"], "assign_key");
+    assert_eq!(keycodes(&path), original);
+
+    let (status, json) = post_json(&app, "/api/layouts/history.json/history/undo", json!({})).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(json["error"], "Nothing to undo");
+
+    post_json(&app, "/api/layouts/history.json/history/redo", json!({})).await;
+    let (status, json) = post_json(&app, "/api/layouts/history.json/history/redo", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["redo_steps"], 0);
+    assert_eq!(keycodes(&path), swapped);
+}
+
+#[tokio::test]
+async fn test_undo_refuses_after_outside_change() {
+    let (state, temp_dir) = create_test_state();
+    let path = temp_dir.path().join("history.json");
+    write_layout_file(&test_layout_basic(1, 3), &path).expect("Failed to write layout");
+    let app = create_router(state);
+
+    post_json(
+        &app,
+        "/api/layouts/history.json/layers/0/keys/0/0",
+        json!({ "keycode": "KC_Q" }),
+    )
+    .await;
+
+    // Saved by the TUI or another program
+    let mut layout = LayoutService::load(&path).unwrap();
+    layout.layers[0].keys[2].keycode = "KC_Z".to_string();
+    LayoutService::save(&layout, &path).unwrap();
+
+    let (status, json) = post_json(&app, "/api/layouts/history.json/history/undo", json!({})).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(json["error"], "Layout was changed outside the web editor");
+    assert_eq!(keycodes(&path)[2], "KC_Z");
+
+    // The history was cleared
+    let (_, json) = post_json(&app, "/api/layouts/history.json/history/undo", json!({})).await;
+    assert_eq!(json["error"], "Nothing to undo");
+}
+
+#[tokio::test]
+async fn test_undo_missing_layout() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, _) = post_json(&app, "/api/layouts/missing.json/history/undo", json!({})).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -28,7 +28,93 @@ async fn test_list_keyboards_with_qmk() {
     let keyboards = json["keyboards"].as_array().unwrap();
     // Should find test_keyboard from our mock QMK setup
     assert!(!keyboards.is_empty());
-    assert!(keyboards.iter().any(|k| k["path"] == "test_keyboard"));
+    let test_keyboard = keyboards
+        .iter()
+        .find(|k| k["path"] == "test_keyboard")
+        .expect("test_keyboard listed");
+    assert_eq!(test_keyboard["usb"]["vid"], 0xFEED);
+    assert!(json["detected"].is_array());
+}
+
+fn add_keyboard(temp_dir: &TempDir, name: &str) {
+    let keyboard_dir = temp_dir.path().join("qmk_firmware/keyboards").join(name);
+    fs::create_dir_all(&keyboard_dir).unwrap();
+    fs::write(
+        keyboard_dir.join("info.json"),
+        r#"{"layouts": {"LAYOUT": {"layout": [{"x": 0, "y": 0}]}}}"#,
+    )
+    .unwrap();
+}
+
+fn keyboard_paths(json: &Value) -> Vec<&str> {
+    json["keyboards"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|k| k["path"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_list_keyboards_is_cached_until_refresh() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state);
+
+    let (_, json) = get_json(&app, "/api/keyboards").await;
+    assert_eq!(keyboard_paths(&json), vec!["test_keyboard"]);
+    assert!(temp_dir
+        .path()
+        .join(".lazyqmk/keyboard_index.json")
+        .exists());
+
+    add_keyboard(&temp_dir, "another_keyboard");
+    let (_, json) = get_json(&app, "/api/keyboards").await;
+    assert_eq!(keyboard_paths(&json), vec!["test_keyboard"]);
+
+    let (status, json) = post_json(&app, "/api/keyboards/refresh", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        keyboard_paths(&json),
+        vec!["another_keyboard", "test_keyboard"]
+    );
+
+    let (_, json) = get_json(&app, "/api/keyboards").await;
+    assert_eq!(
+        keyboard_paths(&json),
+        vec!["another_keyboard", "test_keyboard"]
+    );
+}
+
+#[tokio::test]
+async fn test_list_keyboards_loads_persisted_index() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+    let (_, json) = get_json(&create_router(state), "/api/keyboards").await;
+    assert_eq!(keyboard_paths(&json), vec!["test_keyboard"]);
+
+    // A fresh server serves the saved index before its background rescan
+    add_keyboard(&temp_dir, "another_keyboard");
+    let config = Config {
+        paths: PathConfig {
+            qmk_firmware: Some(temp_dir.path().join("qmk_firmware")),
+            backup_dir: Some(temp_dir.path().join("backups")),
+            keyboard_roots: Vec::new(),
+            qmk_trees: Vec::new(),
+        },
+        build: BuildConfig {
+            output_dir: temp_dir.path().to_path_buf(),
+            userspace: None,
+        },
+        ui: UiConfig::default(),
+        web: WebConfig::default(),
+        git: GitConfig::default(),
+        hooks: HooksConfig::default(),
+        lints: LintConfig::default(),
+        safe_mode: false,
+    };
+    let state = AppState::new(config, temp_dir.path().to_path_buf()).unwrap();
+    let (status, json) = get_json(&create_router(state), "/api/keyboards").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(keyboard_paths(&json), vec!["test_keyboard"]);
 }
 
 #[tokio::test]
@@ -165,6 +251,125 @@ async fn test_create_layout_invalid_variant() {
     assert!(json["error"].as_str().unwrap().contains("Layout variant"));
 }
 
+#[tokio::test]
+async fn test_import_layout() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state);
+
+    let request = json!({
+        "filename": "imported",
+        "name": "Imported Keymap",
+        "keyboard": "test_keyboard",
+        "keymap_c": "enum layers { _BASE, _NAV };\n\
+            const uint16_t keymaps[][2][3] = {\n\
+                [_BASE] = LAYOUT_test(KC_A, KC_B, KC_C, MO(_NAV), KC_SPC, KC_ENT),\n\
+                [_NAV] = LAYOUT_test(_______, KC_UP, _______, _______, KC_LEFT, KC_RGHT)\n\
+            };"
+    });
+
+    let (status, json) = post_json(&app, "/api/layouts/import", request).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["layout"]["metadata"]["name"], "Imported Keymap");
+    assert_eq!(json["layout"]["metadata"]["layout_variant"], "LAYOUT_test");
+    assert_eq!(json["layout"]["layers"][1]["name"], "Nav");
+    assert!(json.get("warnings").is_none());
+    assert!(temp_dir.path().join("imported.json").exists());
+}
+
+#[tokio::test]
+async fn test_import_layout_from_keymap_json() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state);
+
+    let keymap_json = json!({
+        "keyboard": "test_keyboard",
+        "keymap": "shared",
+        "layout": "LAYOUT_test",
+        "layers": [
+            ["KC_A", "KC_B", "KC_C", "MO(1)", "KC_SPC", "KC_ENT"],
+            ["KC_TRNS", "KC_UP", "KC_TRNS", "KC_TRNS", "KC_LEFT", "TD(0)"]
+        ]
+    });
+    let request = json!({
+        "filename": "imported",
+        "name": "Imported Keymap",
+        "keymap_json": keymap_json.to_string()
+    });
+
+    let (status, json) = post_json(&app, "/api/layouts/import", request).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["layout"]["metadata"]["keyboard"], "test_keyboard");
+    assert_eq!(json["layout"]["metadata"]["keymap_name"], "shared");
+    assert_eq!(json["warnings"].as_array().unwrap().len(), 1);
+    assert!(temp_dir.path().join("imported.json").exists());
+
+    // The imported layout exports back to the same keymap.json layers
+    let (status, json) = get_json(&app, "/api/layouts/imported/export/qmk-json").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(json["suggested_filename"]
+        .as_str()
+        .unwrap()
+        .ends_with(".keymap.json"));
+    let exported: serde_json::Value =
+        serde_json::from_str(json["keymap_json"].as_str().unwrap()).unwrap();
+    assert_eq!(exported["layers"][0], keymap_json["layers"][0]);
+    assert_eq!(exported["layers"][1][5], "KC_NO");
+}
+
+#[tokio::test]
+async fn test_import_layout_needs_one_keymap() {
+    let (state, _temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state);
+
+    let request = json!({
+        "filename": "imported",
+        "name": "Imported Keymap",
+        "keyboard": "test_keyboard"
+    });
+
+    let (status, _json) = post_json(&app, "/api/layouts/import", request).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_import_layout_invalid_keymap() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state);
+
+    let request = json!({
+        "filename": "imported",
+        "name": "Imported Keymap",
+        "keyboard": "test_keyboard",
+        "keymap_c": "void matrix_scan_user(void) {}"
+    });
+
+    let (status, json) = post_json(&app, "/api/layouts/import", request).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json["details"].as_str().unwrap().contains("keymaps[]"));
+    assert!(!temp_dir.path().join("imported.json").exists());
+}
+
+#[tokio::test]
+async fn test_import_layout_key_count_mismatch() {
+    let (state, _temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state);
+
+    let request = json!({
+        "filename": "imported",
+        "name": "Imported Keymap",
+        "keyboard": "test_keyboard",
+        "keymap_c": "const uint16_t keymaps[][2][3] = { LAYOUT_test(KC_A, KC_B) };"
+    });
+
+    let (status, _json) = post_json(&app, "/api/layouts/import", request).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
 #[tokio::test]
 async fn test_switch_layout_variant() {
     let (state, temp_dir) = create_test_state_with_qmk();
@@ -59,3 +59,99 @@ async fn test_list_categories() {
     assert!(category_ids.contains(&"navigation"));
     assert!(category_ids.contains(&"layers"));
 }
+
+#[tokio::test]
+async fn test_keycode_display_layer_tap() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/keycodes/display?code=LT(2,KC_SPC)").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["code"], "LT(2,KC_SPC)");
+    assert_eq!(json["valid"], true);
+    assert_eq!(json["display"]["secondary"], "L2");
+    let details = json["details"].as_array().unwrap();
+    assert_eq!(details[0]["kind"], "tap");
+    assert_eq!(details[1]["kind"], "hold");
+}
+
+#[tokio::test]
+async fn test_keycode_display_unknown_keycode() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/keycodes/display?code=NOT_A_KEY").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["valid"], false);
+    assert!(json["display"]["primary"].is_string());
+}
+
+#[tokio::test]
+async fn test_keycode_display_empty_code() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, _json) = get_json(&app, "/api/keycodes/display?code=").await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_keycode_display_missing_layout() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, _json) =
+        get_json(&app, "/api/keycodes/display?code=KC_A&layout=missing.json").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_dump_keycode_db() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/keycodes/dump").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(json["keycodes"].as_array().unwrap().len() > 100);
+    assert!(json["languages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|lang| lang["id"] == "german"));
+    assert_eq!(json["issues"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_get_keycode_favorites_empty() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/keycodes/favorites").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["favorites"], json!([]));
+    assert_eq!(json["recent"], json!([]));
+}
+
+#[tokio::test]
+async fn test_add_keycode_favorite_rejects_unknown_keycode() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/keycodes/favorites",
+        json!({ "keycode": "NOT_A_KEYCODE" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json["error"].as_str().unwrap().contains("Unknown keycode"));
+
+    let (status, _) = delete_json(&app, "/api/keycodes/favorites/KC_A").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
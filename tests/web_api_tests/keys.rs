@@ -0,0 +1,152 @@
+use super::fixtures::{
+    test_layout_basic, test_layout_with_categories, test_layout_with_tap_dances, write_layout_file,
+};
+use super::helpers::*;
+
+#[tokio::test]
+async fn test_assign_key_updates_keycode_and_returns_metadata() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("assign.json");
+    write_layout_file(&test_layout_basic(2, 3), &layout_path).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/layouts/assign.json/layers/0/keys/0/1",
+        json!({ "keycode": "KC_Q" }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["visual_index"], 1);
+    assert_eq!(json["display"]["primary"], "Q");
+
+    let saved = lazyqmk::services::LayoutService::load(&layout_path).unwrap();
+    assert_eq!(saved.layers[0].keys[1].keycode, "KC_Q");
+}
+
+#[tokio::test]
+async fn test_assign_key_sets_and_clears_color_and_category() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("assign.json");
+    write_layout_file(&test_layout_with_categories(), &layout_path)
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/assign.json/layers/0/keys/0/2",
+        json!({ "color_override": { "r": 1, "g": 2, "b": 3 }, "category_id": "numbers" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let saved = lazyqmk::services::LayoutService::load(&layout_path).unwrap();
+    let key = &saved.layers[0].keys[2];
+    assert_eq!(
+        key.color_override,
+        Some(lazyqmk::models::RgbColor::new(1, 2, 3))
+    );
+    assert_eq!(key.category_id.as_deref(), Some("numbers"));
+
+    // Explicit null clears; omitted fields stay untouched.
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/assign.json/layers/0/keys/0/2",
+        json!({ "category_id": null }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let saved = lazyqmk::services::LayoutService::load(&layout_path).unwrap();
+    let key = &saved.layers[0].keys[2];
+    assert_eq!(key.category_id, None);
+    assert_eq!(
+        key.color_override,
+        Some(lazyqmk::models::RgbColor::new(1, 2, 3))
+    );
+}
+
+#[tokio::test]
+async fn test_assign_key_rejects_invalid_references() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("assign.json");
+    write_layout_file(&test_layout_with_tap_dances(), &layout_path)
+        .expect("Failed to write layout");
+    let app = create_router(state);
+    let uri = "/api/layouts/assign.json/layers/0/keys/0/2";
+
+    let (status, json) = post_json(&app, uri, json!({ "keycode": "KC_NOPE_XYZ" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json["error"].as_str().unwrap().contains("Invalid keycode"));
+
+    let (status, _) = post_json(&app, uri, json!({ "keycode": "TD(missing)" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = post_json(&app, uri, json!({ "keycode": "MO(9)" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = post_json(&app, uri, json!({ "category_id": "missing" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = post_json(&app, uri, json!({ "keycode": "TD(esc_caps)" })).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_assign_key_unknown_position_or_layer() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("assign.json");
+    write_layout_file(&test_layout_basic(2, 3), &layout_path).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/assign.json/layers/0/keys/5/5",
+        json!({ "keycode": "KC_A" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/assign.json/layers/7/keys/0/0",
+        json!({ "keycode": "KC_A" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_locked_key_is_reported_and_blocks_swap() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("lock.json");
+    write_layout_file(&test_layout_basic(2, 3), &layout_path).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/lock.json/layers/0/keys/0/0",
+        json!({ "locked": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, json) = get_json(&app, "/api/layouts/lock.json").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["layers"][0]["keys"][0]["locked"], true);
+    assert!(json["layers"][0]["keys"][1].get("locked").is_none());
+
+    let (status, json) = post_json(
+        &app,
+        "/api/layouts/lock.json/swap-keys",
+        json!({
+            "layer": 0,
+            "first_position": { "row": 0, "col": 0 },
+            "second_position": { "row": 0, "col": 1 }
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json["error"].as_str().unwrap().contains("locked"));
+}
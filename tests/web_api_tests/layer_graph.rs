@@ -0,0 +1,53 @@
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+
+#[tokio::test]
+async fn test_layer_graph_lists_layer_keys_and_stuck_layers() {
+    let (state, temp_dir) = create_test_state();
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[0].keys[0].keycode = "TG(1)".to_string();
+    layout.layers[1].keys[0].keycode = "KC_ESC".to_string();
+    write_layout_file(&layout, &temp_dir.path().join("stuck.json"))
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/stuck/layer-graph").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["filename"], "stuck.json");
+
+    let edges = json["edges"].as_array().unwrap();
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0]["from_layer"], 0);
+    assert_eq!(edges[0]["to_layer"], 1);
+    assert_eq!(edges[0]["keycode"], "TG(1)");
+    assert_eq!(edges[0]["kind"], "Toggle (TG)");
+
+    let layers = json["layers"].as_array().unwrap();
+    assert_eq!(layers[1]["reachable"], true);
+    assert_eq!(layers[1]["has_way_back"], false);
+    let issues = json["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].as_str().unwrap().contains("no way back"));
+}
+
+#[tokio::test]
+async fn test_layer_graph_flags_unreachable_layer() {
+    let (state, temp_dir) = create_test_state();
+    write_layout_file(
+        &test_layout_basic(2, 3),
+        &temp_dir.path().join("plain.json"),
+    )
+    .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/plain.json/layer-graph").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["layers"][1]["reachable"], false);
+    assert!(json["issues"][0]
+        .as_str()
+        .unwrap()
+        .contains("can't be reached"));
+
+    let (status, _) = get_json(&app, "/api/layouts/missing.json/layer-graph").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,76 @@
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+
+#[tokio::test]
+async fn test_duplicate_layer_appends_copy() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("dup.json");
+    write_layout_file(&test_layout_basic(2, 3), &layout_path).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) =
+        post_json(&app, "/api/layouts/dup.json/layers/0/duplicate", json!({})).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["layer"], 2);
+    assert_eq!(json["name"], "Base Copy");
+    assert_eq!(json["mirrored_keys"], 0);
+    let saved = lazyqmk::services::LayoutService::load(&layout_path).unwrap();
+    assert_eq!(saved.layers.len(), 3);
+    assert_ne!(saved.layers[2].id, saved.layers[0].id);
+    let keycodes = |layer: usize| -> Vec<String> {
+        saved.layers[layer]
+            .keys
+            .iter()
+            .map(|k| k.keycode.clone())
+            .collect()
+    };
+    assert_eq!(keycodes(2), keycodes(0));
+
+    let (status, _) = post_json(&app, "/api/layouts/dup.json/layers/7/duplicate", json!({})).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_duplicate_layer_mirrors_with_geometry() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+    let layout_path = temp_dir.path().join("dup.json");
+    write_layout_file(&test_layout_basic(2, 3), &layout_path).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/layouts/dup.json/layers/0/duplicate?mirror=true&name=Mirrored",
+        json!({}),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["name"], "Mirrored");
+    assert_eq!(json["mirrored_keys"], 4);
+    let saved = lazyqmk::services::LayoutService::load(&layout_path).unwrap();
+    let keycodes: Vec<_> = saved.layers[2]
+        .keys
+        .iter()
+        .map(|k| k.keycode.as_str())
+        .collect();
+    assert_eq!(keycodes, ["KC_2", "KC_1", "KC_0", "KC_5", "KC_4", "KC_3"]);
+}
+
+#[tokio::test]
+async fn test_duplicate_layer_mirror_needs_geometry() {
+    let (state, temp_dir) = create_test_state();
+    write_layout_file(&test_layout_basic(2, 3), &temp_dir.path().join("dup.json"))
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/layouts/dup.json/layers/0/duplicate?mirror=true",
+        json!({}),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json["error"].as_str().unwrap().contains("geometry"));
+}
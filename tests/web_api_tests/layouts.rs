@@ -1,4 +1,4 @@
-use super::fixtures::{test_layout_basic, write_layout_file};
+use super::fixtures::{test_geometry_basic, test_layout_basic, write_layout_file};
 use super::helpers::*;
 
 #[tokio::test]
@@ -96,3 +96,142 @@ async fn test_save_layout_path_traversal_rejected() {
 
     assert_eq!(status, StatusCode::BAD_REQUEST);
 }
+
+#[tokio::test]
+async fn test_render_metadata_includes_layer_encoders_and_combos() {
+    use lazyqmk::models::{ComboAction, ComboDefinition, EncoderBinding, KeycodeCombo, Position};
+
+    let (state, temp_dir) = create_test_state();
+
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[0]
+        .encoder_map
+        .set(1, EncoderBinding::new("KC_VOLD", "KC_VOLU"));
+    // Same keycode as the base layer, so combos on (1, 0) still fire
+    layout.layers[1].keys[3].keycode = "KC_3".to_string();
+    layout.combo_settings.enabled = true;
+    layout.combo_settings.combos.push(ComboDefinition::new(
+        Position::new(0, 0),
+        Position::new(0, 1),
+        ComboAction::Bootloader,
+    ));
+    layout.combo_settings.keycode_combos = vec![
+        KeycodeCombo::new(vec![Position::new(0, 1), Position::new(0, 2)], "KC_ESC"),
+        KeycodeCombo::new(vec![Position::new(0, 0), Position::new(1, 0)], "KC_TAB"),
+    ];
+    let layout_path = temp_dir.path().join("combos.json");
+    write_layout_file(&layout, &layout_path).expect("Failed to write layout");
+
+    let app = create_router(state);
+    let (status, json) = get_json(&app, "/api/layouts/combos.json/render-metadata").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let base = &json["layers"][0];
+    let encoders = base["encoders"].as_array().unwrap();
+    assert_eq!(encoders.len(), 2);
+    assert_eq!(encoders[0]["default"], true);
+    assert_eq!(encoders[1]["default"], false);
+    assert_eq!(encoders[1]["ccw"]["keycode"], "KC_VOLD");
+    assert_eq!(encoders[1]["cw"]["keycode"], "KC_VOLU");
+
+    let combos = base["combos"].as_array().unwrap();
+    assert_eq!(combos.len(), 3);
+    assert_eq!(combos[0]["kind"], "hold");
+    assert_eq!(combos[0]["visual_indices"], json!([0, 1]));
+    assert_eq!(combos[1]["kind"], "keycode");
+    assert_eq!(combos[1]["keycode"], "KC_ESC");
+
+    // Layer 1: hold combos are base-only and (0, 1) sends another keycode
+    let combos = json["layers"][1]["combos"].as_array().unwrap();
+    assert_eq!(combos.len(), 1);
+    assert_eq!(combos[0]["keycode"], "KC_TAB");
+    assert_eq!(combos[0]["index"], 1);
+    assert_eq!(
+        combos[0]["positions"],
+        json!([{"row": 0, "col": 0}, {"row": 1, "col": 0}])
+    );
+}
+
+#[tokio::test]
+async fn test_export_keymap_drawer_yaml() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[0].keys[1].keycode = "LT(1, KC_SPC)".to_string();
+    let layout_path = temp_dir.path().join("drawer.json");
+    write_layout_file(&layout, &layout_path).expect("Failed to write layout");
+
+    let app = create_router(state);
+    let (status, json) = get_json(&app, "/api/layouts/drawer.json/export/keymap-drawer").await;
+    assert_eq!(status, StatusCode::OK);
+    let suggested = json["suggested_filename"].as_str().unwrap();
+    assert_eq!(suggested.rsplit('.').next(), Some("yaml"));
+
+    let yaml: serde_json::Value = serde_yml::from_str(json["yaml"].as_str().unwrap()).unwrap();
+    let base = yaml["layers"]["Base"].as_array().unwrap();
+    assert_eq!(base.len(), 6);
+    assert_eq!(base[1]["t"], "SPC");
+    assert_eq!(base[1]["h"], "L1");
+}
+
+#[tokio::test]
+async fn test_export_keymap_drawer_requires_layout_variant() {
+    let (state, temp_dir) = create_test_state();
+
+    let mut layout = test_layout_basic(2, 3);
+    layout.metadata.layout_variant = None;
+    let layout_path = temp_dir.path().join("novariant.json");
+    write_layout_file(&layout, &layout_path).expect("Failed to write layout");
+
+    let app = create_router(state);
+    let (status, _) = get_json(&app, "/api/layouts/novariant.json/export/keymap-drawer").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = get_json(&app, "/api/layouts/missing.json/export/keymap-drawer").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_layout_renders_from_geometry_snapshot_without_qmk() {
+    let (state, temp_dir) = create_test_state();
+    let mut layout = test_layout_basic(2, 3);
+    layout.record_geometry(&test_geometry_basic(2, 3));
+    write_layout_file(&layout, &temp_dir.path().join("standalone.json"))
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/standalone.json").await;
+    assert_eq!(status, StatusCode::OK);
+    let key = json["layers"][0]["keys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|key| key["position"] == json!({"row": 1, "col": 2}))
+        .unwrap();
+    assert_eq!(key["matrix_position"], json!([1, 2]));
+
+    // Saving a layout without a snapshot keeps the stored one
+    let mut body: Value = serde_json::to_value(test_layout_basic(2, 3)).unwrap();
+    body["metadata"].as_object_mut().unwrap().remove("geometry");
+    let status = put_json(&app, "/api/layouts/standalone.json", body).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let saved =
+        lazyqmk::services::LayoutService::load(&temp_dir.path().join("standalone.json")).unwrap();
+    assert_eq!(saved.metadata.geometry, layout.metadata.geometry);
+}
+
+#[tokio::test]
+async fn test_save_layout_records_geometry_snapshot() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state);
+
+    let layout_json: Value = serde_json::to_value(test_layout_basic(2, 3)).unwrap();
+    let status = put_json(&app, "/api/layouts/snapshot.json", layout_json).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let saved =
+        lazyqmk::services::LayoutService::load(&temp_dir.path().join("snapshot.json")).unwrap();
+    let snapshot = saved.metadata.geometry.expect("snapshot recorded");
+    assert_eq!(snapshot.layout_variant, "LAYOUT_test");
+    assert_eq!(snapshot.keys.len(), 6);
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use super::helpers::*;
+
+#[tokio::test]
+async fn test_live_preview_streams_connection_state() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/live-preview/events")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+    // Whether or not a keyboard is attached, the listener reports the
+    // connection state first
+    let mut body = response.into_body();
+    let mut received = String::new();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !received.contains("\"type\":") {
+            let frame = body.frame().await.expect("stream ended").unwrap();
+            if let Some(data) = frame.data_ref() {
+                received.push_str(&String::from_utf8_lossy(data));
+            }
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("no hid event; got: {received}"));
+
+    assert!(received.contains("event: hid"), "got: {received}");
+    assert!(
+        received.contains("\"type\":\"unavailable\"")
+            || received.contains("\"type\":\"connected\""),
+        "got: {received}"
+    );
+}
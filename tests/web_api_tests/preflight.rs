@@ -14,6 +14,12 @@ async fn test_preflight_first_run() {
     assert_eq!(json["has_layouts"], false);
     assert_eq!(json["first_run"], true);
     assert!(json["qmk_firmware_path"].is_null());
+
+    // The environment check starts with the missing checkout
+    let checkout = &json["environment"]["checks"][0];
+    assert_eq!(checkout["name"], "QMK checkout");
+    assert_eq!(checkout["level"], "error");
+    assert!(checkout["fix"].is_string());
 }
 
 #[tokio::test]
@@ -70,3 +76,59 @@ async fn test_preflight_returning_user() {
     assert_eq!(json["has_layouts"], true);
     assert_eq!(json["first_run"], false);
 }
+
+#[tokio::test]
+async fn test_preflight_checks_qmk_checkout() {
+    let (state, _temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/preflight").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let checks = json["environment"]["checks"].as_array().unwrap();
+    assert_eq!(checks[0]["level"], "ok");
+    // The test checkout is not a git clone
+    let git = checks.iter().find(|check| check["name"] == "Git").unwrap();
+    assert_eq!(git["level"], "warning");
+    assert!(checks.iter().any(|check| check["name"] == "QMK CLI"));
+}
+
+#[tokio::test]
+async fn test_preflight_setup_errors() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    // Nothing to set up without a QMK path
+    let (status, _) = post_json(
+        &app,
+        "/api/preflight/setup",
+        json!({ "step": "submodules" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (state, temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/preflight/setup",
+        json!({ "step": "submodules" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(json["details"]
+        .as_str()
+        .unwrap()
+        .contains("not a git checkout"));
+
+    // Cloning refuses directories with files in them
+    let (status, json) = post_json(
+        &app,
+        "/api/preflight/setup",
+        json!({ "step": "clone", "qmk_path": temp_dir.path() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(json["details"].as_str().unwrap().contains("not empty"));
+}
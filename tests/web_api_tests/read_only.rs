@@ -0,0 +1,99 @@
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+
+/// Creates a test AppState in read-only mode with one layout in the workspace.
+fn create_read_only_state() -> (AppState, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let config = Config {
+        paths: PathConfig {
+            qmk_firmware: None,
+            backup_dir: Some(temp_dir.path().join("backups")),
+            keyboard_roots: Vec::new(),
+            qmk_trees: Vec::new(),
+        },
+        build: BuildConfig {
+            output_dir: temp_dir.path().to_path_buf(),
+            userspace: None,
+        },
+        ui: UiConfig::default(),
+        web: WebConfig {
+            read_only: true,
+            ..WebConfig::default()
+        },
+        git: GitConfig::default(),
+        hooks: HooksConfig::default(),
+        lints: LintConfig::default(),
+        safe_mode: false,
+    };
+
+    write_layout_file(
+        &test_layout_basic(2, 3),
+        &temp_dir.path().join("shared.json"),
+    )
+    .expect("Failed to write layout");
+
+    let state =
+        AppState::new(config, temp_dir.path().to_path_buf()).expect("Failed to create app state");
+
+    (state, temp_dir)
+}
+
+#[tokio::test]
+async fn test_read_only_allows_reads() {
+    let (state, _temp_dir) = create_read_only_state();
+    assert!(state.is_read_only());
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/shared.json").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["metadata"]["name"], "Test Layout");
+
+    let (status, json) = get_json(&app, "/api/config").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["read_only"], true);
+}
+
+#[tokio::test]
+async fn test_read_only_rejects_mutations() {
+    let (state, temp_dir) = create_read_only_state();
+    let app = create_router(state);
+    let before = fs::read_to_string(temp_dir.path().join("shared.json")).unwrap();
+
+    let (status, json) = post_json(
+        &app,
+        "/api/layouts/shared.json/layers/0/keys/0/0",
+        json!({ "keycode": "KC_Q" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(json["error"], "Server is running in read-only mode");
+
+    let (status, _) = post_json(&app, "/api/layouts/shared.json/generate", json!({})).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/build/start",
+        json!({ "layout_filename": "shared.json" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let status = put_json(&app, "/api/config", json!({ "qmk_firmware_path": "/tmp" })).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let after = fs::read_to_string(temp_dir.path().join("shared.json")).unwrap();
+    assert_eq!(before, after);
+}
+
+#[tokio::test]
+async fn test_default_mode_is_writable() {
+    let (state, _temp_dir) = create_test_state();
+    assert!(!state.is_read_only());
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/config").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["read_only"], false);
+}
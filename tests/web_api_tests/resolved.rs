@@ -0,0 +1,45 @@
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+
+#[tokio::test]
+async fn test_resolved_layer_fills_transparent_keys_from_lower_layers() {
+    let (state, temp_dir) = create_test_state();
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[1].keys[0].keycode = "KC_TRNS".to_string();
+    write_layout_file(&layout, &temp_dir.path().join("stack.json"))
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/stack/resolved/1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["filename"], "stack.json");
+    assert_eq!(json["layer"], 1);
+    assert_eq!(json["name"], "Function");
+
+    let keys = json["keys"].as_array().unwrap();
+    assert_eq!(keys.len(), 6);
+    assert_eq!(keys[0]["keycode"], "KC_TRNS");
+    assert_eq!(
+        keys[0]["effective_keycode"],
+        layout.layers[0].keys[0].keycode.as_str()
+    );
+    assert_eq!(keys[0]["from_layer"], 0);
+    assert_eq!(keys[1]["keycode"], keys[1]["effective_keycode"]);
+    assert_eq!(keys[1]["from_layer"], 1);
+}
+
+#[tokio::test]
+async fn test_resolved_layer_unknown_layer_or_layout_returns_404() {
+    let (state, temp_dir) = create_test_state();
+    write_layout_file(
+        &test_layout_basic(2, 3),
+        &temp_dir.path().join("stack.json"),
+    )
+    .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, _) = get_json(&app, "/api/layouts/stack.json/resolved/5").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get_json(&app, "/api/layouts/missing.json/resolved/0").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
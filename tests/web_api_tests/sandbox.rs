@@ -0,0 +1,183 @@
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+
+/// Creates a test AppState in sandbox mode seeded with one workspace layout.
+fn create_sandbox_state() -> (AppState, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let config = Config {
+        paths: PathConfig {
+            qmk_firmware: None,
+            backup_dir: Some(temp_dir.path().join("backups")),
+            keyboard_roots: Vec::new(),
+            qmk_trees: Vec::new(),
+        },
+        build: BuildConfig {
+            output_dir: temp_dir.path().to_path_buf(),
+            userspace: None,
+        },
+        ui: UiConfig::default(),
+        web: WebConfig {
+            sandbox: true,
+            ..WebConfig::default()
+        },
+        git: GitConfig::default(),
+        hooks: HooksConfig::default(),
+        lints: LintConfig::default(),
+        safe_mode: false,
+    };
+
+    write_layout_file(&test_layout_basic(2, 3), &temp_dir.path().join("seed.json"))
+        .expect("Failed to write layout");
+
+    let state =
+        AppState::new(config, temp_dir.path().to_path_buf()).expect("Failed to create app state");
+
+    (state, temp_dir)
+}
+
+/// Lists the names of the files in `dir`.
+fn file_names(dir: &std::path::Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn test_sandbox_keeps_saves_in_memory() {
+    let (state, temp_dir) = create_sandbox_state();
+    assert!(state.is_sandboxed());
+    assert_ne!(state.workspace_root(), temp_dir.path());
+    let seed_before = fs::read_to_string(temp_dir.path().join("seed.json")).unwrap();
+    let app = create_router(state);
+
+    // Seeded from the workspace
+    let (status, json) = get_json(&app, "/api/layouts").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["layouts"][0]["filename"], "seed.json");
+
+    let mut layout = test_layout_basic(2, 3);
+    layout.metadata.name = "Playground".to_string();
+    let status = put_json(
+        &app,
+        "/api/layouts/new.json",
+        serde_json::to_value(&layout).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let status = put_json(
+        &app,
+        "/api/layouts/seed.json",
+        serde_json::to_value(&layout).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, json) = get_json(&app, "/api/layouts/seed.json").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["metadata"]["name"], "Playground");
+    let (_, json) = get_json(&app, "/api/layouts").await;
+    assert_eq!(json["layouts"].as_array().unwrap().len(), 2);
+
+    // The workspace is untouched
+    assert_eq!(file_names(temp_dir.path()), ["seed.json"]);
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("seed.json")).unwrap(),
+        seed_before
+    );
+}
+
+#[tokio::test]
+async fn test_sandbox_reports_mode_and_rejects_builds() {
+    let (state, _temp_dir) = create_sandbox_state();
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/config").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["sandbox"], true);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/build/start",
+        json!({ "layout_filename": "seed.json" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(json["error"].as_str().unwrap().contains("sandbox"));
+
+    let (status, json) = post_json(&app, "/api/layouts/seed/check", json!({})).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(json["error"].as_str().unwrap().contains("sandbox"));
+
+    let (status, json) = post_json(
+        &app,
+        "/api/preflight/setup",
+        json!({ "step": "submodules" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(json["error"].as_str().unwrap().contains("sandbox"));
+}
+
+#[tokio::test]
+async fn test_sandbox_scratch_dir_removed_with_state() {
+    let (state, _temp_dir) = create_sandbox_state();
+    let scratch = state.workspace_root().clone();
+    assert!(scratch.is_dir());
+
+    drop(state);
+    assert!(!scratch.exists());
+}
+
+#[tokio::test]
+async fn test_sandbox_keycode_favorites_crud() {
+    let (state, _temp_dir) = create_sandbox_state();
+    let app = create_router(state);
+
+    for keycode in ["LT()", "KC_ESC", "LT()"] {
+        let (status, _) = post_json(
+            &app,
+            "/api/keycodes/favorites",
+            json!({ "keycode": keycode }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let (_, json) = get_json(&app, "/api/keycodes/favorites").await;
+    assert_eq!(json["favorites"], json!(["LT()", "KC_ESC"]));
+
+    let status = put_json(
+        &app,
+        "/api/keycodes/favorites",
+        json!({ "favorites": ["KC_ESC", "MT()", "LT()"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, json) = delete_json(&app, "/api/keycodes/favorites/MT%28%29").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["favorites"], json!(["KC_ESC", "LT()"]));
+
+    let status = put_json(
+        &app,
+        "/api/keycodes/favorites",
+        json!({ "favorites": ["KC_ESC", "BOGUS"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (_, json) = get_json(&app, "/api/keycodes/favorites").await;
+    assert_eq!(json["favorites"], json!(["KC_ESC", "LT()"]));
+}
+
+#[tokio::test]
+async fn test_default_state_is_not_sandboxed() {
+    let (state, _temp_dir) = create_test_state();
+    assert!(!state.is_sandboxed());
+    let app = create_router(state);
+
+    let (_, json) = get_json(&app, "/api/config").await;
+    assert_eq!(json["sandbox"], false);
+}
@@ -0,0 +1,50 @@
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+
+#[tokio::test]
+async fn test_search_finds_keycodes_and_labels_on_all_layers() {
+    let (state, temp_dir) = create_test_state();
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[1].keys[2].description = Some("Opens the launcher".to_string());
+    write_layout_file(&layout, &temp_dir.path().join("search.json"))
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/search/search?q=kc_f1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["filename"], "search.json");
+    assert_eq!(json["query"], "kc_f1");
+    let matches = json["matches"].as_array().unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0]["layer"], 1);
+    assert_eq!(matches[0]["layer_name"], "Function");
+    assert_eq!(matches[0]["row"], 0);
+    assert_eq!(matches[0]["col"], 1);
+    assert_eq!(matches[0]["keycode"], "KC_F1");
+    assert!(matches[0].get("label").is_none());
+
+    let (_, json) = get_json(&app, "/api/layouts/search.json/search?q=launcher").await;
+    let matches = json["matches"].as_array().unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0]["keycode"], "KC_F2");
+    assert_eq!(matches[0]["description"], "Opens the launcher");
+
+    // Labels of the base layer are the key numbers
+    let (_, json) = get_json(&app, "/api/layouts/search.json/search?q=4").await;
+    let keycodes: Vec<&str> = json["matches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["keycode"].as_str().unwrap())
+        .collect();
+    assert_eq!(keycodes, ["KC_4", "KC_F4"]);
+}
+
+#[tokio::test]
+async fn test_search_unknown_layout_returns_404() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, _) = get_json(&app, "/api/layouts/missing.json/search?q=KC_A").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
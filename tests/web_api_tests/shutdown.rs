@@ -0,0 +1,63 @@
+use super::helpers::*;
+
+/// Creates a test AppState started with `--allow-shutdown`.
+fn create_shutdown_state() -> (AppState, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let config = Config {
+        paths: PathConfig {
+            qmk_firmware: None,
+            backup_dir: Some(temp_dir.path().join("backups")),
+            keyboard_roots: Vec::new(),
+            qmk_trees: Vec::new(),
+        },
+        build: BuildConfig {
+            output_dir: temp_dir.path().to_path_buf(),
+            userspace: None,
+        },
+        ui: UiConfig::default(),
+        web: WebConfig {
+            allow_shutdown: true,
+            ..WebConfig::default()
+        },
+        git: GitConfig::default(),
+        hooks: HooksConfig::default(),
+        lints: LintConfig::default(),
+        safe_mode: false,
+    };
+
+    let state =
+        AppState::new(config, temp_dir.path().to_path_buf()).expect("Failed to create app state");
+
+    (state, temp_dir)
+}
+
+#[tokio::test]
+async fn test_shutdown_disabled_by_default() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state.clone());
+
+    let (status, json) = get_json(&app, "/api/config").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["shutdown_enabled"], false);
+
+    let (status, json) = post_json(&app, "/api/shutdown", json!({})).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(json["error"].as_str().unwrap().contains("disabled"));
+    assert!(!state.is_shutting_down());
+}
+
+#[tokio::test]
+async fn test_shutdown_accepted_when_enabled() {
+    let (state, _temp_dir) = create_shutdown_state();
+    let app = create_router(state.clone());
+
+    let (status, json) = get_json(&app, "/api/config").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["shutdown_enabled"], true);
+
+    let (status, json) = post_json(&app, "/api/shutdown", json!({})).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(json["cancelled_jobs"], 0);
+    assert!(state.is_shutting_down());
+}
@@ -0,0 +1,81 @@
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+use lazyqmk::models::Position;
+use lazyqmk::services::usage::UsageLog;
+
+#[tokio::test]
+async fn test_stats_reports_layer_counts() {
+    let (state, temp_dir) = create_test_state();
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[0].keys[1].keycode = "KC_0".to_string();
+    layout.layers[0].keys[2].keycode = "KC_NO".to_string();
+    write_layout_file(&layout, &temp_dir.path().join("stats.json"))
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/stats/stats").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["filename"], "stats.json");
+    assert!(json["heatmap"].is_null());
+
+    let base = &json["layers"][0];
+    assert_eq!(base["name"], "Base");
+    assert_eq!(base["keys"], 6);
+    assert_eq!(base["assigned"], 5);
+    assert_eq!(base["unassigned"], 1);
+    assert_eq!(base["duplicates"][0]["keycode"], "KC_0");
+    assert_eq!(
+        base["duplicates"][0]["positions"].as_array().unwrap().len(),
+        2
+    );
+    // No QMK path configured, so no hands or home row
+    assert!(base["home_row"].is_null());
+
+    let function = &json["layers"][1];
+    assert_eq!(function["transparent"], 1);
+}
+
+#[tokio::test]
+async fn test_stats_heatmap_from_corpus() {
+    let (state, temp_dir) = create_test_state();
+    write_layout_file(&test_layout_basic(2, 3), &temp_dir.path().join("heat.json"))
+        .expect("Failed to write layout");
+    fs::write(temp_dir.path().join("corpus.txt"), "110z").unwrap();
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/heat.json/stats?corpus=corpus.txt").await;
+    assert_eq!(status, StatusCode::OK);
+    let heatmap = &json["heatmap"];
+    assert_eq!(heatmap["total"], 4);
+    assert_eq!(heatmap["untyped"], 1);
+    assert_eq!(heatmap["keys"][0]["keycode"], "KC_1");
+    assert_eq!(heatmap["keys"][0]["presses"], 2);
+    assert_eq!(heatmap["keys"][0]["position"]["col"], 1);
+    // No QMK path configured, so no effort score
+    assert!(json["effort"].is_null());
+    assert!(json["usage"].is_null());
+
+    let (status, _) = get_json(&app, "/api/layouts/heat.json/stats?corpus=missing.txt").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get_json(&app, "/api/layouts/heat.json/stats?corpus=..%2Fcorpus.txt").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_stats_include_recorded_usage() {
+    let (state, temp_dir) = create_test_state();
+    write_layout_file(&test_layout_basic(2, 3), &temp_dir.path().join("used.json"))
+        .expect("Failed to write layout");
+    let mut usage = UsageLog::default();
+    usage.record(0, Position::new(1, 2), 7);
+    usage
+        .save(&UsageLog::path_for(&temp_dir.path().join("used.json")))
+        .unwrap();
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/used/stats").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["usage"]["total"], 7);
+    assert_eq!(json["usage"]["keys"][0]["keycode"], "KC_5");
+    assert_eq!(json["usage"]["keys"][0]["presses"], 7);
+}
@@ -0,0 +1,134 @@
+use super::fixtures::{test_layout_with_tap_dances, write_layout_file};
+use super::helpers::*;
+
+#[tokio::test]
+async fn test_list_tap_dances_reports_usage() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("td.json");
+    write_layout_file(&test_layout_with_tap_dances(), &layout_path)
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/td.json/tap-dances").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let tap_dances = json["tap_dances"].as_array().unwrap();
+    assert_eq!(tap_dances.len(), 2);
+    assert_eq!(tap_dances[0]["name"], "esc_caps");
+    assert_eq!(tap_dances[0]["double_tap"], "KC_CAPS");
+    assert_eq!(tap_dances[0]["usage_count"], 1);
+}
+
+#[tokio::test]
+async fn test_create_tap_dance_validates_keycodes() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("td.json");
+    write_layout_file(&test_layout_with_tap_dances(), &layout_path)
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/layouts/td.json/tap-dances",
+        json!({ "name": "tab_esc", "single_tap": "KC_TAB", "hold": "KC_ESC" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["tap_dance"]["name"], "tab_esc");
+    assert_eq!(json["updated_keys"], 0);
+
+    let saved = lazyqmk::services::LayoutService::load(&layout_path).unwrap();
+    let created = saved.get_tap_dance("tab_esc").unwrap();
+    assert_eq!(created.hold.as_deref(), Some("KC_ESC"));
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/td.json/tap-dances",
+        json!({ "name": "bad", "single_tap": "KC_NOT_A_KEY" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/td.json/tap-dances",
+        json!({ "name": "nested", "single_tap": "TD(esc_caps)" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/td.json/tap-dances",
+        json!({ "name": "esc_caps", "single_tap": "KC_A" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_update_tap_dance_renames_key_references() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("td.json");
+    write_layout_file(&test_layout_with_tap_dances(), &layout_path)
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let status = put_json(
+        &app,
+        "/api/layouts/td.json/tap-dances/esc_caps",
+        json!({ "name": "esc_grave", "single_tap": "KC_ESC", "double_tap": "KC_GRV" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let saved = lazyqmk::services::LayoutService::load(&layout_path).unwrap();
+    assert!(saved.get_tap_dance("esc_caps").is_none());
+    assert_eq!(
+        saved
+            .get_tap_dance("esc_grave")
+            .unwrap()
+            .double_tap
+            .as_deref(),
+        Some("KC_GRV")
+    );
+    assert_eq!(saved.layers[0].keys[0].keycode, "TD(esc_grave)");
+
+    let status = put_json(
+        &app,
+        "/api/layouts/td.json/tap-dances/esc_grave",
+        json!({ "name": "shift_ctrl", "single_tap": "KC_ESC" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let status = put_json(
+        &app,
+        "/api/layouts/td.json/tap-dances/missing",
+        json!({ "name": "missing", "single_tap": "KC_ESC" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_delete_tap_dance_clears_key_references() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("td.json");
+    write_layout_file(&test_layout_with_tap_dances(), &layout_path)
+        .expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = delete_json(&app, "/api/layouts/td.json/tap-dances/shift_ctrl").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["updated_keys"], 1);
+    assert!(json.get("tap_dance").is_none());
+
+    let saved = lazyqmk::services::LayoutService::load(&layout_path).unwrap();
+    assert_eq!(saved.tap_dances.len(), 1);
+    assert_eq!(saved.layers[0].keys[1].keycode, "KC_TRNS");
+    assert!(saved.validate_tap_dances().is_ok());
+
+    let (status, _) = delete_json(&app, "/api/layouts/td.json/tap-dances/shift_ctrl").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -200,3 +200,171 @@ async fn test_save_as_template_source_not_found() {
     assert_eq!(status, StatusCode::NOT_FOUND);
     assert!(json["error"].as_str().unwrap().contains("not found"));
 }
+
+/// Writes a 3x2 template (rows 0-2, cols 0-1) for placing on the 2x3
+/// `test_keyboard`, where row 2 is missing and column 2 is free.
+fn write_mismatched_template(template_dir: &std::path::Path, name: &str) -> String {
+    let template = test_template_basic(3, 2, name);
+    let filename = format!("{name}.json");
+    write_layout_file(&template, &template_dir.join(&filename)).expect("Failed to write template");
+    filename
+}
+
+#[tokio::test]
+async fn test_fit_template_proposes_placement() {
+    let (state, _temp_dir) = create_test_state_with_qmk();
+    let (_, _workspace, template_dir) = create_test_state_with_template_dir();
+    let filename =
+        write_mismatched_template(&template_dir, &format!("test_fit_{}", std::process::id()));
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        &format!("/api/templates/{filename}/fit"),
+        json!({"keyboard": "test_keyboard", "layout_variant": "LAYOUT_test"}),
+    )
+    .await;
+
+    cleanup_template(&template_dir, &filename);
+
+    assert_eq!(status, StatusCode::OK);
+    let unplaced = json["unplaced"].as_array().unwrap();
+    assert_eq!(unplaced.len(), 2);
+    assert_eq!(unplaced[0]["position"], json!({"row": 2, "col": 0}));
+    assert!(unplaced[0]["keycode"].is_string());
+    assert_eq!(
+        json["free"],
+        json!([{"row": 0, "col": 2}, {"row": 1, "col": 2}])
+    );
+    assert_eq!(json["remap"][0]["to"], json!({"row": 0, "col": 2}));
+}
+
+#[tokio::test]
+async fn test_apply_template_to_mismatched_keyboard() {
+    let (state, _temp_dir) = create_test_state_with_qmk();
+    let (_, _workspace, template_dir) = create_test_state_with_template_dir();
+    let filename = write_mismatched_template(
+        &template_dir,
+        &format!("test_apply_fit_{}", std::process::id()),
+    );
+    let app = create_router(state);
+    let uri = format!("/api/templates/{filename}/apply");
+
+    // Without a placement the apply is refused
+    let (conflict, _) = post_json(
+        &app,
+        &uri,
+        json!({
+            "target_filename": "fitted_refused",
+            "keyboard": "test_keyboard",
+            "layout_variant": "LAYOUT_test"
+        }),
+    )
+    .await;
+
+    // Keep (2, 0) on the free (1, 2), drop (2, 1)
+    let (status, json) = post_json(
+        &app,
+        &uri,
+        json!({
+            "target_filename": "fitted",
+            "keyboard": "test_keyboard",
+            "layout_variant": "LAYOUT_test",
+            "remap": [{"from": {"row": 2, "col": 0}, "to": {"row": 1, "col": 2}}]
+        }),
+    )
+    .await;
+
+    // A taken or unknown position is rejected
+    let (bad, _) = post_json(
+        &app,
+        &uri,
+        json!({
+            "target_filename": "fitted_bad",
+            "keyboard": "test_keyboard",
+            "layout_variant": "LAYOUT_test",
+            "remap": [{"from": {"row": 2, "col": 0}, "to": {"row": 0, "col": 0}}]
+        }),
+    )
+    .await;
+
+    cleanup_template(&template_dir, &filename);
+
+    assert_eq!(conflict, StatusCode::CONFLICT);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(bad, StatusCode::BAD_REQUEST);
+    assert_eq!(json["metadata"]["is_template"], false);
+    for layer in json["layers"].as_array().unwrap() {
+        let keys = layer["keys"].as_array().unwrap();
+        assert_eq!(keys.len(), 6);
+        assert!(keys.iter().all(|key| key["position"]["row"] != 2));
+        let moved = keys
+            .iter()
+            .find(|key| key["position"] == json!({"row": 1, "col": 2}))
+            .unwrap();
+        assert_ne!(moved["keycode"], "KC_NO");
+        let empty = keys
+            .iter()
+            .find(|key| key["position"] == json!({"row": 0, "col": 2}))
+            .unwrap();
+        assert_eq!(empty["keycode"], "KC_NO");
+    }
+}
+
+#[tokio::test]
+async fn test_apply_template_fills_placeholders() {
+    let (state, _temp_dir, template_dir) = create_test_state_with_template_dir();
+    let name = format!("test_placeholders_{}", std::process::id());
+    let mut template = test_template_basic(2, 3, &name);
+    template.layers[0].keys[0].keycode = "${OS_MOD}(KC_C)".to_string();
+    let mut os_mod = lazyqmk::models::TemplatePlaceholder::new("OS_MOD");
+    os_mod.choices = vec!["LGUI".to_string(), "LCTL".to_string()];
+    template.metadata.placeholders.push(os_mod);
+    let filename = format!("{name}.json");
+    write_layout_file(&template, &template_dir.join(&filename)).expect("Failed to write template");
+    let app = create_router(state);
+    let uri = format!("/api/templates/{filename}/apply");
+
+    let (_, list) = get_json(&app, "/api/templates").await;
+
+    // No value and no default
+    let (missing, missing_json) =
+        post_json(&app, &uri, json!({"target_filename": "os_missing"})).await;
+    // Not one of the choices
+    let (bad, _) = post_json(
+        &app,
+        &uri,
+        json!({"target_filename": "os_bad", "placeholders": {"OS_MOD": "LALT"}}),
+    )
+    .await;
+    let (status, json) = post_json(
+        &app,
+        &uri,
+        json!({"target_filename": "os_windows", "placeholders": {"OS_MOD": "LCTL"}}),
+    )
+    .await;
+
+    cleanup_template(&template_dir, &filename);
+
+    let listed = list["templates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"].as_str() == Some(name.as_str()))
+        .unwrap();
+    assert_eq!(listed["placeholders"][0]["name"], "OS_MOD");
+    assert_eq!(
+        listed["placeholders"][0]["choices"],
+        json!(["LGUI", "LCTL"])
+    );
+
+    assert_eq!(missing, StatusCode::BAD_REQUEST);
+    assert!(missing_json["details"]
+        .as_str()
+        .unwrap()
+        .contains("${OS_MOD}"));
+    assert_eq!(bad, StatusCode::BAD_REQUEST);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["layers"][0]["keys"][0]["keycode"], "LCTL(KC_C)");
+    assert!(json["metadata"].get("placeholders").is_none());
+}
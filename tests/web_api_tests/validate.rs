@@ -0,0 +1,93 @@
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+use lazyqmk::config::LintSeverity;
+
+/// Creates a test AppState whose config sets the given lint severities.
+fn create_state_with_lints(severities: &[(&str, LintSeverity)]) -> (AppState, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let mut lints = LintConfig::default();
+    for (rule, severity) in severities {
+        lints.severities.insert((*rule).to_string(), *severity);
+    }
+    let config = Config {
+        paths: PathConfig {
+            qmk_firmware: None,
+            backup_dir: Some(temp_dir.path().join("backups")),
+            keyboard_roots: Vec::new(),
+            qmk_trees: Vec::new(),
+        },
+        build: BuildConfig {
+            output_dir: temp_dir.path().to_path_buf(),
+            userspace: None,
+        },
+        ui: UiConfig::default(),
+        web: WebConfig::default(),
+        git: GitConfig::default(),
+        hooks: HooksConfig::default(),
+        lints,
+        safe_mode: false,
+    };
+
+    let state =
+        AppState::new(config, temp_dir.path().to_path_buf()).expect("Failed to create app state");
+
+    (state, temp_dir)
+}
+
+#[tokio::test]
+async fn test_validate_reports_lints_as_warnings_by_default() {
+    let (state, temp_dir) = create_test_state();
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[0].keys[0].keycode = "KC_TRNS".to_string();
+    write_layout_file(&layout, &temp_dir.path().join("lint.json")).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/lint/validate").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["valid"], true);
+    assert!(json.get("error").is_none());
+
+    let lints = json["lints"].as_array().unwrap();
+    let rules: Vec<&str> = lints
+        .iter()
+        .map(|lint| lint["rule"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        rules,
+        [
+            "unreachable_layer",
+            "missing_bootloader_key",
+            "transparent_on_base"
+        ]
+    );
+    assert!(lints.iter().all(|lint| lint["severity"] == "warning"));
+    assert_eq!(lints[2]["layer"], 0);
+    assert_eq!(lints[2]["row"], 0);
+    assert_eq!(lints[2]["col"], 0);
+    assert_eq!(json["warnings"].as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_validate_uses_configured_severities() {
+    let (state, temp_dir) = create_state_with_lints(&[
+        ("transparent_on_base", LintSeverity::Error),
+        ("unreachable_layer", LintSeverity::Off),
+        ("missing_bootloader_key", LintSeverity::Off),
+    ]);
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[0].keys[0].keycode = "KC_TRNS".to_string();
+    write_layout_file(&layout, &temp_dir.path().join("lint.json")).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/lint.json/validate").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["valid"], false);
+    assert!(json["error"]
+        .as_str()
+        .unwrap()
+        .contains("on the base layer does nothing"));
+    assert_eq!(json["lints"].as_array().unwrap().len(), 1);
+    assert_eq!(json["lints"][0]["severity"], "error");
+    assert!(json["warnings"].as_array().unwrap().is_empty());
+}
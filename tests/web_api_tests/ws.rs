@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use super::fixtures::{test_layout_basic, write_layout_file};
+use super::helpers::*;
+
+/// Serves `app` on a local port; the server stops with the test runtime.
+async fn serve(app: axum::Router) -> std::net::SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    addr
+}
+
+/// Performs the WebSocket handshake on `/api/ws` and returns the connection.
+async fn connect(addr: std::net::SocketAddr) -> TcpStream {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!(
+        "GET /api/ws HTTP/1.1\r\nHost: {addr}\r\nOrigin: http://{addr}\r\nConnection: Upgrade\r\n\
+         Upgrade: websocket\r\nSec-WebSocket-Version: 13\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n"
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        response.push(stream.read_u8().await.unwrap());
+    }
+    let response = String::from_utf8(response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 101"), "got: {response}");
+    assert!(
+        response.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="),
+        "got: {response}"
+    );
+    stream
+}
+
+/// Reads one unmasked server frame, returning its opcode and payload.
+async fn read_frame(stream: &mut TcpStream) -> (u8, Vec<u8>) {
+    let opcode = stream.read_u8().await.unwrap() & 0x0F;
+    let len = match stream.read_u8().await.unwrap() & 0x7F {
+        126 => u64::from(stream.read_u16().await.unwrap()),
+        127 => stream.read_u64().await.unwrap(),
+        len => u64::from(len),
+    };
+    let mut payload = vec![0; usize::try_from(len).unwrap()];
+    stream.read_exact(&mut payload).await.unwrap();
+    (opcode, payload)
+}
+
+/// Reads text messages until one satisfies `done`, or panics after a timeout.
+async fn read_until(
+    stream: &mut TcpStream,
+    done: impl Fn(&Value) -> bool + Send + Sync,
+) -> Vec<Value> {
+    let mut received = Vec::new();
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let (opcode, payload) = read_frame(stream).await;
+            assert_eq!(opcode, 0x1, "expected a text frame");
+            let message: Value = serde_json::from_slice(&payload).unwrap();
+            let finished = done(&message);
+            received.push(message);
+            if finished {
+                break;
+            }
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("message not received; got: {received:?}"));
+    received
+}
+
+#[tokio::test]
+async fn test_ws_rejects_plain_get() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/ws").await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json["error"].as_str().unwrap().contains("WebSocket"));
+}
+
+#[tokio::test]
+async fn test_ws_rejects_other_origins() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/ws")
+                .header("Host", "localhost:3001")
+                .header("Origin", "https://example.com")
+                .header("Connection", "Upgrade")
+                .header("Upgrade", "websocket")
+                .header("Sec-WebSocket-Version", "13")
+                .header("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ==")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_ws_streams_generate_job_status_and_logs() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+    write_layout_file(
+        &test_layout_basic(2, 3),
+        &temp_dir.path().join("test_layout.json"),
+    )
+    .expect("Failed to write layout");
+    let app = create_router(state);
+    let addr = serve(app.clone()).await;
+    let mut socket = connect(addr).await;
+
+    let (status, json) = post_json(&app, "/api/layouts/test_layout.json/generate", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    let job_id = json["job"]["id"].as_str().unwrap().to_string();
+
+    let messages = read_until(&mut socket, |message| {
+        message["type"] == "generate_status"
+            && matches!(
+                message["job"]["status"].as_str(),
+                Some("completed" | "failed")
+            )
+    })
+    .await;
+
+    let statuses: Vec<&str> = messages
+        .iter()
+        .filter(|message| message["type"] == "generate_status")
+        .map(|message| {
+            assert_eq!(message["job"]["id"], job_id.as_str());
+            message["job"]["status"].as_str().unwrap()
+        })
+        .collect();
+    assert_eq!(statuses[..2], ["pending", "running"]);
+    let logs: Vec<&Value> = messages
+        .iter()
+        .filter(|message| message["type"] == "generate_log")
+        .collect();
+    assert!(!logs.is_empty(), "no log lines in {messages:?}");
+    assert!(logs.iter().all(|log| log["job_id"] == job_id.as_str()));
+    assert!(logs.iter().any(|log| log["entry"]["message"]
+        .as_str()
+        .is_some_and(|message| message.starts_with("Generate started at"))));
+
+    // Ping is answered, close is echoed
+    socket
+        .write_all(&[0x89, 0x80 | 2, 0, 0, 0, 0, b'h', b'i'])
+        .await
+        .unwrap();
+    assert_eq!(read_frame(&mut socket).await, (0xA, b"hi".to_vec()));
+    socket.write_all(&[0x88, 0x80, 0, 0, 0, 0]).await.unwrap();
+    assert_eq!(read_frame(&mut socket).await.0, 0x8);
+}
@@ -4,16 +4,46 @@ import type {
 	Layout,
 	KeycodeListResponse,
 	CategoryListResponse,
+	KeycodeFavoritesResponse,
 	ConfigResponse,
+	ShutdownResponse,
 	ConfigUpdateRequest,
 	SwapKeysRequest,
+	AssignKeyRequest,
+	AuditLogResponse,
+	KeySearchResponse,
+	ResolvedLayerResponse,
+	LayerGraphResponse,
+	LayoutStatsResponse,
+	HistoryResponse,
+	BackupListResponse,
+	BackupRestoreResponse,
+	GitHistoryResponse,
+	LayerFragment,
+	ApplyFragmentRequest,
+	ApplyFragmentResponse,
+	DuplicateLayerResponse,
+	TapDanceRequest,
+	TapDanceListResponse,
+	TapDanceMutationResponse,
+	CreateCategoryRequest,
+	CategoryUpdateRequest,
+	LayoutCategoryListResponse,
+	CategoryMutationResponse,
+	ColorPalette,
+	KeyRenderMetadata,
 	PreflightResponse,
+	QmkEnvReport,
+	QmkSetupStep,
 	GeometryResponse,
 	ApiError,
 	ValidationResponse,
 	InspectResponse,
 	ExportResponse,
+	KeymapDrawerExportResponse,
+	QmkJsonExportResponse,
 	GenerateResponse,
+	KeymapCheck,
 	GenerateJob,
 	GenerateJobStatusResponse,
 	GenerateJobLogsResponse,
@@ -25,7 +55,11 @@ import type {
 	ApplyTemplateRequest,
 	KeyboardListResponse,
 	LayoutVariantsResponse,
+	KeyboardInfoJsonResponse,
 	CreateLayoutRequest,
+	ImportLayoutRequest,
+	ImportLayoutResponse,
+	ImportBundleResponse,
 	SwitchVariantResponse,
 	StartBuildRequest,
 	StartBuildResponse,
@@ -34,15 +68,24 @@ import type {
 	CancelJobResponse,
 	BuildJob,
 	BuildArtifactsResponse,
-	RenderMetadataResponse
+	FlashResponse,
+	RenderMetadataResponse,
+	KeycodeDisplayResponse,
+	LayoutEvent,
+	JobEvent
 } from './types';
 
 interface LazyQmkApiWindow {
 	__LAZYQMK_API_BASE_URL?: string;
 }
 
+/** Request header identifying this browser session to the server */
+const CLIENT_HEADER = 'X-LazyQMK-Client';
+
 export class ApiClient {
 	private baseUrl: string;
+	/** Session id sent with every request; events carry it as `origin` */
+	readonly clientId: string = Math.random().toString(36).slice(2, 12);
 
 	constructor(baseUrl?: string) {
 		// Default to current origin, configurable for testing
@@ -63,6 +106,7 @@ export class ApiClient {
 			...options,
 			headers: {
 				'Content-Type': 'application/json',
+				[CLIENT_HEADER]: this.clientId,
 				...options?.headers
 			}
 		});
@@ -115,6 +159,211 @@ export class ApiClient {
 		});
 	}
 
+	async assignKey(
+		filename: string,
+		layer: number,
+		row: number,
+		col: number,
+		request: AssignKeyRequest
+	): Promise<KeyRenderMetadata> {
+		return this.request<KeyRenderMetadata>(
+			`/api/layouts/${encodeURIComponent(filename)}/layers/${layer}/keys/${row}/${col}`,
+			{
+				method: 'POST',
+				body: JSON.stringify(request)
+			}
+		);
+	}
+
+	async exportLayerFragment(filename: string, layer: number): Promise<LayerFragment> {
+		return this.request<LayerFragment>(
+			`/api/layouts/${encodeURIComponent(filename)}/layers/${layer}/fragment`
+		);
+	}
+
+	async applyLayerFragment(
+		filename: string,
+		layer: number,
+		request: ApplyFragmentRequest
+	): Promise<ApplyFragmentResponse> {
+		return this.request<ApplyFragmentResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/layers/${layer}/apply-fragment`,
+			{
+				method: 'POST',
+				body: JSON.stringify(request)
+			}
+		);
+	}
+
+	async duplicateLayer(
+		filename: string,
+		layer: number,
+		options: { name?: string; mirror?: boolean } = {}
+	): Promise<DuplicateLayerResponse> {
+		const params = new URLSearchParams();
+		if (options.name) params.set('name', options.name);
+		if (options.mirror) params.set('mirror', 'true');
+		const query = params.toString() ? `?${params}` : '';
+		return this.request<DuplicateLayerResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/layers/${layer}/duplicate${query}`,
+			{ method: 'POST' }
+		);
+	}
+
+	async listTapDances(filename: string): Promise<TapDanceListResponse> {
+		return this.request<TapDanceListResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/tap-dances`
+		);
+	}
+
+	async createTapDance(
+		filename: string,
+		request: TapDanceRequest
+	): Promise<TapDanceMutationResponse> {
+		return this.request<TapDanceMutationResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/tap-dances`,
+			{
+				method: 'POST',
+				body: JSON.stringify(request)
+			}
+		);
+	}
+
+	async updateTapDance(
+		filename: string,
+		name: string,
+		request: TapDanceRequest
+	): Promise<TapDanceMutationResponse> {
+		return this.request<TapDanceMutationResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/tap-dances/${encodeURIComponent(name)}`,
+			{
+				method: 'PUT',
+				body: JSON.stringify(request)
+			}
+		);
+	}
+
+	async deleteTapDance(filename: string, name: string): Promise<TapDanceMutationResponse> {
+		return this.request<TapDanceMutationResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/tap-dances/${encodeURIComponent(name)}`,
+			{
+				method: 'DELETE'
+			}
+		);
+	}
+
+	async listLayoutCategories(filename: string): Promise<LayoutCategoryListResponse> {
+		return this.request<LayoutCategoryListResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/categories`
+		);
+	}
+
+	async createCategory(
+		filename: string,
+		request: CreateCategoryRequest
+	): Promise<CategoryMutationResponse> {
+		return this.request<CategoryMutationResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/categories`,
+			{
+				method: 'POST',
+				body: JSON.stringify(request)
+			}
+		);
+	}
+
+	async updateCategory(
+		filename: string,
+		id: string,
+		request: CategoryUpdateRequest
+	): Promise<CategoryMutationResponse> {
+		return this.request<CategoryMutationResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/categories/${encodeURIComponent(id)}`,
+			{
+				method: 'PUT',
+				body: JSON.stringify(request)
+			}
+		);
+	}
+
+	async deleteCategory(filename: string, id: string): Promise<CategoryMutationResponse> {
+		return this.request<CategoryMutationResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/categories/${encodeURIComponent(id)}`,
+			{
+				method: 'DELETE'
+			}
+		);
+	}
+
+	async getPalette(): Promise<ColorPalette> {
+		return this.request<ColorPalette>('/api/palette');
+	}
+
+	async getAuditLog(filename: string, limit?: number): Promise<AuditLogResponse> {
+		const query = limit !== undefined ? `?limit=${limit}` : '';
+		return this.request<AuditLogResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/audit${query}`
+		);
+	}
+
+	async searchKeys(filename: string, query: string): Promise<KeySearchResponse> {
+		return this.request<KeySearchResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/search?q=${encodeURIComponent(query)}`
+		);
+	}
+
+	async getResolvedLayer(filename: string, layer: number): Promise<ResolvedLayerResponse> {
+		return this.request<ResolvedLayerResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/resolved/${layer}`
+		);
+	}
+
+	async getLayerGraph(filename: string): Promise<LayerGraphResponse> {
+		return this.request<LayerGraphResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/layer-graph`
+		);
+	}
+
+	async getLayoutStats(filename: string, corpus?: string): Promise<LayoutStatsResponse> {
+		const query = corpus ? `?corpus=${encodeURIComponent(corpus)}` : '';
+		return this.request<LayoutStatsResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/stats${query}`
+		);
+	}
+
+	async undo(filename: string): Promise<HistoryResponse> {
+		return this.request<HistoryResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/history/undo`,
+			{ method: 'POST' }
+		);
+	}
+
+	async redo(filename: string): Promise<HistoryResponse> {
+		return this.request<HistoryResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/history/redo`,
+			{ method: 'POST' }
+		);
+	}
+
+	async listBackups(filename: string): Promise<BackupListResponse> {
+		return this.request<BackupListResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/backups`
+		);
+	}
+
+	async restoreBackup(filename: string, id: string): Promise<BackupRestoreResponse> {
+		return this.request<BackupRestoreResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/backups/${encodeURIComponent(id)}/restore`,
+			{ method: 'POST' }
+		);
+	}
+
+	async getGitHistory(filename: string, limit?: number): Promise<GitHistoryResponse> {
+		const query = limit !== undefined ? `?limit=${limit}` : '';
+		return this.request<GitHistoryResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/history${query}`
+		);
+	}
+
 	async validateLayout(filename: string): Promise<ValidationResponse> {
 		return this.request<ValidationResponse>(
 			`/api/layouts/${encodeURIComponent(filename)}/validate`
@@ -129,9 +378,42 @@ export class ApiClient {
 		return this.request<ExportResponse>(`/api/layouts/${encodeURIComponent(filename)}/export`);
 	}
 
-	async generateFirmware(filename: string): Promise<GenerateResponse> {
+	async exportKeymapDrawer(filename: string): Promise<KeymapDrawerExportResponse> {
+		return this.request<KeymapDrawerExportResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/export/keymap-drawer`
+		);
+	}
+
+	async exportQmkJson(filename: string): Promise<QmkJsonExportResponse> {
+		return this.request<QmkJsonExportResponse>(
+			`/api/layouts/${encodeURIComponent(filename)}/export/qmk-json`
+		);
+	}
+
+	getBundleDownloadUrl(filename: string): string {
+		return `${this.resolveBaseUrl()}/api/layouts/${encodeURIComponent(filename)}/bundle`;
+	}
+
+	async generateFirmware(
+		filename: string,
+		options?: { qmkLint?: boolean; target?: string; emitters?: string[] }
+	): Promise<GenerateResponse> {
+		const params = new URLSearchParams();
+		if (options?.qmkLint) params.set('qmk_lint', 'true');
+		if (options?.target) params.set('target', options.target);
+		if (options?.emitters?.length) params.set('emitters', options.emitters.join(','));
+		const query = params.size > 0 ? `?${params}` : '';
 		return this.request<GenerateResponse>(
-			`/api/layouts/${encodeURIComponent(filename)}/generate`,
+			`/api/layouts/${encodeURIComponent(filename)}/generate${query}`,
+			{
+				method: 'POST'
+			}
+		);
+	}
+
+	async checkKeymap(filename: string): Promise<KeymapCheck> {
+		return this.request<KeymapCheck>(
+			`/api/layouts/${encodeURIComponent(filename)}/check`,
 			{
 				method: 'POST'
 			}
@@ -158,6 +440,39 @@ export class ApiClient {
 		return this.request<CategoryListResponse>('/api/keycodes/categories');
 	}
 
+	async getKeycodeFavorites(): Promise<KeycodeFavoritesResponse> {
+		return this.request<KeycodeFavoritesResponse>('/api/keycodes/favorites');
+	}
+
+	async addKeycodeFavorite(keycode: string): Promise<KeycodeFavoritesResponse> {
+		return this.request<KeycodeFavoritesResponse>('/api/keycodes/favorites', {
+			method: 'POST',
+			body: JSON.stringify({ keycode })
+		});
+	}
+
+	async setKeycodeFavorites(favorites: string[]): Promise<KeycodeFavoritesResponse> {
+		return this.request<KeycodeFavoritesResponse>('/api/keycodes/favorites', {
+			method: 'PUT',
+			body: JSON.stringify({ favorites })
+		});
+	}
+
+	async removeKeycodeFavorite(keycode: string): Promise<KeycodeFavoritesResponse> {
+		return this.request<KeycodeFavoritesResponse>(
+			`/api/keycodes/favorites/${encodeURIComponent(keycode)}`,
+			{
+				method: 'DELETE'
+			}
+		);
+	}
+
+	async getKeycodeDisplay(code: string, layout?: string): Promise<KeycodeDisplayResponse> {
+		const params = new URLSearchParams({ code });
+		if (layout) params.set('layout', layout);
+		return this.request<KeycodeDisplayResponse>(`/api/keycodes/display?${params.toString()}`);
+	}
+
 	// Config Operations
 	async getConfig(): Promise<ConfigResponse> {
 		return this.request<ConfigResponse>('/api/config');
@@ -170,11 +485,26 @@ export class ApiClient {
 		});
 	}
 
+	// Server lifecycle (desktop wrappers; requires --allow-shutdown)
+	async shutdown(force = false): Promise<ShutdownResponse> {
+		return this.request<ShutdownResponse>(`/api/shutdown${force ? '?force=true' : ''}`, {
+			method: 'POST'
+		});
+	}
+
 	// Preflight check for onboarding
 	async preflight(): Promise<PreflightResponse> {
 		return this.request<PreflightResponse>('/api/preflight');
 	}
 
+	// Run a QMK setup step (can take minutes) and check the environment again
+	async runPreflightSetup(step: QmkSetupStep, qmkPath?: string): Promise<QmkEnvReport> {
+		return this.request<QmkEnvReport>('/api/preflight/setup', {
+			method: 'POST',
+			body: JSON.stringify({ step, qmk_path: qmkPath })
+		});
+	}
+
 	// Effects Operations
 	async listEffects(): Promise<EffectsListResponse> {
 		return this.request<EffectsListResponse>('/api/effects');
@@ -230,6 +560,12 @@ export class ApiClient {
 		);
 	}
 
+	async getKeyboardInfo(keyboard: string): Promise<KeyboardInfoJsonResponse> {
+		return this.request<KeyboardInfoJsonResponse>(
+			`/api/keyboards/${encodeURIComponent(keyboard)}/info`
+		);
+	}
+
 	async createLayout(request: CreateLayoutRequest): Promise<Layout> {
 		return this.request<Layout>('/api/layouts', {
 			method: 'POST',
@@ -237,6 +573,22 @@ export class ApiClient {
 		});
 	}
 
+	async importLayout(request: ImportLayoutRequest): Promise<ImportLayoutResponse> {
+		return this.request<ImportLayoutResponse>('/api/layouts/import', {
+			method: 'POST',
+			body: JSON.stringify(request)
+		});
+	}
+
+	async importBundle(bundle: Blob, filename?: string): Promise<ImportBundleResponse> {
+		const query = filename ? `?filename=${encodeURIComponent(filename)}` : '';
+		return this.request<ImportBundleResponse>(`/api/layouts/import-bundle${query}`, {
+			method: 'POST',
+			headers: { 'Content-Type': 'application/octet-stream' },
+			body: bundle
+		});
+	}
+
 	async switchLayoutVariant(filename: string, layoutVariant: string): Promise<SwitchVariantResponse> {
 		return this.request<SwitchVariantResponse>(
 			`/api/layouts/${encodeURIComponent(filename)}/switch-variant`,
@@ -291,6 +643,17 @@ export class ApiClient {
 		return `${this.resolveBaseUrl()}/api/build/jobs/${encodeURIComponent(jobId)}/artifacts/${encodeURIComponent(artifactId)}/download`;
 	}
 
+	/**
+	 * Flashes a completed build onto a keyboard that is already in bootloader mode.
+	 * @param artifactId Artifact to flash; defaults to the format the detected bootloader accepts
+	 */
+	async flashBuild(jobId: string, artifactId?: string): Promise<FlashResponse> {
+		const query = artifactId ? `?artifact=${encodeURIComponent(artifactId)}` : '';
+		return this.request<FlashResponse>(`/api/build/jobs/${encodeURIComponent(jobId)}/flash${query}`, {
+			method: 'POST'
+		});
+	}
+
 	// Generate Job Operations
 	async listGenerateJobs(): Promise<GenerateJob[]> {
 		return this.request<GenerateJob[]>('/api/generate/jobs');
@@ -321,6 +684,52 @@ export class ApiClient {
 	getGenerateDownloadUrl(jobId: string): string {
 		return `${this.resolveBaseUrl()}/api/generate/jobs/${encodeURIComponent(jobId)}/download`;
 	}
+
+	/**
+	 * Subscribes to live changes made to a layout by other sessions.
+	 * Events caused by this session's own requests are skipped.
+	 * @param filename Layout filename
+	 * @param onEvent Called for each change; a `resync` event means events were missed
+	 * @returns Function that closes the subscription
+	 */
+	subscribeToLayoutEvents(filename: string, onEvent: (event: LayoutEvent) => void): () => void {
+		const source = new EventSource(
+			`${this.resolveBaseUrl()}/api/layouts/${encodeURIComponent(filename)}/events`
+		);
+		source.addEventListener('change', (message) => {
+			const event = JSON.parse((message as MessageEvent).data) as LayoutEvent;
+			if (event.origin !== this.clientId) {
+				onEvent(event);
+			}
+		});
+		source.addEventListener('resync', () => onEvent({ type: 'resync' }));
+		return () => source.close();
+	}
+
+	/**
+	 * Subscribes to build and generate job progress pushed over a WebSocket.
+	 * @param onEvent Called for each status change or log line; a `resync` event means events were missed
+	 * @param onClose Called when the connection fails or is closed by the server
+	 * @returns Function that closes the subscription
+	 */
+	subscribeToJobEvents(onEvent: (event: JobEvent) => void, onClose?: () => void): () => void {
+		const url = new URL(`${this.resolveBaseUrl()}/api/ws`, window.location.href);
+		url.protocol = url.protocol === 'https:' ? 'wss:' : 'ws:';
+		const socket = new WebSocket(url);
+		let closedByClient = false;
+		socket.addEventListener('message', (message) => {
+			onEvent(JSON.parse(message.data as string) as JobEvent);
+		});
+		socket.addEventListener('close', () => {
+			if (!closedByClient) {
+				onClose?.();
+			}
+		});
+		return () => {
+			closedByClient = true;
+			socket.close();
+		};
+	}
 }
 
 // Default instance
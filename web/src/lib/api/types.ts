@@ -20,6 +20,17 @@ export interface Layout {
 	metadata: LayoutMetadata;
 	layers: Layer[];
 	tap_dances?: TapDance[];
+	// Text and key sequence macros (edited in the TUI, preserved on save)
+	macros?: MacroDefinition[];
+	// Keycodes backed by C snippets (edited in the TUI, preserved on save)
+	custom_keycodes?: CustomKeycode[];
+	// Modifier + key overrides (edited in the TUI, preserved on save)
+	key_overrides?: KeyOverride[];
+	// Keycodes Alt Repeat Key sends after specific keys (edited in the TUI, preserved on save)
+	alt_repeat_keys?: AltRepeatKey[];
+	// Named Unicode glyphs and host input modes (edited in the TUI, preserved on save)
+	unicode_map?: UnicodeGlyph[];
+	unicode_input_modes?: UnicodeInputMode[];
 	// RGB settings
 	rgb_enabled?: boolean;
 	rgb_brightness?: number;
@@ -33,10 +44,18 @@ export interface Layout {
 	rgb_overlay_ripple?: RgbOverlayRippleSettings;
 	// PaletteFX settings
 	palette_fx?: PaletteFxSettings;
+	// Conditional RGB behaviors
+	rgb_conditions?: RgbConditionalSettings;
+	// Firmware feature overrides (absent = keyboard default)
+	firmware_features?: FirmwareFeatures;
 	// Tap-hold settings
 	tap_hold_settings?: TapHoldSettings;
 	// Combo settings
 	combo_settings?: ComboSettings;
+	// Count key presses in the firmware (read back with `lazyqmk collect-usage`)
+	usage_counting?: boolean;
+	// Select Word, Sentence Case and Num Word helpers (edited in the TUI, preserved on save)
+	editing_helpers?: EditingHelpers;
 	// Categories
 	categories?: Category[];
 }
@@ -53,9 +72,52 @@ export interface LayoutMetadata {
 	layout_variant?: string;
 	keymap_name?: string;
 	output_format?: string;
+	converter?: string;
 	tags?: string[];
 	is_template?: boolean;
 	version?: string;
+	// Additional keyboards the layout is generated for
+	targets?: KeyboardTarget[];
+	// Values a template asks for when applied
+	placeholders?: TemplatePlaceholder[];
+	// Geometry of the keyboard the layout was last saved on
+	geometry?: GeometrySnapshot;
+	// Configured QMK tree the layout builds against (default tree when unset)
+	qmk_tree?: string;
+}
+
+/** Compact keyboard geometry saved with a layout. */
+export interface GeometrySnapshot {
+	keyboard: string;
+	layout_variant: string;
+	matrix_rows: number;
+	matrix_cols: number;
+	encoder_count?: number;
+	/** Keys in LAYOUT macro order; w/h default to 1, r to 0 */
+	keys: { matrix: [number, number]; led: number; x: number; y: number; w?: number; h?: number; r?: number }[];
+}
+
+export interface Position {
+	row: number;
+	col: number;
+}
+
+export interface PositionRemap {
+	from: Position;
+	// Absent = key is left off the target
+	to?: Position;
+}
+
+export interface KeyboardTarget {
+	name: string;
+	keyboard: string;
+	layout_variant: string;
+	keymap_name?: string;
+	output_format?: string;
+	converter?: string;
+	row_offset?: number;
+	col_offset?: number;
+	remap?: PositionRemap[];
 }
 
 export interface IdleEffect {
@@ -71,6 +133,33 @@ export interface IdleEffectSettings {
 	idle_effect_mode: string;
 }
 
+export interface RgbLayerEffect {
+	layer: number;
+	effect: string;
+}
+
+export interface RgbOffHours {
+	start_minute: number;
+	end_minute: number;
+}
+
+export interface RgbConditionalSettings {
+	dim_after_minutes?: number;
+	dim_brightness_pct?: number;
+	wpm_threshold?: number;
+	wpm_effect?: string;
+	layer_effects?: RgbLayerEffect[];
+	off_hours?: RgbOffHours;
+}
+
+export interface FirmwareFeatures {
+	rgb_matrix?: boolean;
+	audio?: boolean;
+	console?: boolean;
+	mouse_keys?: boolean;
+	unicode?: boolean;
+}
+
 export interface PaletteFxSettings {
 	enabled?: boolean;
 	default_effect?: string;
@@ -104,9 +193,16 @@ export interface TapHoldSettings {
 	tapping_toggle: number;
 	flow_tap_term?: number;
 	chordal_hold: boolean;
+	// Per-key overrides (edited in the TUI key editor, preserved on save)
+	per_key_tapping_terms?: KeyTappingTerm[];
 	preset: string;
 }
 
+export interface KeyTappingTerm {
+	position: Position;
+	tapping_term: number;
+}
+
 export interface Category {
 	id: string;
 	name: string;
@@ -127,10 +223,19 @@ export interface Layer {
 	default_color?: RgbColor;
 	category_id?: string;
 	layer_colors_enabled?: boolean;
+	/** Fixed color for LEDs without a key; follows the layer color when absent */
+	underglow_color?: RgbColor;
+	/** Encoder rotation keycodes keyed by encoder index; unset encoders use defaults */
+	encoder_map?: Record<string, EncoderBinding>;
 	color: string;
 	keys: KeyAssignment[];
 }
 
+export interface EncoderBinding {
+	ccw: string;
+	cw: string;
+}
+
 export interface KeyAssignment {
 	keycode: string;
 	matrix_position: [number, number];
@@ -140,8 +245,53 @@ export interface KeyAssignment {
 	color_override?: RgbColor;
 	category_id?: string;
 	description?: string;
+	/** Locked keys are skipped by bulk edits and pastes */
+	locked?: boolean;
 }
 
+export type MacroStep =
+	| { type: 'text'; value: string }
+	| { type: 'tap'; value: string }
+	| { type: 'delay'; value: number };
+
+export interface MacroDefinition {
+	name: string;
+	steps: MacroStep[];
+}
+
+export interface EditingHelpers {
+	select_word?: boolean;
+	sentence_case?: boolean;
+	num_word?: boolean;
+	num_word_layer: number;
+}
+
+export interface CustomKeycode {
+	name: string;
+	behavior: string;
+	description?: string;
+}
+
+export type OverrideModifier = 'ctrl' | 'shift' | 'alt' | 'gui';
+
+export interface KeyOverride {
+	mods: OverrideModifier[];
+	trigger: string;
+	replacement: string;
+}
+
+export interface AltRepeatKey {
+	keycode: string;
+	alt_keycode: string;
+}
+
+export interface UnicodeGlyph {
+	name: string;
+	codepoint: number;
+}
+
+export type UnicodeInputMode = 'macos' | 'linux' | 'windows' | 'bsd' | 'win_compose' | 'emacs';
+
 export interface TapDance {
 	id?: string;
 	name: string;
@@ -167,9 +317,16 @@ export interface ComboDefinition {
 	hold_duration_ms: number;
 }
 
+/** Combo that sends a keycode when all trigger keys are pressed together */
+export interface KeycodeCombo {
+	keys: { row: number; col: number }[];
+	keycode: string;
+}
+
 export interface ComboSettings {
 	enabled: boolean;
 	combos: ComboDefinition[];
+	keycode_combos?: KeycodeCombo[];
 }
 
 export interface KeycodeInfo {
@@ -194,14 +351,43 @@ export interface CategoryListResponse {
 	categories: CategoryInfo[];
 }
 
+/** Keycode picker favorites (user order) and recent picks (newest first) */
+export interface KeycodeFavoritesResponse {
+	favorites: string[];
+	recent: string[];
+}
+
 export interface ConfigResponse {
 	qmk_firmware_path?: string;
 	output_dir: string;
 	workspace_root: string;
+	/** Server rejects all mutating requests with 403 */
+	read_only: boolean;
+	/** `POST /api/shutdown` is accepted (server started with `--allow-shutdown`) */
+	shutdown_enabled: boolean;
+	/** Layouts live in memory and nothing is written outside a temp directory (`--sandbox`) */
+	sandbox: boolean;
+	/** Build/generate jobs are stopped after this many seconds (0 = no limit) */
+	job_timeout_secs: number;
+	/** Number of firmware builds run at the same time */
+	build_workers: number;
+	/** CPUs each build may use (0 = QMK's default) */
+	build_cpus_per_job: number;
+	/** QMK trees a layout can select as `qmk_tree`, "default" first */
+	qmk_trees: string[];
+}
+
+export interface ShutdownResponse {
+	message: string;
+	/** Jobs cancelled because `force` was set */
+	cancelled_jobs: number;
 }
 
 export interface ConfigUpdateRequest {
 	qmk_firmware_path?: string;
+	job_timeout_secs?: number;
+	build_workers?: number;
+	build_cpus_per_job?: number;
 }
 
 export interface SwapKeysRequest {
@@ -213,6 +399,341 @@ export interface SwapKeysRequest {
 	second_position: { row: number; col: number };
 }
 
+/** Single-key assignment (POST /api/layouts/{filename}/layers/{layer}/keys/{row}/{col}) */
+export interface AssignKeyRequest {
+	/** New QMK keycode; omit to keep the current one */
+	keycode?: string;
+	/** New color override; null clears it, omit to keep it */
+	color_override?: RgbColor | null;
+	/** New category id; null clears it, omit to keep it */
+	category_id?: string | null;
+	/** Lock or unlock the key; omit to keep it */
+	locked?: boolean;
+}
+
+/** A recorded layout modification */
+export interface AuditEntry {
+	/** ISO 8601 timestamp */
+	timestamp: string;
+	/** Who made the change (X-LazyQMK-User header, or local user for TUI) */
+	actor: string;
+	/** Frontend that made the change */
+	source: 'web' | 'tui';
+	/** Operation name (e.g. "save", "assign_key") */
+	action: string;
+	/** Human-readable list of changes */
+	changes: string[];
+}
+
+/** Audit log of a layout (GET /api/layouts/{filename}/audit), oldest first */
+export interface AuditLogResponse {
+	filename: string;
+	entries: AuditEntry[];
+}
+
+/** A key matching a search query */
+export interface KeySearchMatch {
+	layer: number;
+	layer_name: string;
+	row: number;
+	col: number;
+	keycode: string;
+	label?: string;
+	description?: string;
+}
+
+/** Keys matching a query (GET /api/layouts/{filename}/search?q=), ordered by layer */
+export interface KeySearchResponse {
+	filename: string;
+	query: string;
+	matches: KeySearchMatch[];
+}
+
+/** A key with transparency resolved through the layers below */
+export interface ResolvedKey {
+	row: number;
+	col: number;
+	keycode: string;
+	/** The key's own keycode, or for KC_TRNS the first keycode below it */
+	effective_keycode: string;
+	from_layer: number;
+}
+
+/** Keys of one layer with transparency resolved (GET /api/layouts/{filename}/resolved/{layer}) */
+export interface ResolvedLayerResponse {
+	filename: string;
+	layer: number;
+	name: string;
+	keys: ResolvedKey[];
+}
+
+/** A layer of the layer graph */
+export interface LayerGraphNode {
+	layer: number;
+	name: string;
+	/** A chain of layer keys leads here from the base layer */
+	reachable: boolean;
+	/** The layer can be left once a latching key (TG, TO, TT, DF) turned it on */
+	has_way_back: boolean;
+}
+
+/** A key that activates another layer */
+export interface LayerGraphEdge {
+	from_layer: number;
+	to_layer: number;
+	row: number;
+	col: number;
+	keycode: string;
+	kind: string;
+}
+
+/** Which keys activate which layers (GET /api/layouts/{filename}/layer-graph) */
+export interface LayerGraphResponse {
+	filename: string;
+	layers: LayerGraphNode[];
+	edges: LayerGraphEdge[];
+	issues: string[];
+}
+
+/** A keycode assigned to more than one key of a layer */
+export interface DuplicateKeycode {
+	keycode: string;
+	positions: Position[];
+}
+
+/** Statistics of one layer */
+export interface LayerStats {
+	layer: number;
+	name: string;
+	keys: number;
+	assigned: number;
+	transparent: number;
+	unassigned: number;
+	duplicates: DuplicateKeycode[];
+	/** Absent when the keyboard geometry names no home row */
+	home_row: { keys: number; assigned: number; mod_taps: number } | null;
+	/** Modifiers per hand; `unplaced` counts keys the geometry has no hand for */
+	modifiers: { left: number; right: number; unplaced: number };
+}
+
+/** Presses of one key while typing the corpus */
+export interface KeyHeat {
+	layer: number;
+	position: Position;
+	keycode: string;
+	presses: number;
+}
+
+/** How often each key is pressed to type the corpus, most pressed first */
+export interface Heatmap {
+	total: number;
+	untyped: number;
+	left: number;
+	right: number;
+	home_row: number;
+	keys: KeyHeat[];
+}
+
+/** Presses of one finger while typing the corpus */
+export interface FingerLoad {
+	hand: 'left' | 'right';
+	finger: 'thumb' | 'index' | 'middle' | 'ring' | 'pinky';
+	presses: number;
+}
+
+/** Finger travel and effort of typing the corpus on the base layer */
+export interface EffortScore {
+	total: number;
+	typed: number;
+	home_keys: number;
+	/** Finger travel from the home keys, in key widths */
+	distance: number;
+	/** Weighted effort; lower is better */
+	effort: number;
+	bigrams: number;
+	same_finger_bigrams: number;
+	hand_alternations: number;
+	fingers: FingerLoad[];
+}
+
+/** Layout statistics (GET /api/layouts/{filename}/stats?corpus=) */
+export interface LayoutStatsResponse {
+	filename: string;
+	layers: LayerStats[];
+	/** Present when a corpus file was given */
+	heatmap: Heatmap | null;
+	/** Present when a corpus file was given and the keyboard geometry is available */
+	effort: EffortScore | null;
+	/** Key presses recorded with `lazyqmk collect-usage`, if any */
+	usage: Heatmap | null;
+}
+
+/** Result of an undo or redo */
+export interface HistoryResponse {
+	filename: string;
+	/** Action of the change that was undone or redone (e.g. `swap_keys`) */
+	action: string;
+	undo_steps: number;
+	redo_steps: number;
+}
+
+/** An automatic backup of a layout file */
+export interface BackupInfo {
+	/** Identifier used to restore the backup */
+	id: string;
+	/** ISO 8601 timestamp */
+	created_at: string;
+	/** Size in bytes */
+	size: number;
+}
+
+/** Backups of a layout (GET /api/layouts/{filename}/backups), newest first */
+export interface BackupListResponse {
+	filename: string;
+	backups: BackupInfo[];
+}
+
+/** Result of restoring a backup */
+export interface BackupRestoreResponse {
+	filename: string;
+	restored: BackupInfo;
+}
+
+/** A git commit that touched a layout file */
+export interface GitCommit {
+	hash: string;
+	author: string;
+	/** ISO 8601 timestamp */
+	date: string;
+	/** First line of the commit message */
+	summary: string;
+}
+
+/** Git history of a layout (GET /api/layouts/{filename}/history), newest first */
+export interface GitHistoryResponse {
+	filename: string;
+	in_repository: boolean;
+	/** Null outside a git work tree */
+	status: 'untracked' | 'modified' | 'committed' | null;
+	commits: GitCommit[];
+}
+
+/** A layer exported with the categories and tap dances it references */
+export interface LayerFragment {
+	name: string;
+	source_layout: string;
+	/** ISO 8601 timestamp */
+	created: string;
+	layer: Layer;
+	categories: Category[];
+	tap_dances: TapDance[];
+}
+
+/** Apply a fragment inline, or take it from a layer of another workspace layout */
+export interface ApplyFragmentRequest {
+	fragment?: LayerFragment;
+	source_filename?: string;
+	source_layer?: number;
+}
+
+export interface FragmentApplyReport {
+	keys_applied: number;
+	/** Fragment keys without a matching position in the target layer */
+	keys_skipped: number;
+	/** Target keys left unchanged because they are locked */
+	keys_locked: number;
+	categories_added: string[];
+	tap_dances_added: string[];
+	warnings: string[];
+}
+
+export interface ApplyFragmentResponse {
+	filename: string;
+	layer: number;
+	report: FragmentApplyReport;
+}
+
+export interface DuplicateLayerResponse {
+	/** Index of the new layer */
+	layer: number;
+	id: string;
+	name: string;
+	/** Keys changed by mirroring; 0 when not mirrored */
+	mirrored_keys: number;
+}
+
+/** Tap dance body for the tap dance endpoints */
+export interface TapDanceRequest {
+	name: string;
+	single_tap: string;
+	double_tap?: string;
+	hold?: string;
+}
+
+/** Tap dance with the number of keys assigned TD(name) */
+export interface TapDanceUsage extends TapDanceRequest {
+	usage_count: number;
+}
+
+export interface TapDanceListResponse {
+	tap_dances: TapDanceUsage[];
+}
+
+export interface TapDanceMutationResponse {
+	/** The tap dance as saved; omitted after a delete */
+	tap_dance?: TapDanceRequest;
+	/** Number of keys whose TD() keycode was rewritten */
+	updated_keys: number;
+}
+
+/** Body for creating a category; id defaults to the kebab-case name */
+export interface CreateCategoryRequest {
+	id?: string;
+	name: string;
+	color: RgbColor;
+}
+
+/** Partial category update; omitted fields are unchanged */
+export interface CategoryUpdateRequest {
+	name?: string;
+	color?: RgbColor;
+}
+
+/** Category with the number of layers and keys assigned to it */
+export interface LayoutCategoryUsage extends Category {
+	layer_count: number;
+	key_count: number;
+}
+
+export interface LayoutCategoryListResponse {
+	categories: LayoutCategoryUsage[];
+}
+
+export interface CategoryMutationResponse {
+	/** The category as saved; omitted after a delete */
+	category?: Category;
+	/** Number of layer and key assignments cleared by a delete */
+	cleared_references: number;
+}
+
+export interface PaletteShade {
+	level: number;
+	hex: string;
+	r: number;
+	g: number;
+	b: number;
+}
+
+export interface PaletteColor {
+	name: string;
+	shades: PaletteShade[];
+}
+
+/** Curated color palette used by the color picker */
+export interface ColorPalette {
+	colors: PaletteColor[];
+}
+
 export interface PreflightResponse {
 	/** Whether QMK firmware path is configured and valid */
 	qmk_configured: boolean;
@@ -222,11 +743,57 @@ export interface PreflightResponse {
 	first_run: boolean;
 	/** QMK firmware path if configured */
 	qmk_firmware_path?: string;
+	/** Health check of the QMK checkout and build tools */
+	environment: QmkEnvReport;
+}
+
+/** Fix the server can apply itself, mirroring `qmk setup` */
+export type QmkSetupStep = 'clone' | 'submodules';
+
+/** One check of the QMK environment */
+export interface QmkEnvCheck {
+	/** What was checked (e.g. "Submodules") */
+	name: string;
+	level: 'ok' | 'warning' | 'error';
+	/** What was found */
+	message: string;
+	/** How to fix it, for warnings and errors */
+	fix?: string;
+	/** Setup step that fixes it automatically */
+	setup_step?: QmkSetupStep;
+}
+
+export interface QmkEnvReport {
+	checks: QmkEnvCheck[];
 }
 
 export interface ApiError {
 	error: string;
 	details?: string;
+	/** Structured findings when a generate/build job was refused (HTTP 422) */
+	validation?: JobValidation;
+}
+
+/** Validation findings that blocked a generate/build job */
+export interface JobValidation {
+	errors: ValidationIssue[];
+	warnings: string[];
+}
+
+export interface ValidationIssue {
+	kind:
+		| 'invalid_keycode'
+		| 'missing_position'
+		| 'duplicate_position'
+		| 'matrix_out_of_bounds'
+		| 'empty_layer'
+		| 'mismatched_key_count';
+	layer?: number;
+	row?: number;
+	col?: number;
+	position_name?: string;
+	message: string;
+	suggestion?: string;
 }
 
 export interface KeyGeometryInfo {
@@ -253,6 +820,22 @@ export interface GeometryResponse {
 	 * position data, avoiding brittle coordinate inference logic.
 	 */
 	position_to_visual_index?: Record<string, number>;
+	/** Physical RGB LEDs, including underglow (omitted when unknown) */
+	leds?: LedGeometryInfo[];
+	/** Split boards: LEDs mapped to a key on the other half (omitted when none) */
+	split_led_warnings?: string[];
+}
+
+/** Physical RGB LED; x/y is the LED center in key units */
+export interface LedGeometryInfo {
+	led_index: number;
+	x: number;
+	y: number;
+	/** QMK LED flags (2 = underglow, 4 = key light, 8 = indicator) */
+	flags: number;
+	/** Present when the LED sits under a key */
+	matrix_row?: number;
+	matrix_col?: number;
 }
 
 // Validation response
@@ -260,6 +843,18 @@ export interface ValidationResponse {
 	valid: boolean;
 	error?: string;
 	warnings: string[];
+	lints: LintFinding[];
+}
+
+/** A finding of a layout lint; severities are configured under [lints] in config.toml */
+export interface LintFinding {
+	/** Rule ID, e.g. "transparent_on_base" */
+	rule: string;
+	severity: 'warning' | 'error';
+	message: string;
+	layer?: number;
+	row?: number;
+	col?: number;
 }
 
 // Inspect response
@@ -318,6 +913,20 @@ export interface ExportResponse {
 	suggested_filename: string;
 }
 
+// keymap-drawer export response
+export interface KeymapDrawerExportResponse {
+	/** keymap-drawer YAML, render with `keymap draw` */
+	yaml: string;
+	suggested_filename: string;
+}
+
+// QMK keymap.json export response
+export interface QmkJsonExportResponse {
+	/** QMK keymap JSON, build with `qmk compile keymap.json` */
+	keymap_json: string;
+	suggested_filename: string;
+}
+
 // Generate response (initial response from starting a generate job)
 export interface GenerateResponse {
 	status: string;
@@ -325,6 +934,24 @@ export interface GenerateResponse {
 	job: GenerateJob;
 }
 
+/** A compiler error or warning from a keymap check */
+export interface CompilerDiagnostic {
+	file: string;
+	line: number;
+	column: number | null;
+	severity: 'error' | 'warning';
+	message: string;
+}
+
+/** Syntax-only check of the generated keymap (POST /api/layouts/{filename}/check) */
+export interface KeymapCheck {
+	passed: boolean;
+	/** False if only the QMK dry run ran, without compiling keymap.c */
+	syntax_checked: boolean;
+	diagnostics: CompilerDiagnostic[];
+	log: string[];
+}
+
 // Generate Job types
 export type GenerateJobStatus = 'pending' | 'running' | 'completed' | 'failed' | 'cancelled';
 
@@ -341,6 +968,21 @@ export interface GenerateJob {
 	zip_path?: string;
 	download_url?: string;
 	progress: number;
+	/** Findings of the optional `qmk lint` stage */
+	qmk_lint?: QmkLintReport;
+}
+
+export interface QmkLintFinding {
+	level: 'warning' | 'error';
+	message: string;
+}
+
+export interface QmkLintReport {
+	/** True if `qmk lint` succeeded without error findings */
+	passed: boolean;
+	findings: QmkLintFinding[];
+	/** Set when the lint could not be run */
+	error?: string;
 }
 
 export interface GenerateJobStatusResponse {
@@ -377,6 +1019,15 @@ export interface TemplateInfo {
 	tags: string[];
 	created: string;
 	layer_count: number;
+	placeholders: TemplatePlaceholder[];
+}
+
+/** A `${NAME}` value asked for when a template is applied. */
+export interface TemplatePlaceholder {
+	name: string;
+	description?: string;
+	default?: string;
+	choices?: string[];
 }
 
 export interface TemplateListResponse {
@@ -386,20 +1037,43 @@ export interface TemplateListResponse {
 export interface SaveTemplateRequest {
 	name: string;
 	tags?: string[];
+	placeholders?: TemplatePlaceholder[];
 }
 
 export interface ApplyTemplateRequest {
 	target_filename: string;
+	placeholders?: Record<string, string>;
 }
 
 // Keyboard & Setup Wizard types
+export interface UsbId {
+	vid: number;
+	pid: number;
+}
+
 export interface KeyboardInfo {
 	path: string;
 	layout_count: number;
+	/** USB ID declared in the keyboard's info.json, if any */
+	usb?: UsbId;
+}
+
+export interface UsbDevice {
+	id: UsbId;
+	manufacturer: string | null;
+	product: string | null;
+}
+
+/** A plugged-in USB device and the keyboards declaring its ID */
+export interface DetectedKeyboard {
+	device: UsbDevice;
+	keyboards: string[];
 }
 
 export interface KeyboardListResponse {
 	keyboards: KeyboardInfo[];
+	/** Keyboards matching connected USB devices */
+	detected: DetectedKeyboard[];
 }
 
 export interface LayoutVariantInfo {
@@ -412,6 +1086,14 @@ export interface LayoutVariantsResponse {
 	variants: LayoutVariantInfo[];
 }
 
+/** Resolved QMK info.json after merging parent directories */
+export interface KeyboardInfoJsonResponse {
+	keyboard: string;
+	/** Merged files relative to the QMK keyboards directory, parents first */
+	sources: string[];
+	info: Record<string, unknown>;
+}
+
 export interface CreateLayoutRequest {
 	filename: string;
 	name: string;
@@ -421,6 +1103,33 @@ export interface CreateLayoutRequest {
 	author?: string;
 }
 
+export interface ImportLayoutRequest {
+	filename: string;
+	name: string;
+	/** Defaults to the keyboard named in `keymap_json` */
+	keyboard?: string;
+	/** Defaults to the LAYOUT macro used in the keymap */
+	layout_variant?: string;
+	/** Contents of the keymap.c to import */
+	keymap_c?: string;
+	/** Contents of the QMK keymap.json to import, instead of `keymap_c` */
+	keymap_json?: string;
+}
+
+export interface ImportLayoutResponse {
+	layout: Layout;
+	warnings?: string[];
+}
+
+// Share bundle import response
+export interface ImportBundleResponse {
+	/** Filename the layout was saved as */
+	filename: string;
+	layout: Layout;
+	/** Whether the bundled geometry snapshot renders the layout when the keyboard isn't in the QMK tree */
+	geometry: boolean;
+}
+
 export interface SwitchVariantRequest {
 	layout_variant: string;
 }
@@ -441,6 +1150,11 @@ export interface BuildJob {
 	layout_filename: string;
 	keyboard: string;
 	keymap: string;
+	converter?: string;
+	/** Keyboard target of the layout being built, if not its primary keyboard */
+	target?: string;
+	/** Place in the build queue while pending (1 = next to start) */
+	queue_position?: number;
 	created_at: string;
 	started_at?: string;
 	completed_at?: string;
@@ -451,6 +1165,8 @@ export interface BuildJob {
 
 export interface StartBuildRequest {
 	layout_filename: string;
+	/** Build this keyboard target of the layout instead of its primary keyboard */
+	target?: string;
 }
 
 export interface StartBuildResponse {
@@ -488,6 +1204,15 @@ export interface BuildArtifact {
 	download_url: string;
 }
 
+export interface FlashResponse {
+	job_id: string;
+	/** ID of the flashed artifact (its file extension, e.g. "uf2") */
+	artifact_id: string;
+	/** Bootloader that was flashed, e.g. "UF2 drive /media/user/RPI-RP2" */
+	target: string;
+	log: string[];
+}
+
 export interface BuildArtifactsResponse {
 	job_id: string;
 	artifacts: BuildArtifact[];
@@ -528,6 +1253,18 @@ export interface KeyRenderMetadata {
 	details: KeyDetailActionDto[];
 }
 
+/** Display preview for an arbitrary keycode (GET /api/keycodes/display) */
+export interface KeycodeDisplayResponse {
+	/** The keycode as given in the request */
+	code: string;
+	/** Whether the keycode is known to the keycode database */
+	valid: boolean;
+	/** Short labels for in-key display */
+	display: KeyDisplayDto;
+	/** Full action breakdown for Key Details panel */
+	details: KeyDetailActionDto[];
+}
+
 /** Render metadata for a single layer */
 export interface LayerRenderMetadata {
 	/** Layer number */
@@ -536,6 +1273,48 @@ export interface LayerRenderMetadata {
 	name: string;
 	/** Per-key render metadata */
 	keys: KeyRenderMetadata[];
+	/** Encoder rotation actions on this layer, one per encoder */
+	encoders: EncoderRenderMetadata[];
+	/** Combos that fire while this layer is active */
+	combos: ComboRenderMetadata[];
+}
+
+/** One rotation direction of an encoder */
+export interface EncoderActionRenderMetadata {
+	/** Keycode sent */
+	keycode: string;
+	/** Short labels for the encoder ring */
+	display: KeyDisplayDto;
+}
+
+/** Rotation actions of one encoder on a layer */
+export interface EncoderRenderMetadata {
+	/** Encoder index */
+	encoder: number;
+	/** Counter-clockwise action */
+	ccw: EncoderActionRenderMetadata;
+	/** Clockwise action */
+	cw: EncoderActionRenderMetadata;
+	/** True when the layer sets no binding and the firmware default is shown */
+	default: boolean;
+}
+
+/** A combo drawn on a layer */
+export interface ComboRenderMetadata {
+	/** Two-key hold combo or keycode combo */
+	kind: 'hold' | 'keycode';
+	/** Index into the combo list of its kind */
+	index: number;
+	/** Visual positions of the trigger keys */
+	positions: Position[];
+	/** Visual indices of the trigger keys (same order as positions) */
+	visual_indices: number[];
+	/** Keycode sent (keycode combos only) */
+	keycode?: string;
+	/** Short labels for the combo arc */
+	display: KeyDisplayDto;
+	/** How long both keys must be held (hold combos only) */
+	hold_duration_ms?: number;
 }
 
 /** Response for layout render metadata */
@@ -545,3 +1324,27 @@ export interface RenderMetadataResponse {
 	/** Layer-indexed key metadata */
 	layers: LayerRenderMetadata[];
 }
+
+/** Fields shared by change events (GET /api/layouts/{filename}/events) */
+interface LayoutChangeEventBase {
+	/** Server-wide sequence number; later events win */
+	seq: number;
+	/** Client id of the session that made the change */
+	origin?: string | null;
+	/** User who made the change */
+	actor: string;
+}
+
+/** Live change made to a layout by another session */
+export type LayoutEvent =
+	| (LayoutChangeEventBase & { type: 'key_updated'; layer: number; key: KeyAssignment })
+	| (LayoutChangeEventBase & { type: 'layout_replaced' })
+	| { type: 'resync' };
+
+/** Build or generate job progress pushed over GET /api/ws */
+export type JobEvent =
+	| { type: 'build_status'; job: BuildJob }
+	| { type: 'build_log'; job_id: string; entry: LogEntry }
+	| { type: 'generate_status'; job: GenerateJob }
+	| { type: 'generate_log'; job_id: string; entry: LogEntry }
+	| { type: 'resync' };
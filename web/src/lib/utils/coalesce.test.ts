@@ -0,0 +1,35 @@
+import { describe, it, expect } from 'vitest';
+import { coalesce } from './coalesce';
+
+describe('coalesce', () => {
+	it('runs once more for calls made while running', async () => {
+		let runs = 0;
+		let release: () => void = () => {};
+		const task = coalesce(async () => {
+			runs += 1;
+			if (runs === 1) {
+				await new Promise<void>((resolve) => (release = resolve));
+			}
+		});
+
+		const first = task();
+		task();
+		task();
+		release();
+		await first;
+
+		expect(runs).toBe(2);
+	});
+
+	it('runs again after the previous run finished', async () => {
+		let runs = 0;
+		const task = coalesce(async () => {
+			runs += 1;
+		});
+
+		await task();
+		await task();
+
+		expect(runs).toBe(2);
+	});
+});
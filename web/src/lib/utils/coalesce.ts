@@ -0,0 +1,33 @@
+/**
+ * Wraps an async task so calls never overlap: a call made while the task is
+ * running schedules a single rerun once it finishes.
+ *
+ * Used to refresh job state on every pushed job event without issuing
+ * overlapping incremental log fetches.
+ * @param task The task to run
+ * @returns Function that runs the task, or queues one rerun if it is running
+ */
+export function coalesce(task: () => Promise<void>): () => Promise<void> {
+	let running: Promise<void> | null = null;
+	let rerun = false;
+
+	const run = async (): Promise<void> => {
+		if (running) {
+			rerun = true;
+			return running;
+		}
+		running = (async () => {
+			try {
+				do {
+					rerun = false;
+					await task();
+				} while (rerun);
+			} finally {
+				running = null;
+			}
+		})();
+		return running;
+	};
+
+	return run;
+}
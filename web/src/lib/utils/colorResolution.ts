@@ -30,20 +30,33 @@ export function resolveKeyColor(
 		}
 	}
 
-	// 3. Layer category color (only if layer colors enabled)
-	if (layer.layer_colors_enabled !== false && layer.category_id) {
+	// 3-4. Layer category or default color
+	return resolveLayerColor(layer, categories);
+}
+
+/**
+ * Resolves the layer-level color: the layer category color, else the layer
+ * default color. Used for keys without their own color and for LEDs that
+ * have no key (underglow).
+ *
+ * @returns The color as hex string (#RRGGBB), or undefined if layer colors are off
+ */
+export function resolveLayerColor(layer: Layer, categories: Category[]): string | undefined {
+	if (layer.layer_colors_enabled === false) {
+		return undefined;
+	}
+
+	if (layer.category_id) {
 		const layerCategory = categories.find((c) => c.id === layer.category_id);
 		if (layerCategory) {
 			return rgbToHex(layerCategory.color);
 		}
 	}
 
-	// 4. Layer default color (only if layer colors enabled)
-	if (layer.layer_colors_enabled !== false && layer.default_color) {
+	if (layer.default_color) {
 		return rgbToHex(layer.default_color);
 	}
 
-	// No color found
 	return undefined;
 }
 
@@ -6,11 +6,12 @@ import {
 	getKeyId,
 	findKeyByMatrix,
 	findKeyByVisualIndex,
+	transformLeds,
 	KEY_UNIT_SIZE,
 	KEYBOARD_PADDING,
 	KEY_GAP
 } from './geometry';
-import type { KeyGeometryInfo } from '$api/types';
+import type { KeyGeometryInfo, LedGeometryInfo } from '$api/types';
 
 describe('transformGeometry', () => {
 	it('returns empty result for empty keys array', () => {
@@ -264,3 +265,26 @@ describe('findKeyByVisualIndex', () => {
 		expect(key).toBeUndefined();
 	});
 });
+
+describe('transformLeds', () => {
+	const keys: KeyGeometryInfo[] = [
+		{ matrix_row: 0, matrix_col: 0, x: 1, y: 1, width: 1, height: 1, rotation: 0, visual_index: 0 }
+	];
+
+	it('places LEDs without keys in key SVG space', () => {
+		const leds: LedGeometryInfo[] = [
+			{ led_index: 0, x: 2, y: 1.5, flags: 2 },
+			{ led_index: 1, x: 1.5, y: 1.5, flags: 4, matrix_row: 0, matrix_col: 0 }
+		];
+
+		const result = transformLeds(leds, keys);
+
+		expect(result).toEqual([
+			{ ledIndex: 0, cx: KEY_UNIT_SIZE + KEYBOARD_PADDING, cy: KEY_UNIT_SIZE / 2 + KEYBOARD_PADDING }
+		]);
+	});
+
+	it('returns nothing without keys', () => {
+		expect(transformLeds([{ led_index: 0, x: 0, y: 0, flags: 2 }], [])).toHaveLength(0);
+	});
+});
@@ -5,7 +5,7 @@
  * handling unit conversion, padding, and layout bounds calculation.
  */
 
-import type { KeyGeometryInfo } from '$api/types';
+import type { KeyGeometryInfo, LedGeometryInfo } from '$api/types';
 
 /** Default key unit size in SVG pixels */
 export const KEY_UNIT_SIZE = 54;
@@ -152,6 +152,46 @@ export function transformGeometry(
 	};
 }
 
+/** Radius of an underglow/indicator LED marker in SVG pixels */
+export const LED_RADIUS = 5;
+
+/**
+ * An LED without a key, with computed SVG center coordinates.
+ */
+export interface LedSvgData {
+	/** Physical LED index */
+	ledIndex: number;
+	/** SVG x coordinate (center) */
+	cx: number;
+	/** SVG y coordinate (center) */
+	cy: number;
+}
+
+/**
+ * Places LEDs that have no key (underglow, indicators) in the same SVG space
+ * as {@link transformGeometry}. LEDs under keys are skipped; the key itself
+ * shows their color.
+ */
+export function transformLeds(
+	leds: LedGeometryInfo[],
+	keys: KeyGeometryInfo[],
+	unitSize: number = KEY_UNIT_SIZE,
+	padding: number = KEYBOARD_PADDING
+): LedSvgData[] {
+	if (keys.length === 0) {
+		return [];
+	}
+	const minX = Math.min(...keys.map((key) => key.x)) * unitSize;
+	const minY = Math.min(...keys.map((key) => key.y)) * unitSize;
+	return leds
+		.filter((led) => led.matrix_row === undefined)
+		.map((led) => ({
+			ledIndex: led.led_index,
+			cx: led.x * unitSize - minX + padding,
+			cy: led.y * unitSize - minY + padding
+		}));
+}
+
 /**
  * Computes the center point of a key for rotation transforms.
  */